@@ -4,7 +4,7 @@
 
 use makepad_widgets::*;
 
-use super::{ChatApp, ChatHistoryItem, ChatHistoryPanel};
+use super::{AgentTimeline, ChatApp, ChatHistoryItem, ChatHistoryPanel};
 
 live_design! {
     use link::theme::*;
@@ -13,6 +13,7 @@ live_design! {
     use moly_widgets::theme::*;
     use moly_kit::widgets::chat::Chat;
     use moly_kit::widgets::prompt_input::PromptInput;
+    use moly_widgets::dialog::design::*;
 
     // Provider icons - registered so they can be loaded at runtime
     ICON_OPENAI = dep("crate://self/resources/providers/openai.png")
@@ -230,6 +231,172 @@ live_design! {
         }
     }
 
+    // Small outlined button of the header and the agent panel
+    ChatHeaderButton = <Button> {
+        width: Fit, height: 30
+        padding: {left: 12, right: 12}
+        draw_text: {
+            text_style: <FONT_MEDIUM>{ font_size: 11.0 }
+            fn get_color(self) -> vec4 { return #374151; }
+        }
+        draw_bg: {
+            instance hover: 0.0
+            instance pressed: 0.0
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                sdf.box(1.0, 1.0, self.rect_size.x - 2.0, self.rect_size.y - 2.0, 5.0);
+                sdf.fill(mix(#ffffff, #f1f5f9, self.hover));
+                sdf.stroke(#d1d5db, 1.0);
+                return sdf.result;
+            }
+        }
+    }
+
+    // One step of an agent run; clicking it shows or hides the detail
+    AgentStepItem = <View> {
+        width: Fill, height: Fit
+        flow: Down
+        padding: {left: 10, right: 10, top: 6, bottom: 6}
+        spacing: 4
+        cursor: Hand
+
+        title_row = <View> {
+            width: Fill, height: Fit
+            flow: Right
+            spacing: 8
+            align: {y: 0.5}
+
+            marker_label = <Label> {
+                text: "▸"
+                draw_text: { color: (TEXT_MUTED), text_style: <FONT_REGULAR>{ font_size: 10.0 } }
+            }
+            kind_label = <Label> {
+                width: 70
+                draw_text: { color: (ACCENT_INDIGO), text_style: <FONT_MEDIUM>{ font_size: 10.0 } }
+            }
+            title_label = <Label> {
+                width: Fill
+                draw_text: {
+                    color: (TEXT_PRIMARY)
+                    text_style: <FONT_REGULAR>{ font_size: 11.0 }
+                    wrap: Ellipsis
+                }
+            }
+        }
+
+        detail_label = <Label> {
+            width: Fill
+            margin: {left: 18}
+            visible: false
+            draw_text: {
+                color: (TEXT_SECONDARY)
+                text_style: <FONT_REGULAR>{ font_size: 10.5 }
+                wrap: Word
+            }
+        }
+    }
+
+    // Steps of the running or last agent run
+    pub AgentTimeline = {{AgentTimeline}} {
+        width: Fill, height: 220
+        flow: Down
+
+        steps_list = <PortalList> {
+            width: Fill, height: Fill
+            flow: Down
+
+            AgentStepItem = <AgentStepItem> {}
+        }
+    }
+
+    // One source of an answer; hovering shows a preview, clicking opens it
+    SourceRow = <View> {
+        width: Fill, height: Fit
+        flow: Down
+        padding: {left: 8, right: 8, top: 5, bottom: 5}
+        spacing: 4
+        cursor: Hand
+        visible: false
+
+        <View> {
+            width: Fill, height: Fit
+            flow: Right
+            spacing: 8
+            align: {y: 0.5}
+
+            number_label = <Label> {
+                width: 28
+                draw_text: { color: (ACCENT_INDIGO), text_style: <FONT_MEDIUM>{ font_size: 10.5 } }
+            }
+            title_label = <Label> {
+                width: 180
+                draw_text: {
+                    color: (TEXT_PRIMARY)
+                    text_style: <FONT_MEDIUM>{ font_size: 11.0 }
+                    wrap: Ellipsis
+                }
+            }
+            location_label = <Label> {
+                width: Fill
+                draw_text: {
+                    color: (TEXT_MUTED)
+                    text_style: <FONT_REGULAR>{ font_size: 10.0 }
+                    wrap: Ellipsis
+                }
+            }
+        }
+        preview_label = <Label> {
+            width: Fill
+            margin: {left: 36}
+            visible: false
+            draw_text: { color: (TEXT_MUTED), text_style: <FONT_REGULAR>{ font_size: 10.5 }, wrap: Word }
+        }
+    }
+
+    // A model picker line: a group heading, or a model with its state and,
+    // for local models that aren't loaded, a Load button
+    ModelPickerRow = <View> {
+        width: Fill, height: Fit
+        flow: Right
+        padding: {left: 8, right: 8, top: 4, bottom: 4}
+        spacing: 8
+        align: {y: 0.5}
+        cursor: Hand
+        visible: false
+        show_bg: true
+        draw_bg: {
+            instance selected: 0.0
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                sdf.box(0.0, 0.0, self.rect_size.x, self.rect_size.y, 5.0);
+                sdf.fill(mix(#ffffff00, #e0e7ff, self.selected));
+                return sdf.result;
+            }
+        }
+
+        group_label = <Label> {
+            width: Fill
+            visible: false
+            draw_text: { color: (TEXT_MUTED), text_style: <FONT_SEMIBOLD>{ font_size: 10.0 } }
+        }
+        name_label = <Label> {
+            width: Fill
+            margin: {left: 8}
+            draw_text: {
+                color: (TEXT_PRIMARY)
+                text_style: <FONT_MEDIUM>{ font_size: 11.0 }
+                wrap: Ellipsis
+            }
+        }
+        detail_label = <Label> {
+            draw_text: { color: (TEXT_MUTED), text_style: <FONT_REGULAR>{ font_size: 10.0 } }
+        }
+        status_label = <Label> {
+            draw_text: { color: (ACCENT_INDIGO), text_style: <FONT_MEDIUM>{ font_size: 10.0 } }
+        }
+        load_button = <ChatHeaderButton> { text: "Load", visible: false }
+    }
+
     pub ChatApp = {{ChatApp}} {
         width: Fill, height: Fill
         flow: Down
@@ -262,14 +429,487 @@ live_design! {
             padding: 16
             spacing: 4
 
-            title_label = <Label> {
-                text: "Session"
-                draw_text: {
-                    color: #1f2937
-                    text_style: <FONT_SEMIBOLD>{ font_size: 20.0 }
+            <View> {
+                width: Fill, height: Fit
+                flow: Right
+                align: {y: 0.5}
+                spacing: 10
+
+                title_label = <Label> {
+                    text: "Session"
+                    draw_text: {
+                        color: #1f2937
+                        text_style: <FONT_SEMIBOLD>{ font_size: 20.0 }
+                    }
+                }
+                <View> { width: Fill, height: 1 }
+
+                // Tools exposed to the model in this chat
+                tools_summary = <Label> {
+                    draw_text: {
+                        color: (TEXT_MUTED)
+                        text_style: <FONT_REGULAR>{ font_size: 11.0 }
+                    }
+                }
+                tools_button = <Button> {
+                    width: Fit, height: 30
+                    padding: {left: 12, right: 12}
+                    text: "Tools…"
+                    draw_text: {
+                        text_style: <FONT_MEDIUM>{ font_size: 11.0 }
+                        fn get_color(self) -> vec4 { return #374151; }
+                    }
+                    draw_bg: {
+                        instance hover: 0.0
+                        instance pressed: 0.0
+                        fn pixel(self) -> vec4 {
+                            let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                            sdf.box(1.0, 1.0, self.rect_size.x - 2.0, self.rect_size.y - 2.0, 5.0);
+                            sdf.fill(mix(#ffffff, #f1f5f9, self.hover));
+                            sdf.stroke(#d1d5db, 1.0);
+                            return sdf.result;
+                        }
+                    }
+                }
+                model_button = <ChatHeaderButton> {
+                    text: "Model…"
+                }
+                system_prompt_button = <ChatHeaderButton> {
+                    text: "Prompt…"
+                }
+                sampling_button = <ChatHeaderButton> {
+                    text: "Sampling…"
+                }
+                bookmark_button = <ChatHeaderButton> {
+                    text: "Bookmark…"
+                }
+                jump_button = <ChatHeaderButton> {
+                    text: "Jump…"
+                }
+                link_button = <ChatHeaderButton> {
+                    text: "Link…"
+                }
+                versions_button = <ChatHeaderButton> {
+                    text: "Versions…"
+                }
+                sources_button = <ChatHeaderButton> {
+                    text: "Sources…"
+                }
+                agent_button = <ChatHeaderButton> {
+                    text: "Agent…"
+                }
+                read_aloud_button = <ChatHeaderButton> {
+                    text: "Read Aloud…"
+                }
+                translate_button = <ChatHeaderButton> {
+                    text: "Translate…"
+                }
+                notes_button = <ChatHeaderButton> {
+                    text: "Notes"
+                }
+                export_button = <ChatHeaderButton> {
+                    text: "Export…"
+                }
+                share_button = <ChatHeaderButton> {
+                    text: "Share…"
+                }
+                code_button = <ChatHeaderButton> {
+                    text: "Code…"
+                }
+                ui_image_button = <ChatHeaderButton> {
+                    text: "UI Image…"
+                }
+            }
+
+        }
+
+        // Find in this chat (Cmd+F): the query, the match count, and the
+        // current match between the text around it
+        find_panel = <RoundedView> {
+            width: Fill, height: Fit
+            flow: Down
+            margin: {left: 16, right: 16, bottom: 8}
+            padding: 10
+            spacing: 6
+            visible: false
+            show_bg: true
+            draw_bg: { color: (PANEL_BG), border_radius: 8.0, border_size: 1.0, border_color: (BORDER) }
+
+            <View> {
+                width: Fill, height: Fit
+                flow: Right
+                spacing: 8
+                align: {y: 0.5}
+
+                find_input = <TextInput> {
+                    width: 260, height: 30
+                    empty_text: "Find in this chat"
+                    draw_bg: {
+                        fn pixel(self) -> vec4 {
+                            let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                            sdf.box(1.0, 1.0, self.rect_size.x - 2.0, self.rect_size.y - 2.0, 5.0);
+                            sdf.fill(#ffffff);
+                            sdf.stroke(#d1d5db, 1.0);
+                            return sdf.result;
+                        }
+                    }
+                    draw_text: {
+                        color: (TEXT_PRIMARY)
+                        color_empty: (TEXT_MUTED)
+                        text_style: <FONT_REGULAR>{ font_size: 11.0 }
+                    }
+                }
+                find_count = <Label> {
+                    width: Fill
+                    draw_text: {
+                        color: (TEXT_MUTED)
+                        text_style: <FONT_MEDIUM>{ font_size: 11.0 }
+                        wrap: Ellipsis
+                    }
                 }
+                find_prev_button = <ChatHeaderButton> { text: "Previous" }
+                find_next_button = <ChatHeaderButton> { text: "Next" }
+                find_close_button = <ChatHeaderButton> { text: "Close" }
             }
 
+            find_snippet = <View> {
+                width: Fill, height: Fit
+                flow: Right
+                align: {y: 0.5}
+                visible: false
+
+                find_before = <Label> {
+                    draw_text: { color: (TEXT_MUTED), text_style: <FONT_REGULAR>{ font_size: 11.0 } }
+                }
+                <RoundedView> {
+                    width: Fit, height: Fit
+                    padding: {left: 3, right: 3, top: 1, bottom: 1}
+                    show_bg: true
+                    draw_bg: { color: #fef08a, border_radius: 3.0 }
+                    find_match = <Label> {
+                        draw_text: { color: (TEXT_PRIMARY), text_style: <FONT_SEMIBOLD>{ font_size: 11.0 } }
+                    }
+                }
+                find_after = <Label> {
+                    draw_text: { color: (TEXT_MUTED), text_style: <FONT_REGULAR>{ font_size: 11.0 } }
+                }
+            }
+        }
+
+        // Model picker (Cmd+K): local and provider models by where they
+        // run, narrowed by the search
+        model_picker_panel = <RoundedView> {
+            width: Fill, height: Fit
+            flow: Down
+            margin: {left: 16, right: 16, bottom: 8}
+            padding: 10
+            spacing: 2
+            visible: false
+            show_bg: true
+            draw_bg: { color: (PANEL_BG), border_radius: 8.0, border_size: 1.0, border_color: (BORDER) }
+
+            <View> {
+                width: Fill, height: Fit
+                flow: Right
+                spacing: 8
+                margin: {bottom: 4}
+                align: {y: 0.5}
+
+                model_picker_input = <TextInput> {
+                    width: 260, height: 30
+                    empty_text: "Search models"
+                    draw_bg: {
+                        fn pixel(self) -> vec4 {
+                            let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                            sdf.box(1.0, 1.0, self.rect_size.x - 2.0, self.rect_size.y - 2.0, 5.0);
+                            sdf.fill(#ffffff);
+                            sdf.stroke(#d1d5db, 1.0);
+                            return sdf.result;
+                        }
+                    }
+                    draw_text: {
+                        color: (TEXT_PRIMARY)
+                        color_empty: (TEXT_MUTED)
+                        text_style: <FONT_REGULAR>{ font_size: 11.0 }
+                    }
+                }
+                model_picker_count = <Label> {
+                    width: Fill
+                    draw_text: {
+                        color: (TEXT_MUTED)
+                        text_style: <FONT_MEDIUM>{ font_size: 11.0 }
+                        wrap: Ellipsis
+                    }
+                }
+                model_picker_close_button = <ChatHeaderButton> { text: "Close" }
+            }
+
+            picker_row_0 = <ModelPickerRow> {}
+            picker_row_1 = <ModelPickerRow> {}
+            picker_row_2 = <ModelPickerRow> {}
+            picker_row_3 = <ModelPickerRow> {}
+            picker_row_4 = <ModelPickerRow> {}
+            picker_row_5 = <ModelPickerRow> {}
+            picker_row_6 = <ModelPickerRow> {}
+            picker_row_7 = <ModelPickerRow> {}
+            picker_row_8 = <ModelPickerRow> {}
+            picker_row_9 = <ModelPickerRow> {}
+
+            <Label> {
+                margin: {left: 8, top: 4}
+                text: "↑ ↓ to move, Return to pick or load, Esc to close"
+                draw_text: { color: (TEXT_MUTED), text_style: <FONT_REGULAR>{ font_size: 10.0 } }
+            }
+        }
+
+        // Agent run: status, controls and the step timeline
+        agent_panel = <RoundedView> {
+            width: Fill, height: Fit
+            flow: Down
+            margin: {left: 16, right: 16, bottom: 8}
+            padding: 10
+            spacing: 6
+            visible: false
+            show_bg: true
+            draw_bg: { color: (PANEL_BG), border_radius: 8.0, border_size: 1.0, border_color: (BORDER) }
+
+            <View> {
+                width: Fill, height: Fit
+                flow: Right
+                spacing: 8
+                align: {y: 0.5}
+
+                agent_status = <Label> {
+                    width: Fill
+                    draw_text: {
+                        color: (TEXT_PRIMARY)
+                        text_style: <FONT_MEDIUM>{ font_size: 11.5 }
+                        wrap: Ellipsis
+                    }
+                }
+                agent_steps_button = <ChatHeaderButton> { text: "Hide steps" }
+                agent_pause_button = <ChatHeaderButton> { text: "Pause" }
+                agent_stop_button = <ChatHeaderButton> { text: "Stop" }
+                agent_close_button = <ChatHeaderButton> { text: "Close", visible: false }
+            }
+
+            agent_timeline = <AgentTimeline> {}
+        }
+
+        // Reading the conversation aloud: controls, and the sentence being spoken
+        // between the ones around it
+        read_aloud_panel = <RoundedView> {
+            width: Fill, height: Fit
+            flow: Down
+            margin: {left: 16, right: 16, bottom: 8}
+            padding: 10
+            spacing: 6
+            visible: false
+            show_bg: true
+            draw_bg: { color: (PANEL_BG), border_radius: 8.0, border_size: 1.0, border_color: (BORDER) }
+
+            <View> {
+                width: Fill, height: Fit
+                flow: Right
+                spacing: 8
+                align: {y: 0.5}
+
+                read_aloud_status = <Label> {
+                    width: Fill
+                    draw_text: {
+                        color: (TEXT_PRIMARY)
+                        text_style: <FONT_MEDIUM>{ font_size: 11.5 }
+                        wrap: Ellipsis
+                    }
+                }
+                read_aloud_back_button = <ChatHeaderButton> { text: "Back" }
+                read_aloud_pause_button = <ChatHeaderButton> { text: "Pause" }
+                read_aloud_next_button = <ChatHeaderButton> { text: "Next" }
+                read_aloud_speed_button = <ChatHeaderButton> { text: "1×" }
+                read_aloud_stop_button = <ChatHeaderButton> { text: "Stop" }
+            }
+
+            read_aloud_before = <Label> {
+                width: Fill
+                draw_text: { color: (TEXT_MUTED), text_style: <FONT_REGULAR>{ font_size: 11.0 }, wrap: Word }
+            }
+            <RoundedView> {
+                width: Fill, height: Fit
+                padding: {left: 8, right: 8, top: 6, bottom: 6}
+                show_bg: true
+                draw_bg: { color: #eef2ff, border_radius: 5.0 }
+                read_aloud_current = <Label> {
+                    width: Fill
+                    draw_text: { color: (ACCENT_INDIGO), text_style: <FONT_SEMIBOLD>{ font_size: 12.0 }, wrap: Word }
+                }
+            }
+            read_aloud_after = <Label> {
+                width: Fill
+                draw_text: { color: (TEXT_MUTED), text_style: <FONT_REGULAR>{ font_size: 11.0 }, wrap: Word }
+            }
+        }
+
+        // A translation by the loaded model, with where it can go
+        translate_panel = <RoundedView> {
+            width: Fill, height: Fit
+            flow: Down
+            margin: {left: 16, right: 16, bottom: 8}
+            padding: 10
+            spacing: 6
+            visible: false
+            show_bg: true
+            draw_bg: { color: (PANEL_BG), border_radius: 8.0, border_size: 1.0, border_color: (BORDER) }
+
+            <View> {
+                width: Fill, height: Fit
+                flow: Right
+                spacing: 8
+                align: {y: 0.5}
+
+                translate_status = <Label> {
+                    width: Fill
+                    draw_text: {
+                        color: (TEXT_PRIMARY)
+                        text_style: <FONT_MEDIUM>{ font_size: 11.5 }
+                        wrap: Ellipsis
+                    }
+                }
+                translate_copy_button = <ChatHeaderButton> { text: "Copy", visible: false }
+                translate_replace_button = <ChatHeaderButton> { text: "Replace prompt", visible: false }
+                translate_close_button = <ChatHeaderButton> { text: "Close" }
+            }
+
+            translate_result_view = <ScrollYView> {
+                width: Fill, height: 160
+                visible: false
+                translate_result = <Label> {
+                    width: Fill
+                    draw_text: { color: (TEXT_PRIMARY), text_style: <FONT_REGULAR>{ font_size: 12.0 }, wrap: Word }
+                }
+            }
+        }
+
+        // Earlier versions of a regenerated answer, compared with the current one
+        versions_panel = <RoundedView> {
+            width: Fill, height: Fit
+            flow: Down
+            margin: {left: 16, right: 16, bottom: 8}
+            padding: 10
+            spacing: 6
+            visible: false
+            show_bg: true
+            draw_bg: { color: (PANEL_BG), border_radius: 8.0, border_size: 1.0, border_color: (BORDER) }
+
+            <View> {
+                width: Fill, height: Fit
+                flow: Right
+                spacing: 8
+                align: {y: 0.5}
+
+                versions_status = <Label> {
+                    width: Fill
+                    draw_text: {
+                        color: (TEXT_PRIMARY)
+                        text_style: <FONT_MEDIUM>{ font_size: 11.5 }
+                        wrap: Ellipsis
+                    }
+                }
+                versions_older_button = <ChatHeaderButton> { text: "Older" }
+                versions_newer_button = <ChatHeaderButton> { text: "Newer" }
+                versions_layout_button = <ChatHeaderButton> { text: "Side by side" }
+                versions_keep_button = <ChatHeaderButton> { text: "Keep previous" }
+                versions_close_button = <ChatHeaderButton> { text: "Keep current" }
+            }
+
+            versions_inline = <ScrollYView> {
+                width: Fill, height: 220
+                versions_diff_label = <Label> {
+                    width: Fill
+                    draw_text: { color: (TEXT_PRIMARY), text_style: <FONT_REGULAR>{ font_size: 11.0 }, wrap: Word }
+                }
+            }
+
+            versions_side_by_side = <View> {
+                width: Fill, height: 220
+                flow: Right
+                spacing: 12
+                visible: false
+
+                <View> {
+                    width: Fill, height: Fill
+                    flow: Down
+                    spacing: 4
+                    versions_previous_title = <Label> {
+                        draw_text: { color: (TEXT_MUTED), text_style: <FONT_MEDIUM>{ font_size: 10.0 } }
+                    }
+                    <ScrollYView> {
+                        width: Fill, height: Fill
+                        versions_previous_label = <Label> {
+                            width: Fill
+                            draw_text: { color: (TEXT_PRIMARY), text_style: <FONT_REGULAR>{ font_size: 11.0 }, wrap: Word }
+                        }
+                    }
+                }
+                <View> {
+                    width: Fill, height: Fill
+                    flow: Down
+                    spacing: 4
+                    <Label> {
+                        text: "Current"
+                        draw_text: { color: (TEXT_MUTED), text_style: <FONT_MEDIUM>{ font_size: 10.0 } }
+                    }
+                    <ScrollYView> {
+                        width: Fill, height: Fill
+                        versions_current_label = <Label> {
+                            width: Fill
+                            draw_text: { color: (TEXT_PRIMARY), text_style: <FONT_REGULAR>{ font_size: 11.0 }, wrap: Word }
+                        }
+                    }
+                }
+            }
+        }
+
+        // Sources cited by an answer
+        sources_panel = <RoundedView> {
+            width: Fill, height: Fit
+            flow: Down
+            margin: {left: 16, right: 16, bottom: 8}
+            padding: 10
+            spacing: 2
+            visible: false
+            show_bg: true
+            draw_bg: { color: (PANEL_BG), border_radius: 8.0, border_size: 1.0, border_color: (BORDER) }
+
+            <View> {
+                width: Fill, height: Fit
+                flow: Right
+                spacing: 8
+                align: {y: 0.5}
+
+                sources_status = <Label> {
+                    width: Fill
+                    draw_text: {
+                        color: (TEXT_PRIMARY)
+                        text_style: <FONT_MEDIUM>{ font_size: 11.5 }
+                        wrap: Ellipsis
+                    }
+                }
+                sources_close_button = <ChatHeaderButton> { text: "Close" }
+            }
+
+            source_0 = <SourceRow> {}
+            source_1 = <SourceRow> {}
+            source_2 = <SourceRow> {}
+            source_3 = <SourceRow> {}
+            source_4 = <SourceRow> {}
+            source_5 = <SourceRow> {}
+            source_6 = <SourceRow> {}
+            source_7 = <SourceRow> {}
+
+            sources_more = <Label> {
+                margin: {left: 8, top: 4}
+                draw_text: { color: (TEXT_MUTED), text_style: <FONT_REGULAR>{ font_size: 10.0 } }
+            }
         }
 
         // Mode-specific controls bar (VLM image, TTS voice, Image settings, ASR upload)
@@ -309,7 +949,7 @@ live_design! {
                     align: {x: 0.5, y: 0.5}
                     vlm_drop_label = <Label> {
                         text: "Drop image here"
-                        draw_text: { color: #9ca3af, text_style: <FONT_REGULAR>{ font_size: 12.0 } }
+                        draw_text: { color: (TEXT_MUTED), text_style: <FONT_REGULAR>{ font_size: 12.0 } }
                     }
                 }
 
@@ -342,6 +982,8 @@ live_design! {
                         }
                         <Label> { text: "Browse...", draw_text: { color: #ffffff, text_style: <FONT_MEDIUM>{ font_size: 12.0 } } }
                     }
+                    vlm_capture_region_btn = <ChatHeaderButton> { text: "Capture Region" }
+                    vlm_capture_screen_btn = <ChatHeaderButton> { text: "Capture Screen" }
                     vlm_file_label = <Label> {
                         width: Fill, height: Fit
                         text: ""
@@ -376,6 +1018,29 @@ live_design! {
                         <Label> { text: "Clear", draw_text: { color: #dc2626, text_style: <FONT_MEDIUM>{ font_size: 10.0 } } }
                     }
                 }
+
+                // Text read from the image, sent along with the next message
+                vlm_ocr_row = <View> {
+                    width: Fill, height: Fit
+                    flow: Right
+                    spacing: 8
+                    align: {y: 0.5}
+                    visible: false
+
+                    vlm_ocr_btn = <ChatHeaderButton> { text: "Extract Text" }
+                    vlm_ocr_status = <Label> {
+                        width: Fill, height: Fit
+                        text: ""
+                        draw_text: { color: #6b7280, text_style: <FONT_REGULAR>{ font_size: 11.0 }, wrap: Ellipsis }
+                    }
+                    vlm_ocr_copy_btn = <ChatHeaderButton> { text: "Copy", visible: false }
+                    vlm_ocr_insert_btn = <ChatHeaderButton> { text: "Add to Prompt", visible: false }
+                }
+                vlm_ocr_text = <Label> {
+                    width: Fill, height: Fit
+                    visible: false
+                    draw_text: { color: #374151, text_style: <FONT_REGULAR>{ font_size: 11.0 }, wrap: Word }
+                }
             }
 
             // ── TTS voice selector ─────────────────────────────────────
@@ -648,7 +1313,7 @@ live_design! {
                     image_ref_file_label = <Label> {
                         width: Fit, height: Fit
                         text: "No image selected"
-                        draw_text: { color: #9ca3af, text_style: <FONT_REGULAR>{ font_size: 11.0 } }
+                        draw_text: { color: (TEXT_MUTED), text_style: <FONT_REGULAR>{ font_size: 11.0 } }
                     }
                     image_ref_preview = <Image> {
                         width: 48, height: 48
@@ -725,7 +1390,7 @@ live_design! {
                     align: {x: 0.5, y: 0.5}
                     asr_drop_label = <Label> {
                         text: "Drop audio file here"
-                        draw_text: { color: #9ca3af, text_style: <FONT_REGULAR>{ font_size: 12.0 } }
+                        draw_text: { color: (TEXT_MUTED), text_style: <FONT_REGULAR>{ font_size: 12.0 } }
                     }
                 }
 
@@ -788,45 +1453,172 @@ live_design! {
                         <Label> { text: "Clear", draw_text: { color: #dc2626, text_style: <FONT_MEDIUM>{ font_size: 10.0 } } }
                     }
                 }
+
+                // Fixes for this transcription; they start as set in Settings
+                asr_options_row = <View> {
+                    width: Fill, height: Fit
+                    flow: Right
+                    spacing: 6
+                    align: {y: 0.5}
+
+                    asr_punctuate_btn = <View> {
+                        width: Fit, height: 28, padding: {left: 10, right: 10}, cursor: Hand
+                        align: {x: 0.5, y: 0.5}, show_bg: true
+                        draw_bg: { instance selected: 0.0
+                            fn pixel(self) -> vec4 {
+                                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                                sdf.box(0.5, 0.5, self.rect_size.x - 1.0, self.rect_size.y - 1.0, 6.0);
+                                sdf.fill(mix(#f3f4f6, #ecfdf5, self.selected));
+                                sdf.box(0.5, 0.5, self.rect_size.x - 1.0, self.rect_size.y - 1.0, 6.0);
+                                sdf.stroke(mix(#d1d5db, #10b981, self.selected), 1.0);
+                                return sdf.result;
+                            }
+                        }
+                        <Label> { text: "Fix punctuation", draw_text: { color: #374151, text_style: <FONT_MEDIUM>{ font_size: 11.0 } } }
+                    }
+                    asr_vocabulary_btn = <View> {
+                        width: Fit, height: 28, padding: {left: 10, right: 10}, cursor: Hand
+                        align: {x: 0.5, y: 0.5}, show_bg: true
+                        draw_bg: { instance selected: 0.0
+                            fn pixel(self) -> vec4 {
+                                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                                sdf.box(0.5, 0.5, self.rect_size.x - 1.0, self.rect_size.y - 1.0, 6.0);
+                                sdf.fill(mix(#f3f4f6, #ecfdf5, self.selected));
+                                sdf.box(0.5, 0.5, self.rect_size.x - 1.0, self.rect_size.y - 1.0, 6.0);
+                                sdf.stroke(mix(#d1d5db, #10b981, self.selected), 1.0);
+                                return sdf.result;
+                            }
+                        }
+                        <Label> { text: "Use vocabulary", draw_text: { color: #374151, text_style: <FONT_MEDIUM>{ font_size: 11.0 } } }
+                    }
+                }
             }
         }
 
-        // Main content area - full width chat (history moved to shell sidebar)
-        main_content = <View> {
+        // The chat, with the notes panel beside it
+        chat_row = <View> {
             width: Fill, height: Fill
-            flow: Overlay
+            flow: Right
 
-            // Chat widget from moly-kit (always present)
-            chat = <Chat> {
+            // Main content area - full width chat (history moved to shell sidebar)
+            main_content = <View> {
                 width: Fill, height: Fill
+                flow: Overlay
+
+                // Chat widget from moly-kit (always present)
+                chat = <Chat> {
+                    width: Fill, height: Fill
+                }
+
+                // Empty chat welcome overlay (shows greeting when no messages)
+                welcome_overlay = <View> {
+                    width: Fill, height: Fill
+                    flow: Down
+                    align: {x: 0.5, y: 0.35}
+                    spacing: 32
+                    padding: {left: 48, right: 48}
+                    visible: true
+
+                    // Greeting text
+                    greeting_label = <Label> {
+                        width: Fit, height: Fit
+                        text: "What can I help you with?"
+                        draw_text: {
+                            color: #1f2937
+                            text_style: <FONT_SEMIBOLD>{ font_size: 28.0 }
+                        }
+                    }
+
+                    // Prompt input
+                    welcome_prompt = <PromptInput> {
+                        width: Fill, height: Fit
+                    }
+
+                    // Saved session templates, offered while the session is empty
+                    templates_row = <View> {
+                        width: Fit, height: Fit
+                        flow: Right
+                        spacing: 8
+                        margin: {top: -16}
+                        templates_button = <ChatHeaderButton> { text: "Start from a template…" }
+                        remove_template_button = <ChatHeaderButton> { text: "Remove a template…" }
+                    }
+                }
+
+                // Tool picker, toolset naming, agent goal and bookmarks
+                dialog = <MolyDialog> {}
             }
 
-            // Empty chat welcome overlay (shows greeting when no messages)
-            welcome_overlay = <View> {
-                width: Fill, height: Fill
+            // Scratchpad kept with the chat
+            notes_panel = <View> {
+                width: 280, height: Fill
                 flow: Down
-                align: {x: 0.5, y: 0.35}
-                spacing: 32
-                padding: {left: 48, right: 48}
-                visible: true
+                padding: {left: 12, right: 16, bottom: 16}
+                spacing: 8
+                visible: false
 
-                // Greeting text
-                greeting_label = <Label> {
-                    width: Fit, height: Fit
-                    text: "What can I help you with?"
+                <View> {
+                    width: Fill, height: Fit
+                    flow: Right
+                    align: {y: 0.5}
+                    <Label> {
+                        text: "Notes"
+                        draw_text: { color: (TEXT_PRIMARY), text_style: <FONT_SEMIBOLD>{ font_size: 12.0 } }
+                    }
+                    <View> { width: Fill, height: 1 }
+                    notes_close_button = <ChatHeaderButton> { text: "Close" }
+                }
+                notes_input = <TextInput> {
+                    width: Fill, height: Fill
+                    empty_text: "Context, snippets, follow-ups… Saved with this chat, in Markdown."
+                    draw_bg: {
+                        fn pixel(self) -> vec4 {
+                            let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                            sdf.box(1.0, 1.0, self.rect_size.x - 2.0, self.rect_size.y - 2.0, 6.0);
+                            sdf.fill(#ffffff);
+                            sdf.stroke(#d1d5db, 1.0);
+                            return sdf.result;
+                        }
+                    }
                     draw_text: {
-                        color: #1f2937
-                        text_style: <FONT_SEMIBOLD>{ font_size: 28.0 }
+                        color: (TEXT_PRIMARY)
+                        color_empty: (TEXT_MUTED)
+                        text_style: <FONT_REGULAR>{ font_size: 11.0 }
+                        wrap: Word
                     }
                 }
+            }
+        }
 
-                // Prompt input
-                welcome_prompt = <PromptInput> {
-                    width: Fill, height: Fit
+        // Timing, tokens and cost of the latest answer; Details lists them all
+        answer_stats_panel = <View> {
+            width: Fill, height: Fit
+            flow: Down
+            padding: {left: 16, right: 16, bottom: 6}
+            spacing: 4
+            visible: false
+
+            <View> {
+                width: Fill, height: Fit
+                flow: Right
+                spacing: 8
+                align: {y: 0.5}
+
+                answer_stats_label = <Label> {
+                    width: Fill
+                    draw_text: { color: (TEXT_MUTED), text_style: <FONT_REGULAR>{ font_size: 10.0 }, wrap: Ellipsis }
                 }
+                answer_stats_details_button = <ChatHeaderButton> { text: "Details" }
             }
 
+            answer_stats_list = <ScrollYView> {
+                width: Fill, height: 120
+                visible: false
+                answer_stats_list_label = <Label> {
+                    width: Fill
+                    draw_text: { color: (TEXT_MUTED), text_style: <FONT_REGULAR>{ font_size: 10.0 }, wrap: Word }
+                }
+            }
         }
-
     }
 }
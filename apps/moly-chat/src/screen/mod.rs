@@ -12,14 +12,89 @@ use moly_kit::widgets::prompt_input::PromptInputAction;
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex, mpsc};
 
-use moly_data::{ChatId, Store};
+use moly_data::{ChatData, ChatId, ModelLoadPhase, ModelRegistry, MolyError, ProviderType, Store, StoreAction};
+use moly_data::agent::{self, AgentControl, AgentEvent, AgentRequest, AgentStep, StepKind};
+use moly_data::answer_versions::{self, RegenerationWatch};
+use moly_data::artifacts;
+use moly_data::asr_cleanup;
+use moly_data::audio;
+use moly_data::bookmarks;
+use moly_data::chat_defaults::{self, Sampling};
+use moly_data::chat_navigation;
+use moly_data::chat_search::{self, ChatSearch};
+use moly_data::message_stats::{self, AnswerTimer, AnswerTiming, MessageStats};
+use moly_data::citations::{self, Source};
+use moly_data::model_picker::{self, ModelPicker, PickerEntry, PickerModel, PickerRow};
 use moly_data::model_registry::RegistryCategory;
+use moly_data::model_state;
+use moly_data::ocr;
+use moly_data::platform::{self, AudioFormat, FileDialog};
+use moly_data::privacy_filter::{self, PiiMatch};
+use moly_data::prompt_history::PromptRecall;
+use moly_data::read_aloud::{self, ReadAloud};
+use moly_data::session_templates::SessionTemplates;
+use moly_data::system_prompts;
+use moly_data::telemetry;
+use moly_data::chat_tools;
+use moly_data::chat_export;
+use moly_data::chat_share::{self, ShareOptions, SurfacePage};
+use moly_data::code_blocks::{self, CodeBlock};
+use moly_data::code_tool;
+use moly_data::fs_tool;
+use moly_data::held_send::{HeldOutcome, HeldSend};
+use moly_data::tool_permissions;
+use moly_data::translate::{self, Language};
+use moly_data::tts_output;
+use moly_data::tts_voices;
+use moly_data::voice_client;
+use moly_data::voice_presets::{self, OutputFormat, VoicePreset};
+use moly_data::toolsets::{self, BuiltinTool, ToolChoice, ToolSelection};
+use moly_widgets::a11y::{FocusChain, FocusTarget, Role};
+use moly_widgets::{DialogRequest, DialogResult, MolyDialogWidgetRefExt};
+use moly_data::ominix_api_client::{
+    ChatCompletionRequest, ChatMessage, ImageGenerationRequest, OminiXApiClient, SpeechRequest,
+    TranscriptionRequest, VideoGenerationRequest,
+};
 
 static TTS_VOICE_IDS: &[&str] = &[
     "vivian", "serena", "ryan", "aiden", "english_man",
     "uncle_fu", "chinese_woman", "chinese_man", "dialect",
 ];
 
+static IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "webp", "gif", "bmp", "heic"];
+static AUDIO_EXTENSIONS: &[&str] = &["wav", "mp3", "m4a", "flac", "ogg", "aac", "aiff"];
+
+/// Recent messages offered by the Bookmark dialog
+const BOOKMARK_CHOICES: usize = 12;
+
+/// Regenerated answers offered by the Versions dialog
+const VERSION_CHOICES: usize = 12;
+
+/// Recent messages offered as the start or end of a read-aloud range
+const READ_ALOUD_CHOICES: usize = 20;
+
+/// Seconds between checks on the sentence being read aloud
+const READ_ALOUD_POLL: f64 = 0.2;
+
+/// Rows of the sources panel
+const SOURCE_ROWS: usize = 8;
+
+/// Rows of the model picker, group headings included
+const PICKER_ROWS: usize = 10;
+
+/// Seconds after the last keystroke before the prompt draft is saved
+const DRAFT_SAVE_DELAY: f64 = 1.0;
+
+/// An entry of the Tools menu
+#[derive(Clone, Debug)]
+enum ToolsMenuEntry {
+    Choose,
+    UseToolset(String),
+    SaveToolset,
+    DeleteToolset,
+    TouchedFiles,
+}
+
 /// Which mode the chat UI is in, based on the loaded model category.
 #[derive(Clone, Copy, Debug, PartialEq, Default)]
 enum ChatMode {
@@ -32,6 +107,18 @@ enum ChatMode {
     VideoGen,
 }
 
+/// Text sent to the Translate tool, and where a translation can go back to
+struct Translation {
+    /// Message the text came from, or `None` for the prompt
+    message: Option<usize>,
+    text: String,
+    from: Language,
+    to: Language,
+    model_name: String,
+    rx: Option<mpsc::Receiver<Result<String, String>>>,
+    result: Option<Result<String, String>>,
+}
+
 // Actions emitted by ChatHistoryPanel
 #[derive(Clone, Debug, DefaultNone)]
 pub enum ChatHistoryAction {
@@ -275,6 +362,122 @@ impl ChatHistoryPanelRef {
     }
 }
 
+/// Detail shown per agent step at most; tool output can be long
+const MAX_STEP_DETAIL_CHARS: usize = 4000;
+
+/// Timeline of an agent run - handles its own PortalList drawing
+#[derive(Live, LiveHook, Widget)]
+pub struct AgentTimeline {
+    #[deref]
+    view: View,
+
+    #[rust]
+    steps: Vec<AgentStep>,
+
+    /// Whether each step shows its detail
+    #[rust]
+    expanded: Vec<bool>,
+}
+
+impl Widget for AgentTimeline {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        self.view.handle_event(cx, event, scope);
+        self.widget_match_event(cx, event, scope);
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        let steps_list_uid = self.view.portal_list(ids!(steps_list)).widget_uid();
+
+        while let Some(widget) = self.view.draw_walk(cx, scope, walk).step() {
+            if widget.widget_uid() == steps_list_uid {
+                if let Some(mut list) = widget.as_portal_list().borrow_mut() {
+                    list.set_item_range(cx, 0, self.steps.len());
+
+                    while let Some(item_id) = list.next_visible_item(cx) {
+                        let Some(step) = self.steps.get(item_id) else { continue };
+                        let expanded = self.expanded.get(item_id).copied().unwrap_or(false);
+                        let kind = match step.kind {
+                            StepKind::Thought => "Thought",
+                            StepKind::ToolCall => "Tool",
+                            StepKind::ToolResult => "Result",
+                        };
+
+                        let item = list.item(cx, item_id, live_id!(AgentStepItem));
+                        item.label(ids!(title_row.marker_label)).set_text(cx, if expanded { "▾" } else { "▸" });
+                        item.label(ids!(title_row.kind_label)).set_text(cx, &format!("{}. {}", step.number, kind));
+                        item.label(ids!(title_row.title_label)).set_text(cx, &step.title);
+
+                        let detail = item.label(ids!(detail_label));
+                        detail.set_visible(cx, expanded);
+                        if expanded {
+                            let mut text: String = step.detail.chars().take(MAX_STEP_DETAIL_CHARS).collect();
+                            if text.len() < step.detail.len() {
+                                text.push_str("\n…");
+                            }
+                            detail.set_text(cx, &text);
+                        }
+
+                        item.draw_all(cx, scope);
+                    }
+                }
+            }
+        }
+
+        DrawStep::done()
+    }
+}
+
+impl AgentTimeline {
+    pub fn clear(&mut self) {
+        self.steps.clear();
+        self.expanded.clear();
+    }
+
+    pub fn push_step(&mut self, step: AgentStep) {
+        self.steps.push(step);
+        self.expanded.push(false);
+    }
+
+    pub fn steps(&self) -> &[AgentStep] {
+        &self.steps
+    }
+}
+
+impl WidgetMatchEvent for AgentTimeline {
+    fn handle_actions(&mut self, cx: &mut Cx, actions: &Actions, _scope: &mut Scope) {
+        // Clicking a step shows or hides its detail
+        let steps_list = self.view.portal_list(ids!(steps_list));
+        for (item_id, item) in steps_list.items_with_actions(actions) {
+            if let Some(fd) = item.as_view().finger_down(actions) {
+                if fd.tap_count == 1 {
+                    if let Some(expanded) = self.expanded.get_mut(item_id) {
+                        *expanded = !*expanded;
+                        self.view.redraw(cx);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl AgentTimelineRef {
+    pub fn clear(&self) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.clear();
+        }
+    }
+
+    pub fn push_step(&self, step: AgentStep) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.push_step(step);
+        }
+    }
+
+    pub fn steps(&self) -> Vec<AgentStep> {
+        self.borrow().map(|inner| inner.steps().to_vec()).unwrap_or_default()
+    }
+}
+
 #[derive(Live, Widget)]
 pub struct ChatApp {
     #[deref]
@@ -337,6 +540,11 @@ pub struct ChatApp {
     #[rust]
     current_chat_id: Option<ChatId>,
 
+    /// Chat this instance always shows, for a conversation opened in its own
+    /// window; it then ignores the Store's current chat
+    #[rust]
+    pinned_chat_id: Option<ChatId>,
+
     /// Last message count we synced (to detect changes)
     #[rust]
     last_synced_message_count: usize,
@@ -379,6 +587,14 @@ pub struct ChatApp {
     #[rust]
     asr_file_path: String,
 
+    /// ASR: restore punctuation with the loaded LLM; `None` follows Settings
+    #[rust]
+    asr_punctuate: Option<bool>,
+
+    /// ASR: use the custom vocabulary; `None` follows Settings
+    #[rust]
+    asr_vocabulary: Option<bool>,
+
     /// ASR/TTS/Image: receiver for async operation results
     #[rust]
     mode_rx: Option<mpsc::Receiver<Result<String, String>>>,
@@ -391,6 +607,14 @@ pub struct ChatApp {
     #[rust]
     mode_busy: bool,
 
+    /// Messages sent while the model was still loading
+    #[rust]
+    queued_send: Option<HeldSend>,
+
+    /// Prompt to fill in once the requested new chat is created
+    #[rust]
+    pending_prompt: Option<String>,
+
     /// VLM: path to the selected image file
     #[rust]
     vlm_image_path: String,
@@ -399,6 +623,18 @@ pub struct ChatApp {
     #[rust]
     vlm_image_b64: Option<String>,
 
+    /// VLM: text read from the image, added to the next message sent with it
+    #[rust]
+    vlm_image_text: Option<String>,
+
+    /// VLM: receiver for the text extraction
+    #[rust]
+    ocr_rx: Option<mpsc::Receiver<Result<String, String>>>,
+
+    /// VLM: receiver for a screenshot being taken; `None` when cancelled
+    #[rust]
+    capture_rx: Option<mpsc::Receiver<Result<Option<String>, String>>>,
+
     /// Image edit: base64-encoded reference image (set by file picker)
     #[rust]
     image_ref_b64: Option<String>,
@@ -411,6 +647,10 @@ pub struct ChatApp {
     #[rust]
     tts_voice_idx: usize,
 
+    /// TTS: voices the last audio was spoken with, e.g. `vivian + chinese_woman`
+    #[rust]
+    tts_voices_used: String,
+
     /// TTS: path to the last generated audio file
     #[rust]
     tts_audio_path: Option<String>,
@@ -419,7 +659,7 @@ pub struct ChatApp {
     #[rust]
     tts_playing: bool,
 
-    /// TTS: child process handle for afplay (so we can kill it on stop)
+    /// TTS: child process handle of the audio player (so we can kill it on stop)
     #[rust]
     tts_play_process: Option<std::process::Child>,
 
@@ -439,6 +679,214 @@ pub struct ChatApp {
     /// to let the controller process mutations before draw
     #[rust]
     skip_chat_draw_frames: u32,
+
+    /// Tab order over the prompt and the mode-specific inputs
+    #[rust(FocusChain::new(chat_focus_targets()))]
+    focus: FocusChain,
+
+    /// Entries of the open Tools menu
+    #[rust]
+    tools_menu: Vec<ToolsMenuEntry>,
+
+    /// Personal data listed in the open privacy review, in dialog order
+    #[rust]
+    privacy_review: Option<Vec<PiiMatch>>,
+
+    /// Entries of the open tool picker, in dialog order
+    #[rust]
+    tool_choices: Vec<ToolChoice>,
+
+    /// Tool approval requests shown in the dialog, oldest first
+    #[rust]
+    pending_permissions: Vec<u64>,
+
+    /// Agent run in progress: its pause/abort switches and events
+    #[rust]
+    agent_run: Option<(AgentControl, mpsc::Receiver<AgentEvent>)>,
+
+    /// Chat the running or last agent run belongs to
+    #[rust]
+    agent_chat_id: Option<ChatId>,
+
+    /// Whether the agent panel has its step timeline collapsed
+    #[rust]
+    agent_steps_hidden: bool,
+
+    /// Up/Down browsing of the chat's sent prompts
+    #[rust]
+    prompt_recall: PromptRecall,
+
+    /// Saves the prompt draft once typing pauses
+    #[rust]
+    draft_timer: Timer,
+
+    /// The conversation being read aloud, and the chat it belongs to
+    #[rust]
+    read_aloud: Option<(Option<ChatId>, ReadAloud)>,
+
+    /// Moves the read-aloud along while it runs
+    #[rust]
+    read_aloud_timer: Timer,
+
+    /// Message the transcript was last scrolled to while reading aloud
+    #[rust]
+    read_aloud_message: Option<usize>,
+
+    /// Message indices offered by the open Read Aloud dialog, in dialog order
+    #[rust]
+    read_aloud_choices: Vec<usize>,
+
+    /// Whether the find bar is showing
+    #[rust]
+    find_open: bool,
+
+    /// Models offered by the open model picker
+    #[rust]
+    model_picker: Option<ModelPicker>,
+
+    /// What each of the picker's row slots shows
+    #[rust]
+    picker_rows: Vec<PickerRow>,
+
+    /// Matches of the find bar's query in this chat
+    #[rust]
+    chat_search: ChatSearch,
+
+    /// First message of the range being chosen
+    #[rust]
+    read_aloud_from: Option<usize>,
+
+    /// Messages to read while a voice preset is being chosen
+    #[rust]
+    read_aloud_range: Option<(usize, usize)>,
+
+    /// Sources offered by the open Translate dialog; `None` is the prompt
+    #[rust]
+    translate_choices: Vec<Option<usize>>,
+
+    /// The text being translated, before its target language is chosen and
+    /// after
+    #[rust]
+    translation: Option<Translation>,
+
+    /// Whether the notes panel is open
+    #[rust]
+    notes_open: bool,
+
+    /// Saves the chat's notes once typing pauses
+    #[rust]
+    notes_timer: Timer,
+
+    /// Code blocks offered by the open Code dialog, in dialog order
+    #[rust]
+    code_choices: Vec<CodeBlock>,
+
+    /// Code block whose action is being chosen
+    #[rust]
+    code_pending: Option<CodeBlock>,
+
+    /// Output of a code block run with the code tool
+    #[rust]
+    code_run_rx: Option<mpsc::Receiver<Result<String, String>>>,
+
+    /// Generated UI whose image is being saved or copied
+    #[rust]
+    surface_pending: Option<SurfacePage>,
+
+    /// Outcome of rendering a generated UI to an image
+    #[rust]
+    surface_image_rx: Option<mpsc::Receiver<Result<(), String>>>,
+
+    /// Message indices offered by the open Bookmark dialog, in dialog order
+    #[rust]
+    bookmark_choices: Vec<usize>,
+
+    /// Message being bookmarked while its note is asked for
+    #[rust]
+    bookmark_pending: Option<usize>,
+
+    /// First message of each day offered by the open Jump dialog
+    #[rust]
+    jump_choices: Vec<usize>,
+
+    /// Message indices offered by the open Link dialog, in dialog order
+    #[rust]
+    link_choices: Vec<usize>,
+
+    /// Message to scroll to once the chat opened from a bookmark is shown
+    #[rust]
+    scroll_to_message: Option<usize>,
+
+    /// Spots answers replaced by regenerating them
+    #[rust]
+    regeneration_watch: RegenerationWatch,
+
+    /// Times answers for the stats footer
+    #[rust]
+    answer_timer: AnswerTimer,
+
+    /// Whether the stats footer lists every answer
+    #[rust]
+    answer_stats_open: bool,
+
+    /// Answer compared in the versions panel: (chat, message index,
+    /// earlier version shown)
+    #[rust]
+    versions_shown: Option<(ChatId, usize, usize)>,
+
+    /// Whether the versions panel shows both answers side by side
+    /// instead of one inline diff
+    #[rust]
+    versions_side_by_side: bool,
+
+    /// Message indices offered by the open Versions dialog, in dialog order
+    #[rust]
+    version_choices: Vec<usize>,
+
+    /// Sources the running agent looked up, by number
+    #[rust]
+    agent_sources: Vec<Source>,
+
+    /// Sources listed in the sources panel
+    #[rust]
+    sources_shown: Vec<Source>,
+
+    /// Message indices offered by the open Sources dialog, in dialog order
+    #[rust]
+    source_choices: Vec<usize>,
+
+    /// Templates offered while the session is empty; reloaded for each new session
+    #[rust]
+    session_templates: SessionTemplates,
+}
+
+/// Keyboard focus order; whichever prompt is on screen is reachable
+fn chat_focus_targets() -> Vec<FocusTarget> {
+    vec![
+        FocusTarget::new(ids!(main_content.welcome_overlay.welcome_prompt.text_input), Role::TextInput, "Message"),
+        FocusTarget::new(ids!(main_content.welcome_overlay.templates_row.templates_button), Role::Button, "Start from a template"),
+        FocusTarget::new(ids!(main_content.welcome_overlay.templates_row.remove_template_button), Role::Button, "Remove a template"),
+        FocusTarget::new(ids!(main_content.chat.prompt.text_input), Role::TextInput, "Message"),
+        FocusTarget::new(ids!(mode_controls.image_controls.image_neg_row.image_neg_prompt_input), Role::TextInput, "Negative prompt"),
+        FocusTarget::new(ids!(mode_controls.vlm_controls.vlm_file_row.vlm_capture_region_btn), Role::Button, "Capture a region of the screen"),
+        FocusTarget::new(ids!(mode_controls.vlm_controls.vlm_file_row.vlm_capture_screen_btn), Role::Button, "Capture the whole screen"),
+        FocusTarget::new(ids!(notes_panel.notes_input), Role::TextInput, "Notes"),
+        FocusTarget::new(ids!(find_panel.find_input), Role::TextInput, "Find in this chat"),
+        FocusTarget::new(ids!(find_panel.find_prev_button), Role::Button, "Previous match"),
+        FocusTarget::new(ids!(find_panel.find_next_button), Role::Button, "Next match"),
+        FocusTarget::new(ids!(find_panel.find_close_button), Role::Button, "Close find"),
+        FocusTarget::new(ids!(model_picker_panel.model_picker_input), Role::TextInput, "Search models"),
+        FocusTarget::new(ids!(model_picker_panel.model_picker_close_button), Role::Button, "Close the model picker"),
+        FocusTarget::new(ids!(read_aloud_panel.read_aloud_back_button), Role::Button, "Previous sentence"),
+        FocusTarget::new(ids!(read_aloud_panel.read_aloud_pause_button), Role::Button, "Pause or resume reading"),
+        FocusTarget::new(ids!(read_aloud_panel.read_aloud_next_button), Role::Button, "Next sentence"),
+        FocusTarget::new(ids!(read_aloud_panel.read_aloud_speed_button), Role::Button, "Reading speed"),
+        FocusTarget::new(ids!(read_aloud_panel.read_aloud_stop_button), Role::Button, "Stop reading"),
+        FocusTarget::new(ids!(translate_panel.translate_copy_button), Role::Button, "Copy the translation"),
+        FocusTarget::new(ids!(translate_panel.translate_replace_button), Role::Button, "Replace the original with the translation"),
+        FocusTarget::new(ids!(translate_panel.translate_close_button), Role::Button, "Close the translation"),
+        FocusTarget::new(ids!(notes_panel.notes_close_button), Role::Button, "Close notes"),
+    ]
 }
 
 impl LiveHook for ChatApp {
@@ -574,6 +1022,50 @@ impl ChatApp {
         self.needs_new_chat = true;
     }
 
+    /// Like [`Self::request_new_chat`], with `prompt` typed in for the user
+    /// to edit and send
+    pub fn request_new_chat_with_prompt(&mut self, prompt: String) {
+        self.needs_new_chat = true;
+        self.pending_prompt = Some(prompt);
+    }
+
+    /// Forget everything loaded from the previous profile. Called from App
+    /// after it swapped the Store; providers are refetched and the new
+    /// profile's current chat is loaded on the next event.
+    pub fn reset_for_profile(&mut self) {
+        {
+            let mut ctrl = self.chat_controller.lock().unwrap();
+            ctrl.dispatch_mutation(VecMutation::<Bot>::Set(vec![]));
+            ctrl.dispatch_mutation(ChatStateMutation::SetBotId(None));
+        }
+        self.providers_configured = false;
+        self.fetched_provider_ids.clear();
+        self.providers_to_fetch.clear();
+        self.fetch_index = 0;
+        self.fetch_in_progress = false;
+        self.last_bots_count = 0;
+        self.last_saved_bot_id = None;
+        self.restored_saved_model = false;
+        self.reload_chats();
+    }
+
+    /// Drop the open conversation after `store.chats` was reloaded from disk
+    /// (profile switch or backup restore); the current chat is loaded again
+    /// on the next event.
+    pub fn reload_chats(&mut self) {
+        self.chat_controller.lock().unwrap()
+            .dispatch_mutation(VecMutation::<Message>::Set(vec![]));
+        self.needs_new_chat = false;
+        self.pending_prompt = None;
+        self.current_chat_id = None;
+        self.chat_initialized = false;
+        self.last_synced_message_count = 0;
+        self.had_writing_message = false;
+        self.last_synced_content_len = 0;
+        self.last_mode_msg_count = 0;
+        self.in_welcome_mode = true;
+    }
+
     /// Load a chat by ID. Called from App when selecting a chat from history.
     pub fn load_chat(&mut self, chat_id: ChatId) {
         // Store the chat_id to be loaded - we'll handle it in handle_event
@@ -586,6 +1078,48 @@ impl ChatApp {
         self.last_mode_msg_count = 0;
     }
 
+    /// Load a chat and scroll to one of its messages, e.g. from a bookmark
+    pub fn load_chat_at(&mut self, chat_id: ChatId, message_index: usize) {
+        self.load_chat(chat_id);
+        self.scroll_to_message = Some(message_index);
+    }
+
+    /// Show `chat_id` in this instance regardless of which chat is current
+    /// in the Store. Used for chats opened in a separate window.
+    pub fn pin_chat(&mut self, chat_id: ChatId) {
+        self.pinned_chat_id = Some(chat_id);
+        self.load_chat(chat_id);
+    }
+
+    /// The chat this instance currently shows
+    pub fn current_chat_id(&self) -> Option<ChatId> {
+        self.current_chat_id
+    }
+
+    /// Whether an answer or an agent run is in progress. A message held
+    /// until its model loads doesn't count.
+    pub fn is_answering(&self) -> bool {
+        self.agent_run.is_some()
+            || (self.queued_send.is_none()
+                && self.chat_controller.lock().unwrap().state().messages.iter().any(|m| m.metadata.is_writing))
+    }
+
+    /// Stop the answer or agent run in progress, e.g. before quitting. What
+    /// was written so far is already saved.
+    pub fn stop_answering(&mut self) {
+        if let Some((control, _)) = &self.agent_run {
+            control.abort();
+        }
+        self.chat_controller.lock().unwrap().dispatch_task(ChatTask::Stop);
+    }
+
+    /// Save the prompt draft now rather than once typing pauses
+    pub fn flush_draft(&mut self, store: &mut Store) {
+        self.draft_timer = Timer::empty();
+        let Some(chat_id) = self.current_chat_id else { return };
+        store.chats.update_chat_draft(chat_id, &self.prompt_text_input().text());
+    }
+
     /// Initialize the chat from persistence (load or create the current chat)
     fn maybe_initialize_chat(&mut self, cx: &mut Cx, scope: &mut Scope) {
         if self.chat_initialized {
@@ -596,7 +1130,7 @@ impl ChatApp {
         let Some(store) = scope.data.get_mut::<Store>() else { return };
 
         // Get or create the current chat
-        let chat_id = if let Some(id) = store.chats.current_chat_id {
+        let chat_id = if let Some(id) = self.pinned_chat_id.or(store.chats.current_chat_id) {
             id
         } else {
             // No current chat, create one
@@ -640,6 +1174,11 @@ impl ChatApp {
         }
 
         self.chat_initialized = true;
+        self.apply_chat_tools(cx, scope);
+        self.apply_chat_settings(scope);
+        self.restore_draft(cx, scope);
+        self.restore_notes(cx, scope);
+        self.reset_answer_panels(cx, scope);
         self.view.redraw(cx);
     }
 
@@ -683,10 +1222,48 @@ impl ChatApp {
                 self.last_synced_content_len, last_msg_content_len);
         }
 
-        // Update the chat in persistence
+        // Update the chat in persistence, remembering newly sent prompts
+        // for Up-arrow recall and answers replaced by regenerating them
+        let mut replaced = None;
+        let finished = self.answer_timer.observe(&messages, std::time::Instant::now());
         if let Some(store) = scope.data.get_mut::<Store>() {
+            use moly_kit::aitk::protocol::EntityId;
+            // While an answer streams in, the stored copy is only part of it
+            if !self.had_writing_message {
+                let old = store.chats.get_chat_by_id(chat_id).map(|c| c.messages.as_slice()).unwrap_or_default();
+                replaced = self.regeneration_watch.replaced(old, &messages);
+            }
+            if let Some((index, text)) = &replaced {
+                ::log::info!("Answer {} regenerated, keeping the previous version", index);
+                store.chats.record_answer_version(chat_id, *index, text);
+            }
+            let mut sent_any = false;
+            for message in messages.iter().skip(self.last_synced_message_count) {
+                if matches!(message.from, EntityId::User) {
+                    store.chats.record_sent_prompt(chat_id, &message.content.text);
+                    telemetry::record_feature("chat.send");
+                    sent_any = true;
+                }
+            }
+            if sent_any {
+                self.prompt_recall.reset();
+            }
+            if let Some(timing) = finished {
+                if let Some(stats) = Self::answer_stats(store, chat_id, &messages, timing) {
+                    store.chats.set_message_stats(chat_id, stats);
+                }
+            }
             store.chats.update_chat_messages(chat_id, messages);
         }
+        if finished.is_some() {
+            self.update_answer_stats(cx, scope);
+        }
+
+        if let Some((index, _)) = replaced {
+            self.show_versions(cx, scope, index);
+        } else if writing_finished && self.versions_shown.is_some() {
+            self.update_versions_panel(cx, scope);
+        }
 
         // Notify shell to refresh sidebar when chat gets its first messages (title updates)
         if self.last_synced_message_count == 0 && message_count > 0 {
@@ -728,6 +1305,10 @@ impl ChatApp {
 
     /// Create a new chat session
     pub fn create_new_chat(&mut self, cx: &mut Cx, scope: &mut Scope) {
+        // Templates may have been saved from the chat history since
+        self.session_templates = SessionTemplates::load();
+        self.view.redraw(cx);
+
         // Skip if already in an empty welcome session (no messages sent yet)
         if self.in_welcome_mode {
             if let Some(chat_id) = self.current_chat_id {
@@ -739,6 +1320,8 @@ impl ChatApp {
             }
         }
 
+        self.save_draft(scope);
+        self.save_notes(scope);
         let Some(store) = scope.data.get_mut::<Store>() else { return };
 
         // Get current bot_id and all bots to use for new chat
@@ -747,8 +1330,28 @@ impl ChatApp {
             (ctrl.state().bot_id.clone(), ctrl.state().bots.clone())
         };
 
+        // Start with the chat defaults of the model's provider: its default
+        // model when the provider lists it, prompt and sampling
+        let provider_id = current_bot_id.as_ref()
+            .and_then(|bot_id| store.providers_manager.get_provider_for_bot(bot_id))
+            .map(str::to_string);
+        let defaults = provider_id.as_deref()
+            .and_then(|id| store.preferences.get_provider(id))
+            .map(|provider| provider.chat_defaults.clone())
+            .unwrap_or_default();
+        let current_bot_id = defaults.model.as_deref()
+            .and_then(|model| all_bots.iter().find(|bot| {
+                bot.id.id() == model && store.providers_manager.get_provider_for_bot(&bot.id) == provider_id.as_deref()
+            }))
+            .map(|bot| bot.id.clone())
+            .or(current_bot_id);
+
         // Create new chat in store with the current model category
         let chat_id = store.chats.create_chat(current_bot_id.clone());
+        if defaults.system_prompt.is_some() {
+            store.chats.update_chat_system_prompt(chat_id, defaults.system_prompt.clone());
+        }
+        store.chats.update_chat_sampling(chat_id, defaults.sampling);
         let current_cat = store.get_active_local_model_category();
         if current_cat.is_some() {
             store.chats.update_chat_category(chat_id, current_cat);
@@ -791,6 +1394,15 @@ impl ChatApp {
         // Notify the shell to refresh sidebar chat list
         cx.action(ChatHistoryAction::ChatCreated);
 
+        self.apply_chat_tools(cx, scope);
+        self.apply_chat_settings(scope);
+        self.restore_draft(cx, scope);
+        if let Some(prompt) = self.pending_prompt.take() {
+            self.prompt_text_input().set_text(cx, &prompt);
+        }
+        self.restore_notes(cx, scope);
+        self.reset_answer_panels(cx, scope);
+
         // Force redraw the entire view
         self.view.redraw(cx);
     }
@@ -800,6 +1412,8 @@ impl ChatApp {
         if self.current_chat_id == Some(chat_id) {
             return;
         }
+        self.save_draft(scope);
+        self.save_notes(scope);
 
         let Some(store) = scope.data.get_mut::<Store>() else { return };
 
@@ -847,6 +1461,11 @@ impl ChatApp {
             self.view.chat(ids!(main_content.chat)).write().messages_ref().write().instant_scroll_to_bottom(cx);
         }
 
+        self.apply_chat_tools(cx, scope);
+        self.apply_chat_settings(scope);
+        self.restore_draft(cx, scope);
+        self.restore_notes(cx, scope);
+        self.reset_answer_panels(cx, scope);
         self.view.redraw(cx);
     }
 
@@ -902,6 +1521,10 @@ impl ChatApp {
             self.view.chat(ids!(main_content.chat)).write().messages_ref().write().instant_scroll_to_bottom(cx);
         }
 
+        self.apply_chat_tools(cx, scope);
+        self.apply_chat_settings(scope);
+        self.restore_draft(cx, scope);
+        self.restore_notes(cx, scope);
         self.view.redraw(cx);
     }
 }
@@ -923,6 +1546,7 @@ impl Widget for ChatApp {
                 .write()
                 .set_chat_controller(Some(self.chat_controller.clone()));
             self.welcome_prompt_controller_set = true;
+            self.session_templates = SessionTemplates::load();
         }
 
         // Handle pending controller reset (e.g., after models load or view becomes visible)
@@ -952,11 +1576,21 @@ impl Widget for ChatApp {
         if self.needs_new_chat {
             self.needs_new_chat = false;
             self.create_new_chat(cx, scope);
-            // Consume pending_chat_model if set by StoreAction::OpenChatWithModel
-            // (model injection happens via maybe_inject_local_model detecting active_local_model change)
+            // Consume pending_chat_model if set by StoreAction::OpenChatWithModel, OpenTranscriber
+            // or OpenSpeaker (model injection happens via maybe_inject_local_model detecting
+            // active_local_model change) and give the new chat the system prompt preset for that
+            // kind of model, or name the transcription / speech session after its model
             if let Some(store) = scope.data.get_mut::<Store>() {
-                let _ = store.take_pending_chat_model();
+                if let (Some((model_id, category)), Some(chat_id)) = (store.take_pending_chat_model(), self.current_chat_id) {
+                    let prompt = store.preferences.system_prompts.for_model(&model_id, category);
+                    store.chats.update_chat_system_prompt(chat_id, prompt);
+                    if let Some(title) = ChatData::workspace_title(&model_id, category) {
+                        store.chats.update_chat_title(chat_id, title);
+                        cx.action(ChatHistoryAction::ChatCreated);
+                    }
+                }
             }
+            self.apply_chat_settings(scope);
         }
 
         // Sync chat mode from Store's loaded model category
@@ -965,6 +1599,13 @@ impl Widget for ChatApp {
         // Poll mode-specific async results (ASR/TTS/Image)
         self.poll_mode_result(cx);
         self.poll_file_picker(cx, scope);
+        self.poll_image_text(cx);
+        self.poll_screen_capture(cx);
+        self.poll_tool_activity(cx, scope);
+        self.poll_code_run(cx);
+        self.poll_surface_image(cx);
+        self.poll_translation(cx);
+        self.poll_agent(cx, scope);
 
         // Strip stale error messages from ChatTask::Send in non-chat modes.
         // The Chat widget dispatches ChatTask::Send async; the error arrives after
@@ -989,6 +1630,11 @@ impl Widget for ChatApp {
 
         // Initialize chat from persistence (load or create)
         self.maybe_initialize_chat(cx, scope);
+        if self.chat_initialized {
+            if let Some(index) = self.scroll_to_message.take() {
+                self.scroll_to_message_index(cx, index);
+            }
+        }
 
         // Track model selection changes and save to preferences
         self.track_model_selection(scope);
@@ -1006,8 +1652,55 @@ impl Widget for ChatApp {
         } else {
             self.view.chat(ids!(main_content.chat)).handle_event(cx, event, scope);
         }
+        if let Event::KeyDown(ke) = event {
+            let command = ke.modifiers.logo || ke.modifiers.control;
+            if matches!(ke.key_code, KeyCode::ArrowUp | KeyCode::ArrowDown) && !ke.modifiers.shift {
+                if let Some(picker) = self.model_picker.as_mut() {
+                    picker.step(ke.key_code == KeyCode::ArrowDown);
+                    self.refresh_model_picker(cx);
+                } else {
+                    self.recall_prompt(cx, scope, ke.key_code == KeyCode::ArrowUp);
+                }
+            }
+            if ke.key_code == KeyCode::KeyF && command {
+                self.open_find(cx);
+            }
+            if ke.key_code == KeyCode::KeyK && command {
+                if self.model_picker.is_some() {
+                    self.close_model_picker(cx);
+                } else {
+                    self.open_model_picker(cx, scope);
+                }
+            }
+            if ke.key_code == KeyCode::Escape {
+                if self.model_picker.is_some() {
+                    self.close_model_picker(cx);
+                } else if self.find_open {
+                    self.close_find(cx);
+                }
+            }
+        }
+        if self.draft_timer.is_event(event).is_some() {
+            self.save_draft(scope);
+        }
+        if self.read_aloud_timer.is_event(event).is_some() {
+            self.poll_read_aloud(cx);
+        }
+        if self.notes_timer.is_event(event).is_some() {
+            self.save_notes(scope);
+        }
         self.view.view(ids!(header)).handle_event(cx, event, scope);
+        self.view.view(ids!(find_panel)).handle_event(cx, event, scope);
+        self.view.view(ids!(agent_panel)).handle_event(cx, event, scope);
+        self.view.view(ids!(read_aloud_panel)).handle_event(cx, event, scope);
+        self.view.view(ids!(translate_panel)).handle_event(cx, event, scope);
+        self.view.view(ids!(versions_panel)).handle_event(cx, event, scope);
+        self.view.view(ids!(sources_panel)).handle_event(cx, event, scope);
+        self.view.view(ids!(notes_panel)).handle_event(cx, event, scope);
+        self.handle_source_hover(cx, event);
         self.view.view(ids!(mode_controls)).handle_event(cx, event, scope);
+        self.view.moly_dialog(ids!(main_content.dialog)).handle_event(cx, event, scope);
+        self.focus.handle_event(cx, event, scope, &self.view);
 
         // ── VLM image drag-and-drop ──────────────────────────────────────────
         if self.chat_mode == ChatMode::Vlm {
@@ -1036,22 +1729,7 @@ impl Widget for ChatApp {
                             let lower = path.to_lowercase();
                             if lower.ends_with(".jpg") || lower.ends_with(".jpeg") || lower.ends_with(".png")
                                 || lower.ends_with(".bmp") || lower.ends_with(".gif") || lower.ends_with(".webp") {
-                                if let Ok(bytes) = std::fs::read(path) {
-                                    use base64::Engine;
-                                    let b64 = base64::engine::general_purpose::STANDARD.encode(&bytes);
-                                    self.vlm_image_b64 = Some(b64);
-                                    self.vlm_image_path = path.clone();
-                                    let filename = std::path::Path::new(path)
-                                        .file_name()
-                                        .map(|f| f.to_string_lossy().to_string())
-                                        .unwrap_or_else(|| path.clone());
-                                    self.view.label(ids!(mode_controls.vlm_controls.vlm_file_row.vlm_file_label))
-                                        .set_text(cx, &filename);
-                                    let preview = self.view.image(ids!(mode_controls.vlm_controls.vlm_file_row.vlm_preview));
-                                    preview.set_visible(cx, true);
-                                    let _ = preview.load_image_file_by_path(cx, std::path::Path::new(path));
-                                    self.view.redraw(cx);
-                                }
+                                self.set_vlm_image(cx, path);
                                 break;
                             }
                         }
@@ -1106,6 +1784,12 @@ impl Widget for ChatApp {
             }
         }
 
+        // Hold messages sent before the local model finished loading
+        self.queue_while_loading(cx);
+
+        // Ask about personal data in messages the privacy filter held back
+        self.review_held(cx, scope);
+
         // Detect new user messages from Chat widget for non-LLM modes
         self.maybe_handle_mode_message(cx, scope);
 
@@ -1135,6 +1819,10 @@ impl Widget for ChatApp {
             } else {
                 self.view.label(ids!(title_label)).set_text(cx, "");
             }
+
+            let total = toolsets::tool_choices(&store.preferences.mcp_servers).len();
+            let summary = format!("Tools: {} of {}", self.chat_tools(store).len(), total);
+            self.view.label(ids!(tools_summary)).set_text(cx, &summary);
         }
 
         // Update greeting text based on loaded model
@@ -1148,6 +1836,8 @@ impl Widget for ChatApp {
                     .set_text(cx, "What can I help you with?");
             }
         }
+        self.view.view(ids!(main_content.welcome_overlay.templates_row))
+            .set_visible(cx, !self.session_templates.templates.is_empty());
 
         // All non-LLM modes skip welcome overlay and go straight to Chat widget.
         // Only LLM shows the welcome overlay (centered PromptInput) for the first message.
@@ -1185,6 +1875,9 @@ impl Widget for ChatApp {
                 .set_visible(cx, has_image);
             self.view.view(ids!(mode_controls.vlm_controls.vlm_file_row.vlm_clear_btn))
                 .set_visible(cx, has_image);
+            self.view.view(ids!(mode_controls.vlm_controls.vlm_ocr_row)).set_visible(cx, has_image);
+            self.view.label(ids!(mode_controls.vlm_controls.vlm_ocr_text))
+                .set_visible(cx, has_image && self.vlm_image_text.is_some());
             if has_image {
                 self.view.view(ids!(mode_controls.vlm_controls.vlm_drop_zone))
                     .set_visible(cx, false);
@@ -1196,6 +1889,7 @@ impl Widget for ChatApp {
 
         // Show ASR clear button when audio file is selected
         if self.chat_mode == ChatMode::Asr {
+            self.update_asr_options(cx);
             let has_audio = !self.asr_file_path.is_empty();
             self.view.view(ids!(mode_controls.asr_controls.asr_file_row.asr_clear_btn))
                 .set_visible(cx, has_audio);
@@ -1229,7 +1923,7 @@ impl Widget for ChatApp {
             }
         }
 
-        // Check if afplay process finished
+        // Check if the player process finished
         if self.tts_playing {
             if let Some(ref mut proc) = self.tts_play_process {
                 if let Ok(Some(_)) = proc.try_wait() {
@@ -1297,13 +1991,92 @@ impl WidgetMatchEvent for ChatApp {
             }
         }
 
-        // Also directly check the Chat widget's PromptInput for A2UI toggle
-        let chat = self.view.chat(ids!(main_content.chat));
-        if let Some(a2ui_enabled) = chat.read().prompt_input_ref().a2ui_toggled(actions) {
-            eprintln!("[ChatApp] A2UI toggled (direct check): {}", a2ui_enabled);
-            if let Some(ref a2ui_client) = self.a2ui_client {
-                a2ui_client.set_a2ui_enabled(a2ui_enabled);
-                eprintln!("[ChatApp] A2uiClient enabled state set to: {}", a2ui_enabled);
+        if self.view.button(ids!(tools_button)).clicked(actions) {
+            self.open_tools_menu(cx, scope);
+        }
+        if self.view.button(ids!(main_content.welcome_overlay.templates_row.templates_button)).clicked(actions) {
+            self.open_templates(cx, false);
+        }
+        if self.view.button(ids!(main_content.welcome_overlay.templates_row.remove_template_button)).clicked(actions) {
+            self.open_templates(cx, true);
+        }
+        if self.view.button(ids!(system_prompt_button)).clicked(actions) {
+            self.open_system_prompt(cx, scope);
+        }
+        if self.view.button(ids!(sampling_button)).clicked(actions) {
+            self.open_sampling(cx, scope);
+        }
+        if self.view.button(ids!(jump_button)).clicked(actions) {
+            self.open_jump_picker(cx, scope);
+        }
+        if self.view.button(ids!(link_button)).clicked(actions) {
+            self.open_link_picker(cx, scope);
+        }
+        if self.view.button(ids!(bookmark_button)).clicked(actions) {
+            self.open_bookmark_picker(cx, scope);
+        }
+        if self.view.button(ids!(versions_button)).clicked(actions) {
+            self.open_versions_picker(cx, scope);
+        }
+        if self.view.button(ids!(sources_button)).clicked(actions) {
+            self.open_sources_picker(cx, scope);
+        }
+        if self.view.button(ids!(agent_button)).clicked(actions) {
+            self.open_agent_goal(cx, scope);
+        }
+        if self.view.button(ids!(read_aloud_button)).clicked(actions) {
+            self.open_read_aloud_picker(cx, None);
+        }
+        if self.view.button(ids!(translate_button)).clicked(actions) {
+            self.open_translate_picker(cx);
+        }
+        if self.view.button(ids!(model_button)).clicked(actions) {
+            self.open_model_picker(cx, scope);
+        }
+        self.handle_agent_controls(cx, actions);
+        self.handle_read_aloud_controls(cx, actions);
+        self.handle_find_controls(cx, actions);
+        self.handle_model_picker_controls(cx, scope, actions);
+        self.handle_translate_controls(cx, actions);
+        self.handle_versions_controls(cx, scope, actions);
+        self.handle_answer_stats_controls(cx, scope, actions);
+        self.handle_sources_controls(cx, actions);
+        self.handle_dialogs(cx, scope, actions);
+
+        if self.prompt_text_input().changed(actions).is_some() {
+            cx.stop_timer(self.draft_timer);
+            self.draft_timer = cx.start_timeout(DRAFT_SAVE_DELAY);
+        }
+        if self.view.text_input(ids!(notes_panel.notes_input)).changed(actions).is_some() {
+            cx.stop_timer(self.notes_timer);
+            self.notes_timer = cx.start_timeout(DRAFT_SAVE_DELAY);
+        }
+        if self.view.button(ids!(notes_button)).clicked(actions) {
+            self.toggle_notes(cx, scope);
+        }
+        if self.view.button(ids!(notes_panel.notes_close_button)).clicked(actions) && self.notes_open {
+            self.toggle_notes(cx, scope);
+        }
+        if self.view.button(ids!(export_button)).clicked(actions) {
+            self.open_export(cx, scope);
+        }
+        if self.view.button(ids!(share_button)).clicked(actions) {
+            self.open_share(cx);
+        }
+        if self.view.button(ids!(code_button)).clicked(actions) {
+            self.open_code_blocks(cx);
+        }
+        if self.view.button(ids!(ui_image_button)).clicked(actions) {
+            self.open_surface_image(cx);
+        }
+
+        // Also directly check the Chat widget's PromptInput for A2UI toggle
+        let chat = self.view.chat(ids!(main_content.chat));
+        if let Some(a2ui_enabled) = chat.read().prompt_input_ref().a2ui_toggled(actions) {
+            eprintln!("[ChatApp] A2UI toggled (direct check): {}", a2ui_enabled);
+            if let Some(ref a2ui_client) = self.a2ui_client {
+                a2ui_client.set_a2ui_enabled(a2ui_enabled);
+                eprintln!("[ChatApp] A2uiClient enabled state set to: {}", a2ui_enabled);
             } else {
                 eprintln!("[ChatApp] Warning: No A2uiClient available");
             }
@@ -1319,7 +2092,7 @@ impl WidgetMatchEvent for ChatApp {
             if !text.is_empty() {
                 use moly_kit::aitk::protocol::{EntityId, Message, MessageContent};
 
-                {
+                let held = {
                     let mut ctrl = self.chat_controller.lock().unwrap();
                     ctrl.dispatch_mutation(VecMutation::Push(Message {
                         from: EntityId::User,
@@ -1329,7 +2102,19 @@ impl WidgetMatchEvent for ChatApp {
                         },
                         ..Default::default()
                     }));
-                    ctrl.dispatch_task(ChatTask::Send);
+                    // Personal data for a cloud provider is decided on first
+                    let bot_id = ctrl.state().bot_id.clone();
+                    let reviewed = privacy_filter::is_enabled() && Self::cloud_provider_name(scope, bot_id.as_ref()).is_some();
+                    match reviewed.then(|| privacy_filter::review_outbound(&ctrl.state().messages, &privacy_filter::active())) {
+                        Some(privacy_filter::Verdict::Hold(found)) => Some((bot_id, found)),
+                        _ => {
+                            ctrl.dispatch_task(ChatTask::Send);
+                            None
+                        }
+                    }
+                };
+                if let Some((bot_id, found)) = held {
+                    self.open_privacy_review(cx, scope, bot_id.as_ref(), found);
                 }
 
                 // Transfer A2UI toggle state to Chat's PromptInput before reset
@@ -1367,6 +2152,7 @@ impl WidgetMatchEvent for ChatApp {
         {
             self.vlm_image_path.clear();
             self.vlm_image_b64 = None;
+            self.forget_image_text(cx);
             self.view.label(ids!(mode_controls.vlm_controls.vlm_file_row.vlm_file_label))
                 .set_text(cx, "");
             self.view.image(ids!(mode_controls.vlm_controls.vlm_file_row.vlm_preview))
@@ -1378,6 +2164,33 @@ impl WidgetMatchEvent for ChatApp {
             self.view.redraw(cx);
         }
 
+        // VLM: Screenshot buttons
+        if self.view.button(ids!(mode_controls.vlm_controls.vlm_file_row.vlm_capture_region_btn)).clicked(&actions) {
+            self.start_screen_capture(cx, true);
+        }
+        if self.view.button(ids!(mode_controls.vlm_controls.vlm_file_row.vlm_capture_screen_btn)).clicked(&actions) {
+            self.start_screen_capture(cx, false);
+        }
+
+        // VLM: Read the image's text
+        if self.view.button(ids!(mode_controls.vlm_controls.vlm_ocr_row.vlm_ocr_btn)).clicked(&actions) {
+            self.start_image_text(cx);
+        }
+        if let Some(text) = self.vlm_image_text.clone() {
+            if self.view.button(ids!(mode_controls.vlm_controls.vlm_ocr_row.vlm_ocr_copy_btn)).clicked(&actions) {
+                cx.copy_to_clipboard(&text);
+            }
+            if self.view.button(ids!(mode_controls.vlm_controls.vlm_ocr_row.vlm_ocr_insert_btn)).clicked(&actions) {
+                // In the prompt it is sent like any typed text, not again with the message
+                let input = self.prompt_text_input();
+                let prompt = input.text();
+                let joined = if prompt.trim().is_empty() { text } else { format!("{}\n\n{}", prompt.trim_end(), text) };
+                input.set_text(cx, &joined);
+                self.vlm_image_text = None;
+                self.set_image_text_status(cx, "Text added to the prompt.");
+            }
+        }
+
         // ASR: Browse/upload audio button
         if self.view.view(ids!(mode_controls.asr_controls.asr_file_row.asr_browse_btn))
             .finger_down(&actions).is_some()
@@ -1399,6 +2212,20 @@ impl WidgetMatchEvent for ChatApp {
             self.view.redraw(cx);
         }
 
+        // ASR: Punctuation and vocabulary switches for this transcription
+        if self.view.view(ids!(mode_controls.asr_controls.asr_options_row.asr_punctuate_btn))
+            .finger_down(&actions).is_some()
+        {
+            self.asr_punctuate = Some(!self.asr_options().0);
+            self.update_asr_options(cx);
+        }
+        if self.view.view(ids!(mode_controls.asr_controls.asr_options_row.asr_vocabulary_btn))
+            .finger_down(&actions).is_some()
+        {
+            self.asr_vocabulary = Some(!self.asr_options().1);
+            self.update_asr_options(cx);
+        }
+
         // TTS: Voice selection buttons (in mode_controls bar)
         for i in 0..9usize {
             let voice_id = match i {
@@ -1504,6 +2331,8 @@ impl ChatApp {
             self.asr_file_path.clear();
             self.vlm_image_path.clear();
             self.vlm_image_b64 = None;
+            self.vlm_image_text = None;
+            self.ocr_rx = None;
             self.image_ref_b64 = None;
             self.image_ref_path.clear();
             self.mode_rx = None;
@@ -1546,6 +2375,10 @@ impl ChatApp {
         let Some(result) = result else { return };
         self.mode_rx = None;
         self.mode_busy = false;
+        match &result {
+            Ok(_) => telemetry::record_feature(&format!("chat.{:?}", self.chat_mode).to_lowercase()),
+            Err(e) => telemetry::record_error(&MolyError::from_message(e)),
+        }
 
         use moly_kit::aitk::protocol::{Attachment, EntityId, Message, MessageContent};
 
@@ -1553,13 +2386,13 @@ impl ChatApp {
             ChatMode::Vlm => {
                 match result {
                     Ok(text) => (text, vec![]),
-                    Err(e) => (format!("VLM error: {}", e), vec![]),
+                    Err(e) => (format!("VLM error: {}", MolyError::from_message(&e).user_message()), vec![]),
                 }
             }
             ChatMode::Asr => {
                 match result {
                     Ok(text) => (text, vec![]),
-                    Err(e) => (format!("Transcription error: {}", e), vec![]),
+                    Err(e) => (format!("Transcription error: {}", MolyError::from_message(&e).user_message()), vec![]),
                 }
             }
             ChatMode::Tts => {
@@ -1568,19 +2401,23 @@ impl ChatApp {
                         self.tts_audio_path = Some(path.clone());
                         self.tts_playing = false;
                         self.tts_duration_secs = Self::get_wav_duration(&path);
-                        let voice = TTS_VOICE_IDS.get(self.tts_voice_idx)
-                            .copied().unwrap_or("vivian");
+                        let voice = self.tts_voices_used.clone();
                         let dur = self.tts_duration_secs;
                         let mins = dur as u32 / 60;
                         let secs = dur as u32 % 60;
-                        if let Ok(child) = std::process::Command::new("afplay").arg(&path).spawn() {
+                        if let Ok(child) = platform::play_audio(&path) {
                             self.tts_play_process = Some(child);
                             self.tts_playing = true;
                             self.tts_play_start = Some(std::time::Instant::now());
                         }
-                        (format!("Voice: {} | Duration: {}:{:02}", voice, mins, secs), vec![])
+                        let text = if path.ends_with(".wav") {
+                            format!("Voice: {} | Duration: {}:{:02}", voice, mins, secs)
+                        } else {
+                            format!("Voice: {} | {}", voice, tts_output::current().summary())
+                        };
+                        (text, vec![])
                     }
-                    Err(e) => (format!("TTS error: {}", e), vec![]),
+                    Err(e) => (format!("TTS error: {}", MolyError::from_message(&e).user_message()), vec![]),
                 }
             }
             ChatMode::ImageGen => {
@@ -1597,7 +2434,7 @@ impl ChatApp {
                             (format!("Image saved to: {}", path), vec![])
                         }
                     }
-                    Err(e) => (format!("Image generation error: {}", e), vec![]),
+                    Err(e) => (format!("Image generation error: {}", MolyError::from_message(&e).user_message()), vec![]),
                 }
             }
             ChatMode::VideoGen => {
@@ -1605,7 +2442,7 @@ impl ChatApp {
                     Ok(path) => {
                         (format!("Video generated: {}", path), vec![])
                     }
-                    Err(e) => (format!("Video generation error: {}", e), vec![]),
+                    Err(e) => (format!("Video generation error: {}", MolyError::from_message(&e).user_message()), vec![]),
                 }
             }
             _ => return,
@@ -1643,7 +2480,7 @@ impl ChatApp {
     /// instead of relying on ChatTask::Send (which would call the wrong endpoint or
     /// format content parts in the wrong order for VLM).
     fn maybe_handle_mode_message(&mut self, cx: &mut Cx, scope: &mut Scope) {
-        if self.mode_busy { return; }
+        if self.mode_busy || self.queued_send.is_some() { return; }
         if !matches!(self.chat_mode, ChatMode::Vlm | ChatMode::Tts | ChatMode::ImageGen | ChatMode::VideoGen) { return; }
 
         use moly_kit::aitk::protocol::EntityId;
@@ -1702,6 +2539,7 @@ impl ChatApp {
 
         match self.chat_mode {
             ChatMode::Vlm => {
+                let user_text = self.attach_image_text(cx, user_text);
                 self.start_vlm_generate(cx, scope, user_text);
             }
             ChatMode::Tts => {
@@ -1717,6 +2555,153 @@ impl ChatApp {
         }
     }
 
+    /// With the privacy filter on, the client of a cloud provider decides
+    /// before sending ([`privacy_filter::review_outbound`]) and holds back a
+    /// message with email addresses, phone numbers or API keys not yet
+    /// decided on in this chat, without making a request. Once its empty
+    /// reply is finished, the reply is dropped and the user picks what to
+    /// redact; the message goes out when they confirm.
+    fn review_held(&mut self, cx: &mut Cx, scope: &mut Scope) {
+        use moly_kit::aitk::protocol::EntityId;
+
+        if self.privacy_review.is_some() { return; }
+        let mut ctrl = self.chat_controller.lock().unwrap();
+        if ctrl.state().messages.last().is_some_and(|m| m.metadata.is_writing) { return; }
+        let Some(found) = privacy_filter::take_held() else { return };
+        let mut msgs = ctrl.state().messages.clone();
+        if msgs.last().is_some_and(|m| !matches!(m.from, EntityId::User) && m.content.text.is_empty()) {
+            msgs.pop();
+            ctrl.dispatch_mutation(VecMutation::Set(msgs));
+        }
+        let bot_id = ctrl.state().bot_id.clone();
+        drop(ctrl);
+        self.open_privacy_review(cx, scope, bot_id.as_ref(), found);
+    }
+
+    /// The name of the provider serving `bot_id`, if it is outside this
+    /// computer and the local network
+    fn cloud_provider_name(scope: &mut Scope, bot_id: Option<&BotId>) -> Option<String> {
+        let store = scope.data.get::<Store>()?;
+        let provider_id = store.providers_manager.get_provider_for_bot(bot_id?)?;
+        if !store.providers_manager.is_cloud(provider_id) { return None; }
+        Some(store.preferences.get_provider(provider_id).map_or_else(|| provider_id.to_string(), |p| p.name.clone()))
+    }
+
+    /// Ask which of the values `found` in the conversation to redact
+    fn open_privacy_review(&mut self, cx: &mut Cx, scope: &mut Scope, bot_id: Option<&BotId>, found: Vec<PiiMatch>) {
+        let provider = Self::cloud_provider_name(scope, bot_id).unwrap_or_else(|| "the provider".to_string());
+        ::log::info!("Asking what to redact before sending to {} ({} items)", provider, found.len());
+        let labels = found.iter().map(PiiMatch::label).collect();
+        self.view.moly_dialog(ids!(dialog)).open(cx,
+            DialogRequest::multi_select(live_id!(privacy_review), "Redact Before Sending?",
+                format!("This conversation with {} contains what looks like personal data. Checked items are \
+                         replaced with placeholders before sending; answers show the real values.", provider),
+                labels, vec![true; found.len()])
+                .confirm_label("Send"));
+        self.privacy_review = Some(found);
+        self.view.redraw(cx);
+    }
+
+    /// Send the held message, redacting the items left checked
+    fn send_reviewed(&mut self, scope: &mut Scope, checked: &[usize]) {
+        let Some(found) = self.privacy_review.take() else { return };
+        let Some(chat_id) = self.current_chat_id else { return };
+        let Some(store) = scope.data.get_mut::<Store>() else { return };
+        let redact: Vec<bool> = (0..found.len()).map(|i| checked.contains(&i)).collect();
+        let mut decisions = privacy_filter::active();
+        decisions.decide(&found, &redact);
+        privacy_filter::set_active(&decisions);
+        store.chats.update_chat_privacy(chat_id, decisions);
+        self.chat_controller.lock().unwrap().dispatch_task(ChatTask::Send);
+    }
+
+    /// Put the held message back in the prompt, unsent
+    fn cancel_review(&mut self, cx: &mut Cx) {
+        use moly_kit::aitk::protocol::EntityId;
+
+        if self.privacy_review.take().is_none() { return; }
+        let mut ctrl = self.chat_controller.lock().unwrap();
+        let mut msgs = ctrl.state().messages.clone();
+        if !msgs.last().is_some_and(|m| matches!(m.from, EntityId::User)) { return; }
+        let Some(held) = msgs.pop() else { return };
+        ctrl.dispatch_mutation(VecMutation::Set(msgs));
+        drop(ctrl);
+        self.prompt_text_input().set_text(cx, &held.content.text);
+        self.view.redraw(cx);
+    }
+
+    /// Messages sent while the chat's local model is still loading would fail,
+    /// so the request is stopped and the messages wait behind a "waiting for
+    /// model" bubble. They are sent once the load completes; if it fails, the
+    /// bubble says so instead.
+    fn queue_while_loading(&mut self, cx: &mut Cx) {
+        use moly_kit::aitk::protocol::{EntityId, Message, MessageContent, MessageMetadata};
+
+        let mut ctrl = self.chat_controller.lock().unwrap();
+        let Some(bot_id) = ctrl.state().bot_id.clone() else { return };
+        let bubble = |text: String, waiting: bool| Message {
+            from: EntityId::Bot(bot_id.clone()),
+            content: MessageContent { text, ..Default::default() },
+            metadata: MessageMetadata { is_writing: waiting, ..MessageMetadata::default() },
+            ..Default::default()
+        };
+
+        let Some(queued) = self.queued_send.clone() else {
+            if self.mode_busy { return; }
+            let model_id = bot_id.id().to_string();
+            let phase = model_state::load_phase(&model_id);
+            let msgs = &ctrl.state().messages;
+            let Some(held) = HeldSend::hold(self.current_chat_id, &model_id, msgs, phase.as_ref()) else { return };
+            ::log::info!("Holding a message until {} finishes loading", model_id);
+            let kept = held.held(msgs, bubble(held.waiting_text(), true));
+            ctrl.dispatch_task(ChatTask::Stop);
+            ctrl.dispatch_mutation(VecMutation::Set(kept));
+            self.queued_send = Some(held);
+            drop(ctrl);
+            self.view.redraw(cx);
+            return;
+        };
+
+        let mut msgs = ctrl.state().messages.clone();
+        if queued.chat_id != self.current_chat_id || msgs.len() <= queued.first {
+            // Another chat is shown; its messages are no longer ours to send
+            self.queued_send = None;
+            return;
+        }
+        match queued.outcome(model_state::load_phase(&queued.model_id).as_ref()) {
+            HeldOutcome::Wait => {
+                // Keep the bubble last, holding messages sent in the meantime as well
+                if msgs.last().is_some_and(|m| queued.is_waiting_bubble(m)) { return; }
+                let kept = queued.held(&msgs, bubble(queued.waiting_text(), true));
+                ctrl.dispatch_task(ChatTask::Stop);
+                ctrl.dispatch_mutation(VecMutation::Set(kept));
+            }
+            HeldOutcome::Send => {
+                ::log::info!("{} finished loading; sending held messages", queued.model_id);
+                msgs.retain(|m| !queued.is_waiting_bubble(m));
+                ctrl.dispatch_mutation(VecMutation::Set(msgs));
+                if matches!(self.chat_mode, ChatMode::Vlm | ChatMode::Tts | ChatMode::ImageGen | ChatMode::VideoGen) {
+                    // maybe_handle_mode_message picks the messages up
+                    self.last_mode_msg_count = queued.first;
+                } else {
+                    ctrl.dispatch_task(ChatTask::Send);
+                }
+                self.queued_send = None;
+            }
+            HeldOutcome::Fail(note) => {
+                match msgs.iter().rposition(|m| queued.is_waiting_bubble(m)) {
+                    Some(pos) => msgs[pos] = bubble(note, false),
+                    None => msgs.push(bubble(note, false)),
+                }
+                self.last_mode_msg_count = msgs.len();
+                ctrl.dispatch_mutation(VecMutation::Set(msgs));
+                self.queued_send = None;
+            }
+        }
+        drop(ctrl);
+        self.view.redraw(cx);
+    }
+
     /// Audio player: toggle play/stop
     fn handle_audio_play_toggle(&mut self, cx: &mut Cx) {
         let Some(ref path) = self.tts_audio_path else { return };
@@ -1729,7 +2714,7 @@ impl ChatApp {
             self.tts_playing = false;
             self.tts_play_start = None;
         } else {
-            if let Ok(child) = std::process::Command::new("afplay").arg(path).spawn() {
+            if let Ok(child) = platform::play_audio(path) {
                 self.tts_play_process = Some(child);
                 self.tts_playing = true;
                 self.tts_play_start = Some(std::time::Instant::now());
@@ -1749,98 +2734,184 @@ impl ChatApp {
         data_size / byte_rate
     }
 
-    /// Audio player: save as MP3 via save dialog
+    /// Audio player: Save As, in the format the audio was generated in and
+    /// starting in the folder set in Settings
     fn handle_audio_download(&mut self, _cx: &mut Cx) {
-        let Some(ref wav_path) = self.tts_audio_path else { return };
-        let wav_path = wav_path.clone();
+        let Some(ref audio_path) = self.tts_audio_path else { return };
+        let audio_path = std::path::PathBuf::from(audio_path);
+        let extension = audio_path.extension().and_then(|e| e.to_str()).unwrap_or("wav").to_string();
+        let mut dialog = FileDialog::new()
+            .set_file_name(format!("speech.{}", extension))
+            .add_filter("Audio", &[extension.as_str()]);
+        if let Some(dir) = tts_output::current().output_dir() {
+            dialog = dialog.set_directory(dir);
+        }
+        let Some(save_path) = dialog.save_file() else { return };
 
         std::thread::spawn(move || {
-            let output = std::process::Command::new("osascript")
-                .args(["-e", "POSIX path of (choose file name with prompt \"Save audio as MP3\" default name \"speech.mp3\")"])
-                .output();
-            if let Ok(out) = output {
-                let save_path = String::from_utf8_lossy(&out.stdout).trim().to_string();
-                if save_path.is_empty() { return; }
-
-                if save_path.ends_with(".mp3") {
-                    let _ = std::process::Command::new("afconvert")
-                        .args(["-f", "mp4f", "-d", "aac", &wav_path, &save_path])
-                        .status();
-                } else {
-                    let _ = std::fs::copy(&wav_path, &save_path);
-                }
+            if let Err(e) = std::fs::copy(&audio_path, &save_path) {
+                ::log::error!("Saving audio to {:?} failed: {}", save_path, e);
             }
         });
     }
 
-    /// VLM: Open image file browser dialog
-    fn handle_vlm_browse(&mut self, _cx: &mut Cx) {
-        if self.file_picker_rx.is_some() { return; }
+    /// Run a file dialog and hand its result to `poll_file_picker`
+    fn pick_file(&mut self, dialog: FileDialog) {
+        if self.file_picker_rx.is_some() { return; } // Already picking
         let (tx, rx) = mpsc::channel();
-        std::thread::spawn(move || {
-            let output = std::process::Command::new("osascript")
-                .args(["-e", "POSIX path of (choose file of type {\"public.image\"} with prompt \"Select image\")"])
-                .output();
-            match output {
-                Ok(out) => {
-                    let path = String::from_utf8_lossy(&out.stdout).trim().to_string();
-                    if !path.is_empty() {
-                        tx.send(Ok(path)).ok();
-                    } else {
-                        tx.send(Err("Cancelled".to_string())).ok();
-                    }
-                }
-                Err(e) => { tx.send(Err(e.to_string())).ok(); }
-            }
-        });
+        let result = dialog.pick_file()
+            .map(|p| p.to_string_lossy().to_string())
+            .ok_or_else(|| "Cancelled".to_string());
+        tx.send(result).ok();
         self.file_picker_rx = Some(rx);
     }
 
-    /// ASR: Open file browser dialog
-    fn handle_asr_browse(&mut self, _cx: &mut Cx) {
-        if self.file_picker_rx.is_some() { return; } // Already picking
+    /// VLM: Attach the image at `path` to the next message and preview it
+    fn set_vlm_image(&mut self, cx: &mut Cx, path: &str) {
+        let Ok(bytes) = std::fs::read(path) else { return };
+        use base64::Engine;
+        self.vlm_image_b64 = Some(base64::engine::general_purpose::STANDARD.encode(&bytes));
+        self.vlm_image_path = path.to_string();
+        self.forget_image_text(cx);
+        let filename = std::path::Path::new(path)
+            .file_name()
+            .map(|f| f.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.to_string());
+        self.view.label(ids!(mode_controls.vlm_controls.vlm_file_row.vlm_file_label))
+            .set_text(cx, &filename);
+        let preview = self.view.image(ids!(mode_controls.vlm_controls.vlm_file_row.vlm_preview));
+        preview.set_visible(cx, true);
+        let _ = preview.load_image_file_by_path(cx, std::path::Path::new(path));
+        self.view.redraw(cx);
+    }
+
+    /// VLM: Take a screenshot in the background and attach it when taken
+    fn start_screen_capture(&mut self, cx: &mut Cx, region: bool) {
+        if self.capture_rx.is_some() { return; }
+        let path = artifacts::unique_path("screenshot", "png");
         let (tx, rx) = mpsc::channel();
         std::thread::spawn(move || {
-            let output = std::process::Command::new("osascript")
-                .args(["-e", "POSIX path of (choose file of type {\"public.audio\"} with prompt \"Select audio file\")"])
-                .output();
-            match output {
-                Ok(out) => {
-                    let path = String::from_utf8_lossy(&out.stdout).trim().to_string();
-                    if !path.is_empty() {
-                        tx.send(Ok(path)).ok();
-                    } else {
-                        tx.send(Err("Cancelled".to_string())).ok();
-                    }
-                }
-                Err(e) => { tx.send(Err(e.to_string())).ok(); }
-            }
+            let result = platform::capture_screen(&path, region)
+                .map(|taken| taken.then(|| path.to_string_lossy().to_string()));
+            let _ = tx.send(result);
         });
-        self.file_picker_rx = Some(rx);
+        self.capture_rx = Some(rx);
+        telemetry::record_feature("chat.screen_capture");
+        self.view.label(ids!(mode_controls.vlm_controls.vlm_file_row.vlm_file_label))
+            .set_text(cx, if region { "Select a region of the screen…" } else { "Capturing the screen…" });
+        self.view.redraw(cx);
     }
 
-    /// Image Edit: Open file browser for reference image
-    fn handle_image_ref_browse(&mut self, _cx: &mut Cx) {
-        if self.file_picker_rx.is_some() { return; }
+    fn poll_screen_capture(&mut self, cx: &mut Cx) {
+        let Some(rx) = &self.capture_rx else { return };
+        let result = match rx.try_recv() {
+            Ok(result) => result,
+            Err(mpsc::TryRecvError::Empty) => return,
+            Err(mpsc::TryRecvError::Disconnected) => Err("The screen capture stopped unexpectedly.".to_string()),
+        };
+        self.capture_rx = None;
+        match result {
+            Ok(Some(path)) => self.set_vlm_image(cx, &path),
+            Ok(None) => {
+                self.view.label(ids!(mode_controls.vlm_controls.vlm_file_row.vlm_file_label)).set_text(cx, "Capture cancelled.");
+            }
+            Err(e) => {
+                self.view.label(ids!(mode_controls.vlm_controls.vlm_file_row.vlm_file_label)).set_text(cx, &e);
+            }
+        }
+        self.view.redraw(cx);
+    }
+
+    /// VLM: Read the attached image's text with the loaded vision model
+    fn start_image_text(&mut self, cx: &mut Cx) {
+        if self.ocr_rx.is_some() || self.vlm_image_path.is_empty() { return; }
+        let Some(model) = ocr::loaded_vlm(&ModelRegistry::load()) else {
+            self.set_image_text_status(cx, "Load a vision model (VLM) in the Hub to read the text in images.");
+            return;
+        };
+        let path = self.vlm_image_path.clone();
         let (tx, rx) = mpsc::channel();
         std::thread::spawn(move || {
-            let output = std::process::Command::new("osascript")
-                .args(["-e", "set f to choose file of type {\"public.image\"} with prompt \"Select reference image\""])
-                .args(["-e", "POSIX path of f"])
-                .output();
-            match output {
-                Ok(out) => {
-                    let path = String::from_utf8_lossy(&out.stdout).trim().to_string();
-                    if !path.is_empty() {
-                        tx.send(Ok(path)).ok();
-                    } else {
-                        tx.send(Err("Cancelled".to_string())).ok();
-                    }
-                }
-                Err(e) => { tx.send(Err(e.to_string())).ok(); }
-            }
+            let _ = tx.send(ocr::recognize(&model.runtime.api_model_id, &path));
         });
-        self.file_picker_rx = Some(rx);
+        self.ocr_rx = Some(rx);
+        telemetry::record_feature("chat.ocr");
+        self.set_image_text_status(cx, "Reading the text in the image…");
+    }
+
+    fn poll_image_text(&mut self, cx: &mut Cx) {
+        let Some(rx) = &self.ocr_rx else { return };
+        let result = match rx.try_recv() {
+            Ok(result) => result,
+            Err(mpsc::TryRecvError::Empty) => return,
+            Err(mpsc::TryRecvError::Disconnected) => Err("Text extraction stopped unexpectedly.".to_string()),
+        };
+        self.ocr_rx = None;
+        match result {
+            Ok(text) => {
+                let status = format!("{} characters of text found; they are sent with your next message.", text.chars().count());
+                self.view.label(ids!(mode_controls.vlm_controls.vlm_ocr_text)).set_text(cx, &text);
+                self.vlm_image_text = Some(text);
+                self.set_image_text_status(cx, &status);
+            }
+            Err(e) => self.set_image_text_status(cx, &e),
+        }
+    }
+
+    /// Show the extraction's progress; Copy and Add to Prompt only with text
+    fn set_image_text_status(&mut self, cx: &mut Cx, status: &str) {
+        let has_text = self.vlm_image_text.is_some();
+        self.view.label(ids!(mode_controls.vlm_controls.vlm_ocr_row.vlm_ocr_status)).set_text(cx, status);
+        self.view.button(ids!(mode_controls.vlm_controls.vlm_ocr_row.vlm_ocr_copy_btn)).set_visible(cx, has_text);
+        self.view.button(ids!(mode_controls.vlm_controls.vlm_ocr_row.vlm_ocr_insert_btn)).set_visible(cx, has_text);
+        self.view.label(ids!(mode_controls.vlm_controls.vlm_ocr_text)).set_visible(cx, has_text);
+        self.view.redraw(cx);
+    }
+
+    /// A new or cleared image has none of the old one's text
+    fn forget_image_text(&mut self, cx: &mut Cx) {
+        self.vlm_image_text = None;
+        self.ocr_rx = None;
+        self.set_image_text_status(cx, "");
+    }
+
+    /// Put the image's text after the user's message, in the transcript and
+    /// in what is sent, so it stays in context for models without vision
+    fn attach_image_text(&mut self, cx: &mut Cx, user_text: String) -> String {
+        use moly_kit::aitk::protocol::EntityId;
+
+        if self.vlm_image_b64.is_none() { return user_text; }
+        let Some(text) = self.vlm_image_text.take() else { return user_text };
+        let name = std::path::Path::new(&self.vlm_image_path)
+            .file_name()
+            .map(|f| f.to_string_lossy().to_string())
+            .unwrap_or_else(|| "the image".to_string());
+        let combined = ocr::with_image_text(&user_text, &name, &text);
+        {
+            let mut ctrl = self.chat_controller.lock().unwrap();
+            let mut messages = ctrl.state().messages.clone();
+            if let Some(message) = messages.iter_mut().rev().find(|m| matches!(m.from, EntityId::User)) {
+                message.content.text = combined.clone();
+                ctrl.dispatch_mutation(VecMutation::Set(messages));
+            }
+        }
+        self.set_image_text_status(cx, "The image's text was sent with your message.");
+        combined
+    }
+
+    /// VLM: Open image file browser dialog
+    fn handle_vlm_browse(&mut self, _cx: &mut Cx) {
+        self.pick_file(FileDialog::new().add_filter("Image", IMAGE_EXTENSIONS));
+    }
+
+    /// ASR: Open file browser dialog
+    fn handle_asr_browse(&mut self, _cx: &mut Cx) {
+        self.pick_file(FileDialog::new().add_filter("Audio", AUDIO_EXTENSIONS));
+    }
+
+    /// Image Edit: Open file browser for reference image
+    fn handle_image_ref_browse(&mut self, _cx: &mut Cx) {
+        self.pick_file(FileDialog::new().add_filter("Image", IMAGE_EXTENSIONS));
     }
 
     /// Poll for file picker result
@@ -1857,17 +2928,7 @@ impl ChatApp {
 
             match self.chat_mode {
                 ChatMode::Vlm => {
-                    if let Ok(bytes) = std::fs::read(&path) {
-                        use base64::Engine;
-                        let b64 = base64::engine::general_purpose::STANDARD.encode(&bytes);
-                        self.vlm_image_b64 = Some(b64);
-                        self.vlm_image_path = path.clone();
-                        self.view.label(ids!(mode_controls.vlm_controls.vlm_file_row.vlm_file_label))
-                            .set_text(cx, &filename);
-                        let preview = self.view.image(ids!(mode_controls.vlm_controls.vlm_file_row.vlm_preview));
-                        preview.set_visible(cx, true);
-                        let _ = preview.load_image_file_by_path(cx, std::path::Path::new(&path));
-                    }
+                    self.set_vlm_image(cx, &path);
                 }
                 ChatMode::ImageGen => {
                     if let Ok(bytes) = std::fs::read(&path) {
@@ -1944,40 +3005,58 @@ impl ChatApp {
             .set_text(cx, "Transcribing...");
         self.view.redraw(cx);
 
-        let (tx, rx) = mpsc::channel();
-        std::thread::spawn(move || {
-            let result = (|| -> Result<String, String> {
-                // Convert non-WAV to WAV if needed
-                let wav_path = if !file_path.to_lowercase().ends_with(".wav") {
-                    let tmp = format!("/tmp/ominix_asr_{}.wav", std::process::id());
-                    let status = std::process::Command::new("afconvert")
-                        .args(["-f", "WAVE", "-d", "LEI16@16000", "-c", "1", &file_path, &tmp])
-                        .status()
-                        .map_err(|e| format!("afconvert failed: {}", e))?;
-                    if !status.success() {
-                        return Err("Audio conversion failed".to_string());
-                    }
-                    tmp
-                } else {
-                    file_path.clone()
-                };
+        let (punctuate, use_vocabulary) = self.asr_options();
+        let vocabulary = if use_vocabulary { asr_cleanup::current().vocabulary } else { Vec::new() };
+        self.run_mode_request(move || {
+            // Convert non-WAV to WAV if needed
+            let wav_path = if !file_path.to_lowercase().ends_with(".wav") {
+                let tmp = artifacts::unique_path("chat-asr", "wav");
+                platform::convert_audio(&file_path, &tmp, AudioFormat::Wav16kMono)
+                    .map_err(|e| format!("Audio conversion failed: {}", e))?;
+                tmp.to_string_lossy().to_string()
+            } else {
+                file_path.clone()
+            };
 
-                let client = reqwest::blocking::Client::builder()
-                    .timeout(std::time::Duration::from_secs(1800))
-                    .build()
-                    .map_err(|e| e.to_string())?;
-                let body = serde_json::json!({ "file": wav_path, "model": model_id });
-                let resp = client.post("http://localhost:8080/v1/audio/transcriptions")
-                    .json(&body)
-                    .send()
-                    .map_err(|e| e.to_string())?;
-                let json: serde_json::Value = resp.json().map_err(|e| e.to_string())?;
-                json["text"].as_str().map(|s| s.to_string())
-                    .ok_or_else(|| format!("Unexpected response: {}", json))
-            })();
-            tx.send(result).ok();
+            let request = TranscriptionRequest {
+                prompt: asr_cleanup::vocabulary_prompt(&vocabulary),
+                ..TranscriptionRequest::text(model_id, wav_path)
+            };
+            let transcript = OminiXApiClient::localhost(1800).transcribe(&request)?;
+            let text = asr_cleanup::apply_vocabulary(&transcript.text, &vocabulary);
+            if !punctuate || text.trim().is_empty() {
+                return Ok(text);
+            }
+            let Some(llm) = translate::loaded_llm(&ModelRegistry::load()) else {
+                return Ok(format!("{}\n\n_Punctuation wasn't restored: no language model is loaded._", text));
+            };
+            Ok(match asr_cleanup::punctuate(&llm, &text, &vocabulary) {
+                Ok(punctuated) => punctuated,
+                Err(e) => format!("{}\n\n_{}_", text, e),
+            })
         });
-        self.mode_rx = Some(rx);
+    }
+
+    /// ASR: whether this transcription restores punctuation and uses the
+    /// vocabulary, as switched in the ASR controls or else as in Settings
+    fn asr_options(&self) -> (bool, bool) {
+        let settings = asr_cleanup::current();
+        (
+            self.asr_punctuate.unwrap_or(settings.punctuate),
+            self.asr_vocabulary.unwrap_or(settings.use_vocabulary),
+        )
+    }
+
+    fn update_asr_options(&mut self, cx: &mut Cx) {
+        let (punctuate, vocabulary) = self.asr_options();
+        let has_vocabulary = !asr_cleanup::current().vocabulary.is_empty();
+        let punctuate_sel = if punctuate { 1.0_f64 } else { 0.0_f64 };
+        let vocabulary_sel = if vocabulary { 1.0_f64 } else { 0.0_f64 };
+        let row = self.view.view(ids!(mode_controls.asr_controls.asr_options_row));
+        row.view(ids!(asr_punctuate_btn)).apply_over(cx, live! { draw_bg: { selected: (punctuate_sel) } });
+        let vocabulary_btn = row.view(ids!(asr_vocabulary_btn));
+        vocabulary_btn.set_visible(cx, has_vocabulary);
+        vocabulary_btn.apply_over(cx, live! { draw_bg: { selected: (vocabulary_sel) } });
     }
 
     /// TTS: Start speech generation from the given text
@@ -1992,42 +3071,17 @@ impl ChatApp {
         let image_b64 = self.vlm_image_b64.clone();
         self.mode_busy = true;
 
-        let (tx, rx) = mpsc::channel();
-        std::thread::spawn(move || {
-            let result = (|| -> Result<String, String> {
-                let client = reqwest::blocking::Client::builder()
-                    .timeout(std::time::Duration::from_secs(120))
-                    .build()
-                    .map_err(|e| e.to_string())?;
-                let mut content = vec![serde_json::json!({"type": "text", "text": user_text})];
-                if let Some(b64) = image_b64 {
-                    content.push(serde_json::json!({
-                        "type": "image_url",
-                        "image_url": {"url": format!("data:image/jpeg;base64,{}", b64)}
-                    }));
-                }
-                let body = serde_json::json!({
-                    "model": model_id,
-                    "messages": [{"role": "user", "content": content}]
-                });
-                let resp = client.post("http://localhost:8080/v1/chat/completions")
-                    .json(&body)
-                    .send()
-                    .map_err(|e| e.to_string())?;
-                let status = resp.status();
-                let json: serde_json::Value = resp.json().map_err(|e| e.to_string())?;
-                if !status.is_success() {
-                    let err_msg = json["error"]["message"].as_str().unwrap_or("Unknown error");
-                    return Err(format!("API error {}: {}", status, err_msg));
-                }
-                json["choices"][0]["message"]["content"]
-                    .as_str()
-                    .map(|s| s.to_string())
-                    .ok_or_else(|| "No content in response".to_string())
-            })();
-            tx.send(result).ok();
+        let system_prompt = system_prompts::active();
+        self.run_mode_request(move || {
+            let message = match image_b64 {
+                Some(b64) => ChatMessage::user_with_image(user_text, &b64),
+                None => ChatMessage::user(user_text),
+            };
+            let mut messages: Vec<ChatMessage> = system_prompt.map(ChatMessage::system).into_iter().collect();
+            messages.push(message);
+            let request = ChatCompletionRequest { model: model_id, messages, max_tokens: chat_defaults::active().max_tokens };
+            OminiXApiClient::localhost(120).chat_completion(&request).map_err(String::from)
         });
-        self.mode_rx = Some(rx);
     }
 
     fn start_tts_generate(&mut self, _cx: &mut Cx, scope: &mut Scope, text: String) {
@@ -2038,30 +3092,43 @@ impl ChatApp {
         if text.is_empty() { return; }
 
         let voice = TTS_VOICE_IDS.get(self.tts_voice_idx)
-            .copied().unwrap_or("vivian").to_string();
+            .copied().unwrap_or("vivian");
+        // Mixed-language text is spoken a run of sentences at a time, each
+        // with its language's voice
+        let runs = tts_voices::current().runs(&text, voice);
+        let mut used: Vec<&str> = Vec::new();
+        for (voice, _) in &runs {
+            if !used.contains(&voice.as_str()) {
+                used.push(voice);
+            }
+        }
+        self.tts_voices_used = used.join(" + ");
+        let output = tts_output::current();
 
         self.mode_busy = true;
 
-        let (tx, rx) = mpsc::channel();
-        std::thread::spawn(move || {
-            let result = (|| -> Result<String, String> {
-                let client = reqwest::blocking::Client::builder()
-                    .timeout(std::time::Duration::from_secs(300))
-                    .build()
-                    .map_err(|e| e.to_string())?;
-                let body = serde_json::json!({"model": model_id, "input": text, "voice": voice});
-                let resp = client.post("http://localhost:8080/v1/audio/speech")
-                    .json(&body)
-                    .send()
-                    .map_err(|e| e.to_string())?;
-                let bytes = resp.bytes().map_err(|e| e.to_string())?;
-                let out_path = "/tmp/ominix-chat-tts.wav";
-                std::fs::write(out_path, &bytes).map_err(|e| e.to_string())?;
-                Ok(out_path.to_string())
-            })();
-            tx.send(result).ok();
+        self.run_mode_request(move || {
+            let client = OminiXApiClient::localhost(300);
+            let out_path = artifacts::unique_path("chat-tts", output.format.extension());
+            if let [(voice, text)] = runs.as_slice() {
+                let bytes = client.speech(&output.request(&model_id, voice, text))?;
+                std::fs::write(&out_path, &bytes).map_err(|e| e.to_string())?;
+                return Ok(out_path.to_string_lossy().to_string());
+            }
+            // Only WAV parts can be joined; convert the whole afterwards
+            let mut parts = Vec::with_capacity(runs.len());
+            for (voice, text) in &runs {
+                parts.push(client.speech(&output.wav_request(&model_id, voice, text))?);
+            }
+            let wav_path = artifacts::unique_path("chat-tts", "wav");
+            std::fs::write(&wav_path, audio::concat_wavs(&parts)?).map_err(|e| e.to_string())?;
+            if output.format == OutputFormat::Wav {
+                return Ok(wav_path.to_string_lossy().to_string());
+            }
+            output.format.save(&wav_path, &out_path)?;
+            artifacts::release(&wav_path);
+            Ok(out_path.to_string_lossy().to_string())
         });
-        self.mode_rx = Some(rx);
     }
 
     /// Image: Start generation from the given prompt
@@ -2076,50 +3143,20 @@ impl ChatApp {
 
         self.mode_busy = true;
 
-        let (tx, rx) = mpsc::channel();
-        std::thread::spawn(move || {
-            let result = (|| -> Result<String, String> {
-                let client = reqwest::blocking::Client::builder()
-                    .timeout(std::time::Duration::from_secs(600))
-                    .build()
-                    .map_err(|e| e.to_string())?;
-                let mut body = serde_json::json!({
-                    "model": model_id,
-                    "prompt": prompt,
-                    "n": 1,
-                    "size": "512x512",
-                    "response_format": "b64_json"
-                });
-                if !neg_prompt.is_empty() {
-                    body["negative_prompt"] = serde_json::Value::String(neg_prompt);
-                }
-                if let Some(ref_b64) = ref_image_b64 {
-                    body["image"] = serde_json::Value::String(ref_b64);
-                }
-                let resp = client.post("http://localhost:8080/v1/images/generations")
-                    .json(&body)
-                    .send()
-                    .map_err(|e| e.to_string())?;
-                let status = resp.status();
-                let resp_text = resp.text().map_err(|e| format!("reading response: {}", e))?;
-                if !status.is_success() {
-                    return Err(format!("API error {}: {}", status, &resp_text[..resp_text.len().min(500)]));
-                }
-                let json: serde_json::Value = serde_json::from_str(&resp_text)
-                    .map_err(|e| format!("parsing JSON: {} (first 200 chars: {})", e, &resp_text[..resp_text.len().min(200)]))?;
-                let b64 = json["data"][0]["b64_json"].as_str()
-                    .ok_or_else(|| format!("Unexpected response: {}", json))?;
-                use base64::Engine;
-                let bytes = base64::engine::general_purpose::STANDARD.decode(b64)
-                    .map_err(|e| e.to_string())?;
-                let slug = model_id.replace('/', "-").replace(' ', "_");
-                let path = format!("/tmp/ominix-chat-{}.png", slug);
-                std::fs::write(&path, &bytes).map_err(|e| e.to_string())?;
-                Ok(path)
-            })();
-            tx.send(result).ok();
+        self.run_mode_request(move || {
+            let request = ImageGenerationRequest {
+                negative_prompt: Some(neg_prompt).filter(|n| !n.is_empty()),
+                image: ref_image_b64,
+                ..ImageGenerationRequest::new(model_id.clone(), prompt)
+            };
+            let bytes = OminiXApiClient::localhost(600)
+                .generate_image(&request)
+                .and_then(|resp| resp.first_bytes())?;
+            let slug = model_id.replace('/', "-").replace(' ', "_");
+            let path = artifacts::unique_path(&format!("chat-{}", slug), "png");
+            std::fs::write(&path, &bytes).map_err(|e| e.to_string())?;
+            Ok(path.to_string_lossy().to_string())
         });
-        self.mode_rx = Some(rx);
     }
 
     /// Video: Start generation from the given prompt
@@ -2132,42 +3169,36 @@ impl ChatApp {
 
         self.mode_busy = true;
 
+        self.run_mode_request(move || {
+            let request = VideoGenerationRequest {
+                model: model_id.clone(),
+                prompt,
+                response_format: "b64_json".to_string(),
+            };
+            let bytes = OminiXApiClient::localhost(1800)
+                .generate_video(&request)
+                .and_then(|resp| resp.first_bytes())?;
+            let slug = model_id.replace('/', "-").replace(' ', "_");
+            let path = artifacts::unique_path(&format!("chat-video-{}", slug), "mp4");
+            std::fs::write(&path, &bytes).map_err(|e| e.to_string())?;
+            Ok(path.to_string_lossy().to_string())
+        });
+    }
+
+    /// Run a local-model request off the UI thread; `poll_mode_result`
+    /// picks up the result. The browser build has no threads and no local
+    /// runtime, so there it fails right away.
+    fn run_mode_request(&mut self, request: impl FnOnce() -> Result<String, String> + Send + 'static) {
         let (tx, rx) = mpsc::channel();
+        #[cfg(not(target_arch = "wasm32"))]
         std::thread::spawn(move || {
-            let result = (|| -> Result<String, String> {
-                let client = reqwest::blocking::Client::builder()
-                    .timeout(std::time::Duration::from_secs(1800))
-                    .build()
-                    .map_err(|e| e.to_string())?;
-                let body = serde_json::json!({
-                    "model": model_id,
-                    "prompt": prompt,
-                    "n": 1,
-                    "response_format": "b64_json"
-                });
-                let resp = client.post("http://localhost:8080/v1/videos/generations")
-                    .json(&body)
-                    .send()
-                    .map_err(|e| e.to_string())?;
-                let status = resp.status();
-                let resp_text = resp.text().map_err(|e| format!("reading response: {}", e))?;
-                if !status.is_success() {
-                    return Err(format!("API error {}: {}", status, &resp_text[..resp_text.len().min(500)]));
-                }
-                let json: serde_json::Value = serde_json::from_str(&resp_text)
-                    .map_err(|e| format!("parsing JSON: {} (first 200 chars: {})", e, &resp_text[..resp_text.len().min(200)]))?;
-                let b64 = json["data"][0]["b64_json"].as_str()
-                    .ok_or_else(|| format!("Unexpected response: {}", json))?;
-                use base64::Engine;
-                let bytes = base64::engine::general_purpose::STANDARD.decode(b64)
-                    .map_err(|e| e.to_string())?;
-                let slug = model_id.replace('/', "-").replace(' ', "_");
-                let path = format!("/tmp/ominix-chat-video-{}.mp4", slug);
-                std::fs::write(&path, &bytes).map_err(|e| e.to_string())?;
-                Ok(path)
-            })();
-            tx.send(result).ok();
+            tx.send(request()).ok();
         });
+        #[cfg(target_arch = "wasm32")]
+        {
+            drop(request);
+            tx.send(Err(moly_data::platform::NO_LOCAL_RUNTIME.to_string())).ok();
+        }
         self.mode_rx = Some(rx);
     }
 
@@ -2241,7 +3272,8 @@ impl ChatApp {
         for provider in &enabled_providers {
             let api_key = provider.api_key.clone().unwrap_or_default();
             let api_key = api_key.trim().to_string();
-            if api_key.is_empty() {
+            // Ollama is a local daemon and needs no key
+            if api_key.is_empty() && provider.provider_type != moly_data::ProviderType::Ollama {
                 ::log::warn!("API key is empty for provider {}", provider.id);
                 continue;
             }
@@ -2522,8 +3554,9 @@ impl ChatApp {
     /// Returns only bots that are either:
     /// 1. Not in the provider's models list (default to enabled)
     /// 2. Explicitly enabled in the provider's models list
+    /// Models favorited in the Settings catalog browser are moved to the front.
     fn filter_enabled_bots(all_bots: &[Bot], store: &Store) -> Vec<Bot> {
-        all_bots.iter()
+        let mut bots: Vec<Bot> = all_bots.iter()
             .filter(|bot| {
                 // Find which provider this bot belongs to
                 let provider_id = store.providers_manager.get_provider_for_bot(&bot.id);
@@ -2550,7 +3583,16 @@ impl ChatApp {
                 true
             })
             .cloned()
-            .collect()
+            .collect();
+
+        // Stable sort keeps provider order within favorites / non-favorites
+        bots.sort_by_key(|bot| {
+            let is_favorite = store.providers_manager.get_provider_for_bot(&bot.id)
+                .and_then(|pid| store.preferences.get_provider(&pid.to_string()))
+                .map_or(false, |p| p.is_favorite_model(bot.id.id()) || p.is_favorite_model(&bot.name));
+            !is_favorite
+        });
+        bots
     }
 
     /// Restore the saved model selection from preferences
@@ -2747,13 +3789,21 @@ impl ChatApp {
         let Some(store) = scope.data.get::<Store>() else { return };
 
         // Check if current provider supports and has A2UI enabled
-        let a2ui_available = if let Some(ref provider_id) = self.current_provider_id {
+        let provider_ready = if let Some(ref provider_id) = self.current_provider_id {
             store.preferences.get_provider(provider_id)
                 .map(|p| p.is_a2ui_ready())
                 .unwrap_or(false)
         } else {
             false
         };
+        // ...and the chat's tool selection includes it
+        let a2ui_available = provider_ready && self.chat_tools(store).has_builtin(BuiltinTool::A2ui);
+
+        if !a2ui_available {
+            if let Some(ref a2ui_client) = self.a2ui_client {
+                a2ui_client.set_a2ui_enabled(false);
+            }
+        }
 
         // Update the PromptInput in the Chat widget
         let mut chat = self.view.chat(ids!(main_content.chat));
@@ -2766,4 +3816,1881 @@ impl ChatApp {
 
         ::log::info!("A2UI toggle visibility updated: available={}", a2ui_available);
     }
+
+    // ── Bookmarks ──────────────────────────────────────────────────────
+
+    /// Offer the chat's recent messages for bookmarking, newest first
+    fn open_bookmark_picker(&mut self, cx: &mut Cx, scope: &mut Scope) {
+        use moly_kit::aitk::protocol::EntityId;
+
+        let Some(store) = scope.data.get::<Store>() else { return };
+        let Some(chat) = self.current_chat_id.and_then(|id| store.chats.get_chat_by_id(id)) else { return };
+        let choices: Vec<usize> = chat.messages.iter().enumerate().rev()
+            .filter(|(_, m)| !m.content.text.trim().is_empty())
+            .map(|(i, _)| i)
+            .take(BOOKMARK_CHOICES)
+            .collect();
+        if choices.is_empty() {
+            self.view.moly_dialog(ids!(dialog)).open(cx, DialogRequest::confirm(live_id!(bookmark_empty),
+                "Bookmark a Message", "This session has no messages to bookmark yet."));
+            return;
+        }
+        let labels = choices.iter().map(|&i| {
+            let message = &chat.messages[i];
+            let marked = if chat.bookmarks.iter().any(|b| b.message_index == i) { "★ " } else { "" };
+            let who = if matches!(message.from, EntityId::User) { "You" } else { "Answer" };
+            format!("{}{}: {}", marked, who, bookmarks::excerpt(&message.content.text, 70))
+        }).collect();
+        self.view.moly_dialog(ids!(dialog)).open(cx,
+            DialogRequest::select(live_id!(bookmark_message), "Bookmark a Message",
+                "Bookmarked messages are listed on the Bookmarks page. ★ marks those already bookmarked.", labels, 0)
+                .confirm_label("Next"));
+        self.bookmark_choices = choices;
+    }
+
+    // ── Jump to a day, message links ───────────────────────────────────
+
+    /// Offer the days of the chat, to scroll to the first message of one
+    fn open_jump_picker(&mut self, cx: &mut Cx, scope: &mut Scope) {
+        let Some(store) = scope.data.get::<Store>() else { return };
+        let Some(chat) = self.current_chat_id.and_then(|id| store.chats.get_chat_by_id(id)) else { return };
+        let sections = chat_navigation::chat_days(chat);
+        if sections.is_empty() {
+            self.view.moly_dialog(ids!(dialog)).open(cx, DialogRequest::confirm(live_id!(jump_empty),
+                "Jump to a Day", "This session has no messages yet."));
+            return;
+        }
+        let today = chat_navigation::today();
+        let labels = sections.iter().map(|section| format!(
+            "{} · {} message{}",
+            chat_navigation::day_label(section.date, today),
+            section.message_count,
+            if section.message_count == 1 { "" } else { "s" }
+        )).collect();
+        self.view.moly_dialog(ids!(dialog)).open(cx,
+            DialogRequest::select(live_id!(jump_to_day), "Jump to a Day",
+                "Scrolls to the first message sent that day.", labels, sections.len() - 1)
+                .confirm_label("Jump"));
+        self.jump_choices = sections.iter().map(|section| section.message_index).collect();
+    }
+
+    /// Offer the message at the top of the transcript and the latest ones,
+    /// to copy a link to one
+    fn open_link_picker(&mut self, cx: &mut Cx, scope: &mut Scope) {
+        use moly_kit::aitk::protocol::EntityId;
+
+        let Some(store) = scope.data.get::<Store>() else { return };
+        let Some(chat) = self.current_chat_id.and_then(|id| store.chats.get_chat_by_id(id)) else { return };
+        let top = self.view.portal_list(ids!(main_content.chat.messages.list)).first_id();
+        let mut choices: Vec<usize> = Vec::new();
+        for i in std::iter::once(top).chain((0..chat.messages.len()).rev()) {
+            let Some(message) = chat.messages.get(i) else { continue };
+            if !message.content.text.trim().is_empty() && !choices.contains(&i) {
+                choices.push(i);
+            }
+            if choices.len() >= BOOKMARK_CHOICES { break; }
+        }
+        if choices.is_empty() {
+            self.view.moly_dialog(ids!(dialog)).open(cx, DialogRequest::confirm(live_id!(link_empty),
+                "Copy a Message Link", "This session has no messages to link to yet."));
+            return;
+        }
+        let labels = choices.iter().map(|&i| {
+            let message = &chat.messages[i];
+            let who = if matches!(message.from, EntityId::User) { "You" } else { "Answer" };
+            format!("{}: {}", who, bookmarks::excerpt(&message.content.text, 70))
+        }).collect();
+        self.view.moly_dialog(ids!(dialog)).open(cx,
+            DialogRequest::select(live_id!(message_link), "Copy a Message Link",
+                "The first message is the one at the top of the view; the others are the latest.", labels, 0)
+                .confirm_label("Copy Link"));
+        self.link_choices = choices;
+    }
+
+    /// Scroll the transcript so a message is at the top
+    fn scroll_to_message_index(&mut self, cx: &mut Cx, index: usize) {
+        let count = self.chat_controller.lock().unwrap().state().messages.len();
+        if index < count {
+            self.view.portal_list(ids!(main_content.chat.messages.list)).set_first_id_and_scroll(index, 0.0);
+            self.view.redraw(cx);
+        }
+    }
+
+    // ── Answer versions ────────────────────────────────────────────────
+
+    /// Offer the chat's regenerated answers for comparison, latest first
+    fn open_versions_picker(&mut self, cx: &mut Cx, scope: &mut Scope) {
+        let Some(store) = scope.data.get::<Store>() else { return };
+        let Some(chat) = self.current_chat_id.and_then(|id| store.chats.get_chat_by_id(id)) else { return };
+        let mut choices: Vec<usize> = chat.answer_versions.iter()
+            .filter(|v| !v.previous.is_empty() && v.message_index < chat.messages.len())
+            .map(|v| v.message_index)
+            .collect();
+        choices.sort_unstable_by(|a, b| b.cmp(a));
+        choices.truncate(VERSION_CHOICES);
+        match choices.as_slice() {
+            [] => {
+                self.view.moly_dialog(ids!(dialog)).open(cx, DialogRequest::confirm(live_id!(versions_empty),
+                    "Answer Versions", "No answer in this session has been regenerated yet."));
+            }
+            [index] => self.show_versions(cx, scope, *index),
+            _ => {
+                let labels = choices.iter().map(|&i| {
+                    let count = store.chats.answer_versions(chat.id, i).len();
+                    format!("{} ({} earlier)", bookmarks::excerpt(&chat.messages[i].content.text, 60), count)
+                }).collect();
+                self.view.moly_dialog(ids!(dialog)).open(cx,
+                    DialogRequest::select(live_id!(answer_versions), "Answer Versions",
+                        "Compare a regenerated answer with its earlier versions.", labels, 0)
+                        .confirm_label("Compare"));
+                self.version_choices = choices;
+            }
+        }
+    }
+
+    /// Compare an answer with the version it replaced last
+    fn show_versions(&mut self, cx: &mut Cx, scope: &mut Scope, message_index: usize) {
+        let Some(chat_id) = self.current_chat_id else { return };
+        let count = scope.data.get::<Store>()
+            .map(|store| store.chats.answer_versions(chat_id, message_index).len())
+            .unwrap_or(0);
+        if count == 0 {
+            return;
+        }
+        self.versions_shown = Some((chat_id, message_index, count - 1));
+        self.update_versions_panel(cx, scope);
+    }
+
+    /// Show the compared versions, or hide the panel if there are none
+    fn update_versions_panel(&mut self, cx: &mut Cx, scope: &mut Scope) {
+        let panel = self.view.view(ids!(versions_panel));
+        let texts = self.versions_shown
+            .filter(|(chat_id, ..)| Some(*chat_id) == self.current_chat_id)
+            .and_then(|(chat_id, message_index, version)| {
+                let store = scope.data.get::<Store>()?;
+                let current = store.chats.get_chat_by_id(chat_id)?.messages.get(message_index)?.content.text.clone();
+                let versions = store.chats.answer_versions(chat_id, message_index);
+                Some((current, versions.get(version)?.clone(), version, versions.len()))
+            });
+        let Some((current, previous, version, count)) = texts else {
+            self.versions_shown = None;
+            panel.set_visible(cx, false);
+            return;
+        };
+        panel.set_visible(cx, true);
+
+        let writing = self.chat_controller.lock().unwrap().state().messages.iter().any(|m| m.metadata.is_writing);
+        let status = if writing {
+            "The answer is being regenerated…".to_string()
+        } else if self.versions_side_by_side {
+            format!("Earlier answer {} of {} next to the current one", version + 1, count)
+        } else {
+            format!("Earlier answer {} of {} → current   [-removed-] {{+added+}}", version + 1, count)
+        };
+        self.view.label(ids!(versions_panel.versions_status)).set_text(cx, &status);
+        self.view.button(ids!(versions_panel.versions_older_button)).set_visible(cx, version > 0);
+        self.view.button(ids!(versions_panel.versions_newer_button)).set_visible(cx, version + 1 < count);
+        self.view.button(ids!(versions_panel.versions_keep_button)).set_visible(cx, !writing);
+        self.view.button(ids!(versions_panel.versions_layout_button))
+            .set_text(cx, if self.versions_side_by_side { "Inline" } else { "Side by side" });
+
+        self.view.view(ids!(versions_panel.versions_inline)).set_visible(cx, !self.versions_side_by_side);
+        self.view.view(ids!(versions_panel.versions_side_by_side)).set_visible(cx, self.versions_side_by_side);
+        if writing {
+            self.view.label(ids!(versions_panel.versions_diff_label)).set_text(cx, "");
+            self.view.label(ids!(versions_panel.versions_current_label)).set_text(cx, "");
+        } else if self.versions_side_by_side {
+            self.view.label(ids!(versions_panel.versions_previous_title))
+                .set_text(cx, &format!("Earlier answer {}", version + 1));
+            self.view.label(ids!(versions_panel.versions_previous_label)).set_text(cx, &previous);
+            self.view.label(ids!(versions_panel.versions_current_label)).set_text(cx, &current);
+        } else {
+            let diff = answer_versions::marked(&answer_versions::diff_words(&previous, &current));
+            self.view.label(ids!(versions_panel.versions_diff_label)).set_text(cx, &diff);
+        }
+        self.view.redraw(cx);
+    }
+
+    fn handle_versions_controls(&mut self, cx: &mut Cx, scope: &mut Scope, actions: &Actions) {
+        let Some((chat_id, message_index, version)) = self.versions_shown else { return };
+        if self.view.button(ids!(versions_panel.versions_older_button)).clicked(actions) {
+            self.versions_shown = Some((chat_id, message_index, version.saturating_sub(1)));
+            self.update_versions_panel(cx, scope);
+        }
+        if self.view.button(ids!(versions_panel.versions_newer_button)).clicked(actions) {
+            self.versions_shown = Some((chat_id, message_index, version + 1));
+            self.update_versions_panel(cx, scope);
+        }
+        if self.view.button(ids!(versions_panel.versions_layout_button)).clicked(actions) {
+            self.versions_side_by_side = !self.versions_side_by_side;
+            self.update_versions_panel(cx, scope);
+        }
+        if self.view.button(ids!(versions_panel.versions_keep_button)).clicked(actions) {
+            let Some(store) = scope.data.get_mut::<Store>() else { return };
+            if let Some(messages) = store.chats.keep_answer_version(chat_id, message_index, version) {
+                // The replaced answer is now the newest earlier version
+                let count = store.chats.answer_versions(chat_id, message_index).len();
+                self.chat_controller.lock().unwrap().dispatch_mutation(VecMutation::Set(messages));
+                self.versions_shown = Some((chat_id, message_index, count - 1));
+                self.update_versions_panel(cx, scope);
+            }
+        }
+        if self.view.button(ids!(versions_panel.versions_close_button)).clicked(actions) {
+            self.versions_shown = None;
+            self.view.view(ids!(versions_panel)).set_visible(cx, false);
+        }
+    }
+
+    /// Close the versions and sources panels when another chat is shown
+    fn reset_answer_panels(&mut self, cx: &mut Cx, scope: &mut Scope) {
+        self.regeneration_watch.reset();
+        self.answer_timer.reset();
+        self.update_answer_stats(cx, scope);
+        self.update_versions_panel(cx, scope);
+        self.sources_shown.clear();
+        self.view.view(ids!(sources_panel)).set_visible(cx, false);
+        if self.find_open {
+            self.refresh_find(cx, false);
+        }
+    }
+
+    // ── Answer stats ───────────────────────────────────────────────────
+
+    /// Figures for an answer that just finished: who answered, how long it
+    /// took, and the estimated tokens and cost
+    fn answer_stats(store: &Store, chat_id: ChatId, messages: &[Message], timing: AnswerTiming) -> Option<MessageStats> {
+        use moly_kit::aitk::protocol::EntityId;
+        let answer = messages.get(timing.message_index)?;
+        let EntityId::Bot(bot_id) = &answer.from else { return None };
+        let model = store.providers_manager.get_all_bots().iter()
+            .find(|bot| &bot.id == bot_id)
+            .map(|bot| bot.name.clone())
+            .unwrap_or_else(|| bot_id.id().to_string());
+        let provider_id = store.providers_manager.get_provider_for_bot(bot_id).map(str::to_string);
+        let provider = provider_id.as_ref().and_then(|id| store.preferences.get_provider(id));
+        let provider_name = match (&provider, &provider_id) {
+            (Some(provider), _) => provider.name.clone(),
+            (None, Some(id)) => Self::get_provider_display_name(id).to_string(),
+            (None, None) => String::new(),
+        };
+
+        let system_prompt = store.chats.get_chat_by_id(chat_id).and_then(|c| c.system_prompt.clone()).unwrap_or_default();
+        let prompt_tokens = message_stats::estimate_tokens(&system_prompt)
+            + messages[..timing.message_index].iter().map(|m| message_stats::estimate_tokens(&m.content.text)).sum::<u32>();
+        let completion_tokens = message_stats::estimate_tokens(&answer.content.text);
+        let cost_usd = provider
+            .and_then(|p| p.catalog_model(bot_id.id()))
+            .and_then(|m| message_stats::cost(m, prompt_tokens, completion_tokens));
+        Some(MessageStats {
+            message_index: timing.message_index,
+            provider: provider_name,
+            model,
+            first_token_ms: timing.first_token.map(|d| d.as_millis() as u64),
+            total_ms: timing.total.as_millis() as u64,
+            prompt_tokens,
+            completion_tokens,
+            cost_usd,
+        })
+    }
+
+    /// Show the latest answer's figures in the footer, and every answer's
+    /// when it is open
+    fn update_answer_stats(&mut self, cx: &mut Cx, scope: &mut Scope) {
+        let panel = self.view.view(ids!(answer_stats_panel));
+        let Some(store) = scope.data.get::<Store>() else { return };
+        let Some(chat) = self.current_chat_id.and_then(|id| store.chats.get_chat_by_id(id)) else {
+            panel.set_visible(cx, false);
+            return;
+        };
+        let stats = store.chats.message_stats(chat.id);
+        let Some(latest) = stats.last() else {
+            panel.set_visible(cx, false);
+            return;
+        };
+        panel.set_visible(cx, true);
+        self.view.label(ids!(answer_stats_panel.answer_stats_label)).set_text(cx, &latest.summary());
+        self.view.button(ids!(answer_stats_panel.answer_stats_details_button))
+            .set_text(cx, if self.answer_stats_open { "Hide" } else { "Details" });
+        self.view.view(ids!(answer_stats_panel.answer_stats_list)).set_visible(cx, self.answer_stats_open);
+        if self.answer_stats_open {
+            let mut lines = vec![message_stats::totals(stats)];
+            let mut sorted: Vec<&MessageStats> = stats.iter().collect();
+            sorted.sort_by_key(|s| s.message_index);
+            for s in sorted {
+                let excerpt = chat.messages.get(s.message_index)
+                    .map(|m| bookmarks::excerpt(&m.content.text, 40))
+                    .unwrap_or_default();
+                lines.push(format!("“{}” — {}", excerpt, s.summary()));
+            }
+            self.view.label(ids!(answer_stats_panel.answer_stats_list.answer_stats_list_label)).set_text(cx, &lines.join("\n"));
+        }
+        self.view.redraw(cx);
+    }
+
+    fn handle_answer_stats_controls(&mut self, cx: &mut Cx, scope: &mut Scope, actions: &Actions) {
+        if self.view.button(ids!(answer_stats_panel.answer_stats_details_button)).clicked(actions) {
+            self.answer_stats_open = !self.answer_stats_open;
+            self.update_answer_stats(cx, scope);
+        }
+    }
+
+    // ── Sources ────────────────────────────────────────────────────────
+
+    /// Offer the chat's answers that cite sources, latest first
+    fn open_sources_picker(&mut self, cx: &mut Cx, scope: &mut Scope) {
+        let Some(store) = scope.data.get::<Store>() else { return };
+        let Some(chat) = self.current_chat_id.and_then(|id| store.chats.get_chat_by_id(id)) else { return };
+        let mut choices: Vec<usize> = chat.sources.iter()
+            .filter(|s| !s.sources.is_empty() && s.message_index < chat.messages.len())
+            .map(|s| s.message_index)
+            .collect();
+        choices.sort_unstable_by(|a, b| b.cmp(a));
+        choices.truncate(BOOKMARK_CHOICES);
+        match choices.as_slice() {
+            [] => {
+                self.view.moly_dialog(ids!(dialog)).open(cx, DialogRequest::confirm(live_id!(sources_empty),
+                    "Sources", "No answer in this session cites sources yet. Agent answers list the files \
+                    and pages their tools looked up."));
+            }
+            [index] => self.show_sources(cx, scope, *index),
+            _ => {
+                let labels = choices.iter().map(|&i| {
+                    let count = store.chats.message_sources(chat.id, i).len();
+                    format!("{} ({} sources)", bookmarks::excerpt(&chat.messages[i].content.text, 60), count)
+                }).collect();
+                self.view.moly_dialog(ids!(dialog)).open(cx,
+                    DialogRequest::select(live_id!(answer_sources), "Sources",
+                        "Show the sources an answer cites.", labels, 0)
+                        .confirm_label("Show"));
+                self.source_choices = choices;
+            }
+        }
+    }
+
+    /// List the sources of an answer in the sources panel
+    fn show_sources(&mut self, cx: &mut Cx, scope: &mut Scope, message_index: usize) {
+        let Some(chat_id) = self.current_chat_id else { return };
+        let Some(store) = scope.data.get::<Store>() else { return };
+        self.sources_shown = store.chats.message_sources(chat_id, message_index).to_vec();
+        if self.sources_shown.is_empty() {
+            return;
+        }
+
+        self.view.label(ids!(sources_panel.sources_status)).set_text(cx,
+            &format!("{} sources — hover for a preview, click to open", self.sources_shown.len()));
+        for index in 0..SOURCE_ROWS {
+            let row = self.source_row(index);
+            let Some(source) = self.sources_shown.get(index) else {
+                row.set_visible(cx, false);
+                continue;
+            };
+            row.set_visible(cx, true);
+            row.label(ids!(number_label)).set_text(cx, &format!("[{}]", source.number));
+            row.label(ids!(title_label)).set_text(cx, &source.title);
+            row.label(ids!(location_label)).set_text(cx, &source.location);
+            row.label(ids!(preview_label)).set_text(cx, &source.excerpt);
+            row.label(ids!(preview_label)).set_visible(cx, false);
+        }
+        let more = self.sources_shown.len().saturating_sub(SOURCE_ROWS);
+        let more_text = if more > 0 { format!("{} more listed below the answer", more) } else { String::new() };
+        self.view.label(ids!(sources_panel.sources_more)).set_text(cx, &more_text);
+        self.view.view(ids!(sources_panel)).set_visible(cx, true);
+        self.view.redraw(cx);
+    }
+
+    fn source_row(&self, index: usize) -> WidgetRef {
+        match index {
+            0 => self.view.widget(ids!(sources_panel.source_0)),
+            1 => self.view.widget(ids!(sources_panel.source_1)),
+            2 => self.view.widget(ids!(sources_panel.source_2)),
+            3 => self.view.widget(ids!(sources_panel.source_3)),
+            4 => self.view.widget(ids!(sources_panel.source_4)),
+            5 => self.view.widget(ids!(sources_panel.source_5)),
+            6 => self.view.widget(ids!(sources_panel.source_6)),
+            _ => self.view.widget(ids!(sources_panel.source_7)),
+        }
+    }
+
+    /// Show a source's preview while the pointer is over it
+    fn handle_source_hover(&mut self, cx: &mut Cx, event: &Event) {
+        for index in 0..self.sources_shown.len().min(SOURCE_ROWS) {
+            let row = self.source_row(index);
+            match event.hits(cx, row.area()) {
+                Hit::FingerHoverIn(_) => {
+                    row.label(ids!(preview_label)).set_visible(cx, true);
+                    self.view.redraw(cx);
+                }
+                Hit::FingerHoverOut(_) => {
+                    row.label(ids!(preview_label)).set_visible(cx, false);
+                    self.view.redraw(cx);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn handle_sources_controls(&mut self, cx: &mut Cx, actions: &Actions) {
+        if self.sources_shown.is_empty() {
+            return;
+        }
+        if self.view.button(ids!(sources_panel.sources_close_button)).clicked(actions) {
+            self.sources_shown.clear();
+            self.view.view(ids!(sources_panel)).set_visible(cx, false);
+            return;
+        }
+        for index in 0..self.sources_shown.len().min(SOURCE_ROWS) {
+            if self.source_row(index).as_view().finger_down(actions).is_some() {
+                let location = &self.sources_shown[index].location;
+                if let Err(e) = platform::open_path(location) {
+                    ::log::warn!("Could not open source {}: {}", location, e);
+                }
+            }
+        }
+    }
+
+    // ── Prompt drafts and recall ───────────────────────────────────────
+
+    /// Text field of whichever prompt is on screen
+    fn prompt_text_input(&self) -> TextInputRef {
+        if self.in_welcome_mode {
+            self.view.text_input(ids!(main_content.welcome_overlay.welcome_prompt.text_input))
+        } else {
+            self.view.text_input(ids!(main_content.chat.prompt.text_input))
+        }
+    }
+
+    /// Store what is typed in the prompt on the current chat
+    fn save_draft(&mut self, scope: &mut Scope) {
+        self.draft_timer = Timer::empty();
+        if let Some(store) = scope.data.get_mut::<Store>() {
+            self.flush_draft(store);
+        }
+    }
+
+    /// Put the current chat's unsent draft back in the prompt
+    fn restore_draft(&mut self, cx: &mut Cx, scope: &mut Scope) {
+        self.prompt_recall.reset();
+        let draft = scope.data.get::<Store>()
+            .and_then(|store| self.current_chat_id.and_then(|id| store.chats.get_chat_by_id(id)))
+            .map(|chat| chat.draft.clone())
+            .unwrap_or_default();
+        self.view.text_input(ids!(main_content.welcome_overlay.welcome_prompt.text_input)).set_text(cx, "");
+        self.view.text_input(ids!(main_content.chat.prompt.text_input)).set_text(cx, "");
+        self.prompt_text_input().set_text(cx, &draft);
+    }
+
+    /// Up/Down in the focused prompt steps through the chat's sent prompts
+    fn recall_prompt(&mut self, cx: &mut Cx, scope: &mut Scope, older: bool) {
+        let input = self.prompt_text_input();
+        if !cx.has_key_focus(input.area()) {
+            return;
+        }
+        let Some(store) = scope.data.get::<Store>() else { return };
+        let Some(chat) = self.current_chat_id.and_then(|id| store.chats.get_chat_by_id(id)) else { return };
+        let text = input.text();
+        let recalled = if older {
+            self.prompt_recall.older(&chat.sent_prompts, &text)
+        } else {
+            self.prompt_recall.newer(&chat.sent_prompts, &text)
+        };
+        if let Some(recalled) = recalled {
+            input.set_text(cx, &recalled);
+            self.view.redraw(cx);
+        }
+    }
+
+    // ── Tools ──────────────────────────────────────────────────────────
+
+    /// The tools the current chat exposes, limited to what is still set up
+    fn chat_tools(&self, store: &Store) -> ToolSelection {
+        let servers = &store.preferences.mcp_servers;
+        self.current_chat_id
+            .and_then(|id| store.chats.get_chat_by_id(id))
+            .and_then(|chat| chat.tools.as_ref())
+            .map(|tools| tools.available(servers))
+            .unwrap_or_else(|| ToolSelection::all(servers))
+    }
+
+    /// Mirror the current chat's tools for tool clients and offer A2UI only
+    /// if it is among them
+    fn apply_chat_tools(&mut self, cx: &mut Cx, scope: &mut Scope) {
+        let Some(store) = scope.data.get::<Store>() else { return };
+        let tools = self.chat_tools(store);
+        ::log::info!("Chat {:?} exposes {} tools", self.current_chat_id, tools.len());
+        toolsets::set_active(self.current_chat_id, &tools);
+        self.update_a2ui_toggle_visibility(cx, scope);
+    }
+
+    /// Store a tool selection on the current chat and apply it
+    fn set_chat_tools(&mut self, cx: &mut Cx, scope: &mut Scope, tools: ToolSelection) {
+        let Some(chat_id) = self.current_chat_id else { return };
+        if let Some(store) = scope.data.get_mut::<Store>() {
+            store.chats.update_chat_tools(chat_id, Some(tools));
+        }
+        self.apply_chat_tools(cx, scope);
+        self.view.redraw(cx);
+    }
+
+    // ── System prompt ──────────────────────────────────────────────────
+
+    /// Mirror the current chat's system prompt, sampling and privacy filter
+    /// choices for the chat clients
+    fn apply_chat_settings(&self, scope: &mut Scope) {
+        let Some(store) = scope.data.get::<Store>() else { return };
+        let chat = self.current_chat_id.and_then(|id| store.chats.get_chat_by_id(id));
+        system_prompts::set_active(chat.and_then(|chat| chat.system_prompt.as_deref()));
+        chat_defaults::set_active(chat.map(|chat| chat.sampling).unwrap_or_default());
+        privacy_filter::set_active(&chat.map(|chat| chat.privacy.clone()).unwrap_or_default());
+    }
+
+    /// Edit the temperature and answer length of the current chat
+    fn open_sampling(&mut self, cx: &mut Cx, scope: &mut Scope) {
+        let Some(store) = scope.data.get::<Store>() else { return };
+        let sampling = self.current_chat_id
+            .and_then(|id| store.chats.get_chat_by_id(id))
+            .map(|chat| chat.sampling)
+            .unwrap_or_default();
+        self.view.moly_dialog(ids!(dialog)).open(cx,
+            DialogRequest::text_input(live_id!(sampling), "Sampling",
+                "Temperature and the longest answer in tokens, separated by a comma. Leave either \
+                 empty for the model's own; defaults for new chats are in each provider's Settings.",
+                sampling.display())
+                .placeholder("e.g. 0.7, 1024"));
+    }
+
+    /// Edit the system prompt of the current chat
+    fn open_system_prompt(&mut self, cx: &mut Cx, scope: &mut Scope) {
+        let Some(store) = scope.data.get::<Store>() else { return };
+        let prompt = self.current_chat_id
+            .and_then(|id| store.chats.get_chat_by_id(id))
+            .and_then(|chat| chat.system_prompt.clone())
+            .unwrap_or_default();
+        self.view.moly_dialog(ids!(dialog)).open(cx,
+            DialogRequest::text_input(live_id!(system_prompt), "System Prompt",
+                "Sent to the model ahead of this chat. Leave empty for none; \
+                 defaults for chats opened from the Model Hub are in Settings.", prompt)
+                .placeholder("e.g. You are a patient maths tutor."));
+    }
+
+    // ── Notes ──────────────────────────────────────────────────────────
+
+    fn toggle_notes(&mut self, cx: &mut Cx, scope: &mut Scope) {
+        self.save_notes(scope);
+        self.notes_open = !self.notes_open;
+        self.view.view(ids!(notes_panel)).set_visible(cx, self.notes_open);
+        self.view.button(ids!(notes_button)).set_text(cx, if self.notes_open { "Hide Notes" } else { "Notes" });
+        self.restore_notes(cx, scope);
+        self.view.redraw(cx);
+    }
+
+    /// Store the notes panel's text on the current chat
+    fn save_notes(&mut self, scope: &mut Scope) {
+        self.notes_timer = Timer::empty();
+        if !self.notes_open {
+            return;
+        }
+        let Some(chat_id) = self.current_chat_id else { return };
+        let notes = self.view.text_input(ids!(notes_panel.notes_input)).text();
+        if let Some(store) = scope.data.get_mut::<Store>() {
+            store.chats.update_chat_notes(chat_id, &notes);
+        }
+    }
+
+    /// Show the current chat's notes in the panel
+    fn restore_notes(&mut self, cx: &mut Cx, scope: &mut Scope) {
+        let notes = scope.data.get::<Store>()
+            .and_then(|store| self.current_chat_id.and_then(|id| store.chats.get_chat_by_id(id)))
+            .map(|chat| chat.notes.clone())
+            .unwrap_or_default();
+        self.view.text_input(ids!(notes_panel.notes_input)).set_text(cx, &notes);
+    }
+
+    // ── Export ─────────────────────────────────────────────────────────
+
+    /// Export the current chat; asks first whether to include its notes
+    fn open_export(&mut self, cx: &mut Cx, scope: &mut Scope) {
+        self.save_notes(scope);
+        let Some(store) = scope.data.get::<Store>() else { return };
+        let Some(chat) = self.current_chat_id.and_then(|id| store.chats.get_chat_by_id(id)) else { return };
+        if chat.notes.trim().is_empty() {
+            self.export_chat(cx, scope, false);
+            return;
+        }
+        self.view.moly_dialog(ids!(dialog)).open(cx,
+            DialogRequest::select(live_id!(export_chat), "Export Chat",
+                "Save this chat as a Markdown file.",
+                vec!["Messages only".to_string(), "Messages and notes".to_string()], 0)
+                .confirm_label("Export…"));
+    }
+
+    /// Save the current chat as Markdown where the user chooses
+    fn export_chat(&mut self, _cx: &mut Cx, scope: &mut Scope, include_notes: bool) {
+        let Some(store) = scope.data.get::<Store>() else { return };
+        let Some(chat) = self.current_chat_id.and_then(|id| store.chats.get_chat_by_id(id)) else { return };
+        let Some(path) = FileDialog::new()
+            .add_filter("Markdown", &["md"])
+            .set_file_name(chat_export::file_name(chat))
+            .save_file()
+        else { return };
+        match std::fs::write(&path, chat_export::to_markdown(chat, include_notes)) {
+            Ok(()) => telemetry::record_feature("chat.export"),
+            Err(e) => ::log::error!("Exporting chat to {:?} failed: {}", path, e),
+        }
+    }
+
+    /// Share the current chat as a web page; asks whether to hide names
+    fn open_share(&mut self, cx: &mut Cx) {
+        if self.current_chat_id.is_none() {
+            return;
+        }
+        self.view.moly_dialog(ids!(dialog)).open(cx,
+            DialogRequest::select(live_id!(share_chat), "Share as HTML",
+                "Save this chat as a single web page, with its images and generated UIs, to email or publish.",
+                vec!["Show names".to_string(), "Hide names and account name".to_string()], 0)
+                .confirm_label("Save…"));
+    }
+
+    /// Save the current chat as an HTML page where the user chooses
+    fn share_chat(&mut self, _cx: &mut Cx, scope: &mut Scope, hide_names: bool) {
+        let Some(store) = scope.data.get::<Store>() else { return };
+        let Some(chat) = self.current_chat_id.and_then(|id| store.chats.get_chat_by_id(id)) else { return };
+        let Some(path) = FileDialog::new()
+            .add_filter("Web page", &["html"])
+            .set_file_name(chat_share::file_name(chat))
+            .save_file()
+        else { return };
+        let options = ShareOptions {
+            hide_names,
+            names: if hide_names { chat_share::account_names() } else { Vec::new() },
+        };
+        match std::fs::write(&path, chat_share::to_html(chat, &options)) {
+            Ok(()) => telemetry::record_feature("chat.share"),
+            Err(e) => ::log::error!("Sharing chat to {:?} failed: {}", path, e),
+        }
+    }
+
+    // ── Code blocks ────────────────────────────────────────────────────
+
+    /// Pick a code block of the latest answer that has some
+    fn open_code_blocks(&mut self, cx: &mut Cx) {
+        self.code_choices = self.chat_controller.lock().unwrap().state().messages.iter().rev()
+            .filter(|m| matches!(m.from, EntityId::Bot(_)))
+            .map(|m| code_blocks::extract(&m.content.text))
+            .find(|blocks| !blocks.is_empty())
+            .unwrap_or_default();
+        if self.code_choices.is_empty() {
+            self.view.moly_dialog(ids!(dialog)).open(cx, DialogRequest::confirm(live_id!(code_empty),
+                "Code Blocks", "The answers in this session have no code blocks."));
+            return;
+        }
+        let labels = self.code_choices.iter().map(CodeBlock::describe).collect();
+        self.view.moly_dialog(ids!(dialog)).open(cx,
+            DialogRequest::select(live_id!(code_blocks), "Code Blocks",
+                "Code blocks of the latest answer that has some.", labels, 0)
+                .confirm_label("Next…"));
+    }
+
+    /// Ask what to do with the chosen code block
+    fn open_code_actions(&mut self, cx: &mut Cx, index: usize) {
+        let Some(block) = self.code_choices.get(index).cloned() else { return };
+        let mut actions = vec!["Copy".to_string()];
+        if block.runnable() {
+            actions.push("Run with the code tool".to_string());
+        }
+        self.view.moly_dialog(ids!(dialog)).open(cx,
+            DialogRequest::select(live_id!(code_action), "Code Block", block.describe(), actions, 0));
+        self.code_pending = Some(block);
+    }
+
+    /// Run a code block in the background; the code tool asks for permission
+    /// through [`Self::poll_tool_activity`]
+    fn run_code_block(&mut self, block: CodeBlock) {
+        let Some(language) = block.language else { return };
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = tx.send(code_tool::run_snippet(&language, &block.code));
+        });
+        self.code_run_rx = Some(rx);
+        telemetry::record_feature("chat.run_code");
+    }
+
+    fn poll_code_run(&mut self, cx: &mut Cx) {
+        let Some(rx) = &self.code_run_rx else { return };
+        let result = match rx.try_recv() {
+            Ok(result) => result,
+            Err(mpsc::TryRecvError::Empty) => return,
+            Err(mpsc::TryRecvError::Disconnected) => Err("The code run stopped unexpectedly.".to_string()),
+        };
+        self.code_run_rx = None;
+        let (title, text) = match result {
+            Ok(output) if output.trim().is_empty() => ("Code Output", "The code ran without output.".to_string()),
+            Ok(output) => ("Code Output", output),
+            Err(e) => ("Code Not Run", e),
+        };
+        self.view.moly_dialog(ids!(dialog)).open(cx, DialogRequest::confirm(live_id!(code_output), title, text));
+    }
+
+    // ── Generated UI image ─────────────────────────────────────────────
+
+    /// Offer to save or copy the latest generated UI as an image
+    fn open_surface_image(&mut self, cx: &mut Cx) {
+        if self.surface_image_rx.is_some() {
+            return;
+        }
+        self.surface_pending = chat_share::latest_surface(&self.chat_controller.lock().unwrap().state().messages);
+        if self.surface_pending.is_none() {
+            self.view.moly_dialog(ids!(dialog)).open(cx, DialogRequest::confirm(live_id!(surface_empty),
+                "Generated UI", "The answers in this session have no generated UI."));
+            return;
+        }
+        self.view.moly_dialog(ids!(dialog)).open(cx,
+            DialogRequest::select(live_id!(surface_image), "Generated UI Image",
+                "Render the latest generated UI to a PNG, without the rest of the window.",
+                vec!["Save as PNG…".to_string(), "Copy to clipboard".to_string()], 0)
+                .confirm_label("Render"));
+    }
+
+    /// Render the pending UI offscreen in the background, then save it where
+    /// the user chooses or copy it
+    fn render_surface_image(&mut self, copy: bool) {
+        let Some(page) = self.surface_pending.take() else { return };
+        let png = if copy {
+            artifacts::unique_path("generated-ui", "png")
+        } else {
+            let Some(path) = FileDialog::new()
+                .add_filter("PNG image", &["png"])
+                .set_file_name("generated-ui.png")
+                .save_file()
+            else { return };
+            path
+        };
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let html = artifacts::unique_path("generated-ui", "html");
+            let result = std::fs::write(&html, &page.html)
+                .map_err(|e| format!("{}: {}", html.display(), e))
+                .and_then(|()| platform::render_html_to_png(&html, &png, chat_share::SURFACE_WIDTH, page.height))
+                .and_then(|()| if copy { platform::copy_image_to_clipboard(&png) } else { Ok(()) });
+            let _ = tx.send(result);
+        });
+        self.surface_image_rx = Some(rx);
+        telemetry::record_feature(if copy { "chat.ui_image.copy" } else { "chat.ui_image.save" });
+    }
+
+    fn poll_surface_image(&mut self, cx: &mut Cx) {
+        let Some(rx) = &self.surface_image_rx else { return };
+        let result = match rx.try_recv() {
+            Ok(result) => result,
+            Err(mpsc::TryRecvError::Empty) => return,
+            Err(mpsc::TryRecvError::Disconnected) => Err("Rendering stopped unexpectedly.".to_string()),
+        };
+        self.surface_image_rx = None;
+        if let Err(e) = result {
+            self.view.moly_dialog(ids!(dialog)).open(cx,
+                DialogRequest::confirm(live_id!(surface_image_error), "UI Image Not Rendered", e));
+        }
+    }
+
+    // ── Session templates ──────────────────────────────────────────────
+
+    /// Pick a template to start the empty session from, or one to remove
+    fn open_templates(&mut self, cx: &mut Cx, remove: bool) {
+        self.session_templates = SessionTemplates::load();
+        if self.session_templates.templates.is_empty() {
+            self.view.redraw(cx);
+            return;
+        }
+        let labels = self.session_templates.templates.iter().map(|t| t.describe()).collect();
+        let request = if remove {
+            DialogRequest::select(live_id!(remove_template), "Remove a Template",
+                "Sessions started from it keep their messages.", labels, 0)
+                .confirm_label("Remove")
+                .danger()
+        } else {
+            DialogRequest::select(live_id!(use_template), "Start from a Template",
+                "The template's system prompt, tools and messages are copied into this session.", labels, 0)
+                .confirm_label("Start")
+        };
+        self.view.moly_dialog(ids!(dialog)).open(cx, request);
+    }
+
+    /// Copy a template into the current session, which is still empty
+    fn start_from_template(&mut self, cx: &mut Cx, scope: &mut Scope, index: usize) {
+        let Some(template) = self.session_templates.templates.get(index).cloned() else { return };
+        let Some(chat_id) = self.current_chat_id else { return };
+        let Some(store) = scope.data.get_mut::<Store>() else { return };
+        store.chats.apply_template(chat_id, &template);
+
+        let message_count = template.messages.len();
+        let last_content_len = template.messages.last().map(|m| m.content.text.len()).unwrap_or(0);
+        self.chat_controller.lock().unwrap().dispatch_mutation(VecMutation::Set(template.messages));
+        self.last_synced_message_count = message_count;
+        self.had_writing_message = false;
+        self.last_synced_content_len = last_content_len;
+        self.in_welcome_mode = message_count == 0;
+        ::log::info!("Started chat {} from template '{}'", chat_id, template.name);
+
+        // The sidebar shows the template's name
+        cx.action(ChatHistoryAction::ChatCreated);
+        self.apply_chat_tools(cx, scope);
+        self.apply_chat_settings(scope);
+        self.view.redraw(cx);
+    }
+
+    /// Ask the user about tool actions waiting for approval, and record
+    /// the files the files tool used on the chats that ran it
+    fn poll_tool_activity(&mut self, cx: &mut Cx, scope: &mut Scope) {
+        let touched = fs_tool::take_touched();
+        if let (false, Some(store)) = (touched.is_empty(), scope.data.get_mut::<Store>()) {
+            for (chat_id, paths) in touched {
+                store.chats.record_touched_paths(chat_id, paths);
+            }
+        }
+        while let Some(request) = tool_permissions::next_request() {
+            self.view.moly_dialog(ids!(dialog)).open(cx,
+                DialogRequest::confirm(live_id!(tool_permission), request.title, request.detail)
+                    .confirm_label("Allow"));
+            self.pending_permissions.push(request.id);
+        }
+    }
+
+    fn open_tools_menu(&mut self, cx: &mut Cx, scope: &mut Scope) {
+        let Some(store) = scope.data.get::<Store>() else { return };
+        let mut entries = vec![ToolsMenuEntry::Choose];
+        entries.extend(store.preferences.toolsets.iter().map(|t| ToolsMenuEntry::UseToolset(t.name.clone())));
+        entries.push(ToolsMenuEntry::SaveToolset);
+        if !store.preferences.toolsets.is_empty() {
+            entries.push(ToolsMenuEntry::DeleteToolset);
+        }
+        let touched_any = self.current_chat_id
+            .and_then(|id| store.chats.get_chat_by_id(id))
+            .is_some_and(|chat| !chat.touched_paths.is_empty());
+        if touched_any {
+            entries.push(ToolsMenuEntry::TouchedFiles);
+        }
+        let labels = entries.iter().map(|entry| match entry {
+            ToolsMenuEntry::Choose => "Choose tools…".to_string(),
+            ToolsMenuEntry::UseToolset(name) => format!("Use toolset: {}", name),
+            ToolsMenuEntry::SaveToolset => "Save current tools as a toolset…".to_string(),
+            ToolsMenuEntry::DeleteToolset => "Delete a toolset…".to_string(),
+            ToolsMenuEntry::TouchedFiles => "Files used in this chat…".to_string(),
+        }).collect();
+        let summary = format!("This chat exposes {} tools to the model. Fewer tools leave more room in the context.",
+            self.chat_tools(store).len());
+        self.view.moly_dialog(ids!(dialog)).open(cx,
+            DialogRequest::select(live_id!(tools_menu), "Tools", summary, labels, 0).confirm_label("Continue"));
+        self.tools_menu = entries;
+    }
+
+    /// Results of the Tools menu, tool approval and agent goal dialogs
+    fn handle_dialogs(&mut self, cx: &mut Cx, scope: &mut Scope, actions: &Actions) {
+        let dialog = self.view.moly_dialog(ids!(dialog));
+        let Some((id, result)) = dialog.closed(actions) else { return };
+        if id == live_id!(tool_permission) {
+            if !self.pending_permissions.is_empty() {
+                let request = self.pending_permissions.remove(0);
+                tool_permissions::answer(request, result == DialogResult::Confirmed);
+            }
+            return;
+        }
+        match result {
+            DialogResult::Selected(index) if id == live_id!(tools_menu) => {
+                let Some(entry) = self.tools_menu.get(index).cloned() else { return };
+                let Some(store) = scope.data.get::<Store>() else { return };
+                match entry {
+                    ToolsMenuEntry::Choose => {
+                        let tools = self.chat_tools(store);
+                        self.tool_choices = toolsets::tool_choices(&store.preferences.mcp_servers);
+                        let labels = self.tool_choices.iter().map(ToolChoice::label).collect();
+                        let checked = self.tool_choices.iter().map(|c| tools.contains(c)).collect();
+                        dialog.open(cx, DialogRequest::multi_select(live_id!(choose_tools), "Choose Tools",
+                            "Only the checked tools are offered to the model in this chat.", labels, checked)
+                            .confirm_label("Apply"));
+                    }
+                    ToolsMenuEntry::UseToolset(name) => {
+                        let tools = store.preferences.toolsets.iter().find(|t| t.name == name).map(|t| t.tools.clone());
+                        if let Some(tools) = tools {
+                            self.set_chat_tools(cx, scope, tools);
+                        }
+                    }
+                    ToolsMenuEntry::SaveToolset => {
+                        dialog.open(cx, DialogRequest::text_input(live_id!(save_toolset), "Save Toolset",
+                            "Save this chat's tools under a name to reuse them in other chats.", "")
+                            .placeholder("e.g. Research"));
+                    }
+                    ToolsMenuEntry::DeleteToolset => {
+                        let names = store.preferences.toolsets.iter().map(|t| t.name.clone()).collect();
+                        dialog.open(cx, DialogRequest::select(live_id!(delete_toolset), "Delete Toolset",
+                            "Chats using its tools keep them.", names, 0)
+                            .confirm_label("Delete")
+                            .danger());
+                    }
+                    ToolsMenuEntry::TouchedFiles => {
+                        let touched = self.current_chat_id
+                            .and_then(|id| store.chats.get_chat_by_id(id))
+                            .map(|chat| chat.touched_paths.clone())
+                            .unwrap_or_default();
+                        let lines: Vec<String> = touched.iter().rev().take(12).map(|t| t.describe()).collect();
+                        let mut message = lines.join("\n");
+                        if touched.len() > lines.len() {
+                            message.push_str(&format!("\n… and {} earlier", touched.len() - lines.len()));
+                        }
+                        dialog.open(cx, DialogRequest::confirm(live_id!(touched_files), "Files Used in This Chat", message));
+                    }
+                }
+            }
+            DialogResult::MultiSelected(checked) if id == live_id!(choose_tools) => {
+                let tools = ToolSelection::from_choices(checked.iter().filter_map(|i| self.tool_choices.get(*i)));
+                self.set_chat_tools(cx, scope, tools);
+            }
+            DialogResult::Text(name) if id == live_id!(save_toolset) => {
+                let Some(store) = scope.data.get_mut::<Store>() else { return };
+                let tools = self.chat_tools(store);
+                if let Err(e) = store.preferences.save_toolset(&name, tools) {
+                    ::log::warn!("Toolset not saved: {}", e);
+                }
+            }
+            DialogResult::Selected(index) if id == live_id!(delete_toolset) => {
+                let Some(store) = scope.data.get_mut::<Store>() else { return };
+                if let Some(name) = store.preferences.toolsets.get(index).map(|t| t.name.clone()) {
+                    store.preferences.remove_toolset(&name);
+                }
+            }
+            DialogResult::Selected(index) if id == live_id!(export_chat) => {
+                self.export_chat(cx, scope, index == 1);
+            }
+            DialogResult::Selected(index) if id == live_id!(share_chat) => {
+                self.share_chat(cx, scope, index == 1);
+            }
+            DialogResult::Selected(index) if id == live_id!(code_blocks) => {
+                self.open_code_actions(cx, index);
+            }
+            DialogResult::Selected(index) if id == live_id!(surface_image) => {
+                self.render_surface_image(index == 1);
+            }
+            DialogResult::Selected(index) if id == live_id!(code_action) => {
+                let Some(block) = self.code_pending.take() else { return };
+                if index == 0 {
+                    cx.copy_to_clipboard(&block.code);
+                } else {
+                    self.run_code_block(block);
+                }
+            }
+            DialogResult::Selected(index) if id == live_id!(use_template) => {
+                self.start_from_template(cx, scope, index);
+            }
+            DialogResult::Selected(index) if id == live_id!(remove_template) => {
+                let Some(name) = self.session_templates.templates.get(index).map(|t| t.name.clone()) else { return };
+                if let Err(e) = self.session_templates.remove(&name) {
+                    ::log::warn!("Template not removed: {}", e);
+                }
+                self.view.redraw(cx);
+            }
+            DialogResult::Text(prompt) if id == live_id!(system_prompt) => {
+                let Some(chat_id) = self.current_chat_id else { return };
+                if let Some(store) = scope.data.get_mut::<Store>() {
+                    store.chats.update_chat_system_prompt(chat_id, Some(prompt).filter(|p| !p.is_empty()));
+                }
+                self.apply_chat_settings(scope);
+            }
+            DialogResult::Text(text) if id == live_id!(sampling) => {
+                let Some(chat_id) = self.current_chat_id else { return };
+                match Sampling::parse(&text) {
+                    Ok(sampling) => {
+                        if let Some(store) = scope.data.get_mut::<Store>() {
+                            store.chats.update_chat_sampling(chat_id, sampling);
+                        }
+                        self.apply_chat_settings(scope);
+                    }
+                    Err(e) => {
+                        self.view.moly_dialog(ids!(dialog)).open(cx, DialogRequest::text_input(
+                            live_id!(sampling), "Sampling", e, text).placeholder("e.g. 0.7, 1024"));
+                    }
+                }
+            }
+            DialogResult::Text(goal) if id == live_id!(agent_goal) => {
+                self.start_agent(cx, scope, goal);
+            }
+            DialogResult::Selected(index) if id == live_id!(read_aloud_from) => {
+                match index.checked_sub(1).and_then(|i| self.read_aloud_choices.get(i).copied()) {
+                    Some(from) => self.open_read_aloud_picker(cx, Some(from)),
+                    None => self.open_read_aloud_voice(cx, 0, usize::MAX),
+                }
+            }
+            DialogResult::Selected(index) if id == live_id!(read_aloud_to) => {
+                let Some(from) = self.read_aloud_from.take() else { return };
+                let to = index.checked_sub(1).and_then(|i| self.read_aloud_choices.get(i).copied()).unwrap_or(usize::MAX);
+                self.open_read_aloud_voice(cx, from, to);
+            }
+            DialogResult::Selected(index) if id == live_id!(read_aloud_voice) => {
+                let Some((from, to)) = self.read_aloud_range.take() else { return };
+                let preset = index.checked_sub(1).and_then(|i| voice_presets::current().get(i).cloned());
+                self.start_read_aloud(cx, from, to, preset);
+            }
+            DialogResult::Selected(index) if id == live_id!(translate_source) => {
+                let Some(message) = self.translate_choices.get(index).copied() else { return };
+                self.open_translate_target(cx, message);
+            }
+            DialogResult::Selected(index) if id == live_id!(translate_target) => {
+                let Some(to) = Language::ALL.get(index).copied() else { return };
+                self.start_translation(cx, to);
+            }
+            DialogResult::Selected(index) if id == live_id!(bookmark_message) => {
+                let Some(&message_index) = self.bookmark_choices.get(index) else { return };
+                let Some(store) = scope.data.get::<Store>() else { return };
+                let note = self.current_chat_id
+                    .and_then(|id| store.chats.get_chat_by_id(id))
+                    .and_then(|chat| chat.bookmarks.iter().find(|b| b.message_index == message_index))
+                    .map(|b| b.note.clone())
+                    .unwrap_or_default();
+                self.bookmark_pending = Some(message_index);
+                dialog.open(cx, DialogRequest::text_input(live_id!(bookmark_note), "Bookmark Note",
+                    "Add a note to find this message later on the Bookmarks page (optional).", note)
+                    .placeholder("e.g. Best explanation of lifetimes")
+                    .confirm_label("Bookmark"));
+            }
+            DialogResult::Selected(index) if id == live_id!(jump_to_day) => {
+                if let Some(&message_index) = self.jump_choices.get(index) {
+                    self.scroll_to_message_index(cx, message_index);
+                }
+            }
+            DialogResult::Selected(index) if id == live_id!(message_link) => {
+                let (Some(chat_id), Some(&message_index)) = (self.current_chat_id, self.link_choices.get(index)) else { return };
+                let link = chat_navigation::permalink(chat_id, message_index);
+                cx.copy_to_clipboard(&link);
+                dialog.open(cx, DialogRequest::confirm(live_id!(link_copied), "Link Copied",
+                    format!("{}\n\nOpening it shows this chat scrolled to the message.", link)));
+            }
+            DialogResult::Selected(index) if id == live_id!(answer_sources) => {
+                if let Some(&message_index) = self.source_choices.get(index) {
+                    self.show_sources(cx, scope, message_index);
+                }
+            }
+            DialogResult::Selected(index) if id == live_id!(answer_versions) => {
+                if let Some(&message_index) = self.version_choices.get(index) {
+                    self.show_versions(cx, scope, message_index);
+                }
+            }
+            DialogResult::MultiSelected(checked) if id == live_id!(privacy_review) => {
+                self.send_reviewed(scope, &checked);
+            }
+            DialogResult::Cancelled if id == live_id!(privacy_review) => {
+                self.cancel_review(cx);
+            }
+            DialogResult::Text(note) if id == live_id!(bookmark_note) => {
+                let (Some(chat_id), Some(message_index)) = (self.current_chat_id, self.bookmark_pending.take()) else { return };
+                if let Some(store) = scope.data.get_mut::<Store>() {
+                    store.chats.set_bookmark(chat_id, message_index, &note);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // ── Find in chat ───────────────────────────────────────────────────
+
+    fn open_find(&mut self, cx: &mut Cx) {
+        self.find_open = true;
+        self.view.view(ids!(find_panel)).set_visible(cx, true);
+        self.view.text_input(ids!(find_panel.find_input)).set_key_focus(cx);
+        self.refresh_find(cx, false);
+    }
+
+    fn close_find(&mut self, cx: &mut Cx) {
+        self.find_open = false;
+        self.chat_search = ChatSearch::default();
+        self.view.text_input(ids!(find_panel.find_input)).set_text(cx, "");
+        self.view.view(ids!(find_panel)).set_visible(cx, false);
+        self.view.redraw(cx);
+    }
+
+    fn handle_find_controls(&mut self, cx: &mut Cx, actions: &Actions) {
+        if !self.find_open { return; }
+        if self.view.button(ids!(find_panel.find_close_button)).clicked(actions) {
+            self.close_find(cx);
+            return;
+        }
+        let input = self.view.text_input(ids!(find_panel.find_input));
+        if input.changed(actions).is_some() {
+            self.refresh_find(cx, true);
+        }
+        let next = self.view.button(ids!(find_panel.find_next_button)).clicked(actions)
+            || input.returned(actions).is_some();
+        let previous = self.view.button(ids!(find_panel.find_prev_button)).clicked(actions);
+        if next || previous {
+            // Answers may have arrived since the last search
+            self.refresh_find(cx, false);
+            self.chat_search.step(next);
+            self.show_find_match(cx, true);
+        }
+    }
+
+    // ── Model picker ───────────────────────────────────────────────────
+
+    /// Offer the downloaded local models and every provider's enabled models,
+    /// with the chat's current model selected
+    fn open_model_picker(&mut self, cx: &mut Cx, scope: &mut Scope) {
+        let Some(store) = scope.data.get::<Store>() else { return };
+        let mut entries = model_picker::local_entries(&ModelRegistry::load());
+        for bot in Self::filter_enabled_bots(store.providers_manager.get_all_bots(), store) {
+            let Some(provider_id) = store.providers_manager.get_provider_for_bot(&bot.id) else { continue };
+            // The loaded local model is already listed under Local
+            if provider_id == "ominix-local" {
+                continue;
+            }
+            entries.push(PickerEntry {
+                name: bot.name.clone(),
+                group: Self::get_provider_display_name(provider_id).to_string(),
+                detail: String::new(),
+                model: PickerModel::Cloud { bot_id: bot.id.as_str().to_string() },
+            });
+        }
+
+        let mut picker = ModelPicker::new(entries);
+        let current = self.chat_controller.lock().unwrap().state().bot_id.clone();
+        if let Some(current) = current {
+            let index = picker.entries().iter().position(|e| {
+                matches!(&e.model, PickerModel::Cloud { bot_id } if bot_id == current.as_str())
+            });
+            if let Some(index) = index {
+                picker.select(index);
+            }
+        }
+        self.model_picker = Some(picker);
+
+        let input = self.view.text_input(ids!(model_picker_panel.model_picker_input));
+        input.set_text(cx, "");
+        input.set_key_focus(cx);
+        self.view.view(ids!(model_picker_panel)).set_visible(cx, true);
+        self.refresh_model_picker(cx);
+    }
+
+    fn close_model_picker(&mut self, cx: &mut Cx) {
+        self.model_picker = None;
+        self.picker_rows.clear();
+        self.view.view(ids!(model_picker_panel)).set_visible(cx, false);
+        self.view.redraw(cx);
+    }
+
+    /// Show the picker's rows around the selection
+    fn refresh_model_picker(&mut self, cx: &mut Cx) {
+        let Some(picker) = &self.model_picker else { return };
+        let count = match picker.match_count() {
+            0 => "No matching models".to_string(),
+            1 => "1 model".to_string(),
+            n => format!("{} models", n),
+        };
+        self.view.label(ids!(model_picker_panel.model_picker_count)).set_text(cx, &count);
+
+        self.picker_rows = picker.rows(PICKER_ROWS);
+        for index in 0..PICKER_ROWS {
+            let row = self.picker_row(index);
+            match self.picker_rows.get(index) {
+                None => row.set_visible(cx, false),
+                Some(PickerRow::Group(group)) => {
+                    row.label(ids!(group_label)).set_text(cx, group);
+                    row.label(ids!(group_label)).set_visible(cx, true);
+                    row.label(ids!(name_label)).set_visible(cx, false);
+                    row.label(ids!(detail_label)).set_visible(cx, false);
+                    row.label(ids!(status_label)).set_visible(cx, false);
+                    row.button(ids!(load_button)).set_visible(cx, false);
+                    row.as_view().apply_over(cx, live! { draw_bg: { selected: 0.0 } });
+                    row.set_visible(cx, true);
+                }
+                Some(&PickerRow::Entry { index: entry_index, selected }) => {
+                    let entry = &picker.entries()[entry_index];
+                    row.label(ids!(group_label)).set_visible(cx, false);
+                    row.label(ids!(name_label)).set_text(cx, &entry.name);
+                    row.label(ids!(detail_label)).set_text(cx, &entry.detail);
+                    row.label(ids!(status_label)).set_text(cx, entry.status());
+                    row.label(ids!(name_label)).set_visible(cx, true);
+                    row.label(ids!(detail_label)).set_visible(cx, true);
+                    row.label(ids!(status_label)).set_visible(cx, true);
+                    row.button(ids!(load_button)).set_visible(cx, entry.needs_load());
+                    let selected = if selected { 1.0 } else { 0.0 };
+                    row.as_view().apply_over(cx, live! { draw_bg: { selected: (selected) } });
+                    row.set_visible(cx, true);
+                }
+            }
+        }
+        self.view.redraw(cx);
+    }
+
+    fn picker_row(&self, index: usize) -> WidgetRef {
+        match index {
+            0 => self.view.widget(ids!(model_picker_panel.picker_row_0)),
+            1 => self.view.widget(ids!(model_picker_panel.picker_row_1)),
+            2 => self.view.widget(ids!(model_picker_panel.picker_row_2)),
+            3 => self.view.widget(ids!(model_picker_panel.picker_row_3)),
+            4 => self.view.widget(ids!(model_picker_panel.picker_row_4)),
+            5 => self.view.widget(ids!(model_picker_panel.picker_row_5)),
+            6 => self.view.widget(ids!(model_picker_panel.picker_row_6)),
+            7 => self.view.widget(ids!(model_picker_panel.picker_row_7)),
+            8 => self.view.widget(ids!(model_picker_panel.picker_row_8)),
+            _ => self.view.widget(ids!(model_picker_panel.picker_row_9)),
+        }
+    }
+
+    /// Switch the chat to the selected model; a local model that isn't loaded
+    /// is loaded by the shell, which opens a chat with it when it's ready
+    fn pick_model(&mut self, cx: &mut Cx, scope: &mut Scope) {
+        let Some(entry) = self.model_picker.as_ref().and_then(|p| p.selected()).cloned() else { return };
+        self.close_model_picker(cx);
+        match entry.model {
+            PickerModel::Cloud { bot_id } => {
+                let bot_id = BotId::new(&bot_id);
+                self.chat_controller.lock().unwrap().dispatch_mutation(ChatStateMutation::SetBotId(Some(bot_id.clone())));
+                self.switch_to_provider_for_bot_with_ui(cx, &bot_id, scope);
+                telemetry::record_feature("chat.model_picker");
+            }
+            PickerModel::Local { model_id, phase } => {
+                let active = scope.data.get::<Store>().and_then(|store| store.get_active_local_model().map(str::to_string));
+                match (phase, active) {
+                    (Some(ModelLoadPhase::Loaded), Some(active)) => {
+                        self.chat_controller.lock().unwrap().dispatch_mutation(ChatStateMutation::SetBotId(Some(BotId::new(&active))));
+                    }
+                    // Already on its way; the shell switches over when it's loaded
+                    (Some(ModelLoadPhase::Loading), _) => {}
+                    _ => cx.action(StoreAction::LoadLocalModel { model_id }),
+                }
+                telemetry::record_feature("chat.model_picker.local");
+            }
+        }
+        self.view.redraw(cx);
+    }
+
+    fn handle_model_picker_controls(&mut self, cx: &mut Cx, scope: &mut Scope, actions: &Actions) {
+        if self.model_picker.is_none() { return; }
+        if self.view.button(ids!(model_picker_panel.model_picker_close_button)).clicked(actions) {
+            self.close_model_picker(cx);
+            return;
+        }
+        let input = self.view.text_input(ids!(model_picker_panel.model_picker_input));
+        if let Some(query) = input.changed(actions) {
+            if let Some(picker) = self.model_picker.as_mut() {
+                picker.set_query(&query);
+            }
+            self.refresh_model_picker(cx);
+        }
+        if input.returned(actions).is_some() {
+            self.pick_model(cx, scope);
+            return;
+        }
+        for slot in 0..self.picker_rows.len().min(PICKER_ROWS) {
+            let PickerRow::Entry { index, .. } = self.picker_rows[slot] else { continue };
+            let row = self.picker_row(slot);
+            if row.button(ids!(load_button)).clicked(actions) || row.as_view().finger_down(actions).is_some() {
+                if let Some(picker) = self.model_picker.as_mut() {
+                    picker.select(index);
+                }
+                self.pick_model(cx, scope);
+                return;
+            }
+        }
+    }
+
+    /// Search the chat again for the find bar's query; `jump` scrolls to the
+    /// current match
+    fn refresh_find(&mut self, cx: &mut Cx, jump: bool) {
+        let query = self.view.text_input(ids!(find_panel.find_input)).text();
+        {
+            let ctrl = self.chat_controller.lock().unwrap();
+            self.chat_search.update(&ctrl.state().messages, &query);
+        }
+        self.show_find_match(cx, jump);
+    }
+
+    fn show_find_match(&mut self, cx: &mut Cx, jump: bool) {
+        let mut counter = self.chat_search.counter();
+        let snippet = self.chat_search.current_match().copied().and_then(|m| {
+            let ctrl = self.chat_controller.lock().unwrap();
+            let message = ctrl.state().messages.get(m.message_index)?;
+            let who = if matches!(message.from, EntityId::User) { "your message" } else { "an answer" };
+            counter = format!("{} · in {}", counter, who);
+            Some((m.message_index, chat_search::snippet(&message.content.text, &m, chat_search::CONTEXT_CHARS)))
+        });
+        self.view.label(ids!(find_panel.find_count)).set_text(cx, &counter);
+        self.view.view(ids!(find_panel.find_snippet)).set_visible(cx, snippet.is_some());
+        if let Some((index, (before, matched, after))) = snippet {
+            self.view.label(ids!(find_panel.find_before)).set_text(cx, &before);
+            self.view.label(ids!(find_panel.find_match)).set_text(cx, &matched);
+            self.view.label(ids!(find_panel.find_after)).set_text(cx, &after);
+            if jump {
+                self.scroll_to_message_index(cx, index);
+            }
+        }
+        self.view.redraw(cx);
+    }
+
+    // ── Read aloud ─────────────────────────────────────────────────────
+
+    /// Ask where reading starts, or with `from` chosen, where it stops
+    fn open_read_aloud_picker(&mut self, cx: &mut Cx, from: Option<usize>) {
+        use moly_kit::aitk::protocol::EntityId;
+
+        let messages = self.chat_controller.lock().unwrap().state().messages.clone();
+        let mut choices: Vec<usize> = messages.iter().enumerate()
+            .filter(|(i, _)| from.map_or(true, |from| *i >= from))
+            .filter(|(_, m)| !m.metadata.is_writing && !m.content.text.trim().is_empty())
+            .filter(|(_, m)| matches!(m.from, EntityId::User | EntityId::Bot(_)))
+            .map(|(i, _)| i)
+            .collect();
+        if choices.is_empty() {
+            self.view.moly_dialog(ids!(dialog)).open(cx, DialogRequest::confirm(live_id!(read_aloud_empty),
+                "Read Aloud", "This session has no messages to read yet."));
+            return;
+        }
+        // Recent messages are the likely start; a range runs from its start on
+        if from.is_none() {
+            choices = choices.split_off(choices.len().saturating_sub(READ_ALOUD_CHOICES));
+        } else {
+            choices.truncate(READ_ALOUD_CHOICES);
+        }
+        let first = if from.is_none() { "The whole conversation" } else { "To the end" };
+        let labels = std::iter::once(first.to_string()).chain(choices.iter().map(|&i| {
+            let who = if matches!(messages[i].from, EntityId::User) { "You" } else { "Answer" };
+            format!("{}: {}", who, bookmarks::excerpt(&messages[i].content.text, 70))
+        })).collect();
+        let request = match from {
+            None => DialogRequest::select(live_id!(read_aloud_from), "Read Aloud",
+                "Read the conversation with the loaded speech model, or start from a message and choose where to stop next.", labels, 0)
+                .confirm_label("Next"),
+            Some(_) => DialogRequest::select(live_id!(read_aloud_to), "Read Aloud",
+                "Stop after this message.", labels, 0)
+                .confirm_label("Read"),
+        };
+        self.view.moly_dialog(ids!(dialog)).open(cx, request);
+        self.read_aloud_choices = choices;
+        self.read_aloud_from = from;
+    }
+
+    /// With Voice Studio presets saved, ask whether to read with one of them
+    /// or the chat's voice
+    fn open_read_aloud_voice(&mut self, cx: &mut Cx, from: usize, to: usize) {
+        let presets = voice_presets::current();
+        if presets.is_empty() {
+            self.start_read_aloud(cx, from, to, None);
+            return;
+        }
+        let voice = TTS_VOICE_IDS.get(self.tts_voice_idx).copied().unwrap_or("vivian");
+        let labels = std::iter::once(format!("Chat voice ({})", voice))
+            .chain(presets.iter().map(|p| format!("{} — {}", p.name, p.summary())))
+            .collect();
+        self.view.moly_dialog(ids!(dialog)).open(cx, DialogRequest::select(live_id!(read_aloud_voice), "Read Aloud",
+            "Read with the chat's voice, or with a preset saved in Voice Studio.", labels, 0)
+            .confirm_label("Read"));
+        self.read_aloud_range = Some((from, to));
+    }
+
+    /// Read messages `from..=to` of the open chat with the loaded TTS model,
+    /// or with Voice Studio's cloned voices when given a preset
+    fn start_read_aloud(&mut self, cx: &mut Cx, from: usize, to: usize, preset: Option<VoicePreset>) {
+        use moly_kit::aitk::protocol::EntityId;
+
+        let model_id = match &preset {
+            Some(_) => Some(voice_client::VOICE_CLONE_MODEL.to_string()),
+            None => read_aloud::loaded_tts_model(&ModelRegistry::load()),
+        };
+        let Some(model_id) = model_id else {
+            self.view.moly_dialog(ids!(dialog)).open(cx, DialogRequest::confirm(live_id!(read_aloud_no_model),
+                "Read Aloud", "Load a text-to-speech model in the Model Hub, then try again."));
+            return;
+        };
+        let messages = self.chat_controller.lock().unwrap().state().messages.clone();
+        let sentences = read_aloud::sentences(messages.iter().enumerate()
+            .filter(|(i, m)| *i >= from && *i <= to && !m.metadata.is_writing)
+            .filter(|(_, m)| matches!(m.from, EntityId::User | EntityId::Bot(_)))
+            .map(|(i, m)| (i, m.content.text.as_str())));
+        if sentences.is_empty() {
+            return;
+        }
+        let reader = match &preset {
+            Some(preset) => ReadAloud::new(sentences, model_id, &preset.voice).with_preset(preset),
+            None => {
+                let voice = TTS_VOICE_IDS.get(self.tts_voice_idx).copied().unwrap_or("vivian");
+                ReadAloud::new(sentences, model_id, voice).with_voices(tts_voices::current())
+            }
+        };
+        self.read_aloud = Some((self.current_chat_id, reader));
+        self.read_aloud_message = None;
+        cx.stop_timer(self.read_aloud_timer);
+        self.read_aloud_timer = cx.start_interval(READ_ALOUD_POLL);
+        self.view.view(ids!(read_aloud_panel)).set_visible(cx, true);
+        self.poll_read_aloud(cx);
+    }
+
+    fn handle_read_aloud_controls(&mut self, cx: &mut Cx, actions: &Actions) {
+        if self.view.button(ids!(read_aloud_panel.read_aloud_stop_button)).clicked(actions) {
+            self.stop_read_aloud(cx);
+            return;
+        }
+        let Some((_, reader)) = self.read_aloud.as_mut() else { return };
+        let mut changed = false;
+        if self.view.button(ids!(read_aloud_panel.read_aloud_back_button)).clicked(actions) {
+            reader.skip(-1);
+            changed = true;
+        }
+        if self.view.button(ids!(read_aloud_panel.read_aloud_next_button)).clicked(actions) {
+            reader.skip(1);
+            changed = true;
+        }
+        if self.view.button(ids!(read_aloud_panel.read_aloud_pause_button)).clicked(actions) {
+            reader.toggle_pause();
+            changed = true;
+        }
+        if self.view.button(ids!(read_aloud_panel.read_aloud_speed_button)).clicked(actions) {
+            let speed = reader.next_speed();
+            reader.set_speed(speed);
+            changed = true;
+        }
+        if changed {
+            self.poll_read_aloud(cx);
+        }
+    }
+
+    /// Move reading along and show the sentence being spoken, scrolling the
+    /// transcript to its message
+    fn poll_read_aloud(&mut self, cx: &mut Cx) {
+        let Some((chat_id, reader)) = self.read_aloud.as_mut() else { return };
+        if *chat_id != self.current_chat_id {
+            self.stop_read_aloud(cx);
+            return;
+        }
+        reader.tick();
+        let (number, total) = reader.position();
+        let speed = format!("{}×", reader.speed());
+        let status = if let Some(e) = reader.error() {
+            e.to_string()
+        } else if reader.is_finished() {
+            format!("Finished reading {} sentences", total)
+        } else if reader.is_paused() {
+            format!("Paused at sentence {} of {} · {}", number, total, speed)
+        } else if reader.is_waiting() {
+            format!("Preparing sentence {} of {}… · {}", number, total, speed)
+        } else {
+            format!("Reading sentence {} of {} · {}", number, total, speed)
+        };
+        let (before, after) = reader.neighbors();
+        let before = before.map(|s| s.text.clone()).unwrap_or_default();
+        let after = after.map(|s| s.text.clone()).unwrap_or_default();
+        let current = reader.current();
+        let message = current.map(|s| s.message_index);
+        let current = current.map(|s| s.text.clone()).unwrap_or_default();
+        let paused = reader.is_paused();
+        let finished = reader.is_finished();
+
+        self.view.label(ids!(read_aloud_panel.read_aloud_status)).set_text(cx, &status);
+        self.view.label(ids!(read_aloud_panel.read_aloud_before)).set_text(cx, &before);
+        self.view.label(ids!(read_aloud_panel.read_aloud_current)).set_text(cx, &current);
+        self.view.label(ids!(read_aloud_panel.read_aloud_after)).set_text(cx, &after);
+        self.view.button(ids!(read_aloud_panel.read_aloud_pause_button)).set_text(cx, if paused { "Resume" } else { "Pause" });
+        self.view.button(ids!(read_aloud_panel.read_aloud_speed_button)).set_text(cx, &speed);
+        self.view.button(ids!(read_aloud_panel.read_aloud_stop_button)).set_text(cx, if finished { "Close" } else { "Stop" });
+        if finished {
+            cx.stop_timer(self.read_aloud_timer);
+        }
+        if let Some(index) = message.filter(|i| Some(*i) != self.read_aloud_message) {
+            self.read_aloud_message = Some(index);
+            self.scroll_to_message_index(cx, index);
+        }
+        self.view.redraw(cx);
+    }
+
+    fn stop_read_aloud(&mut self, cx: &mut Cx) {
+        // Dropping the reader stops the audio
+        self.read_aloud = None;
+        self.read_aloud_message = None;
+        cx.stop_timer(self.read_aloud_timer);
+        self.view.view(ids!(read_aloud_panel)).set_visible(cx, false);
+        self.view.redraw(cx);
+    }
+
+    // ── Translate ──────────────────────────────────────────────────────
+
+    /// Ask what to translate: the text in the prompt, or a recent message
+    fn open_translate_picker(&mut self, cx: &mut Cx) {
+        use moly_kit::aitk::protocol::EntityId;
+
+        let prompt = self.prompt_text_input().text();
+        let messages = self.chat_controller.lock().unwrap().state().messages.clone();
+        let mut recent: Vec<usize> = messages.iter().enumerate()
+            .filter(|(_, m)| !m.metadata.is_writing && !m.content.text.trim().is_empty())
+            .filter(|(_, m)| matches!(m.from, EntityId::User | EntityId::Bot(_)))
+            .map(|(i, _)| i)
+            .collect();
+        recent = recent.split_off(recent.len().saturating_sub(READ_ALOUD_CHOICES));
+        recent.reverse();
+
+        let mut choices = Vec::new();
+        let mut labels = Vec::new();
+        if !prompt.trim().is_empty() {
+            choices.push(None);
+            labels.push(format!("Prompt: {}", bookmarks::excerpt(&prompt, 70)));
+        }
+        for i in recent {
+            let who = if matches!(messages[i].from, EntityId::User) { "You" } else { "Answer" };
+            choices.push(Some(i));
+            labels.push(format!("{}: {}", who, bookmarks::excerpt(&messages[i].content.text, 70)));
+        }
+        if choices.is_empty() {
+            self.view.moly_dialog(ids!(dialog)).open(cx, DialogRequest::confirm(live_id!(translate_empty),
+                "Translate", "Type something in the prompt, or start a conversation, to have text to translate."));
+            return;
+        }
+        self.view.moly_dialog(ids!(dialog)).open(cx, DialogRequest::select(live_id!(translate_source), "Translate",
+            "Translate with the loaded model. Choose the text; its language is detected.", labels, 0)
+            .confirm_label("Next"));
+        self.translate_choices = choices;
+    }
+
+    /// Ask which language to translate into, the detected one's usual pair first
+    fn open_translate_target(&mut self, cx: &mut Cx, message: Option<usize>) {
+        let text = match message {
+            None => self.prompt_text_input().text(),
+            Some(i) => {
+                let ctrl = self.chat_controller.lock().unwrap();
+                let Some(m) = ctrl.state().messages.get(i) else { return };
+                m.content.text.clone()
+            }
+        };
+        let from = translate::detect(&text);
+        let to = translate::default_target(from);
+        let labels = Language::ALL.iter().map(|l| l.name().to_string()).collect();
+        let selected = Language::ALL.iter().position(|l| *l == to).unwrap_or(0);
+        self.view.moly_dialog(ids!(dialog)).open(cx, DialogRequest::select(live_id!(translate_target), "Translate",
+            format!("The text looks like {}. Translate it into:", from.name()), labels, selected)
+            .confirm_label("Translate"));
+        self.translation = Some(Translation {
+            message, text, from, to, model_name: String::new(), rx: None, result: None,
+        });
+    }
+
+    /// Send the chosen text to the loaded LLM and show the panel
+    fn start_translation(&mut self, cx: &mut Cx, to: Language) {
+        let Some(translation) = self.translation.as_mut() else { return };
+        let Some(model) = translate::loaded_llm(&ModelRegistry::load()) else {
+            self.translation = None;
+            self.view.moly_dialog(ids!(dialog)).open(cx, DialogRequest::confirm(live_id!(translate_no_model),
+                "Translate", "Load a language model in the Model Hub, then try again."));
+            return;
+        };
+        let text = translation.text.clone();
+        let (tx, rx) = mpsc::channel();
+        let model_name = model.name.clone();
+        std::thread::spawn(move || {
+            let _ = tx.send(translate::translate(&model, &text, to));
+        });
+        translation.to = to;
+        translation.model_name = model_name;
+        translation.rx = Some(rx);
+        translation.result = None;
+        telemetry::record_feature("chat.translate");
+        self.view.view(ids!(translate_panel)).set_visible(cx, true);
+        self.update_translate_panel(cx);
+    }
+
+    fn poll_translation(&mut self, cx: &mut Cx) {
+        let Some(translation) = self.translation.as_mut() else { return };
+        let Some(rx) = &translation.rx else { return };
+        let result = match rx.try_recv() {
+            Ok(result) => result,
+            Err(mpsc::TryRecvError::Empty) => return,
+            Err(mpsc::TryRecvError::Disconnected) => Err("The translation stopped unexpectedly.".to_string()),
+        };
+        translation.rx = None;
+        translation.result = Some(result);
+        self.update_translate_panel(cx);
+    }
+
+    fn update_translate_panel(&mut self, cx: &mut Cx) {
+        let Some(translation) = self.translation.as_ref() else { return };
+        let (status, text, done) = match &translation.result {
+            None => (format!("Translating {} into {} with {}…", translation.from.name(), translation.to.name(), translation.model_name),
+                String::new(), false),
+            Some(Ok(text)) => (format!("{} → {}", translation.from.name(), translation.to.name()), text.clone(), true),
+            Some(Err(e)) => (e.clone(), String::new(), false),
+        };
+        let replace = if translation.message.is_some() { "Replace message" } else { "Replace prompt" };
+        self.view.label(ids!(translate_panel.translate_status)).set_text(cx, &status);
+        self.view.label(ids!(translate_panel.translate_result_view.translate_result)).set_text(cx, &text);
+        self.view.view(ids!(translate_panel.translate_result_view)).set_visible(cx, done);
+        self.view.button(ids!(translate_panel.translate_copy_button)).set_visible(cx, done);
+        self.view.button(ids!(translate_panel.translate_replace_button)).set_visible(cx, done);
+        self.view.button(ids!(translate_panel.translate_replace_button)).set_text(cx, replace);
+        self.view.redraw(cx);
+    }
+
+    fn handle_translate_controls(&mut self, cx: &mut Cx, actions: &Actions) {
+        if self.view.button(ids!(translate_panel.translate_close_button)).clicked(actions) {
+            self.close_translation(cx);
+            return;
+        }
+        let Some(Translation { message, text: original, result: Some(Ok(text)), .. }) = self.translation.as_ref() else { return };
+        if self.view.button(ids!(translate_panel.translate_copy_button)).clicked(actions) {
+            cx.copy_to_clipboard(text);
+        }
+        if self.view.button(ids!(translate_panel.translate_replace_button)).clicked(actions) {
+            let text = text.clone();
+            match *message {
+                None => self.prompt_text_input().set_text(cx, &text),
+                Some(index) => {
+                    let mut ctrl = self.chat_controller.lock().unwrap();
+                    let mut messages = ctrl.state().messages.clone();
+                    // Only if the message is still the one that was translated
+                    match messages.get_mut(index).filter(|m| m.content.text == *original) {
+                        Some(m) => {
+                            m.content.text = text;
+                            ctrl.dispatch_mutation(VecMutation::Set(messages));
+                        }
+                        None => {
+                            drop(ctrl);
+                            self.view.label(ids!(translate_panel.translate_status))
+                                .set_text(cx, "The message changed since it was translated; copy the translation instead.");
+                            self.view.redraw(cx);
+                            return;
+                        }
+                    }
+                }
+            }
+            self.close_translation(cx);
+        }
+    }
+
+    fn close_translation(&mut self, cx: &mut Cx) {
+        // A translation still running is dropped with its receiver
+        self.translation = None;
+        self.view.view(ids!(translate_panel)).set_visible(cx, false);
+        self.view.redraw(cx);
+    }
+
+    // ── Agent mode ─────────────────────────────────────────────────────
+
+    /// Ask for the goal of an agent run
+    fn open_agent_goal(&mut self, cx: &mut Cx, scope: &mut Scope) {
+        if self.agent_run.is_some() {
+            self.view.view(ids!(agent_panel)).set_visible(cx, true);
+            self.view.redraw(cx);
+            return;
+        }
+        let Some(store) = scope.data.get::<Store>() else { return };
+        let message = if !chat_tools::offers_functions(&self.chat_tools(store)) {
+            format!("The model works toward the goal on its own for up to {} steps. No agent tools are on in this chat; \
+                turn on Files, Run Code or an MCP server under Tools… to let it act.", agent::DEFAULT_MAX_STEPS)
+        } else {
+            format!("The model works toward the goal on its own, using this chat's tools for up to {} steps. \
+                Each step is kept in the chat, and you can pause or stop it at any time.", agent::DEFAULT_MAX_STEPS)
+        };
+        self.view.moly_dialog(ids!(dialog)).open(cx,
+            DialogRequest::text_input(live_id!(agent_goal), "Agent Mode", message, "")
+                .placeholder("e.g. Summarise the notes in my shared folder")
+                .confirm_label("Start"));
+    }
+
+    /// Start an agent run on the selected model, with the chat so far as
+    /// context and the goal posted as the user's message
+    fn start_agent(&mut self, cx: &mut Cx, scope: &mut Scope, goal: String) {
+        use moly_kit::aitk::protocol::{EntityId, Message, MessageContent};
+
+        let goal = goal.trim().to_string();
+        if goal.is_empty() || self.agent_run.is_some() {
+            return;
+        }
+        let Some(store) = scope.data.get::<Store>() else { return };
+
+        let (bot_id, history) = {
+            let ctrl = self.chat_controller.lock().unwrap();
+            let history: Vec<(String, String)> = ctrl.state().messages.iter()
+                .filter(|m| !m.metadata.is_writing && !m.content.text.is_empty())
+                .filter_map(|m| match &m.from {
+                    EntityId::User => Some(("user".to_string(), m.content.text.clone())),
+                    EntityId::Bot(_) => Some(("assistant".to_string(), m.content.text.clone())),
+                    _ => None,
+                })
+                .collect();
+            (ctrl.state().bot_id.clone(), history)
+        };
+        // The loop speaks the OpenAI chat completions API
+        let provider = bot_id.as_ref()
+            .and_then(|id| store.providers_manager.get_provider_for_bot(id))
+            .and_then(|provider_id| store.preferences.get_provider(&provider_id.to_string()))
+            .filter(|provider| provider.provider_type == ProviderType::OpenAi);
+        let (Some(bot_id), Some(provider)) = (bot_id, provider) else {
+            self.view.moly_dialog(ids!(dialog)).open(cx, DialogRequest::confirm(live_id!(agent_unavailable), "Agent Mode",
+                "Agent mode needs a model from an OpenAI-compatible provider. Choose one in the model selector first."));
+            return;
+        };
+
+        let request = AgentRequest {
+            base_url: provider.url.clone(),
+            api_key: provider.api_key.clone(),
+            model: Self::parse_bot_id_string(bot_id.as_str()).0,
+            history,
+            goal: goal.clone(),
+            tools: self.chat_tools(store),
+            max_steps: agent::DEFAULT_MAX_STEPS,
+            chat_id: self.current_chat_id,
+        };
+        ::log::info!("Starting agent run with {} on {}", request.model, provider.id);
+
+        {
+            let mut ctrl = self.chat_controller.lock().unwrap();
+            ctrl.dispatch_mutation(VecMutation::Push(Message {
+                from: EntityId::User,
+                content: MessageContent {
+                    text: goal,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }));
+            self.last_mode_msg_count = ctrl.state().messages.len();
+        }
+        self.in_welcome_mode = false;
+
+        self.agent_run = Some(agent::start(request));
+        telemetry::record_feature("chat.agent");
+        self.agent_chat_id = self.current_chat_id;
+        self.agent_sources.clear();
+        self.view.agent_timeline(ids!(agent_panel.agent_timeline)).clear();
+        self.view.view(ids!(agent_panel)).set_visible(cx, true);
+        self.update_agent_panel(cx, "Agent working…");
+        self.view.redraw(cx);
+    }
+
+    /// Show the run's status and the controls that apply to it
+    fn update_agent_panel(&self, cx: &mut Cx, status: &str) {
+        let running = self.agent_run.is_some();
+        let paused = self.agent_run.as_ref().is_some_and(|(control, _)| control.is_paused());
+        self.view.label(ids!(agent_panel.agent_status)).set_text(cx, status);
+        let pause_button = self.view.button(ids!(agent_panel.agent_pause_button));
+        pause_button.set_text(cx, if paused { "Resume" } else { "Pause" });
+        pause_button.set_visible(cx, running);
+        self.view.button(ids!(agent_panel.agent_stop_button)).set_visible(cx, running);
+        self.view.button(ids!(agent_panel.agent_close_button)).set_visible(cx, !running);
+    }
+
+    fn handle_agent_controls(&mut self, cx: &mut Cx, actions: &Actions) {
+        if self.view.button(ids!(agent_panel.agent_pause_button)).clicked(actions) {
+            if let Some((control, _)) = &self.agent_run {
+                control.set_paused(!control.is_paused());
+                let status = if control.is_paused() {
+                    "Agent paused; it stops before its next step"
+                } else {
+                    "Agent working…"
+                };
+                self.update_agent_panel(cx, status);
+            }
+        }
+        if self.view.button(ids!(agent_panel.agent_stop_button)).clicked(actions) {
+            if let Some((control, _)) = &self.agent_run {
+                control.abort();
+                self.view.label(ids!(agent_panel.agent_status)).set_text(cx, "Stopping…");
+            }
+        }
+        let steps_button = self.view.button(ids!(agent_panel.agent_steps_button));
+        if steps_button.clicked(actions) {
+            self.agent_steps_hidden = !self.agent_steps_hidden;
+            self.view.widget(ids!(agent_panel.agent_timeline)).set_visible(cx, !self.agent_steps_hidden);
+            steps_button.set_text(cx, if self.agent_steps_hidden { "Show steps" } else { "Hide steps" });
+            self.view.redraw(cx);
+        }
+        if self.view.button(ids!(agent_panel.agent_close_button)).clicked(actions) {
+            self.view.view(ids!(agent_panel)).set_visible(cx, false);
+            self.view.redraw(cx);
+        }
+    }
+
+    /// Add the agent's steps to the timeline and the chat, and post its
+    /// answer when the run ends
+    fn poll_agent(&mut self, cx: &mut Cx, scope: &mut Scope) {
+        let Some((_, events)) = &self.agent_run else { return };
+        let events: Vec<AgentEvent> = events.try_iter().collect();
+        if events.is_empty() {
+            return;
+        }
+
+        let timeline = self.view.agent_timeline(ids!(agent_panel.agent_timeline));
+        for event in events {
+            match event {
+                AgentEvent::Step(step) => {
+                    let status = format!("Agent working… step {}: {}", step.number, step.title);
+                    self.push_agent_message(scope, false, step.message_text());
+                    timeline.push_step(step);
+                    self.update_agent_panel(cx, &status);
+                }
+                AgentEvent::Source(source) => {
+                    self.agent_sources.push(source);
+                }
+                AgentEvent::Finished(answer) => {
+                    self.agent_run = None;
+                    let steps = timeline.steps();
+                    self.post_agent_answer(scope, answer);
+                    let status = match steps.last() {
+                        Some(step) => format!("Agent finished after {} steps", step.number),
+                        None => "Agent finished without using tools".to_string(),
+                    };
+                    self.update_agent_panel(cx, &status);
+                }
+                AgentEvent::Failed(e) => {
+                    self.agent_run = None;
+                    ::log::warn!("Agent run failed: {}", e);
+                    telemetry::record_error(&MolyError::from_message(&e));
+                    let status = format!("Agent stopped: {}", MolyError::from_message(&e).user_message());
+                    self.update_agent_panel(cx, &status);
+                }
+                AgentEvent::Aborted => {
+                    self.agent_run = None;
+                    self.update_agent_panel(cx, "Agent stopped");
+                }
+            }
+        }
+        self.view.redraw(cx);
+    }
+
+    /// Post the final answer with its sources, in the chat the run belongs
+    /// to
+    fn post_agent_answer(&mut self, scope: &mut Scope, answer: String) {
+        let mut text = if answer.is_empty() { "The agent finished without an answer.".to_string() } else { answer };
+        let sources = std::mem::take(&mut self.agent_sources);
+        text.push_str(&citations::footer(&text, &sources));
+        let Some(index) = self.push_agent_message(scope, true, text) else { return };
+        // For the open chat, stored with the message once it is synced
+        if let (Some(chat_id), Some(store)) = (self.agent_chat_id, scope.data.get_mut::<Store>()) {
+            store.chats.set_message_sources(chat_id, index, sources);
+        }
+    }
+
+    /// Add a message to the chat the agent run belongs to, even if another
+    /// one is open now. Answers come from the chat's bot; steps come from
+    /// the app, so they are kept in the chat but never sent to a model.
+    /// Returns the message's index.
+    fn push_agent_message(&mut self, scope: &mut Scope, from_bot: bool, text: String) -> Option<usize> {
+        use moly_kit::aitk::protocol::{EntityId, Message, MessageContent};
+
+        let message = |bot_id: Option<BotId>| Message {
+            from: match bot_id {
+                Some(bot_id) if from_bot => EntityId::Bot(bot_id),
+                None if from_bot => EntityId::System,
+                _ => EntityId::App,
+            },
+            content: MessageContent {
+                text,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        if self.agent_chat_id == self.current_chat_id {
+            let mut ctrl = self.chat_controller.lock().unwrap();
+            let bot_id = ctrl.state().bot_id.clone();
+            ctrl.dispatch_mutation(VecMutation::Push(message(bot_id)));
+            self.last_mode_msg_count = ctrl.state().messages.len();
+            return Some(self.last_mode_msg_count - 1);
+        }
+        let (chat_id, store) = (self.agent_chat_id?, scope.data.get_mut::<Store>()?);
+        let chat = store.chats.get_chat_by_id(chat_id)?;
+        let mut messages = chat.messages.clone();
+        messages.push(message(chat.bot_id.clone()));
+        let index = messages.len() - 1;
+        store.chats.update_chat_messages(chat_id, messages);
+        Some(index)
+    }
 }
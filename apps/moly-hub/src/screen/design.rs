@@ -1,11 +1,13 @@
 use makepad_widgets::*;
 use super::ModelHubApp;
+use super::panel_header::HubPanelHeader;
 
 live_design! {
     use link::theme::*;
     use link::shaders::*;
     use link::widgets::*;
     use moly_widgets::theme::*;
+    use moly_widgets::voice_studio::design::*;
 
     // ── Category badge (5 categories: LLM=0, VLM=1, ASR=2, TTS=3, Image=4) ──
 
@@ -134,6 +136,23 @@ live_design! {
             width: Fill, height: Fit
             flow: Right
             align: {y: 0.5}
+            // Checkbox, shown while selecting models for a bulk action
+            model_check = <View> {
+                width: 14, height: 14
+                margin: {right: 8}
+                visible: false
+                show_bg: true
+                draw_bg: {
+                    instance checked: 0.0
+                    fn pixel(self) -> vec4 {
+                        let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                        sdf.box(1.0, 1.0, self.rect_size.x - 2.0, self.rect_size.y - 2.0, 3.0);
+                        sdf.fill_keep(mix(#ffffff, #3b82f6, self.checked));
+                        sdf.stroke(mix(#9ca3af, #3b82f6, self.checked), 1.0);
+                        return sdf.result;
+                    }
+                }
+            }
             model_status = <HubStatusDot> {}
             model_name = <Label> {
                 width: Fill
@@ -145,6 +164,28 @@ live_design! {
                     wrap: Ellipsis
                 }
             }
+            new_badge = <View> {
+                width: Fit, height: Fit
+                padding: {left: 6, right: 6, top: 2, bottom: 2}
+                margin: {left: 6}
+                visible: false
+                show_bg: true
+                draw_bg: {
+                    fn pixel(self) -> vec4 {
+                        let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                        sdf.box(0.0, 0.0, self.rect_size.x, self.rect_size.y, 3.0);
+                        sdf.fill(#e0e7ff);
+                        return sdf.result;
+                    }
+                }
+                <Label> {
+                    text: "New"
+                    draw_text: {
+                        fn get_color(self) -> vec4 { return #4338ca; }
+                        text_style: <FONT_MEDIUM>{ font_size: 9.0 }
+                    }
+                }
+            }
             downloaded_badge = <View> {
                 width: Fit, height: Fit
                 padding: {left: 6, right: 6, top: 2, bottom: 2}
@@ -167,6 +208,50 @@ live_design! {
                     }
                 }
             }
+            update_badge = <View> {
+                width: Fit, height: Fit
+                padding: {left: 6, right: 6, top: 2, bottom: 2}
+                margin: {left: 6}
+                visible: false
+                show_bg: true
+                draw_bg: {
+                    fn pixel(self) -> vec4 {
+                        let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                        sdf.box(0.0, 0.0, self.rect_size.x, self.rect_size.y, 3.0);
+                        sdf.fill(#fef3c7);
+                        return sdf.result;
+                    }
+                }
+                <Label> {
+                    text: "Update available"
+                    draw_text: {
+                        fn get_color(self) -> vec4 { return #b45309; }
+                        text_style: <FONT_MEDIUM>{ font_size: 9.0 }
+                    }
+                }
+            }
+            deprecated_badge = <View> {
+                width: Fit, height: Fit
+                padding: {left: 6, right: 6, top: 2, bottom: 2}
+                margin: {left: 6}
+                visible: false
+                show_bg: true
+                draw_bg: {
+                    fn pixel(self) -> vec4 {
+                        let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                        sdf.box(0.0, 0.0, self.rect_size.x, self.rect_size.y, 3.0);
+                        sdf.fill(#e5e7eb);
+                        return sdf.result;
+                    }
+                }
+                <Label> {
+                    text: "Deprecated"
+                    draw_text: {
+                        fn get_color(self) -> vec4 { return #4b5563; }
+                        text_style: <FONT_MEDIUM>{ font_size: 9.0 }
+                    }
+                }
+            }
         }
         inline_progress = <HubInlineProgress> { visible: false }
     }
@@ -251,6 +336,57 @@ live_design! {
         }
     }
 
+    // ── Segmented mode button (selected is set from code) ──
+
+    HubModeButton = <HubActionButton> {
+        margin: {right: 4}
+        draw_bg: {
+            instance selected: 0.0
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                sdf.box(0.0, 0.0, self.rect_size.x, self.rect_size.y, 5.0);
+                let idle = mix(#f1f5f9, #e2e8f0, self.hover);
+                let active = #3b82f6;
+                sdf.fill(mix(idle, active, self.selected));
+                return sdf.result;
+            }
+        }
+        draw_text: {
+            instance selected: 0.0
+            fn get_color(self) -> vec4 { return mix(#374151, #ffffff, self.selected); }
+        }
+    }
+
+    // ── Image drop zone (drag_over is set from code) ──
+
+    HubImageDropZone = <View> {
+        width: Fill, height: 64
+        margin: {bottom: 6}
+        show_bg: true
+        draw_bg: {
+            instance drag_over: 0.0
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                let border = mix(#d1d5db, #ec4899, self.drag_over);
+                let fill = mix(#f9fafb, #fdf2f8, self.drag_over);
+                sdf.box(2.0, 2.0, self.rect_size.x - 4.0, self.rect_size.y - 4.0, 8.0);
+                sdf.fill(border);
+                sdf.box(3.5, 3.5, self.rect_size.x - 7.0, self.rect_size.y - 7.0, 6.5);
+                sdf.fill(fill);
+                return sdf.result;
+            }
+        }
+        align: {x: 0.5, y: 0.5}
+
+        drop_label = <Label> {
+            text: "Drop an image here"
+            draw_text: {
+                color: (TEXT_MUTED)
+                text_style: { font_size: 12.0 }
+            }
+        }
+    }
+
     // ── Info row (label + value) ──
 
     HubInfoRow = <View> {
@@ -373,7 +509,7 @@ live_design! {
     }
 
     // Shared model detail header included in each type panel
-    HubPanelHeader = <View> {
+    HubPanelHeader = {{HubPanelHeader}} {
         width: Fill, height: Fit
         flow: Down
         padding: {left: 28, right: 28, top: 22, bottom: 16}
@@ -468,6 +604,14 @@ live_design! {
             flow: Right
             margin: {bottom: 10}
             panel_download_btn = <HubActionButton> { text: "Download" }
+            panel_lan_btn = <HubActionButton> {
+                text: "Import from LAN"
+                visible: false
+            }
+            panel_update_btn = <HubActionButton> {
+                text: "Update"
+                visible: false
+            }
             panel_cancel_btn = <HubActionButton> {
                 text: "Cancel"
                 visible: false
@@ -506,6 +650,55 @@ live_design! {
             panel_chat_btn = <HubActionButton> {
                 text: "Open in Chat"
             }
+            panel_preload_btn = <HubActionButton> {
+                text: "Preload on startup"
+                visible: false
+            }
+            panel_options_btn = <HubActionButton> {
+                text: "Advanced ▸"
+                visible: false
+            }
+        }
+
+        // Runtime options sent with the load request (opened with Advanced)
+        panel_options = <View> {
+            visible: false
+            width: Fill, height: Fit
+            flow: Down
+            margin: {bottom: 8}
+            <View> {
+                width: Fill, height: Fit
+                flow: Right
+                spacing: 8
+                <View> {
+                    width: Fill, height: Fit
+                    flow: Down
+                    <HubInputLabel> { text: "CONTEXT LENGTH" }
+                    panel_ctx_input = <HubPanelInput> { empty_text: "Default" }
+                }
+                <View> {
+                    width: Fill, height: Fit
+                    flow: Down
+                    <HubInputLabel> { text: "GPU LAYERS" }
+                    panel_gpu_layers_input = <HubPanelInput> { empty_text: "Default" }
+                }
+                <View> {
+                    width: Fill, height: Fit
+                    flow: Down
+                    <HubInputLabel> { text: "THREADS" }
+                    panel_threads_input = <HubPanelInput> { empty_text: "Default" }
+                }
+            }
+            <View> {
+                width: Fill, height: Fit
+                flow: Right
+                margin: {top: 6}
+                panel_options_save_btn = <HubActionButton> { text: "Save options" }
+                panel_options_reset_btn = <HubActionButton> { text: "Reset" }
+            }
+            panel_options_hint = <HubPanelStatus> {
+                text: "Used every time this model is loaded. Empty fields keep the runtime's default."
+            }
         }
 
         // Progress bar (visible while downloading)
@@ -553,6 +746,132 @@ live_design! {
     }
 
     // ── TTS voice selector item ──
+    HubAsrSegmentItem = <View> {
+        width: Fill, height: Fit
+        padding: {left: 10, right: 10, top: 6, bottom: 6}
+        cursor: Hand
+        flow: Right
+        spacing: 10
+        show_bg: true
+        draw_bg: {
+            instance hover: 0.0
+            // The segment open in the transcript editor
+            instance selected: 0.0
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                sdf.box(0.0, 0.0, self.rect_size.x, self.rect_size.y, 4.0);
+                sdf.fill(mix(mix(#ffffff, #f1f5f9, self.hover), #dbeafe, self.selected));
+                return sdf.result;
+            }
+        }
+        segment_time = <Label> {
+            width: 110
+            draw_text: {
+                fn get_color(self) -> vec4 { return #6b7280; }
+                text_style: <FONT_MEDIUM>{ font_size: 10.5 }
+            }
+        }
+        segment_text = <Label> {
+            width: Fill
+            draw_text: {
+                fn get_color(self) -> vec4 { return #1f2937; }
+                text_style: <FONT_REGULAR>{ font_size: 11.5 }
+                wrap: Word
+            }
+        }
+    }
+
+    HubOllamaModelItem = <View> {
+        width: Fill, height: 30
+        padding: {left: 10, right: 10}
+        flow: Right
+        align: {y: 0.5}
+        spacing: 10
+        model_name = <Label> {
+            width: Fill
+            draw_text: {
+                fn get_color(self) -> vec4 { return #1f2937; }
+                text_style: <FONT_REGULAR>{ font_size: 11.0 }
+                wrap: Ellipsis
+            }
+        }
+        model_size = <Label> {
+            width: Fit
+            draw_text: {
+                fn get_color(self) -> vec4 { return #6b7280; }
+                text_style: <FONT_REGULAR>{ font_size: 10.5 }
+            }
+        }
+    }
+
+    HubAsrBatchItem = <View> {
+        width: Fill, height: 30
+        padding: {left: 10, right: 10}
+        flow: Right
+        align: {y: 0.5}
+        spacing: 10
+        batch_file_name = <Label> {
+            width: Fill
+            draw_text: {
+                fn get_color(self) -> vec4 { return #1f2937; }
+                text_style: <FONT_REGULAR>{ font_size: 11.0 }
+                wrap: Ellipsis
+            }
+        }
+        batch_file_status = <Label> {
+            width: Fit
+            draw_text: {
+                fn get_color(self) -> vec4 { return #6b7280; }
+                text_style: <FONT_REGULAR>{ font_size: 10.5 }
+            }
+        }
+    }
+
+    HubImageHistoryItem = <View> {
+        width: 160, height: Fill
+        margin: {right: 10}
+        padding: 6
+        flow: Down
+        spacing: 4
+        show_bg: true
+        draw_bg: {
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                sdf.box(1.0, 1.0, self.rect_size.x - 2.0, self.rect_size.y - 2.0, 6.0);
+                sdf.fill(#f8fafc);
+                sdf.stroke(#e5e7eb, 1.0);
+                return sdf.result;
+            }
+        }
+        history_thumb = <Image> {
+            width: Fill, height: 100
+            fit: Biggest
+        }
+        history_prompt = <Label> {
+            width: Fill
+            draw_text: {
+                fn get_color(self) -> vec4 { return #1f2937; }
+                text_style: <FONT_REGULAR>{ font_size: 10.5 }
+                wrap: Ellipsis
+            }
+        }
+        history_meta = <Label> {
+            width: Fill
+            draw_text: {
+                fn get_color(self) -> vec4 { return #6b7280; }
+                text_style: <FONT_REGULAR>{ font_size: 9.5 }
+                wrap: Ellipsis
+            }
+        }
+        <View> {
+            width: Fill, height: Fit
+            flow: Right
+            spacing: 6
+            history_use_btn = <HubActionButton> { text: "Use", width: Fit }
+            history_delete_btn = <HubActionButton> { text: "Delete", width: Fit }
+        }
+    }
+
     HubTtsVoiceItem = <View> {
         width: Fill, height: 40
         padding: {left: 12, right: 12, top: 8, bottom: 8}
@@ -665,49 +984,6 @@ live_design! {
     }
 
     // ── Voice list item inside Voice Studio panel ──
-    HubVoiceListItem = <View> {
-        width: Fill, height: 40
-        padding: {left: 12, right: 12, top: 8, bottom: 8}
-        margin: {left: 4, right: 4}
-        cursor: Hand
-        event_order: Down
-        flow: Right
-        align: {y: 0.5}
-        show_bg: true
-        draw_bg: {
-            instance selected: 0.0
-            fn pixel(self) -> vec4 {
-                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
-                sdf.box(1.0, 1.0, self.rect_size.x - 2.0, self.rect_size.y - 2.0, 6.0);
-                let normal   = #ffffff;
-                let sel_col  = #eaecf0;
-                sdf.fill(mix(normal, sel_col, self.selected));
-                return sdf.result;
-            }
-        }
-        voice_status_dot = <View> {
-            width: 8, height: 8
-            margin: {right: 8}
-            draw_bg: {
-                instance ready: 0.0
-                fn pixel(self) -> vec4 {
-                    let sdf = Sdf2d::viewport(self.pos * self.rect_size);
-                    sdf.circle(4.0, 4.0, 4.0);
-                    sdf.fill(mix(#d1d5db, #22c55a, self.ready));
-                    return sdf.result;
-                }
-            }
-        }
-        voice_item_name = <Label> {
-            width: Fill
-            draw_text: {
-                fn get_color(self) -> vec4 { return #1f2937; }
-                text_style: <FONT_REGULAR>{ font_size: 11.5 }
-                wrap: Ellipsis
-            }
-        }
-    }
-
     pub ModelHubApp = {{ModelHubApp}} {
         width: Fill, height: Fill
         flow: Right
@@ -786,21 +1062,119 @@ live_design! {
                 }
             }
 
-            // Model list
-            hub_model_list = <PortalList> {
-                width: Fill, height: Fill
-                flow: Down
-                HubModelItem        = <HubModelListItem> {}
-                HubCategoryHeader   = <HubCategoryGroupHeader> {}
-                HubSubfolderHeader  = <HubSubfolderGroupHeader> {}
-                HubVoiceStudioItem  = <HubVoiceStudioItem> {}
-            }
-
-        }
-
-        // Vertical divider – 8 px wide for easy dragging, visually 1px center line
-        hub_main_divider = <View> {
-            width: 8, height: Fill
+            // Select several models for a bulk download, verify or remove
+            <View> {
+                width: Fill, height: Fit
+                flow: Right
+                align: {y: 0.5}
+                padding: {left: 10, right: 2, top: 4, bottom: 4}
+                hub_bulk_count = <Label> {
+                    width: Fill
+                    text: ""
+                    draw_text: {
+                        fn get_color(self) -> vec4 { return #6b7280; }
+                        text_style: <FONT_REGULAR>{ font_size: 10.5 }
+                    }
+                }
+                hub_runtime_log_btn = <HubModeButton> {
+                    height: 26
+                    padding: {left: 10, right: 10}
+                    text: "Runtime log"
+                }
+                hub_diagnostics_btn = <HubModeButton> {
+                    height: 26
+                    padding: {left: 10, right: 10}
+                    text: "Diagnostics"
+                }
+                hub_ollama_btn = <HubModeButton> {
+                    height: 26
+                    padding: {left: 10, right: 10}
+                    text: "Ollama"
+                }
+                hub_select_btn = <HubActionButton> {
+                    height: 26
+                    padding: {left: 10, right: 10}
+                    text: "Select"
+                }
+            }
+            hub_bulk_bar = <View> {
+                width: Fill, height: Fit
+                visible: false
+                flow: Down
+                spacing: 6
+                padding: {left: 10, right: 2, bottom: 6}
+                <View> {
+                    width: Fill, height: Fit
+                    flow: Right
+                    hub_bulk_download_btn = <HubActionButton> { height: 26, padding: {left: 10, right: 10}, text: "Download" }
+                    hub_bulk_verify_btn = <HubActionButton> { height: 26, padding: {left: 10, right: 10}, text: "Verify" }
+                    hub_bulk_remove_btn = <HubActionButton> {
+                        height: 26, padding: {left: 10, right: 10}
+                        text: "Remove"
+                        draw_bg: { danger: 1.0 }
+                    }
+                    hub_bulk_plan_btn = <HubActionButton> { height: 26, padding: {left: 10, right: 10}, text: "Plan" }
+                }
+                hub_bulk_status = <Label> {
+                    width: Fill
+                    text: ""
+                    draw_text: {
+                        fn get_color(self) -> vec4 { return #374151; }
+                        text_style: <FONT_REGULAR>{ font_size: 10.5 }
+                        wrap: Word
+                    }
+                }
+            }
+
+            // Model list
+            hub_model_list = <PortalList> {
+                width: Fill, height: Fill
+                flow: Down
+                HubModelItem        = <HubModelListItem> {}
+                HubCategoryHeader   = <HubCategoryGroupHeader> {}
+                HubSubfolderHeader  = <HubSubfolderGroupHeader> {}
+                HubVoiceStudioItem  = <HubVoiceStudioItem> {}
+            }
+
+            // Undo toast for a model removal, shown for a few seconds
+            hub_undo_bar = <View> {
+                width: Fill, height: Fit
+                visible: false
+                flow: Right
+                align: {y: 0.5}
+                spacing: 8
+                margin: {left: 10, right: 10, bottom: 10}
+                padding: {left: 12, right: 6, top: 6, bottom: 6}
+                show_bg: true
+                draw_bg: {
+                    fn pixel(self) -> vec4 {
+                        let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                        sdf.box(0.0, 0.0, self.rect_size.x, self.rect_size.y, 6.0);
+                        sdf.fill(#1f2937);
+                        return sdf.result;
+                    }
+                }
+                hub_undo_label = <Label> {
+                    width: Fill
+                    draw_text: {
+                        fn get_color(self) -> vec4 {
+                            return #f9fafb;
+                        }
+                        text_style: <FONT_REGULAR>{ font_size: 11.0 }
+                        wrap: Word
+                    }
+                }
+                hub_undo_btn = <HubActionButton> {
+                    margin: 0
+                    text: "Undo"
+                }
+            }
+
+        }
+
+        // Vertical divider – 8 px wide for easy dragging, visually 1px center line
+        hub_main_divider = <View> {
+            width: 8, height: Fill
             show_bg: true
             draw_bg: {
                 fn pixel(self) -> vec4 {
@@ -875,11 +1249,54 @@ live_design! {
                         flow: Right
                         margin: {top: 10, bottom: 16}
                         llm_generate_btn = <HubActionButton> { text: "Generate" }
+                        llm_export_request_btn = <HubActionButton> { text: "Export Request..." }
                     }
 
                     <HubInputLabel> { text: "RESPONSE" }
                     llm_response = <HubPanelOutput> {}
                     llm_status = <HubPanelStatus> {}
+
+                    // Prompt format; Server Default leaves it to the runtime
+                    <HubInputLabel> { text: "CHAT TEMPLATE" }
+                    <View> {
+                        width: Fill, height: Fit
+                        flow: Right
+                        llm_template_default_btn = <HubModeButton> {
+                            text: "Server Default"
+                            draw_bg: { selected: 1.0 }
+                            draw_text: { selected: 1.0 }
+                        }
+                        llm_template_chatml_btn  = <HubModeButton> { text: "ChatML" }
+                        llm_template_llama3_btn  = <HubModeButton> { text: "Llama 3" }
+                        llm_template_qwen_btn    = <HubModeButton> { text: "Qwen" }
+                        llm_template_mistral_btn = <HubModeButton> { text: "Mistral" }
+                        llm_template_gemma_btn   = <HubModeButton> { text: "Gemma" }
+                    }
+                    llm_template_hint = <HubPanelStatus> {}
+
+                    // Batch evaluation: run every prompt of a JSONL file
+                    <HubInputLabel> { text: "BATCH EVALUATION", margin: {top: 24, bottom: 4} }
+                    <View> {
+                        width: Fill, height: Fit
+                        flow: Right
+                        align: {y: 0.5}
+                        llm_eval_file_btn   = <HubActionButton> { text: "Choose JSONL..." }
+                        llm_eval_start_btn  = <HubActionButton> { text: "Run Eval" visible: false }
+                        llm_eval_cancel_btn = <HubActionButton> {
+                            text: "Cancel Eval"
+                            visible: false
+                            draw_bg: { danger: 1.0 }
+                        }
+                        llm_eval_export_btn = <HubActionButton> { text: "Export Results..." visible: false }
+                    }
+                    llm_eval_summary = <HubPanelStatus> {
+                        text: "Choose a JSONL file of prompts, with expected answers or patterns to score them."
+                    }
+                    llm_eval_list = <PortalList> {
+                        width: Fill, height: 220
+                        flow: Down
+                        HubAsrBatchItem = <HubAsrBatchItem> {}
+                    }
                 }
             }
 
@@ -929,7 +1346,7 @@ live_design! {
                         vlm_drop_label = <Label> {
                             text: "Drop image here"
                             draw_text: {
-                                color: #9ca3af
+                                color: (TEXT_MUTED)
                                 text_style: { font_size: 12.0 }
                             }
                         }
@@ -944,7 +1361,15 @@ live_design! {
                             width: Fill, height: 36
                             margin: {right: 6, bottom: 0}
                         }
-                        vlm_browse_btn = <HubActionButton> { text: "Browse..." margin: {right: 0} }
+                        vlm_browse_btn = <HubActionButton> { text: "Browse..." }
+                        vlm_capture_region_btn = <HubActionButton> { text: "Capture Region" }
+                        vlm_capture_screen_btn = <HubActionButton> { text: "Capture Screen" margin: {right: 0} }
+                    }
+                    vlm_preview = <Image> {
+                        width: Fill, height: 160
+                        visible: false
+                        margin: {bottom: 8}
+                        fit: Smallest
                     }
 
                     <HubInputLabel> { text: "USER MESSAGE" }
@@ -958,6 +1383,9 @@ live_design! {
                         flow: Right
                         margin: {top: 10, bottom: 16}
                         vlm_generate_btn = <HubActionButton> { text: "Generate" }
+                        vlm_ocr_btn = <HubActionButton> { text: "Extract Text" }
+                        vlm_copy_btn = <HubActionButton> { text: "Copy" }
+                        vlm_export_request_btn = <HubActionButton> { text: "Export Request..." }
                     }
 
                     <HubInputLabel> { text: "RESPONSE" }
@@ -1005,11 +1433,133 @@ live_design! {
                         flow: Right
                         margin: {top: 10, bottom: 16}
                         asr_transcribe_btn = <HubActionButton> { text: "Transcribe" }
+                        asr_notes_btn = <HubActionButton> { text: "Meeting Notes" }
+                        asr_export_request_btn = <HubActionButton> { text: "Export Request..." }
                     }
 
                     <HubInputLabel> { text: "TRANSCRIPT" }
                     asr_transcript = <HubPanelOutput> {}
+
+                    // Timed segments (shown when the server returns timestamps)
+                    asr_segments_section = <View> {
+                        width: Fill, height: Fit
+                        flow: Down
+                        visible: false
+
+                        <View> {
+                            width: Fill, height: Fit
+                            flow: Right
+                            align: {y: 0.5}
+                            <HubInputLabel> { width: Fill, text: "SEGMENTS — click to play" }
+                            asr_edit_btn = <HubActionButton> { text: "Edit Transcript" }
+                            asr_export_srt_btn = <HubActionButton> { text: "Export SRT" }
+                            asr_export_vtt_btn = <HubActionButton> { text: "Export VTT" margin: {right: 0} }
+                        }
+
+                        // Segments beside the editor while correcting
+                        <View> {
+                            width: Fill, height: Fit
+                            flow: Right
+                            spacing: 16
+
+                            asr_segment_list = <PortalList> {
+                                width: Fill, height: 280
+                                flow: Down
+                                HubAsrSegmentItem = <HubAsrSegmentItem> {}
+                            }
+
+                            asr_editor_pane = <View> {
+                                width: Fill, height: Fit
+                                flow: Down
+                                visible: false
+
+                                asr_edit_time = <HubInputLabel> { text: "" }
+                                asr_edit_input = <HubPanelInput> {
+                                    height: 110
+                                    empty_text: "What is said in this segment"
+                                }
+                                <View> {
+                                    width: Fill, height: Fit
+                                    flow: RightWrap
+                                    margin: {top: 6}
+                                    asr_edit_prev_btn   = <HubActionButton> { text: "◀ Previous" }
+                                    asr_edit_next_btn   = <HubActionButton> { text: "Next ▶" }
+                                    asr_edit_play_btn   = <HubActionButton> { text: "Play from Here" }
+                                    asr_edit_stop_btn   = <HubActionButton> { text: "Stop" }
+                                    asr_edit_revert_btn = <HubActionButton> { text: "Revert" margin: {right: 0} }
+                                }
+                                asr_edit_summary = <HubPanelStatus> {}
+
+                                <HubInputLabel> { text: "CORRECTED TRANSCRIPT", margin: {top: 12, bottom: 4} }
+                                <View> {
+                                    width: Fill, height: Fit
+                                    flow: Right
+                                    asr_edit_save_btn = <HubActionButton> { text: "Save Corrections" }
+                                    asr_edit_export_txt_btn = <HubActionButton> { text: "Export Text..." margin: {right: 0} }
+                                }
+
+                                <HubInputLabel> { text: "VOICE TRAINING DATASET", margin: {top: 12, bottom: 4} }
+                                <View> {
+                                    width: Fill, height: Fit
+                                    flow: Right
+                                    align: {y: 0.5}
+                                    asr_dataset_name = <HubPanelInput> {
+                                        width: Fill
+                                        margin: {right: 6, bottom: 0}
+                                        empty_text: "Dataset name"
+                                    }
+                                    asr_dataset_btn = <HubActionButton> { text: "Create Dataset" margin: {right: 0} }
+                                }
+                            }
+                        }
+                    }
+
                     asr_status = <HubPanelStatus> {}
+
+                    // Batch: transcribe every audio file in a folder
+                    <HubInputLabel> { text: "BATCH TRANSCRIPTION", margin: {top: 24, bottom: 4} }
+                    <View> {
+                        width: Fill, height: Fit
+                        flow: Right
+                        align: {y: 0.5}
+                        asr_batch_folder_btn = <HubActionButton> { text: "Choose Folder..." }
+                        asr_batch_output_btn = <HubActionButton> { text: "Output Folder..." }
+                        asr_batch_start_btn  = <HubActionButton> { text: "Start Batch" visible: false }
+                        asr_batch_cancel_btn = <HubActionButton> {
+                            text: "Cancel Batch"
+                            visible: false
+                            draw_bg: { danger: 1.0 }
+                        }
+                    }
+                    asr_batch_summary = <HubPanelStatus> {
+                        text: "Choose a folder of audio files to transcribe."
+                    }
+                    asr_batch_list = <PortalList> {
+                        width: Fill, height: 220
+                        flow: Down
+                        HubAsrBatchItem = <HubAsrBatchItem> {}
+                    }
+
+                    <HubInputLabel> { text: "WATCH FOLDER", margin: {top: 24, bottom: 4} }
+                    <View> {
+                        width: Fill, height: Fit
+                        flow: Right
+                        align: {y: 0.5}
+                        asr_watch_folder_btn = <HubActionButton> { text: "Watch Folder..." }
+                        asr_watch_stop_btn   = <HubActionButton> {
+                            text: "Stop Watching"
+                            visible: false
+                            draw_bg: { danger: 1.0 }
+                        }
+                    }
+                    asr_watch_summary = <HubPanelStatus> {
+                        text: "Audio dropped into a watched folder is transcribed as it arrives, with the text saved next to it."
+                    }
+                    asr_watch_list = <PortalList> {
+                        width: Fill, height: 160
+                        flow: Down
+                        HubAsrBatchItem = <HubAsrBatchItem> {}
+                    }
                 }
             }
 
@@ -1052,6 +1602,12 @@ live_design! {
                         flow: Right
                         margin: {top: 10, bottom: 16}
                         tts_generate_btn = <HubActionButton> { text: "Generate & Play" }
+                        tts_cancel_btn = <HubActionButton> {
+                            text: "Cancel"
+                            visible: false
+                            draw_bg: { danger: 1.0 }
+                        }
+                        tts_export_request_btn = <HubActionButton> { text: "Export Request..." }
                     }
 
                     tts_status = <HubPanelStatus> {}
@@ -1071,6 +1627,30 @@ live_design! {
                             visible: false
                         }
                     }
+
+                    // Audiobook: document → per-chapter audio + joined book
+                    <HubInputLabel> { text: "AUDIOBOOK", margin: {top: 24, bottom: 4} }
+                    <View> {
+                        width: Fill, height: Fit
+                        flow: Right
+                        align: {y: 0.5}
+                        tts_book_file_btn   = <HubActionButton> { text: "Choose Document..." }
+                        tts_book_output_btn = <HubActionButton> { text: "Output Folder..." }
+                        tts_book_start_btn  = <HubActionButton> { text: "Build Audiobook" visible: false }
+                        tts_book_cancel_btn = <HubActionButton> {
+                            text: "Cancel"
+                            visible: false
+                            draw_bg: { danger: 1.0 }
+                        }
+                    }
+                    tts_book_summary = <HubPanelStatus> {
+                        text: "Choose a .txt, .md or .epub document to build chaptered audio."
+                    }
+                    tts_book_list = <PortalList> {
+                        width: Fill, height: 220
+                        flow: Down
+                        HubAsrBatchItem = <HubAsrBatchItem> {}
+                    }
                 }
             }
 
@@ -1101,17 +1681,130 @@ live_design! {
                         empty_text: "A beautiful landscape..."
                     }
 
+                    // Expand the prompt with the loaded LLM
+                    <View> {
+                        width: Fill, height: Fit
+                        flow: Right
+                        align: {y: 0.5}
+                        margin: {top: 4}
+                        img_enhance_btn = <HubActionButton> { text: "Enhance Prompt" }
+                        img_revert_btn = <HubActionButton> { text: "Revert" visible: false }
+                    }
+                    img_enhance_status = <HubPanelStatus> {}
+                    // Word changes from the original prompt, shown after enhancing
+                    img_enhance_diff_view = <View> {
+                        width: Fill, height: Fit
+                        visible: false
+                        margin: {top: 6}
+                        padding: 8
+                        show_bg: true
+                        draw_bg: { fn pixel(self) -> vec4 { return #f8fafc; } }
+                        img_enhance_diff = <Label> {
+                            width: Fill, height: Fit
+                            draw_text: {
+                                fn get_color(self) -> vec4 { return #374151; }
+                                text_style: { font_size: 11.0 }
+                                wrap: Word
+                            }
+                        }
+                    }
+
                     <HubInputLabel> { text: "NEGATIVE PROMPT (OPTIONAL)" }
                     img_neg_prompt = <HubPanelInput> {
                         height: 48
                         empty_text: "blurry, low quality..."
                     }
 
+                    <HubInputLabel> { text: "SEED (OPTIONAL)" }
+                    img_seed = <HubPanelInput> {
+                        width: 200, height: 36
+                        empty_text: "Random"
+                    }
+
+                    <HubInputLabel> { text: "MODE" }
+                    img_mode_row = <View> {
+                        width: Fill, height: Fit
+                        flow: Right
+                        margin: {bottom: 12}
+                        img_mode_text_btn = <HubModeButton> {
+                            text: "Text to Image"
+                            draw_bg: { selected: 1.0 }
+                            draw_text: { selected: 1.0 }
+                        }
+                        img_mode_img2img_btn = <HubModeButton> { text: "Image to Image" }
+                        img_mode_inpaint_btn = <HubModeButton> { text: "Inpaint" }
+                    }
+
+                    // Init image, strength and mask; hidden in Text to Image
+                    img_source_section = <View> {
+                        width: Fill, height: Fit
+                        flow: Down
+                        visible: false
+
+                        <HubInputLabel> { text: "INIT IMAGE" }
+                        img_init_drop_zone = <HubImageDropZone> {}
+                        <View> {
+                            width: Fill, height: Fit
+                            flow: Right
+                            align: {y: 0.5}
+                            margin: {bottom: 8}
+                            img_init_path = <HubPanelInput> {
+                                width: Fill, height: 36
+                                margin: {right: 6, bottom: 0}
+                            }
+                            img_init_browse_btn = <HubActionButton> { text: "Browse..." margin: {right: 0} }
+                        }
+                        img_init_preview = <Image> {
+                            width: Fill, height: 160
+                            visible: false
+                            margin: {bottom: 8}
+                            fit: Smallest
+                        }
+
+                        <HubInputLabel> { text: "STRENGTH" }
+                        img_strength = <HubPanelInput> {
+                            width: 200, height: 36
+                            empty_text: "0.75"
+                        }
+                        <HubPanelStatus> { text: "0 keeps the init image, 1 replaces it." }
+
+                        img_mask_section = <View> {
+                            width: Fill, height: Fit
+                            flow: Down
+                            visible: false
+                            margin: {top: 8}
+
+                            <HubInputLabel> { text: "MASK" }
+                            img_mask_drop_zone = <HubImageDropZone> {
+                                drop_label = { text: "Drop a mask here" }
+                            }
+                            <View> {
+                                width: Fill, height: Fit
+                                flow: Right
+                                align: {y: 0.5}
+                                margin: {bottom: 8}
+                                img_mask_path = <HubPanelInput> {
+                                    width: Fill, height: 36
+                                    margin: {right: 6, bottom: 0}
+                                }
+                                img_mask_browse_btn = <HubActionButton> { text: "Browse..." margin: {right: 0} }
+                            }
+                            img_mask_preview = <Image> {
+                                width: Fill, height: 160
+                                visible: false
+                                margin: {bottom: 8}
+                                fit: Smallest
+                            }
+                            <HubPanelStatus> { text: "White areas are repainted, black areas are kept." }
+                        }
+                    }
+
                     <View> {
                         width: Fill, height: Fit
                         flow: Right
                         margin: {top: 10, bottom: 16}
                         img_generate_btn = <HubActionButton> { text: "Generate Image" }
+                        img_export_request_btn = <HubActionButton> { text: "Export Request..." }
                     }
 
                     img_status = <HubPanelStatus> {}
@@ -1147,6 +1840,15 @@ live_design! {
                             width: Fit
                         }
                     }
+
+                    // Earlier generations, newest first
+                    <HubInputLabel> { text: "HISTORY" }
+                    img_history_empty = <HubPanelStatus> { text: "Generated images appear here." }
+                    img_history_list = <PortalList> {
+                        width: Fill, height: 190
+                        flow: Right
+                        HubImageHistoryItem = <HubImageHistoryItem> {}
+                    }
                 }
             }
 
@@ -1194,7 +1896,7 @@ live_design! {
                         img_edit_drop_label = <Label> {
                             text: "Drop reference image here"
                             draw_text: {
-                                color: #9ca3af
+                                color: (TEXT_MUTED)
                                 text_style: { font_size: 12.0 }
                             }
                         }
@@ -1223,6 +1925,7 @@ live_design! {
                         flow: Right
                         margin: {top: 10, bottom: 16}
                         img_edit_btn = <HubActionButton> { text: "Edit Image" }
+                        img_edit_export_request_btn = <HubActionButton> { text: "Export Request..." }
                     }
 
                     img_edit_status = <HubPanelStatus> {}
@@ -1293,6 +1996,7 @@ live_design! {
                         flow: Right
                         margin: {top: 10, bottom: 16}
                         vid_generate_btn = <HubActionButton> { text: "Generate Video" }
+                        vid_export_request_btn = <HubActionButton> { text: "Export Request..." }
                     }
 
                     vid_status = <HubPanelStatus> {}
@@ -1328,45 +2032,107 @@ live_design! {
                 }
             }
 
-            // ── Voice Studio Panel ──────────────────────────────────────────────
-            hub_voice_panel = <View> {
-            width: Fill, height: Fill
-            visible: false
-            flow: Right
-
-            // Left sub-panel: voice list + actions
-            <View> {
-                width: 240, height: Fill
+            // ── Music Generation Panel ─────────────────────────────────────────
+            hub_music_panel = <ScrollYView> {
+                width: Fill, height: Fill
+                visible: false
                 flow: Down
-                show_bg: true
-                draw_bg: {
-                    fn pixel(self) -> vec4 { return #ffffff; }
+
+                hub_panel_header = <HubPanelHeader> {}
+
+                hub_music_divider = <View> {
+                    width: Fill, height: 1
+                    show_bg: true
+                    draw_bg: {
+                        fn pixel(self) -> vec4 { return #f1f5f9; }
+                    }
                 }
 
-                // Header + New button
                 <View> {
-                    width: Fill, height: 48
-                    padding: {left: 16, right: 8}
-                    align: {y: 0.5}
-                    flow: Right
-                    voice_list_title = <Label> {
-                        width: Fill
-                        text: "Voices"
-                        draw_text: {
-                            fn get_color(self) -> vec4 {
-                                return #1f2937;
+                    width: Fill, height: Fit
+                    flow: Down
+                    padding: {left: 28, right: 28, top: 16, bottom: 32}
+
+                    <HubInputLabel> { text: "PROMPT" }
+                    mus_prompt = <HubPanelInput> {
+                        height: 72
+                        empty_text: "Upbeat lo-fi hip hop with warm piano chords..."
+                    }
+
+                    <View> {
+                        width: Fill, height: Fit
+                        flow: Right
+                        spacing: 12
+                        <View> {
+                            width: Fit, height: Fit
+                            flow: Down
+                            <HubInputLabel> { text: "DURATION IN SECONDS (OPTIONAL)" }
+                            mus_duration = <HubPanelInput> {
+                                width: 200, height: 36
+                                empty_text: "Model default"
+                            }
+                        }
+                        <View> {
+                            width: Fit, height: Fit
+                            flow: Down
+                            <HubInputLabel> { text: "SEED (OPTIONAL)" }
+                            mus_seed = <HubPanelInput> {
+                                width: 200, height: 36
+                                empty_text: "Random"
                             }
-                            text_style: <FONT_SEMIBOLD>{ font_size: 13.0 }
                         }
                     }
-                    voice_new_btn = <HubActionButton> {
-                        text: "+ New"
-                        padding: {left: 8, right: 8}
-                        height: 28
+
+                    <View> {
+                        width: Fill, height: Fit
+                        flow: Right
+                        margin: {top: 10, bottom: 16}
+                        mus_generate_btn = <HubActionButton> { text: "Generate Music" }
+                        mus_export_request_btn = <HubActionButton> { text: "Export Request..." }
+                    }
+
+                    mus_status = <HubPanelStatus> {}
+
+                    // File path + action buttons (hidden until generated)
+                    mus_result_row = <View> {
+                        width: Fill, height: Fit
+                        flow: Right
+                        align: {y: 0.5}
+                        margin: {top: 8, bottom: 8}
+                        visible: false
+                        spacing: 8
+
+                        mus_output_path = <Label> {
+                            width: Fill, height: Fit
+                            draw_text: {
+                                fn get_color(self) -> vec4 { return #374151; }
+                                text_style: { font_size: 11.0 }
+                                wrap: Word
+                            }
+                        }
+
+                        mus_play_btn = <HubActionButton> {
+                            text: "Play"
+                            width: Fit
+                        }
+
+                        mus_open_finder_btn = <HubActionButton> {
+                            text: "Show in Finder"
+                            width: Fit
+                        }
                     }
                 }
+            }
+
+            // ── Reranker Panel ─────────────────────────────────────────────────
+            hub_rerank_panel = <ScrollYView> {
+                width: Fill, height: Fill
+                visible: false
+                flow: Down
+
+                hub_panel_header = <HubPanelHeader> {}
 
-                voice_left_divider = <View> {
+                hub_rerank_divider = <View> {
                     width: Fill, height: 1
                     show_bg: true
                     draw_bg: {
@@ -1374,138 +2140,382 @@ live_design! {
                     }
                 }
 
-                // Voice list
-                voice_list = <PortalList> {
-                    width: Fill, height: Fill
+                <View> {
+                    width: Fill, height: Fit
                     flow: Down
-                    HubVoiceListItem = <HubVoiceListItem> {}
+                    padding: {left: 28, right: 28, top: 16, bottom: 32}
+
+                    <HubInputLabel> { text: "QUERY" }
+                    rerank_query = <HubPanelInput> {
+                        empty_text: "How do I quantize a model to 4 bits?"
+                    }
+
+                    <HubInputLabel> { text: "PASSAGES (ONE PER LINE, SHIFT+ENTER FOR A NEW LINE)" }
+                    rerank_passages = <HubPanelInput> {
+                        height: 160
+                        empty_text: "MLX supports 4-bit and 8-bit quantization..."
+                    }
+
+                    <View> {
+                        width: Fill, height: Fit
+                        flow: Right
+                        margin: {top: 10, bottom: 16}
+                        rerank_btn = <HubActionButton> { text: "Rank Passages" }
+                        rerank_export_request_btn = <HubActionButton> { text: "Export Request..." }
+                    }
+
+                    rerank_status = <HubPanelStatus> {}
+
+                    // Passages with their scores, most relevant first
+                    rerank_results = <Label> {
+                        width: Fill, height: Fit
+                        margin: {top: 8}
+                        draw_text: {
+                            fn get_color(self) -> vec4 { return #374151; }
+                            text_style: { font_size: 11.5 }
+                            wrap: Word
+                        }
+                    }
                 }
             }
 
-            // Vertical divider
-            voice_panel_divider = <View> {
-                width: 1, height: Fill
-                show_bg: true
-                draw_bg: {
-                    fn pixel(self) -> vec4 { return #f1f5f9; }
+            // ── Voice Studio Panel (shared with the Voice app) ──────────────────
+            hub_voice_panel = <VoiceStudio> { visible: false }
+
+            // ── Memory planner: can the checked models be loaded together ──
+            hub_planner_panel = <ScrollYView> {
+                width: Fill, height: Fill
+                visible: false
+                flow: Down
+                spacing: 12
+                padding: {left: 28, right: 28, top: 24, bottom: 24}
+
+                <Label> {
+                    text: "Memory planner"
+                    draw_text: {
+                        fn get_color(self) -> vec4 { return #1f2937; }
+                        text_style: <FONT_SEMIBOLD>{ font_size: 16.0 }
+                    }
+                }
+                <Label> {
+                    width: Fill
+                    text: "Check models in the list to see whether they fit in memory together, before loading any of them. Memory needs are the registry's estimates."
+                    draw_text: {
+                        fn get_color(self) -> vec4 { return #6b7280; }
+                        text_style: <FONT_REGULAR>{ font_size: 11.0 }
+                        wrap: Word
+                    }
+                }
+                planner_summary = <Label> {
+                    width: Fill
+                    text: ""
+                    draw_text: {
+                        fn get_color(self) -> vec4 { return #1f2937; }
+                        text_style: <FONT_SEMIBOLD>{ font_size: 13.0 }
+                        wrap: Word
+                    }
+                }
+                // Planned memory against the installed RAM; the mark is the usable share
+                planner_bar = <View> {
+                    width: Fill, height: 12
+                    show_bg: true
+                    draw_bg: {
+                        instance used: 0.0
+                        instance usable: 0.75
+                        fn pixel(self) -> vec4 {
+                            let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                            sdf.box(0.0, 0.0, self.rect_size.x, self.rect_size.y, 4.0);
+                            sdf.fill(#d1d5db);
+                            sdf.box(0.0, 0.0, self.rect_size.x * min(self.used, 1.0), self.rect_size.y, 4.0);
+                            let color = mix(#10b981, #d97706, step(self.usable, self.used));
+                            sdf.fill(mix(color, #dc2626, step(1.0, self.used)));
+                            sdf.rect(self.rect_size.x * self.usable - 1.0, 0.0, 2.0, self.rect_size.y);
+                            sdf.fill(#374151);
+                            return sdf.result;
+                        }
+                    }
+                }
+                planner_models = <Label> {
+                    width: Fill
+                    text: ""
+                    draw_text: {
+                        fn get_color(self) -> vec4 { return #374151; }
+                        text_style: <FONT_REGULAR>{ font_size: 11.5 }
+                        wrap: Word
+                    }
+                }
+                planner_warnings = <Label> {
+                    width: Fill
+                    text: ""
+                    draw_text: {
+                        fn get_color(self) -> vec4 { return #b91c1c; }
+                        text_style: <FONT_MEDIUM>{ font_size: 11.5 }
+                        wrap: Word
+                    }
                 }
             }
 
-            // Right sub-panel: training form + synthesis
-            <ScrollYView> {
+            // ── Ollama: models installed in the local daemon, and pulls ──
+            hub_ollama_panel = <View> {
                 width: Fill, height: Fill
+                visible: false
                 flow: Down
-                padding: {left: 28, right: 28, top: 20, bottom: 32}
+                spacing: 12
+                padding: {left: 28, right: 28, top: 24, bottom: 24}
 
-                // Training section header
-                voice_training_title = <Label> {
+                <Label> {
+                    text: "Ollama"
+                    draw_text: {
+                        fn get_color(self) -> vec4 { return #1f2937; }
+                        text_style: <FONT_SEMIBOLD>{ font_size: 16.0 }
+                    }
+                }
+                ollama_url = <Label> {
                     width: Fill
-                    margin: {bottom: 12}
-                    text: "VOICE TRAINING"
+                    text: ""
                     draw_text: {
-                        fn get_color(self) -> vec4 {
-                            return #6b7280;
+                        fn get_color(self) -> vec4 { return #6b7280; }
+                        text_style: <FONT_REGULAR>{ font_size: 11.0 }
+                        wrap: Word
+                    }
+                }
+                <View> {
+                    width: Fill, height: Fit
+                    flow: Right
+                    spacing: 12
+                    align: {y: 0.5}
+                    ollama_summary = <Label> {
+                        width: Fill
+                        text: ""
+                        draw_text: {
+                            fn get_color(self) -> vec4 { return #1f2937; }
+                            text_style: <FONT_MEDIUM>{ font_size: 12.0 }
+                            wrap: Word
                         }
-                        text_style: <FONT_SEMIBOLD>{ font_size: 10.5 }
                     }
+                    ollama_refresh_btn = <HubActionButton> { height: 28, padding: {left: 12, right: 12}, text: "Refresh" }
                 }
-
-                <HubInputLabel> { text: "VOICE NAME" }
-                voice_name_input = <HubPanelInput> {
-                    height: 36
-                    empty_text: "My Voice"
+                ollama_model_list = <PortalList> {
+                    width: Fill, height: 320
+                    flow: Down
+                    HubOllamaModelItem = <HubOllamaModelItem> {}
                 }
 
-                <HubInputLabel> { text: "AUDIO FILE (.wav)" }
+                <HubInputLabel> { text: "PULL A MODEL" }
                 <View> {
                     width: Fill, height: Fit
                     flow: Right
                     align: {y: 0.5}
-                    margin: {bottom: 4}
-                    voice_audio_path_input = <HubPanelInput> {
-                        width: Fill, height: 36
+                    ollama_pull_input = <HubPanelInput> {
+                        width: Fill
                         margin: {right: 6, bottom: 0}
+                        empty_text: "Model name, e.g. llama3.2:3b"
                     }
-                    voice_audio_browse_btn = <HubActionButton> { text: "Browse..." margin: {right: 0} }
+                    ollama_pull_btn = <HubActionButton> { text: "Pull" }
+                    ollama_cancel_btn = <HubActionButton> { text: "Cancel" visible: false margin: {right: 0} }
                 }
-
-                <HubInputLabel> { text: "TRANSCRIPT (OPTIONAL)" }
-                voice_transcript_input = <HubPanelInput> {
-                    height: 60
-                    empty_text: "Text spoken in the audio file..."
+                ollama_status = <Label> {
+                    width: Fill
+                    text: ""
+                    draw_text: {
+                        fn get_color(self) -> vec4 { return #6b7280; }
+                        text_style: <FONT_REGULAR>{ font_size: 10.5 }
+                        wrap: Word
+                    }
                 }
+            }
 
-                // Quality selector
-                <HubInputLabel> { text: "QUALITY" }
-                <View> {
+            // ── Diagnostics: what holds the runtime's port, and fixes ──
+            hub_diagnostics_panel = <View> {
+                width: Fill, height: Fill
+                visible: false
+                flow: Down
+                spacing: 12
+                padding: {left: 28, right: 28, top: 24, bottom: 24}
+
+                <Label> {
+                    text: "Diagnostics"
+                    draw_text: {
+                        fn get_color(self) -> vec4 { return #1f2937; }
+                        text_style: <FONT_SEMIBOLD>{ font_size: 16.0 }
+                    }
+                }
+                diagnostics_url = <Label> {
+                    width: Fill
+                    text: ""
+                    draw_text: {
+                        fn get_color(self) -> vec4 { return #6b7280; }
+                        text_style: <FONT_REGULAR>{ font_size: 11.0 }
+                        wrap: Word
+                    }
+                }
+                diagnostics_summary = <Label> {
+                    width: Fill
+                    text: ""
+                    draw_text: {
+                        fn get_color(self) -> vec4 { return #1f2937; }
+                        text_style: <FONT_MEDIUM>{ font_size: 12.0 }
+                        wrap: Word
+                    }
+                }
+                diagnostics_fix_0 = <View> {
                     width: Fill, height: Fit
+                    visible: false
                     flow: Right
-                    margin: {bottom: 12}
-                    voice_quality_fast     = <HubActionButton> { text: "Fast",     margin: {right: 6} }
-                    voice_quality_standard = <HubActionButton> { text: "Standard", margin: {right: 6} }
-                    voice_quality_high     = <HubActionButton> { text: "High" }
+                    spacing: 12
+                    align: {y: 0.5}
+                    fix_btn = <HubActionButton> { height: 28, padding: {left: 12, right: 12}, text: "" }
+                    fix_text = <Label> {
+                        width: Fill
+                        text: ""
+                        draw_text: {
+                            fn get_color(self) -> vec4 { return #374151; }
+                            text_style: <FONT_REGULAR>{ font_size: 11.0 }
+                            wrap: Word
+                        }
+                    }
+                }
+                diagnostics_fix_1 = <View> {
+                    width: Fill, height: Fit
+                    visible: false
+                    flow: Right
+                    spacing: 12
+                    align: {y: 0.5}
+                    fix_btn = <HubActionButton> { height: 28, padding: {left: 12, right: 12}, text: "" }
+                    fix_text = <Label> {
+                        width: Fill
+                        text: ""
+                        draw_text: {
+                            fn get_color(self) -> vec4 { return #374151; }
+                            text_style: <FONT_REGULAR>{ font_size: 11.0 }
+                            wrap: Word
+                        }
+                    }
+                }
+                diagnostics_fix_2 = <View> {
+                    width: Fill, height: Fit
+                    visible: false
+                    flow: Right
+                    spacing: 12
+                    align: {y: 0.5}
+                    fix_btn = <HubActionButton> { height: 28, padding: {left: 12, right: 12}, text: "" }
+                    fix_text = <Label> {
+                        width: Fill
+                        text: ""
+                        draw_text: {
+                            fn get_color(self) -> vec4 { return #374151; }
+                            text_style: <FONT_REGULAR>{ font_size: 11.0 }
+                            wrap: Word
+                        }
+                    }
                 }
-
                 <View> {
                     width: Fill, height: Fit
                     flow: Right
-                    margin: {bottom: 8}
-                    voice_train_btn        = <HubActionButton> { text: "Train Voice", margin: {right: 8} }
-                    voice_cancel_train_btn = <HubActionButton> {
-                        text: "Cancel"
-                        visible: false
-                        draw_bg: { danger: 1.0 }
+                    spacing: 12
+                    align: {y: 0.5}
+                    diagnostics_check_btn = <HubActionButton> { height: 28, padding: {left: 12, right: 12}, text: "Check again" }
+                    diagnostics_status = <Label> {
+                        width: Fill
+                        text: ""
+                        draw_text: {
+                            fn get_color(self) -> vec4 { return #6b7280; }
+                            text_style: <FONT_REGULAR>{ font_size: 10.5 }
+                            wrap: Word
+                        }
                     }
                 }
+            }
 
-                voice_train_status = <HubPanelStatus> {}
+            // ── Runtime log: the ominix-api server's output ──
+            hub_runtime_log_panel = <View> {
+                width: Fill, height: Fill
+                visible: false
+                flow: Down
+                spacing: 10
+                padding: {left: 28, right: 28, top: 24, bottom: 24}
 
-                // Divider
-                voice_synth_divider = <View> {
-                    width: Fill, height: 1
-                    margin: {top: 20, bottom: 20}
-                    show_bg: true
-                    draw_bg: {
-                        fn pixel(self) -> vec4 { return #f1f5f9; }
+                <Label> {
+                    text: "Runtime log"
+                    draw_text: {
+                        fn get_color(self) -> vec4 { return #1f2937; }
+                        text_style: <FONT_SEMIBOLD>{ font_size: 16.0 }
                     }
                 }
-
-                // Synthesis section header
-                voice_synthesis_title = <Label> {
+                runtime_log_path = <Label> {
                     width: Fill
-                    margin: {bottom: 12}
-                    text: "VOICE SYNTHESIS"
+                    text: ""
                     draw_text: {
-                        fn get_color(self) -> vec4 {
-                            return #6b7280;
-                        }
-                        text_style: <FONT_SEMIBOLD>{ font_size: 10.5 }
+                        fn get_color(self) -> vec4 { return #6b7280; }
+                        text_style: <FONT_REGULAR>{ font_size: 11.0 }
+                        wrap: Word
                     }
                 }
-
-                <HubInputLabel> { text: "TEXT TO SYNTHESIZE" }
-                voice_synth_text = <HubPanelInput> {
-                    height: 72
-                    empty_text: "Enter text to synthesize..."
-                }
-
-                <HubInputLabel> { text: "SPEED (0.5 – 2.0)" }
-                voice_speed_input = <HubPanelInput> {
-                    height: 36
-                    empty_text: "1.0"
-                }
-
                 <View> {
                     width: Fill, height: Fit
                     flow: Right
-                    margin: {top: 10, bottom: 8}
-                    voice_generate_btn = <HubActionButton> { text: "Synthesize", margin: {right: 8} }
-                    voice_play_btn     = <HubActionButton> { text: "▶  Play" }
+                    align: {y: 0.5}
+                    runtime_log_trace_btn = <HubModeButton> { height: 26, padding: {left: 10, right: 10}, text: "All" }
+                    runtime_log_info_btn = <HubModeButton> { height: 26, padding: {left: 10, right: 10}, text: "Info" }
+                    runtime_log_warn_btn = <HubModeButton> { height: 26, padding: {left: 10, right: 10}, text: "Warnings" }
+                    runtime_log_error_btn = <HubModeButton> { height: 26, padding: {left: 10, right: 10}, text: "Errors" }
+                    <View> { width: 8, height: 1 }
+                    runtime_log_search = <TextInput> {
+                        width: Fill, height: 28
+                        empty_text: "Search the log, e.g. safetensors"
+                        cursor: Text
+                        draw_bg: {
+                            fn pixel(self) -> vec4 {
+                                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                                sdf.box(0.0, 0.0, self.rect_size.x, self.rect_size.y, 5.0);
+                                sdf.fill(#f1f5f9);
+                                return sdf.result;
+                            }
+                        }
+                        draw_text: {
+                            fn get_color(self) -> vec4 { return #374151; }
+                            color: #374151
+                            color_empty: #9ca3af
+                            text_style: { font_size: 11.0 }
+                        }
+                        draw_cursor: {
+                            uniform border_radius: 0.5
+                            fn pixel(self) -> vec4 {
+                                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                                sdf.box(0.0, 0.0, self.rect_size.x, self.rect_size.y, self.border_radius);
+                                sdf.fill(mix(#00000000, #1f2937, (1.0 - self.blink) * self.focus));
+                                return sdf.result;
+                            }
+                        }
+                    }
+                    <View> { width: 8, height: 1 }
+                    runtime_log_clear_btn = <HubActionButton> { height: 26, padding: {left: 10, right: 10}, text: "Clear" }
+                }
+                runtime_log_scroll = <ScrollYView> {
+                    width: Fill, height: Fill
+                    show_bg: true
+                    draw_bg: { color: #f8fafc }
+                    padding: 10
+                    runtime_log_text = <Label> {
+                        width: Fill
+                        text: ""
+                        draw_text: {
+                            fn get_color(self) -> vec4 { return #1f2937; }
+                            text_style: <FONT_REGULAR>{ font_size: 10.5 }
+                            wrap: Word
+                        }
+                    }
+                }
+                runtime_log_status = <Label> {
+                    width: Fill
+                    text: ""
+                    draw_text: {
+                        fn get_color(self) -> vec4 { return #6b7280; }
+                        text_style: <FONT_REGULAR>{ font_size: 10.5 }
+                        wrap: Word
+                    }
                 }
-
-                voice_synth_status = <HubPanelStatus> {}
             }
-        }
     }
 }
 }
@@ -1,21 +1,50 @@
 pub mod design;
+pub mod panel_header;
 
 use makepad_widgets::*;
 use moly_data::{
-    ModelRegistry, RegistryModel, RegistryCategory, SourceKind, PanelType,
+    ModelRegistry, RegistryCategory, RegistryModel, SourceKind, PanelType,
     ModelRuntimeClient, ServerModelInfo, ServerModelStatus,
-    StoreAction,
-    ensure_server_running,
+    ModelLoadPhase, Store, StoreAction, StoreEvent, Transcript, TranscriptSegment,
+    DownloadStatus, DownloadProgress, MolyError, RegistryFile, TransferRate, ensure_server_running,
+    RuntimeVersion, installed_runtime_version,
 };
+use moly_data::{artifacts, asr_cleanup, download_settings, lan_share, memory_planner, model_integrity, model_state};
+use moly_data::runtime_diagnostics::{self, Diagnosis, Fix};
+use moly_data::ollama_client::{self, OllamaPullProgress};
+use moly_data::CatalogModel;
+use moly_data::runtime_log::{self, LogLevel, LogTail};
+use moly_data::model_integrity::Integrity;
+use moly_data::disk_space::{self, SpaceCheck};
+use moly_data::model_trash::{self, PendingRemoval};
+use moly_data::audio::{concat_wav_files, Wav};
+use moly_data::ominix_api_client::{
+    parse_duration, parse_strength, ChatCompletionRequest, ChatMessage, ImageGenerationRequest,
+    MusicGenerationRequest, OminiXApiClient, RerankRequest, TranscriptionRequest, VideoGenerationRequest,
+};
+use moly_data::audiobook::{chapter_file_name, load_book, BookChapter};
+use moly_data::image_history::{self, ImageHistory};
+use moly_data::{answer_versions, meeting_notes, ocr, power, prompt_enhance, request_export, shutdown, smoke_test, translate};
+use moly_data::meeting_notes::MeetingNotes;
+use moly_data::model_updates::{self, ModelUpdate, RepoFile, RepoRevision};
+use moly_data::transcribe_watch::{self, WatchFolderSettings, WatchItem};
+use moly_data::transcript_editor::{self, TranscriptEditor};
+use moly_data::eval_set::{self, EvalCase, EvalResult};
+use moly_data::chat_template::{self, ChatTemplate, ChatTemplateOverrides};
+use moly_data::runtime_options::{ModelRuntimeOptions, RuntimeOptions};
+use moly_data::tts_longform::{split_into_chunks, synthesize_chunks, DEFAULT_TTS_CHUNK_CHARS};
 use serde::Deserialize;
 use std::sync::{Arc, atomic::{AtomicBool, AtomicU64, Ordering}};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::io::{Read, Write};
 use std::sync::mpsc;
 
 use base64::Engine as _;
-use rfd::FileDialog;
+use moly_data::model_search::{ModelSearch, ModelSearchIndex, HUB_CATEGORIES};
+use moly_data::platform::{self, AudioFormat, FileDialog};
+use moly_widgets::a11y::{FocusChain, FocusTarget, Role};
+use panel_header::{HubPanelHeaderRef, HubPanelHeaderWidgetExt, PanelHeaderState};
 
 // ─── Helpers ─────────────────────────────────────────────────────────────────
 
@@ -35,6 +64,20 @@ fn strip_quant_suffix(name: &str) -> String {
     name.to_string()
 }
 
+/// Seconds of typing pause before the model list is filtered
+const SEARCH_DEBOUNCE: f64 = 0.15;
+
+/// Names each panel's last request is kept under for Export Request
+const EXPORT_LLM: &str = "hub-llm";
+const EXPORT_VLM: &str = "hub-vlm";
+const EXPORT_ASR: &str = "hub-asr";
+const EXPORT_TTS: &str = "hub-tts";
+const EXPORT_IMAGE: &str = "hub-image";
+const EXPORT_IMAGE_EDIT: &str = "hub-image-edit";
+const EXPORT_VIDEO: &str = "hub-video";
+const EXPORT_MUSIC: &str = "hub-music";
+const EXPORT_RERANK: &str = "hub-rerank";
+
 // ─── List row ────────────────────────────────────────────────────────────────
 
 #[derive(Clone, Copy)]
@@ -61,6 +104,8 @@ enum ModelUiState {
     NotDownloaded,
     Downloading,
     Downloaded,
+    /// Files left by an interrupted download
+    Incomplete,
     Error,
 }
 
@@ -70,14 +115,25 @@ impl ModelUiState {
             Self::NotDownloaded => 0.0,
             Self::Downloading   => 1.0,
             Self::Downloaded    => 2.0,
+            Self::Incomplete    => 1.0, // yellow, like a download that stopped
             Self::Error         => 5.0, // red (above blue at 3.0)
         }
     }
+    fn from_status(status: DownloadStatus) -> Self {
+        match status {
+            DownloadStatus::NotDownloaded => Self::NotDownloaded,
+            DownloadStatus::Partial     => Self::Incomplete,
+            DownloadStatus::Downloading => Self::Downloading,
+            DownloadStatus::Downloaded  => Self::Downloaded,
+            DownloadStatus::Error       => Self::Error,
+        }
+    }
     fn label(self) -> &'static str {
         match self {
             Self::NotDownloaded => "Not Downloaded",
             Self::Downloading   => "Downloading...",
             Self::Downloaded    => "Downloaded",
+            Self::Incomplete    => "Incomplete",
             Self::Error         => "Error",
         }
     }
@@ -94,6 +150,14 @@ enum ModelLoadState {
     LoadError,
 }
 
+/// Outcome of the test request sent to a model after it loaded
+#[derive(Clone, PartialEq, Debug)]
+enum Verification {
+    Checking,
+    Verified,
+    Failed(String),
+}
+
 // ─── Combined dot / status helpers ────────────────────────────────────────────
 
 fn combined_dot_value(dl: ModelUiState, load: ModelLoadState) -> f64 {
@@ -120,7 +184,184 @@ fn combined_status_label(dl: ModelUiState, load: ModelLoadState) -> &'static str
 enum ActivePanel {
     #[default]
     None,
-    Llm, Vlm, Asr, Tts, Image, ImageEdit, Video, Voice, Info,
+    Llm, Vlm, Asr, Tts, Image, ImageEdit, Video, Music, Rerank, Voice, Info,
+    /// The memory planner for the checked models
+    Planner,
+    /// The ominix-api server's log
+    RuntimeLog,
+    /// What holds the runtime's port
+    Diagnostics,
+    /// Models installed in the local Ollama daemon
+    Ollama,
+}
+
+/// The category panels and their view ids; each starts with a HubPanelHeader.
+/// A new category adds its panel here.
+fn header_panels() -> [(ActivePanel, LiveId); 9] {
+    [
+        (ActivePanel::Llm, live_id!(hub_llm_panel)),
+        (ActivePanel::Vlm, live_id!(hub_vlm_panel)),
+        (ActivePanel::Asr, live_id!(hub_asr_panel)),
+        (ActivePanel::Tts, live_id!(hub_tts_panel)),
+        (ActivePanel::Image, live_id!(hub_image_panel)),
+        (ActivePanel::ImageEdit, live_id!(hub_image_edit_panel)),
+        (ActivePanel::Video, live_id!(hub_video_panel)),
+        (ActivePanel::Music, live_id!(hub_music_panel)),
+        (ActivePanel::Rerank, live_id!(hub_rerank_panel)),
+    ]
+}
+
+/// Keyboard focus order: search, then for each panel its header actions and
+/// its own inputs. Only the panel on screen is reachable.
+fn hub_focus_targets() -> Vec<FocusTarget> {
+    let mut targets = vec![
+        FocusTarget::new(ids!(search_input), Role::TextInput, "Search models"),
+        FocusTarget::new(ids!(hub_select_btn), Role::Button, "Select models"),
+        FocusTarget::new(ids!(hub_bulk_download_btn), Role::Button, "Download selected models"),
+        FocusTarget::new(ids!(hub_bulk_verify_btn), Role::Button, "Verify selected models"),
+        FocusTarget::new(ids!(hub_bulk_remove_btn), Role::Button, "Remove selected models"),
+        FocusTarget::new(ids!(hub_bulk_plan_btn), Role::Button, "Plan memory for selected models"),
+        FocusTarget::new(ids!(hub_runtime_log_btn), Role::Button, "Runtime log"),
+        FocusTarget::new(ids!(hub_runtime_log_panel.runtime_log_trace_btn), Role::Button, "Show all log lines"),
+        FocusTarget::new(ids!(hub_runtime_log_panel.runtime_log_info_btn), Role::Button, "Show info and above"),
+        FocusTarget::new(ids!(hub_runtime_log_panel.runtime_log_warn_btn), Role::Button, "Show warnings and errors"),
+        FocusTarget::new(ids!(hub_runtime_log_panel.runtime_log_error_btn), Role::Button, "Show errors only"),
+        FocusTarget::new(ids!(hub_runtime_log_panel.runtime_log_search), Role::TextInput, "Search the runtime log"),
+        FocusTarget::new(ids!(hub_runtime_log_panel.runtime_log_clear_btn), Role::Button, "Clear the runtime log view"),
+        FocusTarget::new(ids!(hub_diagnostics_btn), Role::Button, "Diagnostics"),
+        FocusTarget::new(ids!(hub_diagnostics_panel.diagnostics_fix_0.fix_btn), Role::Button, "First suggested fix"),
+        FocusTarget::new(ids!(hub_diagnostics_panel.diagnostics_fix_1.fix_btn), Role::Button, "Second suggested fix"),
+        FocusTarget::new(ids!(hub_diagnostics_panel.diagnostics_fix_2.fix_btn), Role::Button, "Third suggested fix"),
+        FocusTarget::new(ids!(hub_diagnostics_panel.diagnostics_check_btn), Role::Button, "Check the runtime's port again"),
+        FocusTarget::new(ids!(hub_ollama_btn), Role::Button, "Ollama models"),
+        FocusTarget::new(ids!(hub_ollama_panel.ollama_refresh_btn), Role::Button, "List Ollama's models again"),
+        FocusTarget::new(ids!(hub_ollama_panel.ollama_pull_input), Role::TextInput, "Ollama model to pull"),
+        FocusTarget::new(ids!(hub_ollama_panel.ollama_pull_btn), Role::Button, "Pull the model"),
+        FocusTarget::new(ids!(hub_ollama_panel.ollama_cancel_btn), Role::Button, "Cancel the pull"),
+    ];
+    let panels: [(LiveId, &[(LiveId, Role, &str)]); 7] = [
+        (live_id!(hub_llm_panel), &[
+            (live_id!(llm_system), Role::TextInput, "System prompt"),
+            (live_id!(llm_user), Role::TextInput, "Message"),
+            (live_id!(llm_generate_btn), Role::Button, "Generate"),
+            (live_id!(llm_template_default_btn), Role::Button, "Server default chat template"),
+            (live_id!(llm_template_chatml_btn), Role::Button, "ChatML template"),
+            (live_id!(llm_template_llama3_btn), Role::Button, "Llama 3 template"),
+            (live_id!(llm_template_qwen_btn), Role::Button, "Qwen template"),
+            (live_id!(llm_template_mistral_btn), Role::Button, "Mistral template"),
+            (live_id!(llm_template_gemma_btn), Role::Button, "Gemma template"),
+            (live_id!(llm_export_request_btn), Role::Button, "Export the last request"),
+            (live_id!(llm_eval_file_btn), Role::Button, "Choose an eval set"),
+            (live_id!(llm_eval_start_btn), Role::Button, "Run the eval set"),
+            (live_id!(llm_eval_cancel_btn), Role::Button, "Cancel the eval run"),
+            (live_id!(llm_eval_export_btn), Role::Button, "Export eval results"),
+        ]),
+        (live_id!(hub_vlm_panel), &[
+            (live_id!(vlm_browse_btn), Role::Button, "Browse for image"),
+            (live_id!(vlm_user), Role::TextInput, "Question about the image"),
+            (live_id!(vlm_generate_btn), Role::Button, "Generate"),
+            (live_id!(vlm_capture_region_btn), Role::Button, "Capture a region of the screen"),
+            (live_id!(vlm_capture_screen_btn), Role::Button, "Capture the whole screen"),
+            (live_id!(vlm_ocr_btn), Role::Button, "Extract text from the image"),
+            (live_id!(vlm_copy_btn), Role::Button, "Copy the response"),
+            (live_id!(vlm_export_request_btn), Role::Button, "Export the last request"),
+        ]),
+        (live_id!(hub_asr_panel), &[
+            (live_id!(asr_browse_btn), Role::Button, "Browse for audio file"),
+            (live_id!(asr_transcribe_btn), Role::Button, "Transcribe"),
+            (live_id!(asr_notes_btn), Role::Button, "Write meeting notes from the recording"),
+            (live_id!(asr_export_request_btn), Role::Button, "Export the last request"),
+            (live_id!(asr_edit_btn), Role::Button, "Edit the transcript"),
+            (live_id!(asr_edit_input), Role::TextInput, "Text of the selected segment"),
+            (live_id!(asr_edit_prev_btn), Role::Button, "Previous segment"),
+            (live_id!(asr_edit_next_btn), Role::Button, "Next segment"),
+            (live_id!(asr_edit_play_btn), Role::Button, "Play from the selected segment"),
+            (live_id!(asr_edit_stop_btn), Role::Button, "Stop playback"),
+            (live_id!(asr_edit_revert_btn), Role::Button, "Revert the selected segment"),
+            (live_id!(asr_edit_save_btn), Role::Button, "Save the corrections next to the audio file"),
+            (live_id!(asr_edit_export_txt_btn), Role::Button, "Export the corrected transcript as text"),
+            (live_id!(asr_dataset_name), Role::TextInput, "Voice dataset name"),
+            (live_id!(asr_dataset_btn), Role::Button, "Create a voice training dataset from the segments"),
+            (live_id!(asr_watch_folder_btn), Role::Button, "Choose a folder to transcribe recordings from as they arrive"),
+            (live_id!(asr_watch_stop_btn), Role::Button, "Stop watching the folder"),
+        ]),
+        (live_id!(hub_tts_panel), &[
+            (live_id!(tts_text_input), Role::TextInput, "Text to speak"),
+            (live_id!(tts_generate_btn), Role::Button, "Generate and play"),
+            (live_id!(tts_save_btn), Role::Button, "Save to Downloads"),
+            (live_id!(tts_export_request_btn), Role::Button, "Export the last request"),
+        ]),
+        (live_id!(hub_image_panel), &[
+            (live_id!(img_prompt), Role::TextInput, "Image prompt"),
+            (live_id!(img_enhance_btn), Role::Button, "Enhance prompt"),
+            (live_id!(img_revert_btn), Role::Button, "Revert to original prompt"),
+            (live_id!(img_neg_prompt), Role::TextInput, "Negative prompt"),
+            (live_id!(img_seed), Role::TextInput, "Seed"),
+            (live_id!(img_mode_text_btn), Role::Button, "Text to image"),
+            (live_id!(img_mode_img2img_btn), Role::Button, "Image to image"),
+            (live_id!(img_mode_inpaint_btn), Role::Button, "Inpaint"),
+            (live_id!(img_init_browse_btn), Role::Button, "Browse for init image"),
+            (live_id!(img_strength), Role::TextInput, "Strength"),
+            (live_id!(img_mask_browse_btn), Role::Button, "Browse for mask"),
+            (live_id!(img_generate_btn), Role::Button, "Generate image"),
+            (live_id!(img_export_request_btn), Role::Button, "Export the last request"),
+        ]),
+        (live_id!(hub_image_edit_panel), &[
+            (live_id!(img_edit_browse_btn), Role::Button, "Browse for reference image"),
+            (live_id!(img_edit_prompt), Role::TextInput, "Edit instructions"),
+            (live_id!(img_edit_btn), Role::Button, "Edit image"),
+            (live_id!(img_edit_export_request_btn), Role::Button, "Export the last request"),
+        ]),
+        (live_id!(hub_video_panel), &[
+            (live_id!(vid_prompt), Role::TextInput, "Video prompt"),
+            (live_id!(vid_generate_btn), Role::Button, "Generate video"),
+            (live_id!(vid_export_request_btn), Role::Button, "Export the last request"),
+        ]),
+        (live_id!(hub_music_panel), &[
+            (live_id!(mus_prompt), Role::TextInput, "Music prompt"),
+            (live_id!(mus_duration), Role::TextInput, "Duration in seconds"),
+            (live_id!(mus_seed), Role::TextInput, "Seed"),
+            (live_id!(mus_generate_btn), Role::Button, "Generate music"),
+            (live_id!(mus_export_request_btn), Role::Button, "Export the last request"),
+        ]),
+        (live_id!(hub_rerank_panel), &[
+            (live_id!(rerank_query), Role::TextInput, "Query"),
+            (live_id!(rerank_passages), Role::TextInput, "Passages, one per line"),
+            (live_id!(rerank_btn), Role::Button, "Rank passages"),
+            (live_id!(rerank_export_request_btn), Role::Button, "Export the last request"),
+        ]),
+    ];
+    let header: [(LiveId, &str); 10] = [
+        (live_id!(panel_download_btn), "Download model"),
+        (live_id!(panel_lan_btn), "Import from local network"),
+        (live_id!(panel_update_btn), "Download the model's update"),
+        (live_id!(panel_cancel_btn), "Cancel download"),
+        (live_id!(panel_remove_btn), "Remove model"),
+        (live_id!(panel_load_btn), "Load model"),
+        (live_id!(panel_unload_btn), "Unload model"),
+        (live_id!(panel_chat_btn), "Open in chat, transcriber or speaker"),
+        (live_id!(panel_preload_btn), "Preload on startup"),
+        (live_id!(panel_options_btn), "Advanced runtime options"),
+    ];
+    let options: [(LiveId, Role, &str); 5] = [
+        (live_id!(panel_ctx_input), Role::TextInput, "Context length"),
+        (live_id!(panel_gpu_layers_input), Role::TextInput, "GPU layers"),
+        (live_id!(panel_threads_input), Role::TextInput, "Threads"),
+        (live_id!(panel_options_save_btn), Role::Button, "Save runtime options"),
+        (live_id!(panel_options_reset_btn), Role::Button, "Reset runtime options"),
+    ];
+    for (panel, controls) in panels {
+        for (button, label) in header {
+            targets.push(FocusTarget::new(&[panel, live_id!(hub_panel_header), button], Role::Button, label));
+        }
+        for (control, role, label) in options {
+            targets.push(FocusTarget::new(&[panel, live_id!(hub_panel_header), control], role, label));
+        }
+        for &(control, role, label) in controls {
+            targets.push(FocusTarget::new(&[panel, control], role, label));
+        }
+    }
+    targets
 }
 
 // ─── Per-panel interaction state ─────────────────────────────────────────────
@@ -137,13 +378,111 @@ struct VlmState {
     image_path: String, user: String, response: String,
     is_running: bool,
     rx: Option<mpsc::Receiver<Result<String, String>>>,
+    /// Screenshot being taken: its path, or `None` when cancelled
+    capture_rx: Option<mpsc::Receiver<Result<Option<String>, String>>>,
+}
+
+/// What a background `ollama pull` reports
+enum OllamaPullUpdate {
+    Progress(OllamaPullProgress),
+    Finished(Result<(), String>),
 }
 
 #[derive(Default)]
 struct AsrState {
-    audio_path: String, transcript: String,
+    audio_path: String, transcript: Transcript,
+    /// WAV the segment timestamps refer to (source file or converted temp copy)
+    playback_wav: String,
+    playback_is_temp: bool,
     is_running: bool,
-    rx: Option<mpsc::Receiver<Result<String, String>>>,
+    /// (transcript, wav path used for the request, wav is a temp conversion)
+    rx: Option<mpsc::Receiver<Result<(Transcript, String, bool), String>>>,
+    /// Open while correcting the transcript beside the segment list
+    editor: Option<TranscriptEditor>,
+    /// Player started from the editor, so Stop and the next play can end it
+    player: Arc<std::sync::Mutex<Option<std::process::Child>>>,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum BatchFileStatus { Queued, Running, Done, Skipped, Failed }
+
+impl BatchFileStatus {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Queued  => "Queued",
+            Self::Running => "Transcribing…",
+            Self::Done    => "Done",
+            Self::Skipped => "Already done",
+            Self::Failed  => "Failed",
+        }
+    }
+}
+
+struct AsrBatchFile {
+    path: PathBuf,
+    status: BatchFileStatus,
+    error: Option<String>,
+}
+
+enum AsrBatchUpdate {
+    Status(usize, BatchFileStatus, Option<String>),
+    Finished { cancelled: bool },
+}
+
+/// Folder batch transcription. Files whose transcript already exists are
+/// skipped, so starting again after a cancel resumes where it stopped.
+#[derive(Default)]
+struct AsrBatchState {
+    files: Vec<AsrBatchFile>,
+    source_dir: Option<PathBuf>,
+    output_dir: Option<PathBuf>,
+    is_running: bool,
+    cancel: Option<Arc<AtomicBool>>,
+    rx: Option<mpsc::Receiver<AsrBatchUpdate>>,
+}
+
+/// The watched folder's status, as last read from `transcribe_watch`
+#[derive(Default)]
+struct WatchFolderView {
+    items: Vec<WatchItem>,
+    /// `transcribe_watch::generation()` at the last refresh
+    seen: Option<u64>,
+}
+
+enum NotesUpdate {
+    Status(String),
+    Finished(Result<MeetingNotes, String>),
+}
+
+/// Recording → transcript → notes by the loaded LLM, saved as a new chat
+#[derive(Default)]
+struct NotesState {
+    is_running: bool,
+    rx: Option<mpsc::Receiver<NotesUpdate>>,
+}
+
+enum EvalUpdate {
+    Running(usize),
+    Result(usize, EvalResult),
+    Finished { cancelled: bool },
+}
+
+/// Batch evaluation of the selected LLM over a JSONL file of prompts
+#[derive(Default)]
+struct EvalState {
+    source: Option<PathBuf>,
+    cases: Vec<EvalCase>,
+    /// One per case, filled in as the run goes
+    results: Vec<Option<EvalResult>>,
+    running: Option<usize>,
+    is_running: bool,
+    cancel: Option<Arc<AtomicBool>>,
+    rx: Option<mpsc::Receiver<EvalUpdate>>,
+}
+
+enum TtsUpdate {
+    Progress { done: usize, total: usize },
+    Finished(Result<bool, String>),
 }
 
 #[derive(Default)]
@@ -151,10 +490,39 @@ struct TtsState {
     voice_id: String, text: String, voices: Vec<String>,
     output_path: String,
     is_running: bool,
-    rx:        Option<mpsc::Receiver<Result<(), String>>>,
+    cancel:    Option<Arc<AtomicBool>>,
+    rx:        Option<mpsc::Receiver<TtsUpdate>>,
     voices_rx: Option<mpsc::Receiver<Result<Vec<String>, String>>>,
 }
 
+struct AudiobookChapterEntry {
+    chapter: BookChapter,
+    status: BatchFileStatus,
+    /// (chunks done, total chunks) while synthesizing
+    progress: (usize, usize),
+    error: Option<String>,
+}
+
+enum AudiobookUpdate {
+    Status(usize, BatchFileStatus, Option<String>),
+    Progress(usize, usize, usize),
+    /// Ok(Some(path)) is the joined book; Ok(None) means cancelled
+    Finished(Result<Option<PathBuf>, String>),
+}
+
+/// Document → chaptered audio. Each chapter is written to its own WAV in the
+/// output folder; chapters whose file exists are skipped, so a cancelled or
+/// failed build resumes where it stopped.
+#[derive(Default)]
+struct AudiobookState {
+    source: Option<PathBuf>,
+    output_dir: Option<PathBuf>,
+    chapters: Vec<AudiobookChapterEntry>,
+    is_running: bool,
+    cancel: Option<Arc<AtomicBool>>,
+    rx: Option<mpsc::Receiver<AudiobookUpdate>>,
+}
+
 struct TtsVoiceEntry { id: &'static str, label: &'static str, is_chinese: bool }
 
 static TTS_PRESET_VOICES: &[TtsVoiceEntry] = &[
@@ -171,11 +539,26 @@ static TTS_PRESET_VOICES: &[TtsVoiceEntry] = &[
     TtsVoiceEntry { id: "dialect",       label: "Dialect",        is_chinese: true  },
 ];
 
+/// What the image panel generates from
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+enum ImageMode {
+    #[default]
+    Text,
+    ImageToImage,
+    Inpaint,
+}
+
 #[derive(Default)]
 struct ImageState {
-    prompt: String, neg_prompt: String, output_path: String,
+    prompt: String, neg_prompt: String, seed: String, output_path: String,
+    mode: ImageMode, init_path: String, mask_path: String, strength: String,
+    /// The user's prompt before it was enhanced, for reverting
+    original_prompt: Option<String>,
+    enhance_rx: Option<mpsc::Receiver<Result<String, String>>>,
     is_running: bool,
-    rx: Option<mpsc::Receiver<Result<String, String>>>,
+    /// The running request, recorded in the history when it succeeds
+    request: Option<ImageGenerationRequest>,
+    rx: Option<mpsc::Receiver<Result<Vec<u8>, String>>>,
 }
 
 #[derive(Default)]
@@ -192,6 +575,29 @@ struct VideoState {
     rx: Option<mpsc::Receiver<Result<String, String>>>,
 }
 
+/// Sent by the music generation thread
+enum MusicUpdate {
+    /// Fraction done, as the server streams it
+    Progress(f32),
+    /// Path of the saved clip
+    Finished(Result<String, String>),
+}
+
+#[derive(Default)]
+struct RerankState {
+    query: String, passages: String,
+    is_running: bool,
+    /// Passages with their scores, most relevant first
+    rx: Option<mpsc::Receiver<Result<Vec<(String, f32)>, String>>>,
+}
+
+#[derive(Default)]
+struct MusicState {
+    prompt: String, duration: String, seed: String, output_path: String,
+    is_running: bool,
+    rx: Option<mpsc::Receiver<MusicUpdate>>,
+}
+
 // ─── Model download state ─────────────────────────────────────────────────────
 
 #[derive(Clone)]
@@ -201,6 +607,17 @@ struct ModelDownloadState {
     progress_bytes:   Arc<AtomicU64>,
     total_bytes:      Arc<AtomicU64>,
     current_file:     Arc<std::sync::Mutex<String>>,
+    file_index:       Arc<AtomicU64>,
+    file_count:       Arc<AtomicU64>,
+    file_bytes:       Arc<AtomicU64>,
+    file_total:       Arc<AtomicU64>,
+    /// Rolling speed, updated by the download thread as data arrives
+    rate:             Arc<std::sync::Mutex<TransferRate>>,
+    speed:            Arc<AtomicU64>,
+    /// Waiting for a slot under the simultaneous download limit
+    queued:           Arc<AtomicBool>,
+    /// Set while waiting to retry a stalled download
+    stall_note:       Arc<std::sync::Mutex<String>>,
     completed:        Arc<AtomicBool>,
     failed:           Arc<AtomicBool>,
     error_msg:        Arc<std::sync::Mutex<String>>,
@@ -214,6 +631,14 @@ impl ModelDownloadState {
             progress_bytes:   Arc::new(AtomicU64::new(0)),
             total_bytes:      Arc::new(AtomicU64::new(0)),
             current_file:     Arc::new(std::sync::Mutex::new(String::new())),
+            file_index:       Arc::new(AtomicU64::new(0)),
+            file_count:       Arc::new(AtomicU64::new(0)),
+            file_bytes:       Arc::new(AtomicU64::new(0)),
+            file_total:       Arc::new(AtomicU64::new(0)),
+            rate:             Arc::new(std::sync::Mutex::new(TransferRate::new())),
+            speed:            Arc::new(AtomicU64::new(0)),
+            queued:           Arc::new(AtomicBool::new(false)),
+            stall_note:       Arc::new(std::sync::Mutex::new(String::new())),
             completed:        Arc::new(AtomicBool::new(false)),
             failed:           Arc::new(AtomicBool::new(false)),
             error_msg:        Arc::new(std::sync::Mutex::new(String::new())),
@@ -224,9 +649,17 @@ impl ModelDownloadState {
         self.cancel_requested.store(false, Ordering::SeqCst);
         self.progress_bytes.store(0, Ordering::SeqCst);
         self.total_bytes.store(0, Ordering::SeqCst);
+        self.file_index.store(0, Ordering::SeqCst);
+        self.file_count.store(0, Ordering::SeqCst);
+        self.file_bytes.store(0, Ordering::SeqCst);
+        self.file_total.store(0, Ordering::SeqCst);
+        self.speed.store(0, Ordering::SeqCst);
+        self.queued.store(false, Ordering::SeqCst);
+        self.rate.lock().unwrap().reset();
         self.completed.store(false, Ordering::SeqCst);
         self.failed.store(false, Ordering::SeqCst);
         *self.current_file.lock().unwrap() = String::new();
+        *self.stall_note.lock().unwrap() = String::new();
         *self.error_msg.lock().unwrap() = String::new();
     }
     fn fraction(&self) -> f64 {
@@ -234,73 +667,91 @@ impl ModelDownloadState {
         if total == 0 { return 0.0; }
         (self.progress_bytes.load(Ordering::SeqCst) as f64 / total as f64).min(1.0)
     }
+    /// Move on to file `index` of `count`, expected to be `size` bytes
+    fn begin_file(&self, name: &str, index: usize, count: usize, size: u64) {
+        *self.current_file.lock().unwrap() = name.to_string();
+        self.file_index.store(index as u64, Ordering::SeqCst);
+        self.file_count.store(count as u64, Ordering::SeqCst);
+        self.file_bytes.store(0, Ordering::SeqCst);
+        self.file_total.store(size, Ordering::SeqCst);
+    }
+    /// Record bytes received so far, overall and in the current file
+    fn record_bytes(&self, overall_bytes: u64, file_bytes: u64) {
+        self.progress_bytes.store(overall_bytes, Ordering::SeqCst);
+        self.file_bytes.store(file_bytes, Ordering::SeqCst);
+        let mut rate = self.rate.lock().unwrap();
+        rate.record(std::time::Instant::now(), overall_bytes);
+        self.speed.store(rate.bytes_per_sec(), Ordering::SeqCst);
+    }
+    fn to_download_progress(&self) -> DownloadProgress {
+        let overall_bytes = self.progress_bytes.load(Ordering::SeqCst);
+        let overall_total = self.total_bytes.load(Ordering::SeqCst);
+        let speed = self.speed.load(Ordering::SeqCst);
+        let file = self.current_file.lock().unwrap().clone();
+        DownloadProgress {
+            is_active: self.is_downloading.load(Ordering::SeqCst),
+            current_file: (!file.is_empty()).then_some(file),
+            current_file_index: self.file_index.load(Ordering::SeqCst) as usize,
+            total_files: self.file_count.load(Ordering::SeqCst) as usize,
+            current_file_bytes: self.file_bytes.load(Ordering::SeqCst),
+            current_file_total: self.file_total.load(Ordering::SeqCst),
+            overall_bytes,
+            overall_total,
+            speed_bytes_per_sec: speed,
+            eta_seconds: (speed > 0).then(|| overall_total.saturating_sub(overall_bytes).div_ceil(speed)),
+            started_at: None,
+        }
+    }
+    /// "12.5%  (1200/9600 MB)  45.2 MB/s, 3m 12s remaining, file 7/23  model.safetensors (40%)"
     fn progress_text(&self) -> String {
-        let done  = self.progress_bytes.load(Ordering::SeqCst);
-        let total = self.total_bytes.load(Ordering::SeqCst);
-        let file  = self.current_file.lock().unwrap().clone();
-        let pct   = self.fraction() * 100.0;
-        let done_mb  = done  / 1_048_576;
-        let total_mb = total / 1_048_576;
-        if file.is_empty() {
-            format!("{:.1}%  ({}/{} MB)", pct, done_mb, total_mb)
-        } else {
-            format!("{:.1}%  ({}/{} MB)  {}", pct, done_mb, total_mb, file)
+        if self.queued.load(Ordering::SeqCst) {
+            if power::is_active() {
+                return "Paused — low power mode downloads one model at a time…".to_string();
+            }
+            return "Waiting for another download to finish…".to_string();
+        }
+        let note = self.stall_note.lock().unwrap().clone();
+        if !note.is_empty() {
+            return note;
+        }
+        let p = self.to_download_progress();
+        let mut text = format!("{:.1}%  ({}/{} MB)",
+            p.progress_percent() * 100.0, p.overall_bytes / 1_048_576, p.overall_total / 1_048_576);
+        let rate = p.rate_text();
+        if !rate.is_empty() {
+            text.push_str("  ");
+            text.push_str(&rate);
+        }
+        if let Some(file) = &p.current_file {
+            text.push_str("  ");
+            text.push_str(file);
+            if p.current_file_total > 0 && p.total_files > 1 {
+                text.push_str(&format!(" ({:.0}%)", p.file_progress_percent() * 100.0));
+            }
         }
+        text
     }
 }
 
-// ─── Voice Studio types ────────────────────────────────────────────────────────
-
-#[derive(Clone, Debug)]
-struct VoiceEntry {
-    name:     String,
-    is_ready: bool,
-}
-
-#[derive(Default)]
-enum VoiceTrainingState {
-    #[default]
-    Idle,
-    Training { task_id: String, stage: String, progress: f32 },
-    Done,
-    Error(String),
-}
-
-#[derive(Default)]
-enum VoiceSynthesisState {
-    #[default]
-    Idle,
-    Generating,
-    Done { duration_secs: f32 },
-    Error(String),
-}
-
-enum VoicesUpdate {
-    Loaded(Vec<VoiceEntry>),
-    Error(String),
-}
-
-enum VoiceTrainingUpdate {
-    Progress { stage: String, progress: f32 },
-    Done,
-    Error(String),
-}
-
-enum VoiceSynthesisUpdate {
-    Done { duration_secs: f32 },
-    Error(String),
-}
-
 // ─── HF / MS API response types ───────────────────────────────────────────────
 
 #[derive(Deserialize)]
 struct HfBlobsResponse {
+    #[serde(default)]
+    sha: String,
     siblings: Vec<HfSibling>,
 }
 #[derive(Deserialize)]
 struct HfSibling {
     rfilename: String,
     size: Option<u64>,
+    #[serde(rename = "blobId", default)]
+    blob_id: String,
+    lfs: Option<HfLfs>,
+}
+#[derive(Deserialize)]
+struct HfLfs {
+    sha256: String,
 }
 #[derive(Deserialize)]
 struct MsResponse {
@@ -316,6 +767,7 @@ struct MsFile {
     #[serde(rename = "Path")] path: String,
     #[serde(rename = "Size")] size: u64,
     #[serde(rename = "Type")] file_type: String,
+    #[serde(rename = "Sha256", default)] sha256: String,
 }
 
 // ─── Widget ───────────────────────────────────────────────────────────────────
@@ -336,40 +788,134 @@ pub struct ModelHubApp {
 
     // ── Core data ───────────────────────────────────────────────────────────
     #[rust] registry:        Option<ModelRegistry>,
+    /// The registry fetched from the server, merged when it arrives
+    #[rust] registry_update_rx: Option<mpsc::Receiver<ModelRegistry>>,
+    /// Models added by that update, badged "New" until opened
+    #[rust] new_models:      HashSet<String>,
     #[rust] initialized:     bool,
     #[rust] filter:          Filter,
     #[rust] search_query:    String,
+    /// Per-category index of the registry and the current search results
+    #[rust] search:          ModelSearch,
+    /// Fires once typing in the search box pauses
+    #[rust] search_timer:    Timer,
     #[rust] selected_id:     Option<String>,
     #[rust] flat_list:       Vec<ListRow>,
     #[rust] subfolder_names: Vec<String>,
 
     // ── Download tracking ───────────────────────────────────────────────────
-    #[rust] model_states:    HashMap<String, ModelUiState>,
+    // Presence on disk is shared with other screens through model_state
     #[rust] download_states: HashMap<String, ModelDownloadState>,
+    /// Why the last download or load of a model failed, shown in its header
+    #[rust] model_errors:    HashMap<String, MolyError>,
+    /// Low disk space warnings for running downloads
+    #[rust] download_notes:  HashMap<String, String>,
+    /// Newer versions of downloaded models, from the last update check
+    #[rust] model_updates:   HashMap<String, ModelUpdate>,
+    #[rust] update_check_rx: Option<mpsc::Receiver<Vec<(String, Option<ModelUpdate>)>>>,
+    #[rust] last_update_check: Option<std::time::Instant>,
 
     // ── Load / Unload tracking ──────────────────────────────────────────────
     #[rust] load_states:      HashMap<String, ModelLoadState>,
+    /// ominix-api version at the last status poll, to refresh the header
+    /// when it changes
+    #[rust] runtime_version:  Option<RuntimeVersion>,
     /// Receivers for in-flight load operations (key = registry model ID)
     #[rust] load_rxs:         HashMap<String, mpsc::Receiver<Result<(), String>>>,
+    /// When in-flight loads started, to notice stalled ones
+    #[rust] load_started:     HashMap<String, std::time::Instant>,
+    /// Loads past the load timeout, offered for retry or cancel
+    #[rust] stalled_loads:    HashSet<String>,
+    /// Large model whose low power warning is showing; Load again loads it
+    #[rust] power_warned:     Option<String>,
     /// Receivers for in-flight unload operations
     #[rust] unload_rxs:       HashMap<String, mpsc::Receiver<Result<(), String>>>,
+    /// Test requests sent to models after they loaded, see smoke_test
+    #[rust] verifications:    HashMap<String, Verification>,
+    #[rust] smoke_rxs:        HashMap<String, mpsc::Receiver<Result<(), String>>>,
+    /// Registry ids marked "Preload on startup", mirrored from Preferences
+    #[rust] preload_models:   Vec<String>,
     /// One-shot: GET /v1/models to sync server state
     #[rust] server_status_rx: Option<mpsc::Receiver<Result<Vec<ServerModelInfo>, String>>>,
 
     // ── Panel state ─────────────────────────────────────────────────────────
     #[rust] active_panel: ActivePanel,
     #[rust] llm_state:    LlmState,
+    #[rust] llm_eval:     EvalState,
     #[rust] vlm_state:    VlmState,
     #[rust] asr_state:    AsrState,
+    #[rust] asr_batch:    AsrBatchState,
+    #[rust] asr_notes:    NotesState,
+    #[rust] asr_watch:    WatchFolderView,
     #[rust] tts_state:    TtsState,
+    #[rust] audiobook:    AudiobookState,
     #[rust] selected_tts_voice_idx: usize,
     #[rust] image_state:  ImageState,
+    #[rust] image_history: ImageHistory,
+    #[rust] chat_templates: ChatTemplateOverrides,
+    #[rust] runtime_options: ModelRuntimeOptions,
+    /// Whether the panel header's Advanced section is open
+    #[rust] options_open: bool,
     #[rust] image_edit_state: ImageEditState,
     #[rust] video_state:  VideoState,
+    #[rust] music_state:  MusicState,
+    #[rust] rerank_state: RerankState,
 
     // ── Remove confirmation ──────────────────────────────────────────────────
     /// Model ID pending removal confirmation (first click sets this, second click confirms)
     #[rust] pending_remove_id: Option<String>,
+    /// Removed model waiting out its undo window (model ID, staged files)
+    #[rust] pending_removal: Option<(String, PendingRemoval)>,
+    #[rust] undo_timer: Timer,
+
+    // ── Bulk actions ─────────────────────────────────────────────────────────
+    /// Taps in the list check models instead of opening them
+    #[rust] select_mode: bool,
+    #[rust] bulk_selection: HashSet<String>,
+    /// Models started by the last bulk download, for its progress summary
+    #[rust] bulk_downloads: Vec<String>,
+    /// First click on bulk Remove sets this, the second removes
+    #[rust] bulk_remove_armed: bool,
+    /// File checks of a bulk verify: (model ID, new status, complete)
+    #[rust] bulk_verify_rx: Option<mpsc::Receiver<Vec<(String, DownloadStatus, bool)>>>,
+
+    // ── Runtime log ──────────────────────────────────────────────────────────
+    /// Tail of the server's log file, opened with the panel
+    #[rust] runtime_log: Option<LogTail>,
+    /// Least severe level shown
+    #[rust(LogLevel::Info)] runtime_log_level: LogLevel,
+
+    // ── Diagnostics ──────────────────────────────────────────────────────────
+    /// Last check of the runtime's port
+    #[rust] diagnosis: Option<Diagnosis>,
+    /// Port check in flight
+    #[rust] diagnosis_rx: Option<mpsc::Receiver<Diagnosis>>,
+    /// Fix being applied; what it did, or why it failed
+    #[rust] fix_rx: Option<mpsc::Receiver<Result<String, String>>>,
+    /// Fix slot clicked once, waiting for the click that confirms it
+    #[rust] fix_armed: Option<usize>,
+
+    // ── Ollama ───────────────────────────────────────────────────────────────
+    /// The Ollama provider's daemon address, read when the panel opens
+    #[rust] ollama_url: String,
+    /// Whether chats can use the daemon's models
+    #[rust] ollama_ready: bool,
+    /// Installed models, with their size on disk
+    #[rust] ollama_models: Vec<CatalogModel>,
+    #[rust] ollama_list_rx: Option<mpsc::Receiver<Result<Vec<CatalogModel>, String>>>,
+    #[rust] ollama_pull_rx: Option<mpsc::Receiver<OllamaPullUpdate>>,
+    #[rust] ollama_pull_cancel: Option<Arc<AtomicBool>>,
+
+    // ── LAN peers ────────────────────────────────────────────────────────────
+    /// Checks for peers sharing models on the local network, and for the
+    /// registry update
+    #[rust] peer_timer: Timer,
+    /// `lan_share::peers_generation()` at the last header refresh
+    #[rust] peers_seen: u64,
+
+    // ── Keyboard navigation ──────────────────────────────────────────────────
+    /// Tab order over the search box, the active panel's header and its inputs
+    #[rust] focus: FocusChain,
 
     // ── Resizable split pane ─────────────────────────────────────────────────
     /// Width of the left panel in pixels; 0.0 means not yet initialized
@@ -377,47 +923,68 @@ pub struct ModelHubApp {
     /// (start_mouse_x, start_panel_width) captured on FingerDown on the divider
     #[rust] drag_start:          Option<(f64, f64)>,
 
-    // ── Voice Studio state ───────────────────────────────────────────────────
-    #[rust] voices:              Vec<VoiceEntry>,
-    #[rust] selected_voice_idx:  Option<usize>,
-    #[rust] voice_training_state: VoiceTrainingState,
-    #[rust] voice_synthesis_state: VoiceSynthesisState,
-    #[rust] voice_quality:       String,
-    #[rust] voice_language:      String,
-    #[rust] voice_denoise:       bool,
-    #[rust] voice_training_rx:   Option<mpsc::Receiver<VoiceTrainingUpdate>>,
-    #[rust] voice_synthesis_rx:  Option<mpsc::Receiver<VoiceSynthesisUpdate>>,
-    #[rust] voice_list_rx:       Option<mpsc::Receiver<VoicesUpdate>>,
-    #[rust] voice_cancel:        Option<std::sync::Arc<std::sync::atomic::AtomicBool>>,
-    #[rust] voice_task_id:       String,
 }
 
 impl Widget for ModelHubApp {
     fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
-        if !self.initialized { self.initialize(cx); }
+        if !self.initialized {
+            self.initialize(cx);
+            if let Some(store) = scope.data.get::<Store>() {
+                self.preload_models = store.preferences.preload_models.clone();
+            }
+        }
 
-        let actions = cx.capture_actions(|cx| self.view.handle_event(cx, event, scope));
+        let actions = cx.capture_actions(|cx| {
+            self.view.handle_event(cx, event, scope);
+            self.focus.handle_event(cx, event, scope, &self.view);
+        });
 
-        self.handle_search(&actions, cx);
+        self.handle_search(cx, event, &actions);
         self.handle_list_clicks(cx, &actions);
         self.handle_panel_header_buttons(cx, &actions);
+        self.handle_undo_bar(cx, event, &actions);
+        self.handle_bulk_actions(cx, &actions);
+        self.handle_lan_peers(cx, event);
+        self.handle_update_check(cx, event);
+        self.poll_registry_update(cx, event);
+        self.handle_runtime_log(cx, event, &actions);
+        self.handle_diagnostics(cx, &actions);
+        self.handle_ollama(cx, &actions, scope);
         self.handle_load_buttons(cx, &actions);
         self.handle_chat_button(cx, &actions, scope);
+        self.handle_preload_button(cx, &actions, scope);
+        self.handle_runtime_options(cx, &actions);
         self.handle_input_changes(&actions);
         self.handle_llm_actions(cx, &actions);
+        self.handle_llm_eval_actions(cx, &actions);
         self.handle_vlm_actions(cx, &actions);
         self.handle_asr_actions(cx, &actions);
+        self.handle_asr_batch_actions(cx, &actions);
+        self.handle_asr_watch_actions(cx, &actions, scope);
         self.handle_tts_actions(cx, &actions);
+        self.handle_audiobook_actions(cx, &actions);
         self.handle_image_actions(cx, &actions);
         self.handle_image_edit_actions(cx, &actions);
         self.handle_video_actions(cx, &actions);
-        self.handle_voice_actions(cx, &actions);
+        self.handle_music_actions(cx, &actions);
+        self.handle_rerank_actions(cx, &actions);
+        self.handle_request_export(cx, &actions);
+        if let Event::Actions(actions) = event {
+            self.handle_store_events(cx, actions);
+        }
 
         self.poll_downloads(cx);
+        self.poll_bulk_verify(cx);
+        self.poll_diagnostics(cx);
+        self.poll_ollama(cx);
         self.poll_load_channels(cx);
         self.poll_panel_channels(cx);
+        self.poll_asr_batch(cx);
+        self.poll_asr_watch(cx);
+        self.poll_meeting_notes(cx, scope);
+        self.poll_llm_eval(cx);
+        self.poll_audiobook(cx);
         self.check_server_status_result(cx);
-        self.poll_voice_channels(cx);
 
         // ── Resizable divider drag ────────────────────────────────────────────
         let divider_area = self.view.view(ids!(hub_main_divider)).area();
@@ -466,12 +1033,8 @@ impl Widget for ModelHubApp {
                 self.view.view(ids!(hub_vlm_panel.vlm_drop_zone)).apply_over(cx, live! { draw_bg: { drag_over: (0.0) } });
                 for item in e.items.iter() {
                     if let DragItem::FilePath { path, .. } = item {
-                        let lower = path.to_lowercase();
-                        if lower.ends_with(".jpg") || lower.ends_with(".jpeg") || lower.ends_with(".png")
-                            || lower.ends_with(".bmp") || lower.ends_with(".gif") || lower.ends_with(".webp") {
-                            self.vlm_state.image_path = path.clone();
-                            self.view.text_input(ids!(hub_vlm_panel.vlm_image_path)).set_text(cx, path);
-                            self.view.redraw(cx);
+                        if is_image_file(path) {
+                            self.set_vlm_image(cx, path.clone());
                             break;
                         }
                     }
@@ -480,6 +1043,14 @@ impl Widget for ModelHubApp {
             _ => {}
         }
 
+        // ── Image panel init image and mask drop zones ───────────────────────
+        if let Some(path) = image_dropped(cx, event, &self.view.view(ids!(hub_image_panel.img_init_drop_zone))) {
+            self.set_image_source(cx, false, path);
+        }
+        if let Some(path) = image_dropped(cx, event, &self.view.view(ids!(hub_image_panel.img_mask_drop_zone))) {
+            self.set_image_source(cx, true, path);
+        }
+
         // ── Image Edit reference image drop zone ─────────────────────────────
         let edit_drop_area = self.view.view(ids!(hub_image_edit_panel.img_edit_drop_zone)).area();
         match event.drag_hits(cx, edit_drop_area) {
@@ -500,9 +1071,7 @@ impl Widget for ModelHubApp {
                 self.view.view(ids!(hub_image_edit_panel.img_edit_drop_zone)).apply_over(cx, live! { draw_bg: { drag_over: (0.0) } });
                 for item in e.items.iter() {
                     if let DragItem::FilePath { path, .. } = item {
-                        let lower = path.to_lowercase();
-                        if lower.ends_with(".jpg") || lower.ends_with(".jpeg") || lower.ends_with(".png")
-                            || lower.ends_with(".bmp") || lower.ends_with(".gif") || lower.ends_with(".webp") {
+                        if is_image_file(path) {
                             self.image_edit_state.image_path = path.clone();
                             self.view.text_input(ids!(hub_image_edit_panel.img_edit_image_path)).set_text(cx, path);
                             self.view.redraw(cx);
@@ -523,18 +1092,42 @@ impl Widget for ModelHubApp {
 
         let hub_list      = self.view.portal_list(ids!(hub_model_list));
         let hub_list_uid  = hub_list.widget_uid();
-        let voice_list    = self.view.portal_list(ids!(hub_voice_panel.voice_list));
-        let voice_list_uid = voice_list.widget_uid();
         let tts_voice_list    = self.view.portal_list(ids!(hub_tts_panel.tts_voice_list));
         let tts_voice_list_uid = tts_voice_list.widget_uid();
+        let asr_segment_list    = self.view.portal_list(ids!(hub_asr_panel.asr_segment_list));
+        let asr_segment_list_uid = asr_segment_list.widget_uid();
+        let asr_batch_list      = self.view.portal_list(ids!(hub_asr_panel.asr_batch_list));
+        let asr_batch_list_uid  = asr_batch_list.widget_uid();
+        let asr_watch_list      = self.view.portal_list(ids!(hub_asr_panel.asr_watch_list));
+        let asr_watch_list_uid  = asr_watch_list.widget_uid();
+        let tts_book_list       = self.view.portal_list(ids!(hub_tts_panel.tts_book_list));
+        let tts_book_list_uid   = tts_book_list.widget_uid();
+        let img_history_list    = self.view.portal_list(ids!(hub_image_panel.img_history_list));
+        let img_history_list_uid = img_history_list.widget_uid();
+        let llm_eval_list       = self.view.portal_list(ids!(hub_llm_panel.llm_eval_list));
+        let llm_eval_list_uid   = llm_eval_list.widget_uid();
+        let ollama_model_list   = self.view.portal_list(ids!(hub_ollama_panel.ollama_model_list));
+        let ollama_model_list_uid = ollama_model_list.widget_uid();
 
         while let Some(widget) = self.view.draw_walk(cx, scope, walk).step() {
             if widget.widget_uid() == hub_list_uid {
                 self.draw_hub_list(cx, scope, widget);
-            } else if widget.widget_uid() == voice_list_uid {
-                self.draw_voice_list(cx, scope, widget);
             } else if widget.widget_uid() == tts_voice_list_uid {
                 self.draw_tts_voice_list(cx, scope, widget);
+            } else if widget.widget_uid() == asr_segment_list_uid {
+                self.draw_asr_segment_list(cx, scope, widget);
+            } else if widget.widget_uid() == asr_batch_list_uid {
+                self.draw_asr_batch_list(cx, scope, widget);
+            } else if widget.widget_uid() == asr_watch_list_uid {
+                self.draw_asr_watch_list(cx, scope, widget);
+            } else if widget.widget_uid() == tts_book_list_uid {
+                self.draw_audiobook_list(cx, scope, widget);
+            } else if widget.widget_uid() == img_history_list_uid {
+                self.draw_image_history(cx, scope, widget);
+            } else if widget.widget_uid() == llm_eval_list_uid {
+                self.draw_llm_eval_list(cx, scope, widget);
+            } else if widget.widget_uid() == ollama_model_list_uid {
+                self.draw_ollama_model_list(cx, scope, widget);
             }
         }
         DrawStep::done()
@@ -567,7 +1160,9 @@ impl ModelHubApp {
                         .and_then(|r| r.models.get(gi)).map(|m| m.id.as_str()).unwrap_or("");
                     let name = self.registry.as_ref()
                         .and_then(|r| r.models.get(gi)).map(|m| m.name.as_str()).unwrap_or("");
-                    let dl   = self.model_states.get(model_id).copied().unwrap_or(ModelUiState::NotDownloaded);
+                    let deprecated = self.registry.as_ref()
+                        .and_then(|r| r.models.get(gi)).is_some_and(|m| m.deprecated);
+                    let dl   = self.dl_state(model_id);
                     let load = self.load_states.get(model_id).copied().unwrap_or_default();
                     let dot  = combined_dot_value(dl, load);
                     let sel  = self.selected_id.as_deref() == Some(model_id);
@@ -580,6 +1175,12 @@ impl ModelHubApp {
                     // Show "Downloaded" badge for downloaded or loaded models
                     let show_badge = dl == ModelUiState::Downloaded || load != ModelLoadState::Unloaded;
                     item.view(ids!(downloaded_badge)).set_visible(cx, show_badge);
+                    item.view(ids!(new_badge)).set_visible(cx, self.new_models.contains(model_id));
+                    item.view(ids!(update_badge)).set_visible(cx, show_badge && self.model_updates.contains_key(model_id));
+                    item.view(ids!(deprecated_badge)).set_visible(cx, deprecated);
+                    let checked = self.bulk_selection.contains(model_id);
+                    item.view(ids!(model_check)).set_visible(cx, self.select_mode);
+                    item.view(ids!(model_check)).apply_over(cx, live! { draw_bg: { checked: (if checked { 1.0_f64 } else { 0.0_f64 }) } });
                     if let Some(pct) = dl_frac {
                         item.view(ids!(inline_progress)).set_visible(cx, true);
                         item.view(ids!(inline_progress)).apply_over(cx, live! { draw_bg: { progress: (pct) } });
@@ -599,30 +1200,6 @@ impl ModelHubApp {
         }
     }
 
-    // ── Draw voice list ───────────────────────────────────────────────────────
-
-    fn draw_voice_list(&mut self, cx: &mut Cx2d, scope: &mut Scope, widget: WidgetRef) {
-        let binding = widget.as_portal_list();
-        let Some(mut list) = binding.borrow_mut() else { return };
-        list.set_item_range(cx, 0, self.voices.len());
-
-        while let Some(item_id) = list.next_visible_item(cx) {
-            if let Some(voice) = self.voices.get(item_id) {
-                let sel   = self.selected_voice_idx == Some(item_id);
-                let ready = voice.is_ready;
-                let name  = voice.name.clone();
-
-                let item = list.item(cx, item_id, live_id!(HubVoiceListItem));
-                item.label(ids!(voice_item_name)).set_text(cx, &name);
-                item.apply_over(cx, live! { draw_bg: { selected: (if sel { 1.0_f64 } else { 0.0_f64 }) } });
-                item.view(ids!(voice_status_dot)).apply_over(cx, live! {
-                    draw_bg: { ready: (if ready { 1.0_f64 } else { 0.0_f64 }) }
-                });
-                item.draw_all(cx, scope);
-            }
-        }
-    }
-
     // ── Draw TTS voice selector list ──────────────────────────────────────────
 
     fn draw_tts_voice_list(&mut self, cx: &mut Cx2d, scope: &mut Scope, widget: WidgetRef) {
@@ -647,6 +1224,145 @@ impl ModelHubApp {
         }
     }
 
+    // ── Draw ASR segment list ─────────────────────────────────────────────────
+
+    fn draw_asr_segment_list(&mut self, cx: &mut Cx2d, scope: &mut Scope, widget: WidgetRef) {
+        let binding = widget.as_portal_list();
+        let Some(mut list) = binding.borrow_mut() else { return };
+        let editor = self.asr_state.editor.as_ref();
+        let segments = editor.map(|e| e.segments()).unwrap_or(self.asr_state.transcript.segments.as_slice());
+        list.set_item_range(cx, 0, segments.len());
+
+        while let Some(item_id) = list.next_visible_item(cx) {
+            if let Some(seg) = segments.get(item_id) {
+                let time = format!("{} → {}",
+                    moly_data::transcript::format_short_timestamp(seg.start),
+                    moly_data::transcript::format_short_timestamp(seg.end));
+                let text = match editor {
+                    Some(e) if e.is_edited(item_id) => format!("✎ {}", seg.text),
+                    _ => seg.text.clone(),
+                };
+                let selected = if editor.is_some_and(|e| e.current() == item_id) { 1.0 } else { 0.0 };
+                let item = list.item(cx, item_id, live_id!(HubAsrSegmentItem));
+                item.apply_over(cx, live! { draw_bg: { selected: (selected) } });
+                item.label(ids!(segment_time)).set_text(cx, &time);
+                item.label(ids!(segment_text)).set_text(cx, &text);
+                item.draw_all(cx, scope);
+            }
+        }
+    }
+
+    fn draw_asr_batch_list(&mut self, cx: &mut Cx2d, scope: &mut Scope, widget: WidgetRef) {
+        let binding = widget.as_portal_list();
+        let Some(mut list) = binding.borrow_mut() else { return };
+        let files = &self.asr_batch.files;
+        list.set_item_range(cx, 0, files.len());
+
+        while let Some(item_id) = list.next_visible_item(cx) {
+            if let Some(f) = files.get(item_id) {
+                let name = f.path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+                let status = match (&f.status, &f.error) {
+                    (BatchFileStatus::Failed, Some(e)) => format!("Failed: {}", e),
+                    (st, _) => st.label().to_string(),
+                };
+                let item = list.item(cx, item_id, live_id!(HubAsrBatchItem));
+                item.label(ids!(batch_file_name)).set_text(cx, &name);
+                item.label(ids!(batch_file_status)).set_text(cx, &status);
+                item.draw_all(cx, scope);
+            }
+        }
+    }
+
+    fn draw_asr_watch_list(&mut self, cx: &mut Cx2d, scope: &mut Scope, widget: WidgetRef) {
+        let binding = widget.as_portal_list();
+        let Some(mut list) = binding.borrow_mut() else { return };
+        let items = &self.asr_watch.items;
+        list.set_item_range(cx, 0, items.len());
+
+        while let Some(item_id) = list.next_visible_item(cx) {
+            if let Some(watched) = items.get(item_id) {
+                let name = watched.path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+                let item = list.item(cx, item_id, live_id!(HubAsrBatchItem));
+                item.label(ids!(batch_file_name)).set_text(cx, &name);
+                item.label(ids!(batch_file_status)).set_text(cx, &watched.status.label());
+                item.draw_all(cx, scope);
+            }
+        }
+    }
+
+    fn draw_llm_eval_list(&mut self, cx: &mut Cx2d, scope: &mut Scope, widget: WidgetRef) {
+        let binding = widget.as_portal_list();
+        let Some(mut list) = binding.borrow_mut() else { return };
+        let eval = &self.llm_eval;
+        list.set_item_range(cx, 0, eval.cases.len());
+
+        while let Some(item_id) = list.next_visible_item(cx) {
+            if let Some(case) = eval.cases.get(item_id) {
+                let name = format!("{}. {}", case.id, case.prompt().replace('\n', " "));
+                let status = match eval.results.get(item_id).and_then(Option::as_ref) {
+                    Some(EvalResult { answer: Err(e), .. }) => format!("Failed: {}", e),
+                    Some(result) => {
+                        let verdict = match result.passed {
+                            Some(true) => "Pass",
+                            Some(false) => "Fail",
+                            None => "Answered",
+                        };
+                        format!("{} · {:.2} s", verdict, result.latency.as_secs_f64())
+                    }
+                    None if eval.running == Some(item_id) => "Running…".to_string(),
+                    None => "Queued".to_string(),
+                };
+                let item = list.item(cx, item_id, live_id!(HubAsrBatchItem));
+                item.label(ids!(batch_file_name)).set_text(cx, &name);
+                item.label(ids!(batch_file_status)).set_text(cx, &status);
+                item.draw_all(cx, scope);
+            }
+        }
+    }
+
+    fn draw_audiobook_list(&mut self, cx: &mut Cx2d, scope: &mut Scope, widget: WidgetRef) {
+        let binding = widget.as_portal_list();
+        let Some(mut list) = binding.borrow_mut() else { return };
+        let chapters = &self.audiobook.chapters;
+        list.set_item_range(cx, 0, chapters.len());
+
+        while let Some(item_id) = list.next_visible_item(cx) {
+            if let Some(c) = chapters.get(item_id) {
+                let name = format!("{}. {}", item_id + 1, c.chapter.title);
+                let status = match (&c.status, &c.error) {
+                    (BatchFileStatus::Failed, Some(e)) => format!("Failed: {}", e),
+                    (BatchFileStatus::Running, _) if c.progress.1 > 0 => {
+                        format!("Synthesizing {}/{}…", c.progress.0, c.progress.1)
+                    }
+                    (BatchFileStatus::Running, _) => "Synthesizing…".to_string(),
+                    (BatchFileStatus::Queued, _) => format!("{} chars", c.chapter.text.chars().count()),
+                    (st, _) => st.label().to_string(),
+                };
+                let item = list.item(cx, item_id, live_id!(HubAsrBatchItem));
+                item.label(ids!(batch_file_name)).set_text(cx, &name);
+                item.label(ids!(batch_file_status)).set_text(cx, &status);
+                item.draw_all(cx, scope);
+            }
+        }
+    }
+
+    fn draw_image_history(&mut self, cx: &mut Cx2d, scope: &mut Scope, widget: WidgetRef) {
+        let binding = widget.as_portal_list();
+        let Some(mut list) = binding.borrow_mut() else { return };
+        let entries = &self.image_history.entries;
+        list.set_item_range(cx, 0, entries.len());
+
+        while let Some(item_id) = list.next_visible_item(cx) {
+            if let Some(entry) = entries.get(item_id) {
+                let item = list.item(cx, item_id, live_id!(HubImageHistoryItem));
+                let _ = item.image(ids!(history_thumb)).load_image_file_by_path(cx, &entry.file);
+                item.label(ids!(history_prompt)).set_text(cx, &entry.prompt);
+                item.label(ids!(history_meta)).set_text(cx, &entry.details());
+                item.draw_all(cx, scope);
+            }
+        }
+    }
+
     // ── Initialisation ───────────────────────────────────────────────────────
 
     fn initialize(&mut self, cx: &mut Cx) {
@@ -660,32 +1376,70 @@ impl ModelHubApp {
             4 => Filter::Cat(RegistryCategory::Tts),
             5 => Filter::Cat(RegistryCategory::ImageGen),
             6 => Filter::Cat(RegistryCategory::VideoGen),
+            7 => Filter::Cat(RegistryCategory::MusicGen),
+            8 => Filter::Cat(RegistryCategory::Reranker),
             _ => Filter::All,
         };
         let registry = ModelRegistry::load();
-        ModelRegistry::fetch_updates_async();
+        self.registry_update_rx = Some(ModelRegistry::fetch_updates_async());
+        // Download state comes from model_state, kept current by the shell's
+        // ModelWatcher; only load state needs seeding here
         for model in &registry.models {
-            self.model_states.insert(model.id.clone(), scan_state(model));
+            if let Some(phase) = model_state::load_phase(&model.id) {
+                self.load_states.insert(model.id.clone(), load_state_for(&phase));
+            }
         }
+        self.search = ModelSearch::new(ModelSearchIndex::build(&registry.models));
         self.registry = Some(registry);
+        self.image_history = ImageHistory::load();
+        self.chat_templates = ChatTemplateOverrides::load();
+        self.runtime_options = ModelRuntimeOptions::load();
+        self.update_image_history_empty(cx);
         self.rebuild_list();
+        self.focus = FocusChain::new(hub_focus_targets());
+        lan_share::start_discovery();
+        self.peer_timer = cx.start_interval(power::interval(2.0));
         ::log::info!("ModelHubApp::initialize — filter={:?}, flat_list has {} items", self.filter, self.flat_list.len());
-        // Sync load states from the server immediately
+        // Ask the server once for models loaded before the app started; later
+        // changes arrive as StoreEvent::ModelLoadStateChanged
         self.poll_server_status();
         // Hide "Open in Chat" button and loading label (Label doesn't support visible: false in live_design)
-        self.view.widget(ids!(hub_llm_panel.hub_panel_header.panel_chat_btn)).set_visible(cx, false);
-        self.view.widget(ids!(hub_vlm_panel.hub_panel_header.panel_chat_btn)).set_visible(cx, false);
-        self.view.widget(ids!(hub_llm_panel.hub_panel_header.panel_loading_label)).set_visible(cx, false);
-        self.view.widget(ids!(hub_vlm_panel.hub_panel_header.panel_loading_label)).set_visible(cx, false);
-        self.view.widget(ids!(hub_asr_panel.hub_panel_header.panel_loading_label)).set_visible(cx, false);
-        self.view.widget(ids!(hub_tts_panel.hub_panel_header.panel_loading_label)).set_visible(cx, false);
-        self.view.widget(ids!(hub_image_panel.hub_panel_header.panel_loading_label)).set_visible(cx, false);
-        self.view.widget(ids!(hub_image_edit_panel.hub_panel_header.panel_loading_label)).set_visible(cx, false);
+        for (_, panel_id) in header_panels() {
+            let header = self.view.hub_panel_header(&[panel_id, live_id!(hub_panel_header)]);
+            header.widget(ids!(panel_chat_btn)).set_visible(cx, false);
+            header.widget(ids!(panel_loading_label)).set_visible(cx, false);
+        }
         self.view.widget(ids!(hub_voice_panel)).set_visible(cx, false);
-        // Init voice defaults
-        self.voice_quality  = "standard".to_string();
-        self.voice_language = "auto".to_string();
-        self.voice_denoise  = true;
+        self.view.redraw(cx);
+    }
+
+    /// Merge the server's registry into the open list once it arrives.
+    /// Models keep their place, so the selection and running downloads are
+    /// unaffected; added models get a "New" badge.
+    fn poll_registry_update(&mut self, cx: &mut Cx, event: &Event) {
+        if self.peer_timer.is_event(event).is_none() { return; }
+        let Some(rx) = &self.registry_update_rx else { return };
+        let update = match rx.try_recv() {
+            Ok(update) => update,
+            Err(mpsc::TryRecvError::Empty) => return,
+            Err(mpsc::TryRecvError::Disconnected) => {
+                self.registry_update_rx = None;
+                return;
+            }
+        };
+        self.registry_update_rx = None;
+        let Some(registry) = self.registry.as_mut() else { return };
+        let added = registry.merge(update);
+        ::log::info!("Registry update merged into the hub: {} new model(s)", added.len());
+        self.new_models.extend(added);
+
+        let query = self.search.query().to_string();
+        self.search = ModelSearch::new(ModelSearchIndex::build(&registry.models));
+        self.search.set_query(&query);
+        self.rebuild_list();
+        if let Some(sel) = self.selected_id.clone() {
+            self.refresh_header_for(cx, &sel);
+        }
         self.view.redraw(cx);
     }
 
@@ -693,39 +1447,29 @@ impl ModelHubApp {
 
     fn rebuild_list(&mut self) {
         let Some(registry) = &self.registry else { return };
-        let q = self.search_query.to_lowercase();
 
-        const CATS: [RegistryCategory; 6] = [
-            RegistryCategory::Llm, RegistryCategory::Vlm, RegistryCategory::Asr,
-            RegistryCategory::Tts, RegistryCategory::ImageGen, RegistryCategory::VideoGen,
-        ];
+        // Cleared rather than reallocated; the search results are already
+        // filtered per category
         self.flat_list.clear();
         self.subfolder_names.clear();
 
         let single_category = matches!(self.filter, Filter::Cat(_));
-        for cat in CATS {
+        for cat in HUB_CATEGORIES {
             if let Filter::Cat(fc) = self.filter { if fc != cat { continue; } }
-            let models: Vec<usize> = registry.models.iter().enumerate()
-                .filter(|(_, m)| m.category == cat)
-                .filter(|(_, m)| q.is_empty()
-                    || m.name.to_lowercase().contains(&q)
-                    || m.description.to_lowercase().contains(&q)
-                    || m.tags.iter().any(|t| t.to_lowercase().contains(&q)))
-                .map(|(i, _)| i)
-                .collect();
+            let models = self.search.results(cat);
             if models.is_empty() { continue; }
             // Skip category header when locked to a single category
             if !single_category {
                 self.flat_list.push(ListRow::Header(cat));
             }
             // Emit subfolder headers when the subfolder name changes.
-            let mut last_subfolder = String::new();
-            for gi in models {
-                let sf = registry.models[gi].subfolder.clone();
+            let mut last_subfolder = "";
+            for &gi in models {
+                let sf = registry.models[gi].subfolder.as_str();
                 if !sf.is_empty() && sf != last_subfolder {
-                    last_subfolder = sf.clone();
+                    last_subfolder = sf;
                     let idx = self.subfolder_names.len();
-                    self.subfolder_names.push(sf);
+                    self.subfolder_names.push(sf.to_string());
                     self.flat_list.push(ListRow::SubHeader(idx));
                 }
                 self.flat_list.push(ListRow::Model(gi));
@@ -733,7 +1477,7 @@ impl ModelHubApp {
         }
         // Voice Studio: show in the All hub (hub_category 0) and the TTS hub (hub_category 4)
         let in_voice_hub = self.hub_category == 0.0 || self.hub_category as u32 == 4;
-        if in_voice_hub && q.is_empty() {
+        if in_voice_hub && self.search.query().is_empty() {
             self.flat_list.push(ListRow::VoiceStudio);
         }
     }
@@ -743,14 +1487,26 @@ impl ModelHubApp {
     fn show_panel(&mut self, cx: &mut Cx, panel: ActivePanel) {
         self.active_panel = panel;
         self.view.widget(ids!(hub_empty_state)).set_visible(cx, panel == ActivePanel::None);
-        self.view.widget(ids!(hub_llm_panel)).set_visible(cx, panel == ActivePanel::Llm);
-        self.view.widget(ids!(hub_vlm_panel)).set_visible(cx, panel == ActivePanel::Vlm);
-        self.view.widget(ids!(hub_asr_panel)).set_visible(cx, panel == ActivePanel::Asr);
-        self.view.widget(ids!(hub_tts_panel)).set_visible(cx, panel == ActivePanel::Tts);
-        self.view.widget(ids!(hub_image_panel)).set_visible(cx, panel == ActivePanel::Image);
-        self.view.widget(ids!(hub_image_edit_panel)).set_visible(cx, panel == ActivePanel::ImageEdit);
-        self.view.widget(ids!(hub_video_panel)).set_visible(cx, panel == ActivePanel::Video);
+        for (header_panel, panel_id) in header_panels() {
+            self.view.widget(&[panel_id]).set_visible(cx, panel == header_panel);
+        }
         self.view.widget(ids!(hub_voice_panel)).set_visible(cx, panel == ActivePanel::Voice);
+        self.view.widget(ids!(hub_planner_panel)).set_visible(cx, panel == ActivePanel::Planner);
+        self.view.widget(ids!(hub_runtime_log_panel)).set_visible(cx, panel == ActivePanel::RuntimeLog);
+        self.view.widget(ids!(hub_diagnostics_panel)).set_visible(cx, panel == ActivePanel::Diagnostics);
+        self.view.widget(ids!(hub_ollama_panel)).set_visible(cx, panel == ActivePanel::Ollama);
+        let mode_buttons = [
+            (live_id!(hub_runtime_log_btn), ActivePanel::RuntimeLog),
+            (live_id!(hub_diagnostics_btn), ActivePanel::Diagnostics),
+            (live_id!(hub_ollama_btn), ActivePanel::Ollama),
+        ];
+        for (button, button_panel) in mode_buttons {
+            let selected = if panel == button_panel { 1.0 } else { 0.0 };
+            self.view.button(&[button]).apply_over(cx, live! {
+                draw_bg: { selected: (selected) }
+                draw_text: { selected: (selected) }
+            });
+        }
     }
 
     // ── Model selection ───────────────────────────────────────────────────────
@@ -766,6 +1522,7 @@ impl ModelHubApp {
 
     fn on_model_selected(&mut self, cx: &mut Cx, model_id: &str) {
         self.reset_remove_confirmation(cx);
+        self.new_models.remove(model_id);
         let model = self.registry.as_ref()
             .and_then(|r| r.models.iter().find(|m| m.id == model_id))
             .cloned();
@@ -785,10 +1542,16 @@ impl ModelHubApp {
                 }
             }
             RegistryCategory::VideoGen => ActivePanel::Video,
+            RegistryCategory::MusicGen => ActivePanel::Music,
+            RegistryCategory::Reranker => ActivePanel::Rerank,
         };
 
         self.show_panel(cx, panel);
         self.refresh_header_for(cx, model_id);
+        self.fill_runtime_options(cx, model_id);
+        if panel == ActivePanel::Llm {
+            self.update_template_row(cx, &model);
+        }
 
         // TTS: lazily load available voices
         if panel == ActivePanel::Tts
@@ -797,22 +1560,19 @@ impl ModelHubApp {
         {
             self.load_tts_voices();
         }
-
-        // Refresh server status for accurate Load/Unload button state
-        if self.server_status_rx.is_none() {
-            self.poll_server_status();
-        }
     }
 
     // ── Panel header refresh ─────────────────────────────────────────────────
 
     fn refresh_header_for(&mut self, cx: &mut Cx, model_id: &str) {
+        // The header shows the selected model; bulk actions touch others
+        if self.selected_id.as_deref() != Some(model_id) { return; }
         let model = self.registry.as_ref()
             .and_then(|r| r.models.iter().find(|m| m.id == model_id))
             .cloned();
         let Some(model) = model else { return };
 
-        let dl       = self.model_states.get(model_id).copied().unwrap_or(ModelUiState::NotDownloaded);
+        let dl       = self.dl_state(model_id);
         let load     = self.load_states.get(model_id).copied().unwrap_or_default();
         ::log::info!("refresh_header_for: model={}, dl={:?}, load={:?}, panel={:?}", model_id, dl, load, self.active_panel);
         let is_dl    = dl == ModelUiState::Downloading;
@@ -822,27 +1582,69 @@ impl ModelHubApp {
         // Download buttons
         let show_dl   = !is_dl && !is_done && !is_manual;
         let show_can  = is_dl;
-        let show_rm   = is_done;
+        // An incomplete install can be downloaded again or its files removed
+        let show_rm   = is_done || dl == ModelUiState::Incomplete;
         let show_prog = is_dl;
 
         // Load / Unload buttons (not applicable to ImageEdit — sd.cpp runs directly)
         let is_image_edit = self.active_panel == ActivePanel::ImageEdit;
-        let show_load    = is_done && load == ModelLoadState::Unloaded && !is_image_edit;
-        let show_unload  = is_done && load == ModelLoadState::Loaded && !is_image_edit;
-        let show_loading = is_done && load == ModelLoadState::Loading && !is_image_edit;
+        // A stalled load can be retried, or cancelled by unloading
+        let is_stalled   = self.stalled_loads.contains(model_id);
+        // The running ominix-api may be too old for the model
+        let requirement  = model.runtime_requirement(installed_runtime_version()).filter(|_| is_done);
+        let show_load    = is_done && (load == ModelLoadState::Unloaded || is_stalled) && !is_image_edit && requirement.is_none();
+        let show_unload  = is_done && (load == ModelLoadState::Loaded || is_stalled) && !is_image_edit;
+        let show_loading = is_done && load == ModelLoadState::Loading && !is_stalled && !is_image_edit;
 
         let dot      = combined_dot_value(dl, load);
-        let st_label = combined_status_label(dl, load);
+        let verification = self.verifications.get(model_id).filter(|_| load == ModelLoadState::Loaded);
+        let st_label = match verification {
+            Some(Verification::Checking) => "Loaded, checking...",
+            Some(Verification::Verified) => "Loaded (verified)",
+            Some(Verification::Failed(_)) => "Loaded (unverified)",
+            None if load == ModelLoadState::Loaded && smoke_test::supported(model.category) => "Loaded (unverified)",
+            None if is_stalled => "Load stalled",
+            None => combined_status_label(dl, load),
+        };
         let name     = model.name.clone();
         let desc     = model.description.clone();
         let size     = model.storage.size_display.clone();
         let mem      = format!("{:.1} GB", model.runtime.memory_gb);
 
+        // A peer on the network can send the model instead
+        let lan_peer = if show_dl { lan_share::peer_with(model_id) } else { None };
+        let update = self.model_updates.get(model_id).filter(|_| is_done);
+        let deprecation = self.registry.as_ref().and_then(|r| model.deprecation_notice(r));
+
         // Status message
+        let power_warning = self.power_warned.as_deref()
+            .filter(|id| *id == model_id && show_load)
+            .and_then(|_| power::load_warning(&model.name, model.runtime.memory_gb));
         let msg = if is_manual {
             format!("Manual install: {}", model.storage.local_path)
+        } else if let Some(warning) = power_warning {
+            format!("{} Press Load again to load it anyway.", warning)
+        } else if let Some(requirement) = requirement.filter(|_| load == ModelLoadState::Unloaded) {
+            requirement
+        } else if let Some(note) = self.download_notes.get(model_id).filter(|_| is_dl) {
+            note.clone()
+        } else if let Some(peer) = &lan_peer {
+            format!("Also on {} — import it over the local network instead of downloading.", peer.name)
+        } else if let Some(err) = self.model_errors.get(model_id)
+            .filter(|_| dl == ModelUiState::Error || load == ModelLoadState::LoadError || is_stalled)
+        {
+            err.user_message()
+        } else if let Some(Verification::Failed(err)) = verification {
+            format!("Loaded, but a test request failed: {}", err)
+        } else if dl == ModelUiState::Incomplete {
+            "Download incomplete. Download again, or remove the partial files.".to_string()
         } else if load == ModelLoadState::LoadError {
             "Load failed. Check logs — ominix-api may be missing or model files incomplete.".to_string()
+        } else if let Some(update) = update {
+            let reload = if load == ModelLoadState::Loaded { " Load it again afterwards to use it." } else { "" };
+            format!("Update available: {}. Update downloads only those.{}", update.summary(), reload)
+        } else if let Some(notice) = deprecation {
+            format!("{}. It still works, but won't be updated.", notice)
         } else if show_load {
             "Downloaded. Press Load to bring into memory.".to_string()
         } else if is_image_edit && is_done {
@@ -851,16 +1653,23 @@ impl ModelHubApp {
             String::new()
         };
 
-        let dl_state = self.download_states.get(model_id).cloned();
-        let pct = dl_state.as_ref().map(|d| d.fraction());
-        let txt = dl_state.as_ref().map(|d| d.progress_text());
+        let progress = self.download_states.get(model_id).map(|d| (d.fraction(), d.progress_text()));
 
         // Memory guard warning: check if another model of same category is Loaded
         let cat = model.category;
 
-        // "Open in Chat" button — only for LLM/VLM when loaded
-        let show_chat = load == ModelLoadState::Loaded
-            && (cat == RegistryCategory::Llm || cat == RegistryCategory::Vlm);
+        // "Open in Chat" button for LLM/VLM, "Open Transcriber" for ASR and
+        // "Open Speaker" for TTS, when loaded
+        let chat_label = match cat {
+            RegistryCategory::Llm | RegistryCategory::Vlm => Some("Open in Chat"),
+            RegistryCategory::Asr => Some("Open Transcriber"),
+            RegistryCategory::Tts => Some("Open Speaker"),
+            RegistryCategory::ImageGen
+            | RegistryCategory::VideoGen
+            | RegistryCategory::MusicGen
+            | RegistryCategory::Reranker => None,
+        }
+        .filter(|_| load == ModelLoadState::Loaded);
         let blocker_name = if show_load {
             self.registry.as_ref().and_then(|r| {
                 r.models.iter().find(|m| {
@@ -880,215 +1689,146 @@ impl ModelHubApp {
         // Disable Load button if another model is blocking
         let show_load = show_load && blocker_name.is_none();
 
-        // ids!() is compile-time — each panel's paths must be written explicitly.
-        match self.active_panel {
-            ActivePanel::Llm => {
-                self.view.label(ids!(hub_llm_panel.hub_panel_header.panel_model_name)).set_text(cx, &name);
-                self.view.label(ids!(hub_llm_panel.hub_panel_header.panel_model_desc)).set_text(cx, &desc);
-                self.view.view(ids!(hub_llm_panel.hub_panel_header.panel_status_dot))
-                    .apply_over(cx, live! { draw_bg: { status: (dot) } });
-                self.view.label(ids!(hub_llm_panel.hub_panel_header.panel_status_text)).set_text(cx, st_label);
-                self.view.label(ids!(hub_llm_panel.hub_panel_header.panel_size_text)).set_text(cx, &size);
-                self.view.label(ids!(hub_llm_panel.hub_panel_header.panel_mem_text)).set_text(cx, &mem);
-                self.view.widget(ids!(hub_llm_panel.hub_panel_header.panel_download_btn)).set_visible(cx, show_dl);
-                self.view.widget(ids!(hub_llm_panel.hub_panel_header.panel_cancel_btn)).set_visible(cx, show_can);
-                self.view.widget(ids!(hub_llm_panel.hub_panel_header.panel_remove_btn)).set_visible(cx, show_rm);
-                self.view.widget(ids!(hub_llm_panel.hub_panel_header.panel_progress_section)).set_visible(cx, show_prog);
-                self.view.widget(ids!(hub_llm_panel.hub_panel_header.panel_load_btn)).set_visible(cx, show_load);
-                self.view.widget(ids!(hub_llm_panel.hub_panel_header.panel_unload_btn)).set_visible(cx, show_unload);
-                self.view.widget(ids!(hub_llm_panel.hub_panel_header.panel_loading_label)).set_visible(cx, show_loading);
-                self.view.widget(ids!(hub_llm_panel.hub_panel_header.panel_chat_btn)).set_visible(cx, show_chat);
-                self.view.label(ids!(hub_llm_panel.hub_panel_header.panel_status_msg)).set_text(cx, &msg);
-                if show_prog {
-                    if let Some(p) = pct {
-                        self.view.view(ids!(hub_llm_panel.hub_panel_header.panel_progress_fill))
-                            .apply_over(cx, live! { draw_bg: { progress: (p) } });
-                    }
-                    if let Some(ref t) = txt {
-                        self.view.label(ids!(hub_llm_panel.hub_panel_header.panel_progress_text)).set_text(cx, t);
-                    }
-                }
-            }
-            ActivePanel::Vlm => {
-                self.view.label(ids!(hub_vlm_panel.hub_panel_header.panel_model_name)).set_text(cx, &name);
-                self.view.label(ids!(hub_vlm_panel.hub_panel_header.panel_model_desc)).set_text(cx, &desc);
-                self.view.view(ids!(hub_vlm_panel.hub_panel_header.panel_status_dot))
-                    .apply_over(cx, live! { draw_bg: { status: (dot) } });
-                self.view.label(ids!(hub_vlm_panel.hub_panel_header.panel_status_text)).set_text(cx, st_label);
-                self.view.label(ids!(hub_vlm_panel.hub_panel_header.panel_size_text)).set_text(cx, &size);
-                self.view.label(ids!(hub_vlm_panel.hub_panel_header.panel_mem_text)).set_text(cx, &mem);
-                self.view.widget(ids!(hub_vlm_panel.hub_panel_header.panel_download_btn)).set_visible(cx, show_dl);
-                self.view.widget(ids!(hub_vlm_panel.hub_panel_header.panel_cancel_btn)).set_visible(cx, show_can);
-                self.view.widget(ids!(hub_vlm_panel.hub_panel_header.panel_remove_btn)).set_visible(cx, show_rm);
-                self.view.widget(ids!(hub_vlm_panel.hub_panel_header.panel_progress_section)).set_visible(cx, show_prog);
-                self.view.widget(ids!(hub_vlm_panel.hub_panel_header.panel_load_btn)).set_visible(cx, show_load);
-                self.view.widget(ids!(hub_vlm_panel.hub_panel_header.panel_unload_btn)).set_visible(cx, show_unload);
-                self.view.widget(ids!(hub_vlm_panel.hub_panel_header.panel_loading_label)).set_visible(cx, show_loading);
-                self.view.widget(ids!(hub_vlm_panel.hub_panel_header.panel_chat_btn)).set_visible(cx, show_chat);
-                self.view.label(ids!(hub_vlm_panel.hub_panel_header.panel_status_msg)).set_text(cx, &msg);
-                if show_prog {
-                    if let Some(p) = pct {
-                        self.view.view(ids!(hub_vlm_panel.hub_panel_header.panel_progress_fill))
-                            .apply_over(cx, live! { draw_bg: { progress: (p) } });
-                    }
-                    if let Some(ref t) = txt {
-                        self.view.label(ids!(hub_vlm_panel.hub_panel_header.panel_progress_text)).set_text(cx, t);
-                    }
-                }
-            }
-            ActivePanel::Asr => {
-                self.view.label(ids!(hub_asr_panel.hub_panel_header.panel_model_name)).set_text(cx, &name);
-                self.view.label(ids!(hub_asr_panel.hub_panel_header.panel_model_desc)).set_text(cx, &desc);
-                self.view.view(ids!(hub_asr_panel.hub_panel_header.panel_status_dot))
-                    .apply_over(cx, live! { draw_bg: { status: (dot) } });
-                self.view.label(ids!(hub_asr_panel.hub_panel_header.panel_status_text)).set_text(cx, st_label);
-                self.view.label(ids!(hub_asr_panel.hub_panel_header.panel_size_text)).set_text(cx, &size);
-                self.view.label(ids!(hub_asr_panel.hub_panel_header.panel_mem_text)).set_text(cx, &mem);
-                self.view.widget(ids!(hub_asr_panel.hub_panel_header.panel_download_btn)).set_visible(cx, show_dl);
-                self.view.widget(ids!(hub_asr_panel.hub_panel_header.panel_cancel_btn)).set_visible(cx, show_can);
-                self.view.widget(ids!(hub_asr_panel.hub_panel_header.panel_remove_btn)).set_visible(cx, show_rm);
-                self.view.widget(ids!(hub_asr_panel.hub_panel_header.panel_progress_section)).set_visible(cx, show_prog);
-                self.view.widget(ids!(hub_asr_panel.hub_panel_header.panel_load_btn)).set_visible(cx, show_load);
-                self.view.widget(ids!(hub_asr_panel.hub_panel_header.panel_unload_btn)).set_visible(cx, show_unload);
-                self.view.widget(ids!(hub_asr_panel.hub_panel_header.panel_loading_label)).set_visible(cx, show_loading);
-                self.view.label(ids!(hub_asr_panel.hub_panel_header.panel_status_msg)).set_text(cx, &msg);
-                if show_prog {
-                    if let Some(p) = pct {
-                        self.view.view(ids!(hub_asr_panel.hub_panel_header.panel_progress_fill))
-                            .apply_over(cx, live! { draw_bg: { progress: (p) } });
-                    }
-                    if let Some(ref t) = txt {
-                        self.view.label(ids!(hub_asr_panel.hub_panel_header.panel_progress_text)).set_text(cx, t);
-                    }
-                }
-            }
-            ActivePanel::Tts => {
-                self.view.label(ids!(hub_tts_panel.hub_panel_header.panel_model_name)).set_text(cx, &name);
-                self.view.label(ids!(hub_tts_panel.hub_panel_header.panel_model_desc)).set_text(cx, &desc);
-                self.view.view(ids!(hub_tts_panel.hub_panel_header.panel_status_dot))
-                    .apply_over(cx, live! { draw_bg: { status: (dot) } });
-                self.view.label(ids!(hub_tts_panel.hub_panel_header.panel_status_text)).set_text(cx, st_label);
-                self.view.label(ids!(hub_tts_panel.hub_panel_header.panel_size_text)).set_text(cx, &size);
-                self.view.label(ids!(hub_tts_panel.hub_panel_header.panel_mem_text)).set_text(cx, &mem);
-                self.view.widget(ids!(hub_tts_panel.hub_panel_header.panel_download_btn)).set_visible(cx, show_dl);
-                self.view.widget(ids!(hub_tts_panel.hub_panel_header.panel_cancel_btn)).set_visible(cx, show_can);
-                self.view.widget(ids!(hub_tts_panel.hub_panel_header.panel_remove_btn)).set_visible(cx, show_rm);
-                self.view.widget(ids!(hub_tts_panel.hub_panel_header.panel_progress_section)).set_visible(cx, show_prog);
-                self.view.widget(ids!(hub_tts_panel.hub_panel_header.panel_load_btn)).set_visible(cx, show_load);
-                self.view.widget(ids!(hub_tts_panel.hub_panel_header.panel_unload_btn)).set_visible(cx, show_unload);
-                self.view.widget(ids!(hub_tts_panel.hub_panel_header.panel_loading_label)).set_visible(cx, show_loading);
-                self.view.label(ids!(hub_tts_panel.hub_panel_header.panel_status_msg)).set_text(cx, &msg);
-                if show_prog {
-                    if let Some(p) = pct {
-                        self.view.view(ids!(hub_tts_panel.hub_panel_header.panel_progress_fill))
-                            .apply_over(cx, live! { draw_bg: { progress: (p) } });
-                    }
-                    if let Some(ref t) = txt {
-                        self.view.label(ids!(hub_tts_panel.hub_panel_header.panel_progress_text)).set_text(cx, t);
-                    }
-                }
-            }
-            ActivePanel::Image => {
-                self.view.label(ids!(hub_image_panel.hub_panel_header.panel_model_name)).set_text(cx, &name);
-                self.view.label(ids!(hub_image_panel.hub_panel_header.panel_model_desc)).set_text(cx, &desc);
-                self.view.view(ids!(hub_image_panel.hub_panel_header.panel_status_dot))
-                    .apply_over(cx, live! { draw_bg: { status: (dot) } });
-                self.view.label(ids!(hub_image_panel.hub_panel_header.panel_status_text)).set_text(cx, st_label);
-                self.view.label(ids!(hub_image_panel.hub_panel_header.panel_size_text)).set_text(cx, &size);
-                self.view.label(ids!(hub_image_panel.hub_panel_header.panel_mem_text)).set_text(cx, &mem);
-                self.view.widget(ids!(hub_image_panel.hub_panel_header.panel_download_btn)).set_visible(cx, show_dl);
-                self.view.widget(ids!(hub_image_panel.hub_panel_header.panel_cancel_btn)).set_visible(cx, show_can);
-                self.view.widget(ids!(hub_image_panel.hub_panel_header.panel_remove_btn)).set_visible(cx, show_rm);
-                self.view.widget(ids!(hub_image_panel.hub_panel_header.panel_progress_section)).set_visible(cx, show_prog);
-                self.view.widget(ids!(hub_image_panel.hub_panel_header.panel_load_btn)).set_visible(cx, show_load);
-                self.view.widget(ids!(hub_image_panel.hub_panel_header.panel_unload_btn)).set_visible(cx, show_unload);
-                self.view.widget(ids!(hub_image_panel.hub_panel_header.panel_loading_label)).set_visible(cx, show_loading);
-                self.view.label(ids!(hub_image_panel.hub_panel_header.panel_status_msg)).set_text(cx, &msg);
-                if show_prog {
-                    if let Some(p) = pct {
-                        self.view.view(ids!(hub_image_panel.hub_panel_header.panel_progress_fill))
-                            .apply_over(cx, live! { draw_bg: { progress: (p) } });
-                    }
-                    if let Some(ref t) = txt {
-                        self.view.label(ids!(hub_image_panel.hub_panel_header.panel_progress_text)).set_text(cx, t);
-                    }
-                }
-            }
-            ActivePanel::ImageEdit => {
-                self.view.label(ids!(hub_image_edit_panel.hub_panel_header.panel_model_name)).set_text(cx, &name);
-                self.view.label(ids!(hub_image_edit_panel.hub_panel_header.panel_model_desc)).set_text(cx, &desc);
-                self.view.view(ids!(hub_image_edit_panel.hub_panel_header.panel_status_dot))
-                    .apply_over(cx, live! { draw_bg: { status: (dot) } });
-                self.view.label(ids!(hub_image_edit_panel.hub_panel_header.panel_status_text)).set_text(cx, st_label);
-                self.view.label(ids!(hub_image_edit_panel.hub_panel_header.panel_size_text)).set_text(cx, &size);
-                self.view.label(ids!(hub_image_edit_panel.hub_panel_header.panel_mem_text)).set_text(cx, &mem);
-                self.view.widget(ids!(hub_image_edit_panel.hub_panel_header.panel_download_btn)).set_visible(cx, show_dl);
-                self.view.widget(ids!(hub_image_edit_panel.hub_panel_header.panel_cancel_btn)).set_visible(cx, show_can);
-                self.view.widget(ids!(hub_image_edit_panel.hub_panel_header.panel_remove_btn)).set_visible(cx, show_rm);
-                self.view.widget(ids!(hub_image_edit_panel.hub_panel_header.panel_progress_section)).set_visible(cx, show_prog);
-                self.view.widget(ids!(hub_image_edit_panel.hub_panel_header.panel_load_btn)).set_visible(cx, show_load);
-                self.view.widget(ids!(hub_image_edit_panel.hub_panel_header.panel_unload_btn)).set_visible(cx, show_unload);
-                self.view.widget(ids!(hub_image_edit_panel.hub_panel_header.panel_loading_label)).set_visible(cx, show_loading);
-                self.view.label(ids!(hub_image_edit_panel.hub_panel_header.panel_status_msg)).set_text(cx, &msg);
-                if show_prog {
-                    if let Some(p) = pct {
-                        self.view.view(ids!(hub_image_edit_panel.hub_panel_header.panel_progress_fill))
-                            .apply_over(cx, live! { draw_bg: { progress: (p) } });
-                    }
-                    if let Some(ref t) = txt {
-                        self.view.label(ids!(hub_image_edit_panel.hub_panel_header.panel_progress_text)).set_text(cx, t);
-                    }
-                }
-            }
-            ActivePanel::Video => {
-                self.view.label(ids!(hub_video_panel.hub_panel_header.panel_model_name)).set_text(cx, &name);
-                self.view.label(ids!(hub_video_panel.hub_panel_header.panel_model_desc)).set_text(cx, &desc);
-                self.view.view(ids!(hub_video_panel.hub_panel_header.panel_status_dot))
-                    .apply_over(cx, live! { draw_bg: { status: (dot) } });
-                self.view.label(ids!(hub_video_panel.hub_panel_header.panel_status_text)).set_text(cx, st_label);
-                self.view.label(ids!(hub_video_panel.hub_panel_header.panel_size_text)).set_text(cx, &size);
-                self.view.label(ids!(hub_video_panel.hub_panel_header.panel_mem_text)).set_text(cx, &mem);
-                self.view.widget(ids!(hub_video_panel.hub_panel_header.panel_download_btn)).set_visible(cx, show_dl);
-                self.view.widget(ids!(hub_video_panel.hub_panel_header.panel_cancel_btn)).set_visible(cx, show_can);
-                self.view.widget(ids!(hub_video_panel.hub_panel_header.panel_remove_btn)).set_visible(cx, show_rm);
-                self.view.widget(ids!(hub_video_panel.hub_panel_header.panel_progress_section)).set_visible(cx, show_prog);
-                self.view.widget(ids!(hub_video_panel.hub_panel_header.panel_load_btn)).set_visible(cx, show_load);
-                self.view.widget(ids!(hub_video_panel.hub_panel_header.panel_unload_btn)).set_visible(cx, show_unload);
-                self.view.widget(ids!(hub_video_panel.hub_panel_header.panel_loading_label)).set_visible(cx, show_loading);
-                self.view.widget(ids!(hub_video_panel.hub_panel_header.panel_chat_btn)).set_visible(cx, false);
-                self.view.label(ids!(hub_video_panel.hub_panel_header.panel_status_msg)).set_text(cx, &msg);
-                if show_prog {
-                    if let Some(p) = pct {
-                        self.view.view(ids!(hub_video_panel.hub_panel_header.panel_progress_fill))
-                            .apply_over(cx, live! { draw_bg: { progress: (p) } });
-                    }
-                    if let Some(ref t) = txt {
-                        self.view.label(ids!(hub_video_panel.hub_panel_header.panel_progress_text)).set_text(cx, t);
-                    }
-                }
-            }
-            ActivePanel::Voice | ActivePanel::Info => {}
-            ActivePanel::None => {}
+        let (load_label, unload_label) = if is_stalled { ("Retry load", "Cancel load") } else { ("Load", "Unload") };
+        let preload_label = if self.preload_models.iter().any(|m| m == model_id) {
+            "✓ Preload on startup"
+        } else {
+            "Preload on startup"
+        };
+
+        let state = PanelHeaderState {
+            name,
+            description: desc,
+            dot,
+            status: st_label.to_string(),
+            size,
+            memory: mem,
+            message: msg,
+            show_download: show_dl,
+            show_lan_import: lan_peer.is_some(),
+            show_update: update.is_some(),
+            show_cancel: show_can,
+            show_remove: show_rm,
+            show_progress: show_prog,
+            progress,
+            show_load,
+            show_unload,
+            show_loading,
+            load_label,
+            unload_label,
+            chat_label,
+            show_preload: is_done && !is_image_edit,
+            preload_label,
+            show_options: is_done && !is_image_edit,
+            options_open: self.options_open,
+        };
+        if let Some(header) = self.active_header() {
+            header.set_state(cx, &state);
         }
-        self.view.redraw(cx);
     }
 }
 
-// ─── Event handlers ───────────────────────────────────────────────────────────
+/// The LLM panel's template buttons; `None` is Server Default
+fn llm_template_buttons() -> [(LiveId, Option<ChatTemplate>); 6] {
+    [
+        (live_id!(llm_template_default_btn), None),
+        (live_id!(llm_template_chatml_btn), Some(ChatTemplate::ChatMl)),
+        (live_id!(llm_template_llama3_btn), Some(ChatTemplate::Llama3)),
+        (live_id!(llm_template_qwen_btn), Some(ChatTemplate::Qwen)),
+        (live_id!(llm_template_mistral_btn), Some(ChatTemplate::Mistral)),
+        (live_id!(llm_template_gemma_btn), Some(ChatTemplate::Gemma)),
+    ]
+}
 
-impl ModelHubApp {
-    fn handle_search(&mut self, actions: &Actions, cx: &mut Cx) {
-        if let Some(txt) = self.view.text_input(ids!(search_input)).changed(actions) {
-            self.search_query = txt.to_string();
-            self.rebuild_list();
-            self.view.redraw(cx);
-        }
-    }
+/// Lines of the runtime log drawn at once; a label with thousands of lines
+/// makes every redraw slow
+const RUNTIME_LOG_SHOWN: usize = 400;
 
-    fn handle_list_clicks(&mut self, cx: &mut Cx, actions: &Actions) {
-        let list = self.view.portal_list(ids!(hub_model_list));
-        for (item_id, item) in list.items_with_actions(actions) {
-            let row = self.flat_list.get(item_id).copied();
+/// Fix rows of the diagnostics panel
+const DIAGNOSTIC_FIXES: usize = 3;
+
+fn runtime_log_level_buttons() -> [(LiveId, LogLevel); 4] {
+    [
+        (live_id!(runtime_log_trace_btn), LogLevel::Trace),
+        (live_id!(runtime_log_info_btn), LogLevel::Info),
+        (live_id!(runtime_log_warn_btn), LogLevel::Warn),
+        (live_id!(runtime_log_error_btn), LogLevel::Error),
+    ]
+}
+
+fn is_image_file(path: &str) -> bool {
+    let lower = path.to_lowercase();
+    [".jpg", ".jpeg", ".png", ".bmp", ".gif", ".webp"].iter().any(|ext| lower.ends_with(ext))
+}
+
+/// Highlight an image drop zone while a file is dragged over it, and return
+/// the path of an image dropped on it
+fn image_dropped(cx: &mut Cx, event: &Event, zone: &ViewRef) -> Option<String> {
+    match event.drag_hits(cx, zone.area()) {
+        DragHit::Drag(e) => {
+            let over = match e.state {
+                DragState::In | DragState::Over => {
+                    *e.response.lock().unwrap() = DragResponse::Copy;
+                    1.0
+                }
+                DragState::Out => 0.0,
+            };
+            zone.apply_over(cx, live! { draw_bg: { drag_over: (over) } });
+            zone.redraw(cx);
+            None
+        }
+        DragHit::Drop(e) => {
+            zone.apply_over(cx, live! { draw_bg: { drag_over: (0.0) } });
+            zone.redraw(cx);
+            e.items.iter().find_map(|item| match item {
+                DragItem::FilePath { path, .. } if is_image_file(path) => Some(path.clone()),
+                _ => None,
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Read the init image and mask of an image-to-image request from disk
+fn attach_image_sources(request: &mut ImageGenerationRequest, init_path: Option<&str>, mask_path: Option<&str>) -> Result<(), String> {
+    let encode = |path: &str| {
+        std::fs::read(path)
+            .map(|bytes| base64::engine::general_purpose::STANDARD.encode(bytes))
+            .map_err(|e| format!("Failed to read {}: {}", path, e))
+    };
+    request.image = init_path.map(encode).transpose()?;
+    request.mask = mask_path.map(encode).transpose()?;
+    Ok(())
+}
+
+/// An error from a panel's worker thread as a message with a suggested fix
+fn error_text(e: &impl std::fmt::Display) -> String {
+    MolyError::from_message(&e.to_string()).user_message()
+}
+
+// ─── Event handlers ───────────────────────────────────────────────────────────
+
+impl ModelHubApp {
+    /// Filter the list once typing pauses for [`SEARCH_DEBOUNCE`] seconds
+    fn handle_search(&mut self, cx: &mut Cx, event: &Event, actions: &Actions) {
+        if let Some(txt) = self.view.text_input(ids!(search_input)).changed(actions) {
+            self.search_query = txt.to_string();
+            cx.stop_timer(self.search_timer);
+            self.search_timer = cx.start_timeout(SEARCH_DEBOUNCE);
+        }
+        if self.search_timer.is_event(event).is_some() {
+            self.search_timer = Timer::empty();
+            if self.search.set_query(&self.search_query) {
+                self.rebuild_list();
+                self.view.redraw(cx);
+            }
+        }
+    }
+
+    fn handle_list_clicks(&mut self, cx: &mut Cx, actions: &Actions) {
+        let list = self.view.portal_list(ids!(hub_model_list));
+        for (item_id, item) in list.items_with_actions(actions) {
+            let row = self.flat_list.get(item_id).copied();
             if let Some(ListRow::Model(gi)) = row {
                 if let Some(fd) = item.as_view().finger_down(actions) {
                     if fd.tap_count == 1 {
@@ -1096,6 +1836,10 @@ impl ModelHubApp {
                             .and_then(|r| r.models.get(gi))
                             .map(|m| m.id.clone())
                         {
+                            if self.select_mode {
+                                self.toggle_bulk_selection(cx, &id);
+                                continue;
+                            }
                             self.selected_id = Some(id.clone());
                             self.on_model_selected(cx, &id);
                             self.view.redraw(cx);
@@ -1118,44 +1862,20 @@ impl ModelHubApp {
     fn handle_panel_header_buttons(&mut self, cx: &mut Cx, actions: &Actions) {
         let sel = match self.selected_id.clone() { Some(s) => s, None => return };
 
-        let (dl, cancel, rm) = match self.active_panel {
-            ActivePanel::Llm => (
-                self.view.button(ids!(hub_llm_panel.hub_panel_header.panel_download_btn)).clicked(actions),
-                self.view.button(ids!(hub_llm_panel.hub_panel_header.panel_cancel_btn)).clicked(actions),
-                self.view.button(ids!(hub_llm_panel.hub_panel_header.panel_remove_btn)).clicked(actions),
-            ),
-            ActivePanel::Vlm => (
-                self.view.button(ids!(hub_vlm_panel.hub_panel_header.panel_download_btn)).clicked(actions),
-                self.view.button(ids!(hub_vlm_panel.hub_panel_header.panel_cancel_btn)).clicked(actions),
-                self.view.button(ids!(hub_vlm_panel.hub_panel_header.panel_remove_btn)).clicked(actions),
-            ),
-            ActivePanel::Asr => (
-                self.view.button(ids!(hub_asr_panel.hub_panel_header.panel_download_btn)).clicked(actions),
-                self.view.button(ids!(hub_asr_panel.hub_panel_header.panel_cancel_btn)).clicked(actions),
-                self.view.button(ids!(hub_asr_panel.hub_panel_header.panel_remove_btn)).clicked(actions),
-            ),
-            ActivePanel::Tts => (
-                self.view.button(ids!(hub_tts_panel.hub_panel_header.panel_download_btn)).clicked(actions),
-                self.view.button(ids!(hub_tts_panel.hub_panel_header.panel_cancel_btn)).clicked(actions),
-                self.view.button(ids!(hub_tts_panel.hub_panel_header.panel_remove_btn)).clicked(actions),
-            ),
-            ActivePanel::Image => (
-                self.view.button(ids!(hub_image_panel.hub_panel_header.panel_download_btn)).clicked(actions),
-                self.view.button(ids!(hub_image_panel.hub_panel_header.panel_cancel_btn)).clicked(actions),
-                self.view.button(ids!(hub_image_panel.hub_panel_header.panel_remove_btn)).clicked(actions),
-            ),
-            ActivePanel::ImageEdit => (
-                self.view.button(ids!(hub_image_edit_panel.hub_panel_header.panel_download_btn)).clicked(actions),
-                self.view.button(ids!(hub_image_edit_panel.hub_panel_header.panel_cancel_btn)).clicked(actions),
-                self.view.button(ids!(hub_image_edit_panel.hub_panel_header.panel_remove_btn)).clicked(actions),
-            ),
-            ActivePanel::Video => (
-                self.view.button(ids!(hub_video_panel.hub_panel_header.panel_download_btn)).clicked(actions),
-                self.view.button(ids!(hub_video_panel.hub_panel_header.panel_cancel_btn)).clicked(actions),
-                self.view.button(ids!(hub_video_panel.hub_panel_header.panel_remove_btn)).clicked(actions),
-            ),
-            ActivePanel::Voice | ActivePanel::Info | ActivePanel::None => return,
-        };
+        if self.active_header().map_or(false, |h| h.button(ids!(panel_lan_btn)).clicked(actions)) {
+            self.start_lan_import(cx, &sel);
+            return;
+        }
+        if self.active_header().map_or(false, |h| h.button(ids!(panel_update_btn)).clicked(actions)) {
+            // Fetches only what the update changed
+            self.start_download(cx, &sel);
+            return;
+        }
+
+        let Some(header) = self.active_header() else { return };
+        let dl     = header.button(ids!(panel_download_btn)).clicked(actions);
+        let cancel = header.button(ids!(panel_cancel_btn)).clicked(actions);
+        let rm     = header.button(ids!(panel_remove_btn)).clicked(actions);
 
         if dl { self.start_download(cx, &sel); }
         if cancel {
@@ -1163,188 +1883,1812 @@ impl ModelHubApp {
                 ds.cancel_requested.store(true, Ordering::SeqCst);
             }
         }
-        if rm {
-            if self.pending_remove_id.as_deref() == Some(sel.as_str()) {
-                // Second click: confirmed — actually remove the model
-                self.pending_remove_id = None;
-                if let Some(model) = self.registry.as_ref()
-                    .and_then(|r| r.models.iter().find(|m| m.id == sel))
-                {
-                    let path = expand_tilde(&model.storage.local_path);
-                    if std::fs::remove_dir_all(&path).is_ok() {
-                        self.model_states.insert(sel.clone(), ModelUiState::NotDownloaded);
-                        self.load_states.remove(&sel);
-                        self.refresh_header_for(cx, &sel);
-                        self.view.redraw(cx);
-                        ::log::info!("Removed model {}", sel);
-                    }
-                }
+        if rm {
+            if self.pending_remove_id.as_deref() == Some(sel.as_str()) {
+                // Second click: confirmed — move the files aside, undoable for a few seconds
+                self.pending_remove_id = None;
+                self.remove_model(cx, &sel);
+            } else {
+                // First click: show confirmation
+                self.pending_remove_id = Some(sel.clone());
+                self.set_remove_btn_text(cx, "Are you sure?");
+                self.view.redraw(cx);
+            }
+        }
+    }
+
+    // ── Model removal with undo ──────────────────────────────────────────────
+
+    /// Stage a model's files for removal and show the undo toast
+    fn remove_model(&mut self, cx: &mut Cx, model_id: &str) {
+        // Only one removal can be undone at a time
+        self.commit_pending_removal(cx);
+        let Some(model) = self.registry.as_ref()
+            .and_then(|r| r.models.iter().find(|m| m.id == model_id))
+        else { return };
+        let path = expand_tilde(&model.storage.local_path);
+        let name = model.name.clone();
+        match model_trash::stage_removal(Path::new(&path)) {
+            Ok(pending) => {
+                model_state::set_download_status(cx, &path, DownloadStatus::NotDownloaded);
+                self.load_states.remove(model_id);
+                self.refresh_header_for(cx, model_id);
+                self.view.label(ids!(hub_undo_label)).set_text(cx, &format!("Removed {}", name));
+                self.view.view(ids!(hub_undo_bar)).set_visible(cx, true);
+                self.undo_timer = cx.start_timeout(model_trash::UNDO_WINDOW.as_secs_f64());
+                self.pending_removal = Some((model_id.to_string(), pending));
+                self.view.redraw(cx);
+                ::log::info!("Removed model {}", model_id);
+            }
+            Err(e) => ::log::error!("Failed to remove model {}: {}", model_id, e),
+        }
+    }
+
+    fn handle_undo_bar(&mut self, cx: &mut Cx, event: &Event, actions: &Actions) {
+        if self.undo_timer.is_event(event).is_some() {
+            self.commit_pending_removal(cx);
+        }
+        if self.view.button(ids!(hub_undo_btn)).clicked(actions) {
+            self.undo_removal(cx);
+        }
+    }
+
+    /// Put the pending removal's files back
+    fn undo_removal(&mut self, cx: &mut Cx) {
+        let Some((model_id, pending)) = self.pending_removal.take() else { return };
+        cx.stop_timer(self.undo_timer);
+        self.view.view(ids!(hub_undo_bar)).set_visible(cx, false);
+        if let Err(e) = pending.undo() {
+            ::log::error!("Failed to restore model {}: {}", model_id, e);
+            return;
+        }
+        if let Some(model) = self.registry.as_ref().and_then(|r| r.models.iter().find(|m| m.id == model_id)) {
+            let status = model_state::scan_registry_files(model);
+            model_state::set_download_status(cx, &pending.original.to_string_lossy(), status);
+        }
+        self.refresh_header_for(cx, &model_id);
+        self.view.redraw(cx);
+    }
+
+    /// Trash (or delete) the pending removal's files in the background
+    fn commit_pending_removal(&mut self, cx: &mut Cx) {
+        let Some((model_id, pending)) = self.pending_removal.take() else { return };
+        cx.stop_timer(self.undo_timer);
+        self.view.view(ids!(hub_undo_bar)).set_visible(cx, false);
+        self.view.redraw(cx);
+        let hard_delete = model_trash::hard_delete();
+        std::thread::spawn(move || {
+            if let Err(e) = pending.commit(hard_delete) {
+                ::log::error!("Failed to remove model {}: {}", model_id, e);
+            }
+        });
+    }
+
+    // ── Runtime log ──────────────────────────────────────────────────────────
+
+    fn handle_runtime_log(&mut self, cx: &mut Cx, event: &Event, actions: &Actions) {
+        if self.view.button(ids!(hub_runtime_log_btn)).clicked(actions) {
+            if self.active_panel == ActivePanel::RuntimeLog {
+                self.show_panel(cx, ActivePanel::None);
+            } else {
+                // The log file may have been changed in Settings since it was opened
+                let path = runtime_log::path();
+                if self.runtime_log.as_ref().map_or(true, |tail| *tail.path() != path) {
+                    self.runtime_log = Some(LogTail::new(path));
+                }
+                self.selected_id = None;
+                self.show_panel(cx, ActivePanel::RuntimeLog);
+                self.poll_runtime_log(cx);
+                self.update_runtime_log(cx);
+            }
+            self.view.redraw(cx);
+        }
+        if self.active_panel != ActivePanel::RuntimeLog { return; }
+
+        let mut changed = self.view.text_input(ids!(hub_runtime_log_panel.runtime_log_search)).changed(actions).is_some();
+        for (button, level) in runtime_log_level_buttons() {
+            if self.view.button(&[live_id!(hub_runtime_log_panel), button]).clicked(actions) {
+                self.runtime_log_level = level;
+                changed = true;
+            }
+        }
+        if self.view.button(ids!(hub_runtime_log_panel.runtime_log_clear_btn)).clicked(actions) {
+            if let Some(tail) = self.runtime_log.as_mut() {
+                tail.clear();
+            }
+            changed = true;
+        }
+        if self.peer_timer.is_event(event).is_some() && self.poll_runtime_log(cx) {
+            changed = true;
+        }
+        if changed {
+            self.update_runtime_log(cx);
+            self.view.redraw(cx);
+        }
+    }
+
+    /// Read new lines; whether there were any
+    fn poll_runtime_log(&mut self, cx: &mut Cx) -> bool {
+        let Some(tail) = self.runtime_log.as_mut() else { return false };
+        match tail.poll() {
+            Ok(added) => added,
+            Err(e) => {
+                let status = format!("Nothing to show yet ({}). The log is written when the app starts the runtime.", e);
+                self.view.label(ids!(hub_runtime_log_panel.runtime_log_status)).set_text(cx, &status);
+                self.view.redraw(cx);
+                false
+            }
+        }
+    }
+
+    /// Show the lines passing the level and search filters, newest first
+    fn update_runtime_log(&mut self, cx: &mut Cx) {
+        let Some(tail) = self.runtime_log.as_ref() else { return };
+        let query = self.view.text_input(ids!(hub_runtime_log_panel.runtime_log_search)).text();
+        let lines = runtime_log::filter(tail.lines(), self.runtime_log_level, &query);
+        let text = lines.iter().rev().take(RUNTIME_LOG_SHOWN).map(|l| l.text.as_str()).collect::<Vec<_>>().join("\n");
+        let status = match (tail.lines().len(), lines.len()) {
+            (0, _) => "No output yet. New lines appear here as the runtime writes them.".to_string(),
+            (total, shown) if shown > RUNTIME_LOG_SHOWN => format!("Newest {} of {} matching lines ({} read)", RUNTIME_LOG_SHOWN, shown, total),
+            (total, shown) => format!("{} of {} lines, newest first", shown, total),
+        };
+        self.view.label(ids!(hub_runtime_log_panel.runtime_log_path)).set_text(cx, &tail.path().display().to_string());
+        self.view.label(ids!(hub_runtime_log_panel.runtime_log_text)).set_text(cx, &text);
+        self.view.label(ids!(hub_runtime_log_panel.runtime_log_status)).set_text(cx, &status);
+        for (button, level) in runtime_log_level_buttons() {
+            let selected = if level == self.runtime_log_level { 1.0 } else { 0.0 };
+            self.view.button(&[live_id!(hub_runtime_log_panel), button]).apply_over(cx, live! {
+                draw_bg: { selected: (selected) }
+                draw_text: { selected: (selected) }
+            });
+        }
+    }
+
+    // ── Diagnostics ──────────────────────────────────────────────────────────
+
+    fn handle_diagnostics(&mut self, cx: &mut Cx, actions: &Actions) {
+        if self.view.button(ids!(hub_diagnostics_btn)).clicked(actions) {
+            if self.active_panel == ActivePanel::Diagnostics {
+                self.show_panel(cx, ActivePanel::None);
+            } else {
+                self.selected_id = None;
+                self.show_panel(cx, ActivePanel::Diagnostics);
+                self.start_diagnosis(cx);
+            }
+            self.view.redraw(cx);
+        }
+        if self.active_panel != ActivePanel::Diagnostics { return; }
+
+        if self.view.button(ids!(hub_diagnostics_panel.diagnostics_check_btn)).clicked(actions) {
+            self.start_diagnosis(cx);
+        }
+        let fixes = self.diagnosis.as_ref().map(|d| d.fixes()).unwrap_or_default();
+        for (slot, fix) in fixes.into_iter().enumerate().take(DIAGNOSTIC_FIXES) {
+            if self.diagnostics_fix(slot).button(ids!(fix_btn)).clicked(actions) {
+                self.apply_fix(cx, slot, fix);
+            }
+        }
+    }
+
+    fn diagnostics_fix(&self, slot: usize) -> WidgetRef {
+        match slot {
+            0 => self.view.widget(ids!(hub_diagnostics_panel.diagnostics_fix_0)),
+            1 => self.view.widget(ids!(hub_diagnostics_panel.diagnostics_fix_1)),
+            _ => self.view.widget(ids!(hub_diagnostics_panel.diagnostics_fix_2)),
+        }
+    }
+
+    /// Check the runtime's port in the background
+    fn start_diagnosis(&mut self, cx: &mut Cx) {
+        if self.diagnosis_rx.is_some() { return; }
+        let (tx, rx) = mpsc::channel();
+        self.diagnosis_rx = Some(rx);
+        self.fix_armed = None;
+        std::thread::spawn(move || {
+            let _ = tx.send(runtime_diagnostics::diagnose());
+        });
+        self.view.label(ids!(hub_diagnostics_panel.diagnostics_status)).set_text(cx, "Checking the runtime's port…");
+        self.view.redraw(cx);
+        cx.new_next_frame();
+    }
+
+    fn poll_diagnostics(&mut self, cx: &mut Cx) {
+        if let Some(rx) = &self.diagnosis_rx {
+            match rx.try_recv() {
+                Ok(diagnosis) => {
+                    self.diagnosis_rx = None;
+                    ::log::info!("Runtime port check: {}", diagnosis.summary());
+                    self.diagnosis = Some(diagnosis);
+                    self.view.label(ids!(hub_diagnostics_panel.diagnostics_status)).set_text(cx, "");
+                    self.show_diagnosis(cx);
+                }
+                Err(mpsc::TryRecvError::Empty) => cx.new_next_frame(),
+                Err(mpsc::TryRecvError::Disconnected) => self.diagnosis_rx = None,
+            }
+        }
+        let Some(rx) = &self.fix_rx else { return };
+        let result = match rx.try_recv() {
+            Ok(result) => result,
+            Err(mpsc::TryRecvError::Empty) => { cx.new_next_frame(); return; }
+            Err(mpsc::TryRecvError::Disconnected) => Err("The fix stopped unexpectedly.".to_string()),
+        };
+        self.fix_rx = None;
+        if let Err(e) = &result {
+            ::log::warn!("Runtime port fix failed: {}", e);
+        }
+        // Check again to show whether it worked, keeping what the fix reported
+        self.start_diagnosis(cx);
+        let status = result.unwrap_or_else(|e| e);
+        self.view.label(ids!(hub_diagnostics_panel.diagnostics_status)).set_text(cx, &status);
+    }
+
+    fn show_diagnosis(&mut self, cx: &mut Cx) {
+        let Some(diagnosis) = &self.diagnosis else { return };
+        self.view.label(ids!(hub_diagnostics_panel.diagnostics_url))
+            .set_text(cx, &format!("Runtime address: {}", diagnosis.url));
+        self.view.label(ids!(hub_diagnostics_panel.diagnostics_summary)).set_text(cx, &diagnosis.summary());
+        let fixes = diagnosis.fixes();
+        for slot in 0..DIAGNOSTIC_FIXES {
+            let row = self.diagnostics_fix(slot);
+            let Some(fix) = fixes.get(slot) else {
+                row.set_visible(cx, false);
+                continue;
+            };
+            let label = match fix {
+                Fix::StopProcess(listener) if self.fix_armed == Some(slot) => format!("Stop {}?", listener.name),
+                _ => fix.label(),
+            };
+            row.button(ids!(fix_btn)).set_text(cx, &label);
+            row.label(ids!(fix_text)).set_text(cx, &fix.description());
+            row.set_visible(cx, true);
+        }
+        self.view.redraw(cx);
+    }
+
+    fn apply_fix(&mut self, cx: &mut Cx, slot: usize, fix: Fix) {
+        if self.fix_rx.is_some() || self.diagnosis_rx.is_some() { return; }
+        let (tx, rx) = mpsc::channel();
+        match fix {
+            Fix::StartRuntime => {
+                std::thread::spawn(move || {
+                    let _ = tx.send(ensure_server_running().map(|()| "Runtime started.".to_string()));
+                });
+            }
+            Fix::StopProcess(listener) => {
+                // Stopping another program takes a second click
+                if self.fix_armed != Some(slot) {
+                    self.fix_armed = Some(slot);
+                    self.show_diagnosis(cx);
+                    return;
+                }
+                self.fix_armed = None;
+                std::thread::spawn(move || {
+                    let result = runtime_diagnostics::stop_process(listener.pid)
+                        .and_then(|()| ensure_server_running())
+                        .map(|()| format!("Stopped {} and started the runtime.", listener.name));
+                    let _ = tx.send(result);
+                });
+            }
+            Fix::ChangePort(port) => {
+                let setting = runtime_diagnostics::port_setting(port);
+                cx.copy_to_clipboard(&setting);
+                self.view.label(ids!(hub_diagnostics_panel.diagnostics_status))
+                    .set_text(cx, &format!("Copied {}", setting));
+                self.view.redraw(cx);
+                return;
+            }
+        }
+        self.fix_rx = Some(rx);
+        self.view.label(ids!(hub_diagnostics_panel.diagnostics_status)).set_text(cx, "Working…");
+        self.view.redraw(cx);
+        cx.new_next_frame();
+    }
+
+    // ── Ollama ───────────────────────────────────────────────────────────────
+
+    fn handle_ollama(&mut self, cx: &mut Cx, actions: &Actions, scope: &mut Scope) {
+        if self.view.button(ids!(hub_ollama_btn)).clicked(actions) {
+            if self.active_panel == ActivePanel::Ollama {
+                self.show_panel(cx, ActivePanel::None);
+            } else {
+                let provider = scope.data.get::<Store>()
+                    .and_then(|store| store.preferences.get_provider(&"ollama".to_string()).cloned());
+                self.ollama_url = ollama_client::ollama_base_url(provider.as_ref().map_or("", |p| p.url.as_str()));
+                self.ollama_ready = provider.is_some_and(|p| p.is_ready());
+                self.selected_id = None;
+                self.show_panel(cx, ActivePanel::Ollama);
+                self.start_ollama_list(cx);
+            }
+            self.view.redraw(cx);
+        }
+        if self.active_panel != ActivePanel::Ollama { return; }
+
+        if self.view.button(ids!(hub_ollama_panel.ollama_refresh_btn)).clicked(actions) {
+            self.start_ollama_list(cx);
+        }
+        if self.view.button(ids!(hub_ollama_panel.ollama_pull_btn)).clicked(actions) {
+            self.start_ollama_pull(cx);
+        }
+        if self.view.button(ids!(hub_ollama_panel.ollama_cancel_btn)).clicked(actions) {
+            if let Some(cancel) = &self.ollama_pull_cancel {
+                cancel.store(true, Ordering::SeqCst);
+            }
+        }
+    }
+
+    /// List the daemon's models in the background
+    fn start_ollama_list(&mut self, cx: &mut Cx) {
+        if self.ollama_list_rx.is_some() { return; }
+        let (tx, rx) = mpsc::channel();
+        self.ollama_list_rx = Some(rx);
+        let url = self.ollama_url.clone();
+        std::thread::spawn(move || {
+            let _ = tx.send(moly_data::list_ollama_models(&url));
+        });
+        let mut address = format!("Daemon: {}", self.ollama_url);
+        if !self.ollama_ready {
+            address.push_str(" · Turn on Ollama in Settings → Providers to chat with these models");
+        }
+        self.view.label(ids!(hub_ollama_panel.ollama_url)).set_text(cx, &address);
+        self.view.label(ids!(hub_ollama_panel.ollama_summary)).set_text(cx, "Listing models…");
+        self.view.redraw(cx);
+        cx.new_next_frame();
+    }
+
+    fn start_ollama_pull(&mut self, cx: &mut Cx) {
+        if self.ollama_pull_rx.is_some() { return; }
+        let status = self.view.label(ids!(hub_ollama_panel.ollama_status));
+        let model = self.view.text_input(ids!(hub_ollama_panel.ollama_pull_input)).text().trim().to_string();
+        if model.is_empty() {
+            status.set_text(cx, "Enter a model name, e.g. llama3.2:3b");
+            return;
+        }
+        let cancel = Arc::new(AtomicBool::new(false));
+        self.ollama_pull_cancel = Some(cancel.clone());
+        let (tx, rx) = mpsc::channel();
+        self.ollama_pull_rx = Some(rx);
+        let url = self.ollama_url.clone();
+        ::log::info!("Pulling Ollama model {} from {}", model, url);
+        std::thread::spawn(move || {
+            let progress_tx = tx.clone();
+            let result = moly_data::pull_ollama_model(&url, &model, &cancel, |p| {
+                let _ = progress_tx.send(OllamaPullUpdate::Progress(p));
+            });
+            let _ = tx.send(OllamaPullUpdate::Finished(result));
+        });
+        status.set_text(cx, "Starting pull…");
+        self.view.button(ids!(hub_ollama_panel.ollama_cancel_btn)).set_visible(cx, true);
+        self.view.redraw(cx);
+        cx.new_next_frame();
+    }
+
+    fn poll_ollama(&mut self, cx: &mut Cx) {
+        if let Some(rx) = &self.ollama_list_rx {
+            match rx.try_recv() {
+                Ok(result) => {
+                    self.ollama_list_rx = None;
+                    let summary = match result {
+                        Ok(models) => {
+                            let summary = ollama_client::installed_summary(&models);
+                            self.ollama_models = models;
+                            summary
+                        }
+                        Err(e) => {
+                            self.ollama_models.clear();
+                            e
+                        }
+                    };
+                    self.view.label(ids!(hub_ollama_panel.ollama_summary)).set_text(cx, &summary);
+                    self.view.redraw(cx);
+                }
+                Err(mpsc::TryRecvError::Empty) => cx.new_next_frame(),
+                Err(mpsc::TryRecvError::Disconnected) => self.ollama_list_rx = None,
+            }
+        }
+
+        let Some(rx) = &self.ollama_pull_rx else { return };
+        let mut latest: Option<OllamaPullProgress> = None;
+        let finished = loop {
+            match rx.try_recv() {
+                Ok(OllamaPullUpdate::Progress(p)) => latest = Some(p),
+                Ok(OllamaPullUpdate::Finished(result)) => break Some(result),
+                Err(mpsc::TryRecvError::Empty) => break None,
+                Err(mpsc::TryRecvError::Disconnected) => break Some(Err("The pull stopped unexpectedly.".to_string())),
+            }
+        };
+        let status = self.view.label(ids!(hub_ollama_panel.ollama_status));
+        if let Some(p) = latest {
+            let text = if p.total > 0 {
+                format!("{} — {:.0}% of {}", p.status, p.fraction() * 100.0, disk_space::format_size(p.total))
+            } else {
+                p.status.clone()
+            };
+            status.set_text(cx, &text);
+        }
+        let Some(result) = finished else {
+            cx.new_next_frame();
+            return;
+        };
+        let cancelled = self.ollama_pull_cancel.take().is_some_and(|c| c.load(Ordering::SeqCst));
+        self.ollama_pull_rx = None;
+        self.view.button(ids!(hub_ollama_panel.ollama_cancel_btn)).set_visible(cx, false);
+        let msg = match result {
+            Ok(()) if cancelled => "Pull cancelled.".to_string(),
+            Ok(()) => {
+                self.start_ollama_list(cx);
+                "Pull complete.".to_string()
+            }
+            Err(e) => format!("Pull failed: {}", e),
+        };
+        status.set_text(cx, &msg);
+        self.view.redraw(cx);
+    }
+
+    fn draw_ollama_model_list(&mut self, cx: &mut Cx2d, scope: &mut Scope, widget: WidgetRef) {
+        let binding = widget.as_portal_list();
+        let Some(mut list) = binding.borrow_mut() else { return };
+        list.set_item_range(cx, 0, self.ollama_models.len());
+
+        while let Some(item_id) = list.next_visible_item(cx) {
+            if let Some(model) = self.ollama_models.get(item_id) {
+                let size = model.size_bytes.map(disk_space::format_size).unwrap_or_default();
+                let item = list.item(cx, item_id, live_id!(HubOllamaModelItem));
+                item.label(ids!(model_name)).set_text(cx, &model.id);
+                item.label(ids!(model_size)).set_text(cx, &size);
+                item.draw_all(cx, scope);
+            }
+        }
+    }
+
+    // ── Bulk actions ─────────────────────────────────────────────────────────
+
+    fn handle_bulk_actions(&mut self, cx: &mut Cx, actions: &Actions) {
+        if self.view.button(ids!(hub_select_btn)).clicked(actions) {
+            self.select_mode = !self.select_mode;
+            self.bulk_selection.clear();
+            self.disarm_bulk_remove(cx);
+            self.view.button(ids!(hub_select_btn)).set_text(cx, if self.select_mode { "Done" } else { "Select" });
+            self.view.view(ids!(hub_bulk_bar)).set_visible(cx, self.select_mode);
+            if !self.select_mode && self.active_panel == ActivePanel::Planner {
+                self.show_panel(cx, ActivePanel::None);
+            }
+            self.update_bulk_count(cx);
+            self.view.redraw(cx);
+        }
+        if !self.select_mode { return; }
+        if self.view.button(ids!(hub_bulk_download_btn)).clicked(actions) {
+            self.disarm_bulk_remove(cx);
+            self.bulk_download(cx);
+        }
+        if self.view.button(ids!(hub_bulk_verify_btn)).clicked(actions) {
+            self.disarm_bulk_remove(cx);
+            self.bulk_verify(cx);
+        }
+        if self.view.button(ids!(hub_bulk_remove_btn)).clicked(actions) {
+            self.bulk_remove(cx);
+        }
+        if self.view.button(ids!(hub_bulk_plan_btn)).clicked(actions) {
+            self.disarm_bulk_remove(cx);
+            self.selected_id = None;
+            self.show_panel(cx, ActivePanel::Planner);
+            self.update_planner(cx);
+            self.view.redraw(cx);
+        }
+    }
+
+    fn toggle_bulk_selection(&mut self, cx: &mut Cx, model_id: &str) {
+        if !self.bulk_selection.remove(model_id) {
+            self.bulk_selection.insert(model_id.to_string());
+        }
+        self.disarm_bulk_remove(cx);
+        self.update_bulk_count(cx);
+        if self.active_panel == ActivePanel::Planner {
+            self.update_planner(cx);
+        }
+        self.view.redraw(cx);
+    }
+
+    fn update_bulk_count(&mut self, cx: &mut Cx) {
+        let text = match (self.select_mode, self.bulk_selection.len()) {
+            (false, _) => String::new(),
+            (true, 0) => "Tap models to select them".to_string(),
+            (true, n) => format!("{} selected", n),
+        };
+        self.view.label(ids!(hub_bulk_count)).set_text(cx, &text);
+    }
+
+    fn disarm_bulk_remove(&mut self, cx: &mut Cx) {
+        if std::mem::take(&mut self.bulk_remove_armed) {
+            self.view.button(ids!(hub_bulk_remove_btn)).set_text(cx, "Remove");
+        }
+    }
+
+    /// Show whether the checked models fit in memory together
+    fn update_planner(&mut self, cx: &mut Cx) {
+        let models = self.bulk_models();
+        let plan = memory_planner::plan(&models, platform::total_memory());
+        let warnings = if models.is_empty() { Vec::new() } else { plan.warnings() };
+        let summary = match (models.is_empty(), plan.verdict) {
+            (true, _) => "No models checked".to_string(),
+            (false, memory_planner::Verdict::Fits) if warnings.is_empty() => format!("{} — fits", plan.summary()),
+            (false, _) => plan.summary(),
+        };
+        let used = plan.system_gb.map_or(0.0, |system| plan.required_gb / system);
+        let lines: Vec<String> = models.iter()
+            .map(|m| {
+                let loaded = self.load_states.get(&m.id).copied() == Some(ModelLoadState::Loaded);
+                format!("• {} ({}) — {:.1} GB{}", m.name, m.category.label(), m.runtime.memory_gb,
+                    if loaded { ", loaded" } else { "" })
+            })
+            .collect();
+        // Loaded models outside the plan take memory too
+        let others: Vec<String> = self.registry.as_ref()
+            .map(|r| r.models.iter()
+                .filter(|m| !self.bulk_selection.contains(&m.id))
+                .filter(|m| self.load_states.get(&m.id).copied() == Some(ModelLoadState::Loaded))
+                .map(|m| format!("{} ({:.1} GB)", m.name, m.runtime.memory_gb))
+                .collect())
+            .unwrap_or_default();
+        let mut text = lines.join("\n");
+        if !others.is_empty() {
+            text.push_str(&format!("\n\nAlso loaded now, not in the plan: {}. Unload them first, or check them to count them.",
+                others.join(", ")));
+        }
+        self.view.label(ids!(hub_planner_panel.planner_summary)).set_text(cx, &summary);
+        self.view.label(ids!(hub_planner_panel.planner_models)).set_text(cx, &text);
+        self.view.label(ids!(hub_planner_panel.planner_warnings)).set_text(cx, &warnings.join("\n"));
+        let usable = memory_planner::USABLE_FRACTION;
+        self.view.view(ids!(hub_planner_panel.planner_bar))
+            .apply_over(cx, live! { draw_bg: { used: (used), usable: (usable) } });
+    }
+
+    /// The checked models, in list order
+    fn bulk_models(&self) -> Vec<RegistryModel> {
+        self.registry.as_ref()
+            .map(|r| r.models.iter().filter(|m| self.bulk_selection.contains(&m.id)).cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Queue the checked models that aren't on disk yet; the simultaneous
+    /// download limit decides how many run at once
+    fn bulk_download(&mut self, cx: &mut Cx) {
+        let mut started = Vec::new();
+        let mut skipped = 0;
+        for model in self.bulk_models() {
+            let on_disk = self.dl_state(&model.id) == ModelUiState::Downloaded;
+            if on_disk || model.source.kind == SourceKind::Manual || self.download_states.contains_key(&model.id) {
+                skipped += 1;
+                continue;
+            }
+            self.start_download(cx, &model.id);
+            if self.download_states.contains_key(&model.id) {
+                started.push(model.id);
+            } else {
+                skipped += 1;
+            }
+        }
+        let status = if started.is_empty() {
+            "Nothing to download: the selected models are downloaded, downloading or can't be downloaded.".to_string()
+        } else {
+            self.bulk_downloads = started;
+            let mut status = self.bulk_download_summary();
+            if skipped > 0 {
+                status.push_str(&format!(" ({} skipped)", skipped));
+            }
+            status
+        };
+        self.view.label(ids!(hub_bulk_status)).set_text(cx, &status);
+        self.view.redraw(cx);
+    }
+
+    /// See [`download_settings::BulkProgress::summary`]
+    fn bulk_download_summary(&self) -> String {
+        let mut progress = download_settings::BulkProgress { count: self.bulk_downloads.len(), ..Default::default() };
+        for id in &self.bulk_downloads {
+            if let Some(ds) = self.download_states.get(id) {
+                if ds.queued.load(Ordering::SeqCst) {
+                    progress.waiting += 1;
+                } else {
+                    progress.active += 1;
+                    progress.bytes += ds.progress_bytes.load(Ordering::SeqCst);
+                    progress.total += ds.total_bytes.load(Ordering::SeqCst);
+                    progress.speed += ds.speed.load(Ordering::SeqCst);
+                }
+                continue;
+            }
+            match self.dl_state(id) {
+                ModelUiState::Downloaded => progress.done += 1,
+                _ => progress.failed += 1,
+            }
+        }
+        progress.summary()
+    }
+
+    /// Check the files of the checked models in the background
+    fn bulk_verify(&mut self, cx: &mut Cx) {
+        if self.bulk_verify_rx.is_some() { return; }
+        let models: Vec<RegistryModel> = self.bulk_models().into_iter()
+            .filter(|m| !self.download_states.contains_key(&m.id) && self.dl_state(&m.id) != ModelUiState::NotDownloaded)
+            .collect();
+        if models.is_empty() {
+            self.view.label(ids!(hub_bulk_status)).set_text(cx, "None of the selected models has files to verify.");
+            return;
+        }
+        self.view.label(ids!(hub_bulk_status)).set_text(cx, &format!("Verifying {} model(s)…", models.len()));
+        let (tx, rx) = mpsc::channel();
+        self.bulk_verify_rx = Some(rx);
+        std::thread::spawn(move || {
+            let results = models.iter()
+                .map(|m| {
+                    let complete = !matches!(model_integrity::check_registry_model(m), Integrity::Incomplete { .. });
+                    (m.id.clone(), model_state::scan_registry_files(m), complete)
+                })
+                .collect();
+            let _ = tx.send(results);
+        });
+        cx.new_next_frame();
+    }
+
+    fn poll_bulk_verify(&mut self, cx: &mut Cx) {
+        let Some(rx) = &self.bulk_verify_rx else { return };
+        let results = match rx.try_recv() {
+            Ok(results) => results,
+            Err(mpsc::TryRecvError::Empty) => { cx.new_next_frame(); return; }
+            Err(mpsc::TryRecvError::Disconnected) => Vec::new(),
+        };
+        self.bulk_verify_rx = None;
+        let mut incomplete = Vec::new();
+        for (id, status, complete) in &results {
+            if let Some(key) = self.storage_key(id) {
+                model_state::set_download_status(cx, &key, *status);
+            }
+            if !complete || *status == DownloadStatus::Partial {
+                let name = self.registry.as_ref().and_then(|r| r.get(id)).map_or(id.clone(), |m| m.name.clone());
+                incomplete.push(name);
+            }
+        }
+        let status = if incomplete.is_empty() {
+            format!("Verified {} model(s): all complete.", results.len())
+        } else {
+            format!("Verified {} model(s): {} incomplete ({}). Download them again to fetch the missing files.",
+                results.len(), incomplete.len(), incomplete.join(", "))
+        };
+        self.view.label(ids!(hub_bulk_status)).set_text(cx, &status);
+        if let Some(sel) = self.selected_id.clone() {
+            self.refresh_header_for(cx, &sel);
+        }
+        self.view.redraw(cx);
+    }
+
+    /// Remove the checked models' files after a second click. Unlike a single
+    /// removal there is no undo; files go to the trash unless hard delete is on.
+    fn bulk_remove(&mut self, cx: &mut Cx) {
+        let models: Vec<RegistryModel> = self.bulk_models().into_iter()
+            .filter(|m| !self.download_states.contains_key(&m.id) && self.dl_state(&m.id) != ModelUiState::NotDownloaded)
+            .filter(|m| m.source.kind != SourceKind::Manual)
+            .collect();
+        if models.is_empty() {
+            self.view.label(ids!(hub_bulk_status)).set_text(cx, "None of the selected models has files to remove.");
+            return;
+        }
+        if !self.bulk_remove_armed {
+            self.bulk_remove_armed = true;
+            self.view.button(ids!(hub_bulk_remove_btn)).set_text(cx, &format!("Remove {}?", models.len()));
+            self.view.redraw(cx);
+            return;
+        }
+        self.disarm_bulk_remove(cx);
+        self.commit_pending_removal(cx);
+
+        let mut staged = Vec::new();
+        let mut failed = 0;
+        for model in &models {
+            let path = expand_tilde(&model.storage.local_path);
+            match model_trash::stage_removal(Path::new(&path)) {
+                Ok(pending) => {
+                    model_state::set_download_status(cx, &path, DownloadStatus::NotDownloaded);
+                    self.load_states.remove(&model.id);
+                    staged.push((model.id.clone(), pending));
+                }
+                Err(e) => {
+                    ::log::error!("Failed to remove model {}: {}", model.id, e);
+                    failed += 1;
+                }
+            }
+        }
+        let removed = staged.len();
+        let hard_delete = model_trash::hard_delete();
+        std::thread::spawn(move || {
+            for (model_id, pending) in staged {
+                if let Err(e) = pending.commit(hard_delete) {
+                    ::log::error!("Failed to remove model {}: {}", model_id, e);
+                }
+            }
+        });
+        let status = match failed {
+            0 => format!("Removed {} model(s).", removed),
+            _ => format!("Removed {} model(s); {} couldn't be removed, see the log.", removed, failed),
+        };
+        self.view.label(ids!(hub_bulk_status)).set_text(cx, &status);
+        if let Some(sel) = self.selected_id.clone() {
+            self.refresh_header_for(cx, &sel);
+        }
+        self.view.redraw(cx);
+    }
+
+    /// Header of the active model panel
+    fn active_header(&self) -> Option<HubPanelHeaderRef> {
+        let (_, panel_id) = header_panels().into_iter().find(|(panel, _)| *panel == self.active_panel)?;
+        Some(self.view.hub_panel_header(&[panel_id, live_id!(hub_panel_header)]))
+    }
+
+    // ── LAN import ───────────────────────────────────────────────────────────
+
+    /// Check the repos of downloaded models for newer versions every few
+    /// hours, and badge the models that have one
+    fn handle_update_check(&mut self, cx: &mut Cx, event: &Event) {
+        if self.peer_timer.is_event(event).is_none() { return; }
+        if let Some(rx) = &self.update_check_rx {
+            let Ok(results) = rx.try_recv() else { return };
+            self.update_check_rx = None;
+            for (model_id, update) in results {
+                match update {
+                    Some(update) => { self.model_updates.insert(model_id, update); }
+                    None => { self.model_updates.remove(&model_id); }
+                }
+            }
+            self.view.redraw(cx);
+            if let Some(sel) = self.selected_id.clone() {
+                self.refresh_header_for(cx, &sel);
+            }
+            return;
+        }
+        if !model_updates::check_due(self.last_update_check) { return; }
+        self.last_update_check = Some(std::time::Instant::now());
+
+        let models: Vec<RegistryModel> = self.registry.as_ref()
+            .map(|r| r.models.iter()
+                .filter(|m| matches!(m.source.kind, SourceKind::HuggingFace | SourceKind::ModelScope))
+                .filter(|m| match self.filter { Filter::All => true, Filter::Cat(cat) => m.category == cat })
+                .filter(|m| self.dl_state(&m.id) == ModelUiState::Downloaded)
+                .cloned()
+                .collect())
+            .unwrap_or_default();
+        if models.is_empty() { return; }
+        let (tx, rx) = mpsc::channel();
+        self.update_check_rx = Some(rx);
+        std::thread::spawn(move || {
+            let _work = shutdown::track(format!("Checking {} models for updates", models.len()), None);
+            let _ = tx.send(check_model_updates(&models));
+        });
+    }
+
+    /// Refresh the header when peers on the network change
+    fn handle_lan_peers(&mut self, cx: &mut Cx, event: &Event) {
+        if self.peer_timer.is_event(event).is_none() { return; }
+        let generation = lan_share::peers_generation();
+        if generation == self.peers_seen { return; }
+        self.peers_seen = generation;
+        if let Some(sel) = self.selected_id.clone() {
+            self.refresh_header_for(cx, &sel);
+        }
+    }
+
+    /// Copy a model from a peer, tracked like a download
+    fn start_lan_import(&mut self, cx: &mut Cx, model_id: &str) {
+        let Some(peer) = lan_share::peer_with(model_id) else { return };
+        let Some(model) = self.registry.as_ref()
+            .and_then(|r| r.models.iter().find(|m| m.id == model_id)).cloned()
+        else { return };
+        if !self.check_disk_space(cx, &model) { return; }
+
+        let ds = self.download_states
+            .entry(model_id.to_string()).or_insert_with(ModelDownloadState::new).clone();
+        ds.reset();
+        ds.is_downloading.store(true, Ordering::SeqCst);
+        self.model_errors.remove(model_id);
+
+        model_state::set_download_status(cx, &model.storage.expanded_path(), DownloadStatus::Downloading);
+        self.refresh_header_for(cx, model_id);
+        cx.new_next_frame();
+
+        let model_id_owned = model_id.to_string();
+        let dest = PathBuf::from(expand_tilde(&model.storage.local_path));
+        ::log::info!("Importing {} from {} ({})", model_id, peer.name, peer.addr);
+        let work = format!("Importing {} from {}", model.name, peer.name);
+        std::thread::spawn(move || {
+            let _work = shutdown::track(work, Some(ds.cancel_requested.clone()));
+            let progress = lan_share::TransferProgress {
+                cancel:       &ds.cancel_requested,
+                total_bytes:  &ds.total_bytes,
+                done_bytes:   &ds.progress_bytes,
+                current_file: &ds.current_file,
+            };
+            match lan_share::import_model(peer.addr, &model_id_owned, &dest, &progress) {
+                Ok(()) => ds.completed.store(true, Ordering::SeqCst),
+                Err(e) => {
+                    *ds.error_msg.lock().unwrap() = e;
+                    ds.failed.store(true, Ordering::SeqCst);
+                }
+            }
+            ds.is_downloading.store(false, Ordering::SeqCst);
+        });
+    }
+
+    /// Set the remove button text on the active panel
+    fn set_remove_btn_text(&mut self, cx: &mut Cx, text: &str) {
+        if let Some(header) = self.active_header() {
+            header.button(ids!(panel_remove_btn)).set_text(cx, text);
+        }
+    }
+
+    /// Reset the remove confirmation state (e.g. when switching models)
+    fn reset_remove_confirmation(&mut self, cx: &mut Cx) {
+        if self.pending_remove_id.take().is_some() {
+            self.set_remove_btn_text(cx, "Remove");
+        }
+    }
+
+    /// Handle Load / Unload buttons in the active panel header.
+    fn handle_load_buttons(&mut self, cx: &mut Cx, actions: &Actions) {
+        let sel = match self.selected_id.clone() { Some(s) => s, None => return };
+
+        let Some(header) = self.active_header() else { return };
+        let load_clicked   = header.button(ids!(panel_load_btn)).clicked(actions);
+        let unload_clicked = header.button(ids!(panel_unload_btn)).clicked(actions);
+
+        if load_clicked   { self.start_load(cx, &sel); }
+        if unload_clicked { self.start_unload(cx, &sel); }
+    }
+
+    /// Handle "Open in Chat", "Open Transcriber" and "Open Speaker" — dispatch
+    /// OpenChatWithModel, OpenTranscriber or OpenSpeaker to open a fresh session.
+    fn handle_chat_button(&mut self, cx: &mut Cx, actions: &Actions, _scope: &mut Scope) {
+        // Only shown for categories with somewhere to open the model
+        if !self.active_header().is_some_and(|h| h.button(ids!(panel_chat_btn)).clicked(actions)) { return; }
+
+        let sel = match self.selected_id.clone() { Some(s) => s, None => return };
+
+        let model = self.registry.as_ref()
+            .and_then(|r| r.models.iter().find(|m| m.id == sel))
+            .cloned();
+        let Some(model) = model else { return };
+
+        let api_model_id = model.runtime.api_model_id.clone();
+        let category = model.category;
+
+        let action = match category {
+            RegistryCategory::Asr => StoreAction::OpenTranscriber { model_id: api_model_id },
+            RegistryCategory::Tts => StoreAction::OpenSpeaker { model_id: api_model_id },
+            _ => StoreAction::OpenChatWithModel { model_id: api_model_id, category },
+        };
+        cx.action(action);
+    }
+
+    /// Handle "Preload on startup" — mark or unmark the selected model so the
+    /// shell loads it when the app starts.
+    fn handle_preload_button(&mut self, cx: &mut Cx, actions: &Actions, scope: &mut Scope) {
+        if !self.active_header().map_or(false, |h| h.button(ids!(panel_preload_btn)).clicked(actions)) { return; }
+        let Some(sel) = self.selected_id.clone() else { return };
+        let Some(store) = scope.data.get_mut::<Store>() else { return };
+        store.preferences.toggle_preload_model(&sel);
+        self.preload_models = store.preferences.preload_models.clone();
+        self.refresh_header_for(cx, &sel);
+    }
+
+    /// Handle the Advanced section: open or close it, and save or reset the
+    /// selected model's runtime options.
+    fn handle_runtime_options(&mut self, cx: &mut Cx, actions: &Actions) {
+        let Some(header) = self.active_header() else { return };
+        let Some(sel) = self.selected_id.clone() else { return };
+        if header.button(ids!(panel_options_btn)).clicked(actions) {
+            self.options_open = !self.options_open;
+            self.refresh_header_for(cx, &sel);
+        }
+
+        let options = if header.button(ids!(panel_options_save_btn)).clicked(actions) {
+            RuntimeOptions::parse(
+                &header.text_input(ids!(panel_ctx_input)).text(),
+                &header.text_input(ids!(panel_gpu_layers_input)).text(),
+                &header.text_input(ids!(panel_threads_input)).text(),
+            )
+        } else if header.button(ids!(panel_options_reset_btn)).clicked(actions) {
+            Ok(RuntimeOptions::default())
+        } else {
+            return;
+        };
+        let hint = match options.and_then(|options| self.runtime_options.set(&sel, options)) {
+            Ok(()) => {
+                self.fill_runtime_options(cx, &sel);
+                if self.load_states.get(&sel).copied() == Some(ModelLoadState::Loaded) {
+                    "Saved. Unload the model and load it again to use them.".to_string()
+                } else {
+                    "Saved. Used every time this model is loaded.".to_string()
+                }
+            }
+            Err(e) => e,
+        };
+        header.label(ids!(panel_options_hint)).set_text(cx, &hint);
+        header.redraw(cx);
+    }
+
+    /// Show a model's saved runtime options in the Advanced section
+    fn fill_runtime_options(&mut self, cx: &mut Cx, model_id: &str) {
+        let Some(header) = self.active_header() else { return };
+        let options = self.runtime_options.get(model_id);
+        let text = |value: Option<u32>| value.map(|v| v.to_string()).unwrap_or_default();
+        header.text_input(ids!(panel_ctx_input)).set_text(cx, &text(options.context_length));
+        header.text_input(ids!(panel_gpu_layers_input)).set_text(cx, &text(options.gpu_layers));
+        header.text_input(ids!(panel_threads_input)).set_text(cx, &text(options.threads));
+        header.label(ids!(panel_options_hint))
+            .set_text(cx, "Used every time this model is loaded. Empty fields keep the runtime's default.");
+        header.redraw(cx);
+    }
+
+    fn handle_input_changes(&mut self, actions: &Actions) {
+        if let Some(t) = self.view.text_input(ids!(hub_llm_panel.llm_system)).changed(actions)       { self.llm_state.system = t.to_string(); }
+        if let Some(t) = self.view.text_input(ids!(hub_llm_panel.llm_user)).changed(actions)         { self.llm_state.user = t.to_string(); }
+        if let Some(t) = self.view.text_input(ids!(hub_vlm_panel.vlm_image_path)).changed(actions)   { self.vlm_state.image_path = t.to_string(); }
+        if let Some(t) = self.view.text_input(ids!(hub_vlm_panel.vlm_user)).changed(actions)         { self.vlm_state.user = t.to_string(); }
+        if let Some(t) = self.view.text_input(ids!(hub_asr_panel.asr_audio_path)).changed(actions)   { self.asr_state.audio_path = t.to_string(); }
+        if let Some(t) = self.view.text_input(ids!(hub_tts_panel.tts_text_input)).changed(actions)   { self.tts_state.text = t.to_string(); }
+        if let Some(t) = self.view.text_input(ids!(hub_image_panel.img_prompt)).changed(actions)     { self.image_state.prompt = t.to_string(); self.forget_prompt_enhancement(cx); }
+        if let Some(t) = self.view.text_input(ids!(hub_image_panel.img_neg_prompt)).changed(actions) { self.image_state.neg_prompt = t.to_string(); }
+        if let Some(t) = self.view.text_input(ids!(hub_image_panel.img_seed)).changed(actions)       { self.image_state.seed = t.to_string(); }
+        if let Some(t) = self.view.text_input(ids!(hub_image_panel.img_init_path)).changed(actions)  { self.image_state.init_path = t.to_string(); }
+        if let Some(t) = self.view.text_input(ids!(hub_image_panel.img_mask_path)).changed(actions)  { self.image_state.mask_path = t.to_string(); }
+        if let Some(t) = self.view.text_input(ids!(hub_image_panel.img_strength)).changed(actions)   { self.image_state.strength = t.to_string(); }
+        if let Some(t) = self.view.text_input(ids!(hub_image_edit_panel.img_edit_image_path)).changed(actions) { self.image_edit_state.image_path = t.to_string(); }
+        if let Some(t) = self.view.text_input(ids!(hub_image_edit_panel.img_edit_prompt)).changed(actions)     { self.image_edit_state.prompt = t.to_string(); }
+        if let Some(t) = self.view.text_input(ids!(hub_video_panel.vid_prompt)).changed(actions)               { self.video_state.prompt = t.to_string(); }
+        if let Some(t) = self.view.text_input(ids!(hub_music_panel.mus_prompt)).changed(actions)               { self.music_state.prompt = t.to_string(); }
+        if let Some(t) = self.view.text_input(ids!(hub_music_panel.mus_duration)).changed(actions)             { self.music_state.duration = t.to_string(); }
+        if let Some(t) = self.view.text_input(ids!(hub_music_panel.mus_seed)).changed(actions)                 { self.music_state.seed = t.to_string(); }
+        if let Some(t) = self.view.text_input(ids!(hub_rerank_panel.rerank_query)).changed(actions)            { self.rerank_state.query = t.to_string(); }
+        if let Some(t) = self.view.text_input(ids!(hub_rerank_panel.rerank_passages)).changed(actions)         { self.rerank_state.passages = t.to_string(); }
+    }
+
+    fn handle_llm_actions(&mut self, cx: &mut Cx, actions: &Actions) {
+        if self.view.button(ids!(hub_llm_panel.llm_generate_btn)).clicked(actions) {
+            if let Some(sel) = self.selected_id.clone() {
+                let system = self.llm_state.system.clone();
+                let user   = self.llm_state.user.clone();
+                self.call_llm(cx, sel, system, user);
+            }
+        }
+
+        for (button, template) in llm_template_buttons() {
+            if !self.view.button(&[live_id!(hub_llm_panel), button]).clicked(actions) { continue }
+            let Some(model) = self.selected_id.as_ref()
+                .and_then(|id| self.registry.as_ref()?.models.iter().find(|m| &m.id == id))
+                .cloned() else { continue };
+            if let Err(e) = self.chat_templates.set(&model.id, template) {
+                self.view.label(ids!(hub_llm_panel.llm_template_hint)).set_text(cx, &format!("Couldn't save: {}", e));
+                continue;
+            }
+            self.update_template_row(cx, &model);
+        }
+    }
+
+    fn handle_llm_eval_actions(&mut self, cx: &mut Cx, actions: &Actions) {
+        if self.view.button(ids!(hub_llm_panel.llm_eval_file_btn)).clicked(actions) && !self.llm_eval.is_running {
+            if let Some(path) = FileDialog::new().add_filter("JSON Lines", &["jsonl", "json"]).pick_file() {
+                match eval_set::load(&path) {
+                    Ok(cases) => {
+                        self.llm_eval.results = vec![None; cases.len()];
+                        self.llm_eval.cases = cases;
+                        self.llm_eval.source = Some(path);
+                        self.update_llm_eval_summary(cx);
+                    }
+                    Err(e) => {
+                        self.view.label(ids!(hub_llm_panel.llm_eval_summary)).set_text(cx, &format!("Error: {}", e));
+                    }
+                }
+            }
+        }
+        if self.view.button(ids!(hub_llm_panel.llm_eval_start_btn)).clicked(actions) {
+            if let Some(sel) = self.selected_id.clone() {
+                let load = self.load_states.get(&sel).copied().unwrap_or_default();
+                if load != ModelLoadState::Loaded {
+                    self.view.label(ids!(hub_llm_panel.llm_eval_summary)).set_text(cx, "Model not loaded — click Load first.");
+                    return;
+                }
+                self.start_llm_eval(cx, sel);
+            }
+        }
+        if self.view.button(ids!(hub_llm_panel.llm_eval_cancel_btn)).clicked(actions) {
+            if let Some(cancel) = &self.llm_eval.cancel {
+                cancel.store(true, Ordering::SeqCst);
+                self.view.label(ids!(hub_llm_panel.llm_eval_summary)).set_text(cx, "Cancelling after the current prompt…");
+            }
+        }
+        if self.view.button(ids!(hub_llm_panel.llm_eval_export_btn)).clicked(actions) {
+            let stem = self.llm_eval.source.as_deref()
+                .and_then(Path::file_stem)
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or_else(|| "eval".to_string());
+            if let Some(dest) = FileDialog::new()
+                .add_filter("CSV", &["csv"])
+                .add_filter("JSON Lines", &["jsonl"])
+                .set_file_name(format!("{}-results.csv", stem))
+                .save_file()
+            {
+                let eval = &self.llm_eval;
+                let contents = if dest.extension().is_some_and(|ext| ext == "jsonl") {
+                    eval_set::results_jsonl(&eval.cases, &eval.results)
+                } else {
+                    eval_set::results_csv(&eval.cases, &eval.results)
+                };
+                let msg = match std::fs::write(&dest, contents) {
+                    Ok(()) => format!("Saved {}", dest.display()),
+                    Err(e) => format!("Error: could not write {}: {}", dest.display(), e),
+                };
+                self.view.label(ids!(hub_llm_panel.llm_eval_summary)).set_text(cx, &msg);
+            }
+        }
+    }
+
+    fn update_llm_eval_summary(&mut self, cx: &mut Cx) {
+        let eval = &self.llm_eval;
+        let done = eval.results.iter().flatten().count();
+        let text = match &eval.source {
+            None => "Choose a JSONL file of prompts, with expected answers or patterns to score them.".to_string(),
+            Some(path) if done == 0 => format!("{} — {} prompts", path.display(), eval.cases.len()),
+            Some(path) => format!("{} — {}/{} run: {}", path.display(), done, eval.cases.len(),
+                eval_set::Summary::new(eval.results.iter().flatten()).line()),
+        };
+        self.view.label(ids!(hub_llm_panel.llm_eval_summary)).set_text(cx, &text);
+        self.view.widget(ids!(hub_llm_panel.llm_eval_start_btn)).set_visible(cx, !eval.is_running && !eval.cases.is_empty());
+        self.view.widget(ids!(hub_llm_panel.llm_eval_cancel_btn)).set_visible(cx, eval.is_running);
+        self.view.widget(ids!(hub_llm_panel.llm_eval_export_btn)).set_visible(cx, !eval.is_running && done > 0);
+        self.view.redraw(cx);
+    }
+
+    fn start_llm_eval(&mut self, cx: &mut Cx, model_id: String) {
+        if self.llm_eval.is_running || self.llm_eval.cases.is_empty() { return; }
+
+        let cases = self.llm_eval.cases.clone();
+        self.llm_eval.results = vec![None; cases.len()];
+        let template = self.registry.as_ref()
+            .and_then(|r| r.models.iter().find(|m| m.id == model_id))
+            .and_then(|m| chat_template::template_for(m, &self.chat_templates));
+
+        let cancel = Arc::new(AtomicBool::new(false));
+        self.llm_eval.cancel = Some(cancel.clone());
+        let (tx, rx) = mpsc::channel();
+        self.llm_eval.rx = Some(rx);
+        self.llm_eval.is_running = true;
+        self.update_llm_eval_summary(cx);
+
+        let work = format!("Evaluating {} cases", cases.len());
+        std::thread::spawn(move || {
+            let _work = shutdown::track(work, Some(cancel.clone()));
+            let client = OminiXApiClient::localhost(300);
+            for (i, case) in cases.iter().enumerate() {
+                if cancel.load(Ordering::SeqCst) {
+                    let _ = tx.send(EvalUpdate::Finished { cancelled: true });
+                    return;
+                }
+                let _ = tx.send(EvalUpdate::Running(i));
+                let result = eval_set::run_case(&client, &model_id, template, case);
+                if let Err(e) = &result.answer {
+                    ::log::warn!("Eval prompt {} failed: {}", case.id, e);
+                }
+                let _ = tx.send(EvalUpdate::Result(i, result));
+            }
+            let _ = tx.send(EvalUpdate::Finished { cancelled: false });
+        });
+        cx.new_next_frame();
+    }
+
+    fn poll_llm_eval(&mut self, cx: &mut Cx) {
+        let Some(rx) = &self.llm_eval.rx else { return };
+        let mut changed = false;
+        let mut finished = None;
+        while let Ok(update) = rx.try_recv() {
+            changed = true;
+            match update {
+                EvalUpdate::Running(i) => self.llm_eval.running = Some(i),
+                EvalUpdate::Result(i, result) => {
+                    if let Some(slot) = self.llm_eval.results.get_mut(i) {
+                        *slot = Some(result);
+                    }
+                }
+                EvalUpdate::Finished { cancelled } => { finished = Some(cancelled); break; }
+            }
+        }
+        if let Some(cancelled) = finished {
+            self.llm_eval.is_running = false;
+            self.llm_eval.running = None;
+            self.llm_eval.rx = None;
+            self.llm_eval.cancel = None;
+            ::log::info!("LLM eval finished (cancelled: {})", cancelled);
+        } else {
+            cx.new_next_frame();
+        }
+        if changed { self.update_llm_eval_summary(cx); }
+    }
+
+    /// Mark the model's chosen template and say how its prompts are sent
+    fn update_template_row(&mut self, cx: &mut Cx, model: &RegistryModel) {
+        let chosen = self.chat_templates.models.get(&model.id).copied();
+        for (button, template) in llm_template_buttons() {
+            let selected = if template == chosen { 1.0 } else { 0.0 };
+            self.view.button(&[live_id!(hub_llm_panel), button]).apply_over(cx, live! {
+                draw_bg: { selected: (selected) }
+                draw_text: { selected: (selected) }
+            });
+        }
+        let hint = match chat_template::template_for(model, &self.chat_templates) {
+            Some(template) => format!(
+                "Prompts are formatted as {} here and sent to /v1/completions. Applies in chat the next time the model is opened there.",
+                template.label()
+            ),
+            None => "The runtime formats prompts with the model's own template.".to_string(),
+        };
+        self.view.label(ids!(hub_llm_panel.llm_template_hint)).set_text(cx, &hint);
+        self.view.redraw(cx);
+    }
+    fn handle_vlm_actions(&mut self, cx: &mut Cx, actions: &Actions) {
+        if self.view.button(ids!(hub_vlm_panel.vlm_browse_btn)).clicked(actions) {
+            if let Some(path) = FileDialog::new()
+                .add_filter("Image", &["jpg", "jpeg", "png", "bmp", "gif", "webp"])
+                .pick_file()
+            {
+                self.set_vlm_image(cx, path.to_string_lossy().to_string());
+            }
+        }
+        if self.view.button(ids!(hub_vlm_panel.vlm_capture_region_btn)).clicked(actions) {
+            self.capture_vlm_screen(cx, true);
+        }
+        if self.view.button(ids!(hub_vlm_panel.vlm_capture_screen_btn)).clicked(actions) {
+            self.capture_vlm_screen(cx, false);
+        }
+        if self.view.button(ids!(hub_vlm_panel.vlm_generate_btn)).clicked(actions) {
+            if let Some(sel) = self.selected_id.clone() {
+                let img  = self.vlm_state.image_path.clone();
+                let user = self.vlm_state.user.clone();
+                self.call_vlm(cx, sel, img, user);
+            }
+        }
+        if self.view.button(ids!(hub_vlm_panel.vlm_ocr_btn)).clicked(actions) {
+            if let Some(sel) = self.selected_id.clone() {
+                let img = self.vlm_state.image_path.clone();
+                self.extract_vlm_text(cx, sel, img);
+            }
+        }
+        if self.view.button(ids!(hub_vlm_panel.vlm_copy_btn)).clicked(actions) {
+            let text = self.view.label(ids!(hub_vlm_panel.vlm_response.output_label)).text();
+            if !text.is_empty() {
+                cx.copy_to_clipboard(&text);
+                self.view.label(ids!(hub_vlm_panel.vlm_status)).set_text(cx, "Copied.");
+            }
+        }
+    }
+    /// Export Request: the panel's last request as a `curl` script, or its
+    /// JSON body alone when saved with a `.json` name
+    fn handle_request_export(&mut self, cx: &mut Cx, actions: &Actions) {
+        let panels = [
+            (ids!(hub_llm_panel.llm_export_request_btn), EXPORT_LLM, ids!(hub_llm_panel.llm_status)),
+            (ids!(hub_vlm_panel.vlm_export_request_btn), EXPORT_VLM, ids!(hub_vlm_panel.vlm_status)),
+            (ids!(hub_asr_panel.asr_export_request_btn), EXPORT_ASR, ids!(hub_asr_panel.asr_status)),
+            (ids!(hub_tts_panel.tts_export_request_btn), EXPORT_TTS, ids!(hub_tts_panel.tts_status)),
+            (ids!(hub_image_panel.img_export_request_btn), EXPORT_IMAGE, ids!(hub_image_panel.img_status)),
+            (ids!(hub_image_edit_panel.img_edit_export_request_btn), EXPORT_IMAGE_EDIT, ids!(hub_image_edit_panel.img_edit_status)),
+            (ids!(hub_video_panel.vid_export_request_btn), EXPORT_VIDEO, ids!(hub_video_panel.vid_status)),
+            (ids!(hub_music_panel.mus_export_request_btn), EXPORT_MUSIC, ids!(hub_music_panel.mus_status)),
+            (ids!(hub_rerank_panel.rerank_export_request_btn), EXPORT_RERANK, ids!(hub_rerank_panel.rerank_status)),
+        ];
+        let Some((_, name, status)) = panels.into_iter().find(|(button, _, _)| self.view.button(*button).clicked(actions)) else {
+            return;
+        };
+        let Some(request) = request_export::last(name) else {
+            self.view.label(status).set_text(cx, "Run the model once, then export its request.");
+            return;
+        };
+        if let Some(dest) = FileDialog::new()
+            .add_filter("Shell script", &["sh"])
+            .add_filter("JSON body", &["json"])
+            .set_file_name(format!("{}.sh", request.file_stem()))
+            .save_file()
+        {
+            let contents = if dest.extension().is_some_and(|ext| ext == "json") {
+                request.pretty_body()
+            } else {
+                request.curl_script()
+            };
+            let msg = match std::fs::write(&dest, contents) {
+                Ok(()) => format!("Saved {}", dest.display()),
+                Err(e) => format!("Error: could not write {}: {}", dest.display(), e),
+            };
+            self.view.label(status).set_text(cx, &msg);
+        }
+    }
+    fn handle_asr_actions(&mut self, cx: &mut Cx, actions: &Actions) {
+        if self.view.button(ids!(hub_asr_panel.asr_browse_btn)).clicked(actions) {
+            if let Some(path) = FileDialog::new()
+                .add_filter("Audio", &["wav", "mp3", "m4a", "flac", "ogg", "aac"])
+                .pick_file()
+            {
+                let s = path.to_string_lossy().to_string();
+                self.asr_state.audio_path = s.clone();
+                self.view.text_input(ids!(hub_asr_panel.asr_audio_path)).set_text(cx, &s);
+                self.view.redraw(cx);
+            }
+        }
+        if self.view.button(ids!(hub_asr_panel.asr_transcribe_btn)).clicked(actions) {
+            if let Some(sel) = self.selected_id.clone() {
+                let load = self.load_states.get(&sel).copied().unwrap_or_default();
+                if load != ModelLoadState::Loaded {
+                    self.view.label(ids!(hub_asr_panel.asr_status)).set_text(cx, "Model not loaded — click Load first.");
+                    return;
+                }
+                let path = self.asr_state.audio_path.clone();
+                self.call_asr(cx, sel, path);
+            }
+        }
+        if self.view.button(ids!(hub_asr_panel.asr_notes_btn)).clicked(actions) {
+            if let Some(sel) = self.selected_id.clone() {
+                let load = self.load_states.get(&sel).copied().unwrap_or_default();
+                if load != ModelLoadState::Loaded {
+                    self.view.label(ids!(hub_asr_panel.asr_status)).set_text(cx, "Model not loaded — click Load first.");
+                    return;
+                }
+                let path = self.asr_state.audio_path.clone();
+                self.call_meeting_notes(cx, sel, path);
+            }
+        }
+
+        // Subtitle export
+        let export = if self.view.button(ids!(hub_asr_panel.asr_export_srt_btn)).clicked(actions) {
+            Some(("srt", "SubRip"))
+        } else if self.view.button(ids!(hub_asr_panel.asr_export_vtt_btn)).clicked(actions) {
+            Some(("vtt", "WebVTT"))
+        } else {
+            None
+        };
+        if let Some((ext, filter_name)) = export {
+            let stem = Path::new(&self.asr_state.audio_path).file_stem()
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or_else(|| "transcript".to_string());
+            if let Some(dest) = FileDialog::new()
+                .add_filter(filter_name, &[ext])
+                .set_file_name(format!("{}.{}", stem, ext))
+                .save_file()
+            {
+                // Subtitles carry the corrections while the editor is open
+                let transcript = match &self.asr_state.editor {
+                    Some(editor) => editor.transcript(),
+                    None => self.asr_state.transcript.clone(),
+                };
+                let contents = if ext == "srt" { transcript.to_srt() } else { transcript.to_vtt() };
+                let msg = match std::fs::write(&dest, contents) {
+                    Ok(()) => format!("Saved {}", dest.display()),
+                    Err(e) => format!("Error: could not write {}: {}", dest.display(), e),
+                };
+                self.view.label(ids!(hub_asr_panel.asr_status)).set_text(cx, &msg);
+            }
+        }
+
+        // Click a segment to play just that span; while editing, select it
+        // and play on from its start
+        let segment_list = self.view.portal_list(ids!(hub_asr_panel.asr_segment_list));
+        for (item_id, item) in segment_list.items_with_actions(actions) {
+            if let Some(fd) = item.as_view().finger_down(actions) {
+                if fd.tap_count == 1 {
+                    if let Some(editor) = &mut self.asr_state.editor {
+                        editor.select(item_id);
+                        self.show_asr_edit_segment(cx);
+                        self.play_asr_from_current(cx);
+                    } else if let Some(seg) = self.asr_state.transcript.segments.get(item_id).cloned() {
+                        self.play_asr_segment(cx, seg);
+                    }
+                }
+            }
+        }
+
+        self.handle_asr_editor_actions(cx, actions);
+    }
+
+    fn handle_asr_editor_actions(&mut self, cx: &mut Cx, actions: &Actions) {
+        if self.view.button(ids!(hub_asr_panel.asr_edit_btn)).clicked(actions) {
+            match self.asr_state.editor.take() {
+                Some(editor) => {
+                    // Closing keeps the corrections as the panel's transcript
+                    self.stop_asr_player();
+                    self.asr_state.transcript = editor.transcript();
+                    self.view.label(ids!(hub_asr_panel.asr_transcript.output_label))
+                        .set_text(cx, &self.asr_state.transcript.text);
+                }
+                None => {
+                    let saved = transcript_editor::load_corrections(Path::new(&self.asr_state.audio_path));
+                    let status = if saved.is_some() { "Picked up the corrections saved for this recording." } else { "" };
+                    let transcript = saved.unwrap_or_else(|| self.asr_state.transcript.clone());
+                    self.asr_state.editor = Some(TranscriptEditor::new(&transcript));
+                    self.view.label(ids!(hub_asr_panel.asr_status)).set_text(cx, status);
+                }
+            }
+            let editing = self.asr_state.editor.is_some();
+            self.view.view(ids!(hub_asr_panel.asr_editor_pane)).set_visible(cx, editing);
+            self.view.button(ids!(hub_asr_panel.asr_edit_btn))
+                .set_text(cx, if editing { "Done Editing" } else { "Edit Transcript" });
+            self.show_asr_edit_segment(cx);
+            self.view.redraw(cx);
+        }
+        let Some(editor) = &mut self.asr_state.editor else { return };
+
+        // `moved`: another segment (or its original text) goes in the input
+        let (mut edited, mut moved) = (false, false);
+        if let Some(text) = self.view.text_input(ids!(hub_asr_panel.asr_edit_input)).changed(actions) {
+            let index = editor.current();
+            editor.set_text(index, &text);
+            edited = true;
+        }
+        if self.view.button(ids!(hub_asr_panel.asr_edit_prev_btn)).clicked(actions) {
+            editor.step(false);
+            moved = true;
+        }
+        if self.view.button(ids!(hub_asr_panel.asr_edit_next_btn)).clicked(actions) {
+            editor.step(true);
+            moved = true;
+        }
+        if self.view.button(ids!(hub_asr_panel.asr_edit_revert_btn)).clicked(actions) {
+            let index = editor.current();
+            editor.revert(index);
+            moved = true;
+        }
+        if moved {
+            self.show_asr_edit_segment(cx);
+        } else if edited {
+            self.update_asr_edit_summary(cx);
+        }
+        if moved || edited {
+            self.view.redraw(cx);
+        }
+        if self.view.button(ids!(hub_asr_panel.asr_edit_play_btn)).clicked(actions) {
+            self.play_asr_from_current(cx);
+        }
+        if self.view.button(ids!(hub_asr_panel.asr_edit_stop_btn)).clicked(actions) {
+            self.stop_asr_player();
+        }
+
+        let Some(editor) = &self.asr_state.editor else { return };
+        let audio = Path::new(&self.asr_state.audio_path);
+        let status = self.view.label(ids!(hub_asr_panel.asr_edit_summary));
+        if self.view.button(ids!(hub_asr_panel.asr_edit_save_btn)).clicked(actions) {
+            let path = transcript_editor::corrections_path(audio);
+            let msg = match editor.save(&path) {
+                Ok(()) => format!("Saved {}", path.display()),
+                Err(e) => format!("Error: {}", e),
+            };
+            status.set_text(cx, &msg);
+        }
+        if self.view.button(ids!(hub_asr_panel.asr_edit_export_txt_btn)).clicked(actions) {
+            let stem = audio.file_stem()
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or_else(|| "transcript".to_string());
+            if let Some(dest) = FileDialog::new()
+                .add_filter("Text", &["txt"])
+                .set_file_name(format!("{}.txt", stem))
+                .save_file()
+            {
+                let msg = match std::fs::write(&dest, editor.to_text()) {
+                    Ok(()) => format!("Saved {}", dest.display()),
+                    Err(e) => format!("Error: could not write {}: {}", dest.display(), e),
+                };
+                status.set_text(cx, &msg);
+            }
+        }
+        if self.view.button(ids!(hub_asr_panel.asr_dataset_btn)).clicked(actions) {
+            let name = self.view.text_input(ids!(hub_asr_panel.asr_dataset_name)).text();
+            let msg = if name.trim().is_empty() {
+                "Enter a name for the dataset.".to_string()
             } else {
-                // First click: show confirmation
-                self.pending_remove_id = Some(sel.clone());
-                self.set_remove_btn_text(cx, "Are you sure?");
-                self.view.redraw(cx);
-            }
+                let clips = artifacts::session_dir().join("transcript-clips");
+                let dataset = editor.voice_dataset(&name, Path::new(&self.asr_state.playback_wav), &clips)
+                    .and_then(|mut dataset| dataset.save().map(|dir| (dataset, dir)));
+                match dataset {
+                    // Voice Studio lists it with the other datasets; say
+                    // now if it can't be trained on as it is
+                    Ok((dataset, dir)) => match dataset.validate() {
+                        Ok(()) => format!("Saved dataset {} ({}) to {}", dataset.name, dataset.summary(), dir.display()),
+                        Err(e) => format!("Saved dataset {} to {}. {}", dataset.name, dir.display(), e),
+                    },
+                    Err(e) => format!("Error: {}", e),
+                }
+            };
+            status.set_text(cx, &msg);
         }
     }
 
-    /// Set the remove button text on the active panel
-    fn set_remove_btn_text(&mut self, cx: &mut Cx, text: &str) {
-        let btn_id = match self.active_panel {
-            ActivePanel::Llm       => ids!(hub_llm_panel.hub_panel_header.panel_remove_btn),
-            ActivePanel::Vlm       => ids!(hub_vlm_panel.hub_panel_header.panel_remove_btn),
-            ActivePanel::Asr       => ids!(hub_asr_panel.hub_panel_header.panel_remove_btn),
-            ActivePanel::Tts       => ids!(hub_tts_panel.hub_panel_header.panel_remove_btn),
-            ActivePanel::Image     => ids!(hub_image_panel.hub_panel_header.panel_remove_btn),
-            ActivePanel::ImageEdit => ids!(hub_image_edit_panel.hub_panel_header.panel_remove_btn),
-            ActivePanel::Video     => ids!(hub_video_panel.hub_panel_header.panel_remove_btn),
-            _ => return,
+    /// Show the selected segment in the editor pane
+    fn show_asr_edit_segment(&mut self, cx: &mut Cx) {
+        let Some(editor) = &self.asr_state.editor else { return };
+        let (time, text) = match editor.current_segment() {
+            Some(seg) => (
+                format!("SEGMENT {} OF {} · {} → {}",
+                    editor.current() + 1,
+                    editor.segments().len(),
+                    moly_data::transcript::format_short_timestamp(seg.start),
+                    moly_data::transcript::format_short_timestamp(seg.end)),
+                seg.text.clone(),
+            ),
+            None => ("NO SEGMENTS".to_string(), String::new()),
         };
-        self.view.button(btn_id).set_text(cx, text);
-    }
-
-    /// Reset the remove confirmation state (e.g. when switching models)
-    fn reset_remove_confirmation(&mut self, cx: &mut Cx) {
-        if self.pending_remove_id.take().is_some() {
-            self.set_remove_btn_text(cx, "Remove");
-        }
+        self.view.label(ids!(hub_asr_panel.asr_edit_time)).set_text(cx, &time);
+        self.view.text_input(ids!(hub_asr_panel.asr_edit_input)).set_text(cx, &text);
+        self.update_asr_edit_summary(cx);
     }
 
-    /// Handle Load / Unload buttons in the active panel header.
-    fn handle_load_buttons(&mut self, cx: &mut Cx, actions: &Actions) {
-        let sel = match self.selected_id.clone() { Some(s) => s, None => return };
-
-        let (load_clicked, unload_clicked) = match self.active_panel {
-            ActivePanel::Llm => (
-                self.view.button(ids!(hub_llm_panel.hub_panel_header.panel_load_btn)).clicked(actions),
-                self.view.button(ids!(hub_llm_panel.hub_panel_header.panel_unload_btn)).clicked(actions),
-            ),
-            ActivePanel::Vlm => (
-                self.view.button(ids!(hub_vlm_panel.hub_panel_header.panel_load_btn)).clicked(actions),
-                self.view.button(ids!(hub_vlm_panel.hub_panel_header.panel_unload_btn)).clicked(actions),
-            ),
-            ActivePanel::Asr => (
-                self.view.button(ids!(hub_asr_panel.hub_panel_header.panel_load_btn)).clicked(actions),
-                self.view.button(ids!(hub_asr_panel.hub_panel_header.panel_unload_btn)).clicked(actions),
-            ),
-            ActivePanel::Tts => (
-                self.view.button(ids!(hub_tts_panel.hub_panel_header.panel_load_btn)).clicked(actions),
-                self.view.button(ids!(hub_tts_panel.hub_panel_header.panel_unload_btn)).clicked(actions),
-            ),
-            ActivePanel::Image => (
-                self.view.button(ids!(hub_image_panel.hub_panel_header.panel_load_btn)).clicked(actions),
-                self.view.button(ids!(hub_image_panel.hub_panel_header.panel_unload_btn)).clicked(actions),
-            ),
-            ActivePanel::ImageEdit => (
-                self.view.button(ids!(hub_image_edit_panel.hub_panel_header.panel_load_btn)).clicked(actions),
-                self.view.button(ids!(hub_image_edit_panel.hub_panel_header.panel_unload_btn)).clicked(actions),
-            ),
-            ActivePanel::Video => (
-                self.view.button(ids!(hub_video_panel.hub_panel_header.panel_load_btn)).clicked(actions),
-                self.view.button(ids!(hub_video_panel.hub_panel_header.panel_unload_btn)).clicked(actions),
-            ),
-            ActivePanel::Voice | ActivePanel::Info | ActivePanel::None => return,
+    fn update_asr_edit_summary(&mut self, cx: &mut Cx) {
+        let Some(editor) = &self.asr_state.editor else { return };
+        let text = match editor.edited_count() {
+            0 => "No corrections yet.".to_string(),
+            1 => "1 segment corrected.".to_string(),
+            n => format!("{} segments corrected.", n),
         };
+        self.view.label(ids!(hub_asr_panel.asr_edit_summary)).set_text(cx, &text);
+    }
 
-        if load_clicked   { self.start_load(cx, &sel); }
-        if unload_clicked { self.start_unload(cx, &sel); }
+    fn handle_asr_batch_actions(&mut self, cx: &mut Cx, actions: &Actions) {
+        if self.view.button(ids!(hub_asr_panel.asr_batch_folder_btn)).clicked(actions) && !self.asr_batch.is_running {
+            if let Some(dir) = FileDialog::new().pick_folder() {
+                self.asr_batch.files = list_audio_files(&dir).into_iter()
+                    .map(|path| AsrBatchFile { path, status: BatchFileStatus::Queued, error: None })
+                    .collect();
+                self.asr_batch.source_dir = Some(dir);
+                self.update_asr_batch_summary(cx);
+            }
+        }
+        if self.view.button(ids!(hub_asr_panel.asr_batch_output_btn)).clicked(actions) && !self.asr_batch.is_running {
+            self.asr_batch.output_dir = FileDialog::new().pick_folder();
+            self.update_asr_batch_summary(cx);
+        }
+        if self.view.button(ids!(hub_asr_panel.asr_batch_start_btn)).clicked(actions) {
+            if let Some(sel) = self.selected_id.clone() {
+                let load = self.load_states.get(&sel).copied().unwrap_or_default();
+                if load != ModelLoadState::Loaded {
+                    self.view.label(ids!(hub_asr_panel.asr_batch_summary)).set_text(cx, "Model not loaded — click Load first.");
+                    return;
+                }
+                self.start_asr_batch(cx, sel);
+            }
+        }
+        if self.view.button(ids!(hub_asr_panel.asr_batch_cancel_btn)).clicked(actions) {
+            if let Some(cancel) = &self.asr_batch.cancel {
+                cancel.store(true, Ordering::SeqCst);
+                self.view.label(ids!(hub_asr_panel.asr_batch_summary)).set_text(cx, "Cancelling after the current file…");
+            }
+        }
     }
 
-    /// Handle "Open in Chat" button — dispatch OpenChatWithModel to open a fresh chat session.
-    fn handle_chat_button(&mut self, cx: &mut Cx, actions: &Actions, _scope: &mut Scope) {
-        let chat_clicked = match self.active_panel {
-            ActivePanel::Llm =>
-                self.view.button(ids!(hub_llm_panel.hub_panel_header.panel_chat_btn)).clicked(actions),
-            ActivePanel::Vlm =>
-                self.view.button(ids!(hub_vlm_panel.hub_panel_header.panel_chat_btn)).clicked(actions),
-            _ => false,
+    fn update_asr_batch_summary(&mut self, cx: &mut Cx) {
+        let b = &self.asr_batch;
+        let done = b.files.iter().filter(|f| matches!(f.status, BatchFileStatus::Done | BatchFileStatus::Skipped)).count();
+        let failed = b.files.iter().filter(|f| f.status == BatchFileStatus::Failed).count();
+        let out = match &b.output_dir {
+            Some(d) => format!("→ {}", d.display()),
+            None => "→ next to source files".to_string(),
         };
+        let text = match &b.source_dir {
+            None => "Choose a folder of audio files to transcribe.".to_string(),
+            Some(dir) => format!("{} — {}/{} done, {} failed {}",
+                dir.display(), done, b.files.len(), failed, out),
+        };
+        self.view.label(ids!(hub_asr_panel.asr_batch_summary)).set_text(cx, &text);
+        self.view.widget(ids!(hub_asr_panel.asr_batch_start_btn)).set_visible(cx, !b.is_running && !b.files.is_empty());
+        self.view.widget(ids!(hub_asr_panel.asr_batch_cancel_btn)).set_visible(cx, b.is_running);
+        self.view.redraw(cx);
+    }
 
-        if !chat_clicked { return; }
+    fn start_asr_batch(&mut self, cx: &mut Cx, model_id: String) {
+        if self.asr_batch.is_running || self.asr_batch.files.is_empty() { return; }
 
-        let sel = match self.selected_id.clone() { Some(s) => s, None => return };
+        let output_dir = self.asr_batch.output_dir.clone();
+        let paths: Vec<PathBuf> = self.asr_batch.files.iter().map(|f| f.path.clone()).collect();
+        let stems = transcribe_watch::batch_stems(&paths);
+        for f in &mut self.asr_batch.files {
+            f.status = BatchFileStatus::Queued;
+            f.error = None;
+        }
 
-        let model = self.registry.as_ref()
-            .and_then(|r| r.models.iter().find(|m| m.id == sel))
-            .cloned();
-        let Some(model) = model else { return };
+        let cancel = Arc::new(AtomicBool::new(false));
+        self.asr_batch.cancel = Some(cancel.clone());
+        let (tx, rx) = mpsc::channel();
+        self.asr_batch.rx = Some(rx);
+        self.asr_batch.is_running = true;
+        self.update_asr_batch_summary(cx);
 
-        let api_model_id = model.runtime.api_model_id.clone();
-        let category = model.category;
+        let work = format!("Transcribing {} files", paths.len());
+        std::thread::spawn(move || {
+            let _work = shutdown::track(work, Some(cancel.clone()));
+            let client = OminiXApiClient::localhost(1800);
 
-        cx.action(StoreAction::OpenChatWithModel {
-            model_id: api_model_id,
-            category,
+            for (i, (audio, stem)) in paths.iter().zip(&stems).enumerate() {
+                if cancel.load(Ordering::SeqCst) {
+                    let _ = tx.send(AsrBatchUpdate::Finished { cancelled: true });
+                    return;
+                }
+                let txt_path = batch_transcript_path(audio, stem, output_dir.as_deref(), "txt");
+                if txt_path.exists() {
+                    let _ = tx.send(AsrBatchUpdate::Status(i, BatchFileStatus::Skipped, None));
+                    continue;
+                }
+                let _ = tx.send(AsrBatchUpdate::Status(i, BatchFileStatus::Running, None));
+
+                let result = transcribe_file_blocking(&client, &model_id, &audio.to_string_lossy())
+                    .and_then(|(transcript, wav_path, is_temp)| {
+                        if is_temp { artifacts::release(Path::new(&wav_path)); }
+                        if transcript.has_timestamps() {
+                            let srt_path = batch_transcript_path(audio, stem, output_dir.as_deref(), "srt");
+                            std::fs::write(&srt_path, transcript.to_srt()).map_err(|e| e.to_string())?;
+                        }
+                        // Write the .txt last: its presence marks the file as done for resume
+                        std::fs::write(&txt_path, &transcript.text).map_err(|e| e.to_string())
+                    });
+                let _ = match result {
+                    Ok(()) => tx.send(AsrBatchUpdate::Status(i, BatchFileStatus::Done, None)),
+                    Err(e) => {
+                        ::log::warn!("Batch ASR failed for {}: {}", audio.display(), e);
+                        tx.send(AsrBatchUpdate::Status(i, BatchFileStatus::Failed, Some(e)))
+                    }
+                };
+            }
+            let _ = tx.send(AsrBatchUpdate::Finished { cancelled: false });
         });
+        cx.new_next_frame();
     }
 
-    fn handle_input_changes(&mut self, actions: &Actions) {
-        if let Some(t) = self.view.text_input(ids!(hub_llm_panel.llm_system)).changed(actions)       { self.llm_state.system = t.to_string(); }
-        if let Some(t) = self.view.text_input(ids!(hub_llm_panel.llm_user)).changed(actions)         { self.llm_state.user = t.to_string(); }
-        if let Some(t) = self.view.text_input(ids!(hub_vlm_panel.vlm_image_path)).changed(actions)   { self.vlm_state.image_path = t.to_string(); }
-        if let Some(t) = self.view.text_input(ids!(hub_vlm_panel.vlm_user)).changed(actions)         { self.vlm_state.user = t.to_string(); }
-        if let Some(t) = self.view.text_input(ids!(hub_asr_panel.asr_audio_path)).changed(actions)   { self.asr_state.audio_path = t.to_string(); }
-        if let Some(t) = self.view.text_input(ids!(hub_tts_panel.tts_text_input)).changed(actions)   { self.tts_state.text = t.to_string(); }
-        if let Some(t) = self.view.text_input(ids!(hub_image_panel.img_prompt)).changed(actions)     { self.image_state.prompt = t.to_string(); }
-        if let Some(t) = self.view.text_input(ids!(hub_image_panel.img_neg_prompt)).changed(actions) { self.image_state.neg_prompt = t.to_string(); }
-        if let Some(t) = self.view.text_input(ids!(hub_image_edit_panel.img_edit_image_path)).changed(actions) { self.image_edit_state.image_path = t.to_string(); }
-        if let Some(t) = self.view.text_input(ids!(hub_image_edit_panel.img_edit_prompt)).changed(actions)     { self.image_edit_state.prompt = t.to_string(); }
-        if let Some(t) = self.view.text_input(ids!(hub_video_panel.vid_prompt)).changed(actions)               { self.video_state.prompt = t.to_string(); }
-    }
-
-    fn handle_llm_actions(&mut self, cx: &mut Cx, actions: &Actions) {
-        if self.view.button(ids!(hub_llm_panel.llm_generate_btn)).clicked(actions) {
-            if let Some(sel) = self.selected_id.clone() {
-                let system = self.llm_state.system.clone();
-                let user   = self.llm_state.user.clone();
-                self.call_llm(cx, sel, system, user);
+    fn poll_asr_batch(&mut self, cx: &mut Cx) {
+        let Some(rx) = &self.asr_batch.rx else { return };
+        let mut changed = false;
+        let mut finished = None;
+        while let Ok(update) = rx.try_recv() {
+            changed = true;
+            match update {
+                AsrBatchUpdate::Status(i, status, error) => {
+                    if let Some(f) = self.asr_batch.files.get_mut(i) {
+                        f.status = status;
+                        f.error = error;
+                    }
+                }
+                AsrBatchUpdate::Finished { cancelled } => { finished = Some(cancelled); break; }
             }
         }
-    }
-    fn handle_vlm_actions(&mut self, cx: &mut Cx, actions: &Actions) {
-        if self.view.button(ids!(hub_vlm_panel.vlm_browse_btn)).clicked(actions) {
-            if let Some(path) = FileDialog::new()
-                .add_filter("Image", &["jpg", "jpeg", "png", "bmp", "gif", "webp"])
-                .pick_file()
-            {
-                let s = path.to_string_lossy().to_string();
-                self.vlm_state.image_path = s.clone();
-                self.view.text_input(ids!(hub_vlm_panel.vlm_image_path)).set_text(cx, &s);
-                self.view.redraw(cx);
+        if let Some(cancelled) = finished {
+            self.asr_batch.is_running = false;
+            self.asr_batch.rx = None;
+            self.asr_batch.cancel = None;
+            // Anything still running when cancelled goes back to the queue
+            for f in &mut self.asr_batch.files {
+                if f.status == BatchFileStatus::Running { f.status = BatchFileStatus::Queued; }
             }
+            ::log::info!("ASR batch finished (cancelled: {})", cancelled);
+        } else {
+            cx.new_next_frame();
         }
-        if self.view.button(ids!(hub_vlm_panel.vlm_generate_btn)).clicked(actions) {
-            if let Some(sel) = self.selected_id.clone() {
-                let img  = self.vlm_state.image_path.clone();
-                let user = self.vlm_state.user.clone();
-                self.call_vlm(cx, sel, img, user);
-            }
+        if changed { self.update_asr_batch_summary(cx); }
+    }
+
+    /// Watch a folder for recordings, or stop. The choice is saved, so the
+    /// folder is watched again from the next start.
+    fn handle_asr_watch_actions(&mut self, cx: &mut Cx, actions: &Actions, scope: &mut Scope) {
+        let settings = if self.view.button(ids!(hub_asr_panel.asr_watch_folder_btn)).clicked(actions) {
+            let Some(dir) = FileDialog::new().pick_folder() else { return };
+            WatchFolderSettings { enabled: true, folder: dir.to_string_lossy().to_string() }
+        } else if self.view.button(ids!(hub_asr_panel.asr_watch_stop_btn)).clicked(actions) {
+            let folder = transcribe_watch::folder().map(|dir| dir.to_string_lossy().to_string()).unwrap_or_default();
+            WatchFolderSettings { enabled: false, folder }
+        } else {
+            return;
+        };
+        transcribe_watch::set_current(&settings);
+        if let Some(store) = scope.data.get_mut::<Store>() {
+            store.preferences.watch_folder = settings;
+            store.preferences.save();
         }
+        self.poll_asr_watch(cx);
     }
-    fn handle_asr_actions(&mut self, cx: &mut Cx, actions: &Actions) {
-        if self.view.button(ids!(hub_asr_panel.asr_browse_btn)).clicked(actions) {
+
+    fn poll_asr_watch(&mut self, cx: &mut Cx) {
+        let generation = transcribe_watch::generation();
+        if self.asr_watch.seen == Some(generation) { return; }
+        self.asr_watch.seen = Some(generation);
+        self.asr_watch.items = transcribe_watch::items();
+        let text = match transcribe_watch::folder() {
+            Some(dir) => format!("Watching {} — {}", dir.display(), transcribe_watch::summary()),
+            None => "Audio dropped into a watched folder is transcribed as it arrives, with the text saved next to it.".to_string(),
+        };
+        self.view.label(ids!(hub_asr_panel.asr_watch_summary)).set_text(cx, &text);
+        self.view.widget(ids!(hub_asr_panel.asr_watch_stop_btn)).set_visible(cx, transcribe_watch::folder().is_some());
+        self.view.redraw(cx);
+    }
+
+    fn handle_audiobook_actions(&mut self, cx: &mut Cx, actions: &Actions) {
+        if self.view.button(ids!(hub_tts_panel.tts_book_file_btn)).clicked(actions) && !self.audiobook.is_running {
             if let Some(path) = FileDialog::new()
-                .add_filter("Audio", &["wav", "mp3", "m4a", "flac", "ogg", "aac"])
+                .add_filter("Documents", &["txt", "md", "markdown", "epub"])
                 .pick_file()
             {
-                let s = path.to_string_lossy().to_string();
-                self.asr_state.audio_path = s.clone();
-                self.view.text_input(ids!(hub_asr_panel.asr_audio_path)).set_text(cx, &s);
-                self.view.redraw(cx);
+                match load_book(&path) {
+                    Ok(chapters) => {
+                        self.audiobook.chapters = chapters.into_iter()
+                            .map(|chapter| AudiobookChapterEntry {
+                                chapter, status: BatchFileStatus::Queued, progress: (0, 0), error: None,
+                            })
+                            .collect();
+                        self.audiobook.source = Some(path);
+                        self.update_audiobook_summary(cx);
+                    }
+                    Err(e) => {
+                        self.view.label(ids!(hub_tts_panel.tts_book_summary)).set_text(cx, &format!("Error: {}", error_text(&e)));
+                    }
+                }
             }
         }
-        if self.view.button(ids!(hub_asr_panel.asr_transcribe_btn)).clicked(actions) {
+        if self.view.button(ids!(hub_tts_panel.tts_book_output_btn)).clicked(actions) && !self.audiobook.is_running {
+            self.audiobook.output_dir = FileDialog::new().pick_folder();
+            self.update_audiobook_summary(cx);
+        }
+        if self.view.button(ids!(hub_tts_panel.tts_book_start_btn)).clicked(actions) {
             if let Some(sel) = self.selected_id.clone() {
                 let load = self.load_states.get(&sel).copied().unwrap_or_default();
                 if load != ModelLoadState::Loaded {
-                    self.view.label(ids!(hub_asr_panel.asr_status)).set_text(cx, "Model not loaded — click Load first.");
+                    self.view.label(ids!(hub_tts_panel.tts_book_summary)).set_text(cx, "Model not loaded — click Load first.");
                     return;
                 }
-                let path = self.asr_state.audio_path.clone();
-                self.call_asr(cx, sel, path);
+                self.start_audiobook(cx, sel);
+            }
+        }
+        if self.view.button(ids!(hub_tts_panel.tts_book_cancel_btn)).clicked(actions) {
+            if let Some(cancel) = &self.audiobook.cancel {
+                cancel.store(true, Ordering::SeqCst);
+                self.view.label(ids!(hub_tts_panel.tts_book_summary)).set_text(cx, "Cancelling after the current chunk…");
+            }
+        }
+    }
+
+    /// Output folder: chosen folder, or `<document> (audiobook)` next to the source
+    fn audiobook_output_dir(&self) -> Option<PathBuf> {
+        let source = self.audiobook.source.as_ref()?;
+        self.audiobook.output_dir.clone().or_else(|| {
+            let stem = source.file_stem()?.to_string_lossy().to_string();
+            Some(source.with_file_name(format!("{} (audiobook)", stem)))
+        })
+    }
+
+    fn update_audiobook_summary(&mut self, cx: &mut Cx) {
+        let b = &self.audiobook;
+        let done = b.chapters.iter().filter(|c| matches!(c.status, BatchFileStatus::Done | BatchFileStatus::Skipped)).count();
+        let failed = b.chapters.iter().filter(|c| c.status == BatchFileStatus::Failed).count();
+        let text = match (&b.source, self.audiobook_output_dir()) {
+            (Some(src), Some(out)) => format!("{} — {}/{} chapters done, {} failed → {}",
+                src.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default(),
+                done, b.chapters.len(), failed, out.display()),
+            _ => "Choose a .txt, .md or .epub document to build chaptered audio.".to_string(),
+        };
+        self.view.label(ids!(hub_tts_panel.tts_book_summary)).set_text(cx, &text);
+        self.view.widget(ids!(hub_tts_panel.tts_book_start_btn)).set_visible(cx, !b.is_running && !b.chapters.is_empty());
+        self.view.widget(ids!(hub_tts_panel.tts_book_cancel_btn)).set_visible(cx, b.is_running);
+        self.view.redraw(cx);
+    }
+
+    fn start_audiobook(&mut self, cx: &mut Cx, model_id: String) {
+        if self.audiobook.is_running || self.audiobook.chapters.is_empty() { return; }
+        let Some(output_dir) = self.audiobook_output_dir() else { return };
+        let book_name = self.audiobook.source.as_ref()
+            .and_then(|s| s.file_stem())
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_else(|| "audiobook".to_string());
+        let voice = if self.tts_state.voice_id.is_empty() { "vivian".to_string() } else { self.tts_state.voice_id.clone() };
+        let chapters: Vec<BookChapter> = self.audiobook.chapters.iter().map(|c| c.chapter.clone()).collect();
+        for c in &mut self.audiobook.chapters {
+            c.status = BatchFileStatus::Queued;
+            c.progress = (0, 0);
+            c.error = None;
+        }
+
+        let cancel = Arc::new(AtomicBool::new(false));
+        self.audiobook.cancel = Some(cancel.clone());
+        let (tx, rx) = mpsc::channel();
+        self.audiobook.rx = Some(rx);
+        self.audiobook.is_running = true;
+        self.update_audiobook_summary(cx);
+
+        let work = format!("Building the audiobook {}", book_name);
+        std::thread::spawn(move || {
+            let _work = shutdown::track(work, Some(cancel.clone()));
+            let result = build_audiobook(&model_id, &voice, &chapters, &output_dir, &book_name, &cancel, &tx);
+            let _ = tx.send(AudiobookUpdate::Finished(result));
+        });
+        cx.new_next_frame();
+    }
+
+    fn poll_audiobook(&mut self, cx: &mut Cx) {
+        let Some(rx) = &self.audiobook.rx else { return };
+        let mut changed = false;
+        let mut finished = None;
+        while let Ok(update) = rx.try_recv() {
+            changed = true;
+            match update {
+                AudiobookUpdate::Status(i, status, error) => {
+                    if let Some(c) = self.audiobook.chapters.get_mut(i) {
+                        c.status = status;
+                        c.error = error;
+                    }
+                }
+                AudiobookUpdate::Progress(i, done, total) => {
+                    if let Some(c) = self.audiobook.chapters.get_mut(i) { c.progress = (done, total); }
+                }
+                AudiobookUpdate::Finished(result) => { finished = Some(result); break; }
+            }
+        }
+        let Some(result) = finished else {
+            cx.new_next_frame();
+            if changed { self.update_audiobook_summary(cx); }
+            return;
+        };
+
+        self.audiobook.is_running = false;
+        self.audiobook.rx = None;
+        self.audiobook.cancel = None;
+        for c in &mut self.audiobook.chapters {
+            if c.status == BatchFileStatus::Running { c.status = BatchFileStatus::Queued; }
+        }
+        self.update_audiobook_summary(cx);
+        let msg = match result {
+            Ok(Some(path)) => format!("Audiobook ready: {}", path.display()),
+            Ok(None) => "Cancelled — start again to resume.".to_string(),
+            Err(e) => format!("Error: {}", error_text(&e)),
+        };
+        self.view.label(ids!(hub_tts_panel.tts_book_summary)).set_text(cx, &msg);
+    }
+
+    /// Play the recording from the start of the editor's segment on
+    fn play_asr_from_current(&mut self, cx: &mut Cx) {
+        let src = self.asr_state.playback_wav.clone();
+        let Some(seg) = self.asr_state.editor.as_ref().and_then(|e| e.current_segment()).cloned() else { return };
+        if src.is_empty() { return; }
+        self.stop_asr_player();
+        self.view.label(ids!(hub_asr_panel.asr_status))
+            .set_text(cx, &format!("Playing from {:.1}s", seg.start));
+        let player = self.asr_state.player.clone();
+        std::thread::spawn(move || {
+            let dest = artifacts::session_file("asr-from.wav");
+            let child = write_wav_segment(Path::new(&src), seg.start, f64::MAX, &dest)
+                .and_then(|()| platform::play_audio(&dest));
+            match child {
+                Ok(child) => {
+                    if let Some(mut previous) = player.lock().unwrap().replace(child) {
+                        previous.kill().ok();
+                        previous.wait().ok();
+                    }
+                }
+                Err(e) => ::log::warn!("Transcript playback failed: {}", e),
             }
+        });
+    }
+
+    fn stop_asr_player(&self) {
+        if let Some(mut child) = self.asr_state.player.lock().unwrap().take() {
+            child.kill().ok();
+            child.wait().ok();
         }
     }
+
+    fn play_asr_segment(&mut self, cx: &mut Cx, seg: TranscriptSegment) {
+        let src = self.asr_state.playback_wav.clone();
+        if src.is_empty() { return; }
+        self.view.label(ids!(hub_asr_panel.asr_status))
+            .set_text(cx, &format!("Playing {:.1}s – {:.1}s", seg.start, seg.end));
+        std::thread::spawn(move || {
+            let dest = artifacts::session_file("asr-segment.wav");
+            match write_wav_segment(Path::new(&src), seg.start, seg.end, &dest) {
+                Ok(()) => { let _ = platform::play_audio(&dest); }
+                Err(e) => ::log::warn!("Segment playback failed: {}", e),
+            }
+        });
+    }
     fn handle_tts_actions(&mut self, cx: &mut Cx, actions: &Actions) {
         // TTS voice selector clicks
         let tts_voice_list = self.view.portal_list(ids!(hub_tts_panel.tts_voice_list));
@@ -1391,7 +3735,7 @@ impl ModelHubApp {
         if self.view.button(ids!(hub_tts_panel.tts_result_row.tts_finder_btn)).clicked(actions) {
             let path = self.tts_state.output_path.clone();
             if !path.is_empty() {
-                let _ = std::process::Command::new("open").args(["-R", &path]).spawn();
+                let _ = platform::reveal_path(&path);
             }
         }
 
@@ -1407,6 +3751,12 @@ impl ModelHubApp {
                 self.call_tts(cx, sel, voice, text);
             }
         }
+        if self.view.button(ids!(hub_tts_panel.tts_cancel_btn)).clicked(actions) {
+            if let Some(cancel) = &self.tts_state.cancel {
+                cancel.store(true, Ordering::SeqCst);
+                self.view.label(ids!(hub_tts_panel.tts_status)).set_text(cx, "Cancelling after the current chunk…");
+            }
+        }
     }
     fn handle_image_actions(&mut self, cx: &mut Cx, actions: &Actions) {
         if self.view.button(ids!(hub_image_panel.img_generate_btn)).clicked(actions) {
@@ -1416,18 +3766,222 @@ impl ModelHubApp {
                     self.view.label(ids!(hub_image_panel.img_status)).set_text(cx, "Model not loaded — click Load first.");
                     return;
                 }
-                let prompt = self.image_state.prompt.clone();
-                let neg    = self.image_state.neg_prompt.clone();
-                self.call_image(cx, sel, prompt, neg);
+                let seed = match self.image_state.seed.trim() {
+                    "" => image_history::random_seed(),
+                    seed => match seed.parse::<u64>() {
+                        Ok(seed) => seed,
+                        Err(_) => {
+                            self.view.label(ids!(hub_image_panel.img_status)).set_text(cx, "The seed must be a whole number, or empty for a random one.");
+                            return;
+                        }
+                    },
+                };
+                let mut request = ImageGenerationRequest {
+                    negative_prompt: Some(self.image_state.neg_prompt.clone()).filter(|n| !n.is_empty()),
+                    seed: Some(seed),
+                    ..ImageGenerationRequest::new(sel, self.image_state.prompt.clone())
+                };
+                let mode = self.image_state.mode;
+                let (mut init_path, mut mask_path) = (None, None);
+                if mode != ImageMode::Text {
+                    let init = self.image_state.init_path.trim();
+                    let mask = self.image_state.mask_path.trim();
+                    let problem = if init.is_empty() {
+                        Some("Choose an init image.".to_string())
+                    } else if mode == ImageMode::Inpaint && mask.is_empty() {
+                        Some("Choose a mask for the areas to repaint.".to_string())
+                    } else {
+                        parse_strength(&self.image_state.strength).map(|s| request.strength = Some(s)).err()
+                    };
+                    if let Some(problem) = problem {
+                        self.view.label(ids!(hub_image_panel.img_status)).set_text(cx, &problem);
+                        return;
+                    }
+                    init_path = Some(init.to_string());
+                    mask_path = Some(mask.to_string()).filter(|_| mode == ImageMode::Inpaint);
+                }
+                self.call_image(cx, request, init_path, mask_path);
+            }
+        }
+
+        if self.view.button(ids!(hub_image_panel.img_enhance_btn)).clicked(actions) {
+            self.enhance_prompt(cx);
+        }
+        if self.view.button(ids!(hub_image_panel.img_revert_btn)).clicked(actions) {
+            if let Some(original) = self.image_state.original_prompt.clone() {
+                self.image_state.prompt = original;
+                self.view.text_input(ids!(hub_image_panel.img_prompt)).set_text(cx, &self.image_state.prompt);
+                self.forget_prompt_enhancement(cx);
+                self.view.label(ids!(hub_image_panel.img_enhance_status)).set_text(cx, "Back to your prompt.");
+            }
+        }
+
+        let modes = [
+            (ids!(hub_image_panel.img_mode_text_btn), ImageMode::Text),
+            (ids!(hub_image_panel.img_mode_img2img_btn), ImageMode::ImageToImage),
+            (ids!(hub_image_panel.img_mode_inpaint_btn), ImageMode::Inpaint),
+        ];
+        for (button, mode) in modes {
+            if self.view.button(button).clicked(actions) {
+                self.image_state.mode = mode;
+                self.update_image_mode(cx);
+            }
+        }
+
+        for (button, is_mask) in [(ids!(hub_image_panel.img_init_browse_btn), false), (ids!(hub_image_panel.img_mask_browse_btn), true)] {
+            if self.view.button(button).clicked(actions) {
+                if let Some(path) = FileDialog::new()
+                    .add_filter("Image", &["jpg", "jpeg", "png", "bmp", "gif", "webp"])
+                    .pick_file()
+                {
+                    self.set_image_source(cx, is_mask, path.to_string_lossy().to_string());
+                }
             }
         }
 
         if self.view.button(ids!(hub_image_panel.img_result_row.img_open_finder_btn)).clicked(actions) {
             let path = self.image_state.output_path.clone();
             if !path.is_empty() {
-                let _ = std::process::Command::new("open").args(["-R", &path]).spawn();
+                let _ = platform::reveal_path(&path);
+            }
+        }
+
+        // History: reuse an entry's settings, or delete it
+        let history_list = self.view.portal_list(ids!(hub_image_panel.img_history_list));
+        for (item_id, item) in history_list.items_with_actions(actions) {
+            let Some(entry) = self.image_history.entries.get(item_id).cloned() else { continue };
+            if item.button(ids!(history_use_btn)).clicked(actions) {
+                self.image_state.prompt = entry.prompt.clone();
+                self.image_state.neg_prompt = entry.negative_prompt.clone();
+                self.image_state.seed = entry.seed.map(|s| s.to_string()).unwrap_or_default();
+                self.forget_prompt_enhancement(cx);
+                self.view.text_input(ids!(hub_image_panel.img_prompt)).set_text(cx, &self.image_state.prompt);
+                self.view.text_input(ids!(hub_image_panel.img_neg_prompt)).set_text(cx, &self.image_state.neg_prompt);
+                self.view.text_input(ids!(hub_image_panel.img_seed)).set_text(cx, &self.image_state.seed);
+                let note = if self.selected_id.as_deref() == Some(entry.model.as_str()) {
+                    "Settings loaded. Generate to run them again.".to_string()
+                } else {
+                    format!("Settings loaded. They were made with {}.", entry.model)
+                };
+                self.view.label(ids!(hub_image_panel.img_status)).set_text(cx, &note);
+                self.view.redraw(cx);
             }
+            if item.button(ids!(history_delete_btn)).clicked(actions) {
+                if let Err(e) = self.image_history.remove(&entry.id) {
+                    self.view.label(ids!(hub_image_panel.img_status)).set_text(cx, &format!("Couldn't delete: {}", e));
+                }
+                self.update_image_history_empty(cx);
+                self.view.redraw(cx);
+            }
+        }
+    }
+
+    /// Ask the loaded LLM for a more detailed version of the prompt
+    fn enhance_prompt(&mut self, cx: &mut Cx) {
+        if self.image_state.enhance_rx.is_some() { return; }
+        let status = self.view.label(ids!(hub_image_panel.img_enhance_status));
+        let prompt = self.image_state.prompt.trim().to_string();
+        if prompt.is_empty() {
+            status.set_text(cx, "Enter a prompt to enhance.");
+            return;
         }
+        let llm = self.registry.as_ref().and_then(|r| {
+            [RegistryCategory::Llm, RegistryCategory::Vlm].into_iter().find_map(|cat| {
+                r.models.iter().find(|m| {
+                    m.category == cat && self.load_states.get(&m.id).copied() == Some(ModelLoadState::Loaded)
+                })
+            })
+        });
+        let Some(llm) = llm else {
+            status.set_text(cx, "Load an LLM in the Model Hub to enhance prompts.");
+            return;
+        };
+        status.set_text(cx, &format!("Enhancing with {}...", llm.name));
+        let model = llm.runtime.api_model_id.clone();
+        let template = chat_template::template_for(llm, &self.chat_templates);
+
+        let (tx, rx) = mpsc::channel();
+        self.image_state.enhance_rx = Some(rx);
+        std::thread::spawn(move || {
+            let request = prompt_enhance::request(model, &prompt);
+            let result = chat_template::complete(&OminiXApiClient::localhost(120), &request.model, template, request.messages)
+                .map_err(String::from)
+                .and_then(|answer| prompt_enhance::clean_answer(&answer));
+            let _ = tx.send(result);
+        });
+        self.view.redraw(cx);
+        cx.new_next_frame();
+    }
+
+    /// Put the enhanced prompt in place and show what changed
+    fn apply_enhanced_prompt(&mut self, cx: &mut Cx, enhanced: String) {
+        let previous = std::mem::replace(&mut self.image_state.prompt, enhanced);
+        // Enhancing twice still reverts to what the user wrote
+        let original = self.image_state.original_prompt.get_or_insert(previous).clone();
+        self.view.text_input(ids!(hub_image_panel.img_prompt)).set_text(cx, &self.image_state.prompt);
+        let diff = answer_versions::marked(&answer_versions::diff_words(&original, &self.image_state.prompt));
+        self.view.label(ids!(hub_image_panel.img_enhance_diff_view.img_enhance_diff)).set_text(cx, &diff);
+        self.view.view(ids!(hub_image_panel.img_enhance_diff_view)).set_visible(cx, true);
+        self.view.button(ids!(hub_image_panel.img_revert_btn)).set_visible(cx, true);
+        self.view.label(ids!(hub_image_panel.img_enhance_status))
+            .set_text(cx, "Prompt enhanced: [-removed-] {+added+}. Revert to go back to yours.");
+    }
+
+    /// Drop the revert point, e.g. once the user edits the prompt
+    fn forget_prompt_enhancement(&mut self, cx: &mut Cx) {
+        if self.image_state.original_prompt.take().is_none() { return; }
+        self.view.view(ids!(hub_image_panel.img_enhance_diff_view)).set_visible(cx, false);
+        self.view.button(ids!(hub_image_panel.img_revert_btn)).set_visible(cx, false);
+        self.view.label(ids!(hub_image_panel.img_enhance_status)).set_text(cx, "");
+        self.view.redraw(cx);
+    }
+
+    /// Show the inputs of the chosen mode and mark its button
+    fn update_image_mode(&mut self, cx: &mut Cx) {
+        let mode = self.image_state.mode;
+        let buttons = [
+            (ids!(hub_image_panel.img_mode_text_btn), ImageMode::Text),
+            (ids!(hub_image_panel.img_mode_img2img_btn), ImageMode::ImageToImage),
+            (ids!(hub_image_panel.img_mode_inpaint_btn), ImageMode::Inpaint),
+        ];
+        for (button, button_mode) in buttons {
+            let selected = if button_mode == mode { 1.0 } else { 0.0 };
+            self.view.button(button).apply_over(cx, live! {
+                draw_bg: { selected: (selected) }
+                draw_text: { selected: (selected) }
+            });
+        }
+        self.view.view(ids!(hub_image_panel.img_source_section)).set_visible(cx, mode != ImageMode::Text);
+        self.view.view(ids!(hub_image_panel.img_mask_section)).set_visible(cx, mode == ImageMode::Inpaint);
+        let generate = match mode {
+            ImageMode::Text => "Generate Image",
+            ImageMode::ImageToImage => "Transform Image",
+            ImageMode::Inpaint => "Inpaint Image",
+        };
+        self.view.button(ids!(hub_image_panel.img_generate_btn)).set_text(cx, generate);
+        self.view.redraw(cx);
+    }
+
+    /// Use `path` as the init image, or as the mask, and preview it
+    fn set_image_source(&mut self, cx: &mut Cx, is_mask: bool, path: String) {
+        let (input, preview) = if is_mask {
+            self.image_state.mask_path = path.clone();
+            (ids!(hub_image_panel.img_mask_path), ids!(hub_image_panel.img_mask_preview))
+        } else {
+            self.image_state.init_path = path.clone();
+            (ids!(hub_image_panel.img_init_path), ids!(hub_image_panel.img_init_preview))
+        };
+        self.view.text_input(input).set_text(cx, &path);
+        let preview = self.view.image(preview);
+        let loaded = preview.load_image_file_by_path(cx, Path::new(&path)).is_ok();
+        preview.set_visible(cx, loaded);
+        self.view.redraw(cx);
+    }
+
+    fn update_image_history_empty(&mut self, cx: &mut Cx) {
+        let empty = self.image_history.entries.is_empty();
+        self.view.widget(ids!(hub_image_panel.img_history_empty)).set_visible(cx, empty);
+        self.view.widget(ids!(hub_image_panel.img_history_list)).set_visible(cx, !empty);
     }
 
     fn handle_image_edit_actions(&mut self, cx: &mut Cx, actions: &Actions) {
@@ -1447,8 +4001,7 @@ impl ModelHubApp {
         // Edit button — sd.cpp models don't need API loading, just download
         if self.view.button(ids!(hub_image_edit_panel.img_edit_btn)).clicked(actions) {
             if let Some(sel) = self.selected_id.clone() {
-                let dl = self.model_states.get(&sel).copied().unwrap_or(ModelUiState::NotDownloaded);
-                if dl != ModelUiState::Downloaded {
+                if self.dl_state(&sel) != ModelUiState::Downloaded {
                     self.view.label(ids!(hub_image_edit_panel.img_edit_status)).set_text(cx, "Model not downloaded yet — click Download first.");
                     return;
                 }
@@ -1462,7 +4015,7 @@ impl ModelHubApp {
         if self.view.button(ids!(hub_image_edit_panel.img_edit_result_row.img_edit_open_finder_btn)).clicked(actions) {
             let path = self.image_edit_state.output_path.clone();
             if !path.is_empty() {
-                let _ = std::process::Command::new("open").args(["-R", &path]).spawn();
+                let _ = platform::reveal_path(&path);
             }
         }
     }
@@ -1483,354 +4036,98 @@ impl ModelHubApp {
         if self.view.button(ids!(hub_video_panel.vid_result_row.vid_play_btn)).clicked(actions) {
             let path = self.video_state.output_path.clone();
             if !path.is_empty() {
-                let _ = std::process::Command::new("open").arg(&path).spawn();
+                let _ = platform::open_path(&path);
             }
         }
 
         if self.view.button(ids!(hub_video_panel.vid_result_row.vid_open_finder_btn)).clicked(actions) {
             let path = self.video_state.output_path.clone();
             if !path.is_empty() {
-                let _ = std::process::Command::new("open").args(["-R", &path]).spawn();
+                let _ = platform::reveal_path(&path);
             }
         }
     }
 
-    // ── Voice Studio event handlers ───────────────────────────────────────────
-
-    fn on_voice_studio_selected(&mut self, cx: &mut Cx) {
-        self.show_panel(cx, ActivePanel::Voice);
-        // Initialize voice defaults if not done yet
-        if self.voice_quality.is_empty() {
-            self.voice_quality  = "standard".to_string();
-            self.voice_language = "auto".to_string();
-            self.voice_denoise  = true;
-        }
-        // Fetch voice list if not already done
-        if self.voices.is_empty() && self.voice_list_rx.is_none() {
-            self.fetch_voice_list();
-        }
-    }
-
-    fn handle_voice_actions(&mut self, cx: &mut Cx, actions: &Actions) {
-        if self.active_panel != ActivePanel::Voice { return; }
-
-        // + New Voice button — clear form
-        if self.view.button(ids!(hub_voice_panel.voice_new_btn)).clicked(actions) {
-            self.selected_voice_idx = None;
-            self.view.text_input(ids!(hub_voice_panel.voice_name_input)).set_text(cx, "");
-            self.view.text_input(ids!(hub_voice_panel.voice_audio_path_input)).set_text(cx, "");
-            self.view.text_input(ids!(hub_voice_panel.voice_transcript_input)).set_text(cx, "");
-            self.view.redraw(cx);
-        }
-
-        // Voice list clicks
-        let voices_list = self.view.portal_list(ids!(hub_voice_panel.voice_list));
-        for (item_id, item) in voices_list.items_with_actions(actions) {
-            if let Some(fd) = item.as_view().finger_down(actions) {
-                if fd.tap_count == 1 {
-                    self.selected_voice_idx = Some(item_id);
-                    self.view.redraw(cx);
+    fn handle_music_actions(&mut self, cx: &mut Cx, actions: &Actions) {
+        if self.view.button(ids!(hub_music_panel.mus_generate_btn)).clicked(actions) {
+            if let Some(sel) = self.selected_id.clone() {
+                let status = self.view.label(ids!(hub_music_panel.mus_status));
+                let load = self.load_states.get(&sel).copied().unwrap_or_default();
+                if load != ModelLoadState::Loaded {
+                    status.set_text(cx, "Model not loaded — click Load first.");
+                    return;
                 }
+                let duration = match parse_duration(&self.music_state.duration) {
+                    Ok(duration) => duration,
+                    Err(e) => {
+                        status.set_text(cx, &e);
+                        return;
+                    }
+                };
+                let seed = match self.music_state.seed.trim() {
+                    "" => image_history::random_seed(),
+                    seed => match seed.parse::<u64>() {
+                        Ok(seed) => seed,
+                        Err(_) => {
+                            status.set_text(cx, "The seed must be a whole number, or empty for a random one.");
+                            return;
+                        }
+                    },
+                };
+                let request = MusicGenerationRequest {
+                    duration,
+                    seed: Some(seed),
+                    ..MusicGenerationRequest::wav(sel, self.music_state.prompt.trim())
+                };
+                self.call_music(cx, request);
             }
         }
 
-        // Quality buttons
-        if self.view.button(ids!(hub_voice_panel.voice_quality_fast)).clicked(actions) {
-            self.voice_quality = "fast".to_string();
-            self.view.redraw(cx);
-        }
-        if self.view.button(ids!(hub_voice_panel.voice_quality_standard)).clicked(actions) {
-            self.voice_quality = "standard".to_string();
-            self.view.redraw(cx);
-        }
-        if self.view.button(ids!(hub_voice_panel.voice_quality_high)).clicked(actions) {
-            self.voice_quality = "high".to_string();
-            self.view.redraw(cx);
-        }
-
-        // Browse audio file for training
-        if self.view.button(ids!(hub_voice_panel.voice_audio_browse_btn)).clicked(actions) {
-            if let Some(path) = FileDialog::new()
-                .add_filter("Audio", &["wav", "mp3", "m4a", "flac", "ogg", "aac"])
-                .pick_file()
-            {
-                let s = path.to_string_lossy().to_string();
-                self.view.text_input(ids!(hub_voice_panel.voice_audio_path_input)).set_text(cx, &s);
-                self.view.redraw(cx);
-            }
-        }
-
-        // Train button
-        if self.view.button(ids!(hub_voice_panel.voice_train_btn)).clicked(actions) {
-            let name       = self.view.text_input(ids!(hub_voice_panel.voice_name_input)).text();
-            let audio_path = self.view.text_input(ids!(hub_voice_panel.voice_audio_path_input)).text();
-            let transcript = self.view.text_input(ids!(hub_voice_panel.voice_transcript_input)).text();
-            if name.trim().is_empty() {
-                self.view.label(ids!(hub_voice_panel.voice_train_status)).set_text(cx, "Please enter a voice name.");
-            } else if audio_path.trim().is_empty() {
-                self.view.label(ids!(hub_voice_panel.voice_train_status)).set_text(cx, "Please enter the path to a WAV file.");
-            } else {
-                self.start_voice_training(cx, name, audio_path, transcript);
-            }
-        }
-
-        // Cancel training button
-        if self.view.button(ids!(hub_voice_panel.voice_cancel_train_btn)).clicked(actions) {
-            if let Some(cancel) = &self.voice_cancel {
-                cancel.store(true, std::sync::atomic::Ordering::SeqCst);
-            }
-            let task_id = self.voice_task_id.clone();
-            std::thread::spawn(move || {
-                let _ = reqwest::blocking::Client::new()
-                    .post("http://localhost:8080/v1/voices/train/cancel")
-                    .json(&serde_json::json!({ "task_id": task_id }))
-                    .send();
-            });
-            self.voice_training_state = VoiceTrainingState::Idle;
-            self.voice_training_rx = None;
-            self.view.redraw(cx);
-        }
-
-        // Generate/synthesize button
-        if self.view.button(ids!(hub_voice_panel.voice_generate_btn)).clicked(actions) {
-            let text = self.view.text_input(ids!(hub_voice_panel.voice_synth_text)).text();
-            let speed_str = self.view.text_input(ids!(hub_voice_panel.voice_speed_input)).text();
-            let speed: f32 = speed_str.parse().unwrap_or(1.0);
-            if text.trim().is_empty() {
-                self.view.label(ids!(hub_voice_panel.voice_synth_status)).set_text(cx, "Please enter text to synthesize.");
-            } else if let Some(idx) = self.selected_voice_idx {
-                if idx < self.voices.len() {
-                    let voice_name = self.voices[idx].name.clone();
-                    self.start_voice_synthesis(cx, text, voice_name, speed);
-                } else {
-                    self.view.label(ids!(hub_voice_panel.voice_synth_status)).set_text(cx, "Selected voice is no longer available.");
+        if self.view.button(ids!(hub_music_panel.mus_result_row.mus_play_btn)).clicked(actions) {
+            let path = self.music_state.output_path.clone();
+            if !path.is_empty() {
+                if let Err(e) = platform::play_audio(&path) {
+                    self.view.label(ids!(hub_music_panel.mus_status)).set_text(cx, &format!("Can't play the clip: {}", e));
                 }
-            } else {
-                self.view.label(ids!(hub_voice_panel.voice_synth_status)).set_text(cx, "Please select a voice from the list.");
             }
         }
 
-        // Play button
-        if self.view.button(ids!(hub_voice_panel.voice_play_btn)).clicked(actions) {
-            std::process::Command::new("afplay")
-                .arg("/tmp/ominix-voice-out.wav")
-                .spawn()
-                .ok();
+        if self.view.button(ids!(hub_music_panel.mus_result_row.mus_open_finder_btn)).clicked(actions) {
+            let path = self.music_state.output_path.clone();
+            if !path.is_empty() {
+                let _ = platform::reveal_path(&path);
+            }
         }
     }
 
-    fn poll_voice_channels(&mut self, cx: &mut Cx) {
-        let mut need_next_frame = false;
-
-        // Voice list fetch
-        if let Some(rx) = &self.voice_list_rx {
-            if let Ok(update) = rx.try_recv() {
-                match update {
-                    VoicesUpdate::Loaded(voices) => { self.voices = voices; }
-                    VoicesUpdate::Error(e) => { ::log::warn!("Voice list fetch failed: {}", e); }
-                }
-                self.voice_list_rx = None;
-                self.view.redraw(cx);
-            }
+    fn handle_rerank_actions(&mut self, cx: &mut Cx, actions: &Actions) {
+        if !self.view.button(ids!(hub_rerank_panel.rerank_btn)).clicked(actions) {
+            return;
         }
-
-        // Training updates
-        if let Some(rx) = &self.voice_training_rx {
-            match rx.try_recv() {
-                Ok(VoiceTrainingUpdate::Progress { stage, progress }) => {
-                    self.voice_training_state = VoiceTrainingState::Training {
-                        task_id: self.voice_task_id.clone(),
-                        stage,
-                        progress,
-                    };
-                    need_next_frame = true;
-                    self.view.redraw(cx);
-                }
-                Ok(VoiceTrainingUpdate::Done) => {
-                    self.voice_training_state = VoiceTrainingState::Done;
-                    self.voice_training_rx = None;
-                    self.voice_cancel = None;
-                    self.view.label(ids!(hub_voice_panel.voice_train_status)).set_text(cx, "Training complete!");
-                    self.fetch_voice_list();
-                    self.view.redraw(cx);
-                }
-                Ok(VoiceTrainingUpdate::Error(e)) => {
-                    let msg = format!("Training failed: {}", e);
-                    self.voice_training_state = VoiceTrainingState::Error(e);
-                    self.voice_training_rx = None;
-                    self.voice_cancel = None;
-                    self.view.label(ids!(hub_voice_panel.voice_train_status)).set_text(cx, &msg);
-                    self.view.redraw(cx);
-                }
-                Err(mpsc::TryRecvError::Empty) => { need_next_frame = true; }
-                Err(mpsc::TryRecvError::Disconnected) => { self.voice_training_rx = None; }
-            }
+        let Some(sel) = self.selected_id.clone() else { return };
+        let status = self.view.label(ids!(hub_rerank_panel.rerank_status));
+        let load = self.load_states.get(&sel).copied().unwrap_or_default();
+        if load != ModelLoadState::Loaded {
+            status.set_text(cx, "Model not loaded — click Load first.");
+            return;
         }
-
-        // Synthesis updates
-        if let Some(rx) = &self.voice_synthesis_rx {
-            match rx.try_recv() {
-                Ok(VoiceSynthesisUpdate::Done { duration_secs }) => {
-                    self.voice_synthesis_state = VoiceSynthesisState::Done { duration_secs };
-                    self.voice_synthesis_rx = None;
-                    let msg = format!("Ready — {:.1}s generated", duration_secs);
-                    self.view.label(ids!(hub_voice_panel.voice_synth_status)).set_text(cx, &msg);
-                    self.view.redraw(cx);
-                }
-                Ok(VoiceSynthesisUpdate::Error(e)) => {
-                    let msg = format!("Synthesis failed: {}", e);
-                    self.voice_synthesis_state = VoiceSynthesisState::Error(e);
-                    self.voice_synthesis_rx = None;
-                    self.view.label(ids!(hub_voice_panel.voice_synth_status)).set_text(cx, &msg);
-                    self.view.redraw(cx);
-                }
-                Err(mpsc::TryRecvError::Empty) => { need_next_frame = true; }
-                Err(mpsc::TryRecvError::Disconnected) => { self.voice_synthesis_rx = None; }
-            }
+        let query = self.rerank_state.query.trim().to_string();
+        let passages: Vec<String> = self.rerank_state.passages.lines()
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+            .map(String::from)
+            .collect();
+        if query.is_empty() || passages.is_empty() {
+            status.set_text(cx, "Enter a query and at least one passage.");
+            return;
         }
-
-        if need_next_frame { cx.new_next_frame(); }
-    }
-
-    fn fetch_voice_list(&mut self) {
-        let (tx, rx) = mpsc::channel::<VoicesUpdate>();
-        self.voice_list_rx = Some(rx);
-        std::thread::spawn(move || {
-            match reqwest::blocking::get("http://localhost:8080/v1/voices") {
-                Ok(resp) => {
-                    if let Ok(json) = resp.json::<serde_json::Value>() {
-                        let voices = json.as_array()
-                            .map(|arr| arr.iter().filter_map(|v| {
-                                let name     = v["name"].as_str()?.to_string();
-                                let is_ready = v["status"].as_str().map(|s| s == "ready").unwrap_or(false);
-                                Some(VoiceEntry { name, is_ready })
-                            }).collect::<Vec<_>>())
-                            .unwrap_or_default();
-                        let _ = tx.send(VoicesUpdate::Loaded(voices));
-                    } else {
-                        let _ = tx.send(VoicesUpdate::Error("Invalid JSON response".to_string()));
-                    }
-                }
-                Err(e) => { let _ = tx.send(VoicesUpdate::Error(e.to_string())); }
-            }
-        });
-    }
-
-    fn start_voice_training(&mut self, cx: &mut Cx, name: String, audio_path: String, transcript: String) {
-        let quality  = self.voice_quality.clone();
-        let language = self.voice_language.clone();
-        let denoise  = self.voice_denoise;
-
-        let cancel = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
-        self.voice_cancel = Some(cancel.clone());
-        let (tx, rx) = mpsc::channel::<VoiceTrainingUpdate>();
-        self.voice_training_rx = Some(rx);
-        self.voice_training_state = VoiceTrainingState::Training {
-            task_id: String::new(),
-            stage:   "Starting...".to_string(),
-            progress: 0.0,
-        };
-
-        std::thread::spawn(move || {
-            // Read and base64-encode audio file
-            let audio_bytes = match std::fs::read(&audio_path) {
-                Ok(b) => b,
-                Err(e) => {
-                    let _ = tx.send(VoiceTrainingUpdate::Error(e.to_string()));
-                    return;
-                }
-            };
-            let audio_b64 = base64::engine::general_purpose::STANDARD.encode(&audio_bytes);
-
-            let payload = serde_json::json!({
-                "name": name,
-                "audio_data": audio_b64,
-                "transcript": transcript,
-                "quality": quality,
-                "language": language,
-                "denoise": denoise,
-            });
-
-            let resp = match reqwest::blocking::Client::new()
-                .post("http://localhost:8080/v1/voices/train")
-                .json(&payload)
-                .send()
-            {
-                Ok(r) => r,
-                Err(e) => { let _ = tx.send(VoiceTrainingUpdate::Error(e.to_string())); return; }
-            };
-
-            let task_id = match resp.json::<serde_json::Value>() {
-                Ok(v) => v["task_id"].as_str().unwrap_or("").to_string(),
-                Err(e) => { let _ = tx.send(VoiceTrainingUpdate::Error(e.to_string())); return; }
-            };
-
-            // Poll for status
-            loop {
-                if cancel.load(std::sync::atomic::Ordering::SeqCst) { return; }
-                std::thread::sleep(std::time::Duration::from_millis(800));
-                let status_url = format!("http://localhost:8080/v1/voices/train/status?task_id={}", task_id);
-                let status = match reqwest::blocking::get(&status_url) {
-                    Ok(r) => match r.json::<serde_json::Value>() {
-                        Ok(v) => v,
-                        Err(_) => continue,
-                    },
-                    Err(_) => continue,
-                };
-                let state    = status["state"].as_str().unwrap_or("").to_string();
-                let stage    = status["stage"].as_str().unwrap_or("").to_string();
-                let progress = status["progress"].as_f64().unwrap_or(0.0) as f32;
-                match state.as_str() {
-                    "done"  => { let _ = tx.send(VoiceTrainingUpdate::Done); return; }
-                    "error" => {
-                        let msg = status["error"].as_str().unwrap_or("Unknown error").to_string();
-                        let _ = tx.send(VoiceTrainingUpdate::Error(msg));
-                        return;
-                    }
-                    _ => { let _ = tx.send(VoiceTrainingUpdate::Progress { stage, progress }); }
-                }
-            }
-        });
-
-        self.view.redraw(cx);
+        self.call_rerank(cx, RerankRequest { model: sel, query, documents: passages, top_n: None });
     }
 
-    fn start_voice_synthesis(&mut self, cx: &mut Cx, text: String, voice_name: String, speed: f32) {
-        let (tx, rx) = mpsc::channel::<VoiceSynthesisUpdate>();
-        self.voice_synthesis_rx = Some(rx);
-        self.voice_synthesis_state = VoiceSynthesisState::Generating;
-        self.view.label(ids!(hub_voice_panel.voice_synth_status)).set_text(cx, "Generating...");
-
-        std::thread::spawn(move || {
-            let payload = serde_json::json!({
-                "model": "gpt-so-vits",
-                "input": text,
-                "voice": voice_name,
-                "speed": speed,
-                "response_format": "wav",
-            });
-            let t0 = std::time::Instant::now();
-            match reqwest::blocking::Client::new()
-                .post("http://localhost:8080/v1/audio/speech")
-                .json(&payload)
-                .send()
-            {
-                Ok(mut resp) => {
-                    let mut buf = Vec::new();
-                    match resp.copy_to(&mut buf) {
-                        Ok(_) => {
-                            let _ = std::fs::write("/tmp/ominix-voice-out.wav", &buf);
-                            let duration_secs = t0.elapsed().as_secs_f32();
-                            let _ = tx.send(VoiceSynthesisUpdate::Done { duration_secs });
-                        }
-                        Err(e) => { let _ = tx.send(VoiceSynthesisUpdate::Error(e.to_string())); }
-                    }
-                }
-                Err(e) => { let _ = tx.send(VoiceSynthesisUpdate::Error(e.to_string())); }
-            }
-        });
+    // ── Voice Studio event handlers ───────────────────────────────────────────
 
-        self.view.redraw(cx);
+    fn on_voice_studio_selected(&mut self, cx: &mut Cx) {
+        self.show_panel(cx, ActivePanel::Voice);
     }
 }
 
@@ -1838,10 +4135,14 @@ impl ModelHubApp {
 
 impl ModelHubApp {
     fn start_load(&mut self, cx: &mut Cx, model_id: &str) {
+        // A stalled load is retried; its late answer is ignored
+        if self.stalled_loads.remove(model_id) {
+            self.load_rxs.remove(model_id);
+        }
         if self.load_rxs.contains_key(model_id) { return; } // already in flight
 
         // Must be downloaded first
-        if self.model_states.get(model_id).copied() != Some(ModelUiState::Downloaded) {
+        if self.dl_state(model_id) != ModelUiState::Downloaded {
             return;
         }
 
@@ -1849,8 +4150,25 @@ impl ModelHubApp {
             .and_then(|r| r.models.iter().find(|m| m.id == model_id)).cloned()
         { Some(m) => m, None => return };
 
+        // The header explains why a model the runtime is too old for can't load
+        if model.runtime_requirement(installed_runtime_version()).is_some() {
+            self.refresh_header_for(cx, model_id);
+            return;
+        }
+
+        // In low power mode a large model loads on the second click
+        let warned = self.power_warned.take();
+        if warned.as_deref() != Some(model_id) && power::load_warning(&model.name, model.runtime.memory_gb).is_some() {
+            self.power_warned = Some(model_id.to_string());
+            self.refresh_header_for(cx, model_id);
+            return;
+        }
+
         self.load_states.insert(model_id.to_string(), ModelLoadState::Loading);
+        self.model_errors.remove(model_id);
+        self.forget_verification(model_id);
         self.refresh_header_for(cx, model_id);
+        self.publish_load_phase(cx, model_id, ModelLoadPhase::Loading);
 
         let api_id = model.runtime.api_model_id.clone();
         let model_type = match model.category {
@@ -1860,14 +4178,20 @@ impl ModelHubApp {
             RegistryCategory::Tts      => "tts",
             RegistryCategory::ImageGen => "image",
             RegistryCategory::VideoGen => "video",
+            RegistryCategory::MusicGen => "music",
+            RegistryCategory::Reranker => "rerank",
         }.to_string();
+        let options = self.runtime_options.get(model_id);
         let (tx, rx) = mpsc::channel::<Result<(), String>>();
         self.load_rxs.insert(model_id.to_string(), rx);
+        self.load_started.insert(model_id.to_string(), std::time::Instant::now());
 
         std::thread::spawn(move || {
             // Auto-start ominix-api if it isn't running yet
+            let client = ModelRuntimeClient::localhost();
             let result = ensure_server_running()
-                .and_then(|()| ModelRuntimeClient::localhost().load_model(&api_id, &model_type));
+                .and_then(|()| client.check_compatible(&model))
+                .and_then(|()| client.load_model_with(&api_id, &model_type, &options));
             let _ = tx.send(result);
         });
 
@@ -1877,6 +4201,12 @@ impl ModelHubApp {
 
     fn start_unload(&mut self, cx: &mut Cx, model_id: &str) {
         if self.unload_rxs.contains_key(model_id) { return; }
+        // Unloading cancels a stalled load
+        if self.stalled_loads.remove(model_id) {
+            self.load_rxs.remove(model_id);
+            self.load_started.remove(model_id);
+            self.model_errors.remove(model_id);
+        }
 
         let model = match self.registry.as_ref()
             .and_then(|r| r.models.iter().find(|m| m.id == model_id)).cloned()
@@ -1884,6 +4214,7 @@ impl ModelHubApp {
 
         // Optimistic update
         self.load_states.insert(model_id.to_string(), ModelLoadState::Unloaded);
+        self.forget_verification(model_id);
         self.refresh_header_for(cx, model_id);
 
         let model_type = match model.category {
@@ -1893,6 +4224,8 @@ impl ModelHubApp {
             RegistryCategory::Tts      => "tts",
             RegistryCategory::ImageGen => "image",
             RegistryCategory::VideoGen => "video",
+            RegistryCategory::MusicGen => "music",
+            RegistryCategory::Reranker => "rerank",
         }.to_string();
         let model_id_owned = model_id.to_string();
         let (tx, rx) = mpsc::channel::<Result<(), String>>();
@@ -1916,7 +4249,13 @@ impl ModelHubApp {
         self.server_status_rx = Some(rx);
 
         std::thread::spawn(move || {
-            let result = ModelRuntimeClient::localhost().list_models();
+            let client = ModelRuntimeClient::localhost();
+            let result = client.list_models();
+            if result.is_ok() {
+                // Remembered for installed_runtime_version(); the server may
+                // have been updated since the last poll
+                let _ = client.version();
+            }
             let _ = tx.send(result);
         });
     }
@@ -1925,12 +4264,15 @@ impl ModelHubApp {
         let done = if let Some(rx) = &self.server_status_rx {
             match rx.try_recv() {
                 Ok(Ok(infos)) => {
-                    let mut changed = false;
+                    let version = installed_runtime_version();
+                    let mut changed = version != self.runtime_version;
+                    self.runtime_version = version;
                     // Build set of loaded IDs reported by server
                     let loaded_api_ids: HashMap<String, ServerModelStatus> = infos.iter()
                         .map(|i| (i.api_id.clone(), i.status))
                         .collect();
 
+                    let mut loaded_elsewhere = Vec::new();
                     if let Some(registry) = &self.registry {
                         for model in &registry.models {
                             let server_status = loaded_api_ids
@@ -1945,14 +4287,26 @@ impl ModelHubApp {
                                 ServerModelStatus::Unloaded => ModelLoadState::Unloaded,
                             };
 
+                            // A stalled load that finished after all
+                            if new_load == ModelLoadState::Loaded && self.stalled_loads.remove(&model.id) {
+                                self.load_rxs.remove(&model.id);
+                                self.load_started.remove(&model.id);
+                                self.model_errors.remove(&model.id);
+                                changed = true;
+                            }
                             let old = self.load_states.get(&model.id).copied().unwrap_or_default();
                             if old != new_load {
                                 self.load_states.insert(model.id.clone(), new_load);
                                 changed = true;
+                                // Tell the shell's selector and the other apps
+                                loaded_elsewhere.push((model.id.clone(), new_load));
                             }
                         }
                     }
 
+                    for (model_id, state) in loaded_elsewhere {
+                        self.publish_load_phase(cx, &model_id, load_phase_for(state));
+                    }
                     if changed {
                         if let Some(sel) = self.selected_id.clone() {
                             self.refresh_header_for(cx, &sel);
@@ -1980,45 +4334,66 @@ impl ModelHubApp {
         let load_ids: Vec<String> = self.load_rxs.keys().cloned().collect();
         let mut load_done:   Vec<String>         = Vec::new();
         let mut load_failed: Vec<(String, String)> = Vec::new();
+        let mut load_stalled: Vec<String>        = Vec::new();
+        let load_timeout = std::time::Duration::from_secs(download_settings::current().load_timeout_mins * 60);
 
         for id in &load_ids {
             if let Some(rx) = self.load_rxs.get(id) {
                 match rx.try_recv() {
                     Ok(Ok(()))    => load_done.push(id.clone()),
                     Ok(Err(e))    => load_failed.push((id.clone(), e)),
-                    Err(mpsc::TryRecvError::Empty) => {}
+                    Err(mpsc::TryRecvError::Empty) => {
+                        let started = self.load_started.get(id).copied();
+                        if !self.stalled_loads.contains(id) && started.is_some_and(|t| t.elapsed() > load_timeout) {
+                            load_stalled.push(id.clone());
+                        }
+                    }
                     Err(mpsc::TryRecvError::Disconnected) => load_done.push(id.clone()),
                 }
             }
         }
 
+        // Keep waiting for the answer, but let the user retry or cancel
+        for id in load_stalled {
+            let err = format!("Load stalled: no answer from ominix-api after {} min", load_timeout.as_secs() / 60);
+            ::log::warn!("{} ({})", err, id);
+            self.stalled_loads.insert(id.clone());
+            self.model_errors.insert(id.clone(), MolyError::from_message(&err));
+            if self.selected_id.as_deref() == Some(id.as_str()) {
+                self.refresh_header_for(cx, &id);
+            }
+            self.view.redraw(cx);
+            self.publish_load_phase(cx, &id, ModelLoadPhase::Failed(err));
+        }
+
         for id in load_done {
             self.load_states.insert(id.clone(), ModelLoadState::Loaded);
             self.load_rxs.remove(&id);
+            self.load_started.remove(&id);
+            if self.stalled_loads.remove(&id) {
+                self.model_errors.remove(&id);
+            }
             if self.selected_id.as_deref() == Some(id.as_str()) {
                 self.refresh_header_for(cx, &id);
             }
-            // Notify shell so the top model selector bar updates
-            if let Some(registry) = &self.registry {
-                if let Some(model) = registry.models.iter().find(|m| m.id == id) {
-                    cx.action(StoreAction::HubModelLoaded {
-                        model_id: id.clone(),
-                        model_name: strip_quant_suffix(&model.name),
-                        category: model.category,
-                    });
-                }
-            }
+            self.publish_load_phase(cx, &id, ModelLoadPhase::Loaded);
+            self.start_smoke_test(cx, &id);
             self.view.redraw(cx);
             ::log::info!("Model loaded: {}", id);
         }
         for (id, err) in load_failed {
             self.load_states.insert(id.clone(), ModelLoadState::LoadError);
+            self.model_errors.insert(id.clone(), MolyError::from_message(&err));
             self.load_rxs.remove(&id);
+            self.load_started.remove(&id);
+            self.stalled_loads.remove(&id);
+            self.forget_verification(&id);
             if self.selected_id.as_deref() == Some(id.as_str()) {
                 self.refresh_header_for(cx, &id);
             }
             self.view.redraw(cx);
             ::log::error!("Load failed for {}: {}", id, err);
+            self.publish_load_phase(cx, &id, ModelLoadPhase::Failed(err));
         }
 
         // --- Unload results ---
@@ -2044,8 +4419,7 @@ impl ModelHubApp {
             if self.selected_id.as_deref() == Some(id.as_str()) {
                 self.refresh_header_for(cx, &id);
             }
-            // Notify shell so the top model selector bar clears
-            cx.action(StoreAction::HubModelUnloaded { model_id: id.clone() });
+            self.publish_load_phase(cx, &id, ModelLoadPhase::Unloaded);
             self.view.redraw(cx);
             ::log::info!("Model unloaded: {}", id);
         }
@@ -2060,11 +4434,149 @@ impl ModelHubApp {
             ::log::error!("Unload failed for {}: {}", id, err);
         }
 
+        // --- Test requests after loading ---
+        let smoke_ids: Vec<String> = self.smoke_rxs.keys().cloned().collect();
+        for id in smoke_ids {
+            let result = match self.smoke_rxs.get(&id).map(|rx| rx.try_recv()) {
+                Some(Ok(result)) => result,
+                Some(Err(mpsc::TryRecvError::Disconnected)) => Err("The test request stopped unexpectedly.".to_string()),
+                _ => continue,
+            };
+            self.smoke_rxs.remove(&id);
+            let verification = match result {
+                Ok(()) => Verification::Verified,
+                Err(err) => {
+                    ::log::warn!("Test request failed for {}: {}", id, err);
+                    Verification::Failed(err)
+                }
+            };
+            self.verifications.insert(id.clone(), verification);
+            if self.selected_id.as_deref() == Some(id.as_str()) {
+                self.refresh_header_for(cx, &id);
+            }
+            self.view.redraw(cx);
+        }
+
         // Keep the frame loop going while operations are in flight
-        if !self.load_rxs.is_empty() || !self.unload_rxs.is_empty() {
+        if !self.load_rxs.is_empty() || !self.unload_rxs.is_empty() || !self.smoke_rxs.is_empty() {
             cx.new_next_frame();
         }
     }
+
+    /// Send a just loaded model a tiny request of its kind, to show whether
+    /// it actually works
+    fn start_smoke_test(&mut self, cx: &mut Cx, model_id: &str) {
+        let model = match self.registry.as_ref()
+            .and_then(|r| r.models.iter().find(|m| m.id == model_id)).cloned()
+        { Some(m) => m, None => return };
+        if !smoke_test::supported(model.category) || self.smoke_rxs.contains_key(model_id) { return; }
+
+        let template = chat_template::template_for(&model, &self.chat_templates);
+        let (tx, rx) = mpsc::channel();
+        self.smoke_rxs.insert(model_id.to_string(), rx);
+        self.verifications.insert(model_id.to_string(), Verification::Checking);
+        std::thread::spawn(move || {
+            let _ = tx.send(smoke_test::run(&model, template));
+        });
+        if self.selected_id.as_deref() == Some(model_id) {
+            self.refresh_header_for(cx, model_id);
+        }
+        cx.new_next_frame();
+    }
+
+    /// Drop the outcome of an earlier test request; a late result is ignored
+    fn forget_verification(&mut self, model_id: &str) {
+        self.verifications.remove(model_id);
+        self.smoke_rxs.remove(model_id);
+    }
+
+    // ── Store events ─────────────────────────────────────────────────────────
+
+    /// Broadcast a load state change so the shell's model selector and other
+    /// apps stay in sync with the hub.
+    fn publish_load_phase(&self, cx: &mut Cx, model_id: &str, phase: ModelLoadPhase) {
+        let Some(model) = self.registry.as_ref()
+            .and_then(|r| r.models.iter().find(|m| m.id == model_id)) else { return };
+        cx.action(StoreEvent::ModelLoadStateChanged {
+            model_id: model_id.to_string(),
+            model_name: strip_quant_suffix(&model.name),
+            category: model.category,
+            phase,
+        });
+    }
+
+    /// Follow loads and unloads started elsewhere (e.g. the shell's model selector).
+    fn handle_store_events(&mut self, cx: &mut Cx, actions: &Actions) {
+        for (id, _, phase) in StoreEvent::model_load_changes(actions) {
+            // Our own operations are already reflected in load_states
+            if self.load_rxs.contains_key(&id) || self.unload_rxs.contains_key(&id) { continue; }
+            let state = load_state_for(&phase);
+            if self.load_states.get(&id) == Some(&state) { continue; }
+            self.load_states.insert(id.clone(), state);
+            // Loaded from elsewhere, e.g. the shell's model selector: check it too
+            if state == ModelLoadState::Loaded {
+                self.start_smoke_test(cx, &id);
+            } else {
+                self.forget_verification(&id);
+            }
+            if self.selected_id.as_deref() == Some(id.as_str()) {
+                self.refresh_header_for(cx, &id);
+            }
+            self.view.redraw(cx);
+        }
+
+        // Check peers, the registry update and the runtime log less often in low power mode
+        if StoreEvent::iter(actions).any(|e| matches!(e, StoreEvent::PowerModeChanged { .. })) {
+            cx.stop_timer(self.peer_timer);
+            self.peer_timer = cx.start_interval(power::interval(2.0));
+            if let Some(sel) = self.selected_id.clone() {
+                self.refresh_header_for(cx, &sel);
+            }
+        }
+
+        // Downloads and removals made in other screens
+        for key in StoreEvent::model_state_changes(actions) {
+            if let Some(sel) = self.selected_id.clone() {
+                if self.storage_key(&sel).as_deref() == Some(key.as_str()) {
+                    self.refresh_header_for(cx, &sel);
+                }
+            }
+            self.view.redraw(cx);
+        }
+    }
+
+    /// Download state of a registry model, as shared with the other screens
+    fn dl_state(&self, model_id: &str) -> ModelUiState {
+        self.registry.as_ref()
+            .and_then(|r| r.get(model_id))
+            .map(|m| ModelUiState::from_status(model_state::registry_download_status(m)))
+            .unwrap_or(ModelUiState::NotDownloaded)
+    }
+
+    /// Key of a registry model in model_state (its expanded storage path)
+    fn storage_key(&self, model_id: &str) -> Option<String> {
+        self.registry.as_ref()
+            .and_then(|r| r.get(model_id))
+            .map(|m| m.storage.expanded_path())
+    }
+}
+
+fn load_state_for(phase: &ModelLoadPhase) -> ModelLoadState {
+    match phase {
+        ModelLoadPhase::Loading   => ModelLoadState::Loading,
+        ModelLoadPhase::Loaded    => ModelLoadState::Loaded,
+        ModelLoadPhase::Unloaded  => ModelLoadState::Unloaded,
+        ModelLoadPhase::Failed(_) => ModelLoadState::LoadError,
+    }
+}
+
+fn load_phase_for(state: ModelLoadState) -> ModelLoadPhase {
+    match state {
+        ModelLoadState::Loading   => ModelLoadPhase::Loading,
+        ModelLoadState::Loaded    => ModelLoadPhase::Loaded,
+        ModelLoadState::Unloaded  => ModelLoadPhase::Unloaded,
+        ModelLoadState::LoadError => ModelLoadPhase::Failed("The runtime reported a load error".to_string()),
+    }
 }
 
 // ─── Inference API calls ──────────────────────────────────────────────────────
@@ -2079,23 +4591,15 @@ impl ModelHubApp {
 
         let (tx, rx) = mpsc::channel();
         self.llm_state.rx = Some(rx);
+        let template = self.registry.as_ref()
+            .and_then(|r| r.models.iter().find(|m| m.id == model_id))
+            .and_then(|m| chat_template::template_for(m, &self.chat_templates));
         std::thread::spawn(move || {
-            let client = reqwest::blocking::Client::builder()
-                .timeout(std::time::Duration::from_secs(120)).build().unwrap();
-            let body = serde_json::json!({
-                "model": model_id,
-                "messages": [
-                    {"role": "system", "content": system},
-                    {"role": "user",   "content": user}
-                ]
-            });
-            let result = client.post("http://localhost:8080/v1/chat/completions")
-                .json(&body).send()
-                .map_err(|e| e.to_string())
-                .and_then(|r| r.json::<serde_json::Value>().map_err(|e| e.to_string()))
-                .and_then(|v| v["choices"][0]["message"]["content"]
-                    .as_str().map(|s| s.to_string())
-                    .ok_or_else(|| "No content in response".to_string()));
+            let _work = shutdown::track("Generating an answer in the LLM Hub", None);
+            let messages = vec![ChatMessage::system(system), ChatMessage::user(user)];
+            let client = OminiXApiClient::localhost(120).recording(EXPORT_LLM);
+            let result = chat_template::complete(&client, &model_id, template, messages)
+                .map_err(String::from);
             let _ = tx.send(result);
         });
         cx.new_next_frame();
@@ -2111,29 +4615,63 @@ impl ModelHubApp {
         let (tx, rx) = mpsc::channel();
         self.vlm_state.rx = Some(rx);
         std::thread::spawn(move || {
-            let client = reqwest::blocking::Client::builder()
-                .timeout(std::time::Duration::from_secs(120)).build().unwrap();
-            let img_b64 = if !image_path.is_empty() {
-                std::fs::read(&image_path).ok()
-                    .map(|b| base64::engine::general_purpose::STANDARD.encode(&b))
-            } else { None };
-            let mut content = vec![serde_json::json!({"type": "text", "text": user})];
-            if let Some(b64) = img_b64 {
-                content.push(serde_json::json!({
-                    "type": "image_url",
-                    "image_url": {"url": format!("data:image/jpeg;base64,{}", b64)}
-                }));
-            }
-            let body = serde_json::json!({"model": model_id, "messages": [{"role": "user", "content": content}]});
-            let result = client.post("http://localhost:8080/v1/chat/completions")
-                .json(&body).send()
-                .map_err(|e| e.to_string())
-                .and_then(|r| r.json::<serde_json::Value>().map_err(|e| e.to_string()))
-                .and_then(|v| v["choices"][0]["message"]["content"]
-                    .as_str().map(|s| s.to_string())
-                    .ok_or_else(|| "No content in response".to_string()));
+            let _work = shutdown::track("Generating an answer in the VLM Hub", None);
+            let image = if image_path.is_empty() { None } else { std::fs::read(&image_path).ok() };
+            let message = match image {
+                Some(bytes) => ChatMessage::user_with_image(user, &base64::engine::general_purpose::STANDARD.encode(&bytes)),
+                None => ChatMessage::user(user),
+            };
+            let request = ChatCompletionRequest { model: model_id, messages: vec![message], max_tokens: None };
+            let result = OminiXApiClient::localhost(120).recording(EXPORT_VLM).chat_completion(&request).map_err(String::from);
+            let _ = tx.send(result);
+        });
+        cx.new_next_frame();
+    }
+
+    /// Use the image at `path` for the next request and preview it
+    fn set_vlm_image(&mut self, cx: &mut Cx, path: String) {
+        self.view.text_input(ids!(hub_vlm_panel.vlm_image_path)).set_text(cx, &path);
+        let preview = self.view.image(ids!(hub_vlm_panel.vlm_preview));
+        let loaded = preview.load_image_file_by_path(cx, Path::new(&path)).is_ok();
+        preview.set_visible(cx, loaded);
+        self.vlm_state.image_path = path;
+        self.view.redraw(cx);
+    }
+
+    /// Take a screenshot in the background; it becomes the panel's image
+    fn capture_vlm_screen(&mut self, cx: &mut Cx, region: bool) {
+        if self.vlm_state.capture_rx.is_some() { return; }
+        let path = artifacts::unique_path("screenshot", "png");
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let result = platform::capture_screen(&path, region)
+                .map(|taken| taken.then(|| path.to_string_lossy().to_string()));
             let _ = tx.send(result);
         });
+        self.vlm_state.capture_rx = Some(rx);
+        let status = if region { "Select a region of the screen..." } else { "Capturing the screen..." };
+        self.view.label(ids!(hub_vlm_panel.vlm_status)).set_text(cx, status);
+        self.view.redraw(cx);
+        cx.new_next_frame();
+    }
+
+    /// Show the text in the image instead of an answer, ready to copy
+    fn extract_vlm_text(&mut self, cx: &mut Cx, model_id: String, image_path: String) {
+        if self.vlm_state.is_running { return; }
+        if image_path.is_empty() {
+            self.view.label(ids!(hub_vlm_panel.vlm_status)).set_text(cx, "Choose an image to read its text.");
+            return;
+        }
+        self.vlm_state.is_running = true;
+        self.view.label(ids!(hub_vlm_panel.vlm_status)).set_text(cx, "Extracting text...");
+        self.view.label(ids!(hub_vlm_panel.vlm_response.output_label)).set_text(cx, "");
+        self.view.redraw(cx);
+
+        let (tx, rx) = mpsc::channel();
+        self.vlm_state.rx = Some(rx);
+        std::thread::spawn(move || {
+            let _ = tx.send(ocr::recognize(&model_id, &image_path));
+        });
         cx.new_next_frame();
     }
 
@@ -2144,6 +4682,18 @@ impl ModelHubApp {
             return;
         }
         self.asr_state.is_running = true;
+        // Drop the previous run's converted audio and segments
+        if self.asr_state.playback_is_temp {
+            artifacts::release(Path::new(&self.asr_state.playback_wav));
+        }
+        self.asr_state.playback_wav.clear();
+        self.asr_state.playback_is_temp = false;
+        self.asr_state.transcript = Transcript::default();
+        self.asr_state.editor = None;
+        self.stop_asr_player();
+        self.view.view(ids!(hub_asr_panel.asr_segments_section)).set_visible(cx, false);
+        self.view.view(ids!(hub_asr_panel.asr_editor_pane)).set_visible(cx, false);
+        self.view.button(ids!(hub_asr_panel.asr_edit_btn)).set_text(cx, "Edit Transcript");
         let is_wav = audio_path.to_lowercase().ends_with(".wav");
         let status_msg = if is_wav { "Transcribing..." } else { "Converting + transcribing..." };
         self.view.label(ids!(hub_asr_panel.asr_status)).set_text(cx, status_msg);
@@ -2151,99 +4701,135 @@ impl ModelHubApp {
         self.view.redraw(cx);
 
         let (tx, rx) = mpsc::channel();
-        self.asr_state.rx = Some(rx);
+        self.asr_state.rx = Some(rx);
+        let name = Path::new(&audio_path).file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        let work = format!("Transcribing {}", name);
+        std::thread::spawn(move || {
+            let _work = shutdown::track(work, None);
+            let client = OminiXApiClient::localhost(1800).recording(EXPORT_ASR);
+            let _ = tx.send(transcribe_file_blocking(&client, &model_id, &audio_path));
+        });
+        cx.new_next_frame();
+    }
+
+    /// Transcribe the recording, have the loaded LLM write notes on it, and
+    /// save them as a new chat
+    fn call_meeting_notes(&mut self, cx: &mut Cx, model_id: String, audio_path: String) {
+        if self.asr_notes.is_running || self.asr_state.is_running { return; }
+        let status = self.view.label(ids!(hub_asr_panel.asr_status));
+        if audio_path.is_empty() {
+            status.set_text(cx, "Enter an audio file path.");
+            return;
+        }
+        let Some(llm) = translate::loaded_llm(&ModelRegistry::load()) else {
+            status.set_text(cx, "Meeting notes are written by a language model — load one in the LLM hub as well.");
+            return;
+        };
+        self.asr_notes.is_running = true;
+        status.set_text(cx, "Transcribing the recording...");
+        self.view.redraw(cx);
+
+        let (tx, rx) = mpsc::channel();
+        self.asr_notes.rx = Some(rx);
+        let name = Path::new(&audio_path).file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        let work = format!("Writing meeting notes for {}", name);
         std::thread::spawn(move || {
-            let client = reqwest::blocking::Client::builder()
-                .timeout(std::time::Duration::from_secs(1800)).build().unwrap();
-
-            // OminiX-API only accepts WAV. Convert non-WAV files using afconvert (macOS built-in).
-            let (wav_path, is_temp) = if !audio_path.to_lowercase().ends_with(".wav") {
-                let tmp = format!("/tmp/ominix_asr_{}.wav",
-                    std::time::SystemTime::now()
-                        .duration_since(std::time::UNIX_EPOCH)
-                        .unwrap_or_default().as_millis());
-                let output = std::process::Command::new("afconvert")
-                    .args(["-f", "WAVE", "-d", "LEI16@16000", "-c", "1", &audio_path, &tmp])
-                    .output();
-                match output {
-                    Ok(o) if o.status.success() => (tmp, true),
-                    Ok(o) => {
-                        let stderr = String::from_utf8_lossy(&o.stderr);
-                        let _ = tx.send(Err(format!("Format conversion failed: {}", stderr.trim())));
-                        return;
-                    }
-                    Err(e) => {
-                        let _ = tx.send(Err(format!("afconvert not available: {}. Please convert to WAV first.", e)));
-                        return;
-                    }
+            let _work = shutdown::track(work, None);
+            let client = OminiXApiClient::localhost(1800);
+            let result = transcribe_file_blocking(&client, &model_id, &audio_path).and_then(|(transcript, wav_path, is_temp)| {
+                if is_temp { artifacts::release(Path::new(&wav_path)); }
+                if transcript.text.trim().is_empty() {
+                    return Err("No speech was recognized in the recording.".to_string());
                 }
-            } else {
-                (audio_path.clone(), false)
-            };
-
-            // Send the WAV file path directly — OminiX-API reads it from disk (no size limit)
-            let body = serde_json::json!({ "file": wav_path, "model": model_id });
-            let result = client.post("http://localhost:8080/v1/audio/transcriptions")
-                .json(&body).send()
-                .map_err(|e| e.to_string())
-                .and_then(|r| {
-                    let status = r.status();
-                    let text = r.text().map_err(|e| e.to_string())?;
-                    if !status.is_success() {
-                        return Err(format!("HTTP {}: {}", status, text.chars().take(300).collect::<String>()));
-                    }
-                    serde_json::from_str::<serde_json::Value>(&text)
-                        .map_err(|e| format!("Bad JSON ({}): {}", e, text.chars().take(200).collect::<String>()))
+                meeting_notes::write(&llm, &audio_path, &transcript.text, |done, total| {
+                    let step = if total > 1 { format!(" ({} of {})", (done + 1).min(total), total) } else { String::new() };
+                    let _ = tx.send(NotesUpdate::Status(format!("Writing notes with {}{}...", llm.name, step)));
                 })
-                .and_then(|v| v["text"].as_str().map(|s| s.to_string())
-                    .ok_or_else(|| format!("No 'text' field in response: {}", v)));
-            // Clean up temp WAV after the request completes
-            if is_temp { let _ = std::fs::remove_file(&wav_path); }
-            let _ = tx.send(result);
+            });
+            let _ = tx.send(NotesUpdate::Finished(result));
         });
         cx.new_next_frame();
     }
 
+    fn poll_meeting_notes(&mut self, cx: &mut Cx, scope: &mut Scope) {
+        let Some(rx) = &self.asr_notes.rx else { return };
+        let mut finished = None;
+        while let Ok(update) = rx.try_recv() {
+            match update {
+                NotesUpdate::Status(text) => self.view.label(ids!(hub_asr_panel.asr_status)).set_text(cx, &text),
+                NotesUpdate::Finished(result) => { finished = Some(result); break; }
+            }
+        }
+        let Some(result) = finished else {
+            cx.new_next_frame();
+            return;
+        };
+        self.asr_notes.is_running = false;
+        self.asr_notes.rx = None;
+        let msg = match result {
+            Ok(notes) => {
+                self.view.label(ids!(hub_asr_panel.asr_transcript.output_label)).set_text(cx, &notes.transcript);
+                match scope.data.get_mut::<Store>() {
+                    Some(store) => {
+                        let chat_id = notes.save(&mut store.chats);
+                        cx.action(StoreAction::OpenChat { chat_id, message_index: None });
+                        "Notes saved as a new chat.".to_string()
+                    }
+                    None => "Error: the notes couldn't be saved.".to_string(),
+                }
+            }
+            Err(e) => format!("Error: {}", error_text(&e)),
+        };
+        self.view.label(ids!(hub_asr_panel.asr_status)).set_text(cx, &msg);
+        self.view.redraw(cx);
+    }
+
     fn call_tts(&mut self, cx: &mut Cx, model_id: String, voice_id: String, text: String) {
         if self.tts_state.is_running { return; }
         if text.is_empty() {
             self.view.label(ids!(hub_tts_panel.tts_status)).set_text(cx, "Enter text to synthesize.");
             return;
         }
+        let chunks = split_into_chunks(&text, DEFAULT_TTS_CHUNK_CHARS);
+        let cancel = Arc::new(AtomicBool::new(false));
         self.tts_state.is_running = true;
         self.tts_state.output_path.clear();
-        self.view.label(ids!(hub_tts_panel.tts_status)).set_text(cx, "Generating audio...");
+        self.tts_state.cancel = Some(cancel.clone());
+        let status = if chunks.len() > 1 {
+            format!("Generating chunk 1/{}...", chunks.len())
+        } else {
+            "Generating audio...".to_string()
+        };
+        self.view.label(ids!(hub_tts_panel.tts_status)).set_text(cx, &status);
         self.view.view(ids!(hub_tts_panel.tts_result_row)).set_visible(cx, false);
+        self.view.widget(ids!(hub_tts_panel.tts_cancel_btn)).set_visible(cx, chunks.len() > 1);
         self.view.redraw(cx);
 
         let (tx, rx) = mpsc::channel();
         self.tts_state.rx = Some(rx);
         let voice = if voice_id.is_empty() { "vivian".to_string() } else { voice_id };
         std::thread::spawn(move || {
-            let client = reqwest::blocking::Client::builder()
-                .timeout(std::time::Duration::from_secs(120)).build().unwrap();
-            let body = serde_json::json!({"model": model_id, "input": text, "voice": voice});
-            let result = client.post("http://localhost:8080/v1/audio/speech")
-                .json(&body).send()
-                .map_err(|e| e.to_string())
-                .and_then(|r| {
-                    if !r.status().is_success() { return Err(format!("HTTP {}", r.status())); }
-                    r.bytes().map_err(|e| e.to_string())
-                })
-                .and_then(|b| {
-                    let out = "/tmp/ominix-hub-tts.wav";
-                    std::fs::write(out, &b).map_err(|e| e.to_string())?;
-                    std::process::Command::new("afplay").arg(out).spawn().map_err(|e| e.to_string())?;
-                    Ok(())
-                });
-            let _ = tx.send(result);
+            let _work = shutdown::track("Generating speech", Some(cancel.clone()));
+            let client = OminiXApiClient::localhost(120).recording(EXPORT_TTS);
+            let progress_tx = tx.clone();
+            let result = synthesize_chunks(&client, &model_id, &voice, &chunks, &cancel, |done, total| {
+                let _ = progress_tx.send(TtsUpdate::Progress { done, total });
+            })
+            .and_then(|wav| {
+                let Some(wav) = wav else { return Ok(false) };
+                let out = artifacts::session_file("hub-tts.wav");
+                std::fs::write(&out, &wav).map_err(|e| e.to_string())?;
+                platform::play_audio(&out)?;
+                Ok(true)
+            });
+            let _ = tx.send(TtsUpdate::Finished(result));
         });
         cx.new_next_frame();
     }
 
-    fn call_image(&mut self, cx: &mut Cx, model_id: String, prompt: String, neg_prompt: String) {
+    fn call_image(&mut self, cx: &mut Cx, request: ImageGenerationRequest, init_path: Option<String>, mask_path: Option<String>) {
         if self.image_state.is_running { return; }
-        if prompt.is_empty() {
+        if request.prompt.is_empty() {
             self.view.label(ids!(hub_image_panel.img_status)).set_text(cx, "Enter a prompt.");
             return;
         }
@@ -2253,39 +4839,17 @@ impl ModelHubApp {
         self.view.image(ids!(hub_image_panel.img_preview)).set_visible(cx, false);
         self.view.redraw(cx);
 
-        // Build a filename slug from the prompt (first 40 chars, alphanumeric + dash)
-        let slug: String = prompt.chars()
-            .take(40)
-            .map(|c| if c.is_alphanumeric() { c.to_ascii_lowercase() } else { '-' })
-            .collect::<String>()
-            .trim_matches('-')
-            .to_string();
-        let slug = if slug.is_empty() { "image".to_string() } else { slug };
-        // Collapse runs of dashes
-        let slug = slug.split('-').filter(|s| !s.is_empty()).collect::<Vec<_>>().join("-");
-        let out_path = format!("/tmp/ominix-{}.png", slug);
-
         let (tx, rx) = mpsc::channel();
         self.image_state.rx = Some(rx);
+        self.image_state.request = Some(request.clone());
         std::thread::spawn(move || {
-            let client = reqwest::blocking::Client::builder()
-                .timeout(std::time::Duration::from_secs(300)).build().unwrap();
-            let mut body = serde_json::json!({
-                "model": model_id, "prompt": prompt,
-                "n": 1, "size": "512x512", "response_format": "b64_json"
-            });
-            if !neg_prompt.is_empty() { body["negative_prompt"] = serde_json::Value::String(neg_prompt); }
-            let result = client.post("http://localhost:8080/v1/images/generations")
-                .json(&body).send()
-                .map_err(|e| e.to_string())
-                .and_then(|r| r.json::<serde_json::Value>().map_err(|e| e.to_string()))
-                .and_then(|v| {
-                    let b64 = v["data"][0]["b64_json"].as_str()
-                        .ok_or_else(|| "No image data".to_string())?;
-                    let bytes = base64::engine::general_purpose::STANDARD.decode(b64)
-                        .map_err(|e| e.to_string())?;
-                    std::fs::write(&out_path, &bytes).map_err(|e| e.to_string())?;
-                    Ok(out_path)
+            let _work = shutdown::track("Generating an image", None);
+            let mut request = request;
+            let result = attach_image_sources(&mut request, init_path.as_deref(), mask_path.as_deref())
+                .and_then(|()| {
+                    OminiXApiClient::localhost(300).recording(EXPORT_IMAGE).generate_image(&request)
+                        .and_then(|resp| resp.first_bytes())
+                        .map_err(String::from)
                 });
             let _ = tx.send(result);
         });
@@ -2313,11 +4877,12 @@ impl ModelHubApp {
             .collect::<String>().trim_matches('-').to_string();
         let slug = if slug.is_empty() { "edit".to_string() } else { slug };
         let slug = slug.split('-').filter(|s| !s.is_empty()).collect::<Vec<_>>().join("-");
-        let out_path = format!("/tmp/ominix-edit-{}.png", slug);
+        let out_path = artifacts::unique_path(&format!("hub-edit-{}", slug), "png");
 
         let (tx, rx) = mpsc::channel();
         self.image_edit_state.rx = Some(rx);
         std::thread::spawn(move || {
+            let _work = shutdown::track("Editing an image", None);
             // Ensure ominix-api is running (sd.cpp models don't go through load_model)
             if let Err(e) = ensure_server_running() {
                 let _ = tx.send(Err(format!("Failed to start server: {}", e)));
@@ -2330,36 +4895,16 @@ impl ModelHubApp {
                 Err(e) => { let _ = tx.send(Err(format!("Failed to read image: {}", e))); return; }
             };
 
-            let client = reqwest::blocking::Client::builder()
-                .timeout(std::time::Duration::from_secs(600)).build().unwrap();
-            let body = serde_json::json!({
-                "model": model_id,
-                "prompt": prompt,
-                "image": image_b64,
-                "n": 1, "size": "512x512", "response_format": "b64_json"
-            });
-            let result = client.post("http://localhost:8080/v1/images/generations")
-                .json(&body).send()
-                .map_err(|e| e.to_string())
-                .and_then(|r| {
-                    let status = r.status();
-                    let text = r.text().map_err(|e| e.to_string())?;
-                    if !status.is_success() {
-                        return Err(format!("HTTP {}: {}", status, text));
-                    }
-                    serde_json::from_str::<serde_json::Value>(&text).map_err(|e| e.to_string())
-                })
-                .and_then(|v| {
-                    // Check for API error response
-                    if let Some(err) = v.get("error") {
-                        return Err(format!("API error: {}", err));
-                    }
-                    let b64 = v["data"][0]["b64_json"].as_str()
-                        .ok_or_else(|| format!("No image data in response: {}", v))?;
-                    let bytes = base64::engine::general_purpose::STANDARD.decode(b64)
-                        .map_err(|e| e.to_string())?;
+            let request = ImageGenerationRequest {
+                image: Some(image_b64),
+                ..ImageGenerationRequest::new(model_id, prompt)
+            };
+            let result = OminiXApiClient::localhost(600).recording(EXPORT_IMAGE_EDIT).generate_image(&request)
+                .and_then(|resp| resp.first_bytes())
+                .map_err(String::from)
+                .and_then(|bytes| {
                     std::fs::write(&out_path, &bytes).map_err(|e| e.to_string())?;
-                    Ok(out_path)
+                    Ok(out_path.to_string_lossy().to_string())
                 });
             let _ = tx.send(result);
         });
@@ -2385,40 +4930,86 @@ impl ModelHubApp {
             .to_string();
         let slug = if slug.is_empty() { "video".to_string() } else { slug };
         let slug = slug.split('-').filter(|s| !s.is_empty()).collect::<Vec<_>>().join("-");
-        let out_path = format!("/tmp/ominix-{}.mp4", slug);
+        let out_path = artifacts::unique_path(&format!("hub-{}", slug), "mp4");
 
         let (tx, rx) = mpsc::channel();
         self.video_state.rx = Some(rx);
         std::thread::spawn(move || {
-            let client = reqwest::blocking::Client::builder()
-                .timeout(std::time::Duration::from_secs(600)).build().unwrap();
-            let body = serde_json::json!({
-                "model": model_id,
-                "prompt": prompt,
-                "response_format": "b64_json"
-            });
-            let result = client.post("http://localhost:8080/v1/videos/generations")
-                .json(&body).send()
-                .map_err(|e| e.to_string())
-                .and_then(|r| {
-                    let status = r.status();
-                    let text = r.text().map_err(|e| e.to_string())?;
-                    if !status.is_success() {
-                        return Err(format!("HTTP {}: {}", status, text));
-                    }
-                    serde_json::from_str::<serde_json::Value>(&text).map_err(|e| e.to_string())
+            let _work = shutdown::track("Generating a video", None);
+            let request = VideoGenerationRequest {
+                model: model_id,
+                prompt,
+                response_format: "b64_json".to_string(),
+            };
+            let result = OminiXApiClient::localhost(600).recording(EXPORT_VIDEO).generate_video(&request)
+                .and_then(|resp| resp.first_bytes())
+                .map_err(String::from)
+                .and_then(|bytes| {
+                    std::fs::write(&out_path, &bytes).map_err(|e| e.to_string())?;
+                    Ok(out_path.to_string_lossy().to_string())
+                });
+            let _ = tx.send(result);
+        });
+        cx.new_next_frame();
+    }
+
+    fn call_music(&mut self, cx: &mut Cx, request: MusicGenerationRequest) {
+        if self.music_state.is_running { return; }
+        if request.prompt.is_empty() {
+            self.view.label(ids!(hub_music_panel.mus_status)).set_text(cx, "Enter a prompt.");
+            return;
+        }
+        self.music_state.is_running = true;
+        self.view.label(ids!(hub_music_panel.mus_status)).set_text(cx, "Generating music...");
+        self.view.view(ids!(hub_music_panel.mus_result_row)).set_visible(cx, false);
+        self.view.redraw(cx);
+
+        let slug = request.prompt.chars()
+            .take(40)
+            .map(|c| if c.is_alphanumeric() { c.to_ascii_lowercase() } else { '-' })
+            .collect::<String>()
+            .split('-')
+            .filter(|s| !s.is_empty())
+            .collect::<Vec<_>>()
+            .join("-");
+        let slug = if slug.is_empty() { "music".to_string() } else { slug };
+        let out_path = artifacts::unique_path(&format!("hub-music-{}", slug), "wav");
+
+        let (tx, rx) = mpsc::channel();
+        self.music_state.rx = Some(rx);
+        std::thread::spawn(move || {
+            let _work = shutdown::track("Generating music", None);
+            let progress_tx = tx.clone();
+            let result = OminiXApiClient::localhost(600).recording(EXPORT_MUSIC)
+                .generate_music(&request, |fraction| {
+                    let _ = progress_tx.send(MusicUpdate::Progress(fraction));
                 })
-                .and_then(|v| {
-                    if let Some(err) = v.get("error") {
-                        return Err(format!("API error: {}", err));
-                    }
-                    let b64 = v["data"][0]["b64_json"].as_str()
-                        .ok_or_else(|| format!("No video data in response: {}", v))?;
-                    let bytes = base64::engine::general_purpose::STANDARD.decode(b64)
-                        .map_err(|e| e.to_string())?;
+                .map_err(String::from)
+                .and_then(|bytes| {
                     std::fs::write(&out_path, &bytes).map_err(|e| e.to_string())?;
-                    Ok(out_path)
+                    Ok(out_path.to_string_lossy().to_string())
                 });
+            let _ = tx.send(MusicUpdate::Finished(result));
+        });
+        cx.new_next_frame();
+    }
+
+    fn call_rerank(&mut self, cx: &mut Cx, request: RerankRequest) {
+        if self.rerank_state.is_running { return; }
+        self.rerank_state.is_running = true;
+        self.view.label(ids!(hub_rerank_panel.rerank_status))
+            .set_text(cx, &format!("Ranking {} passages...", request.documents.len()));
+        self.view.label(ids!(hub_rerank_panel.rerank_results)).set_text(cx, "");
+        self.view.redraw(cx);
+
+        let (tx, rx) = mpsc::channel();
+        self.rerank_state.rx = Some(rx);
+        std::thread::spawn(move || {
+            let result = OminiXApiClient::localhost(120).recording(EXPORT_RERANK).rerank(&request)
+                .map(|results| results.into_iter()
+                    .map(|r| (request.documents[r.index].clone(), r.relevance_score))
+                    .collect())
+                .map_err(String::from);
             let _ = tx.send(result);
         });
         cx.new_next_frame();
@@ -2428,14 +5019,9 @@ impl ModelHubApp {
         let (tx, rx) = mpsc::channel();
         self.tts_state.voices_rx = Some(rx);
         std::thread::spawn(move || {
-            let client = reqwest::blocking::Client::builder()
-                .timeout(std::time::Duration::from_secs(10)).build().unwrap();
-            let result = client.get("http://localhost:8080/v1/voices").send()
-                .map_err(|e| e.to_string())
-                .and_then(|r| r.json::<serde_json::Value>().map_err(|e| e.to_string()))
-                .map(|v| v["voices"].as_array()
-                    .map(|a| a.iter().filter_map(|x| x.as_str().map(|s| s.to_string())).collect::<Vec<_>>())
-                    .unwrap_or_default());
+            let result = OminiXApiClient::localhost(10).list_voices()
+                .map(|voices| voices.into_iter().map(|v| v.name).collect::<Vec<_>>())
+                .map_err(String::from);
             let _ = tx.send(result);
         });
     }
@@ -2444,18 +5030,42 @@ impl ModelHubApp {
 // ─── Download operations ──────────────────────────────────────────────────────
 
 impl ModelHubApp {
+    /// Disk space pre-flight: refuse a download that can't fit, with the
+    /// shortfall in the header, and note when it leaves the disk nearly full
+    fn check_disk_space(&mut self, cx: &mut Cx, model: &RegistryModel) -> bool {
+        let dest = model.storage.expanded_path();
+        let space = disk_space::check_download_space(Path::new(&dest), model.storage.size_bytes, 0);
+        let note = space.message();
+        if let SpaceCheck::Short { .. } = space {
+            let msg = note.unwrap_or_default();
+            ::log::warn!("Not downloading {}: {}", model.id, msg);
+            self.model_errors.insert(model.id.clone(), MolyError::DiskFull(msg));
+            model_state::set_download_status(cx, &dest, DownloadStatus::Error);
+            self.refresh_header_for(cx, &model.id);
+            return false;
+        }
+        match note {
+            Some(note) => { self.download_notes.insert(model.id.clone(), note); }
+            None => { self.download_notes.remove(&model.id); }
+        }
+        true
+    }
+
     fn start_download(&mut self, cx: &mut Cx, model_id: &str) {
         let Some(model) = self.registry.as_ref()
             .and_then(|r| r.models.iter().find(|m| m.id == model_id)).cloned()
         else { return };
         if model.source.kind == SourceKind::Manual { return; }
 
+        if !self.check_disk_space(cx, &model) { return; }
+
         let ds = self.download_states
             .entry(model_id.to_string()).or_insert_with(ModelDownloadState::new).clone();
         ds.reset();
         ds.is_downloading.store(true, Ordering::SeqCst);
+        self.model_errors.remove(model_id);
 
-        self.model_states.insert(model_id.to_string(), ModelUiState::Downloading);
+        model_state::set_download_status(cx, &model.storage.expanded_path(), DownloadStatus::Downloading);
         self.refresh_header_for(cx, model_id);
         cx.new_next_frame();
 
@@ -2465,6 +5075,9 @@ impl ModelHubApp {
         let source_url     = model.source.url.clone().unwrap_or_default();
         let repo_id        = model.source.repo_id.clone().unwrap_or_default();
         let revision       = model.source.revision.clone();
+        // An update fetches its changed files again and deletes removed ones
+        let update         = self.model_updates.get(model_id).cloned().unwrap_or_default();
+        let refresh: HashSet<String> = update.changed.iter().map(|f| f.path.clone()).collect();
         // Collect extra sources for multi-file models
         let extra_sources: Vec<(String, String)> = model.extra_sources.iter()
             .filter_map(|es| {
@@ -2474,9 +5087,24 @@ impl ModelHubApp {
             })
             .collect();
 
+        let work = format!("Downloading {}", model.name);
         std::thread::spawn(move || {
-            let client = match reqwest::blocking::Client::builder()
-                .timeout(std::time::Duration::from_secs(3600)).build()
+            let _work = shutdown::track(work, Some(ds.cancel_requested.clone()));
+            ds.queued.store(true, Ordering::SeqCst);
+            let slot = download_settings::acquire_slot(&ds.cancel_requested);
+            ds.queued.store(false, Ordering::SeqCst);
+            let Some(_slot) = slot else {
+                *ds.error_msg.lock().unwrap() = "Cancelled".to_string();
+                ds.failed.store(true, Ordering::SeqCst);
+                ds.is_downloading.store(false, Ordering::SeqCst);
+                return;
+            };
+            // The blocking client's timeout applies to each read, so a
+            // connection that goes quiet fails after `stall_secs`
+            let settings = download_settings::current();
+            let client = match moly_data::network::blocking_client()
+                .connect_timeout(std::time::Duration::from_secs(30))
+                .timeout(std::time::Duration::from_secs(settings.stall_secs)).build()
             {
                 Ok(c) => c,
                 Err(e) => {
@@ -2486,12 +5114,52 @@ impl ModelHubApp {
                     return;
                 }
             };
-            // Download primary source
-            let result = match source_kind {
-                SourceKind::HuggingFace => download_hf(&client, &repo_id, &revision, &local_path, &ds),
-                SourceKind::ModelScope  => download_ms(&client, &repo_id, &revision, &local_path, &ds),
-                SourceKind::DirectUrl   => download_direct_url(&client, &source_url, &local_path, &ds),
-                _                       => Err("Source not supported".to_string()),
+            let download = || -> Result<(), String> {
+                // Download primary source
+                match source_kind {
+                    SourceKind::HuggingFace => download_hf(&client, &repo_id, &revision, &local_path, &refresh, &ds)?,
+                    SourceKind::ModelScope  => download_ms(&client, &repo_id, &revision, &local_path, &refresh, &ds)?,
+                    SourceKind::DirectUrl   => download_direct_url(&client, &source_url, &local_path, &ds)?,
+                    _                       => return Err("Source not supported".to_string()),
+                }
+                // Download extra sources sequentially
+                for (url, dir) in &extra_sources {
+                    if ds.cancel_requested.load(Ordering::SeqCst) { break; }
+                    download_direct_url(&client, url, dir, &ds)?;
+                }
+                Ok(())
+            };
+            // Retry a stalled download; files already complete are kept
+            let mut attempt = 0;
+            let result = loop {
+                match download() {
+                    Err(e) if download_settings::is_stall(&e) && attempt < settings.stall_retries
+                        && !ds.cancel_requested.load(Ordering::SeqCst) =>
+                    {
+                        attempt += 1;
+                        let delay = download_settings::retry_delay(attempt);
+                        ::log::warn!("Download of {} stalled ({}), retry {} of {} in {:?}",
+                            model_id_owned, e, attempt, settings.stall_retries, delay);
+                        *ds.stall_note.lock().unwrap() = format!(
+                            "Stalled — no data for {} s. Retrying in {} s (retry {} of {})…",
+                            settings.stall_secs, delay.as_secs(), attempt, settings.stall_retries);
+                        let until = std::time::Instant::now() + delay;
+                        while std::time::Instant::now() < until && !ds.cancel_requested.load(Ordering::SeqCst) {
+                            std::thread::sleep(std::time::Duration::from_millis(250));
+                        }
+                        ds.stall_note.lock().unwrap().clear();
+                        if ds.cancel_requested.load(Ordering::SeqCst) {
+                            break Err("Cancelled".to_string());
+                        }
+                        ds.total_bytes.store(0, Ordering::SeqCst);
+                        ds.rate.lock().unwrap().reset();
+                    }
+                    Err(e) if download_settings::is_stall(&e) => {
+                        break Err(format!("Download stalled: no data for {} s after {} retries ({})",
+                            settings.stall_secs, attempt, e));
+                    }
+                    result => break result,
+                }
             };
             if let Err(e) = result {
                 *ds.error_msg.lock().unwrap() = e;
@@ -2499,16 +5167,23 @@ impl ModelHubApp {
                 ds.is_downloading.store(false, Ordering::SeqCst);
                 return;
             }
-            // Download extra sources sequentially
-            for (url, dir) in extra_sources {
-                if ds.cancel_requested.load(Ordering::SeqCst) { break; }
-                if let Err(e) = download_direct_url(&client, &url, &dir, &ds) {
-                    *ds.error_msg.lock().unwrap() = e;
+            if download_settings::current().verify_after_download {
+                if let Integrity::Incomplete { missing, truncated } = model_integrity::check_registry_model(&model) {
+                    let mut files: Vec<&str> = missing.iter().chain(&truncated).map(String::as_str).collect();
+                    files.truncate(3);
+                    *ds.error_msg.lock().unwrap() = format!(
+                        "Download incomplete: {} file(s) missing, {} truncated ({})",
+                        missing.len(), truncated.len(), files.join(", "));
                     ds.failed.store(true, Ordering::SeqCst);
                     ds.is_downloading.store(false, Ordering::SeqCst);
                     return;
                 }
             }
+            for path in &update.removed {
+                if let Err(e) = std::fs::remove_file(Path::new(&local_path).join(path)) {
+                    ::log::warn!("Couldn't remove {} after updating {}: {}", path, model_id_owned, e);
+                }
+            }
             ds.completed.store(true, Ordering::SeqCst);
             ds.is_downloading.store(false, Ordering::SeqCst);
             ::log::info!("Download finished: {}", model_id_owned);
@@ -2529,19 +5204,34 @@ impl ModelHubApp {
         }
 
         for id in done {
-            self.model_states.insert(id.clone(), ModelUiState::Downloaded);
+            if let Some(key) = self.storage_key(&id) {
+                model_state::set_download_status(cx, &key, DownloadStatus::Downloaded);
+            }
             self.download_states.remove(&id);
+            self.download_notes.remove(&id);
+            self.model_updates.remove(&id);
             if self.selected_id.as_deref() == Some(id.as_str()) {
                 self.refresh_header_for(cx, &id);
             }
+            cx.action(StoreEvent::DownloadCompleted { model_id: id });
         }
         for (id, err) in failed {
-            self.model_states.insert(id.clone(), ModelUiState::Error);
+            if let Some(key) = self.storage_key(&id) {
+                model_state::set_download_status(cx, &key, DownloadStatus::Error);
+            }
             self.download_states.remove(&id);
+            self.download_notes.remove(&id);
+            self.model_errors.insert(id.clone(), MolyError::from_message(&err));
             if self.selected_id.as_deref() == Some(id.as_str()) {
                 self.refresh_header_for(cx, &id);
             }
             ::log::error!("Download error for {}: {}", id, err);
+            cx.action(StoreEvent::DownloadFailed { model_id: id, error: err });
+        }
+
+        if !self.bulk_downloads.is_empty() {
+            let summary = self.bulk_download_summary();
+            self.view.label(ids!(hub_bulk_status)).set_text(cx, &summary);
         }
 
         // Live progress for the selected model
@@ -2550,39 +5240,9 @@ impl ModelHubApp {
                 if ds.is_downloading.load(Ordering::SeqCst) {
                     let pct = ds.fraction();
                     let txt = ds.progress_text();
-                    let panel = self.active_panel;
-                    match panel {
-                        ActivePanel::Llm => {
-                            self.view.view(ids!(hub_llm_panel.hub_panel_header.panel_progress_fill)).apply_over(cx, live! { draw_bg: { progress: (pct) } });
-                            self.view.label(ids!(hub_llm_panel.hub_panel_header.panel_progress_text)).set_text(cx, &txt);
-                        }
-                        ActivePanel::Vlm => {
-                            self.view.view(ids!(hub_vlm_panel.hub_panel_header.panel_progress_fill)).apply_over(cx, live! { draw_bg: { progress: (pct) } });
-                            self.view.label(ids!(hub_vlm_panel.hub_panel_header.panel_progress_text)).set_text(cx, &txt);
-                        }
-                        ActivePanel::Asr => {
-                            self.view.view(ids!(hub_asr_panel.hub_panel_header.panel_progress_fill)).apply_over(cx, live! { draw_bg: { progress: (pct) } });
-                            self.view.label(ids!(hub_asr_panel.hub_panel_header.panel_progress_text)).set_text(cx, &txt);
-                        }
-                        ActivePanel::Tts => {
-                            self.view.view(ids!(hub_tts_panel.hub_panel_header.panel_progress_fill)).apply_over(cx, live! { draw_bg: { progress: (pct) } });
-                            self.view.label(ids!(hub_tts_panel.hub_panel_header.panel_progress_text)).set_text(cx, &txt);
-                        }
-                        ActivePanel::Image => {
-                            self.view.view(ids!(hub_image_panel.hub_panel_header.panel_progress_fill)).apply_over(cx, live! { draw_bg: { progress: (pct) } });
-                            self.view.label(ids!(hub_image_panel.hub_panel_header.panel_progress_text)).set_text(cx, &txt);
-                        }
-                        ActivePanel::ImageEdit => {
-                            self.view.view(ids!(hub_image_edit_panel.hub_panel_header.panel_progress_fill)).apply_over(cx, live! { draw_bg: { progress: (pct) } });
-                            self.view.label(ids!(hub_image_edit_panel.hub_panel_header.panel_progress_text)).set_text(cx, &txt);
-                        }
-                        ActivePanel::Video => {
-                            self.view.view(ids!(hub_video_panel.hub_panel_header.panel_progress_fill)).apply_over(cx, live! { draw_bg: { progress: (pct) } });
-                            self.view.label(ids!(hub_video_panel.hub_panel_header.panel_progress_text)).set_text(cx, &txt);
-                        }
-                        ActivePanel::Voice | ActivePanel::Info | ActivePanel::None => {}
+                    if let Some(header) = self.active_header() {
+                        header.set_progress(cx, pct, &txt);
                     }
-                    self.view.redraw(cx);
                 }
             }
         }
@@ -2603,7 +5263,7 @@ impl ModelHubApp {
                             match result {
                                 Ok(t)  => { self.view.label($label).set_text(cx, &t);
                                             self.view.label($status).set_text(cx, "Done."); }
-                                Err(e) => { self.view.label($status).set_text(cx, &format!("Error: {}", e)); }
+                                Err(e) => { self.view.label($status).set_text(cx, &format!("Error: {}", error_text(&e))); }
                             }
                             $state.is_running = false;
                             $state.rx = None;
@@ -2620,13 +5280,78 @@ impl ModelHubApp {
         poll_string_rx!(self.vlm_state,
             ids!(hub_vlm_panel.vlm_response.output_label),
             ids!(hub_vlm_panel.vlm_status));
-        poll_string_rx!(self.asr_state,
-            ids!(hub_asr_panel.asr_transcript.output_label),
-            ids!(hub_asr_panel.asr_status));
+        if let Some(rx) = &self.vlm_state.capture_rx {
+            match rx.try_recv() {
+                Err(mpsc::TryRecvError::Empty) => cx.new_next_frame(),
+                result => {
+                    self.vlm_state.capture_rx = None;
+                    let status = match result {
+                        Ok(Ok(Some(path))) => {
+                            self.set_vlm_image(cx, path);
+                            "Screenshot attached.".to_string()
+                        }
+                        Ok(Ok(None)) => "Capture cancelled.".to_string(),
+                        Ok(Err(e)) => format!("Error: {}", e),
+                        Err(_) => "Error: the screen capture stopped unexpectedly.".to_string(),
+                    };
+                    self.view.label(ids!(hub_vlm_panel.vlm_status)).set_text(cx, &status);
+                    redraw = true;
+                }
+            }
+        }
+        // ASR: custom poll to fill the segment list and enable subtitle export
+        if self.asr_state.is_running {
+            if let Some(rx) = &self.asr_state.rx {
+                if let Ok(result) = rx.try_recv() {
+                    match result {
+                        Ok((transcript, wav_path, is_temp)) => {
+                            self.view.label(ids!(hub_asr_panel.asr_transcript.output_label)).set_text(cx, &transcript.text);
+                            let status = if transcript.has_timestamps() {
+                                format!("Done — {} segments.", transcript.segments.len())
+                            } else {
+                                "Done. (No timestamps returned; subtitle export unavailable.)".to_string()
+                            };
+                            self.view.label(ids!(hub_asr_panel.asr_status)).set_text(cx, &status);
+                            self.view.view(ids!(hub_asr_panel.asr_segments_section)).set_visible(cx, transcript.has_timestamps());
+                            self.asr_state.transcript = transcript;
+                            self.asr_state.playback_wav = wav_path;
+                            self.asr_state.playback_is_temp = is_temp;
+                        }
+                        Err(e) => {
+                            self.view.label(ids!(hub_asr_panel.asr_status)).set_text(cx, &format!("Error: {}", error_text(&e)));
+                        }
+                    }
+                    self.asr_state.is_running = false;
+                    self.asr_state.rx = None;
+                    redraw = true;
+                } else { cx.new_next_frame(); }
+            }
+        }
+        // Prompt enhancement
+        if let Some(rx) = &self.image_state.enhance_rx {
+            if let Ok(result) = rx.try_recv() {
+                self.image_state.enhance_rx = None;
+                match result {
+                    Ok(enhanced) => self.apply_enhanced_prompt(cx, enhanced),
+                    Err(e) => {
+                        self.view.label(ids!(hub_image_panel.img_enhance_status)).set_text(cx, &format!("Error: {}", error_text(&e)));
+                    }
+                }
+                redraw = true;
+            } else { cx.new_next_frame(); }
+        }
         // Image gen: custom poll so we can show preview + result row
         if self.image_state.is_running {
             if let Some(rx) = &self.image_state.rx {
                 if let Ok(result) = rx.try_recv() {
+                    // Keep the image with its settings in the history
+                    let request = self.image_state.request.take();
+                    let result = result.and_then(|bytes| {
+                        let request = request.ok_or("No request recorded for this image")?;
+                        let entry = self.image_history.add(&request, &bytes)?;
+                        Ok(entry.file.to_string_lossy().to_string())
+                    });
+                    self.update_image_history_empty(cx);
                     match result {
                         Ok(path) => {
                             self.view.label(ids!(hub_image_panel.img_status)).set_text(cx, "Done.");
@@ -2640,7 +5365,7 @@ impl ModelHubApp {
                             self.image_state.output_path = path;
                         }
                         Err(e) => {
-                            self.view.label(ids!(hub_image_panel.img_status)).set_text(cx, &format!("Error: {}", e));
+                            self.view.label(ids!(hub_image_panel.img_status)).set_text(cx, &format!("Error: {}", error_text(&e)));
                         }
                     }
                     self.image_state.is_running = false;
@@ -2665,7 +5390,7 @@ impl ModelHubApp {
                             self.image_edit_state.output_path = path;
                         }
                         Err(e) => {
-                            self.view.label(ids!(hub_image_edit_panel.img_edit_status)).set_text(cx, &format!("Error: {}", e));
+                            self.view.label(ids!(hub_image_edit_panel.img_edit_status)).set_text(cx, &format!("Error: {}", error_text(&e)));
                         }
                     }
                     self.image_edit_state.is_running = false;
@@ -2687,7 +5412,7 @@ impl ModelHubApp {
                             self.video_state.output_path = path;
                         }
                         Err(e) => {
-                            self.view.label(ids!(hub_video_panel.vid_status)).set_text(cx, &format!("Error: {}", e));
+                            self.view.label(ids!(hub_video_panel.vid_status)).set_text(cx, &format!("Error: {}", error_text(&e)));
                         }
                     }
                     self.video_state.is_running = false;
@@ -2697,22 +5422,96 @@ impl ModelHubApp {
             }
         }
 
-        // TTS (returns ())
+        // Reranker: passages in score order
+        if self.rerank_state.is_running {
+            if let Some(rx) = &self.rerank_state.rx {
+                if let Ok(result) = rx.try_recv() {
+                    match result {
+                        Ok(ranked) => {
+                            let lines: Vec<String> = ranked.iter().enumerate()
+                                .map(|(i, (passage, score))| format!("{}.  {:.3}  {}", i + 1, score, passage))
+                                .collect();
+                            self.view.label(ids!(hub_rerank_panel.rerank_status)).set_text(cx, "Done.");
+                            self.view.label(ids!(hub_rerank_panel.rerank_results)).set_text(cx, &lines.join("\n"));
+                        }
+                        Err(e) => {
+                            self.view.label(ids!(hub_rerank_panel.rerank_status)).set_text(cx, &format!("Error: {}", error_text(&e)));
+                        }
+                    }
+                    self.rerank_state.is_running = false;
+                    self.rerank_state.rx = None;
+                    redraw = true;
+                } else { cx.new_next_frame(); }
+            }
+        }
+
+        // Music gen: streamed progress, then the result row
+        if self.music_state.is_running {
+            if let Some(rx) = &self.music_state.rx {
+                let mut finished = None;
+                while let Ok(update) = rx.try_recv() {
+                    match update {
+                        MusicUpdate::Progress(fraction) => {
+                            self.view.label(ids!(hub_music_panel.mus_status))
+                                .set_text(cx, &format!("Generating music... {:.0}%", fraction.clamp(0.0, 1.0) * 100.0));
+                            redraw = true;
+                        }
+                        MusicUpdate::Finished(result) => { finished = Some(result); break; }
+                    }
+                }
+                if let Some(result) = finished {
+                    match result {
+                        Ok(path) => {
+                            self.view.label(ids!(hub_music_panel.mus_status)).set_text(cx, "Done.");
+                            self.view.label(ids!(hub_music_panel.mus_output_path)).set_text(cx, &path);
+                            self.view.view(ids!(hub_music_panel.mus_result_row)).set_visible(cx, true);
+                            self.music_state.output_path = path;
+                        }
+                        Err(e) => {
+                            self.view.label(ids!(hub_music_panel.mus_status)).set_text(cx, &format!("Error: {}", error_text(&e)));
+                        }
+                    }
+                    self.music_state.is_running = false;
+                    self.music_state.rx = None;
+                    redraw = true;
+                } else { cx.new_next_frame(); }
+            }
+        }
+
+        // TTS: per-chunk progress, then play the joined audio
         if self.tts_state.is_running {
             if let Some(rx) = &self.tts_state.rx {
-                if let Ok(result) = rx.try_recv() {
+                let mut finished = None;
+                while let Ok(update) = rx.try_recv() {
+                    match update {
+                        TtsUpdate::Progress { done, total } if done < total => {
+                            self.view.label(ids!(hub_tts_panel.tts_status))
+                                .set_text(cx, &format!("Generating chunk {}/{}...", done + 1, total));
+                            redraw = true;
+                        }
+                        TtsUpdate::Progress { .. } => {
+                            self.view.label(ids!(hub_tts_panel.tts_status)).set_text(cx, "Joining audio...");
+                            redraw = true;
+                        }
+                        TtsUpdate::Finished(result) => { finished = Some(result); break; }
+                    }
+                }
+                if let Some(result) = finished {
                     match result {
-                        Ok(()) => {
+                        Ok(true) => {
                             self.view.label(ids!(hub_tts_panel.tts_status)).set_text(cx, "Playing...");
-                            self.tts_state.output_path = "/tmp/ominix-hub-tts.wav".to_string();
+                            self.tts_state.output_path = artifacts::session_file("hub-tts.wav").to_string_lossy().to_string();
                             self.view.view(ids!(hub_tts_panel.tts_result_row)).set_visible(cx, true);
                             self.view.button(ids!(hub_tts_panel.tts_result_row.tts_save_btn)).set_visible(cx, true);
                             self.view.button(ids!(hub_tts_panel.tts_result_row.tts_finder_btn)).set_visible(cx, false);
                         }
-                        Err(e) => { self.view.label(ids!(hub_tts_panel.tts_status)).set_text(cx, &format!("Error: {}", e)); }
+                        Ok(false) => { self.view.label(ids!(hub_tts_panel.tts_status)).set_text(cx, "Cancelled."); }
+                        Err(e) => { self.view.label(ids!(hub_tts_panel.tts_status)).set_text(cx, &format!("Error: {}", error_text(&e))); }
                     }
                     self.tts_state.is_running = false;
                     self.tts_state.rx = None;
+                    self.tts_state.cancel = None;
+                    self.view.widget(ids!(hub_tts_panel.tts_cancel_btn)).set_visible(cx, false);
                     redraw = true;
                 } else { cx.new_next_frame(); }
             }
@@ -2738,84 +5537,8 @@ impl ModelHubApp {
 
 // ─── Filesystem helpers ───────────────────────────────────────────────────────
 
-fn scan_state(model: &RegistryModel) -> ModelUiState {
-    let p = expand_tilde(&model.storage.local_path);
-    let path = Path::new(&p);
-    if !path.exists() { return ModelUiState::NotDownloaded; }
-
-    // For multi-source models, verify all required files are present
-    if !model.extra_sources.is_empty() {
-        // Check primary file from URL
-        if let Some(url) = &model.source.url {
-            let filename = url.split('/').last().and_then(|f| f.split('?').next()).unwrap_or("");
-            if !filename.is_empty() && !path.join(filename).exists() {
-                return ModelUiState::NotDownloaded;
-            }
-        }
-        // Check each extra source file
-        for extra in &model.extra_sources {
-            if let Some(url) = &extra.source.url {
-                let filename = url.split('/').last().and_then(|f| f.split('?').next()).unwrap_or("");
-                let dir = expand_tilde(&extra.storage.local_path);
-                if !filename.is_empty() && !Path::new(&dir).join(filename).exists() {
-                    return ModelUiState::NotDownloaded;
-                }
-            }
-        }
-        return ModelUiState::Downloaded;
-    }
-
-    // For models with a known size > 100 MB, require at least one weight file
-    // (.safetensors, .bin, or .gguf) to exist — prevents partial downloads (e.g. only
-    // config.json fetched by the HF library) from showing as "Downloaded".
-    // For GGUF models, also check file size against expected to avoid false positives
-    // when multiple quant variants share the same HuggingFace repo directory.
-    if model.storage.size_bytes > 100 * 1024 * 1024 {
-        return if has_weight_files(path, model.storage.size_bytes) { ModelUiState::Downloaded } else { ModelUiState::NotDownloaded };
-    }
-    let n = std::fs::read_dir(path)
-        .map(|e| e.filter_map(|x| x.ok())
-             .filter(|x| !x.file_name().to_string_lossy().starts_with('.')).count())
-        .unwrap_or(0);
-    if n > 0 { ModelUiState::Downloaded } else { ModelUiState::NotDownloaded }
-}
-
-/// Recursively search for weight files (.safetensors, .bin, .gguf) under `dir`.
-/// For .gguf files, compares actual size against `expected_size` (5% tolerance)
-/// to avoid false positives when multiple quant variants share a directory.
-fn has_weight_files(dir: &Path, expected_size: u64) -> bool {
-    let Ok(entries) = std::fs::read_dir(dir) else { return false; };
-    for entry in entries.filter_map(|e| e.ok()) {
-        let p = entry.path();
-        if p.is_file() {
-            let name = p.file_name().unwrap_or_default().to_string_lossy();
-            if name.ends_with(".gguf") {
-                if expected_size > 0 {
-                    let actual = std::fs::metadata(&p).map(|m| m.len()).unwrap_or(0);
-                    let tolerance = expected_size / 20; // 5%
-                    if (actual as i64 - expected_size as i64).unsigned_abs() <= tolerance {
-                        return true;
-                    }
-                } else {
-                    return true;
-                }
-            } else if name.ends_with(".safetensors") || name.ends_with(".bin") {
-                return true;
-            }
-        } else if p.is_dir() && has_weight_files(&p, expected_size) {
-            return true;
-        }
-    }
-    false
-}
-
 fn expand_tilde(path: &str) -> String {
-    if path.starts_with("~/") {
-        if let Some(home) = dirs::home_dir() {
-            return home.join(&path[2..]).to_string_lossy().to_string();
-        }
-    }
-    path.to_string()
+    download_settings::models_path(path)
 }
 
 fn hf_token() -> Option<String> {
@@ -2833,15 +5556,240 @@ fn hf_token() -> Option<String> {
     None
 }
 
+// ─── Audio helpers ────────────────────────────────────────────────────────────
+
+/// Transcribe one audio file with the loaded ASR model (blocking).
+///
+/// OminiX-API only accepts WAV, so other formats are converted first (afconvert
+/// on macOS, ffmpeg elsewhere). Returns the transcript plus the WAV that was sent
+/// and whether that WAV is a temp conversion the caller must clean up. The
+/// custom vocabulary from Settings, if on, is sent as a hint and its spellings
+/// applied to the transcript.
+fn transcribe_file_blocking(
+    client: &OminiXApiClient,
+    model_id: &str,
+    audio_path: &str,
+) -> Result<(Transcript, String, bool), String> {
+    let (wav_path, is_temp) = if !audio_path.to_lowercase().ends_with(".wav") {
+        let tmp = artifacts::unique_path("hub-asr", "wav");
+        platform::convert_audio(audio_path, &tmp, AudioFormat::Wav16kMono)
+            .map_err(|e| format!("Format conversion failed: {}. Please convert to WAV first.", e))?;
+        (tmp.to_string_lossy().to_string(), true)
+    } else {
+        (audio_path.to_string(), false)
+    };
+
+    // Send the WAV file path directly — OminiX-API reads it from disk (no size limit).
+    // Segment timestamps are requested for subtitle export.
+    let settings = asr_cleanup::current();
+    let vocabulary = settings.active_vocabulary();
+    let request = TranscriptionRequest {
+        prompt: asr_cleanup::vocabulary_prompt(vocabulary),
+        ..TranscriptionRequest::with_segments(model_id, wav_path.as_str())
+    };
+    let result = client.transcribe(&request)
+        .map(|mut t| {
+            t.text = asr_cleanup::apply_vocabulary(&t.text, vocabulary);
+            for segment in &mut t.segments {
+                segment.text = asr_cleanup::apply_vocabulary(&segment.text, vocabulary);
+            }
+            t
+        })
+        .map_err(String::from);
+    // The temp WAV is kept for segment playback; clean it up only on failure
+    if is_temp && result.is_err() { artifacts::release(Path::new(&wav_path)); }
+    result.map(|t| (t, wav_path, is_temp))
+}
+
+const BATCH_AUDIO_EXTENSIONS: &[&str] = &["wav", "mp3", "m4a", "flac", "ogg", "aac"];
+
+/// Audio files directly inside `dir`, sorted by name.
+fn list_audio_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = std::fs::read_dir(dir)
+        .map(|rd| rd.flatten().map(|e| e.path()).collect())
+        .unwrap_or_default();
+    files.retain(|p| p.is_file() && p.extension()
+        .and_then(|e| e.to_str())
+        .map_or(false, |e| BATCH_AUDIO_EXTENSIONS.contains(&e.to_lowercase().as_str())));
+    files.sort();
+    files
+}
+
+/// Where a batch transcript for `audio` is written (`<stem>.txt`, plus
+/// `.srt`); `stem` comes from [`transcribe_watch::batch_stems`].
+fn batch_transcript_path(audio: &Path, stem: &str, output_dir: Option<&Path>, ext: &str) -> PathBuf {
+    let dir = output_dir.map(Path::to_path_buf)
+        .or_else(|| audio.parent().map(Path::to_path_buf))
+        .unwrap_or_default();
+    dir.join(format!("{}.{}", stem, ext))
+}
+
+/// Synthesize every chapter into `output_dir`, then join them into one book
+/// file (blocking — runs on the audiobook thread).
+///
+/// Chapter files are written via a `.part` file and renamed, so an existing
+/// chapter file is always complete and can be skipped on resume. The joined
+/// book is converted to m4b when a converter is available, otherwise the
+/// concatenated WAV is kept.
+fn build_audiobook(
+    model_id: &str,
+    voice: &str,
+    chapters: &[BookChapter],
+    output_dir: &Path,
+    book_name: &str,
+    cancel: &AtomicBool,
+    tx: &mpsc::Sender<AudiobookUpdate>,
+) -> Result<Option<PathBuf>, String> {
+    std::fs::create_dir_all(output_dir)
+        .map_err(|e| format!("Cannot create {}: {}", output_dir.display(), e))?;
+    let client = OminiXApiClient::localhost(120);
+
+    let mut files = Vec::with_capacity(chapters.len());
+    let mut failed = 0;
+    for (i, chapter) in chapters.iter().enumerate() {
+        if cancel.load(Ordering::SeqCst) { return Ok(None); }
+        let path = output_dir.join(chapter_file_name(i, &chapter.title));
+        files.push(path.clone());
+        if path.exists() {
+            let _ = tx.send(AudiobookUpdate::Status(i, BatchFileStatus::Skipped, None));
+            continue;
+        }
+        let _ = tx.send(AudiobookUpdate::Status(i, BatchFileStatus::Running, None));
+
+        let chunks = split_into_chunks(&chapter.text, DEFAULT_TTS_CHUNK_CHARS);
+        let result = synthesize_chunks(&client, model_id, voice, &chunks, cancel, |done, total| {
+            let _ = tx.send(AudiobookUpdate::Progress(i, done, total));
+        })
+        .and_then(|wav| {
+            let Some(wav) = wav else { return Ok(false) };
+            let part = path.with_extension("wav.part");
+            std::fs::write(&part, wav).map_err(|e| e.to_string())?;
+            std::fs::rename(&part, &path).map_err(|e| e.to_string())?;
+            Ok(true)
+        });
+        match result {
+            Ok(true) => { let _ = tx.send(AudiobookUpdate::Status(i, BatchFileStatus::Done, None)); }
+            Ok(false) => return Ok(None),
+            Err(e) => {
+                ::log::warn!("Audiobook chapter {} failed: {}", i + 1, e);
+                failed += 1;
+                let _ = tx.send(AudiobookUpdate::Status(i, BatchFileStatus::Failed, Some(e)));
+            }
+        }
+    }
+    if failed > 0 {
+        return Err(format!("{} chapter(s) failed — start again to retry them", failed));
+    }
+
+    let wav_path = output_dir.join(format!("{}.wav", book_name));
+    concat_wav_files(&files, &wav_path)?;
+    let m4b_path = output_dir.join(format!("{}.m4b", book_name));
+    match platform::convert_audio(&wav_path, &m4b_path, AudioFormat::M4b) {
+        Ok(()) => {
+            let _ = std::fs::remove_file(&wav_path);
+            Ok(Some(m4b_path))
+        }
+        Err(e) => {
+            ::log::info!("{}; keeping {}", e, wav_path.display());
+            Ok(Some(wav_path))
+        }
+    }
+}
+
+/// Copy the `[start, end)` seconds of a PCM WAV file into a new WAV at `dest`.
+fn write_wav_segment(src: &Path, start: f64, end: f64, dest: &Path) -> Result<(), String> {
+    let bytes = std::fs::read(src).map_err(|e| format!("Cannot read {}: {}", src.display(), e))?;
+    let out = Wav::parse(&bytes)?.slice(start, end)?;
+    std::fs::write(dest, out).map_err(|e| format!("Cannot write {}: {}", dest.display(), e))
+}
+
+// ─── Update check ─────────────────────────────────────────────────────────────
+
+/// List the repo of each model again and compare it with the version on
+/// disk. Models whose repo couldn't be listed are left out.
+fn check_model_updates(models: &[RegistryModel]) -> Vec<(String, Option<ModelUpdate>)> {
+    let client = match moly_data::network::blocking_client()
+        .connect_timeout(std::time::Duration::from_secs(15))
+        .timeout(std::time::Duration::from_secs(30)).build()
+    {
+        Ok(c) => c,
+        Err(e) => {
+            ::log::warn!("Update check: {}", e);
+            return Vec::new();
+        }
+    };
+    let mut results = Vec::new();
+    for model in models {
+        let Some(local) = model_updates::local_revision(Path::new(&model.storage.expanded_path())) else { continue };
+        let repo_id = model.source.repo_id.clone().unwrap_or_default();
+        let remote = match model.source.kind {
+            SourceKind::HuggingFace => download_settings::hf_endpoints().iter()
+                .find_map(|endpoint| hf_file_list(&client, endpoint, &repo_id).ok())
+                .ok_or_else(|| "no endpoint listed the repo".to_string()),
+            _ => ms_file_list(&client, &repo_id, &model.source.revision),
+        };
+        match remote {
+            Ok(remote) => {
+                let update = model_updates::changes(&local, &remote);
+                if let Some(update) = &update {
+                    ::log::info!("Update available for {}: {}", model.id, update.summary());
+                }
+                results.push((model.id.clone(), update));
+            }
+            Err(e) => ::log::warn!("Update check for {} failed: {}", model.id, e),
+        }
+    }
+    results
+}
+
 // ─── HuggingFace download ─────────────────────────────────────────────────────
 
 fn download_hf(
     client: &reqwest::blocking::Client,
     repo_id: &str, revision: &str, local_path: &str,
+    refresh: &HashSet<String>,
     ds: &ModelDownloadState,
 ) -> Result<(), String> {
+    // Configured mirrors first, then huggingface.co; files come from
+    // whichever endpoint listed the repo
+    let mut listed = Err("No HuggingFace endpoint".to_string());
+    for endpoint in download_settings::hf_endpoints() {
+        listed = hf_file_list(client, &endpoint, repo_id).map(|files| (endpoint.clone(), files));
+        match &listed {
+            Ok(_) => break,
+            Err(e) => ::log::warn!("Listing {} on {} failed: {}", repo_id, endpoint, e),
+        }
+    }
+    let (endpoint, listing) = listed?;
+    write_download_manifest(local_path, &listing.registry_files());
+
+    let files = &listing.files;
+    ds.total_bytes.store(files.iter().map(|f| f.size).sum(), Ordering::SeqCst);
+    let mut done = 0u64;
+    for (index, RepoFile { path, size, .. }) in files.iter().enumerate() {
+        if ds.cancel_requested.load(Ordering::SeqCst) { return Err("Cancelled".to_string()); }
+        let file_url = format!("{}/{}/resolve/{}/{}", endpoint, repo_id, revision, path);
+        let dest = PathBuf::from(local_path).join(path);
+        // Create parent directories for nested paths (e.g. transformer/model.safetensors)
+        if let Some(parent) = dest.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        ds.begin_file(path, index, files.len(), *size);
+        if !refresh.contains(path) && is_complete(&dest, *size) {
+            done += size;
+            ds.record_bytes(done, *size);
+            continue;
+        }
+        done += stream_download(client, &file_url, &dest, ds, done)?;
+    }
+    write_download_revision(local_path, &listing);
+    Ok(())
+}
+
+/// Files of a HuggingFace repo with their sizes and hashes, from `endpoint`
+fn hf_file_list(client: &reqwest::blocking::Client, endpoint: &str, repo_id: &str) -> Result<RepoRevision, String> {
     // Use ?blobs=true to get all files recursively (including subdirectories) with sizes
-    let url = format!("https://huggingface.co/api/models/{}?blobs=true", repo_id);
+    let url = format!("{}/api/models/{}?blobs=true", endpoint, repo_id);
     let mut req = client.get(&url);
     if let Some(tok) = hf_token() { req = req.header("Authorization", format!("Bearer {}", tok)); }
     let resp = req.send().map_err(|e| e.to_string())?;
@@ -2850,26 +5798,16 @@ fn download_hf(
     }
     if !resp.status().is_success() { return Err(format!("HF API {}", resp.status())); }
     let body: HfBlobsResponse = resp.json().map_err(|e| e.to_string())?;
-    let files: Vec<(String, u64)> = body.siblings.into_iter()
+    let files: Vec<RepoFile> = body.siblings.into_iter()
         .filter(|s| !s.rfilename.starts_with('.'))
-        .map(|s| (s.rfilename, s.size.unwrap_or(0)))
+        .map(|s| RepoFile {
+            hash: s.lfs.map(|lfs| lfs.sha256).unwrap_or(s.blob_id),
+            path: s.rfilename,
+            size: s.size.unwrap_or(0),
+        })
         .collect();
     if files.is_empty() { return Err("No files in repo".to_string()); }
-
-    ds.total_bytes.store(files.iter().map(|(_, s)| s).sum(), Ordering::SeqCst);
-    let mut done = 0u64;
-    for (path, _) in &files {
-        if ds.cancel_requested.load(Ordering::SeqCst) { return Err("Cancelled".to_string()); }
-        let file_url = format!("https://huggingface.co/{}/resolve/{}/{}", repo_id, revision, path);
-        let dest = PathBuf::from(local_path).join(path);
-        // Create parent directories for nested paths (e.g. transformer/model.safetensors)
-        if let Some(parent) = dest.parent() {
-            std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
-        }
-        *ds.current_file.lock().unwrap() = path.clone();
-        done += stream_download(client, &file_url, &dest, &ds.cancel_requested, &ds.progress_bytes, done)?;
-    }
-    Ok(())
+    Ok(RepoRevision { commit: body.sha, files })
 }
 
 // ─── Direct URL download (single file) ────────────────────────────────────────
@@ -2890,7 +5828,7 @@ fn download_direct_url(
     if let Some(p) = dest.parent() {
         std::fs::create_dir_all(p).map_err(|e| e.to_string())?;
     }
-    *ds.current_file.lock().unwrap() = filename.to_string();
+    ds.begin_file(filename, 0, 1, 0);
     // HEAD request to get Content-Length for progress tracking
     let mut req = client.head(url);
     if let Some(tok) = hf_token() { req = req.header("Authorization", format!("Bearer {}", tok)); }
@@ -2901,10 +5839,11 @@ fn download_direct_url(
         {
             let current_total = ds.total_bytes.load(Ordering::SeqCst);
             ds.total_bytes.store(current_total + len, Ordering::SeqCst);
+            ds.file_total.store(len, Ordering::SeqCst);
         }
     }
     let done_before = ds.progress_bytes.load(Ordering::SeqCst);
-    stream_download(client, url, &dest, &ds.cancel_requested, &ds.progress_bytes, done_before)?;
+    stream_download(client, url, &dest, ds, done_before)?;
     Ok(())
 }
 
@@ -2913,40 +5852,80 @@ fn download_direct_url(
 fn download_ms(
     client: &reqwest::blocking::Client,
     repo_id: &str, revision: &str, local_path: &str,
+    refresh: &HashSet<String>,
     ds: &ModelDownloadState,
 ) -> Result<(), String> {
-    let url = format!(
-        "https://modelscope.cn/api/v1/models/{}/repo/files?Revision={}&Recursive=true",
-        repo_id, revision
-    );
-    let resp = client.get(&url).send().map_err(|e| e.to_string())?;
-    let ms: MsResponse = resp.json().map_err(|e| e.to_string())?;
-    if ms.code != 200 { return Err(format!("ModelScope code {}", ms.code)); }
-    let data = ms.data.ok_or_else(|| "empty data".to_string())?;
-    let files: Vec<(String, u64)> = data.files.into_iter()
-        .filter(|f| f.file_type == "blob").map(|f| (f.path, f.size)).collect();
+    let listing = ms_file_list(client, repo_id, revision)?;
+    write_download_manifest(local_path, &listing.registry_files());
 
-    ds.total_bytes.store(files.iter().map(|(_, s)| s).sum(), Ordering::SeqCst);
+    let files = &listing.files;
+    ds.total_bytes.store(files.iter().map(|f| f.size).sum(), Ordering::SeqCst);
     let mut done = 0u64;
-    for (path, _) in &files {
+    for (index, RepoFile { path, size, .. }) in files.iter().enumerate() {
         if ds.cancel_requested.load(Ordering::SeqCst) { return Err("Cancelled".to_string()); }
         let file_url = format!(
             "https://modelscope.cn/api/v1/models/{}/repo?Revision={}&FilePath={}",
             repo_id, revision, path
         );
         let dest = PathBuf::from(local_path).join(path);
-        *ds.current_file.lock().unwrap() = path.clone();
-        done += stream_download(client, &file_url, &dest, &ds.cancel_requested, &ds.progress_bytes, done)?;
+        ds.begin_file(path, index, files.len(), *size);
+        if !refresh.contains(path) && is_complete(&dest, *size) {
+            done += size;
+            ds.record_bytes(done, *size);
+            continue;
+        }
+        done += stream_download(client, &file_url, &dest, ds, done)?;
     }
+    write_download_revision(local_path, &listing);
     Ok(())
 }
 
+/// Files of a ModelScope repo at `revision` with their sizes and hashes.
+/// ModelScope doesn't report the commit.
+fn ms_file_list(client: &reqwest::blocking::Client, repo_id: &str, revision: &str) -> Result<RepoRevision, String> {
+    let url = format!(
+        "https://modelscope.cn/api/v1/models/{}/repo/files?Revision={}&Recursive=true",
+        repo_id, revision
+    );
+    let resp = client.get(&url).send().map_err(|e| e.to_string())?;
+    let ms: MsResponse = resp.json().map_err(|e| e.to_string())?;
+    if ms.code != 200 { return Err(format!("ModelScope code {}", ms.code)); }
+    let data = ms.data.ok_or_else(|| "empty data".to_string())?;
+    let files = data.files.into_iter()
+        .filter(|f| f.file_type == "blob")
+        .map(|f| RepoFile { path: f.path, size: f.size, hash: f.sha256 })
+        .collect();
+    Ok(RepoRevision { commit: String::new(), files })
+}
+
+/// Whether `dest` was already downloaded in full, e.g. before a retry
+fn is_complete(dest: &Path, size: u64) -> bool {
+    size > 0 && std::fs::metadata(dest).is_ok_and(|m| m.len() == size)
+}
+
+/// Record the repo's file list so later scans can tell an interrupted
+/// download from a complete one
+fn write_download_manifest(local_path: &str, files: &[RegistryFile]) {
+    if let Err(e) = model_integrity::write_manifest(Path::new(local_path), files) {
+        ::log::warn!("Failed to write download manifest: {}", e);
+    }
+}
+
+/// Record the version a finished download fetched, to check for updates
+fn write_download_revision(local_path: &str, listing: &RepoRevision) {
+    if let Err(e) = model_updates::write_revision(Path::new(local_path), listing) {
+        ::log::warn!("Failed to record the downloaded revision: {}", e);
+    }
+}
+
 // ─── Stream helper ────────────────────────────────────────────────────────────
 
+/// Download `url` to `dest` through `dest.part`, so an interrupted download
+/// never leaves a file under its final name. `base_done` is the byte count
+/// of the files before this one; progress and speed are recorded in `ds`.
 fn stream_download(
     client: &reqwest::blocking::Client,
-    url: &str, dest: &Path, cancel: &Arc<AtomicBool>,
-    progress_bytes: &Arc<AtomicU64>, base_done: u64,
+    url: &str, dest: &Path, ds: &ModelDownloadState, base_done: u64,
 ) -> Result<u64, String> {
     if let Some(p) = dest.parent() { std::fs::create_dir_all(p).map_err(|e| e.to_string())?; }
     let mut req = client.get(url);
@@ -2956,13 +5935,14 @@ fn stream_download(
 
     let file_name = dest.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
     ::log::info!("stream_download: starting {} from {}", file_name, url);
-    let mut file = std::fs::File::create(dest).map_err(|e| e.to_string())?;
+    let part = dest.with_file_name(format!("{}{}", file_name, model_integrity::PART_SUFFIX));
+    let mut file = std::fs::File::create(&part).map_err(|e| e.to_string())?;
     let mut buf  = [0u8; 65536];
     let mut total = 0u64;
     let mut last_log = 0u64;
     loop {
-        if cancel.load(Ordering::SeqCst) {
-            drop(file); let _ = std::fs::remove_file(dest);
+        if ds.cancel_requested.load(Ordering::SeqCst) {
+            drop(file); let _ = std::fs::remove_file(&part);
             return Err("Cancelled".to_string());
         }
         match resp.read(&mut buf) {
@@ -2970,7 +5950,8 @@ fn stream_download(
             Ok(n) => {
                 file.write_all(&buf[..n]).map_err(|e| e.to_string())?;
                 total += n as u64;
-                progress_bytes.store(base_done + total, Ordering::SeqCst);
+                ds.record_bytes(base_done + total, total);
+                download_settings::throttle(n);
                 // Log every 50MB to verify streaming works
                 if total / (50 * 1_048_576) > last_log / (50 * 1_048_576) {
                     ::log::info!("stream_download: {} — {} MB downloaded", file_name, total / 1_048_576);
@@ -2980,5 +5961,7 @@ fn stream_download(
             Err(e) => return Err(e.to_string()),
         }
     }
+    drop(file);
+    std::fs::rename(&part, dest).map_err(|e| e.to_string())?;
     Ok(total)
 }
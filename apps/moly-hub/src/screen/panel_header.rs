@@ -0,0 +1,158 @@
+//! Model header shared by the category panels
+//!
+//! Every category panel starts with a `HubPanelHeader`: the selected model's
+//! name and status, its download, load and chat actions, and its Advanced
+//! runtime options. The hub works out what the header should show as a
+//! [`PanelHeaderState`] and hands it to [`HubPanelHeaderRef::set_state`], so
+//! a new category only needs a panel with a header in `design.rs` and an
+//! entry in the hub's `header_panels()`.
+
+use makepad_widgets::*;
+use moly_widgets::UiStateCache;
+
+/// Everything a panel header shows for the selected model
+#[derive(Clone, Debug, Default)]
+pub struct PanelHeaderState {
+    pub name: String,
+    pub description: String,
+    /// Status dot value (see `combined_dot_value`)
+    pub dot: f64,
+    pub status: String,
+    pub size: String,
+    pub memory: String,
+    /// The line under the actions: errors, warnings and hints
+    pub message: String,
+
+    pub show_download: bool,
+    pub show_lan_import: bool,
+    pub show_update: bool,
+    pub show_cancel: bool,
+    pub show_remove: bool,
+    pub show_progress: bool,
+    /// Download progress as (fraction, text)
+    pub progress: Option<(f64, String)>,
+
+    pub show_load: bool,
+    pub show_unload: bool,
+    pub show_loading: bool,
+    pub load_label: &'static str,
+    pub unload_label: &'static str,
+    /// "Open in Chat", "Open Transcriber" or "Open Speaker"; hidden when `None`
+    pub chat_label: Option<&'static str>,
+
+    pub show_preload: bool,
+    pub preload_label: &'static str,
+    pub show_options: bool,
+    pub options_open: bool,
+}
+
+#[derive(Live, LiveHook, Widget)]
+pub struct HubPanelHeader {
+    #[deref]
+    view: View,
+
+    /// What was last written, so unchanged values aren't set again
+    #[rust]
+    cache: UiStateCache,
+}
+
+impl Widget for HubPanelHeader {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        self.view.handle_event(cx, event, scope);
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        self.view.draw_walk(cx, scope, walk)
+    }
+}
+
+impl HubPanelHeader {
+    /// Show `state`, writing only what changed since the last call
+    pub fn set_state(&mut self, cx: &mut Cx, state: &PanelHeaderState) {
+        let mut changed = false;
+        changed |= self.cache.set_text(cx, &self.view.label(ids!(panel_model_name)), &state.name);
+        changed |= self.cache.set_text(cx, &self.view.label(ids!(panel_model_desc)), &state.description);
+        let status_dot = self.view.view(ids!(panel_status_dot));
+        if self.cache.changed_f64(status_dot.widget_uid(), live_id!(status), state.dot) {
+            status_dot.apply_over(cx, live! { draw_bg: { status: (state.dot) } });
+            changed = true;
+        }
+        changed |= self.cache.set_text(cx, &self.view.label(ids!(panel_status_text)), &state.status);
+        changed |= self.cache.set_text(cx, &self.view.label(ids!(panel_size_text)), &state.size);
+        changed |= self.cache.set_text(cx, &self.view.label(ids!(panel_mem_text)), &state.memory);
+
+        let visibility = [
+            (live_id!(panel_download_btn), state.show_download),
+            (live_id!(panel_lan_btn), state.show_lan_import),
+            (live_id!(panel_update_btn), state.show_update),
+            (live_id!(panel_cancel_btn), state.show_cancel),
+            (live_id!(panel_remove_btn), state.show_remove),
+            (live_id!(panel_progress_section), state.show_progress),
+            (live_id!(panel_load_btn), state.show_load),
+            (live_id!(panel_unload_btn), state.show_unload),
+            (live_id!(panel_loading_label), state.show_loading),
+            (live_id!(panel_chat_btn), state.chat_label.is_some()),
+            (live_id!(panel_preload_btn), state.show_preload),
+            (live_id!(panel_options_btn), state.show_options),
+            (live_id!(panel_options), state.show_options && state.options_open),
+        ];
+        for (id, visible) in visibility {
+            changed |= self.cache.set_visible(cx, &self.view.widget(&[id]), visible);
+        }
+        changed |= self.cache.set_text(cx, &self.view.label(ids!(panel_status_msg)), &state.message);
+
+        let options_label = if state.options_open { "Advanced ▾" } else { "Advanced ▸" };
+        for (id, text) in [
+            (live_id!(panel_preload_btn), state.preload_label),
+            (live_id!(panel_options_btn), options_label),
+            (live_id!(panel_load_btn), state.load_label),
+            (live_id!(panel_unload_btn), state.unload_label),
+            (live_id!(panel_chat_btn), state.chat_label.unwrap_or_default()),
+        ] {
+            let button = self.view.button(&[id]);
+            if self.cache.changed(button.widget_uid(), live_id!(text), text) {
+                button.set_text(cx, text);
+                changed = true;
+            }
+        }
+        if state.show_progress {
+            if let Some((fraction, text)) = &state.progress {
+                changed |= self.write_progress(cx, *fraction, text);
+            }
+        }
+        if changed {
+            self.view.redraw(cx);
+        }
+    }
+
+    /// Move the download progress bar; called every frame while downloading
+    pub fn set_progress(&mut self, cx: &mut Cx, fraction: f64, text: &str) {
+        if self.write_progress(cx, fraction, text) {
+            self.view.redraw(cx);
+        }
+    }
+
+    fn write_progress(&mut self, cx: &mut Cx, fraction: f64, text: &str) -> bool {
+        let fill = self.view.view(ids!(panel_progress_fill));
+        let mut changed = false;
+        if self.cache.changed_f64(fill.widget_uid(), live_id!(progress), fraction) {
+            fill.apply_over(cx, live! { draw_bg: { progress: (fraction) } });
+            changed = true;
+        }
+        changed | self.cache.set_text(cx, &self.view.label(ids!(panel_progress_text)), text)
+    }
+}
+
+impl HubPanelHeaderRef {
+    pub fn set_state(&self, cx: &mut Cx, state: &PanelHeaderState) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_state(cx, state);
+        }
+    }
+
+    pub fn set_progress(&self, cx: &mut Cx, fraction: f64, text: &str) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_progress(cx, fraction, text);
+        }
+    }
+}
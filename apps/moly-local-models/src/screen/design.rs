@@ -402,6 +402,44 @@ live_design! {
                 }
             }
 
+            // Shown once after local_models.json (V1) was migrated
+            migration_banner = <View> {
+                width: Fill, height: Fit
+                flow: Down
+                padding: 12
+                spacing: 6
+                margin: {bottom: 16}
+                visible: false
+                show_bg: true
+                draw_bg: {
+                    instance radius: 6.0
+                    fn pixel(self) -> vec4 {
+                        let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                        sdf.box(0.5, 0.5, self.rect_size.x - 1.0, self.rect_size.y - 1.0, self.radius);
+                        sdf.fill(#eff6ff);
+                        sdf.stroke(#bfdbfe, 1.0);
+                        return sdf.result;
+                    }
+                }
+
+                <SectionTitle> { text: "Local models were migrated" }
+                migration_report_label = <Label> {
+                    width: Fill, height: Fit
+                    draw_text: {
+                        fn get_color(self) -> vec4 {
+                            return #374151;
+                        }
+                        text_style: <FONT_REGULAR>{ font_size: 11.0 }
+                        wrap: Word
+                    }
+                }
+                <View> {
+                    width: Fill, height: Fit
+                    align: {x: 1.0}
+                    dismiss_migration_button = <ActionButton> { text: "Dismiss" }
+                }
+            }
+
             // Model header - smaller title
             model_header = <View> {
                 width: Fill, height: Fit
@@ -497,9 +535,37 @@ live_design! {
                     text: "Remove"
                     draw_bg: { btn_type: 1.0 }
                 }
+            }
 
-                refresh_button = <ActionButton> {
-                    text: "Refresh"
+            // Undo toast for a removal, shown for a few seconds
+            undo_bar = <View> {
+                width: Fill, height: Fit
+                flow: Right
+                align: {y: 0.5}
+                margin: {top: 12}
+                padding: {left: 12, right: 6, top: 6, bottom: 6}
+                visible: false
+                show_bg: true
+                draw_bg: {
+                    fn pixel(self) -> vec4 {
+                        let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                        sdf.box(0.0, 0.0, self.rect_size.x, self.rect_size.y, 6.0);
+                        sdf.fill(#1f2937);
+                        return sdf.result;
+                    }
+                }
+                undo_label = <Label> {
+                    width: Fill
+                    draw_text: {
+                        fn get_color(self) -> vec4 {
+                            return #f9fafb;
+                        }
+                        text_style: <FONT_REGULAR>{ font_size: 11.0 }
+                    }
+                }
+                undo_button = <ActionButton> {
+                    margin: 0
+                    text: "Undo"
                     draw_bg: { btn_type: 0.0 }
                 }
             }
@@ -3,7 +3,11 @@ pub mod design;
 use makepad_widgets::*;
 use moly_data::{
     LocalModelsConfigV2, LocalModelV2, ModelState, DownloadProgress, SourceType, ModelCategory,
+    MigrationReport, StoreEvent, DownloadStatus, ModelWatcher,
 };
+use moly_data::{model_integrity, model_state, shutdown, TransferRate};
+use moly_data::disk_space::{self, SpaceCheck};
+use moly_data::model_trash::{self, PendingRemoval};
 use serde::Deserialize;
 use std::sync::{Arc, atomic::{AtomicBool, AtomicU64, Ordering}};
 use std::collections::HashMap;
@@ -68,6 +72,11 @@ struct ModelDownloadState {
     current_file: Arc<std::sync::Mutex<Option<String>>>,
     current_file_index: Arc<AtomicU64>,
     total_files: Arc<AtomicU64>,
+    current_file_bytes: Arc<AtomicU64>,
+    current_file_total: Arc<AtomicU64>,
+    /// Rolling speed, updated by the download thread as data arrives
+    rate: Arc<std::sync::Mutex<TransferRate>>,
+    speed_bytes_per_sec: Arc<AtomicU64>,
     completed: Arc<AtomicBool>,
     error: Arc<std::sync::Mutex<Option<String>>>,
 }
@@ -83,6 +92,10 @@ impl ModelDownloadState {
             current_file: Arc::new(std::sync::Mutex::new(None)),
             current_file_index: Arc::new(AtomicU64::new(0)),
             total_files: Arc::new(AtomicU64::new(0)),
+            current_file_bytes: Arc::new(AtomicU64::new(0)),
+            current_file_total: Arc::new(AtomicU64::new(0)),
+            rate: Arc::new(std::sync::Mutex::new(TransferRate::new())),
+            speed_bytes_per_sec: Arc::new(AtomicU64::new(0)),
             completed: Arc::new(AtomicBool::new(false)),
             error: Arc::new(std::sync::Mutex::new(None)),
         }
@@ -95,6 +108,10 @@ impl ModelDownloadState {
         self.total_bytes.store(0, Ordering::SeqCst);
         self.current_file_index.store(0, Ordering::SeqCst);
         self.total_files.store(0, Ordering::SeqCst);
+        self.current_file_bytes.store(0, Ordering::SeqCst);
+        self.current_file_total.store(0, Ordering::SeqCst);
+        self.speed_bytes_per_sec.store(0, Ordering::SeqCst);
+        self.rate.lock().unwrap().reset();
         self.completed.store(false, Ordering::SeqCst);
         *self.current_file.lock().unwrap() = None;
         *self.error.lock().unwrap() = None;
@@ -109,17 +126,38 @@ impl ModelDownloadState {
         (progress as f64 / total as f64).min(1.0)
     }
 
+    /// Start counting a new set of files, e.g. when retrying from a backup URL
+    fn begin_files(&self, total_bytes: u64, total_files: usize) {
+        self.total_bytes.store(total_bytes, Ordering::SeqCst);
+        self.total_files.store(total_files as u64, Ordering::SeqCst);
+        self.speed_bytes_per_sec.store(0, Ordering::SeqCst);
+        self.rate.lock().unwrap().reset();
+    }
+
+    /// Record bytes received so far, overall and in the current file
+    fn record_bytes(&self, overall_bytes: u64, file_bytes: u64) {
+        self.progress_bytes.store(overall_bytes, Ordering::SeqCst);
+        self.current_file_bytes.store(file_bytes, Ordering::SeqCst);
+        let mut rate = self.rate.lock().unwrap();
+        rate.record(std::time::Instant::now(), overall_bytes);
+        self.speed_bytes_per_sec.store(rate.bytes_per_sec(), Ordering::SeqCst);
+    }
+
     fn to_download_progress(&self) -> DownloadProgress {
+        let overall_bytes = self.progress_bytes.load(Ordering::SeqCst);
+        let overall_total = self.total_bytes.load(Ordering::SeqCst);
+        let speed = self.speed_bytes_per_sec.load(Ordering::SeqCst);
         DownloadProgress {
             is_active: self.is_downloading.load(Ordering::SeqCst),
             current_file: self.current_file.lock().unwrap().clone(),
             current_file_index: self.current_file_index.load(Ordering::SeqCst) as usize,
-            current_file_bytes: 0, // Not tracked separately
-            current_file_total: 0,
-            overall_bytes: self.progress_bytes.load(Ordering::SeqCst),
-            overall_total: self.total_bytes.load(Ordering::SeqCst),
-            speed_bytes_per_sec: 0, // Could add speed tracking
-            eta_seconds: None,
+            total_files: self.total_files.load(Ordering::SeqCst) as usize,
+            current_file_bytes: self.current_file_bytes.load(Ordering::SeqCst),
+            current_file_total: self.current_file_total.load(Ordering::SeqCst),
+            overall_bytes,
+            overall_total,
+            speed_bytes_per_sec: speed,
+            eta_seconds: (speed > 0).then(|| overall_total.saturating_sub(overall_bytes).div_ceil(speed)),
             started_at: None,
         }
     }
@@ -146,6 +184,25 @@ pub struct LocalModelsApp {
     /// Flat list of rows for the PortalList: interleaved category headers and model indices
     #[rust]
     flat_list: Vec<ListRow>,
+
+    /// Undismissed report from the V1 → V2 config migration
+    #[rust]
+    migration_report: Option<MigrationReport>,
+
+    /// Watches the model directories; polled on `watch_timer`
+    #[rust]
+    model_watcher: Option<ModelWatcher>,
+    #[rust]
+    watch_timer: Timer,
+
+    /// Model index awaiting a second click on Remove
+    #[rust]
+    pending_remove_index: Option<usize>,
+    /// Removed model waiting out its undo window (model ID, staged files)
+    #[rust]
+    pending_removal: Option<(String, PendingRemoval)>,
+    #[rust]
+    undo_timer: Timer,
 }
 
 impl Widget for LocalModelsApp {
@@ -159,6 +216,25 @@ impl Widget for LocalModelsApp {
             self.rebuild_flat_list();
             ::log::info!("Loaded local models V2 config with {} models",
                 self.config.as_ref().map(|c| c.models.len()).unwrap_or(0));
+
+            // Loading the config runs the migration, so the report is current
+            self.migration_report = MigrationReport::load().filter(|r| !r.acknowledged);
+            if let Some(report) = &self.migration_report {
+                let text = format!(
+                    "{}\nFiles were left in place. The old config was kept at {}.",
+                    report.summary_lines().join("\n"),
+                    report.v1_backup.display(),
+                );
+                self.view.label(ids!(migration_report_label)).set_text(cx, &text);
+            }
+            self.view.view(ids!(migration_banner)).set_visible(cx, self.migration_report.is_some());
+
+            if let Some(config) = &self.config {
+                let mut watcher = ModelWatcher::new();
+                watcher.watch_local_models(config);
+                self.model_watcher = Some(watcher);
+                self.watch_timer = cx.start_interval(1.0);
+            }
         }
 
         // Handle events
@@ -169,6 +245,19 @@ impl Widget for LocalModelsApp {
         // Handle model list item clicks
         self.handle_model_list_clicks(cx, &actions);
 
+        // Downloads and removals made in other screens
+        if let Event::Actions(actions) = event {
+            self.handle_model_state_changes(cx, actions);
+        }
+
+        if self.view.button(ids!(dismiss_migration_button)).clicked(&actions) {
+            if let Some(mut report) = self.migration_report.take() {
+                report.acknowledge();
+            }
+            self.view.view(ids!(migration_banner)).set_visible(cx, false);
+            self.view.redraw(cx);
+        }
+
         // Handle download button click
         if self.view.button(ids!(download_button)).clicked(&actions) {
             if let (Some(config), Some(idx)) = (&self.config, self.selected_model_index) {
@@ -206,33 +295,23 @@ impl Widget for LocalModelsApp {
         // Handle remove button click
         if self.view.button(ids!(remove_button)).clicked(&actions) {
             if let Some(idx) = self.selected_model_index {
-                // Check if model is downloaded before removing
-                let is_ready = self.config.as_ref()
-                    .and_then(|c| c.models.get(idx))
-                    .map(|m| m.status.state == ModelState::Ready)
-                    .unwrap_or(false);
-
-                if is_ready {
-                    self.remove_model_files(cx, idx);
-                } else {
-                    self.view.label(ids!(status_message)).set_text(
-                        cx, "Model is not downloaded"
-                    );
-                    self.view.redraw(cx);
-                }
+                self.request_remove(cx, idx);
             }
         }
 
-        // Handle refresh button click
-        if self.view.button(ids!(refresh_button)).clicked(&actions) {
-            if let Some(config) = &mut self.config {
-                config.startup_scan();
-                config.save();
-                ::log::info!("Refreshed model availability");
-                self.view.label(ids!(status_message)).set_text(cx, "Model status refreshed");
+        // Removal undo toast
+        if self.view.button(ids!(undo_button)).clicked(&actions) {
+            self.undo_removal(cx);
+        }
+        if self.undo_timer.is_event(event).is_some() {
+            self.commit_pending_removal(cx);
+        }
+
+        // Pick up model folders copied in or deleted by hand
+        if self.watch_timer.is_event(event).is_some() {
+            if let Some(watcher) = &mut self.model_watcher {
+                watcher.poll(cx);
             }
-            self.rebuild_flat_list();
-            self.view.redraw(cx);
         }
 
         // Check for download completion or progress updates for all active downloads
@@ -306,9 +385,25 @@ impl LocalModelsApp {
 
         ::log::info!("Starting download for model: {} from {}", model_name, url);
 
+        // Refuse downloads that can't fit rather than failing midway. Paraformer
+        // is converted after download, which the engine budgets at 10% extra.
+        let size = model.storage.total_size_bytes;
+        let conversion = if url.contains("paraformer") { size / 10 } else { 0 };
+        let space = disk_space::check_download_space(std::path::Path::new(&dest_path), size, conversion);
+        if let SpaceCheck::Short { .. } = space {
+            let msg = space.message().unwrap_or_default();
+            ::log::warn!("Not downloading {}: {}", model_name, msg);
+            self.view.label(ids!(status_message)).set_text(
+                cx, &format!("Can't download {}: {}", model_name, msg)
+            );
+            self.view.redraw(cx);
+            return;
+        }
+
         // Update model status to Downloading
         config.models[model_index].status.state = ModelState::Downloading;
         config.save();
+        publish_state(cx, &config.models[model_index]);
 
         // Create or reset download state for this model
         let state = ModelDownloadState::new(&model_id);
@@ -316,13 +411,17 @@ impl LocalModelsApp {
         self.download_states.insert(model_id.clone(), state.clone());
 
         // Update UI
-        self.view.label(ids!(status_message)).set_text(
-            cx, &format!("Downloading {}...", model_name)
-        );
+        let status = match space.message() {
+            Some(note) => format!("Downloading {}... {}", model_name, note),
+            None => format!("Downloading {}...", model_name),
+        };
+        self.view.label(ids!(status_message)).set_text(cx, &status);
         self.view.redraw(cx);
 
         // Spawn download thread
+        let work = format!("Downloading {}", model_name);
         std::thread::spawn(move || {
+            let _work = shutdown::track(work, Some(state.cancel_requested.clone()));
             // Try primary URL first, then backups
             let all_urls: Vec<String> = std::iter::once(url.clone())
                 .chain(backup_urls.into_iter())
@@ -369,7 +468,7 @@ impl LocalModelsApp {
         source_type: SourceType,
     ) -> Result<(), String> {
         // Create HTTP client
-        let client = reqwest::blocking::Client::builder()
+        let client = moly_data::network::blocking_client()
             .timeout(std::time::Duration::from_secs(3600)) // 1 hour timeout for large files
             .build()
             .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
@@ -406,13 +505,12 @@ impl LocalModelsApp {
 
         // Calculate total size and set file count
         let total_size: u64 = files.iter().map(|(_, size)| *size).sum();
-        state.total_bytes.store(total_size, Ordering::SeqCst);
-        state.total_files.store(files.len() as u64, Ordering::SeqCst);
+        state.begin_files(total_size, files.len());
         ::log::info!("Total download size: {} bytes ({} files)", total_size, files.len());
 
         // Download each file
         let mut downloaded_bytes: u64 = 0;
-        for (file_index, (file_path, _file_size)) in files.iter().enumerate() {
+        for (file_index, (file_path, file_size)) in files.iter().enumerate() {
             if state.cancel_requested.load(Ordering::SeqCst) {
                 let _ = std::fs::remove_dir_all(dest_path);
                 return Err("Download cancelled".to_string());
@@ -420,6 +518,8 @@ impl LocalModelsApp {
 
             // Update current file info
             state.current_file_index.store(file_index as u64, Ordering::SeqCst);
+            state.current_file_bytes.store(0, Ordering::SeqCst);
+            state.current_file_total.store(*file_size, Ordering::SeqCst);
             *state.current_file.lock().unwrap() = Some(file_path.clone());
 
             let local_path = std::path::Path::new(dest_path).join(file_path);
@@ -470,13 +570,12 @@ impl LocalModelsApp {
         // Calculate total size (add 10% for conversion overhead)
         let download_size: u64 = files.iter().map(|(_, size)| *size).sum();
         let total_size = if is_paraformer { download_size + download_size / 10 } else { download_size };
-        state.total_bytes.store(total_size, Ordering::SeqCst);
-        state.total_files.store(files.len() as u64, Ordering::SeqCst);
+        state.begin_files(total_size, files.len());
         ::log::info!("Total download size: {} bytes ({} files)", download_size, files.len());
 
         // Download each file
         let mut downloaded_bytes: u64 = 0;
-        for (file_index, (file_path, _file_size)) in files.iter().enumerate() {
+        for (file_index, (file_path, file_size)) in files.iter().enumerate() {
             if state.cancel_requested.load(Ordering::SeqCst) {
                 let _ = std::fs::remove_dir_all(&download_dir);
                 return Err("Download cancelled".to_string());
@@ -484,6 +583,8 @@ impl LocalModelsApp {
 
             // Update current file info
             state.current_file_index.store(file_index as u64, Ordering::SeqCst);
+            state.current_file_bytes.store(0, Ordering::SeqCst);
+            state.current_file_total.store(*file_size, Ordering::SeqCst);
             *state.current_file.lock().unwrap() = Some(file_path.clone());
 
             let local_path = std::path::Path::new(&download_dir).join(file_path);
@@ -613,12 +714,17 @@ impl LocalModelsApp {
             return Err(format!("Failed to download: HTTP {}", response.status()));
         }
 
-        let mut file = std::fs::File::create(local_path)
+        // Written as `<name>.part` until complete, so scans never mistake an
+        // interrupted download for a finished file
+        let file_name = local_path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        let part_path = local_path.with_file_name(format!("{}{}", file_name, model_integrity::PART_SUFFIX));
+        let mut file = std::fs::File::create(&part_path)
             .map_err(|e| format!("Failed to create file: {}", e))?;
 
         use std::io::{Read, Write};
         let mut reader = response;
         let mut buffer = [0u8; 8192];
+        let mut file_bytes = 0u64;
 
         loop {
             if state.cancel_requested.load(Ordering::SeqCst) {
@@ -636,9 +742,13 @@ impl LocalModelsApp {
                 .map_err(|e| format!("Failed to write data: {}", e))?;
 
             *downloaded_bytes += bytes_read as u64;
-            state.progress_bytes.store(*downloaded_bytes, Ordering::SeqCst);
+            file_bytes += bytes_read as u64;
+            state.record_bytes(*downloaded_bytes, file_bytes);
         }
 
+        drop(file);
+        std::fs::rename(&part_path, local_path)
+            .map_err(|e| format!("Failed to finish file: {}", e))?;
         Ok(())
     }
 
@@ -753,6 +863,7 @@ impl LocalModelsApp {
                         cx, &format!("Successfully downloaded {}", model_name)
                     );
                 }
+                publish_state(cx, model);
             }
         }
 
@@ -762,20 +873,13 @@ impl LocalModelsApp {
     /// Update the progress bar UI for the currently selected model
     fn update_progress_bar(&mut self, cx: &mut Cx2d) {
         // Get selected model's download state
-        let (is_downloading, progress, progress_bytes, total_bytes, current_file) = self.config
+        let progress = self.config
             .as_ref()
             .and_then(|c| self.selected_model_index.and_then(|idx| c.models.get(idx)))
-            .and_then(|model| {
-                self.download_states.get(&model.id).map(|state| {
-                    let is_dl = state.is_downloading.load(Ordering::SeqCst);
-                    let prog = state.progress_percent();
-                    let prog_bytes = state.progress_bytes.load(Ordering::SeqCst);
-                    let total = state.total_bytes.load(Ordering::SeqCst);
-                    let file = state.current_file.lock().unwrap().clone();
-                    (is_dl, prog, prog_bytes, total, file)
-                })
-            })
-            .unwrap_or((false, 0.0, 0, 0, None));
+            .and_then(|model| self.download_states.get(&model.id))
+            .map(|state| state.to_download_progress())
+            .unwrap_or_default();
+        let is_downloading = progress.is_active;
 
         // Show/hide progress section
         self.view.view(ids!(progress_section)).apply_over(cx, live! {
@@ -792,28 +896,30 @@ impl LocalModelsApp {
 
         if is_downloading {
             // Update progress bar fill width (as percentage of parent)
-            let fill_width = (progress * 300.0) as i64; // Assuming ~300px width
+            let fraction = progress.progress_percent();
+            let fill_width = (fraction * 300.0) as i64; // Assuming ~300px width
             self.view.view(ids!(progress_bar_fill)).apply_over(cx, live! {
                 width: (fill_width)
             });
 
-            // Update progress text with current file info
-            let progress_text = if let Some(file) = current_file {
-                format!(
-                    "{:.1}% ({} / {}) - {}",
-                    progress * 100.0,
-                    format_bytes(progress_bytes),
-                    format_bytes(total_bytes),
-                    file
-                )
-            } else {
-                format!(
-                    "{:.1}% ({} / {})",
-                    progress * 100.0,
-                    format_bytes(progress_bytes),
-                    format_bytes(total_bytes)
-                )
-            };
+            // "12.5% (1.20 GB / 9.60 GB) - 45.2 MB/s, 3m 12s remaining, file 7/23 - model.safetensors (40%)"
+            let mut progress_text = format!(
+                "{:.1}% ({} / {})",
+                fraction * 100.0,
+                format_bytes(progress.overall_bytes),
+                format_bytes(progress.overall_total)
+            );
+            let rate = progress.rate_text();
+            if !rate.is_empty() {
+                progress_text.push_str(" - ");
+                progress_text.push_str(&rate);
+            }
+            if let Some(file) = &progress.current_file {
+                progress_text.push_str(&format!(" - {}", file));
+                if progress.current_file_total > 0 {
+                    progress_text.push_str(&format!(" ({:.0}%)", progress.file_progress_percent() * 100.0));
+                }
+            }
             self.view.label(ids!(progress_text)).set_text(cx, &progress_text);
         }
     }
@@ -869,12 +975,16 @@ impl LocalModelsApp {
             };
 
             if item.button(ids!(remove_item_button)).clicked(actions) {
-                self.remove_model_files(cx, model_idx);
+                self.selected_model_index = Some(model_idx);
+                self.request_remove(cx, model_idx);
                 return;
             }
 
             if let Some(fd) = item.as_view().finger_down(actions) {
                 if fd.tap_count == 1 {
+                    if self.selected_model_index != Some(model_idx) {
+                        self.reset_remove_confirmation(cx);
+                    }
                     self.selected_model_index = Some(model_idx);
                     self.view.redraw(cx);
                 }
@@ -882,23 +992,77 @@ impl LocalModelsApp {
         }
     }
 
-    /// Remove downloaded model files for a specific model
+    /// Rescan models whose files another screen downloaded or removed
+    fn handle_model_state_changes(&mut self, cx: &mut Cx, actions: &Actions) {
+        let Some(config) = &mut self.config else { return };
+        let mut changed = false;
+        for key in StoreEvent::model_state_changes(actions) {
+            // Wait for another screen's download to finish before rescanning
+            if model_state::download_status(&key) == DownloadStatus::Downloading { continue; }
+            let ids: Vec<String> = config.models.iter()
+                .filter(|m| m.expanded_path() == key)
+                .filter(|m| !self.download_states.get(&m.id)
+                    .map_or(false, |s| s.is_downloading.load(Ordering::SeqCst)))
+                .map(|m| m.id.clone())
+                .collect();
+            for id in ids {
+                config.refresh_model(&id);
+                changed = true;
+            }
+        }
+        if changed {
+            self.view.redraw(cx);
+        }
+    }
+
+    /// First click on Remove asks for confirmation, the second removes
+    fn request_remove(&mut self, cx: &mut Cx, model_index: usize) {
+        // Check if model is downloaded before removing
+        let is_ready = self.config.as_ref()
+            .and_then(|c| c.models.get(model_index))
+            .map(|m| m.status.state == ModelState::Ready)
+            .unwrap_or(false);
+        if !is_ready {
+            self.view.label(ids!(status_message)).set_text(cx, "Model is not downloaded");
+            self.view.redraw(cx);
+            return;
+        }
+
+        if self.pending_remove_index == Some(model_index) {
+            self.reset_remove_confirmation(cx);
+            self.remove_model_files(cx, model_index);
+        } else {
+            self.pending_remove_index = Some(model_index);
+            self.view.button(ids!(remove_button)).set_text(cx, "Are you sure?");
+            self.view.redraw(cx);
+        }
+    }
+
+    fn reset_remove_confirmation(&mut self, cx: &mut Cx) {
+        if self.pending_remove_index.take().is_some() {
+            self.view.button(ids!(remove_button)).set_text(cx, "Remove");
+        }
+    }
+
+    /// Move a model's files aside and offer to undo for a few seconds
     fn remove_model_files(&mut self, cx: &mut Cx, model_index: usize) {
+        // Only one removal can be undone at a time
+        self.commit_pending_removal(cx);
+
         let Some(config) = &mut self.config else { return };
         if model_index >= config.models.len() { return; }
 
         let model = &config.models[model_index];
+        let model_id = model.id.clone();
         let model_name = model.name.clone();
         let expanded_path = model.expanded_path();
 
         ::log::info!("Removing model files for: {} at {}", model_name, expanded_path);
 
-        // Try to remove the directory
         let path = std::path::Path::new(&expanded_path);
         if path.exists() {
-            match std::fs::remove_dir_all(path) {
-                Ok(_) => {
-                    ::log::info!("Successfully removed model files: {}", expanded_path);
+            match model_trash::stage_removal(path) {
+                Ok(pending) => {
                     // Update model status
                     let model = &mut config.models[model_index];
                     model.status.state = ModelState::NotAvailable;
@@ -909,12 +1073,14 @@ impl LocalModelsApp {
                         file.downloaded = false;
                     }
                     config.save();
-                    self.view.label(ids!(status_message)).set_text(
-                        cx, &format!("Removed {}", model_name)
-                    );
+                    self.view.label(ids!(status_message)).set_text(cx, "");
+                    self.view.label(ids!(undo_label)).set_text(cx, &format!("Removed {}", model_name));
+                    self.view.view(ids!(undo_bar)).set_visible(cx, true);
+                    self.undo_timer = cx.start_timeout(model_trash::UNDO_WINDOW.as_secs_f64());
+                    self.pending_removal = Some((model_id, pending));
                 }
                 Err(e) => {
-                    ::log::error!("Failed to remove model files: {:?}", e);
+                    ::log::error!("Failed to remove model files: {}", e);
                     self.view.label(ids!(status_message)).set_text(
                         cx, &format!("Failed to remove {}: {}", model_name, e)
                     );
@@ -932,8 +1098,43 @@ impl LocalModelsApp {
                 cx, &format!("{} was already removed", model_name)
             );
         }
+        publish_state(cx, &config.models[model_index]);
+
+        self.view.redraw(cx);
+    }
+
+    /// Put the pending removal's files back and rescan the model
+    fn undo_removal(&mut self, cx: &mut Cx) {
+        let Some((model_id, pending)) = self.pending_removal.take() else { return };
+        cx.stop_timer(self.undo_timer);
+        self.view.view(ids!(undo_bar)).set_visible(cx, false);
+        if let Err(e) = pending.undo() {
+            ::log::error!("Failed to restore model {}: {}", model_id, e);
+            self.view.label(ids!(status_message)).set_text(cx, &format!("Couldn't restore: {}", e));
+            self.view.redraw(cx);
+            return;
+        }
+        if let Some(config) = &mut self.config {
+            config.refresh_model(&model_id);
+            if let Some(model) = config.get_model(&model_id) {
+                publish_state(cx, model);
+            }
+        }
+        self.view.redraw(cx);
+    }
 
+    /// Trash (or delete) the pending removal's files in the background
+    fn commit_pending_removal(&mut self, cx: &mut Cx) {
+        let Some((model_id, pending)) = self.pending_removal.take() else { return };
+        cx.stop_timer(self.undo_timer);
+        self.view.view(ids!(undo_bar)).set_visible(cx, false);
         self.view.redraw(cx);
+        let hard_delete = model_trash::hard_delete();
+        std::thread::spawn(move || {
+            if let Err(e) = pending.commit(hard_delete) {
+                ::log::error!("Failed to remove model {}: {}", model_id, e);
+            }
+        });
     }
 
     /// Draw the models PortalList, grouped by category
@@ -1093,6 +1294,11 @@ impl LocalModelsApp {
     }
 }
 
+/// Share a model's download state with the other screens
+fn publish_state(cx: &mut Cx, model: &LocalModelV2) {
+    model_state::set_download_status(cx, &model.expanded_path(), DownloadStatus::from_local_state(model.status.state));
+}
+
 /// Format bytes as human-readable string
 fn format_bytes(bytes: u64) -> String {
     const KB: u64 = 1024;
@@ -9,10 +9,172 @@ live_design! {
     use link::shaders::*;
     use link::widgets::*;
     use moly_widgets::theme::*;
+    use moly_widgets::dialog::design::*;
+
+    McpButton = <Button> {
+        width: Fit, height: 30
+        padding: {left: 12, right: 12}
+        draw_bg: {
+            instance hover: 0.0
+            instance pressed: 0.0
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                sdf.box(1.0, 1.0, self.rect_size.x - 2.0, self.rect_size.y - 2.0, 5.0);
+                sdf.fill(mix(#ffffff, #f1f5f9, self.hover));
+                sdf.stroke(#d1d5db, 1.0);
+                return sdf.result;
+            }
+        }
+        draw_text: {
+            fn get_color(self) -> vec4 { return #374151; }
+            text_style: <FONT_MEDIUM>{ font_size: 10.5 }
+        }
+    }
+
+    McpPrimaryButton = <McpButton> {
+        draw_bg: {
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                sdf.box(1.0, 1.0, self.rect_size.x - 2.0, self.rect_size.y - 2.0, 5.0);
+                sdf.fill(mix(#3b82f6, #2563fa, self.hover));
+                return sdf.result;
+            }
+        }
+        draw_text: {
+            fn get_color(self) -> vec4 { return #ffffff; }
+        }
+    }
+
+    McpTextInput = <TextInput> {
+        width: Fill, height: 36
+        padding: {left: 10, right: 10, top: 8, bottom: 8}
+        cursor: Text
+        draw_bg: {
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                sdf.box(1.0, 1.0, self.rect_size.x - 2.0, self.rect_size.y - 2.0, 5.0);
+                sdf.fill(#ffffff);
+                sdf.stroke(#d1d5db, 1.0);
+                return sdf.result;
+            }
+        }
+        draw_text: {
+            color: (TEXT_PRIMARY)
+            color_empty: (TEXT_MUTED)
+            text_style: <FONT_REGULAR>{ font_size: 11.0 }
+        }
+    }
+
+    McpFieldLabel = <Label> {
+        draw_text: {
+            color: (TEXT_SECONDARY)
+            text_style: <FONT_SEMIBOLD>{ font_size: 10.5 }
+        }
+    }
+
+    McpHint = <Label> {
+        width: Fill
+        draw_text: {
+            color: (TEXT_MUTED)
+            text_style: <FONT_REGULAR>{ font_size: 10.0 }
+            wrap: Word
+        }
+    }
+
+    McpSectionTitle = <Label> {
+        draw_text: {
+            color: (TEXT_PRIMARY)
+            text_style: <FONT_SEMIBOLD>{ font_size: 14.0 }
+        }
+    }
+
+    // One catalog entry with its Install button
+    CatalogCard = <RoundedView> {
+        width: Fill, height: Fit
+        flow: Down
+        padding: 14
+        spacing: 4
+        visible: false
+        show_bg: true
+        draw_bg: {
+            color: (PANEL_BG)
+            border_radius: 8.0
+            border_color: (BORDER)
+            border_size: 1.0
+        }
+
+        <View> {
+            width: Fill, height: Fit
+            flow: Right
+            align: {y: 0.5}
+            spacing: 8
+
+            template_name = <Label> {
+                draw_text: {
+                    color: (TEXT_PRIMARY)
+                    text_style: <FONT_SEMIBOLD>{ font_size: 12.0 }
+                }
+            }
+            template_runtime = <Label> {
+                draw_text: {
+                    color: (TEXT_MUTED)
+                    text_style: <FONT_REGULAR>{ font_size: 9.5 }
+                }
+            }
+            <View> { width: Fill, height: 1 }
+            install_button = <McpPrimaryButton> { text: "Install" }
+        }
+        template_description = <McpHint> {}
+    }
+
+    // An installed server
+    ServerRow = <View> {
+        width: Fill, height: Fit
+        flow: Right
+        align: {y: 0.5}
+        padding: {top: 10, bottom: 10, left: 14, right: 14}
+        spacing: 10
+        show_bg: true
+        draw_bg: {
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                sdf.rect(0.0, self.rect_size.y - 1.0, self.rect_size.x, 1.0);
+                sdf.fill(#e5e7eb);
+                return sdf.result;
+            }
+        }
+
+        <View> {
+            width: Fill, height: Fit
+            flow: Down
+            spacing: 3
+
+            server_name = <Label> {
+                draw_text: {
+                    color: (TEXT_PRIMARY)
+                    text_style: <FONT_SEMIBOLD>{ font_size: 12.0 }
+                }
+            }
+            server_command = <McpHint> {}
+        }
+        edit_server_button = <McpButton> { text: "Edit" }
+        toggle_server_button = <McpButton> { text: "Disable" }
+        remove_server_button = <McpButton> { text: "Remove" }
+    }
+
+    // Label and input for one value a template asks for
+    TemplateInputField = <View> {
+        width: Fill, height: Fit
+        flow: Down
+        spacing: 4
+        visible: false
+        input_label = <McpFieldLabel> {}
+        input_value = <McpTextInput> {}
+    }
 
     pub McpApp = {{McpApp}} {
         width: Fill, height: Fill
-        flow: Down, align: {x: 0.5, y: 0.5}
+        flow: Overlay
         show_bg: true
         draw_bg: {
             fn pixel(self) -> vec4 {
@@ -20,24 +182,228 @@ live_design! {
             }
         }
 
-        title_label = <Label> {
-            text: "MCP App"
-            draw_text: {
-                fn get_color(self) -> vec4 {
-                    return #1f2937;
+        <View> {
+            width: Fill, height: Fill
+            flow: Down
+            padding: 24
+            spacing: 16
+
+            <View> {
+                width: Fill, height: Fit
+                flow: Right
+                align: {y: 0.5}
+
+                <View> {
+                    width: Fill, height: Fit
+                    flow: Down
+                    spacing: 4
+                    title_label = <Label> {
+                        text: "MCP Servers"
+                        draw_text: {
+                            color: (TEXT_PRIMARY)
+                            text_style: <FONT_SEMIBOLD>{ font_size: 22.0 }
+                        }
+                    }
+                    subtitle_label = <Label> {
+                        text: "Tools that chat models can use, through the Model Context Protocol (Desktop Only)"
+                        draw_text: {
+                            color: (TEXT_SECONDARY)
+                            text_style: <FONT_REGULAR>{ font_size: 11.0 }
+                        }
+                    }
+                }
+                add_custom_button = <McpButton> { text: "Add Custom Server…" }
+            }
+
+            <View> {
+                width: Fill, height: Fill
+                flow: Right
+                spacing: 20
+
+                // Installed servers
+                <RoundedView> {
+                    width: Fill, height: Fill
+                    flow: Down
+                    show_bg: true
+                    draw_bg: {
+                        color: (PANEL_BG)
+                        border_radius: 8.0
+                        border_color: (BORDER)
+                        border_size: 1.0
+                    }
+
+                    <View> {
+                        width: Fill, height: Fit
+                        padding: 14
+                        <McpSectionTitle> { text: "Installed" }
+                    }
+                    empty_servers_label = <McpHint> {
+                        padding: {left: 14, right: 14}
+                        text: "No MCP servers yet. Install one from the catalog, or add your own."
+                    }
+                    servers_list = <PortalList> {
+                        width: Fill, height: Fill
+                        drag_scrolling: false
+                        ServerRow = <ServerRow> {}
+                    }
+                }
+
+                // Catalog
+                <ScrollYView> {
+                    width: 380, height: Fill
+                    flow: Down
+                    spacing: 10
+
+                    <McpSectionTitle> { text: "Shared Folders" }
+                    <RoundedView> {
+                        width: Fill, height: Fit
+                        flow: Down
+                        padding: 14
+                        spacing: 8
+                        show_bg: true
+                        draw_bg: {
+                            color: (PANEL_BG)
+                            border_radius: 8.0
+                            border_color: (BORDER)
+                            border_size: 1.0
+                        }
+
+                        <McpHint> {
+                            text: "The built-in Files tool can list and read files in these folders, and write them when you allow each change."
+                        }
+                        shared_folders_label = <Label> {
+                            width: Fill
+                            draw_text: {
+                                color: (TEXT_PRIMARY)
+                                text_style: <FONT_REGULAR>{ font_size: 10.5 }
+                                wrap: Word
+                            }
+                        }
+                        <View> {
+                            width: Fill, height: Fit
+                            flow: Right
+                            spacing: 8
+                            add_folder_button = <McpButton> { text: "Add Folder…" }
+                            remove_folder_button = <McpButton> { text: "Remove…" }
+                        }
+                    }
+
+                    <McpSectionTitle> { text: "Run Code" }
+                    <RoundedView> {
+                        width: Fill, height: Fit
+                        flow: Down
+                        padding: 14
+                        spacing: 8
+                        show_bg: true
+                        draw_bg: {
+                            color: (PANEL_BG)
+                            border_radius: 8.0
+                            border_color: (BORDER)
+                            border_size: 1.0
+                        }
+
+                        <McpHint> {
+                            text: "Chats that turn on Run Code can run Python and JavaScript snippets after you approve each one. Snippets start in an empty folder and stop at these limits."
+                        }
+                        <View> {
+                            width: Fill, height: Fit
+                            flow: Right
+                            align: {y: 0.5}
+                            spacing: 8
+                            <McpFieldLabel> { text: "Seconds" }
+                            code_timeout_input = <McpTextInput> { width: 70, empty_text: "30" }
+                            <McpFieldLabel> { text: "Memory (MB)" }
+                            code_memory_input = <McpTextInput> { width: 80, empty_text: "512" }
+                            save_code_limits_button = <McpButton> { text: "Save" }
+                        }
+                        code_limits_status = <McpHint> {}
+                    }
+
+                    <McpSectionTitle> { text: "Catalog" }
+                    catalog_0 = <CatalogCard> {}
+                    catalog_1 = <CatalogCard> {}
+                    catalog_2 = <CatalogCard> {}
+                    catalog_3 = <CatalogCard> {}
+                    catalog_4 = <CatalogCard> {}
+                    catalog_5 = <CatalogCard> {}
+                    catalog_6 = <CatalogCard> {}
+                    catalog_7 = <CatalogCard> {}
                 }
-                text_style: <FONT_SEMIBOLD>{ font_size: 32.0 }
             }
         }
-        subtitle_label = <Label> {
-            margin: {top: 8}
-            text: "Model Context Protocol (Desktop Only)"
-            draw_text: {
-                fn get_color(self) -> vec4 {
-                    return #6b7280;
+
+        // Install / edit form (overlay)
+        server_form = <View> {
+            width: Fill, height: Fill
+            flow: Overlay
+            visible: false
+            show_bg: true
+            draw_bg: {
+                fn pixel(self) -> vec4 {
+                    return vec4(0.0, 0.0, 0.0, 0.45);
+                }
+            }
+
+            <View> {
+                width: Fill, height: Fill
+                align: {x: 0.5, y: 0.5}
+
+                <RoundedView> {
+                    width: 480, height: Fit
+                    flow: Down
+                    padding: 24
+                    spacing: 12
+                    show_bg: true
+                    draw_bg: {
+                        color: (PANEL_BG)
+                        border_radius: 10.0
+                        border_color: (BORDER)
+                        border_size: 1.0
+                    }
+
+                    form_title = <Label> {
+                        draw_text: {
+                            color: (TEXT_PRIMARY)
+                            text_style: <FONT_SEMIBOLD>{ font_size: 15.0 }
+                        }
+                    }
+                    form_hint = <McpHint> {}
+
+                    <McpFieldLabel> { text: "Name" }
+                    form_name = <McpTextInput> { empty_text: "e.g. Notes" }
+                    <McpFieldLabel> { text: "Command" }
+                    form_command = <McpTextInput> { empty_text: "npx" }
+                    <McpFieldLabel> { text: "Arguments" }
+                    form_args = <McpTextInput> { empty_text: "-y @scope/server" }
+
+                    input_0 = <TemplateInputField> {}
+                    input_1 = <TemplateInputField> {}
+                    input_2 = <TemplateInputField> {}
+
+                    <McpFieldLabel> { text: "Environment" }
+                    form_env = <McpTextInput> { empty_text: "KEY=value KEY2=value" }
+
+                    form_status = <Label> {
+                        width: Fill
+                        draw_text: {
+                            color: (ACCENT_RED)
+                            text_style: <FONT_REGULAR>{ font_size: 10.5 }
+                            wrap: Word
+                        }
+                    }
+
+                    <View> {
+                        width: Fill, height: Fit
+                        flow: Right
+                        spacing: 10
+                        align: {x: 1.0}
+                        cancel_form_button = <McpButton> { text: "Cancel" }
+                        save_form_button = <McpPrimaryButton> { text: "Add Server" }
+                    }
                 }
-                text_style: <FONT_REGULAR>{ font_size: 14.0 }
             }
         }
+
+        dialog = <MolyDialog> {}
     }
 }
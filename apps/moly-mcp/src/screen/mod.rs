@@ -1,21 +1,361 @@
 //! MCP Screen Widget Implementation
+//!
+//! Lists the MCP servers set up in preferences and the curated catalog.
+//! Installing from the catalog opens the server form prefilled with the
+//! template's command and arguments, plus a field for each value the
+//! template needs (a folder, an API key); adding a custom server or editing
+//! one uses the same form.
+//!
+//! It also manages the folders shared with the built-in Files tool and the
+//! limits of the Run Code tool.
 
 pub mod design;
 
 use makepad_widgets::*;
+use moly_data::code_tool::{self, CodeToolSettings};
+use moly_data::mcp_catalog::{self, McpTemplate, TemplateInput, MAX_TEMPLATE_INPUTS, MCP_CATALOG};
+use moly_data::mcp_servers::{self, McpServerConfig};
+use moly_data::platform::FileDialog;
+use moly_data::Store;
+use moly_widgets::{DialogRequest, DialogResult, MolyDialogWidgetRefExt};
+use std::collections::HashMap;
+
+/// Widget ids of the catalog card slots in `design.rs`
+const CATALOG_IDS: [LiveId; 8] = [
+    live_id!(catalog_0), live_id!(catalog_1), live_id!(catalog_2), live_id!(catalog_3),
+    live_id!(catalog_4), live_id!(catalog_5), live_id!(catalog_6), live_id!(catalog_7),
+];
+
+/// Widget ids of the template input slots of the server form
+const INPUT_IDS: [LiveId; MAX_TEMPLATE_INPUTS] = [live_id!(input_0), live_id!(input_1), live_id!(input_2)];
+
+/// What the server form is for
+#[derive(Clone, Debug)]
+enum FormMode {
+    Install(&'static McpTemplate),
+    Custom,
+    /// Editing this server, as saved
+    Edit(McpServerConfig),
+}
 
 #[derive(Live, LiveHook, Widget)]
 pub struct McpApp {
     #[deref]
     pub view: View,
+
+    #[rust]
+    catalog_shown: bool,
+
+    /// Names of the installed servers, in list order
+    #[rust]
+    server_names: Vec<String>,
+
+    /// Open server form, if any
+    #[rust]
+    form: Option<FormMode>,
+
+    /// Template inputs shown in the form's input slots
+    #[rust]
+    form_inputs: Vec<TemplateInput>,
+
+    /// Server waiting for the remove confirmation
+    #[rust]
+    pending_remove: Option<String>,
 }
 
 impl Widget for McpApp {
     fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
-        self.view.handle_event(cx, event, scope);
+        if !self.catalog_shown {
+            self.catalog_shown = true;
+            self.show_catalog(cx);
+            self.show_code_limits(cx, scope);
+        }
+
+        let actions = cx.capture_actions(|cx| self.view.handle_event(cx, event, scope));
+
+        for (template, id) in MCP_CATALOG.iter().zip(CATALOG_IDS) {
+            if self.view.button(&[id, live_id!(install_button)]).clicked(&actions) {
+                self.open_form(cx, FormMode::Install(template));
+            }
+        }
+        if self.view.button(ids!(add_custom_button)).clicked(&actions) {
+            self.open_form(cx, FormMode::Custom);
+        }
+        if self.view.button(ids!(cancel_form_button)).clicked(&actions) {
+            self.close_form(cx);
+        }
+        if self.view.button(ids!(save_form_button)).clicked(&actions) {
+            self.save_form(cx, scope);
+        }
+        if self.view.button(ids!(add_folder_button)).clicked(&actions) {
+            if let Some(dir) = FileDialog::new().set_title("Share Folder with Chat Models").pick_folder() {
+                if let Some(store) = scope.data.get_mut::<Store>() {
+                    store.preferences.add_fs_tool_dir(dir.to_string_lossy().to_string());
+                }
+                self.view.redraw(cx);
+            }
+        }
+        if self.view.button(ids!(save_code_limits_button)).clicked(&actions) {
+            self.save_code_limits(cx, scope);
+        }
+        if self.view.button(ids!(remove_folder_button)).clicked(&actions) {
+            let dirs = scope.data.get::<Store>().map(|s| s.preferences.fs_tool_dirs.clone()).unwrap_or_default();
+            if !dirs.is_empty() {
+                self.view.moly_dialog(ids!(dialog)).open(cx,
+                    DialogRequest::select(live_id!(remove_folder), "Stop Sharing a Folder",
+                        "Chat models will no longer be able to use its files.", dirs, 0)
+                        .confirm_label("Remove")
+                        .danger());
+            }
+        }
+
+        self.handle_server_list_clicks(cx, scope, &actions);
+
+        if let Some((id, result)) = self.view.moly_dialog(ids!(dialog)).closed(&actions) {
+            if id == live_id!(remove_server) {
+                let name = self.pending_remove.take();
+                if let (Some(name), DialogResult::Confirmed) = (name, result) {
+                    if let Some(store) = scope.data.get_mut::<Store>() {
+                        store.preferences.remove_mcp_server(&name);
+                    }
+                    self.view.redraw(cx);
+                }
+            } else if id == live_id!(remove_folder) {
+                let store = scope.data.get_mut::<Store>();
+                if let (Some(store), DialogResult::Selected(index)) = (store, result) {
+                    if let Some(dir) = store.preferences.fs_tool_dirs.get(index).cloned() {
+                        store.preferences.remove_fs_tool_dir(&dir);
+                    }
+                    self.view.redraw(cx);
+                }
+            }
+        }
     }
 
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
-        self.view.draw_walk(cx, scope, walk)
+        self.server_names = scope.data.get::<Store>()
+            .map(|s| s.preferences.mcp_servers.iter().map(|m| m.name.clone()).collect())
+            .unwrap_or_default();
+        self.view.label(ids!(empty_servers_label)).set_visible(cx, self.server_names.is_empty());
+        let folders = scope.data.get::<Store>()
+            .map(|s| s.preferences.fs_tool_dirs.join("\n"))
+            .unwrap_or_default();
+        let folders = if folders.is_empty() { "No folders shared yet.".to_string() } else { folders };
+        self.view.label(ids!(shared_folders_label)).set_text(cx, &folders);
+
+        let servers_list = self.view.portal_list(ids!(servers_list));
+        let servers_list_uid = servers_list.widget_uid();
+
+        while let Some(widget) = self.view.draw_walk(cx, scope, walk).step() {
+            if widget.widget_uid() == servers_list_uid {
+                self.draw_servers_list(cx, scope, widget);
+            }
+        }
+        DrawStep::done()
+    }
+}
+
+impl McpApp {
+    fn show_catalog(&mut self, cx: &mut Cx) {
+        if MCP_CATALOG.len() > CATALOG_IDS.len() {
+            ::log::warn!("MCP catalog has {} entries; only {} are shown", MCP_CATALOG.len(), CATALOG_IDS.len());
+        }
+        for (i, id) in CATALOG_IDS.iter().enumerate() {
+            let card = self.view.view(&[*id]);
+            let Some(template) = MCP_CATALOG.get(i) else {
+                card.set_visible(cx, false);
+                continue;
+            };
+            card.label(ids!(template_name)).set_text(cx, template.name);
+            card.label(ids!(template_runtime)).set_text(cx, template.runtime_hint());
+            card.label(ids!(template_description)).set_text(cx, template.description);
+            card.set_visible(cx, true);
+        }
+    }
+
+    fn show_code_limits(&mut self, cx: &mut Cx, scope: &mut Scope) {
+        let limits = scope.data.get::<Store>().map(|s| s.preferences.code_tool.clone()).unwrap_or_default();
+        self.view.text_input(ids!(code_timeout_input)).set_text(cx, &limits.timeout_secs.to_string());
+        self.view.text_input(ids!(code_memory_input)).set_text(cx, &limits.memory_mb.to_string());
+    }
+
+    fn save_code_limits(&mut self, cx: &mut Cx, scope: &mut Scope) {
+        let parse = |id: &[LiveId], what: &str| {
+            let text = self.view.text_input(id).text();
+            text.trim().parse::<u64>().map_err(|_| format!("{} must be a whole number", what))
+        };
+        let result = parse(ids!(code_timeout_input), "Seconds").and_then(|timeout_secs| {
+            let limits = CodeToolSettings { timeout_secs, memory_mb: parse(ids!(code_memory_input), "Memory")? };
+            limits.validate()?;
+            let store = scope.data.get_mut::<Store>().ok_or("Settings are not loaded")?;
+            code_tool::set_current(&limits);
+            store.preferences.code_tool = limits;
+            store.preferences.save();
+            Ok(())
+        });
+        let status = match result {
+            Ok(()) => "Saved.".to_string(),
+            Err(e) => e,
+        };
+        self.view.label(ids!(code_limits_status)).set_text(cx, &status);
+        self.view.redraw(cx);
+    }
+
+    fn draw_servers_list(&mut self, cx: &mut Cx2d, scope: &mut Scope, widget: WidgetRef) {
+        let binding = widget.as_portal_list();
+        let Some(mut list) = binding.borrow_mut() else { return };
+        list.set_item_range(cx, 0, self.server_names.len());
+
+        while let Some(item_id) = list.next_visible_item(cx) {
+            let Some(server) = scope.data.get::<Store>()
+                .and_then(|s| s.preferences.mcp_servers.get(item_id).cloned())
+            else { continue };
+            let item = list.item(cx, item_id, live_id!(ServerRow));
+            let name = if server.enabled { server.name.clone() } else { format!("{} (disabled)", server.name) };
+            item.label(ids!(server_name)).set_text(cx, &name);
+            let mut command = server.command_line();
+            if !server.env.is_empty() {
+                let keys: Vec<&str> = server.env.keys().map(String::as_str).collect();
+                command.push_str(&format!("\nEnvironment: {}", keys.join(", ")));
+            }
+            item.label(ids!(server_command)).set_text(cx, &command);
+            item.button(ids!(toggle_server_button)).set_text(cx, if server.enabled { "Disable" } else { "Enable" });
+            item.draw_all(cx, scope);
+        }
+    }
+
+    fn handle_server_list_clicks(&mut self, cx: &mut Cx, scope: &mut Scope, actions: &Actions) {
+        let servers_list = self.view.portal_list(ids!(servers_list));
+        for (item_id, item) in servers_list.items_with_actions(actions) {
+            let Some(name) = self.server_names.get(item_id).cloned() else { continue };
+            if item.button(ids!(edit_server_button)).clicked(actions) {
+                let server = scope.data.get::<Store>()
+                    .and_then(|s| s.preferences.mcp_servers.iter().find(|m| m.name == name).cloned());
+                if let Some(server) = server {
+                    self.open_form(cx, FormMode::Edit(server));
+                }
+            } else if item.button(ids!(toggle_server_button)).clicked(actions) {
+                if let Some(store) = scope.data.get_mut::<Store>() {
+                    let enabled = store.preferences.mcp_servers.iter().any(|s| s.name == name && s.enabled);
+                    store.preferences.set_mcp_server_enabled(&name, !enabled);
+                }
+                self.view.redraw(cx);
+            } else if item.button(ids!(remove_server_button)).clicked(actions) {
+                self.view.moly_dialog(ids!(dialog)).open(cx,
+                    DialogRequest::confirm(live_id!(remove_server), format!("Remove {}?", name),
+                        "Chats will no longer be able to use its tools.")
+                        .confirm_label("Remove")
+                        .danger());
+                self.pending_remove = Some(name);
+            }
+        }
+    }
+
+    // ── Server form ──
+
+    fn open_form(&mut self, cx: &mut Cx, mode: FormMode) {
+        let (title, hint, save_label, server, inputs) = match &mode {
+            FormMode::Install(template) => {
+                let hint = match template.runtime_hint() {
+                    "" => template.description.to_string(),
+                    runtime => format!("{} {}.", template.description, runtime),
+                };
+                (format!("Install {}", template.name), hint, "Add Server", template.server(), template.inputs.to_vec())
+            }
+            FormMode::Custom => (
+                "Add MCP Server".to_string(),
+                "A local command that speaks MCP over stdio.".to_string(),
+                "Add Server",
+                McpServerConfig::new("", "", Vec::new()),
+                Vec::new(),
+            ),
+            FormMode::Edit(server) => {
+                // Arguments are already filled in; only secrets get their own field
+                let inputs: Vec<TemplateInput> = server.template_id.as_deref()
+                    .and_then(mcp_catalog::find_template)
+                    .map(|t| t.inputs.iter().filter(|i| i.secret).copied().collect())
+                    .unwrap_or_default();
+                (format!("Edit {}", server.name), String::new(), "Save", server.clone(), inputs)
+            }
+        };
+
+        self.view.label(ids!(form_title)).set_text(cx, &title);
+        let hint_label = self.view.label(ids!(form_hint));
+        hint_label.set_text(cx, &hint);
+        hint_label.set_visible(cx, !hint.is_empty());
+        self.view.text_input(ids!(form_name)).set_text(cx, &server.name);
+        self.view.text_input(ids!(form_command)).set_text(cx, &server.command);
+        self.view.text_input(ids!(form_args)).set_text(cx, &mcp_servers::join_args(&server.args));
+        let env = mcp_servers::format_env(&server.env, |key| inputs.iter().any(|i| i.key == key));
+        self.view.text_input(ids!(form_env)).set_text(cx, &env);
+
+        for (i, id) in INPUT_IDS.iter().enumerate() {
+            let field = self.view.view(&[*id]);
+            let Some(input) = inputs.get(i) else {
+                field.set_visible(cx, false);
+                continue;
+            };
+            let label = if input.required { input.label.to_string() } else { format!("{} (optional)", input.label) };
+            field.label(ids!(input_label)).set_text(cx, &label);
+            let value = field.text_input(ids!(input_value));
+            value.set_text(cx, server.env.get(input.key).map_or("", String::as_str));
+            value.apply_over(cx, live! { empty_text: (input.placeholder), is_password: (input.secret) });
+            field.set_visible(cx, true);
+        }
+
+        self.view.button(ids!(save_form_button)).set_text(cx, save_label);
+        self.view.label(ids!(form_status)).set_text(cx, "");
+        self.view.view(ids!(server_form)).set_visible(cx, true);
+        self.form_inputs = inputs;
+        self.form = Some(mode);
+        self.view.redraw(cx);
+    }
+
+    fn close_form(&mut self, cx: &mut Cx) {
+        self.form = None;
+        self.form_inputs.clear();
+        self.view.view(ids!(server_form)).set_visible(cx, false);
+        self.view.redraw(cx);
+    }
+
+    /// The server described by the form
+    fn read_form(&self) -> Result<McpServerConfig, String> {
+        let mut server = match &self.form {
+            Some(FormMode::Install(template)) => template.server(),
+            Some(FormMode::Edit(server)) => server.clone(),
+            _ => McpServerConfig::new("", "", Vec::new()),
+        };
+        server.name = self.view.text_input(ids!(form_name)).text().trim().to_string();
+        server.command = self.view.text_input(ids!(form_command)).text().trim().to_string();
+        server.args = mcp_servers::split_args(&self.view.text_input(ids!(form_args)).text());
+        server.env = mcp_servers::parse_env(&self.view.text_input(ids!(form_env)).text())?;
+
+        let values: HashMap<&str, String> = self.form_inputs.iter().zip(INPUT_IDS)
+            .map(|(input, id)| (input.key, self.view.view(&[id]).text_input(ids!(input_value)).text()))
+            .collect();
+        mcp_catalog::apply_inputs(&self.form_inputs, &values, &mut server)?;
+        if let Some(placeholder) = server.args.iter().find(|a| a.contains('{') && a.contains('}')) {
+            return Err(format!("Replace {} in the arguments", placeholder));
+        }
+        Ok(server)
+    }
+
+    fn save_form(&mut self, cx: &mut Cx, scope: &mut Scope) {
+        let replace = match &self.form {
+            Some(FormMode::Edit(server)) => Some(server.name.clone()),
+            Some(_) => None,
+            None => return,
+        };
+        let result = self.read_form().and_then(|server| {
+            let store = scope.data.get_mut::<Store>().ok_or("Settings are not loaded")?;
+            store.preferences.save_mcp_server(server, replace.as_deref())
+        });
+        match result {
+            Ok(()) => self.close_form(cx),
+            Err(e) => {
+                self.view.label(ids!(form_status)).set_text(cx, &e);
+                self.view.redraw(cx);
+            }
+        }
     }
 }
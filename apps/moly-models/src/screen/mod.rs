@@ -3,7 +3,8 @@
 pub mod design;
 
 use makepad_widgets::*;
-use moly_data::{Store, Model, ModelFile, FileId, PendingDownload, PendingDownloadsStatus, ServerConnectionStatus};
+use moly_data::{Store, Model, ModelFile, FileId, PendingDownload, PendingDownloadsStatus, ServerConnectionStatus, DownloadStatus, StoreEvent};
+use moly_data::model_state;
 use std::sync::{Arc, Mutex};
 use std::collections::HashMap;
 
@@ -106,6 +107,13 @@ impl Widget for ModelsApp {
         // Check for async task results
         self.check_task_results(cx, scope);
 
+        // Download states shared with the other model screens
+        if let Event::Actions(actions) = event {
+            if StoreEvent::model_state_changes(actions).next().is_some() {
+                self.view.redraw(cx);
+            }
+        }
+
         // Handle events
         let actions = cx.capture_actions(|cx| {
             self.view.handle_event(cx, event, scope);
@@ -289,9 +297,17 @@ impl ModelsApp {
                 }
                 ModelsTaskResult::DownloadStarted(Err(e)) => {
                     ::log::error!("Failed to start download: {}", e);
+                    let failed: Vec<FileId> = self.active_downloads.iter()
+                        .filter(|(_, d)| matches!(d.status, PendingDownloadsStatus::Initializing))
+                        .map(|(id, _)| id.clone())
+                        .collect();
+                    for id in failed {
+                        self.active_downloads.remove(&id);
+                        model_state::set_download_status(cx, &id, DownloadStatus::Error);
+                    }
                 }
                 ModelsTaskResult::DownloadsUpdate(Ok(downloads)) => {
-                    self.update_downloads_state(downloads);
+                    self.update_downloads_state(cx, downloads);
                 }
                 ModelsTaskResult::DownloadsUpdate(Err(e)) => {
                     ::log::error!("Failed to get downloads: {}", e);
@@ -302,7 +318,7 @@ impl ModelsApp {
     }
 
     /// Update download state from pending downloads
-    fn update_downloads_state(&mut self, downloads: Vec<PendingDownload>) {
+    fn update_downloads_state(&mut self, cx: &mut Cx, downloads: Vec<PendingDownload>) {
         // Update or add downloads
         for download in &downloads {
             let file_id = download.file.id.clone();
@@ -320,9 +336,17 @@ impl ModelsApp {
             }
         }
 
-        // Remove completed downloads
+        // Remove finished downloads and share their outcome with the other screens
         let active_ids: Vec<_> = downloads.iter().map(|d| d.file.id.clone()).collect();
-        self.active_downloads.retain(|id, _| active_ids.contains(id));
+        let finished: Vec<(FileId, bool)> = self.active_downloads.iter()
+            .filter(|(id, _)| !active_ids.contains(id))
+            .map(|(id, d)| (id.clone(), matches!(d.status, PendingDownloadsStatus::Error)))
+            .collect();
+        for (id, failed) in finished {
+            self.active_downloads.remove(&id);
+            let status = if failed { DownloadStatus::Error } else { DownloadStatus::Downloaded };
+            model_state::set_download_status(cx, &id, status);
+        }
 
         // Stop polling if no more downloads
         if self.active_downloads.is_empty() {
@@ -446,7 +470,9 @@ impl ModelsApp {
                         let progress_text = format!("{}%", (download_state.progress * 100.0) as u32);
                         item_widget.button(ids!(download_btn)).set_text(cx, &progress_text);
                     }
-                } else if first_file.downloaded {
+                } else if first_file.downloaded
+                    || model_state::download_status(&first_file.id) == DownloadStatus::Downloaded
+                {
                     item_widget.button(ids!(download_btn)).set_text(cx, "Downloaded");
                 } else {
                     item_widget.button(ids!(download_btn)).set_text(cx, "Download");
@@ -474,7 +500,9 @@ impl ModelsApp {
                     let model = &self.models[item_id];
                     if !model.files.is_empty() {
                         let file = &model.files[0];
-                        if !file.downloaded && !self.active_downloads.contains_key(&file.id) {
+                        let downloaded = file.downloaded
+                            || model_state::download_status(&file.id) == DownloadStatus::Downloaded;
+                        if !downloaded && !self.active_downloads.contains_key(&file.id) {
                             self.start_download(cx, scope, file.clone(), model.name.clone());
                         }
                     }
@@ -498,6 +526,7 @@ impl ModelsApp {
             progress: 0.0,
             status: PendingDownloadsStatus::Initializing,
         });
+        model_state::set_download_status(cx, &file_id, DownloadStatus::Downloading);
 
         self.view.redraw(cx);
 
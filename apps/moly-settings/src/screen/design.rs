@@ -317,6 +317,486 @@ live_design! {
 
                 ProviderListItem = <ProviderItem> {}
             }
+
+            // Removed models go to the Trash unless this is on
+            hard_delete_bar = <View> {
+                width: Fill, height: Fit
+                flow: Right
+                align: {y: 0.5}
+                spacing: 12
+                padding: {left: 16, right: 16, top: 12}
+
+                <SettingsLabel> { text: "Delete removed models permanently" }
+                <View> { width: Fill, height: 1 }
+                hard_delete_toggle = <EnableToggle> {}
+            }
+
+            // The runtime may outlive the app, e.g. when started separately
+            unload_on_quit_bar = <View> {
+                width: Fill, height: Fit
+                flow: Right
+                align: {y: 0.5}
+                spacing: 12
+                padding: {left: 16, right: 16, top: 12}
+
+                <SettingsLabel> { text: "Unload models when quitting" }
+                <View> { width: Fill, height: 1 }
+                unload_on_quit_toggle = <EnableToggle> {}
+            }
+
+            // Emails, phone numbers and keys in prompts to cloud providers
+            privacy_filter_bar = <View> {
+                width: Fill, height: Fit
+                flow: Down
+                spacing: 4
+                padding: {left: 16, right: 16, top: 12}
+
+                <View> {
+                    width: Fill, height: Fit
+                    flow: Right
+                    align: {y: 0.5}
+                    spacing: 12
+
+                    <SettingsLabel> { text: "Privacy filter" }
+                    <View> { width: Fill, height: 1 }
+                    privacy_filter_toggle = <EnableToggle> {}
+                }
+                <SettingsHint> {
+                    width: Fill
+                    text: "Before a message goes to a cloud provider, list the email addresses, phone numbers and API keys in it and offer to replace them with placeholders. Answers show the real values."
+                    draw_text: { wrap: Word }
+                }
+            }
+
+            // Slower background checks, one download at a time, and a
+            // warning before loading large models
+            power_bar = <View> {
+                width: Fill, height: Fit
+                flow: Down
+                spacing: 4
+                padding: {left: 16, right: 16, top: 12}
+
+                <View> {
+                    width: Fill, height: Fit
+                    flow: Right
+                    align: {y: 0.5}
+                    spacing: 12
+
+                    <SettingsLabel> { text: "Low power mode" }
+                    <View> { width: Fill, height: 1 }
+                    low_power_toggle = <EnableToggle> {}
+                }
+                <View> {
+                    width: Fill, height: Fit
+                    flow: Right
+                    align: {y: 0.5}
+                    spacing: 12
+
+                    <SettingsLabel> { text: "Use low power mode on battery" }
+                    <View> { width: Fill, height: 1 }
+                    power_battery_toggle = <EnableToggle> {}
+                }
+                power_status = <SettingsHint> {
+                    width: Fill
+                    text: ""
+                    draw_text: { wrap: Word }
+                }
+            }
+
+            // Serve downloaded models to other instances on the network
+            lan_share_bar = <View> {
+                width: Fill, height: Fit
+                flow: Down
+                spacing: 4
+                padding: {left: 16, right: 16, top: 12}
+
+                <View> {
+                    width: Fill, height: Fit
+                    flow: Right
+                    align: {y: 0.5}
+                    spacing: 12
+
+                    <SettingsLabel> { text: "Share models on local network" }
+                    <View> { width: Fill, height: 1 }
+                    lan_share_toggle = <EnableToggle> {}
+                }
+                lan_share_status = <SettingsHint> {
+                    width: Fill
+                    text: ""
+                    draw_text: { wrap: Word }
+                }
+            }
+
+            // OpenAI-compatible endpoint for phones and other computers
+            api_proxy_bar = <View> {
+                width: Fill, height: Fit
+                flow: Down
+                spacing: 4
+                padding: {left: 16, right: 16, top: 12}
+
+                <View> {
+                    width: Fill, height: Fit
+                    flow: Right
+                    align: {y: 0.5}
+                    spacing: 12
+
+                    <SettingsLabel> { text: "Serve models to other devices" }
+                    <View> { width: Fill, height: 1 }
+                    api_proxy_toggle = <EnableToggle> {}
+                }
+                api_proxy_status = <SettingsHint> {
+                    width: Fill
+                    text: ""
+                    draw_text: { wrap: Word }
+                }
+                api_clients_button = <TestButton> { text: "Manage Clients…" }
+            }
+
+            // Models folder, download limits and mirrors
+            downloads_bar = <View> {
+                width: Fill, height: Fit
+                flow: Down
+                spacing: 4
+                padding: {left: 16, right: 16, top: 12}
+
+                <View> {
+                    width: Fill, height: Fit
+                    flow: Right
+                    align: {y: 0.5}
+                    spacing: 12
+
+                    <SettingsLabel> { text: "Storage & downloads" }
+                    <View> { width: Fill, height: 1 }
+                    downloads_button = <TestButton> { text: "Configure…" }
+                }
+                downloads_summary = <SettingsHint> {
+                    width: Fill
+                    text: ""
+                    draw_text: { wrap: Word }
+                }
+            }
+
+            // Proxy and custom root certificates for all connections
+            network_bar = <View> {
+                width: Fill, height: Fit
+                flow: Down
+                spacing: 4
+                padding: {left: 16, right: 16, top: 12}
+
+                <View> {
+                    width: Fill, height: Fit
+                    flow: Right
+                    align: {y: 0.5}
+                    spacing: 12
+
+                    <SettingsLabel> { text: "Proxy & certificates" }
+                    <View> { width: Fill, height: 1 }
+                    network_button = <TestButton> { text: "Configure…" }
+                }
+                network_summary = <SettingsHint> {
+                    width: Fill
+                    text: ""
+                    draw_text: { wrap: Word }
+                }
+            }
+
+            // Default system prompts for chats opened from the Model Hub
+            system_prompts_bar = <View> {
+                width: Fill, height: Fit
+                flow: Down
+                spacing: 4
+                padding: {left: 16, right: 16, top: 12}
+
+                <View> {
+                    width: Fill, height: Fit
+                    flow: Right
+                    align: {y: 0.5}
+                    spacing: 12
+
+                    <SettingsLabel> { text: "System prompts" }
+                    <View> { width: Fill, height: 1 }
+                    system_prompts_button = <TestButton> { text: "Edit…" }
+                }
+                <SettingsHint> {
+                    width: Fill
+                    text: "Given to chats opened from the Model Hub by kind of model. Each chat can change its own with Prompt…"
+                    draw_text: { wrap: Word }
+                }
+            }
+
+            // Opt-in usage statistics, kept locally
+            telemetry_bar = <View> {
+                width: Fill, height: Fit
+                flow: Down
+                spacing: 4
+                padding: {left: 16, right: 16, top: 12}
+
+                <View> {
+                    width: Fill, height: Fit
+                    flow: Right
+                    align: {y: 0.5}
+                    spacing: 12
+
+                    <SettingsLabel> { text: "Usage statistics" }
+                    <View> { width: Fill, height: 1 }
+                    telemetry_toggle = <EnableToggle> {}
+                }
+                telemetry_summary = <SettingsHint> {
+                    width: Fill
+                    text: ""
+                    draw_text: { wrap: Word }
+                }
+                telemetry_button = <TestButton> { text: "View Statistics…" }
+            }
+
+            // Chat history search by meaning, with a local embedding model
+            semantic_search_bar = <View> {
+                width: Fill, height: Fit
+                flow: Down
+                spacing: 4
+                padding: {left: 16, right: 16, top: 12}
+
+                <View> {
+                    width: Fill, height: Fit
+                    flow: Right
+                    align: {y: 0.5}
+                    spacing: 12
+
+                    <SettingsLabel> { text: "Search chats by meaning" }
+                    <View> { width: Fill, height: 1 }
+                    semantic_search_toggle = <EnableToggle> {}
+                }
+                embedding_model_input = <SettingsTextInput> { empty_text: "Embedding model, e.g. bge-small-en-v1.5" }
+                reranker_model_input = <SettingsTextInput> { empty_text: "Reranker model (optional), e.g. bge-reranker-base" }
+                <SettingsHint> {
+                    width: Fill
+                    text: "Session History also lists chats related to the search, using this embedding model in the local runtime. With a reranker, the closest chats are ranked again by how well they answer the search. Messages never leave this computer."
+                    draw_text: { wrap: Word }
+                }
+            }
+
+            // Quick actions for copied text
+            clipboard_watch_bar = <View> {
+                width: Fill, height: Fit
+                flow: Down
+                spacing: 4
+                padding: {left: 16, right: 16, top: 12}
+
+                <View> {
+                    width: Fill, height: Fit
+                    flow: Right
+                    align: {y: 0.5}
+                    spacing: 12
+
+                    <SettingsLabel> { text: "Clipboard quick actions" }
+                    <View> { width: Fill, height: 1 }
+                    clipboard_watch_toggle = <EnableToggle> {}
+                }
+                <View> {
+                    width: Fill, height: Fit
+                    flow: Right
+                    align: {y: 0.5}
+                    spacing: 12
+
+                    <SettingsHint> { text: "Offer for copies of at least" }
+                    clipboard_min_chars_input = <SettingsTextInput> { width: 80, empty_text: "20" }
+                    <SettingsHint> { text: "characters" }
+                }
+                <View> {
+                    width: Fill, height: Fit
+                    flow: Right
+                    align: {y: 0.5}
+                    spacing: 12
+
+                    <SettingsHint> { text: "Ignore passwords, keys and card numbers" }
+                    <View> { width: Fill, height: 1 }
+                    clipboard_skip_secrets_toggle = <EnableToggle> {}
+                }
+                <SettingsHint> {
+                    width: Fill
+                    text: "Copying text offers Summarize, Translate or Ask about this, each opening a new chat with the text. The clipboard is only read while this is on, and the text goes nowhere until you pick an action."
+                    draw_text: { wrap: Word }
+                }
+            }
+
+            // System-wide shortcut opening Quick Ask
+            quick_ask_bar = <View> {
+                width: Fill, height: Fit
+                flow: Down
+                spacing: 4
+                padding: {left: 16, right: 16, top: 12}
+
+                <View> {
+                    width: Fill, height: Fit
+                    flow: Right
+                    align: {y: 0.5}
+                    spacing: 12
+
+                    <SettingsLabel> { text: "Quick Ask shortcut" }
+                    <View> { width: Fill, height: 1 }
+                    quick_ask_hotkey_input = <SettingsTextInput> { width: 160, empty_text: "Ctrl+Alt+Space" }
+                    quick_ask_register_button = <TestButton> { text: "Add Shortcut" }
+                }
+                quick_ask_status = <SettingsHint> {
+                    width: Fill
+                    text: ""
+                    draw_text: { wrap: Word }
+                }
+                <SettingsHint> {
+                    width: Fill
+                    text: "The shortcut opens a small window from any app to ask the loaded model a question; its answer can be moved into a full chat. Add Shortcut registers it with GNOME or the Windows Start menu; on macOS or other desktops, bind the command shown above to the keys yourself."
+                    draw_text: { wrap: Word }
+                }
+            }
+
+            // Which voice speaks each language in TTS and Read Aloud
+            tts_voices_bar = <View> {
+                width: Fill, height: Fit
+                flow: Down
+                spacing: 6
+                padding: {left: 16, right: 16, top: 12}
+
+                <View> {
+                    width: Fill, height: Fit
+                    flow: Right
+                    align: {y: 0.5}
+                    spacing: 12
+
+                    <SettingsLabel> { text: "Pick the voice by language" }
+                    <View> { width: Fill, height: 1 }
+                    tts_auto_voice_toggle = <EnableToggle> {}
+                }
+                tts_voices_input = <SettingsTextInput> { empty_text: "Chinese: chinese_woman, English: vivian" }
+                tts_voices_status = <SettingsHint> {
+                    width: Fill
+                    text: ""
+                    draw_text: { wrap: Word }
+                }
+                <SettingsHint> {
+                    width: Fill
+                    text: "Text to speech and Read Aloud guess the language of each sentence and speak it with the voice listed for it, so replies mixing Chinese and English switch voices as they go. Other languages use the voice picked in the chat."
+                    draw_text: { wrap: Word }
+                }
+            }
+
+            // Format, sample rate and save folder of generated speech
+            tts_output_bar = <View> {
+                width: Fill, height: Fit
+                flow: Down
+                spacing: 6
+                padding: {left: 16, right: 16, top: 12}
+
+                <View> {
+                    width: Fill, height: Fit
+                    flow: Right
+                    align: {y: 0.5}
+                    spacing: 12
+
+                    <SettingsLabel> { text: "Speech format" }
+                    <View> { width: Fill, height: 1 }
+                    tts_format_input = <SettingsTextInput> { width: 90, empty_text: "WAV" }
+                    <SettingsHint> { text: "at" }
+                    tts_sample_rate_input = <SettingsTextInput> { width: 100, empty_text: "Model's" }
+                    <SettingsHint> { text: "Hz" }
+                }
+                <View> {
+                    width: Fill, height: Fit
+                    flow: Right
+                    align: {y: 0.5}
+                    spacing: 8
+
+                    tts_output_dir_input = <SettingsTextInput> { empty_text: "Save As opens in the system's default folder" }
+                    browse_tts_output_dir_button = <TestButton> { text: "Browse…" }
+                }
+                tts_output_status = <SettingsHint> {
+                    width: Fill
+                    text: ""
+                    draw_text: { wrap: Word }
+                }
+                <SettingsHint> {
+                    width: Fill
+                    text: "Text to speech in the chat asks the model for WAV, MP3, AAC or FLAC at this sample rate, and Save As starts in the folder above. Read Aloud always plays WAV."
+                    draw_text: { wrap: Word }
+                }
+            }
+
+            // Custom vocabulary and punctuation for transcripts
+            asr_bar = <View> {
+                width: Fill, height: Fit
+                flow: Down
+                spacing: 6
+                padding: {left: 16, right: 16, top: 12}
+
+                <View> {
+                    width: Fill, height: Fit
+                    flow: Right
+                    align: {y: 0.5}
+                    spacing: 12
+
+                    <SettingsLabel> { text: "Restore punctuation in transcripts" }
+                    <View> { width: Fill, height: 1 }
+                    asr_punctuate_toggle = <EnableToggle> {}
+                }
+                <View> {
+                    width: Fill, height: Fit
+                    flow: Right
+                    align: {y: 0.5}
+                    spacing: 12
+
+                    <SettingsLabel> { text: "Use custom vocabulary" }
+                    <View> { width: Fill, height: 1 }
+                    asr_vocabulary_toggle = <EnableToggle> {}
+                }
+                asr_vocabulary_input = <SettingsTextInput> { empty_text: "Names and terms, e.g. OminiX, MLX, Qwen3" }
+                <SettingsHint> {
+                    width: Fill
+                    text: "The vocabulary is given to the speech model as a hint, and its terms are spelled as written here in transcripts. Punctuation and capitals are restored by the loaded language model, which is slower. Both can be switched for each transcription in the chat."
+                    draw_text: { wrap: Word }
+                }
+            }
+
+            // Recent requests to the local runtime and providers
+            request_log_bar = <View> {
+                width: Fill, height: Fit
+                flow: Right
+                align: {y: 0.5}
+                spacing: 12
+                padding: {left: 16, right: 16, top: 12}
+
+                <SettingsLabel> { text: "Request log" }
+                <View> { width: Fill, height: 1 }
+                request_log_button = <TestButton> { text: "View Requests…" }
+            }
+
+            // Where the ominix-api server's output is written
+            runtime_log_bar = <View> {
+                width: Fill, height: Fit
+                flow: Down
+                spacing: 6
+                padding: {left: 16, right: 16, top: 12}
+
+                <SettingsLabel> { text: "Runtime log" }
+                runtime_log_input = <SettingsTextInput> { empty_text: "~/.OminiX/logs/ominix-api.log" }
+                <SettingsHint> {
+                    width: Fill
+                    text: "The local runtime's output is written here when the app starts it, and shown under Runtime log in the Model Hub. Point it at another file if you start ominix-api yourself."
+                    draw_text: { wrap: Word }
+                }
+            }
+
+            // Settings export / import
+            sync_bar = <View> {
+                width: Fill, height: Fit
+                flow: Right
+                spacing: 8
+                padding: {left: 16, right: 16, top: 12, bottom: 16}
+
+                export_settings_button = <TestButton> { text: "Export…" }
+                import_settings_button = <TestButton> { text: "Import…" }
+                chat_backups_button = <TestButton> { text: "Backups…" }
+                model_cleanup_button = <TestButton> { text: "Clean Up Models…" }
+                workspace_button = <TestButton> { text: "Workspace…" }
+            }
         }
 
         // Divider
@@ -405,18 +885,41 @@ live_design! {
                 <SettingsHint> { text: "Your API key (stored locally)" }
             }
 
-            // A2UI section (only visible for OpenAI-compatible providers)
-            a2ui_section = <View> {
+            // What new chats with this provider start with (chat providers only)
+            chat_defaults_section = <View> {
                 width: Fill, height: Fit
                 flow: Down
                 spacing: 6
-                visible: true
 
-                a2ui_header = <View> {
+                <SettingsLabel> { text: "New Chat Defaults" }
+                default_model_input = <SettingsTextInput> { empty_text: "Default model, e.g. gpt-4o-mini" }
+                <View> {
                     width: Fill, height: Fit
                     flow: Right
-                    align: {y: 0.5}
-                    spacing: 12
+                    spacing: 8
+                    default_temperature_input = <SettingsTextInput> { width: 160, empty_text: "Temperature" }
+                    default_max_tokens_input = <SettingsTextInput> { width: 160, empty_text: "Max tokens" }
+                }
+                default_system_prompt_input = <SettingsTextInput> { height: 72, empty_text: "System prompt" }
+                chat_defaults_hint = <SettingsHint> {
+                    width: Fill
+                    text: "New chats with this provider start with these; each chat can change them with Prompt… and Sampling…. Empty fields are left to the model."
+                    draw_text: { wrap: Word }
+                }
+            }
+
+            // A2UI section (only visible for OpenAI-compatible providers)
+            a2ui_section = <View> {
+                width: Fill, height: Fit
+                flow: Down
+                spacing: 6
+                visible: true
+
+                a2ui_header = <View> {
+                    width: Fill, height: Fit
+                    flow: Right
+                    align: {y: 0.5}
+                    spacing: 12
 
                     <SettingsLabel> { text: "A2UI (AI-to-UI)" }
 
@@ -430,6 +933,36 @@ live_design! {
                 }
             }
 
+            // Ollama model pull (only visible for the Ollama provider)
+            ollama_section = <View> {
+                width: Fill, height: Fit
+                flow: Down
+                spacing: 6
+                visible: false
+
+                <SettingsLabel> { text: "Pull Model" }
+
+                <View> {
+                    width: Fill, height: Fit
+                    flow: Right
+                    align: {y: 0.5}
+                    spacing: 8
+
+                    ollama_pull_input = <SettingsTextInput> {
+                        empty_text: "llama3.2:3b"
+                    }
+                    ollama_pull_button = <TestButton> { text: "Pull" }
+                    ollama_cancel_pull_button = <TestButton> {
+                        visible: false
+                        text: "Cancel"
+                    }
+                }
+
+                ollama_pull_status = <SettingsHint> {
+                    text: "Downloads a model from the Ollama library into the local daemon"
+                }
+            }
+
             // Actions
             actions = <View> {
                 width: Fill, height: Fit
@@ -515,8 +1048,19 @@ live_design! {
                         }
                     }
 
+                    models_updated_label = <SettingsHint> {
+                        text: ""
+                    }
+
                     <View> { width: Fill } // Spacer
 
+                    refresh_models_button = <TestButton> {
+                        height: 28
+                        padding: {left: 12, right: 12, top: 4, bottom: 4}
+                        draw_text: { text_style: <FONT_SEMIBOLD>{ font_size: 10.0 } }
+                        text: "Refresh"
+                    }
+
                     select_all_label = <Label> {
                         text: "Select All"
                         draw_text: {
@@ -568,6 +1112,31 @@ live_design! {
                                     text_style: <FONT_REGULAR>{ font_size: 11.0 }
                                 }
                             }
+
+                            // Context length / pricing from the provider catalog
+                            model_meta = <SettingsHint> {
+                                width: Fit
+                                text: ""
+                            }
+
+                            favorite_button = <Button> {
+                                width: 28, height: 28
+                                padding: 0
+                                draw_bg: {
+                                    instance hover: 0.0
+                                    fn pixel(self) -> vec4 {
+                                        let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                                        sdf.box(0.0, 0.0, self.rect_size.x, self.rect_size.y, 4.0);
+                                        sdf.fill(mix(#00000000, #e5e7eb, self.hover));
+                                        return sdf.result;
+                                    }
+                                }
+                                draw_text: {
+                                    color: #f59e0b
+                                    text_style: <FONT_REGULAR>{ font_size: 13.0 }
+                                }
+                                text: "☆"
+                            }
                         }
                     }
                 }
@@ -715,5 +1284,1181 @@ live_design! {
                 }
             }
         }
+
+        // Chat Backups Modal (overlay)
+        backup_modal = <View> {
+            width: Fill, height: Fill
+            flow: Overlay
+            visible: false
+            show_bg: true
+            draw_bg: {
+                fn pixel(self) -> vec4 {
+                    return vec4(0.0, 0.0, 0.0, 0.5); // Semi-transparent backdrop
+                }
+            }
+
+            <View> {
+                width: Fill, height: Fill
+                align: {x: 0.5, y: 0.5}
+
+                backup_content = <View> {
+                    width: 440, height: Fit
+                    flow: Down
+                    padding: 24
+                    spacing: 16
+                    show_bg: true
+                    draw_bg: {
+                        instance radius: 8.0
+                        fn pixel(self) -> vec4 {
+                            let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                            let sz = self.rect_size - 2.0;
+                            sdf.box(1.0, 1.0, sz.x, sz.y, self.radius);
+                            sdf.fill(#f3f4f6);
+                            sdf.stroke(#d1d5db, 1.0);
+                            return sdf.result;
+                        }
+                    }
+
+                    <Label> {
+                        text: "Chat Backups"
+                        draw_text: {
+                            fn get_color(self) -> vec4 {
+                                return #1f2937;
+                            }
+                            text_style: <FONT_SEMIBOLD>{ font_size: 18.0 }
+                        }
+                    }
+
+                    <View> {
+                        width: Fill, height: Fit
+                        flow: Right
+                        align: {y: 0.5}
+                        spacing: 12
+
+                        <SettingsLabel> { text: "Automatic snapshots" }
+                        <View> { width: Fill, height: 1 }
+                        backup_auto_toggle = <EnableToggle> {}
+                    }
+
+                    <View> {
+                        width: Fill, height: Fit
+                        flow: Right
+                        spacing: 12
+
+                        <View> {
+                            width: Fill, height: Fit
+                            flow: Down
+                            spacing: 6
+
+                            <SettingsLabel> { text: "Every (hours)" }
+                            backup_interval_input = <SettingsTextInput> { empty_text: "24" }
+                        }
+                        <View> {
+                            width: Fill, height: Fit
+                            flow: Down
+                            spacing: 6
+
+                            <SettingsLabel> { text: "Keep" }
+                            backup_retention_input = <SettingsTextInput> { empty_text: "14" }
+                        }
+                    }
+
+                    backup_summary = <SettingsHint> {
+                        width: Fill
+                        text: ""
+                        draw_text: { wrap: Word }
+                    }
+
+                    backup_status = <SettingsHint> {
+                        width: Fill
+                        text: ""
+                        draw_text: { wrap: Word }
+                    }
+
+                    <View> {
+                        width: Fill, height: Fit
+                        flow: Right
+                        spacing: 12
+                        margin: {top: 8}
+                        align: {x: 1.0}
+
+                        close_backup_button = <TestButton> {
+                            text: "Close"
+                        }
+                        restore_backup_button = <TestButton> {
+                            text: "Restore…"
+                        }
+                        backup_now_button = <SaveButton> {
+                            text: "Back Up Now"
+                        }
+                    }
+                }
+            }
+        }
+
+        // Model Cleanup Modal (overlay)
+        cleanup_modal = <View> {
+            width: Fill, height: Fill
+            flow: Overlay
+            visible: false
+            show_bg: true
+            draw_bg: {
+                fn pixel(self) -> vec4 {
+                    return vec4(0.0, 0.0, 0.0, 0.5); // Semi-transparent backdrop
+                }
+            }
+
+            <View> {
+                width: Fill, height: Fill
+                align: {x: 0.5, y: 0.5}
+
+                cleanup_content = <View> {
+                    width: 480, height: Fit
+                    flow: Down
+                    padding: 24
+                    spacing: 16
+                    show_bg: true
+                    draw_bg: {
+                        instance radius: 8.0
+                        fn pixel(self) -> vec4 {
+                            let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                            let sz = self.rect_size - 2.0;
+                            sdf.box(1.0, 1.0, sz.x, sz.y, self.radius);
+                            sdf.fill(#f3f4f6);
+                            sdf.stroke(#d1d5db, 1.0);
+                            return sdf.result;
+                        }
+                    }
+
+                    <Label> {
+                        text: "Model Cleanup"
+                        draw_text: {
+                            fn get_color(self) -> vec4 {
+                                return #1f2937;
+                            }
+                            text_style: <FONT_SEMIBOLD>{ font_size: 18.0 }
+                        }
+                    }
+
+                    <SettingsHint> {
+                        width: Fill
+                        text: "Files left behind by cancelled or failed model downloads."
+                        draw_text: { wrap: Word }
+                    }
+
+                    cleanup_list = <SettingsLabel> {
+                        width: Fill
+                        text: ""
+                        draw_text: { wrap: Word }
+                    }
+
+                    cleanup_status = <SettingsHint> {
+                        width: Fill
+                        text: ""
+                        draw_text: { wrap: Word }
+                    }
+
+                    <View> {
+                        width: Fill, height: Fit
+                        flow: Right
+                        spacing: 12
+                        margin: {top: 8}
+                        align: {x: 1.0}
+
+                        close_cleanup_button = <TestButton> {
+                            text: "Close"
+                        }
+                        delete_orphans_button = <SaveButton> {
+                            text: "Remove All"
+                        }
+                    }
+                }
+            }
+        }
+
+        // Remote Access Clients Modal (overlay)
+        api_clients_modal = <View> {
+            width: Fill, height: Fill
+            flow: Overlay
+            visible: false
+            show_bg: true
+            draw_bg: {
+                fn pixel(self) -> vec4 {
+                    return vec4(0.0, 0.0, 0.0, 0.5); // Semi-transparent backdrop
+                }
+            }
+
+            <View> {
+                width: Fill, height: Fill
+                align: {x: 0.5, y: 0.5}
+
+                api_clients_content = <View> {
+                    width: 480, height: Fit
+                    flow: Down
+                    padding: 24
+                    spacing: 16
+                    show_bg: true
+                    draw_bg: {
+                        instance radius: 8.0
+                        fn pixel(self) -> vec4 {
+                            let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                            let sz = self.rect_size - 2.0;
+                            sdf.box(1.0, 1.0, sz.x, sz.y, self.radius);
+                            sdf.fill(#f3f4f6);
+                            sdf.stroke(#d1d5db, 1.0);
+                            return sdf.result;
+                        }
+                    }
+
+                    <Label> {
+                        text: "Remote Access Clients"
+                        draw_text: {
+                            fn get_color(self) -> vec4 {
+                                return #1f2937;
+                            }
+                            text_style: <FONT_SEMIBOLD>{ font_size: 18.0 }
+                        }
+                    }
+
+                    <SettingsHint> {
+                        width: Fill
+                        text: "Each device uses its own token as its OpenAI API key. Revoking a client locks it out immediately."
+                        draw_text: { wrap: Word }
+                    }
+
+                    <View> {
+                        width: Fill, height: Fit
+                        flow: Down
+                        spacing: 6
+
+                        <SettingsLabel> { text: "Port" }
+                        api_port_input = <SettingsTextInput> { empty_text: "8099" }
+                    }
+
+                    api_clients_list = <SettingsLabel> {
+                        width: Fill
+                        text: ""
+                        draw_text: { wrap: Word }
+                    }
+
+                    <View> {
+                        width: Fill, height: Fit
+                        flow: Down
+                        spacing: 6
+
+                        <SettingsLabel> { text: "Device name" }
+                        api_client_name_input = <SettingsTextInput> { empty_text: "e.g. Phone" }
+                    }
+
+                    api_clients_status = <SettingsHint> {
+                        width: Fill
+                        text: ""
+                        draw_text: { wrap: Word }
+                    }
+
+                    <View> {
+                        width: Fill, height: Fit
+                        flow: Right
+                        spacing: 12
+                        margin: {top: 8}
+                        align: {x: 1.0}
+
+                        close_api_clients_button = <TestButton> {
+                            text: "Close"
+                        }
+                        revoke_api_client_button = <TestButton> {
+                            text: "Revoke"
+                        }
+                        add_api_client_button = <SaveButton> {
+                            text: "Create Token"
+                        }
+                    }
+                }
+            }
+        }
+
+        // Storage & Downloads Modal (overlay)
+        downloads_modal = <View> {
+            width: Fill, height: Fill
+            flow: Overlay
+            visible: false
+            show_bg: true
+            draw_bg: {
+                fn pixel(self) -> vec4 {
+                    return vec4(0.0, 0.0, 0.0, 0.5); // Semi-transparent backdrop
+                }
+            }
+
+            <View> {
+                width: Fill, height: Fill
+                align: {x: 0.5, y: 0.5}
+
+                downloads_content = <View> {
+                    width: 520, height: Fit
+                    flow: Down
+                    padding: 24
+                    spacing: 16
+                    show_bg: true
+                    draw_bg: {
+                        instance radius: 8.0
+                        fn pixel(self) -> vec4 {
+                            let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                            let sz = self.rect_size - 2.0;
+                            sdf.box(1.0, 1.0, sz.x, sz.y, self.radius);
+                            sdf.fill(#f3f4f6);
+                            sdf.stroke(#d1d5db, 1.0);
+                            return sdf.result;
+                        }
+                    }
+
+                    <Label> {
+                        text: "Storage & Downloads"
+                        draw_text: {
+                            fn get_color(self) -> vec4 {
+                                return #1f2937;
+                            }
+                            text_style: <FONT_SEMIBOLD>{ font_size: 18.0 }
+                        }
+                    }
+
+                    <View> {
+                        width: Fill, height: Fit
+                        flow: Down
+                        spacing: 6
+
+                        <SettingsLabel> { text: "Models folder" }
+                        <View> {
+                            width: Fill, height: Fit
+                            flow: Right
+                            align: {y: 0.5}
+                            spacing: 8
+
+                            models_dir_input = <SettingsTextInput> { empty_text: "~/.OminiX/models" }
+                            browse_models_dir_button = <TestButton> { text: "Browse…" }
+                        }
+                        <SettingsHint> {
+                            width: Fill
+                            text: "New downloads go here. Models already downloaded are not moved."
+                            draw_text: { wrap: Word }
+                        }
+                    }
+
+                    <View> {
+                        width: Fill, height: Fit
+                        flow: Right
+                        spacing: 12
+
+                        <View> {
+                            width: Fill, height: Fit
+                            flow: Down
+                            spacing: 6
+
+                            <SettingsLabel> { text: "Simultaneous downloads" }
+                            max_downloads_input = <SettingsTextInput> { empty_text: "2" }
+                        }
+                        <View> {
+                            width: Fill, height: Fit
+                            flow: Down
+                            spacing: 6
+
+                            <SettingsLabel> { text: "Speed limit (MB/s)" }
+                            speed_limit_input = <SettingsTextInput> { empty_text: "No limit" }
+                        }
+                    }
+
+                    <View> {
+                        width: Fill, height: Fit
+                        flow: Down
+                        spacing: 6
+
+                        <SettingsLabel> { text: "HuggingFace mirrors" }
+                        mirrors_input = <SettingsTextInput> { empty_text: "e.g. https://hf-mirror.com" }
+                        <SettingsHint> {
+                            width: Fill
+                            text: "Comma separated; tried in order before huggingface.co."
+                            draw_text: { wrap: Word }
+                        }
+                    }
+
+                    <View> {
+                        width: Fill, height: Fit
+                        flow: Right
+                        spacing: 12
+
+                        <View> {
+                            width: Fill, height: Fit
+                            flow: Down
+                            spacing: 6
+
+                            <SettingsLabel> { text: "Stall timeout (s)" }
+                            stall_secs_input = <SettingsTextInput> { empty_text: "60" }
+                        }
+                        <View> {
+                            width: Fill, height: Fit
+                            flow: Down
+                            spacing: 6
+
+                            <SettingsLabel> { text: "Automatic retries" }
+                            stall_retries_input = <SettingsTextInput> { empty_text: "2" }
+                        }
+                        <View> {
+                            width: Fill, height: Fit
+                            flow: Down
+                            spacing: 6
+
+                            <SettingsLabel> { text: "Load timeout (min)" }
+                            load_timeout_input = <SettingsTextInput> { empty_text: "10" }
+                        }
+                    }
+                    <SettingsHint> {
+                        width: Fill
+                        text: "A download that receives nothing for the stall timeout is retried, then stopped with Retry and Cancel. A load that takes longer than its timeout offers the same."
+                        draw_text: { wrap: Word }
+                    }
+
+                    <View> {
+                        width: Fill, height: Fit
+                        flow: Right
+                        align: {y: 0.5}
+                        spacing: 12
+
+                        <SettingsLabel> { text: "Verify files after download" }
+                        <View> { width: Fill, height: 1 }
+                        verify_downloads_toggle = <EnableToggle> {}
+                    }
+
+                    <View> {
+                        width: Fill, height: Fit
+                        flow: Right
+                        align: {y: 0.5}
+                        spacing: 12
+
+                        <SettingsLabel> { text: "Remove partial downloads at startup" }
+                        <View> { width: Fill, height: 1 }
+                        cleanup_partial_toggle = <EnableToggle> {}
+                    }
+
+                    <View> {
+                        width: Fill, height: Fit
+                        flow: Down
+                        spacing: 6
+
+                        <SettingsLabel> { text: "Keep generated files (hours)" }
+                        generated_files_input = <SettingsTextInput> { empty_text: "0" }
+                        <SettingsHint> {
+                            width: Fill
+                            text: "Generated speech, images and video are removed when you quit, or this many hours later."
+                            draw_text: { wrap: Word }
+                        }
+                    }
+
+                    downloads_status = <SettingsHint> {
+                        width: Fill
+                        text: ""
+                        draw_text: { wrap: Word }
+                    }
+
+                    <View> {
+                        width: Fill, height: Fit
+                        flow: Right
+                        spacing: 12
+                        margin: {top: 8}
+                        align: {x: 1.0}
+
+                        cancel_downloads_button = <TestButton> {
+                            text: "Cancel"
+                        }
+                        save_downloads_button = <SaveButton> {
+                            text: "Save"
+                        }
+                    }
+                }
+            }
+        }
+
+        // Network Modal (overlay)
+        network_modal = <View> {
+            width: Fill, height: Fill
+            flow: Overlay
+            visible: false
+            show_bg: true
+            draw_bg: {
+                fn pixel(self) -> vec4 {
+                    return vec4(0.0, 0.0, 0.0, 0.5); // Semi-transparent backdrop
+                }
+            }
+
+            <View> {
+                width: Fill, height: Fill
+                align: {x: 0.5, y: 0.5}
+
+                network_content = <View> {
+                    width: 520, height: Fit
+                    flow: Down
+                    padding: 24
+                    spacing: 16
+                    show_bg: true
+                    draw_bg: {
+                        instance radius: 8.0
+                        fn pixel(self) -> vec4 {
+                            let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                            let sz = self.rect_size - 2.0;
+                            sdf.box(1.0, 1.0, sz.x, sz.y, self.radius);
+                            sdf.fill(#f3f4f6);
+                            sdf.stroke(#d1d5db, 1.0);
+                            return sdf.result;
+                        }
+                    }
+
+                    <Label> {
+                        text: "Proxy & Certificates"
+                        draw_text: {
+                            fn get_color(self) -> vec4 {
+                                return #1f2937;
+                            }
+                            text_style: <FONT_SEMIBOLD>{ font_size: 18.0 }
+                        }
+                    }
+
+                    <View> {
+                        width: Fill, height: Fit
+                        flow: Down
+                        spacing: 6
+
+                        <SettingsLabel> { text: "Proxy" }
+                        proxy_url_input = <SettingsTextInput> { empty_text: "e.g. http://proxy.corp.example:8080" }
+                        <SettingsHint> {
+                            width: Fill
+                            text: "Model downloads, the hub and all providers connect through it. Leave empty to connect directly."
+                            draw_text: { wrap: Word }
+                        }
+                    }
+
+                    <View> {
+                        width: Fill, height: Fit
+                        flow: Right
+                        spacing: 12
+
+                        <View> {
+                            width: Fill, height: Fit
+                            flow: Down
+                            spacing: 6
+
+                            <SettingsLabel> { text: "Username" }
+                            proxy_username_input = <SettingsTextInput> { empty_text: "None" }
+                        }
+                        <View> {
+                            width: Fill, height: Fit
+                            flow: Down
+                            spacing: 6
+
+                            <SettingsLabel> { text: "Password" }
+                            proxy_password_input = <SettingsTextInput> {
+                                empty_text: "Saved encrypted on this computer"
+                                is_password: true
+                            }
+                        }
+                    }
+
+                    <View> {
+                        width: Fill, height: Fit
+                        flow: Down
+                        spacing: 6
+
+                        <SettingsLabel> { text: "Connect directly to" }
+                        no_proxy_input = <SettingsTextInput> { empty_text: "e.g. .corp.example, 10.0.0.0/8" }
+                        <SettingsHint> {
+                            width: Fill
+                            text: "Comma separated hosts and networks. This computer is always reached directly."
+                            draw_text: { wrap: Word }
+                        }
+                    }
+
+                    <View> {
+                        width: Fill, height: Fit
+                        flow: Down
+                        spacing: 6
+
+                        <SettingsLabel> { text: "Root certificates (PEM)" }
+                        <View> {
+                            width: Fill, height: Fit
+                            flow: Right
+                            align: {y: 0.5}
+                            spacing: 8
+
+                            ca_cert_input = <SettingsTextInput> { empty_text: "Built-in certificates only" }
+                            browse_ca_cert_button = <TestButton> { text: "Browse…" }
+                        }
+                        <SettingsHint> {
+                            width: Fill
+                            text: "Trusted besides the built-in ones, for proxies and servers signed by your organization. Chats that send attachments or interactive UI to remote providers use only the built-in certificates."
+                            draw_text: { wrap: Word }
+                        }
+                    }
+
+                    network_status = <SettingsHint> {
+                        width: Fill
+                        text: ""
+                        draw_text: { wrap: Word }
+                    }
+
+                    <View> {
+                        width: Fill, height: Fit
+                        flow: Right
+                        spacing: 12
+                        margin: {top: 8}
+                        align: {x: 1.0}
+
+                        cancel_network_button = <TestButton> {
+                            text: "Cancel"
+                        }
+                        save_network_button = <SaveButton> {
+                            text: "Save"
+                        }
+                    }
+                }
+            }
+        }
+
+        // System Prompts Modal (overlay)
+        system_prompts_modal = <View> {
+            width: Fill, height: Fill
+            flow: Overlay
+            visible: false
+            show_bg: true
+            draw_bg: {
+                fn pixel(self) -> vec4 {
+                    return vec4(0.0, 0.0, 0.0, 0.5); // Semi-transparent backdrop
+                }
+            }
+
+            <View> {
+                width: Fill, height: Fill
+                align: {x: 0.5, y: 0.5}
+
+                system_prompts_content = <View> {
+                    width: 560, height: Fit
+                    flow: Down
+                    padding: 24
+                    spacing: 16
+                    show_bg: true
+                    draw_bg: {
+                        instance radius: 8.0
+                        fn pixel(self) -> vec4 {
+                            let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                            let sz = self.rect_size - 2.0;
+                            sdf.box(1.0, 1.0, sz.x, sz.y, self.radius);
+                            sdf.fill(#f3f4f6);
+                            sdf.stroke(#d1d5db, 1.0);
+                            return sdf.result;
+                        }
+                    }
+
+                    <Label> {
+                        text: "System Prompts"
+                        draw_text: {
+                            fn get_color(self) -> vec4 {
+                                return #1f2937;
+                            }
+                            text_style: <FONT_SEMIBOLD>{ font_size: 18.0 }
+                        }
+                    }
+
+                    <SettingsHint> {
+                        width: Fill
+                        text: "Chats opened from the Model Hub start with the prompt for their kind of model. Leave one empty to start those chats without a system prompt."
+                        draw_text: { wrap: Word }
+                    }
+
+                    <View> {
+                        width: Fill, height: Fit
+                        flow: Down
+                        spacing: 6
+
+                        <SettingsLabel> { text: "LLM assistant" }
+                        assistant_prompt_input = <SettingsTextInput> { empty_text: "No system prompt" }
+                    }
+
+                    <View> {
+                        width: Fill, height: Fit
+                        flow: Down
+                        spacing: 6
+
+                        <SettingsLabel> { text: "VLM describer" }
+                        describer_prompt_input = <SettingsTextInput> { empty_text: "No system prompt" }
+                    }
+
+                    <View> {
+                        width: Fill, height: Fit
+                        flow: Down
+                        spacing: 6
+
+                        <SettingsLabel> { text: "Coding model" }
+                        coding_prompt_input = <SettingsTextInput> { empty_text: "No system prompt" }
+                        <SettingsHint> {
+                            width: Fill
+                            text: "Used for language models whose name or tags mention code."
+                            draw_text: { wrap: Word }
+                        }
+                    }
+
+                    <View> {
+                        width: Fill, height: Fit
+                        flow: Right
+                        spacing: 12
+                        margin: {top: 8}
+                        align: {x: 1.0}
+
+                        reset_system_prompts_button = <TestButton> {
+                            text: "Restore Defaults"
+                        }
+                        <View> { width: Fill, height: 1 }
+                        cancel_system_prompts_button = <TestButton> {
+                            text: "Cancel"
+                        }
+                        save_system_prompts_button = <SaveButton> {
+                            text: "Save"
+                        }
+                    }
+                }
+            }
+        }
+
+        // Export / Import Settings Modal (overlay)
+        sync_modal = <View> {
+            width: Fill, height: Fill
+            flow: Overlay
+            visible: false
+            show_bg: true
+            draw_bg: {
+                fn pixel(self) -> vec4 {
+                    return vec4(0.0, 0.0, 0.0, 0.5); // Semi-transparent backdrop
+                }
+            }
+
+            <View> {
+                width: Fill, height: Fill
+                align: {x: 0.5, y: 0.5}
+
+                sync_content = <View> {
+                    width: 440, height: Fit
+                    flow: Down
+                    padding: 24
+                    spacing: 16
+                    show_bg: true
+                    draw_bg: {
+                        instance radius: 8.0
+                        fn pixel(self) -> vec4 {
+                            let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                            let sz = self.rect_size - 2.0;
+                            sdf.box(1.0, 1.0, sz.x, sz.y, self.radius);
+                            sdf.fill(#f3f4f6);
+                            sdf.stroke(#d1d5db, 1.0);
+                            return sdf.result;
+                        }
+                    }
+
+                    sync_modal_title = <Label> {
+                        text: "Export Settings"
+                        draw_text: {
+                            fn get_color(self) -> vec4 {
+                                return #1f2937;
+                            }
+                            text_style: <FONT_SEMIBOLD>{ font_size: 18.0 }
+                        }
+                    }
+
+                    passphrase_section = <View> {
+                        width: Fill, height: Fit
+                        flow: Down
+                        spacing: 6
+
+                        <SettingsLabel> { text: "Passphrase (optional)" }
+                        sync_passphrase_input = <SettingsTextInput> {
+                            is_password: true
+                            empty_text: "Protects API keys in the file"
+                        }
+                        sync_passphrase_hint = <SettingsHint> {
+                            text: "Without a passphrase, API keys are left out of the export."
+                        }
+                    }
+
+                    // Shown when imported providers differ from the local ones
+                    sync_conflicts = <View> {
+                        width: Fill, height: Fit
+                        flow: Down
+                        spacing: 6
+                        visible: false
+
+                        <SettingsLabel> { text: "Providers that differ from yours" }
+                        sync_conflicts_label = <SettingsHint> {
+                            width: Fill
+                            text: ""
+                            draw_text: { wrap: Word }
+                        }
+                    }
+
+                    sync_status = <SettingsHint> {
+                        width: Fill
+                        text: ""
+                        draw_text: { wrap: Word }
+                    }
+
+                    sync_actions = <View> {
+                        width: Fill, height: Fit
+                        flow: Right
+                        spacing: 12
+                        margin: {top: 8}
+                        align: {x: 1.0}
+
+                        cancel_sync_button = <TestButton> {
+                            text: "Cancel"
+                        }
+                        keep_local_button = <TestButton> {
+                            text: "Keep Mine"
+                            visible: false
+                        }
+                        use_imported_button = <SaveButton> {
+                            text: "Use Imported"
+                            visible: false
+                        }
+                        sync_confirm_button = <SaveButton> {
+                            text: "Choose File…"
+                        }
+                    }
+                }
+            }
+        }
+
+        // Usage Statistics Modal (overlay)
+        telemetry_modal = <View> {
+            width: Fill, height: Fill
+            flow: Overlay
+            visible: false
+            show_bg: true
+            draw_bg: {
+                fn pixel(self) -> vec4 {
+                    return vec4(0.0, 0.0, 0.0, 0.5); // Semi-transparent backdrop
+                }
+            }
+
+            <View> {
+                width: Fill, height: Fill
+                align: {x: 0.5, y: 0.5}
+
+                telemetry_content = <View> {
+                    width: 480, height: Fit
+                    flow: Down
+                    padding: 24
+                    spacing: 16
+                    show_bg: true
+                    draw_bg: {
+                        instance radius: 8.0
+                        fn pixel(self) -> vec4 {
+                            let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                            let sz = self.rect_size - 2.0;
+                            sdf.box(1.0, 1.0, sz.x, sz.y, self.radius);
+                            sdf.fill(#f3f4f6);
+                            sdf.stroke(#d1d5db, 1.0);
+                            return sdf.result;
+                        }
+                    }
+
+                    <Label> {
+                        text: "Usage Statistics"
+                        draw_text: {
+                            fn get_color(self) -> vec4 {
+                                return #1f2937;
+                            }
+                            text_style: <FONT_SEMIBOLD>{ font_size: 18.0 }
+                        }
+                    }
+
+                    <SettingsHint> {
+                        width: Fill
+                        text: "Counts of the features you use and the kinds of errors you hit. No prompts, file names or error messages are recorded, and nothing is sent anywhere unless you set an endpoint below."
+                        draw_text: { wrap: Word }
+                    }
+
+                    telemetry_counts = <SettingsHint> {
+                        width: Fill
+                        text: ""
+                        draw_text: { wrap: Word }
+                    }
+
+                    <View> {
+                        width: Fill, height: Fit
+                        flow: Down
+                        spacing: 6
+
+                        <SettingsLabel> { text: "Send to (optional)" }
+                        telemetry_endpoint_input = <SettingsTextInput> { empty_text: "https://stats.example.com/collect" }
+                    }
+
+                    telemetry_status = <SettingsHint> {
+                        width: Fill
+                        text: ""
+                        draw_text: { wrap: Word }
+                    }
+
+                    <View> {
+                        width: Fill, height: Fit
+                        flow: Right
+                        spacing: 12
+                        margin: {top: 8}
+                        align: {x: 1.0}
+
+                        clear_telemetry_button = <TestButton> {
+                            text: "Clear"
+                        }
+                        export_telemetry_button = <TestButton> {
+                            text: "Export…"
+                        }
+                        close_telemetry_button = <SaveButton> {
+                            text: "Done"
+                        }
+                    }
+                }
+            }
+        }
+
+        // Workspace Archive Modal (overlay)
+        workspace_modal = <View> {
+            width: Fill, height: Fill
+            flow: Overlay
+            visible: false
+            show_bg: true
+            draw_bg: {
+                fn pixel(self) -> vec4 {
+                    return vec4(0.0, 0.0, 0.0, 0.5); // Semi-transparent backdrop
+                }
+            }
+
+            <View> {
+                width: Fill, height: Fill
+                align: {x: 0.5, y: 0.5}
+
+                workspace_content = <View> {
+                    width: 480, height: Fit
+                    flow: Down
+                    padding: 24
+                    spacing: 16
+                    show_bg: true
+                    draw_bg: {
+                        instance radius: 8.0
+                        fn pixel(self) -> vec4 {
+                            let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                            let sz = self.rect_size - 2.0;
+                            sdf.box(1.0, 1.0, sz.x, sz.y, self.radius);
+                            sdf.fill(#f3f4f6);
+                            sdf.stroke(#d1d5db, 1.0);
+                            return sdf.result;
+                        }
+                    }
+
+                    workspace_title = <Label> {
+                        text: "Workspace Archive"
+                        draw_text: {
+                            fn get_color(self) -> vec4 {
+                                return #1f2937;
+                            }
+                            text_style: <FONT_SEMIBOLD>{ font_size: 18.0 }
+                        }
+                    }
+
+                    workspace_hint = <SettingsHint> {
+                        width: Fill
+                        text: ""
+                        draw_text: { wrap: Word }
+                    }
+
+                    <View> {
+                        width: Fill, height: Fit
+                        flow: Right
+                        align: {y: 0.5}
+                        spacing: 12
+
+                        <View> {
+                            width: Fill, height: Fit
+                            flow: Down
+                            spacing: 2
+
+                            <SettingsLabel> { text: "Chats, preferences and prompts" }
+                            workspace_profile_size = <SettingsHint> { text: "" }
+                        }
+                        workspace_profile_toggle = <EnableToggle> {}
+                    }
+
+                    <View> {
+                        width: Fill, height: Fit
+                        flow: Right
+                        align: {y: 0.5}
+                        spacing: 12
+
+                        <View> {
+                            width: Fill, height: Fit
+                            flow: Down
+                            spacing: 2
+
+                            <SettingsLabel> { text: "Voice datasets" }
+                            workspace_voices_size = <SettingsHint> { text: "" }
+                        }
+                        workspace_voices_toggle = <EnableToggle> {}
+                    }
+
+                    <View> {
+                        width: Fill, height: Fit
+                        flow: Right
+                        align: {y: 0.5}
+                        spacing: 12
+
+                        <View> {
+                            width: Fill, height: Fit
+                            flow: Down
+                            spacing: 2
+
+                            <SettingsLabel> { text: "Model registry additions" }
+                            workspace_registry_size = <SettingsHint> { text: "" }
+                        }
+                        workspace_registry_toggle = <EnableToggle> {}
+                    }
+
+                    <View> {
+                        width: Fill, height: Fit
+                        flow: Right
+                        align: {y: 0.5}
+                        spacing: 12
+
+                        <View> {
+                            width: Fill, height: Fit
+                            flow: Down
+                            spacing: 2
+
+                            <SettingsLabel> { text: "Model weights" }
+                            workspace_models_size = <SettingsHint> { text: "" }
+                        }
+                        workspace_models_toggle = <EnableToggle> {}
+                    }
+
+                    workspace_status = <SettingsHint> {
+                        width: Fill
+                        text: ""
+                        draw_text: { wrap: Word }
+                    }
+
+                    <View> {
+                        width: Fill, height: Fit
+                        flow: Right
+                        spacing: 12
+                        margin: {top: 8}
+                        align: {x: 1.0}
+
+                        workspace_import_button = <TestButton> {
+                            text: "Import…"
+                        }
+                        workspace_export_button = <TestButton> {
+                            text: "Export…"
+                        }
+                        workspace_apply_button = <TestButton> {
+                            text: "Import"
+                        }
+                        close_workspace_button = <SaveButton> {
+                            text: "Done"
+                        }
+                    }
+                }
+            }
+        }
+
+        // Request Log Modal (overlay)
+        request_log_modal = <View> {
+            width: Fill, height: Fill
+            flow: Overlay
+            visible: false
+            show_bg: true
+            draw_bg: {
+                fn pixel(self) -> vec4 {
+                    return vec4(0.0, 0.0, 0.0, 0.5); // Semi-transparent backdrop
+                }
+            }
+
+            <View> {
+                width: Fill, height: Fill
+                align: {x: 0.5, y: 0.5}
+
+                request_log_content = <View> {
+                    width: 640, height: Fit
+                    flow: Down
+                    padding: 24
+                    spacing: 16
+                    show_bg: true
+                    draw_bg: {
+                        instance radius: 8.0
+                        fn pixel(self) -> vec4 {
+                            let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                            let sz = self.rect_size - 2.0;
+                            sdf.box(1.0, 1.0, sz.x, sz.y, self.radius);
+                            sdf.fill(#f3f4f6);
+                            sdf.stroke(#d1d5db, 1.0);
+                            return sdf.result;
+                        }
+                    }
+
+                    <Label> {
+                        text: "Request Log"
+                        draw_text: {
+                            fn get_color(self) -> vec4 {
+                                return #1f2937;
+                            }
+                            text_style: <FONT_SEMIBOLD>{ font_size: 18.0 }
+                        }
+                    }
+
+                    <SettingsHint> {
+                        width: Fill
+                        text: "Requests to the local runtime and cloud providers since the app started, newest first. Bodies are cut short and API keys are hidden."
+                        draw_text: { wrap: Word }
+                    }
+
+                    request_log_filter_input = <SettingsTextInput> { empty_text: "Filter, e.g. POST 500 or openai" }
+
+                    <ScrollYView> {
+                        width: Fill, height: 360
+                        request_log_list = <SettingsLabel> {
+                            width: Fill
+                            text: ""
+                            draw_text: { wrap: Word }
+                        }
+                    }
+
+                    request_log_status = <SettingsHint> {
+                        width: Fill
+                        text: ""
+                        draw_text: { wrap: Word }
+                    }
+
+                    <View> {
+                        width: Fill, height: Fit
+                        flow: Right
+                        spacing: 12
+                        margin: {top: 8}
+                        align: {x: 1.0}
+
+                        clear_request_log_button = <TestButton> {
+                            text: "Clear"
+                        }
+                        export_har_button = <TestButton> {
+                            text: "Export HAR…"
+                        }
+                        export_jsonl_button = <TestButton> {
+                            text: "Export JSONL…"
+                        }
+                        close_request_log_button = <SaveButton> {
+                            text: "Done"
+                        }
+                    }
+                }
+            }
+        }
     }
 }
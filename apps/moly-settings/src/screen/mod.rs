@@ -4,11 +4,27 @@ pub mod design;
 
 use makepad_widgets::*;
 use makepad_component::widgets::{MpSwitchWidgetExt, MpSwitchWidgetRefExt};
-use moly_data::{Store, ProviderId, ProviderConnectionStatus};
+use moly_data::{Chats, DownloadStatus, ModelRegistry, Store, StoreAction, StoreEvent, ProviderId, ProviderConnectionStatus, ProviderType, CatalogModel, OllamaPullProgress};
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
-use std::path::Path;
-use serde::Deserialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver};
+use std::path::{Path, PathBuf};
+use moly_data::chat_backup;
+use moly_data::chat_defaults;
+use moly_data::download_settings::{self, DownloadSettings};
+use moly_data::network::{self, NetworkSettings};
+use moly_data::{api_proxy, artifacts, lan_share, model_integrity, model_state, model_trash, platform, privacy_filter, quick_ask, request_log, runtime_log, telemetry, tts_output, tts_voices};
+use moly_data::tts_output::TtsOutputSettings;
+use moly_data::voice_presets::OutputFormat;
+use moly_data::asr_cleanup;
+use moly_data::model_integrity::OrphanedData;
+use moly_data::power::{self, PowerSettings};
+use moly_data::settings_sync::{self, ConflictResolution, ImportPlan, SettingsExport};
+use moly_data::system_prompts::{PresetKind, SystemPromptPresets};
+use moly_data::workspace_archive::{self, Manifest, Section};
+use moly_data::platform::FileDialog;
+use moly_widgets::a11y::{FocusChain, FocusTarget, Role};
 
 /// Result from connection test stored in shared state
 #[derive(Clone, Debug)]
@@ -16,22 +32,31 @@ struct ConnectionTestResult {
     provider_id: String,
     status: ProviderConnectionStatus,
     model_count: Option<usize>,
-    models: Vec<String>,
+    models: Vec<CatalogModel>,
 }
 
 /// Shared state for async connection testing
 type ConnectionTestState = Arc<Mutex<Option<ConnectionTestResult>>>;
 
-/// Response from OpenAI-compatible /models endpoint
-#[derive(Deserialize)]
-struct ModelsResponse {
-    data: Vec<ModelInfo>,
+/// Which flow the export/import modal is showing
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum SyncMode {
+    Export,
+    Import,
 }
 
-#[derive(Deserialize)]
-#[allow(dead_code)]
-struct ModelInfo {
-    id: String,
+/// Progress of a workspace export or import running in the background
+enum WorkspaceUpdate {
+    Progress(u64, u64),
+    Exported(Result<Manifest, String>),
+    Imported(Result<usize, String>),
+}
+
+/// Progress from a background `ollama pull`
+enum OllamaPullUpdate {
+    Progress(OllamaPullProgress),
+    Done,
+    Error(String),
 }
 
 #[derive(Live, LiveHook, Widget)]
@@ -66,6 +91,20 @@ pub struct SettingsApp {
     #[rust]
     fetched_models: Vec<(String, bool)>,
 
+    /// Catalog metadata and favorites for the selected provider, keyed by model ID
+    #[rust]
+    fetched_metadata: HashMap<String, CatalogModel>,
+
+    #[rust]
+    favorite_models: Vec<String>,
+
+    /// Running Ollama pull (progress channel + cancel flag)
+    #[rust]
+    ollama_pull_rx: Option<Receiver<OllamaPullUpdate>>,
+
+    #[rust]
+    ollama_pull_cancel: Option<Arc<AtomicBool>>,
+
     /// Whether the Add Provider modal is visible
     #[rust]
     modal_visible: bool,
@@ -77,10 +116,250 @@ pub struct SettingsApp {
     /// Connection status per provider (persists after testing)
     #[rust]
     provider_statuses: HashMap<String, ProviderConnectionStatus>,
+
+    /// Open export/import modal, if any
+    #[rust]
+    sync_mode: Option<SyncMode>,
+
+    /// Import waiting for the user to resolve conflicts
+    #[rust]
+    pending_import: Option<ImportPlan>,
+
+    /// Whether the chat backups modal is open
+    #[rust]
+    backup_modal_visible: bool,
+
+    /// Whether the model cleanup modal is open
+    #[rust]
+    cleanup_modal_visible: bool,
+
+    /// Partial download data listed in the cleanup modal
+    #[rust]
+    orphans: Vec<OrphanedData>,
+
+    /// Whether the remote access clients modal is open
+    #[rust]
+    api_clients_modal_visible: bool,
+
+    /// Whether the storage & downloads modal is open
+    #[rust]
+    downloads_modal_visible: bool,
+
+    /// Toggle states of the storage & downloads modal until saved
+    #[rust]
+    downloads_draft: DownloadSettings,
+
+    /// Whether the proxy & certificates modal is open
+    #[rust]
+    network_modal_visible: bool,
+
+    /// Whether the system prompts modal is open
+    #[rust]
+    system_prompts_modal_visible: bool,
+
+    /// Whether the usage statistics modal is open
+    #[rust]
+    telemetry_modal_visible: bool,
+
+    /// Whether the request log modal is open
+    #[rust]
+    request_log_modal_visible: bool,
+
+    /// [`request_log::generation`] the open request log shows
+    #[rust]
+    request_log_generation: u64,
+
+    /// Whether the workspace archive modal is open
+    #[rust]
+    workspace_modal_visible: bool,
+
+    /// Sections switched on in the workspace archive modal
+    #[rust]
+    workspace_sections: Vec<Section>,
+
+    /// Archive chosen for import and its manifest; `None` while exporting
+    #[rust]
+    workspace_import: Option<(PathBuf, Manifest)>,
+
+    /// Progress of the running workspace export or import
+    #[rust]
+    workspace_rx: Option<Receiver<WorkspaceUpdate>>,
+
+    /// Tab order over the page, or over the open modal
+    #[rust(FocusChain::new(settings_focus_targets()))]
+    focus: FocusChain,
+}
+
+/// Inputs of the system prompts modal and the preset each edits
+fn system_prompt_inputs() -> [(LiveId, PresetKind); 3] {
+    [
+        (live_id!(assistant_prompt_input), PresetKind::Assistant),
+        (live_id!(describer_prompt_input), PresetKind::Describer),
+        (live_id!(coding_prompt_input), PresetKind::Coding),
+    ]
+}
+
+/// Toggles and size labels of the workspace archive modal, by section
+fn workspace_toggles() -> [(LiveId, LiveId, Section); 4] {
+    [
+        (live_id!(workspace_profile_toggle), live_id!(workspace_profile_size), Section::Profile),
+        (live_id!(workspace_voices_toggle), live_id!(workspace_voices_size), Section::Voices),
+        (live_id!(workspace_registry_toggle), live_id!(workspace_registry_size), Section::Registry),
+        (live_id!(workspace_models_toggle), live_id!(workspace_models_size), Section::Models),
+    ]
+}
+
+/// Keyboard focus order. Controls of closed modals and of other provider
+/// types are off screen and skipped.
+fn settings_focus_targets() -> Vec<FocusTarget> {
+    use Role::{Button, TextInput, Toggle};
+    let targets: &[(&[LiveId], Role, &str)] = &[
+        (ids!(add_provider_button), Button, "Add provider"),
+        (ids!(api_host_input), TextInput, "API host"),
+        (ids!(api_key_input), TextInput, "API key"),
+        (ids!(default_model_input), TextInput, "Default model for new chats"),
+        (ids!(default_temperature_input), TextInput, "Default temperature"),
+        (ids!(default_max_tokens_input), TextInput, "Default max tokens"),
+        (ids!(default_system_prompt_input), TextInput, "Default system prompt"),
+        (ids!(ollama_pull_input), TextInput, "Model to pull"),
+        (ids!(ollama_pull_button), Button, "Pull model"),
+        (ids!(save_button), Button, "Save provider"),
+        (ids!(test_button), Button, "Test connection"),
+        (ids!(delete_provider_button), Button, "Delete provider"),
+        (ids!(refresh_models_button), Button, "Refresh models"),
+        (ids!(hard_delete_toggle), Toggle, "Delete models permanently"),
+        (ids!(unload_on_quit_toggle), Toggle, "Unload models when quitting"),
+        (ids!(privacy_filter_toggle), Toggle, "Privacy filter for cloud providers"),
+        (ids!(low_power_toggle), Toggle, "Low power mode"),
+        (ids!(power_battery_toggle), Toggle, "Use low power mode on battery"),
+        (ids!(lan_share_toggle), Toggle, "Share models on the local network"),
+        (ids!(api_proxy_toggle), Toggle, "Remote access endpoint"),
+        (ids!(api_clients_button), Button, "Manage remote access clients"),
+        (ids!(downloads_button), Button, "Storage and downloads"),
+        (ids!(network_button), Button, "Proxy and certificates"),
+        (ids!(system_prompts_button), Button, "System prompts"),
+        (ids!(telemetry_toggle), Toggle, "Usage statistics"),
+        (ids!(telemetry_button), Button, "View usage statistics"),
+        (ids!(semantic_search_toggle), Toggle, "Search chats by meaning"),
+        (ids!(embedding_model_input), TextInput, "Embedding model"),
+        (ids!(reranker_model_input), TextInput, "Reranker model"),
+        (ids!(clipboard_watch_toggle), Toggle, "Clipboard quick actions"),
+        (ids!(clipboard_min_chars_input), TextInput, "Shortest copy offering quick actions"),
+        (ids!(clipboard_skip_secrets_toggle), Toggle, "Ignore passwords, keys and card numbers"),
+        (ids!(quick_ask_hotkey_input), TextInput, "Quick Ask shortcut"),
+        (ids!(quick_ask_register_button), Button, "Add Quick Ask shortcut"),
+        (ids!(tts_auto_voice_toggle), Toggle, "Pick the voice by language"),
+        (ids!(tts_voices_input), TextInput, "Voice for each language"),
+        (ids!(tts_format_input), TextInput, "Speech format"),
+        (ids!(tts_sample_rate_input), TextInput, "Speech sample rate"),
+        (ids!(tts_output_dir_input), TextInput, "Folder Save As opens in"),
+        (ids!(browse_tts_output_dir_button), Button, "Browse for the speech folder"),
+        (ids!(asr_punctuate_toggle), Toggle, "Restore punctuation in transcripts"),
+        (ids!(asr_vocabulary_toggle), Toggle, "Use custom vocabulary"),
+        (ids!(asr_vocabulary_input), TextInput, "Custom vocabulary"),
+        (ids!(request_log_button), Button, "View request log"),
+        (ids!(runtime_log_input), TextInput, "Runtime log file"),
+        (ids!(export_settings_button), Button, "Export settings"),
+        (ids!(import_settings_button), Button, "Import settings"),
+        (ids!(chat_backups_button), Button, "Chat backups"),
+        (ids!(model_cleanup_button), Button, "Clean up models"),
+        (ids!(workspace_button), Button, "Workspace archive"),
+        // Add provider modal
+        (ids!(new_provider_name), TextInput, "Provider name"),
+        (ids!(new_provider_url), TextInput, "Provider URL"),
+        (ids!(new_provider_key), TextInput, "Provider API key"),
+        (ids!(save_new_provider_button), Button, "Add provider"),
+        (ids!(cancel_modal_button), Button, "Cancel"),
+        // Backups modal
+        (ids!(backup_interval_input), TextInput, "Backup interval in hours"),
+        (ids!(backup_retention_input), TextInput, "Backups to keep"),
+        (ids!(backup_now_button), Button, "Back up now"),
+        (ids!(restore_backup_button), Button, "Restore backup"),
+        (ids!(close_backup_button), Button, "Close"),
+        // Cleanup modal
+        (ids!(delete_orphans_button), Button, "Delete partial downloads"),
+        (ids!(close_cleanup_button), Button, "Close"),
+        // Remote access clients modal
+        (ids!(api_port_input), TextInput, "Port"),
+        (ids!(api_client_name_input), TextInput, "Client name"),
+        (ids!(add_api_client_button), Button, "Add client"),
+        (ids!(revoke_api_client_button), Button, "Revoke client"),
+        (ids!(close_api_clients_button), Button, "Close"),
+        // Storage & downloads modal
+        (ids!(models_dir_input), TextInput, "Models folder"),
+        (ids!(browse_models_dir_button), Button, "Choose models folder"),
+        (ids!(max_downloads_input), TextInput, "Simultaneous downloads"),
+        (ids!(speed_limit_input), TextInput, "Speed limit in megabytes per second"),
+        (ids!(mirrors_input), TextInput, "HuggingFace mirrors"),
+        (ids!(stall_secs_input), TextInput, "Stall timeout in seconds"),
+        (ids!(stall_retries_input), TextInput, "Automatic retries of a stalled download"),
+        (ids!(load_timeout_input), TextInput, "Load timeout in minutes"),
+        (ids!(verify_downloads_toggle), Toggle, "Verify files after download"),
+        (ids!(cleanup_partial_toggle), Toggle, "Remove partial downloads at startup"),
+        (ids!(generated_files_input), TextInput, "Hours to keep generated files"),
+        (ids!(cancel_downloads_button), Button, "Cancel"),
+        (ids!(save_downloads_button), Button, "Save"),
+        // Proxy & certificates modal
+        (ids!(proxy_url_input), TextInput, "Proxy URL"),
+        (ids!(proxy_username_input), TextInput, "Proxy username"),
+        (ids!(proxy_password_input), TextInput, "Proxy password"),
+        (ids!(no_proxy_input), TextInput, "Hosts to connect to directly"),
+        (ids!(ca_cert_input), TextInput, "Root certificates file"),
+        (ids!(browse_ca_cert_button), Button, "Choose root certificates file"),
+        (ids!(cancel_network_button), Button, "Cancel"),
+        (ids!(save_network_button), Button, "Save"),
+        // System prompts modal
+        (ids!(assistant_prompt_input), TextInput, "LLM assistant system prompt"),
+        (ids!(describer_prompt_input), TextInput, "VLM describer system prompt"),
+        (ids!(coding_prompt_input), TextInput, "Coding model system prompt"),
+        (ids!(reset_system_prompts_button), Button, "Restore default prompts"),
+        (ids!(cancel_system_prompts_button), Button, "Cancel"),
+        (ids!(save_system_prompts_button), Button, "Save"),
+        // Usage statistics modal
+        (ids!(telemetry_endpoint_input), TextInput, "Statistics endpoint"),
+        (ids!(clear_telemetry_button), Button, "Clear statistics"),
+        (ids!(export_telemetry_button), Button, "Export statistics"),
+        (ids!(close_telemetry_button), Button, "Done"),
+        // Workspace archive modal
+        (ids!(workspace_profile_toggle), Toggle, "Chats, preferences and prompts"),
+        (ids!(workspace_voices_toggle), Toggle, "Voice datasets"),
+        (ids!(workspace_registry_toggle), Toggle, "Model registry additions"),
+        (ids!(workspace_models_toggle), Toggle, "Model weights"),
+        (ids!(workspace_import_button), Button, "Import workspace"),
+        (ids!(workspace_export_button), Button, "Export workspace"),
+        (ids!(workspace_apply_button), Button, "Import the chosen sections"),
+        (ids!(close_workspace_button), Button, "Done"),
+        // Request log modal
+        (ids!(request_log_filter_input), TextInput, "Filter requests"),
+        (ids!(clear_request_log_button), Button, "Clear request log"),
+        (ids!(export_har_button), Button, "Export as HAR"),
+        (ids!(export_jsonl_button), Button, "Export as JSON Lines"),
+        (ids!(close_request_log_button), Button, "Done"),
+        // Export / import modal
+        (ids!(sync_passphrase_input), TextInput, "Passphrase"),
+        (ids!(sync_confirm_button), Button, "Continue"),
+        (ids!(use_imported_button), Button, "Use imported settings"),
+        (ids!(keep_local_button), Button, "Keep local settings"),
+        (ids!(cancel_sync_button), Button, "Cancel"),
+    ];
+    targets.iter().map(|&(path, role, label)| FocusTarget::new(path, role, label)).collect()
 }
 
 impl Widget for SettingsApp {
     fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        // Another profile's providers are in the Store now; reinitialize below
+        if let Event::Actions(actions) = event {
+            if StoreEvent::iter(actions).any(|e| matches!(e, StoreEvent::ProfileChanged { .. })) {
+                self.selected_provider_id = None;
+                self.provider_statuses.clear();
+            }
+            if StoreEvent::iter(actions).any(|e| matches!(e, StoreEvent::PowerModeChanged { .. })) {
+                if let Some(store) = scope.data.get::<Store>() {
+                    self.update_power_status(cx, &store.preferences.power);
+                }
+            }
+        }
+
         // Initialize shared state if needed
         if Arc::strong_count(&self.connection_test_state) == 0 {
             self.connection_test_state = Arc::new(Mutex::new(None));
@@ -91,6 +370,42 @@ impl Widget for SettingsApp {
             self.selected_provider_id = Some("openai".to_string());
             self.connection_test_state = Arc::new(Mutex::new(None));
             self.load_provider_data(cx, scope);
+            if let Some(store) = scope.data.get::<Store>() {
+                self.view.mp_switch(ids!(hard_delete_toggle)).set_on(cx, store.preferences.hard_delete_models);
+                self.view.mp_switch(ids!(unload_on_quit_toggle)).set_on(cx, store.preferences.unload_models_on_quit);
+                self.view.mp_switch(ids!(privacy_filter_toggle)).set_on(cx, store.preferences.privacy_filter);
+                self.view.mp_switch(ids!(low_power_toggle)).set_on(cx, store.preferences.power.low_power);
+                self.view.mp_switch(ids!(power_battery_toggle)).set_on(cx, store.preferences.power.on_battery);
+                self.update_power_status(cx, &store.preferences.power);
+                self.view.mp_switch(ids!(lan_share_toggle)).set_on(cx, store.preferences.lan_sharing);
+                self.view.mp_switch(ids!(api_proxy_toggle)).set_on(cx, store.preferences.api_proxy.enabled);
+                self.view.mp_switch(ids!(telemetry_toggle)).set_on(cx, store.preferences.telemetry.enabled);
+                self.view.mp_switch(ids!(semantic_search_toggle)).set_on(cx, store.preferences.semantic_search.enabled);
+                self.view.text_input(ids!(embedding_model_input)).set_text(cx, &store.preferences.semantic_search.model);
+                self.view.text_input(ids!(reranker_model_input)).set_text(cx, &store.preferences.semantic_search.reranker);
+                let clipboard = &store.preferences.clipboard_watch;
+                self.view.mp_switch(ids!(clipboard_watch_toggle)).set_on(cx, clipboard.enabled);
+                self.view.text_input(ids!(clipboard_min_chars_input)).set_text(cx, &clipboard.min_chars.to_string());
+                self.view.mp_switch(ids!(clipboard_skip_secrets_toggle)).set_on(cx, clipboard.skip_secrets);
+                self.view.text_input(ids!(quick_ask_hotkey_input)).set_text(cx, &store.preferences.quick_ask_hotkey);
+                self.view.label(ids!(quick_ask_status)).set_text(cx, &format!("Command: {}", quick_ask::command().join(" ")));
+                self.view.mp_switch(ids!(tts_auto_voice_toggle)).set_on(cx, store.preferences.tts_voices.auto);
+                self.view.text_input(ids!(tts_voices_input)).set_text(cx, &tts_voices::format(&store.preferences.tts_voices.voices));
+                let tts_output = &store.preferences.tts_output;
+                self.view.text_input(ids!(tts_format_input)).set_text(cx, tts_output.format.label());
+                let rate = if tts_output.sample_rate == 0 { String::new() } else { tts_output.sample_rate.to_string() };
+                self.view.text_input(ids!(tts_sample_rate_input)).set_text(cx, &rate);
+                self.view.text_input(ids!(tts_output_dir_input)).set_text(cx, &tts_output.directory);
+                self.view.mp_switch(ids!(asr_punctuate_toggle)).set_on(cx, store.preferences.asr.punctuate);
+                self.view.mp_switch(ids!(asr_vocabulary_toggle)).set_on(cx, store.preferences.asr.use_vocabulary);
+                self.view.text_input(ids!(asr_vocabulary_input)).set_text(cx, &store.preferences.asr.vocabulary.join(", "));
+                self.view.text_input(ids!(runtime_log_input)).set_text(cx, &store.preferences.runtime_log_path);
+            }
+            self.update_lan_share_status(cx);
+            self.update_api_proxy_status(cx, scope, None);
+            self.update_downloads_summary(cx, scope);
+            self.update_network_summary(cx, scope);
+            self.update_telemetry_summary(cx);
             self.view.redraw(cx);
 
             // Log icon paths at startup for debugging (debug level)
@@ -100,9 +415,18 @@ impl Widget for SettingsApp {
         // Check for connection test results
         self.check_connection_test_result(cx, scope);
 
+        // Poll a running Ollama pull
+        self.poll_ollama_pull(cx, scope);
+        self.poll_workspace(cx);
+
         // Handle events
+        let modal = self.open_modal();
         let actions = cx.capture_actions(|cx| {
             self.view.handle_event(cx, event, scope);
+            match &modal {
+                Some(modal) => self.focus.handle_event(cx, event, scope, modal),
+                None => self.focus.handle_event(cx, event, scope, &self.view),
+            }
         });
 
         // Handle provider list item clicks
@@ -113,8 +437,9 @@ impl Widget for SettingsApp {
             self.save_provider(cx, scope);
         }
 
-        // Test Connection button click
-        if self.view.button(ids!(test_button)).clicked(&actions) {
+        // Test Connection / Refresh models button click
+        if self.view.button(ids!(test_button)).clicked(&actions)
+            || self.view.button(ids!(refresh_models_button)).clicked(&actions) {
             self.test_connection(cx, scope);
         }
 
@@ -134,11 +459,407 @@ impl Widget for SettingsApp {
             self.save_new_provider(cx, scope);
         }
 
+        // Ollama pull / cancel
+        if self.view.button(ids!(ollama_pull_button)).clicked(&actions) {
+            self.start_ollama_pull(cx, scope);
+        }
+        if self.view.button(ids!(ollama_cancel_pull_button)).clicked(&actions) {
+            if let Some(cancel) = &self.ollama_pull_cancel {
+                cancel.store(true, Ordering::SeqCst);
+            }
+        }
+
         // Delete provider button click
         if self.view.button(ids!(delete_provider_button)).clicked(&actions) {
             self.delete_provider(cx, scope);
         }
 
+        // Settings export / import
+        if self.view.button(ids!(export_settings_button)).clicked(&actions) {
+            self.open_sync_modal(cx, SyncMode::Export);
+        }
+        if self.view.button(ids!(import_settings_button)).clicked(&actions) {
+            self.open_sync_modal(cx, SyncMode::Import);
+        }
+        if self.view.button(ids!(cancel_sync_button)).clicked(&actions) {
+            self.close_sync_modal(cx);
+        }
+        if self.view.button(ids!(sync_confirm_button)).clicked(&actions) {
+            match self.sync_mode {
+                Some(SyncMode::Export) => self.export_settings(cx, scope),
+                Some(SyncMode::Import) => self.import_settings(cx, scope),
+                None => {}
+            }
+        }
+        if self.view.button(ids!(keep_local_button)).clicked(&actions) {
+            self.finish_import(cx, scope, ConflictResolution::KeepLocal);
+        }
+        if self.view.button(ids!(use_imported_button)).clicked(&actions) {
+            self.finish_import(cx, scope, ConflictResolution::UseImported);
+        }
+
+        // Model removal: Trash or permanent delete
+        if let Some(enabled) = self.view.mp_switch(ids!(hard_delete_toggle)).changed(&actions) {
+            if let Some(store) = scope.data.get_mut::<Store>() {
+                store.preferences.hard_delete_models = enabled;
+                store.preferences.save();
+            }
+            model_trash::set_hard_delete(enabled);
+        }
+
+        if let Some(enabled) = self.view.mp_switch(ids!(unload_on_quit_toggle)).changed(&actions) {
+            if let Some(store) = scope.data.get_mut::<Store>() {
+                store.preferences.unload_models_on_quit = enabled;
+                store.preferences.save();
+            }
+        }
+
+        if let Some(enabled) = self.view.mp_switch(ids!(privacy_filter_toggle)).changed(&actions) {
+            if let Some(store) = scope.data.get_mut::<Store>() {
+                store.preferences.privacy_filter = enabled;
+                store.preferences.save();
+            }
+            privacy_filter::set_enabled(enabled);
+        }
+
+        // Low power mode; the shell saves the settings and applies them
+        let low_power = self.view.mp_switch(ids!(low_power_toggle)).changed(&actions);
+        let on_battery = self.view.mp_switch(ids!(power_battery_toggle)).changed(&actions);
+        if low_power.is_some() || on_battery.is_some() {
+            if let Some(store) = scope.data.get::<Store>() {
+                let mut settings = store.preferences.power.clone();
+                settings.low_power = low_power.unwrap_or(settings.low_power);
+                settings.on_battery = on_battery.unwrap_or(settings.on_battery);
+                self.update_power_status(cx, &settings);
+                cx.action(StoreAction::SetPowerSettings(settings));
+            }
+        }
+
+        // LAN model sharing
+        if let Some(enabled) = self.view.mp_switch(ids!(lan_share_toggle)).changed(&actions) {
+            if let Some(store) = scope.data.get_mut::<Store>() {
+                store.preferences.lan_sharing = enabled;
+                store.preferences.save();
+            }
+            if enabled {
+                if let Err(e) = lan_share::start_sharing() {
+                    ::log::error!("Failed to start LAN model sharing: {}", e);
+                }
+            } else {
+                lan_share::stop_sharing();
+            }
+            self.update_lan_share_status(cx);
+        }
+
+        // OpenAI-compatible endpoint for other devices
+        if let Some(enabled) = self.view.mp_switch(ids!(api_proxy_toggle)).changed(&actions) {
+            if let Some(store) = scope.data.get_mut::<Store>() {
+                store.preferences.api_proxy.enabled = enabled;
+                store.preferences.save();
+            }
+            self.apply_api_proxy(cx, scope);
+        }
+        if self.view.button(ids!(api_clients_button)).clicked(&actions) {
+            self.open_api_clients_modal(cx, scope);
+        }
+        if self.view.button(ids!(add_api_client_button)).clicked(&actions) {
+            self.add_api_client(cx, scope);
+        }
+        if self.view.button(ids!(revoke_api_client_button)).clicked(&actions) {
+            self.revoke_api_client(cx, scope);
+        }
+        if self.view.button(ids!(close_api_clients_button)).clicked(&actions) {
+            self.save_api_port(cx, scope);
+            self.api_clients_modal_visible = false;
+            self.view.redraw(cx);
+        }
+
+        // Storage & downloads
+        if self.view.button(ids!(downloads_button)).clicked(&actions) {
+            self.open_downloads_modal(cx, scope);
+        }
+        if self.view.button(ids!(browse_models_dir_button)).clicked(&actions) {
+            if let Some(dir) = FileDialog::new().set_title("Models Folder").pick_folder() {
+                self.view.text_input(ids!(models_dir_input)).set_text(cx, &dir.to_string_lossy());
+            }
+        }
+        if self.view.button(ids!(cancel_downloads_button)).clicked(&actions) {
+            self.downloads_modal_visible = false;
+            self.view.redraw(cx);
+        }
+        if let Some(enabled) = self.view.mp_switch(ids!(verify_downloads_toggle)).changed(&actions) {
+            self.downloads_draft.verify_after_download = enabled;
+        }
+        if let Some(enabled) = self.view.mp_switch(ids!(cleanup_partial_toggle)).changed(&actions) {
+            self.downloads_draft.cleanup_partial_on_start = enabled;
+        }
+        if self.view.button(ids!(save_downloads_button)).clicked(&actions) {
+            self.save_download_settings(cx, scope);
+        }
+
+        // Proxy & certificates
+        if self.view.button(ids!(network_button)).clicked(&actions) {
+            self.open_network_modal(cx, scope);
+        }
+        if self.view.button(ids!(browse_ca_cert_button)).clicked(&actions) {
+            if let Some(path) = FileDialog::new().set_title("Root Certificates")
+                .add_filter("PEM certificates", &["pem", "crt", "cer"])
+                .pick_file()
+            {
+                self.view.text_input(ids!(ca_cert_input)).set_text(cx, &path.to_string_lossy());
+            }
+        }
+        if self.view.button(ids!(cancel_network_button)).clicked(&actions) {
+            self.network_modal_visible = false;
+            self.view.redraw(cx);
+        }
+        if self.view.button(ids!(save_network_button)).clicked(&actions) {
+            self.save_network_settings(cx, scope);
+        }
+
+        // System prompts
+        if self.view.button(ids!(system_prompts_button)).clicked(&actions) {
+            self.open_system_prompts_modal(cx, scope);
+        }
+        if self.view.button(ids!(reset_system_prompts_button)).clicked(&actions) {
+            self.show_system_prompts(cx, &SystemPromptPresets::default());
+        }
+        if self.view.button(ids!(cancel_system_prompts_button)).clicked(&actions) {
+            self.system_prompts_modal_visible = false;
+            self.view.redraw(cx);
+        }
+        if self.view.button(ids!(save_system_prompts_button)).clicked(&actions) {
+            self.save_system_prompts(cx, scope);
+        }
+
+        // Usage statistics
+        if let Some(enabled) = self.view.mp_switch(ids!(telemetry_toggle)).changed(&actions) {
+            if let Some(store) = scope.data.get_mut::<Store>() {
+                store.preferences.telemetry.enabled = enabled;
+                store.preferences.save();
+                telemetry::set_current(&store.preferences.telemetry);
+            }
+            self.update_telemetry_summary(cx);
+        }
+        if self.view.button(ids!(telemetry_button)).clicked(&actions) {
+            self.open_telemetry_modal(cx, scope);
+        }
+        if self.view.button(ids!(clear_telemetry_button)).clicked(&actions) {
+            self.clear_telemetry(cx);
+        }
+        if self.view.button(ids!(export_telemetry_button)).clicked(&actions) {
+            self.export_telemetry(cx);
+        }
+        if self.view.button(ids!(close_telemetry_button)).clicked(&actions) {
+            self.save_telemetry_endpoint(cx, scope);
+        }
+
+        // Chat history search by meaning
+        if let Some(enabled) = self.view.mp_switch(ids!(semantic_search_toggle)).changed(&actions) {
+            if let Some(store) = scope.data.get_mut::<Store>() {
+                store.preferences.semantic_search.enabled = enabled;
+                store.preferences.save();
+            }
+        }
+        if let Some(model) = self.view.text_input(ids!(embedding_model_input)).changed(&actions) {
+            if let Some(store) = scope.data.get_mut::<Store>() {
+                store.preferences.semantic_search.model = model.trim().to_string();
+                store.preferences.save();
+            }
+        }
+        if let Some(model) = self.view.text_input(ids!(reranker_model_input)).changed(&actions) {
+            if let Some(store) = scope.data.get_mut::<Store>() {
+                store.preferences.semantic_search.reranker = model.trim().to_string();
+                store.preferences.save();
+            }
+        }
+
+        // Clipboard quick actions
+        if let Some(enabled) = self.view.mp_switch(ids!(clipboard_watch_toggle)).changed(&actions) {
+            if let Some(store) = scope.data.get_mut::<Store>() {
+                store.preferences.clipboard_watch.enabled = enabled;
+                store.preferences.save();
+            }
+        }
+        if let Some(text) = self.view.text_input(ids!(clipboard_min_chars_input)).changed(&actions) {
+            if let (Ok(min_chars), Some(store)) = (text.trim().parse::<usize>(), scope.data.get_mut::<Store>()) {
+                store.preferences.clipboard_watch.min_chars = min_chars;
+                store.preferences.save();
+            }
+        }
+        if let Some(skip) = self.view.mp_switch(ids!(clipboard_skip_secrets_toggle)).changed(&actions) {
+            if let Some(store) = scope.data.get_mut::<Store>() {
+                store.preferences.clipboard_watch.skip_secrets = skip;
+                store.preferences.save();
+            }
+        }
+
+        // Quick Ask shortcut
+        if let Some(hotkey) = self.view.text_input(ids!(quick_ask_hotkey_input)).changed(&actions) {
+            if let Some(store) = scope.data.get_mut::<Store>() {
+                store.preferences.quick_ask_hotkey = hotkey.trim().to_string();
+                store.preferences.save();
+            }
+        }
+        if self.view.button(ids!(quick_ask_register_button)).clicked(&actions) {
+            let hotkey = self.view.text_input(ids!(quick_ask_hotkey_input)).text();
+            let status = match quick_ask::Hotkey::parse(&hotkey)
+                .and_then(|key| platform::register_shortcut("OminiX Quick Ask", &key, &quick_ask::command()))
+            {
+                Ok(()) => format!("{} now opens Quick Ask.", hotkey.trim()),
+                Err(e) => e,
+            };
+            self.view.label(ids!(quick_ask_status)).set_text(cx, &status);
+        }
+
+        // Voices by language
+        if let Some(auto) = self.view.mp_switch(ids!(tts_auto_voice_toggle)).changed(&actions) {
+            if let Some(store) = scope.data.get_mut::<Store>() {
+                store.preferences.tts_voices.auto = auto;
+                store.preferences.save();
+                tts_voices::set_current(&store.preferences.tts_voices);
+            }
+        }
+        if let Some(text) = self.view.text_input(ids!(tts_voices_input)).changed(&actions) {
+            match tts_voices::parse(&text) {
+                Ok(voices) => {
+                    self.view.label(ids!(tts_voices_status)).set_text(cx, "");
+                    if let Some(store) = scope.data.get_mut::<Store>() {
+                        store.preferences.tts_voices.voices = voices;
+                        store.preferences.save();
+                        tts_voices::set_current(&store.preferences.tts_voices);
+                    }
+                }
+                Err(e) => self.view.label(ids!(tts_voices_status)).set_text(cx, &e),
+            }
+        }
+
+        // Speech format and folder
+        if self.view.button(ids!(browse_tts_output_dir_button)).clicked(&actions) {
+            if let Some(dir) = FileDialog::new().set_title("Speech Folder").pick_folder() {
+                self.view.text_input(ids!(tts_output_dir_input)).set_text(cx, &dir.to_string_lossy());
+                self.save_tts_output(cx, scope);
+            }
+        }
+        let tts_format = self.view.text_input(ids!(tts_format_input)).changed(&actions);
+        let tts_sample_rate = self.view.text_input(ids!(tts_sample_rate_input)).changed(&actions);
+        let tts_output_dir = self.view.text_input(ids!(tts_output_dir_input)).changed(&actions);
+        if tts_format.is_some() || tts_sample_rate.is_some() || tts_output_dir.is_some() {
+            self.save_tts_output(cx, scope);
+        }
+
+        // Transcripts
+        let asr_punctuate = self.view.mp_switch(ids!(asr_punctuate_toggle)).changed(&actions);
+        let asr_use_vocabulary = self.view.mp_switch(ids!(asr_vocabulary_toggle)).changed(&actions);
+        let asr_vocabulary = self.view.text_input(ids!(asr_vocabulary_input)).changed(&actions);
+        if asr_punctuate.is_some() || asr_use_vocabulary.is_some() || asr_vocabulary.is_some() {
+            if let Some(store) = scope.data.get_mut::<Store>() {
+                let asr = &mut store.preferences.asr;
+                if let Some(on) = asr_punctuate { asr.punctuate = on; }
+                if let Some(on) = asr_use_vocabulary { asr.use_vocabulary = on; }
+                if let Some(text) = asr_vocabulary { asr.vocabulary = asr_cleanup::parse_vocabulary(&text); }
+                asr_cleanup::set_current(asr);
+                store.preferences.save();
+            }
+        }
+
+        // Runtime log file
+        if let Some(path) = self.view.text_input(ids!(runtime_log_input)).changed(&actions) {
+            if let Some(store) = scope.data.get_mut::<Store>() {
+                store.preferences.runtime_log_path = path.trim().to_string();
+                store.preferences.save();
+                runtime_log::set_path(&store.preferences.runtime_log_path);
+            }
+        }
+
+        // Request log
+        if self.view.button(ids!(request_log_button)).clicked(&actions) {
+            self.view.text_input(ids!(request_log_filter_input)).set_text(cx, "");
+            self.view.label(ids!(request_log_status)).set_text(cx, "");
+            self.request_log_modal_visible = true;
+            self.update_request_log(cx);
+        }
+        if self.request_log_modal_visible {
+            if self.view.text_input(ids!(request_log_filter_input)).changed(&actions).is_some()
+                || self.request_log_generation != request_log::generation()
+            {
+                self.update_request_log(cx);
+            }
+        }
+        if self.view.button(ids!(clear_request_log_button)).clicked(&actions) {
+            request_log::clear();
+            self.view.label(ids!(request_log_status)).set_text(cx, "Log cleared.");
+            self.update_request_log(cx);
+        }
+        if self.view.button(ids!(export_har_button)).clicked(&actions) {
+            self.export_request_log(cx, true);
+        }
+        if self.view.button(ids!(export_jsonl_button)).clicked(&actions) {
+            self.export_request_log(cx, false);
+        }
+        if self.view.button(ids!(close_request_log_button)).clicked(&actions) {
+            self.request_log_modal_visible = false;
+            self.view.redraw(cx);
+        }
+
+        // Chat backups
+        if self.view.button(ids!(chat_backups_button)).clicked(&actions) {
+            self.open_backup_modal(cx, scope);
+        }
+        if self.view.button(ids!(close_backup_button)).clicked(&actions) {
+            self.save_backup_settings(scope);
+            self.backup_modal_visible = false;
+            self.view.redraw(cx);
+        }
+        if let Some(enabled) = self.view.mp_switch(ids!(backup_auto_toggle)).changed(&actions) {
+            if let Some(store) = scope.data.get_mut::<Store>() {
+                store.preferences.chat_backup.enabled = enabled;
+                store.preferences.save();
+            }
+        }
+        if self.view.button(ids!(backup_now_button)).clicked(&actions) {
+            self.backup_chats_now(cx, scope);
+        }
+        if self.view.button(ids!(restore_backup_button)).clicked(&actions) {
+            self.restore_chats(cx, scope);
+        }
+
+        // Partial download cleanup
+        if self.view.button(ids!(workspace_button)).clicked(&actions) {
+            self.open_workspace_modal(cx);
+        }
+        for (toggle, _, section) in workspace_toggles() {
+            if let Some(on) = self.view.mp_switch(&[toggle]).changed(&actions) {
+                self.workspace_sections.retain(|s| *s != section);
+                if on {
+                    self.workspace_sections.push(section);
+                }
+            }
+        }
+        if self.view.button(ids!(workspace_export_button)).clicked(&actions) {
+            self.export_workspace(cx);
+        }
+        if self.view.button(ids!(workspace_import_button)).clicked(&actions) {
+            self.choose_workspace_archive(cx);
+        }
+        if self.view.button(ids!(workspace_apply_button)).clicked(&actions) {
+            self.import_workspace(cx, scope);
+        }
+        if self.view.button(ids!(close_workspace_button)).clicked(&actions) {
+            self.workspace_modal_visible = false;
+            self.view.redraw(cx);
+        }
+        if self.view.button(ids!(model_cleanup_button)).clicked(&actions) {
+            self.open_cleanup_modal(cx, scope);
+        }
+        if self.view.button(ids!(close_cleanup_button)).clicked(&actions) {
+            self.cleanup_modal_visible = false;
+            self.view.redraw(cx);
+        }
+        if self.view.button(ids!(delete_orphans_button)).clicked(&actions) {
+            self.delete_orphans(cx, scope);
+        }
+
         // Handle model checkbox clicks
         self.handle_model_checkbox_clicks(cx, scope, &actions);
 
@@ -147,6 +868,8 @@ impl Widget for SettingsApp {
 
         // Handle A2UI toggle
         self.handle_a2ui_toggle(cx, scope, &actions);
+
+        self.handle_chat_defaults_inputs(cx, scope, &actions);
     }
 
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
@@ -165,6 +888,16 @@ impl Widget for SettingsApp {
 
         // Show/hide add provider modal
         self.view.view(ids!(add_provider_modal)).set_visible(cx, self.modal_visible);
+        self.view.view(ids!(sync_modal)).set_visible(cx, self.sync_mode.is_some());
+        self.view.view(ids!(backup_modal)).set_visible(cx, self.backup_modal_visible);
+        self.view.view(ids!(cleanup_modal)).set_visible(cx, self.cleanup_modal_visible);
+        self.view.view(ids!(api_clients_modal)).set_visible(cx, self.api_clients_modal_visible);
+        self.view.view(ids!(downloads_modal)).set_visible(cx, self.downloads_modal_visible);
+        self.view.view(ids!(network_modal)).set_visible(cx, self.network_modal_visible);
+        self.view.view(ids!(system_prompts_modal)).set_visible(cx, self.system_prompts_modal_visible);
+        self.view.view(ids!(telemetry_modal)).set_visible(cx, self.telemetry_modal_visible);
+        self.view.view(ids!(request_log_modal)).set_visible(cx, self.request_log_modal_visible);
+        self.view.view(ids!(workspace_modal)).set_visible(cx, self.workspace_modal_visible);
 
         // Update provider list from store
         if let Some(store) = scope.data.get::<Store>() {
@@ -196,8 +929,16 @@ impl Widget for SettingsApp {
                             let (model_name, enabled) = &self.fetched_models[item_id];
                             let item_widget = list.item(cx, item_id, live_id!(ModelItem));
 
-                            // Set model name
+                            // Set model name and catalog metadata
                             item_widget.label(ids!(model_name)).set_text(cx, model_name);
+                            let meta = self.fetched_metadata.get(model_name)
+                                .map(|m| m.metadata_summary())
+                                .unwrap_or_default();
+                            item_widget.label(ids!(model_meta)).set_text(cx, &meta);
+
+                            let is_favorite = self.favorite_models.iter().any(|m| m == model_name);
+                            item_widget.button(ids!(favorite_button))
+                                .set_text(cx, if is_favorite { "★" } else { "☆" });
 
                             // Set switch state
                             item_widget.mp_switch(ids!(model_enabled)).set_on(cx, *enabled);
@@ -238,6 +979,8 @@ impl SettingsApp {
         self.connection_status = ProviderConnectionStatus::NotConnected;
         self.model_count = None;
         self.fetched_models.clear();
+        self.fetched_metadata.clear();
+        self.favorite_models.clear();
         self.connection_test_in_progress = false;
         self.load_provider_data(cx, scope);
         self.view.redraw(cx);
@@ -271,6 +1014,20 @@ impl SettingsApp {
                 // Show/hide delete button based on whether provider was custom added
                 self.view.button(ids!(delete_provider_button)).set_visible(cx, provider.was_customly_added);
 
+                // Ollama gets a pull box for downloading new models
+                let is_ollama = provider.provider_type == ProviderType::Ollama;
+                self.view.view(ids!(ollama_section)).set_visible(cx, is_ollama);
+
+                // Chat defaults for providers that chat; realtime voice has its own prompt
+                let chats = !matches!(provider.provider_type, ProviderType::OminiXImage | ProviderType::OpenAiRealtime);
+                self.view.view(ids!(chat_defaults_section)).set_visible(cx, chats);
+                let defaults = &provider.chat_defaults;
+                let number = |n: Option<String>| n.unwrap_or_default();
+                self.view.text_input(ids!(default_model_input)).set_text(cx, defaults.model.as_deref().unwrap_or_default());
+                self.view.text_input(ids!(default_temperature_input)).set_text(cx, &number(defaults.sampling.temperature.map(|t| t.to_string())));
+                self.view.text_input(ids!(default_max_tokens_input)).set_text(cx, &number(defaults.sampling.max_tokens.map(|t| t.to_string())));
+                self.view.text_input(ids!(default_system_prompt_input)).set_text(cx, defaults.system_prompt.as_deref().unwrap_or_default());
+
                 // Show/hide A2UI section based on provider type (only for OpenAI-compatible)
                 let supports_a2ui = provider.supports_a2ui();
                 self.view.view(ids!(a2ui_section)).set_visible(cx, supports_a2ui);
@@ -280,6 +1037,22 @@ impl SettingsApp {
                     self.view.mp_switch(ids!(a2ui_toggle)).set_on(cx, provider.a2ui_enabled);
                 }
 
+                // Show the cached model catalog, if any, without needing a re-test
+                self.favorite_models = provider.favorite_models.clone();
+                if !provider.catalog.is_empty() {
+                    let stored_models: HashMap<String, bool> = provider.models.iter().cloned().collect();
+                    self.fetched_models = provider.catalog.iter()
+                        .map(|m| (m.id.clone(), stored_models.get(&m.id).copied().unwrap_or(true)))
+                        .collect();
+                    self.fetched_metadata = provider.catalog.iter()
+                        .map(|m| (m.id.clone(), m.clone()))
+                        .collect();
+                }
+                let refreshed_text = provider.catalog_updated_display()
+                    .map(|t| format!("Updated {}", t))
+                    .unwrap_or_default();
+                self.view.label(ids!(models_updated_label)).set_text(cx, &refreshed_text);
+
                 // Clear status message
                 self.view.label(ids!(status_message)).set_text(cx, "");
             } else {
@@ -438,6 +1211,19 @@ impl SettingsApp {
         let models_list = self.view.portal_list(ids!(models_list));
 
         for (item_id, item) in models_list.items_with_actions(actions) {
+            if item.button(ids!(favorite_button)).clicked(actions) && item_id < self.fetched_models.len() {
+                let model_name = self.fetched_models[item_id].0.clone();
+                if let (Some(provider_id), Some(store)) = (&self.selected_provider_id, scope.data.get_mut::<Store>()) {
+                    let now_favorite = store.preferences.toggle_provider_favorite_model(provider_id, &model_name);
+                    self.favorite_models = store.preferences.get_provider(provider_id)
+                        .map(|p| p.favorite_models.clone())
+                        .unwrap_or_default();
+                    ::log::info!("Model '{}' favorite: {}", model_name, now_favorite);
+                }
+                self.view.redraw(cx);
+                continue;
+            }
+
             let switch = item.mp_switch(ids!(model_enabled));
             if let Some(new_state) = switch.changed(&actions) {
                 if item_id < self.fetched_models.len() {
@@ -473,6 +1259,44 @@ impl SettingsApp {
         }
     }
 
+    /// Save the provider's new chat defaults as they are typed; a temperature
+    /// or token limit that doesn't parse is kept as it was
+    fn handle_chat_defaults_inputs(&mut self, cx: &mut Cx, scope: &mut Scope, actions: &Actions) {
+        let model = self.view.text_input(ids!(default_model_input)).changed(actions);
+        let temperature = self.view.text_input(ids!(default_temperature_input)).changed(actions);
+        let max_tokens = self.view.text_input(ids!(default_max_tokens_input)).changed(actions);
+        let system_prompt = self.view.text_input(ids!(default_system_prompt_input)).changed(actions);
+        if model.is_none() && temperature.is_none() && max_tokens.is_none() && system_prompt.is_none() {
+            return;
+        }
+        let Some(provider_id) = self.selected_provider_id.clone() else { return };
+        let Some(store) = scope.data.get_mut::<Store>() else { return };
+        let Some(provider) = store.preferences.get_provider_mut(&provider_id) else { return };
+        let non_empty = |text: String| Some(text.trim().to_string()).filter(|t| !t.is_empty());
+        let defaults = &mut provider.chat_defaults;
+        let mut error = None;
+        if let Some(text) = model {
+            defaults.model = non_empty(text);
+        }
+        if let Some(text) = system_prompt {
+            defaults.system_prompt = non_empty(text);
+        }
+        if let Some(text) = temperature {
+            match chat_defaults::parse_temperature(&text) {
+                Ok(t) => defaults.sampling.temperature = t,
+                Err(e) => error = Some(e),
+            }
+        }
+        if let Some(text) = max_tokens {
+            match chat_defaults::parse_max_tokens(&text) {
+                Ok(n) => defaults.sampling.max_tokens = n,
+                Err(e) => error = Some(e),
+            }
+        }
+        store.preferences.save();
+        self.view.label(ids!(status_message)).set_text(cx, &error.map(|e| format!("Error: {}", e)).unwrap_or_default());
+    }
+
     /// Handle the Select All toggle for models
     fn handle_select_all_toggle(&mut self, cx: &mut Cx, scope: &mut Scope, actions: &Actions) {
         let select_all_toggle = self.view.mp_switch(ids!(select_all_toggle));
@@ -522,9 +1346,13 @@ impl SettingsApp {
     }
 
     /// Start a connection test for the currently selected provider
-    fn test_connection(&mut self, cx: &mut Cx, _scope: &mut Scope) {
+    fn test_connection(&mut self, cx: &mut Cx, scope: &mut Scope) {
         let Some(provider_id) = self.selected_provider_id.clone() else { return };
 
+        let is_ollama = scope.data.get::<Store>()
+            .and_then(|store| store.preferences.get_provider(&provider_id))
+            .map_or(false, |p| p.provider_type == ProviderType::Ollama);
+
         // Get provider URL and API key from the current input values
         let url = self.view.text_input(ids!(api_host_input)).text().trim().to_string();
         let api_key = self.view.text_input(ids!(api_key_input)).text().trim().to_string();
@@ -537,7 +1365,7 @@ impl SettingsApp {
             api_key.chars().take(8).collect::<String>()
         );
 
-        if api_key.is_empty() {
+        if api_key.is_empty() && !is_ollama {
             self.connection_status = ProviderConnectionStatus::Error("No API key provided".to_string());
             self.view.label(ids!(status_message)).set_text(cx, "Error: No API key provided");
             self.view.redraw(cx);
@@ -547,6 +1375,10 @@ impl SettingsApp {
         // Update status to connecting
         self.connection_status = ProviderConnectionStatus::Connecting;
         self.provider_statuses.insert(provider_id.clone(), ProviderConnectionStatus::Connecting);
+        cx.action(StoreEvent::ProviderStatusChanged {
+            provider_id: provider_id.clone(),
+            status: ProviderConnectionStatus::Connecting,
+        });
         self.connection_test_in_progress = true;
         self.view.label(ids!(status_message)).set_text(cx, "Testing connection...");
         self.view.redraw(cx);
@@ -559,13 +1391,18 @@ impl SettingsApp {
 
         // Spawn a thread to test the connection
         std::thread::spawn(move || {
-            let result = test_provider_connection(&url_clone, &api_key_clone);
+            // Ollama's native listing includes model sizes
+            let result = if is_ollama {
+                moly_data::list_ollama_models(&url_clone)
+            } else {
+                moly_data::fetch_provider_catalog(&url_clone, &api_key_clone)
+            };
 
             let test_result = match result {
-                Ok((model_count, models)) => ConnectionTestResult {
+                Ok(models) => ConnectionTestResult {
                     provider_id: provider_id_clone,
                     status: ProviderConnectionStatus::Connected,
-                    model_count: Some(model_count),
+                    model_count: Some(models.len()),
                     models,
                 },
                 Err(e) => ConnectionTestResult {
@@ -604,6 +1441,10 @@ impl SettingsApp {
                 test_result.provider_id.clone(),
                 test_result.status.clone()
             );
+            cx.action(StoreEvent::ProviderStatusChanged {
+                provider_id: test_result.provider_id.clone(),
+                status: test_result.status.clone(),
+            });
 
             // Only apply detailed results if this is for the currently selected provider
             if self.selected_provider_id.as_ref() == Some(&test_result.provider_id) {
@@ -611,23 +1452,30 @@ impl SettingsApp {
                 self.model_count = test_result.model_count;
                 self.connection_test_in_progress = false;
 
-                // Get stored model preferences for this provider
-                let stored_models: HashMap<String, bool> = if let Some(store) = scope.data.get::<Store>() {
-                    if let Some(provider) = store.preferences.get_provider(&test_result.provider_id) {
-                        provider.models.iter().cloned().collect()
-                    } else {
-                        HashMap::new()
+                // Cache the catalog (this also records newly seen models as enabled)
+                let mut stored_models: HashMap<String, bool> = HashMap::new();
+                if let Some(store) = scope.data.get_mut::<Store>() {
+                    if !test_result.models.is_empty() {
+                        store.preferences.set_provider_catalog(&test_result.provider_id, test_result.models.clone());
                     }
-                } else {
-                    HashMap::new()
-                };
+                    if let Some(provider) = store.preferences.get_provider(&test_result.provider_id) {
+                        stored_models = provider.models.iter().cloned().collect();
+                        self.favorite_models = provider.favorite_models.clone();
+                    }
+                }
 
                 // Merge fetched models with stored enabled state
-                self.fetched_models = test_result.models.into_iter().map(|name| {
+                self.fetched_models = test_result.models.iter().map(|m| {
                     // Use stored preference, default to enabled if not found
-                    let enabled = stored_models.get(&name).copied().unwrap_or(true);
-                    (name, enabled)
+                    let enabled = stored_models.get(&m.id).copied().unwrap_or(true);
+                    (m.id.clone(), enabled)
                 }).collect();
+                self.fetched_metadata = test_result.models.into_iter()
+                    .map(|m| (m.id.clone(), m))
+                    .collect();
+                if !self.fetched_models.is_empty() {
+                    self.view.label(ids!(models_updated_label)).set_text(cx, "Updated just now");
+                }
 
                 // Update status message
                 let status_text = match &test_result.status {
@@ -647,6 +1495,120 @@ impl SettingsApp {
         }
     }
 
+    /// Start pulling the model named in the Ollama pull input
+    fn start_ollama_pull(&mut self, cx: &mut Cx, _scope: &mut Scope) {
+        if self.ollama_pull_rx.is_some() {
+            return;
+        }
+        let model = self.view.text_input(ids!(ollama_pull_input)).text().trim().to_string();
+        if model.is_empty() {
+            self.view.label(ids!(ollama_pull_status)).set_text(cx, "Enter a model name, e.g. llama3.2:3b");
+            return;
+        }
+        let url = self.view.text_input(ids!(api_host_input)).text().trim().to_string();
+
+        let cancel = Arc::new(AtomicBool::new(false));
+        self.ollama_pull_cancel = Some(cancel.clone());
+        let (tx, rx) = mpsc::channel();
+        self.ollama_pull_rx = Some(rx);
+
+        ::log::info!("Pulling Ollama model {} from {}", model, url);
+        std::thread::spawn(move || {
+            let progress_tx = tx.clone();
+            let result = moly_data::pull_ollama_model(&url, &model, &cancel, |p| {
+                let _ = progress_tx.send(OllamaPullUpdate::Progress(p));
+            });
+            let _ = tx.send(match result {
+                Ok(()) => OllamaPullUpdate::Done,
+                Err(e) => OllamaPullUpdate::Error(e),
+            });
+        });
+
+        self.view.label(ids!(ollama_pull_status)).set_text(cx, "Starting pull…");
+        self.view.button(ids!(ollama_cancel_pull_button)).set_visible(cx, true);
+        cx.new_next_frame();
+        self.view.redraw(cx);
+    }
+
+    /// Drain pull progress; refreshes the model list once the pull finishes
+    fn poll_ollama_pull(&mut self, cx: &mut Cx, scope: &mut Scope) {
+        let Some(rx) = &self.ollama_pull_rx else { return };
+
+        let mut finished: Option<Result<(), String>> = None;
+        let mut latest: Option<OllamaPullProgress> = None;
+        loop {
+            match rx.try_recv() {
+                Ok(OllamaPullUpdate::Progress(p)) => latest = Some(p),
+                Ok(OllamaPullUpdate::Done) => { finished = Some(Ok(())); break; }
+                Ok(OllamaPullUpdate::Error(e)) => { finished = Some(Err(e)); break; }
+                Err(mpsc::TryRecvError::Empty) => break,
+                Err(mpsc::TryRecvError::Disconnected) => { finished = Some(Ok(())); break; }
+            }
+        }
+
+        if let Some(p) = latest {
+            let text = if p.total > 0 {
+                format!("{} — {:.0}% of {:.1} GB", p.status, p.fraction() * 100.0,
+                    p.total as f64 / 1_000_000_000.0)
+            } else {
+                p.status.clone()
+            };
+            self.view.label(ids!(ollama_pull_status)).set_text(cx, &text);
+        }
+
+        match finished {
+            None => cx.new_next_frame(),
+            Some(result) => {
+                let cancelled = self.ollama_pull_cancel.as_ref()
+                    .map_or(false, |c| c.load(Ordering::SeqCst));
+                self.ollama_pull_rx = None;
+                self.ollama_pull_cancel = None;
+                self.view.button(ids!(ollama_cancel_pull_button)).set_visible(cx, false);
+                let msg = match result {
+                    Ok(()) if cancelled => "Pull cancelled".to_string(),
+                    Ok(()) => {
+                        // Refresh the installed model list
+                        self.test_connection(cx, scope);
+                        "Pull complete".to_string()
+                    }
+                    Err(e) => format!("Pull failed: {}", e),
+                };
+                self.view.label(ids!(ollama_pull_status)).set_text(cx, &msg);
+            }
+        }
+        self.view.redraw(cx);
+    }
+
+    /// The modal on top of the page, which keyboard focus is confined to
+    fn open_modal(&self) -> Option<WidgetRef> {
+        let id = if self.modal_visible {
+            live_id!(add_provider_modal)
+        } else if self.sync_mode.is_some() {
+            live_id!(sync_modal)
+        } else if self.backup_modal_visible {
+            live_id!(backup_modal)
+        } else if self.cleanup_modal_visible {
+            live_id!(cleanup_modal)
+        } else if self.api_clients_modal_visible {
+            live_id!(api_clients_modal)
+        } else if self.downloads_modal_visible {
+            live_id!(downloads_modal)
+        } else if self.network_modal_visible {
+            live_id!(network_modal)
+        } else if self.system_prompts_modal_visible {
+            live_id!(system_prompts_modal)
+        } else if self.telemetry_modal_visible {
+            live_id!(telemetry_modal)
+        } else if self.request_log_modal_visible {
+            live_id!(request_log_modal)
+        } else if self.workspace_modal_visible {
+            live_id!(workspace_modal)
+        } else {
+            return None;
+        };
+        Some(self.view.widget(&[id]))
+    }
+
     /// Open the Add Provider modal
     fn open_add_provider_modal(&mut self, cx: &mut Cx) {
         self.modal_visible = true;
@@ -740,110 +1702,948 @@ impl SettingsApp {
 
         self.view.redraw(cx);
     }
-}
 
-/// Test connection to a provider by fetching models
-/// Returns (model_count, model_names) on success, or an error message on failure
-fn test_provider_connection(base_url: &str, api_key: &str) -> Result<(usize, Vec<String>), String> {
-    use reqwest::blocking::Client;
-    use std::time::Duration;
+    // ── Settings export / import ─────────────────────────────────────────────
 
-    let base = base_url.trim_end_matches('/');
+    fn open_sync_modal(&mut self, cx: &mut Cx, mode: SyncMode) {
+        self.sync_mode = Some(mode);
+        self.pending_import = None;
+        let (title, hint) = match mode {
+            SyncMode::Export => ("Export Settings", "Without a passphrase, API keys are left out of the export."),
+            SyncMode::Import => ("Import Settings", "Needed only if the export contains encrypted API keys."),
+        };
+        self.view.label(ids!(sync_modal_title)).set_text(cx, title);
+        self.view.label(ids!(sync_passphrase_hint)).set_text(cx, hint);
+        self.view.text_input(ids!(sync_passphrase_input)).set_text(cx, "");
+        self.view.label(ids!(sync_status)).set_text(cx, "");
+        self.show_sync_conflicts(cx, false);
+        self.view.redraw(cx);
+    }
 
-    // Try multiple endpoint patterns (different providers use different paths)
-    let endpoints_to_try = [
-        format!("{}/models", base),           // OpenAI standard: /v1/models
-        format!("{}/v1/models", base),        // Some need explicit /v1
-        format!("{}", base),                  // Base URL might already include /models
-    ];
+    fn close_sync_modal(&mut self, cx: &mut Cx) {
+        self.sync_mode = None;
+        self.pending_import = None;
+        self.view.redraw(cx);
+    }
 
-    // Create blocking client with timeout
-    let client = Client::builder()
-        .timeout(Duration::from_secs(10))
-        .build()
-        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+    fn show_sync_conflicts(&mut self, cx: &mut Cx, visible: bool) {
+        self.view.view(ids!(sync_conflicts)).set_visible(cx, visible);
+        self.view.button(ids!(keep_local_button)).set_visible(cx, visible);
+        self.view.button(ids!(use_imported_button)).set_visible(cx, visible);
+        self.view.button(ids!(sync_confirm_button)).set_visible(cx, !visible);
+    }
 
-    let mut last_error = String::new();
+    fn sync_passphrase(&self) -> Option<String> {
+        Some(self.view.text_input(ids!(sync_passphrase_input)).text()).filter(|p| !p.is_empty())
+    }
 
-    for models_url in &endpoints_to_try {
-        eprintln!(
-            "[Settings] Testing connection to: {} (key len={})",
-            models_url,
-            api_key.len()
-        );
+    /// Write all settings to a JSON file chosen by the user
+    fn export_settings(&mut self, cx: &mut Cx, scope: &mut Scope) {
+        let Some(store) = scope.data.get::<Store>() else { return };
+        let passphrase = self.sync_passphrase();
+        let export = match SettingsExport::from_preferences(&store.preferences, passphrase.as_deref()) {
+            Ok(export) => export,
+            Err(e) => {
+                self.view.label(ids!(sync_status)).set_text(cx, &format!("Export failed: {}", e));
+                return;
+            }
+        };
+        let Some(path) = FileDialog::new()
+            .add_filter("JSON", &["json"])
+            .set_file_name("ominix-studio-settings.json")
+            .save_file()
+        else { return };
+
+        match export.write_to(&path) {
+            Ok(()) => {
+                ::log::info!("Exported settings to {}", path.display());
+                let keys = if passphrase.is_some() { "API keys encrypted" } else { "API keys omitted" };
+                self.close_sync_modal(cx);
+                self.view.label(ids!(status_message))
+                    .set_text(cx, &format!("Settings exported to {} ({})", path.display(), keys));
+            }
+            Err(e) => self.view.label(ids!(sync_status)).set_text(cx, &format!("Export failed: {}", e)),
+        }
+        self.view.redraw(cx);
+    }
 
-        // Make request to models endpoint
-        let response = match client
-            .get(models_url)
-            .header("Authorization", format!("Bearer {}", api_key))
-            .header("Content-Type", "application/json")
-            .send()
-        {
-            Ok(resp) => resp,
+    /// Read an export and apply it, asking how to resolve conflicts if any
+    fn import_settings(&mut self, cx: &mut Cx, scope: &mut Scope) {
+        let Some(path) = FileDialog::new()
+            .add_filter("JSON", &["json"])
+            .pick_file()
+        else { return };
+        let Some(store) = scope.data.get::<Store>() else { return };
+
+        let passphrase = self.sync_passphrase();
+        let plan = SettingsExport::read_from(&path)
+            .and_then(|export| settings_sync::plan_import(&store.preferences, &export, passphrase.as_deref()));
+        let plan = match plan {
+            Ok(plan) => plan,
             Err(e) => {
-                last_error = if e.is_timeout() {
-                    "Connection timed out".to_string()
-                } else if e.is_connect() {
-                    "Failed to connect to server".to_string()
-                } else {
-                    format!("Request failed: {}", e)
-                };
-                eprintln!("[Settings] Connection error: {}", last_error);
-                continue;
+                self.view.label(ids!(sync_status)).set_text(cx, &e);
+                self.view.redraw(cx);
+                return;
             }
         };
 
-        let status = response.status();
-        eprintln!("[Settings] Response status: {}", status);
+        if plan.conflicts.is_empty() {
+            self.pending_import = Some(plan);
+            self.finish_import(cx, scope, ConflictResolution::KeepLocal);
+            return;
+        }
+
+        let lines: Vec<String> = plan.conflicts.iter()
+            .map(|c| format!("• {}: {}", c.name, c.differences.join(", ")))
+            .collect();
+        self.view.label(ids!(sync_conflicts_label)).set_text(cx, &lines.join("\n"));
+        self.view.label(ids!(sync_status)).set_text(cx, &format!(
+            "{} new provider(s) will be added. Keep your versions of the providers above, or use the imported ones?",
+            plan.new_providers.len()
+        ));
+        self.pending_import = Some(plan);
+        self.show_sync_conflicts(cx, true);
+        self.view.redraw(cx);
+    }
+
+    fn finish_import(&mut self, cx: &mut Cx, scope: &mut Scope, resolution: ConflictResolution) {
+        let Some(plan) = self.pending_import.take() else { return };
+        let Some(store) = scope.data.get_mut::<Store>() else { return };
 
-        // If 404, try next endpoint
-        if status.as_u16() == 404 {
-            last_error = format!("Endpoint not found: {}", models_url);
-            continue;
+        let added = plan.new_providers.len();
+        let updated = if resolution == ConflictResolution::UseImported { plan.conflicts.len() } else { 0 };
+        let keys = if plan.has_secrets { "" } else { " API keys were not included." };
+        settings_sync::apply_import(&mut store.preferences, plan, resolution);
+        store.reconfigure_providers();
+        ::log::info!("Imported settings: {} added, {} updated", added, updated);
+
+        self.close_sync_modal(cx);
+        // Reload the selected provider from the merged preferences
+        self.provider_statuses.clear();
+        self.load_provider_data(cx, scope);
+        self.view.label(ids!(status_message)).set_text(cx, &format!(
+            "Settings imported: {} provider(s) added, {} updated.{}",
+            added, updated, keys
+        ));
+        self.view.redraw(cx);
+    }
+
+    // ── Chat backups ─────────────────────────────────────────────────────────
+
+    fn open_backup_modal(&mut self, cx: &mut Cx, scope: &mut Scope) {
+        let Some(store) = scope.data.get::<Store>() else { return };
+        let settings = &store.preferences.chat_backup;
+        self.view.mp_switch(ids!(backup_auto_toggle)).set_on(cx, settings.enabled);
+        self.view.text_input(ids!(backup_interval_input)).set_text(cx, &settings.interval_hours.to_string());
+        self.view.text_input(ids!(backup_retention_input)).set_text(cx, &settings.retention.to_string());
+        self.view.label(ids!(backup_status)).set_text(cx, "");
+        self.update_backup_summary(cx);
+        self.backup_modal_visible = true;
+        self.view.redraw(cx);
+    }
+
+    fn update_backup_summary(&mut self, cx: &mut Cx) {
+        let backups = chat_backup::list_backups();
+        let summary = match backups.first() {
+            Some(newest) => format!(
+                "{} snapshot(s) in {}. Newest: {}",
+                backups.len(),
+                chat_backup::backups_dir().display(),
+                newest.created_at.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M"),
+            ),
+            None => "No snapshots yet.".to_string(),
+        };
+        self.view.label(ids!(backup_summary)).set_text(cx, &summary);
+    }
+
+    /// Persist the interval and retention inputs; unparsable numbers keep
+    /// their old value
+    fn save_backup_settings(&mut self, scope: &mut Scope) {
+        let Some(store) = scope.data.get_mut::<Store>() else { return };
+        let settings = &mut store.preferences.chat_backup;
+        if let Ok(hours) = self.view.text_input(ids!(backup_interval_input)).text().trim().parse::<u32>() {
+            settings.interval_hours = hours.max(1);
+        }
+        if let Ok(count) = self.view.text_input(ids!(backup_retention_input)).text().trim().parse::<usize>() {
+            settings.retention = count.max(1);
+        }
+        store.preferences.save();
+    }
+
+    fn backup_chats_now(&mut self, cx: &mut Cx, scope: &mut Scope) {
+        self.save_backup_settings(scope);
+        let Some(store) = scope.data.get::<Store>() else { return };
+        let status = match chat_backup::create_backup(&store.chats.saved_chats, store.preferences.chat_backup.retention) {
+            Ok(_) => format!("Backed up {} chat(s).", store.chats.saved_chats.len()),
+            Err(e) => format!("Backup failed: {}", e),
+        };
+        self.view.label(ids!(backup_status)).set_text(cx, &status);
+        self.update_backup_summary(cx);
+        self.view.redraw(cx);
+    }
+
+    /// Replace chat history with a snapshot picked by the user. The current
+    /// history is snapshotted first, so the restore can itself be restored.
+    fn restore_chats(&mut self, cx: &mut Cx, scope: &mut Scope) {
+        let Some(path) = FileDialog::new()
+            .set_directory(chat_backup::backups_dir())
+            .add_filter("Chat backup", &["json"])
+            .pick_file()
+        else { return };
+        let Some(store) = scope.data.get_mut::<Store>() else { return };
+
+        let retention = store.preferences.chat_backup.retention;
+        let chats_dir = store.chats.chats_dir().clone();
+        match chat_backup::restore_backup(&path, &store.chats.saved_chats, &chats_dir, retention) {
+            Ok(count) => {
+                store.chats = Chats::load();
+                cx.action(StoreEvent::ChatsRestored);
+                self.view.label(ids!(backup_status)).set_text(cx, &format!(
+                    "Restored {} chat(s). Your previous history was saved as a snapshot.",
+                    count
+                ));
+            }
+            Err(e) => self.view.label(ids!(backup_status)).set_text(cx, &format!("Restore failed: {}", e)),
+        }
+        self.update_backup_summary(cx);
+        self.view.redraw(cx);
+    }
+
+    // ── Workspace archive ──
+
+    /// Open the modal for an export, with the sizes of this workspace
+    fn open_workspace_modal(&mut self, cx: &mut Cx) {
+        self.workspace_import = None;
+        self.workspace_sections.clear();
+        for (toggle, size, section) in workspace_toggles() {
+            let (files, bytes) = workspace_archive::section_size(section);
+            let text = if files == 0 { "Nothing yet".to_string() } else { format!("{} file(s), {}", files, format_bytes(bytes)) };
+            self.view.label(&[size]).set_text(cx, &text);
+            // Model weights are large and can be downloaded again
+            let on = section != Section::Models && files > 0;
+            self.view.mp_switch(&[toggle]).set_on(cx, on);
+            if on {
+                self.workspace_sections.push(section);
+            }
+        }
+        self.view.label(ids!(workspace_title)).set_text(cx, "Workspace Archive");
+        self.view.label(ids!(workspace_hint)).set_text(cx,
+            "Export everything to one archive to move to another machine or keep a full backup, or import an archive. \
+             Provider API keys are included, so keep the archive private.");
+        if self.workspace_rx.is_none() {
+            self.view.label(ids!(workspace_status)).set_text(cx, "");
+        }
+        self.show_workspace_buttons(cx);
+        self.workspace_modal_visible = true;
+        self.view.redraw(cx);
+    }
+
+    fn show_workspace_buttons(&mut self, cx: &mut Cx) {
+        let idle = self.workspace_rx.is_none();
+        let importing = self.workspace_import.is_some();
+        self.view.button(ids!(workspace_export_button)).set_visible(cx, idle && !importing);
+        self.view.button(ids!(workspace_import_button)).set_visible(cx, idle);
+        self.view.button(ids!(workspace_apply_button)).set_visible(cx, idle && importing);
+    }
+
+    /// Sections switched on in the modal, among those the archive holds
+    /// when importing
+    fn chosen_sections(&self) -> Vec<Section> {
+        Section::ALL.into_iter()
+            .filter(|section| self.workspace_sections.contains(section))
+            .filter(|section| self.workspace_import.as_ref().map_or(true, |(_, m)| m.section(*section).is_some()))
+            .collect()
+    }
+
+    fn export_workspace(&mut self, cx: &mut Cx) {
+        let sections = self.chosen_sections();
+        if sections.is_empty() {
+            self.view.label(ids!(workspace_status)).set_text(cx, "Choose something to export.");
+            return;
+        }
+        let Some(path) = FileDialog::new()
+            .add_filter("Workspace archive", &["tar"])
+            .set_file_name(format!("OminiX-workspace-{}.tar", chrono::Local::now().format("%Y-%m-%d")))
+            .save_file()
+        else { return };
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let progress = tx.clone();
+            let result = workspace_archive::export(&path, &sections, |done, total| {
+                let _ = progress.send(WorkspaceUpdate::Progress(done, total));
+            });
+            let _ = tx.send(WorkspaceUpdate::Exported(result));
+        });
+        self.workspace_rx = Some(rx);
+        self.view.label(ids!(workspace_status)).set_text(cx, "Exporting…");
+        self.show_workspace_buttons(cx);
+        self.view.redraw(cx);
+    }
+
+    /// First step of an import: show what the chosen archive holds
+    fn choose_workspace_archive(&mut self, cx: &mut Cx) {
+        let Some(path) = FileDialog::new()
+            .add_filter("Workspace archive", &["tar"])
+            .pick_file()
+        else { return };
+        let manifest = match workspace_archive::read_manifest(&path) {
+            Ok(manifest) => manifest,
+            Err(e) => {
+                self.view.label(ids!(workspace_status)).set_text(cx, &format!("Can't import: {}", e));
+                return;
+            }
+        };
+        self.workspace_sections.clear();
+        for (toggle, size, section) in workspace_toggles() {
+            let info = manifest.section(section);
+            let text = match info {
+                Some(info) => format!("{} file(s), {}", info.files, format_bytes(info.bytes)),
+                None => "Not in this archive".to_string(),
+            };
+            self.view.label(&[size]).set_text(cx, &text);
+            let on = info.is_some_and(|i| i.files > 0);
+            self.view.mp_switch(&[toggle]).set_on(cx, on);
+            if on {
+                self.workspace_sections.push(section);
+            }
         }
+        self.view.label(ids!(workspace_title)).set_text(cx, "Import Workspace");
+        self.view.label(ids!(workspace_hint)).set_text(cx, &format!(
+            "Exported from the \"{}\" profile on {}. Files from the archive replace files of the same name in this profile; \
+             your current chats are saved as a backup first.",
+            manifest.profile,
+            manifest.exported_at.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M")));
+        self.view.label(ids!(workspace_status)).set_text(cx, "");
+        self.workspace_import = Some((path, manifest));
+        self.show_workspace_buttons(cx);
+        self.view.redraw(cx);
+    }
 
-        // Check response status
-        if !status.is_success() {
-            let error_text = response.text().unwrap_or_default();
-            eprintln!("[Settings] Error response: {}", &error_text[..error_text.len().min(500)]);
-            return Err(match status.as_u16() {
-                401 => "Invalid API key".to_string(),
-                403 => "Access denied".to_string(),
-                429 => "Rate limited".to_string(),
-                _ => format!("HTTP {}: {}", status.as_u16(), error_text),
+    fn import_workspace(&mut self, cx: &mut Cx, scope: &mut Scope) {
+        let Some(path) = self.workspace_import.as_ref().map(|(path, _)| path.clone()) else { return };
+        let sections = self.chosen_sections();
+        if sections.is_empty() {
+            self.view.label(ids!(workspace_status)).set_text(cx, "Choose something to import.");
+            return;
+        }
+        if sections.contains(&Section::Profile) {
+            let Some(store) = scope.data.get::<Store>() else { return };
+            if let Err(e) = chat_backup::create_backup(&store.chats.saved_chats, store.preferences.chat_backup.retention) {
+                self.view.label(ids!(workspace_status)).set_text(cx, &format!("Not imported, backing up your chats failed: {}", e));
+                return;
+            }
+        }
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let progress = tx.clone();
+            let result = workspace_archive::import(&path, &sections, |done, total| {
+                let _ = progress.send(WorkspaceUpdate::Progress(done, total));
             });
+            let _ = tx.send(WorkspaceUpdate::Imported(result));
+        });
+        self.workspace_rx = Some(rx);
+        self.view.label(ids!(workspace_status)).set_text(cx, "Importing…");
+        self.show_workspace_buttons(cx);
+        self.view.redraw(cx);
+    }
+
+    fn poll_workspace(&mut self, cx: &mut Cx) {
+        let Some(rx) = &self.workspace_rx else { return };
+        let mut progress = None;
+        let mut imported = false;
+        let finished = loop {
+            match rx.try_recv() {
+                Ok(WorkspaceUpdate::Progress(done, total)) => progress = Some((done, total)),
+                Ok(WorkspaceUpdate::Exported(result)) => break Some(match result {
+                    Ok(manifest) => format!("Exported {} in {} file(s).",
+                        format_bytes(manifest.total_bytes()), manifest.sections.iter().map(|s| s.files).sum::<usize>()),
+                    Err(e) => format!("Export failed: {}", e),
+                }),
+                Ok(WorkspaceUpdate::Imported(result)) => break Some(match result {
+                    Ok(files) => {
+                        imported = true;
+                        format!("Imported {} file(s).", files)
+                    }
+                    Err(e) => format!("Import stopped: {}", e),
+                }),
+                Err(mpsc::TryRecvError::Empty) => break None,
+                Err(mpsc::TryRecvError::Disconnected) => break Some("The archive stopped unexpectedly.".to_string()),
+            }
+        };
+
+        let Some(status) = finished else {
+            if let Some((done, total)) = progress.filter(|(_, total)| *total > 0) {
+                let status = format!("{:.0}% of {}", done as f64 * 100.0 / total as f64, format_bytes(total));
+                self.view.label(ids!(workspace_status)).set_text(cx, &status);
+            }
+            cx.new_next_frame();
+            return;
+        };
+        self.workspace_rx = None;
+        if imported {
+            self.workspace_import = None;
+            cx.action(StoreEvent::WorkspaceImported);
         }
+        self.view.label(ids!(workspace_status)).set_text(cx, &status);
+        self.show_workspace_buttons(cx);
+        self.view.redraw(cx);
+    }
+
+    fn update_power_status(&mut self, cx: &mut Cx, settings: &PowerSettings) {
+        let status = format!(
+            "{}. In low power mode background checks run less often, downloads run one at a time, and models needing {:.0} GB or more ask before loading.",
+            settings.status(power::on_battery()),
+            settings.large_model_gb
+        );
+        self.view.label(ids!(power_status)).set_text(cx, &status);
+        self.view.redraw(cx);
+    }
 
-        // Parse response
-        let body = match response.text() {
-            Ok(b) => b,
+    fn update_lan_share_status(&mut self, cx: &mut Cx) {
+        let status = match lan_share::sharing_port() {
+            Some(port) => format!("Other OminiX Studio instances on this network can import your downloaded models (port {}).", port),
+            None => "Turn on to let other instances on this network import your downloaded models.".to_string(),
+        };
+        self.view.label(ids!(lan_share_status)).set_text(cx, &status);
+        self.view.redraw(cx);
+    }
+
+    // ── Remote access ──
+
+    /// Start or stop the endpoint to match the preferences
+    fn apply_api_proxy(&mut self, cx: &mut Cx, scope: &mut Scope) {
+        let error = scope.data.get::<Store>().and_then(|s| api_proxy::apply(&s.preferences.api_proxy).err());
+        if let Some(e) = &error {
+            ::log::error!("Failed to start the OpenAI-compatible endpoint: {}", e);
+        }
+        self.update_api_proxy_status(cx, scope, error);
+    }
+
+    fn update_api_proxy_status(&mut self, cx: &mut Cx, scope: &mut Scope, error: Option<String>) {
+        let client_count = scope.data.get::<Store>().map_or(0, |s| s.preferences.api_proxy.clients.len());
+        let status = match (error, api_proxy::running_port()) {
+            (Some(e), _) => format!("Couldn't start: {}", e),
+            (None, Some(port)) => {
+                let host = api_proxy::lan_address().map_or("<this computer>".to_string(), |ip| ip.to_string());
+                let url = format!("http://{}:{}/v1", host, port);
+                if client_count == 0 {
+                    format!("Serving at {}. Add a client to get an API key.", url)
+                } else {
+                    format!("Serving at {} for {} client(s).", url, client_count)
+                }
+            }
+            (None, None) => "Turn on to let phones and other computers on this network use the loaded model through an OpenAI-compatible API.".to_string(),
+        };
+        self.view.label(ids!(api_proxy_status)).set_text(cx, &status);
+        self.view.redraw(cx);
+    }
+
+    fn open_api_clients_modal(&mut self, cx: &mut Cx, scope: &mut Scope) {
+        let Some(store) = scope.data.get::<Store>() else { return };
+        let port = store.preferences.api_proxy.port.to_string();
+        self.view.text_input(ids!(api_port_input)).set_text(cx, &port);
+        self.view.text_input(ids!(api_client_name_input)).set_text(cx, "");
+        self.view.label(ids!(api_clients_status)).set_text(cx, "");
+        self.update_api_clients_list(cx, scope);
+        self.api_clients_modal_visible = true;
+        self.view.redraw(cx);
+    }
+
+    fn update_api_clients_list(&mut self, cx: &mut Cx, scope: &mut Scope) {
+        let Some(store) = scope.data.get::<Store>() else { return };
+        let clients = &store.preferences.api_proxy.clients;
+        let text = if clients.is_empty() {
+            "No clients yet.".to_string()
+        } else {
+            clients.iter().map(|c| {
+                let last_used = api_proxy::last_used(&c.name)
+                    .map_or("not used this session".to_string(), |t| format!("last used {}", t.with_timezone(&chrono::Local).format("%H:%M")));
+                format!("{} — {} — created {}, {}", c.name, c.token_hint, c.created_at.format("%Y-%m-%d"), last_used)
+            }).collect::<Vec<_>>().join("\n")
+        };
+        self.view.label(ids!(api_clients_list)).set_text(cx, &text);
+    }
+
+    /// Create a client and copy its token, which is shown in full only now
+    fn add_api_client(&mut self, cx: &mut Cx, scope: &mut Scope) {
+        let name = self.view.text_input(ids!(api_client_name_input)).text();
+        let Some(store) = scope.data.get_mut::<Store>() else { return };
+        let status = match store.preferences.api_proxy.add_client(&name) {
+            Ok(token) => {
+                let name = name.trim();
+                store.preferences.save();
+                api_proxy::set_clients(&store.preferences.api_proxy.clients);
+                cx.copy_to_clipboard(&token);
+                self.view.text_input(ids!(api_client_name_input)).set_text(cx, "");
+                format!("Token for {} (copied to the clipboard):\n{}", name, token)
+            }
+            Err(e) => e,
+        };
+        self.view.label(ids!(api_clients_status)).set_text(cx, &status);
+        self.update_api_clients_list(cx, scope);
+        self.update_api_proxy_status(cx, scope, None);
+    }
+
+    fn revoke_api_client(&mut self, cx: &mut Cx, scope: &mut Scope) {
+        let name = self.view.text_input(ids!(api_client_name_input)).text();
+        let Some(store) = scope.data.get_mut::<Store>() else { return };
+        let status = if store.preferences.api_proxy.revoke_client(&name) {
+            store.preferences.save();
+            api_proxy::set_clients(&store.preferences.api_proxy.clients);
+            self.view.text_input(ids!(api_client_name_input)).set_text(cx, "");
+            format!("Revoked {}.", name.trim())
+        } else {
+            "Enter the name of a client to revoke.".to_string()
+        };
+        self.view.label(ids!(api_clients_status)).set_text(cx, &status);
+        self.update_api_clients_list(cx, scope);
+        self.update_api_proxy_status(cx, scope, None);
+    }
+
+    /// Persist the port input, restarting the endpoint on the new port;
+    /// an unparsable port keeps the old one
+    fn save_api_port(&mut self, cx: &mut Cx, scope: &mut Scope) {
+        let Ok(port) = self.view.text_input(ids!(api_port_input)).text().trim().parse::<u16>() else { return };
+        let Some(store) = scope.data.get_mut::<Store>() else { return };
+        if port == 0 || port == store.preferences.api_proxy.port { return; }
+        store.preferences.api_proxy.port = port;
+        store.preferences.save();
+        self.apply_api_proxy(cx, scope);
+    }
+
+    // ── Storage & downloads ──
+
+    fn open_downloads_modal(&mut self, cx: &mut Cx, scope: &mut Scope) {
+        let Some(store) = scope.data.get::<Store>() else { return };
+        let settings = &store.preferences.downloads;
+        self.view.text_input(ids!(models_dir_input)).set_text(cx, settings.models_dir.as_deref().unwrap_or(""));
+        self.view.text_input(ids!(max_downloads_input)).set_text(cx, &settings.max_concurrent.to_string());
+        let limit = if settings.max_mb_per_sec == 0 { String::new() } else { settings.max_mb_per_sec.to_string() };
+        self.view.text_input(ids!(speed_limit_input)).set_text(cx, &limit);
+        self.view.text_input(ids!(mirrors_input)).set_text(cx, &settings.mirrors.join(", "));
+        self.view.text_input(ids!(stall_secs_input)).set_text(cx, &settings.stall_secs.to_string());
+        self.view.text_input(ids!(stall_retries_input)).set_text(cx, &settings.stall_retries.to_string());
+        self.view.text_input(ids!(load_timeout_input)).set_text(cx, &settings.load_timeout_mins.to_string());
+        self.view.mp_switch(ids!(verify_downloads_toggle)).set_on(cx, settings.verify_after_download);
+        self.view.mp_switch(ids!(cleanup_partial_toggle)).set_on(cx, settings.cleanup_partial_on_start);
+        let hours = store.preferences.generated_files_hours;
+        self.view.text_input(ids!(generated_files_input)).set_text(cx, &if hours == 0 { String::new() } else { hours.to_string() });
+        self.view.label(ids!(downloads_status)).set_text(cx, "");
+        self.downloads_draft = settings.clone();
+        self.downloads_modal_visible = true;
+        self.view.redraw(cx);
+    }
+
+    /// The settings entered in the modal, checked
+    fn read_download_settings(&self) -> Result<DownloadSettings, String> {
+        let models_dir = self.view.text_input(ids!(models_dir_input)).text().trim().to_string();
+        let max_concurrent = self.view.text_input(ids!(max_downloads_input)).text().trim().parse::<usize>()
+            .map_err(|_| "Simultaneous downloads must be a whole number".to_string())?;
+        let limit = self.view.text_input(ids!(speed_limit_input)).text();
+        let max_mb_per_sec = match limit.trim() {
+            "" => 0,
+            limit => limit.parse::<u32>().map_err(|_| "Speed limit must be a whole number of MB/s, or empty for none".to_string())?,
+        };
+        let stall_secs = self.view.text_input(ids!(stall_secs_input)).text().trim().parse::<u64>()
+            .map_err(|_| "Stall timeout must be a whole number of seconds".to_string())?;
+        let stall_retries = self.view.text_input(ids!(stall_retries_input)).text().trim().parse::<u32>()
+            .map_err(|_| "Automatic retries must be a whole number".to_string())?;
+        let load_timeout_mins = self.view.text_input(ids!(load_timeout_input)).text().trim().parse::<u64>()
+            .map_err(|_| "Load timeout must be a whole number of minutes".to_string())?;
+        let settings = DownloadSettings {
+            models_dir: (!models_dir.is_empty()).then_some(models_dir),
+            max_concurrent,
+            max_mb_per_sec,
+            mirrors: download_settings::parse_mirrors(&self.view.text_input(ids!(mirrors_input)).text()),
+            stall_secs,
+            stall_retries,
+            load_timeout_mins,
+            ..self.downloads_draft.clone()
+        };
+        settings.validate()?;
+        Ok(settings)
+    }
+
+    /// Read the speech format, sample rate and folder and save them when valid
+    fn save_tts_output(&mut self, cx: &mut Cx, scope: &mut Scope) {
+        let format = self.view.text_input(ids!(tts_format_input)).text();
+        let format = if format.trim().is_empty() { Ok(OutputFormat::Wav) } else { OutputFormat::parse(&format) };
+        let read = format.and_then(|format| {
+            let sample_rate = tts_output::parse_sample_rate(&self.view.text_input(ids!(tts_sample_rate_input)).text())?;
+            let directory = self.view.text_input(ids!(tts_output_dir_input)).text().trim().to_string();
+            if !directory.is_empty() && !std::path::Path::new(&directory).is_dir() {
+                return Err(format!("{} isn't a folder", directory));
+            }
+            Ok(TtsOutputSettings { format, sample_rate, directory })
+        });
+        let settings = match read {
+            Ok(settings) => settings,
             Err(e) => {
-                last_error = format!("Failed to read response: {}", e);
-                continue;
+                self.view.label(ids!(tts_output_status)).set_text(cx, &e);
+                return;
             }
         };
+        self.view.label(ids!(tts_output_status)).set_text(cx, &format!("Speech is generated as {}.", settings.summary()));
+        tts_output::set_current(&settings);
+        if let Some(store) = scope.data.get_mut::<Store>() {
+            store.preferences.tts_output = settings;
+            store.preferences.save();
+        }
+    }
 
-        // Try to parse as OpenAI-compatible models response
-        eprintln!("[Settings] Response body ({} bytes): {}",
-            body.len(), &body[..body.len().min(300)]);
-        match serde_json::from_str::<ModelsResponse>(&body) {
-            Ok(models) => {
-                let model_names: Vec<String> = models.data.into_iter().map(|m| m.id).collect();
-                eprintln!("[Settings] Found {} models", model_names.len());
-                return Ok((model_names.len(), model_names));
+    /// Hours to keep generated files after quitting; empty means none
+    fn read_generated_files_hours(&self) -> Result<u32, String> {
+        match self.view.text_input(ids!(generated_files_input)).text().trim() {
+            "" => Ok(0),
+            hours => hours.parse::<u32>().map_err(|_| "Hours to keep generated files must be a whole number".to_string()),
+        }
+    }
+
+    /// Save and apply the modal's settings, or show why they can't be
+    fn save_download_settings(&mut self, cx: &mut Cx, scope: &mut Scope) {
+        let read = self.read_download_settings()
+            .and_then(|settings| Ok((settings, self.read_generated_files_hours()?)));
+        let (settings, generated_files_hours) = match read {
+            Ok(read) => read,
+            Err(e) => {
+                self.view.label(ids!(downloads_status)).set_text(cx, &e);
+                self.view.redraw(cx);
+                return;
             }
+        };
+        let Some(store) = scope.data.get_mut::<Store>() else { return };
+        download_settings::set_current(&settings);
+        artifacts::set_retention_hours(generated_files_hours);
+        store.preferences.downloads = settings;
+        store.preferences.generated_files_hours = generated_files_hours;
+        store.preferences.save();
+        self.downloads_modal_visible = false;
+        self.update_downloads_summary(cx, scope);
+        self.view.redraw(cx);
+    }
+
+    fn update_downloads_summary(&mut self, cx: &mut Cx, scope: &mut Scope) {
+        let Some(store) = scope.data.get::<Store>() else { return };
+        let settings = &store.preferences.downloads;
+        let limit = match settings.max_mb_per_sec {
+            0 => "no speed limit".to_string(),
+            mb => format!("up to {} MB/s", mb),
+        };
+        let mut summary = format!("{} — {} at a time, {}",
+            settings.models_dir.as_deref().unwrap_or(download_settings::DEFAULT_MODELS_DIR), settings.max_concurrent, limit);
+        if !settings.mirrors.is_empty() {
+            summary.push_str(&format!(", {} mirror(s)", settings.mirrors.len()));
+        }
+        self.view.label(ids!(downloads_summary)).set_text(cx, &summary);
+    }
+
+    // ── Proxy & certificates ──
+
+    fn open_network_modal(&mut self, cx: &mut Cx, scope: &mut Scope) {
+        let Some(store) = scope.data.get::<Store>() else { return };
+        let settings = &store.preferences.network;
+        self.view.text_input(ids!(proxy_url_input)).set_text(cx, settings.proxy_url.as_deref().unwrap_or(""));
+        self.view.text_input(ids!(proxy_username_input)).set_text(cx, &settings.proxy_username);
+        self.view.text_input(ids!(proxy_password_input)).set_text(cx, &settings.proxy_password);
+        self.view.text_input(ids!(no_proxy_input)).set_text(cx, &settings.no_proxy);
+        self.view.text_input(ids!(ca_cert_input)).set_text(cx, settings.ca_cert_path.as_deref().unwrap_or(""));
+        self.view.label(ids!(network_status)).set_text(cx, "");
+        self.network_modal_visible = true;
+        self.view.redraw(cx);
+    }
+
+    /// The settings entered in the modal, checked
+    fn read_network_settings(&self) -> Result<NetworkSettings, String> {
+        let text = |id: &[LiveId]| self.view.text_input(id).text().trim().to_string();
+        let proxy_url = text(ids!(proxy_url_input)).trim_end_matches('/').to_string();
+        let ca_cert_path = text(ids!(ca_cert_input));
+        let settings = NetworkSettings {
+            proxy_url: (!proxy_url.is_empty()).then_some(proxy_url),
+            proxy_username: text(ids!(proxy_username_input)),
+            // Passwords may start or end with spaces
+            proxy_password: self.view.text_input(ids!(proxy_password_input)).text(),
+            no_proxy: text(ids!(no_proxy_input)),
+            ca_cert_path: (!ca_cert_path.is_empty()).then_some(ca_cert_path),
+        };
+        settings.validate()?;
+        Ok(settings)
+    }
+
+    /// Save and apply the modal's settings, or show why they can't be
+    fn save_network_settings(&mut self, cx: &mut Cx, scope: &mut Scope) {
+        let settings = match self.read_network_settings() {
+            Ok(settings) => settings,
             Err(e) => {
-                // If we got a 200 but can't parse models, still consider it connected
-                eprintln!("[Settings] Could not parse models response: {}", e);
-                return Ok((0, vec![]));
+                self.view.label(ids!(network_status)).set_text(cx, &e);
+                self.view.redraw(cx);
+                return;
+            }
+        };
+        let Some(store) = scope.data.get_mut::<Store>() else { return };
+        network::set_current(&settings);
+        store.preferences.network = settings;
+        store.preferences.save();
+        // Provider clients only read the proxy when they're built
+        store.reconfigure_providers();
+        self.network_modal_visible = false;
+        self.update_network_summary(cx, scope);
+        self.view.redraw(cx);
+    }
+
+    fn update_network_summary(&mut self, cx: &mut Cx, scope: &mut Scope) {
+        let Some(store) = scope.data.get::<Store>() else { return };
+        let settings = &store.preferences.network;
+        let mut summary = match (&settings.proxy_url, settings.proxy_username.is_empty()) {
+            (None, _) => "Direct connection".to_string(),
+            (Some(url), true) => format!("Through {}", url),
+            (Some(url), false) => format!("Through {} as {}", url, settings.proxy_username),
+        };
+        if let Some(path) = &settings.ca_cert_path {
+            summary.push_str(&format!(", trusting {}", path));
+        }
+        self.view.label(ids!(network_summary)).set_text(cx, &summary);
+    }
+
+    // ── System prompts ──
+
+    fn open_system_prompts_modal(&mut self, cx: &mut Cx, scope: &mut Scope) {
+        let Some(store) = scope.data.get::<Store>() else { return };
+        let presets = store.preferences.system_prompts.clone();
+        self.show_system_prompts(cx, &presets);
+        self.system_prompts_modal_visible = true;
+        self.view.redraw(cx);
+    }
+
+    fn show_system_prompts(&mut self, cx: &mut Cx, presets: &SystemPromptPresets) {
+        for (input, kind) in system_prompt_inputs() {
+            self.view.text_input(&[input]).set_text(cx, presets.get(kind));
+        }
+    }
+
+    /// Save the modal's prompts; chats already open keep theirs
+    fn save_system_prompts(&mut self, cx: &mut Cx, scope: &mut Scope) {
+        let Some(store) = scope.data.get_mut::<Store>() else { return };
+        for (input, kind) in system_prompt_inputs() {
+            let text = self.view.text_input(&[input]).text();
+            store.preferences.system_prompts.set(kind, &text);
+        }
+        store.preferences.save();
+        self.system_prompts_modal_visible = false;
+        self.view.redraw(cx);
+    }
+
+    // ── Usage statistics ──
+
+    fn update_telemetry_summary(&mut self, cx: &mut Cx) {
+        let summary = if !telemetry::is_enabled() {
+            "Off. Turn on to count, on this computer only, which features you use and which kinds of errors happen.".to_string()
+        } else {
+            let report = telemetry::report();
+            let uses: u64 = report.features.values().sum();
+            let errors: u64 = report.errors.values().sum();
+            format!("{} feature use(s) and {} error(s) counted since {}.",
+                uses, errors, report.since.with_timezone(&chrono::Local).format("%Y-%m-%d"))
+        };
+        self.view.label(ids!(telemetry_summary)).set_text(cx, &summary);
+    }
+
+    fn open_telemetry_modal(&mut self, cx: &mut Cx, scope: &mut Scope) {
+        let Some(store) = scope.data.get::<Store>() else { return };
+        self.view.text_input(ids!(telemetry_endpoint_input)).set_text(cx, &store.preferences.telemetry.endpoint);
+        self.view.label(ids!(telemetry_status)).set_text(cx, "");
+        self.update_telemetry_counts(cx);
+        self.telemetry_modal_visible = true;
+        self.view.redraw(cx);
+    }
+
+    fn update_telemetry_counts(&mut self, cx: &mut Cx) {
+        let report = telemetry::report();
+        let text = if report.is_empty() {
+            "Nothing recorded yet.".to_string()
+        } else {
+            report.summary(12).join("\n")
+        };
+        self.view.label(ids!(telemetry_counts)).set_text(cx, &text);
+    }
+
+    fn clear_telemetry(&mut self, cx: &mut Cx) {
+        let status = match telemetry::clear() {
+            Ok(()) => "Statistics cleared.".to_string(),
+            Err(e) => format!("Clearing failed: {}", e),
+        };
+        self.view.label(ids!(telemetry_status)).set_text(cx, &status);
+        self.update_telemetry_counts(cx);
+        self.update_telemetry_summary(cx);
+        self.view.redraw(cx);
+    }
+
+    /// Write the counts to a JSON file chosen by the user
+    fn export_telemetry(&mut self, cx: &mut Cx) {
+        let Some(path) = FileDialog::new()
+            .add_filter("JSON", &["json"])
+            .set_file_name("ominix-studio-usage.json")
+            .save_file()
+        else { return };
+        let status = match telemetry::export_json().and_then(|json| {
+            std::fs::write(&path, json).map_err(|e| format!("{}: {}", path.display(), e))
+        }) {
+            Ok(()) => format!("Statistics exported to {}", path.display()),
+            Err(e) => format!("Export failed: {}", e),
+        };
+        self.view.label(ids!(telemetry_status)).set_text(cx, &status);
+        self.view.redraw(cx);
+    }
+
+    /// Save the endpoint and close, or show why it can't be used
+    fn save_telemetry_endpoint(&mut self, cx: &mut Cx, scope: &mut Scope) {
+        let Some(store) = scope.data.get_mut::<Store>() else { return };
+        let endpoint = self.view.text_input(ids!(telemetry_endpoint_input)).text().trim().to_string();
+        let settings = telemetry::TelemetrySettings { endpoint, ..store.preferences.telemetry.clone() };
+        if let Err(e) = settings.validate() {
+            self.view.label(ids!(telemetry_status)).set_text(cx, &e);
+            self.view.redraw(cx);
+            return;
+        }
+        if settings != store.preferences.telemetry {
+            telemetry::set_current(&settings);
+            store.preferences.telemetry = settings;
+            store.preferences.save();
+        }
+        self.telemetry_modal_visible = false;
+        self.view.redraw(cx);
+    }
+
+    // ── Request log ──
+
+    /// Requests matching the filter, newest first
+    fn filtered_requests(&self) -> Vec<request_log::RequestRecord> {
+        let query = self.view.text_input(ids!(request_log_filter_input)).text();
+        request_log::entries().into_iter().rev().filter(|r| request_log::matches(r, &query)).collect()
+    }
+
+    fn update_request_log(&mut self, cx: &mut Cx) {
+        const SHOWN: usize = 50;
+        self.request_log_generation = request_log::generation();
+        let records = self.filtered_requests();
+        let text = if records.is_empty() {
+            "No requests.".to_string()
+        } else {
+            let mut entries: Vec<String> = records.iter().take(SHOWN).map(|r| {
+                let mut entry = r.summary();
+                if let Some(error) = &r.error {
+                    entry.push_str(&format!("\nError: {}", error));
+                }
+                if r.reconstructed {
+                    entry.push_str("\nRebuilt from the chat; the raw request and HTTP status weren't captured.");
+                }
+                if !r.request_body.is_empty() {
+                    entry.push_str(&format!("\n→ {}", r.request_body));
+                }
+                if !r.response_body.is_empty() {
+                    entry.push_str(&format!("\n← {}", r.response_body));
+                }
+                entry
+            }).collect();
+            if records.len() > SHOWN {
+                entries.push(format!("…and {} older. Export to see all of them.", records.len() - SHOWN));
+            }
+            entries.join("\n\n")
+        };
+        self.view.label(ids!(request_log_list)).set_text(cx, &text);
+        self.view.redraw(cx);
+    }
+
+    /// Write the filtered requests to a HAR or JSON Lines file chosen by the user
+    fn export_request_log(&mut self, cx: &mut Cx, har: bool) {
+        let records = self.filtered_requests();
+        let (filter, extension, contents) = if har {
+            ("HTTP Archive", "har", request_log::to_har(&records))
+        } else {
+            ("JSON Lines", "jsonl", request_log::to_jsonl(&records))
+        };
+        let Some(path) = FileDialog::new()
+            .add_filter(filter, &[extension])
+            .set_file_name(format!("ominix-studio-requests.{}", extension))
+            .save_file()
+        else { return };
+        let status = match std::fs::write(&path, contents) {
+            Ok(()) => format!("{} request(s) exported to {}", records.len(), path.display()),
+            Err(e) => format!("Export failed: {}: {}", path.display(), e),
+        };
+        self.view.label(ids!(request_log_status)).set_text(cx, &status);
+        self.view.redraw(cx);
+    }
+
+    // ── Model cleanup ──
+
+    fn open_cleanup_modal(&mut self, cx: &mut Cx, scope: &mut Scope) {
+        self.orphans = model_integrity::find_orphans(&ModelRegistry::load());
+        let hard_delete = scope.data.get::<Store>().map_or(false, |s| s.preferences.hard_delete_models);
+        self.view.button(ids!(delete_orphans_button))
+            .set_text(cx, if hard_delete { "Delete All" } else { "Move All to Trash" });
+        self.view.label(ids!(cleanup_status)).set_text(cx, "");
+        self.update_cleanup_list(cx);
+        self.cleanup_modal_visible = true;
+        self.view.redraw(cx);
+    }
+
+    fn update_cleanup_list(&mut self, cx: &mut Cx) {
+        let text = if self.orphans.is_empty() {
+            "Nothing to clean up.".to_string()
+        } else {
+            let total: u64 = self.orphans.iter().map(|o| o.bytes).sum();
+            let mut lines: Vec<String> = self.orphans.iter()
+                .map(|o| format!("{} — {} ({})\n{}", o.model_id, o.reason, format_bytes(o.bytes), o.path.display()))
+                .collect();
+            lines.push(format!("Total: {}", format_bytes(total)));
+            lines.join("\n\n")
+        };
+        self.view.label(ids!(cleanup_list)).set_text(cx, &text);
+        self.view.button(ids!(delete_orphans_button)).set_visible(cx, !self.orphans.is_empty());
+    }
+
+    /// Remove every listed orphan and rescan the models they belonged to
+    fn delete_orphans(&mut self, cx: &mut Cx, scope: &mut Scope) {
+        let hard_delete = scope.data.get::<Store>().map_or(false, |s| s.preferences.hard_delete_models);
+        let mut removed = 0;
+        let mut errors = Vec::new();
+        for orphan in std::mem::take(&mut self.orphans) {
+            match model_integrity::delete_orphan(&orphan, hard_delete) {
+                Ok(()) => removed += 1,
+                Err(e) => {
+                    ::log::error!("Cleanup failed: {}", e);
+                    errors.push(e);
+                    self.orphans.push(orphan);
+                }
             }
         }
+
+        let registry = ModelRegistry::load();
+        for model in &registry.models {
+            let key = model.storage.expanded_path();
+            if model_state::download_status(&key) == DownloadStatus::Partial {
+                model_state::set_download_status(cx, &key, model_state::scan_registry_files(model));
+            }
+        }
+
+        let status = if errors.is_empty() {
+            format!("Removed {} item(s).", removed)
+        } else {
+            format!("Removed {} item(s). {} failed: {}", removed, errors.len(), errors.join("; "))
+        };
+        self.view.label(ids!(cleanup_status)).set_text(cx, &status);
+        self.update_cleanup_list(cx);
+        self.view.redraw(cx);
     }
+}
 
-    // All endpoints failed
-    Err(if last_error.is_empty() {
-        "Could not find models endpoint".to_string()
+/// Format bytes as human-readable string
+fn format_bytes(bytes: u64) -> String {
+    const MB: u64 = 1024 * 1024;
+    const GB: u64 = MB * 1024;
+    if bytes >= GB {
+        format!("{:.2} GB", bytes as f64 / GB as f64)
     } else {
-        last_error
-    })
+        format!("{:.1} MB", bytes as f64 / MB as f64)
+    }
 }
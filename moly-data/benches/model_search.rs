@@ -0,0 +1,66 @@
+//! Hub search over a registry of a few thousand models, typed one character
+//! at a time the way `search_input` delivers it.
+//!
+//! Run with `cargo bench -p moly-data --bench model_search`.
+
+use moly_data::model_search::{ModelSearch, ModelSearchIndex, HUB_CATEGORIES};
+use moly_data::ModelRegistry;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const COPIES: usize = 50;
+const ROUNDS: u32 = 200;
+
+fn main() {
+    let bundled = ModelRegistry::bundled();
+    let mut models = Vec::with_capacity(bundled.models.len() * COPIES);
+    for copy in 0..COPIES {
+        for model in &bundled.models {
+            let mut model = model.clone();
+            model.id = format!("{}-{}", model.id, copy);
+            model.name = format!("{} {}", model.name, copy);
+            models.push(model);
+        }
+    }
+    println!("{} models", models.len());
+
+    let build = time(|| { black_box(ModelSearchIndex::build(&models)); });
+    println!("build index          {:>10.1?}", build);
+
+    let index = ModelSearchIndex::build(&models);
+    let typed = ["q", "qw", "qwe", "qwen", "qwen3", "qwen3 ", "qwen3 4"];
+
+    let incremental = time(|| {
+        let mut search = ModelSearch::new(index.clone());
+        for query in typed {
+            search.set_query(query);
+            black_box(HUB_CATEGORIES.map(|c| search.results(c).len()));
+        }
+    });
+    println!("typing, incremental  {:>10.1?}", incremental);
+
+    let full = time(|| {
+        for query in typed {
+            let q = query.to_lowercase();
+            for cat in HUB_CATEGORIES {
+                black_box(models.iter()
+                    .filter(|m| m.category == cat)
+                    .filter(|m| m.name.to_lowercase().contains(&q)
+                        || m.description.to_lowercase().contains(&q)
+                        || m.tags.iter().any(|t| t.to_lowercase().contains(&q)))
+                    .count());
+            }
+        }
+    });
+    println!("typing, full rescan  {:>10.1?}", full);
+}
+
+/// Mean time of one run
+fn time(mut f: impl FnMut()) -> Duration {
+    f();
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        f();
+    }
+    start.elapsed() / ROUNDS
+}
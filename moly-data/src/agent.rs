@@ -0,0 +1,265 @@
+//! Agent mode
+//!
+//! Works toward a goal as a loop of model calls: when a reply calls tools,
+//! their results go back to the model, until it answers without calling
+//! one. Past the step limit the model is asked to sum up instead. Each
+//! thought, tool call and result is reported as an [`AgentEvent`], so the
+//! chat app can show the run as a timeline while it happens and keep each
+//! step in the chat ([`AgentStep::message_text`]).
+//!
+//! The loop runs on its own thread against the provider's OpenAI-compatible
+//! `/chat/completions` endpoint and offers the tools the chat exposes,
+//! built-in and MCP ([`crate::chat_tools`]). Results of lookups are numbered
+//! as sources the answer can cite ([`crate::citations`]).
+
+use serde_json::{json, Value};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::time::Duration;
+
+use crate::chat_tools;
+use crate::chats::ChatId;
+use crate::citations::{self, Source};
+use crate::toolsets::ToolSelection;
+
+pub const DEFAULT_MAX_STEPS: usize = 8;
+
+/// Tool output kept in the chat's step messages, in characters
+const STEP_MESSAGE_CHARS: usize = 2_000;
+
+const AGENT_SYSTEM_PROMPT: &str = "You are working toward the user's goal step by step. \
+Use the tools when they help, one or a few calls at a time, and look at each result before deciding the next step. \
+When the goal is reached, or can't be, reply without calling tools: summarise what you did and what you found. \
+Tool results that start with \"Source [n]\" can be cited: put [n] after the sentences that use them.";
+
+const STEP_LIMIT_PROMPT: &str = "You have reached the step limit. Without calling tools, \
+summarise what you did, what you found and what is left to do.";
+
+/// Everything a run needs; built by the chat app from the chat on screen
+#[derive(Clone, Debug)]
+pub struct AgentRequest {
+    /// Provider base URL, e.g. `https://api.openai.com/v1`
+    pub base_url: String,
+    pub api_key: Option<String>,
+    pub model: String,
+    /// Earlier messages of the chat as (role, text)
+    pub history: Vec<(String, String)>,
+    pub goal: String,
+    pub tools: ToolSelection,
+    pub max_steps: usize,
+    /// The chat the run is for, which records the files it touches
+    pub chat_id: Option<ChatId>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum StepKind {
+    /// Text the model wrote alongside its tool calls
+    Thought,
+    ToolCall,
+    ToolResult,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AgentStep {
+    /// Model call the step belongs to, from 1
+    pub number: usize,
+    pub kind: StepKind,
+    pub title: String,
+    pub detail: String,
+}
+
+impl AgentStep {
+    /// The step as a chat message, with long tool output cut short
+    pub fn message_text(&self) -> String {
+        match self.kind {
+            StepKind::Thought => format!("**Step {} · {}**\n\n{}", self.number, self.title, self.detail),
+            StepKind::ToolCall => format!("**Step {} · Calling {}**\n\n```json\n{}\n```", self.number, self.title, self.detail),
+            StepKind::ToolResult => {
+                let mut detail: String = self.detail.chars().take(STEP_MESSAGE_CHARS).collect();
+                if self.detail.chars().count() > STEP_MESSAGE_CHARS {
+                    detail.push_str("\n…");
+                }
+                format!("**Step {} · {}**\n\n```\n{}\n```", self.number, self.title, detail)
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum AgentEvent {
+    Step(AgentStep),
+    /// A tool result the answer may cite
+    Source(Source),
+    /// The model's final answer
+    Finished(String),
+    Failed(String),
+    Aborted,
+}
+
+/// Pause and abort switches of a running agent
+#[derive(Clone, Debug, Default)]
+pub struct AgentControl {
+    paused: Arc<AtomicBool>,
+    aborted: Arc<AtomicBool>,
+}
+
+impl AgentControl {
+    /// Hold the run before its next model call or tool call
+    pub fn set_paused(&self, paused: bool) {
+        self.paused.store(paused, Ordering::SeqCst);
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::SeqCst)
+    }
+
+    /// Stop the run at the next step
+    pub fn abort(&self) {
+        self.aborted.store(true, Ordering::SeqCst);
+    }
+
+    /// Wait while paused. Returns false once aborted.
+    fn proceed(&self) -> bool {
+        while self.is_paused() && !self.aborted.load(Ordering::SeqCst) {
+            std::thread::sleep(Duration::from_millis(100));
+        }
+        !self.aborted.load(Ordering::SeqCst)
+    }
+}
+
+/// A tool call from a model reply
+#[derive(Clone, Debug, PartialEq)]
+pub struct ToolCall {
+    pub id: String,
+    pub name: String,
+    pub args: Value,
+}
+
+/// The tool calls of an assistant message. Arguments arrive as a JSON
+/// string; ones that don't parse become an empty object. Every call is
+/// kept, since each needs an answer: one without a function name gets an
+/// empty name, which [`chat_tools::call`] answers with an error.
+pub fn tool_calls(message: &Value) -> Vec<ToolCall> {
+    let Some(calls) = message.get("tool_calls").and_then(Value::as_array) else { return Vec::new() };
+    calls.iter().map(|call| {
+        let function = call.get("function");
+        let args = match function.and_then(|f| f.get("arguments")) {
+            Some(Value::String(text)) => serde_json::from_str(text).unwrap_or_else(|_| json!({})),
+            Some(value @ Value::Object(_)) => value.clone(),
+            _ => json!({}),
+        };
+        ToolCall {
+            id: call.get("id").and_then(Value::as_str).unwrap_or_default().to_string(),
+            name: function.and_then(|f| f.get("name")).and_then(Value::as_str).unwrap_or_default().to_string(),
+            args,
+        }
+    }).collect()
+}
+
+/// Start a run on its own thread
+pub fn start(request: AgentRequest) -> (AgentControl, mpsc::Receiver<AgentEvent>) {
+    let control = AgentControl::default();
+    let (tx, rx) = mpsc::channel();
+    let thread_control = control.clone();
+    std::thread::spawn(move || {
+        let send = |event: AgentEvent| {
+            let _ = tx.send(event);
+            makepad_widgets::SignalToUI::set_ui_signal();
+        };
+        let outcome = run(&request, &thread_control, &send);
+        send(match outcome {
+            Ok(Some(summary)) => AgentEvent::Finished(summary),
+            Ok(None) => AgentEvent::Aborted,
+            Err(e) => AgentEvent::Failed(e),
+        });
+    });
+    (control, rx)
+}
+
+/// The run itself; `Ok(None)` when aborted
+fn run(request: &AgentRequest, control: &AgentControl, send: &dyn Fn(AgentEvent)) -> Result<Option<String>, String> {
+    let tools = chat_tools::definitions(&request.tools);
+    log::info!("Agent run with {} and {} tool functions: {}", request.model, tools.len(), request.goal);
+
+    let mut messages = vec![json!({ "role": "system", "content": AGENT_SYSTEM_PROMPT })];
+    messages.extend(request.history.iter().map(|(role, text)| json!({ "role": role, "content": text })));
+    messages.push(json!({ "role": "user", "content": request.goal }));
+
+    let mut sources = 0;
+    for number in 1..=request.max_steps.max(1) {
+        if !control.proceed() { return Ok(None); }
+        let reply = complete(request, &messages, &tools)?;
+        let text = reply.get("content").and_then(Value::as_str).unwrap_or_default().trim().to_string();
+        let calls = tool_calls(&reply);
+        if calls.is_empty() {
+            return Ok(Some(text));
+        }
+        if !text.is_empty() {
+            send(AgentEvent::Step(AgentStep { number, kind: StepKind::Thought, title: "Thinking".to_string(), detail: text }));
+        }
+        messages.push(reply);
+
+        for call in calls {
+            if !control.proceed() { return Ok(None); }
+            let name = if call.name.is_empty() { "Unnamed tool" } else { call.name.as_str() };
+            let args = serde_json::to_string_pretty(&call.args).unwrap_or_default();
+            send(AgentEvent::Step(AgentStep { number, kind: StepKind::ToolCall, title: name.to_string(), detail: args }));
+            let result = chat_tools::call(request.chat_id, &call.name, &call.args);
+            let title = format!("{} {}", name, if result.is_ok() { "returned" } else { "failed" });
+            let mut output = chat_tools::result_text(result);
+            if let Some(location) = citations::location_of(&call.name, &call.args).filter(|_| !output.starts_with("Error: ")) {
+                sources += 1;
+                send(AgentEvent::Source(Source::new(sources, &location, &output)));
+                output = format!("Source [{}]: {}\n\n{}", sources, location, output);
+            }
+            send(AgentEvent::Step(AgentStep { number, kind: StepKind::ToolResult, title, detail: output.clone() }));
+            messages.push(json!({ "role": "tool", "tool_call_id": call.id, "content": output }));
+        }
+    }
+
+    if !control.proceed() { return Ok(None); }
+    messages.push(json!({ "role": "user", "content": STEP_LIMIT_PROMPT }));
+    let reply = complete(request, &messages, &[])?;
+    Ok(Some(reply.get("content").and_then(Value::as_str).unwrap_or_default().trim().to_string()))
+}
+
+/// One chat completion; returns the reply message
+fn complete(request: &AgentRequest, messages: &[Value], tools: &[Value]) -> Result<Value, String> {
+    chat_tools::complete(&request.base_url, request.api_key.as_deref(), &request.model, messages, tools)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tool_calls() {
+        let message = json!({
+            "role": "assistant",
+            "content": null,
+            "tool_calls": [
+                { "id": "a", "type": "function", "function": { "name": "fs_list_dir", "arguments": "{\"path\": \"notes\"}" } },
+                { "id": "b", "type": "function", "function": { "name": "run_code", "arguments": "not json" } },
+                { "id": "c", "type": "function", "function": {} }
+            ]
+        });
+        let calls = tool_calls(&message);
+        assert_eq!(calls.len(), 3);
+        assert_eq!(calls[0].args["path"], "notes");
+        assert_eq!(calls[1].args, json!({}));
+        assert_eq!((calls[2].id.as_str(), calls[2].name.as_str()), ("c", ""));
+        assert!(chat_tools::call(None, &calls[2].name, &calls[2].args).is_err());
+        assert!(tool_calls(&json!({ "role": "assistant", "content": "done" })).is_empty());
+    }
+
+    #[test]
+    fn test_step_messages() {
+        let call = AgentStep { number: 2, kind: StepKind::ToolCall, title: "fs_read_file".into(), detail: "{}".into() };
+        assert_eq!(call.message_text(), "**Step 2 · Calling fs_read_file**\n\n```json\n{}\n```");
+
+        let result = AgentStep { number: 2, kind: StepKind::ToolResult, title: "fs_read_file returned".into(), detail: "x".repeat(5_000) };
+        let text = result.message_text();
+        assert!(text.starts_with("**Step 2 · fs_read_file returned**"));
+        assert!(text.ends_with("…\n```") && text.chars().count() < 2_100, "{}", text.len());
+    }
+}
@@ -0,0 +1,209 @@
+//! Earlier versions of regenerated answers
+//!
+//! Regenerating an answer replaces it in the chat. The answer it replaced is
+//! kept on the chat ([`crate::ChatData::answer_versions`]) so the two can be
+//! compared and either one kept. [`RegenerationWatch`] spots replacements by
+//! comparing the chat's messages before and after each update, and
+//! [`diff_words`] compares two versions word by word.
+
+use moly_kit::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Earlier versions kept per answer
+pub const MAX_VERSIONS: usize = 10;
+
+/// Above this many word pairs, versions are shown as one removal and one
+/// addition instead of being compared word by word
+const MAX_DIFF_CELLS: usize = 4_000_000;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AnswerVersions {
+    /// Position of the answer in the chat
+    pub message_index: usize,
+    /// Answers it replaced, oldest first
+    pub previous: Vec<String>,
+}
+
+/// Spots answers replaced by a regeneration
+#[derive(Clone, Debug, Default)]
+pub struct RegenerationWatch {
+    /// An answer removed from the end of the chat, which the next answer at
+    /// the same position replaces
+    removed: Option<(usize, String)>,
+}
+
+impl RegenerationWatch {
+    /// The finished answer that `new` replaced compared to `old`, as
+    /// (message index, text)
+    pub fn replaced(&mut self, old: &[Message], new: &[Message]) -> Option<(usize, String)> {
+        let first_change = old.iter().zip(new).position(|(a, b)| !same(a, b))
+            .or_else(|| (old.len() != new.len()).then(|| old.len().min(new.len())));
+
+        if let Some(index) = first_change {
+            if let Some(before) = old.get(index).filter(|m| is_finished_answer(m)) {
+                let text = before.content.text.clone();
+                match new.get(index) {
+                    Some(after) if is_bot(after) => {
+                        self.removed = None;
+                        return Some((index, text));
+                    }
+                    Some(_) => self.removed = None,
+                    // Regenerating may drop the answer before the new one starts
+                    None => self.removed = Some((index, text)),
+                }
+                return None;
+            }
+        }
+
+        let index = self.removed.as_ref()?.0;
+        match new.get(index) {
+            Some(after) if is_bot(after) => self.removed.take(),
+            Some(_) => {
+                self.removed = None;
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Forget a pending removal, e.g. when switching chats
+    pub fn reset(&mut self) {
+        self.removed = None;
+    }
+}
+
+fn is_bot(message: &Message) -> bool {
+    matches!(message.from, EntityId::Bot(_))
+}
+
+fn is_finished_answer(message: &Message) -> bool {
+    is_bot(message) && !message.metadata.is_writing && !message.content.text.trim().is_empty()
+}
+
+fn same(a: &Message, b: &Message) -> bool {
+    is_bot(a) == is_bot(b) && a.content.text == b.content.text
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Change {
+    Same,
+    Removed,
+    Added,
+}
+
+/// Word-level changes from `old` to `new`; whitespace stays with the word
+/// before it
+pub fn diff_words(old: &str, new: &str) -> Vec<(Change, String)> {
+    let a: Vec<&str> = old.split_inclusive(char::is_whitespace).collect();
+    let b: Vec<&str> = new.split_inclusive(char::is_whitespace).collect();
+
+    let prefix = a.iter().zip(&b).take_while(|(x, y)| x == y).count();
+    let suffix = a[prefix..].iter().rev().zip(b[prefix..].iter().rev()).take_while(|(x, y)| x == y).count();
+    let (a_mid, b_mid) = (&a[prefix..a.len() - suffix], &b[prefix..b.len() - suffix]);
+
+    let mut changes: Vec<(Change, &str)> = a[..prefix].iter().map(|w| (Change::Same, *w)).collect();
+    if a_mid.len() * b_mid.len() > MAX_DIFF_CELLS {
+        changes.extend(a_mid.iter().map(|w| (Change::Removed, *w)));
+        changes.extend(b_mid.iter().map(|w| (Change::Added, *w)));
+    } else {
+        // Longest common subsequence, filled from the end
+        let width = b_mid.len() + 1;
+        let mut lcs = vec![0u32; (a_mid.len() + 1) * width];
+        for i in (0..a_mid.len()).rev() {
+            for j in (0..b_mid.len()).rev() {
+                lcs[i * width + j] = if a_mid[i] == b_mid[j] {
+                    lcs[(i + 1) * width + j + 1] + 1
+                } else {
+                    lcs[(i + 1) * width + j].max(lcs[i * width + j + 1])
+                };
+            }
+        }
+        let (mut i, mut j) = (0, 0);
+        while i < a_mid.len() || j < b_mid.len() {
+            if i < a_mid.len() && j < b_mid.len() && a_mid[i] == b_mid[j] {
+                changes.push((Change::Same, a_mid[i]));
+                i += 1;
+                j += 1;
+            } else if i < a_mid.len() && (j == b_mid.len() || lcs[(i + 1) * width + j] >= lcs[i * width + j + 1]) {
+                changes.push((Change::Removed, a_mid[i]));
+                i += 1;
+            } else {
+                changes.push((Change::Added, b_mid[j]));
+                j += 1;
+            }
+        }
+    }
+    changes.extend(a[a.len() - suffix..].iter().map(|w| (Change::Same, *w)));
+
+    let mut merged: Vec<(Change, String)> = Vec::new();
+    for (change, word) in changes {
+        match merged.last_mut() {
+            Some((last, text)) if *last == change => text.push_str(word),
+            _ => merged.push((change, word.to_string())),
+        }
+    }
+    merged
+}
+
+/// Changes as one text, marked like `git diff --word-diff`:
+/// `[-removed-]` and `{+added+}`
+pub fn marked(changes: &[(Change, String)]) -> String {
+    changes.iter().map(|(change, text)| match change {
+        Change::Same => text.clone(),
+        Change::Removed => format!("[-{}-]", text),
+        Change::Added => format!("{{+{}+}}", text),
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(from: EntityId, text: &str, is_writing: bool) -> Message {
+        let mut message = Message {
+            from,
+            content: MessageContent { text: text.to_string(), ..Default::default() },
+            ..Default::default()
+        };
+        message.metadata.is_writing = is_writing;
+        message
+    }
+
+    #[test]
+    fn test_watch_spots_regenerated_answer() {
+        let bot = EntityId::Bot(BotId::new("model"));
+        let question = message(EntityId::User, "Why?", false);
+        let answer = message(bot.clone(), "Because.", false);
+        let mut watch = RegenerationWatch::default();
+
+        // Replaced in place by a new answer
+        let old = vec![question.clone(), answer.clone()];
+        let new = vec![question.clone(), message(bot.clone(), "", true)];
+        assert_eq!(watch.replaced(&old, &new), Some((1, "Because.".to_string())));
+        // Streaming the new answer is not another replacement
+        let streamed = vec![question.clone(), message(bot.clone(), "Well", true)];
+        assert_eq!(watch.replaced(&new, &streamed), None);
+
+        // Dropped first, then a new answer at the same position
+        let dropped = vec![question.clone()];
+        assert_eq!(watch.replaced(&old, &dropped), None);
+        assert_eq!(watch.replaced(&dropped, &new), Some((1, "Because.".to_string())));
+
+        // A follow-up question is not a replacement
+        let follow_up = vec![question, answer, message(EntityId::User, "And?", false)];
+        assert_eq!(watch.replaced(&old, &follow_up), None);
+    }
+
+    #[test]
+    fn test_diff_words() {
+        let changes = diff_words("The sky is blue today.", "The sky is grey today.");
+        assert_eq!(changes, vec![
+            (Change::Same, "The sky is ".to_string()),
+            (Change::Removed, "blue ".to_string()),
+            (Change::Added, "grey ".to_string()),
+            (Change::Same, "today.".to_string()),
+        ]);
+        assert_eq!(marked(&changes), "The sky is [-blue -]{+grey +}today.");
+        assert!(diff_words("same", "same").iter().all(|(c, _)| *c == Change::Same));
+    }
+}
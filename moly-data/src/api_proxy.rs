@@ -0,0 +1,549 @@
+//! OpenAI-compatible endpoint for other devices on the network
+//!
+//! The model runtime (ominix-api) only listens on localhost. With remote
+//! access on, [`start`] listens on every interface and forwards `/v1/...`
+//! requests to the runtime, so phones or other computers can use whatever
+//! model is loaded in the app. Each request must carry
+//! `Authorization: Bearer <token>` with the token of one of the clients set
+//! up in Settings; tokens are created and revoked per client so one device
+//! can be cut off without touching the others. Only a hash of each token is
+//! kept, so the token is shown once, when the client is added.
+//!
+//! Only inference is forwarded: chat completions, embeddings, speech and
+//! transcription. The runtime also reads files named in a request from this
+//! computer's disk, so bodies must carry their data inline; a request naming
+//! a file path is refused.
+//!
+//! Responses are passed through as they arrive, so streamed chat completions
+//! work unchanged.
+
+use chrono::{DateTime, Utc};
+use ring::digest;
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::net::{IpAddr, TcpListener, TcpStream, UdpSocket};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use crate::model_runtime_client::runtime_url;
+
+/// Port the endpoint listens on unless changed in preferences
+pub const DEFAULT_PORT: u16 = 8099;
+
+const TOKEN_PREFIX: &str = "omx-";
+const MAX_HEAD_BYTES: usize = 16 * 1024;
+/// Large enough for image edits and audio uploads
+const MAX_BODY_BYTES: usize = 64 * 1024 * 1024;
+/// Endpoints forwarded to the runtime; everything else stays local
+const ALLOWED_PATHS: &[&str] = &["/v1/chat/completions", "/v1/embeddings", "/v1/audio/speech", "/v1/audio/transcriptions"];
+
+/// A device allowed to use the endpoint
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(from = "StoredApiClient")]
+pub struct ApiClient {
+    pub name: String,
+    /// SHA-256 of the token, hex encoded
+    pub token_hash: String,
+    /// Start of the token, for telling clients apart, e.g. `omx-3f9a…`
+    pub token_hint: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A client as saved, including ones from before tokens were hashed
+#[derive(Deserialize)]
+struct StoredApiClient {
+    name: String,
+    #[serde(default)]
+    token_hash: String,
+    #[serde(default)]
+    token_hint: String,
+    /// Plaintext token kept by earlier versions; hashed on load
+    #[serde(default)]
+    token: Option<String>,
+    created_at: DateTime<Utc>,
+}
+
+impl From<StoredApiClient> for ApiClient {
+    fn from(stored: StoredApiClient) -> Self {
+        match stored.token {
+            Some(token) if stored.token_hash.is_empty() => Self {
+                name: stored.name,
+                token_hash: hash_token(&token),
+                token_hint: masked_token(&token),
+                created_at: stored.created_at,
+            },
+            _ => Self {
+                name: stored.name,
+                token_hash: stored.token_hash,
+                token_hint: stored.token_hint,
+                created_at: stored.created_at,
+            },
+        }
+    }
+}
+
+/// Remote access settings, stored in preferences
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ApiProxySettings {
+    pub enabled: bool,
+    pub port: u16,
+    pub clients: Vec<ApiClient>,
+}
+
+impl Default for ApiProxySettings {
+    fn default() -> Self {
+        Self { enabled: false, port: DEFAULT_PORT, clients: Vec::new() }
+    }
+}
+
+impl ApiProxySettings {
+    /// Create a client with a fresh token, returned here and nowhere else
+    pub fn add_client(&mut self, name: &str) -> Result<String, String> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err("Enter a name for the device".to_string());
+        }
+        if self.clients.iter().any(|c| c.name.eq_ignore_ascii_case(name)) {
+            return Err(format!("There is already a client named {}", name));
+        }
+        let token = generate_token()?;
+        self.clients.push(ApiClient {
+            name: name.to_string(),
+            token_hash: hash_token(&token),
+            token_hint: masked_token(&token),
+            created_at: Utc::now(),
+        });
+        Ok(token)
+    }
+
+    /// Remove a client, invalidating its token. Returns whether it existed.
+    pub fn revoke_client(&mut self, name: &str) -> bool {
+        let before = self.clients.len();
+        self.clients.retain(|c| !c.name.eq_ignore_ascii_case(name.trim()));
+        self.clients.len() != before
+    }
+}
+
+/// A random bearer token
+pub fn generate_token() -> Result<String, String> {
+    let mut bytes = [0u8; 24];
+    SystemRandom::new().fill(&mut bytes).map_err(|_| "Failed to generate token".to_string())?;
+    let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+    Ok(format!("{}{}", TOKEN_PREFIX, hex))
+}
+
+/// Token shortened for display, e.g. `omx-3f9a…`
+pub fn masked_token(token: &str) -> String {
+    let shown: String = token.chars().take(TOKEN_PREFIX.len() + 4).collect();
+    format!("{}…", shown)
+}
+
+fn hash_token(token: &str) -> String {
+    digest::digest(&digest::SHA256, token.as_bytes()).as_ref().iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+// ─── Server ──────────────────────────────────────────────────────────────────
+
+struct ProxyServer {
+    port: u16,
+    stop: Arc<AtomicBool>,
+    accept_thread: JoinHandle<()>,
+}
+
+fn server() -> &'static Mutex<Option<ProxyServer>> {
+    static SERVER: OnceLock<Mutex<Option<ProxyServer>>> = OnceLock::new();
+    SERVER.get_or_init(Default::default)
+}
+
+/// Clients allowed in, mirrored from preferences
+fn clients() -> &'static RwLock<Vec<ApiClient>> {
+    static CLIENTS: OnceLock<RwLock<Vec<ApiClient>>> = OnceLock::new();
+    CLIENTS.get_or_init(Default::default)
+}
+
+/// When each client last made a request, this session
+fn last_used_times() -> &'static Mutex<HashMap<String, DateTime<Utc>>> {
+    static LAST_USED: OnceLock<Mutex<HashMap<String, DateTime<Utc>>>> = OnceLock::new();
+    LAST_USED.get_or_init(Default::default)
+}
+
+/// Start or stop the endpoint to match `settings`, returning the port it
+/// is listening on
+pub fn apply(settings: &ApiProxySettings) -> Result<Option<u16>, String> {
+    set_clients(&settings.clients);
+    if !settings.enabled {
+        stop();
+        return Ok(None);
+    }
+    if running_port().is_some_and(|port| port != settings.port) {
+        stop();
+    }
+    start(settings.port).map(Some)
+}
+
+/// Listen on `port`. Does nothing if already running.
+pub fn start(port: u16) -> Result<u16, String> {
+    let mut slot = server().lock().map_err(|e| e.to_string())?;
+    if let Some(running) = slot.as_ref() {
+        return Ok(running.port);
+    }
+
+    let listener = TcpListener::bind(("0.0.0.0", port)).map_err(|e| format!("Can't listen on port {}: {}", port, e))?;
+    listener.set_nonblocking(true).map_err(|e| e.to_string())?;
+
+    let stop = Arc::new(AtomicBool::new(false));
+    let stop_flag = stop.clone();
+    let accept_thread = std::thread::spawn(move || accept_loop(listener, stop_flag));
+    log::info!("Serving the OpenAI-compatible endpoint on port {}", port);
+    *slot = Some(ProxyServer { port, stop, accept_thread });
+    Ok(port)
+}
+
+/// Stop listening. Requests already being answered run to completion.
+pub fn stop() {
+    let Ok(mut slot) = server().lock() else { return };
+    let Some(running) = slot.take() else { return };
+    running.stop.store(true, Ordering::SeqCst);
+    // Free the port before returning, so it can be bound again right away
+    let _ = running.accept_thread.join();
+    log::info!("Stopped the OpenAI-compatible endpoint");
+}
+
+/// Port being served on, if running
+pub fn running_port() -> Option<u16> {
+    server().lock().ok().and_then(|s| s.as_ref().map(|s| s.port))
+}
+
+/// Replace the clients allowed in; revoked tokens stop working immediately
+pub fn set_clients(list: &[ApiClient]) {
+    if let Ok(mut c) = clients().write() {
+        *c = list.to_vec();
+    }
+}
+
+/// This machine's address on the local network, for showing clients where
+/// to connect. Connecting a UDP socket sends nothing; it only picks the
+/// interface that routes outward.
+pub fn lan_address() -> Option<IpAddr> {
+    let socket = UdpSocket::bind(("0.0.0.0", 0)).ok()?;
+    socket.connect(("8.8.8.8", 80)).ok()?;
+    socket.local_addr().ok().map(|a| a.ip()).filter(|ip| !ip.is_loopback())
+}
+
+/// When a client last made a request, if it has since the app started
+pub fn last_used(name: &str) -> Option<DateTime<Utc>> {
+    last_used_times().lock().ok().and_then(|t| t.get(name).copied())
+}
+
+fn accept_loop(listener: TcpListener, stop: Arc<AtomicBool>) {
+    while !stop.load(Ordering::SeqCst) {
+        match listener.accept() {
+            Ok((stream, peer)) => {
+                log::debug!("API request from {}", peer);
+                std::thread::spawn(move || handle_connection(stream));
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                std::thread::sleep(Duration::from_millis(100));
+            }
+            Err(e) => {
+                log::warn!("API endpoint accept failed: {}", e);
+                std::thread::sleep(Duration::from_secs(1));
+            }
+        }
+    }
+}
+
+/// A parsed request head, plus any body bytes read along with it
+struct Request {
+    method: String,
+    path: String,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+impl Request {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers.iter().find(|(k, _)| k.eq_ignore_ascii_case(name)).map(|(_, v)| v.as_str())
+    }
+}
+
+fn handle_connection(mut stream: TcpStream) {
+    let _ = stream.set_nonblocking(false);
+    let _ = stream.set_read_timeout(Some(Duration::from_secs(30)));
+    if let Err((status, message)) = serve(&mut stream) {
+        let _ = respond_error(&mut stream, status, &message);
+    }
+}
+
+fn serve(stream: &mut TcpStream) -> Result<(), (u16, String)> {
+    let mut request = read_request(stream).ok_or_else(|| (400, "Malformed request".to_string()))?;
+    check_endpoint(&request.method, &request.path)?;
+
+    let client = {
+        let list = clients().read().map_err(|e| (500, e.to_string()))?;
+        request.header("authorization")
+            .and_then(|h| find_client(&list, h))
+            .map(|c| c.name.clone())
+            .ok_or_else(|| (401, "Missing or invalid API token".to_string()))?
+    };
+    if let Ok(mut times) = last_used_times().lock() {
+        times.insert(client.clone(), Utc::now());
+    }
+    log::info!("{} {} {}", client, request.method, request.path);
+
+    if request.header("transfer-encoding").is_some() {
+        return Err((411, "Chunked request bodies are not supported".to_string()));
+    }
+    let len: usize = request.header("content-length").and_then(|v| v.trim().parse().ok()).unwrap_or(0);
+    if len > MAX_BODY_BYTES {
+        return Err((413, "Request body too large".to_string()));
+    }
+    while request.body.len() < len {
+        let mut buf = vec![0u8; (len - request.body.len()).min(64 * 1024)];
+        let n = stream.read(&mut buf).map_err(|e| (400, e.to_string()))?;
+        if n == 0 { return Err((400, "Request body ended early".to_string())); }
+        request.body.extend_from_slice(&buf[..n]);
+    }
+    request.body.truncate(len);
+    check_body(request.header("content-type").unwrap_or_default(), &request.body)?;
+
+    let (addr, prefix) = runtime_target(&runtime_url());
+    let mut runtime = TcpStream::connect(addr.as_str())
+        .map_err(|_| (502, "No model runtime is running in OminiX Studio".to_string()))?;
+    write_upstream(&mut runtime, &addr, &prefix, &request).map_err(|e| (502, e.to_string()))?;
+
+    // Too late for an error response once the runtime has started answering
+    if let Err(e) = std::io::copy(&mut runtime, stream) {
+        log::warn!("Response to {} for {} stopped: {}", client, request.path, e);
+    }
+    Ok(())
+}
+
+fn read_request(stream: &mut TcpStream) -> Option<Request> {
+    let mut data = Vec::new();
+    let mut buf = [0u8; 4096];
+    let head_end = loop {
+        if let Some(pos) = data.windows(4).position(|w| w == b"\r\n\r\n") {
+            break pos;
+        }
+        if data.len() > MAX_HEAD_BYTES { return None; }
+        let n = stream.read(&mut buf).ok()?;
+        if n == 0 { return None; }
+        data.extend_from_slice(&buf[..n]);
+    };
+
+    let head = String::from_utf8_lossy(&data[..head_end]).to_string();
+    let mut lines = head.split("\r\n");
+    let mut parts = lines.next()?.split_whitespace();
+    let method = parts.next()?.to_string();
+    let path = parts.next()?.to_string();
+    let headers = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(k, v)| (k.trim().to_string(), v.trim().to_string()))
+        .collect();
+    Some(Request { method, path, headers, body: data[head_end + 4..].to_vec() })
+}
+
+/// Where the runtime at `url` listens: `host:port` for the connection and
+/// the `Host` header, and the path its endpoints sit under
+fn runtime_target(url: &str) -> (String, String) {
+    let url = url.trim();
+    let (scheme, rest) = url.split_once("://").unwrap_or(("http", url));
+    let (authority, path) = rest.split_at(rest.find('/').unwrap_or(rest.len()));
+    let authority = authority.rsplit_once('@').map_or(authority, |(_, host)| host);
+    // After the closing bracket of an IPv6 address, if there is one
+    let has_port = authority[authority.rfind(']').unwrap_or(0)..].contains(':');
+    let addr = match (has_port, scheme) {
+        (true, _) => authority.to_string(),
+        (false, "https") => format!("{}:443", authority),
+        (false, _) => format!("{}:80", authority),
+    };
+    (addr, path.trim_end_matches('/').to_string())
+}
+
+/// Forward the request to the runtime at `addr`, under `prefix`, dropping
+/// the client's token and hop-by-hop headers
+fn write_upstream(runtime: &mut TcpStream, addr: &str, prefix: &str, request: &Request) -> std::io::Result<()> {
+    let mut head = format!("{} {}{} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n", request.method, prefix, request.path, addr);
+    for (name, value) in &request.headers {
+        let skip = ["host", "connection", "keep-alive", "authorization", "proxy-authorization", "upgrade"]
+            .iter()
+            .any(|h| name.eq_ignore_ascii_case(h));
+        if !skip {
+            head.push_str(&format!("{}: {}\r\n", name, value));
+        }
+    }
+    head.push_str("\r\n");
+    runtime.write_all(head.as_bytes())?;
+    runtime.write_all(&request.body)
+}
+
+/// Error in the OpenAI error format, so client libraries show the message
+fn respond_error(stream: &mut TcpStream, status: u16, message: &str) -> std::io::Result<()> {
+    let reason = match status {
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        411 => "Length Required",
+        413 => "Payload Too Large",
+        415 => "Unsupported Media Type",
+        502 => "Bad Gateway",
+        _ => "Internal Server Error",
+    };
+    let body = serde_json::json!({
+        "error": { "message": message, "type": if status == 401 { "authentication_error" } else { "invalid_request_error" } }
+    }).to_string();
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status, reason, body.len(), body
+    )
+}
+
+/// Refuse anything but a POST to one of [`ALLOWED_PATHS`]
+fn check_endpoint(method: &str, path: &str) -> Result<(), (u16, String)> {
+    let path = path.split('?').next().unwrap_or_default();
+    if method == "POST" && ALLOWED_PATHS.contains(&path) {
+        return Ok(());
+    }
+    Err((404, format!("{} {} is not available over remote access", method, path)))
+}
+
+/// Refuse bodies that would have the runtime read a file from this computer:
+/// in JSON, file and `*_path` fields and image URLs must be `data:` URLs; in
+/// uploads, file fields must be actual file parts
+fn check_body(content_type: &str, body: &[u8]) -> Result<(), (u16, String)> {
+    if body.is_empty() {
+        return Ok(());
+    }
+    let media_type = content_type.split(';').next().unwrap_or_default().trim().to_ascii_lowercase();
+    let inline = match media_type.as_str() {
+        "application/json" => {
+            let value: serde_json::Value = serde_json::from_slice(body).map_err(|e| (400, format!("Invalid JSON: {}", e)))?;
+            json_is_inline(&value)
+        }
+        "multipart/form-data" => multipart_is_inline(body),
+        _ => return Err((415, format!("Unsupported content type {:?}", content_type))),
+    };
+    if inline {
+        Ok(())
+    } else {
+        Err((400, "Files must be sent inline, as data: URLs or file uploads, not as paths".to_string()))
+    }
+}
+
+fn is_file_field(key: &str) -> bool {
+    key == "file" || key == "url" || key.ends_with("_path")
+}
+
+fn json_is_inline(value: &serde_json::Value) -> bool {
+    match value {
+        serde_json::Value::Object(map) => map.iter().all(|(key, value)| match value {
+            serde_json::Value::String(s) if is_file_field(key) => s.starts_with("data:"),
+            _ => json_is_inline(value),
+        }),
+        serde_json::Value::Array(items) => items.iter().all(json_is_inline),
+        _ => true,
+    }
+}
+
+/// Every form field named like a file carries a `filename`, so its content
+/// is the upload rather than a path
+fn multipart_is_inline(body: &[u8]) -> bool {
+    String::from_utf8_lossy(body)
+        .split("\r\n")
+        .filter(|line| line.to_ascii_lowercase().starts_with("content-disposition:"))
+        .all(|line| {
+            let name = line.split(';').find_map(|p| p.trim().strip_prefix("name=")).map(|n| n.trim_matches('"'));
+            !name.is_some_and(is_file_field) || line.contains("filename=")
+        })
+}
+
+/// The client whose token an `Authorization` header carries
+fn find_client<'a>(clients: &'a [ApiClient], header: &str) -> Option<&'a ApiClient> {
+    let token = header.trim().strip_prefix("Bearer ")?.trim();
+    let hash = hash_token(token);
+    clients.iter().find(|c| tokens_match(&c.token_hash, &hash))
+}
+
+/// Compare without exiting at the first differing byte
+fn tokens_match(a: &str, b: &str) -> bool {
+    a.len() == b.len() && a.bytes().zip(b.bytes()).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clients() {
+        let mut settings = ApiProxySettings::default();
+        let token = settings.add_client(" Phone ").unwrap();
+        assert!(token.starts_with(TOKEN_PREFIX) && token.len() == TOKEN_PREFIX.len() + 48);
+        assert!(settings.add_client("phone").is_err());
+        assert!(settings.add_client("  ").is_err());
+        settings.add_client("Laptop").unwrap();
+
+        assert_eq!(find_client(&settings.clients, &format!("Bearer {}", token)).map(|c| c.name.as_str()), Some("Phone"));
+        assert!(find_client(&settings.clients, "Bearer omx-wrong").is_none());
+        assert!(find_client(&settings.clients, &token).is_none());
+
+        assert!(settings.revoke_client("Phone"));
+        assert!(!settings.revoke_client("Phone"));
+        assert!(find_client(&settings.clients, &format!("Bearer {}", token)).is_none());
+    }
+
+    #[test]
+    fn test_tokens_are_stored_hashed() {
+        let mut settings = ApiProxySettings::default();
+        let token = settings.add_client("Phone").unwrap();
+        let saved = serde_json::to_string(&settings).unwrap();
+        assert!(!saved.contains(&token) && saved.contains(&hash_token(&token)));
+        assert_eq!(serde_json::from_str::<ApiProxySettings>(&saved).unwrap(), settings);
+
+        // Clients saved with a plaintext token keep working after the upgrade
+        let old = r#"{"clients":[{"name":"Tablet","token":"omx-abcdef","created_at":"2026-01-01T00:00:00Z"}]}"#;
+        let upgraded: ApiProxySettings = serde_json::from_str(old).unwrap();
+        assert_eq!(upgraded.clients[0].token_hint, "omx-abcd…");
+        assert!(find_client(&upgraded.clients, "Bearer omx-abcdef").is_some());
+        assert!(!serde_json::to_string(&upgraded).unwrap().contains("omx-abcdef"));
+    }
+
+    #[test]
+    fn test_runtime_target() {
+        assert_eq!(runtime_target("http://localhost:8080"), ("localhost:8080".to_string(), String::new()));
+        assert_eq!(runtime_target("http://127.0.0.1:9311/"), ("127.0.0.1:9311".to_string(), String::new()));
+        assert_eq!(runtime_target("http://mock.local/runtime/"), ("mock.local:80".to_string(), "/runtime".to_string()));
+        assert_eq!(runtime_target("http://[::1]:8080"), ("[::1]:8080".to_string(), String::new()));
+        assert_eq!(runtime_target("http://[::1]"), ("[::1]:80".to_string(), String::new()));
+    }
+
+    #[test]
+    fn test_only_inline_inference_is_forwarded() {
+        assert!(check_endpoint("POST", "/v1/chat/completions").is_ok());
+        assert!(check_endpoint("POST", "/v1/audio/transcriptions?x=1").is_ok());
+        assert_eq!(check_endpoint("GET", "/v1/chat/completions").unwrap_err().0, 404);
+        assert_eq!(check_endpoint("POST", "/v1/voices/train").unwrap_err().0, 404);
+        assert_eq!(check_endpoint("POST", "/v1/models/load").unwrap_err().0, 404);
+
+        let json = "application/json";
+        assert!(check_body(json, br#"{"model":"m","messages":[{"role":"user","content":"hi"}]}"#).is_ok());
+        let image = br#"{"messages":[{"content":[{"type":"image_url","image_url":{"url":"data:image/png;base64,AAAA"}}]}]}"#;
+        assert!(check_body(json, image).is_ok());
+        assert_eq!(check_body(json, br#"{"model":"m","file":"/etc/passwd"}"#).unwrap_err().0, 400);
+        let remote = br#"{"messages":[{"content":[{"type":"image_url","image_url":{"url":"/Users/me/a.png"}}]}]}"#;
+        assert_eq!(check_body(json, remote).unwrap_err().0, 400);
+        assert_eq!(check_body("text/plain", b"/etc/passwd").unwrap_err().0, 415);
+
+        let form = "multipart/form-data; boundary=b";
+        let upload = b"--b\r\nContent-Disposition: form-data; name=\"file\"; filename=\"a.wav\"\r\n\r\nRIFF\r\n--b--\r\n";
+        assert!(check_body(form, upload).is_ok());
+        let path = b"--b\r\nContent-Disposition: form-data; name=\"file\"\r\n\r\n/etc/passwd\r\n--b--\r\n";
+        assert_eq!(check_body(form, path).unwrap_err().0, 400);
+    }
+}
@@ -0,0 +1,164 @@
+//! Session-scoped files for generated audio, images and video
+//!
+//! Generated speech, images, videos and converted recordings go in a folder
+//! for this run of the app under the user's cache directory, instead of
+//! fixed names in `/tmp` that two windows could overwrite. [`unique_path`]
+//! hands out a new file name each time, [`session_file`] a fixed name within
+//! the session for outputs that replace the previous one. Both are tracked
+//! until [`release`]d.
+//!
+//! [`cleanup_session`] removes the folder on exit unless the preferences keep
+//! generated files for a while; [`sweep_old`] then removes earlier sessions'
+//! folders once they are older than that, including those of runs that
+//! crashed.
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, SystemTime};
+
+const ARTIFACTS_DIR: &str = "artifacts";
+const SESSION_PREFIX: &str = "session-";
+
+/// Folders changed this recently are left alone by [`sweep_old`]; they may
+/// belong to another window that is still running
+const MIN_SWEEP_AGE: Duration = Duration::from_secs(60 * 60);
+
+/// Hours generated files are kept after quitting; 0 removes them on exit
+static RETENTION_HOURS: AtomicU32 = AtomicU32::new(0);
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+static TRACKED: Mutex<Vec<PathBuf>> = Mutex::new(Vec::new());
+static SESSION_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// Mirror [`crate::Preferences::generated_files_hours`]
+pub fn set_retention_hours(hours: u32) {
+    RETENTION_HOURS.store(hours, Ordering::SeqCst);
+}
+
+/// Where every session's folder lives
+pub fn artifacts_root() -> PathBuf {
+    dirs::cache_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join("ominix-studio")
+        .join(ARTIFACTS_DIR)
+}
+
+/// This session's folder, created on first use
+pub fn session_dir() -> PathBuf {
+    let dir = SESSION_DIR.get_or_init(|| {
+        let started = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap_or_default().as_secs();
+        artifacts_root().join(format!("{}{}-{}", SESSION_PREFIX, started, std::process::id()))
+    });
+    if let Err(e) = std::fs::create_dir_all(dir) {
+        log::warn!("Failed to create {}: {}", dir.display(), e);
+    }
+    dir.clone()
+}
+
+/// A new file in this session's folder, e.g. `unique_path("chat-image", "png")`
+/// gives `…/chat-image-3.png`
+pub fn unique_path(prefix: &str, extension: &str) -> PathBuf {
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    track(session_dir().join(format!("{}-{}.{}", sanitize(prefix), id, extension)))
+}
+
+/// A file with a fixed name in this session's folder, for outputs that
+/// replace the previous one, e.g. the last synthesized sample
+pub fn session_file(name: &str) -> PathBuf {
+    track(session_dir().join(sanitize(name)))
+}
+
+/// Paths handed out this session and not released
+pub fn tracked() -> Vec<PathBuf> {
+    TRACKED.lock().unwrap().clone()
+}
+
+/// Delete a file now, e.g. a conversion that is no longer needed
+pub fn release(path: &Path) {
+    TRACKED.lock().unwrap().retain(|p| p != path);
+    if path.exists() {
+        if let Err(e) = std::fs::remove_file(path) {
+            log::warn!("Failed to remove {}: {}", path.display(), e);
+        }
+    }
+}
+
+/// Remove this session's files on exit, unless they are kept for a while
+pub fn cleanup_session() {
+    if RETENTION_HOURS.load(Ordering::SeqCst) > 0 {
+        return;
+    }
+    TRACKED.lock().unwrap().clear();
+    let Some(dir) = SESSION_DIR.get() else { return };
+    if let Err(e) = std::fs::remove_dir_all(dir) {
+        log::warn!("Failed to remove {}: {}", dir.display(), e);
+    }
+}
+
+/// Remove earlier sessions' folders older than the retention. Returns how
+/// many were removed.
+pub fn sweep_old() -> usize {
+    let retention = Duration::from_secs(RETENTION_HOURS.load(Ordering::SeqCst) as u64 * 60 * 60);
+    sweep_dir(&artifacts_root(), retention.max(MIN_SWEEP_AGE), SystemTime::now())
+}
+
+fn sweep_dir(root: &Path, max_age: Duration, now: SystemTime) -> usize {
+    let Ok(entries) = std::fs::read_dir(root) else { return 0 };
+    let mut removed = 0;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let is_session = path.file_name().and_then(|n| n.to_str()).is_some_and(|n| n.starts_with(SESSION_PREFIX));
+        if !is_session || SESSION_DIR.get() == Some(&path) {
+            continue;
+        }
+        let modified = entry.metadata().and_then(|m| m.modified()).unwrap_or(now);
+        if now.duration_since(modified).unwrap_or_default() < max_age {
+            continue;
+        }
+        match std::fs::remove_dir_all(&path) {
+            Ok(()) => removed += 1,
+            Err(e) => log::warn!("Failed to remove {}: {}", path.display(), e),
+        }
+    }
+    removed
+}
+
+fn track(path: PathBuf) -> PathBuf {
+    let mut tracked = TRACKED.lock().unwrap();
+    if !tracked.contains(&path) {
+        tracked.push(path.clone());
+    }
+    path
+}
+
+/// Keep names to one path component
+fn sanitize(name: &str) -> String {
+    name.chars().map(|c| if c == '/' || c == '\\' { '-' } else { c }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paths_and_sweep() {
+        let first = unique_path("chat/image", "png");
+        let second = unique_path("chat/image", "png");
+        assert_ne!(first, second);
+        assert_eq!(first.parent(), Some(session_dir().as_path()));
+        assert!(first.file_name().unwrap().to_str().unwrap().starts_with("chat-image-"));
+        assert_eq!(session_file("voice-out.wav"), session_file("voice-out.wav"));
+        assert!(tracked().contains(&first));
+        release(&first);
+        assert!(!tracked().contains(&first));
+
+        let root = std::env::temp_dir().join(format!("moly-artifacts-test-{}", std::process::id()));
+        std::fs::create_dir_all(root.join("session-1-1")).unwrap();
+        std::fs::create_dir_all(root.join("unrelated")).unwrap();
+        let later = SystemTime::now() + Duration::from_secs(2 * 60 * 60);
+        assert_eq!(sweep_dir(&root, MIN_SWEEP_AGE, SystemTime::now()), 0);
+        assert_eq!(sweep_dir(&root, MIN_SWEEP_AGE, later), 1);
+        assert!(root.join("unrelated").exists());
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,201 @@
+//! Tidying up transcripts
+//!
+//! Speech recognition gets names and jargon wrong and, depending on the
+//! model, leaves out punctuation. Two optional fixes, set in Settings and
+//! switched per transcription in the chat's ASR mode:
+//!
+//! - a custom vocabulary, given to the ASR model as a hint
+//!   ([`vocabulary_prompt`]) and then used to correct the spelling of its
+//!   terms in the transcript ([`apply_vocabulary`]);
+//! - punctuation and casing restored by the LLM the runtime has loaded
+//!   ([`punctuate`]), which is told to keep the words as they are.
+
+use serde::{Deserialize, Serialize};
+use std::sync::RwLock;
+
+use crate::chat_template::{self, ChatTemplateOverrides};
+use crate::model_registry::RegistryModel;
+use crate::ominix_api_client::{ChatMessage, OminiXApiClient};
+
+/// Longest vocabulary hint sent with a transcription; Whisper-style models
+/// only read a couple of hundred tokens of it
+pub const MAX_PROMPT_CHARS: usize = 600;
+
+/// How much the letters of a punctuated transcript may differ from the
+/// original before the model is taken to have rewritten it
+const MAX_REWRITE: f32 = 0.2;
+
+/// Transcript fixes, stored in preferences
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AsrSettings {
+    /// Restore punctuation with the loaded LLM, unless switched off for a
+    /// transcription
+    pub punctuate: bool,
+    /// Use the vocabulary, unless switched off for a transcription
+    pub use_vocabulary: bool,
+    /// Names and terms to spell as given
+    pub vocabulary: Vec<String>,
+}
+
+impl Default for AsrSettings {
+    fn default() -> Self {
+        Self { punctuate: false, use_vocabulary: true, vocabulary: Vec::new() }
+    }
+}
+
+impl AsrSettings {
+    /// The vocabulary when it's to be used
+    pub fn active_vocabulary(&self) -> &[String] {
+        if self.use_vocabulary { &self.vocabulary } else { &[] }
+    }
+}
+
+/// Read Settings' vocabulary: terms separated by commas or lines
+pub fn parse_vocabulary(text: &str) -> Vec<String> {
+    let mut terms: Vec<String> = Vec::new();
+    for term in text.split([',', '\n']).map(str::trim).filter(|t| !t.is_empty()) {
+        if !terms.iter().any(|t| t.eq_ignore_ascii_case(term)) {
+            terms.push(term.to_string());
+        }
+    }
+    terms
+}
+
+/// The hint given to the ASR model, e.g. `Glossary: OminiX, MLX, Qwen3.`
+pub fn vocabulary_prompt(vocabulary: &[String]) -> Option<String> {
+    let mut prompt = String::from("Glossary:");
+    for term in vocabulary {
+        if prompt.len() + term.len() + 2 > MAX_PROMPT_CHARS {
+            break;
+        }
+        if !prompt.ends_with(':') {
+            prompt.push(',');
+        }
+        prompt.push(' ');
+        prompt.push_str(term);
+    }
+    if prompt.ends_with(':') {
+        return None;
+    }
+    prompt.push('.');
+    Some(prompt)
+}
+
+/// Spell the vocabulary's terms as given wherever the transcript has them in
+/// other letter cases, e.g. `ominix` → `OminiX`
+pub fn apply_vocabulary(text: &str, vocabulary: &[String]) -> String {
+    let mut text = text.to_string();
+    for term in vocabulary.iter().filter(|t| !t.is_empty()) {
+        let mut out = String::with_capacity(text.len());
+        let mut rest = text.as_str();
+        let mut previous: Option<char> = None;
+        while !rest.is_empty() {
+            let is_match = rest.get(..term.len()).is_some_and(|s| s.eq_ignore_ascii_case(term))
+                && !joins_word(previous, term.chars().next())
+                && !joins_word(rest[term.len()..].chars().next(), term.chars().last());
+            if is_match {
+                out.push_str(term);
+                previous = term.chars().last();
+                rest = &rest[term.len()..];
+            } else {
+                let c = rest.chars().next().unwrap();
+                out.push(c);
+                previous = Some(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+        text = out;
+    }
+    text
+}
+
+/// Whether `outside`, next to a term's letter `edge`, makes it part of a
+/// longer word. CJK scripts, from U+2E80 on, don't space words, so they
+/// have no word edges to respect.
+fn joins_word(outside: Option<char>, edge: Option<char>) -> bool {
+    matches!((outside, edge), (Some(o), Some(e)) if o.is_alphanumeric() && (o as u32) < 0x2E80 && e.is_ascii_alphanumeric())
+}
+
+/// System prompt asking to restore punctuation and casing
+pub fn instruction(vocabulary: &[String]) -> String {
+    let mut instruction = String::from(
+        "You restore punctuation and capitalization in speech transcripts. Add punctuation, capital letters \
+        and paragraph breaks to the user's text. Do not add, remove, reorder or translate words, and do not \
+        answer or comment on the text. Reply with the corrected transcript only.",
+    );
+    if !vocabulary.is_empty() {
+        instruction.push_str(&format!(
+            " Where a word was clearly misheard for one of these terms, write the term: {}.",
+            vocabulary.join(", ")
+        ));
+    }
+    instruction
+}
+
+/// The punctuated transcript from a model's answer. An answer whose letters
+/// differ much from the transcript's is refused: the model rewrote or
+/// answered it instead.
+pub fn clean_answer(answer: &str, original: &str) -> Result<String, String> {
+    let text = match answer.find("</think>") {
+        Some(end) => &answer[end + "</think>".len()..],
+        None => answer,
+    }
+    .trim();
+    let letters = |s: &str| s.chars().filter(|c| c.is_alphanumeric()).count() as f32;
+    let (before, after) = (letters(original), letters(text));
+    if text.is_empty() || (after - before).abs() > before.max(1.0) * MAX_REWRITE {
+        return Err("The model changed the words, so the transcript is shown as recognized.".to_string());
+    }
+    Ok(text.to_string())
+}
+
+/// Restore punctuation and casing of `text` with `model`. Blocks; call it
+/// off the UI thread.
+pub fn punctuate(model: &RegistryModel, text: &str, vocabulary: &[String]) -> Result<String, String> {
+    let template = chat_template::template_for(model, &ChatTemplateOverrides::load());
+    let messages = vec![ChatMessage::system(instruction(vocabulary)), ChatMessage::user(text.trim())];
+    chat_template::complete(&OminiXApiClient::localhost(300), &model.runtime.api_model_id, template, messages)
+        .map_err(|e| format!("Punctuating with {} failed: {}", model.name, e))
+        .and_then(|answer| clean_answer(&answer, text))
+}
+
+static CURRENT: RwLock<Option<AsrSettings>> = RwLock::new(None);
+
+/// Mirror [`crate::Preferences::asr`]
+pub fn set_current(settings: &AsrSettings) {
+    *CURRENT.write().unwrap() = Some(settings.clone());
+}
+
+pub fn current() -> AsrSettings {
+    CURRENT.read().unwrap().clone().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vocabulary() {
+        let vocabulary = parse_vocabulary("OminiX, MLX\nQwen3,  mlx ,");
+        assert_eq!(vocabulary, ["OminiX", "MLX", "Qwen3"]);
+        assert_eq!(vocabulary_prompt(&vocabulary).unwrap(), "Glossary: OminiX, MLX, Qwen3.");
+        assert_eq!(vocabulary_prompt(&[]), None);
+
+        assert_eq!(
+            apply_vocabulary("load qwen3 with mlx in ominix, not mlxs. 用ominix", &vocabulary),
+            "load Qwen3 with MLX in OminiX, not mlxs. 用OminiX"
+        );
+    }
+
+    #[test]
+    fn test_clean_answer() {
+        let original = "so we loaded the model and it worked";
+        assert_eq!(
+            clean_answer("<think>ok</think>\nSo we loaded the model, and it worked.", original).unwrap(),
+            "So we loaded the model, and it worked."
+        );
+        assert!(clean_answer("Great to hear that loading the model went well for you!", original).is_err());
+        assert!(clean_answer("", original).is_err());
+    }
+}
@@ -0,0 +1,174 @@
+//! PCM WAV helpers.
+//!
+//! Minimal RIFF/WAVE handling for audio returned by ominix-api: slicing a
+//! time range out of a file and joining chunks produced by separate requests.
+
+use std::fs::File;
+use std::io::{Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+/// Parsed view of a PCM WAV file.
+pub struct Wav<'a> {
+    /// First 16 bytes of the `fmt ` chunk (format, channels, rates, bits)
+    pub fmt: [u8; 16],
+    /// Raw sample data from the `data` chunk
+    pub data: &'a [u8],
+}
+
+impl<'a> Wav<'a> {
+    pub fn parse(bytes: &'a [u8]) -> Result<Self, String> {
+        if bytes.len() < 12 || &bytes[0..4] != b"RIFF" || &bytes[8..12] != b"WAVE" {
+            return Err("Not a RIFF/WAVE file".to_string());
+        }
+
+        let mut fmt: Option<&[u8]> = None;
+        let mut data: Option<&[u8]> = None;
+        let mut pos = 12;
+        while pos + 8 <= bytes.len() {
+            let id = &bytes[pos..pos + 4];
+            let size = u32::from_le_bytes([bytes[pos + 4], bytes[pos + 5], bytes[pos + 6], bytes[pos + 7]]) as usize;
+            let body_end = (pos + 8 + size).min(bytes.len());
+            match id {
+                b"fmt " => fmt = Some(&bytes[pos + 8..body_end]),
+                b"data" => data = Some(&bytes[pos + 8..body_end]),
+                _ => {}
+            }
+            // Chunks are padded to even sizes
+            pos = pos + 8 + size + (size & 1);
+        }
+
+        let fmt = fmt.filter(|f| f.len() >= 16).ok_or("Missing fmt chunk")?;
+        let data = data.ok_or("Missing data chunk")?;
+        let mut fmt16 = [0u8; 16];
+        fmt16.copy_from_slice(&fmt[..16]);
+        Ok(Self { fmt: fmt16, data })
+    }
+
+    pub fn byte_rate(&self) -> u32 {
+        u32::from_le_bytes([self.fmt[8], self.fmt[9], self.fmt[10], self.fmt[11]])
+    }
+
+    pub fn block_align(&self) -> usize {
+        u16::from_le_bytes([self.fmt[12], self.fmt[13]]).max(1) as usize
+    }
+
+    pub fn duration_secs(&self) -> f64 {
+        match self.byte_rate() {
+            0 => 0.0,
+            rate => self.data.len() as f64 / rate as f64,
+        }
+    }
+
+    /// Extract `[start, end)` seconds as a new WAV file.
+    pub fn slice(&self, start: f64, end: f64) -> Result<Vec<u8>, String> {
+        let byte_rate = self.byte_rate() as f64;
+        let block_align = self.block_align();
+        let to_offset = |t: f64| -> usize {
+            let raw = (t.max(0.0) * byte_rate) as usize;
+            (raw - raw % block_align).min(self.data.len())
+        };
+        let (from, to) = (to_offset(start), to_offset(end));
+        if to <= from {
+            return Err("Empty segment".to_string());
+        }
+        Ok(build_wav(&self.fmt, &self.data[from..to]))
+    }
+}
+
+/// Write a canonical 44-byte-header WAV.
+pub fn build_wav(fmt: &[u8; 16], data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(44 + data.len());
+    out.extend_from_slice(b"RIFF");
+    out.extend_from_slice(&((36 + data.len()) as u32).to_le_bytes());
+    out.extend_from_slice(b"WAVEfmt ");
+    out.extend_from_slice(&16u32.to_le_bytes());
+    out.extend_from_slice(fmt);
+    out.extend_from_slice(b"data");
+    out.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    out.extend_from_slice(data);
+    out
+}
+
+/// Concatenate WAV files that share one sample format.
+pub fn concat_wavs(parts: &[Vec<u8>]) -> Result<Vec<u8>, String> {
+    let mut fmt: Option<[u8; 16]> = None;
+    let mut data = Vec::new();
+    for (i, part) in parts.iter().enumerate() {
+        let wav = Wav::parse(part).map_err(|e| format!("Chunk {}: {}", i + 1, e))?;
+        match fmt {
+            None => fmt = Some(wav.fmt),
+            Some(f) if f != wav.fmt => {
+                return Err(format!("Chunk {} has a different audio format", i + 1));
+            }
+            Some(_) => {}
+        }
+        data.extend_from_slice(wav.data);
+    }
+    let fmt = fmt.ok_or("No audio to concatenate")?;
+    Ok(build_wav(&fmt, &data))
+}
+
+/// Concatenate WAV files on disk into `dest` without holding them all in
+/// memory (chapter files of an audiobook can add up to gigabytes).
+pub fn concat_wav_files(paths: &[PathBuf], dest: &Path) -> Result<(), String> {
+    let mut out = File::create(dest).map_err(|e| format!("Cannot create {}: {}", dest.display(), e))?;
+    let mut fmt: Option<[u8; 16]> = None;
+    let mut total: u64 = 0;
+
+    // Header is rewritten with the real sizes once all data is written
+    out.write_all(&build_wav(&[0u8; 16], &[])).map_err(|e| e.to_string())?;
+    for path in paths {
+        let bytes = std::fs::read(path).map_err(|e| format!("Cannot read {}: {}", path.display(), e))?;
+        let wav = Wav::parse(&bytes).map_err(|e| format!("{}: {}", path.display(), e))?;
+        match fmt {
+            None => fmt = Some(wav.fmt),
+            Some(f) if f != wav.fmt => {
+                return Err(format!("{} has a different audio format", path.display()));
+            }
+            Some(_) => {}
+        }
+        out.write_all(wav.data).map_err(|e| e.to_string())?;
+        total += wav.data.len() as u64;
+    }
+    let fmt = fmt.ok_or("No audio to concatenate")?;
+    if total > u32::MAX as u64 - 36 {
+        return Err("Audio too long for a single WAV file".to_string());
+    }
+
+    let mut header = build_wav(&fmt, &[]);
+    header[4..8].copy_from_slice(&((36 + total) as u32).to_le_bytes());
+    header[40..44].copy_from_slice(&(total as u32).to_le_bytes());
+    out.seek(SeekFrom::Start(0)).map_err(|e| e.to_string())?;
+    out.write_all(&header).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 16 kHz mono 16-bit format block
+    fn fmt_16k_mono() -> [u8; 16] {
+        let mut fmt = [0u8; 16];
+        fmt[0..2].copy_from_slice(&1u16.to_le_bytes());
+        fmt[2..4].copy_from_slice(&1u16.to_le_bytes());
+        fmt[4..8].copy_from_slice(&16000u32.to_le_bytes());
+        fmt[8..12].copy_from_slice(&32000u32.to_le_bytes());
+        fmt[12..14].copy_from_slice(&2u16.to_le_bytes());
+        fmt[14..16].copy_from_slice(&16u16.to_le_bytes());
+        fmt
+    }
+
+    #[test]
+    fn test_concat_and_slice() {
+        let a = build_wav(&fmt_16k_mono(), &vec![1u8; 32000]);
+        let b = build_wav(&fmt_16k_mono(), &vec![2u8; 16000]);
+        let joined = concat_wavs(&[a, b]).unwrap();
+        let wav = Wav::parse(&joined).unwrap();
+        assert_eq!(wav.duration_secs(), 1.5);
+
+        let tail = wav.slice(1.0, 1.5).unwrap();
+        let tail = Wav::parse(&tail).unwrap();
+        assert!(tail.data.iter().all(|&b| b == 2));
+        assert_eq!(tail.data.len(), 16000);
+    }
+}
@@ -0,0 +1,396 @@
+//! Audiobook source parsing.
+//!
+//! Splits a plain text, Markdown or EPUB document into chapters for the
+//! audiobook builder, which synthesizes each chapter with the long-form TTS
+//! engine and writes one audio file per chapter.
+
+use std::path::Path;
+
+/// One chapter of a book, as plain text ready for TTS.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BookChapter {
+    pub title: String,
+    pub text: String,
+}
+
+/// Load a document and split it into chapters based on its extension.
+pub fn load_book(path: &Path) -> Result<Vec<BookChapter>, String> {
+    let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("").to_lowercase();
+    let chapters = if ext == "epub" {
+        read_epub(path)?
+    } else {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("Cannot read {}: {}", path.display(), e))?;
+        match ext.as_str() {
+            "md" | "markdown" => split_markdown_chapters(&text),
+            _ => split_text_chapters(&text),
+        }
+    };
+    let chapters: Vec<BookChapter> = chapters.into_iter()
+        .filter(|c| !c.text.trim().is_empty())
+        .collect();
+    if chapters.is_empty() {
+        return Err("No text found in document".to_string());
+    }
+    Ok(chapters)
+}
+
+/// File name for a chapter's audio, e.g. `03 - The Road.wav`.
+pub fn chapter_file_name(index: usize, title: &str) -> String {
+    let clean: String = title.chars()
+        .map(|c| if c.is_alphanumeric() || c == ' ' || c == '-' || c == '_' { c } else { ' ' })
+        .take(60)
+        .collect();
+    let clean = clean.split_whitespace().collect::<Vec<_>>().join(" ");
+    if clean.is_empty() {
+        format!("{:02}.wav", index + 1)
+    } else {
+        format!("{:02} - {}.wav", index + 1, clean)
+    }
+}
+
+// ─── Markdown / plain text ───────────────────────────────────────────────────
+
+fn heading_level(line: &str) -> Option<usize> {
+    let hashes = line.chars().take_while(|&c| c == '#').count();
+    if (1..=6).contains(&hashes) && line[hashes..].starts_with(' ') {
+        Some(hashes)
+    } else {
+        None
+    }
+}
+
+/// Split Markdown at its chapter headings.
+///
+/// The chapter level is the highest heading level that occurs more than once,
+/// so a single `# Book Title` above `## Chapter` headings is not a chapter.
+pub fn split_markdown_chapters(md: &str) -> Vec<BookChapter> {
+    let mut counts = [0usize; 7];
+    let mut in_code = false;
+    for line in md.lines() {
+        if line.trim_start().starts_with("```") { in_code = !in_code; continue; }
+        if !in_code {
+            if let Some(level) = heading_level(line) { counts[level] += 1; }
+        }
+    }
+    let chapter_level = (1..=6).find(|&l| counts[l] >= 2)
+        .or_else(|| (1..=6).find(|&l| counts[l] == 1));
+    let Some(chapter_level) = chapter_level else {
+        return vec![BookChapter { title: "Full text".to_string(), text: markdown_to_plain(md) }];
+    };
+
+    let mut chapters = Vec::new();
+    let mut title = "Introduction".to_string();
+    let mut body = String::new();
+    in_code = false;
+    for line in md.lines() {
+        if line.trim_start().starts_with("```") { in_code = !in_code; continue; }
+        if in_code { continue; }
+        if heading_level(line) == Some(chapter_level) {
+            chapters.push(BookChapter { title, text: markdown_to_plain(&body) });
+            title = line.trim_start_matches('#').trim().to_string();
+            body.clear();
+        } else {
+            body.push_str(line);
+            body.push('\n');
+        }
+    }
+    chapters.push(BookChapter { title, text: markdown_to_plain(&body) });
+    chapters
+}
+
+/// Strip Markdown syntax that should not be read aloud.
+pub(crate) fn markdown_to_plain(md: &str) -> String {
+    let mut out = String::new();
+    for line in md.lines() {
+        let mut line = line.trim();
+        line = line.trim_start_matches('#').trim_start();
+        for prefix in ["> ", "- ", "* ", "+ "] {
+            if let Some(rest) = line.strip_prefix(prefix) { line = rest; }
+        }
+        out.push_str(&strip_inline_markdown(line));
+        out.push('\n');
+    }
+    collapse_blank_lines(&out)
+}
+
+fn strip_inline_markdown(line: &str) -> String {
+    let mut out = String::new();
+    let chars: Vec<char> = line.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        // [text](url) → text, ![alt](url) → nothing
+        if c == '[' || (c == '!' && chars.get(i + 1) == Some(&'[')) {
+            let is_image = c == '!';
+            let open = if is_image { i + 1 } else { i };
+            if let Some(close) = chars[open..].iter().position(|&x| x == ']').map(|p| p + open) {
+                if chars.get(close + 1) == Some(&'(') {
+                    if let Some(end) = chars[close..].iter().position(|&x| x == ')').map(|p| p + close) {
+                        if !is_image {
+                            out.extend(&chars[open + 1..close]);
+                        }
+                        i = end + 1;
+                        continue;
+                    }
+                }
+            }
+        }
+        if c != '*' && c != '`' {
+            out.push(c);
+        }
+        i += 1;
+    }
+    out
+}
+
+fn is_text_chapter_heading(line: &str) -> bool {
+    let line = line.trim();
+    if line.is_empty() || line.chars().count() > 80 {
+        return false;
+    }
+    let lower = line.to_lowercase();
+    if lower.starts_with("chapter ") || lower == "prologue" || lower == "epilogue" {
+        return true;
+    }
+    // 第一章 / 第12章
+    line.starts_with('第') && line.chars().take(12).any(|c| c == '章')
+}
+
+/// Split plain text at "Chapter N" / "第N章" lines.
+pub fn split_text_chapters(text: &str) -> Vec<BookChapter> {
+    if !text.lines().any(is_text_chapter_heading) {
+        return vec![BookChapter { title: "Full text".to_string(), text: collapse_blank_lines(text) }];
+    }
+    let mut chapters = Vec::new();
+    let mut title = "Introduction".to_string();
+    let mut body = String::new();
+    for line in text.lines() {
+        if is_text_chapter_heading(line) {
+            chapters.push(BookChapter { title, text: collapse_blank_lines(&body) });
+            title = line.trim().to_string();
+            body.clear();
+        } else {
+            body.push_str(line);
+            body.push('\n');
+        }
+    }
+    chapters.push(BookChapter { title, text: collapse_blank_lines(&body) });
+    chapters
+}
+
+fn collapse_blank_lines(text: &str) -> String {
+    let mut out = String::new();
+    let mut blank = 0;
+    for line in text.lines() {
+        if line.trim().is_empty() {
+            blank += 1;
+            continue;
+        }
+        if !out.is_empty() {
+            out.push_str(if blank > 0 { "\n\n" } else { "\n" });
+        }
+        out.push_str(line.trim_end());
+        blank = 0;
+    }
+    out
+}
+
+// ─── EPUB ────────────────────────────────────────────────────────────────────
+
+/// Read one entry of a zip archive with the system `unzip` tool.
+fn unzip_entry(archive: &Path, entry: &str) -> Result<String, String> {
+    let output = std::process::Command::new("unzip")
+        .arg("-p")
+        .arg(archive)
+        .arg(entry)
+        .output()
+        .map_err(|e| format!("Could not run unzip: {}", e))?;
+    if !output.status.success() {
+        return Err(format!("{} not found in {}", entry, archive.display()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Chapters of an EPUB in spine (reading) order.
+fn read_epub(path: &Path) -> Result<Vec<BookChapter>, String> {
+    let container = unzip_entry(path, "META-INF/container.xml")?;
+    let opf_path = xml_tags(&container, "rootfile")
+        .find_map(|tag| xml_attr(tag, "full-path"))
+        .ok_or("EPUB has no rootfile")?;
+    let opf = unzip_entry(path, &opf_path)?;
+    let base = opf_path.rsplit_once('/').map(|(dir, _)| format!("{}/", dir)).unwrap_or_default();
+
+    let manifest: Vec<(String, String)> = xml_tags(&opf, "item")
+        .filter_map(|tag| Some((xml_attr(tag, "id")?, xml_attr(tag, "href")?)))
+        .collect();
+
+    let mut chapters = Vec::new();
+    for idref in xml_tags(&opf, "itemref").filter_map(|tag| xml_attr(tag, "idref")) {
+        let Some((_, href)) = manifest.iter().find(|(id, _)| *id == idref) else { continue };
+        let entry = format!("{}{}", base, href.replace("%20", " "));
+        let html = match unzip_entry(path, &entry) {
+            Ok(h) => h,
+            Err(e) => {
+                log::warn!("Skipping EPUB item {}: {}", entry, e);
+                continue;
+            }
+        };
+        let text = html_to_text(&html);
+        if text.trim().is_empty() {
+            continue;
+        }
+        let title = ["h1", "h2", "h3", "title"].iter()
+            .find_map(|t| element_text(&html, t))
+            .unwrap_or_else(|| format!("Chapter {}", chapters.len() + 1));
+        chapters.push(BookChapter { title, text });
+    }
+    Ok(chapters)
+}
+
+/// Iterate over the opening tags `<name ...>` in a document.
+fn xml_tags<'a>(doc: &'a str, name: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+    let open = format!("<{}", name);
+    let mut pos = 0;
+    std::iter::from_fn(move || {
+        while let Some(found) = doc[pos..].find(&open) {
+            let start = pos + found;
+            let after = start + open.len();
+            pos = after;
+            let next = doc[after..].chars().next();
+            if matches!(next, Some(c) if c.is_whitespace() || c == '/' || c == '>') {
+                let end = doc[after..].find('>').map(|e| after + e + 1).unwrap_or(doc.len());
+                pos = end;
+                return Some(&doc[start..end]);
+            }
+        }
+        None
+    })
+}
+
+fn xml_attr(tag: &str, name: &str) -> Option<String> {
+    for quote in ['"', '\''] {
+        let key = format!("{}={}", name, quote);
+        let mut from = 0;
+        while let Some(found) = tag[from..].find(&key) {
+            let at = from + found;
+            from = at + key.len();
+            // Must be a whole attribute name, not the tail of e.g. `media-type`
+            if !tag[..at].ends_with(char::is_whitespace) {
+                continue;
+            }
+            let end = tag[from..].find(quote)? + from;
+            return Some(tag[from..end].to_string());
+        }
+    }
+    None
+}
+
+/// Plain text of the first `<name>` element, if non-empty.
+fn element_text(html: &str, name: &str) -> Option<String> {
+    let tag = xml_tags(html, name).next()?;
+    let start = html.find(tag)? + tag.len();
+    let end = html[start..].find(&format!("</{}", name))? + start;
+    let text = html_to_text(&html[start..end]).split_whitespace().collect::<Vec<_>>().join(" ");
+    (!text.is_empty()).then_some(text)
+}
+
+/// Convert (X)HTML to readable text: drop head/script/style, break lines at
+/// block elements, strip remaining tags and decode common entities.
+pub fn html_to_text(html: &str) -> String {
+    let mut html = html.to_string();
+    for skip in ["head", "script", "style"] {
+        while let Some(start) = html.find(&format!("<{}", skip)) {
+            let close = format!("</{}>", skip);
+            match html[start..].find(&close) {
+                Some(end) => html.replace_range(start..start + end + close.len(), ""),
+                None => break,
+            }
+        }
+    }
+
+    let mut out = String::new();
+    let mut rest = html.as_str();
+    while let Some(lt) = rest.find('<') {
+        out.push_str(&rest[..lt]);
+        let Some(gt) = rest[lt..].find('>') else { rest = ""; break };
+        let tag = rest[lt + 1..lt + gt].trim_start_matches('/').to_lowercase();
+        let tag_name = tag.split(|c: char| c.is_whitespace() || c == '/').next().unwrap_or("");
+        if matches!(tag_name, "p" | "br" | "div" | "li" | "tr" | "h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "blockquote" | "section") {
+            out.push_str("\n\n");
+        }
+        rest = &rest[lt + gt + 1..];
+    }
+    out.push_str(rest);
+
+    let decoded = decode_entities(&out);
+    let lines: Vec<String> = decoded.lines()
+        .map(|l| l.split_whitespace().collect::<Vec<_>>().join(" "))
+        .collect();
+    collapse_blank_lines(&lines.join("\n"))
+}
+
+fn decode_entities(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        let after = &rest[amp..];
+        let Some(semi) = after.find(';').filter(|&s| s <= 10) else {
+            out.push('&');
+            rest = &after[1..];
+            continue;
+        };
+        let entity = &after[1..semi];
+        let decoded = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some(' '),
+            _ if entity.starts_with("#x") || entity.starts_with("#X") => {
+                u32::from_str_radix(&entity[2..], 16).ok().and_then(char::from_u32)
+            }
+            _ if entity.starts_with('#') => entity[1..].parse().ok().and_then(char::from_u32),
+            _ => None,
+        };
+        match decoded {
+            Some(c) => { out.push(c); rest = &after[semi + 1..]; }
+            None => { out.push('&'); rest = &after[1..]; }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_markdown_chapters_skip_book_title() {
+        let md = "# My Book\n\nIntro line.\n\n## One\n\nFirst **bold** [link](http://x).\n\n## Two\n\n![img](a.png)Second.\n";
+        let chapters = split_markdown_chapters(md);
+        let titles: Vec<&str> = chapters.iter().map(|c| c.title.as_str()).collect();
+        assert_eq!(titles, vec!["Introduction", "One", "Two"]);
+        assert_eq!(chapters[0].text, "My Book\n\nIntro line.");
+        assert_eq!(chapters[1].text, "First bold link.");
+        assert_eq!(chapters[2].text, "Second.");
+    }
+
+    #[test]
+    fn test_text_chapters() {
+        let text = "Chapter 1\nIt begins.\n\nChapter 2\nIt ends.";
+        let chapters = split_text_chapters(text);
+        assert_eq!(chapters[1], BookChapter { title: "Chapter 1".into(), text: "It begins.".into() });
+        assert_eq!(chapters[2].title, "Chapter 2");
+    }
+
+    #[test]
+    fn test_html_to_text() {
+        let html = "<html><head><title>T</title></head><body><h1>Title</h1><p>Tom &amp; Jerry&#33;</p><p>Next</p></body></html>";
+        assert_eq!(html_to_text(html), "Title\n\nTom & Jerry!\n\nNext");
+        assert_eq!(element_text(html, "h1").as_deref(), Some("Title"));
+    }
+}
@@ -0,0 +1,101 @@
+//! Bookmarked messages
+//!
+//! A bookmark marks one message of a chat, optionally with a note. Bookmarks
+//! are stored on their chat ([`crate::ChatData::bookmarks`]); [`collect`]
+//! gathers them across chats for the Bookmarks page.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::chats::{ChatData, ChatId};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Bookmark {
+    /// Position of the message in the chat
+    pub message_index: usize,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub note: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A bookmark with what it points to, for listing
+#[derive(Clone, Debug, PartialEq)]
+pub struct BookmarkEntry {
+    pub chat_id: ChatId,
+    pub chat_title: String,
+    pub message_index: usize,
+    pub excerpt: String,
+    pub note: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl BookmarkEntry {
+    /// Whether the note, message or chat title contains `query` (lowercase)
+    pub fn matches(&self, query: &str) -> bool {
+        query.is_empty()
+            || self.note.to_lowercase().contains(query)
+            || self.excerpt.to_lowercase().contains(query)
+            || self.chat_title.to_lowercase().contains(query)
+    }
+}
+
+/// A message shortened to one line of at most `max_chars` characters
+pub fn excerpt(text: &str, max_chars: usize) -> String {
+    let line = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if line.chars().count() <= max_chars {
+        return line;
+    }
+    let mut short: String = line.chars().take(max_chars.saturating_sub(1)).collect();
+    short.push('…');
+    short
+}
+
+/// Bookmarks of all chats, newest first
+pub fn collect(chats: &[ChatData], excerpt_chars: usize) -> Vec<BookmarkEntry> {
+    let mut entries: Vec<BookmarkEntry> = chats.iter().flat_map(|chat| {
+        chat.bookmarks.iter().filter_map(move |bookmark| {
+            let message = chat.messages.get(bookmark.message_index)?;
+            Some(BookmarkEntry {
+                chat_id: chat.id,
+                chat_title: chat.title.clone(),
+                message_index: bookmark.message_index,
+                excerpt: excerpt(&message.content.text, excerpt_chars),
+                note: bookmark.note.clone(),
+                created_at: bookmark.created_at,
+            })
+        })
+    }).collect();
+    entries.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use moly_kit::prelude::*;
+
+    #[test]
+    fn test_collect_and_excerpt() {
+        let mut chat = ChatData::with_title("Rust".to_string());
+        for text in ["What are lifetimes?", "Lifetimes   describe\nhow long references live."] {
+            chat.messages.push(Message {
+                content: MessageContent { text: text.to_string(), ..Default::default() },
+                ..Default::default()
+            });
+        }
+        let older = Utc::now() - chrono::Duration::days(1);
+        chat.bookmarks = vec![
+            Bookmark { message_index: 0, note: String::new(), created_at: older },
+            Bookmark { message_index: 1, note: "Good answer".to_string(), created_at: Utc::now() },
+            // Points past the end; skipped
+            Bookmark { message_index: 5, note: String::new(), created_at: Utc::now() },
+        ];
+
+        let entries = collect(&[chat], 20);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].message_index, 1);
+        assert_eq!(entries[0].excerpt, "Lifetimes describe …");
+        assert!(entries[0].matches("good"));
+        assert!(!entries[1].matches("good"));
+    }
+}
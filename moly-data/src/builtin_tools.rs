@@ -0,0 +1,34 @@
+//! Built-in tools the app runs itself
+//!
+//! [`definitions`] lists the functions offered to the model for the tools a
+//! chat exposes, in OpenAI function calling format; [`call`] runs a function
+//! call the model made. A2UI is not here: the A2UI client wrapper injects
+//! and renders its tools.
+
+use serde_json::Value;
+
+use crate::chats::ChatId;
+use crate::code_tool;
+use crate::fs_tool;
+use crate::toolsets::{BuiltinTool, ToolSelection};
+
+/// Function definitions for the built-in tools in `tools`
+pub fn definitions(tools: &ToolSelection) -> Vec<Value> {
+    let mut definitions = Vec::new();
+    if tools.has_builtin(BuiltinTool::Files) {
+        definitions.extend(fs_tool::definitions());
+    }
+    if tools.has_builtin(BuiltinTool::CodeExecution) {
+        definitions.extend(code_tool::definitions());
+    }
+    definitions
+}
+
+/// Run a function call the model made in the chat `chat_id`. `args` is
+/// the JSON object it sent.
+pub fn call(chat_id: Option<ChatId>, name: &str, args: &Value) -> Result<String, String> {
+    log::info!("Built-in tool call: {}", name);
+    fs_tool::call(chat_id, name, args)
+        .or_else(|| code_tool::call(name, args))
+        .unwrap_or_else(|| Err(format!("Unknown tool: {}", name)))
+}
@@ -0,0 +1,182 @@
+//! Chat history snapshots
+//!
+//! A snapshot bundles every chat of the active profile into one timestamped
+//! JSON file under `backups/` in the profile's data directory. The shell takes
+//! one automatically when the configured interval has passed, old snapshots
+//! are pruned to the retention count, and Settings can take or restore one on
+//! demand. Restoring first snapshots the current state, so it can be undone.
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::chats::{write_atomic, ChatData};
+use crate::profiles;
+
+const BACKUPS_DIR: &str = "backups";
+const BACKUP_PREFIX: &str = "chats-";
+const BACKUP_SUFFIX: &str = ".backup.json";
+const TIMESTAMP_FORMAT: &str = "%Y%m%d-%H%M%S";
+
+/// Automatic snapshot settings, stored in preferences
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ChatBackupSettings {
+    /// Take snapshots automatically
+    pub enabled: bool,
+    /// Hours between automatic snapshots
+    pub interval_hours: u32,
+    /// Number of snapshots to keep
+    pub retention: usize,
+}
+
+impl Default for ChatBackupSettings {
+    fn default() -> Self {
+        Self { enabled: true, interval_hours: 24, retention: 14 }
+    }
+}
+
+/// Contents of a snapshot file
+#[derive(Serialize, Deserialize)]
+struct ChatBackup {
+    created_at: DateTime<Utc>,
+    chats: Vec<ChatData>,
+}
+
+/// A snapshot on disk
+#[derive(Clone, Debug)]
+pub struct BackupInfo {
+    pub path: PathBuf,
+    pub created_at: DateTime<Utc>,
+}
+
+/// `backups/` in the active profile's data directory
+pub fn backups_dir() -> PathBuf {
+    profiles::data_dir().join(BACKUPS_DIR)
+}
+
+/// Write a snapshot of `chats` and prune old ones down to `retention`.
+pub fn create_backup(chats: &[ChatData], retention: usize) -> Result<PathBuf, String> {
+    let dir = backups_dir();
+    std::fs::create_dir_all(&dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+
+    let created_at = Utc::now();
+    let mut path = dir.join(backup_file_name(&created_at));
+    // Two snapshots in the same second (e.g. right before a restore)
+    let mut n = 2;
+    while path.exists() {
+        path = dir.join(format!("{}{}-{}{}", BACKUP_PREFIX, created_at.format(TIMESTAMP_FORMAT), n, BACKUP_SUFFIX));
+        n += 1;
+    }
+
+    let backup = ChatBackup { created_at, chats: chats.to_vec() };
+    let json = serde_json::to_string(&backup).map_err(|e| e.to_string())?;
+    write_atomic(&path, json.as_bytes())?;
+    log::info!("Backed up {} chats to {:?}", chats.len(), path);
+
+    prune_backups(retention);
+    Ok(path)
+}
+
+/// Snapshots of the active profile, newest first
+pub fn list_backups() -> Vec<BackupInfo> {
+    let Ok(entries) = std::fs::read_dir(backups_dir()) else { return Vec::new() };
+    let mut backups: Vec<BackupInfo> = entries.flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            let name = path.file_name()?.to_str()?;
+            let created_at = parse_backup_time(name)?;
+            Some(BackupInfo { path, created_at })
+        })
+        .collect();
+    backups.sort_by(|a, b| b.created_at.cmp(&a.created_at).then(b.path.cmp(&a.path)));
+    backups
+}
+
+/// Whether an automatic snapshot should be taken now
+pub fn is_backup_due(settings: &ChatBackupSettings, latest: Option<DateTime<Utc>>, now: DateTime<Utc>) -> bool {
+    if !settings.enabled { return false; }
+    match latest {
+        None => true,
+        Some(latest) => now - latest >= chrono::Duration::hours(settings.interval_hours.max(1) as i64),
+    }
+}
+
+/// Read the chats stored in a snapshot
+pub fn read_backup(path: &Path) -> Result<Vec<ChatData>, String> {
+    let json = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let backup: ChatBackup = serde_json::from_str(&json)
+        .map_err(|e| format!("Not a chat backup: {}", e))?;
+    Ok(backup.chats)
+}
+
+/// Replace the chats directory with a snapshot's contents. The current chats
+/// are snapshotted first so the restore can be reverted.
+pub fn restore_backup(path: &Path, current: &[ChatData], chats_dir: &Path, retention: usize) -> Result<usize, String> {
+    let restored = read_backup(path)?;
+    // Keep the pre-restore snapshot even if retention would drop it
+    create_backup(current, retention.max(1) + 1)?;
+
+    std::fs::create_dir_all(chats_dir).map_err(|e| format!("{}: {}", chats_dir.display(), e))?;
+    if let Ok(entries) = std::fs::read_dir(chats_dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().map_or(false, |e| e == "json") {
+                if let Err(e) = std::fs::remove_file(&path) {
+                    log::warn!("Failed to remove {:?} during restore: {:?}", path, e);
+                }
+            }
+        }
+    }
+    let chats_dir = chats_dir.to_path_buf();
+    for chat in &restored {
+        chat.save(&chats_dir);
+    }
+    log::info!("Restored {} chats from {:?}", restored.len(), path);
+    Ok(restored.len())
+}
+
+/// Delete all but the newest `retention` snapshots
+pub fn prune_backups(retention: usize) {
+    for old in list_backups().into_iter().skip(retention.max(1)) {
+        if let Err(e) = std::fs::remove_file(&old.path) {
+            log::warn!("Failed to prune backup {:?}: {:?}", old.path, e);
+        }
+    }
+}
+
+fn backup_file_name(created_at: &DateTime<Utc>) -> String {
+    format!("{}{}{}", BACKUP_PREFIX, created_at.format(TIMESTAMP_FORMAT), BACKUP_SUFFIX)
+}
+
+fn parse_backup_time(file_name: &str) -> Option<DateTime<Utc>> {
+    let stamp = file_name.strip_prefix(BACKUP_PREFIX)?.strip_suffix(BACKUP_SUFFIX)?;
+    // Drop a same-second "-N" suffix
+    let stamp = stamp.get(..15)?;
+    NaiveDateTime::parse_from_str(stamp, TIMESTAMP_FORMAT).ok().map(|t| t.and_utc())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backup_file_names() {
+        let t = DateTime::parse_from_rfc3339("2026-03-04T05:06:07Z").unwrap().with_timezone(&Utc);
+        let name = backup_file_name(&t);
+        assert_eq!(name, "chats-20260304-050607.backup.json");
+        assert_eq!(parse_backup_time(&name), Some(t));
+        assert_eq!(parse_backup_time("chats-20260304-050607-2.backup.json"), Some(t));
+        assert_eq!(parse_backup_time("notes.json"), None);
+    }
+
+    #[test]
+    fn test_backup_due() {
+        let now = Utc::now();
+        let settings = ChatBackupSettings { enabled: true, interval_hours: 6, retention: 3 };
+        assert!(is_backup_due(&settings, None, now));
+        assert!(!is_backup_due(&settings, Some(now - chrono::Duration::hours(5)), now));
+        assert!(is_backup_due(&settings, Some(now - chrono::Duration::hours(6)), now));
+        let disabled = ChatBackupSettings { enabled: false, ..settings };
+        assert!(!is_backup_due(&disabled, None, now));
+    }
+}
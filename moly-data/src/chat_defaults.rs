@@ -0,0 +1,143 @@
+//! Defaults for new chats, by provider
+//!
+//! Each provider in Settings can name a default model, temperature, answer
+//! length and system prompt ([`ChatDefaults`], kept in its
+//! `ProviderPreferences`). A new chat with that provider starts with them,
+//! and the chat's own Prompt… and Sampling… settings override them from then
+//! on. The open chat's [`Sampling`] is mirrored with [`set_active`] and sent
+//! by the clients whose requests this crate builds: the local runtime's
+//! completions client, Ollama, and for OpenAI-compatible providers
+//! [`crate::proxied_client`] and the tool rounds of [`crate::chat_tools`].
+//! Their chats with attachments or A2UI go through moly-kit's client, which
+//! sends no sampling.
+
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+
+/// Highest temperature providers accept
+pub const MAX_TEMPERATURE: f32 = 2.0;
+
+/// Sampling of the chat that is open
+static ACTIVE: Mutex<Sampling> = Mutex::new(Sampling { temperature: None, max_tokens: None });
+
+/// How answers are sampled; `None` leaves it to the model
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Sampling {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+    /// Longest answer, in tokens
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u32>,
+}
+
+impl Sampling {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// e.g. `0.7, 1024`, as typed in the chat's Sampling… dialog; empty parts
+    /// are left to the model
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut parts = text.split(',');
+        let temperature = parse_temperature(parts.next().unwrap_or_default())?;
+        let max_tokens = parse_max_tokens(parts.next().unwrap_or_default())?;
+        if parts.next().is_some() {
+            return Err("Enter a temperature and a token limit, separated by a comma.".to_string());
+        }
+        Ok(Self { temperature, max_tokens })
+    }
+
+    /// The inverse of [`Self::parse`]
+    pub fn display(&self) -> String {
+        let temperature = self.temperature.map(|t| t.to_string()).unwrap_or_default();
+        let max_tokens = self.max_tokens.map(|t| t.to_string()).unwrap_or_default();
+        match (temperature.is_empty(), max_tokens.is_empty()) {
+            (true, true) => String::new(),
+            (_, true) => temperature,
+            _ => format!("{}, {}", temperature, max_tokens),
+        }
+    }
+}
+
+pub fn parse_temperature(text: &str) -> Result<Option<f32>, String> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Ok(None);
+    }
+    match text.parse::<f32>() {
+        Ok(t) if (0.0..=MAX_TEMPERATURE).contains(&t) => Ok(Some(t)),
+        _ => Err(format!("Temperature must be a number from 0 to {}.", MAX_TEMPERATURE)),
+    }
+}
+
+pub fn parse_max_tokens(text: &str) -> Result<Option<u32>, String> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Ok(None);
+    }
+    match text.parse::<u32>() {
+        Ok(n) if n > 0 => Ok(Some(n)),
+        _ => Err("Max tokens must be a whole number above 0.".to_string()),
+    }
+}
+
+/// What a new chat with a provider starts with
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ChatDefaults {
+    /// Model id, as the provider lists it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    #[serde(flatten)]
+    pub sampling: Sampling,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub system_prompt: Option<String>,
+}
+
+impl ChatDefaults {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Mirror the open chat's [`crate::ChatData::sampling`]
+pub fn set_active(sampling: Sampling) {
+    *ACTIVE.lock().unwrap() = sampling;
+}
+
+pub fn active() -> Sampling {
+    *ACTIVE.lock().unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_sampling() {
+        assert_eq!(Sampling::parse(""), Ok(Sampling::default()));
+        assert_eq!(Sampling::parse("0.7"), Ok(Sampling { temperature: Some(0.7), max_tokens: None }));
+        assert_eq!(Sampling::parse(" , 512"), Ok(Sampling { temperature: None, max_tokens: Some(512) }));
+        assert!(Sampling::parse("3, 512").is_err());
+        assert!(Sampling::parse("0.7, -1").is_err());
+        assert!(Sampling::parse("0.7, 512, 1").is_err());
+
+        for text in ["", "0.7", "0.2, 1024", ", 64"] {
+            assert_eq!(Sampling::parse(&Sampling::parse(text).unwrap().display()), Sampling::parse(text));
+        }
+    }
+
+    #[test]
+    fn test_defaults_round_trip() {
+        let defaults = ChatDefaults {
+            model: Some("gpt-4o-mini".to_string()),
+            sampling: Sampling { temperature: Some(0.2), max_tokens: None },
+            system_prompt: None,
+        };
+        let json = serde_json::to_string(&defaults).unwrap();
+        assert_eq!(json, r#"{"model":"gpt-4o-mini","temperature":0.2}"#);
+        assert_eq!(serde_json::from_str::<ChatDefaults>(&json).unwrap(), defaults);
+        assert!(ChatDefaults::default().is_empty());
+    }
+}
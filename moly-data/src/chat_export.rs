@@ -0,0 +1,83 @@
+//! Saving a chat as a Markdown document
+//!
+//! [`to_markdown`] writes the chat's title, system prompt and messages, and
+//! its notes when the user asks for them. Messages still being written and
+//! empty ones are left out.
+
+use moly_kit::prelude::*;
+
+use crate::chats::ChatData;
+
+/// `chat` as Markdown; `include_notes` appends the chat's notes
+pub fn to_markdown(chat: &ChatData, include_notes: bool) -> String {
+    let mut out = format!("# {}\n\n", chat.title);
+    out.push_str(&format!("_Exported from OminiX Studio on {}_\n\n", chrono::Local::now().format("%Y-%m-%d %H:%M")));
+    if let Some(prompt) = chat.system_prompt.as_deref().filter(|p| !p.trim().is_empty()) {
+        out.push_str("**System prompt:**\n\n");
+        for line in prompt.trim().lines() {
+            out.push_str(&format!("> {}\n", line));
+        }
+        out.push('\n');
+    }
+    for message in &chat.messages {
+        let text = message.content.text.trim();
+        if message.metadata.is_writing || text.is_empty() {
+            continue;
+        }
+        let author = match &message.from {
+            EntityId::User => "You".to_string(),
+            EntityId::Bot(bot_id) => bot_id.id().to_string(),
+            EntityId::System => "System".to_string(),
+            _ => "Tool".to_string(),
+        };
+        out.push_str(&format!("## {}\n\n{}\n\n", author, text));
+    }
+    if include_notes && !chat.notes.trim().is_empty() {
+        out.push_str(&format!("---\n\n## Notes\n\n{}\n", chat.notes.trim()));
+    }
+    out
+}
+
+/// File name for the exported chat, from its title
+pub fn file_name(chat: &ChatData) -> String {
+    format!("{}.md", file_stem(chat))
+}
+
+/// The chat's title as a file name, without an extension
+pub fn file_stem(chat: &ChatData) -> String {
+    let name: String = chat.title.chars()
+        .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' { c } else { ' ' })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join("-");
+    if name.is_empty() { "chat".to_string() } else { name }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_markdown_with_notes() {
+        let mut chat = ChatData::with_title("Rust: lifetimes?".to_string());
+        chat.messages.push(Message {
+            from: EntityId::User,
+            content: MessageContent { text: "What is 'a?".to_string(), ..Default::default() },
+            ..Default::default()
+        });
+        chat.messages.push(Message {
+            from: EntityId::Bot(BotId::new("qwen3-8b")),
+            content: MessageContent { text: "A lifetime.".to_string(), ..Default::default() },
+            ..Default::default()
+        });
+        chat.notes = "- read the nomicon".to_string();
+
+        let without = to_markdown(&chat, false);
+        assert!(without.starts_with("# Rust: lifetimes?\n"));
+        assert!(without.contains("## You\n\nWhat is 'a?\n\n## qwen3-8b\n\nA lifetime.\n"));
+        assert!(!without.contains("nomicon"));
+        assert!(to_markdown(&chat, true).ends_with("## Notes\n\n- read the nomicon\n"));
+        assert_eq!(file_name(&chat), "Rust-lifetimes.md");
+    }
+}
@@ -0,0 +1,162 @@
+//! Finding your way around long chats
+//!
+//! Each chat keeps where every day of it starts: the first message sent that
+//! day ([`crate::ChatData::days`], kept up by [`record_day`]). The chat's
+//! Jump menu lists the days ([`day_sections`]) and scrolls to the one picked.
+//!
+//! Messages also have links, `ominix://chat/<id>#<message>` ([`permalink`]).
+//! The desktop runs the app with the link it opens ([`link_arg`]); when an
+//! instance is already running the link is handed to it instead
+//! ([`crate::quick_ask::open_in_running`]), which shows the chat scrolled to
+//! the message.
+
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::chats::{ChatData, ChatId};
+
+/// URL scheme of message links
+pub const SCHEME: &str = "ominix";
+
+const LINK_PREFIX: &str = "ominix://chat/";
+
+/// Where a day starts in a chat
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DayMark {
+    /// Position of the first message sent that day
+    pub message_index: usize,
+    /// Local date
+    pub date: NaiveDate,
+}
+
+/// Follow a change of a chat's messages from `previous_count` to `count`:
+/// forget days whose messages were removed, and start `today` at the first
+/// new message unless it already started
+pub fn record_day(days: &mut Vec<DayMark>, previous_count: usize, count: usize, today: NaiveDate) {
+    days.retain(|d| d.message_index < count);
+    if count > previous_count && days.last().map(|d| d.date) != Some(today) {
+        days.push(DayMark { message_index: previous_count, date: today });
+    }
+}
+
+/// A day of a chat, for the Jump menu
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DaySection {
+    pub date: NaiveDate,
+    pub message_index: usize,
+    pub message_count: usize,
+}
+
+/// The days of a chat in order. Messages from before days were recorded
+/// count as sent on the day the chat was created.
+pub fn day_sections(days: &[DayMark], created: NaiveDate, message_count: usize) -> Vec<DaySection> {
+    let mut marks: Vec<DayMark> = Vec::new();
+    if message_count > 0 && days.first().is_none_or(|d| d.message_index > 0) {
+        marks.push(DayMark { message_index: 0, date: created });
+    }
+    for mark in days.iter().filter(|d| d.message_index < message_count) {
+        if marks.last().is_none_or(|last| last.date != mark.date && last.message_index < mark.message_index) {
+            marks.push(*mark);
+        }
+    }
+    marks.iter().enumerate().map(|(i, mark)| {
+        let end = marks.get(i + 1).map(|next| next.message_index).unwrap_or(message_count);
+        DaySection { date: mark.date, message_index: mark.message_index, message_count: end - mark.message_index }
+    }).collect()
+}
+
+/// [`day_sections`] of a chat
+pub fn chat_days(chat: &ChatData) -> Vec<DaySection> {
+    day_sections(&chat.days, local_date(chat.created_at), chat.messages.len())
+}
+
+pub fn local_date(time: DateTime<Utc>) -> NaiveDate {
+    time.with_timezone(&chrono::Local).date_naive()
+}
+
+pub fn today() -> NaiveDate {
+    chrono::Local::now().date_naive()
+}
+
+/// e.g. `Today`, `Yesterday`, `Mon 12 Oct`, or `3 Mar 2025` in another year
+pub fn day_label(date: NaiveDate, today: NaiveDate) -> String {
+    if date == today {
+        "Today".to_string()
+    } else if today.pred_opt() == Some(date) {
+        "Yesterday".to_string()
+    } else if date.year() == today.year() {
+        date.format("%a %-d %b").to_string()
+    } else {
+        date.format("%-d %b %Y").to_string()
+    }
+}
+
+/// A link to a message, e.g. `ominix://chat/1718000000000#12`
+pub fn permalink(chat_id: ChatId, message_index: usize) -> String {
+    format!("{}{}#{}", LINK_PREFIX, chat_id, message_index)
+}
+
+/// What a link points to
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ChatLink {
+    pub chat_id: ChatId,
+    /// `None` for a link to the whole chat
+    pub message_index: Option<usize>,
+}
+
+pub fn parse_link(link: &str) -> Option<ChatLink> {
+    let rest = link.trim().strip_prefix(LINK_PREFIX)?;
+    let (chat, message) = match rest.split_once('#') {
+        Some((chat, message)) => (chat, Some(message.parse().ok()?)),
+        None => (rest, None),
+    };
+    Some(ChatLink { chat_id: chat.trim_end_matches('/').parse().ok()?, message_index: message })
+}
+
+/// The link the app was started with, if any
+pub fn link_arg() -> Option<String> {
+    std::env::args().skip(1).find(|arg| arg.starts_with(LINK_PREFIX))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, d).unwrap()
+    }
+
+    #[test]
+    fn test_days() {
+        let mut days = Vec::new();
+        record_day(&mut days, 4, 6, day(14));
+        record_day(&mut days, 6, 8, day(14));
+        record_day(&mut days, 8, 10, day(16));
+        // Regenerating an answer keeps the count
+        record_day(&mut days, 10, 10, day(16));
+        assert_eq!(days, [DayMark { message_index: 4, date: day(14) }, DayMark { message_index: 8, date: day(16) }]);
+
+        let sections = day_sections(&days, day(2), 10);
+        assert_eq!(sections.iter().map(|s| (s.message_index, s.message_count)).collect::<Vec<_>>(), [(0, 4), (4, 4), (8, 2)]);
+        assert_eq!(sections[0].date, day(2));
+
+        record_day(&mut days, 10, 7, day(16));
+        assert_eq!(days.len(), 1);
+        assert!(day_sections(&[], day(2), 0).is_empty());
+
+        assert_eq!(day_label(day(16), day(16)), "Today");
+        assert_eq!(day_label(day(15), day(16)), "Yesterday");
+        assert_eq!(day_label(day(12), day(16)), "Mon 12 Oct");
+        assert_eq!(day_label(NaiveDate::from_ymd_opt(2025, 3, 3).unwrap(), day(16)), "3 Mar 2025");
+    }
+
+    #[test]
+    fn test_links() {
+        let link = permalink(1718000000000, 12);
+        assert_eq!(link, "ominix://chat/1718000000000#12");
+        assert_eq!(parse_link(&link), Some(ChatLink { chat_id: 1718000000000, message_index: Some(12) }));
+        assert_eq!(parse_link("ominix://chat/42/"), Some(ChatLink { chat_id: 42, message_index: None }));
+        assert_eq!(parse_link("ominix://chat/42#x"), None);
+        assert_eq!(parse_link("https://example.com"), None);
+    }
+}
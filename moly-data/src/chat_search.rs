@@ -0,0 +1,164 @@
+//! Finding text within the open conversation
+//!
+//! Unlike the history search, which picks a chat, this searches the messages
+//! of the chat on screen. [`ChatSearch`] keeps the matches of the query and
+//! which one is current; the chat's find bar (Cmd+F) shows the count, the
+//! current match in its context ([`snippet`]), and scrolls the transcript to
+//! its message. Matching ignores case.
+
+use moly_kit::prelude::*;
+
+/// Characters of context shown on each side of a match
+pub const CONTEXT_CHARS: usize = 60;
+
+/// A match in a message's text, as a byte range
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SearchMatch {
+    pub message_index: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// All matches of `query` in the messages, in transcript order
+pub fn find(messages: &[Message], query: &str) -> Vec<SearchMatch> {
+    let query: Vec<char> = query.trim().chars().map(fold).collect();
+    if query.is_empty() {
+        return Vec::new();
+    }
+    let mut matches = Vec::new();
+    for (message_index, message) in messages.iter().enumerate() {
+        let text = &message.content.text;
+        let chars: Vec<(usize, char)> = text.char_indices().map(|(i, c)| (i, fold(c))).collect();
+        let mut i = 0;
+        while i + query.len() <= chars.len() {
+            if chars[i..i + query.len()].iter().map(|(_, c)| *c).eq(query.iter().copied()) {
+                let end = chars.get(i + query.len()).map(|(b, _)| *b).unwrap_or(text.len());
+                matches.push(SearchMatch { message_index, start: chars[i].0, end });
+                i += query.len();
+            } else {
+                i += 1;
+            }
+        }
+    }
+    matches
+}
+
+/// Lowercase a character, keeping one character per character so byte
+/// offsets stay those of the original text
+fn fold(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+/// A match with the text around it on one line, as `(before, match, after)`
+pub fn snippet(text: &str, m: &SearchMatch, context_chars: usize) -> (String, String, String) {
+    let one_line = |s: &str| s.split_whitespace().collect::<Vec<_>>().join(" ");
+    let before: Vec<char> = text[..m.start].chars().collect();
+    let mut before_text: String = before[before.len().saturating_sub(context_chars)..].iter().collect();
+    if before.len() > context_chars {
+        before_text.insert(0, '…');
+    }
+    let after = &text[m.end..];
+    let mut after_text: String = after.chars().take(context_chars).collect();
+    if after.chars().count() > context_chars {
+        after_text.push('…');
+    }
+    // Keep the space next to the match, which split_whitespace would drop
+    let pad_before = if before_text.ends_with(char::is_whitespace) { " " } else { "" };
+    let pad_after = if after_text.starts_with(char::is_whitespace) { " " } else { "" };
+    (
+        format!("{}{}", one_line(&before_text), pad_before),
+        one_line(&text[m.start..m.end]),
+        format!("{}{}", pad_after, one_line(&after_text)),
+    )
+}
+
+/// The find bar's query, its matches and the current one
+#[derive(Clone, Debug, Default)]
+pub struct ChatSearch {
+    pub query: String,
+    pub matches: Vec<SearchMatch>,
+    pub current: Option<usize>,
+}
+
+impl ChatSearch {
+    /// Search again, e.g. after the query or the messages changed. The
+    /// current match stays on the same message when it still has one.
+    pub fn update(&mut self, messages: &[Message], query: &str) {
+        let previous = self.current_match().map(|m| m.message_index);
+        self.query = query.to_string();
+        self.matches = find(messages, query);
+        self.current = if self.matches.is_empty() {
+            None
+        } else {
+            let at = previous.unwrap_or(0);
+            Some(self.matches.iter().position(|m| m.message_index >= at).unwrap_or(0))
+        };
+    }
+
+    /// Move to the next match, or the previous one, wrapping around
+    pub fn step(&mut self, forward: bool) {
+        let count = self.matches.len();
+        if count == 0 {
+            return;
+        }
+        self.current = Some(match self.current {
+            Some(i) if forward => (i + 1) % count,
+            Some(i) => (i + count - 1) % count,
+            None => 0,
+        });
+    }
+
+    pub fn current_match(&self) -> Option<&SearchMatch> {
+        self.matches.get(self.current?)
+    }
+
+    /// e.g. `3 of 12`, or `No matches`
+    pub fn counter(&self) -> String {
+        match self.current {
+            _ if self.query.trim().is_empty() => String::new(),
+            Some(i) => format!("{} of {}", i + 1, self.matches.len()),
+            None => "No matches".to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(text: &str) -> Message {
+        Message {
+            from: EntityId::User,
+            content: MessageContent { text: text.to_string(), ..Default::default() },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_find_and_step() {
+        let messages = vec![message("Rust is fast"), message("Nothing here"), message("RUST, rust and Rüst")];
+        let mut search = ChatSearch::default();
+        search.update(&messages, "rust");
+        assert_eq!(search.matches.len(), 3);
+        assert_eq!(search.counter(), "1 of 3");
+        search.step(false);
+        assert_eq!(search.current_match(), Some(&SearchMatch { message_index: 2, start: 6, end: 10 }));
+        search.step(true);
+        assert_eq!(search.counter(), "1 of 3");
+
+        search.update(&messages, "rüst");
+        assert_eq!(search.matches, [SearchMatch { message_index: 2, start: 15, end: 20 }]);
+        search.update(&messages, "python");
+        assert_eq!(search.counter(), "No matches");
+        search.update(&messages, " ");
+        assert_eq!(search.counter(), "");
+    }
+
+    #[test]
+    fn test_snippet() {
+        let text = "First line\nthe match is here and then more text follows";
+        let m = find(&[message(text)], "MATCH")[0];
+        assert_eq!(snippet(text, &m, 8), ("…ine the ".to_string(), "match".to_string(), " is here…".to_string()));
+        assert_eq!(snippet("match", &find(&[message("match")], "match")[0], 8), (String::new(), "match".to_string(), String::new()));
+    }
+}
@@ -0,0 +1,503 @@
+//! Sharing a chat as a single HTML page
+//!
+//! [`to_html`] renders the chat's messages, the images in them and the UIs
+//! the model drew with A2UI into one file with its styling inline, ready to
+//! attach to an email or publish as is. A2UI snapshots are the fenced JSON
+//! blocks that start rendering a surface; they are drawn with their data
+//! filled in and their controls disabled. With [`ShareOptions::hide_names`]
+//! the authors become "User" and "Assistant" and the given names are taken
+//! out of the text.
+//!
+//! [`latest_surface`] puts the last UI the model drew on a page of its own,
+//! which the chat renders offscreen to a PNG to save or copy.
+
+use std::collections::HashMap;
+
+use base64::Engine;
+use moly_kit::prelude::*;
+use serde_json::Value;
+
+use crate::chat_export;
+use crate::chats::ChatData;
+use crate::code_blocks;
+
+/// What replaces hidden names in the text
+const NAME_PLACEHOLDER: &str = "[name]";
+
+/// Nesting beyond this in an A2UI snapshot is not drawn
+const MAX_A2UI_DEPTH: usize = 16;
+
+/// Width of the page a generated UI is rendered on, in CSS pixels
+pub const SURFACE_WIDTH: u32 = 720;
+
+/// Space around a generated UI on its own page: the page and snapshot
+/// padding and border, and some slack for the height estimate
+const SURFACE_MARGIN: u32 = 74;
+
+#[derive(Clone, Debug, Default)]
+pub struct ShareOptions {
+    /// Label the authors "User" and "Assistant", and replace [`Self::names`]
+    /// in the text
+    pub hide_names: bool,
+    /// Whole words replaced when hiding names, e.g. [`account_names`]
+    pub names: Vec<String>,
+}
+
+/// The login name and home folder name of this account, which show up in
+/// pasted paths and terminal output
+pub fn account_names() -> Vec<String> {
+    let mut names: Vec<String> = ["USER", "USERNAME"].iter().filter_map(|var| std::env::var(var).ok()).collect();
+    if let Some(home) = dirs::home_dir().and_then(|h| h.file_name().map(|n| n.to_string_lossy().into_owned())) {
+        names.push(home);
+    }
+    names.retain(|n| n.chars().count() > 1);
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// `chat` as a standalone HTML page
+pub fn to_html(chat: &ChatData, options: &ShareOptions) -> String {
+    let hide = |text: &str| {
+        if options.hide_names { hide_names(text, &options.names) } else { text.to_string() }
+    };
+    let mut body = format!("<h1>{}</h1>\n", escape(&hide(&chat.title)));
+    body.push_str(&format!(
+        "<p class=\"meta\">Shared from OminiX Studio on {}</p>\n",
+        chrono::Local::now().format("%Y-%m-%d %H:%M")
+    ));
+    if let Some(prompt) = chat.system_prompt.as_deref().filter(|p| !p.trim().is_empty()) {
+        body.push_str(&format!(
+            "<details class=\"system\"><summary>System prompt</summary>{}</details>\n",
+            render_text(&hide(prompt.trim()))
+        ));
+    }
+    for message in &chat.messages {
+        let text = message.content.text.trim();
+        let images = render_images(message, &hide);
+        if message.metadata.is_writing || (text.is_empty() && images.is_empty()) {
+            continue;
+        }
+        let (class, author) = match &message.from {
+            EntityId::User => ("user", if options.hide_names { "User" } else { "You" }.to_string()),
+            EntityId::Bot(_) if options.hide_names => ("bot", "Assistant".to_string()),
+            EntityId::Bot(bot_id) => ("bot", bot_id.id().to_string()),
+            EntityId::System => ("system", "System".to_string()),
+            _ => ("tool", "Tool".to_string()),
+        };
+        body.push_str(&format!(
+            "<article class=\"message {}\">\n<header>{}</header>\n{}{}</article>\n",
+            class,
+            escape(&author),
+            render_text(&hide(text)),
+            images
+        ));
+    }
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+         <title>{}</title>\n<style>{}</style>\n</head>\n<body>\n<main>\n{}</main>\n</body>\n</html>\n",
+        escape(&hide(&chat.title)),
+        STYLE,
+        body
+    )
+}
+
+/// File name for the shared chat, from its title
+pub fn file_name(chat: &ChatData) -> String {
+    format!("{}.html", chat_export::file_stem(chat))
+}
+
+/// A generated UI on a page of its own
+#[derive(Clone, Debug, PartialEq)]
+pub struct SurfacePage {
+    pub html: String,
+    /// Estimated from the components, in CSS pixels; the page is
+    /// [`SURFACE_WIDTH`] wide
+    pub height: u32,
+}
+
+/// The last UI the model drew in `messages`
+pub fn latest_surface(messages: &[Message]) -> Option<SurfacePage> {
+    let (html, height) = messages
+        .iter()
+        .rev()
+        .filter(|m| matches!(m.from, EntityId::Bot(_)))
+        .find_map(|m| code_blocks::extract(&m.content.text).iter().rev().find_map(|b| a2ui_snapshot(&b.code)))?;
+    let html = format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>Generated UI</title>\n\
+         <style>{}</style>\n</head>\n<body>\n<main class=\"surface\"><div class=\"a2ui\">{}</div></main>\n</body>\n</html>\n",
+        STYLE, html
+    );
+    Some(SurfacePage { html, height: height + SURFACE_MARGIN })
+}
+
+/// Replace each of `names` where it stands as a whole word
+fn hide_names(text: &str, names: &[String]) -> String {
+    let mut text = text.to_string();
+    for name in names.iter().filter(|n| !n.is_empty()) {
+        let mut out = String::with_capacity(text.len());
+        let mut rest = text.as_str();
+        while let Some(at) = rest.find(name.as_str()) {
+            let end = at + name.len();
+            let before = rest[..at].chars().next_back();
+            let after = rest[end..].chars().next();
+            let is_word = |c: Option<char>| c.is_some_and(|c| c.is_alphanumeric() || c == '_');
+            out.push_str(&rest[..at]);
+            out.push_str(if is_word(before) || is_word(after) { name } else { NAME_PLACEHOLDER });
+            rest = &rest[end..];
+        }
+        out.push_str(rest);
+        text = out;
+    }
+    text
+}
+
+/// The message's images, embedded in the page
+fn render_images(message: &Message, hide: &dyn Fn(&str) -> String) -> String {
+    let mut out = String::new();
+    for attachment in &message.content.attachments {
+        let Some(mime) = attachment.content_type.as_deref().filter(|m| m.starts_with("image/")) else { continue };
+        let name = escape(&hide(&attachment.name));
+        match futures::executor::block_on(attachment.read()) {
+            Ok(bytes) => out.push_str(&format!(
+                "<figure><img src=\"data:{};base64,{}\" alt=\"{}\"></figure>\n",
+                escape(mime),
+                base64::engine::general_purpose::STANDARD.encode(&bytes[..]),
+                name
+            )),
+            Err(e) => {
+                log::warn!("Image {} left out of the shared chat: {}", attachment.name, e);
+                out.push_str(&format!("<p class=\"missing\">Image not available: {}</p>\n", name));
+            }
+        }
+    }
+    out
+}
+
+/// Paragraphs, line breaks, inline code and fenced code blocks; fenced
+/// A2UI snapshots are drawn
+fn render_text(text: &str) -> String {
+    let mut out = String::new();
+    let mut paragraph: Vec<&str> = Vec::new();
+    let mut code: Option<Vec<&str>> = None;
+    for line in text.lines() {
+        if line.trim_start().starts_with("```") {
+            match code.take() {
+                Some(lines) => out.push_str(&render_code(&lines.join("\n"))),
+                None => {
+                    flush_paragraph(&mut out, &mut paragraph);
+                    code = Some(Vec::new());
+                }
+            }
+        } else if let Some(lines) = code.as_mut() {
+            lines.push(line);
+        } else if line.trim().is_empty() {
+            flush_paragraph(&mut out, &mut paragraph);
+        } else {
+            paragraph.push(line);
+        }
+    }
+    // An unclosed fence runs to the end of the message
+    if let Some(lines) = code {
+        out.push_str(&render_code(&lines.join("\n")));
+    }
+    flush_paragraph(&mut out, &mut paragraph);
+    out
+}
+
+fn flush_paragraph(out: &mut String, paragraph: &mut Vec<&str>) {
+    if paragraph.is_empty() {
+        return;
+    }
+    let lines: Vec<String> = paragraph.drain(..).map(render_inline).collect();
+    out.push_str(&format!("<p>{}</p>\n", lines.join("<br>\n")));
+}
+
+/// A line with `inline code` spans
+fn render_inline(line: &str) -> String {
+    let parts: Vec<&str> = line.split('`').collect();
+    // An odd count of backticks leaves the last one as typed
+    let closed = if parts.len().is_multiple_of(2) { parts.len() - 1 } else { parts.len() };
+    let mut out = String::new();
+    for (i, part) in parts.iter().enumerate() {
+        if i >= closed {
+            out.push('`');
+            out.push_str(&escape(part));
+        } else if i % 2 == 1 {
+            out.push_str(&format!("<code>{}</code>", escape(part)));
+        } else {
+            out.push_str(&escape(part));
+        }
+    }
+    out
+}
+
+fn render_code(code: &str) -> String {
+    match a2ui_snapshot(code) {
+        Some((html, _)) => format!("<div class=\"a2ui\">{}</div>\n", html),
+        None => format!("<pre><code>{}</code></pre>\n", escape(code)),
+    }
+}
+
+// ─── A2UI ─────────────────────────────────────────────────────────────────────
+
+/// The UI an A2UI message list draws and its estimated height, if `code`
+/// is one
+fn a2ui_snapshot(code: &str) -> Option<(String, u32)> {
+    let messages: Vec<Value> = serde_json::from_str(code).ok()?;
+    let root = messages.iter().find_map(|m| m["beginRendering"]["root"].as_str())?;
+    let mut components = HashMap::new();
+    let mut data = HashMap::new();
+    for message in &messages {
+        for component in message["surfaceUpdate"]["components"].as_array().into_iter().flatten() {
+            if let Some(id) = component["id"].as_str() {
+                components.insert(id, &component["component"]);
+            }
+        }
+        for content in message["dataModelUpdate"]["contents"].as_array().into_iter().flatten() {
+            if let Some(key) = content["key"].as_str() {
+                let value = ["valueString", "valueNumber", "valueBoolean"].iter().find_map(|k| content.get(*k));
+                if let Some(value) = value {
+                    data.insert(key, value);
+                }
+            }
+        }
+    }
+    let surface = Surface { components, data };
+    Some((surface.render(root, 0), surface.height(root, 0)))
+}
+
+struct Surface<'a> {
+    components: HashMap<&'a str, &'a Value>,
+    data: HashMap<&'a str, &'a Value>,
+}
+
+impl Surface<'_> {
+    fn render(&self, id: &str, depth: usize) -> String {
+        let Some((kind, props)) = self.components.get(id).and_then(|c| c.as_object()?.iter().next()) else {
+            return String::new();
+        };
+        if depth > MAX_A2UI_DEPTH {
+            return String::new();
+        }
+        let child = |key: &str| props[key].as_str().map(|c| self.render(c, depth + 1)).unwrap_or_default();
+        match kind.as_str() {
+            "Text" => {
+                let hint = props["usageHint"].as_str().unwrap_or("body");
+                let class: String = hint.chars().filter(|c| c.is_ascii_alphanumeric()).collect();
+                format!("<div class=\"a2ui-text {}\">{}</div>", class, escape(&self.text(&props["text"])))
+            }
+            "Button" => {
+                let primary = if props["primary"].as_bool() == Some(true) { " class=\"primary\"" } else { "" };
+                format!("<button{} disabled>{}</button>", primary, child("child"))
+            }
+            "TextField" => format!(
+                "<input type=\"text\" disabled placeholder=\"{}\" value=\"{}\">",
+                escape(&self.text(&props["placeholder"])),
+                escape(&self.text(&props["value"]))
+            ),
+            "CheckBox" => {
+                let checked = self.value(&props["checked"]).and_then(Value::as_bool) == Some(true);
+                format!(
+                    "<label><input type=\"checkbox\" disabled{}> {}</label>",
+                    if checked { " checked" } else { "" },
+                    escape(&self.text(&props["label"]))
+                )
+            }
+            "Slider" => {
+                let number = |key: &str| self.value(&props[key]).and_then(Value::as_f64);
+                let (min, max) = (number("min").unwrap_or(0.0), number("max").unwrap_or(100.0));
+                format!(
+                    "<input type=\"range\" disabled min=\"{}\" max=\"{}\" value=\"{}\">",
+                    min,
+                    max,
+                    number("value").unwrap_or(min)
+                )
+            }
+            "Card" => format!("<div class=\"a2ui-card\">{}</div>", child("child")),
+            "Column" | "Row" => {
+                let children: String = children(props).map(|c| self.render(c, depth + 1)).collect();
+                format!("<div class=\"a2ui-{}\">{}</div>", kind.to_lowercase(), children)
+            }
+            _ => String::new(),
+        }
+    }
+
+    /// Roughly how tall the page draws a component, in CSS pixels
+    fn height(&self, id: &str, depth: usize) -> u32 {
+        let Some((kind, props)) = self.components.get(id).and_then(|c| c.as_object()?.iter().next()) else {
+            return 0;
+        };
+        if depth > MAX_A2UI_DEPTH {
+            return 0;
+        }
+        let heights = || children(props).map(|c| self.height(c, depth + 1));
+        match kind.as_str() {
+            "Text" => match props["usageHint"].as_str() {
+                Some("h1") => 34,
+                Some("h2") => 28,
+                Some("h3") => 26,
+                Some("caption") => 18,
+                _ => 24,
+            },
+            "Button" | "TextField" | "Slider" => 34,
+            "CheckBox" => 24,
+            "Card" => props["child"].as_str().map(|c| self.height(c, depth + 1)).unwrap_or(0) + 26,
+            "Column" => heights().sum::<u32>() + 8 * (heights().count() as u32).saturating_sub(1),
+            "Row" => heights().max().unwrap_or(0),
+            _ => 0,
+        }
+    }
+
+    /// A literal, or the data a path points to
+    fn value<'v>(&'v self, bound: &'v Value) -> Option<&'v Value> {
+        if let Some(path) = bound["path"].as_str() {
+            return self.data.get(path).copied();
+        }
+        ["literalString", "literalNumber", "literalBoolean"].iter().find_map(|k| bound.get(*k))
+    }
+
+    fn text(&self, bound: &Value) -> String {
+        match self.value(bound) {
+            Some(Value::String(s)) => s.clone(),
+            Some(Value::Null) | None => String::new(),
+            Some(other) => other.to_string(),
+        }
+    }
+}
+
+/// The ids a Column or Row lists
+fn children(props: &Value) -> impl Iterator<Item = &str> {
+    props["children"]["explicitList"].as_array().into_iter().flatten().filter_map(Value::as_str)
+}
+
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+const STYLE: &str = "
+:root { color-scheme: light dark; --bg: #f7f7f8; --card: #ffffff; --user: #e8f0fe; --text: #1f2328; --muted: #6b7280; --line: #d8dee4; --accent: #3b82f6; }
+@media (prefers-color-scheme: dark) { :root { --bg: #16181d; --card: #20232a; --user: #1e2a44; --text: #e6e6e6; --muted: #9aa3ad; --line: #343a44; } }
+* { box-sizing: border-box; }
+body { margin: 0; background: var(--bg); color: var(--text); font: 15px/1.55 -apple-system, BlinkMacSystemFont, 'Segoe UI', Roboto, sans-serif; }
+main { max-width: 760px; margin: 0 auto; padding: 32px 16px 64px; }
+h1 { font-size: 24px; margin: 0 0 4px; }
+.meta { color: var(--muted); font-size: 13px; margin: 0 0 24px; }
+.system { color: var(--muted); margin-bottom: 16px; }
+.message { background: var(--card); border: 1px solid var(--line); border-radius: 10px; padding: 12px 16px; margin: 12px 0; }
+.message.user { background: var(--user); }
+.message > header { font-weight: 600; font-size: 13px; color: var(--muted); margin-bottom: 4px; }
+p { margin: 8px 0; overflow-wrap: anywhere; }
+code { font: 13px ui-monospace, SFMono-Regular, Menlo, monospace; background: rgba(127, 127, 127, 0.15); padding: 1px 4px; border-radius: 4px; }
+pre { background: rgba(127, 127, 127, 0.12); padding: 12px; border-radius: 8px; overflow-x: auto; }
+pre code { background: none; padding: 0; }
+figure { margin: 8px 0; }
+img { max-width: 100%; border-radius: 8px; }
+.missing { color: var(--muted); font-style: italic; }
+.a2ui { border: 1px dashed var(--line); border-radius: 8px; padding: 12px; margin: 8px 0; }
+.surface { max-width: none; padding: 16px; }
+.surface .a2ui { margin: 0; }
+.a2ui-column { display: flex; flex-direction: column; gap: 8px; }
+.a2ui-row { display: flex; flex-direction: row; flex-wrap: wrap; align-items: center; gap: 8px; }
+.a2ui-card { border: 1px solid var(--line); border-radius: 8px; padding: 12px; }
+.a2ui-text.h1 { font-size: 22px; font-weight: 600; }
+.a2ui-text.h2 { font-size: 18px; font-weight: 600; }
+.a2ui-text.h3 { font-size: 16px; font-weight: 600; }
+.a2ui-text.caption { font-size: 12px; color: var(--muted); }
+button { font: inherit; padding: 4px 12px; border-radius: 6px; border: 1px solid var(--line); background: var(--card); color: var(--text); }
+button.primary { background: var(--accent); border-color: var(--accent); color: #ffffff; }
+";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(from: EntityId, text: &str) -> Message {
+        Message { from, content: MessageContent { text: text.to_string(), ..Default::default() }, ..Default::default() }
+    }
+
+    #[test]
+    fn test_html_hides_names() {
+        let mut chat = ChatData::with_title("Paths <on> macOS".to_string());
+        chat.messages.push(message(EntityId::User, "Why is /Users/ada/x.rs slow?\nSee `cargo build`"));
+        chat.messages.push(message(EntityId::Bot(BotId::new("qwen3-8b")), "```\nfn main() {}\n```"));
+
+        let shown = to_html(&chat, &ShareOptions::default());
+        assert!(shown.contains("<title>Paths &lt;on&gt; macOS</title>"));
+        assert!(shown.contains("<header>You</header>\n<p>Why is /Users/ada/x.rs slow?<br>\nSee <code>cargo build</code></p>"));
+        assert!(shown.contains("<header>qwen3-8b</header>\n<pre><code>fn main() {}</code></pre>"));
+
+        let options = ShareOptions { hide_names: true, names: vec!["ada".to_string()] };
+        let hidden = to_html(&chat, &options);
+        assert!(hidden.contains("<header>User</header>\n<p>Why is /Users/[name]/x.rs slow?"));
+        assert!(hidden.contains("<header>Assistant</header>"));
+        assert!(!hidden.contains("qwen3-8b"));
+        assert_eq!(hide_names("ada, adam and Ada", &options.names), "[name], adam and Ada");
+        assert_eq!(file_name(&chat), "Paths-on-macOS.html");
+    }
+
+    #[test]
+    fn test_a2ui_snapshot() {
+        let snapshot = r#"[
+            {"beginRendering": {"surfaceId": "canvas", "root": "row"}},
+            {"surfaceUpdate": {"surfaceId": "canvas", "components": [
+                {"id": "label", "component": {"Text": {"text": {"literalString": "Volume"}, "usageHint": "body"}}},
+                {"id": "slider", "component": {"Slider": {"min": {"literalNumber": 0}, "max": {"literalNumber": 100}, "value": {"path": "/volume"}}}},
+                {"id": "row", "component": {"Row": {"children": {"explicitList": ["label", "slider"]}}}}
+            ]}},
+            {"dataModelUpdate": {"surfaceId": "canvas", "path": "/", "contents": [{"key": "/volume", "valueNumber": 50}]}}
+        ]"#;
+        assert_eq!(
+            a2ui_snapshot(snapshot).unwrap(),
+            (
+                "<div class=\"a2ui-row\"><div class=\"a2ui-text body\">Volume</div>\
+                 <input type=\"range\" disabled min=\"0\" max=\"100\" value=\"50\"></div>"
+                    .to_string(),
+                34
+            )
+        );
+        assert_eq!(a2ui_snapshot("[1, 2]"), None);
+        assert!(render_text(&format!("```json\n{}\n```", snapshot)).starts_with("<div class=\"a2ui\">"));
+
+        let answer = message(EntityId::Bot(BotId::new("qwen3-8b")), &format!("Here:\n```json\n{}\n```", snapshot));
+        let page = latest_surface(&[answer, message(EntityId::User, "thanks")]).unwrap();
+        assert_eq!(page.height, 34 + SURFACE_MARGIN);
+        assert!(page.html.contains("<main class=\"surface\"><div class=\"a2ui\"><div class=\"a2ui-row\">"));
+        assert_eq!(latest_surface(&[message(EntityId::User, "```\nfn main() {}\n```")]), None);
+    }
+
+    /// The page is sized from the components, and only the model's last UI
+    /// is rendered
+    #[test]
+    fn test_latest_surface_height() {
+        let form = r#"[
+            {"surfaceUpdate": {"components": [
+                {"id": "root", "component": {"Card": {"child": "col"}}},
+                {"id": "col", "component": {"Column": {"children": {"explicitList": ["title", "name", "send"]}}}},
+                {"id": "title", "component": {"Text": {"text": {"literalString": "Sign up"}, "usageHint": "h1"}}},
+                {"id": "name", "component": {"TextField": {"label": {"literalString": "Name"}}}},
+                {"id": "send", "component": {"Button": {"child": "title"}}}
+            ]}},
+            {"beginRendering": {"root": "root"}}
+        ]"#;
+        let bot = EntityId::Bot(BotId::new("qwen3-8b"));
+        let older = message(bot.clone(), "```json\n[{\"beginRendering\": {\"root\": \"x\"}}]\n```");
+        let latest = message(bot, &format!("```json\n{}\n```", form));
+        let page = latest_surface(&[older, latest]).unwrap();
+        // Card padding + h1, field and button with two gaps between them
+        assert_eq!(page.height, 26 + (34 + 34 + 34 + 2 * 8) + SURFACE_MARGIN);
+        assert!(page.html.contains("Sign up"));
+    }
+}
@@ -0,0 +1,256 @@
+//! Chat templates for local models that take formatted prompts
+//!
+//! Most local models are served on `/v1/chat/completions`, where the runtime
+//! formats the conversation itself. Models whose registry entry uses the
+//! `completions` API take a finished prompt on `/v1/completions` instead;
+//! [`ChatTemplate::render`] writes the conversation in the format the model
+//! was trained on. The template comes from the registry or is guessed from
+//! the model id, and can be chosen per model in the Hub's LLM panel
+//! ([`ChatTemplateOverrides`]); choosing one also sends that model
+//! formatted prompts.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use crate::model_registry::{ApiType, ModelRegistry, RegistryModel};
+use crate::ominix_api_client::{ChatCompletionRequest, ChatMessage, CompletionRequest, OminiXApiClient, OminiXApiError, Usage};
+
+const OVERRIDES_FILENAME: &str = "chat_templates.json";
+
+/// Default system prompt of Qwen's template
+const QWEN_SYSTEM: &str = "You are a helpful assistant.";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChatTemplate {
+    ChatMl,
+    Llama3,
+    Qwen,
+    Mistral,
+    Gemma,
+}
+
+impl ChatTemplate {
+    pub const ALL: [ChatTemplate; 5] = [Self::ChatMl, Self::Llama3, Self::Qwen, Self::Mistral, Self::Gemma];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::ChatMl => "ChatML",
+            Self::Llama3 => "Llama 3",
+            Self::Qwen => "Qwen",
+            Self::Mistral => "Mistral",
+            Self::Gemma => "Gemma",
+        }
+    }
+
+    /// The template a model id suggests, e.g. Llama 3 for `llama-3.2-3b`
+    pub fn guess(model_id: &str) -> Option<Self> {
+        let id = model_id.to_lowercase();
+        if id.contains("llama-3") || id.contains("llama3") {
+            Some(Self::Llama3)
+        } else if id.contains("qwen") {
+            Some(Self::Qwen)
+        } else if id.contains("mistral") || id.contains("mixtral") {
+            Some(Self::Mistral)
+        } else if id.contains("gemma") {
+            Some(Self::Gemma)
+        } else {
+            None
+        }
+    }
+
+    /// Tokens that end the model's turn
+    pub fn stop(&self) -> &'static [&'static str] {
+        match self {
+            Self::ChatMl | Self::Qwen => &["<|im_end|>"],
+            Self::Llama3 => &["<|eot_id|>"],
+            Self::Mistral => &["</s>"],
+            Self::Gemma => &["<end_of_turn>"],
+        }
+    }
+
+    /// The conversation as (role, text) pairs, formatted up to where the
+    /// assistant's answer starts
+    pub fn render(&self, messages: &[(String, String)]) -> String {
+        let mut prompt = String::new();
+        match self {
+            Self::ChatMl | Self::Qwen => {
+                if *self == Self::Qwen && messages.first().map_or(true, |(role, _)| role != "system") {
+                    prompt.push_str(&format!("<|im_start|>system\n{}<|im_end|>\n", QWEN_SYSTEM));
+                }
+                for (role, text) in messages {
+                    prompt.push_str(&format!("<|im_start|>{}\n{}<|im_end|>\n", role, text));
+                }
+                prompt.push_str("<|im_start|>assistant\n");
+            }
+            Self::Llama3 => {
+                prompt.push_str("<|begin_of_text|>");
+                for (role, text) in messages {
+                    prompt.push_str(&format!("<|start_header_id|>{}<|end_header_id|>\n\n{}<|eot_id|>", role, text));
+                }
+                prompt.push_str("<|start_header_id|>assistant<|end_header_id|>\n\n");
+            }
+            Self::Mistral => {
+                prompt.push_str("<s>");
+                for (role, text) in without_system(messages) {
+                    if role == "assistant" {
+                        prompt.push_str(&format!(" {}</s>", text));
+                    } else {
+                        prompt.push_str(&format!("[INST] {} [/INST]", text));
+                    }
+                }
+            }
+            Self::Gemma => {
+                prompt.push_str("<bos>");
+                for (role, text) in without_system(messages) {
+                    let role = if role == "assistant" { "model" } else { "user" };
+                    prompt.push_str(&format!("<start_of_turn>{}\n{}<end_of_turn>\n", role, text));
+                }
+                prompt.push_str("<start_of_turn>model\n");
+            }
+        }
+        prompt
+    }
+}
+
+/// For templates without a system role: system messages go at the start of
+/// the first user message
+fn without_system(messages: &[(String, String)]) -> Vec<(String, String)> {
+    let system: Vec<&str> = messages.iter().filter(|(role, _)| role == "system").map(|(_, text)| text.as_str()).collect();
+    let mut rest: Vec<(String, String)> = messages.iter().filter(|(role, _)| role != "system").cloned().collect();
+    if !system.is_empty() {
+        let system = system.join("\n\n");
+        match rest.iter_mut().find(|(role, _)| role == "user") {
+            Some((_, text)) => *text = format!("{}\n\n{}", system, text),
+            None => rest.insert(0, ("user".to_string(), system)),
+        }
+    }
+    rest
+}
+
+/// Templates chosen per model in the Hub, by registry id
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ChatTemplateOverrides {
+    #[serde(default)]
+    pub models: BTreeMap<String, ChatTemplate>,
+}
+
+impl ChatTemplateOverrides {
+    pub fn path() -> PathBuf {
+        crate::profiles::data_dir().join(OVERRIDES_FILENAME)
+    }
+
+    pub fn load() -> Self {
+        std::fs::read_to_string(Self::path())
+            .ok()
+            .and_then(|contents| match serde_json::from_str(&contents) {
+                Ok(overrides) => Some(overrides),
+                Err(e) => {
+                    log::error!("Failed to parse chat template overrides: {:?}", e);
+                    None
+                }
+            })
+            .unwrap_or_default()
+    }
+
+    /// Choose a template for a model, or go back to the registry's with `None`, and save
+    pub fn set(&mut self, model_id: &str, template: Option<ChatTemplate>) -> Result<(), String> {
+        match template {
+            Some(template) => self.models.insert(model_id.to_string(), template),
+            None => self.models.remove(model_id),
+        };
+        let path = Self::path();
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+        }
+        let json = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        std::fs::write(&path, json).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+}
+
+/// The template to format prompts for `model` with, or `None` when the
+/// runtime formats them itself
+pub fn template_for(model: &RegistryModel, overrides: &ChatTemplateOverrides) -> Option<ChatTemplate> {
+    if let Some(template) = overrides.models.get(&model.id) {
+        return Some(*template);
+    }
+    (model.runtime.api_type == ApiType::Completions).then(|| {
+        model.runtime.chat_template
+            .or_else(|| ChatTemplate::guess(&model.runtime.api_model_id))
+            .unwrap_or(ChatTemplate::ChatMl)
+    })
+}
+
+/// Like [`template_for`], looking the model up by registry or API id
+pub fn template_for_id(model_id: &str) -> Option<ChatTemplate> {
+    let registry = ModelRegistry::load();
+    let model = registry.models.iter().find(|m| m.id == model_id || m.runtime.api_model_id == model_id)?;
+    template_for(model, &ChatTemplateOverrides::load())
+}
+
+/// Send a conversation to `model`: formatted with `template` to
+/// `/v1/completions` when there is one, otherwise to `/v1/chat/completions`
+pub fn complete(
+    client: &OminiXApiClient,
+    model: &str,
+    template: Option<ChatTemplate>,
+    messages: Vec<ChatMessage>,
+) -> Result<String, OminiXApiError> {
+    complete_with_usage(client, model, template, messages).map(|(text, _)| text)
+}
+
+/// [`complete`], with the token counts when the server reports them
+pub fn complete_with_usage(
+    client: &OminiXApiClient,
+    model: &str,
+    template: Option<ChatTemplate>,
+    messages: Vec<ChatMessage>,
+) -> Result<(String, Option<Usage>), OminiXApiError> {
+    let Some(template) = template else {
+        return client.chat_completion_with_usage(&ChatCompletionRequest { model: model.to_string(), messages, max_tokens: None });
+    };
+    let turns: Vec<(String, String)> = messages.iter().map(|m| (m.role.clone(), m.text())).collect();
+    let request = CompletionRequest {
+        model: model.to_string(),
+        prompt: template.render(&turns),
+        stop: template.stop().iter().map(|s| s.to_string()).collect(),
+        max_tokens: None,
+    };
+    client.completion_with_usage(&request).map(|(text, usage)| (text.trim().to_string(), usage))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn turns() -> Vec<(String, String)> {
+        vec![
+            ("system".to_string(), "Be brief.".to_string()),
+            ("user".to_string(), "Hi".to_string()),
+        ]
+    }
+
+    #[test]
+    fn test_render_templates() {
+        assert_eq!(
+            ChatTemplate::ChatMl.render(&turns()),
+            "<|im_start|>system\nBe brief.<|im_end|>\n<|im_start|>user\nHi<|im_end|>\n<|im_start|>assistant\n"
+        );
+        assert_eq!(
+            ChatTemplate::Llama3.render(&turns()),
+            "<|begin_of_text|><|start_header_id|>system<|end_header_id|>\n\nBe brief.<|eot_id|>\
+             <|start_header_id|>user<|end_header_id|>\n\nHi<|eot_id|><|start_header_id|>assistant<|end_header_id|>\n\n"
+        );
+        assert_eq!(ChatTemplate::Mistral.render(&turns()), "<s>[INST] Be brief.\n\nHi [/INST]");
+        assert_eq!(
+            ChatTemplate::Gemma.render(&turns()[1..]),
+            "<bos><start_of_turn>user\nHi<end_of_turn>\n<start_of_turn>model\n"
+        );
+        assert!(ChatTemplate::Qwen.render(&turns()[1..]).starts_with("<|im_start|>system\nYou are a helpful assistant."));
+
+        assert_eq!(ChatTemplate::guess("Meta-Llama-3.1-8B"), Some(ChatTemplate::Llama3));
+        assert_eq!(ChatTemplate::guess("phi-4-mini"), None);
+        assert_eq!(ChatTemplate::guess("qwen3-4b-q8"), Some(ChatTemplate::Qwen));
+    }
+}
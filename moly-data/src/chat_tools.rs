@@ -0,0 +1,278 @@
+//! Tools offered to chat models
+//!
+//! [`definitions`] lists the functions a chat's tool selection offers: the
+//! built-in tools ([`crate::builtin_tools`]) and the tools of its MCP servers
+//! ([`crate::mcp_client`]); [`call`] runs one. Agent mode uses them for its
+//! runs. In ordinary chats [`ToolsClient`] offers the functions of the chat
+//! on screen ([`crate::toolsets::active`]) with each request and runs the
+//! calls the model makes until it answers.
+//!
+//! Tool rounds are not streamed: the answer appears once the model stops
+//! calling tools. Conversations with attachments, and requests that already
+//! carry tools (A2UI), go to the provider unchanged.
+
+use futures::StreamExt;
+use moly_kit::aitk::protocol::*;
+use moly_kit::aitk::utils::asynchronous::{BoxPlatformSendFuture, BoxPlatformSendStream};
+use serde_json::{json, Value};
+use std::time::Duration;
+
+use crate::agent;
+use crate::builtin_tools;
+use crate::chat_defaults::{self, Sampling};
+use crate::chats::ChatId;
+use crate::mcp_client::{self, McpTool};
+use crate::toolsets::{self, BuiltinTool, ToolSelection};
+
+/// Model calls per message at most before it must answer without tools
+pub const MAX_ROUNDS: usize = 8;
+
+/// Tool output sent back to the model at most, in characters
+pub const MAX_RESULT_CHARS: usize = 16_000;
+
+/// Function definitions for the tools in `tools`. Starts the MCP servers
+/// named there, so call it off the UI thread.
+pub fn definitions(tools: &ToolSelection) -> Vec<Value> {
+    let mut definitions = builtin_tools::definitions(tools);
+    definitions.extend(mcp_client::tools(&tools.mcp_servers).iter().map(McpTool::definition));
+    definitions
+}
+
+/// Whether `tools` offers the model any functions. A2UI is offered by its
+/// own client.
+pub fn offers_functions(tools: &ToolSelection) -> bool {
+    tools.has_builtin(BuiltinTool::Files) || tools.has_builtin(BuiltinTool::CodeExecution) || !tools.mcp_servers.is_empty()
+}
+
+/// Run a function call the model made in the chat `chat_id`. `args` is
+/// the JSON object it sent.
+pub fn call(chat_id: Option<ChatId>, name: &str, args: &Value) -> Result<String, String> {
+    if name.is_empty() {
+        return Err("The call names no function".to_string());
+    }
+    match mcp_client::call(name, args) {
+        Some(result) => result,
+        None => builtin_tools::call(chat_id, name, args),
+    }
+}
+
+/// A call's output as sent back to the model, cut to [`MAX_RESULT_CHARS`]
+pub fn result_text(result: Result<String, String>) -> String {
+    let output = result.unwrap_or_else(|e| format!("Error: {}", e));
+    if output.chars().count() > MAX_RESULT_CHARS {
+        output.chars().take(MAX_RESULT_CHARS).collect::<String>() + "\n… output cut off"
+    } else {
+        output
+    }
+}
+
+/// The body of a chat completion request, with the chat's `sampling`
+fn request_body(model: &str, messages: &[Value], tools: &[Value], sampling: Sampling) -> Value {
+    let mut body = json!({ "model": model, "messages": messages });
+    if !tools.is_empty() {
+        body["tools"] = json!(tools);
+    }
+    if let Some(temperature) = sampling.temperature {
+        body["temperature"] = temperature.into();
+    }
+    if let Some(max_tokens) = sampling.max_tokens {
+        body["max_tokens"] = max_tokens.into();
+    }
+    body
+}
+
+/// One chat completion against an OpenAI-compatible `base_url`, sampled as
+/// the open chat ([`chat_defaults::active`]); returns the reply message
+#[cfg(not(target_arch = "wasm32"))]
+pub fn complete(base_url: &str, api_key: Option<&str>, model: &str, messages: &[Value], tools: &[Value]) -> Result<Value, String> {
+    let body = request_body(model, messages, tools, chat_defaults::active());
+    let client = crate::network::blocking_client()
+        .timeout(Duration::from_secs(300))
+        .build()
+        .map_err(|e| e.to_string())?;
+    let url = format!("{}/chat/completions", base_url.trim_end_matches('/'));
+    let mut post = client.post(&url).json(&body);
+    if let Some(key) = api_key.filter(|k| !k.is_empty()) {
+        post = post.bearer_auth(key);
+    }
+    let response = post.send().map_err(|e| format!("Could not reach {}: {}", url, e))?;
+    let status = response.status();
+    let text = response.text().map_err(|e| e.to_string())?;
+    if !status.is_success() {
+        return Err(format!("{} returned {}: {}", url, status, text.chars().take(500).collect::<String>()));
+    }
+    let value: Value = serde_json::from_str(&text).map_err(|e| format!("Unexpected response from {}: {}", url, e))?;
+    value.pointer("/choices/0/message").cloned()
+        .ok_or_else(|| format!("No reply in the response from {}", url))
+}
+
+/// Tool calls use blocking HTTP, which the browser build lacks
+#[cfg(target_arch = "wasm32")]
+pub fn complete(_base_url: &str, _api_key: Option<&str>, _model: &str, _messages: &[Value], _tools: &[Value]) -> Result<Value, String> {
+    Err("Tools aren't available in the browser".to_string())
+}
+
+/// Complete `messages`, running the calls the model makes, for at most
+/// [`MAX_ROUNDS`] rounds. `progress` hears the name of each call before it
+/// runs. Returns the answer.
+fn run_rounds(
+    messages: &mut Vec<Value>,
+    tools: &[Value],
+    complete: &mut dyn FnMut(&[Value], &[Value]) -> Result<Value, String>,
+    call: &dyn Fn(&str, &Value) -> Result<String, String>,
+    progress: &dyn Fn(&str),
+) -> Result<String, String> {
+    for _ in 0..MAX_ROUNDS {
+        let reply = complete(messages, tools)?;
+        let calls = agent::tool_calls(&reply);
+        if calls.is_empty() {
+            return Ok(reply.get("content").and_then(Value::as_str).unwrap_or_default().trim().to_string());
+        }
+        messages.push(reply);
+        for tool_call in calls {
+            progress(&tool_call.name);
+            let output = result_text(call(&tool_call.name, &tool_call.args));
+            messages.push(json!({ "role": "tool", "tool_call_id": tool_call.id, "content": output }));
+        }
+    }
+    let reply = complete(messages, &[])?;
+    Ok(reply.get("content").and_then(Value::as_str).unwrap_or_default().trim().to_string())
+}
+
+fn role(from: &EntityId) -> Option<&'static str> {
+    match from {
+        EntityId::User => Some("user"),
+        EntityId::Bot(_) => Some("assistant"),
+        EntityId::System => Some("system"),
+        _ => None,
+    }
+}
+
+/// Offers the active chat's tools with each request to an OpenAI-compatible
+/// provider and runs the calls the model makes
+pub struct ToolsClient {
+    inner: Box<dyn BotClient>,
+    base_url: String,
+    api_key: Option<String>,
+}
+
+impl ToolsClient {
+    pub fn new(inner: Box<dyn BotClient>, base_url: &str, api_key: Option<&str>) -> Self {
+        Self { inner, base_url: base_url.to_string(), api_key: api_key.map(str::to_string) }
+    }
+}
+
+impl Clone for ToolsClient {
+    fn clone(&self) -> Self {
+        Self { inner: self.inner.clone_box(), base_url: self.base_url.clone(), api_key: self.api_key.clone() }
+    }
+}
+
+impl BotClient for ToolsClient {
+    fn bots(&mut self) -> BoxPlatformSendFuture<'static, ClientResult<Vec<Bot>>> {
+        self.inner.bots()
+    }
+
+    fn send(
+        &mut self,
+        bot_id: &BotId,
+        messages: &[Message],
+        tools: &[Tool],
+    ) -> BoxPlatformSendStream<'static, ClientResult<MessageContent>> {
+        let selection = toolsets::active().filter(offers_functions);
+        let plain = tools.is_empty() && messages.iter().all(|m| m.content.attachments.is_empty());
+        let Some(selection) = selection.filter(|_| plain) else {
+            return self.inner.send(bot_id, messages, tools);
+        };
+        let mut conversation: Vec<Value> = messages
+            .iter()
+            .filter(|m| !m.content.text.trim().is_empty())
+            .filter_map(|m| Some(json!({ "role": role(&m.from)?, "content": m.content.text })))
+            .collect();
+        let (base_url, api_key, model) = (self.base_url.clone(), self.api_key.clone(), bot_id.id().to_string());
+        let chat_id = toolsets::active_chat();
+
+        // Tools block, so the rounds run on their own thread
+        let (tx, mut rx) = futures::channel::mpsc::unbounded();
+        std::thread::spawn(move || {
+            let functions = definitions(&selection);
+            log::info!("Chat request to {} with {} tool functions", model, functions.len());
+            let progress = |name: &str| {
+                let text = format!("_Running {}…_", name);
+                let _ = tx.unbounded_send(ClientResult::new_ok(MessageContent { text, ..Default::default() }));
+            };
+            let mut ask = |messages: &[Value], tools: &[Value]| complete(&base_url, api_key.as_deref(), &model, messages, tools);
+            let call = |name: &str, args: &Value| call(chat_id, name, args);
+            let result = match run_rounds(&mut conversation, &functions, &mut ask, &call, &progress) {
+                Ok(text) => ClientResult::new_ok(MessageContent { text, ..Default::default() }),
+                Err(e) => ClientResult::new_err(ClientError::new(ClientErrorKind::Response, e).into()),
+            };
+            let _ = tx.unbounded_send(result);
+        });
+        Box::pin(async_stream::stream! {
+            while let Some(result) = rx.next().await {
+                yield result;
+            }
+        })
+    }
+
+    fn clone_box(&self) -> Box<dyn BotClient> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    #[test]
+    fn test_definitions_follow_selection() {
+        let files = ToolSelection { builtins: vec![BuiltinTool::A2ui, BuiltinTool::Files], mcp_servers: vec!["not-set-up".into()] };
+        let names: Vec<String> = definitions(&files).iter()
+            .filter_map(|d| d["function"]["name"].as_str().map(str::to_string))
+            .collect();
+        assert!(!names.is_empty() && names.iter().all(|n| n.starts_with("fs_")), "{:?}", names);
+        assert!(offers_functions(&files));
+
+        let a2ui_only = ToolSelection { builtins: vec![BuiltinTool::A2ui], mcp_servers: vec![] };
+        assert!(definitions(&a2ui_only).is_empty());
+        assert!(!offers_functions(&a2ui_only));
+    }
+
+    #[test]
+    fn test_request_body() {
+        let messages = [json!({ "role": "user", "content": "Hi" })];
+        let sampling = Sampling { temperature: Some(0.25), max_tokens: Some(256) };
+        let body = request_body("gpt-4o-mini", &messages, &[json!({ "type": "function" })], sampling);
+        assert_eq!(body["temperature"], 0.25);
+        assert_eq!(body["max_tokens"], 256);
+        assert_eq!(body["tools"].as_array().map(Vec::len), Some(1));
+
+        let body = request_body("gpt-4o-mini", &messages, &[], Sampling::default());
+        assert_eq!(body, json!({ "model": "gpt-4o-mini", "messages": messages }));
+    }
+
+    #[test]
+    fn test_rounds_run_calls_until_answer() {
+        let replies = RefCell::new(vec![
+            json!({ "role": "assistant", "content": "Here it is.", "tool_calls": null }),
+            json!({ "role": "assistant", "content": null, "tool_calls": [
+                { "id": "call-1", "type": "function", "function": { "name": "fs_read_file", "arguments": "{\"path\":\"a.md\"}" } }
+            ] }),
+        ]);
+        let mut complete = |_: &[Value], _: &[Value]| Ok(replies.borrow_mut().pop().unwrap());
+        let called = RefCell::new(Vec::new());
+        let call = |name: &str, args: &Value| {
+            called.borrow_mut().push(format!("{} {}", name, args["path"]));
+            Ok("contents".to_string())
+        };
+        let mut messages = vec![json!({ "role": "user", "content": "Read a.md" })];
+
+        let answer = run_rounds(&mut messages, &[json!({})], &mut complete, &call, &|_| {}).unwrap();
+        assert_eq!(answer, "Here it is.");
+        assert_eq!(called.into_inner(), ["fs_read_file \"a.md\""]);
+        assert_eq!(messages.len(), 3);
+        assert_eq!(messages[2], json!({ "role": "tool", "tool_call_id": "call-1", "content": "contents" }));
+    }
+}
@@ -1,14 +1,36 @@
 use chrono::{DateTime, Utc};
 use moly_kit::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
-
+use std::path::{Path, PathBuf};
+
+use crate::answer_versions::{self, AnswerVersions};
+use crate::bookmarks::Bookmark;
+use crate::chat_defaults::Sampling;
+use crate::chat_navigation::{self, DayMark};
+use crate::citations::{MessageSources, Source};
+use crate::fs_tool::TouchedPath;
+use crate::message_stats::MessageStats;
 use crate::model_registry::RegistryCategory;
+use crate::privacy_filter::Decisions;
+use crate::profiles;
+use crate::session_templates::SessionTemplate;
+use crate::toolsets::ToolSelection;
 
 pub type ChatId = u128;
 
 const CHATS_DIR: &str = "chats";
 
+/// Sent prompts kept per chat for recall
+const MAX_SENT_PROMPTS: usize = 100;
+
+/// Write through a temporary file and rename, so an app killed mid-write
+/// never leaves a truncated chat behind
+pub(crate) fn write_atomic(path: &Path, contents: &[u8]) -> Result<(), String> {
+    let tmp = path.with_extension("tmp");
+    std::fs::write(&tmp, contents).map_err(|e| format!("{}: {}", tmp.display(), e))?;
+    std::fs::rename(&tmp, path).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
 /// Serializable chat data for persistence
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct ChatData {
@@ -17,6 +39,45 @@ pub struct ChatData {
     pub bot_id: Option<BotId>,
     #[serde(default)]
     pub model_category: Option<RegistryCategory>,
+    /// Sent to the model ahead of the conversation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system_prompt: Option<String>,
+    /// Temperature and answer length; the model's own when unset
+    #[serde(default, skip_serializing_if = "Sampling::is_default")]
+    pub sampling: Sampling,
+    /// Values redacted from, or allowed in, messages to cloud providers
+    #[serde(default, skip_serializing_if = "Decisions::is_empty")]
+    pub privacy: Decisions,
+    /// Tools exposed to the model; `None` for every available tool
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tools: Option<ToolSelection>,
+    /// Files the files tool used for this chat
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub touched_paths: Vec<TouchedPath>,
+    /// Text typed in the prompt but not sent yet
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub draft: String,
+    /// The user's scratchpad for this chat, plain Markdown
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub notes: String,
+    /// Prompts sent in this chat, oldest first, for Up-arrow recall
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sent_prompts: Vec<String>,
+    /// Bookmarked messages, in the order they were bookmarked
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub bookmarks: Vec<Bookmark>,
+    /// Answers replaced by regenerating them
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub answer_versions: Vec<AnswerVersions>,
+    /// Sources cited by answers, by message
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sources: Vec<MessageSources>,
+    /// Timing, tokens and cost of answers, in the order they finished
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub message_stats: Vec<MessageStats>,
+    /// Where each day of the chat starts, for jumping to a date
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub days: Vec<DayMark>,
     pub messages: Vec<Message>,
     pub created_at: DateTime<Utc>,
     pub accessed_at: DateTime<Utc>,
@@ -27,6 +88,17 @@ impl ChatData {
         Self::with_title("New Session".to_string())
     }
 
+    /// Title of a session opened on an ASR or TTS model from the Hub, e.g.
+    /// "Transcription · whisper-large-v3"; other models keep the usual title
+    pub fn workspace_title(model_id: &str, category: RegistryCategory) -> Option<String> {
+        let workspace = match category {
+            RegistryCategory::Asr => "Transcription",
+            RegistryCategory::Tts => "Speech",
+            _ => return None,
+        };
+        Some(format!("{} · {}", workspace, model_id))
+    }
+
     pub fn with_title(title: String) -> Self {
         let now = Utc::now();
         Self {
@@ -34,6 +106,19 @@ impl ChatData {
             title,
             bot_id: None,
             model_category: None,
+            system_prompt: None,
+            sampling: Sampling::default(),
+            privacy: Decisions::default(),
+            tools: None,
+            touched_paths: Vec::new(),
+            draft: String::new(),
+            notes: String::new(),
+            sent_prompts: Vec::new(),
+            bookmarks: Vec::new(),
+            answer_versions: Vec::new(),
+            sources: Vec::new(),
+            message_stats: Vec::new(),
+            days: Vec::new(),
             messages: Vec::new(),
             created_at: now,
             accessed_at: now,
@@ -51,8 +136,8 @@ impl ChatData {
 
         match serde_json::to_string_pretty(self) {
             Ok(json) => {
-                if let Err(e) = std::fs::write(&path, &json) {
-                    log::error!("Failed to save chat {}: {:?}", self.id, e);
+                if let Err(e) = write_atomic(&path, json.as_bytes()) {
+                    log::error!("Failed to save chat {}: {}", self.id, e);
                 } else {
                     log::debug!("Saved chat {} to {:?}", self.id, path);
                 }
@@ -188,13 +273,9 @@ impl Chats {
         }
     }
 
-    /// Get the chats directory path (~/.moly/chats/)
+    /// Get the chats directory path (`chats/` in the active profile's data directory)
     fn get_chats_dir() -> PathBuf {
-        if let Some(home) = dirs::home_dir() {
-            home.join(".moly").join(CHATS_DIR)
-        } else {
-            PathBuf::from(CHATS_DIR)
-        }
+        profiles::data_dir().join(CHATS_DIR)
     }
 
     /// Calculate the highest "New Session X" number from existing session titles
@@ -306,6 +387,50 @@ impl Chats {
         id
     }
 
+    /// Copy a chat with all its messages into a new chat, saved and listed
+    /// first; the current chat doesn't change
+    pub fn duplicate_chat(&mut self, chat_id: ChatId) -> Option<ChatId> {
+        let mut copy = self.get_chat_by_id(chat_id)?.clone();
+        let now = Utc::now();
+        let mut id = now.timestamp_millis() as u128;
+        while self.saved_chats.iter().any(|c| c.id == id) {
+            id += 1;
+        }
+        copy.id = id;
+        copy.title = format!("{} (copy)", copy.title);
+        // The copy's messages keep the days they were sent
+        if copy.days.first().is_none_or(|d| d.message_index > 0) && !copy.messages.is_empty() {
+            let created = chat_navigation::local_date(copy.created_at);
+            copy.days.insert(0, DayMark { message_index: 0, date: created });
+        }
+        copy.created_at = now;
+        copy.accessed_at = now;
+        copy.save(&self.chats_dir);
+        self.saved_chats.insert(0, copy);
+        log::info!("Duplicated chat {} as {}", chat_id, id);
+        Some(id)
+    }
+
+    /// Start a chat from a template: its name, system prompt, tools, model
+    /// kind and messages replace the chat's own
+    pub fn apply_template(&mut self, chat_id: ChatId, template: &SessionTemplate) {
+        let chats_dir = self.chats_dir.clone();
+        if let Some(chat) = self.get_chat_by_id_mut(chat_id) {
+            chat.title = template.name.clone();
+            chat.system_prompt = template.system_prompt.clone();
+            chat.tools = template.tools.clone();
+            if template.model_category.is_some() {
+                chat.model_category = template.model_category;
+            }
+            chat.messages = template.messages.clone();
+            chat.bookmarks.clear();
+            chat.answer_versions.clear();
+            chat.sources.clear();
+            chat.message_stats.clear();
+            chat.save(&chats_dir);
+        }
+    }
+
     pub fn get_chat_by_id(&self, chat_id: ChatId) -> Option<&ChatData> {
         self.saved_chats.iter().find(|c| c.id == chat_id)
     }
@@ -359,7 +484,14 @@ impl Chats {
             for msg in &mut messages {
                 msg.metadata.is_writing = false;
             }
+            let previous_count = chat.messages.len();
             chat.messages = messages;
+            // Drop bookmarks of messages that were removed
+            let count = chat.messages.len();
+            chat_navigation::record_day(&mut chat.days, previous_count, count, chat_navigation::today());
+            chat.bookmarks.retain(|b| b.message_index < count);
+            chat.sources.retain(|s| s.message_index < count);
+            chat.message_stats.retain(|s| s.message_index < count);
             chat.maybe_update_title_from_messages();
             chat.save(&chats_dir);
         }
@@ -374,6 +506,15 @@ impl Chats {
         }
     }
 
+    /// Rename a chat and save
+    pub fn update_chat_title(&mut self, chat_id: ChatId, title: String) {
+        let chats_dir = self.chats_dir.clone();
+        if let Some(chat) = self.get_chat_by_id_mut(chat_id) {
+            chat.title = title;
+            chat.save(&chats_dir);
+        }
+    }
+
     /// Update a chat's model category and save
     pub fn update_chat_category(&mut self, chat_id: ChatId, category: Option<RegistryCategory>) {
         let chats_dir = self.chats_dir.clone();
@@ -383,6 +524,204 @@ impl Chats {
         }
     }
 
+    /// Update a chat's tool selection and save
+    pub fn update_chat_tools(&mut self, chat_id: ChatId, tools: Option<ToolSelection>) {
+        let chats_dir = self.chats_dir.clone();
+        if let Some(chat) = self.get_chat_by_id_mut(chat_id) {
+            chat.tools = tools;
+            chat.save(&chats_dir);
+        }
+    }
+
+    /// Update a chat's system prompt and save
+    pub fn update_chat_system_prompt(&mut self, chat_id: ChatId, prompt: Option<String>) {
+        let chats_dir = self.chats_dir.clone();
+        if let Some(chat) = self.get_chat_by_id_mut(chat_id) {
+            chat.system_prompt = prompt;
+            chat.save(&chats_dir);
+        }
+    }
+
+    /// Update a chat's temperature and answer length and save
+    pub fn update_chat_sampling(&mut self, chat_id: ChatId, sampling: Sampling) {
+        let chats_dir = self.chats_dir.clone();
+        if let Some(chat) = self.get_chat_by_id_mut(chat_id) {
+            chat.sampling = sampling;
+            chat.save(&chats_dir);
+        }
+    }
+
+    /// Update what the privacy filter redacts in a chat and save
+    pub fn update_chat_privacy(&mut self, chat_id: ChatId, privacy: Decisions) {
+        let chats_dir = self.chats_dir.clone();
+        if let Some(chat) = self.get_chat_by_id_mut(chat_id) {
+            chat.privacy = privacy;
+            chat.save(&chats_dir);
+        }
+    }
+
+    /// Update a chat's notes and save
+    pub fn update_chat_notes(&mut self, chat_id: ChatId, notes: &str) {
+        let chats_dir = self.chats_dir.clone();
+        if let Some(chat) = self.get_chat_by_id_mut(chat_id) {
+            if chat.notes != notes {
+                chat.notes = notes.to_string();
+                chat.save(&chats_dir);
+            }
+        }
+    }
+
+    /// Add paths the files tool used to a chat and save
+    pub fn record_touched_paths(&mut self, chat_id: ChatId, touched: Vec<TouchedPath>) {
+        let chats_dir = self.chats_dir.clone();
+        if let Some(chat) = self.get_chat_by_id_mut(chat_id) {
+            chat.touched_paths.extend(touched);
+            chat.save(&chats_dir);
+        }
+    }
+
+    /// Update a chat's unsent prompt text and save if it changed
+    pub fn update_chat_draft(&mut self, chat_id: ChatId, draft: &str) {
+        let chats_dir = self.chats_dir.clone();
+        if let Some(chat) = self.get_chat_by_id_mut(chat_id) {
+            if chat.draft != draft {
+                chat.draft = draft.to_string();
+                chat.save(&chats_dir);
+            }
+        }
+    }
+
+    /// Remember a prompt sent in a chat, clear its draft and save
+    pub fn record_sent_prompt(&mut self, chat_id: ChatId, prompt: &str) {
+        let prompt = prompt.trim();
+        if prompt.is_empty() {
+            return;
+        }
+        let chats_dir = self.chats_dir.clone();
+        if let Some(chat) = self.get_chat_by_id_mut(chat_id) {
+            // Like a shell, repeating the last prompt doesn't add an entry
+            if chat.sent_prompts.last().map(String::as_str) != Some(prompt) {
+                chat.sent_prompts.push(prompt.to_string());
+                let excess = chat.sent_prompts.len().saturating_sub(MAX_SENT_PROMPTS);
+                chat.sent_prompts.drain(..excess);
+            }
+            chat.draft.clear();
+            chat.save(&chats_dir);
+        }
+    }
+
+    /// Bookmark a message of a chat, or change the note of its bookmark, and save
+    pub fn set_bookmark(&mut self, chat_id: ChatId, message_index: usize, note: &str) {
+        let chats_dir = self.chats_dir.clone();
+        if let Some(chat) = self.get_chat_by_id_mut(chat_id) {
+            if message_index >= chat.messages.len() {
+                return;
+            }
+            match chat.bookmarks.iter_mut().find(|b| b.message_index == message_index) {
+                Some(bookmark) => bookmark.note = note.to_string(),
+                None => chat.bookmarks.push(Bookmark {
+                    message_index,
+                    note: note.to_string(),
+                    created_at: Utc::now(),
+                }),
+            }
+            chat.save(&chats_dir);
+        }
+    }
+
+    /// Remove the bookmark of a message and save
+    pub fn remove_bookmark(&mut self, chat_id: ChatId, message_index: usize) {
+        let chats_dir = self.chats_dir.clone();
+        if let Some(chat) = self.get_chat_by_id_mut(chat_id) {
+            chat.bookmarks.retain(|b| b.message_index != message_index);
+            chat.save(&chats_dir);
+        }
+    }
+
+    /// Record the sources behind an answer and save
+    pub fn set_message_sources(&mut self, chat_id: ChatId, message_index: usize, sources: Vec<Source>) {
+        let chats_dir = self.chats_dir.clone();
+        if let Some(chat) = self.get_chat_by_id_mut(chat_id) {
+            chat.sources.retain(|s| s.message_index != message_index);
+            if !sources.is_empty() {
+                chat.sources.push(MessageSources { message_index, sources });
+            }
+            chat.save(&chats_dir);
+        }
+    }
+
+    /// Sources behind an answer, by number
+    pub fn message_sources(&self, chat_id: ChatId, message_index: usize) -> &[Source] {
+        self.get_chat_by_id(chat_id)
+            .and_then(|chat| chat.sources.iter().find(|s| s.message_index == message_index))
+            .map(|s| s.sources.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Record how an answer was produced, replacing earlier figures for the
+    /// same message, and save
+    pub fn set_message_stats(&mut self, chat_id: ChatId, stats: MessageStats) {
+        let chats_dir = self.chats_dir.clone();
+        if let Some(chat) = self.get_chat_by_id_mut(chat_id) {
+            chat.message_stats.retain(|s| s.message_index != stats.message_index);
+            chat.message_stats.push(stats);
+            chat.save(&chats_dir);
+        }
+    }
+
+    /// Figures of the chat's answers, in the order they finished
+    pub fn message_stats(&self, chat_id: ChatId) -> &[MessageStats] {
+        self.get_chat_by_id(chat_id).map(|chat| chat.message_stats.as_slice()).unwrap_or_default()
+    }
+
+    /// Keep an answer that was replaced by regenerating it, and save
+    pub fn record_answer_version(&mut self, chat_id: ChatId, message_index: usize, text: &str) {
+        let chats_dir = self.chats_dir.clone();
+        if let Some(chat) = self.get_chat_by_id_mut(chat_id) {
+            let index = match chat.answer_versions.iter().position(|v| v.message_index == message_index) {
+                Some(index) => index,
+                None => {
+                    chat.answer_versions.push(AnswerVersions { message_index, previous: Vec::new() });
+                    chat.answer_versions.len() - 1
+                }
+            };
+            let previous = &mut chat.answer_versions[index].previous;
+            if previous.last().map(String::as_str) == Some(text) {
+                return;
+            }
+            previous.push(text.to_string());
+            if previous.len() > answer_versions::MAX_VERSIONS {
+                previous.remove(0);
+            }
+            chat.save(&chats_dir);
+        }
+    }
+
+    /// Earlier versions of an answer, oldest first
+    pub fn answer_versions(&self, chat_id: ChatId, message_index: usize) -> &[String] {
+        self.get_chat_by_id(chat_id)
+            .and_then(|chat| chat.answer_versions.iter().find(|v| v.message_index == message_index))
+            .map(|v| v.previous.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Put an earlier version back as the answer and keep the current one
+    /// as a version instead, then save. Returns the chat's messages.
+    pub fn keep_answer_version(&mut self, chat_id: ChatId, message_index: usize, version: usize) -> Option<Vec<Message>> {
+        let chats_dir = self.chats_dir.clone();
+        let chat = self.get_chat_by_id_mut(chat_id)?;
+        let versions = chat.answer_versions.iter_mut().find(|v| v.message_index == message_index)?;
+        if version >= versions.previous.len() {
+            return None;
+        }
+        let message = chat.messages.get_mut(message_index)?;
+        let kept = versions.previous.remove(version);
+        let replaced = std::mem::replace(&mut message.content.text, kept);
+        versions.previous.push(replaced);
+        chat.save(&chats_dir);
+        Some(chat.messages.clone())
+    }
+
     /// Get the chats directory path
     pub fn chats_dir(&self) -> &PathBuf {
         &self.chats_dir
@@ -0,0 +1,141 @@
+//! Numbered sources behind an answer
+//!
+//! Tools that look things up, like reading a shared file or fetching a page,
+//! supply sources. The agent numbers them as they come in, marks each tool
+//! result with its number and asks the model to cite it as `[n]`. The
+//! sources of an answer are kept on the chat ([`crate::ChatData::sources`])
+//! so backups keep the attribution, and [`footer`] lists them below the
+//! answer text.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::bookmarks;
+
+/// Characters of a tool result kept as the source's preview
+const EXCERPT_CHARS: usize = 280;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Source {
+    /// Number the answer cites it by, from 1
+    pub number: usize,
+    pub title: String,
+    /// File path or URL
+    pub location: String,
+    /// Start of what the tool returned, for previews
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub excerpt: String,
+}
+
+impl Source {
+    pub fn new(number: usize, location: &str, output: &str) -> Self {
+        Self {
+            number,
+            title: title_of(location),
+            location: location.to_string(),
+            excerpt: bookmarks::excerpt(output, EXCERPT_CHARS),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MessageSources {
+    /// Position of the answer in the chat
+    pub message_index: usize,
+    pub sources: Vec<Source>,
+}
+
+/// What a tool call looked up, if it is a lookup: the file it read or the
+/// page it fetched
+pub fn location_of(name: &str, args: &Value) -> Option<String> {
+    let location = match name {
+        "fs_read_file" => args.get("path"),
+        _ => args.get("url"),
+    };
+    location.and_then(Value::as_str).map(str::trim).filter(|l| !l.is_empty()).map(str::to_string)
+}
+
+/// A short name for a path or URL: the file name, or the host and last
+/// path segment of a page
+fn title_of(location: &str) -> String {
+    let trimmed = location.trim_end_matches('/');
+    if let Some((_, rest)) = trimmed.split_once("://") {
+        let mut parts = rest.split('/');
+        let host = parts.next().unwrap_or_default();
+        return match parts.last() {
+            Some(last) if !last.is_empty() => format!("{} – {}", host, last),
+            _ => host.to_string(),
+        };
+    }
+    trimmed.rsplit(['/', '\\']).next().unwrap_or(trimmed).to_string()
+}
+
+/// Source numbers cited as `[n]` in `text`, in order, without repeats
+pub fn cited_numbers(text: &str) -> Vec<usize> {
+    let mut numbers = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('[') {
+        rest = &rest[start + 1..];
+        let Some(end) = rest.find(']') else { break };
+        if let Ok(number) = rest[..end].trim().parse::<usize>() {
+            if !numbers.contains(&number) {
+                numbers.push(number);
+            }
+        }
+    }
+    numbers
+}
+
+/// Markdown list of the sources to put below an answer. Sources the answer
+/// cites come first; the rest are listed as consulted.
+pub fn footer(text: &str, sources: &[Source]) -> String {
+    if sources.is_empty() {
+        return String::new();
+    }
+    let cited = cited_numbers(text);
+    let line = |source: &Source| {
+        if source.location.contains("://") {
+            format!("- [{}] [{}]({})", source.number, source.title, source.location)
+        } else {
+            format!("- [{}] {} — `{}`", source.number, source.title, source.location)
+        }
+    };
+    let (used, consulted): (Vec<&Source>, Vec<&Source>) = sources.iter().partition(|s| cited.contains(&s.number));
+    let mut footer = String::new();
+    if !used.is_empty() {
+        footer.push_str("\n\n**Sources**\n");
+        footer.push_str(&used.iter().map(|s| line(s)).collect::<Vec<_>>().join("\n"));
+    }
+    if !consulted.is_empty() {
+        footer.push_str("\n\n**Also consulted**\n");
+        footer.push_str(&consulted.iter().map(|s| line(s)).collect::<Vec<_>>().join("\n"));
+    }
+    footer
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_sources_and_footer() {
+        assert_eq!(location_of("fs_read_file", &json!({ "path": "/notes/plan.md" })).as_deref(), Some("/notes/plan.md"));
+        assert_eq!(location_of("fetch", &json!({ "url": "https://example.com/docs/intro" })).as_deref(),
+            Some("https://example.com/docs/intro"));
+        assert_eq!(location_of("fs_list_dir", &json!({ "path": "/notes" })), None);
+
+        let sources = vec![
+            Source::new(1, "/notes/plan.md", "Ship in May"),
+            Source::new(2, "https://example.com/docs/intro", "Intro"),
+        ];
+        assert_eq!(sources[0].title, "plan.md");
+        assert_eq!(sources[1].title, "example.com – intro");
+
+        let answer = "We ship in May [1]. See [1] and [note].";
+        assert_eq!(cited_numbers(answer), vec![1]);
+        let footer = footer(answer, &sources);
+        assert!(footer.contains("**Sources**\n- [1] plan.md — `/notes/plan.md`"));
+        assert!(footer.contains("**Also consulted**\n- [2] [example.com – intro](https://example.com/docs/intro)"));
+    }
+}
@@ -0,0 +1,142 @@
+//! Code blocks in assistant answers
+//!
+//! [`extract`] finds the fenced blocks of an answer so the chat can offer
+//! actions on them: copying one, or running it with the code tool
+//! ([`crate::code_tool::run_snippet`]). Fences without a language get one
+//! from [`detect_language`], a few cheap hints that are right for the common
+//! cases and return `None` otherwise.
+
+/// A fenced block of an answer
+#[derive(Clone, Debug, PartialEq)]
+pub struct CodeBlock {
+    /// Language of the fence, lowercase, or the detected one
+    pub language: Option<String>,
+    /// Whether `language` was detected rather than given
+    pub detected: bool,
+    pub code: String,
+}
+
+impl CodeBlock {
+    /// Whether the code tool can run this block
+    pub fn runnable(&self) -> bool {
+        self.language.as_deref().is_some_and(|l| crate::code_tool::supports(l))
+    }
+
+    /// One line for a picker: language, size and first line
+    pub fn describe(&self) -> String {
+        let language = match (&self.language, self.detected) {
+            (Some(l), false) => l.clone(),
+            (Some(l), true) => format!("{} (detected)", l),
+            (None, _) => "code".to_string(),
+        };
+        let first = self.code.lines().find(|l| !l.trim().is_empty()).unwrap_or_default().trim();
+        let first: String = first.chars().take(60).collect();
+        format!("{}, {} line(s): {}", language, self.code.lines().count(), first)
+    }
+}
+
+/// The fenced code blocks of `markdown`, in order. An unclosed fence runs to
+/// the end, as it does while an answer is still streaming.
+pub fn extract(markdown: &str) -> Vec<CodeBlock> {
+    let mut blocks = Vec::new();
+    let mut open: Option<(String, Option<String>, Vec<&str>)> = None;
+    for line in markdown.lines() {
+        let trimmed = line.trim_start();
+        match &mut open {
+            None => {
+                let fence: String = trimmed.chars().take_while(|c| *c == '`' || *c == '~').collect();
+                if fence.len() >= 3 && fence.chars().all(|c| c == fence.chars().next().unwrap()) {
+                    let info = trimmed[fence.len()..].trim();
+                    let language = info.split_whitespace().next().map(|l| l.to_ascii_lowercase());
+                    open = Some((fence, language, Vec::new()));
+                }
+            }
+            Some((fence, _, lines)) => {
+                if trimmed.starts_with(fence.as_str()) && trimmed.trim_start_matches(&fence[..1]).trim().is_empty() {
+                    let (_, language, lines) = open.take().unwrap();
+                    blocks.push(block(language, &lines));
+                } else {
+                    lines.push(line);
+                }
+            }
+        }
+    }
+    if let Some((_, language, lines)) = open {
+        blocks.push(block(language, &lines));
+    }
+    blocks
+}
+
+fn block(language: Option<String>, lines: &[&str]) -> CodeBlock {
+    let code = lines.join("\n");
+    match language {
+        Some(language) => CodeBlock { language: Some(language), detected: false, code },
+        None => CodeBlock { language: detect_language(&code).map(str::to_string), detected: true, code },
+    }
+}
+
+/// Guess the language of unlabeled code
+pub fn detect_language(code: &str) -> Option<&'static str> {
+    let text = code.trim();
+    if text.is_empty() {
+        return None;
+    }
+    let first = text.lines().next().unwrap_or_default().trim();
+    if first.starts_with("#!") {
+        return Some(if first.contains("python") {
+            "python"
+        } else if first.contains("node") {
+            "javascript"
+        } else {
+            "bash"
+        });
+    }
+    if (text.starts_with('{') && text.ends_with('}')) || (text.starts_with('[') && text.ends_with(']')) {
+        if serde_json::from_str::<serde_json::Value>(text).is_ok() {
+            return Some("json");
+        }
+    }
+    if text.starts_with("<!DOCTYPE") || text.starts_with("<html") || (text.starts_with('<') && text.contains("</")) {
+        return Some("html");
+    }
+    let has = |needles: &[&str]| needles.iter().any(|n| text.contains(n));
+    let lines_start = |prefixes: &[&str]| text.lines().any(|l| prefixes.iter().any(|p| l.trim_start().starts_with(p)));
+    if has(&["fn main(", "let mut ", "impl ", "pub fn ", "::new(", "println!("]) {
+        Some("rust")
+    } else if lines_start(&["def ", "import ", "from ", "elif ", "print("]) && !has(&["{", ";\n"]) {
+        Some("python")
+    } else if has(&["#include", "int main(", "std::"]) {
+        Some("cpp")
+    } else if has(&["console.log(", "function ", "=> ", "const ", "require(", "document."]) {
+        Some("javascript")
+    } else if lines_start(&["SELECT ", "select ", "INSERT ", "CREATE TABLE", "UPDATE "]) {
+        Some("sql")
+    } else if lines_start(&["$ ", "sudo ", "cd ", "ls ", "echo ", "export ", "pip ", "npm ", "cargo ", "git ", "brew "]) {
+        Some("bash")
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_and_detect() {
+        let answer = "Try this:\n\n```Python\nprint('hi')\n```\n\nor\n\n```\nconst x = 1;\nconsole.log(x);\n```\n\n~~~\n{\"a\": 1}\n~~~\n```\n$ cargo build";
+        let blocks = extract(answer);
+        assert_eq!(blocks.len(), 4);
+        assert_eq!(blocks[0], CodeBlock { language: Some("python".to_string()), detected: false, code: "print('hi')".to_string() });
+        assert!(blocks[0].runnable());
+        assert_eq!(blocks[1].language.as_deref(), Some("javascript"));
+        assert!(blocks[1].detected && blocks[1].runnable());
+        assert_eq!(blocks[2].language.as_deref(), Some("json"));
+        assert!(!blocks[2].runnable());
+        assert_eq!(blocks[3].language.as_deref(), Some("bash"));
+
+        assert_eq!(detect_language("def f(x):\n    return x * 2"), Some("python"));
+        assert_eq!(detect_language("fn main() {\n    println!(\"hi\");\n}"), Some("rust"));
+        assert_eq!(detect_language("just some words"), None);
+    }
+}
@@ -0,0 +1,334 @@
+//! Built-in code execution tool
+//!
+//! Runs Python or JavaScript snippets from the model in a subprocess and
+//! returns what they printed. Each run asks the user first
+//! ([`crate::tool_permissions`]), then runs in a fresh scratch folder with a
+//! cleared environment, no stdin, a wall-clock limit and (on Unix) CPU and
+//! memory limits. On Unix a snippet runs in its own process group, so
+//! processes it starts are stopped with it. This keeps snippets from wandering off or running away; it
+//! is not a container, so approve only code you'd run yourself.
+//!
+//! The limits are stored in preferences and mirrored here with
+//! [`set_current`], like the download settings.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::io::Read;
+use std::path::Path;
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+
+use crate::tool_permissions;
+
+/// Name shown when the tool asks for approval
+pub const TOOL_NAME: &str = "Run Code";
+
+/// Output kept per stream; the rest is cut off
+const MAX_OUTPUT_BYTES: u64 = 64 * 1024;
+
+/// Code shown in the approval dialog at most
+const MAX_PREVIEW_CHARS: usize = 1500;
+
+/// Limits for each run, stored in preferences
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CodeToolSettings {
+    pub timeout_secs: u64,
+    pub memory_mb: u64,
+}
+
+impl Default for CodeToolSettings {
+    fn default() -> Self {
+        Self { timeout_secs: 30, memory_mb: 512 }
+    }
+}
+
+impl CodeToolSettings {
+    /// Check values entered in the MCP app before they are saved
+    pub fn validate(&self) -> Result<(), String> {
+        if !(1..=600).contains(&self.timeout_secs) {
+            return Err("Time limit must be between 1 and 600 seconds".to_string());
+        }
+        if !(64..=16384).contains(&self.memory_mb) {
+            return Err("Memory limit must be between 64 and 16384 MB".to_string());
+        }
+        Ok(())
+    }
+}
+
+static CURRENT: RwLock<Option<CodeToolSettings>> = RwLock::new(None);
+
+/// Mirror [`crate::Preferences::code_tool`]
+pub fn set_current(settings: &CodeToolSettings) {
+    *CURRENT.write().unwrap() = Some(settings.clone());
+}
+
+fn current() -> CodeToolSettings {
+    CURRENT.read().unwrap().clone().unwrap_or_default()
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Language {
+    Python,
+    JavaScript,
+}
+
+impl Language {
+    fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "python" | "py" | "python3" => Some(Language::Python),
+            "javascript" | "js" | "node" => Some(Language::JavaScript),
+            _ => None,
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            Language::Python => "Python",
+            Language::JavaScript => "JavaScript",
+        }
+    }
+
+    fn command(&self, script: &Path) -> Command {
+        match self {
+            Language::Python => {
+                let mut command = Command::new("python3");
+                // Isolated mode: no user site-packages or PYTHON* variables
+                command.arg("-I").arg(script);
+                command
+            }
+            Language::JavaScript => {
+                let mut command = Command::new("node");
+                command.arg(script);
+                command
+            }
+        }
+    }
+
+    fn file_name(&self) -> &'static str {
+        match self {
+            Language::Python => "main.py",
+            Language::JavaScript => "main.js",
+        }
+    }
+}
+
+/// Tool definition in OpenAI function calling format
+pub fn definitions() -> Vec<Value> {
+    vec![json!({
+        "type": "function",
+        "function": {
+            "name": "run_code",
+            "description": "Run a Python or JavaScript snippet on the user's computer and return its exit code, stdout and stderr. Print results to see them. The user approves every run; runs are time and memory limited and start in an empty folder.",
+            "parameters": {
+                "type": "object",
+                "properties": {
+                    "language": { "type": "string", "enum": ["python", "javascript"] },
+                    "code": { "type": "string", "description": "The complete program" }
+                },
+                "required": ["language", "code"]
+            }
+        }
+    })]
+}
+
+/// Run a call to the tool. Returns `None` if `name` is not the tool's.
+pub fn call(name: &str, args: &Value) -> Option<Result<String, String>> {
+    if name != "run_code" {
+        return None;
+    }
+    let language = args.get("language").and_then(Value::as_str).unwrap_or_default();
+    let code = args.get("code").and_then(Value::as_str).unwrap_or_default();
+    let result = match Language::parse(language) {
+        Some(language) => run(language, code),
+        None => Err(format!("Unsupported language: {:?}. Use python or javascript.", language)),
+    };
+    if let Err(e) = &result {
+        log::warn!("run_code failed: {}", e);
+    }
+    Some(result)
+}
+
+/// Whether [`run_snippet`] can run code in `language`, e.g. `py` or `javascript`
+pub fn supports(language: &str) -> bool {
+    Language::parse(language).is_some()
+}
+
+/// Run a code block the user picked in the chat, with the same approval and
+/// limits as the model's runs. Blocks; call it off the UI thread.
+pub fn run_snippet(language: &str, code: &str) -> Result<String, String> {
+    match Language::parse(language) {
+        Some(language) => run(language, code),
+        None => Err(format!("{} code can't be run; the code tool runs Python and JavaScript.", language)),
+    }
+}
+
+fn run(language: Language, code: &str) -> Result<String, String> {
+    let limits = current();
+    let mut preview: String = code.chars().take(MAX_PREVIEW_CHARS).collect();
+    if preview.len() < code.len() {
+        preview.push_str("\n…");
+    }
+    let detail = format!("Limits: {} s, {} MB of memory.\n\n{}", limits.timeout_secs, limits.memory_mb, preview);
+    if !tool_permissions::ask(TOOL_NAME, format!("Run this {} code?", language.label()), detail) {
+        return Err("The user did not allow running this code".to_string());
+    }
+
+    static RUNS: AtomicU64 = AtomicU64::new(0);
+    let scratch = std::env::temp_dir()
+        .join(format!("moly-run-{}-{}", std::process::id(), RUNS.fetch_add(1, Ordering::Relaxed)));
+    std::fs::create_dir_all(&scratch).map_err(|e| format!("{}: {}", scratch.display(), e))?;
+    let script = scratch.join(language.file_name());
+    let result = std::fs::write(&script, code)
+        .map_err(|e| format!("{}: {}", script.display(), e))
+        .and_then(|_| run_sandboxed(language.command(&script), &scratch, &limits));
+    let _ = std::fs::remove_dir_all(&scratch);
+    result
+}
+
+fn run_sandboxed(mut command: Command, scratch: &Path, limits: &CodeToolSettings) -> Result<String, String> {
+    command
+        .current_dir(scratch)
+        .env_clear()
+        .env("PATH", std::env::var_os("PATH").unwrap_or_default())
+        .env("HOME", scratch)
+        .env("TMPDIR", scratch)
+        .env("LANG", "en_US.UTF-8")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        command.process_group(0);
+        let memory = limits.memory_mb * 1024 * 1024;
+        let cpu = limits.timeout_secs;
+        // SAFETY: only async-signal-safe calls between fork and exec
+        unsafe {
+            command.pre_exec(move || {
+                let memory = libc::rlimit { rlim_cur: memory as libc::rlim_t, rlim_max: memory as libc::rlim_t };
+                let cpu = libc::rlimit { rlim_cur: cpu as libc::rlim_t, rlim_max: cpu as libc::rlim_t };
+                // Data rather than address space: Node reserves far more
+                // address space than it uses and won't start under
+                // RLIMIT_AS. Not enforced everywhere; the wall-clock limit
+                // below still applies.
+                libc::setrlimit(libc::RLIMIT_DATA, &memory);
+                libc::setrlimit(libc::RLIMIT_CPU, &cpu);
+                Ok(())
+            });
+        }
+    }
+
+    let program = command.get_program().to_string_lossy().to_string();
+    let mut child = command.spawn().map_err(|e| format!("Could not start {}: {}. Is it installed?", program, e))?;
+    let stdout = child.stdout.take().map(read_capped);
+    let stderr = child.stderr.take().map(read_capped);
+
+    let deadline = Instant::now() + Duration::from_secs(limits.timeout_secs);
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => {
+                // Leftover background processes would keep the output open
+                kill_group(&mut child);
+                break Some(status);
+            }
+            Ok(None) if Instant::now() >= deadline => {
+                kill_group(&mut child);
+                break None;
+            }
+            Ok(None) => std::thread::sleep(Duration::from_millis(50)),
+            Err(e) => return Err(format!("Waiting for {} failed: {}", program, e)),
+        }
+    };
+
+    let stdout = stdout.and_then(|h| h.join().ok()).unwrap_or_default();
+    let stderr = stderr.and_then(|h| h.join().ok()).unwrap_or_default();
+    let outcome = match status {
+        Some(status) => match status.code() {
+            Some(code) => format!("Exit code {}", code),
+            None => "Stopped by a signal (likely the CPU or memory limit)".to_string(),
+        },
+        None => format!("Stopped after the {} s time limit", limits.timeout_secs),
+    };
+    Ok(format!("{}\n--- stdout ---\n{}\n--- stderr ---\n{}", outcome, stdout, stderr))
+}
+
+/// Kill the snippet and every process it started
+fn kill_group(child: &mut Child) {
+    #[cfg(unix)]
+    // SAFETY: plain syscall; the group is the snippet's own (`process_group(0)`)
+    unsafe {
+        libc::kill(-(child.id() as libc::pid_t), libc::SIGKILL);
+    }
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
+/// Read a stream on its own thread, keeping the first [`MAX_OUTPUT_BYTES`]
+fn read_capped(stream: impl Read + Send + 'static) -> std::thread::JoinHandle<String> {
+    std::thread::spawn(move || {
+        let mut kept = Vec::new();
+        let mut stream = stream;
+        let _ = (&mut stream).take(MAX_OUTPUT_BYTES).read_to_end(&mut kept);
+        // Drain the rest so the child never blocks on a full pipe
+        let dropped = std::io::copy(&mut stream, &mut std::io::sink()).unwrap_or(0);
+        let mut text = String::from_utf8_lossy(&kept).to_string();
+        if dropped > 0 {
+            text.push_str(&format!("\n… {} more bytes cut off", dropped));
+        }
+        text
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_settings_and_languages() {
+        assert!(CodeToolSettings::default().validate().is_ok());
+        assert!(CodeToolSettings { timeout_secs: 0, ..Default::default() }.validate().is_err());
+        assert_eq!(Language::parse(" JS "), Some(Language::JavaScript));
+        assert_eq!(Language::parse("ruby"), None);
+        assert!(call("fs_read_file", &json!({})).is_none());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_started_processes_are_stopped() {
+        let scratch = std::env::temp_dir();
+        let limits = CodeToolSettings { timeout_secs: 2, ..Default::default() };
+        let started = Instant::now();
+        let mut exits = Command::new("sh");
+        exits.args(["-c", "sleep 30 & echo started"]);
+        let output = run_sandboxed(exits, &scratch, &limits).unwrap();
+        assert!(output.starts_with("Exit code 0") && output.contains("started"), "{}", output);
+
+        let mut hangs = Command::new("sh");
+        hangs.args(["-c", "sleep 30 & sleep 30"]);
+        let output = run_sandboxed(hangs, &scratch, &limits).unwrap();
+        assert!(output.starts_with("Stopped after the 2 s time limit"), "{}", output);
+        assert!(started.elapsed() < Duration::from_secs(20));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_languages_start_at_default_limits() {
+        let scratch = std::env::temp_dir().join(format!("moly-run-test-{}", std::process::id()));
+        std::fs::create_dir_all(&scratch).unwrap();
+        for (language, code) in [(Language::JavaScript, "console.log(6 * 7)"), (Language::Python, "print(6 * 7)")] {
+            let installed = language.command(Path::new("--version")).output().is_ok_and(|o| o.status.success());
+            if !installed {
+                continue;
+            }
+            let script = scratch.join(language.file_name());
+            std::fs::write(&script, code).unwrap();
+            let output = run_sandboxed(language.command(&script), &scratch, &CodeToolSettings::default()).unwrap();
+            assert!(output.starts_with("Exit code 0") && output.contains("42"), "{:?}: {}", language, output);
+        }
+        let _ = std::fs::remove_dir_all(&scratch);
+    }
+}
@@ -0,0 +1,129 @@
+//! Chat client for local models that take formatted prompts.
+//!
+//! Formats the conversation with the model's [`ChatTemplate`] and sends it
+//! to the runtime's `/v1/completions` endpoint. Used instead of the OpenAI
+//! chat client for the injected local model when it has a template, see
+//! [`crate::chat_template::template_for_id`].
+
+use moly_kit::aitk::protocol::*;
+use moly_kit::aitk::utils::asynchronous::{BoxPlatformSendFuture, BoxPlatformSendStream};
+use std::sync::Arc;
+
+use crate::chat_defaults;
+use crate::chat_template::ChatTemplate;
+
+#[derive(Debug)]
+struct CompletionsClientInner {
+    /// Runtime root, without `/v1`
+    base_url: String,
+    template: ChatTemplate,
+    bots: Vec<Bot>,
+    client: reqwest::Client,
+}
+
+#[derive(Debug, Clone)]
+pub struct CompletionsClient(Arc<CompletionsClientInner>);
+
+impl CompletionsClient {
+    pub fn new(base_url: &str, template: ChatTemplate, bots: Vec<Bot>) -> Self {
+        let client = crate::network::async_client()
+            .timeout(std::time::Duration::from_secs(600))
+            .build()
+            .unwrap_or_else(|_| reqwest::Client::new());
+        CompletionsClient(Arc::new(CompletionsClientInner {
+            base_url: base_url.trim_end_matches('/').trim_end_matches("/v1").to_string(),
+            template,
+            bots,
+            client,
+        }))
+    }
+
+    pub fn template(&self) -> ChatTemplate {
+        self.0.template
+    }
+}
+
+fn role(from: &EntityId) -> Option<&'static str> {
+    match from {
+        EntityId::User => Some("user"),
+        EntityId::Bot(_) => Some("assistant"),
+        EntityId::System => Some("system"),
+        _ => None,
+    }
+}
+
+impl BotClient for CompletionsClient {
+    fn bots(&mut self) -> BoxPlatformSendFuture<'static, ClientResult<Vec<Bot>>> {
+        let bots = self.0.bots.clone();
+        Box::pin(async move { ClientResult::new_ok(bots) })
+    }
+
+    fn send(
+        &mut self,
+        bot_id: &BotId,
+        messages: &[Message],
+        _tools: &[Tool],
+    ) -> BoxPlatformSendStream<'static, ClientResult<MessageContent>> {
+        let inner = self.0.clone();
+        let turns: Vec<(String, String)> = messages
+            .iter()
+            .filter(|m| !m.content.text.trim().is_empty())
+            .filter_map(|m| Some((role(&m.from)?.to_string(), m.content.text.clone())))
+            .collect();
+        let mut body = serde_json::json!({
+            "model": bot_id.id(),
+            "prompt": inner.template.render(&turns),
+            "stop": inner.template.stop(),
+        });
+        let sampling = chat_defaults::active();
+        if let Some(temperature) = sampling.temperature {
+            body["temperature"] = temperature.into();
+        }
+        if let Some(max_tokens) = sampling.max_tokens {
+            body["max_tokens"] = max_tokens.into();
+        }
+
+        Box::pin(async_stream::stream! {
+            let url = format!("{}/v1/completions", inner.base_url);
+            let response = match inner.client.post(&url).json(&body).send().await {
+                Ok(r) => r,
+                Err(e) => {
+                    yield ClientResult::new_err(ClientError::new_with_source(
+                        ClientErrorKind::Network,
+                        format!("Could not send request to {url}. Verify your connection and the server status."),
+                        Some(e),
+                    ).into());
+                    return;
+                }
+            };
+
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            if !status.is_success() {
+                yield ClientResult::new_err(ClientError::new(
+                    ClientErrorKind::Response,
+                    format!("Request to {url} failed with status {} and content: {}", status, text),
+                ).into());
+                return;
+            }
+
+            let answer = serde_json::from_str::<serde_json::Value>(&text)
+                .ok()
+                .and_then(|v| v.pointer("/choices/0/text").and_then(|t| t.as_str()).map(str::to_string));
+            match answer {
+                Some(answer) => yield ClientResult::new_ok(MessageContent {
+                    text: answer.trim().to_string(),
+                    ..Default::default()
+                }),
+                None => yield ClientResult::new_err(ClientError::new(
+                    ClientErrorKind::Format,
+                    format!("Response from {url} has no completion text."),
+                ).into()),
+            }
+        })
+    }
+
+    fn clone_box(&self) -> Box<dyn BotClient> {
+        Box::new(self.clone())
+    }
+}
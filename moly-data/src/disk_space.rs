@@ -0,0 +1,91 @@
+//! Disk space pre-flight for model downloads
+//!
+//! Checked before a download starts, against the registry's size for the
+//! model plus any conversion output, so a download that can't fit is refused
+//! up front with the exact shortfall instead of failing midway with a write
+//! error. Files already in the model's folder are replaced by the download,
+//! so they count as space that will be freed.
+
+use crate::model_integrity;
+use crate::platform;
+use std::path::Path;
+
+/// Warn when less than this would be left free after the download
+pub const LOW_SPACE_REMAINING: u64 = 2 * 1024 * 1024 * 1024;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SpaceCheck {
+    /// Fits with room to spare, or the size isn't known
+    Enough,
+    /// Fits, but leaves less than [`LOW_SPACE_REMAINING`] free
+    Tight { required: u64, available: u64 },
+    /// Doesn't fit
+    Short { required: u64, available: u64 },
+}
+
+impl SpaceCheck {
+    pub fn classify(required: u64, available: u64) -> Self {
+        if required > available {
+            Self::Short { required, available }
+        } else if available - required < LOW_SPACE_REMAINING {
+            Self::Tight { required, available }
+        } else {
+            Self::Enough
+        }
+    }
+
+    /// What to tell the user, if anything
+    pub fn message(&self) -> Option<String> {
+        match *self {
+            Self::Enough => None,
+            Self::Tight { required, available } => Some(format!(
+                "Only {} will be left free on this disk after the download.",
+                format_size(available - required),
+            )),
+            Self::Short { required, available } => Some(format!(
+                "Needs {} free but only {} is available on this disk. Free up {} and try again.",
+                format_size(required),
+                format_size(available),
+                format_size(required - available),
+            )),
+        }
+    }
+}
+
+/// Check the volume of `dest` for a download of `download_bytes`, plus
+/// `extra_bytes` of conversion output. Unknown sizes or free space pass.
+pub fn check_download_space(dest: &Path, download_bytes: u64, extra_bytes: u64) -> SpaceCheck {
+    if download_bytes == 0 { return SpaceCheck::Enough; }
+    let Some(available) = platform::available_space(dest) else { return SpaceCheck::Enough };
+    let required = (download_bytes + extra_bytes).saturating_sub(model_integrity::dir_size(dest));
+    SpaceCheck::classify(required, available)
+}
+
+/// "12.4 GB"
+pub fn format_size(bytes: u64) -> String {
+    const MB: f64 = 1024.0 * 1024.0;
+    const GB: f64 = MB * 1024.0;
+    let b = bytes as f64;
+    if b >= GB {
+        format!("{:.1} GB", b / GB)
+    } else {
+        format!("{:.0} MB", (b / MB).ceil())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify() {
+        const GB: u64 = 1024 * 1024 * 1024;
+        assert_eq!(SpaceCheck::classify(8 * GB, 100 * GB), SpaceCheck::Enough);
+        assert!(matches!(SpaceCheck::classify(8 * GB, 9 * GB), SpaceCheck::Tight { .. }));
+        let short = SpaceCheck::classify(12 * GB, 8 * GB);
+        assert_eq!(
+            short.message().unwrap(),
+            "Needs 12.0 GB free but only 8.0 GB is available on this disk. Free up 4.0 GB and try again.",
+        );
+    }
+}
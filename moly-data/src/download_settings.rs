@@ -0,0 +1,347 @@
+//! Download behaviour and model storage
+//!
+//! Set under Settings → Storage & Downloads and stored in preferences. Like
+//! the hard-delete preference, the store mirrors them here with
+//! [`set_current`] so download threads and path helpers that don't hold the
+//! store can honour them.
+//!
+//! - A custom models folder replaces `~/.OminiX/models` in every model path
+//!   ([`models_path`]); models already downloaded are not moved.
+//! - [`acquire_slot`] limits how many models download at once and
+//!   [`throttle`] caps the combined bandwidth of all downloads.
+//! - Mirrors are tried in order before huggingface.co ([`hf_endpoints`]).
+//! - A download that receives nothing for [`DownloadSettings::stall_secs`]
+//!   is retried after [`retry_delay`], up to
+//!   [`DownloadSettings::stall_retries`] times, then reported as stalled
+//!   instead of waiting forever. Loads get [`DownloadSettings::load_timeout_mins`].
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Condvar, Mutex, OnceLock, RwLock};
+use std::time::{Duration, Instant};
+
+/// Where models are stored unless changed in settings
+pub const DEFAULT_MODELS_DIR: &str = "~/.OminiX/models";
+
+pub const HF_ENDPOINT: &str = "https://huggingface.co";
+
+/// Upper bound for [`DownloadSettings::max_concurrent`]
+pub const MAX_CONCURRENT_LIMIT: usize = 8;
+
+/// Upper bound for [`DownloadSettings::stall_retries`]
+pub const MAX_STALL_RETRIES: u32 = 10;
+
+/// Download settings, stored in preferences
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DownloadSettings {
+    /// Replaces [`DEFAULT_MODELS_DIR`]; `None` keeps the default
+    pub models_dir: Option<String>,
+    /// Models downloading at once; further downloads wait for a slot
+    pub max_concurrent: usize,
+    /// Combined cap for all downloads in MB/s, 0 for no cap
+    pub max_mb_per_sec: u32,
+    /// HuggingFace mirror base URLs (e.g. `https://hf-mirror.com`), tried in
+    /// order before huggingface.co
+    pub mirrors: Vec<String>,
+    /// Check a finished download against its file list and report missing
+    /// or truncated files as a failure
+    pub verify_after_download: bool,
+    /// Remove partial downloads left by earlier sessions at startup
+    pub cleanup_partial_on_start: bool,
+    /// Seconds without data before a download counts as stalled
+    pub stall_secs: u64,
+    /// Times a stalled download is retried before it is reported
+    pub stall_retries: u32,
+    /// Minutes a model load may take before it counts as stalled
+    pub load_timeout_mins: u64,
+}
+
+impl Default for DownloadSettings {
+    fn default() -> Self {
+        Self {
+            models_dir: None,
+            max_concurrent: 2,
+            max_mb_per_sec: 0,
+            mirrors: Vec::new(),
+            verify_after_download: true,
+            cleanup_partial_on_start: false,
+            stall_secs: 60,
+            stall_retries: 2,
+            load_timeout_mins: 10,
+        }
+    }
+}
+
+impl DownloadSettings {
+    /// Check values entered in settings before they are saved
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(dir) = &self.models_dir {
+            let expanded = expand_home(dir);
+            let path = Path::new(&expanded);
+            if !path.is_absolute() {
+                return Err(format!("Models folder must be an absolute path: {}", dir));
+            }
+            if path.exists() && !path.is_dir() {
+                return Err(format!("Models folder is a file: {}", dir));
+            }
+        }
+        if !(1..=MAX_CONCURRENT_LIMIT).contains(&self.max_concurrent) {
+            return Err(format!("Simultaneous downloads must be between 1 and {}", MAX_CONCURRENT_LIMIT));
+        }
+        for mirror in &self.mirrors {
+            if !(mirror.starts_with("https://") || mirror.starts_with("http://")) || mirror.contains(char::is_whitespace) {
+                return Err(format!("Mirror must be an http(s) URL: {}", mirror));
+            }
+        }
+        if !(10..=3600).contains(&self.stall_secs) {
+            return Err("Stall timeout must be between 10 and 3600 seconds".to_string());
+        }
+        if self.stall_retries > MAX_STALL_RETRIES {
+            return Err(format!("Automatic retries must be at most {}", MAX_STALL_RETRIES));
+        }
+        if !(1..=120).contains(&self.load_timeout_mins) {
+            return Err("Load timeout must be between 1 and 120 minutes".to_string());
+        }
+        Ok(())
+    }
+
+    /// The models folder, with `~` expanded
+    pub fn models_dir(&self) -> String {
+        expand_home(self.models_dir.as_deref().unwrap_or(DEFAULT_MODELS_DIR))
+    }
+}
+
+/// Split a comma or line separated list of mirror URLs, dropping trailing
+/// slashes and duplicates
+pub fn parse_mirrors(text: &str) -> Vec<String> {
+    let mut mirrors: Vec<String> = Vec::new();
+    for url in text.split([',', '\n', ' ']).map(|s| s.trim().trim_end_matches('/')) {
+        if !url.is_empty() && !mirrors.iter().any(|m| m == url) {
+            mirrors.push(url.to_string());
+        }
+    }
+    mirrors
+}
+
+// ─── Mirrored settings ────────────────────────────────────────────────────────
+
+fn current_lock() -> &'static RwLock<DownloadSettings> {
+    static CURRENT: OnceLock<RwLock<DownloadSettings>> = OnceLock::new();
+    CURRENT.get_or_init(|| RwLock::new(DownloadSettings::default()))
+}
+
+/// The settings in effect
+pub fn current() -> DownloadSettings {
+    current_lock().read().unwrap().clone()
+}
+
+/// Mirror [`crate::Preferences::downloads`]
+pub fn set_current(settings: &DownloadSettings) {
+    *current_lock().write().unwrap() = settings.clone();
+    SLOTS.1.notify_all();
+}
+
+/// Expand `~` in a model path, moving paths under [`DEFAULT_MODELS_DIR`]
+/// into the custom models folder when one is set
+pub fn models_path(path: &str) -> String {
+    let default_dir = expand_home(DEFAULT_MODELS_DIR);
+    let expanded = expand_home(path);
+    let custom_dir = current_lock().read().unwrap().models_dir.clone();
+    match (custom_dir, expanded.strip_prefix(&default_dir)) {
+        (Some(dir), Some(rest)) if rest.is_empty() || rest.starts_with(['/', '\\']) => {
+            format!("{}{}", expand_home(&dir).trim_end_matches('/'), rest)
+        }
+        _ => expanded,
+    }
+}
+
+/// Base URLs to fetch HuggingFace repos from, mirrors first
+pub fn hf_endpoints() -> Vec<String> {
+    let mut endpoints = current_lock().read().unwrap().mirrors.clone();
+    endpoints.push(HF_ENDPOINT.to_string());
+    endpoints
+}
+
+fn expand_home(path: &str) -> String {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest).to_string_lossy().to_string(),
+        _ => path.to_string(),
+    }
+}
+
+// ─── Concurrency ──────────────────────────────────────────────────────────────
+
+/// Downloads running now, and a signal for when one ends
+static SLOTS: (Mutex<usize>, Condvar) = (Mutex::new(0), Condvar::new());
+
+/// A running download's share of [`DownloadSettings::max_concurrent`],
+/// released on drop
+pub struct DownloadSlot(());
+
+impl Drop for DownloadSlot {
+    fn drop(&mut self) {
+        *SLOTS.0.lock().unwrap() -= 1;
+        SLOTS.1.notify_one();
+    }
+}
+
+/// Wait for a free download slot. Returns `None` if `cancel` is set while
+/// waiting.
+pub fn acquire_slot(cancel: &AtomicBool) -> Option<DownloadSlot> {
+    let mut running = SLOTS.0.lock().unwrap();
+    loop {
+        if cancel.load(Ordering::SeqCst) { return None; }
+        if *running < concurrency() {
+            *running += 1;
+            return Some(DownloadSlot(()));
+        }
+        // Wake up now and then to notice cancellation
+        running = SLOTS.1.wait_timeout(running, Duration::from_millis(250)).unwrap().0;
+    }
+}
+
+/// Whether another download can start without waiting
+pub fn slot_available() -> bool {
+    *SLOTS.0.lock().unwrap() < concurrency()
+}
+
+/// Downloads allowed at once; one while [`crate::power`] saves energy
+fn concurrency() -> usize {
+    if crate::power::is_active() { 1 } else { current().max_concurrent.max(1) }
+}
+
+/// Let queued downloads check again whether they can start
+pub(crate) fn wake_queue() {
+    SLOTS.1.notify_all();
+}
+
+// ─── Stalls ───────────────────────────────────────────────────────────────────
+
+/// Wait before retry `attempt` of a stalled download (1 for the first):
+/// 5 s, 15 s, 45 s, then 2 minutes between further retries
+pub fn retry_delay(attempt: u32) -> Duration {
+    Duration::from_secs((5 * 3u64.pow(attempt.saturating_sub(1).min(3))).min(120))
+}
+
+/// Whether a download error means the connection stalled or dropped, so
+/// trying again may help
+pub fn is_stall(error: &str) -> bool {
+    let error = error.to_lowercase();
+    error.contains("timed out") || error.contains("timeout") || error.contains("connection reset")
+        || error.contains("connection closed") || error.contains("unexpected eof")
+}
+
+// ─── Bandwidth ────────────────────────────────────────────────────────────────
+
+/// When the next byte may be read under the cap, shared by all downloads
+static NEXT_READ: Mutex<Option<Instant>> = Mutex::new(None);
+
+/// Call after reading `bytes` from a download; sleeps as needed to keep the
+/// combined rate under [`DownloadSettings::max_mb_per_sec`]
+pub fn throttle(bytes: usize) {
+    let limit = current_lock().read().unwrap().max_mb_per_sec;
+    if limit == 0 { return; }
+    let cost = Duration::from_secs_f64(bytes as f64 / (limit as f64 * 1_048_576.0));
+    let now = Instant::now();
+    let start = {
+        let mut next = NEXT_READ.lock().unwrap();
+        let start = next.map_or(now, |n| n.max(now));
+        *next = Some(start + cost);
+        start
+    };
+    if start > now {
+        std::thread::sleep(start - now);
+    }
+}
+
+// ─── Bulk downloads ───────────────────────────────────────────────────────────
+
+/// Where the models of a bulk download stand, counted by the Hub
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BulkProgress {
+    /// Models in the bulk download
+    pub count: usize,
+    pub active: usize,
+    /// Waiting for a download slot
+    pub waiting: usize,
+    pub done: usize,
+    pub failed: usize,
+    /// Bytes received and expected by the active downloads
+    pub bytes: u64,
+    pub total: u64,
+    /// Combined rate of the active downloads, bytes per second
+    pub speed: u64,
+}
+
+impl BulkProgress {
+    /// "Downloading 2 of 5 model(s), 2 waiting, 1 done — 3.4 GB of 12.0 GB at 45.2 MB/s"
+    pub fn summary(&self) -> String {
+        if self.active + self.waiting == 0 {
+            return match self.failed {
+                0 => format!("Downloaded {} model(s).", self.done),
+                _ => format!("Downloaded {} of {} model(s); {} failed.", self.done, self.count, self.failed),
+            };
+        }
+        let mut text = format!("Downloading {} of {} model(s)", self.active, self.count);
+        if self.waiting > 0 { text.push_str(&format!(", {} waiting", self.waiting)); }
+        if self.done > 0 { text.push_str(&format!(", {} done", self.done)); }
+        if self.failed > 0 { text.push_str(&format!(", {} failed", self.failed)); }
+        if self.total > 0 {
+            text.push_str(&format!(
+                " — {} of {}",
+                crate::disk_space::format_size(self.bytes),
+                crate::disk_space::format_size(self.total)
+            ));
+            if self.speed > 0 {
+                text.push_str(&format!(" at {:.1} MB/s", self.speed as f64 / 1_048_576.0));
+            }
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_and_parse() {
+        assert!(DownloadSettings::default().validate().is_ok());
+        let bad = DownloadSettings { max_concurrent: 0, ..Default::default() };
+        assert!(bad.validate().is_err());
+        let bad = DownloadSettings { models_dir: Some("models".to_string()), ..Default::default() };
+        assert!(bad.validate().is_err());
+
+        let mirrors = parse_mirrors("https://hf-mirror.com/, https://hf-mirror.com\nhttp://10.0.0.2:8000");
+        assert_eq!(mirrors, vec!["https://hf-mirror.com", "http://10.0.0.2:8000"]);
+        let bad = DownloadSettings { mirrors: vec!["hf-mirror.com".to_string()], ..Default::default() };
+        assert!(bad.validate().is_err());
+        let bad = DownloadSettings { stall_secs: 0, ..Default::default() };
+        assert!(bad.validate().is_err());
+    }
+
+    #[test]
+    fn test_stall_retries() {
+        assert_eq!(retry_delay(1), Duration::from_secs(5));
+        assert_eq!(retry_delay(2), Duration::from_secs(15));
+        assert_eq!(retry_delay(3), Duration::from_secs(45));
+        assert_eq!(retry_delay(9), Duration::from_secs(120));
+        assert!(is_stall("error decoding response body: operation timed out"));
+        assert!(is_stall("Connection reset by peer (os error 104)"));
+        assert!(!is_stall("HTTP 404 Not Found"));
+    }
+
+    #[test]
+    fn test_bulk_summary() {
+        let running = BulkProgress { count: 5, active: 2, waiting: 2, done: 1, ..Default::default() };
+        assert_eq!(running.summary(), "Downloading 2 of 5 model(s), 2 waiting, 1 done");
+        let sized = BulkProgress { total: 4 * 1_073_741_824, speed: 2 * 1_048_576, ..running.clone() };
+        assert!(sized.summary().ends_with(" at 2.0 MB/s"), "{}", sized.summary());
+
+        let finished = BulkProgress { count: 3, done: 2, failed: 1, ..Default::default() };
+        assert_eq!(finished.summary(), "Downloaded 2 of 3 model(s); 1 failed.");
+        assert_eq!(BulkProgress { count: 3, done: 3, ..Default::default() }.summary(), "Downloaded 3 model(s).");
+    }
+}
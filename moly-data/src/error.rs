@@ -0,0 +1,245 @@
+//! User-facing error categories
+//!
+//! Most of the data layer still returns `Result<_, String>`. [`MolyError`]
+//! sorts those messages (and `reqwest`, I/O and OminiX API errors) into the
+//! few cases a user can act on, so screens can show what went wrong and what
+//! to try next instead of a raw error string:
+//!
+//! ```rust,ignore
+//! Err(e) => status.set_text(cx, &format!("Transcription failed: {}", MolyError::from_message(&e).user_message())),
+//! ```
+
+use crate::ominix_api_client::OminiXApiError;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum MolyError {
+    /// No response from a remote server (offline, DNS, timeout, proxy)
+    Network(String),
+    /// Credentials missing or rejected (401 / 403, gated models)
+    Auth(String),
+    /// The local ominix-api runtime isn't reachable
+    RuntimeOffline,
+    /// A write failed because the disk is full
+    DiskFull(String),
+    /// A download or load stopped making progress and was given up
+    Stalled(String),
+    Cancelled,
+    /// Any other non-2xx response; `body` is truncated
+    Server { status: u16, body: String },
+    /// Doesn't fit a category; shown as is
+    Other(String),
+}
+
+/// Longest server body kept in [`MolyError::Server`]
+const MAX_BODY_CHARS: usize = 200;
+
+impl MolyError {
+    /// Classify an error message from the string-based APIs
+    pub fn from_message(message: &str) -> Self {
+        let lower = message.to_lowercase();
+        let message = message.trim().to_string();
+
+        if lower.contains("cancelled") || lower.contains("canceled") {
+            return Self::Cancelled;
+        }
+        if lower.contains("no space left") || lower.contains("os error 28") || lower.contains("disk full")
+            || lower.contains("not enough space")
+        {
+            return Self::DiskFull(message);
+        }
+        if lower.contains("stalled") {
+            return Self::Stalled(message);
+        }
+        if let Some((status, body)) = http_status(&message) {
+            return Self::from_status(status, body);
+        }
+        if lower.contains("unauthorized") || lower.contains("access denied") || lower.contains("authentication")
+            || lower.contains("invalid api key")
+        {
+            return Self::Auth(message);
+        }
+        let local = lower.contains("localhost") || lower.contains("127.0.0.1") || lower.contains("ominix-api");
+        let unreachable = lower.contains("connection refused") || lower.contains("connection failed")
+            || lower.contains("error sending request") || lower.contains("timed out")
+            || lower.contains("dns error") || lower.contains("failed to lookup address")
+            || lower.contains("network is unreachable") || lower.contains("connection reset");
+        if unreachable && local {
+            Self::RuntimeOffline
+        } else if unreachable {
+            Self::Network(message)
+        } else {
+            Self::Other(message)
+        }
+    }
+
+    /// Classify a non-2xx HTTP response
+    pub fn from_status(status: u16, body: &str) -> Self {
+        let body: String = body.trim().chars().take(MAX_BODY_CHARS).collect();
+        match status {
+            401 | 403 => Self::Auth(if body.is_empty() { format!("HTTP {}", status) } else { body }),
+            507 => Self::DiskFull(body),
+            _ => Self::Server { status, body },
+        }
+    }
+
+    /// Stable name of the category, without any message text
+    pub fn category(&self) -> &'static str {
+        match self {
+            Self::Network(_) => "network",
+            Self::Auth(_) => "auth",
+            Self::RuntimeOffline => "runtime_offline",
+            Self::DiskFull(_) => "disk_full",
+            Self::Stalled(_) => "stalled",
+            Self::Cancelled => "cancelled",
+            Self::Server { status, .. } if *status >= 500 => "server_5xx",
+            Self::Server { .. } => "server_4xx",
+            Self::Other(_) => "other",
+        }
+    }
+
+    /// Short description of what went wrong
+    pub fn title(&self) -> &'static str {
+        match self {
+            Self::Network(_) => "Couldn't reach the server",
+            Self::Auth(_) => "The server rejected the credentials",
+            Self::RuntimeOffline => "The local model runtime isn't running",
+            Self::DiskFull(_) => "Not enough disk space",
+            Self::Stalled(_) => "Stopped making progress",
+            Self::Cancelled => "Cancelled",
+            Self::Server { status, .. } if *status >= 500 => "The server had an internal error",
+            Self::Server { status: 404, .. } => "The server couldn't find that model or endpoint",
+            Self::Server { status: 429, .. } => "The server is rate limiting requests",
+            Self::Server { .. } => "The server refused the request",
+            Self::Other(_) => "Something went wrong",
+        }
+    }
+
+    /// What the user can do about it
+    pub fn suggestion(&self) -> Option<&'static str> {
+        match self {
+            Self::Network(_) => Some("Check your internet connection or proxy settings, then try again."),
+            Self::Auth(_) => Some("Check the API key in Settings. For gated HuggingFace models, accept the license on huggingface.co and set HF_TOKEN."),
+            Self::RuntimeOffline => Some("Load a model from the Model Hub to start ominix-api, then try again."),
+            Self::DiskFull(_) => Some("Free up space, or remove unused models from Settings → Model cleanup."),
+            Self::Stalled(_) => Some("Retry, or cancel and try again later. How long to wait is set in Settings → Storage & Downloads."),
+            Self::Cancelled => None,
+            Self::Server { status, .. } if *status >= 500 => Some("Try again in a moment; check the runtime log if it keeps failing."),
+            Self::Server { status: 404, .. } => Some("Check the model name, or update the model registry."),
+            Self::Server { status: 429, .. } => Some("Wait a minute before retrying."),
+            Self::Server { .. } => None,
+            Self::Other(_) => None,
+        }
+    }
+
+    /// Title, detail and suggestion as one line for a status label
+    pub fn user_message(&self) -> String {
+        let detail = match self {
+            Self::Server { status, body } if body.is_empty() => format!("HTTP {}", status),
+            Self::Server { status, body } => format!("HTTP {}: {}", status, body),
+            Self::Other(message) => return message.clone(),
+            // Already a sentence, e.g. the disk space pre-flight's shortfall
+            Self::DiskFull(detail) if !detail.is_empty() => {
+                return format!("{}. {}. {}", self.title(), detail.trim_end_matches('.'), self.suggestion().unwrap_or_default());
+            }
+            _ => String::new(),
+        };
+        let mut text = self.title().to_string();
+        if !detail.is_empty() {
+            text.push_str(&format!(" ({})", detail));
+        }
+        if let Some(suggestion) = self.suggestion() {
+            text.push_str(". ");
+            text.push_str(suggestion);
+        }
+        text
+    }
+}
+
+impl std::fmt::Display for MolyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Network(e) | Self::Auth(e) | Self::DiskFull(e) | Self::Stalled(e) | Self::Other(e) if !e.is_empty() => {
+                write!(f, "{}: {}", self.title(), e)
+            }
+            Self::Server { status, body } => write!(f, "HTTP {}: {}", status, body),
+            _ => write!(f, "{}", self.title()),
+        }
+    }
+}
+
+impl std::error::Error for MolyError {}
+
+impl From<MolyError> for String {
+    fn from(e: MolyError) -> Self {
+        e.to_string()
+    }
+}
+
+impl From<OminiXApiError> for MolyError {
+    fn from(e: OminiXApiError) -> Self {
+        match e {
+            // The OminiX client only talks to the local runtime
+            OminiXApiError::Connection(_) => Self::RuntimeOffline,
+            OminiXApiError::Http { status, body } => Self::from_status(status, &body),
+            OminiXApiError::Api(message) | OminiXApiError::Decode(message) => Self::from_message(&message),
+        }
+    }
+}
+
+impl From<reqwest::Error> for MolyError {
+    fn from(e: reqwest::Error) -> Self {
+        if let Some(status) = e.status() {
+            return Self::from_status(status.as_u16(), "");
+        }
+        if e.is_timeout() {
+            return Self::Network(e.to_string());
+        }
+        Self::from_message(&e.to_string())
+    }
+}
+
+impl From<std::io::Error> for MolyError {
+    fn from(e: std::io::Error) -> Self {
+        Self::from_message(&e.to_string())
+    }
+}
+
+/// First "HTTP 404" / "API 401" style status code in a message, with the
+/// text after it
+fn http_status(message: &str) -> Option<(u16, &str)> {
+    ["HTTP ", "API "].iter().find_map(|marker| {
+        let rest = &message[message.find(marker)? + marker.len()..];
+        let digits = rest.chars().take_while(|c| c.is_ascii_digit()).count();
+        if digits != 3 { return None; }
+        let status = rest[..3].parse().ok()?;
+        Some((status, rest[3..].trim_start_matches([':', ' '])))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_messages() {
+        assert_eq!(MolyError::from_message("Download cancelled"), MolyError::Cancelled);
+        assert!(matches!(MolyError::from_message("No space left on device (os error 28)"), MolyError::DiskFull(_)));
+        assert!(matches!(MolyError::from_message("HF API 401 Unauthorized"), MolyError::Auth(_)));
+        assert_eq!(
+            MolyError::from_message("Failed to download: HTTP 404 Not Found"),
+            MolyError::Server { status: 404, body: "Not Found".to_string() },
+        );
+        assert_eq!(
+            MolyError::from_message("HTTP 503: model busy"),
+            MolyError::Server { status: 503, body: "model busy".to_string() },
+        );
+        assert_eq!(
+            MolyError::from_message("error sending request for url (http://localhost:8080/v1/models): Connection refused"),
+            MolyError::RuntimeOffline,
+        );
+        assert!(matches!(MolyError::from_message("error sending request for url (https://huggingface.co/api): dns error"), MolyError::Network(_)));
+        assert!(matches!(MolyError::from_message("Download stalled: no data for 60 s after 2 retries (operation timed out)"), MolyError::Stalled(_)));
+        assert_eq!(MolyError::from_message("Invalid WAV header"), MolyError::Other("Invalid WAV header".to_string()));
+        assert!(MolyError::RuntimeOffline.user_message().contains("Model Hub"));
+    }
+}
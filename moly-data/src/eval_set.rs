@@ -0,0 +1,601 @@
+//! Batch evaluation of an LLM against a JSONL file of prompts
+//!
+//! Each line of an eval set is one case ([`parse`]). It can be a chat
+//! completion body, `{"messages": [...]}`, whose trailing assistant message,
+//! if any, is the expected answer; or a single prompt, `{"prompt": "..."}`
+//! (also `input` or `question`), with an optional `system` prompt. A case is
+//! scored when it has an `expected` answer (also `ideal` or `answer`),
+//! compared loosely ([`normalize`]), or a `pattern` (also `regex`) the answer
+//! must contain a match of. Patterns take the common regex syntax: `.`,
+//! classes like `[a-z]` and `\d`, groups, `|`, `* + ? {n,m}`, `^ $ \b`, and
+//! a leading `(?i)` to ignore case.
+//!
+//! [`run_case`] times one case and keeps the token counts the runtime
+//! reports; [`Summary`] totals a run and [`results_csv`] / [`results_jsonl`]
+//! write it out.
+
+use serde::Serialize;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crate::chat_template::{self, ChatTemplate};
+use crate::ominix_api_client::{ChatMessage, OminiXApiClient, Usage};
+
+/// One prompt of an eval set
+#[derive(Clone, Debug)]
+pub struct EvalCase {
+    /// The line's `id`, or its line number
+    pub id: String,
+    pub messages: Vec<ChatMessage>,
+    pub expected: Option<String>,
+    pub pattern: Option<String>,
+}
+
+impl EvalCase {
+    /// The last user message, to show the case by
+    pub fn prompt(&self) -> String {
+        self.messages.iter().rev().find(|m| m.role == "user").map(|m| m.text()).unwrap_or_default()
+    }
+
+    /// Whether `answer` passes: matches the pattern when there is one,
+    /// otherwise equals the expected answer. `None` when there's neither.
+    pub fn score(&self, answer: &str) -> Option<bool> {
+        let answer = without_think(answer);
+        if let Some(pattern) = &self.pattern {
+            return Some(Pattern::new(pattern).is_ok_and(|p| p.is_match(answer)));
+        }
+        self.expected.as_ref().map(|expected| normalize(expected) == normalize(answer))
+    }
+}
+
+/// Read an eval set from a JSONL file
+pub fn load(path: &Path) -> Result<Vec<EvalCase>, String> {
+    let text = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    parse(&text)
+}
+
+/// The cases of an eval set, one JSON object per line. Blank lines are
+/// skipped; anything else that isn't a case is an error naming its line.
+pub fn parse(text: &str) -> Result<Vec<EvalCase>, String> {
+    let mut cases = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let case = parse_case(line, i + 1).map_err(|e| format!("Line {}: {}", i + 1, e))?;
+        cases.push(case);
+    }
+    if cases.is_empty() {
+        return Err("The file has no prompts.".to_string());
+    }
+    Ok(cases)
+}
+
+fn parse_case(line: &str, number: usize) -> Result<EvalCase, String> {
+    let value: serde_json::Value = serde_json::from_str(line).map_err(|e| format!("not JSON: {}", e))?;
+    let field = |keys: &[&str]| keys.iter().find_map(|k| value.get(*k).and_then(|v| v.as_str())).map(str::to_string);
+    let id = match value.get("id") {
+        Some(serde_json::Value::String(id)) => id.clone(),
+        Some(serde_json::Value::Number(id)) => id.to_string(),
+        _ => number.to_string(),
+    };
+    let mut expected = field(&["expected", "ideal", "answer"]);
+    let pattern = field(&["pattern", "regex"]);
+    if let Some(pattern) = &pattern {
+        Pattern::new(pattern).map_err(|e| format!("pattern {:?}: {}", pattern, e))?;
+    }
+
+    let mut messages = Vec::new();
+    if let Some(list) = value.get("messages").and_then(|m| m.as_array()) {
+        for message in list {
+            let role = message.get("role").and_then(|r| r.as_str()).ok_or("a message has no role")?;
+            let text = match message.get("content") {
+                Some(serde_json::Value::String(text)) => text.clone(),
+                // Content parts: keep the text ones
+                Some(serde_json::Value::Array(parts)) => parts.iter()
+                    .filter_map(|p| p.get("text").and_then(|t| t.as_str()))
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+                _ => String::new(),
+            };
+            messages.push(ChatMessage { role: role.to_string(), ..ChatMessage::user(text) });
+        }
+        // A trailing assistant turn is the answer to expect
+        if messages.last().is_some_and(|m| m.role == "assistant") {
+            let answer = messages.pop().map(|m| m.text());
+            expected = expected.or(answer);
+        }
+    } else if let Some(prompt) = field(&["prompt", "input", "question"]) {
+        if let Some(system) = field(&["system"]) {
+            messages.push(ChatMessage::system(system));
+        }
+        messages.push(ChatMessage::user(prompt));
+    }
+    if !messages.iter().any(|m| m.role == "user") {
+        return Err("no prompt; give \"prompt\" or \"messages\"".to_string());
+    }
+    Ok(EvalCase { id, messages, expected, pattern })
+}
+
+/// The outcome of one case
+#[derive(Clone, Debug)]
+pub struct EvalResult {
+    pub answer: Result<String, String>,
+    pub latency: Duration,
+    pub usage: Option<Usage>,
+    /// `None` when the case isn't scored or the request failed
+    pub passed: Option<bool>,
+}
+
+/// Send a case to `model` and score the answer. Blocks; call it off the UI
+/// thread.
+pub fn run_case(client: &OminiXApiClient, model: &str, template: Option<ChatTemplate>, case: &EvalCase) -> EvalResult {
+    let started = Instant::now();
+    let result = chat_template::complete_with_usage(client, model, template, case.messages.clone());
+    let latency = started.elapsed();
+    match result {
+        Ok((answer, usage)) => EvalResult { passed: case.score(&answer), answer: Ok(answer), latency, usage },
+        Err(e) => EvalResult { answer: Err(e.to_string()), latency, usage: None, passed: None },
+    }
+}
+
+/// Totals of a run
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Summary {
+    pub completed: usize,
+    pub failed: usize,
+    pub scored: usize,
+    pub passed: usize,
+    pub mean_latency: Duration,
+    pub completion_tokens: u64,
+    /// Answer tokens per second of request time, when tokens were reported
+    pub tokens_per_sec: Option<f64>,
+}
+
+impl Summary {
+    pub fn new<'a>(results: impl IntoIterator<Item = &'a EvalResult>) -> Self {
+        let mut summary = Summary::default();
+        let (mut latency, mut timed_tokens) = (Duration::ZERO, Duration::ZERO);
+        for result in results {
+            if result.answer.is_err() {
+                summary.failed += 1;
+                continue;
+            }
+            summary.completed += 1;
+            latency += result.latency;
+            if let Some(passed) = result.passed {
+                summary.scored += 1;
+                summary.passed += passed as usize;
+            }
+            if let Some(usage) = result.usage {
+                summary.completion_tokens += usage.completion_tokens as u64;
+                timed_tokens += result.latency;
+            }
+        }
+        if summary.completed > 0 {
+            summary.mean_latency = latency / summary.completed as u32;
+        }
+        if !timed_tokens.is_zero() {
+            summary.tokens_per_sec = Some(summary.completion_tokens as f64 / timed_tokens.as_secs_f64());
+        }
+        summary
+    }
+
+    /// e.g. `7/10 passed (70%) · 1 failed · 1.24 s mean · 38.5 tok/s`
+    pub fn line(&self) -> String {
+        let mut parts = Vec::new();
+        if self.scored > 0 {
+            parts.push(format!("{}/{} passed ({:.0}%)", self.passed, self.scored, self.passed as f64 * 100.0 / self.scored as f64));
+        } else {
+            parts.push(format!("{} answered", self.completed));
+        }
+        if self.failed > 0 {
+            parts.push(format!("{} failed", self.failed));
+        }
+        if self.completed > 0 {
+            parts.push(format!("{:.2} s mean", self.mean_latency.as_secs_f64()));
+        }
+        if let Some(rate) = self.tokens_per_sec {
+            parts.push(format!("{:.1} tok/s", rate));
+        }
+        parts.join(" · ")
+    }
+}
+
+#[derive(Serialize)]
+struct ResultRow<'a> {
+    id: &'a str,
+    prompt: String,
+    expected: Option<&'a str>,
+    pattern: Option<&'a str>,
+    answer: Option<&'a str>,
+    error: Option<&'a str>,
+    passed: Option<bool>,
+    latency_ms: u128,
+    prompt_tokens: Option<u32>,
+    completion_tokens: Option<u32>,
+}
+
+fn rows<'a>(cases: &'a [EvalCase], results: &'a [Option<EvalResult>]) -> impl Iterator<Item = ResultRow<'a>> {
+    cases.iter().zip(results).filter_map(|(case, result)| {
+        let result = result.as_ref()?;
+        Some(ResultRow {
+            id: &case.id,
+            prompt: case.prompt(),
+            expected: case.expected.as_deref(),
+            pattern: case.pattern.as_deref(),
+            answer: result.answer.as_deref().ok(),
+            error: result.answer.as_ref().err().map(String::as_str),
+            passed: result.passed,
+            latency_ms: result.latency.as_millis(),
+            prompt_tokens: result.usage.map(|u| u.prompt_tokens),
+            completion_tokens: result.usage.map(|u| u.completion_tokens),
+        })
+    })
+}
+
+/// The cases that were run, one JSON object per line
+pub fn results_jsonl(cases: &[EvalCase], results: &[Option<EvalResult>]) -> String {
+    rows(cases, results)
+        .filter_map(|row| serde_json::to_string(&row).ok())
+        .map(|line| line + "\n")
+        .collect()
+}
+
+/// The cases that were run as CSV, with a header row
+pub fn results_csv(cases: &[EvalCase], results: &[Option<EvalResult>]) -> String {
+    let mut csv = "id,prompt,expected,pattern,answer,error,passed,latency_ms,prompt_tokens,completion_tokens\n".to_string();
+    let opt = |n: Option<u32>| n.map(|n| n.to_string()).unwrap_or_default();
+    for row in rows(cases, results) {
+        let fields = [
+            csv_field(row.id),
+            csv_field(&row.prompt),
+            csv_field(row.expected.unwrap_or("")),
+            csv_field(row.pattern.unwrap_or("")),
+            csv_field(row.answer.unwrap_or("")),
+            csv_field(row.error.unwrap_or("")),
+            row.passed.map(|p| p.to_string()).unwrap_or_default(),
+            row.latency_ms.to_string(),
+            opt(row.prompt_tokens),
+            opt(row.completion_tokens),
+        ];
+        csv.push_str(&fields.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+/// An answer as compared for an exact match: lowercase, whitespace
+/// collapsed, and without surrounding quotes or a final period
+pub fn normalize(answer: &str) -> String {
+    let words = without_think(answer).split_whitespace().collect::<Vec<_>>().join(" ");
+    words.trim_matches(|c| c == '"' || c == '\'').trim_end_matches('.').trim().to_lowercase()
+}
+
+fn without_think(answer: &str) -> &str {
+    match answer.find("</think>") {
+        Some(end) => &answer[end + "</think>".len()..],
+        None => answer,
+    }
+}
+
+// ─── Patterns ─────────────────────────────────────────────────────────────────
+
+/// A regular expression, matched by backtracking. Eval patterns are short
+/// and answers a few KB, so there is no need for more.
+struct Pattern {
+    alternatives: Vec<Vec<Piece>>,
+    ignore_case: bool,
+}
+
+struct Piece {
+    atom: Atom,
+    min: usize,
+    max: Option<usize>,
+}
+
+enum Atom {
+    Any,
+    Start,
+    End,
+    WordBoundary,
+    Set { negated: bool, items: Vec<SetItem> },
+    Group(Vec<Vec<Piece>>),
+}
+
+#[derive(Clone, Copy)]
+enum SetItem {
+    Range(char, char),
+    /// `\d`, `\w` or `\s`; uppercase for the complement
+    Class(char),
+}
+
+impl SetItem {
+    fn escaped(c: char) -> SetItem {
+        match c {
+            'd' | 'w' | 's' | 'D' | 'W' | 'S' => SetItem::Class(c),
+            'n' => SetItem::Range('\n', '\n'),
+            't' => SetItem::Range('\t', '\t'),
+            c => SetItem::Range(c, c),
+        }
+    }
+
+    fn matches(self, c: char) -> bool {
+        match self {
+            SetItem::Range(from, to) => (from..=to).contains(&c),
+            SetItem::Class(class) => {
+                let is = match class.to_ascii_lowercase() {
+                    'd' => c.is_ascii_digit(),
+                    'w' => c.is_alphanumeric() || c == '_',
+                    _ => c.is_whitespace(),
+                };
+                is != class.is_ascii_uppercase()
+            }
+        }
+    }
+}
+
+impl Pattern {
+    fn new(pattern: &str) -> Result<Pattern, String> {
+        let (ignore_case, pattern) = match pattern.strip_prefix("(?i)") {
+            Some(rest) => (true, rest),
+            None => (false, pattern),
+        };
+        let mut parser = Parser { chars: pattern.chars().collect(), pos: 0 };
+        let alternatives = parser.alternatives(0)?;
+        Ok(Pattern { alternatives, ignore_case })
+    }
+
+    /// Whether the pattern matches anywhere in `text`
+    fn is_match(&self, text: &str) -> bool {
+        let text: Vec<char> = text.chars().collect();
+        let matcher = Matcher { text: &text, ignore_case: self.ignore_case };
+        (0..=text.len()).any(|start| matcher.alternatives(&self.alternatives, start, &mut |_| true))
+    }
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    fn next(&mut self) -> Option<char> {
+        let c = self.chars.get(self.pos).copied();
+        self.pos += 1;
+        c
+    }
+
+    fn alternatives(&mut self, depth: usize) -> Result<Vec<Vec<Piece>>, String> {
+        let mut alternatives = vec![Vec::new()];
+        while let Some(&c) = self.chars.get(self.pos) {
+            match c {
+                '|' => {
+                    self.pos += 1;
+                    alternatives.push(Vec::new());
+                }
+                ')' if depth > 0 => break,
+                ')' => return Err("unmatched )".to_string()),
+                _ => {
+                    let atom = self.atom(depth)?;
+                    let (min, max) = self.quantifier()?;
+                    alternatives.last_mut().unwrap().push(Piece { atom, min, max });
+                }
+            }
+        }
+        Ok(alternatives)
+    }
+
+    fn atom(&mut self, depth: usize) -> Result<Atom, String> {
+        let literal = |c| Atom::Set { negated: false, items: vec![SetItem::Range(c, c)] };
+        Ok(match self.next().unwrap_or_default() {
+            '.' => Atom::Any,
+            '^' => Atom::Start,
+            '$' => Atom::End,
+            '(' => {
+                if self.chars[self.pos..].starts_with(&['?', ':']) {
+                    self.pos += 2;
+                }
+                let group = self.alternatives(depth + 1)?;
+                if self.next() != Some(')') {
+                    return Err("missing )".to_string());
+                }
+                Atom::Group(group)
+            }
+            '[' => self.set()?,
+            '\\' => match self.next().ok_or("trailing \\")? {
+                'b' => Atom::WordBoundary,
+                c => Atom::Set { negated: false, items: vec![SetItem::escaped(c)] },
+            },
+            c @ ('*' | '+' | '?' | '{') => return Err(format!("nothing to repeat before {}", c)),
+            c => literal(c),
+        })
+    }
+
+    fn set(&mut self) -> Result<Atom, String> {
+        let negated = self.chars.get(self.pos) == Some(&'^');
+        if negated {
+            self.pos += 1;
+        }
+        let mut items = Vec::new();
+        loop {
+            let c = self.next().ok_or("missing ]")?;
+            let item = match c {
+                ']' if !items.is_empty() => break,
+                '\\' => SetItem::escaped(self.next().ok_or("missing ]")?),
+                c => SetItem::Range(c, c),
+            };
+            // `a-z`, unless the `-` ends the set
+            if let (SetItem::Range(from, _), Some('-'), Some(&to)) = (item, self.chars.get(self.pos).copied(), self.chars.get(self.pos + 1)) {
+                if to != ']' {
+                    self.pos += 2;
+                    if to < from {
+                        return Err(format!("range {}-{} is backwards", from, to));
+                    }
+                    items.push(SetItem::Range(from, to));
+                    continue;
+                }
+            }
+            items.push(item);
+        }
+        Ok(Atom::Set { negated, items })
+    }
+
+    fn quantifier(&mut self) -> Result<(usize, Option<usize>), String> {
+        let bounds = match self.chars.get(self.pos) {
+            Some('*') => (0, None),
+            Some('+') => (1, None),
+            Some('?') => (0, Some(1)),
+            Some('{') => {
+                let end = self.chars[self.pos..].iter().position(|c| *c == '}').ok_or("missing }")?;
+                let inside: String = self.chars[self.pos + 1..self.pos + end].iter().collect();
+                let number = |n: &str| n.trim().parse::<usize>().map_err(|_| format!("bad count {{{}}}", inside));
+                let bounds = match inside.split_once(',') {
+                    None => (number(&inside)?, Some(number(&inside)?)),
+                    Some((min, max)) if max.trim().is_empty() => (number(min)?, None),
+                    Some((min, max)) => (number(min)?, Some(number(max)?)),
+                };
+                self.pos += end;
+                bounds
+            }
+            _ => return Ok((1, Some(1))),
+        };
+        self.pos += 1;
+        // Lazy quantifiers match the same texts
+        if self.chars.get(self.pos) == Some(&'?') {
+            self.pos += 1;
+        }
+        Ok(bounds)
+    }
+}
+
+struct Matcher<'a> {
+    text: &'a [char],
+    ignore_case: bool,
+}
+
+impl Matcher<'_> {
+    fn alternatives(&self, alternatives: &[Vec<Piece>], pos: usize, then: &mut dyn FnMut(usize) -> bool) -> bool {
+        alternatives.iter().any(|pieces| self.sequence(pieces, pos, then))
+    }
+
+    fn sequence(&self, pieces: &[Piece], pos: usize, then: &mut dyn FnMut(usize) -> bool) -> bool {
+        let Some((piece, rest)) = pieces.split_first() else { return then(pos) };
+        self.repeat(piece, pos, 0, &mut |next| self.sequence(rest, next, then))
+    }
+
+    /// Match `piece` greedily, giving back repetitions until the rest matches
+    fn repeat(&self, piece: &Piece, pos: usize, count: usize, then: &mut dyn FnMut(usize) -> bool) -> bool {
+        if piece.max.map_or(true, |max| count < max)
+            && self.atom(&piece.atom, pos, &mut |next| {
+                // An empty repetition can't make progress once the minimum is met
+                (next > pos || count < piece.min) && self.repeat(piece, next, count + 1, then)
+            })
+        {
+            return true;
+        }
+        count >= piece.min && then(pos)
+    }
+
+    fn atom(&self, atom: &Atom, pos: usize, then: &mut dyn FnMut(usize) -> bool) -> bool {
+        match atom {
+            Atom::Start => pos == 0 && then(pos),
+            Atom::End => pos == self.text.len() && then(pos),
+            Atom::WordBoundary => {
+                let is_word = |i: Option<usize>| i.and_then(|i| self.text.get(i)).is_some_and(|c| SetItem::Class('w').matches(*c));
+                is_word(pos.checked_sub(1)) != is_word(Some(pos)) && then(pos)
+            }
+            Atom::Any => self.text.get(pos).is_some_and(|c| *c != '\n') && then(pos + 1),
+            Atom::Set { negated, items } => {
+                let Some(&c) = self.text.get(pos) else { return false };
+                let in_set = |c: char| items.iter().any(|item| item.matches(c));
+                let found = in_set(c)
+                    || (self.ignore_case && c.to_lowercase().chain(c.to_uppercase()).any(in_set));
+                found != *negated && then(pos + 1)
+            }
+            Atom::Group(alternatives) => self.alternatives(alternatives, pos, then),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_score() {
+        let text = r#"{"id": "capital", "prompt": "Capital of France?", "expected": "Paris"}
+
+{"messages": [{"role": "system", "content": "Be brief."}, {"role": "user", "content": [{"type": "text", "text": "2+2?"}]}, {"role": "assistant", "content": "4"}]}
+{"question": "Name a primary color.", "pattern": "(?i)\\b(red|blue|yellow)\\b"}
+{"input": "Say hi"}"#;
+        let cases = parse(text).unwrap();
+        assert_eq!(cases.len(), 4);
+        assert_eq!(cases[0].id, "capital");
+        assert_eq!(cases[1].id, "3");
+        assert_eq!(cases[1].messages.len(), 2);
+        assert_eq!(cases[1].prompt(), "2+2?");
+        assert_eq!(cases[1].expected.as_deref(), Some("4"));
+
+        assert_eq!(cases[0].score("<think>Easy.</think> paris."), Some(true));
+        assert_eq!(cases[0].score("Lyon"), Some(false));
+        assert_eq!(cases[2].score("I'd say Blue."), Some(true));
+        assert_eq!(cases[2].score("Green"), Some(false));
+        assert_eq!(cases[3].score("Hi!"), None);
+
+        assert_eq!(parse("{\"prompt\": \"a\"}\n{\"expected\": \"b\"}").unwrap_err(), "Line 2: no prompt; give \"prompt\" or \"messages\"");
+        assert!(parse(r#"{"prompt": "a", "pattern": "(unclosed"}"#).unwrap_err().starts_with("Line 1: pattern"));
+    }
+
+    #[test]
+    fn test_patterns() {
+        let is_match = |pattern: &str, text: &str| Pattern::new(pattern).unwrap().is_match(text);
+        assert!(is_match(r"^\d{3}-\d{4}$", "555-1234"));
+        assert!(!is_match(r"^\d{3}-\d{4}$", "555-12345"));
+        assert!(is_match("colou?r", "the color red"));
+        assert!(is_match("^(ab)+c$", "ababc"));
+        assert!(!is_match("^(ab)+c$", "abac"));
+        assert!(is_match("[^a-z ]", "all lower but 1"));
+        assert!(!is_match("[^a-z ]", "all lower"));
+        assert!(is_match("(?i)ANSWER: *[a-d]", "answer:   C"));
+        assert!(is_match("x*", ""));
+        assert!(!is_match(r"\bred\b", "covered"));
+        assert!(is_match("(a*)*b", "aaab"));
+        assert!(Pattern::new("a{2").is_err());
+        assert!(Pattern::new("*a").is_err());
+    }
+
+    #[test]
+    fn test_summary_and_export() {
+        let cases = parse("{\"prompt\": \"Say \\\"hi\\\", please\", \"expected\": \"hi\"}\n{\"prompt\": \"b\"}\n{\"prompt\": \"c\"}").unwrap();
+        let result = |answer: Result<&str, &str>, passed, millis, tokens| EvalResult {
+            answer: answer.map(String::from).map_err(String::from),
+            latency: Duration::from_millis(millis),
+            usage: tokens.map(|completion_tokens| Usage { prompt_tokens: 5, completion_tokens }),
+            passed,
+        };
+        let results = vec![
+            Some(result(Ok("hi"), Some(true), 1000, Some(20))),
+            Some(result(Err("HTTP 500"), None, 50, None)),
+            None,
+        ];
+        let summary = Summary::new(results.iter().flatten());
+        assert_eq!(summary.line(), "1/1 passed (100%) · 1 failed · 1.00 s mean · 20.0 tok/s");
+
+        let csv = results_csv(&cases, &results);
+        assert_eq!(csv.lines().count(), 3);
+        assert!(csv.contains("\n1,\"Say \"\"hi\"\", please\",hi,,hi,,true,1000,5,20\n"));
+        assert!(csv.ends_with("2,b,,,,HTTP 500,,50,,\n"));
+        let jsonl = results_jsonl(&cases, &results);
+        assert_eq!(jsonl.lines().count(), 2);
+        assert!(jsonl.contains("\"error\":\"HTTP 500\""));
+    }
+}
@@ -0,0 +1,277 @@
+//! Built-in filesystem tool
+//!
+//! Lets chat models list, read and write files, but only inside folders the
+//! user approved in the MCP app. Every path is resolved to its real location
+//! first, so `..` and symlinks can't reach outside those folders; links that
+//! lead nowhere are refused, and writes never follow a link. Writes also
+//! need the user's approval each time ([`crate::tool_permissions`]).
+//!
+//! The approved folders are stored in preferences; the store mirrors them
+//! here with [`set_allowed_dirs`] since the tool runs on a background
+//! thread. Paths the tool touched are queued with the chat that ran it for
+//! the chat app, which records them on that chat ([`take_touched`]).
+
+use chrono::{DateTime, Local, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, RwLock};
+
+use crate::chats::ChatId;
+use crate::tool_permissions;
+
+/// Name shown when the tool asks for approval
+pub const TOOL_NAME: &str = "Files";
+
+/// Files larger than this are read only up to this size
+const MAX_READ_BYTES: usize = 256 * 1024;
+
+/// Entries listed at most per folder
+const MAX_LIST_ENTRIES: usize = 500;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FsOp {
+    List,
+    Read,
+    Write,
+}
+
+impl FsOp {
+    pub fn label(&self) -> &'static str {
+        match self {
+            FsOp::List => "Listed",
+            FsOp::Read => "Read",
+            FsOp::Write => "Wrote",
+        }
+    }
+}
+
+/// A path the tool used on behalf of a chat
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TouchedPath {
+    pub path: String,
+    pub op: FsOp,
+    pub at: DateTime<Utc>,
+}
+
+impl TouchedPath {
+    /// One line for lists, e.g. "Read /Users/me/notes.md — Oct 16 10:20"
+    pub fn describe(&self) -> String {
+        format!("{} {} — {}", self.op.label(), self.path, self.at.with_timezone(&Local).format("%b %d %H:%M"))
+    }
+}
+
+// ─── Approved folders ─────────────────────────────────────────────────────────
+
+static ALLOWED_DIRS: RwLock<Vec<String>> = RwLock::new(Vec::new());
+static TOUCHED: Mutex<Vec<(ChatId, TouchedPath)>> = Mutex::new(Vec::new());
+
+/// Mirror [`crate::Preferences::fs_tool_dirs`]
+pub fn set_allowed_dirs(dirs: &[String]) {
+    *ALLOWED_DIRS.write().unwrap() = dirs.to_vec();
+}
+
+/// Paths touched since the last call by the chat that ran the tool,
+/// oldest first
+pub fn take_touched() -> HashMap<ChatId, Vec<TouchedPath>> {
+    let mut touched: HashMap<ChatId, Vec<TouchedPath>> = HashMap::new();
+    for (chat_id, path) in std::mem::take(&mut *TOUCHED.lock().unwrap()) {
+        touched.entry(chat_id).or_default().push(path);
+    }
+    touched
+}
+
+/// Calls made outside a chat are not recorded
+fn touch(chat_id: Option<ChatId>, path: &Path, op: FsOp) {
+    let Some(chat_id) = chat_id else { return };
+    TOUCHED.lock().unwrap().push((chat_id, TouchedPath { path: path.display().to_string(), op, at: Utc::now() }));
+}
+
+/// Resolve a path the model asked for to its real location, and check that
+/// it is inside one of `roots`. Relative paths are taken from the first
+/// root. A path that doesn't exist yet is accepted when its folder does,
+/// unless it is a link whose target doesn't exist.
+pub fn resolve(path: &str, roots: &[String]) -> Result<PathBuf, String> {
+    let roots: Vec<PathBuf> = roots.iter().filter_map(|r| std::fs::canonicalize(r).ok()).collect();
+    let Some(first) = roots.first() else {
+        return Err("No folders are shared with chat models. Add one in the MCP app.".to_string());
+    };
+    let requested = Path::new(path.trim());
+    let requested = if requested.is_absolute() { requested.to_path_buf() } else { first.join(requested) };
+
+    let real = match std::fs::canonicalize(&requested) {
+        Ok(real) => real,
+        Err(_) if std::fs::symlink_metadata(&requested).is_ok() => {
+            return Err(format!("{} is a link to a file that doesn't exist", path));
+        }
+        Err(_) => {
+            let (Some(parent), Some(name)) = (requested.parent(), requested.file_name()) else {
+                return Err(format!("Invalid path: {}", path));
+            };
+            let parent = std::fs::canonicalize(parent).map_err(|_| format!("Folder not found: {}", parent.display()))?;
+            parent.join(name)
+        }
+    };
+    if roots.iter().any(|root| real.starts_with(root)) {
+        Ok(real)
+    } else {
+        Err(format!("{} is outside the folders shared with chat models", path))
+    }
+}
+
+// ─── Tool ─────────────────────────────────────────────────────────────────────
+
+/// Tool definitions in OpenAI function calling format
+pub fn definitions() -> Vec<Value> {
+    let path_param = json!({ "type": "string", "description": "Absolute path, or relative to the first shared folder" });
+    vec![
+        json!({
+            "type": "function",
+            "function": {
+                "name": "fs_list_dir",
+                "description": "List the files and folders in a folder the user shared",
+                "parameters": { "type": "object", "properties": { "path": path_param }, "required": ["path"] }
+            }
+        }),
+        json!({
+            "type": "function",
+            "function": {
+                "name": "fs_read_file",
+                "description": "Read a text file in a folder the user shared",
+                "parameters": { "type": "object", "properties": { "path": path_param }, "required": ["path"] }
+            }
+        }),
+        json!({
+            "type": "function",
+            "function": {
+                "name": "fs_write_file",
+                "description": "Create or replace a text file in a folder the user shared. The user is asked to approve every write.",
+                "parameters": {
+                    "type": "object",
+                    "properties": {
+                        "path": path_param,
+                        "content": { "type": "string", "description": "The complete new file content" }
+                    },
+                    "required": ["path", "content"]
+                }
+            }
+        }),
+    ]
+}
+
+/// Run a call to one of the tool's functions for the chat `chat_id`.
+/// Returns `None` if `name` is not one of them.
+pub fn call(chat_id: Option<ChatId>, name: &str, args: &Value) -> Option<Result<String, String>> {
+    let path = args.get("path").and_then(Value::as_str).unwrap_or_default();
+    let roots = ALLOWED_DIRS.read().unwrap().clone();
+    let (op, resolved) = match name {
+        "fs_list_dir" => (FsOp::List, resolve(path, &roots)),
+        "fs_read_file" => (FsOp::Read, resolve(path, &roots)),
+        "fs_write_file" => (FsOp::Write, resolve(path, &roots)),
+        _ => return None,
+    };
+    let result = resolved.and_then(|path| {
+        let output = match op {
+            FsOp::List => list_dir(&path),
+            FsOp::Read => read_file(&path),
+            FsOp::Write => write_file(&path, args.get("content").and_then(Value::as_str).unwrap_or_default()),
+        }?;
+        touch(chat_id, &path, op);
+        Ok(output)
+    });
+    if let Err(e) = &result {
+        log::warn!("{} failed: {}", name, e);
+    }
+    Some(result)
+}
+
+fn list_dir(dir: &Path) -> Result<String, String> {
+    let entries = std::fs::read_dir(dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+    let mut lines: Vec<String> = entries.flatten().map(|entry| {
+        let name = entry.file_name().to_string_lossy().to_string();
+        match entry.metadata() {
+            Ok(meta) if meta.is_dir() => format!("{}/", name),
+            Ok(meta) => format!("{} ({} bytes)", name, meta.len()),
+            Err(_) => name,
+        }
+    }).collect();
+    lines.sort();
+    let total = lines.len();
+    lines.truncate(MAX_LIST_ENTRIES);
+    if total > MAX_LIST_ENTRIES {
+        lines.push(format!("… and {} more", total - MAX_LIST_ENTRIES));
+    }
+    Ok(lines.join("\n"))
+}
+
+fn read_file(path: &Path) -> Result<String, String> {
+    let bytes = std::fs::read(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let mut text = String::from_utf8_lossy(&bytes[..bytes.len().min(MAX_READ_BYTES)]).to_string();
+    if bytes.len() > MAX_READ_BYTES {
+        text.push_str(&format!("\n… truncated, {} of {} bytes shown", MAX_READ_BYTES, bytes.len()));
+    }
+    Ok(text)
+}
+
+fn write_file(path: &Path, content: &str) -> Result<String, String> {
+    let detail = match std::fs::metadata(path) {
+        Ok(meta) => format!("{}\n\nReplaces the existing file ({} bytes) with {} bytes.", path.display(), meta.len(), content.len()),
+        Err(_) => format!("{}\n\nCreates a new file of {} bytes.", path.display(), content.len()),
+    };
+    let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    if !tool_permissions::ask(TOOL_NAME, format!("Allow writing {}?", name), detail) {
+        return Err("The user did not allow this write".to_string());
+    }
+    // A link put in place since the path was resolved is not followed
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.custom_flags(libc::O_NOFOLLOW);
+    }
+    options.open(path)
+        .and_then(|mut file| file.write_all(content.as_bytes()))
+        .map_err(|e| format!("{}: {}", path.display(), e))?;
+    Ok(format!("Wrote {} bytes to {}", content.len(), path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_stays_inside_roots() {
+        let root = std::env::temp_dir().join(format!("moly-fs-tool-{}", std::process::id()));
+        std::fs::create_dir_all(root.join("notes")).unwrap();
+        let roots = vec![root.display().to_string()];
+        let real_root = std::fs::canonicalize(&root).unwrap();
+
+        assert_eq!(resolve("notes", &roots).unwrap(), real_root.join("notes"));
+        assert_eq!(resolve("notes/new.md", &roots).unwrap(), real_root.join("notes").join("new.md"));
+        assert!(resolve("../outside.txt", &roots).is_err());
+        assert!(resolve("notes/../../outside.txt", &roots).is_err());
+        assert!(resolve("missing/new.md", &roots).is_err());
+        assert!(resolve("notes", &[]).is_err());
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_dangling_links_are_refused() {
+        let root = std::env::temp_dir().join(format!("moly-fs-tool-links-{}", std::process::id()));
+        std::fs::create_dir_all(&root).unwrap();
+        let outside = std::env::temp_dir().join(format!("moly-fs-tool-outside-{}.txt", std::process::id()));
+        std::os::unix::fs::symlink(&outside, root.join("escape.txt")).unwrap();
+        let roots = vec![root.display().to_string()];
+
+        assert!(resolve("escape.txt", &roots).unwrap_err().contains("link"));
+        assert!(!outside.exists());
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,135 @@
+//! Chat messages held while the local model loads
+//!
+//! A message sent before the chat's local model finished loading would fail.
+//! The chat stops that request instead and keeps the message behind a
+//! "waiting" bubble as a [`HeldSend`]. Each time [`crate::model_state`]
+//! reports a new load phase, [`HeldSend::outcome`] says whether to keep
+//! waiting, send, or replace the bubble with a note.
+
+use moly_kit::prelude::*;
+
+use crate::chats::ChatId;
+use crate::store::ModelLoadPhase;
+use crate::MolyError;
+
+/// Messages held back until `model_id` finishes loading
+#[derive(Clone, Debug, PartialEq)]
+pub struct HeldSend {
+    pub chat_id: Option<ChatId>,
+    /// Registry id of the loading model
+    pub model_id: String,
+    /// Index of the first held message; the waiting bubble is always last
+    pub first: usize,
+}
+
+/// What to do with held messages in a load phase
+#[derive(Clone, Debug, PartialEq)]
+pub enum HeldOutcome {
+    Wait,
+    Send,
+    /// The load failed or was undone; the note replaces the bubble
+    Fail(String),
+}
+
+impl HeldSend {
+    /// Hold the send `messages` just started, the user's message followed by
+    /// the reply being written, when the model is still loading
+    pub fn hold(
+        chat_id: Option<ChatId>,
+        model_id: &str,
+        messages: &[Message],
+        phase: Option<&ModelLoadPhase>,
+    ) -> Option<Self> {
+        let n = messages.len();
+        if n < 2 || !messages[n - 1].metadata.is_writing || !matches!(messages[n - 2].from, EntityId::User) {
+            return None;
+        }
+        if phase != Some(&ModelLoadPhase::Loading) {
+            return None;
+        }
+        Some(Self { chat_id, model_id: model_id.to_string(), first: n - 2 })
+    }
+
+    pub fn waiting_text(&self) -> String {
+        format!("Waiting for {} to finish loading…", self.model_id)
+    }
+
+    pub fn is_waiting_bubble(&self, message: &Message) -> bool {
+        message.metadata.is_writing && message.content.text == self.waiting_text()
+    }
+
+    /// The chat with the waiting `bubble` last: the messages before the held
+    /// ones, then the user's messages since. Replies the stopped requests
+    /// started writing are dropped.
+    pub fn held(&self, messages: &[Message], bubble: Message) -> Vec<Message> {
+        let first = self.first.min(messages.len());
+        let mut kept = messages[..first].to_vec();
+        kept.extend(messages[first..].iter().filter(|m| matches!(m.from, EntityId::User)).cloned());
+        kept.push(bubble);
+        kept
+    }
+
+    pub fn outcome(&self, phase: Option<&ModelLoadPhase>) -> HeldOutcome {
+        let reason = match phase {
+            Some(ModelLoadPhase::Loading) => return HeldOutcome::Wait,
+            Some(ModelLoadPhase::Loaded) => return HeldOutcome::Send,
+            Some(ModelLoadPhase::Failed(e)) => MolyError::from_message(e).user_message(),
+            Some(ModelLoadPhase::Unloaded) | None => "it was unloaded".to_string(),
+        };
+        HeldOutcome::Fail(format!(
+            "Not sent: {} didn't finish loading ({}). Send it again once the model is loaded.",
+            self.model_id, reason
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(from: EntityId, text: &str, is_writing: bool) -> Message {
+        let mut message = Message {
+            from,
+            content: MessageContent { text: text.to_string(), ..Default::default() },
+            ..Default::default()
+        };
+        message.metadata.is_writing = is_writing;
+        message
+    }
+
+    #[test]
+    fn test_hold_only_new_sends_while_loading() {
+        let bot = EntityId::Bot(BotId::new("qwen3-4b"));
+        let sending = vec![message(EntityId::User, "Hi", false), message(bot.clone(), "", true)];
+        let loading = Some(&ModelLoadPhase::Loading);
+
+        let held = HeldSend::hold(Some(7), "qwen3-4b", &sending, loading).unwrap();
+        assert_eq!(held.first, 0);
+        assert!(HeldSend::hold(Some(7), "qwen3-4b", &sending, Some(&ModelLoadPhase::Loaded)).is_none());
+        let answered = vec![message(EntityId::User, "Hi", false), message(bot, "Hello", false)];
+        assert!(HeldSend::hold(Some(7), "qwen3-4b", &answered, loading).is_none());
+    }
+
+    #[test]
+    fn test_held_keeps_user_messages_and_bubble_last() {
+        let bot = EntityId::Bot(BotId::new("qwen3-4b"));
+        let held = HeldSend { chat_id: None, model_id: "qwen3-4b".into(), first: 1 };
+        let bubble = message(bot.clone(), &held.waiting_text(), true);
+        let messages = vec![
+            message(EntityId::User, "Earlier", false),
+            message(EntityId::User, "Hi", false),
+            bubble.clone(),
+            message(EntityId::User, "Also this", false),
+            message(bot, "", true),
+        ];
+        let kept = held.held(&messages, bubble);
+        let texts: Vec<_> = kept.iter().map(|m| m.content.text.as_str()).collect();
+        assert_eq!(texts, ["Earlier", "Hi", "Also this", "Waiting for qwen3-4b to finish loading…"]);
+        assert!(held.is_waiting_bubble(kept.last().unwrap()));
+
+        assert_eq!(held.outcome(Some(&ModelLoadPhase::Loading)), HeldOutcome::Wait);
+        assert_eq!(held.outcome(Some(&ModelLoadPhase::Loaded)), HeldOutcome::Send);
+        let HeldOutcome::Fail(note) = held.outcome(None) else { panic!("expected a note") };
+        assert!(note.contains("it was unloaded"), "{}", note);
+    }
+}
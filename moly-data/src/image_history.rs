@@ -0,0 +1,165 @@
+//! Images generated in the Model Hub, with the settings that made them
+//!
+//! Each generation keeps its prompt, negative prompt, seed, size and model
+//! next to a copy of the image under the profile's `image_history` folder,
+//! so the Hub's image panel can list earlier results and run their settings
+//! again. Only the newest [`MAX_ENTRIES`] are kept.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use crate::ominix_api_client::ImageGenerationRequest;
+
+const HISTORY_DIR: &str = "image_history";
+const HISTORY_FILENAME: &str = "history.json";
+
+/// Generations kept; older ones are removed with their images
+pub const MAX_ENTRIES: usize = 200;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ImageGeneration {
+    pub id: String,
+    pub created_at: DateTime<Utc>,
+    pub model: String,
+    pub prompt: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub negative_prompt: String,
+    #[serde(default)]
+    pub seed: Option<u64>,
+    pub size: String,
+    /// The image, inside the history folder
+    pub file: PathBuf,
+}
+
+impl ImageGeneration {
+    /// A request with the same settings, for running them again
+    pub fn to_request(&self) -> ImageGenerationRequest {
+        ImageGenerationRequest {
+            negative_prompt: Some(self.negative_prompt.clone()).filter(|n| !n.is_empty()),
+            seed: self.seed,
+            size: self.size.clone(),
+            ..ImageGenerationRequest::new(self.model.clone(), self.prompt.clone())
+        }
+    }
+
+    /// Seed, size and local time, e.g. `seed 42 · 512x512 · Mar 03 14:05`
+    pub fn details(&self) -> String {
+        let when = self.created_at.with_timezone(&chrono::Local).format("%b %d %H:%M");
+        match self.seed {
+            Some(seed) => format!("seed {} · {} · {}", seed, self.size, when),
+            None => format!("{} · {}", self.size, when),
+        }
+    }
+}
+
+/// Newest first
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ImageHistory {
+    pub entries: Vec<ImageGeneration>,
+}
+
+impl ImageHistory {
+    pub fn load() -> Self {
+        std::fs::read_to_string(history_dir().join(HISTORY_FILENAME))
+            .ok()
+            .and_then(|contents| match serde_json::from_str(&contents) {
+                Ok(history) => Some(history),
+                Err(e) => {
+                    log::error!("Failed to parse image history: {:?}", e);
+                    None
+                }
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let dir = history_dir();
+        std::fs::create_dir_all(&dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+        let json = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        std::fs::write(dir.join(HISTORY_FILENAME), json).map_err(|e| e.to_string())
+    }
+
+    /// Store a generated image with the request that made it, and save
+    pub fn add(&mut self, request: &ImageGenerationRequest, image: &[u8]) -> Result<&ImageGeneration, String> {
+        let created_at = Utc::now();
+        let id = format!("img-{}", created_at.timestamp_millis());
+        let dir = history_dir();
+        std::fs::create_dir_all(&dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+        let file = dir.join(format!("{}.png", id));
+        std::fs::write(&file, image).map_err(|e| format!("{}: {}", file.display(), e))?;
+
+        self.entries.insert(0, ImageGeneration {
+            id,
+            created_at,
+            model: request.model.clone(),
+            prompt: request.prompt.clone(),
+            negative_prompt: request.negative_prompt.clone().unwrap_or_default(),
+            seed: request.seed,
+            size: request.size.clone(),
+            file,
+        });
+        for old in self.entries.drain(MAX_ENTRIES.min(self.entries.len())..) {
+            let _ = std::fs::remove_file(&old.file);
+        }
+        self.save()?;
+        Ok(&self.entries[0])
+    }
+
+    /// Remove a generation and its image, and save. Returns whether it existed.
+    pub fn remove(&mut self, id: &str) -> Result<bool, String> {
+        let Some(index) = self.entries.iter().position(|e| e.id == id) else { return Ok(false) };
+        let entry = self.entries.remove(index);
+        if entry.file.exists() {
+            std::fs::remove_file(&entry.file).map_err(|e| format!("{}: {}", entry.file.display(), e))?;
+        }
+        self.save()?;
+        Ok(true)
+    }
+}
+
+/// Where generations of the active profile are kept
+pub fn history_dir() -> PathBuf {
+    crate::profiles::data_dir().join(HISTORY_DIR)
+}
+
+/// A seed for a generation that doesn't ask for one, so it can be repeated
+pub fn random_seed() -> u64 {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos() as u64;
+    // Keep it short enough to read and type back in
+    nanos % 4_294_967_296
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entry_reruns_its_settings() {
+        let entry = ImageGeneration {
+            id: "img-1".into(),
+            created_at: Utc::now(),
+            model: "sdxl-turbo".into(),
+            prompt: "A lighthouse at dusk".into(),
+            negative_prompt: "blurry".into(),
+            seed: Some(42),
+            size: "768x768".into(),
+            file: PathBuf::from("img-1.png"),
+        };
+        let request = entry.to_request();
+        assert_eq!(request.model, "sdxl-turbo");
+        assert_eq!(request.negative_prompt.as_deref(), Some("blurry"));
+        assert_eq!(request.seed, Some(42));
+        assert_eq!(request.size, "768x768");
+        assert!(entry.details().starts_with("seed 42 · 768x768 · "));
+
+        let history: ImageHistory = serde_json::from_str(
+            r#"{"entries":[{"id":"img-1","created_at":"2026-01-01T00:00:00Z","model":"m","prompt":"p","size":"512x512","file":"img-1.png"}]}"#,
+        ).unwrap();
+        assert_eq!(history.entries[0].seed, None);
+        assert!(history.entries[0].negative_prompt.is_empty());
+    }
+}
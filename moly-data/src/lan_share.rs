@@ -0,0 +1,581 @@
+//! Model sharing between OminiX Studio instances on the local network
+//!
+//! With sharing on, [`start_sharing`] serves the registry models this machine
+//! has downloaded over plain HTTP and announces the server over mDNS as
+//! `_ominix-models._tcp`. Another instance finds it after [`start_discovery`]
+//! and copies a model across with [`import_model`] instead of downloading it
+//! again, checking every file against the SHA-256 the peer reports.
+//!
+//! Endpoints:
+//! - `GET /models` — downloaded models, as [`SharedModel`]s
+//! - `GET /models/<id>/manifest` — the model's files, as [`SharedFile`]s
+//! - `GET /models/<id>/files/<path>` — one file's contents
+//!
+//! Only files listed in a downloaded model's manifest are ever served.
+
+use mdns_sd::{ServiceDaemon, ServiceEvent, ServiceInfo};
+use ring::digest;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::net::{IpAddr, SocketAddr, TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
+use std::time::{Duration, SystemTime};
+
+use crate::model_integrity::{self, PART_SUFFIX};
+use crate::model_registry::{ModelRegistry, RegistryFile, RegistryModel};
+use crate::model_state::{self, DownloadStatus};
+
+/// mDNS service type the servers announce
+pub const SERVICE_TYPE: &str = "_ominix-models._tcp.local.";
+
+/// A model a peer can send
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SharedModel {
+    /// Registry id
+    pub id: String,
+    pub name: String,
+    pub size_bytes: u64,
+}
+
+/// One file of a shared model
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SharedFile {
+    /// Relative to the model folder, `/`-separated
+    pub path: String,
+    pub size_bytes: u64,
+    /// Lowercase hex SHA-256
+    pub sha256: String,
+}
+
+/// Another instance found on the network
+#[derive(Clone, Debug)]
+pub struct Peer {
+    /// mDNS instance name, shown to the user
+    pub name: String,
+    pub addr: SocketAddr,
+    pub models: Vec<SharedModel>,
+    fullname: String,
+}
+
+impl Peer {
+    pub fn has_model(&self, model_id: &str) -> bool {
+        self.models.iter().any(|m| m.id == model_id)
+    }
+}
+
+/// Where an import stands, shared with the UI thread
+pub struct TransferProgress<'a> {
+    pub cancel: &'a AtomicBool,
+    pub total_bytes: &'a AtomicU64,
+    pub done_bytes: &'a AtomicU64,
+    pub current_file: &'a Mutex<String>,
+}
+
+// ─── Server ──────────────────────────────────────────────────────────────────
+
+struct ShareServer {
+    port: u16,
+    stop: Arc<AtomicBool>,
+    mdns: Option<ServiceDaemon>,
+    /// Our own mDNS name, so browsing doesn't list this instance
+    fullname: String,
+}
+
+fn server() -> &'static Mutex<Option<ShareServer>> {
+    static SERVER: OnceLock<Mutex<Option<ShareServer>>> = OnceLock::new();
+    SERVER.get_or_init(Default::default)
+}
+
+/// Start serving downloaded models, returning the port. Does nothing if
+/// already sharing.
+pub fn start_sharing() -> Result<u16, String> {
+    let mut slot = server().lock().map_err(|e| e.to_string())?;
+    if let Some(running) = slot.as_ref() {
+        return Ok(running.port);
+    }
+
+    let listener = TcpListener::bind(("0.0.0.0", 0)).map_err(|e| format!("Can't open a port for sharing: {}", e))?;
+    listener.set_nonblocking(true).map_err(|e| e.to_string())?;
+    let port = listener.local_addr().map_err(|e| e.to_string())?.port();
+
+    let stop = Arc::new(AtomicBool::new(false));
+    let stop_flag = stop.clone();
+    std::thread::spawn(move || accept_loop(listener, stop_flag));
+
+    let instance = instance_name();
+    let (mdns, fullname) = match announce(&instance, port) {
+        Ok((daemon, fullname)) => (Some(daemon), fullname),
+        Err(e) => {
+            log::warn!("Sharing models without mDNS discovery: {}", e);
+            (None, String::new())
+        }
+    };
+    log::info!("Sharing models on port {} as {:?}", port, instance);
+    *slot = Some(ShareServer { port, stop, mdns, fullname });
+    Ok(port)
+}
+
+/// Stop serving and withdraw the mDNS announcement
+pub fn stop_sharing() {
+    let Ok(mut slot) = server().lock() else { return };
+    let Some(running) = slot.take() else { return };
+    running.stop.store(true, Ordering::SeqCst);
+    if let Some(daemon) = running.mdns {
+        let _ = daemon.unregister(&running.fullname);
+        let _ = daemon.shutdown();
+    }
+    log::info!("Stopped sharing models");
+}
+
+/// Port being served on, if sharing
+pub fn sharing_port() -> Option<u16> {
+    server().lock().ok().and_then(|s| s.as_ref().map(|s| s.port))
+}
+
+fn own_fullname() -> Option<String> {
+    server().lock().ok().and_then(|s| s.as_ref().map(|s| s.fullname.clone()))
+}
+
+fn instance_name() -> String {
+    let host = std::env::var("HOSTNAME")
+        .or_else(|_| std::env::var("COMPUTERNAME"))
+        .or_else(|_| std::env::var("USER"))
+        .unwrap_or_else(|_| "this computer".to_string());
+    format!("OminiX Studio on {}", host)
+}
+
+fn announce(instance: &str, port: u16) -> Result<(ServiceDaemon, String), String> {
+    let daemon = ServiceDaemon::new().map_err(|e| e.to_string())?;
+    let host = format!("ominix-studio-{}.local.", std::process::id());
+    let info = ServiceInfo::new(SERVICE_TYPE, instance, &host, "", port, None::<HashMap<String, String>>)
+        .map_err(|e| e.to_string())?
+        .enable_addr_auto();
+    let fullname = info.get_fullname().to_string();
+    daemon.register(info).map_err(|e| e.to_string())?;
+    Ok((daemon, fullname))
+}
+
+fn accept_loop(listener: TcpListener, stop: Arc<AtomicBool>) {
+    while !stop.load(Ordering::SeqCst) {
+        match listener.accept() {
+            Ok((stream, peer)) => {
+                log::debug!("LAN share request from {}", peer);
+                std::thread::spawn(move || handle_connection(stream));
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                std::thread::sleep(Duration::from_millis(200));
+            }
+            Err(e) => {
+                log::warn!("LAN share accept failed: {}", e);
+                std::thread::sleep(Duration::from_secs(1));
+            }
+        }
+    }
+}
+
+fn handle_connection(mut stream: TcpStream) {
+    let _ = stream.set_nonblocking(false);
+    let _ = stream.set_read_timeout(Some(Duration::from_secs(10)));
+    let result = match read_request_path(&mut stream) {
+        Some(path) => route(&path, &mut stream),
+        None => Err((400, "Bad request".to_string())),
+    };
+    if let Err((status, message)) = result {
+        let _ = respond(&mut stream, status, "text/plain", message.as_bytes());
+    }
+}
+
+/// Path of a GET request, or `None` for anything else
+fn read_request_path(stream: &mut TcpStream) -> Option<String> {
+    let mut head = Vec::new();
+    let mut buf = [0u8; 1024];
+    while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+        let n = stream.read(&mut buf).ok()?;
+        if n == 0 || head.len() > 8192 { return None; }
+        head.extend_from_slice(&buf[..n]);
+    }
+    let head = String::from_utf8_lossy(&head);
+    let mut parts = head.lines().next()?.split_whitespace();
+    if parts.next()? != "GET" { return None; }
+    Some(parts.next()?.to_string())
+}
+
+fn route(path: &str, stream: &mut TcpStream) -> Result<(), (u16, String)> {
+    let segments: Vec<String> = path.trim_start_matches('/').split('/').map(percent_decode).collect();
+    let segments: Vec<&str> = segments.iter().map(String::as_str).collect();
+    let registry = ModelRegistry::load();
+
+    match segments.as_slice() {
+        ["models"] => {
+            let models: Vec<SharedModel> = shareable(&registry)
+                .map(|m| SharedModel { id: m.id.clone(), name: m.name.clone(), size_bytes: m.storage.size_bytes })
+                .collect();
+            respond_json(stream, &models)
+        }
+        ["models", id, "manifest"] => {
+            let model = find_shareable(&registry, id)?;
+            let mut files = Vec::new();
+            for (rel, abs, size_bytes) in model_files(Path::new(&model.storage.expanded_path())) {
+                let sha256 = file_sha256(&abs).map_err(|e| (500, e))?;
+                files.push(SharedFile { path: rel, size_bytes, sha256 });
+            }
+            respond_json(stream, &files)
+        }
+        ["models", id, "files", rest @ ..] if !rest.is_empty() => {
+            let model = find_shareable(&registry, id)?;
+            let rel = rest.join("/");
+            let (_, abs, size) = model_files(Path::new(&model.storage.expanded_path()))
+                .into_iter()
+                .find(|(path, _, _)| *path == rel)
+                .ok_or_else(|| (404, format!("No file {}", rel)))?;
+            let mut file = std::fs::File::open(&abs).map_err(|e| (500, e.to_string()))?;
+            write_head(stream, 200, "application/octet-stream", size).map_err(|e| (500, e))?;
+            // Too late for an error response once the body has started
+            if let Err(e) = std::io::copy(&mut file, stream) {
+                log::warn!("Sending {} of {} stopped: {}", rel, id, e);
+            }
+            Ok(())
+        }
+        _ => Err((404, "Not found".to_string())),
+    }
+}
+
+fn shareable(registry: &ModelRegistry) -> impl Iterator<Item = &RegistryModel> {
+    registry.models.iter()
+        .filter(|m| model_state::registry_download_status(m) == DownloadStatus::Downloaded)
+}
+
+fn find_shareable<'a>(registry: &'a ModelRegistry, id: &str) -> Result<&'a RegistryModel, (u16, String)> {
+    shareable(registry).find(|m| m.id == id).ok_or_else(|| (404, format!("Model {} is not shared", id)))
+}
+
+/// Files of a model folder as (relative path, absolute path, size). Hidden
+/// files and unfinished downloads are left out.
+fn model_files(dir: &Path) -> Vec<(String, PathBuf, u64)> {
+    fn walk(dir: &Path, prefix: &str, out: &mut Vec<(String, PathBuf, u64)>) {
+        let Ok(entries) = std::fs::read_dir(dir) else { return };
+        let mut entries: Vec<_> = entries.flatten().collect();
+        entries.sort_by_key(|e| e.file_name());
+        for entry in entries {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name.starts_with('.') || name.ends_with(PART_SUFFIX) { continue; }
+            let path = entry.path();
+            let rel = if prefix.is_empty() { name.clone() } else { format!("{}/{}", prefix, name) };
+            // Follows symlinks, which the HF cache uses in snapshots/
+            let Ok(meta) = std::fs::metadata(&path) else { continue };
+            if meta.is_dir() {
+                walk(&path, &rel, out);
+            } else {
+                out.push((rel, path, meta.len()));
+            }
+        }
+    }
+    let mut files = Vec::new();
+    walk(dir, "", &mut files);
+    files
+}
+
+/// SHA-256 of a file, cached until its size or modification time changes
+fn file_sha256(path: &Path) -> Result<String, String> {
+    static CACHE: OnceLock<Mutex<HashMap<PathBuf, (u64, SystemTime, String)>>> = OnceLock::new();
+    let cache = CACHE.get_or_init(Default::default);
+    let meta = std::fs::metadata(path).map_err(|e| e.to_string())?;
+    let modified = meta.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+    if let Some((len, time, hash)) = cache.lock().ok().and_then(|c| c.get(path).cloned()) {
+        if len == meta.len() && time == modified { return Ok(hash); }
+    }
+
+    let mut file = std::fs::File::open(path).map_err(|e| e.to_string())?;
+    let mut context = digest::Context::new(&digest::SHA256);
+    let mut buf = vec![0u8; 1 << 20];
+    loop {
+        let n = file.read(&mut buf).map_err(|e| e.to_string())?;
+        if n == 0 { break; }
+        context.update(&buf[..n]);
+    }
+    let hash = to_hex(context.finish().as_ref());
+    if let Ok(mut c) = cache.lock() {
+        c.insert(path.to_path_buf(), (meta.len(), modified, hash.clone()));
+    }
+    Ok(hash)
+}
+
+fn write_head(stream: &mut TcpStream, status: u16, content_type: &str, len: u64) -> Result<(), String> {
+    let reason = match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        _ => "Internal Server Error",
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        status, reason, content_type, len
+    ).map_err(|e| e.to_string())
+}
+
+fn respond(stream: &mut TcpStream, status: u16, content_type: &str, body: &[u8]) -> Result<(), String> {
+    write_head(stream, status, content_type, body.len() as u64)?;
+    stream.write_all(body).map_err(|e| e.to_string())
+}
+
+fn respond_json<T: Serialize>(stream: &mut TcpStream, value: &T) -> Result<(), (u16, String)> {
+    let body = serde_json::to_vec(value).map_err(|e| (500, e.to_string()))?;
+    respond(stream, 200, "application/json", &body).map_err(|e| (500, e))
+}
+
+// ─── Discovery ───────────────────────────────────────────────────────────────
+
+#[derive(Default)]
+struct Discovery {
+    daemon: Option<ServiceDaemon>,
+    peers: Vec<Peer>,
+}
+
+fn discovery() -> &'static RwLock<Discovery> {
+    static DISCOVERY: OnceLock<RwLock<Discovery>> = OnceLock::new();
+    DISCOVERY.get_or_init(Default::default)
+}
+
+/// Bumped whenever the peer list changes
+static PEERS_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Start looking for other instances on the network. Safe to call from every
+/// screen that wants peers; only the first call starts browsing.
+pub fn start_discovery() {
+    let Ok(mut state) = discovery().write() else { return };
+    if state.daemon.is_some() { return; }
+    let daemon = match ServiceDaemon::new() {
+        Ok(daemon) => daemon,
+        Err(e) => {
+            log::warn!("LAN model discovery unavailable: {}", e);
+            return;
+        }
+    };
+    let events = match daemon.browse(SERVICE_TYPE) {
+        Ok(events) => events,
+        Err(e) => {
+            log::warn!("LAN model discovery unavailable: {}", e);
+            return;
+        }
+    };
+    state.daemon = Some(daemon);
+
+    std::thread::spawn(move || {
+        while let Ok(event) = events.recv() {
+            match event {
+                ServiceEvent::ServiceResolved(info) => {
+                    let fullname = info.get_fullname().to_string();
+                    if own_fullname().as_deref() == Some(fullname.as_str()) { continue; }
+                    let Some(ip) = pick_address(info.get_addresses().iter().copied()) else { continue };
+                    let addr = SocketAddr::new(ip, info.get_port());
+                    let name = fullname.trim_end_matches(SERVICE_TYPE).trim_end_matches('.').to_string();
+                    // Ask for its models off the discovery thread
+                    std::thread::spawn(move || match fetch_models(addr) {
+                        Ok(models) => {
+                            log::info!("Found peer {} at {} sharing {} models", name, addr, models.len());
+                            update_peers(|peers| {
+                                peers.retain(|p| p.fullname != fullname);
+                                peers.push(Peer { name, addr, models, fullname });
+                            });
+                        }
+                        Err(e) => log::warn!("Peer {} at {} didn't list its models: {}", name, addr, e),
+                    });
+                }
+                ServiceEvent::ServiceRemoved(_, fullname) => {
+                    update_peers(|peers| peers.retain(|p| p.fullname != fullname));
+                }
+                _ => {}
+            }
+        }
+    });
+}
+
+fn update_peers(f: impl FnOnce(&mut Vec<Peer>)) {
+    if let Ok(mut state) = discovery().write() {
+        f(&mut state.peers);
+        PEERS_GENERATION.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+/// Changes whenever a peer is found, resolved again or lost; compare with a
+/// value seen earlier to know when to refresh
+pub fn peers_generation() -> u64 {
+    PEERS_GENERATION.load(Ordering::SeqCst)
+}
+
+/// Instances found so far
+pub fn peers() -> Vec<Peer> {
+    discovery().read().map(|s| s.peers.clone()).unwrap_or_default()
+}
+
+/// A peer sharing `model_id`, if any
+pub fn peer_with(model_id: &str) -> Option<Peer> {
+    discovery().read().ok()?.peers.iter().find(|p| p.has_model(model_id)).cloned()
+}
+
+fn pick_address(addrs: impl Iterator<Item = IpAddr>) -> Option<IpAddr> {
+    let addrs: Vec<IpAddr> = addrs.collect();
+    addrs.iter().find(|a| a.is_ipv4()).or_else(|| addrs.first()).copied()
+}
+
+fn client() -> Result<reqwest::blocking::Client, String> {
+    crate::network::blocking_client()
+        .connect_timeout(Duration::from_secs(5))
+        // Large files stream for a long time; only stalls should time out
+        .timeout(None)
+        .build()
+        .map_err(|e| e.to_string())
+}
+
+fn fetch_models(addr: SocketAddr) -> Result<Vec<SharedModel>, String> {
+    let url = format!("http://{}/models", addr);
+    let resp = client()?.get(&url).timeout(Duration::from_secs(10)).send().map_err(|e| e.to_string())?;
+    if !resp.status().is_success() { return Err(format!("HTTP {}", resp.status())); }
+    resp.json().map_err(|e| e.to_string())
+}
+
+// ─── Import ──────────────────────────────────────────────────────────────────
+
+/// Copy a model from a peer into `dest`, verifying every file's checksum.
+/// Files arrive as `.part` and are renamed once verified, and the file list
+/// is recorded first, so an interrupted import shows up as incomplete.
+pub fn import_model(peer: SocketAddr, model_id: &str, dest: &Path, progress: &TransferProgress) -> Result<(), String> {
+    let http = client()?;
+    let manifest_url = format!("http://{}/models/{}/manifest", peer, percent_encode(model_id));
+    let resp = http.get(&manifest_url).send().map_err(|e| e.to_string())?;
+    if !resp.status().is_success() {
+        return Err(format!("Peer refused {}: HTTP {}", model_id, resp.status()));
+    }
+    let files: Vec<SharedFile> = resp.json().map_err(|e| e.to_string())?;
+    if files.is_empty() { return Err("Peer has no files for this model".to_string()); }
+    for file in &files {
+        if !is_safe_relative(&file.path) {
+            return Err(format!("Peer sent an unsafe file path: {}", file.path));
+        }
+    }
+
+    let listed: Vec<RegistryFile> = files.iter()
+        .map(|f| RegistryFile { path: f.path.clone(), size_bytes: f.size_bytes })
+        .collect();
+    model_integrity::write_manifest(dest, &listed)?;
+    progress.total_bytes.store(files.iter().map(|f| f.size_bytes).sum(), Ordering::SeqCst);
+    progress.done_bytes.store(0, Ordering::SeqCst);
+
+    for file in &files {
+        if progress.cancel.load(Ordering::SeqCst) { return Err("Cancelled".to_string()); }
+        *progress.current_file.lock().unwrap() = file.path.clone();
+        let url = format!(
+            "http://{}/models/{}/files/{}",
+            peer,
+            percent_encode(model_id),
+            file.path.split('/').map(percent_encode).collect::<Vec<_>>().join("/"),
+        );
+        receive_file(&http, &url, &dest.join(&file.path), file, progress)?;
+    }
+    log::info!("Imported {} ({} files) from {}", model_id, files.len(), peer);
+    Ok(())
+}
+
+fn receive_file(
+    http: &reqwest::blocking::Client,
+    url: &str,
+    dest: &Path,
+    expected: &SharedFile,
+    progress: &TransferProgress,
+) -> Result<(), String> {
+    if let Some(parent) = dest.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let mut resp = http.get(url).send().map_err(|e| e.to_string())?;
+    if !resp.status().is_success() {
+        return Err(format!("{}: HTTP {}", expected.path, resp.status()));
+    }
+
+    let file_name = dest.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    let part = dest.with_file_name(format!("{}{}", file_name, PART_SUFFIX));
+    let mut out = std::fs::File::create(&part).map_err(|e| e.to_string())?;
+    let mut context = digest::Context::new(&digest::SHA256);
+    let mut buf = vec![0u8; 1 << 16];
+    loop {
+        if progress.cancel.load(Ordering::SeqCst) {
+            drop(out);
+            let _ = std::fs::remove_file(&part);
+            return Err("Cancelled".to_string());
+        }
+        let n = resp.read(&mut buf).map_err(|e| e.to_string())?;
+        if n == 0 { break; }
+        out.write_all(&buf[..n]).map_err(|e| e.to_string())?;
+        context.update(&buf[..n]);
+        progress.done_bytes.fetch_add(n as u64, Ordering::SeqCst);
+    }
+    drop(out);
+
+    let actual = to_hex(context.finish().as_ref());
+    if actual != expected.sha256 {
+        let _ = std::fs::remove_file(&part);
+        return Err(format!("Checksum mismatch for {}", expected.path));
+    }
+    std::fs::rename(&part, dest).map_err(|e| e.to_string())
+}
+
+/// A relative path that stays inside the model folder
+fn is_safe_relative(path: &str) -> bool {
+    !path.is_empty()
+        && !path.starts_with('/')
+        && !path.contains('\\')
+        && path.split('/').all(|seg| !seg.is_empty() && seg != "." && seg != "..")
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn percent_encode(segment: &str) -> String {
+    segment.bytes().map(|b| match b {
+        b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (b as char).to_string(),
+        _ => format!("%{:02X}", b),
+    }).collect()
+}
+
+fn percent_decode(segment: &str) -> String {
+    let bytes = segment.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+            if let Some(b) = hex.and_then(|h| u8::from_str_radix(h, 16).ok()) {
+                out.push(b);
+                i += 3;
+                continue;
+            }
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paths() {
+        let name = "transformer/diffusion pytorch model.safetensors";
+        let encoded: Vec<String> = name.split('/').map(percent_encode).collect();
+        assert_eq!(encoded.join("/"), "transformer/diffusion%20pytorch%20model.safetensors");
+        let decoded: Vec<String> = encoded.iter().map(|s| percent_decode(s)).collect();
+        assert_eq!(decoded.join("/"), name);
+
+        assert!(is_safe_relative("config.json"));
+        assert!(is_safe_relative("snapshots/abc/model.gguf"));
+        assert!(!is_safe_relative("../secrets"));
+        assert!(!is_safe_relative("/etc/passwd"));
+        assert!(!is_safe_relative("a//b"));
+    }
+}
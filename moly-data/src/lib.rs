@@ -1,17 +1,114 @@
 pub mod a2ui_builder;
 pub mod model_runtime_client;
 pub mod a2ui_tools;
+pub mod agent;
+pub mod answer_versions;
+pub mod api_proxy;
+pub mod artifacts;
+pub mod asr_cleanup;
+pub mod audio;
+pub mod audiobook;
+pub mod bookmarks;
+pub mod builtin_tools;
+pub mod chat_backup;
+pub mod chat_defaults;
+pub mod chat_export;
+pub mod chat_navigation;
+pub mod chat_search;
+pub mod chat_share;
+pub mod chat_template;
+pub mod chat_tools;
 pub mod chats;
+pub mod citations;
+pub mod clipboard_watch;
+pub mod code_blocks;
+pub mod code_tool;
+pub mod completions_client;
+pub mod disk_space;
+pub mod download_settings;
+pub mod error;
+pub mod eval_set;
+pub mod fs_tool;
+pub mod held_send;
+pub mod image_history;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod lan_share;
+pub mod load_scheduler;
 pub mod local_models;
+pub mod local_models_migration;
+pub mod mcp_catalog;
+pub mod mcp_client;
+pub mod mcp_servers;
+pub mod meeting_notes;
+pub mod memory_planner;
+pub mod message_stats;
+pub mod model_integrity;
+pub mod model_picker;
 pub mod model_registry;
+pub mod model_search;
+pub mod model_state;
+pub mod model_trash;
+pub mod model_updates;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod model_watcher;
+#[cfg(all(any(test, feature = "mock-runtime"), not(target_arch = "wasm32")))]
+pub mod mock_runtime;
 pub mod moly_client;
+pub mod network;
+pub mod ollama_client;
+pub mod ocr;
+pub mod ominix_api_client;
 pub mod ominix_image_client;
+pub mod platform;
+pub mod power;
 pub mod preferences;
+pub mod profiles;
+pub mod preload;
+pub mod privacy_filter;
+pub mod prompt_enhance;
+pub mod prompt_history;
+pub mod provider_catalog;
 pub mod providers;
 pub mod providers_manager;
+pub mod proxied_client;
+pub mod quick_ask;
+pub mod read_aloud;
+pub mod request_export;
+pub mod request_log;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod runtime_diagnostics;
+pub mod runtime_log;
+pub mod runtime_options;
+pub mod secrets;
+pub mod semantic_search;
+pub mod session_templates;
+pub mod settings_sync;
+pub mod shutdown;
+pub mod smoke_test;
 pub mod store;
+pub mod stream_lines;
+pub mod system_prompts;
+pub mod telemetry;
+pub mod tool_permissions;
+pub mod toolsets;
+pub mod transcribe_watch;
+pub mod transcript;
+pub mod transcript_editor;
+pub mod transfer_rate;
+pub mod translate;
+pub mod tts_longform;
+pub mod tts_output;
+pub mod tts_voices;
+pub mod voice_client;
+pub mod voice_dataset;
+pub mod voice_presets;
+pub mod voice_training;
+pub mod workspace_archive;
 
+pub use api_proxy::ApiProxySettings;
+pub use chat_backup::ChatBackupSettings;
 pub use chats::{ChatData, ChatId, Chats};
+pub use error::MolyError;
 pub use local_models::{
     // V1 (legacy)
     LocalModel, LocalModelsConfig, ModelCategory, ModelStatus,
@@ -19,17 +116,28 @@ pub use local_models::{
     LocalModelV2, LocalModelsConfigV2, ModelState, ModelSource, ModelStorage,
     ModelFileInfo, ModelRuntime, ModelStatusInfo, DownloadProgress, SourceType,
 };
+pub use local_models_migration::MigrationReport;
+pub use mcp_servers::McpServerConfig;
 pub use moly_client::{MolyClient, ServerConnectionStatus};
+pub use ollama_client::{OllamaClient, OllamaPullProgress, list_ollama_models, pull_ollama_model, OLLAMA_DEFAULT_URL};
+pub use ominix_api_client::{OminiXApiClient, OminiXApiError};
 pub use ominix_image_client::{OminiXImageClient, ImageGenerationConfig};
-pub use preferences::Preferences;
+pub use preferences::{Preferences, WindowState};
+pub use profiles::{Profile, Profiles};
+pub use provider_catalog::{CatalogModel, fetch_provider_catalog};
 pub use providers::{ProviderPreferences, ProviderId, ProviderType, ProviderConnectionStatus, get_supported_providers};
 pub use providers_manager::ProvidersManager;
 pub use model_registry::{
     ModelRegistry, RegistryModel, RegistryCategory, RegistrySource, RegistryStorage,
-    RegistryRuntime, RegistryUiHints, ApiType, PanelType, SourceKind, ExtraModelSource,
+    RegistryRuntime, RegistryUiHints, ApiType, PanelType, SourceKind, ExtraModelSource, RegistryFile, RuntimeVersion,
 };
-pub use model_runtime_client::{ModelRuntimeClient, ServerModelStatus, ServerModelInfo, ensure_server_running, kill_server_process};
-pub use store::{Store, StoreAction};
+pub use model_state::DownloadStatus;
+#[cfg(not(target_arch = "wasm32"))]
+pub use model_watcher::ModelWatcher;
+pub use model_runtime_client::{ModelRuntimeClient, ServerModelStatus, ServerModelInfo, ensure_server_running, installed_runtime_version, kill_server_process};
+pub use store::{ModelLoadPhase, Store, StoreAction, StoreEvent};
+pub use transcript::{Transcript, TranscriptSegment};
+pub use transfer_rate::TransferRate;
 
 // A2UI (AI-to-UI) exports
 pub use a2ui_builder::A2uiBuilder;
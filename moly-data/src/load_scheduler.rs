@@ -0,0 +1,318 @@
+//! Loading several models as a batch
+//!
+//! The startup preload hands its [`crate::preload::plan`] to [`run`], which
+//! starts loads side by side where that is safe and one after another where
+//! it isn't:
+//!
+//! - Chat models (LLM and VLM) load one at a time in plan order, so the chat
+//!   model chats switch to is the last one planned.
+//! - A load joins those running only while everything loaded and loading,
+//!   including the models the runtime had loaded before the batch
+//!   ([`runtime_loaded_gb`]), fits in the usable memory
+//!   ([`crate::memory_planner::USABLE_FRACTION`] of the RAM); past that, or
+//!   with the RAM unknown, it waits until nothing else is loading so the
+//!   loads' peaks don't add up.
+//! - At most [`MAX_PARALLEL`] loads run at once.
+//!
+//! [`LoadProgress`] sums up the whole batch for the shell's header.
+
+use crate::memory_planner;
+use crate::model_registry::{ModelRegistry, RegistryCategory, RegistryModel};
+use crate::model_runtime_client::{ServerModelInfo, ServerModelStatus};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+
+/// Loads running at once, whatever the memory
+pub const MAX_PARALLEL: usize = 3;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum JobState {
+    Waiting,
+    Loading,
+    Loaded,
+    Failed(String),
+    /// Not started before the batch was cancelled
+    Cancelled,
+}
+
+#[derive(Clone, Debug)]
+struct Job {
+    model: RegistryModel,
+    state: JobState,
+    /// Jobs that have to finish first
+    after: Vec<usize>,
+}
+
+/// Which models of a batch can start loading, see the module docs
+#[derive(Clone, Debug)]
+pub struct LoadScheduler {
+    jobs: Vec<Job>,
+    usable_gb: Option<f64>,
+    /// Taken by models loaded before the batch
+    loaded_gb: f64,
+}
+
+fn is_chat(category: RegistryCategory) -> bool {
+    matches!(category, RegistryCategory::Llm | RegistryCategory::Vlm)
+}
+
+impl LoadScheduler {
+    /// A batch of `models` in plan order, on a machine with `system_bytes`
+    /// of RAM when known, where models already loaded take `loaded_gb`
+    pub fn new(models: Vec<RegistryModel>, system_bytes: Option<u64>, loaded_gb: f64) -> Self {
+        let usable_gb = memory_planner::plan(std::iter::empty::<&RegistryModel>(), system_bytes).usable_gb();
+        let jobs = models
+            .iter()
+            .enumerate()
+            .map(|(index, model)| Job {
+                model: model.clone(),
+                state: JobState::Waiting,
+                after: (0..index).filter(|&earlier| is_chat(models[earlier].category) && is_chat(model.category)).collect(),
+            })
+            .collect();
+        Self { jobs, usable_gb, loaded_gb }
+    }
+
+    fn count(&self, state: &JobState) -> usize {
+        self.jobs.iter().filter(|j| j.state == *state).count()
+    }
+
+    fn finished(&self, index: usize) -> bool {
+        !matches!(self.jobs[index].state, JobState::Waiting | JobState::Loading)
+    }
+
+    /// Whether job `index` fits in memory beside the models loaded and
+    /// loading
+    fn fits(&self, index: usize) -> bool {
+        let Some(usable) = self.usable_gb else { return false };
+        let committed: f64 = self.loaded_gb + self
+            .jobs
+            .iter()
+            .filter(|j| matches!(j.state, JobState::Loaded | JobState::Loading))
+            .map(|j| j.model.runtime.memory_gb as f64)
+            .sum();
+        committed + self.jobs[index].model.runtime.memory_gb as f64 <= usable
+    }
+
+    /// The models that can start loading now, marked as loading
+    pub fn start_ready(&mut self) -> Vec<RegistryModel> {
+        let mut started = Vec::new();
+        for index in 0..self.jobs.len() {
+            let loading = self.count(&JobState::Loading);
+            if loading >= MAX_PARALLEL {
+                break;
+            }
+            if self.jobs[index].state != JobState::Waiting || !self.jobs[index].after.iter().all(|&d| self.finished(d)) {
+                continue;
+            }
+            if loading > 0 && !self.fits(index) {
+                continue;
+            }
+            self.jobs[index].state = JobState::Loading;
+            started.push(self.jobs[index].model.clone());
+        }
+        started
+    }
+
+    /// Record how loading a model ended
+    pub fn finish(&mut self, model_id: &str, result: Result<(), String>) {
+        if let Some(job) = self.jobs.iter_mut().find(|j| j.model.id == model_id && j.state == JobState::Loading) {
+            job.state = match result {
+                Ok(()) => JobState::Loaded,
+                Err(e) => JobState::Failed(e),
+            };
+        }
+    }
+
+    /// Start no more loads; those running finish
+    pub fn cancel(&mut self) {
+        for job in self.jobs.iter_mut().filter(|j| j.state == JobState::Waiting) {
+            job.state = JobState::Cancelled;
+        }
+    }
+
+    pub fn is_done(&self) -> bool {
+        (0..self.jobs.len()).all(|index| self.finished(index))
+    }
+
+    pub fn state(&self, model_id: &str) -> Option<&JobState> {
+        self.jobs.iter().find(|j| j.model.id == model_id).map(|j| &j.state)
+    }
+
+    pub fn progress(&self) -> LoadProgress {
+        LoadProgress {
+            total: self.jobs.len(),
+            loaded: self.count(&JobState::Loaded),
+            failed: self.jobs.iter().filter(|j| matches!(j.state, JobState::Failed(_))).count(),
+            loading: self.jobs.iter().filter(|j| j.state == JobState::Loading).map(|j| j.model.name.clone()).collect(),
+        }
+    }
+}
+
+/// Memory the models the runtime reports loaded or loading take: what it
+/// reports for each, else the registry's estimate. Models of `batch` are
+/// left out; their jobs count them.
+pub fn runtime_loaded_gb(server: &[ServerModelInfo], registry: &ModelRegistry, batch: &[RegistryModel]) -> f64 {
+    server
+        .iter()
+        .filter(|info| matches!(info.status, ServerModelStatus::Loaded | ServerModelStatus::Loading))
+        .filter(|info| !batch.iter().any(|m| m.runtime.api_model_id == info.api_id))
+        .map(|info| {
+            let estimate = || registry.models.iter().find(|m| m.runtime.api_model_id == info.api_id).map(|m| m.runtime.memory_gb);
+            info.memory_gb.or_else(estimate).unwrap_or(0.0) as f64
+        })
+        .sum()
+}
+
+/// Where a batch of loads is
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LoadProgress {
+    pub total: usize,
+    pub loaded: usize,
+    pub failed: usize,
+    /// Names of the models loading now
+    pub loading: Vec<String>,
+}
+
+impl LoadProgress {
+    /// "Loading Qwen3 8B, Whisper Large (1 of 3 ready)"
+    pub fn summary(&self) -> String {
+        let mut text = match self.loading.as_slice() {
+            [] => "Waiting to load".to_string(),
+            names => format!("Loading {}", names.join(", ")),
+        };
+        text.push_str(&format!(" ({} of {} ready", self.loaded, self.total));
+        if self.failed > 0 {
+            text.push_str(&format!(", {} failed", self.failed));
+        }
+        text.push(')');
+        text
+    }
+}
+
+/// What [`run`] reports as the batch goes
+#[derive(Clone, Debug)]
+pub enum LoadEvent {
+    Started(RegistryModel),
+    Finished(RegistryModel, Result<(), String>),
+    Progress(LoadProgress),
+}
+
+/// Load the scheduler's models with `load`, each on its own thread as the
+/// scheduler allows, until all are done. Once `cancel` is set no more loads
+/// start. Blocks; call it off the UI thread.
+pub fn run<L, E>(mut scheduler: LoadScheduler, load: L, cancel: &AtomicBool, mut on_event: E)
+where
+    L: Fn(&RegistryModel) -> Result<(), String> + Send + Sync + 'static,
+    E: FnMut(LoadEvent),
+{
+    let load = Arc::new(load);
+    let (tx, rx) = mpsc::channel::<(RegistryModel, Result<(), String>)>();
+    loop {
+        if cancel.load(Ordering::Relaxed) {
+            scheduler.cancel();
+        }
+        for model in scheduler.start_ready() {
+            log::info!("Loading {} in a batch", model.id);
+            on_event(LoadEvent::Started(model.clone()));
+            let (tx, load) = (tx.clone(), load.clone());
+            std::thread::spawn(move || {
+                let result = load(&model);
+                let _ = tx.send((model, result));
+            });
+        }
+        on_event(LoadEvent::Progress(scheduler.progress()));
+        if scheduler.is_done() {
+            break;
+        }
+        let Ok((model, result)) = rx.recv() else { break };
+        scheduler.finish(&model.id, result.clone());
+        on_event(LoadEvent::Finished(model, result));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model_registry::ModelRegistry;
+
+    const GIB: u64 = 1024 * 1024 * 1024;
+
+    fn model(category: RegistryCategory, memory_gb: f32) -> RegistryModel {
+        let registry = ModelRegistry::bundled();
+        let mut model = registry.models.iter().find(|m| m.category == category).unwrap().clone();
+        model.id = format!("{}-{}", model.id, memory_gb);
+        model.runtime.memory_gb = memory_gb;
+        model
+    }
+
+    #[test]
+    fn test_schedule() {
+        let (asr, tts) = (model(RegistryCategory::Asr, 2.0), model(RegistryCategory::Tts, 3.0));
+        let (vlm, llm) = (model(RegistryCategory::Vlm, 6.0), model(RegistryCategory::Llm, 5.0));
+        let models = vec![asr.clone(), tts.clone(), vlm.clone(), llm.clone()];
+
+        // 32 GB installed, 24 usable: everything but the second chat model
+        // starts together
+        let mut scheduler = LoadScheduler::new(models.clone(), Some(32 * GIB), 0.0);
+        let ids = |models: Vec<RegistryModel>| models.into_iter().map(|m| m.id).collect::<Vec<_>>();
+        assert_eq!(ids(scheduler.start_ready()), [asr.id.as_str(), tts.id.as_str(), vlm.id.as_str()]);
+        scheduler.finish(&vlm.id, Err("out of memory".to_string()));
+        assert_eq!(ids(scheduler.start_ready()), [llm.id.as_str()]);
+        assert_eq!(scheduler.progress().summary(), format!("Loading {}, {}, {} (0 of 4 ready, 1 failed)", asr.name, tts.name, llm.name));
+
+        // 8 GB installed, 6 usable: one at a time past the budget
+        let mut scheduler = LoadScheduler::new(models.clone(), Some(8 * GIB), 0.0);
+        assert_eq!(ids(scheduler.start_ready()), [asr.id.as_str(), tts.id.as_str()]);
+        scheduler.finish(&asr.id, Ok(()));
+        scheduler.finish(&tts.id, Ok(()));
+        assert_eq!(ids(scheduler.start_ready()), [vlm.id.as_str()]);
+        scheduler.cancel();
+        assert!(!scheduler.is_done());
+        scheduler.finish(&vlm.id, Ok(()));
+        assert!(scheduler.is_done());
+        assert_eq!(scheduler.state(&llm.id), Some(&JobState::Cancelled));
+
+        // Unknown memory: one at a time
+        let mut scheduler = LoadScheduler::new(models, None, 0.0);
+        assert_eq!(scheduler.start_ready().len(), 1);
+    }
+
+    #[test]
+    fn test_runtime_models_take_memory() {
+        let (asr, tts) = (model(RegistryCategory::Asr, 2.0), model(RegistryCategory::Tts, 3.0));
+        let registry = ModelRegistry::bundled();
+        let chat = registry.by_category(RegistryCategory::Llm).next().unwrap().clone();
+        let estimated = chat.runtime.memory_gb as f64;
+        let info = |api_id: &str, status, memory_gb| ServerModelInfo { api_id: api_id.to_string(), status, memory_gb };
+        let server = vec![
+            info(&chat.runtime.api_model_id, ServerModelStatus::Loaded, None),
+            info("other-model", ServerModelStatus::Loaded, Some(17.0)),
+            info("unloaded-model", ServerModelStatus::Unloaded, Some(40.0)),
+            info(&asr.runtime.api_model_id, ServerModelStatus::Loading, Some(2.0)),
+        ];
+        let loaded = runtime_loaded_gb(&server, &registry, &[asr.clone(), tts.clone()]);
+        assert_eq!(loaded, 17.0 + estimated);
+
+        // 24 GB usable, 20 of it already taken: the second load waits
+        let mut scheduler = LoadScheduler::new(vec![asr.clone(), tts.clone()], Some(32 * GIB), 20.0);
+        assert_eq!(scheduler.start_ready().len(), 1);
+        scheduler.finish(&asr.id, Ok(()));
+        assert_eq!(scheduler.start_ready().into_iter().map(|m| m.id).collect::<Vec<_>>(), [tts.id]);
+    }
+
+    #[test]
+    fn test_run() {
+        let models = vec![model(RegistryCategory::Asr, 1.0), model(RegistryCategory::Tts, 1.0), model(RegistryCategory::Llm, 4.0)];
+        let scheduler = LoadScheduler::new(models, Some(16 * GIB), 0.0);
+        let tts_id = model(RegistryCategory::Tts, 1.0).id;
+        let load = move |m: &RegistryModel| if m.id == tts_id { Err("missing weights".to_string()) } else { Ok(()) };
+        let mut events = Vec::new();
+        run(scheduler, load, &AtomicBool::new(false), |event| events.push(event));
+
+        let started = events.iter().filter(|e| matches!(e, LoadEvent::Started(_))).count();
+        assert_eq!(started, 3);
+        let Some(LoadEvent::Progress(last)) = events.last() else { panic!("no final progress") };
+        assert_eq!((last.total, last.loaded, last.failed, last.loading.len()), (3, 2, 1, 0));
+    }
+}
@@ -3,7 +3,9 @@
 //! Manages local AI model information and checks their availability on disk.
 //!
 //! This module provides two versions of the local models configuration:
-//! - V1 (legacy): Simple model definitions with basic status tracking
+//! - V1 (legacy): Simple model definitions with basic status tracking. Only
+//!   the data types are always built, so an old `local_models.json` can be
+//!   migrated; loading and saving it needs the `legacy-v1` feature.
 //! - V2 (current): Comprehensive JSON-based system with per-file tracking,
 //!   backup URLs, runtime memory info, and per-model download progress
 
@@ -11,6 +13,11 @@ use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 
+use crate::local_models_migration;
+use crate::model_state;
+use crate::profiles;
+use crate::transfer_rate;
+
 const LOCAL_MODELS_FILENAME: &str = "local_models.json";
 const LOCAL_MODELS_CONFIG_FILENAME: &str = "local_models_config.json";
 const CONFIG_VERSION: &str = "1.0.0";
@@ -85,6 +92,7 @@ pub struct LocalModel {
     pub status: ModelStatus,
 }
 
+#[cfg(feature = "legacy-v1")]
 impl LocalModel {
     /// Check if the model exists on disk and update status
     pub fn check_availability(&mut self) {
@@ -123,16 +131,12 @@ impl LocalModel {
 }
 
 /// Expand ~ to home directory
-fn expand_tilde(path: &str) -> String {
-    if path.starts_with("~/") {
-        if let Some(home) = dirs::home_dir() {
-            return home.join(&path[2..]).to_string_lossy().to_string();
-        }
-    }
-    path.to_string()
+pub(crate) fn expand_tilde(path: &str) -> String {
+    crate::download_settings::models_path(path)
 }
 
 /// Get the default list of supported local models
+#[cfg(feature = "legacy-v1")]
 pub fn get_default_local_models() -> Vec<LocalModel> {
     vec![
         LocalModel {
@@ -194,6 +198,7 @@ pub struct LocalModelsConfig {
     pub models: Vec<LocalModel>,
 }
 
+#[cfg(feature = "legacy-v1")]
 impl Default for LocalModelsConfig {
     fn default() -> Self {
         Self {
@@ -202,6 +207,7 @@ impl Default for LocalModelsConfig {
     }
 }
 
+#[cfg(feature = "legacy-v1")]
 impl LocalModelsConfig {
     /// Load config from disk, or return defaults if not found
     pub fn load() -> Self {
@@ -259,13 +265,9 @@ impl LocalModelsConfig {
         }
     }
 
-    /// Get the path to the config file
+    /// Get the path to the config file (in the active profile's data directory)
     fn config_path() -> PathBuf {
-        if let Some(home) = dirs::home_dir() {
-            home.join(".moly").join(LOCAL_MODELS_FILENAME)
-        } else {
-            PathBuf::from(".moly").join(LOCAL_MODELS_FILENAME)
-        }
+        profiles::data_dir().join(LOCAL_MODELS_FILENAME)
     }
 
     /// Check availability for all models
@@ -494,6 +496,8 @@ pub struct DownloadProgress {
     #[serde(default)]
     pub current_file_index: usize,
     #[serde(default)]
+    pub total_files: usize,
+    #[serde(default)]
     pub current_file_bytes: u64,
     #[serde(default)]
     pub current_file_total: u64,
@@ -529,6 +533,7 @@ impl DownloadProgress {
         self.overall_total = total_bytes;
         self.overall_bytes = 0;
         self.current_file_index = 0;
+        self.total_files = total_files;
         self.started_at = Some(Utc::now().to_rfc3339());
     }
 
@@ -540,6 +545,30 @@ impl DownloadProgress {
         self.overall_bytes = overall_bytes;
     }
 
+    /// Progress of the current file (0.0 to 1.0)
+    pub fn file_progress_percent(&self) -> f64 {
+        if self.current_file_total == 0 {
+            return 0.0;
+        }
+        (self.current_file_bytes as f64 / self.current_file_total as f64).min(1.0)
+    }
+
+    /// Speed, time remaining and file position, e.g.
+    /// "45.2 MB/s, 3m 12s remaining, file 7/23". Parts not known yet are left out.
+    pub fn rate_text(&self) -> String {
+        let mut parts = Vec::new();
+        if self.speed_bytes_per_sec > 0 {
+            parts.push(transfer_rate::format_speed(self.speed_bytes_per_sec));
+        }
+        if let Some(eta) = self.eta_seconds {
+            parts.push(format!("{} remaining", transfer_rate::format_eta(eta)));
+        }
+        if self.total_files > 1 {
+            parts.push(format!("file {}/{}", self.current_file_index + 1, self.total_files));
+        }
+        parts.join(", ")
+    }
+
     /// Mark download as complete
     pub fn complete(&mut self) {
         self.is_active = false;
@@ -736,44 +765,40 @@ impl Default for LocalModelsConfigV2 {
 }
 
 impl LocalModelsConfigV2 {
-    /// Load config from disk, migrate from V1 if needed, or return defaults
+    /// Load config from disk, migrate a leftover V1 config into it, or
+    /// return defaults
     pub fn load() -> Self {
         let v2_path = Self::config_path();
         log::debug!("Loading local models V2 config from {:?}", v2_path);
 
         // Try to load V2 config first
-        if let Ok(contents) = std::fs::read_to_string(&v2_path) {
-            match serde_json::from_str::<LocalModelsConfigV2>(&contents) {
-                Ok(mut config) => {
+        let loaded = std::fs::read_to_string(&v2_path).ok()
+            .and_then(|contents| match serde_json::from_str::<LocalModelsConfigV2>(&contents) {
+                Ok(config) => {
                     log::info!("Loaded V2 config with {} models", config.models.len());
-                    config.merge_with_defaults();
-                    config.startup_scan();
-                    return config;
+                    Some(config)
                 }
                 Err(e) => {
                     log::error!("Failed to parse V2 config: {:?}", e);
+                    None
                 }
-            }
-        }
-
-        // Try to migrate from V1
-        let v1_path = Self::legacy_config_path();
-        if let Ok(contents) = std::fs::read_to_string(&v1_path) {
-            if let Ok(v1_config) = serde_json::from_str::<LocalModelsConfig>(&contents) {
-                log::info!("Migrating from V1 config with {} models", v1_config.models.len());
-                let mut config = Self::migrate_from_v1(&v1_config);
-                config.merge_with_defaults();
-                config.startup_scan();
-                config.save();
-                return config;
-            }
+            });
+        let is_new = loaded.is_none();
+        let mut config = loaded.unwrap_or_else(|| {
+            log::info!("Creating default V2 config");
+            Self::default()
+        });
+        config.merge_with_defaults();
+
+        // Fold in V1 models (scans and saves on its own)
+        if local_models_migration::migrate_v1(&mut config).is_some() {
+            return config;
         }
 
-        // Return defaults
-        log::info!("Creating default V2 config");
-        let mut config = Self::default();
         config.startup_scan();
-        config.save();
+        if is_new {
+            config.save();
+        }
         config
     }
 
@@ -802,32 +827,14 @@ impl LocalModelsConfigV2 {
         }
     }
 
-    /// Get V2 config file path
+    /// Get V2 config file path (in the active profile's data directory)
     fn config_path() -> PathBuf {
-        if let Some(home) = dirs::home_dir() {
-            home.join(".moly").join(LOCAL_MODELS_CONFIG_FILENAME)
-        } else {
-            PathBuf::from(".moly").join(LOCAL_MODELS_CONFIG_FILENAME)
-        }
+        profiles::data_dir().join(LOCAL_MODELS_CONFIG_FILENAME)
     }
 
     /// Get legacy V1 config file path
-    fn legacy_config_path() -> PathBuf {
-        if let Some(home) = dirs::home_dir() {
-            home.join(".moly").join(LOCAL_MODELS_FILENAME)
-        } else {
-            PathBuf::from(".moly").join(LOCAL_MODELS_FILENAME)
-        }
-    }
-
-    /// Migrate from V1 configuration
-    fn migrate_from_v1(v1: &LocalModelsConfig) -> Self {
-        let models: Vec<LocalModelV2> = v1.models.iter().map(LocalModelV2::from_legacy).collect();
-        Self {
-            version: CONFIG_VERSION.to_string(),
-            last_updated: Some(Utc::now().to_rfc3339()),
-            models,
-        }
+    pub(crate) fn legacy_config_path() -> PathBuf {
+        profiles::data_dir().join(LOCAL_MODELS_FILENAME)
     }
 
     /// Scan filesystem for all models on startup
@@ -837,6 +844,7 @@ impl LocalModelsConfigV2 {
             // Don't scan if actively downloading
             if !model.download_progress.is_active {
                 model.scan_filesystem();
+                model_state::record_local_model(model);
             }
         }
         self.last_updated = Some(Utc::now().to_rfc3339());
@@ -890,6 +898,7 @@ impl LocalModelsConfigV2 {
     pub fn refresh_model(&mut self, id: &str) {
         if let Some(model) = self.get_model_mut(id) {
             model.scan_filesystem();
+            model_state::record_local_model(model);
             self.last_updated = Some(Utc::now().to_rfc3339());
             self.save();
         }
@@ -0,0 +1,189 @@
+//! One-time migration of the V1 local models config into V2
+//!
+//! V1 kept its models in `local_models.json` with its own ids ("flux",
+//! "zimage", ...). When that file is still around, its models are folded into
+//! the V2 config: entries that point at the same directory as a V2 model (or
+//! share its id) are merged into it, anything else is carried over with
+//! [`LocalModelV2::from_legacy`]. Model files are never moved or deleted, so
+//! downloads made with V1 show up as ready after the scan. The V1 file is
+//! then renamed so the migration doesn't run again, and a report is written
+//! for the Local Models screen to show.
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use crate::local_models::{expand_tilde, LocalModelV2, LocalModelsConfig, LocalModelsConfigV2, ModelState};
+use crate::profiles;
+
+const REPORT_FILENAME: &str = "local_models_migration.json";
+const RETIRED_V1_FILENAME: &str = "local_models.v1.json";
+
+/// What happened to one V1 model
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MigrationOutcome {
+    /// Same model as an existing V2 entry; the V2 entry is kept
+    Merged,
+    /// Not known to V2; added as a new entry
+    Added,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MigrationEntry {
+    pub v1_id: String,
+    pub v2_id: String,
+    pub name: String,
+    pub outcome: MigrationOutcome,
+    /// State of the V2 entry after scanning its files
+    pub state: ModelState,
+}
+
+/// Result of a V1 → V2 migration, kept on disk until dismissed
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MigrationReport {
+    pub migrated_at: String,
+    /// Where the V1 config was moved to
+    pub v1_backup: PathBuf,
+    pub entries: Vec<MigrationEntry>,
+    /// The user has seen and dismissed the report
+    #[serde(default)]
+    pub acknowledged: bool,
+}
+
+impl MigrationReport {
+    /// The last migration report, if any
+    pub fn load() -> Option<Self> {
+        let contents = std::fs::read_to_string(report_path()).ok()?;
+        match serde_json::from_str(&contents) {
+            Ok(report) => Some(report),
+            Err(e) => {
+                log::error!("Failed to parse migration report: {:?}", e);
+                None
+            }
+        }
+    }
+
+    pub fn save(&self) {
+        match serde_json::to_string_pretty(self) {
+            Ok(json) => {
+                if let Err(e) = std::fs::write(report_path(), json) {
+                    log::error!("Failed to write migration report: {:?}", e);
+                }
+            }
+            Err(e) => log::error!("Failed to serialize migration report: {:?}", e),
+        }
+    }
+
+    /// Hide the report from now on
+    pub fn acknowledge(&mut self) {
+        self.acknowledged = true;
+        self.save();
+    }
+
+    /// One line per model, for display
+    pub fn summary_lines(&self) -> Vec<String> {
+        self.entries.iter().map(|e| {
+            let outcome = match e.outcome {
+                MigrationOutcome::Merged if e.v1_id == e.v2_id => "kept".to_string(),
+                MigrationOutcome::Merged => format!("merged into {}", e.v2_id),
+                MigrationOutcome::Added => "added".to_string(),
+            };
+            format!("{} — {} ({})", e.name, outcome, e.state.label())
+        }).collect()
+    }
+}
+
+/// Migrate a leftover V1 config into `config`. Returns `None` when there is
+/// nothing to migrate; otherwise `config` has been scanned and saved.
+pub fn migrate_v1(config: &mut LocalModelsConfigV2) -> Option<MigrationReport> {
+    let v1_path = LocalModelsConfigV2::legacy_config_path();
+    let contents = std::fs::read_to_string(&v1_path).ok()?;
+    let v1: LocalModelsConfig = match serde_json::from_str(&contents) {
+        Ok(v1) => v1,
+        Err(e) => {
+            log::error!("Failed to parse V1 config {:?}, leaving it in place: {:?}", v1_path, e);
+            return None;
+        }
+    };
+    log::info!("Migrating {} models from V1 config", v1.models.len());
+
+    let mut entries = map_v1_models(&v1, config);
+    config.startup_scan();
+    config.save();
+    for entry in &mut entries {
+        entry.state = config.get_model(&entry.v2_id).map(|m| m.status.state).unwrap_or_default();
+    }
+
+    // Keep the V1 file as a backup, out of the way of the next load
+    let v1_backup = v1_path.with_file_name(RETIRED_V1_FILENAME);
+    if let Err(e) = std::fs::rename(&v1_path, &v1_backup) {
+        log::error!("Failed to retire V1 config {:?}: {:?}", v1_path, e);
+    }
+
+    let report = MigrationReport {
+        migrated_at: Utc::now().to_rfc3339(),
+        v1_backup,
+        entries,
+        acknowledged: false,
+    };
+    report.save();
+    Some(report)
+}
+
+/// Merge V1 models into the V2 list, matching on id or on the directory the
+/// files live in
+fn map_v1_models(v1: &LocalModelsConfig, config: &mut LocalModelsConfigV2) -> Vec<MigrationEntry> {
+    v1.models.iter().map(|model| {
+        let path = expand_tilde(&model.model_path);
+        let existing = config.models.iter()
+            .find(|m| m.id == model.id || m.expanded_path() == path)
+            .map(|m| m.id.clone());
+        let (v2_id, outcome) = match existing {
+            Some(id) => (id, MigrationOutcome::Merged),
+            None => {
+                config.models.push(LocalModelV2::from_legacy(model));
+                (model.id.clone(), MigrationOutcome::Added)
+            }
+        };
+        MigrationEntry {
+            v1_id: model.id.clone(),
+            v2_id,
+            name: model.name.clone(),
+            outcome,
+            state: ModelState::from_legacy(model.status),
+        }
+    }).collect()
+}
+
+fn report_path() -> PathBuf {
+    profiles::data_dir().join(REPORT_FILENAME)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_v1_models() {
+        let v1: LocalModelsConfig = serde_json::from_str(r#"{"models": [
+            {"id": "flux", "name": "FLUX", "description": "", "category": "Image", "size": "~13 GB",
+             "download_url": "https://huggingface.co/black-forest-labs/FLUX.2-klein-4B",
+             "model_path": "~/.cache/huggingface/hub/models--black-forest-labs--FLUX.2-klein-4B"},
+            {"id": "qwen3-8b", "name": "Qwen3 8B", "description": "", "category": "Llm", "size": "~8 GB",
+             "download_url": "https://huggingface.co/x/y", "model_path": "/elsewhere/qwen"},
+            {"id": "custom", "name": "Custom", "description": "", "category": "Llm", "size": "1 GB",
+             "download_url": "https://modelscope.cn/models/a/b", "model_path": "/models/custom"}
+        ]}"#).unwrap();
+        let mut config = LocalModelsConfigV2::default();
+        let before = config.models.len();
+
+        let entries = map_v1_models(&v1, &mut config);
+        let outcome = |id: &str| entries.iter().find(|e| e.v1_id == id).map(|e| (e.v2_id.as_str(), e.outcome));
+        assert_eq!(outcome("flux"), Some(("flux-klein-4b", MigrationOutcome::Merged)));
+        assert_eq!(outcome("qwen3-8b"), Some(("qwen3-8b", MigrationOutcome::Merged)));
+        assert_eq!(outcome("custom"), Some(("custom", MigrationOutcome::Added)));
+        assert_eq!(config.models.len(), before + 1);
+        assert_eq!(config.get_model("custom").unwrap().storage.local_path, "/models/custom");
+    }
+}
@@ -0,0 +1,194 @@
+//! Curated MCP servers
+//!
+//! Templates for common MCP servers that the MCP app offers as one-click
+//! installs. A template prefills the command and arguments; its
+//! [`TemplateInput`]s are what the user still has to provide — a folder to
+//! expose, a database file, an API key — and are asked for before the server
+//! is added.
+//!
+//! Arguments may contain `{key}` placeholders, replaced with the value of
+//! the input with that key.
+
+use std::collections::HashMap;
+
+use crate::mcp_servers::McpServerConfig;
+
+/// Where a template input ends up
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum InputTarget {
+    /// Replaces `{key}` in the arguments
+    Arg,
+    /// Set as the environment variable `key`
+    Env,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TemplateInput {
+    pub key: &'static str,
+    pub label: &'static str,
+    pub placeholder: &'static str,
+    pub target: InputTarget,
+    /// Entered hidden, e.g. API keys
+    pub secret: bool,
+    pub required: bool,
+}
+
+impl TemplateInput {
+    const fn arg(key: &'static str, label: &'static str, placeholder: &'static str) -> Self {
+        Self { key, label, placeholder, target: InputTarget::Arg, secret: false, required: true }
+    }
+
+    const fn secret_env(key: &'static str, label: &'static str, placeholder: &'static str) -> Self {
+        Self { key, label, placeholder, target: InputTarget::Env, secret: true, required: true }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct McpTemplate {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+    pub command: &'static str,
+    pub args: &'static [&'static str],
+    pub inputs: &'static [TemplateInput],
+}
+
+/// Most inputs any template has; the install form has this many slots
+pub const MAX_TEMPLATE_INPUTS: usize = 3;
+
+pub const MCP_CATALOG: &[McpTemplate] = &[
+    McpTemplate {
+        id: "filesystem",
+        name: "Filesystem",
+        description: "Read, write and search files in one folder.",
+        command: "npx",
+        args: &["-y", "@modelcontextprotocol/server-filesystem", "{directory}"],
+        inputs: &[TemplateInput::arg("directory", "Folder to expose", "/Users/me/Documents")],
+    },
+    McpTemplate {
+        id: "git",
+        name: "Git",
+        description: "Inspect history, diffs and branches of a repository.",
+        command: "uvx",
+        args: &["mcp-server-git", "--repository", "{repository}"],
+        inputs: &[TemplateInput::arg("repository", "Repository folder", "/Users/me/src/project")],
+    },
+    McpTemplate {
+        id: "fetch",
+        name: "Fetch",
+        description: "Fetch web pages and convert them to markdown.",
+        command: "uvx",
+        args: &["mcp-server-fetch"],
+        inputs: &[],
+    },
+    McpTemplate {
+        id: "sqlite",
+        name: "SQLite",
+        description: "Query and update a SQLite database.",
+        command: "uvx",
+        args: &["mcp-server-sqlite", "--db-path", "{db_path}"],
+        inputs: &[TemplateInput::arg("db_path", "Database file", "/Users/me/data.db")],
+    },
+    McpTemplate {
+        id: "browser",
+        name: "Browser",
+        description: "Drive a headless Chrome: open pages, click, fill forms, take screenshots.",
+        command: "npx",
+        args: &["-y", "@modelcontextprotocol/server-puppeteer"],
+        inputs: &[],
+    },
+    McpTemplate {
+        id: "github",
+        name: "GitHub",
+        description: "Search repositories, read and open issues and pull requests.",
+        command: "npx",
+        args: &["-y", "@modelcontextprotocol/server-github"],
+        inputs: &[TemplateInput::secret_env("GITHUB_PERSONAL_ACCESS_TOKEN", "GitHub token", "ghp_…")],
+    },
+    McpTemplate {
+        id: "brave-search",
+        name: "Brave Search",
+        description: "Web and local search through the Brave Search API.",
+        command: "npx",
+        args: &["-y", "@modelcontextprotocol/server-brave-search"],
+        inputs: &[TemplateInput::secret_env("BRAVE_API_KEY", "Brave Search API key", "BSA…")],
+    },
+];
+
+pub fn find_template(id: &str) -> Option<&'static McpTemplate> {
+    MCP_CATALOG.iter().find(|t| t.id == id)
+}
+
+impl McpTemplate {
+    /// The server this template installs, placeholders still in place
+    pub fn server(&self) -> McpServerConfig {
+        let mut server = McpServerConfig::new(self.name, self.command, self.args.iter().map(|a| a.to_string()).collect());
+        server.template_id = Some(self.id.to_string());
+        server
+    }
+
+    /// The server this template installs, with `values` (by input key)
+    /// filled in. Fails if a required input is empty.
+    pub fn install(&self, values: &HashMap<&str, String>) -> Result<McpServerConfig, String> {
+        let mut server = self.server();
+        apply_inputs(self.inputs, values, &mut server)?;
+        Ok(server)
+    }
+
+    /// Whether the server needs something installed to run
+    pub fn runtime_hint(&self) -> &'static str {
+        match self.command {
+            "npx" => "Needs Node.js",
+            "uvx" => "Needs uv (Python)",
+            _ => "",
+        }
+    }
+}
+
+/// Fill `inputs` into a server's arguments and environment from `values`
+/// (by input key). Fails if a required input is empty.
+pub fn apply_inputs(inputs: &[TemplateInput], values: &HashMap<&str, String>, server: &mut McpServerConfig) -> Result<(), String> {
+    for input in inputs {
+        let value = values.get(input.key).map(|v| v.trim()).unwrap_or_default();
+        if value.is_empty() {
+            if input.required {
+                return Err(format!("{} is required", input.label));
+            }
+            continue;
+        }
+        match input.target {
+            InputTarget::Arg => {
+                let placeholder = format!("{{{}}}", input.key);
+                for arg in &mut server.args {
+                    *arg = arg.replace(&placeholder, value);
+                }
+            }
+            InputTarget::Env => {
+                server.env.insert(input.key.to_string(), value.to_string());
+            }
+        }
+    }
+    // Placeholders of optional inputs left empty
+    server.args.retain(|a| !(a.starts_with('{') && a.ends_with('}')));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_install_templates() {
+        assert!(MCP_CATALOG.iter().all(|t| t.inputs.len() <= MAX_TEMPLATE_INPUTS));
+
+        let fs = find_template("filesystem").unwrap();
+        assert!(fs.install(&HashMap::new()).is_err());
+        let server = fs.install(&HashMap::from([("directory", "/tmp/notes".to_string())])).unwrap();
+        assert_eq!(server.args, vec!["-y", "@modelcontextprotocol/server-filesystem", "/tmp/notes"]);
+
+        let github = find_template("github").unwrap();
+        let server = github.install(&HashMap::from([("GITHUB_PERSONAL_ACCESS_TOKEN", " ghp_x ".to_string())])).unwrap();
+        assert_eq!(server.env.get("GITHUB_PERSONAL_ACCESS_TOKEN").map(String::as_str), Some("ghp_x"));
+        assert_eq!(server.template_id.as_deref(), Some("github"));
+    }
+}
@@ -0,0 +1,311 @@
+//! Tools of the MCP servers set up in the MCP app
+//!
+//! A server is started the first time a chat needs its tools, as a
+//! subprocess spoken to with JSON-RPC over stdio: `initialize`, then
+//! `tools/list` and `tools/call`. It keeps running for later calls; a server
+//! whose entry is changed, disabled or removed is stopped. The store mirrors
+//! the entries here with [`set_servers`], since tools run on background
+//! threads.
+//!
+//! Models see each tool as `<server>__<tool>`, so tools of different servers
+//! can't clash.
+
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+use crate::mcp_servers::McpServerConfig;
+
+const PROTOCOL_VERSION: &str = "2024-11-05";
+/// How long a server gets to start and list its tools
+const START_TIMEOUT: Duration = Duration::from_secs(30);
+const CALL_TIMEOUT: Duration = Duration::from_secs(120);
+/// Longest function name OpenAI-compatible APIs accept
+const MAX_FUNCTION_NAME: usize = 64;
+
+/// A tool one of the servers offers
+#[derive(Clone, Debug, PartialEq)]
+pub struct McpTool {
+    pub server: String,
+    pub name: String,
+    pub description: String,
+    /// JSON Schema of the arguments
+    pub input_schema: Value,
+}
+
+impl McpTool {
+    /// The name the model calls it by
+    pub fn function_name(&self) -> String {
+        function_name(&self.server, &self.name)
+    }
+
+    /// Definition in OpenAI function calling format
+    pub fn definition(&self) -> Value {
+        json!({
+            "type": "function",
+            "function": {
+                "name": self.function_name(),
+                "description": self.description,
+                "parameters": self.input_schema,
+            }
+        })
+    }
+}
+
+/// `<server>__<tool>`, with characters function names can't have replaced
+fn function_name(server: &str, tool: &str) -> String {
+    let name: String = format!("{}__{}", server, tool)
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '_' || c == '-' { c } else { '_' })
+        .collect();
+    name.chars().take(MAX_FUNCTION_NAME).collect()
+}
+
+// ─── Connection ───────────────────────────────────────────────────────────────
+
+/// A running server
+struct Connection {
+    config: McpServerConfig,
+    child: Child,
+    stdin: ChildStdin,
+    /// Lines the server wrote, read on their own thread
+    lines: Receiver<String>,
+    next_id: u64,
+}
+
+impl Connection {
+    fn start(config: &McpServerConfig) -> Result<Self, String> {
+        log::info!("Starting MCP server {}: {}", config.name, config.command_line());
+        let mut child = Command::new(&config.command)
+            .args(&config.args)
+            .envs(&config.env)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| format!("Couldn't start {}: {}", config.command, e))?;
+        let stdin = child.stdin.take().ok_or("The server has no stdin")?;
+        let stdout = child.stdout.take().ok_or("The server has no stdout")?;
+        let (tx, lines) = mpsc::channel();
+        std::thread::spawn(move || {
+            for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                if tx.send(line).is_err() { break; }
+            }
+        });
+
+        let mut connection = Self { config: config.clone(), child, stdin, lines, next_id: 1 };
+        connection.request("initialize", json!({
+            "protocolVersion": PROTOCOL_VERSION,
+            "capabilities": {},
+            "clientInfo": { "name": "OminiX Studio", "version": env!("CARGO_PKG_VERSION") },
+        }), START_TIMEOUT)?;
+        connection.write(&json!({ "jsonrpc": "2.0", "method": "notifications/initialized" }))?;
+        Ok(connection)
+    }
+
+    fn write(&mut self, message: &Value) -> Result<(), String> {
+        writeln!(self.stdin, "{}", message)
+            .and_then(|_| self.stdin.flush())
+            .map_err(|e| format!("{} stopped: {}", self.config.name, e))
+    }
+
+    /// Send a request and wait for its response, skipping notifications and
+    /// requests from the server
+    fn request(&mut self, method: &str, params: Value, timeout: Duration) -> Result<Value, String> {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.write(&json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }))?;
+
+        let deadline = Instant::now() + timeout;
+        loop {
+            let line = match self.lines.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+                Ok(line) => line,
+                Err(RecvTimeoutError::Timeout) => return Err(format!("{} didn't answer {} in time", self.config.name, method)),
+                Err(RecvTimeoutError::Disconnected) => return Err(format!("{} stopped", self.config.name)),
+            };
+            let Ok(message) = serde_json::from_str::<Value>(&line) else { continue };
+            if message.get("id").and_then(Value::as_u64) != Some(id) || message.get("method").is_some() {
+                continue;
+            }
+            if let Some(error) = message.get("error") {
+                let text = error.get("message").and_then(Value::as_str).unwrap_or("unknown error");
+                return Err(format!("{}: {}", self.config.name, text));
+            }
+            return Ok(message.get("result").cloned().unwrap_or(Value::Null));
+        }
+    }
+
+    fn list_tools(&mut self) -> Result<Vec<McpTool>, String> {
+        let mut tools = Vec::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let params = match &cursor {
+                Some(cursor) => json!({ "cursor": cursor }),
+                None => json!({}),
+            };
+            let result = self.request("tools/list", params, START_TIMEOUT)?;
+            for tool in result.get("tools").and_then(Value::as_array).into_iter().flatten() {
+                let Some(name) = tool.get("name").and_then(Value::as_str) else { continue };
+                tools.push(McpTool {
+                    server: self.config.name.clone(),
+                    name: name.to_string(),
+                    description: tool.get("description").and_then(Value::as_str).unwrap_or_default().to_string(),
+                    input_schema: tool.get("inputSchema").cloned().unwrap_or_else(|| json!({ "type": "object" })),
+                });
+            }
+            cursor = result.get("nextCursor").and_then(Value::as_str).map(str::to_string);
+            if cursor.is_none() {
+                return Ok(tools);
+            }
+        }
+    }
+
+    fn call(&mut self, tool: &str, args: &Value) -> Result<String, String> {
+        let result = self.request("tools/call", json!({ "name": tool, "arguments": args }), CALL_TIMEOUT)?;
+        call_result(&result)
+    }
+}
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        log::info!("Stopping MCP server {}", self.config.name);
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// The text of a `tools/call` result; a result flagged as an error is `Err`
+fn call_result(result: &Value) -> Result<String, String> {
+    let text = result.get("content").and_then(Value::as_array).into_iter().flatten()
+        .map(|part| match part.get("type").and_then(Value::as_str) {
+            Some("text") => part.get("text").and_then(Value::as_str).unwrap_or_default().to_string(),
+            Some(other) => format!("[{} content]", other),
+            None => String::new(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    if result.get("isError").and_then(Value::as_bool).unwrap_or(false) {
+        Err(text)
+    } else {
+        Ok(text)
+    }
+}
+
+// ─── Servers ──────────────────────────────────────────────────────────────────
+
+/// A started server and the tools it listed. The connection is locked
+/// only while a request is under way.
+struct Server {
+    config: McpServerConfig,
+    tools: Vec<McpTool>,
+    connection: Arc<Mutex<Connection>>,
+}
+
+static SERVERS: RwLock<Vec<McpServerConfig>> = RwLock::new(Vec::new());
+static RUNNING: Mutex<Option<HashMap<String, Server>>> = Mutex::new(None);
+
+/// Mirror [`crate::Preferences::mcp_servers`], stopping servers whose entry
+/// changed or is gone
+pub fn set_servers(servers: &[McpServerConfig]) {
+    *SERVERS.write().unwrap() = servers.to_vec();
+    if let Some(running) = RUNNING.lock().unwrap().as_mut() {
+        running.retain(|name, server| servers.iter().any(|s| s.enabled && &s.name == name && *s == server.config));
+    }
+}
+
+/// Stop every server, when the app quits
+pub fn stop_all() {
+    RUNNING.lock().unwrap().take();
+}
+
+/// The tools of the server named `name`, started if needed
+fn server_tools(name: &str) -> Result<Vec<McpTool>, String> {
+    if let Some(server) = RUNNING.lock().unwrap().as_ref().and_then(|r| r.get(name)) {
+        return Ok(server.tools.clone());
+    }
+    let config = SERVERS.read().unwrap().iter()
+        .find(|s| s.enabled && s.name == name)
+        .cloned()
+        .ok_or_else(|| format!("The MCP server {} is not set up", name))?;
+    // Started outside the lock, which tool calls of other servers need
+    let mut connection = Connection::start(&config)?;
+    let tools = connection.list_tools()?;
+    log::info!("MCP server {} offers {} tools", name, tools.len());
+    let server = Server { config, tools: tools.clone(), connection: Arc::new(Mutex::new(connection)) };
+    RUNNING.lock().unwrap().get_or_insert_with(HashMap::new).insert(name.to_string(), server);
+    Ok(tools)
+}
+
+/// The tools of the named servers. Servers that fail to start are skipped.
+pub fn tools(servers: &[String]) -> Vec<McpTool> {
+    servers.iter()
+        .flat_map(|name| server_tools(name).unwrap_or_else(|e| {
+            log::warn!("No tools from MCP server {}: {}", name, e);
+            Vec::new()
+        }))
+        .collect()
+}
+
+/// Run a call to a tool of a running server. Returns `None` if `function`
+/// is not one of their tools.
+pub fn call(function: &str, args: &Value) -> Option<Result<String, String>> {
+    let (tool, connection) = {
+        let running = RUNNING.lock().unwrap();
+        running.as_ref()?.values().find_map(|server| {
+            let tool = server.tools.iter().find(|t| t.function_name() == function)?;
+            Some((tool.clone(), server.connection.clone()))
+        })?
+    };
+    log::info!("MCP tool call: {} on {}", tool.name, tool.server);
+    let result = connection.lock().unwrap().call(&tool.name, args);
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_function_names_and_results() {
+        assert_eq!(function_name("files", "read_file"), "files__read_file");
+        assert_eq!(function_name("My Notes (work)", "search"), "My_Notes__work___search");
+        assert_eq!(function_name(&"s".repeat(80), "t").len(), MAX_FUNCTION_NAME);
+
+        let result = json!({ "content": [{ "type": "text", "text": "one" }, { "type": "image", "data": "…" }] });
+        assert_eq!(call_result(&result).unwrap(), "one\n[image content]");
+        let failed = json!({ "content": [{ "type": "text", "text": "no such file" }], "isError": true });
+        assert_eq!(call_result(&failed).unwrap_err(), "no such file");
+    }
+
+    /// A server scripted in `sh` that answers the requests in the order the
+    /// client sends them
+    #[cfg(unix)]
+    #[test]
+    fn test_stdio_server() {
+        let script = r#"
+read line
+echo '{"jsonrpc":"2.0","id":1,"result":{"protocolVersion":"2024-11-05","capabilities":{}}}'
+read line
+read line
+echo '{"jsonrpc":"2.0","method":"notifications/message","params":{}}'
+echo '{"jsonrpc":"2.0","id":2,"result":{"tools":[{"name":"echo","description":"Echo","inputSchema":{"type":"object"}}]}}'
+read line
+echo '{"jsonrpc":"2.0","id":3,"result":{"content":[{"type":"text","text":"hello"}]}}'
+"#;
+        let config = McpServerConfig::new("test-echo", "sh", vec!["-c".to_string(), script.to_string()]);
+        set_servers(&[config]);
+
+        let tools = tools(&["test-echo".to_string(), "missing".to_string()]);
+        assert_eq!(tools.len(), 1);
+        assert_eq!(tools[0].definition()["function"]["name"], "test-echo__echo");
+        assert_eq!(call("test-echo__echo", &json!({})), Some(Ok("hello".to_string())));
+        assert_eq!(call("test-echo__other", &json!({})), None);
+
+        set_servers(&[]);
+        assert_eq!(call("test-echo__echo", &json!({})), None);
+    }
+}
@@ -0,0 +1,128 @@
+//! MCP server configurations
+//!
+//! Each entry is a local MCP server started as a subprocess speaking MCP
+//! over stdio: a command, its arguments and extra environment variables
+//! (often API keys). They are stored in preferences, next to the provider
+//! API keys, and edited in the MCP app.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct McpServerConfig {
+    /// Unique, shown in the MCP app and tool pickers
+    pub name: String,
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Catalog entry this was installed from, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template_id: Option<String>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl McpServerConfig {
+    pub fn new(name: impl Into<String>, command: impl Into<String>, args: Vec<String>) -> Self {
+        Self {
+            name: name.into(),
+            command: command.into(),
+            args,
+            env: BTreeMap::new(),
+            enabled: true,
+            template_id: None,
+        }
+    }
+
+    /// Check a server entered in the MCP app before it is saved
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("Enter a name for the server".to_string());
+        }
+        if self.command.trim().is_empty() {
+            return Err("Enter the command that starts the server".to_string());
+        }
+        if let Some(key) = self.env.keys().find(|k| k.is_empty() || k.contains(['=', ' '])) {
+            return Err(format!("Invalid environment variable name: {:?}", key));
+        }
+        Ok(())
+    }
+
+    /// Command and arguments as one line, for lists
+    pub fn command_line(&self) -> String {
+        join_args(std::iter::once(&self.command).chain(&self.args))
+    }
+}
+
+/// Join arguments into a line [`split_args`] splits back, quoting those
+/// with spaces
+pub fn join_args<'a>(args: impl IntoIterator<Item = &'a String>) -> String {
+    args.into_iter()
+        .map(|a| if a.is_empty() || a.contains(char::is_whitespace) { format!("\"{}\"", a) } else { a.clone() })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Split an argument line on whitespace, keeping "quoted parts" together
+pub fn split_args(line: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut quote = None;
+    let mut in_arg = false;
+    for c in line.chars() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), c) => current.push(c),
+            (None, '"' | '\'') => { quote = Some(c); in_arg = true; }
+            (None, c) if c.is_whitespace() => {
+                if in_arg { args.push(std::mem::take(&mut current)); }
+                in_arg = false;
+            }
+            (None, c) => { current.push(c); in_arg = true; }
+        }
+    }
+    if in_arg { args.push(current); }
+    args
+}
+
+/// Parse `KEY=value KEY2="a value"` into environment variables
+pub fn parse_env(line: &str) -> Result<BTreeMap<String, String>, String> {
+    split_args(line).into_iter().map(|pair| match pair.split_once('=') {
+        Some((key, value)) if !key.is_empty() => Ok((key.to_string(), value.to_string())),
+        _ => Err(format!("Environment variables must be written KEY=value, not {:?}", pair)),
+    }).collect()
+}
+
+/// The inverse of [`parse_env`], leaving out the keys `skip` matches
+pub fn format_env(env: &BTreeMap<String, String>, skip: impl Fn(&str) -> bool) -> String {
+    let pairs: Vec<String> = env.iter()
+        .filter(|(key, _)| !skip(key))
+        .map(|(key, value)| format!("{}={}", key, value))
+        .collect();
+    join_args(&pairs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_args() {
+        assert_eq!(split_args("-y  @scope/server \"/Users/me/My Files\" ''"),
+            vec!["-y", "@scope/server", "/Users/me/My Files", ""]);
+        let server = McpServerConfig::new("files", "npx", split_args("-y server '/tmp/a b'"));
+        assert_eq!(server.command_line(), "npx -y server \"/tmp/a b\"");
+        assert!(McpServerConfig::new("", "npx", vec![]).validate().is_err());
+
+        let env = parse_env("TOKEN=abc NAME=\"My Name\"").unwrap();
+        assert_eq!(env.get("NAME").map(String::as_str), Some("My Name"));
+        assert_eq!(parse_env(&format_env(&env, |_| false)).unwrap(), env);
+        assert!(parse_env("TOKEN").is_err());
+    }
+}
@@ -0,0 +1,244 @@
+//! Meeting notes from a recording
+//!
+//! One click in the ASR panel of the Model Hub transcribes a recording with
+//! the loaded ASR model, then has the loaded LLM write structured notes from
+//! the transcript ([`summarize`]): a summary, decisions, action items and
+//! open questions. Transcripts longer than a model reads at once are
+//! summarized part by part and the partial notes merged. The result is saved
+//! as a new chat ([`MeetingNotes::save`]) with the transcript, and the
+//! recording when it isn't too large, attached to its first message, so the
+//! notes can be discussed further with the same model.
+
+use moly_kit::prelude::*;
+
+use crate::chat_template::{self, ChatTemplateOverrides};
+use crate::chats::{ChatId, Chats};
+use crate::model_registry::{RegistryCategory, RegistryModel};
+use crate::ominix_api_client::{ChatMessage, OminiXApiClient};
+use crate::tts_longform::split_into_chunks;
+
+/// Transcript characters summarized in one request
+pub const CHUNK_CHARS: usize = 12_000;
+
+/// Recordings larger than this are named in the chat instead of attached
+pub const MAX_AUDIO_ATTACHMENT_BYTES: u64 = 25 * 1024 * 1024;
+
+/// How the notes are laid out
+const NOTES_TEMPLATE: &str = "\
+## Summary
+A few sentences on what the meeting was about and what came of it.
+
+## Decisions
+- One line per decision.
+
+## Action items
+- [ ] Task — owner, due date if one was mentioned
+
+## Open questions
+- Questions raised but not settled.
+
+## Key points
+- Facts, figures and arguments worth keeping.";
+
+/// System prompt asking for notes on a whole transcript
+pub fn instruction() -> String {
+    format!(
+        "You write meeting notes from speech transcripts. Using only what the user's transcript says, fill in \
+        this Markdown template:\n\n{}\n\nWrite \"None\" under a heading with nothing to list. Keep names, \
+        numbers and dates exactly as said, and write in the language of the transcript. Reply with the notes \
+        only.",
+        NOTES_TEMPLATE
+    )
+}
+
+/// System prompt asking for notes on one part of a long transcript
+fn part_instruction(part: usize, parts: usize) -> String {
+    format!(
+        "You take notes on part {} of {} of a meeting transcript. List the topics discussed, decisions, tasks \
+        with their owners and due dates, open questions and key facts from the user's text as short bullet \
+        points. Keep names, numbers and dates exactly as said. Reply with the bullet points only.",
+        part, parts
+    )
+}
+
+/// System prompt asking to merge the notes on consecutive parts
+fn merge_instruction() -> String {
+    format!(
+        "The user gives you notes on consecutive parts of one meeting. Merge them into one set of meeting \
+        notes in this Markdown template:\n\n{}\n\nCombine repeated points, and write \"None\" under a heading \
+        with nothing to list. Reply with the notes only.",
+        NOTES_TEMPLATE
+    )
+}
+
+/// A model's answer without a `<think>` block
+fn clean_answer(answer: &str) -> Result<String, String> {
+    let text = match answer.find("</think>") {
+        Some(end) => &answer[end + "</think>".len()..],
+        None => answer,
+    }
+    .trim();
+    if text.is_empty() {
+        return Err("The model returned no notes.".to_string());
+    }
+    Ok(text.to_string())
+}
+
+/// Write notes on `transcript` with `model`. `on_step(done, total)` follows
+/// the requests, which are one per part plus one to merge them. Blocks; call
+/// it off the UI thread.
+pub fn summarize(
+    model: &RegistryModel,
+    transcript: &str,
+    mut on_step: impl FnMut(usize, usize),
+) -> Result<String, String> {
+    let template = chat_template::template_for(model, &ChatTemplateOverrides::load());
+    let client = OminiXApiClient::localhost(600);
+    let ask = |system: String, text: &str| {
+        chat_template::complete(&client, &model.runtime.api_model_id, template, vec![
+            ChatMessage::system(system),
+            ChatMessage::user(text),
+        ])
+        .map_err(|e| format!("Writing notes with {} failed: {}", model.name, e))
+        .and_then(|answer| clean_answer(&answer))
+    };
+
+    let parts = split_into_chunks(transcript.trim(), CHUNK_CHARS);
+    if parts.len() <= 1 {
+        on_step(0, 1);
+        let notes = ask(instruction(), transcript.trim())?;
+        on_step(1, 1);
+        return Ok(notes);
+    }
+    let total = parts.len() + 1;
+    let mut partial = Vec::with_capacity(parts.len());
+    for (i, part) in parts.iter().enumerate() {
+        on_step(i, total);
+        partial.push(format!("Part {}:\n{}", i + 1, ask(part_instruction(i + 1, parts.len()), part)?));
+    }
+    on_step(parts.len(), total);
+    let notes = ask(merge_instruction(), &partial.join("\n\n"))?;
+    on_step(total, total);
+    Ok(notes)
+}
+
+/// Notes on a recording, ready to be saved as a chat
+pub struct MeetingNotes {
+    pub title: String,
+    pub transcript: String,
+    /// API id of the model that wrote them
+    pub model_id: String,
+    pub messages: Vec<Message>,
+}
+
+impl MeetingNotes {
+    /// Save as a new chat with the model that wrote the notes; returns its id
+    pub fn save(self, chats: &mut Chats) -> ChatId {
+        let chat_id = chats.create_chat(Some(BotId::new(&self.model_id)));
+        chats.update_chat_messages(chat_id, self.messages);
+        chats.update_chat_title(chat_id, self.title);
+        chats.update_chat_category(chat_id, Some(RegistryCategory::Llm));
+        log::info!("Saved meeting notes as chat {}", chat_id);
+        chat_id
+    }
+}
+
+/// Write notes on the transcript of the recording at `audio_path` with
+/// `model`, as [`summarize`] does
+pub fn write(
+    model: &RegistryModel,
+    audio_path: &str,
+    transcript: &str,
+    on_step: impl FnMut(usize, usize),
+) -> Result<MeetingNotes, String> {
+    let notes = summarize(model, transcript, on_step)?;
+    let model_id = model.runtime.api_model_id.clone();
+    Ok(MeetingNotes {
+        title: chat_title(audio_path),
+        transcript: transcript.to_string(),
+        messages: as_messages(&model_id, audio_path, transcript, &notes),
+        model_id,
+    })
+}
+
+/// Title of the chat holding the notes, e.g. `Notes: standup-0412`
+fn chat_title(audio_path: &str) -> String {
+    let stem = std::path::Path::new(audio_path)
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "recording".to_string());
+    format!("Notes: {}", stem)
+}
+
+/// The notes as the messages of a chat with `model_id`: the recording and
+/// its transcript attached to a first message, then the notes as the answer
+fn as_messages(model_id: &str, audio_path: &str, transcript: &str, notes: &str) -> Vec<Message> {
+    let path = std::path::Path::new(audio_path);
+    let file_name = path.file_name().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+    let stem = path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_else(|| "recording".to_string());
+
+    let mut attachments = vec![Attachment::from_bytes(
+        format!("{} transcript.txt", stem),
+        Some("text/plain".to_string()),
+        transcript.as_bytes(),
+    )];
+    let mut text = format!("Meeting notes for the recording {}.", file_name);
+    match std::fs::metadata(path).map(|m| m.len()) {
+        Ok(size) if size > MAX_AUDIO_ATTACHMENT_BYTES => {
+            text.push_str(&format!(" The recording is too large to attach; it's at {}.", audio_path));
+        }
+        Ok(_) => {
+            if let Ok(bytes) = std::fs::read(path) {
+                attachments.push(Attachment::from_bytes(file_name.clone(), audio_mime(&file_name), &bytes));
+            }
+        }
+        Err(e) => log::warn!("Couldn't attach {}: {}", audio_path, e),
+    }
+
+    vec![
+        Message {
+            from: EntityId::User,
+            content: MessageContent { text, attachments, ..Default::default() },
+            ..Default::default()
+        },
+        Message {
+            from: EntityId::Bot(BotId::new(model_id)),
+            content: MessageContent { text: notes.trim().to_string(), ..Default::default() },
+            ..Default::default()
+        },
+    ]
+}
+
+fn audio_mime(file_name: &str) -> Option<String> {
+    let extension = file_name.rsplit_once('.')?.1.to_ascii_lowercase();
+    let mime = match extension.as_str() {
+        "wav" => "audio/wav",
+        "mp3" => "audio/mpeg",
+        "m4a" | "aac" => "audio/mp4",
+        "flac" => "audio/flac",
+        "ogg" => "audio/ogg",
+        _ => return None,
+    };
+    Some(mime.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_notes_chat() {
+        assert_eq!(chat_title("/recordings/standup-0412.m4a"), "Notes: standup-0412");
+        assert_eq!(clean_answer("<think>hm</think>\n## Summary\nShipped.").unwrap(), "## Summary\nShipped.");
+        assert!(clean_answer("<think>hm</think>").is_err());
+        assert!(instruction().contains("## Action items"));
+
+        let messages = as_messages("qwen3-8b", "/nowhere/standup.wav", "we ship friday", "## Summary\nShip.");
+        assert_eq!(messages.len(), 2);
+        assert!(matches!(messages[0].from, EntityId::User));
+        assert_eq!(messages[0].content.text, "Meeting notes for the recording standup.wav.");
+        assert_eq!(messages[0].content.attachments.len(), 1);
+        assert_eq!(messages[1].content.text, "## Summary\nShip.");
+        assert_eq!(audio_mime("a.MP3").as_deref(), Some("audio/mpeg"));
+    }
+}
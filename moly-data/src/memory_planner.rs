@@ -0,0 +1,140 @@
+//! Planning which models to keep loaded together
+//!
+//! Before loading several models, the Model Hub's planner adds up their
+//! `runtime.memory_gb` from the registry and compares it with the installed
+//! RAM ([`crate::platform::total_memory`]). Only part of the RAM is counted
+//! as usable ([`USABLE_FRACTION`]): the system, the app and the GPU's wired
+//! memory limit need the rest. The runtime also keeps one model per
+//! category, so two models of the same category are flagged as well.
+
+use crate::model_registry::{RegistryCategory, RegistryModel};
+
+/// Share of the installed RAM models can use without the system swapping
+pub const USABLE_FRACTION: f64 = 0.75;
+
+const GB: f64 = 1024.0 * 1024.0 * 1024.0;
+
+/// Whether the planned models fit in memory
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Verdict {
+    /// Within the usable share of the RAM
+    Fits,
+    /// Over the usable share but under the installed RAM; loads, but the
+    /// system will swap
+    Tight,
+    /// More than the installed RAM
+    TooLarge,
+    /// The installed RAM couldn't be determined
+    Unknown,
+}
+
+/// The planned models' combined memory against the system's
+#[derive(Clone, Debug, PartialEq)]
+pub struct MemoryPlan {
+    /// Summed `memory_gb` of the planned models
+    pub required_gb: f64,
+    /// Installed RAM, when known
+    pub system_gb: Option<f64>,
+    pub verdict: Verdict,
+    /// Categories with more than one planned model, and those models' names
+    pub conflicts: Vec<(RegistryCategory, Vec<String>)>,
+}
+
+impl MemoryPlan {
+    /// The share of the RAM models can use
+    pub fn usable_gb(&self) -> Option<f64> {
+        self.system_gb.map(|gb| gb * USABLE_FRACTION)
+    }
+
+    /// Whether the models can be loaded together at all
+    pub fn is_feasible(&self) -> bool {
+        self.conflicts.is_empty() && self.verdict != Verdict::TooLarge
+    }
+
+    /// "19.5 GB of 24.0 GB usable (32 GB installed)"
+    pub fn summary(&self) -> String {
+        match (self.system_gb, self.usable_gb()) {
+            (Some(system), Some(usable)) => {
+                format!("{:.1} GB of {:.1} GB usable ({:.0} GB installed)", self.required_gb, usable, system)
+            }
+            _ => format!("{:.1} GB needed; installed memory unknown", self.required_gb),
+        }
+    }
+
+    /// Why the plan won't work well, one sentence each
+    pub fn warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        match (self.verdict, self.system_gb, self.usable_gb()) {
+            (Verdict::TooLarge, Some(system), _) => warnings.push(format!(
+                "Needs {:.1} GB more than the {:.0} GB installed. Drop a model or pick smaller ones.",
+                self.required_gb - system, system)),
+            (Verdict::Tight, _, Some(usable)) => warnings.push(format!(
+                "{:.1} GB over the usable memory. The models may load, but the system will swap and slow down.",
+                self.required_gb - usable)),
+            _ => {}
+        }
+        for (category, names) in &self.conflicts {
+            warnings.push(format!("Only one {} model can be loaded at a time: {}.", category.label(), names.join(", ")));
+        }
+        warnings
+    }
+}
+
+/// Plan keeping `models` loaded together on a system with `system_bytes` of RAM
+pub fn plan<'a>(models: impl IntoIterator<Item = &'a RegistryModel>, system_bytes: Option<u64>) -> MemoryPlan {
+    let mut required_gb = 0.0;
+    let mut by_category: Vec<(RegistryCategory, Vec<String>)> = Vec::new();
+    for model in models {
+        required_gb += model.runtime.memory_gb as f64;
+        match by_category.iter_mut().find(|(c, _)| *c == model.category) {
+            Some((_, names)) => names.push(model.name.clone()),
+            None => by_category.push((model.category, vec![model.name.clone()])),
+        }
+    }
+    let system_gb = system_bytes.filter(|b| *b > 0).map(|b| b as f64 / GB);
+    let verdict = match system_gb {
+        None => Verdict::Unknown,
+        Some(system) if required_gb > system => Verdict::TooLarge,
+        Some(system) if required_gb > system * USABLE_FRACTION => Verdict::Tight,
+        Some(_) => Verdict::Fits,
+    };
+    MemoryPlan {
+        required_gb,
+        system_gb,
+        verdict,
+        conflicts: by_category.into_iter().filter(|(_, names)| names.len() > 1).collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model_registry::ModelRegistry;
+
+    #[test]
+    fn test_plan() {
+        let registry = ModelRegistry::bundled();
+        let model = |category: RegistryCategory, memory_gb: f32| {
+            let mut model = registry.by_category(category).next().unwrap().clone();
+            model.runtime.memory_gb = memory_gb;
+            model
+        };
+        let llm = model(RegistryCategory::Llm, 10.0);
+        let asr = model(RegistryCategory::Asr, 2.0);
+        let gb = |n: u64| Some(n * 1024 * 1024 * 1024);
+
+        let fits = plan([&llm, &asr], gb(32));
+        assert_eq!(fits.verdict, Verdict::Fits);
+        assert!(fits.is_feasible() && fits.warnings().is_empty());
+        assert_eq!(fits.summary(), "12.0 GB of 24.0 GB usable (32 GB installed)");
+
+        assert_eq!(plan([&llm, &asr], gb(14)).verdict, Verdict::Tight);
+        assert!(!plan([&llm, &asr], gb(8)).is_feasible());
+        assert_eq!(plan([&llm], None).verdict, Verdict::Unknown);
+
+        let second = model(RegistryCategory::Llm, 4.0);
+        let conflict = plan([&llm, &second], gb(64));
+        assert_eq!(conflict.conflicts.len(), 1);
+        assert!(!conflict.is_feasible());
+    }
+}
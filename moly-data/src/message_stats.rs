@@ -0,0 +1,212 @@
+//! Provider, model, timing, tokens and cost of each answer
+//!
+//! The chat times every answer from the prompt being sent ([`AnswerTimer`])
+//! and keeps the figures on the chat ([`crate::ChatData::message_stats`]),
+//! shown in a footer under the conversation. The chat clients don't report
+//! token usage, so counts are estimated from the text ([`estimate_tokens`]);
+//! the prompt side counts the whole conversation sent before the answer. For
+//! providers whose catalog lists prices, that gives an estimated cost.
+
+use moly_kit::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+use crate::provider_catalog::CatalogModel;
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct MessageStats {
+    /// Position of the answer in the chat
+    pub message_index: usize,
+    /// Provider name, e.g. `OpenAI` or `OminiX Local`
+    pub provider: String,
+    pub model: String,
+    /// From sending the prompt to the first text of the answer
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub first_token_ms: Option<u64>,
+    /// From sending the prompt to the end of the answer
+    pub total_ms: u64,
+    /// Estimated tokens sent, the conversation up to the prompt
+    pub prompt_tokens: u32,
+    /// Estimated tokens of the answer
+    pub completion_tokens: u32,
+    /// Estimated USD, for providers with prices in their catalog
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost_usd: Option<f64>,
+}
+
+impl MessageStats {
+    /// e.g. `gpt-4o-mini · OpenAI · 2.4 s (0.6 s to first token) · ~850 in / ~120 out tokens · ~$0.0002`
+    pub fn summary(&self) -> String {
+        let mut parts = vec![self.model.clone()];
+        if !self.provider.is_empty() {
+            parts.push(self.provider.clone());
+        }
+        let total = seconds(self.total_ms);
+        parts.push(match self.first_token_ms {
+            Some(first) => format!("{} ({} to first token)", total, seconds(first)),
+            None => total,
+        });
+        parts.push(format!("~{} in / ~{} out tokens", self.prompt_tokens, self.completion_tokens));
+        if let Some(cost) = self.cost_usd {
+            parts.push(format!("~{}", dollars(cost)));
+        }
+        parts.join(" · ")
+    }
+}
+
+/// Totals over a chat's answers, e.g. `3 answers · 7.1 s · ~2400 tokens · ~$0.0031`
+pub fn totals(stats: &[MessageStats]) -> String {
+    let total_ms: u64 = stats.iter().map(|s| s.total_ms).sum();
+    let tokens: u64 = stats.iter().map(|s| (s.prompt_tokens + s.completion_tokens) as u64).sum();
+    let mut line = format!(
+        "{} answer{} · {} · ~{} tokens",
+        stats.len(),
+        if stats.len() == 1 { "" } else { "s" },
+        seconds(total_ms),
+        tokens
+    );
+    let costs: Vec<f64> = stats.iter().filter_map(|s| s.cost_usd).collect();
+    if !costs.is_empty() {
+        line.push_str(&format!(" · ~{}", dollars(costs.iter().sum())));
+    }
+    line
+}
+
+fn seconds(ms: u64) -> String {
+    format!("{:.1} s", ms as f64 / 1000.0)
+}
+
+fn dollars(usd: f64) -> String {
+    if usd < 0.01 { format!("${:.4}", usd) } else { format!("${:.2}", usd) }
+}
+
+/// Tokens in `text`, roughly: a CJK character is about one token, other
+/// text about four characters per token
+pub fn estimate_tokens(text: &str) -> u32 {
+    let (mut cjk, mut other) = (0u32, 0u32);
+    for c in text.chars() {
+        match c as u32 {
+            0x3040..=0x30FF | 0x3400..=0x4DBF | 0x4E00..=0x9FFF | 0xAC00..=0xD7AF => cjk += 1,
+            _ => other += 1,
+        }
+    }
+    cjk + other.div_ceil(4)
+}
+
+/// Estimated USD for a request, when the catalog lists the model's prices
+pub fn cost(model: &CatalogModel, prompt_tokens: u32, completion_tokens: u32) -> Option<f64> {
+    let prompt = model.prompt_price? * prompt_tokens as f64;
+    let completion = model.completion_price.unwrap_or(0.0) * completion_tokens as f64;
+    Some((prompt + completion) / 1_000_000.0)
+}
+
+/// How long an answer took
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AnswerTiming {
+    pub message_index: usize,
+    pub first_token: Option<Duration>,
+    pub total: Duration,
+}
+
+/// Times answers by watching the chat's messages as they change
+#[derive(Clone, Debug, Default)]
+pub struct AnswerTimer {
+    /// The prompt being answered: its index and when it was sent
+    sent: Option<(usize, Instant)>,
+    first_token: Option<Duration>,
+}
+
+impl AnswerTimer {
+    /// Look at the messages after a change; returns the timing of an answer
+    /// that has just finished
+    pub fn observe(&mut self, messages: &[Message], now: Instant) -> Option<AnswerTiming> {
+        let index = messages.len().checked_sub(1)?;
+        let message = &messages[index];
+        match &message.from {
+            EntityId::User => {
+                if self.sent.map(|(sent, _)| sent) != Some(index) {
+                    self.sent = Some((index, now));
+                    self.first_token = None;
+                }
+                None
+            }
+            EntityId::Bot(_) => {
+                let (sent, sent_at) = self.sent?;
+                if index <= sent {
+                    return None;
+                }
+                if self.first_token.is_none() && !message.content.text.is_empty() {
+                    self.first_token = Some(now - sent_at);
+                }
+                if message.metadata.is_writing || message.content.text.trim().is_empty() {
+                    return None;
+                }
+                self.sent = None;
+                Some(AnswerTiming { message_index: index, first_token: self.first_token.take(), total: now - sent_at })
+            }
+            _ => None,
+        }
+    }
+
+    /// Stop timing, e.g. when switching chats
+    pub fn reset(&mut self) {
+        self.sent = None;
+        self.first_token = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(from: EntityId, text: &str, writing: bool) -> Message {
+        let mut message = Message {
+            from,
+            content: MessageContent { text: text.to_string(), ..Default::default() },
+            ..Default::default()
+        };
+        message.metadata.is_writing = writing;
+        message
+    }
+
+    #[test]
+    fn test_answer_timer() {
+        let start = Instant::now();
+        let at = |ms| start + Duration::from_millis(ms);
+        let bot = || EntityId::Bot(BotId::new("qwen3-4b"));
+        let mut timer = AnswerTimer::default();
+        let mut messages = vec![message(EntityId::User, "Hi", false)];
+        assert_eq!(timer.observe(&messages, at(0)), None);
+        messages.push(message(bot(), "", true));
+        assert_eq!(timer.observe(&messages, at(100)), None);
+        messages[1].content.text = "Hel".to_string();
+        assert_eq!(timer.observe(&messages, at(400)), None);
+        messages[1] = message(bot(), "Hello!", false);
+        let timing = timer.observe(&messages, at(900)).unwrap();
+        assert_eq!(timing.message_index, 1);
+        assert_eq!(timing.first_token, Some(Duration::from_millis(400)));
+        assert_eq!(timing.total, Duration::from_millis(900));
+        // Nothing more until the next prompt
+        assert_eq!(timer.observe(&messages, at(1000)), None);
+    }
+
+    #[test]
+    fn test_estimates_and_summary() {
+        assert_eq!(estimate_tokens("Hello world!"), 3);
+        assert_eq!(estimate_tokens("你好"), 2);
+        let model = CatalogModel { prompt_price: Some(0.15), completion_price: Some(0.6), ..Default::default() };
+        let stats = MessageStats {
+            message_index: 1,
+            provider: "OpenAI".to_string(),
+            model: "gpt-4o-mini".to_string(),
+            first_token_ms: Some(600),
+            total_ms: 2400,
+            prompt_tokens: 850,
+            completion_tokens: 120,
+            cost_usd: cost(&model, 850, 120),
+        };
+        assert_eq!(stats.summary(), "gpt-4o-mini · OpenAI · 2.4 s (0.6 s to first token) · ~850 in / ~120 out tokens · ~$0.0002");
+        assert_eq!(totals(&[stats.clone(), stats]), "2 answers · 4.8 s · ~1940 tokens · ~$0.0004");
+        assert_eq!(cost(&CatalogModel::default(), 10, 10), None);
+    }
+}
@@ -0,0 +1,300 @@
+//! In-process mock of the ominix-api runtime, for integration tests
+//!
+//! [`MockRuntime::start`] serves the endpoints the clients in this crate
+//! call on a free local port: version, models and load/unload, chat and prompt
+//! completions, reranking, transcriptions, speech, music and voice training. Point
+//! [`crate::ModelRuntimeClient::new`] or
+//! [`crate::ominix_api_client::OminiXApiClient::new`] at
+//! [`MockRuntime::base_url`] to run load and inference flows without an
+//! MLX runtime. Every request is recorded so tests can check what was sent.
+//!
+//! Built for this crate's tests, and for other crates with the
+//! `mock-runtime` feature.
+
+use base64::Engine as _;
+use serde_json::{json, Value};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Version the mock reports at `/v1/version`
+pub const MOCK_RUNTIME_VERSION: &str = "0.9.0";
+
+/// A request the mock received
+#[derive(Clone, Debug, PartialEq)]
+pub struct RecordedRequest {
+    pub method: String,
+    /// Path with its query string
+    pub path: String,
+    /// JSON body; `Null` when there was none
+    pub body: Value,
+}
+
+#[derive(Debug, Default)]
+struct MockState {
+    /// Loaded models as (model id, model type)
+    loaded: Vec<(String, String)>,
+    /// Voices as (name, ready)
+    voices: Vec<(String, bool)>,
+    /// Training tasks as (task id, voice name)
+    tasks: Vec<(String, String)>,
+    requests: Vec<RecordedRequest>,
+}
+
+/// The mock server; stops when dropped
+pub struct MockRuntime {
+    base_url: String,
+    state: Arc<Mutex<MockState>>,
+    stop: Arc<AtomicBool>,
+}
+
+impl MockRuntime {
+    pub fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("mock runtime could not bind a port");
+        let base_url = format!("http://{}", listener.local_addr().expect("mock runtime has no address"));
+        let state = Arc::new(Mutex::new(MockState::default()));
+        let stop = Arc::new(AtomicBool::new(false));
+        let (thread_state, thread_stop) = (state.clone(), stop.clone());
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                if thread_stop.load(Ordering::SeqCst) {
+                    break;
+                }
+                let Ok(stream) = stream else { continue };
+                let state = thread_state.clone();
+                std::thread::spawn(move || serve(stream, &state));
+            }
+        });
+        Self { base_url, state, stop }
+    }
+
+    /// Server root, e.g. `http://127.0.0.1:50123`
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Requests received so far, oldest first
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.state.lock().unwrap().requests.clone()
+    }
+
+    /// Ids of the loaded models
+    pub fn loaded_models(&self) -> Vec<String> {
+        self.state.lock().unwrap().loaded.iter().map(|(id, _)| id.clone()).collect()
+    }
+
+    /// Add a voice the server already knows
+    pub fn add_voice(&self, name: &str, ready: bool) {
+        self.state.lock().unwrap().voices.push((name.to_string(), ready));
+    }
+}
+
+impl Drop for MockRuntime {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+        // Wake the accept loop so it sees the flag
+        let _ = TcpStream::connect(self.base_url.trim_start_matches("http://"));
+    }
+}
+
+fn serve(stream: TcpStream, state: &Mutex<MockState>) {
+    let Ok(read_half) = stream.try_clone() else { return };
+    let mut reader = BufReader::new(read_half);
+    let mut request_line = String::new();
+    if reader.read_line(&mut request_line).is_err() {
+        return;
+    }
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(path)) = (parts.next(), parts.next()) else { return };
+
+    let mut content_length = 0;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header).is_err() || header.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().unwrap_or(0);
+            }
+        }
+    }
+    let mut body = vec![0; content_length];
+    if reader.read_exact(&mut body).is_err() {
+        return;
+    }
+    let body = serde_json::from_slice(&body).unwrap_or(Value::Null);
+
+    let (status, content_type, payload) = {
+        let mut state = state.lock().unwrap();
+        state.requests.push(RecordedRequest { method: method.to_string(), path: path.to_string(), body: body.clone() });
+        respond(&mut state, method, path, &body)
+    };
+    let mut stream = stream;
+    let head = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        status, content_type, payload.len()
+    );
+    let _ = stream.write_all(head.as_bytes()).and_then(|_| stream.write_all(&payload));
+}
+
+fn respond(state: &mut MockState, method: &str, path: &str, body: &Value) -> (&'static str, &'static str, Vec<u8>) {
+    let ok = |value: Value| ("200 OK", "application/json", value.to_string().into_bytes());
+    let error = |status: &'static str, message: &str| {
+        (status, "application/json", json!({ "error": { "message": message } }).to_string().into_bytes())
+    };
+    let text = |key: &str| body.get(key).and_then(Value::as_str).unwrap_or_default().to_string();
+    let (route, query) = path.split_once('?').unwrap_or((path, ""));
+
+    match (method, route) {
+        ("GET", "/v1/version") => ok(json!({ "version": MOCK_RUNTIME_VERSION })),
+        ("GET", "/v1/models") => ok(json!({
+            "data": state.loaded.iter().map(|(id, _)| json!({ "id": id, "status": "loaded", "memory_gb": 1.5 })).collect::<Vec<_>>()
+        })),
+        ("POST", "/v1/models/load") => {
+            let (model, model_type) = (text("model"), text("model_type"));
+            if model.is_empty() {
+                return error("400 Bad Request", "model is required");
+            }
+            state.loaded.retain(|(_, t)| *t != model_type);
+            state.loaded.push((model, model_type));
+            ok(json!({ "status": "loaded" }))
+        }
+        ("POST", "/v1/models/unload") => {
+            let model_type = text("model_type");
+            state.loaded.retain(|(_, t)| model_type != "all" && *t != model_type);
+            ok(json!({ "status": "unloaded" }))
+        }
+        ("POST", "/v1/chat/completions") => {
+            if !state.loaded.iter().any(|(_, t)| t == "llm" || t == "vlm") {
+                return error("400 Bad Request", "model not loaded");
+            }
+            let prompt = body["messages"].as_array()
+                .and_then(|messages| messages.iter().rev().find(|m| m["role"] == "user"))
+                .and_then(|m| m["content"].as_str())
+                .unwrap_or_default();
+            ok(json!({ "choices": [{ "message": { "role": "assistant", "content": format!("Echo: {}", prompt) } }] }))
+        }
+        ("POST", "/v1/completions") => {
+            if !state.loaded.iter().any(|(_, t)| t == "llm") {
+                return error("400 Bad Request", "model not loaded");
+            }
+            ok(json!({ "choices": [{ "text": format!(" Completed {} characters", text("prompt").chars().count()) }] }))
+        }
+        // Scored by the share of query words in each document
+        ("POST", "/v1/rerank") => {
+            if !state.loaded.iter().any(|(_, t)| t == "rerank") {
+                return error("400 Bad Request", "model not loaded");
+            }
+            let query = text("query").to_lowercase();
+            let words: Vec<&str> = query.split_whitespace().collect();
+            let documents = body["documents"].as_array().cloned().unwrap_or_default();
+            let results: Vec<Value> = documents.iter().enumerate().map(|(index, document)| {
+                let document = document.as_str().unwrap_or_default().to_lowercase();
+                let found = words.iter().filter(|w| document.contains(*w)).count();
+                json!({ "index": index, "relevance_score": found as f64 / words.len().max(1) as f64 })
+            }).collect();
+            ok(json!({ "results": results }))
+        }
+        ("POST", "/v1/audio/transcriptions") => ok(json!({
+            "text": "mock transcript",
+            "segments": [{ "start": 0.0, "end": 1.0, "text": "mock transcript" }]
+        })),
+        ("POST", "/v1/audio/speech") => ("200 OK", "audio/wav", silent_wav()),
+        // Streamed: two progress events, then the clip
+        ("POST", "/v1/audio/generations") => {
+            if !state.loaded.iter().any(|(_, t)| t == "music") {
+                return error("400 Bad Request", "model not loaded");
+            }
+            let audio = base64::engine::general_purpose::STANDARD.encode(silent_wav());
+            let events = [json!({ "progress": 0.5 }), json!({ "progress": 1.0 }), json!({ "data": [{ "b64_json": audio }] })];
+            let mut body: String = events.iter().map(|e| format!("data: {}\n\n", e)).collect();
+            body.push_str("data: [DONE]\n\n");
+            ("200 OK", "text/event-stream", body.into_bytes())
+        }
+        ("GET", "/v1/voices") => ok(json!({
+            "voices": state.voices.iter()
+                .map(|(name, ready)| json!({ "name": name, "status": if *ready { "ready" } else { "training" } }))
+                .collect::<Vec<_>>()
+        })),
+        ("POST", "/v1/voices/train") => {
+            let name = text("name");
+            if name.is_empty() || text("audio_data").is_empty() {
+                return error("400 Bad Request", "name and audio_data are required");
+            }
+            let task_id = format!("task-{}", state.tasks.len() + 1);
+            state.voices.push((name.clone(), false));
+            state.tasks.push((task_id.clone(), name));
+            ok(json!({ "task_id": task_id }))
+        }
+        // Training finishes on the first status poll
+        ("GET", "/v1/voices/train/status") => {
+            let task_id = query.strip_prefix("task_id=").unwrap_or_default();
+            let Some((_, name)) = state.tasks.iter().find(|(id, _)| id == task_id).cloned() else {
+                return error("404 Not Found", "unknown task");
+            };
+            for voice in state.voices.iter_mut().filter(|(n, _)| *n == name) {
+                voice.1 = true;
+            }
+            ok(json!({ "state": "done", "stage": "done", "progress": 1.0 }))
+        }
+        ("POST", "/v1/voices/train/cancel") => ok(json!({})),
+        _ => error("404 Not Found", "no such endpoint"),
+    }
+}
+
+/// A tenth of a second of 16 kHz mono silence
+fn silent_wav() -> Vec<u8> {
+    let samples = 1600u32;
+    let data_len = samples * 2;
+    let mut wav = Vec::with_capacity(44 + data_len as usize);
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+    wav.extend_from_slice(b"WAVEfmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes());
+    wav.extend_from_slice(&1u16.to_le_bytes());
+    wav.extend_from_slice(&1u16.to_le_bytes());
+    wav.extend_from_slice(&16_000u32.to_le_bytes());
+    wav.extend_from_slice(&32_000u32.to_le_bytes());
+    wav.extend_from_slice(&2u16.to_le_bytes());
+    wav.extend_from_slice(&16u16.to_le_bytes());
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&data_len.to_le_bytes());
+    wav.resize(44 + data_len as usize, 0);
+    wav
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chat_template::{self, ChatTemplate};
+    use crate::ominix_api_client::{ChatCompletionRequest, ChatMessage, OminiXApiClient, SpeechRequest};
+    use crate::ModelRuntimeClient;
+
+    #[test]
+    fn test_load_and_chat_flow() {
+        let mock = MockRuntime::start();
+        let runtime = ModelRuntimeClient::new(mock.base_url());
+        let api = OminiXApiClient::new(mock.base_url(), 5);
+        let chat = ChatCompletionRequest { model: "qwen3-4b".into(), messages: vec![ChatMessage::user("Hi")], max_tokens: None };
+
+        assert!(runtime.is_alive());
+        assert_eq!(runtime.version().unwrap().to_string(), MOCK_RUNTIME_VERSION);
+        assert!(api.chat_completion(&chat).is_err());
+
+        runtime.load_model("qwen3-4b", "llm").unwrap();
+        let models = runtime.list_models().unwrap();
+        assert_eq!(models.len(), 1);
+        assert_eq!(models[0].api_id, "qwen3-4b");
+        assert_eq!(api.chat_completion(&chat).unwrap(), "Echo: Hi");
+        let completed = chat_template::complete(&api, "qwen3-4b", Some(ChatTemplate::ChatMl), vec![ChatMessage::user("Hi")]);
+        assert_eq!(completed.unwrap(), "Completed 52 characters");
+        assert_eq!(&api.speech(&SpeechRequest::wav("tts", "vivian", "Hi")).unwrap()[..4], b"RIFF");
+
+        runtime.unload_model("all").unwrap();
+        assert!(mock.loaded_models().is_empty());
+        let load = mock.requests().into_iter().find(|r| r.path == "/v1/models/load").unwrap();
+        assert_eq!(load.body, json!({ "model": "qwen3-4b", "model_type": "llm" }));
+    }
+}
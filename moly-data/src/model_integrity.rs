@@ -0,0 +1,217 @@
+//! Integrity checks for downloaded model files
+//!
+//! A cancelled or failed download used to leave whatever it had fetched in
+//! the model folder, and one weight file was enough for the folder to look
+//! downloaded. Downloads now write each file as `<name>.part` and rename it
+//! once complete, and record the file list they resolved in the model folder
+//! (`.moly-manifest.json`). Scans compare the folder against the registry's
+//! `storage.files` when it has them, or else against that manifest, so
+//! incomplete installs show up as [`DownloadStatus::Partial`].
+//!
+//! [`find_orphans`] lists leftover partial data for the cleanup tool in
+//! Settings; [`delete_orphan`] removes it.
+//!
+//! [`DownloadStatus::Partial`]: crate::model_state::DownloadStatus::Partial
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use crate::model_registry::{ModelRegistry, RegistryFile, RegistryModel};
+use crate::model_state::{self, DownloadStatus};
+use crate::model_trash;
+
+pub const MANIFEST_FILENAME: &str = ".moly-manifest.json";
+/// Suffix of a file that is still being downloaded
+pub const PART_SUFFIX: &str = ".part";
+
+#[derive(Serialize, Deserialize)]
+struct Manifest {
+    files: Vec<RegistryFile>,
+}
+
+/// How a model folder compares with its file list
+#[derive(Clone, Debug, PartialEq)]
+pub enum Integrity {
+    /// No file list to compare against
+    Unknown,
+    Complete,
+    Incomplete {
+        missing: Vec<String>,
+        /// Present but smaller than expected
+        truncated: Vec<String>,
+    },
+}
+
+/// Partial download data that can be cleaned up
+#[derive(Clone, Debug)]
+pub struct OrphanedData {
+    pub path: PathBuf,
+    pub bytes: u64,
+    /// Registry model the data belongs to
+    pub model_id: String,
+    pub reason: String,
+}
+
+/// Record the files a download is about to fetch
+pub fn write_manifest(dir: &Path, files: &[RegistryFile]) -> Result<(), String> {
+    std::fs::create_dir_all(dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+    let manifest = Manifest { files: files.to_vec() };
+    let json = serde_json::to_string_pretty(&manifest).map_err(|e| e.to_string())?;
+    let path = dir.join(MANIFEST_FILENAME);
+    std::fs::write(&path, json).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+/// The file list recorded by the last download into `dir`
+pub fn read_manifest(dir: &Path) -> Option<Vec<RegistryFile>> {
+    let json = std::fs::read_to_string(dir.join(MANIFEST_FILENAME)).ok()?;
+    serde_json::from_str::<Manifest>(&json).ok().map(|m| m.files)
+}
+
+/// Compare a folder against the files it should hold
+pub fn check_files(dir: &Path, expected: &[RegistryFile]) -> Integrity {
+    if expected.is_empty() { return Integrity::Unknown; }
+    let mut missing = Vec::new();
+    let mut truncated = Vec::new();
+    for file in expected {
+        // Follows symlinks, which the HF cache uses in snapshots/
+        match std::fs::metadata(dir.join(&file.path)) {
+            Err(_) => missing.push(file.path.clone()),
+            Ok(meta) if file.size_bytes > 0 && meta.len() < file.size_bytes => truncated.push(file.path.clone()),
+            Ok(_) => {}
+        }
+    }
+    if missing.is_empty() && truncated.is_empty() {
+        Integrity::Complete
+    } else {
+        Integrity::Incomplete { missing, truncated }
+    }
+}
+
+/// Check a registry model against the registry's file list, or the one its
+/// last download recorded
+pub fn check_registry_model(model: &RegistryModel) -> Integrity {
+    let expanded = model.storage.expanded_path();
+    let dir = Path::new(&expanded);
+    if !model.storage.files.is_empty() {
+        return check_files(dir, &model.storage.files);
+    }
+    match read_manifest(dir) {
+        Some(files) => check_files(dir, &files),
+        None => Integrity::Unknown,
+    }
+}
+
+/// Whether any unfinished `.part` file is left under `dir`
+pub fn has_part_files(dir: &Path) -> bool {
+    !find_part_files(dir).is_empty()
+}
+
+/// Partial download data of registry models that aren't downloading now
+pub fn find_orphans(registry: &ModelRegistry) -> Vec<OrphanedData> {
+    let mut orphans = Vec::new();
+    let mut seen = HashSet::new();
+    for model in &registry.models {
+        let key = model.storage.expanded_path();
+        if model_state::download_status(&key) == DownloadStatus::Downloading { continue; }
+        let dir = PathBuf::from(&key);
+        if !dir.is_dir() { continue; }
+
+        if let Integrity::Incomplete { missing, truncated } = check_registry_model(model) {
+            // Another model may be installed in the same folder; only the
+            // truncated files can go then
+            let shared = registry.models.iter()
+                .any(|m| m.id != model.id && m.storage.expanded_path() == key);
+            if shared {
+                for file in &truncated {
+                    push_orphan(&mut orphans, &mut seen, dir.join(file), model, "truncated download");
+                }
+            } else if seen.insert(dir.clone()) {
+                orphans.push(OrphanedData {
+                    bytes: dir_size(&dir),
+                    path: dir.clone(),
+                    model_id: model.id.clone(),
+                    reason: format!("{} of its files missing or incomplete", missing.len() + truncated.len()),
+                });
+                continue;
+            }
+        }
+
+        let mut dirs = vec![dir];
+        dirs.extend(model.extra_sources.iter().map(|e| PathBuf::from(e.storage.expanded_path())));
+        for dir in dirs {
+            for part in find_part_files(&dir) {
+                push_orphan(&mut orphans, &mut seen, part, model, "unfinished download");
+            }
+        }
+    }
+    orphans
+}
+
+/// Trash (or delete) one piece of orphaned data
+pub fn delete_orphan(orphan: &OrphanedData, hard_delete: bool) -> Result<(), String> {
+    model_trash::remove_now(&orphan.path, hard_delete)?;
+    log::info!("Cleaned up {:?} ({} bytes) of {}", orphan.path, orphan.bytes, orphan.model_id);
+    Ok(())
+}
+
+fn push_orphan(orphans: &mut Vec<OrphanedData>, seen: &mut HashSet<PathBuf>, path: PathBuf, model: &RegistryModel, reason: &str) {
+    if !seen.insert(path.clone()) { return; }
+    let bytes = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+    orphans.push(OrphanedData { path, bytes, model_id: model.id.clone(), reason: reason.to_string() });
+}
+
+fn find_part_files(dir: &Path) -> Vec<PathBuf> {
+    let mut found = Vec::new();
+    let Ok(entries) = std::fs::read_dir(dir) else { return found };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Ok(file_type) = entry.file_type() else { continue };
+        if file_type.is_dir() {
+            found.extend(find_part_files(&path));
+        } else if entry.file_name().to_string_lossy().ends_with(PART_SUFFIX) {
+            found.push(path);
+        }
+    }
+    found
+}
+
+/// Total size of the files under `dir`, 0 if it doesn't exist
+pub fn dir_size(dir: &Path) -> u64 {
+    let Ok(entries) = std::fs::read_dir(dir) else { return 0 };
+    entries.flatten().map(|entry| match entry.file_type() {
+        Ok(t) if t.is_dir() => dir_size(&entry.path()),
+        Ok(_) => entry.metadata().map(|m| m.len()).unwrap_or(0),
+        Err(_) => 0,
+    }).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_files() {
+        let dir = std::env::temp_dir().join(format!("moly-integrity-test-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("transformer")).unwrap();
+        std::fs::write(dir.join("config.json"), "{}").unwrap();
+        std::fs::write(dir.join("transformer/model.safetensors"), [0u8; 10]).unwrap();
+        std::fs::write(dir.join("model.safetensors.part"), [0u8; 4]).unwrap();
+
+        let file = |path: &str, size_bytes: u64| RegistryFile { path: path.to_string(), size_bytes };
+        assert_eq!(check_files(&dir, &[]), Integrity::Unknown);
+        assert_eq!(check_files(&dir, &[file("config.json", 0), file("transformer/model.safetensors", 10)]), Integrity::Complete);
+        assert_eq!(
+            check_files(&dir, &[file("config.json", 0), file("transformer/model.safetensors", 20), file("model.safetensors", 8)]),
+            Integrity::Incomplete {
+                missing: vec!["model.safetensors".to_string()],
+                truncated: vec!["transformer/model.safetensors".to_string()],
+            },
+        );
+
+        write_manifest(&dir, &[file("config.json", 2)]).unwrap();
+        assert_eq!(read_manifest(&dir), Some(vec![file("config.json", 2)]));
+        assert_eq!(find_part_files(&dir), vec![dir.join("model.safetensors.part")]);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,220 @@
+//! The list behind the chat header's model picker
+//!
+//! The picker offers the downloaded local chat models and the enabled models
+//! of every provider, grouped by where they run, with the local group first.
+//! Typing narrows the list ([`ModelPicker::set_query`]), the arrow keys move
+//! the selection across groups ([`ModelPicker::step`]) and Return picks it. A
+//! local model that isn't loaded is picked by loading it.
+
+use crate::model_registry::{ModelRegistry, RegistryCategory};
+use crate::model_state::{self, DownloadStatus};
+use crate::ModelLoadPhase;
+
+/// Group of the models that run on this machine
+pub const LOCAL_GROUP: &str = "Local";
+
+/// A model the picker can switch the chat to
+#[derive(Clone, Debug, PartialEq)]
+pub enum PickerModel {
+    /// A provider's model, by bot id
+    Cloud { bot_id: String },
+    /// A downloaded registry model, by registry id
+    Local { model_id: String, phase: Option<ModelLoadPhase> },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PickerEntry {
+    pub name: String,
+    /// Provider name, or [`LOCAL_GROUP`]
+    pub group: String,
+    /// e.g. the download size of a local model
+    pub detail: String,
+    pub model: PickerModel,
+}
+
+impl PickerEntry {
+    /// A local model that has to be loaded before it can answer
+    pub fn needs_load(&self) -> bool {
+        matches!(&self.model, PickerModel::Local { phase, .. } if !matches!(phase, Some(ModelLoadPhase::Loaded | ModelLoadPhase::Loading)))
+    }
+
+    /// What the row says about the model's state
+    pub fn status(&self) -> &'static str {
+        match &self.model {
+            PickerModel::Local { phase: Some(ModelLoadPhase::Loaded), .. } => "Loaded",
+            PickerModel::Local { phase: Some(ModelLoadPhase::Loading), .. } => "Loading…",
+            _ => "",
+        }
+    }
+
+    fn matches(&self, terms: &[String]) -> bool {
+        let text = format!("{} {}", self.name, self.group).to_lowercase();
+        terms.iter().all(|t| text.contains(t.as_str()))
+    }
+}
+
+/// A line of the picker: a group heading or an entry
+#[derive(Clone, Debug, PartialEq)]
+pub enum PickerRow {
+    Group(String),
+    /// Index into [`ModelPicker::entries`]
+    Entry { index: usize, selected: bool },
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct ModelPicker {
+    entries: Vec<PickerEntry>,
+    /// Entries matching the query, in list order
+    shown: Vec<usize>,
+    /// Position in `shown`
+    selected: usize,
+}
+
+impl ModelPicker {
+    /// A picker over `entries`, kept in group order with [`LOCAL_GROUP`] first
+    pub fn new(mut entries: Vec<PickerEntry>) -> Self {
+        let mut groups: Vec<String> = vec![LOCAL_GROUP.to_string()];
+        for entry in &entries {
+            if !groups.contains(&entry.group) {
+                groups.push(entry.group.clone());
+            }
+        }
+        entries.sort_by_key(|e| groups.iter().position(|g| *g == e.group));
+        let shown = (0..entries.len()).collect();
+        Self { entries, shown, selected: 0 }
+    }
+
+    pub fn entries(&self) -> &[PickerEntry] {
+        &self.entries
+    }
+
+    /// Show the entries whose name or group contains every word of `query`,
+    /// selecting the first
+    pub fn set_query(&mut self, query: &str) {
+        let terms: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+        self.shown = (0..self.entries.len()).filter(|&i| self.entries[i].matches(&terms)).collect();
+        self.selected = 0;
+    }
+
+    pub fn match_count(&self) -> usize {
+        self.shown.len()
+    }
+
+    /// Move the selection down or up, wrapping around
+    pub fn step(&mut self, down: bool) {
+        let count = self.shown.len();
+        if count == 0 {
+            return;
+        }
+        self.selected = if down { (self.selected + 1) % count } else { (self.selected + count - 1) % count };
+    }
+
+    /// Select an entry by its index in [`Self::entries`], as when clicked
+    pub fn select(&mut self, index: usize) {
+        if let Some(position) = self.shown.iter().position(|&i| i == index) {
+            self.selected = position;
+        }
+    }
+
+    pub fn selected(&self) -> Option<&PickerEntry> {
+        self.shown.get(self.selected).map(|&i| &self.entries[i])
+    }
+
+    /// At most `limit` rows around the selection, each group headed by its
+    /// name
+    pub fn rows(&self, limit: usize) -> Vec<PickerRow> {
+        let mut rows = Vec::new();
+        let mut selected_row = 0;
+        for (position, &index) in self.shown.iter().enumerate() {
+            let group = &self.entries[index].group;
+            if rows.is_empty() || position > 0 && self.entries[self.shown[position - 1]].group != *group {
+                rows.push(PickerRow::Group(group.clone()));
+            }
+            if position == self.selected {
+                selected_row = rows.len();
+            }
+            rows.push(PickerRow::Entry { index, selected: position == self.selected });
+        }
+        let start = (selected_row + 1).saturating_sub(limit);
+        rows.into_iter().skip(start).take(limit).collect()
+    }
+}
+
+/// The downloaded local models a chat can use, with their load state
+pub fn local_entries(registry: &ModelRegistry) -> Vec<PickerEntry> {
+    registry
+        .models
+        .iter()
+        .filter(|m| matches!(m.category, RegistryCategory::Llm | RegistryCategory::Vlm) && !m.deprecated)
+        .filter(|m| model_state::registry_download_status(m) == DownloadStatus::Downloaded)
+        .map(|m| PickerEntry {
+            name: m.name.clone(),
+            group: LOCAL_GROUP.to_string(),
+            detail: m.storage.size_display.clone(),
+            model: PickerModel::Local { model_id: m.id.clone(), phase: model_state::load_phase(&m.id) },
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cloud(name: &str, group: &str) -> PickerEntry {
+        PickerEntry {
+            name: name.to_string(),
+            group: group.to_string(),
+            detail: String::new(),
+            model: PickerModel::Cloud { bot_id: name.to_string() },
+        }
+    }
+
+    fn local(name: &str, phase: Option<ModelLoadPhase>) -> PickerEntry {
+        PickerEntry {
+            name: name.to_string(),
+            group: LOCAL_GROUP.to_string(),
+            detail: "4.6 GB".to_string(),
+            model: PickerModel::Local { model_id: name.to_lowercase(), phase },
+        }
+    }
+
+    #[test]
+    fn test_groups_filter_and_step() {
+        let mut picker = ModelPicker::new(vec![
+            cloud("gpt-4o", "OpenAI"),
+            local("Qwen3 8B", Some(ModelLoadPhase::Loaded)),
+            cloud("deepseek-chat", "DeepSeek"),
+            cloud("gpt-4o-mini", "OpenAI"),
+            local("GLM 4", None),
+        ]);
+        let names: Vec<&str> = picker.entries().iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["Qwen3 8B", "GLM 4", "gpt-4o", "gpt-4o-mini", "deepseek-chat"]);
+        assert_eq!(picker.rows(3), [
+            PickerRow::Group("Local".to_string()),
+            PickerRow::Entry { index: 0, selected: true },
+            PickerRow::Entry { index: 1, selected: false },
+        ]);
+
+        picker.step(false);
+        assert_eq!(picker.selected().unwrap().name, "deepseek-chat");
+        assert_eq!(picker.rows(2), [PickerRow::Group("DeepSeek".to_string()), PickerRow::Entry { index: 4, selected: true }]);
+
+        picker.set_query("openai MINI");
+        assert_eq!(picker.match_count(), 1);
+        assert_eq!(picker.selected().unwrap().name, "gpt-4o-mini");
+        picker.set_query("glm");
+        assert!(picker.selected().unwrap().needs_load());
+        picker.set_query("claude");
+        assert_eq!(picker.selected(), None);
+        assert!(picker.rows(8).is_empty());
+    }
+
+    #[test]
+    fn test_local_status() {
+        assert!(!local("Qwen3 8B", Some(ModelLoadPhase::Loaded)).needs_load());
+        assert!(!local("Qwen3 8B", Some(ModelLoadPhase::Loading)).needs_load());
+        assert!(local("Qwen3 8B", Some(ModelLoadPhase::Failed("out of memory".to_string()))).needs_load());
+        assert_eq!(local("Qwen3 8B", Some(ModelLoadPhase::Loading)).status(), "Loading…");
+        assert!(!cloud("gpt-4o", "OpenAI").needs_load());
+    }
+}
@@ -8,6 +8,9 @@
 
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
+use std::sync::mpsc;
+
+use crate::chat_template::ChatTemplate;
 
 // ─── Category ────────────────────────────────────────────────────────────────
 
@@ -27,6 +30,10 @@ pub enum RegistryCategory {
     ImageGen,
     /// Video Generation — text/image in, video out
     VideoGen,
+    /// Music / Audio Generation — text in, audio out
+    MusicGen,
+    /// Rerankers — query + passages in, relevance scores out
+    Reranker,
 }
 
 impl RegistryCategory {
@@ -38,6 +45,8 @@ impl RegistryCategory {
             Self::Tts => "TTS",
             Self::ImageGen => "Image",
             Self::VideoGen => "Video",
+            Self::MusicGen => "Music",
+            Self::Reranker => "Rerank",
         }
     }
 
@@ -50,6 +59,8 @@ impl RegistryCategory {
             Self::Tts => "#f59e0b",      // amber
             Self::ImageGen => "#ec4899", // pink
             Self::VideoGen => "#0ea5e9", // sky blue
+            Self::MusicGen => "#f97316", // orange
+            Self::Reranker => "#14b8a6", // teal
         }
     }
 }
@@ -63,6 +74,9 @@ impl RegistryCategory {
 pub enum ApiType {
     /// POST /v1/chat/completions  (LLM + VLM)
     ChatCompletions,
+    /// POST /v1/completions  (LLMs that take a prompt formatted with their
+    /// chat template, see [`crate::chat_template`])
+    Completions,
     /// POST /v1/audio/transcriptions  (ASR)
     AudioTranscription,
     /// POST /v1/audio/speech  (TTS)
@@ -71,6 +85,10 @@ pub enum ApiType {
     ImageGeneration,
     /// POST /v1/videos/generations  (Video Gen — coming soon)
     VideoGeneration,
+    /// POST /v1/audio/generations  (Music Gen)
+    MusicGeneration,
+    /// POST /v1/rerank  (Reranker)
+    Rerank,
 }
 
 // ─── Panel Type ───────────────────────────────────────────────────────────────
@@ -88,6 +106,9 @@ pub enum PanelType {
     /// Image editing: takes a reference image + text prompt → edited image
     ImageEdit,
     VideoGeneration,
+    MusicGeneration,
+    /// Query + candidate passages → passages ranked by relevance
+    Rerank,
 }
 
 // ─── Source ───────────────────────────────────────────────────────────────────
@@ -142,6 +163,19 @@ pub struct RegistryStorage {
     /// Human-readable size string (e.g. "~8 GB")
     #[serde(default)]
     pub size_display: String,
+    /// Files the model needs, relative to `local_path`. When empty, the list
+    /// recorded by the last download is used instead.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub files: Vec<RegistryFile>,
+}
+
+/// One file of a model, relative to its storage path
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RegistryFile {
+    pub path: String,
+    /// Expected size in bytes (0 = unknown)
+    #[serde(default)]
+    pub size_bytes: u64,
 }
 
 impl RegistryStorage {
@@ -173,12 +207,46 @@ pub struct RegistryRuntime {
     /// Quantization format used (e.g. "8bit", "4bit", "fp16", "bf16")
     #[serde(skip_serializing_if = "Option::is_none")]
     pub quantization: Option<String>,
+    /// Prompt format for `completions` models; guessed from the model id
+    /// when missing
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chat_template: Option<ChatTemplate>,
+    /// Oldest ominix-api version that can load the model (e.g. "0.9.0")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_runtime_version: Option<String>,
 }
 
 fn default_true() -> bool {
     true
 }
 
+/// An ominix-api version, as reported by its `/v1/version` endpoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct RuntimeVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl RuntimeVersion {
+    /// Parse "1.2.3", "v1.2" or "1.2.3-beta"; missing parts are zero
+    pub fn parse(s: &str) -> Option<Self> {
+        let s = s.trim().trim_start_matches(['v', 'V']);
+        let core = s.split(['-', '+', ' ']).next().unwrap_or("");
+        let mut parts = core.split('.').map(|p| p.parse::<u32>().ok());
+        let major = parts.next()??;
+        let minor = parts.next().unwrap_or(Some(0))?;
+        let patch = parts.next().unwrap_or(Some(0))?;
+        Some(Self { major, minor, patch })
+    }
+}
+
+impl std::fmt::Display for RuntimeVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
 // ─── UI Hints ─────────────────────────────────────────────────────────────────
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -239,6 +307,12 @@ pub struct RegistryModel {
     /// Downloaded sequentially after the primary source.
     #[serde(default)]
     pub extra_sources: Vec<ExtraModelSource>,
+    /// No longer maintained; still listed so existing downloads keep working
+    #[serde(default)]
+    pub deprecated: bool,
+    /// Id of the model to use instead of a deprecated one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replaced_by: Option<String>,
 }
 
 impl RegistryModel {
@@ -246,6 +320,28 @@ impl RegistryModel {
     pub fn accent_color(&self) -> &str {
         self.ui.color.as_deref().unwrap_or_else(|| self.category.color())
     }
+
+    /// Why the `installed` runtime can't load this model, or `None` when it
+    /// can. An unknown installed version gets the benefit of the doubt.
+    pub fn runtime_requirement(&self, installed: Option<RuntimeVersion>) -> Option<String> {
+        let min = RuntimeVersion::parse(self.runtime.min_runtime_version.as_deref()?)?;
+        let installed = installed?;
+        (installed < min).then(|| {
+            format!("Requires OminiX runtime \u{2265} {} (installed: {}). Update ominix-api to load this model.", min, installed)
+        })
+    }
+
+    /// e.g. "Deprecated — use Qwen3 8B instead"; the replacement's name is
+    /// looked up in `registry`
+    pub fn deprecation_notice(&self, registry: &ModelRegistry) -> Option<String> {
+        if !self.deprecated {
+            return None;
+        }
+        Some(match self.replaced_by.as_deref() {
+            Some(id) => format!("Deprecated \u{2014} use {} instead", registry.get(id).map_or(id, |m| m.name.as_str())),
+            None => "Deprecated \u{2014} no longer maintained".to_string(),
+        })
+    }
 }
 
 // ─── Registry ─────────────────────────────────────────────────────────────────
@@ -257,7 +353,8 @@ impl RegistryModel {
 /// 2. User override at `~/.ominix/models_registry.json` (merged on top)
 ///
 /// Server updates are fetched in the background on launch and written to
-/// the user override file so they take effect on the next startup.
+/// the user override file; the Model Hub also merges them into its open
+/// list as soon as they arrive.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ModelRegistry {
     /// Semver string (e.g. "1.0.0")
@@ -269,11 +366,16 @@ pub struct ModelRegistry {
 const BUNDLED_REGISTRY: &str = include_str!("models_registry.json");
 
 impl ModelRegistry {
+    /// The registry compiled into the binary, without the user override
+    pub fn bundled() -> Self {
+        serde_json::from_str(BUNDLED_REGISTRY)
+            .expect("bundled models_registry.json is invalid — this is a compile-time bug")
+    }
+
     /// Load registry: bundled defaults merged with user override.
     pub fn load() -> Self {
         // 1. Parse bundled JSON (must succeed — it's compiled-in)
-        let mut registry: ModelRegistry = serde_json::from_str(BUNDLED_REGISTRY)
-            .expect("bundled models_registry.json is invalid — this is a compile-time bug");
+        let mut registry = Self::bundled();
 
         // 2. Merge user override if present
         if let Some(override_path) = Self::override_path() {
@@ -303,25 +405,38 @@ impl ModelRegistry {
 
     /// Merge another registry on top: existing models are updated,
     /// new models are appended.  The caller's version wins.
-    pub fn merge(&mut self, other: ModelRegistry) {
+    /// Returns the ids of the models that were added.
+    pub fn merge(&mut self, other: ModelRegistry) -> Vec<String> {
+        let mut added = Vec::new();
         for incoming in other.models {
             if let Some(existing) = self.models.iter_mut().find(|m| m.id == incoming.id) {
                 *existing = incoming;
             } else {
+                added.push(incoming.id.clone());
                 self.models.push(incoming);
             }
         }
+        added
     }
 
     /// Fetch an updated registry from the OminiX server in a background thread.
-    /// On success the result is saved to `~/.ominix/models_registry.json` and
-    /// will be picked up the next time `ModelRegistry::load()` is called.
-    pub fn fetch_updates_async() {
-        std::thread::spawn(|| {
+    /// On success the result is saved to `~/.ominix/models_registry.json`, so
+    /// `ModelRegistry::load()` picks it up next time, and sent on the returned
+    /// channel for screens to [`merge`](Self::merge) right away.
+    pub fn fetch_updates_async() -> mpsc::Receiver<ModelRegistry> {
+        let (tx, rx) = mpsc::channel();
+
+        // No threads or blocking HTTP in the browser build; the bundled
+        // registry is used as is
+        #[cfg(target_arch = "wasm32")]
+        drop(tx);
+
+        #[cfg(not(target_arch = "wasm32"))]
+        std::thread::spawn(move || {
             const REGISTRY_URL: &str =
                 "https://registry.ominix.ai/models_registry.json";
 
-            let client = reqwest::blocking::Client::builder()
+            let client = crate::network::blocking_client()
                 .timeout(std::time::Duration::from_secs(10))
                 .build();
 
@@ -345,6 +460,7 @@ impl ModelRegistry {
                                     registry.models.len()
                                 );
                             }
+                            let _ = tx.send(registry);
                         }
                         Err(e) => {
                             log::debug!("ModelRegistry fetch: failed to parse JSON: {}", e);
@@ -362,6 +478,8 @@ impl ModelRegistry {
                 }
             }
         });
+
+        rx
     }
 
     /// Save this registry to the user override file.
@@ -382,7 +500,7 @@ impl ModelRegistry {
     }
 
     /// `~/.ominix/models_registry.json`
-    fn override_path() -> Option<PathBuf> {
+    pub(crate) fn override_path() -> Option<PathBuf> {
         dirs::home_dir().map(|h| h.join(".ominix").join("models_registry.json"))
     }
 
@@ -409,10 +527,47 @@ impl ModelRegistry {
 // ─── Helpers ──────────────────────────────────────────────────────────────────
 
 fn expand_tilde(path: &str) -> String {
-    if path.starts_with("~/") {
-        if let Some(home) = dirs::home_dir() {
-            return home.join(&path[2..]).to_string_lossy().to_string();
-        }
+    crate::download_settings::models_path(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_reports_added_models() {
+        let mut registry = ModelRegistry::bundled();
+        let count = registry.models.len();
+        let mut update = ModelRegistry { version: registry.version.clone(), models: vec![registry.models[0].clone()] };
+        update.models[0].name = "Renamed".to_string();
+        let mut added = registry.models[0].clone();
+        added.id = "new-model".to_string();
+        update.models.push(added);
+
+        assert_eq!(registry.merge(update), vec!["new-model".to_string()]);
+        assert_eq!(registry.models.len(), count + 1);
+        assert_eq!(registry.models[0].name, "Renamed");
+    }
+
+    #[test]
+    fn test_runtime_requirement() {
+        assert_eq!(RuntimeVersion::parse("v1.2"), Some(RuntimeVersion { major: 1, minor: 2, patch: 0 }));
+        assert_eq!(RuntimeVersion::parse("0.10.1-beta").unwrap().to_string(), "0.10.1");
+        assert!(RuntimeVersion::parse("0.10.1").unwrap() > RuntimeVersion::parse("0.9.9").unwrap());
+        assert_eq!(RuntimeVersion::parse("dev"), None);
+
+        let registry = ModelRegistry::bundled();
+        let mut model = registry.models[0].clone();
+        assert_eq!(model.runtime_requirement(RuntimeVersion::parse("0.1.0")), None);
+        model.runtime.min_runtime_version = Some("0.9.0".to_string());
+        assert_eq!(model.runtime_requirement(None), None);
+        assert_eq!(model.runtime_requirement(RuntimeVersion::parse("0.9.0")), None);
+        let message = model.runtime_requirement(RuntimeVersion::parse("0.8.2")).unwrap();
+        assert!(message.starts_with("Requires OminiX runtime \u{2265} 0.9.0 (installed: 0.8.2)"));
+
+        assert_eq!(model.deprecation_notice(&registry), None);
+        model.deprecated = true;
+        model.replaced_by = Some(registry.models[1].id.clone());
+        assert_eq!(model.deprecation_notice(&registry).unwrap(), format!("Deprecated \u{2014} use {} instead", registry.models[1].name));
     }
-    path.to_string()
 }
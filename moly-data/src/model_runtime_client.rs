@@ -3,11 +3,15 @@
 //! All requests go to localhost:8080 (ominix-api) and follow the API contracts:
 //!
 //!   GET  /v1/models               → list + status of every loaded model
+//!   GET  /v1/version              → version of the running server
 //!   POST /v1/models/{id}/load     → load a model into memory (blocks until done)
 //!   POST /v1/models/{id}/unload   → free the model from memory
 
 use serde::Deserialize;
 use std::sync::Mutex;
+
+use crate::model_registry::{RegistryModel, RuntimeVersion};
+use crate::runtime_options::RuntimeOptions;
 use std::sync::atomic::{AtomicI32, Ordering};
 
 /// Handle to the ominix-api child process we launched (None if we didn't launch it).
@@ -17,6 +21,15 @@ static SERVER_CHILD: Mutex<Option<std::process::Child>> = Mutex::new(None);
 /// Stored as AtomicI32 so it can be read safely from signal handlers.
 static SERVER_PID: AtomicI32 = AtomicI32::new(0);
 
+/// Version the server last reported, for checks that can't wait on a request.
+static INSTALLED_VERSION: Mutex<Option<RuntimeVersion>> = Mutex::new(None);
+
+/// Version of the running ominix-api, once [`ModelRuntimeClient::version`]
+/// has succeeded; `None` before that or when the server doesn't report it.
+pub fn installed_runtime_version() -> Option<RuntimeVersion> {
+    INSTALLED_VERSION.lock().ok().and_then(|v| *v)
+}
+
 /// Record the PID of an externally-running ominix-api server.
 pub fn set_server_pid(pid: i32) {
     SERVER_PID.store(pid, Ordering::Relaxed);
@@ -105,6 +118,11 @@ struct ModelEntry {
     memory_gb: Option<f32>,
 }
 
+#[derive(Deserialize)]
+struct VersionResponse {
+    version: String,
+}
+
 // ─── Auto-launch helpers ──────────────────────────────────────────────────────
 
 /// Locate the `ominix-api` binary by searching in order:
@@ -175,6 +193,17 @@ pub fn ensure_server_running() -> Result<(), String> {
         return Ok(());
     }
 
+    // Another program on the port would get the requests meant for the
+    // server we launch
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        use crate::runtime_diagnostics::{self, PortState};
+        let diagnosis = runtime_diagnostics::diagnose();
+        if matches!(diagnosis.state, PortState::OtherServer { .. } | PortState::NotHttp { .. }) {
+            return Err(format!("{} Model Hub → Diagnostics suggests fixes.", diagnosis.summary()));
+        }
+    }
+
     // Find the binary
     let binary = find_api_binary().ok_or_else(|| {
         "ominix-api binary not found. Install it or set OMINIX_API_BIN.".to_string()
@@ -182,9 +211,19 @@ pub fn ensure_server_running() -> Result<(), String> {
 
     log::info!("Auto-starting ominix-api from {}", binary.display());
 
+    // Keep the server's output for the Model Hub's runtime log
+    let (stdout, stderr) = match crate::runtime_log::open_for_append()
+        .and_then(|file| file.try_clone().map(|copy| (file, copy)).map_err(|e| e.to_string()))
+    {
+        Ok((out, err)) => (std::process::Stdio::from(out), std::process::Stdio::from(err)),
+        Err(e) => {
+            log::warn!("Runtime log not written: {}", e);
+            (std::process::Stdio::null(), std::process::Stdio::null())
+        }
+    };
     let child = std::process::Command::new(&binary)
-        .stdout(std::process::Stdio::null())
-        .stderr(std::process::Stdio::null())
+        .stdout(stdout)
+        .stderr(stderr)
         .spawn()
         .map_err(|e| format!("Failed to launch ominix-api: {}", e))?;
 
@@ -209,6 +248,17 @@ pub fn ensure_server_running() -> Result<(), String> {
 
 // ─── Client ───────────────────────────────────────────────────────────────────
 
+const DEFAULT_RUNTIME_URL: &str = "http://localhost:8080";
+
+/// Root of the ominix-api server the `localhost()` clients talk to.
+/// `OMINIX_API_URL` overrides it, e.g. to run against a mock runtime.
+pub fn runtime_url() -> String {
+    std::env::var("OMINIX_API_URL")
+        .ok()
+        .filter(|url| !url.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_RUNTIME_URL.to_string())
+}
+
 /// Thin blocking HTTP client for the ominix-api runtime endpoints.
 ///
 /// All calls block the calling thread — run them inside `std::thread::spawn`.
@@ -226,14 +276,17 @@ impl ModelRuntimeClient {
     }
 
     pub fn localhost() -> Self {
-        Self::new("http://localhost:8080")
+        Self::new(runtime_url())
     }
+}
 
+#[cfg(not(target_arch = "wasm32"))]
+impl ModelRuntimeClient {
     // ── Liveness check ───────────────────────────────────────────────────────
 
     /// Returns `true` if the server responds to `GET /v1/models` within 2 s.
     pub fn is_alive(&self) -> bool {
-        let Ok(client) = reqwest::blocking::Client::builder()
+        let Ok(client) = crate::network::blocking_client()
             .timeout(std::time::Duration::from_secs(2))
             .build()
         else { return false };
@@ -264,24 +317,55 @@ impl ModelRuntimeClient {
         }).collect())
     }
 
+    // ── Version ──────────────────────────────────────────────────────────────
+
+    /// `GET /v1/version` — the server's version, also remembered for
+    /// [`installed_runtime_version`]. Servers older than the endpoint answer
+    /// 404, which leaves the version unknown.
+    pub fn version(&self) -> Result<RuntimeVersion, String> {
+        let client = self.client(5)?;
+        let url    = format!("{}/v1/version", self.base_url);
+        let resp   = client.get(&url).send().map_err(|e| e.to_string())?;
+
+        if !resp.status().is_success() {
+            return Err(format!("HTTP {}", resp.status()));
+        }
+
+        let body: VersionResponse = resp.json().map_err(|e| e.to_string())?;
+        let version = RuntimeVersion::parse(&body.version)
+            .ok_or_else(|| format!("Unrecognised ominix-api version \"{}\"", body.version))?;
+        if let Ok(mut installed) = INSTALLED_VERSION.lock() {
+            *installed = Some(version);
+        }
+        Ok(version)
+    }
+
+    /// `Err` with a "requires runtime ≥ X" message when the server is too
+    /// old for `model`; a server that doesn't report its version passes
+    pub fn check_compatible(&self, model: &RegistryModel) -> Result<(), String> {
+        match model.runtime_requirement(self.version().ok()) {
+            Some(requirement) => Err(requirement),
+            None => Ok(()),
+        }
+    }
+
     // ── Load ──────────────────────────────────────────────────────────────────
 
     /// `POST /v1/models/load` — blocks until the model is ready.
     /// Large models may take several minutes.
     /// `model_type`: "llm", "vlm", "asr", "tts", or "image"
     pub fn load_model(&self, api_model_id: &str, model_type: &str) -> Result<(), String> {
+        self.load_model_with(api_model_id, model_type, &RuntimeOptions::default())
+    }
+
+    /// Like [`Self::load_model`], with the options the model was given in the Hub
+    pub fn load_model_with(&self, api_model_id: &str, model_type: &str, options: &RuntimeOptions) -> Result<(), String> {
         let client = self.client(600)?;          // 10-minute ceiling
         let url    = format!("{}/v1/models/load", self.base_url);
-        let body   = serde_json::json!({ "model": api_model_id, "model_type": model_type });
-        let resp   = client.post(&url).json(&body).send().map_err(|e| e.to_string())?;
-
-        if resp.status().is_success() {
-            Ok(())
-        } else {
-            let status = resp.status();
-            let text   = resp.text().unwrap_or_default();
-            Err(format!("HTTP {} — {}", status, text.trim()))
-        }
+        let mut body = options.to_json();
+        body.insert("model".to_string(), api_model_id.into());
+        body.insert("model_type".to_string(), model_type.into());
+        self.post(&client, &url, &serde_json::Value::Object(body))
     }
 
     // ── Unload ────────────────────────────────────────────────────────────────
@@ -292,23 +376,69 @@ impl ModelRuntimeClient {
         let client = self.client(30)?;
         let url    = format!("{}/v1/models/unload", self.base_url);
         let body   = serde_json::json!({ "model_type": model_type });
-        let resp   = client.post(&url).json(&body).send().map_err(|e| e.to_string())?;
+        self.post(&client, &url, &body)
+    }
 
-        if resp.status().is_success() {
+    // ── Internal ─────────────────────────────────────────────────────────────
+
+    /// POST `body` and check the status, recording the request in [`crate::request_log`]
+    fn post(&self, client: &reqwest::blocking::Client, url: &str, body: &serde_json::Value) -> Result<(), String> {
+        let bytes   = body.to_string().into_bytes();
+        let pending = crate::request_log::start(crate::request_log::LOCAL_RUNTIME, "POST", url, Some(&bytes));
+        let resp    = match client.post(url).header(reqwest::header::CONTENT_TYPE, "application/json").body(bytes).send() {
+            Ok(resp) => resp,
+            Err(e) => {
+                pending.fail(None, &e.to_string());
+                return Err(e.to_string());
+            }
+        };
+        let status = resp.status();
+        let text   = resp.text().unwrap_or_default();
+        pending.finish(status.as_u16(), text.as_bytes());
+
+        if status.is_success() {
             Ok(())
         } else {
-            let status = resp.status();
-            let text   = resp.text().unwrap_or_default();
             Err(format!("HTTP {} — {}", status, text.trim()))
         }
     }
 
-    // ── Internal ─────────────────────────────────────────────────────────────
-
     fn client(&self, timeout_secs: u64) -> Result<reqwest::blocking::Client, String> {
-        reqwest::blocking::Client::builder()
+        crate::network::blocking_client()
             .timeout(std::time::Duration::from_secs(timeout_secs))
             .build()
             .map_err(|e| e.to_string())
     }
 }
+
+/// The browser build has no runtime to talk to
+#[cfg(target_arch = "wasm32")]
+impl ModelRuntimeClient {
+    pub fn is_alive(&self) -> bool {
+        false
+    }
+
+    pub fn list_models(&self) -> Result<Vec<ServerModelInfo>, String> {
+        Err(crate::platform::NO_LOCAL_RUNTIME.to_string())
+    }
+
+    pub fn version(&self) -> Result<RuntimeVersion, String> {
+        Err(crate::platform::NO_LOCAL_RUNTIME.to_string())
+    }
+
+    pub fn check_compatible(&self, _model: &RegistryModel) -> Result<(), String> {
+        Err(crate::platform::NO_LOCAL_RUNTIME.to_string())
+    }
+
+    pub fn load_model(&self, _api_model_id: &str, _model_type: &str) -> Result<(), String> {
+        Err(crate::platform::NO_LOCAL_RUNTIME.to_string())
+    }
+
+    pub fn load_model_with(&self, _api_model_id: &str, _model_type: &str, _options: &RuntimeOptions) -> Result<(), String> {
+        Err(crate::platform::NO_LOCAL_RUNTIME.to_string())
+    }
+
+    pub fn unload_model(&self, _model_type: &str) -> Result<(), String> {
+        Err(crate::platform::NO_LOCAL_RUNTIME.to_string())
+    }
+}
@@ -0,0 +1,135 @@
+//! Model Hub search
+//!
+//! [`ModelSearchIndex`] is built once per registry: models grouped by
+//! category in registry order, plus one lowercased haystack per model (name,
+//! description and tags). [`ModelSearch`] keeps the results of the last
+//! query, so typing one more character only rechecks the models that
+//! matched before instead of rescanning the registry.
+
+use crate::model_registry::{RegistryCategory, RegistryModel};
+
+/// Categories in the order the hub lists them
+pub const HUB_CATEGORIES: [RegistryCategory; 8] = [
+    RegistryCategory::Llm, RegistryCategory::Vlm, RegistryCategory::Asr,
+    RegistryCategory::Tts, RegistryCategory::ImageGen, RegistryCategory::VideoGen,
+    RegistryCategory::MusicGen, RegistryCategory::Reranker,
+];
+
+/// Per-category model lists and lowercased search text
+#[derive(Debug, Clone, Default)]
+pub struct ModelSearchIndex {
+    /// Registry indices of each entry of [`HUB_CATEGORIES`]
+    by_category: Vec<Vec<usize>>,
+    /// Lowercased "name\ndescription\ntag\ntag…", by registry index
+    haystacks: Vec<String>,
+}
+
+impl ModelSearchIndex {
+    pub fn build(models: &[RegistryModel]) -> Self {
+        let by_category = HUB_CATEGORIES.iter()
+            .map(|cat| models.iter().enumerate()
+                .filter(|(_, m)| m.category == *cat)
+                .map(|(i, _)| i)
+                .collect())
+            .collect();
+        let haystacks = models.iter()
+            .map(|m| {
+                let mut text = format!("{}\n{}", m.name, m.description);
+                for tag in &m.tags {
+                    text.push('\n');
+                    text.push_str(tag);
+                }
+                text.to_lowercase()
+            })
+            .collect();
+        Self { by_category, haystacks }
+    }
+
+    /// Registry indices of a category, in registry order
+    pub fn category(&self, category: RegistryCategory) -> &[usize] {
+        HUB_CATEGORIES.iter().position(|c| *c == category)
+            .and_then(|i| self.by_category.get(i))
+            .map_or(&[], |v| v.as_slice())
+    }
+
+    /// Whether the model matches an already lowercased query
+    pub fn matches(&self, model: usize, query: &str) -> bool {
+        query.is_empty() || self.haystacks.get(model).map_or(false, |h| h.contains(query))
+    }
+}
+
+/// A search over an index that narrows its previous results while the query
+/// only grows
+#[derive(Debug, Clone, Default)]
+pub struct ModelSearch {
+    index: ModelSearchIndex,
+    query: String,
+    /// Matches of `query` per entry of [`HUB_CATEGORIES`]
+    results: Vec<Vec<usize>>,
+}
+
+impl ModelSearch {
+    pub fn new(index: ModelSearchIndex) -> Self {
+        let results = index.by_category.clone();
+        Self { index, query: String::new(), results }
+    }
+
+    /// Run `query` (case-insensitive). Returns whether the results changed.
+    pub fn set_query(&mut self, query: &str) -> bool {
+        let query = query.trim().to_lowercase();
+        if query == self.query { return false; }
+
+        if !self.query.is_empty() && query.contains(self.query.as_str()) {
+            // Anything matching the longer query matched the shorter one
+            for results in &mut self.results {
+                results.retain(|&i| self.index.matches(i, &query));
+            }
+        } else {
+            self.results = self.index.by_category.iter()
+                .map(|models| models.iter().copied().filter(|&i| self.index.matches(i, &query)).collect())
+                .collect();
+        }
+        self.query = query;
+        true
+    }
+
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    /// Matches in a category, in registry order
+    pub fn results(&self, category: RegistryCategory) -> &[usize] {
+        HUB_CATEGORIES.iter().position(|c| *c == category)
+            .and_then(|i| self.results.get(i))
+            .map_or(&[], |v| v.as_slice())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model_registry::ModelRegistry;
+
+    #[test]
+    fn test_incremental_search_matches_full_scan() {
+        let registry = ModelRegistry::bundled();
+        let index = ModelSearchIndex::build(&registry.models);
+        let mut search = ModelSearch::new(index.clone());
+
+        for query in ["q", "qw", "qwen", "qwen3", "qwen", "", "Whisper", "zzz-no-match"] {
+            search.set_query(query);
+            let q = query.to_lowercase();
+            for cat in HUB_CATEGORIES {
+                let expected: Vec<usize> = registry.models.iter().enumerate()
+                    .filter(|(_, m)| m.category == cat)
+                    .filter(|(_, m)| q.is_empty()
+                        || m.name.to_lowercase().contains(&q)
+                        || m.description.to_lowercase().contains(&q)
+                        || m.tags.iter().any(|t| t.to_lowercase().contains(&q)))
+                    .map(|(i, _)| i)
+                    .collect();
+                assert_eq!(search.results(cat), expected.as_slice(), "query {:?}, {:?}", query, cat);
+            }
+        }
+    }
+}
@@ -0,0 +1,225 @@
+//! Shared model state — one view of what is on disk, downloading and loaded
+//!
+//! The Model Hub (registry models), Local Models (V2 config) and Models
+//! (Moly server files) screens used to track this separately, so a download
+//! finished in one screen stayed "Not Downloaded" in the others until a manual
+//! rescan. They now all read from and write to this service.
+//!
+//! Download state is keyed by the model's expanded storage path, which is the
+//! one thing the registry and the V2 config agree on; Moly server files use
+//! their file id. Load state is keyed by registry id and mirrored from
+//! [`StoreEvent::ModelLoadStateChanged`] by [`Store::handle_event`].
+//!
+//! Every change made through [`set_download_status`] emits
+//! [`StoreEvent::ModelStateChanged`] so other screens can refresh:
+//!
+//! ```rust,ignore
+//! if let Event::Actions(actions) = event {
+//!     for key in StoreEvent::model_state_changes(actions) {
+//!         self.refresh_model_at(cx, &key);
+//!     }
+//! }
+//! ```
+//!
+//! [`Store::handle_event`]: crate::store::Store::handle_event
+
+use makepad_widgets::Cx;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{OnceLock, RwLock};
+
+use crate::local_models::{LocalModelV2, ModelState};
+use crate::model_integrity::{self, Integrity};
+use crate::model_registry::RegistryModel;
+use crate::store::{ModelLoadPhase, StoreEvent};
+
+/// Whether a model's files are present
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub enum DownloadStatus {
+    #[default]
+    NotDownloaded,
+    Downloading,
+    /// Some files present (interrupted download)
+    Partial,
+    Downloaded,
+    Error,
+}
+
+impl DownloadStatus {
+    pub fn from_local_state(state: ModelState) -> Self {
+        match state {
+            ModelState::NotAvailable => Self::NotDownloaded,
+            ModelState::Downloading | ModelState::Verifying => Self::Downloading,
+            ModelState::Ready => Self::Downloaded,
+            ModelState::Partial => Self::Partial,
+            ModelState::Error => Self::Error,
+        }
+    }
+}
+
+#[derive(Default)]
+struct ModelStates {
+    downloads: HashMap<String, DownloadStatus>,
+    loads: HashMap<String, ModelLoadPhase>,
+}
+
+fn states() -> &'static RwLock<ModelStates> {
+    static STATES: OnceLock<RwLock<ModelStates>> = OnceLock::new();
+    STATES.get_or_init(Default::default)
+}
+
+/// Last known download status for a storage path (or Moly file id)
+pub fn download_status(key: &str) -> DownloadStatus {
+    states().read().ok()
+        .and_then(|s| s.downloads.get(key).copied())
+        .unwrap_or_default()
+}
+
+/// Download status of a registry model, scanning its files on first use
+pub fn registry_download_status(model: &RegistryModel) -> DownloadStatus {
+    let key = model.storage.expanded_path();
+    let known = states().read().ok().and_then(|s| s.downloads.get(&key).copied());
+    known.unwrap_or_else(|| scan_registry_model(model))
+}
+
+/// Change a download status and tell the other screens
+pub fn set_download_status(cx: &mut Cx, key: &str, status: DownloadStatus) {
+    if record_download(key, status, true) {
+        cx.action(StoreEvent::ModelStateChanged { key: key.to_string() });
+    }
+}
+
+/// Check a registry model's files on disk and record the result. A download
+/// in progress is left alone.
+pub fn scan_registry_model(model: &RegistryModel) -> DownloadStatus {
+    let key = model.storage.expanded_path();
+    record_download(&key, scan_registry_files(model), false);
+    download_status(&key)
+}
+
+/// Record a V2 local model's scanned state. A download in progress is left
+/// alone.
+pub fn record_local_model(model: &LocalModelV2) {
+    record_download(&model.expanded_path(), DownloadStatus::from_local_state(model.status.state), false);
+}
+
+/// Last known load phase of a registry model
+pub fn load_phase(model_id: &str) -> Option<ModelLoadPhase> {
+    states().read().ok().and_then(|s| s.loads.get(model_id).cloned())
+}
+
+pub(crate) fn record_load_phase(model_id: &str, phase: ModelLoadPhase) {
+    if let Ok(mut s) = states().write() {
+        s.loads.insert(model_id.to_string(), phase);
+    }
+}
+
+/// Returns whether the status changed. Scans (`force == false`) never
+/// override an active download.
+fn record_download(key: &str, status: DownloadStatus, force: bool) -> bool {
+    let Ok(mut s) = states().write() else { return false };
+    let old = s.downloads.get(key).copied();
+    if !force && old == Some(DownloadStatus::Downloading) { return false; }
+    if old == Some(status) { return false; }
+    s.downloads.insert(key.to_string(), status);
+    true
+}
+
+/// What a registry model's folder holds: everything it needs, the remains of
+/// an interrupted download, or nothing
+pub fn scan_registry_files(model: &RegistryModel) -> DownloadStatus {
+    let expanded = model.storage.expanded_path();
+    let path = Path::new(&expanded);
+    if !path.exists() { return DownloadStatus::NotDownloaded; }
+    let has_parts = model_integrity::has_part_files(path);
+    match model_integrity::check_registry_model(model) {
+        Integrity::Complete if !has_parts => DownloadStatus::Downloaded,
+        Integrity::Complete | Integrity::Incomplete { .. } => DownloadStatus::Partial,
+        Integrity::Unknown if has_parts => DownloadStatus::Partial,
+        Integrity::Unknown if registry_files_present(model) => DownloadStatus::Downloaded,
+        Integrity::Unknown => DownloadStatus::NotDownloaded,
+    }
+}
+
+/// Whether every file a registry model needs is on disk, judged by file
+/// names and sizes when there is no file list to compare against
+pub fn registry_files_present(model: &RegistryModel) -> bool {
+    let expanded = model.storage.expanded_path();
+    let path = Path::new(&expanded);
+    if !path.exists() { return false; }
+
+    // For multi-source models, verify all required files are present
+    if !model.extra_sources.is_empty() {
+        let file_name = |url: &str| url.split('/').last()
+            .and_then(|f| f.split('?').next())
+            .unwrap_or("")
+            .to_string();
+        if let Some(url) = &model.source.url {
+            let name = file_name(url);
+            if !name.is_empty() && !path.join(&name).exists() { return false; }
+        }
+        return model.extra_sources.iter().all(|extra| {
+            let Some(url) = &extra.source.url else { return true };
+            let name = file_name(url);
+            name.is_empty() || Path::new(&extra.storage.expanded_path()).join(&name).exists()
+        });
+    }
+
+    // For models with a known size > 100 MB, require at least one weight file
+    // (.safetensors, .bin, or .gguf) to exist — prevents partial downloads (e.g. only
+    // config.json fetched by the HF library) from showing as "Downloaded".
+    if model.storage.size_bytes > 100 * 1024 * 1024 {
+        return has_weight_files(path, model.storage.size_bytes);
+    }
+    std::fs::read_dir(path)
+        .map(|e| e.filter_map(|x| x.ok())
+             .filter(|x| !x.file_name().to_string_lossy().starts_with('.')).count())
+        .unwrap_or(0) > 0
+}
+
+/// Recursively search for weight files (.safetensors, .bin, .gguf) under `dir`.
+/// For .gguf files, compares actual size against `expected_size` (5% tolerance)
+/// to avoid false positives when multiple quant variants share a directory.
+fn has_weight_files(dir: &Path, expected_size: u64) -> bool {
+    let Ok(entries) = std::fs::read_dir(dir) else { return false };
+    for entry in entries.filter_map(|e| e.ok()) {
+        let p = entry.path();
+        if p.is_file() {
+            let name = p.file_name().unwrap_or_default().to_string_lossy();
+            if name.ends_with(".gguf") {
+                if expected_size > 0 {
+                    // Follows symlinks, which the HF cache uses in snapshots/
+                    let actual = std::fs::metadata(&p).map(|m| m.len()).unwrap_or(0);
+                    let tolerance = expected_size / 20; // 5%
+                    if (actual as i64 - expected_size as i64).unsigned_abs() <= tolerance {
+                        return true;
+                    }
+                } else {
+                    return true;
+                }
+            } else if name.ends_with(".safetensors") || name.ends_with(".bin") {
+                return true;
+            }
+        } else if p.is_dir() && has_weight_files(&p, expected_size) {
+            return true;
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scan_keeps_active_download() {
+        let key = "/tmp/moly-model-state-test/model";
+        assert!(record_download(key, DownloadStatus::Downloading, true));
+        assert!(!record_download(key, DownloadStatus::NotDownloaded, false));
+        assert_eq!(download_status(key), DownloadStatus::Downloading);
+        assert!(record_download(key, DownloadStatus::Downloaded, true));
+        assert!(!record_download(key, DownloadStatus::Downloaded, false));
+        assert!(record_download(key, DownloadStatus::NotDownloaded, false));
+        assert_eq!(download_status(key), DownloadStatus::NotDownloaded);
+    }
+}
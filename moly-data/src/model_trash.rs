@@ -0,0 +1,137 @@
+//! Removing model files, with a short undo window
+//!
+//! Removing a model first moves its folder aside to a hidden sibling
+//! (`.moly-removed-<name>`), which is instant and trivially undone. When the
+//! undo window passes, [`PendingRemoval::commit`] sends the folder to the
+//! system Trash — or deletes it outright when the user has opted into hard
+//! deletes in Settings. Folders left staged by a previous session (the app
+//! quit inside the undo window) are committed by [`sweep_staged`].
+//!
+//! The hard-delete preference is mirrored here by the store so screens that
+//! don't hold the store can honour it.
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+/// How long the undo toast stays up before the removal is committed
+pub const UNDO_WINDOW: Duration = Duration::from_secs(8);
+
+const STAGED_PREFIX: &str = ".moly-removed-";
+
+static HARD_DELETE: AtomicBool = AtomicBool::new(false);
+
+/// Whether removed models are deleted permanently instead of trashed
+pub fn hard_delete() -> bool {
+    HARD_DELETE.load(Ordering::Relaxed)
+}
+
+/// Mirror [`crate::Preferences::hard_delete_models`]
+pub fn set_hard_delete(enabled: bool) {
+    HARD_DELETE.store(enabled, Ordering::Relaxed);
+}
+
+/// A model folder that has been moved aside but not yet trashed
+#[derive(Clone, Debug)]
+pub struct PendingRemoval {
+    /// Where the model lived
+    pub original: PathBuf,
+    /// Where it is parked until committed
+    pub staged: PathBuf,
+}
+
+impl PendingRemoval {
+    /// Put the model folder back
+    pub fn undo(&self) -> Result<(), String> {
+        if self.original.exists() {
+            return Err(format!("{} already exists", self.original.display()));
+        }
+        std::fs::rename(&self.staged, &self.original)
+            .map_err(|e| format!("{}: {}", self.original.display(), e))?;
+        log::info!("Restored removed model {:?}", self.original);
+        Ok(())
+    }
+
+    /// Trash (or delete) the staged folder. Can be slow for large models, so
+    /// callers should run it off the UI thread.
+    pub fn commit(&self, hard_delete: bool) -> Result<(), String> {
+        discard(&self.staged, hard_delete)?;
+        log::info!("Removed model {:?} ({})", self.original, if hard_delete { "deleted" } else { "moved to Trash" });
+        Ok(())
+    }
+}
+
+/// Move a model folder aside so it can still be restored
+pub fn stage_removal(path: &Path) -> Result<PendingRemoval, String> {
+    if !path.exists() {
+        return Err(format!("{} does not exist", path.display()));
+    }
+    let staged = staged_path(path).ok_or_else(|| format!("Can't remove {}", path.display()))?;
+    // A leftover from an earlier removal of the same model
+    if staged.exists() {
+        discard(&staged, false)?;
+    }
+    std::fs::rename(path, &staged).map_err(|e| format!("{}: {}", path.display(), e))?;
+    Ok(PendingRemoval { original: path.to_path_buf(), staged })
+}
+
+/// Remove a model folder (or a single file) right away, without an undo window
+pub fn remove_now(path: &Path, hard_delete: bool) -> Result<(), String> {
+    discard(path, hard_delete)
+}
+
+/// Commit removals staged in `dir` by an earlier session
+pub fn sweep_staged(dir: &Path, hard_delete: bool) {
+    let Ok(entries) = std::fs::read_dir(dir) else { return };
+    for entry in entries.flatten() {
+        if entry.file_name().to_string_lossy().starts_with(STAGED_PREFIX) {
+            if let Err(e) = discard(&entry.path(), hard_delete) {
+                log::warn!("Failed to clean up removed model {:?}: {}", entry.path(), e);
+            }
+        }
+    }
+}
+
+fn discard(path: &Path, hard_delete: bool) -> Result<(), String> {
+    if hard_delete {
+        let result = if path.is_dir() { std::fs::remove_dir_all(path) } else { std::fs::remove_file(path) };
+        result.map_err(|e| format!("{}: {}", path.display(), e))
+    } else {
+        #[cfg(not(target_arch = "wasm32"))]
+        return trash::delete(path).map_err(|e| format!("Couldn't move {} to the Trash: {}", path.display(), e));
+        #[cfg(target_arch = "wasm32")]
+        return Err(format!("Couldn't move {} to the Trash: no Trash in the browser", path.display()));
+    }
+}
+
+fn staged_path(path: &Path) -> Option<PathBuf> {
+    let name = path.file_name()?.to_str()?;
+    Some(path.with_file_name(format!("{}{}", STAGED_PREFIX, name)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stage_and_undo() {
+        let root = std::env::temp_dir().join(format!("moly-trash-test-{}", std::process::id()));
+        let model = root.join("models--org--model");
+        std::fs::create_dir_all(&model).unwrap();
+        std::fs::write(model.join("config.json"), "{}").unwrap();
+
+        let pending = stage_removal(&model).unwrap();
+        assert!(!model.exists());
+        assert_eq!(pending.staged, root.join(".moly-removed-models--org--model"));
+        assert!(pending.staged.join("config.json").exists());
+
+        pending.undo().unwrap();
+        assert!(model.join("config.json").exists());
+        assert!(!pending.staged.exists());
+
+        let pending = stage_removal(&model).unwrap();
+        pending.commit(true).unwrap();
+        assert!(!model.exists() && !pending.staged.exists());
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
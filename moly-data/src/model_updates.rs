@@ -0,0 +1,174 @@
+//! Updates to downloaded models
+//!
+//! Downloads from Hugging Face and ModelScope record the version of the repo
+//! they fetched in the model folder (`.moly-revision.json`): its commit and
+//! the content hash of each file. Every few hours the Model Hub lists the
+//! repos of downloaded models again and compares them with what's on disk
+//! ([`changes`]). Models whose repo changed are badged "Update available",
+//! and updating fetches only the files that changed. Models downloaded
+//! before revisions were recorded are compared by file size, from their
+//! download manifest ([`crate::model_integrity`]).
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crate::disk_space::format_size;
+use crate::model_integrity;
+use crate::model_registry::RegistryFile;
+
+pub const REVISION_FILENAME: &str = ".moly-revision.json";
+
+/// How often downloaded models are checked for updates
+pub const CHECK_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// A file of a repo
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct RepoFile {
+    pub path: String,
+    pub size: u64,
+    /// SHA-256 of LFS files, the git blob id of others; empty when unknown
+    #[serde(default)]
+    pub hash: String,
+}
+
+/// A repo's files at one commit
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct RepoRevision {
+    /// Empty when the hub doesn't report it
+    #[serde(default)]
+    pub commit: String,
+    pub files: Vec<RepoFile>,
+}
+
+impl RepoRevision {
+    /// The files as the download manifest lists them
+    pub fn registry_files(&self) -> Vec<RegistryFile> {
+        self.files.iter().map(|f| RegistryFile { path: f.path.clone(), size_bytes: f.size }).collect()
+    }
+}
+
+/// Record the revision a download fetched in full
+pub fn write_revision(dir: &Path, revision: &RepoRevision) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(revision).map_err(|e| e.to_string())?;
+    let path = dir.join(REVISION_FILENAME);
+    std::fs::write(&path, json).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+pub fn read_revision(dir: &Path) -> Option<RepoRevision> {
+    let json = std::fs::read_to_string(dir.join(REVISION_FILENAME)).ok()?;
+    serde_json::from_str(&json).ok()
+}
+
+/// What `dir` holds: the recorded revision, or else the download
+/// manifest's files without hashes
+pub fn local_revision(dir: &Path) -> Option<RepoRevision> {
+    read_revision(dir).or_else(|| {
+        let files = model_integrity::read_manifest(dir)?;
+        Some(RepoRevision {
+            commit: String::new(),
+            files: files.into_iter().map(|f| RepoFile { path: f.path, size: f.size_bytes, hash: String::new() }).collect(),
+        })
+    })
+}
+
+/// What updating a model fetches
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ModelUpdate {
+    /// Commit of the new version, empty when unknown
+    pub commit: String,
+    /// New files and files whose content changed
+    pub changed: Vec<RepoFile>,
+    /// Files no longer in the repo
+    pub removed: Vec<String>,
+}
+
+impl ModelUpdate {
+    pub fn bytes(&self) -> u64 {
+        self.changed.iter().map(|f| f.size).sum()
+    }
+
+    /// e.g. `2 files changed (1.5 GB), 1 removed`
+    pub fn summary(&self) -> String {
+        let files = if self.changed.len() == 1 { "file" } else { "files" };
+        let mut summary = format!("{} {} changed ({})", self.changed.len(), files, format_size(self.bytes()));
+        if !self.removed.is_empty() {
+            summary.push_str(&format!(", {} removed", self.removed.len()));
+        }
+        summary
+    }
+}
+
+/// How `remote` differs from the `local` files; `None` when they're the
+/// same. Files are compared by hash when both sides have one, and by size
+/// otherwise.
+pub fn changes(local: &RepoRevision, remote: &RepoRevision) -> Option<ModelUpdate> {
+    if !local.commit.is_empty() && local.commit == remote.commit {
+        return None;
+    }
+    let changed: Vec<RepoFile> = remote.files.iter()
+        .filter(|file| match local.files.iter().find(|l| l.path == file.path) {
+            None => true,
+            Some(l) if !l.hash.is_empty() && !file.hash.is_empty() => l.hash != file.hash,
+            Some(l) => file.size > 0 && l.size != file.size,
+        })
+        .cloned()
+        .collect();
+    let removed: Vec<String> = local.files.iter()
+        .filter(|l| !remote.files.iter().any(|f| f.path == l.path))
+        .map(|l| l.path.clone())
+        .collect();
+    if changed.is_empty() && removed.is_empty() {
+        return None;
+    }
+    Some(ModelUpdate { commit: remote.commit.clone(), changed, removed })
+}
+
+/// Whether it's time to check again, given when the last check started
+pub fn check_due(last: Option<Instant>) -> bool {
+    last.is_none_or(|last| last.elapsed() >= CHECK_INTERVAL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(path: &str, size: u64, hash: &str) -> RepoFile {
+        RepoFile { path: path.to_string(), size, hash: hash.to_string() }
+    }
+
+    #[test]
+    fn test_changes() {
+        let local = RepoRevision {
+            commit: "a1".to_string(),
+            files: vec![file("config.json", 10, "c1"), file("model.safetensors", 900, "w1"), file("old.txt", 3, "o1")],
+        };
+        assert_eq!(changes(&local, &local), None);
+
+        let remote = RepoRevision {
+            commit: "b2".to_string(),
+            files: vec![file("config.json", 10, "c1"), file("model.safetensors", 900, "w2"), file("tokenizer.json", 50, "t1")],
+        };
+        let update = changes(&local, &remote).unwrap();
+        assert_eq!(update.commit, "b2");
+        assert_eq!(update.changed.iter().map(|f| f.path.as_str()).collect::<Vec<_>>(), ["model.safetensors", "tokenizer.json"]);
+        assert_eq!(update.removed, ["old.txt"]);
+        assert_eq!(update.bytes(), 950);
+
+        // A new commit with the same files needs no update
+        let same_files = RepoRevision { commit: "c3".to_string(), files: local.files.clone() };
+        assert_eq!(changes(&local, &same_files), None);
+    }
+
+    #[test]
+    fn test_changes_from_manifest() {
+        // Downloaded before revisions were recorded: sizes only
+        let local = RepoRevision { commit: String::new(), files: vec![file("model.safetensors", 900, "")] };
+        let same = RepoRevision { commit: "b2".to_string(), files: vec![file("model.safetensors", 900, "w2")] };
+        assert_eq!(changes(&local, &same), None);
+        let grown = RepoRevision { commit: "b2".to_string(), files: vec![file("model.safetensors", 901, "w2")] };
+        assert_eq!(changes(&local, &grown).unwrap().changed.len(), 1);
+        assert!(check_due(None));
+        assert!(!check_due(Some(Instant::now())));
+    }
+}
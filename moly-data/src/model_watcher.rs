@@ -0,0 +1,155 @@
+//! Filesystem watcher for model storage directories
+//!
+//! Watches the directories that hold model folders, so folders copied in or
+//! deleted by hand show up in [`crate::model_state`] without a manual
+//! refresh. Events are collected on notify's thread; call [`ModelWatcher::poll`]
+//! from the UI thread (e.g. on a one-second timer) to rescan the affected
+//! models once their directory has been quiet for a moment. Every status that
+//! changes is published as [`crate::StoreEvent::ModelStateChanged`].
+
+use makepad_widgets::Cx;
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+use crate::local_models::LocalModelsConfigV2;
+use crate::model_registry::ModelRegistry;
+use crate::model_state::{self, DownloadStatus};
+
+/// How long a model directory must be quiet before it is rescanned
+const SETTLE_TIME: Duration = Duration::from_millis(1500);
+
+struct WatchedModel {
+    /// Expanded storage path, the key in model_state
+    key: String,
+    scan: Box<dyn Fn() -> DownloadStatus>,
+}
+
+pub struct ModelWatcher {
+    watcher: Option<RecommendedWatcher>,
+    rx: mpsc::Receiver<PathBuf>,
+    models: Vec<WatchedModel>,
+    /// Parent directories being watched
+    roots: HashSet<PathBuf>,
+    /// Models with recent events, and when the last one arrived
+    pending: HashMap<String, Instant>,
+}
+
+impl ModelWatcher {
+    pub fn new() -> Self {
+        let (tx, rx) = mpsc::channel();
+        let watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+            if let Ok(event) = res {
+                for path in event.paths {
+                    let _ = tx.send(path);
+                }
+            }
+        });
+        let watcher = match watcher {
+            Ok(w) => Some(w),
+            Err(e) => {
+                log::warn!("Model directory watcher unavailable: {:?}", e);
+                None
+            }
+        };
+        Self { watcher, rx, models: Vec::new(), roots: HashSet::new(), pending: HashMap::new() }
+    }
+
+    /// Watch every model in the registry, scanning each once now
+    pub fn watch_registry(&mut self, registry: &ModelRegistry) {
+        for model in &registry.models {
+            model_state::scan_registry_model(model);
+            let model = model.clone();
+            self.add(model.storage.expanded_path(), Box::new(move || model_state::scan_registry_files(&model)));
+        }
+    }
+
+    /// Watch the models of the V2 local models config
+    pub fn watch_local_models(&mut self, config: &LocalModelsConfigV2) {
+        for model in &config.models {
+            let model = model.clone();
+            self.add(model.expanded_path(), Box::new(move || {
+                let mut model = model.clone();
+                model.scan_filesystem();
+                DownloadStatus::from_local_state(model.status.state)
+            }));
+        }
+    }
+
+    fn add(&mut self, key: String, scan: Box<dyn Fn() -> DownloadStatus>) {
+        if self.models.iter().any(|m| m.key == key) { return; }
+        self.models.push(WatchedModel { key, scan });
+        self.watch_missing_roots();
+    }
+
+    /// Watch the parent directory of every model. Parents that don't exist
+    /// yet (e.g. no Hugging Face cache) are retried on each poll.
+    fn watch_missing_roots(&mut self) {
+        let Some(watcher) = &mut self.watcher else { return };
+        for model in &self.models {
+            let Some(parent) = Path::new(&model.key).parent() else { continue };
+            if self.roots.contains(parent) || !parent.is_dir() { continue; }
+            match watcher.watch(parent, RecursiveMode::Recursive) {
+                Ok(()) => {
+                    log::debug!("Watching model directory {:?}", parent);
+                    self.roots.insert(parent.to_path_buf());
+                }
+                Err(e) => log::warn!("Failed to watch {:?}: {:?}", parent, e),
+            }
+        }
+    }
+
+    /// Rescan models whose directories changed and have settled, publishing
+    /// any status that changed
+    pub fn poll(&mut self, cx: &mut Cx) {
+        if self.watcher.is_none() { return; }
+        self.watch_missing_roots();
+
+        let now = Instant::now();
+        while let Ok(path) = self.rx.try_recv() {
+            for model in self.models.iter().filter(|m| affects(&path, &m.key)) {
+                self.pending.insert(model.key.clone(), now);
+            }
+        }
+
+        let settled: Vec<String> = self.pending.iter()
+            .filter(|(_, last)| now.duration_since(**last) >= SETTLE_TIME)
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in settled {
+            self.pending.remove(&key);
+            // Our own downloads write here constantly; they publish when done
+            if model_state::download_status(&key) == DownloadStatus::Downloading { continue; }
+            let Some(model) = self.models.iter().find(|m| m.key == key) else { continue };
+            let status = (model.scan)();
+            if status != model_state::download_status(&key) {
+                log::info!("Model files changed on disk: {} is now {:?}", key, status);
+                model_state::set_download_status(cx, &key, status);
+            }
+        }
+    }
+}
+
+/// Whether an event on `path` can change the model stored at `key`: a file
+/// inside the model folder, the folder itself, or one of its ancestors
+fn affects(path: &Path, key: &str) -> bool {
+    let key = Path::new(key);
+    path.starts_with(key) || key.starts_with(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_paths() {
+        let key = "/home/u/.cache/huggingface/hub/models--org--model";
+        assert!(affects(Path::new("/home/u/.cache/huggingface/hub/models--org--model/snapshots/a/model.safetensors"), key));
+        assert!(affects(Path::new("/home/u/.cache/huggingface/hub/models--org--model"), key));
+        assert!(affects(Path::new("/home/u/.cache/huggingface"), key));
+        assert!(!affects(Path::new("/home/u/.cache/huggingface/hub/models--org--model-q8"), key));
+        assert!(!affects(Path::new("/home/u/.cache/huggingface/hub/models--other"), key));
+    }
+}
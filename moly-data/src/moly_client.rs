@@ -55,7 +55,7 @@ impl MolyClient {
         let base_url = format!("http://localhost:{}", port);
 
         Self {
-            client: Client::builder()
+            client: crate::network::async_client()
                 .timeout(std::time::Duration::from_secs(30))
                 .build()
                 .expect("Failed to create HTTP client"),
@@ -0,0 +1,85 @@
+//! Reading the text in an image
+//!
+//! A vision model loaded in the runtime is asked to transcribe an image
+//! ([`recognize`]), so the text of a screenshot, receipt or scanned page can
+//! be copied, searched and kept in a conversation as plain text. The chat
+//! puts it next to the image in the message it is sent with
+//! ([`with_image_text`]), where models without vision can read it too; the
+//! Hub's VLM panel shows it in place of an answer.
+
+use base64::Engine;
+
+use crate::model_registry::{ModelRegistry, RegistryCategory, RegistryModel};
+use crate::model_state;
+use crate::ominix_api_client::{ChatCompletionRequest, ChatMessage, OminiXApiClient};
+use crate::store::ModelLoadPhase;
+
+/// What the model answers when the image has no text
+const NO_TEXT: &str = "NO TEXT";
+
+/// Prompt sent with the image
+pub const INSTRUCTION: &str = "Transcribe all the text in this image exactly as written, \
+in reading order, keeping line breaks and the original language. Do not translate, summarize or describe the image. \
+Reply with the text only. If the image has no text, reply NO TEXT.";
+
+/// Chat request asking `model` for the text of a base64-encoded image
+pub fn request(model: impl Into<String>, image_b64: &str) -> ChatCompletionRequest {
+    ChatCompletionRequest {
+        model: model.into(),
+        messages: vec![ChatMessage::user_with_image(INSTRUCTION, image_b64)],
+        max_tokens: None,
+    }
+}
+
+/// The text from a model's answer: without a `<think>` block or a code fence
+/// around it. An image without text is an error.
+pub fn clean_answer(answer: &str) -> Result<String, String> {
+    let mut text = answer;
+    if let Some(end) = text.find("</think>") {
+        text = &text[end + "</think>".len()..];
+    }
+    let mut text = text.trim();
+    if let Some(inner) = text.strip_prefix("```").and_then(|t| t.strip_suffix("```")) {
+        // Drop the fence's language tag, if any
+        text = inner.split_once('\n').map_or(inner, |(_, rest)| rest).trim();
+    }
+    if text.is_empty() || text.trim_end_matches('.').eq_ignore_ascii_case(NO_TEXT) {
+        return Err("No text found in the image.".to_string());
+    }
+    Ok(text.to_string())
+}
+
+/// A vision model the runtime has loaded
+pub fn loaded_vlm(registry: &ModelRegistry) -> Option<RegistryModel> {
+    registry.by_category(RegistryCategory::Vlm)
+        .find(|m| model_state::load_phase(&m.id) == Some(ModelLoadPhase::Loaded))
+        .cloned()
+}
+
+/// The text in the image at `path`, read by `model`. Blocks; call it off the
+/// UI thread.
+pub fn recognize(model: &str, path: &str) -> Result<String, String> {
+    let bytes = std::fs::read(path).map_err(|e| format!("{}: {}", path, e))?;
+    let image_b64 = base64::engine::general_purpose::STANDARD.encode(&bytes);
+    let answer = OminiXApiClient::localhost(120).chat_completion(&request(model, &image_b64))?;
+    clean_answer(&answer)
+}
+
+/// `prompt` followed by the text recognized in the image `name`
+pub fn with_image_text(prompt: &str, name: &str, text: &str) -> String {
+    format!("{}\n\nText in {}:\n\n{}", prompt.trim_end(), name, text.trim())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clean_answer() {
+        assert_eq!(clean_answer("<think>A receipt.</think>\nTOTAL  $12.50\nThank you").unwrap(), "TOTAL  $12.50\nThank you");
+        assert_eq!(clean_answer("```text\n欢迎光临\nWelcome\n```").unwrap(), "欢迎光临\nWelcome");
+        assert!(clean_answer("No text.").is_err());
+        assert!(clean_answer("  ").is_err());
+        assert_eq!(with_image_text("What does it say? ", "sign.png", "OPEN\n"), "What does it say?\n\nText in sign.png:\n\nOPEN");
+    }
+}
@@ -0,0 +1,380 @@
+//! Ollama native API client.
+//!
+//! Talks to a local Ollama daemon directly instead of through its
+//! OpenAI-compatible shim, so we get model sizes and pull progress:
+//!
+//!   GET  /api/tags   → installed models with size on disk
+//!   POST /api/pull   → download a model (NDJSON progress stream)
+//!   POST /api/chat   → streaming chat (NDJSON, one chunk per line)
+
+use futures::StreamExt;
+use moly_kit::aitk::protocol::*;
+use moly_kit::aitk::utils::asynchronous::{BoxPlatformSendFuture, BoxPlatformSendStream};
+#[cfg(not(target_arch = "wasm32"))]
+use std::io::{BufRead, BufReader};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+
+use crate::chat_defaults;
+use crate::provider_catalog::{parse_models_response, CatalogModel};
+use crate::stream_lines::LineBuffer;
+
+/// Default address of the Ollama daemon
+pub const OLLAMA_DEFAULT_URL: &str = "http://localhost:11434";
+
+/// Normalise a configured provider URL to the daemon root.
+///
+/// Older preferences stored the OpenAI shim URL (`.../v1`), so strip that.
+pub fn ollama_base_url(url: &str) -> String {
+    let url = url.trim().trim_end_matches('/');
+    let url = url.strip_suffix("/v1").or_else(|| url.strip_suffix("/api")).unwrap_or(url);
+    if url.is_empty() {
+        OLLAMA_DEFAULT_URL.to_string()
+    } else {
+        url.to_string()
+    }
+}
+
+// ─── Blocking helpers (call from a thread) ────────────────────────────────────
+
+/// "3 models · 12.4 GB" for the daemon's installed models
+pub fn installed_summary(models: &[CatalogModel]) -> String {
+    let total: u64 = models.iter().filter_map(|m| m.size_bytes).sum();
+    match models.len() {
+        0 => "No models installed".to_string(),
+        1 => format!("1 model · {}", crate::disk_space::format_size(total)),
+        n => format!("{} models · {}", n, crate::disk_space::format_size(total)),
+    }
+}
+
+/// Progress line from `/api/pull`
+#[derive(Clone, Debug, Default)]
+pub struct OllamaPullProgress {
+    pub status: String,
+    pub completed: u64,
+    pub total: u64,
+}
+
+impl OllamaPullProgress {
+    /// Fraction of the current layer downloaded (0.0 when the size is unknown)
+    pub fn fraction(&self) -> f64 {
+        if self.total == 0 {
+            0.0
+        } else {
+            (self.completed as f64 / self.total as f64).min(1.0)
+        }
+    }
+}
+
+/// List models installed in the Ollama daemon.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn list_ollama_models(url: &str) -> Result<Vec<CatalogModel>, String> {
+    let base = ollama_base_url(url);
+    let client = crate::network::blocking_client()
+        .timeout(std::time::Duration::from_secs(10))
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let resp = client
+        .get(format!("{}/api/tags", base))
+        .send()
+        .map_err(|e| if e.is_connect() {
+            format!("Ollama is not running at {}", base)
+        } else {
+            format!("GET /api/tags failed: {}", e)
+        })?;
+
+    if !resp.status().is_success() {
+        return Err(format!("GET /api/tags HTTP {}", resp.status()));
+    }
+
+    let body = resp.text().map_err(|e| e.to_string())?;
+    parse_models_response(&body)
+}
+
+/// Pull a model, reporting each progress line until the daemon reports success.
+/// Returns `Ok(())` early if `cancel` is set.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn pull_ollama_model(
+    url: &str,
+    model: &str,
+    cancel: &AtomicBool,
+    mut on_progress: impl FnMut(OllamaPullProgress),
+) -> Result<(), String> {
+    let base = ollama_base_url(url);
+    // No overall timeout: large models take a long time to pull
+    let client = crate::network::blocking_client()
+        .timeout(None)
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let resp = client
+        .post(format!("{}/api/pull", base))
+        .json(&serde_json::json!({ "model": model, "stream": true }))
+        .send()
+        .map_err(|e| format!("POST /api/pull failed: {}", e))?;
+
+    if !resp.status().is_success() {
+        let status = resp.status();
+        let body = resp.text().unwrap_or_default();
+        return Err(format!("Pull failed (HTTP {}): {}", status, body));
+    }
+
+    for line in BufReader::new(resp).lines() {
+        if cancel.load(Ordering::SeqCst) {
+            log::info!("Ollama pull of {} cancelled", model);
+            return Ok(());
+        }
+        let line = line.map_err(|e| format!("Pull stream interrupted: {}", e))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let v: serde_json::Value = serde_json::from_str(&line).map_err(|e| e.to_string())?;
+        if let Some(err) = v.get("error").and_then(|e| e.as_str()) {
+            return Err(err.to_string());
+        }
+        let progress = OllamaPullProgress {
+            status: v.get("status").and_then(|s| s.as_str()).unwrap_or("").to_string(),
+            completed: v.get("completed").and_then(|n| n.as_u64()).unwrap_or(0),
+            total: v.get("total").and_then(|n| n.as_u64()).unwrap_or(0),
+        };
+        let done = progress.status == "success";
+        on_progress(progress);
+        if done {
+            return Ok(());
+        }
+    }
+
+    Err("Pull stream ended before completion".to_string())
+}
+
+/// Model management needs blocking HTTP; chat goes through [`OllamaClient`]
+#[cfg(target_arch = "wasm32")]
+pub fn list_ollama_models(_url: &str) -> Result<Vec<CatalogModel>, String> {
+    Err("Listing Ollama models isn't available in the browser".to_string())
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn pull_ollama_model(
+    _url: &str,
+    _model: &str,
+    _cancel: &AtomicBool,
+    _on_progress: impl FnMut(OllamaPullProgress),
+) -> Result<(), String> {
+    Err("Pulling Ollama models isn't available in the browser".to_string())
+}
+
+// ─── BotClient ────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone)]
+struct OllamaClientInner {
+    base_url: String,
+    client: reqwest::Client,
+}
+
+/// Chat client for Ollama's native `/api/chat` endpoint.
+#[derive(Debug)]
+pub struct OllamaClient(Arc<RwLock<OllamaClientInner>>);
+
+impl Clone for OllamaClient {
+    fn clone(&self) -> Self {
+        OllamaClient(Arc::clone(&self.0))
+    }
+}
+
+impl OllamaClient {
+    pub fn new(url: &str) -> Self {
+        let inner = OllamaClientInner {
+            base_url: ollama_base_url(url),
+            client: crate::network::async_client().build().unwrap_or_else(|_| reqwest::Client::new()),
+        };
+        OllamaClient(Arc::new(RwLock::new(inner)))
+    }
+
+    /// Get the daemon base URL
+    pub fn get_url(&self) -> String {
+        self.0.read().unwrap().base_url.clone()
+    }
+}
+
+fn ollama_role(from: &EntityId) -> Option<&'static str> {
+    match from {
+        EntityId::User => Some("user"),
+        EntityId::Bot(_) => Some("assistant"),
+        EntityId::System => Some("system"),
+        _ => None,
+    }
+}
+
+impl BotClient for OllamaClient {
+    fn bots(&mut self) -> BoxPlatformSendFuture<'static, ClientResult<Vec<Bot>>> {
+        let inner = self.0.read().unwrap().clone();
+
+        Box::pin(async move {
+            let url = format!("{}/api/tags", inner.base_url);
+            let response = match inner.client.get(&url).send().await {
+                Ok(r) => r,
+                Err(e) => {
+                    return ClientResult::new_err(ClientError::new_with_source(
+                        ClientErrorKind::Network,
+                        format!("Could not reach Ollama at {url}. Is the daemon running?"),
+                        Some(e),
+                    ).into());
+                }
+            };
+            let text = response.text().await.unwrap_or_default();
+            let models = match parse_models_response(&text) {
+                Ok(m) => m,
+                Err(e) => return ClientResult::new_err(ClientError::new(ClientErrorKind::Format, e).into()),
+            };
+
+            let bots = models
+                .into_iter()
+                .map(|m| Bot {
+                    id: BotId::new(&m.id),
+                    name: m.id,
+                    avatar: EntityAvatar::Text("O".to_string()),
+                    capabilities: BotCapabilities::new().with_capability(BotCapability::TextInput),
+                })
+                .collect();
+
+            ClientResult::new_ok(bots)
+        })
+    }
+
+    fn send(
+        &mut self,
+        bot_id: &BotId,
+        messages: &[Message],
+        _tools: &[Tool],
+    ) -> BoxPlatformSendStream<'static, ClientResult<MessageContent>> {
+        let inner = self.0.read().unwrap().clone();
+        let model = bot_id.id().to_string();
+        let chat_messages: Vec<serde_json::Value> = messages
+            .iter()
+            .filter_map(|m| {
+                let role = ollama_role(&m.from)?;
+                Some(serde_json::json!({ "role": role, "content": m.content.text }))
+            })
+            .collect();
+        let sampling = chat_defaults::active();
+        let mut options = serde_json::Map::new();
+        if let Some(temperature) = sampling.temperature {
+            options.insert("temperature".into(), temperature.into());
+        }
+        if let Some(max_tokens) = sampling.max_tokens {
+            options.insert("num_predict".into(), max_tokens.into());
+        }
+
+        Box::pin(async_stream::stream! {
+            let url = format!("{}/api/chat", inner.base_url);
+            let mut body = serde_json::json!({
+                "model": model,
+                "messages": chat_messages,
+                "stream": true,
+            });
+            if !options.is_empty() {
+                body["options"] = options.into();
+            }
+
+            let response = match inner.client.post(&url).json(&body).send().await {
+                Ok(r) => r,
+                Err(e) => {
+                    yield ClientResult::new_err(ClientError::new_with_source(
+                        ClientErrorKind::Network,
+                        format!("Could not send request to {url}. Is Ollama running?"),
+                        Some(e),
+                    ).into());
+                    return;
+                }
+            };
+
+            if !response.status().is_success() {
+                let status = response.status();
+                let text = response.text().await.unwrap_or_default();
+                yield ClientResult::new_err(ClientError::new(
+                    ClientErrorKind::Response,
+                    format!("Request to {url} failed with status {} and content: {}", status, text),
+                ).into());
+                return;
+            }
+
+            // Each NDJSON line carries a delta; yield the accumulated content
+            let mut content = MessageContent::default();
+            let mut buffer = LineBuffer::default();
+            let mut stream = response.bytes_stream();
+            while let Some(chunk) = stream.next().await {
+                let chunk = match chunk {
+                    Ok(c) => c,
+                    Err(e) => {
+                        yield ClientResult::new_err(ClientError::new_with_source(
+                            ClientErrorKind::Network,
+                            "Ollama response stream interrupted".to_string(),
+                            Some(e),
+                        ).into());
+                        return;
+                    }
+                };
+                buffer.push(&chunk);
+
+                while let Some(line) = buffer.next_line() {
+                    let Ok(v) = serde_json::from_str::<serde_json::Value>(line.trim()) else { continue };
+                    if let Some(err) = v.get("error").and_then(|e| e.as_str()) {
+                        yield ClientResult::new_err(ClientError::new(
+                            ClientErrorKind::Response,
+                            err.to_string(),
+                        ).into());
+                        return;
+                    }
+                    if let Some(delta) = v.pointer("/message/content").and_then(|c| c.as_str()) {
+                        content.text.push_str(delta);
+                        yield ClientResult::new_ok(content.clone());
+                    }
+                }
+            }
+        })
+    }
+
+    fn clone_box(&self) -> Box<dyn BotClient> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::preferences::Preferences;
+    use crate::providers::ProviderType;
+
+    #[test]
+    fn test_base_url_and_summary() {
+        assert_eq!(ollama_base_url("http://localhost:11434/v1/"), "http://localhost:11434");
+        assert_eq!(ollama_base_url("http://gpu-box:11434/api"), "http://gpu-box:11434");
+        assert_eq!(ollama_base_url(" "), OLLAMA_DEFAULT_URL);
+
+        let models = parse_models_response(
+            r#"{"models":[{"name":"llama3.2:3b","size":2019393189},{"name":"qwen3:8b","size":5225388164}]}"#,
+        )
+        .unwrap();
+        assert_eq!(models[0].size_bytes, Some(2019393189));
+        assert_eq!(installed_summary(&models), "2 models · 6.7 GB");
+        assert_eq!(installed_summary(&[]), "No models installed");
+    }
+
+    #[test]
+    fn test_migrate_openai_shim() {
+        // Ollama set up through the OpenAI shim, without a key
+        let mut prefs = Preferences::default();
+        let ollama = prefs.providers_preferences.iter_mut().find(|p| p.id == "ollama").unwrap();
+        ollama.provider_type = ProviderType::OpenAi;
+        ollama.url = "http://gpu-box:11434/v1".to_string();
+        ollama.enabled = true;
+        ollama.api_key = None;
+
+        prefs.merge_with_supported_providers();
+        let ollama = prefs.get_provider(&"ollama".to_string()).unwrap();
+        assert_eq!(ollama.provider_type, ProviderType::Ollama);
+        assert_eq!(ollama.url, "http://gpu-box:11434");
+        assert!(ollama.is_ready());
+    }
+}
@@ -0,0 +1,1070 @@
+//! Typed blocking client for the ominix-api inference endpoints.
+//!
+//!   POST /v1/chat/completions        → [`OminiXApiClient::chat_completion`]
+//!   POST /v1/completions             → [`OminiXApiClient::completion`]
+//!   POST /v1/rerank                  → [`OminiXApiClient::rerank`]
+//!   POST /v1/audio/transcriptions    → [`OminiXApiClient::transcribe`]
+//!   POST /v1/audio/speech            → [`OminiXApiClient::speech`]
+//!   POST /v1/audio/generations       → [`OminiXApiClient::generate_music`]
+//!   POST /v1/images/generations      → [`OminiXApiClient::generate_image`]
+//!   POST /v1/videos/generations      → [`OminiXApiClient::generate_video`]
+//!   GET  /v1/voices                  → [`OminiXApiClient::list_voices`]
+//!   POST /v1/voices/train[/cancel]   → [`OminiXApiClient::train_voice`]
+//!   GET  /v1/voices/train/status     → [`OminiXApiClient::train_status`]
+//!
+//! Load/unload lives in [`crate::ModelRuntimeClient`]. All calls block the
+//! calling thread — run them inside `std::thread::spawn`. Every call is kept
+//! in [`crate::request_log`].
+
+use base64::Engine as _;
+use serde::{Deserialize, Serialize};
+
+use crate::transcript::Transcript;
+
+// ─── Errors ───────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum OminiXApiError {
+    /// The request never got a response (server down, timeout, ...)
+    Connection(String),
+    /// Non-2xx response; `body` is truncated
+    Http { status: u16, body: String },
+    /// 2xx response carrying an `error` object
+    Api(String),
+    /// Response body did not have the expected shape
+    Decode(String),
+}
+
+impl std::fmt::Display for OminiXApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Connection(e) => write!(f, "Connection failed: {}", e),
+            Self::Http { status, body } if body.is_empty() => write!(f, "HTTP {}", status),
+            Self::Http { status, body } => write!(f, "HTTP {}: {}", status, body),
+            Self::Api(e) => write!(f, "API error: {}", e),
+            Self::Decode(e) => write!(f, "Unexpected response: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for OminiXApiError {}
+
+impl From<OminiXApiError> for String {
+    fn from(e: OminiXApiError) -> Self {
+        e.to_string()
+    }
+}
+
+// ─── Chat completions ─────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ChatCompletionRequest {
+    pub model: String,
+    pub messages: Vec<ChatMessage>,
+    /// Longest answer, in tokens; the server's default when `None`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ChatMessage {
+    pub role: String,
+    pub content: ChatContent,
+}
+
+impl ChatMessage {
+    pub fn system(text: impl Into<String>) -> Self {
+        Self { role: "system".into(), content: ChatContent::Text(text.into()) }
+    }
+
+    pub fn user(text: impl Into<String>) -> Self {
+        Self { role: "user".into(), content: ChatContent::Text(text.into()) }
+    }
+
+    /// User message with an attached base64 JPEG/PNG image, sent as a data URL
+    pub fn user_with_image(text: impl Into<String>, image_b64: &str) -> Self {
+        Self {
+            role: "user".into(),
+            content: ChatContent::Parts(vec![
+                ContentPart::Text { text: text.into() },
+                ContentPart::ImageUrl { image_url: ImageUrl { url: format!("data:image/jpeg;base64,{}", image_b64) } },
+            ]),
+        }
+    }
+
+    /// The text of the message, without attached images
+    pub fn text(&self) -> String {
+        match &self.content {
+            ChatContent::Text(text) => text.clone(),
+            ChatContent::Parts(parts) => parts.iter()
+                .filter_map(|p| match p {
+                    ContentPart::Text { text } => Some(text.as_str()),
+                    ContentPart::ImageUrl { .. } => None,
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum ChatContent {
+    Text(String),
+    Parts(Vec<ContentPart>),
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ContentPart {
+    Text { text: String },
+    ImageUrl { image_url: ImageUrl },
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ImageUrl {
+    pub url: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ChatCompletionResponse {
+    #[serde(default)]
+    pub choices: Vec<ChatChoice>,
+    #[serde(default)]
+    pub usage: Option<Usage>,
+}
+
+/// Token counts a completion response reports
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+pub struct Usage {
+    #[serde(default)]
+    pub prompt_tokens: u32,
+    #[serde(default)]
+    pub completion_tokens: u32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ChatChoice {
+    pub message: ChatResponseMessage,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ChatResponseMessage {
+    #[serde(default)]
+    pub content: Option<String>,
+}
+
+/// `POST /v1/completions` body, for models that take a formatted prompt
+#[derive(Debug, Clone, Serialize)]
+pub struct CompletionRequest {
+    pub model: String,
+    pub prompt: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub stop: Vec<String>,
+    /// Longest answer, in tokens; the server's default when `None`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u32>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CompletionResponse {
+    #[serde(default)]
+    pub choices: Vec<CompletionChoice>,
+    #[serde(default)]
+    pub usage: Option<Usage>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CompletionChoice {
+    #[serde(default)]
+    pub text: String,
+}
+
+/// `POST /v1/embeddings` body
+#[derive(Debug, Clone, Serialize)]
+pub struct EmbeddingRequest {
+    pub model: String,
+    pub input: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct EmbeddingResponse {
+    #[serde(default)]
+    pub data: Vec<EmbeddingData>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct EmbeddingData {
+    #[serde(default)]
+    pub index: usize,
+    pub embedding: Vec<f32>,
+}
+
+/// `POST /v1/rerank` body
+#[derive(Debug, Clone, Serialize)]
+pub struct RerankRequest {
+    pub model: String,
+    pub query: String,
+    pub documents: Vec<String>,
+    /// Only the best `top_n` are returned; all of them when `None`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_n: Option<usize>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RerankResponse {
+    #[serde(default)]
+    pub results: Vec<RerankResult>,
+}
+
+/// How relevant one of the documents is to the query
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct RerankResult {
+    /// Position of the document in the request
+    pub index: usize,
+    pub relevance_score: f32,
+}
+
+// ─── Audio ────────────────────────────────────────────────────────────────────
+
+/// OminiX-API reads `file` from disk, so there is no upload size limit.
+#[derive(Debug, Clone, Serialize)]
+pub struct TranscriptionRequest {
+    pub file: String,
+    pub model: String,
+    pub response_format: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub timestamp_granularities: Vec<String>,
+    /// Text the speech likely contains, e.g. names to spell right
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prompt: Option<String>,
+}
+
+impl TranscriptionRequest {
+    /// Plain `{"text"}` response
+    pub fn text(model: impl Into<String>, file: impl Into<String>) -> Self {
+        Self {
+            file: file.into(),
+            model: model.into(),
+            response_format: "json".into(),
+            timestamp_granularities: Vec::new(),
+            prompt: None,
+        }
+    }
+
+    /// `verbose_json` with segment timestamps, for subtitle export
+    pub fn with_segments(model: impl Into<String>, file: impl Into<String>) -> Self {
+        Self {
+            file: file.into(),
+            model: model.into(),
+            response_format: "verbose_json".into(),
+            timestamp_granularities: vec!["segment".into()],
+            prompt: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SpeechRequest {
+    pub model: String,
+    pub input: String,
+    pub voice: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub speed: Option<f32>,
+    /// Language hint such as "zh"; the model detects it when `None`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    /// Hz; the model's own rate when `None`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sample_rate: Option<u32>,
+    pub response_format: String,
+}
+
+impl SpeechRequest {
+    pub fn wav(model: impl Into<String>, voice: impl Into<String>, input: impl Into<String>) -> Self {
+        Self::new(model, voice, input, "wav")
+    }
+
+    /// Speech encoded as `response_format`, e.g. "mp3" or "flac"
+    pub fn new(
+        model: impl Into<String>,
+        voice: impl Into<String>,
+        input: impl Into<String>,
+        response_format: impl Into<String>,
+    ) -> Self {
+        Self {
+            model: model.into(),
+            input: input.into(),
+            voice: voice.into(),
+            speed: None,
+            language: None,
+            sample_rate: None,
+            response_format: response_format.into(),
+        }
+    }
+}
+
+// ─── Music generation ─────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MusicGenerationRequest {
+    pub model: String,
+    pub prompt: String,
+    /// Length of the clip in seconds; the model's default when `None`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration: Option<f32>,
+    /// Sampling seed, so a result can be reproduced
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+    pub response_format: String,
+}
+
+impl MusicGenerationRequest {
+    pub fn wav(model: impl Into<String>, prompt: impl Into<String>) -> Self {
+        Self {
+            model: model.into(),
+            prompt: prompt.into(),
+            duration: None,
+            seed: None,
+            response_format: "wav".into(),
+        }
+    }
+}
+
+/// Longest clip a music model is asked for, in seconds
+pub const MAX_MUSIC_SECONDS: f32 = 300.0;
+
+/// Read a duration in seconds typed by the user; empty leaves it to the model
+pub fn parse_duration(text: &str) -> Result<Option<f32>, String> {
+    let text = text.trim().trim_end_matches('s').trim();
+    if text.is_empty() {
+        return Ok(None);
+    }
+    match text.parse::<f32>() {
+        Ok(seconds) if seconds > 0.0 && seconds <= MAX_MUSIC_SECONDS => Ok(Some(seconds)),
+        _ => Err(format!("Duration must be a number of seconds up to {}.", MAX_MUSIC_SECONDS)),
+    }
+}
+
+// ─── Image / video generation ─────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ImageGenerationRequest {
+    pub model: String,
+    pub prompt: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub negative_prompt: Option<String>,
+    /// Base64 reference image for editing, or init image for image-to-image
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+    /// How far image-to-image moves from the init image, 0.0–1.0
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub strength: Option<f32>,
+    /// Base64 inpainting mask; white areas are repainted, black ones kept
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mask: Option<String>,
+    /// Sampling seed, so a result can be reproduced
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+    pub n: u32,
+    pub size: String,
+    pub response_format: String,
+}
+
+impl ImageGenerationRequest {
+    pub fn new(model: impl Into<String>, prompt: impl Into<String>) -> Self {
+        Self {
+            model: model.into(),
+            prompt: prompt.into(),
+            negative_prompt: None,
+            image: None,
+            strength: None,
+            mask: None,
+            seed: None,
+            n: 1,
+            size: "512x512".into(),
+            response_format: "b64_json".into(),
+        }
+    }
+}
+
+/// Image-to-image strength when none is given
+pub const DEFAULT_STRENGTH: f32 = 0.75;
+
+/// Read a strength typed by the user; empty gives [`DEFAULT_STRENGTH`]
+pub fn parse_strength(text: &str) -> Result<f32, String> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Ok(DEFAULT_STRENGTH);
+    }
+    match text.parse::<f32>() {
+        Ok(strength) if (0.0..=1.0).contains(&strength) => Ok(strength),
+        _ => Err("Strength must be a number from 0 to 1.".to_string()),
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct VideoGenerationRequest {
+    pub model: String,
+    pub prompt: String,
+    pub response_format: String,
+}
+
+/// Response of image, video and music generation
+#[derive(Debug, Clone, Deserialize)]
+pub struct GenerationResponse {
+    #[serde(default)]
+    pub data: Vec<GeneratedMedia>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GeneratedMedia {
+    #[serde(default)]
+    pub b64_json: Option<String>,
+    #[serde(default)]
+    pub url: Option<String>,
+}
+
+impl GenerationResponse {
+    /// Decoded bytes of the first result
+    pub fn first_bytes(&self) -> Result<Vec<u8>, OminiXApiError> {
+        let b64 = self.data.first()
+            .and_then(|d| d.b64_json.as_deref())
+            .ok_or_else(|| OminiXApiError::Decode("no b64_json data in response".into()))?;
+        base64::engine::general_purpose::STANDARD.decode(b64)
+            .map_err(|e| OminiXApiError::Decode(e.to_string()))
+    }
+}
+
+// ─── Voices ───────────────────────────────────────────────────────────────────
+
+/// One sample of a training dataset
+#[derive(Debug, Clone, Serialize)]
+pub struct VoiceTrainSample {
+    /// Base64 audio file
+    pub audio_data: String,
+    pub transcript: String,
+}
+
+/// `POST /v1/voices/train` body. The Voice app and the Hub both send it
+/// through [`crate::voice_client`]; `test_train_voice_payload` pins the
+/// field names ominix-api reads.
+#[derive(Debug, Clone, Serialize)]
+pub struct VoiceTrainRequest {
+    pub name: String,
+    /// First sample, repeated for servers that take a single clip
+    pub audio_data: String,
+    pub transcript: String,
+    pub samples: Vec<VoiceTrainSample>,
+    pub quality: String,
+    pub language: String,
+    pub denoise: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct VoiceTrainResponse {
+    task_id: String,
+}
+
+/// `GET /v1/voices/train/status`. Servers report completion either as
+/// `state: "done"|"error"` or as `done: true` / `progress >= 1` / `error`.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct VoiceTrainStatus {
+    #[serde(default)]
+    pub state: String,
+    #[serde(default)]
+    pub stage: String,
+    #[serde(default)]
+    pub progress: f32,
+    #[serde(default)]
+    pub done: bool,
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+impl VoiceTrainStatus {
+    pub fn error_message(&self) -> Option<&str> {
+        match self.error.as_deref().filter(|e| !e.is_empty()) {
+            Some(e) => Some(e),
+            None if self.state == "error" => Some("Unknown error"),
+            None => None,
+        }
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.state == "done" || self.done || self.progress >= 1.0
+    }
+}
+
+/// A voice known to the server
+#[derive(Debug, Clone, PartialEq)]
+pub struct VoiceListEntry {
+    pub name: String,
+    pub is_ready: bool,
+}
+
+/// `GET /v1/voices` comes as `[{name, status}]`, `{"data": [{voice_id|name}]}`
+/// or `{"voices": [name]}` depending on the server version.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum VoicesResponse {
+    Data { data: Vec<VoiceItem> },
+    Voices { voices: Vec<VoiceItem> },
+    List(Vec<VoiceItem>),
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum VoiceItem {
+    Name(String),
+    Entry {
+        #[serde(alias = "voice_id")]
+        name: String,
+        #[serde(default)]
+        status: Option<String>,
+    },
+}
+
+impl VoicesResponse {
+    fn into_entries(self) -> Vec<VoiceListEntry> {
+        let items = match self {
+            Self::Data { data } => data,
+            Self::Voices { voices } => voices,
+            Self::List(list) => list,
+        };
+        items.into_iter().map(VoiceListEntry::from).collect()
+    }
+}
+
+impl From<VoiceItem> for VoiceListEntry {
+    fn from(item: VoiceItem) -> Self {
+        match item {
+            VoiceItem::Name(name) => Self { name, is_ready: true },
+            // Entries without a status are listed only once trained
+            VoiceItem::Entry { name, status } => Self {
+                is_ready: status.as_deref().map_or(true, |s| s == "ready"),
+                name,
+            },
+        }
+    }
+}
+
+// ─── Client ───────────────────────────────────────────────────────────────────
+
+pub struct OminiXApiClient {
+    base_url: String,
+    /// Name the last request is kept under, see [`OminiXApiClient::recording`]
+    recording: Option<&'static str>,
+    #[cfg(not(target_arch = "wasm32"))]
+    http: reqwest::blocking::Client,
+}
+
+impl OminiXApiClient {
+    /// `base_url` is the server root, e.g. `http://localhost:8080`.
+    /// `timeout_secs` bounds each request; generation can take minutes.
+    pub fn new(base_url: impl Into<String>, timeout_secs: u64) -> Self {
+        let mut url = base_url.into();
+        if url.ends_with('/') {
+            url.pop();
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        let http = crate::network::blocking_client()
+            .timeout(std::time::Duration::from_secs(timeout_secs))
+            .build()
+            .unwrap_or_else(|_| reqwest::blocking::Client::new());
+        #[cfg(target_arch = "wasm32")]
+        let _ = timeout_secs;
+        Self {
+            base_url: url,
+            recording: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            http,
+        }
+    }
+
+    /// The local runtime, see [`crate::model_runtime_client::runtime_url`]
+    pub fn localhost(timeout_secs: u64) -> Self {
+        Self::new(crate::model_runtime_client::runtime_url(), timeout_secs)
+    }
+
+    /// Keep the last request this client sends, body and all, as `name`'s in
+    /// [`crate::request_export`]
+    pub fn recording(mut self, name: &'static str) -> Self {
+        self.recording = Some(name);
+        self
+    }
+
+    /// `POST /v1/chat/completions` — returns the first choice's text.
+    pub fn chat_completion(&self, request: &ChatCompletionRequest) -> Result<String, OminiXApiError> {
+        self.chat_completion_with_usage(request).map(|(text, _)| text)
+    }
+
+    /// [`Self::chat_completion`], with the token counts when the server
+    /// reports them
+    pub fn chat_completion_with_usage(&self, request: &ChatCompletionRequest) -> Result<(String, Option<Usage>), OminiXApiError> {
+        let resp: ChatCompletionResponse = self.post_json("/v1/chat/completions", request)?;
+        let usage = resp.usage;
+        resp.choices.into_iter().next()
+            .and_then(|c| c.message.content)
+            .map(|text| (text, usage))
+            .ok_or_else(|| OminiXApiError::Decode("no content in response".into()))
+    }
+
+    /// `POST /v1/chat/completions` streamed: `on_text` gets each piece of the
+    /// answer as it arrives and can return `false` to stop early. Returns the
+    /// text received.
+    pub fn chat_completion_stream(
+        &self,
+        request: &ChatCompletionRequest,
+        mut on_text: impl FnMut(&str) -> bool,
+    ) -> Result<String, OminiXApiError> {
+        let mut body = serde_json::to_value(request).map_err(|e| OminiXApiError::Decode(e.to_string()))?;
+        body["stream"] = serde_json::Value::Bool(true);
+        let mut answer = String::new();
+        let mut error = None;
+        self.post_events("/v1/chat/completions", &body, &mut |data| {
+            let Ok(event) = serde_json::from_str::<serde_json::Value>(data) else { return true };
+            if let Some(err) = event.get("error").filter(|e| !e.is_null()) {
+                error = Some(err.get("message").and_then(|m| m.as_str()).map(String::from).unwrap_or_else(|| err.to_string()));
+                return false;
+            }
+            let choice = &event["choices"][0];
+            let piece = choice["delta"]["content"].as_str().or_else(|| choice["text"].as_str()).unwrap_or("");
+            if piece.is_empty() {
+                return true;
+            }
+            answer.push_str(piece);
+            on_text(piece)
+        })?;
+        match error {
+            Some(e) => Err(OminiXApiError::Api(e)),
+            None => Ok(answer),
+        }
+    }
+
+    /// `POST /v1/completions` — returns the first choice's text.
+    pub fn completion(&self, request: &CompletionRequest) -> Result<String, OminiXApiError> {
+        self.completion_with_usage(request).map(|(text, _)| text)
+    }
+
+    /// [`Self::completion`], with the token counts when the server reports
+    /// them
+    pub fn completion_with_usage(&self, request: &CompletionRequest) -> Result<(String, Option<Usage>), OminiXApiError> {
+        let resp: CompletionResponse = self.post_json("/v1/completions", request)?;
+        let usage = resp.usage;
+        resp.choices.into_iter().next()
+            .map(|c| (c.text, usage))
+            .ok_or_else(|| OminiXApiError::Decode("no choices in response".into()))
+    }
+
+    /// `POST /v1/embeddings` — one vector per input, in input order.
+    pub fn embeddings(&self, request: &EmbeddingRequest) -> Result<Vec<Vec<f32>>, OminiXApiError> {
+        let mut resp: EmbeddingResponse = self.post_json("/v1/embeddings", request)?;
+        if resp.data.len() != request.input.len() {
+            return Err(OminiXApiError::Decode(format!(
+                "{} embeddings for {} inputs", resp.data.len(), request.input.len())));
+        }
+        resp.data.sort_by_key(|d| d.index);
+        Ok(resp.data.into_iter().map(|d| d.embedding).collect())
+    }
+
+    /// `POST /v1/rerank` — the documents' scores, most relevant first.
+    pub fn rerank(&self, request: &RerankRequest) -> Result<Vec<RerankResult>, OminiXApiError> {
+        let mut resp: RerankResponse = self.post_json("/v1/rerank", request)?;
+        if let Some(result) = resp.results.iter().find(|r| r.index >= request.documents.len()) {
+            return Err(OminiXApiError::Decode(format!(
+                "score for document {} of {}", result.index, request.documents.len())));
+        }
+        resp.results.sort_by(|a, b| b.relevance_score.total_cmp(&a.relevance_score));
+        Ok(resp.results)
+    }
+
+    /// `POST /v1/audio/transcriptions`
+    pub fn transcribe(&self, request: &TranscriptionRequest) -> Result<Transcript, OminiXApiError> {
+        let value: serde_json::Value = self.post_json("/v1/audio/transcriptions", request)?;
+        Transcript::from_response(&value).map_err(OminiXApiError::Decode)
+    }
+
+    /// `POST /v1/audio/speech` — returns the encoded audio.
+    pub fn speech(&self, request: &SpeechRequest) -> Result<Vec<u8>, OminiXApiError> {
+        self.post_bytes("/v1/audio/speech", request)
+    }
+
+    /// `POST /v1/audio/generations` streamed: `on_progress` gets the
+    /// fraction done (0.0–1.0) as the server reports it. Returns the encoded
+    /// audio.
+    pub fn generate_music(
+        &self,
+        request: &MusicGenerationRequest,
+        mut on_progress: impl FnMut(f32),
+    ) -> Result<Vec<u8>, OminiXApiError> {
+        let mut body = serde_json::to_value(request).map_err(|e| OminiXApiError::Decode(e.to_string()))?;
+        body["stream"] = serde_json::Value::Bool(true);
+        let mut result = None;
+        let mut error = None;
+        self.post_events("/v1/audio/generations", &body, &mut |data| {
+            let Ok(event) = serde_json::from_str::<serde_json::Value>(data) else { return true };
+            if let Some(err) = event.get("error").filter(|e| !e.is_null()) {
+                error = Some(err.get("message").and_then(|m| m.as_str()).map(String::from).unwrap_or_else(|| err.to_string()));
+                return false;
+            }
+            if let Some(progress) = event.get("progress").and_then(|p| p.as_f64()) {
+                on_progress(progress.clamp(0.0, 1.0) as f32);
+            }
+            if event.get("data").is_some() {
+                result = Some(serde_json::from_value::<GenerationResponse>(event));
+                return false;
+            }
+            true
+        })?;
+        if let Some(e) = error {
+            return Err(OminiXApiError::Api(e));
+        }
+        match result {
+            Some(Ok(resp)) => resp.first_bytes(),
+            Some(Err(e)) => Err(OminiXApiError::Decode(e.to_string())),
+            None => Err(OminiXApiError::Decode("no audio in response".into())),
+        }
+    }
+
+    /// `POST /v1/images/generations` (also used for image edits)
+    pub fn generate_image(&self, request: &ImageGenerationRequest) -> Result<GenerationResponse, OminiXApiError> {
+        self.post_json("/v1/images/generations", request)
+    }
+
+    /// `POST /v1/videos/generations`
+    pub fn generate_video(&self, request: &VideoGenerationRequest) -> Result<GenerationResponse, OminiXApiError> {
+        self.post_json("/v1/videos/generations", request)
+    }
+
+    /// `GET /v1/voices`
+    pub fn list_voices(&self) -> Result<Vec<VoiceListEntry>, OminiXApiError> {
+        let resp: VoicesResponse = self.get_json("/v1/voices")?;
+        Ok(resp.into_entries())
+    }
+
+    /// `POST /v1/voices/train` — returns the task id.
+    pub fn train_voice(&self, request: &VoiceTrainRequest) -> Result<String, OminiXApiError> {
+        let resp: VoiceTrainResponse = self.post_json("/v1/voices/train", request)?;
+        Ok(resp.task_id)
+    }
+
+    /// `GET /v1/voices/train/status?task_id=…`
+    /// A failed task is a successful status response with `error` set.
+    pub fn train_status(&self, task_id: &str) -> Result<VoiceTrainStatus, OminiXApiError> {
+        self.get_status_json(&format!("/v1/voices/train/status?task_id={}", task_id))
+    }
+
+    /// `POST /v1/voices/train/cancel`
+    pub fn cancel_training(&self, task_id: &str) -> Result<(), OminiXApiError> {
+        let _: serde_json::Value = self.post_json("/v1/voices/train/cancel", &serde_json::json!({ "task_id": task_id }))
+            .or_else(|e| match e {
+                // Some servers answer with an empty body
+                OminiXApiError::Decode(_) => Ok(serde_json::Value::Null),
+                e => Err(e),
+            })?;
+        Ok(())
+    }
+
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl OminiXApiClient {
+    // ── Internal ─────────────────────────────────────────────────────────────
+
+    fn get_json<T: serde::de::DeserializeOwned>(&self, path: &str) -> Result<T, OminiXApiError> {
+        let body = self.send("GET", path, None)?;
+        parse_body(&String::from_utf8_lossy(&body))
+    }
+
+    fn post_json<B: Serialize, T: serde::de::DeserializeOwned>(&self, path: &str, body: &B) -> Result<T, OminiXApiError> {
+        let body = self.send("POST", path, Some(encode(body)?))?;
+        parse_body(&String::from_utf8_lossy(&body))
+    }
+
+    fn post_bytes<B: Serialize>(&self, path: &str, body: &B) -> Result<Vec<u8>, OminiXApiError> {
+        self.send("POST", path, Some(encode(body)?))
+    }
+
+    /// Like `get_json`, but an `error` field is left for the caller to read
+    fn get_status_json<T: serde::de::DeserializeOwned>(&self, path: &str) -> Result<T, OminiXApiError> {
+        let body = self.send("GET", path, None)?;
+        serde_json::from_slice(&body).map_err(|e| OminiXApiError::Decode(e.to_string()))
+    }
+
+    /// Send a request answered with server-sent events, passing the data of
+    /// each event to `on_event` until the stream ends, `[DONE]` arrives or
+    /// `on_event` returns `false`
+    fn post_events(
+        &self,
+        path: &str,
+        body: &serde_json::Value,
+        on_event: &mut dyn FnMut(&str) -> bool,
+    ) -> Result<(), OminiXApiError> {
+        use std::io::BufRead;
+        let url = format!("{}{}", self.base_url, path);
+        let body = encode(body)?;
+        let pending = crate::request_log::start(crate::request_log::LOCAL_RUNTIME, "POST", &url, Some(&body));
+        if let Some(name) = self.recording {
+            crate::request_export::record(name, "POST", &url, Some(&body));
+        }
+        let request = self.http.post(&url).header(reqwest::header::CONTENT_TYPE, "application/json").body(body);
+        let resp = match request.send() {
+            Ok(resp) => resp,
+            Err(e) => {
+                pending.fail(None, &e.to_string());
+                return Err(OminiXApiError::Connection(e.to_string()));
+            }
+        };
+        let status = resp.status();
+        if !status.is_success() {
+            let bytes = resp.bytes().map(|b| b.to_vec()).unwrap_or_default();
+            pending.finish(status.as_u16(), &bytes);
+            return Err(OminiXApiError::Http {
+                status: status.as_u16(),
+                body: String::from_utf8_lossy(&bytes).trim().chars().take(300).collect(),
+            });
+        }
+        let mut received = Vec::new();
+        for line in std::io::BufReader::new(resp).lines() {
+            let line = match line {
+                Ok(line) => line,
+                Err(e) => {
+                    pending.fail(Some(status.as_u16()), &e.to_string());
+                    return Err(OminiXApiError::Connection(e.to_string()));
+                }
+            };
+            received.extend_from_slice(line.as_bytes());
+            received.push(b'\n');
+            let Some(data) = line.strip_prefix("data:") else { continue };
+            let data = data.trim();
+            if data == "[DONE]" || !on_event(data) {
+                break;
+            }
+        }
+        pending.finish(status.as_u16(), &received);
+        Ok(())
+    }
+
+    /// Send a request and return the body of a 2xx response, recording both
+    /// in [`crate::request_log`]
+    fn send(&self, method: &str, path: &str, body: Option<Vec<u8>>) -> Result<Vec<u8>, OminiXApiError> {
+        let url = format!("{}{}", self.base_url, path);
+        let pending = crate::request_log::start(crate::request_log::LOCAL_RUNTIME, method, &url, body.as_deref());
+        if let Some(name) = self.recording {
+            crate::request_export::record(name, method, &url, body.as_deref());
+        }
+        let request = match body {
+            Some(body) => self.http.post(&url).header(reqwest::header::CONTENT_TYPE, "application/json").body(body),
+            None => self.http.get(&url),
+        };
+        let resp = match request.send() {
+            Ok(resp) => resp,
+            Err(e) => {
+                pending.fail(None, &e.to_string());
+                return Err(OminiXApiError::Connection(e.to_string()));
+            }
+        };
+        let status = resp.status();
+        let bytes = match resp.bytes() {
+            Ok(bytes) => bytes.to_vec(),
+            Err(e) => {
+                pending.fail(Some(status.as_u16()), &e.to_string());
+                return Err(OminiXApiError::Connection(e.to_string()));
+            }
+        };
+        pending.finish(status.as_u16(), &bytes);
+        if !status.is_success() {
+            return Err(OminiXApiError::Http {
+                status: status.as_u16(),
+                body: String::from_utf8_lossy(&bytes).trim().chars().take(300).collect(),
+            });
+        }
+        Ok(bytes)
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn encode<B: Serialize>(body: &B) -> Result<Vec<u8>, OminiXApiError> {
+    serde_json::to_vec(body).map_err(|e| OminiXApiError::Decode(e.to_string()))
+}
+
+/// The browser build has no blocking HTTP and no local runtime; every call
+/// fails with [`crate::platform::NO_LOCAL_RUNTIME`]
+#[cfg(target_arch = "wasm32")]
+impl OminiXApiClient {
+    fn unavailable<T>(&self) -> Result<T, OminiXApiError> {
+        Err(OminiXApiError::Connection(crate::platform::NO_LOCAL_RUNTIME.to_string()))
+    }
+
+    fn get_json<T: serde::de::DeserializeOwned>(&self, _path: &str) -> Result<T, OminiXApiError> {
+        self.unavailable()
+    }
+
+    fn post_json<B: Serialize, T: serde::de::DeserializeOwned>(&self, _path: &str, _body: &B) -> Result<T, OminiXApiError> {
+        self.unavailable()
+    }
+
+    fn post_bytes<B: Serialize>(&self, _path: &str, _body: &B) -> Result<Vec<u8>, OminiXApiError> {
+        self.unavailable()
+    }
+
+    fn get_status_json<T: serde::de::DeserializeOwned>(&self, _path: &str) -> Result<T, OminiXApiError> {
+        self.unavailable()
+    }
+
+    fn post_events(
+        &self,
+        _path: &str,
+        _body: &serde_json::Value,
+        _on_event: &mut dyn FnMut(&str) -> bool,
+    ) -> Result<(), OminiXApiError> {
+        self.unavailable()
+    }
+}
+
+/// Parse a 2xx body, surfacing an `{"error": ...}` payload as [`OminiXApiError::Api`].
+fn parse_body<T: serde::de::DeserializeOwned>(text: &str) -> Result<T, OminiXApiError> {
+    let value: serde_json::Value = serde_json::from_str(text).map_err(|e| {
+        OminiXApiError::Decode(format!("{} ({})", e, text.chars().take(200).collect::<String>()))
+    })?;
+    if let Some(err) = value.get("error").filter(|e| !e.is_null()) {
+        let msg = err.get("message").and_then(|m| m.as_str())
+            .or_else(|| err.as_str())
+            .map(String::from)
+            .unwrap_or_else(|| err.to_string());
+        return Err(OminiXApiError::Api(msg));
+    }
+    serde_json::from_value(value).map_err(|e| OminiXApiError::Decode(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_image_to_image_fields() {
+        let text = serde_json::to_value(ImageGenerationRequest::new("sdxl", "A fox")).unwrap();
+        assert!(text.get("image").is_none() && text.get("strength").is_none() && text.get("mask").is_none());
+
+        let inpaint = ImageGenerationRequest {
+            image: Some("aW1n".into()),
+            strength: Some(parse_strength("0.5").unwrap()),
+            mask: Some("bWFzaw==".into()),
+            ..ImageGenerationRequest::new("sdxl", "A fox")
+        };
+        let body = serde_json::to_value(inpaint).unwrap();
+        assert_eq!((body["image"].as_str(), body["mask"].as_str()), (Some("aW1n"), Some("bWFzaw==")));
+        assert_eq!(body["strength"], 0.5);
+        assert_eq!(parse_strength(" "), Ok(DEFAULT_STRENGTH));
+        assert!(parse_strength("1.5").is_err());
+    }
+
+    #[test]
+    fn test_voice_list_shapes() {
+        let parse = |s: &str| serde_json::from_str::<VoicesResponse>(s).unwrap().into_entries();
+        let ready = |name: &str, is_ready| VoiceListEntry { name: name.into(), is_ready };
+        assert_eq!(parse(r#"[{"name":"a","status":"training"}]"#), vec![ready("a", false)]);
+        assert_eq!(parse(r#"{"data":[{"voice_id":"b"}]}"#), vec![ready("b", true)]);
+        assert_eq!(parse(r#"{"voices":["c"]}"#), vec![ready("c", true)]);
+    }
+
+    /// The training payload the Voice app and the Hub both send; the field
+    /// names are what ominix-api reads
+    #[test]
+    fn test_train_voice_payload() {
+        let mock = crate::mock_runtime::MockRuntime::start();
+        let sample = VoiceTrainSample { audio_data: "UklGRg==".into(), transcript: "Hello there".into() };
+        let request = VoiceTrainRequest {
+            name: "narrator".into(),
+            audio_data: sample.audio_data.clone(),
+            transcript: sample.transcript.clone(),
+            samples: vec![sample],
+            quality: "standard".into(),
+            language: "en".into(),
+            denoise: true,
+        };
+        let task_id = OminiXApiClient::new(mock.base_url(), 5).train_voice(&request).unwrap();
+        assert_eq!(task_id, "task-1");
+
+        let sent = mock.requests().pop().unwrap();
+        assert_eq!((sent.method.as_str(), sent.path.as_str()), ("POST", "/v1/voices/train"));
+        let body = sent.body;
+        assert_eq!(body["name"], "narrator");
+        assert_eq!(body["audio_data"], "UklGRg==");
+        assert_eq!(body["transcript"], "Hello there");
+        assert_eq!(body["samples"][0]["audio_data"], "UklGRg==");
+        assert_eq!(body["samples"][0]["transcript"], "Hello there");
+        assert_eq!(body["denoise"], true);
+        assert!(body.get("voice_name").is_none() && body.get("audio").is_none());
+    }
+
+    #[test]
+    fn test_rerank() {
+        let mock = crate::mock_runtime::MockRuntime::start();
+        let client = OminiXApiClient::new(mock.base_url(), 5);
+        let request = RerankRequest {
+            model: "bge-reranker-base".into(),
+            query: "mlx quantization".into(),
+            documents: vec!["bread recipe".into(), "quantization on mlx".into(), "mlx install".into()],
+            top_n: None,
+        };
+        assert!(client.rerank(&request).is_err());
+
+        crate::ModelRuntimeClient::new(mock.base_url()).load_model("bge-reranker-base", "rerank").unwrap();
+        let ranked = client.rerank(&request).unwrap();
+        assert_eq!(ranked.iter().map(|r| r.index).collect::<Vec<_>>(), [1, 2, 0]);
+        assert!(ranked[0].relevance_score > ranked[1].relevance_score);
+    }
+
+    #[test]
+    fn test_music_generation() {
+        assert_eq!(parse_duration(""), Ok(None));
+        assert_eq!(parse_duration("30s"), Ok(Some(30.0)));
+        assert!(parse_duration("0").is_err() && parse_duration("600").is_err() && parse_duration("long").is_err());
+
+        let mock = crate::mock_runtime::MockRuntime::start();
+        crate::ModelRuntimeClient::new(mock.base_url()).load_model("musicgen-small", "music").unwrap();
+        let request = MusicGenerationRequest { duration: Some(30.0), ..MusicGenerationRequest::wav("musicgen-small", "Calm piano") };
+        let mut progress = Vec::new();
+        let audio = OminiXApiClient::new(mock.base_url(), 5).generate_music(&request, |p| progress.push(p)).unwrap();
+        assert_eq!(&audio[..4], b"RIFF");
+        assert_eq!(progress, [0.5, 1.0]);
+
+        let body = mock.requests().pop().unwrap().body;
+        assert_eq!(body["duration"], 30.0);
+        assert_eq!(body["stream"], true);
+        assert!(body.get("seed").is_none());
+    }
+
+    #[test]
+    fn test_error_payload_is_surfaced() {
+        let err = parse_body::<GenerationResponse>(r#"{"error":{"message":"model not loaded"}}"#).unwrap_err();
+        assert_eq!(err, OminiXApiError::Api("model not loaded".into()));
+        let err = parse_body::<GenerationResponse>(r#"{"error":"busy"}"#).unwrap_err();
+        assert_eq!(err.to_string(), "API error: busy");
+    }
+
+    /// Status polls read the `error` field themselves, so it isn't turned
+    /// into an error on the way
+    #[test]
+    fn test_train_status() {
+        let mock = crate::mock_runtime::MockRuntime::start();
+        let client = OminiXApiClient::new(mock.base_url(), 5);
+        let sample = VoiceTrainSample { audio_data: "UklGRg==".into(), transcript: "Hi".into() };
+        let request = VoiceTrainRequest {
+            name: "narrator".into(),
+            audio_data: sample.audio_data.clone(),
+            transcript: sample.transcript.clone(),
+            samples: vec![sample],
+            quality: "fast".into(),
+            language: "en".into(),
+            denoise: false,
+        };
+        let task_id = client.train_voice(&request).unwrap();
+
+        let status = client.train_status(&task_id).unwrap();
+        assert_eq!((status.state.as_str(), status.progress, status.error), ("done", 1.0, None));
+        assert!(matches!(client.train_status("task-9"), Err(OminiXApiError::Http { status: 404, .. })));
+    }
+}
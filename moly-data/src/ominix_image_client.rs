@@ -1,7 +1,8 @@
 //! OminiX Image Client - Configurable OpenAI-compatible image generation client
 //!
 //! This client is designed to work with OminiX-API's `/v1/images/generations` endpoint
-//! with full support for configurable parameters like size, model, strength (for img2img), etc.
+//! with full support for configurable parameters like size, model, strength (for img2img),
+//! and a mask for inpainting.
 
 use moly_kit::aitk::protocol::*;
 use moly_kit::aitk::utils::asynchronous::{BoxPlatformSendFuture, BoxPlatformSendStream};
@@ -96,6 +97,8 @@ struct OminiXImageClientInner {
     config: ImageGenerationConfig,
     /// Reference image for img2img (base64 encoded)
     reference_image: Option<String>,
+    /// Inpainting mask for the reference image (base64 encoded)
+    mask_image: Option<String>,
 }
 
 /// OminiX Image Generation Client
@@ -108,6 +111,7 @@ struct OminiXImageClientInner {
 /// - Configurable image size (512x512, 1024x1024, etc.)
 /// - Support for multiple images per request
 /// - Image-to-image (img2img) with reference image and strength
+/// - Inpainting with a mask over the reference image
 /// - Works with both FLUX and Z-Image models
 ///
 /// ## Example
@@ -121,6 +125,9 @@ struct OminiXImageClientInner {
 /// // For img2img
 /// client.set_reference_image(Some(base64_image_data));
 /// client.set_strength(0.75);
+///
+/// // For inpainting, white areas of the mask are repainted
+/// client.set_mask_image(Some(base64_mask_data));
 /// ```
 #[derive(Debug)]
 pub struct OminiXImageClient(Arc<RwLock<OminiXImageClientInner>>);
@@ -135,7 +142,7 @@ impl OminiXImageClient {
     /// Create a new client pointing to the given base URL
     pub fn new(url: String) -> Self {
         let headers = HeaderMap::new();
-        let client = reqwest::Client::builder()
+        let client = crate::network::async_client()
             .timeout(std::time::Duration::from_secs(600)) // 10 minute timeout for image gen
             .build()
             .unwrap_or_else(|_| reqwest::Client::new());
@@ -146,6 +153,7 @@ impl OminiXImageClient {
             headers,
             config: ImageGenerationConfig::default(),
             reference_image: None,
+            mask_image: None,
         };
 
         OminiXImageClient(Arc::new(RwLock::new(inner)))
@@ -195,6 +203,11 @@ impl OminiXImageClient {
         self.0.write().unwrap().reference_image = image_base64;
     }
 
+    /// Set inpainting mask for the reference image (base64 encoded PNG)
+    pub fn set_mask_image(&mut self, mask_base64: Option<String>) {
+        self.0.write().unwrap().mask_image = mask_base64;
+    }
+
     /// Set image size
     pub fn set_size(&mut self, size: impl Into<String>) {
         self.0.write().unwrap().config.size = size.into();
@@ -238,6 +251,9 @@ impl OminiXImageClient {
             if let Some(strength) = inner.config.strength {
                 request_json["strength"] = serde_json::json!(strength);
             }
+            if let Some(mask) = &inner.mask_image {
+                request_json["mask"] = serde_json::json!(mask);
+            }
         }
 
         log::debug!("Image generation request to {}: model={}, size={}",
@@ -0,0 +1,673 @@
+//! Platform services that only exist on desktop
+//!
+//! File dialogs, audio playback and conversion, and revealing files all go
+//! through here instead of calling `rfd` or spawning `afplay`/`afconvert`/
+//! `open` at each call site. Desktop builds get the real implementation;
+//! wasm32, iOS and Android builds compile against stubs that report the
+//! feature as unavailable, so the screens using them degrade (dialogs
+//! return nothing, playback returns an error) instead of failing to build.
+
+use std::path::Path;
+use std::process::{Child, Command};
+
+use crate::quick_ask::Hotkey;
+
+/// Whether this build has the desktop services below
+pub const DESKTOP: bool = cfg!(not(any(target_arch = "wasm32", target_os = "ios", target_os = "android")));
+
+/// Error for local-runtime calls in the browser build, which has no
+/// blocking HTTP and no ominix-api process to talk to
+pub const NO_LOCAL_RUNTIME: &str = "Local models aren't available in the browser";
+
+/// Native file dialogs: [`rfd::FileDialog`] on desktop
+#[cfg(not(any(target_arch = "wasm32", target_os = "ios", target_os = "android")))]
+pub use rfd::FileDialog;
+
+/// Stand-in for `rfd::FileDialog` where there are no native dialogs; every
+/// pick is treated as cancelled
+#[cfg(any(target_arch = "wasm32", target_os = "ios", target_os = "android"))]
+#[derive(Clone, Debug, Default)]
+pub struct FileDialog;
+
+#[cfg(any(target_arch = "wasm32", target_os = "ios", target_os = "android"))]
+impl FileDialog {
+    pub fn new() -> Self { Self }
+    pub fn add_filter(self, _name: impl Into<String>, _extensions: &[impl ToString]) -> Self { self }
+    pub fn set_directory<P: AsRef<Path>>(self, _path: P) -> Self { self }
+    pub fn set_file_name(self, _name: impl Into<String>) -> Self { self }
+    pub fn set_title(self, _title: impl Into<String>) -> Self { self }
+    pub fn pick_file(self) -> Option<std::path::PathBuf> { None }
+    pub fn pick_files(self) -> Option<Vec<std::path::PathBuf>> { None }
+    pub fn pick_folder(self) -> Option<std::path::PathBuf> { None }
+    pub fn save_file(self) -> Option<std::path::PathBuf> { None }
+}
+
+/// Target of [`convert_audio`]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AudioFormat {
+    /// 16 kHz mono 16-bit WAV, what the ASR models take
+    Wav16kMono,
+    /// AAC in an MPEG-4 container
+    Aac,
+    /// AAC audiobook (`.m4b`)
+    M4b,
+    Flac,
+    Mp3,
+}
+
+/// Start playing an audio file. The returned process can be polled with
+/// `try_wait` and killed to stop playback. Besides WAV, Linux plays other
+/// formats with `ffplay` and Windows with the Media Player control.
+pub fn play_audio(path: impl AsRef<Path>) -> Result<Child, String> {
+    let path = path.as_ref();
+    let wav = path.extension().is_some_and(|e| e.eq_ignore_ascii_case("wav"));
+    let quoted = path.display().to_string().replace('\'', "''");
+    let command = if cfg!(target_os = "macos") {
+        Some(("afplay", vec![path.as_os_str().to_owned()]))
+    } else if cfg!(target_os = "linux") && wav {
+        Some(("aplay", vec!["-q".into(), path.as_os_str().to_owned()]))
+    } else if cfg!(target_os = "linux") {
+        Some(("ffplay", vec!["-nodisp".into(), "-autoexit".into(), "-loglevel".into(), "quiet".into(), path.as_os_str().to_owned()]))
+    } else if cfg!(target_os = "windows") && wav {
+        let script = format!("(New-Object Media.SoundPlayer '{}').PlaySync()", quoted);
+        Some(("powershell", vec!["-NoProfile".into(), "-Command".into(), script.into()]))
+    } else if cfg!(target_os = "windows") {
+        let script = format!(
+            "Add-Type -AssemblyName PresentationCore; $p = New-Object System.Windows.Media.MediaPlayer; $p.Open([uri]'{}'); $p.Play(); \
+             do {{ Start-Sleep -Milliseconds 200 }} until ($p.NaturalDuration.HasTimeSpan -and $p.Position -ge $p.NaturalDuration.TimeSpan)",
+            quoted
+        );
+        Some(("powershell", vec!["-NoProfile".into(), "-Command".into(), script.into()]))
+    } else {
+        None
+    };
+    let (program, args) = command.ok_or_else(|| "Audio playback isn't available on this platform".to_string())?;
+    Command::new(program).args(args).spawn().map_err(|e| format!("{} failed: {}", program, e))
+}
+
+/// Convert an audio file with `afconvert` on macOS, or `ffmpeg` elsewhere
+/// and for MP3, which `afconvert` can't write
+pub fn convert_audio(input: impl AsRef<Path>, output: impl AsRef<Path>, format: AudioFormat) -> Result<(), String> {
+    if !DESKTOP {
+        return Err("Audio conversion isn't available on this platform".to_string());
+    }
+    let (program, mut command) = convert_command(input.as_ref(), output.as_ref(), format, cfg!(target_os = "macos"));
+    let result = command.output().map_err(|e| format!("{} not available: {}", program, e))?;
+    if result.status.success() {
+        Ok(())
+    } else {
+        Err(format!("{} failed: {}", program, String::from_utf8_lossy(&result.stderr).trim()))
+    }
+}
+
+/// The conversion [`convert_audio`] runs, with `afconvert` where it's
+/// available and can write `format`
+fn convert_command(input: &Path, output: &Path, format: AudioFormat, afconvert: bool) -> (&'static str, Command) {
+    let afconvert_args = match format {
+        AudioFormat::Wav16kMono => Some(["-f", "WAVE", "-d", "LEI16@16000", "-c", "1"].as_slice()),
+        AudioFormat::Aac => Some(["-f", "mp4f", "-d", "aac"].as_slice()),
+        AudioFormat::M4b => Some(["-f", "m4bf", "-d", "aac"].as_slice()),
+        AudioFormat::Flac => Some(["-f", "flac", "-d", "flac"].as_slice()),
+        AudioFormat::Mp3 => None,
+    };
+    if let Some(args) = afconvert_args.filter(|_| afconvert) {
+        let mut command = Command::new("afconvert");
+        command.args(args);
+        command.arg(input).arg(output);
+        ("afconvert", command)
+    } else {
+        let mut command = Command::new("ffmpeg");
+        command.args(["-y", "-loglevel", "error", "-i"]).arg(input);
+        command.args(match format {
+            AudioFormat::Wav16kMono => ["-ar", "16000", "-ac", "1", "-c:a", "pcm_s16le"].as_slice(),
+            AudioFormat::Aac => ["-c:a", "aac", "-f", "mp4"].as_slice(),
+            AudioFormat::M4b => ["-c:a", "aac", "-f", "ipod"].as_slice(),
+            AudioFormat::Flac => ["-c:a", "flac", "-f", "flac"].as_slice(),
+            AudioFormat::Mp3 => ["-c:a", "libmp3lame", "-f", "mp3"].as_slice(),
+        });
+        command.arg(output);
+        ("ffmpeg", command)
+    }
+}
+
+/// Open a file or folder with its default application
+pub fn open_path(path: impl AsRef<Path>) -> Result<(), String> {
+    let path = path.as_ref();
+    let program = if cfg!(target_os = "macos") {
+        "open"
+    } else if cfg!(target_os = "windows") {
+        "explorer"
+    } else if DESKTOP {
+        "xdg-open"
+    } else {
+        return Err("Opening files isn't available on this platform".to_string());
+    };
+    Command::new(program).arg(path).spawn().map(|_| ()).map_err(|e| format!("{} failed: {}", program, e))
+}
+
+/// Show a file selected in the system file manager. Where that isn't
+/// supported, its folder is opened instead.
+pub fn reveal_path(path: impl AsRef<Path>) -> Result<(), String> {
+    let path = path.as_ref();
+    let spawned = if cfg!(target_os = "macos") {
+        Command::new("open").arg("-R").arg(path).spawn()
+    } else if cfg!(target_os = "windows") {
+        Command::new("explorer").arg(format!("/select,{}", path.display())).spawn()
+    } else {
+        return open_path(path.parent().unwrap_or(path));
+    };
+    spawned.map(|_| ()).map_err(|e| e.to_string())
+}
+
+/// Show a desktop notification: `osascript` on macOS, `notify-send` on
+/// Linux and a tray balloon from PowerShell on Windows
+pub fn notify(title: &str, body: &str) -> Result<(), String> {
+    if cfg!(target_os = "macos") {
+        let quote = |s: &str| s.replace('\\', "\\\\").replace('"', "\\\"");
+        let script = format!("display notification \"{}\" with title \"{}\"", quote(body), quote(title));
+        run_checked("osascript", Command::new("osascript").args(["-e", &script]))
+    } else if cfg!(target_os = "windows") {
+        let quote = |s: &str| s.replace('\'', "''");
+        let script = format!(
+            "Add-Type -AssemblyName System.Windows.Forms; $n = New-Object System.Windows.Forms.NotifyIcon; \
+            $n.Icon = [System.Drawing.SystemIcons]::Information; $n.Visible = $true; \
+            $n.ShowBalloonTip(5000, '{}', '{}', 'Info'); Start-Sleep -Seconds 6; $n.Dispose()",
+            quote(title),
+            quote(body)
+        );
+        // The script stays up while the balloon shows; don't wait for it
+        Command::new("powershell")
+            .args(["-NoProfile", "-Command", &script])
+            .spawn()
+            .map(|_| ())
+            .map_err(|e| format!("powershell failed: {}", e))
+    } else if DESKTOP {
+        run_checked("notify-send", Command::new("notify-send").args(["--app-name=OminiX Studio", title, body]))
+    } else {
+        Err("Notifications aren't available on this platform".to_string())
+    }
+}
+
+/// The text on the system clipboard, empty when it holds none. Uses
+/// `pbpaste` on macOS, PowerShell on Windows, and `wl-paste`, `xclip` or
+/// `xsel` on Linux.
+pub fn read_clipboard() -> Result<String, String> {
+    let candidates: &[(&str, &[&str])] = if cfg!(target_os = "macos") {
+        &[("pbpaste", &[])]
+    } else if cfg!(target_os = "windows") {
+        &[("powershell", &["-NoProfile", "-Command", "Get-Clipboard -Raw"])]
+    } else if DESKTOP && std::env::var_os("WAYLAND_DISPLAY").is_some() {
+        &[("wl-paste", &["-n"]), ("xclip", &["-selection", "clipboard", "-o"]), ("xsel", &["-b", "-o"])]
+    } else if DESKTOP {
+        &[("xclip", &["-selection", "clipboard", "-o"]), ("xsel", &["-b", "-o"])]
+    } else {
+        return Err("The clipboard isn't available on this platform".to_string());
+    };
+    for (program, args) in candidates {
+        match Command::new(program).args(*args).output() {
+            // An empty clipboard or one holding an image is an error for most tools
+            Ok(output) if !output.status.success() => return Ok(String::new()),
+            Ok(output) => return Ok(String::from_utf8_lossy(&output.stdout).into_owned()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(format!("{} failed: {}", program, e)),
+        }
+    }
+    Err("No clipboard tool found; install wl-clipboard, xclip or xsel".to_string())
+}
+
+/// Save a screenshot to `output` as PNG: a region the user drags out, or the
+/// whole screen. Uses `screencapture` on macOS and the first of
+/// `gnome-screenshot`, `spectacle`, `grim` (with `slurp`) or ImageMagick's
+/// `import` found on Linux; Windows can only capture the whole screen.
+/// Blocks until the capture is taken. Returns `Ok(false)` when the user
+/// cancelled the selection.
+pub fn capture_screen(output: impl AsRef<Path>, region: bool) -> Result<bool, String> {
+    let output = output.as_ref();
+    let _ = std::fs::remove_file(output);
+    if cfg!(target_os = "macos") {
+        let mut command = Command::new("screencapture");
+        command.arg("-x");
+        if region {
+            command.arg("-i");
+        }
+        run_capture("screencapture", command.arg(output))?;
+    } else if cfg!(target_os = "windows") {
+        if region {
+            return Err("Capturing a region isn't available on Windows; capture the whole screen instead".to_string());
+        }
+        let script = format!(
+            "Add-Type -AssemblyName System.Windows.Forms,System.Drawing; \
+            $b = [System.Windows.Forms.SystemInformation]::VirtualScreen; \
+            $img = New-Object System.Drawing.Bitmap $b.Width, $b.Height; \
+            [System.Drawing.Graphics]::FromImage($img).CopyFromScreen($b.Left, $b.Top, 0, 0, $img.Size); \
+            $img.Save('{}')",
+            output.display().to_string().replace('\'', "''")
+        );
+        run_capture("powershell", Command::new("powershell").args(["-NoProfile", "-Command", &script]))?;
+    } else if DESKTOP {
+        capture_screen_linux(output, region)?;
+    } else {
+        return Err("Screen capture isn't available on this platform".to_string());
+    }
+    Ok(output.exists())
+}
+
+fn capture_screen_linux(output: &Path, region: bool) -> Result<(), String> {
+    let mut gnome = Command::new("gnome-screenshot");
+    if region {
+        gnome.arg("-a");
+    }
+    let mut spectacle = Command::new("spectacle");
+    spectacle.args(["-b", "-n", if region { "-r" } else { "-f" }, "-o"]).arg(output);
+    let mut import = Command::new("import");
+    if !region {
+        import.args(["-window", "root"]);
+    }
+    let candidates = [("gnome-screenshot", gnome.arg("-f").arg(output)), ("spectacle", &mut spectacle)];
+    for (program, command) in candidates {
+        match run_capture(program, command) {
+            Err(e) if e.ends_with(NOT_INSTALLED) => continue,
+            result => return result,
+        }
+    }
+    // grim only captures; slurp lets the user pick the region
+    let mut grim = Command::new("grim");
+    let selection = if region { Command::new("slurp").output().ok() } else { None };
+    match &selection {
+        // Escape in slurp: cancelled
+        Some(selection) if !selection.status.success() => return Ok(()),
+        Some(selection) => {
+            grim.args(["-g", String::from_utf8_lossy(&selection.stdout).trim()]);
+        }
+        None => {}
+    }
+    if !region || selection.is_some() {
+        match run_capture("grim", grim.arg(output)) {
+            Err(e) if e.ends_with(NOT_INSTALLED) => {}
+            result => return result,
+        }
+    }
+    run_capture("import", import.arg(output)).map_err(|e| {
+        if e.ends_with(NOT_INSTALLED) {
+            "No screenshot tool found; install gnome-screenshot, spectacle, grim or ImageMagick".to_string()
+        } else {
+            e
+        }
+    })
+}
+
+const NOT_INSTALLED: &str = "is not installed";
+
+fn run_capture(program: &str, command: &mut Command) -> Result<(), String> {
+    match command.output() {
+        // Tools exit with an error when the selection is cancelled; the
+        // missing file tells the caller
+        Ok(_) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Err(format!("{} {}", program, NOT_INSTALLED)),
+        Err(e) => Err(format!("{} failed: {}", program, e)),
+    }
+}
+
+/// Render an HTML file offscreen to a PNG of `width` by `height` CSS pixels,
+/// at twice that resolution. Uses the first headless Chrome, Edge or
+/// Chromium found, or `wkhtmltoimage`, which sizes the height itself.
+/// Blocks until the image is written.
+pub fn render_html_to_png(html: impl AsRef<Path>, output: impl AsRef<Path>, width: u32, height: u32) -> Result<(), String> {
+    let (html, output) = (html.as_ref(), output.as_ref());
+    let _ = std::fs::remove_file(output);
+    let browsers: &[&str] = if cfg!(target_os = "macos") {
+        &[
+            "/Applications/Google Chrome.app/Contents/MacOS/Google Chrome",
+            "/Applications/Microsoft Edge.app/Contents/MacOS/Microsoft Edge",
+            "/Applications/Chromium.app/Contents/MacOS/Chromium",
+        ]
+    } else if cfg!(target_os = "windows") {
+        &[
+            "C:\\Program Files (x86)\\Microsoft\\Edge\\Application\\msedge.exe",
+            "C:\\Program Files\\Google\\Chrome\\Application\\chrome.exe",
+        ]
+    } else if DESKTOP {
+        &["chromium", "chromium-browser", "google-chrome", "microsoft-edge"]
+    } else {
+        return Err("Rendering images isn't available on this platform".to_string());
+    };
+    let url = format!("file:///{}", html.display().to_string().replace('\\', "/").trim_start_matches('/'));
+    for browser in browsers {
+        let mut command = Command::new(browser);
+        command
+            .args(["--headless", "--disable-gpu", "--hide-scrollbars", "--force-device-scale-factor=2"])
+            .arg(format!("--window-size={},{}", width, height))
+            .arg(format!("--screenshot={}", output.display()))
+            .arg(&url);
+        match run_capture(browser, &mut command) {
+            Err(e) if e.ends_with(NOT_INSTALLED) => continue,
+            Err(e) => return Err(e),
+            Ok(()) if output.exists() => return Ok(()),
+            Ok(()) => return Err(format!("{} wrote no image", browser)),
+        }
+    }
+    let mut command = Command::new("wkhtmltoimage");
+    command.args(["--quiet", "--zoom", "2", "--width"]).arg((width * 2).to_string()).arg(html).arg(output);
+    match run_capture("wkhtmltoimage", &mut command) {
+        Err(e) if e.ends_with(NOT_INSTALLED) => {
+            Err("No headless browser found; install Chrome, Edge, Chromium or wkhtmltoimage".to_string())
+        }
+        Err(e) => Err(e),
+        Ok(()) if output.exists() => Ok(()),
+        Ok(()) => Err("wkhtmltoimage wrote no image".to_string()),
+    }
+}
+
+/// Put a PNG on the system clipboard: `osascript` on macOS, PowerShell on
+/// Windows, and `wl-copy` or `xclip` on Linux
+pub fn copy_image_to_clipboard(png: impl AsRef<Path>) -> Result<(), String> {
+    let png = png.as_ref();
+    if cfg!(target_os = "macos") {
+        let script = format!(
+            "set the clipboard to (read (POSIX file \"{}\") as «class PNGf»)",
+            png.display().to_string().replace('\\', "\\\\").replace('"', "\\\"")
+        );
+        return run_checked("osascript", Command::new("osascript").args(["-e", &script]));
+    } else if cfg!(target_os = "windows") {
+        let script = format!(
+            "Add-Type -AssemblyName System.Windows.Forms,System.Drawing; \
+            [System.Windows.Forms.Clipboard]::SetImage([System.Drawing.Image]::FromFile('{}'))",
+            png.display().to_string().replace('\'', "''")
+        );
+        return run_checked("powershell", Command::new("powershell").args(["-NoProfile", "-STA", "-Command", &script]));
+    } else if !DESKTOP {
+        return Err("The clipboard isn't available on this platform".to_string());
+    }
+    let file = std::fs::File::open(png).map_err(|e| format!("{}: {}", png.display(), e))?;
+    let mut wl_copy = Command::new("wl-copy");
+    wl_copy.args(["--type", "image/png"]).stdin(file);
+    let mut xclip = Command::new("xclip");
+    xclip.args(["-selection", "clipboard", "-t", "image/png", "-i"]).arg(png);
+    let candidates = if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+        [("wl-copy", wl_copy), ("xclip", xclip)]
+    } else {
+        [("xclip", xclip), ("wl-copy", wl_copy)]
+    };
+    for (program, mut command) in candidates {
+        // Both stay behind to serve the clipboard; don't wait on their output
+        command.stdout(std::process::Stdio::null()).stderr(std::process::Stdio::null());
+        match command.status() {
+            Ok(status) if status.success() => return Ok(()),
+            Ok(status) => return Err(format!("{} failed: {}", program, status)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(format!("{} failed: {}", program, e)),
+        }
+    }
+    Err("No clipboard tool found; install wl-clipboard or xclip".to_string())
+}
+
+/// Bind `hotkey` to `command` system-wide: a GNOME custom keybinding on
+/// Linux, or a Start menu entry with a shortcut key on Windows. Registering
+/// again replaces the earlier binding. macOS has no way to do this outside
+/// System Settings.
+pub fn register_shortcut(name: &str, hotkey: &Hotkey, command: &[String]) -> Result<(), String> {
+    if cfg!(target_os = "macos") {
+        return Err(format!(
+            "macOS binds shortcuts in System Settings. Make a Shortcuts action that runs \"{}\" and give it {} there.",
+            shell_command(command),
+            hotkey_label(hotkey)
+        ));
+    } else if cfg!(target_os = "windows") {
+        let programs = std::env::var("APPDATA").map_err(|_| "APPDATA isn't set".to_string())?;
+        let link = Path::new(&programs).join("Microsoft\\Windows\\Start Menu\\Programs").join(format!("{}.lnk", name));
+        let quote = |text: &str| format!("'{}'", text.replace('\'', "''"));
+        let script = format!(
+            "$s = (New-Object -ComObject WScript.Shell).CreateShortcut({}); \
+            $s.TargetPath = {}; $s.Arguments = {}; $s.Hotkey = {}; $s.Save()",
+            quote(&link.display().to_string()),
+            quote(command.first().map(String::as_str).unwrap_or_default()),
+            quote(&command[1.min(command.len())..].join(" ")),
+            quote(&hotkey.windows()?)
+        );
+        return run_checked("powershell", Command::new("powershell").args(["-NoProfile", "-Command", &script]));
+    } else if !DESKTOP {
+        return Err("Keyboard shortcuts aren't available on this platform".to_string());
+    }
+    // GNOME keeps custom keybindings as a list of paths, each with its own keys
+    const SCHEMA: &str = "org.gnome.settings-daemon.plugins.media-keys";
+    let path = format!(
+        "/org/gnome/settings-daemon/plugins/media-keys/custom-keybindings/{}/",
+        name.to_ascii_lowercase().replace(|c: char| !c.is_ascii_alphanumeric(), "-")
+    );
+    let output = Command::new("gsettings")
+        .args(["get", SCHEMA, "custom-keybindings"])
+        .output()
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => "Only GNOME shortcuts can be added from here; bind the command in your desktop's keyboard settings".to_string(),
+            _ => format!("gsettings failed: {}", e),
+        })?;
+    let current = String::from_utf8_lossy(&output.stdout);
+    let mut paths: Vec<String> = current
+        .split('\'')
+        .skip(1)
+        .step_by(2)
+        .map(String::from)
+        .collect();
+    if !paths.contains(&path) {
+        paths.push(path.clone());
+        let list = format!("[{}]", paths.iter().map(|p| format!("'{}'", p)).collect::<Vec<_>>().join(", "));
+        run_checked("gsettings", Command::new("gsettings").args(["set", SCHEMA, "custom-keybindings", &list]))?;
+    }
+    let keybinding = format!("{}.custom-keybinding:{}", SCHEMA, path);
+    let quote = |text: &str| format!("'{}'", text.replace('\\', "\\\\").replace('\'', "\\'"));
+    for (key, value) in [("name", name.to_string()), ("command", shell_command(command)), ("binding", hotkey.gnome())] {
+        run_checked("gsettings", Command::new("gsettings").args(["set", &keybinding, key, &quote(&value)]))?;
+    }
+    Ok(())
+}
+
+/// Have links with `scheme` open with `command`, which gets the link as its
+/// last argument: an entry in the user's registry classes on Windows, or a
+/// desktop entry set as the scheme's handler elsewhere. macOS apps declare
+/// their schemes in the app bundle instead.
+pub fn register_url_scheme(scheme: &str, command: &[String]) -> Result<(), String> {
+    if cfg!(target_os = "macos") {
+        return Err("macOS takes URL schemes from the app bundle".to_string());
+    } else if cfg!(target_os = "windows") {
+        let key = format!("HKCU\\Software\\Classes\\{}", scheme);
+        let open = format!("{} \"%1\"", shell_command(command));
+        run_checked("reg", Command::new("reg").args(["add", &key, "/ve", "/d", &format!("URL:{} link", scheme), "/f"]))?;
+        run_checked("reg", Command::new("reg").args(["add", &key, "/v", "URL Protocol", "/d", "", "/f"]))?;
+        return run_checked("reg", Command::new("reg").args(["add", &format!("{}\\shell\\open\\command", key), "/ve", "/d", &open, "/f"]));
+    } else if !DESKTOP {
+        return Err("Links can't open the app on this platform".to_string());
+    }
+    let dir = dirs::data_dir().ok_or("No data directory")?.join("applications");
+    std::fs::create_dir_all(&dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+    let name = format!("ominix-studio-{}.desktop", scheme);
+    let entry = format!(
+        "[Desktop Entry]\nType=Application\nName=OminiX Studio\nExec={} %u\nMimeType=x-scheme-handler/{};\nNoDisplay=true\n",
+        shell_command(command),
+        scheme
+    );
+    std::fs::write(dir.join(&name), entry).map_err(|e| format!("{}: {}", name, e))?;
+    run_checked("xdg-mime", Command::new("xdg-mime").args(["default", &name, &format!("x-scheme-handler/{}", scheme)]))
+}
+
+/// `command` as a shell would take it, quoting words with spaces
+fn shell_command(command: &[String]) -> String {
+    command.iter()
+        .map(|word| if word.contains(' ') { format!("\"{}\"", word) } else { word.clone() })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn hotkey_label(hotkey: &Hotkey) -> String {
+    let mut parts = Vec::new();
+    for (on, name) in [(hotkey.ctrl, "Control"), (hotkey.alt, "Option"), (hotkey.shift, "Shift"), (hotkey.super_key, "Command")] {
+        if on {
+            parts.push(name.to_string());
+        }
+    }
+    parts.push(hotkey.key.clone());
+    parts.join("+")
+}
+
+/// Run `command`, turning a failed exit into its error output
+fn run_checked(program: &str, command: &mut Command) -> Result<(), String> {
+    let output = command.output().map_err(|e| format!("{} failed: {}", program, e))?;
+    if output.status.success() {
+        return Ok(());
+    }
+    let error = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    Err(if error.is_empty() { format!("{} failed", program) } else { format!("{} failed: {}", program, error) })
+}
+
+/// Free bytes on the volume holding `path`, or its nearest existing ancestor
+/// when it doesn't exist yet. `None` where this can't be determined.
+pub fn available_space(path: impl AsRef<Path>) -> Option<u64> {
+    let mut path = path.as_ref();
+    while !path.exists() {
+        path = path.parent()?;
+    }
+    volume_available_space(path)
+}
+
+#[cfg(unix)]
+fn volume_available_space(path: &Path) -> Option<u64> {
+    use std::os::unix::ffi::OsStrExt;
+    let path = std::ffi::CString::new(path.as_os_str().as_bytes()).ok()?;
+    // SAFETY: `path` is NUL-terminated and `stat` is a plain C struct
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statvfs(path.as_ptr(), &mut stat) } != 0 {
+        return None;
+    }
+    Some(stat.f_bavail as u64 * stat.f_frsize as u64)
+}
+
+#[cfg(not(unix))]
+fn volume_available_space(_path: &Path) -> Option<u64> {
+    None
+}
+
+/// Installed RAM in bytes. `None` where this can't be determined.
+#[cfg(target_os = "macos")]
+pub fn total_memory() -> Option<u64> {
+    let mut bytes: u64 = 0;
+    let mut len = std::mem::size_of::<u64>();
+    // SAFETY: hw.memsize is a 64-bit integer and `len` is its size
+    let result = unsafe {
+        libc::sysctlbyname(
+            b"hw.memsize\0".as_ptr() as *const libc::c_char,
+            &mut bytes as *mut u64 as *mut libc::c_void,
+            &mut len,
+            std::ptr::null_mut(),
+            0,
+        )
+    };
+    (result == 0 && bytes > 0).then_some(bytes)
+}
+
+/// Installed RAM in bytes. `None` where this can't be determined.
+#[cfg(target_os = "linux")]
+pub fn total_memory() -> Option<u64> {
+    let meminfo = std::fs::read_to_string("/proc/meminfo").ok()?;
+    let line = meminfo.lines().find(|l| l.starts_with("MemTotal:"))?;
+    let kb: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kb * 1024)
+}
+
+/// Installed RAM in bytes. `None` where this can't be determined.
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
+pub fn total_memory() -> Option<u64> {
+    None
+}
+
+/// Whether the computer is running on battery. `None` where this can't be
+/// determined, e.g. on desktops without one.
+#[cfg(target_os = "macos")]
+pub fn on_battery() -> Option<bool> {
+    let output = Command::new("pmset").args(["-g", "batt"]).output().ok()?;
+    let text = String::from_utf8_lossy(&output.stdout);
+    if !text.contains("InternalBattery") {
+        return None;
+    }
+    Some(text.contains("'Battery Power'"))
+}
+
+/// Whether the computer is running on battery. `None` where this can't be
+/// determined, e.g. on desktops without one.
+#[cfg(target_os = "linux")]
+pub fn on_battery() -> Option<bool> {
+    let mut battery = None;
+    for entry in std::fs::read_dir("/sys/class/power_supply").ok()?.flatten() {
+        let read = |name: &str| std::fs::read_to_string(entry.path().join(name)).unwrap_or_default().trim().to_string();
+        match read("type").as_str() {
+            "Mains" if read("online") == "1" => return Some(false),
+            "Battery" => battery = Some(battery.unwrap_or(false) || read("status") == "Discharging"),
+            _ => {}
+        }
+    }
+    battery
+}
+
+/// Whether the computer is running on battery. `None` where this can't be
+/// determined, e.g. on desktops without one.
+#[cfg(target_os = "windows")]
+pub fn on_battery() -> Option<bool> {
+    let output = Command::new("powershell")
+        .args(["-NoProfile", "-Command", "(Get-CimInstance Win32_Battery).BatteryStatus"])
+        .output()
+        .ok()?;
+    // 1 means discharging; nothing is printed without a battery
+    let status: u32 = String::from_utf8_lossy(&output.stdout).lines().next()?.trim().parse().ok()?;
+    Some(status == 1)
+}
+
+/// Whether the computer is running on battery. `None` where this can't be
+/// determined, e.g. on desktops without one.
+#[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
+pub fn on_battery() -> Option<bool> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(command: &Command) -> Vec<String> {
+        command.get_args().map(|a| a.to_string_lossy().into_owned()).collect()
+    }
+
+    #[test]
+    fn test_convert_command() {
+        let (input, output) = (Path::new("in.m4a"), Path::new("out.wav"));
+        let (program, command) = convert_command(input, output, AudioFormat::Wav16kMono, true);
+        assert_eq!(program, "afconvert");
+        assert_eq!(args(&command), ["-f", "WAVE", "-d", "LEI16@16000", "-c", "1", "in.m4a", "out.wav"]);
+
+        // afconvert can't write MP3, and isn't there off macOS
+        assert_eq!(convert_command(input, output, AudioFormat::Mp3, true).0, "ffmpeg");
+        let (program, command) = convert_command(input, output, AudioFormat::Wav16kMono, false);
+        assert_eq!(program, "ffmpeg");
+        assert_eq!(
+            args(&command),
+            ["-y", "-loglevel", "error", "-i", "in.m4a", "-ar", "16000", "-ac", "1", "-c:a", "pcm_s16le", "out.wav"]
+        );
+    }
+
+    /// Desktop builds get the real services; a missing file is an error, not
+    /// a stub's "unavailable"
+    #[test]
+    fn test_desktop_services() {
+        assert_eq!(DESKTOP, cfg!(any(target_os = "macos", target_os = "linux", target_os = "windows")));
+        let missing = std::env::temp_dir().join("moly-platform-test-missing.wav");
+        let error = convert_audio(&missing, missing.with_extension("flac"), AudioFormat::Flac).unwrap_err();
+        assert!(!error.contains("isn't available on this platform"), "{}", error);
+    }
+
+    /// Screenshot tools are tried in turn: a missing one moves on to the
+    /// next, and one that exits with an error means the user cancelled
+    #[cfg(unix)]
+    #[test]
+    fn test_run_capture_outcomes() {
+        let missing = run_capture("moly-no-such-tool", &mut Command::new("moly-no-such-tool")).unwrap_err();
+        assert!(missing.ends_with(NOT_INSTALLED), "{}", missing);
+        assert_eq!(run_capture("false", &mut Command::new("false")), Ok(()));
+    }
+}
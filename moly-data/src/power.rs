@@ -0,0 +1,198 @@
+//! Low power mode
+//!
+//! Turned on by hand in Settings, or by itself while the computer runs on
+//! battery. While it is active ([`is_active`]) the app checks things in the
+//! background less often ([`interval`]), one model downloads at a time with
+//! the others paused in the queue ([`crate::download_settings::acquire_slot`]),
+//! and loading a model that needs a lot of memory asks first
+//! ([`load_warning`]).
+//!
+//! Settings change it with [`crate::StoreAction::SetPowerSettings`]. The
+//! shell saves the change, follows the power source ([`BatteryWatcher`]) and
+//! announces each switch with [`crate::StoreEvent::PowerModeChanged`].
+
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, OnceLock, RwLock};
+use std::time::Duration;
+
+/// How much less often background checks run in low power mode
+pub const SLOWDOWN: f64 = 4.0;
+
+/// How often the power source is checked
+const BATTERY_POLL: Duration = Duration::from_secs(30);
+
+/// Low power settings, stored in preferences
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PowerSettings {
+    /// Low power mode turned on by hand
+    pub low_power: bool,
+    /// Turn it on while the computer runs on battery
+    pub on_battery: bool,
+    /// Loading a model needing at least this many GB asks first
+    pub large_model_gb: f32,
+}
+
+impl Default for PowerSettings {
+    fn default() -> Self {
+        Self { low_power: false, on_battery: true, large_model_gb: 8.0 }
+    }
+}
+
+impl PowerSettings {
+    /// Whether low power mode applies, given the power source
+    pub fn active(&self, on_battery: bool) -> bool {
+        self.low_power || (self.on_battery && on_battery)
+    }
+
+    /// What to ask before loading a model needing `memory_gb` while low
+    /// power mode applies; `None` when there's nothing to ask
+    pub fn load_warning(&self, on_battery: bool, name: &str, memory_gb: f32) -> Option<String> {
+        if !self.active(on_battery) || memory_gb < self.large_model_gb.max(0.1) {
+            return None;
+        }
+        Some(format!(
+            "Low power mode is on and {} needs about {:.0} GB of memory. Running it drains the battery quickly.",
+            name, memory_gb
+        ))
+    }
+
+    /// e.g. `Low power mode is on (running on battery)`
+    pub fn status(&self, on_battery: bool) -> String {
+        match (self.active(on_battery), self.low_power) {
+            (true, true) => "Low power mode is on".to_string(),
+            (true, false) => "Low power mode is on (running on battery)".to_string(),
+            (false, _) if on_battery => "Running on battery".to_string(),
+            (false, _) => "Low power mode is off".to_string(),
+        }
+    }
+}
+
+// ─── Mirrored state ───────────────────────────────────────────────────────────
+
+#[derive(Default)]
+struct PowerState {
+    settings: PowerSettings,
+    on_battery: bool,
+}
+
+fn state() -> &'static RwLock<PowerState> {
+    static STATE: OnceLock<RwLock<PowerState>> = OnceLock::new();
+    STATE.get_or_init(Default::default)
+}
+
+/// Mirror [`crate::Preferences::power`]
+pub fn set_current(settings: &PowerSettings) {
+    state().write().unwrap().settings = settings.clone();
+    crate::download_settings::wake_queue();
+}
+
+pub fn current() -> PowerSettings {
+    state().read().unwrap().settings.clone()
+}
+
+/// Record the power source, as reported by [`BatteryWatcher`]
+pub fn set_on_battery(on_battery: bool) {
+    state().write().unwrap().on_battery = on_battery;
+    crate::download_settings::wake_queue();
+}
+
+/// Whether the computer was last seen running on battery
+pub fn on_battery() -> bool {
+    state().read().unwrap().on_battery
+}
+
+/// Whether low power mode is in effect
+pub fn is_active() -> bool {
+    let state = state().read().unwrap();
+    state.settings.active(state.on_battery)
+}
+
+/// Seconds between background checks that normally run every `normal` seconds
+pub fn interval(normal: f64) -> f64 {
+    if is_active() { normal * SLOWDOWN } else { normal }
+}
+
+/// See [`PowerSettings::load_warning`]
+pub fn load_warning(name: &str, memory_gb: f32) -> Option<String> {
+    let state = state().read().unwrap();
+    state.settings.load_warning(state.on_battery, name, memory_gb)
+}
+
+/// See [`PowerSettings::status`]
+pub fn status() -> String {
+    let state = state().read().unwrap();
+    state.settings.status(state.on_battery)
+}
+
+/// Checks the power source on its own thread and reports when it changes.
+/// The thread stops when the watcher is dropped.
+pub struct BatteryWatcher {
+    rx: mpsc::Receiver<bool>,
+    stop: Arc<AtomicBool>,
+}
+
+impl BatteryWatcher {
+    /// Start watching; the current power source is reported first. Nothing
+    /// is reported where it can't be determined, e.g. on desktops.
+    pub fn start() -> Self {
+        let (tx, rx) = mpsc::channel();
+        let stop = Arc::new(AtomicBool::new(false));
+        let stopped = stop.clone();
+        std::thread::spawn(move || {
+            let mut last = None;
+            while !stopped.load(Ordering::Relaxed) {
+                let now = crate::platform::on_battery();
+                if now.is_some() && now != last {
+                    last = now;
+                    if tx.send(now.unwrap_or(false)).is_err() {
+                        return;
+                    }
+                    makepad_widgets::SignalToUI::set_ui_signal();
+                }
+                // Wake up now and then to notice the watcher was dropped
+                for _ in 0..BATTERY_POLL.as_secs() {
+                    if stopped.load(Ordering::Relaxed) { return; }
+                    std::thread::sleep(Duration::from_secs(1));
+                }
+            }
+        });
+        Self { rx, stop }
+    }
+
+    /// The power source if it changed since the last call: `true` on battery
+    pub fn poll(&self) -> Option<bool> {
+        self.rx.try_iter().last()
+    }
+}
+
+impl Drop for BatteryWatcher {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_active_and_warning() {
+        let settings = PowerSettings::default();
+        assert!(!settings.active(false));
+        assert!(settings.active(true));
+        assert!(!PowerSettings { on_battery: false, ..settings.clone() }.active(true));
+        assert!(PowerSettings { low_power: true, ..settings.clone() }.active(false));
+
+        assert_eq!(settings.status(true), "Low power mode is on (running on battery)");
+        assert_eq!(PowerSettings { on_battery: false, ..settings.clone() }.status(true), "Running on battery");
+
+        assert_eq!(settings.load_warning(false, "Qwen3 32B", 20.0), None);
+        assert_eq!(settings.load_warning(true, "Qwen3 4B", 3.0), None);
+        assert_eq!(
+            settings.load_warning(true, "Qwen3 32B", 19.6).unwrap(),
+            "Low power mode is on and Qwen3 32B needs about 20 GB of memory. Running it drains the battery quickly."
+        );
+    }
+}
@@ -1,7 +1,26 @@
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
-use crate::providers::{get_supported_providers, ProviderId, ProviderPreferences};
+use crate::api_proxy::ApiProxySettings;
+use crate::asr_cleanup::AsrSettings;
+use crate::chat_backup::ChatBackupSettings;
+use crate::clipboard_watch::ClipboardWatchSettings;
+use crate::code_tool::CodeToolSettings;
+use crate::download_settings::DownloadSettings;
+use crate::mcp_servers::McpServerConfig;
+use crate::network::NetworkSettings;
+use crate::power::PowerSettings;
+use crate::profiles;
+use crate::semantic_search::SemanticSearchSettings;
+use crate::system_prompts::SystemPromptPresets;
+use crate::telemetry::TelemetrySettings;
+use crate::transcribe_watch::WatchFolderSettings;
+use crate::tts_output::TtsOutputSettings;
+use crate::tts_voices::VoiceMap;
+use crate::voice_presets::VoicePreset;
+use crate::toolsets::{ToolSelection, Toolset};
+use crate::provider_catalog::CatalogModel;
+use crate::providers::{get_supported_providers, ProviderId, ProviderPreferences, ProviderType};
 
 const PREFERENCES_FILENAME: &str = "preferences.json";
 
@@ -23,12 +42,150 @@ pub struct Preferences {
     /// Currently selected chat model
     #[serde(default)]
     pub current_chat_model: Option<String>,
+
+    /// Automatic chat snapshot settings
+    #[serde(default)]
+    pub chat_backup: ChatBackupSettings,
+
+    /// Delete removed models permanently instead of moving them to the Trash
+    #[serde(default)]
+    pub hard_delete_models: bool,
+
+    /// Ask the runtime to unload its models when the app quits, for a
+    /// runtime that keeps running afterwards
+    #[serde(default)]
+    pub unload_models_on_quit: bool,
+
+    /// Check prompts to cloud providers for personal data and offer to
+    /// redact it before sending
+    #[serde(default)]
+    pub privacy_filter: bool,
+
+    /// Serve downloaded models to other instances on the local network
+    #[serde(default)]
+    pub lan_sharing: bool,
+
+    /// OpenAI-compatible endpoint for other devices, and their tokens
+    #[serde(default)]
+    pub api_proxy: ApiProxySettings,
+
+    /// Models folder, concurrency, bandwidth cap and mirrors for downloads
+    #[serde(default)]
+    pub downloads: DownloadSettings,
+
+    /// Proxy and custom root certificates for all connections
+    #[serde(default)]
+    pub network: NetworkSettings,
+
+    /// Low power mode, by hand or on battery
+    #[serde(default)]
+    pub power: PowerSettings,
+
+    /// Custom vocabulary and punctuation fixes for transcripts
+    #[serde(default)]
+    pub asr: AsrSettings,
+
+    /// Folder whose recordings are transcribed as they arrive
+    #[serde(default)]
+    pub watch_folder: WatchFolderSettings,
+
+    /// Voices the TTS mode and Read Aloud use for each language
+    #[serde(default)]
+    pub tts_voices: VoiceMap,
+
+    /// Format, sample rate and save folder of the chat's text to speech
+    #[serde(default)]
+    pub tts_output: TtsOutputSettings,
+
+    /// Named synthesis settings saved in Voice Studio
+    #[serde(default)]
+    pub voice_presets: Vec<VoicePreset>,
+
+    /// MCP servers set up in the MCP app
+    #[serde(default)]
+    pub mcp_servers: Vec<McpServerConfig>,
+
+    /// Named tool selections offered by the chat's tool picker
+    #[serde(default)]
+    pub toolsets: Vec<Toolset>,
+
+    /// Folders the built-in files tool may use
+    #[serde(default)]
+    pub fs_tool_dirs: Vec<String>,
+
+    /// Time and memory limits of the built-in code execution tool
+    #[serde(default)]
+    pub code_tool: CodeToolSettings,
+
+    /// Hours generated audio, images and video are kept after quitting;
+    /// 0 removes them on exit
+    #[serde(default)]
+    pub generated_files_hours: u32,
+
+    /// System prompts given to chats opened from the Model Hub, by kind of model
+    #[serde(default)]
+    pub system_prompts: SystemPromptPresets,
+
+    /// Registry ids of the models loaded when the app starts
+    #[serde(default)]
+    pub preload_models: Vec<String>,
+
+    /// Opt-in usage statistics and where, if anywhere, to send them
+    #[serde(default)]
+    pub telemetry: TelemetrySettings,
+
+    /// Searching chat history by meaning with a local embedding model
+    #[serde(default)]
+    pub semantic_search: SemanticSearchSettings,
+
+    /// Log file of the ominix-api server; empty uses
+    /// [`crate::runtime_log::DEFAULT_LOG_PATH`]
+    #[serde(default)]
+    pub runtime_log_path: String,
+
+    /// Offering quick actions for text copied to the clipboard
+    #[serde(default)]
+    pub clipboard_watch: ClipboardWatchSettings,
+
+    /// Keyboard shortcut that opens Quick Ask from any app
+    #[serde(default = "default_quick_ask_hotkey")]
+    pub quick_ask_hotkey: String,
+
+    /// Main window geometry from the last session
+    #[serde(default)]
+    pub window: Option<WindowState>,
+}
+
+/// Size and position of the main window, in logical pixels
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct WindowState {
+    pub width: f64,
+    pub height: f64,
+    pub x: f64,
+    pub y: f64,
+    #[serde(default)]
+    pub maximized: bool,
+}
+
+impl WindowState {
+    /// Smallest size restored, so a bad saved value can't leave the window unusable
+    pub const MIN_SIZE: (f64, f64) = (640.0, 480.0);
+
+    /// Whether this is worth restoring
+    pub fn is_usable(&self) -> bool {
+        self.width >= Self::MIN_SIZE.0 && self.height >= Self::MIN_SIZE.1
+            && self.x.is_finite() && self.y.is_finite()
+    }
 }
 
 fn default_sidebar_expanded() -> bool {
     true
 }
 
+fn default_quick_ask_hotkey() -> String {
+    crate::quick_ask::DEFAULT_HOTKEY.to_string()
+}
+
 impl Default for Preferences {
     fn default() -> Self {
         Self {
@@ -36,6 +193,33 @@ impl Default for Preferences {
             current_view: "Chat".to_string(),
             providers_preferences: get_supported_providers(),
             current_chat_model: None,
+            chat_backup: ChatBackupSettings::default(),
+            hard_delete_models: false,
+            unload_models_on_quit: false,
+            privacy_filter: false,
+            lan_sharing: false,
+            api_proxy: ApiProxySettings::default(),
+            downloads: DownloadSettings::default(),
+            network: NetworkSettings::default(),
+            power: PowerSettings::default(),
+            asr: AsrSettings::default(),
+            watch_folder: WatchFolderSettings::default(),
+            tts_voices: VoiceMap::default(),
+            tts_output: TtsOutputSettings::default(),
+            voice_presets: Vec::new(),
+            mcp_servers: Vec::new(),
+            toolsets: Vec::new(),
+            fs_tool_dirs: Vec::new(),
+            code_tool: CodeToolSettings::default(),
+            generated_files_hours: 0,
+            system_prompts: SystemPromptPresets::default(),
+            preload_models: Vec::new(),
+            telemetry: TelemetrySettings::default(),
+            semantic_search: SemanticSearchSettings::default(),
+            runtime_log_path: String::new(),
+            clipboard_watch: ClipboardWatchSettings::default(),
+            quick_ask_hotkey: default_quick_ask_hotkey(),
+            window: None,
         }
     }
 }
@@ -93,17 +277,11 @@ impl Preferences {
         }
     }
 
-    /// Get the path to the preferences file
+    /// Get the path to the preferences file (in the active profile's data directory)
     fn preferences_path() -> PathBuf {
-        // Use home directory for reliable persistence
-        if let Some(home) = dirs::home_dir() {
-            let path = home.join(".moly").join(PREFERENCES_FILENAME);
-            log::debug!("Preferences path: {:?}", path);
-            path
-        } else {
-            // Fallback to current directory
-            PathBuf::from(".moly").join(PREFERENCES_FILENAME)
-        }
+        let path = profiles::data_dir().join(PREFERENCES_FILENAME);
+        log::debug!("Preferences path: {:?}", path);
+        path
     }
 
     /// Set sidebar expanded state and save
@@ -120,6 +298,13 @@ impl Preferences {
         self.save();
     }
 
+    /// Remember the main window geometry and save
+    pub fn set_window_state(&mut self, state: WindowState) {
+        if self.window == Some(state) { return; }
+        self.window = Some(state);
+        self.save();
+    }
+
     /// Get a provider by ID
     pub fn get_provider(&self, id: &ProviderId) -> Option<&ProviderPreferences> {
         self.providers_preferences.iter().find(|p| &p.id == id)
@@ -159,6 +344,128 @@ impl Preferences {
         }
     }
 
+    /// Replace a provider's cached model catalog and save.
+    ///
+    /// Models not yet in the provider's enabled list are added as enabled;
+    /// existing enabled/hidden choices are kept.
+    pub fn set_provider_catalog(&mut self, id: &ProviderId, catalog: Vec<CatalogModel>) {
+        if let Some(provider) = self.get_provider_mut(id) {
+            for model in &catalog {
+                if !provider.models.iter().any(|(name, _)| name == &model.id) {
+                    provider.models.push((model.id.clone(), true));
+                }
+            }
+            log::info!("set_provider_catalog: provider={}, models={}", id, catalog.len());
+            provider.catalog = catalog;
+            provider.catalog_updated_at = Some(chrono::Utc::now().to_rfc3339());
+            self.save();
+        }
+    }
+
+    /// Toggle a model's favorite flag for a provider and save.
+    /// Returns the new favorite state.
+    pub fn toggle_provider_favorite_model(&mut self, id: &ProviderId, model_id: &str) -> bool {
+        let Some(provider) = self.get_provider_mut(id) else { return false };
+        let now_favorite = if provider.is_favorite_model(model_id) {
+            provider.favorite_models.retain(|m| m != model_id);
+            false
+        } else {
+            provider.favorite_models.push(model_id.to_string());
+            true
+        };
+        self.save();
+        now_favorite
+    }
+
+    /// Toggle whether a model is loaded at startup and save.
+    /// Returns the new state.
+    pub fn toggle_preload_model(&mut self, model_id: &str) -> bool {
+        let now_preloaded = if self.preload_models.iter().any(|m| m == model_id) {
+            self.preload_models.retain(|m| m != model_id);
+            false
+        } else {
+            self.preload_models.push(model_id.to_string());
+            true
+        };
+        log::info!("toggle_preload_model: {} -> {}", model_id, now_preloaded);
+        self.save();
+        now_preloaded
+    }
+
+    /// Add an MCP server, or replace the one with the same name when
+    /// `replace` is set, and save
+    pub fn save_mcp_server(&mut self, server: McpServerConfig, replace: Option<&str>) -> Result<(), String> {
+        server.validate()?;
+        let taken = self.mcp_servers.iter()
+            .any(|s| s.name.eq_ignore_ascii_case(server.name.trim()) && Some(s.name.as_str()) != replace);
+        if taken {
+            return Err(format!("There is already a server named {}", server.name.trim()));
+        }
+        log::info!("save_mcp_server: {} ({})", server.name, server.command_line());
+        match replace.and_then(|name| self.mcp_servers.iter_mut().find(|s| s.name == name)) {
+            Some(existing) => *existing = server,
+            None => self.mcp_servers.push(server),
+        }
+        crate::mcp_client::set_servers(&self.mcp_servers);
+        self.save();
+        Ok(())
+    }
+
+    /// Remove an MCP server and save
+    pub fn remove_mcp_server(&mut self, name: &str) {
+        self.mcp_servers.retain(|s| s.name != name);
+        crate::mcp_client::set_servers(&self.mcp_servers);
+        self.save();
+    }
+
+    /// Enable or disable an MCP server and save
+    pub fn set_mcp_server_enabled(&mut self, name: &str, enabled: bool) {
+        if let Some(server) = self.mcp_servers.iter_mut().find(|s| s.name == name) {
+            server.enabled = enabled;
+            crate::mcp_client::set_servers(&self.mcp_servers);
+            self.save();
+        }
+    }
+
+    /// Save a tool selection under `name`, replacing a toolset of the same
+    /// name, and save
+    pub fn save_toolset(&mut self, name: &str, tools: ToolSelection) -> Result<(), String> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err("Enter a name for the toolset".to_string());
+        }
+        log::info!("save_toolset: {} ({} tools)", name, tools.len());
+        match self.toolsets.iter_mut().find(|t| t.name.eq_ignore_ascii_case(name)) {
+            Some(existing) => existing.tools = tools,
+            None => self.toolsets.push(Toolset { name: name.to_string(), tools }),
+        }
+        self.save();
+        Ok(())
+    }
+
+    /// Remove a toolset and save
+    pub fn remove_toolset(&mut self, name: &str) {
+        self.toolsets.retain(|t| t.name != name);
+        self.save();
+    }
+
+    /// Share a folder with the files tool and save
+    pub fn add_fs_tool_dir(&mut self, dir: String) {
+        if !self.fs_tool_dirs.contains(&dir) {
+            log::info!("add_fs_tool_dir: {}", dir);
+            self.fs_tool_dirs.push(dir);
+            crate::fs_tool::set_allowed_dirs(&self.fs_tool_dirs);
+            self.save();
+        }
+    }
+
+    /// Stop sharing a folder with the files tool and save
+    pub fn remove_fs_tool_dir(&mut self, dir: &str) {
+        self.fs_tool_dirs.retain(|d| d != dir);
+        crate::fs_tool::set_allowed_dirs(&self.fs_tool_dirs);
+        self.save();
+    }
+
     /// Set the current chat model and save
     pub fn set_current_chat_model(&mut self, model: Option<String>) {
         log::info!("set_current_chat_model: {:?}", model);
@@ -193,6 +500,16 @@ impl Preferences {
             if let Some(existing) = self.providers_preferences.iter_mut().find(|p| p.id == sp.id) {
                 // Update name to match the canonical name from supported providers
                 existing.name = sp.name;
+                // Built-in Ollama used to be configured through the OpenAI shim
+                if sp.provider_type == ProviderType::Ollama && existing.provider_type != ProviderType::Ollama {
+                    existing.provider_type = ProviderType::Ollama;
+                    // Keep a custom host, minus the shim's /v1
+                    existing.url = crate::ollama_client::ollama_base_url(&existing.url);
+                    // A local daemon needs no key, so an enabled one stays enabled
+                    if existing.requires_api_key() {
+                        existing.enabled = existing.enabled && existing.has_api_key();
+                    }
+                }
             } else {
                 // Add missing provider
                 self.providers_preferences.push(sp);
@@ -200,3 +517,29 @@ impl Preferences {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_window_state_is_usable() {
+        let state = WindowState { width: 1200.0, height: 800.0, x: 40.0, y: 30.0, maximized: false };
+        assert!(state.is_usable());
+        assert!(!WindowState { width: 200.0, ..state }.is_usable());
+        assert!(!WindowState { x: f64::NAN, ..state }.is_usable());
+    }
+
+    /// Preferences saved before the window was remembered, and geometry
+    /// saved before `maximized`, still load
+    #[test]
+    fn test_window_state_serde() {
+        let prefs: Preferences = serde_json::from_str("{}").unwrap();
+        assert_eq!(prefs.window, None);
+
+        let state: WindowState = serde_json::from_str(r#"{"width":900,"height":700,"x":0,"y":0}"#).unwrap();
+        assert!(!state.maximized);
+        let json = serde_json::to_string(&Preferences { window: Some(state), ..Default::default() }).unwrap();
+        assert_eq!(serde_json::from_str::<Preferences>(&json).unwrap().window, Some(state));
+    }
+}
@@ -0,0 +1,56 @@
+//! Loading everyday models when the app starts
+//!
+//! Models marked "Preload on startup" in the Hub are kept by registry id in
+//! [`crate::Preferences::preload_models`]. At startup the shell loads the
+//! models [`plan`] picks from that list as one batch
+//! ([`crate::load_scheduler`]) and shows the progress in its header.
+
+use crate::model_registry::{ModelRegistry, RegistryCategory, RegistryModel};
+
+/// The models to load at startup, in loading order
+///
+/// Ids that are no longer in the registry or whose files are missing are
+/// skipped. The runtime holds one model of each kind, so only the first
+/// marked model of a category is kept. Chat models load last, so the one
+/// the model selector ends up showing is the one chats use.
+pub fn plan(ids: &[String], registry: &ModelRegistry, is_downloaded: impl Fn(&RegistryModel) -> bool) -> Vec<RegistryModel> {
+    let mut models: Vec<RegistryModel> = Vec::new();
+    for id in ids {
+        let Some(model) = registry.models.iter().find(|m| &m.id == id) else {
+            log::warn!("Preload: {} is not in the registry", id);
+            continue;
+        };
+        if !is_downloaded(model) {
+            log::warn!("Preload: skipping {}, it is not downloaded", id);
+            continue;
+        }
+        if models.iter().any(|m| m.category == model.category) {
+            log::warn!("Preload: skipping {}, another {} model is preloaded", id, model.category.label());
+            continue;
+        }
+        models.push(model.clone());
+    }
+    models.sort_by_key(|m| matches!(m.category, RegistryCategory::Llm | RegistryCategory::Vlm));
+    models
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan_loads_chat_models_last() {
+        let registry = ModelRegistry::bundled();
+        let pick = |category: RegistryCategory| {
+            registry.models.iter().filter(|m| m.category == category).map(|m| m.id.clone()).collect::<Vec<_>>()
+        };
+        let (llms, asrs) = (pick(RegistryCategory::Llm), pick(RegistryCategory::Asr));
+        assert!(llms.len() >= 2 && !asrs.is_empty());
+
+        let ids = vec![llms[0].clone(), llms[1].clone(), asrs[0].clone(), "no-such-model".to_string()];
+        let planned: Vec<String> = plan(&ids, &registry, |_| true).into_iter().map(|m| m.id).collect();
+        assert_eq!(planned, vec![asrs[0].clone(), llms[0].clone()]);
+
+        assert!(plan(&ids, &registry, |m| m.category != RegistryCategory::Llm).iter().all(|m| m.category == RegistryCategory::Asr));
+    }
+}
@@ -0,0 +1,418 @@
+//! Redact-and-send filter for prompts to cloud providers
+//!
+//! When the filter is on (Settings → Privacy filter), messages to providers
+//! outside this computer and the local network ([`is_cloud`]) are checked
+//! for email addresses, phone numbers and API keys ([`find`]). The chat lists
+//! what a message contains and the user picks what to redact; each redacted
+//! value gets a placeholder like `[EMAIL_1]`. The choices are kept with the
+//! chat ([`crate::ChatData::privacy`]) and mirrored here with [`set_active`].
+//!
+//! [`RedactingClient`] swaps values for placeholders in everything it sends
+//! and placeholders back for values in the answers, so the transcript on
+//! screen shows the real text. Before anything is sent, [`review_outbound`]
+//! decides whether the conversation can go out; one with values the user
+//! hasn't decided on yet is held without a request, and the chat asks with
+//! [`take_held`] what to redact.
+
+use futures::StreamExt;
+use moly_kit::aitk::protocol::*;
+use moly_kit::aitk::utils::asynchronous::{BoxPlatformSendFuture, BoxPlatformSendStream};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+/// Mirrors [`crate::Preferences::privacy_filter`]
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Decisions of the chat that is open
+static ACTIVE: Mutex<Decisions> = Mutex::new(Decisions { redacted: Vec::new(), allowed: Vec::new() });
+
+/// Values of the last conversation held back, for the chat to ask about
+static HELD: Mutex<Option<Vec<PiiMatch>>> = Mutex::new(None);
+
+/// Key prefixes of common providers and code hosts
+const KEY_PREFIXES: [&str; 11] =
+    ["sk-", "sk_live_", "rk_live_", "ghp_", "gho_", "github_pat_", "glpat-", "xoxb-", "xoxp-", "AKIA", "AIza"];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PiiKind {
+    Email,
+    Phone,
+    ApiKey,
+}
+
+impl PiiKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Email => "Email",
+            Self::Phone => "Phone number",
+            Self::ApiKey => "API key",
+        }
+    }
+
+    fn tag(self) -> &'static str {
+        match self {
+            Self::Email => "EMAIL",
+            Self::Phone => "PHONE",
+            Self::ApiKey => "KEY",
+        }
+    }
+}
+
+/// A value found in a message
+#[derive(Clone, Debug, PartialEq)]
+pub struct PiiMatch {
+    pub kind: PiiKind,
+    pub text: String,
+}
+
+impl PiiMatch {
+    /// e.g. `Email: ada@example.com`; keys show only their start
+    pub fn label(&self) -> String {
+        let text = match self.kind {
+            PiiKind::ApiKey => format!("{}…", self.text.chars().take(8).collect::<String>()),
+            _ => self.text.clone(),
+        };
+        format!("{}: {}", self.kind.label(), text)
+    }
+}
+
+/// A redacted value and what is sent instead
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Redaction {
+    pub placeholder: String,
+    pub text: String,
+}
+
+/// What the user decided about values found in a chat's messages
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Decisions {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub redacted: Vec<Redaction>,
+    /// Values to send as they are
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub allowed: Vec<String>,
+}
+
+impl Decisions {
+    pub fn is_empty(&self) -> bool {
+        self.redacted.is_empty() && self.allowed.is_empty()
+    }
+
+    /// Values in `text` not decided on yet
+    pub fn pending(&self, text: &str) -> Vec<PiiMatch> {
+        find(text)
+            .into_iter()
+            .filter(|m| !self.allowed.contains(&m.text) && !self.redacted.iter().any(|r| r.text == m.text))
+            .collect()
+    }
+
+    /// Record the user's choice for each match: redact it or send it as is.
+    /// Placeholders are numbered after those of the same kind.
+    pub fn decide(&mut self, matches: &[PiiMatch], redact: &[bool]) {
+        for (m, redact) in matches.iter().zip(redact) {
+            if !redact {
+                self.allowed.push(m.text.clone());
+                continue;
+            }
+            let prefix = format!("[{}_", m.kind.tag());
+            let number = self.redacted.iter().filter(|r| r.placeholder.starts_with(&prefix)).count() + 1;
+            self.redacted.push(Redaction { placeholder: format!("{}{}]", prefix, number), text: m.text.clone() });
+        }
+    }
+
+    /// `text` as it is sent
+    pub fn redact(&self, text: &str) -> String {
+        let mut redactions: Vec<&Redaction> = self.redacted.iter().collect();
+        // A value inside a longer one mustn't break it up
+        redactions.sort_by_key(|r| std::cmp::Reverse(r.text.len()));
+        redactions.iter().fold(text.to_string(), |text, r| text.replace(&r.text, &r.placeholder))
+    }
+
+    /// `text` as it is shown
+    pub fn restore(&self, text: &str) -> String {
+        self.redacted.iter().fold(text.to_string(), |text, r| text.replace(&r.placeholder, &r.text))
+    }
+}
+
+/// Email addresses, phone numbers and API keys in `text`, each once, in the
+/// order they appear
+pub fn find(text: &str) -> Vec<PiiMatch> {
+    let mut found: Vec<(usize, PiiMatch)> = Vec::new();
+    let mut offset = 0;
+    for word in text.split_inclusive(char::is_whitespace) {
+        let start = offset;
+        offset += word.len();
+        let token = word.trim().trim_matches(|c: char| "\"'`()[]{}<>,;:.!?".contains(c));
+        let kind = if is_email(token) {
+            PiiKind::Email
+        } else if is_api_key(token) {
+            PiiKind::ApiKey
+        } else {
+            continue;
+        };
+        found.push((start, PiiMatch { kind, text: token.to_string() }));
+    }
+    found.extend(phone_numbers(text).into_iter().map(|(start, text)| (start, PiiMatch { kind: PiiKind::Phone, text })));
+    found.sort_by_key(|(start, _)| *start);
+
+    let mut matches: Vec<PiiMatch> = Vec::new();
+    for (_, m) in found {
+        if !matches.iter().any(|seen| seen.text == m.text) {
+            matches.push(m);
+        }
+    }
+    matches
+}
+
+fn is_email(token: &str) -> bool {
+    let Some((local, domain)) = token.split_once('@') else { return false };
+    let local_ok = !local.is_empty() && local.chars().all(|c| c.is_alphanumeric() || "._%+-".contains(c));
+    let labels: Vec<&str> = domain.split('.').collect();
+    let domain_ok = labels.len() >= 2
+        && labels.iter().all(|l| !l.is_empty() && l.chars().all(|c| c.is_alphanumeric() || c == '-'))
+        && labels.last().is_some_and(|tld| tld.len() >= 2 && tld.chars().all(char::is_alphabetic));
+    local_ok && domain_ok
+}
+
+fn is_api_key(token: &str) -> bool {
+    token.len() >= 20
+        && KEY_PREFIXES.iter().any(|p| token.starts_with(p))
+        && token.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Runs of digits and separators with 10 to 15 digits that start with `+`
+/// or contain a separator, so plain long numbers (order ids, timestamps)
+/// aren't taken for phone numbers. Returns each run with its byte offset.
+fn phone_numbers(text: &str) -> Vec<(usize, String)> {
+    let is_part = |c: char| c.is_ascii_digit() || " -.()+".contains(c);
+    let mut numbers = Vec::new();
+    let mut chars = text.char_indices().peekable();
+    let mut previous: Option<char> = None;
+    while let Some((start, c)) = chars.next() {
+        let begins = (c.is_ascii_digit() || c == '+' || c == '(') && !previous.is_some_and(|p| p.is_alphanumeric());
+        previous = Some(c);
+        if !begins {
+            continue;
+        }
+        let mut end = start + c.len_utf8();
+        while let Some(&(i, next)) = chars.peek() {
+            if !is_part(next) {
+                break;
+            }
+            end = i + next.len_utf8();
+            previous = Some(next);
+            chars.next();
+        }
+        let run = text[start..end].trim_end_matches(|c: char| !c.is_ascii_digit() && c != ')');
+        let followed_by_word = text[start + run.len()..].chars().next().is_some_and(|c| c.is_alphanumeric());
+        let digits = run.chars().filter(char::is_ascii_digit).count();
+        let formatted = run.starts_with('+') || run.contains([' ', '-', '.', '(']);
+        if (10..=15).contains(&digits) && formatted && !followed_by_word {
+            numbers.push((start, run.to_string()));
+        }
+    }
+    numbers
+}
+
+/// Whether requests to `url` leave this computer and the local network
+pub fn is_cloud(url: &str) -> bool {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    let authority = authority.rsplit_once('@').map_or(authority, |(_, host)| host);
+    let host = match authority.strip_prefix('[') {
+        Some(v6) => v6.split(']').next().unwrap_or_default(),
+        None => authority.split(':').next().unwrap_or_default(),
+    };
+    let host = host.to_lowercase();
+    if host.is_empty() || host == "localhost" || host.ends_with(".local") || host.ends_with(".localhost") {
+        return false;
+    }
+    match host.parse::<std::net::IpAddr>() {
+        Ok(std::net::IpAddr::V4(ip)) => !(ip.is_loopback() || ip.is_private() || ip.is_link_local()),
+        Ok(std::net::IpAddr::V6(ip)) => !(ip.is_loopback() || (ip.segments()[0] & 0xfe00) == 0xfc00),
+        Err(_) => true,
+    }
+}
+
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::SeqCst);
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::SeqCst)
+}
+
+/// Mirror the open chat's [`crate::ChatData::privacy`]
+pub fn set_active(decisions: &Decisions) {
+    *ACTIVE.lock().unwrap() = decisions.clone();
+}
+
+pub fn active() -> Decisions {
+    ACTIVE.lock().unwrap().clone()
+}
+
+/// Whether a conversation can be sent
+#[derive(Clone, Debug, PartialEq)]
+pub enum Verdict {
+    Send,
+    /// Values not decided on yet, in the order they appear; nothing is sent
+    Hold(Vec<PiiMatch>),
+}
+
+/// Decide on `messages` before they are sent. Earlier messages go out again
+/// with the last one, so every message of the user is checked.
+pub fn review_outbound(messages: &[Message], decisions: &Decisions) -> Verdict {
+    let mut found: Vec<PiiMatch> = Vec::new();
+    for m in messages.iter().filter(|m| matches!(m.from, EntityId::User)) {
+        for pending in decisions.pending(&m.content.text) {
+            if !found.contains(&pending) {
+                found.push(pending);
+            }
+        }
+    }
+    if found.is_empty() { Verdict::Send } else { Verdict::Hold(found) }
+}
+
+/// The values of a conversation [`RedactingClient`] held back since the
+/// last call
+pub fn take_held() -> Option<Vec<PiiMatch>> {
+    HELD.lock().unwrap().take()
+}
+
+/// Redacts messages to a cloud provider and restores its answers
+pub struct RedactingClient {
+    inner: Box<dyn BotClient>,
+}
+
+impl RedactingClient {
+    pub fn new(inner: Box<dyn BotClient>) -> Self {
+        Self { inner }
+    }
+}
+
+impl Clone for RedactingClient {
+    fn clone(&self) -> Self {
+        Self { inner: self.inner.clone_box() }
+    }
+}
+
+impl BotClient for RedactingClient {
+    fn bots(&mut self) -> BoxPlatformSendFuture<'static, ClientResult<Vec<Bot>>> {
+        self.inner.bots()
+    }
+
+    fn send(
+        &mut self,
+        bot_id: &BotId,
+        messages: &[Message],
+        tools: &[Tool],
+    ) -> BoxPlatformSendStream<'static, ClientResult<MessageContent>> {
+        if !is_enabled() {
+            return self.inner.send(bot_id, messages, tools);
+        }
+        let decisions = active();
+        if let Verdict::Hold(found) = review_outbound(messages, &decisions) {
+            // Nothing goes out; the chat asks what to redact, then sends again
+            log::info!("Holding a message to {} for privacy review ({} items)", bot_id.id(), found.len());
+            *HELD.lock().unwrap() = Some(found);
+            makepad_widgets::SignalToUI::set_ui_signal();
+            return Box::pin(futures::stream::empty());
+        }
+        if decisions.redacted.is_empty() {
+            return self.inner.send(bot_id, messages, tools);
+        }
+        let redacted: Vec<Message> = messages
+            .iter()
+            .map(|m| {
+                let mut m = m.clone();
+                m.content.text = decisions.redact(&m.content.text);
+                m
+            })
+            .collect();
+        let mut inner = self.inner.send(bot_id, &redacted, tools);
+        Box::pin(async_stream::stream! {
+            while let Some(result) = inner.next().await {
+                match result.value() {
+                    Some(content) if result.errors().is_empty() => {
+                        let mut content = content.clone();
+                        content.text = decisions.restore(&content.text);
+                        yield ClientResult::new_ok(content);
+                    }
+                    _ => yield result,
+                }
+            }
+        })
+    }
+
+    fn clone_box(&self) -> Box<dyn BotClient> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find() {
+        let text = "Mail ada@example.com or call +1 415-555-0100 (ada@example.com). Key: sk-proj-abcdefghij0123456789. \
+                    Order 20240115093000 shipped on 2024-01-15; see https://example.com/@team.";
+        let found = find(text);
+        assert_eq!(
+            found,
+            vec![
+                PiiMatch { kind: PiiKind::Email, text: "ada@example.com".to_string() },
+                PiiMatch { kind: PiiKind::Phone, text: "+1 415-555-0100".to_string() },
+                PiiMatch { kind: PiiKind::ApiKey, text: "sk-proj-abcdefghij0123456789".to_string() },
+            ]
+        );
+        assert_eq!(found[2].label(), "API key: sk-proj-…");
+        assert!(find("Call (020) 7946 0958 tomorrow").iter().any(|m| m.kind == PiiKind::Phone));
+        assert!(find("The sum of 1234567890 and 5 is large").is_empty());
+    }
+
+    #[test]
+    fn test_redact_and_restore() {
+        let text = "Write to ada@example.com and bob@example.com, phone +44 20 7946 0958.";
+        let mut decisions = Decisions::default();
+        let pending = decisions.pending(text);
+        assert_eq!(pending.len(), 3);
+        decisions.decide(&pending, &[true, true, false]);
+        assert!(decisions.pending(text).is_empty());
+
+        let sent = decisions.redact(text);
+        assert_eq!(sent, "Write to [EMAIL_1] and [EMAIL_2], phone +44 20 7946 0958.");
+        assert_eq!(decisions.restore("I emailed [EMAIL_2]."), "I emailed bob@example.com.");
+        assert_eq!(decisions.restore(&sent), text);
+    }
+
+    #[test]
+    fn test_review_outbound() {
+        let user = |text: &str| Message {
+            from: EntityId::User,
+            content: MessageContent { text: text.to_string(), ..Default::default() },
+            ..Default::default()
+        };
+        let messages = vec![user("I'm ada@example.com"), user("Call +1 415-555-0100, or ada@example.com")];
+        let mut decisions = Decisions::default();
+        let Verdict::Hold(found) = review_outbound(&messages, &decisions) else { panic!("expected a hold") };
+        let texts: Vec<_> = found.iter().map(|m| m.text.as_str()).collect();
+        assert_eq!(texts, ["ada@example.com", "+1 415-555-0100"]);
+
+        decisions.decide(&found, &[true, false]);
+        assert_eq!(review_outbound(&messages, &decisions), Verdict::Send);
+    }
+
+    #[test]
+    fn test_is_cloud() {
+        assert!(is_cloud("https://api.openai.com/v1"));
+        assert!(is_cloud("https://8.8.8.8:443"));
+        assert!(!is_cloud("http://localhost:11434"));
+        assert!(!is_cloud("http://127.0.0.1:8080/v1"));
+        assert!(!is_cloud("http://192.168.1.20:1234/v1"));
+        assert!(!is_cloud("http://[::1]:8080"));
+        assert!(!is_cloud("http://gpu-box.local:8000"));
+    }
+}
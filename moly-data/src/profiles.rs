@@ -0,0 +1,205 @@
+//! Named profiles ("Work", "Personal", ...) with isolated data
+//!
+//! Each profile has its own preferences (including provider API keys), chat
+//! history and local model registries. The built-in default profile keeps
+//! using `~/.moly` so existing installs carry over unchanged; every other
+//! profile lives in `~/.moly/profiles/<id>/`. The profile list itself is
+//! shared, in `~/.moly/profiles.json`.
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::RwLock;
+
+const PROFILES_FILENAME: &str = "profiles.json";
+const PROFILES_DIR: &str = "profiles";
+
+/// Id of the built-in profile that owns the legacy `~/.moly` data
+pub const DEFAULT_PROFILE_ID: &str = "default";
+
+/// Profile whose data directory is used by every loader; empty = default
+static ACTIVE_PROFILE: RwLock<String> = RwLock::new(String::new());
+
+/// A named, isolated configuration
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Profile {
+    /// Directory name under `~/.moly/profiles/`
+    pub id: String,
+    /// Name shown in the header
+    pub name: String,
+}
+
+/// The list of profiles plus startup behaviour
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Profiles {
+    pub profiles: Vec<Profile>,
+    /// Profile opened at startup (the built-in default when unset)
+    #[serde(default)]
+    pub default_profile: Option<String>,
+    /// Show the profile picker when the app starts
+    #[serde(default)]
+    pub ask_at_startup: bool,
+}
+
+impl Default for Profiles {
+    fn default() -> Self {
+        Self {
+            profiles: vec![Profile { id: DEFAULT_PROFILE_ID.to_string(), name: "Default".to_string() }],
+            default_profile: None,
+            ask_at_startup: false,
+        }
+    }
+}
+
+impl Profiles {
+    /// Load the profile list, falling back to just the default profile
+    pub fn load() -> Self {
+        let path = moly_root().join(PROFILES_FILENAME);
+        let mut profiles: Profiles = std::fs::read_to_string(&path).ok()
+            .and_then(|json| match serde_json::from_str(&json) {
+                Ok(p) => Some(p),
+                Err(e) => {
+                    log::error!("Failed to parse profiles: {:?}", e);
+                    None
+                }
+            })
+            .unwrap_or_default();
+        if profiles.get(DEFAULT_PROFILE_ID).is_none() {
+            profiles.profiles.insert(0, Profile { id: DEFAULT_PROFILE_ID.to_string(), name: "Default".to_string() });
+        }
+        profiles
+    }
+
+    pub fn save(&self) {
+        let path = moly_root().join(PROFILES_FILENAME);
+        if let Some(parent) = path.parent() {
+            if let Err(e) = std::fs::create_dir_all(parent) {
+                log::error!("Failed to create profiles directory: {:?}", e);
+                return;
+            }
+        }
+        match serde_json::to_string_pretty(self) {
+            Ok(json) => {
+                if let Err(e) = std::fs::write(&path, json) {
+                    log::error!("Failed to save profiles: {:?}", e);
+                }
+            }
+            Err(e) => log::error!("Failed to serialize profiles: {:?}", e),
+        }
+    }
+
+    pub fn get(&self, id: &str) -> Option<&Profile> {
+        self.profiles.iter().find(|p| p.id == id)
+    }
+
+    /// Find a profile by id or (case-insensitive) name, e.g. from `--profile`
+    pub fn find(&self, id_or_name: &str) -> Option<&Profile> {
+        self.get(id_or_name)
+            .or_else(|| self.profiles.iter().find(|p| p.name.eq_ignore_ascii_case(id_or_name)))
+    }
+
+    /// Add a profile. Its directory is created lazily by the first save.
+    pub fn create(&mut self, name: &str) -> Result<Profile, String> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err("Profile name cannot be empty".to_string());
+        }
+        if self.profiles.iter().any(|p| p.name.eq_ignore_ascii_case(name)) {
+            return Err(format!("A profile named \"{}\" already exists", name));
+        }
+
+        let base: String = name.to_lowercase().chars()
+            .map(|c| if c.is_alphanumeric() { c } else { '-' })
+            .collect::<String>()
+            .split('-').filter(|s| !s.is_empty()).collect::<Vec<_>>().join("-");
+        let base = if base.is_empty() { "profile".to_string() } else { base };
+        let mut id = base.clone();
+        let mut n = 2;
+        while self.get(&id).is_some() {
+            id = format!("{}-{}", base, n);
+            n += 1;
+        }
+
+        let profile = Profile { id, name: name.to_string() };
+        self.profiles.push(profile.clone());
+        Ok(profile)
+    }
+
+    /// Make a profile the one opened at startup
+    pub fn set_default(&mut self, id: &str) {
+        self.default_profile = Some(id.to_string()).filter(|id| id != DEFAULT_PROFILE_ID);
+    }
+
+    /// Id of the profile to open at startup
+    pub fn startup_profile(&self) -> &str {
+        self.default_profile.as_deref()
+            .filter(|id| self.get(id).is_some())
+            .unwrap_or(DEFAULT_PROFILE_ID)
+    }
+
+    pub fn is_default(&self, id: &str) -> bool {
+        self.startup_profile() == id
+    }
+}
+
+/// `~/.moly`, shared by all profiles
+fn moly_root() -> PathBuf {
+    dirs::home_dir()
+        .map(|home| home.join(".moly"))
+        .unwrap_or_else(|| PathBuf::from(".moly"))
+}
+
+/// Data directory of a profile
+pub fn profile_dir(id: &str) -> PathBuf {
+    if id.is_empty() || id == DEFAULT_PROFILE_ID {
+        moly_root()
+    } else {
+        moly_root().join(PROFILES_DIR).join(id)
+    }
+}
+
+/// Data directory of the active profile. Preferences, chats and local model
+/// registries resolve their paths through here.
+pub fn data_dir() -> PathBuf {
+    profile_dir(&active_profile_id())
+}
+
+pub fn active_profile_id() -> String {
+    let id = ACTIVE_PROFILE.read().map(|id| id.clone()).unwrap_or_default();
+    if id.is_empty() { DEFAULT_PROFILE_ID.to_string() } else { id }
+}
+
+/// Switch the active profile. Reload the Store afterwards to pick up its data.
+pub fn set_active_profile(id: &str) {
+    if let Ok(mut active) = ACTIVE_PROFILE.write() {
+        *active = id.to_string();
+    }
+    log::info!("Active profile: {} ({:?})", id, profile_dir(id));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_profile_ids() {
+        let mut profiles = Profiles::default();
+        assert_eq!(profiles.create("Work Stuff").unwrap().id, "work-stuff");
+        assert_eq!(profiles.create("work/stuff!").unwrap().id, "work-stuff-2");
+        assert!(profiles.create("  ").is_err());
+        assert!(profiles.create("WORK STUFF").is_err());
+        assert_eq!(profiles.find("work stuff").map(|p| p.id.as_str()), Some("work-stuff"));
+    }
+
+    #[test]
+    fn test_default_profile() {
+        let mut profiles = Profiles::default();
+        assert_eq!(profiles.startup_profile(), DEFAULT_PROFILE_ID);
+        let work = profiles.create("Work").unwrap();
+        profiles.set_default(&work.id);
+        assert!(profiles.is_default("work"));
+        profiles.default_profile = Some("missing".to_string());
+        assert_eq!(profiles.startup_profile(), DEFAULT_PROFILE_ID);
+        assert_eq!(profile_dir(DEFAULT_PROFILE_ID), moly_root());
+        assert_eq!(profile_dir("work"), moly_root().join("profiles").join("work"));
+    }
+}
@@ -0,0 +1,60 @@
+//! Expanding short image prompts with a local LLM
+//!
+//! The Hub's image panel sends the user's prompt to whichever LLM is loaded
+//! with [`INSTRUCTION`], and replaces the prompt with the answer after
+//! [`clean_answer`] has removed reasoning, labels and quotes. The original
+//! is kept so the change can be shown with
+//! [`crate::answer_versions::diff_words`] and reverted.
+
+use crate::ominix_api_client::{ChatCompletionRequest, ChatMessage};
+
+/// System prompt for the expansion
+pub const INSTRUCTION: &str = "You rewrite prompts for an image generation model. \
+Expand the user's prompt into one detailed prompt of at most 60 words: keep its subject and intent, \
+and add the setting, lighting, composition, style and level of detail. \
+Reply with the prompt only, without quotes, labels or explanations.";
+
+/// Chat request asking `model` to expand `prompt`
+pub fn request(model: impl Into<String>, prompt: &str) -> ChatCompletionRequest {
+    ChatCompletionRequest {
+        model: model.into(),
+        messages: vec![ChatMessage::system(INSTRUCTION), ChatMessage::user(prompt.trim())],
+        max_tokens: None,
+    }
+}
+
+/// The prompt from a model's answer: without a `<think>` block, a
+/// `Prompt:` label or surrounding quotes
+pub fn clean_answer(answer: &str) -> Result<String, String> {
+    let mut text = answer;
+    if let Some(end) = text.find("</think>") {
+        text = &text[end + "</think>".len()..];
+    }
+    let mut text = text.trim();
+    for label in ["Enhanced prompt:", "Prompt:"] {
+        if text.len() >= label.len() && text[..label.len()].eq_ignore_ascii_case(label) {
+            text = text[label.len()..].trim_start();
+        }
+    }
+    let text = text.trim_matches(|c| c == '"' || c == '“' || c == '”' || c == '\'').trim();
+    if text.is_empty() {
+        return Err("The model returned an empty prompt.".to_string());
+    }
+    Ok(text.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clean_answer() {
+        assert_eq!(
+            clean_answer("<think>The user wants a cat.</think>\nPrompt: \"A tabby cat on a sunlit windowsill\"").unwrap(),
+            "A tabby cat on a sunlit windowsill"
+        );
+        assert_eq!(clean_answer("A lighthouse at dusk, oil painting").unwrap(), "A lighthouse at dusk, oil painting");
+        assert!(clean_answer("<think>hmm</think>  \"\" ").is_err());
+        assert_eq!(request("qwen3-4b", " cat ").messages.len(), 2);
+    }
+}
@@ -0,0 +1,82 @@
+//! Shell-like recall of sent prompts
+//!
+//! Up in an empty prompt brings back the last prompt sent in the chat, and
+//! each further Up an older one. Down steps forward again and, past the
+//! newest, empties the prompt. Browsing only starts from an empty prompt and
+//! ends when a recalled prompt is edited, so otherwise the arrows move the
+//! cursor as usual.
+//!
+//! The prompts themselves are stored per chat in
+//! [`crate::ChatData::sent_prompts`].
+
+/// Position while browsing a chat's sent prompts
+#[derive(Clone, Debug, Default)]
+pub struct PromptRecall {
+    /// Index into the sent prompts while browsing
+    position: Option<usize>,
+}
+
+impl PromptRecall {
+    /// The prompt before the one shown, if `text` is empty or still the
+    /// recalled prompt
+    pub fn older(&mut self, sent: &[String], text: &str) -> Option<String> {
+        self.end_if_edited(sent, text);
+        let position = match self.position {
+            Some(0) => return None,
+            Some(position) => position - 1,
+            None if text.is_empty() && !sent.is_empty() => sent.len() - 1,
+            None => return None,
+        };
+        self.position = Some(position);
+        Some(sent[position].clone())
+    }
+
+    /// The prompt after the one shown, or an empty prompt past the newest
+    pub fn newer(&mut self, sent: &[String], text: &str) -> Option<String> {
+        self.end_if_edited(sent, text);
+        let position = self.position?;
+        if position + 1 < sent.len() {
+            self.position = Some(position + 1);
+            Some(sent[position + 1].clone())
+        } else {
+            self.position = None;
+            Some(String::new())
+        }
+    }
+
+    /// Stop browsing, e.g. after sending or switching chats
+    pub fn reset(&mut self) {
+        self.position = None;
+    }
+
+    fn end_if_edited(&mut self, sent: &[String], text: &str) {
+        let shown = self.position.and_then(|p| sent.get(p));
+        if self.position.is_some() && shown.map(String::as_str) != Some(text) {
+            self.reset();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recall_like_a_shell() {
+        let sent = vec!["first".to_string(), "second".to_string()];
+        let mut recall = PromptRecall::default();
+
+        assert_eq!(recall.older(&sent, "typing"), None);
+        assert_eq!(recall.older(&sent, "").as_deref(), Some("second"));
+        assert_eq!(recall.older(&sent, "second").as_deref(), Some("first"));
+        assert_eq!(recall.older(&sent, "first"), None);
+        assert_eq!(recall.newer(&sent, "first").as_deref(), Some("second"));
+        assert_eq!(recall.newer(&sent, "second").as_deref(), Some(""));
+        assert_eq!(recall.newer(&sent, ""), None);
+
+        // Editing a recalled prompt ends browsing
+        assert_eq!(recall.older(&sent, "").as_deref(), Some("second"));
+        assert_eq!(recall.older(&sent, "second, edited"), None);
+        assert_eq!(recall.newer(&sent, "second, edited"), None);
+    }
+}
@@ -0,0 +1,228 @@
+//! Provider model catalog.
+//!
+//! Fetches a provider's list-models endpoint and normalises the different
+//! response shapes into [`CatalogModel`] entries:
+//!
+//!   OpenAI-compatible   {"data": [{"id", "owned_by", "context_window"?}]}
+//!   OpenRouter          {"data": [{"id", "context_length", "pricing": {"prompt", "completion"}}]}
+//!   Ollama /api/tags    {"models": [{"name", "size"}]}
+//!
+//! The parsed catalog is cached per provider in `ProviderPreferences` so the
+//! Settings browser and the chat model picker work without refetching.
+
+use serde::{Deserialize, Serialize};
+
+/// A model advertised by a provider, with whatever metadata it exposes.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct CatalogModel {
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owned_by: Option<String>,
+    /// Maximum context window in tokens
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context_length: Option<u64>,
+    /// USD per million prompt tokens
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt_price: Option<f64>,
+    /// USD per million completion tokens
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completion_price: Option<f64>,
+    /// On-disk size for locally served models (Ollama)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size_bytes: Option<u64>,
+}
+
+impl CatalogModel {
+    /// One-line metadata summary for list rows, e.g. "128K ctx · $2.50/$10.00 per 1M".
+    pub fn metadata_summary(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(ctx) = self.context_length {
+            if ctx >= 1000 {
+                parts.push(format!("{}K ctx", ctx / 1000));
+            } else {
+                parts.push(format!("{} ctx", ctx));
+            }
+        }
+        match (self.prompt_price, self.completion_price) {
+            (Some(p), Some(c)) if p == 0.0 && c == 0.0 => parts.push("free".to_string()),
+            (Some(p), Some(c)) => parts.push(format!("${:.2}/${:.2} per 1M", p, c)),
+            (Some(p), None) => parts.push(format!("${:.2} per 1M in", p)),
+            _ => {}
+        }
+        if let Some(bytes) = self.size_bytes {
+            parts.push(format!("{:.1} GB", bytes as f64 / 1_000_000_000.0));
+        }
+        if parts.is_empty() {
+            if let Some(owner) = &self.owned_by {
+                parts.push(owner.clone());
+            }
+        }
+        parts.join(" · ")
+    }
+}
+
+/// Parse a list-models response body into catalog entries.
+pub fn parse_models_response(body: &str) -> Result<Vec<CatalogModel>, String> {
+    let value: serde_json::Value = serde_json::from_str(body)
+        .map_err(|e| format!("Invalid models response: {}", e))?;
+
+    let entries = value.get("data").and_then(|d| d.as_array())
+        .or_else(|| value.get("models").and_then(|d| d.as_array()))
+        .or_else(|| value.as_array())
+        .ok_or_else(|| "No model list in response".to_string())?;
+
+    Ok(entries.iter().filter_map(parse_entry).collect())
+}
+
+fn parse_entry(v: &serde_json::Value) -> Option<CatalogModel> {
+    // Some providers return bare strings
+    if let Some(id) = v.as_str() {
+        return Some(CatalogModel { id: id.to_string(), ..Default::default() });
+    }
+
+    let id = ["id", "name", "model"].iter()
+        .find_map(|k| v.get(*k).and_then(|x| x.as_str()))?
+        .to_string();
+
+    let context_length = ["context_length", "context_window", "max_context_length", "inputTokenLimit"]
+        .iter()
+        .find_map(|k| v.get(*k).and_then(|x| x.as_u64()))
+        .or_else(|| v.pointer("/top_provider/context_length").and_then(|x| x.as_u64()));
+
+    // OpenRouter reports prices per token as strings
+    let price = |key: &str| -> Option<f64> {
+        let p = v.get("pricing")?.get(key)?;
+        let per_token = p.as_f64().or_else(|| p.as_str().and_then(|s| s.parse().ok()))?;
+        Some(per_token * 1_000_000.0)
+    };
+
+    Some(CatalogModel {
+        id,
+        owned_by: v.get("owned_by").and_then(|x| x.as_str()).map(String::from),
+        context_length,
+        prompt_price: price("prompt"),
+        completion_price: price("completion"),
+        size_bytes: v.get("size").and_then(|x| x.as_u64()),
+    })
+}
+
+/// Fetch the model catalog from a provider (blocking — call from a thread).
+///
+/// Tries the common endpoint layouts in order, moving on after a 404.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn fetch_provider_catalog(base_url: &str, api_key: &str) -> Result<Vec<CatalogModel>, String> {
+    use std::time::Duration;
+
+    let base = base_url.trim_end_matches('/');
+    let endpoints_to_try = [
+        format!("{}/models", base),
+        format!("{}/v1/models", base),
+        base.to_string(),
+    ];
+
+    let client = crate::network::blocking_client()
+        .timeout(Duration::from_secs(10))
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+
+    let mut last_error = String::new();
+
+    for models_url in &endpoints_to_try {
+        log::debug!("Fetching model catalog from {}", models_url);
+
+        let mut req = client.get(models_url).header("Content-Type", "application/json");
+        if !api_key.is_empty() {
+            req = req.header("Authorization", format!("Bearer {}", api_key));
+        }
+
+        let response = match req.send() {
+            Ok(resp) => resp,
+            Err(e) => {
+                last_error = if e.is_timeout() {
+                    "Connection timed out".to_string()
+                } else if e.is_connect() {
+                    "Failed to connect to server".to_string()
+                } else {
+                    format!("Request failed: {}", e)
+                };
+                continue;
+            }
+        };
+
+        let status = response.status();
+        if status.as_u16() == 404 {
+            last_error = format!("Endpoint not found: {}", models_url);
+            continue;
+        }
+
+        if !status.is_success() {
+            let error_text = response.text().unwrap_or_default();
+            return Err(match status.as_u16() {
+                401 => "Invalid API key".to_string(),
+                403 => "Access denied".to_string(),
+                429 => "Rate limited".to_string(),
+                _ => format!("HTTP {}: {}", status.as_u16(), error_text),
+            });
+        }
+
+        let body = match response.text() {
+            Ok(b) => b,
+            Err(e) => {
+                last_error = format!("Failed to read response: {}", e);
+                continue;
+            }
+        };
+
+        return match parse_models_response(&body) {
+            Ok(models) => {
+                log::info!("Fetched {} models from {}", models.len(), models_url);
+                Ok(models)
+            }
+            Err(e) => {
+                // A 200 we can't parse still means the provider is reachable
+                log::warn!("Could not parse models response from {}: {}", models_url, e);
+                Ok(vec![])
+            }
+        };
+    }
+
+    Err(if last_error.is_empty() {
+        "Could not find models endpoint".to_string()
+    } else {
+        last_error
+    })
+}
+
+/// Catalogs are fetched with blocking HTTP, which the browser build lacks
+#[cfg(target_arch = "wasm32")]
+pub fn fetch_provider_catalog(_base_url: &str, _api_key: &str) -> Result<Vec<CatalogModel>, String> {
+    Err("Fetching model catalogs isn't available in the browser".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_openrouter_metadata() {
+        let body = r#"{"data": [{
+            "id": "openai/gpt-4o",
+            "context_length": 128000,
+            "pricing": {"prompt": "0.0000025", "completion": "0.00001"}
+        }]}"#;
+        let models = parse_models_response(body).unwrap();
+
+        assert_eq!(models.len(), 1);
+        assert_eq!(models[0].context_length, Some(128000));
+        assert_eq!(models[0].metadata_summary(), "128K ctx · $2.50/$10.00 per 1M");
+    }
+
+    #[test]
+    fn test_parse_ollama_tags() {
+        let body = r#"{"models": [{"name": "llama3:8b", "size": 4661224676}]}"#;
+        let models = parse_models_response(body).unwrap();
+
+        assert_eq!(models[0].id, "llama3:8b");
+        assert_eq!(models[0].size_bytes, Some(4661224676));
+    }
+}
@@ -1,5 +1,8 @@
 use serde::{Deserialize, Serialize};
 
+use crate::chat_defaults::ChatDefaults;
+use crate::provider_catalog::CatalogModel;
+
 /// Unique identifier for a provider
 pub type ProviderId = String;
 
@@ -16,6 +19,8 @@ pub enum ProviderType {
     OminiXImage,
     MoFa,
     MolyServer,
+    /// Local Ollama daemon (native /api endpoints)
+    Ollama,
 }
 
 /// Connection status of a provider
@@ -57,6 +62,18 @@ pub struct ProviderPreferences {
     /// Only applicable for OpenAI-compatible providers that support function calling
     #[serde(default)]
     pub a2ui_enabled: bool,
+    /// Model IDs pinned to the top of the chat model picker
+    #[serde(default)]
+    pub favorite_models: Vec<String>,
+    /// Last model catalog fetched from the provider's list-models endpoint
+    #[serde(default)]
+    pub catalog: Vec<CatalogModel>,
+    /// When `catalog` was last refreshed (RFC 3339)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub catalog_updated_at: Option<String>,
+    /// What new chats with this provider start with
+    #[serde(default, skip_serializing_if = "ChatDefaults::is_empty")]
+    pub chat_defaults: ChatDefaults,
 }
 
 fn default_true() -> bool {
@@ -77,6 +94,10 @@ impl Default for ProviderPreferences {
             system_prompt: None,
             tools_enabled: true,
             a2ui_enabled: false,
+            favorite_models: Vec::new(),
+            catalog: Vec::new(),
+            catalog_updated_at: None,
+            chat_defaults: ChatDefaults::default(),
         }
     }
 }
@@ -99,7 +120,7 @@ impl ProviderPreferences {
     pub fn requires_api_key(&self) -> bool {
         match self.provider_type {
             // Local providers don't require API keys
-            ProviderType::OminiXImage => false,
+            ProviderType::OminiXImage | ProviderType::Ollama => false,
             // All other providers require API keys
             _ => true,
         }
@@ -115,6 +136,22 @@ impl ProviderPreferences {
         matches!(self.provider_type, ProviderType::OpenAi)
     }
 
+    /// Check if a model is pinned as a favorite
+    pub fn is_favorite_model(&self, model_id: &str) -> bool {
+        self.favorite_models.iter().any(|m| m == model_id)
+    }
+
+    /// Look up catalog metadata for a model
+    pub fn catalog_model(&self, model_id: &str) -> Option<&CatalogModel> {
+        self.catalog.iter().find(|m| m.id == model_id)
+    }
+
+    /// Local time the catalog was last refreshed, formatted for display
+    pub fn catalog_updated_display(&self) -> Option<String> {
+        let ts = chrono::DateTime::parse_from_rfc3339(self.catalog_updated_at.as_deref()?).ok()?;
+        Some(ts.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M").to_string())
+    }
+
     /// Check if A2UI is both supported and enabled for this provider
     pub fn is_a2ui_ready(&self) -> bool {
         self.supports_a2ui() && self.a2ui_enabled && self.is_ready()
@@ -155,8 +192,10 @@ pub fn get_supported_providers() -> Vec<ProviderPreferences> {
         ProviderPreferences {
             id: "ollama".to_string(),
             name: "Ollama".to_string(),
-            url: "http://localhost:11434/v1".to_string(),
-            provider_type: ProviderType::OpenAi,
+            url: "http://localhost:11434".to_string(),
+            provider_type: ProviderType::Ollama,
+            // Opt-in: a missing daemon would otherwise stall model loading
+            enabled: false,
             ..Default::default()
         },
         ProviderPreferences {
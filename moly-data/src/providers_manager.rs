@@ -3,8 +3,16 @@ use moly_kit::aitk::clients::openai::OpenAiClient;
 use moly_kit::aitk::clients::openai_realtime::OpenAiRealtimeClient;
 use moly_kit::aitk::protocol::{Bot, BotCapabilities, BotCapability, BotClient, BotId, EntityAvatar};
 
+use crate::chat_template::ChatTemplate;
+use crate::chat_tools::ToolsClient;
+use crate::completions_client::CompletionsClient;
+use crate::ollama_client::OllamaClient;
+use crate::privacy_filter::{self, RedactingClient};
 use crate::ominix_image_client::{OminiXImageClient, ImageGenerationConfig};
 use crate::providers::{ProviderPreferences, ProviderType};
+use crate::proxied_client::ProxiedClient;
+use crate::request_log::{LoggingClient, LOCAL_RUNTIME};
+use crate::system_prompts::SystemPromptClient;
 
 /// Manages multiple AI provider clients and their models
 pub struct ProvidersManager {
@@ -14,6 +22,14 @@ pub struct ProvidersManager {
     realtime_clients: HashMap<String, OpenAiRealtimeClient>,
     /// Map of provider_id -> OminiXImageClient (for image generation)
     image_clients: HashMap<String, OminiXImageClient>,
+    /// Map of provider_id -> OllamaClient (native Ollama chat)
+    ollama_clients: HashMap<String, OllamaClient>,
+    /// Chat client for the injected local model when it takes formatted prompts
+    local_completions: Option<CompletionsClient>,
+    /// Map of provider_id -> (name, url), shown in the request log
+    endpoints: HashMap<String, (String, String)>,
+    /// Map of provider_id -> API key of the OpenAI-compatible clients, for tool rounds
+    api_keys: HashMap<String, String>,
     /// Map of provider_id -> list of bots from that provider
     provider_bots: HashMap<String, Vec<Bot>>,
     /// Combined list of all bots from all providers
@@ -34,6 +50,10 @@ impl ProvidersManager {
             clients: HashMap::new(),
             realtime_clients: HashMap::new(),
             image_clients: HashMap::new(),
+            ollama_clients: HashMap::new(),
+            local_completions: None,
+            endpoints: HashMap::new(),
+            api_keys: HashMap::new(),
             provider_bots: HashMap::new(),
             all_bots: Vec::new(),
             active_provider_id: None,
@@ -45,10 +65,15 @@ impl ProvidersManager {
         self.clients.clear();
         self.realtime_clients.clear();
         self.image_clients.clear();
+        self.ollama_clients.clear();
+        self.local_completions = None;
+        self.endpoints.clear();
+        self.api_keys.clear();
         self.provider_bots.clear();
         self.all_bots.clear();
 
         for provider in providers {
+            self.endpoints.insert(provider.id.clone(), (provider.name.clone(), provider.url.trim_end_matches('/').to_string()));
             // OminiX Image doesn't require API key for local server
             let api_key = provider.api_key.as_ref().map(|k| k.trim()).unwrap_or("");
 
@@ -82,6 +107,12 @@ impl ProvidersManager {
                     log::info!("Configured OminiX image client for provider: {} ({})", provider.id, provider.url);
                     self.image_clients.insert(provider.id.clone(), client);
                 }
+                ProviderType::Ollama => {
+                    // Local daemon, no API key
+                    let client = OllamaClient::new(&provider.url);
+                    log::info!("Configured Ollama client for provider: {} ({})", provider.id, client.get_url());
+                    self.ollama_clients.insert(provider.id.clone(), client);
+                }
                 _ => {
                     if api_key.is_empty() {
                         continue;
@@ -91,6 +122,7 @@ impl ProvidersManager {
                     if client.set_key(api_key).is_ok() {
                         log::info!("Configured client for provider: {} ({})", provider.id, provider.url);
                         self.clients.insert(provider.id.clone(), client);
+                        self.api_keys.insert(provider.id.clone(), api_key.to_string());
 
                         // Set first provider as active if none set
                         if self.active_provider_id.is_none() {
@@ -152,16 +184,51 @@ impl ProvidersManager {
     }
 
     /// Get a boxed BotClient for any provider type
+    ///
+    /// Chat requests through it are kept in [`crate::request_log`], and
+    /// those to cloud providers pass the [`crate::privacy_filter`].
+    /// OpenAI-compatible chats offer the open chat's tools
+    /// ([`crate::chat_tools`]), and those to remote providers use the
+    /// configured proxy ([`crate::proxied_client`]).
     pub fn get_bot_client(&self, provider_id: &str) -> Option<Box<dyn BotClient>> {
-        if let Some(client) = self.clients.get(provider_id) {
-            Some(Box::new(client.clone()))
+        let (client, path, chats): (Box<dyn BotClient>, &str, bool) = if provider_id == "ominix-local" {
+            // The open chat's system prompt goes with every request to the local model
+            let (client, path): (Box<dyn BotClient>, &str) = match &self.local_completions {
+                Some(client) => (Box::new(client.clone()), "/v1/completions"),
+                None => {
+                    let client = Box::new(self.clients.get(provider_id)?.clone());
+                    (Box::new(ToolsClient::new(client, "http://localhost:8080/v1", Some("sk-local"))), "/v1/chat/completions")
+                }
+            };
+            let client = SystemPromptClient::new(client);
+            return Some(Box::new(LoggingClient::new(Box::new(client), LOCAL_RUNTIME, &format!("http://localhost:8080{}", path))));
+        } else if let Some(client) = self.clients.get(provider_id) {
+            // The chat's system prompt, which may come from the provider's chat defaults
+            let url = self.endpoints.get(provider_id).map(|(_, url)| url.as_str()).unwrap_or_default();
+            let key = self.api_keys.get(provider_id).map(String::as_str);
+            let client = ToolsClient::new(Box::new(ProxiedClient::new(Box::new(client.clone()), url, key)), url, key);
+            (Box::new(SystemPromptClient::new(Box::new(client))), "/chat/completions", true)
         } else if let Some(client) = self.realtime_clients.get(provider_id) {
-            Some(Box::new(client.clone()))
+            (Box::new(client.clone()), "", false)
         } else if let Some(client) = self.image_clients.get(provider_id) {
-            Some(Box::new(client.clone()))
+            (Box::new(client.clone()), "/images/generations", false)
+        } else if let Some(client) = self.ollama_clients.get(provider_id) {
+            (Box::new(SystemPromptClient::new(Box::new(client.clone()))), "/api/chat", true)
         } else {
-            None
+            return None;
+        };
+        let (name, url) = self.endpoints.get(provider_id).cloned().unwrap_or_else(|| (provider_id.to_string(), String::new()));
+        let client = Box::new(LoggingClient::new(client, &name, &format!("{}{}", url, path)));
+        // Outside the logging client, so the log shows what was actually sent
+        if chats && privacy_filter::is_cloud(&url) {
+            return Some(Box::new(RedactingClient::new(client)));
         }
+        Some(client)
+    }
+
+    /// Whether chats with a provider leave this computer and the local network
+    pub fn is_cloud(&self, provider_id: &str) -> bool {
+        self.endpoints.get(provider_id).is_some_and(|(_, url)| privacy_filter::is_cloud(url))
     }
 
     /// Set the active provider by ID
@@ -241,6 +308,11 @@ impl ProvidersManager {
                 return Some(provider_id);
             }
         }
+        for (provider_id, _) in &self.ollama_clients {
+            if bot_id_str.contains(provider_id) {
+                return Some(provider_id);
+            }
+        }
         None
     }
 
@@ -249,8 +321,9 @@ impl ProvidersManager {
     /// This bypasses the normal configure_providers flow and directly registers
     /// an OpenAI-compatible client for the locally-running ominix-api server.
     /// When `is_vlm` is true, the bot gets `AttachmentInput` capability so the
-    /// built-in attach button shows in the PromptInput.
-    pub fn inject_local_model(&mut self, model_id: &str, is_vlm: bool) {
+    /// built-in attach button shows in the PromptInput. With a `template`,
+    /// chat goes through a [`CompletionsClient`] that formats the prompt.
+    pub fn inject_local_model(&mut self, model_id: &str, is_vlm: bool, template: Option<ChatTemplate>) {
         let mut client = OpenAiClient::new("http://localhost:8080/v1".to_string());
         // Local server accepts any non-empty key string
         let _ = client.set_key("sk-local");
@@ -269,14 +342,16 @@ impl ProvidersManager {
             avatar: EntityAvatar::Text("AI".to_string()),
             capabilities,
         };
+        self.local_completions = template.map(|t| CompletionsClient::new("http://localhost:8080/v1", t, vec![bot.clone()]));
         self.provider_bots.insert("ominix-local".to_string(), vec![bot]);
         self.rebuild_all_bots();
-        log::info!("Injected local model: {} (vlm={}) at localhost:8080", model_id, is_vlm);
+        log::info!("Injected local model: {} (vlm={}, template={:?}) at localhost:8080", model_id, is_vlm, template);
     }
 
     /// Remove the injected local model provider
     pub fn remove_local_model(&mut self) {
         self.clients.remove("ominix-local");
+        self.local_completions = None;
         self.provider_bots.remove("ominix-local");
         self.rebuild_all_bots();
         log::info!("Removed local model provider");
@@ -290,6 +365,7 @@ impl ProvidersManager {
     /// Check if any providers are configured
     pub fn has_providers(&self) -> bool {
         !self.clients.is_empty() || !self.realtime_clients.is_empty() || !self.image_clients.is_empty()
+            || !self.ollama_clients.is_empty()
     }
 
     /// Get list of configured provider IDs
@@ -297,6 +373,7 @@ impl ProvidersManager {
         self.clients.keys()
             .chain(self.realtime_clients.keys())
             .chain(self.image_clients.keys())
+            .chain(self.ollama_clients.keys())
             .map(|s| s.as_str())
             .collect()
     }
@@ -0,0 +1,230 @@
+//! Chat with OpenAI-compatible providers through the configured proxy
+//!
+//! moly-kit builds the HTTP client of its OpenAI client itself, so it can't
+//! be handed the proxy or root certificates of [`crate::network`], and its
+//! requests carry no temperature or answer length. While a proxy or
+//! certificates are set, [`ProxiedClient`] lists the provider's models with
+//! a client built here; while either is set or the open chat has sampling
+//! ([`crate::chat_defaults::active`]), it streams the chat completions
+//! itself. Requests it can't express, those with attachments or with tools
+//! (A2UI), still go through the wrapped client.
+
+use futures::StreamExt;
+use moly_kit::aitk::protocol::*;
+use moly_kit::aitk::utils::asynchronous::{BoxPlatformSendFuture, BoxPlatformSendStream};
+use serde_json::{json, Value};
+
+use crate::chat_defaults;
+use crate::network;
+use crate::stream_lines::LineBuffer;
+
+fn role(from: &EntityId) -> Option<&'static str> {
+    match from {
+        EntityId::User => Some("user"),
+        EntityId::Bot(_) => Some("assistant"),
+        EntityId::System => Some("system"),
+        _ => None,
+    }
+}
+
+/// The text of one server-sent `data:` line of a streamed completion;
+/// `None` for other lines and the final `[DONE]`
+fn stream_delta(line: &str) -> Option<Result<String, String>> {
+    let data = line.trim().strip_prefix("data:")?.trim();
+    if data == "[DONE]" {
+        return None;
+    }
+    let value: Value = serde_json::from_str(data).ok()?;
+    if let Some(error) = value.get("error") {
+        let message = error.get("message").and_then(Value::as_str).unwrap_or("unknown error");
+        return Some(Err(message.to_string()));
+    }
+    value.pointer("/choices/0/delta/content").and_then(Value::as_str).map(|text| Ok(text.to_string()))
+}
+
+/// Sends the chats of an OpenAI-compatible provider through the proxy and
+/// with the chat's sampling
+pub struct ProxiedClient {
+    inner: Box<dyn BotClient>,
+    base_url: String,
+    api_key: Option<String>,
+}
+
+impl ProxiedClient {
+    pub fn new(inner: Box<dyn BotClient>, base_url: &str, api_key: Option<&str>) -> Self {
+        Self {
+            inner,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            api_key: api_key.filter(|k| !k.is_empty()).map(str::to_string),
+        }
+    }
+
+    fn post(&self, client: &reqwest::Client, path: &str) -> reqwest::RequestBuilder {
+        let request = client.post(format!("{}{}", self.base_url, path));
+        match &self.api_key {
+            Some(key) => request.bearer_auth(key),
+            None => request,
+        }
+    }
+}
+
+impl Clone for ProxiedClient {
+    fn clone(&self) -> Self {
+        Self { inner: self.inner.clone_box(), base_url: self.base_url.clone(), api_key: self.api_key.clone() }
+    }
+}
+
+impl BotClient for ProxiedClient {
+    fn bots(&mut self) -> BoxPlatformSendFuture<'static, ClientResult<Vec<Bot>>> {
+        if !network::current().is_custom() {
+            return self.inner.bots();
+        }
+        let client = network::async_client().build().unwrap_or_else(|_| reqwest::Client::new());
+        let url = format!("{}/models", self.base_url);
+        let mut request = client.get(&url);
+        if let Some(key) = &self.api_key {
+            request = request.bearer_auth(key);
+        }
+        Box::pin(async move {
+            let response = match request.send().await {
+                Ok(response) => response,
+                Err(e) => {
+                    return ClientResult::new_err(ClientError::new_with_source(
+                        ClientErrorKind::Network,
+                        format!("Could not reach {url} through the proxy"),
+                        Some(e),
+                    ).into());
+                }
+            };
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            if !status.is_success() {
+                return ClientResult::new_err(ClientError::new(
+                    ClientErrorKind::Response,
+                    format!("Request to {url} failed with status {}: {}", status, text),
+                ).into());
+            }
+            let ids: Vec<String> = serde_json::from_str::<Value>(&text).ok()
+                .and_then(|v| v.get("data").and_then(Value::as_array).cloned())
+                .unwrap_or_default()
+                .iter()
+                .filter_map(|m| m.get("id").and_then(Value::as_str).map(str::to_string))
+                .collect();
+            let bots = ids.into_iter()
+                .map(|id| Bot {
+                    id: BotId::new(&id),
+                    name: id,
+                    avatar: EntityAvatar::Text("AI".to_string()),
+                    capabilities: BotCapabilities::new().with_capability(BotCapability::TextInput),
+                })
+                .collect();
+            ClientResult::new_ok(bots)
+        })
+    }
+
+    fn send(
+        &mut self,
+        bot_id: &BotId,
+        messages: &[Message],
+        tools: &[Tool],
+    ) -> BoxPlatformSendStream<'static, ClientResult<MessageContent>> {
+        let plain = tools.is_empty() && messages.iter().all(|m| m.content.attachments.is_empty());
+        let sampling = chat_defaults::active();
+        if !plain || (!network::current().is_custom() && sampling.is_default()) {
+            return self.inner.send(bot_id, messages, tools);
+        }
+        let conversation: Vec<Value> = messages
+            .iter()
+            .filter(|m| !m.content.text.trim().is_empty())
+            .filter_map(|m| Some(json!({ "role": role(&m.from)?, "content": m.content.text })))
+            .collect();
+        let mut body = json!({ "model": bot_id.id(), "messages": conversation, "stream": true });
+        if let Some(temperature) = sampling.temperature {
+            body["temperature"] = temperature.into();
+        }
+        if let Some(max_tokens) = sampling.max_tokens {
+            body["max_tokens"] = max_tokens.into();
+        }
+        let client = network::async_client().build().unwrap_or_else(|_| reqwest::Client::new());
+        let request = self.post(&client, "/chat/completions").json(&body);
+        let url = format!("{}/chat/completions", self.base_url);
+
+        Box::pin(async_stream::stream! {
+            let response = match request.send().await {
+                Ok(response) => response,
+                Err(e) => {
+                    yield ClientResult::new_err(ClientError::new_with_source(
+                        ClientErrorKind::Network,
+                        format!("Could not send request to {url}"),
+                        Some(e),
+                    ).into());
+                    return;
+                }
+            };
+            if !response.status().is_success() {
+                let status = response.status();
+                let text = response.text().await.unwrap_or_default();
+                yield ClientResult::new_err(ClientError::new(
+                    ClientErrorKind::Response,
+                    format!("Request to {url} failed with status {} and content: {}", status, text),
+                ).into());
+                return;
+            }
+
+            // Server-sent events, one `data:` line per delta; yield the
+            // accumulated content
+            let mut content = MessageContent::default();
+            let mut buffer = LineBuffer::default();
+            let mut stream = response.bytes_stream();
+            while let Some(chunk) = stream.next().await {
+                let chunk = match chunk {
+                    Ok(chunk) => chunk,
+                    Err(e) => {
+                        yield ClientResult::new_err(ClientError::new_with_source(
+                            ClientErrorKind::Network,
+                            "Response stream interrupted".to_string(),
+                            Some(e),
+                        ).into());
+                        return;
+                    }
+                };
+                buffer.push(&chunk);
+                while let Some(line) = buffer.next_line() {
+                    match stream_delta(&line) {
+                        Some(Ok(delta)) => {
+                            content.text.push_str(&delta);
+                            yield ClientResult::new_ok(content.clone());
+                        }
+                        Some(Err(e)) => {
+                            yield ClientResult::new_err(ClientError::new(ClientErrorKind::Response, e).into());
+                            return;
+                        }
+                        None => {}
+                    }
+                }
+            }
+        })
+    }
+
+    fn clone_box(&self) -> Box<dyn BotClient> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stream_delta() {
+        let line = r#"data: {"choices":[{"index":0,"delta":{"content":"Hel"}}]}"#;
+        assert_eq!(stream_delta(line), Some(Ok("Hel".to_string())));
+        assert_eq!(stream_delta(r#"data: {"choices":[{"delta":{"role":"assistant"}}]}"#), None);
+        assert_eq!(stream_delta("data: [DONE]"), None);
+        assert_eq!(stream_delta(": keep-alive"), None);
+        assert_eq!(
+            stream_delta(r#"data: {"error":{"message":"Rate limit reached"}}"#),
+            Some(Err("Rate limit reached".to_string()))
+        );
+    }
+}
@@ -0,0 +1,315 @@
+//! Quick Ask: a question from any app
+//!
+//! A desktop keyboard shortcut runs the app with [`FLAG`]. When an instance
+//! is already running, [`summon_running`] hands it the request over a
+//! localhost socket ([`SummonListener`]) and the new process exits; otherwise
+//! the app starts with Quick Ask open. The Quick Ask window streams the
+//! answer of the loaded LLM ([`ask`]) and can turn the exchange into a full
+//! chat ([`as_messages`]). Message links opened while the app runs reach
+//! it over the same socket ([`open_in_running`]).
+//!
+//! Shortcuts belong to the desktop, so the app registers one with it
+//! ([`crate::platform::register_shortcut`]): a GNOME custom keybinding, or
+//! the shortcut key of a Start menu entry on Windows. macOS only binds
+//! commands to keys in System Settings, where it is done by hand.
+
+use moly_kit::prelude::*;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::time::Duration;
+
+use crate::chat_template::{self, ChatTemplateOverrides};
+use crate::model_registry::RegistryModel;
+use crate::ominix_api_client::{ChatCompletionRequest, ChatMessage, OminiXApiClient};
+
+/// Command-line flag the shortcut runs the app with
+pub const FLAG: &str = "--quick-ask";
+
+/// Shortcut suggested until the user picks one
+pub const DEFAULT_HOTKEY: &str = "Ctrl+Alt+Space";
+
+/// Localhost port a running instance listens on for [`FLAG`]
+const SUMMON_PORT: u16 = 47291;
+
+const SUMMON_MESSAGE: &[u8] = b"ominix-quick-ask\n";
+/// Followed by a message link, see [`open_in_running`]
+const OPEN_MESSAGE: &str = "ominix-open ";
+const SUMMON_REPLY: &[u8] = b"ok\n";
+
+/// A key with modifiers, written like `Ctrl+Alt+Space`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Hotkey {
+    pub ctrl: bool,
+    pub alt: bool,
+    pub shift: bool,
+    /// Cmd on macOS, the Windows key elsewhere
+    pub super_key: bool,
+    /// `A`–`Z`, `0`–`9`, `F1`–`F12` or `Space`
+    pub key: String,
+}
+
+impl Hotkey {
+    pub fn parse(text: &str) -> Result<Hotkey, String> {
+        let mut hotkey = Hotkey { ctrl: false, alt: false, shift: false, super_key: false, key: String::new() };
+        for part in text.split('+').map(str::trim) {
+            match part.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => hotkey.ctrl = true,
+                "alt" | "option" => hotkey.alt = true,
+                "shift" => hotkey.shift = true,
+                "super" | "cmd" | "command" | "win" | "meta" => hotkey.super_key = true,
+                _ if !hotkey.key.is_empty() => return Err(format!("\"{}\" has more than one key", text.trim())),
+                "space" => hotkey.key = "Space".to_string(),
+                key if key.len() == 1 && key.chars().all(|c| c.is_ascii_alphanumeric()) => {
+                    hotkey.key = key.to_ascii_uppercase();
+                }
+                key if key.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()).is_some_and(|n| (1..=12).contains(&n)) => {
+                    hotkey.key = key.to_ascii_uppercase();
+                }
+                _ => return Err(format!("\"{}\" isn't a key a shortcut can use", part)),
+            }
+        }
+        if hotkey.key.is_empty() {
+            return Err("The shortcut needs a key, like Ctrl+Alt+Space".to_string());
+        }
+        if !(hotkey.ctrl || hotkey.alt || hotkey.super_key) {
+            return Err("Add Ctrl, Alt or Super to the shortcut so it doesn't get in the way of typing".to_string());
+        }
+        Ok(hotkey)
+    }
+
+    /// GNOME's accelerator syntax, e.g. `<Control><Alt>space`
+    pub fn gnome(&self) -> String {
+        let mut out = String::new();
+        for (on, name) in [(self.ctrl, "<Control>"), (self.alt, "<Alt>"), (self.shift, "<Shift>"), (self.super_key, "<Super>")] {
+            if on {
+                out.push_str(name);
+            }
+        }
+        out.push_str(&if self.key.len() == 1 { self.key.to_ascii_lowercase() } else { self.key.replace("Space", "space") });
+        out
+    }
+
+    /// A Windows shortcut's hotkey, e.g. `CTRL+ALT+SPACE`. Windows doesn't
+    /// allow the Windows key in these.
+    pub fn windows(&self) -> Result<String, String> {
+        if self.super_key {
+            return Err("Windows shortcuts can't use the Windows key; use Ctrl and Alt instead".to_string());
+        }
+        let mut parts = Vec::new();
+        for (on, name) in [(self.ctrl, "CTRL"), (self.alt, "ALT"), (self.shift, "SHIFT")] {
+            if on {
+                parts.push(name.to_string());
+            }
+        }
+        parts.push(self.key.to_ascii_uppercase());
+        Ok(parts.join("+"))
+    }
+}
+
+/// The command a shortcut should run to open Quick Ask
+pub fn command() -> Vec<String> {
+    let program = std::env::current_exe().map(|p| p.display().to_string()).unwrap_or_else(|_| "ominix-studio".to_string());
+    vec![program, FLAG.to_string()]
+}
+
+/// Hand a Quick Ask request to the instance already running. Returns
+/// whether one took it.
+pub fn summon_running() -> bool {
+    send_to_running(SUMMON_MESSAGE)
+}
+
+/// Hand a message link ([`crate::chat_navigation::permalink`]) to the
+/// instance already running. Returns whether one took it.
+pub fn open_in_running(link: &str) -> bool {
+    send_to_running(format!("{}{}\n", OPEN_MESSAGE, link.trim()).as_bytes())
+}
+
+fn send_to_running(message: &[u8]) -> bool {
+    let addr = SocketAddr::from((Ipv4Addr::LOCALHOST, SUMMON_PORT));
+    let Ok(mut stream) = TcpStream::connect_timeout(&addr, Duration::from_millis(500)) else { return false };
+    let _ = stream.set_read_timeout(Some(Duration::from_secs(2)));
+    let mut reply = [0u8; 3];
+    stream.write_all(message).is_ok() && stream.read_exact(&mut reply).is_ok() && reply == SUMMON_REPLY
+}
+
+/// A request from another process
+#[derive(Clone, Debug, PartialEq)]
+pub enum Summon {
+    QuickAsk,
+    /// Show the chat a message link points to
+    Open(String),
+}
+
+fn parse_summon(line: &str) -> Option<Summon> {
+    if line.as_bytes() == SUMMON_MESSAGE {
+        return Some(Summon::QuickAsk);
+    }
+    let link = line.strip_prefix(OPEN_MESSAGE)?.trim();
+    (!link.is_empty()).then(|| Summon::Open(link.to_string()))
+}
+
+/// Listens for [`summon_running`] and [`open_in_running`] from other
+/// processes while it is alive
+pub struct SummonListener {
+    rx: mpsc::Receiver<Summon>,
+    stop: Arc<AtomicBool>,
+}
+
+impl SummonListener {
+    pub fn start() -> Result<Self, String> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, SUMMON_PORT))
+            .map_err(|e| format!("Quick Ask can't listen on port {}: {}", SUMMON_PORT, e))?;
+        listener.set_nonblocking(true).map_err(|e| e.to_string())?;
+        let (tx, rx) = mpsc::channel();
+        let stop = Arc::new(AtomicBool::new(false));
+        let stopped = stop.clone();
+        std::thread::spawn(move || {
+            while !stopped.load(Ordering::Relaxed) {
+                match listener.accept() {
+                    Ok((mut stream, _)) => {
+                        let _ = stream.set_nonblocking(false);
+                        let _ = stream.set_read_timeout(Some(Duration::from_secs(2)));
+                        let mut line = String::new();
+                        let read = BufReader::new((&stream).take(4096)).read_line(&mut line);
+                        if let Some(summon) = read.ok().and_then(|_| parse_summon(&line)) {
+                            let _ = stream.write_all(SUMMON_REPLY);
+                            if tx.send(summon).is_err() {
+                                return;
+                            }
+                            makepad_widgets::SignalToUI::set_ui_signal();
+                        }
+                    }
+                    Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                        std::thread::sleep(Duration::from_millis(100));
+                    }
+                    Err(e) => {
+                        log::warn!("Quick Ask accept failed: {}", e);
+                        std::thread::sleep(Duration::from_secs(1));
+                    }
+                }
+            }
+        });
+        Ok(Self { rx, stop })
+    }
+
+    /// Requests received since the last call
+    pub fn poll(&self) -> Vec<Summon> {
+        self.rx.try_iter().collect()
+    }
+}
+
+impl Drop for SummonListener {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Progress of an answer
+#[derive(Clone, Debug, PartialEq)]
+pub enum AskEvent {
+    /// The next piece of the answer
+    Text(String),
+    Done,
+    Failed(String),
+}
+
+/// Ask `model` a question on its own thread. Models answered through a chat
+/// template aren't streamed and arrive in one piece. Dropping the receiver
+/// stops the answer.
+pub fn ask(model: &RegistryModel, question: &str) -> mpsc::Receiver<AskEvent> {
+    let (tx, rx) = mpsc::channel();
+    let template = chat_template::template_for(model, &ChatTemplateOverrides::load());
+    let request = ChatCompletionRequest {
+        model: model.runtime.api_model_id.clone(),
+        messages: vec![ChatMessage::user(question.trim())],
+        max_tokens: None,
+    };
+    std::thread::spawn(move || {
+        let send = |event: AskEvent| {
+            let sent = tx.send(event).is_ok();
+            makepad_widgets::SignalToUI::set_ui_signal();
+            sent
+        };
+        let client = OminiXApiClient::localhost(600);
+        let result = match template {
+            Some(template) => chat_template::complete(&client, &request.model, Some(template), request.messages.clone())
+                .map(|answer| {
+                    send(AskEvent::Text(answer));
+                }),
+            None => client.chat_completion_stream(&request, |piece| send(AskEvent::Text(piece.to_string()))).map(|_| ()),
+        };
+        send(match result {
+            Ok(()) => AskEvent::Done,
+            Err(e) => AskEvent::Failed(e.to_string()),
+        });
+    });
+    rx
+}
+
+/// The part of an answer worth showing: a reasoning model's `<think>` block
+/// is left out, and while it is still open nothing is shown yet
+pub fn visible_answer(answer: &str) -> &str {
+    let trimmed = answer.trim_start();
+    if !trimmed.starts_with("<think>") {
+        return answer.trim();
+    }
+    match trimmed.find("</think>") {
+        Some(end) => trimmed[end + "</think>".len()..].trim(),
+        None => "",
+    }
+}
+
+/// A Quick Ask exchange as the messages of a chat with `model_id`
+pub fn as_messages(model_id: &str, question: &str, answer: &str) -> Vec<Message> {
+    let message = |from: EntityId, text: &str| Message {
+        from,
+        content: MessageContent { text: text.trim().to_string(), ..Default::default() },
+        ..Default::default()
+    };
+    let mut messages = vec![message(EntityId::User, question)];
+    if !answer.trim().is_empty() {
+        messages.push(message(EntityId::Bot(BotId::new(model_id)), answer));
+    }
+    messages
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hotkeys() {
+        let hotkey = Hotkey::parse("ctrl + alt + space").unwrap();
+        assert_eq!(hotkey.gnome(), "<Control><Alt>space");
+        assert_eq!(hotkey.windows().unwrap(), "CTRL+ALT+SPACE");
+        let hotkey = Hotkey::parse("Cmd+Shift+k").unwrap();
+        assert_eq!(hotkey.gnome(), "<Shift><Super>k");
+        assert!(hotkey.windows().is_err());
+        assert_eq!(Hotkey::parse("Alt+F9").unwrap().gnome(), "<Alt>F9");
+        assert!(Hotkey::parse("Shift+A").is_err());
+        assert!(Hotkey::parse("Ctrl+Alt").is_err());
+        assert!(Hotkey::parse("Ctrl+A+B").is_err());
+        assert!(Hotkey::parse("Ctrl+Tab").is_err());
+    }
+
+    #[test]
+    fn test_summon_messages() {
+        assert_eq!(parse_summon("ominix-quick-ask\n"), Some(Summon::QuickAsk));
+        assert_eq!(parse_summon("ominix-open ominix://chat/42#3\n"), Some(Summon::Open("ominix://chat/42#3".to_string())));
+        assert_eq!(parse_summon("ominix-open \n"), None);
+        assert_eq!(parse_summon("hello\n"), None);
+    }
+
+    #[test]
+    fn test_visible_answer_and_messages() {
+        assert_eq!(visible_answer("<think>The user wants"), "");
+        assert_eq!(visible_answer("<think>Short.</think>\n\nParis."), "Paris.");
+        assert_eq!(visible_answer(" Paris. "), "Paris.");
+        let messages = as_messages("qwen3-4b", "Capital of France?", "Paris.");
+        assert_eq!(messages.len(), 2);
+        assert!(matches!(&messages[1].from, EntityId::Bot(bot) if bot.id() == "qwen3-4b"));
+        assert_eq!(as_messages("qwen3-4b", "Hi", " ").len(), 1);
+    }
+}
@@ -0,0 +1,331 @@
+//! Reading a conversation aloud
+//!
+//! [`sentences`] turns a chat's messages, or a range of them, into the
+//! sentences to speak: Markdown is stripped and code blocks are skipped. A
+//! [`ReadAloud`] then speaks them in order with a TTS model loaded in the
+//! local runtime, one `/v1/audio/speech` request per sentence, fetching a few
+//! ahead of the one playing so there is no gap between them. Knowing which
+//! sentence is playing lets the chat highlight it, and skipping or changing
+//! the speed only costs the sentence being spoken.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::process::Child;
+use std::sync::mpsc;
+
+use crate::model_registry::{ModelRegistry, RegistryCategory};
+use crate::model_state;
+use crate::ominix_api_client::{OminiXApiClient, SpeechRequest};
+use crate::store::ModelLoadPhase;
+use crate::tts_longform::{split_sentences, DEFAULT_TTS_CHUNK_CHARS};
+use crate::tts_voices::VoiceMap;
+
+/// Playback speeds offered, 1.0 being the model's own
+pub const SPEEDS: [f32; 5] = [0.75, 1.0, 1.25, 1.5, 2.0];
+
+/// Sentences synthesized ahead of the one playing
+const PREFETCH: usize = 2;
+
+/// A sentence to speak, and the message it comes from
+#[derive(Clone, Debug, PartialEq)]
+pub struct Sentence {
+    pub message_index: usize,
+    pub text: String,
+}
+
+/// The sentences of `messages`, given as `(index in the chat, text)`
+pub fn sentences<'a>(messages: impl IntoIterator<Item = (usize, &'a str)>) -> Vec<Sentence> {
+    messages.into_iter()
+        .flat_map(|(message_index, text)| {
+            let plain = crate::audiobook::markdown_to_plain(&without_code(text));
+            split_sentences(&plain, DEFAULT_TTS_CHUNK_CHARS)
+                .into_iter()
+                .map(move |text| Sentence { message_index, text })
+        })
+        .collect()
+}
+
+/// `markdown` without its fenced code blocks, which don't read well
+fn without_code(markdown: &str) -> String {
+    let mut out = String::new();
+    let mut fence: Option<&str> = None;
+    for line in markdown.lines() {
+        let trimmed = line.trim_start();
+        match fence {
+            Some(open) if trimmed.starts_with(open) => fence = None,
+            Some(_) => {}
+            None if trimmed.starts_with("```") || trimmed.starts_with("~~~") => fence = Some(&trimmed[..3]),
+            None => {
+                out.push_str(line);
+                out.push('\n');
+            }
+        }
+    }
+    out
+}
+
+/// API id of a text-to-speech model the runtime has loaded
+pub fn loaded_tts_model(registry: &ModelRegistry) -> Option<String> {
+    registry.by_category(RegistryCategory::Tts)
+        .find(|m| model_state::load_phase(&m.id) == Some(ModelLoadPhase::Loaded))
+        .map(|m| m.runtime.api_model_id.clone())
+}
+
+struct Pending {
+    index: usize,
+    speed: f32,
+    rx: mpsc::Receiver<Result<PathBuf, String>>,
+}
+
+/// Speaks a list of sentences in order. Call [`ReadAloud::tick`] often (every
+/// frame while it runs); it starts requests and playback and notices when a
+/// sentence ends.
+pub struct ReadAloud {
+    sentences: Vec<Sentence>,
+    model_id: String,
+    voice: String,
+    /// Voices by language; `voice` speaks the rest
+    voices: VoiceMap,
+    /// Language hint sent with every sentence
+    language: Option<String>,
+    current: usize,
+    speed: f32,
+    paused: bool,
+    /// Audio of sentences at the current speed
+    audio: HashMap<usize, PathBuf>,
+    pending: Option<Pending>,
+    player: Option<Child>,
+    error: Option<String>,
+}
+
+impl ReadAloud {
+    pub fn new(sentences: Vec<Sentence>, model_id: impl Into<String>, voice: impl Into<String>) -> Self {
+        Self {
+            sentences,
+            model_id: model_id.into(),
+            voice: voice.into(),
+            voices: VoiceMap::default(),
+            language: None,
+            current: 0,
+            speed: 1.0,
+            paused: false,
+            audio: HashMap::new(),
+            pending: None,
+            player: None,
+            error: None,
+        }
+    }
+
+    /// Read with a [`crate::voice_presets::VoicePreset`]'s speed and language
+    pub fn with_preset(mut self, preset: &crate::voice_presets::VoicePreset) -> Self {
+        self.speed = preset.speed;
+        self.language = preset.language_hint();
+        self
+    }
+
+    /// Speak each sentence with the voice of its language
+    pub fn with_voices(mut self, voices: VoiceMap) -> Self {
+        self.voices = voices;
+        self
+    }
+
+    /// The sentence being spoken, or next to be
+    pub fn current(&self) -> Option<&Sentence> {
+        self.sentences.get(self.current)
+    }
+
+    /// The sentences before and after the current one, for context
+    pub fn neighbors(&self) -> (Option<&Sentence>, Option<&Sentence>) {
+        let before = self.current.checked_sub(1).and_then(|i| self.sentences.get(i));
+        (before, self.sentences.get(self.current + 1))
+    }
+
+    /// (1-based number of the current sentence, number of sentences)
+    pub fn position(&self) -> (usize, usize) {
+        ((self.current + 1).min(self.sentences.len()), self.sentences.len())
+    }
+
+    pub fn speed(&self) -> f32 {
+        self.speed
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.current >= self.sentences.len()
+    }
+
+    /// Whether the current sentence is still being synthesized
+    pub fn is_waiting(&self) -> bool {
+        !self.paused && !self.is_finished() && self.player.is_none() && self.error.is_none()
+    }
+
+    /// Why reading stopped, if it failed
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    /// Pause, or resume from the start of the sentence that was playing
+    pub fn toggle_pause(&mut self) {
+        self.paused = !self.paused;
+        self.stop_player();
+        if !self.paused {
+            self.error = None;
+        }
+    }
+
+    /// Move `delta` sentences forward or back, stopping the one playing
+    pub fn skip(&mut self, delta: isize) {
+        let last = self.sentences.len().saturating_sub(1) as isize;
+        self.current = (self.current as isize + delta).clamp(0, last.max(0)) as usize;
+        self.stop_player();
+        self.error = None;
+    }
+
+    /// Speak at `speed` from the current sentence on
+    pub fn set_speed(&mut self, speed: f32) {
+        if speed == self.speed {
+            return;
+        }
+        self.speed = speed;
+        self.audio.clear();
+        self.stop_player();
+    }
+
+    /// The next speed of [`SPEEDS`], wrapping around
+    pub fn next_speed(&self) -> f32 {
+        let at = SPEEDS.iter().position(|s| *s == self.speed).unwrap_or(0);
+        SPEEDS[(at + 1) % SPEEDS.len()]
+    }
+
+    /// Advance playback. Returns whether the current sentence or the state
+    /// shown to the user changed.
+    pub fn tick(&mut self) -> bool {
+        let mut changed = self.receive_audio();
+        if self.paused || self.error.is_some() || self.is_finished() {
+            return changed;
+        }
+        if let Some(player) = self.player.as_mut() {
+            match player.try_wait() {
+                Ok(None) => {}
+                Ok(Some(_)) | Err(_) => {
+                    self.player = None;
+                    self.audio.remove(&self.current);
+                    self.current += 1;
+                    changed = true;
+                }
+            }
+        }
+        if self.player.is_none() && !self.is_finished() {
+            if let Some(path) = self.audio.get(&self.current) {
+                match crate::platform::play_audio(path) {
+                    Ok(child) => self.player = Some(child),
+                    Err(e) => self.error = Some(e),
+                }
+                changed = true;
+            }
+        }
+        self.request_next();
+        changed
+    }
+
+    /// Stop speaking for good
+    pub fn stop(&mut self) {
+        self.stop_player();
+        self.current = self.sentences.len();
+    }
+
+    fn stop_player(&mut self) {
+        if let Some(mut player) = self.player.take() {
+            let _ = player.kill();
+            let _ = player.wait();
+        }
+    }
+
+    fn receive_audio(&mut self) -> bool {
+        let Some(pending) = &self.pending else { return false };
+        let result = match pending.rx.try_recv() {
+            Ok(result) => result,
+            Err(mpsc::TryRecvError::Empty) => return false,
+            Err(mpsc::TryRecvError::Disconnected) => Err("Speech synthesis stopped unexpectedly".to_string()),
+        };
+        let Some(pending) = self.pending.take() else { return false };
+        if pending.speed != self.speed || pending.index < self.current {
+            // Skipped past, or made at another speed
+            return false;
+        }
+        match result {
+            Ok(path) => {
+                self.audio.insert(pending.index, path);
+                false
+            }
+            Err(e) => {
+                self.error = Some(format!("Couldn't speak sentence {}: {}", pending.index + 1, e));
+                true
+            }
+        }
+    }
+
+    /// Synthesize the first sentence from the current one on that has no audio
+    fn request_next(&mut self) {
+        if self.pending.is_some() {
+            return;
+        }
+        let end = (self.current + 1 + PREFETCH).min(self.sentences.len());
+        let Some(index) = (self.current..end).find(|i| !self.audio.contains_key(i)) else { return };
+        let text = &self.sentences[index].text;
+        let request = SpeechRequest {
+            speed: Some(self.speed).filter(|s| *s != 1.0),
+            language: self.language.clone(),
+            ..SpeechRequest::wav(&self.model_id, self.voices.pick(text, &self.voice), text)
+        };
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let result = OminiXApiClient::localhost(120)
+                .speech(&request)
+                .map_err(String::from)
+                .and_then(|bytes| {
+                    let path = crate::artifacts::unique_path("read-aloud", "wav");
+                    std::fs::write(&path, &bytes).map_err(|e| e.to_string())?;
+                    Ok(path)
+                });
+            let _ = tx.send(result);
+        });
+        self.pending = Some(Pending { index, speed: self.speed, rx });
+    }
+}
+
+impl Drop for ReadAloud {
+    fn drop(&mut self) {
+        self.stop_player();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sentences_and_navigation() {
+        let answer = "# Plan\n\nFirst, **load** the model. Then ask!\n\n```rust\nfn main() {}\n```\n- Keep it short";
+        let list = sentences([(0, "Hi there."), (3, answer)]);
+        let texts: Vec<&str> = list.iter().map(|s| s.text.as_str()).collect();
+        assert_eq!(texts, ["Hi there.", "Plan", "First, load the model.", "Then ask!", "Keep it short"]);
+        assert_eq!(list[2].message_index, 3);
+
+        let mut reader = ReadAloud::new(list, "qwen3-tts", "vivian");
+        assert_eq!(reader.position(), (1, 5));
+        reader.skip(-1);
+        assert_eq!(reader.current().unwrap().text, "Hi there.");
+        reader.skip(10);
+        assert_eq!(reader.position(), (5, 5));
+        assert_eq!(reader.neighbors().0.unwrap().text, "Then ask!");
+        assert_eq!(reader.next_speed(), 1.25);
+        reader.set_speed(2.0);
+        assert_eq!(reader.next_speed(), 0.75);
+        reader.stop();
+        assert!(reader.is_finished() && !reader.is_waiting());
+    }
+}
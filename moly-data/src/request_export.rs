@@ -0,0 +1,128 @@
+//! Runtime requests saved as files to run again outside the app
+//!
+//! A client made with [`crate::OminiXApiClient::recording`] keeps the last
+//! request it sends under a name, the Hub panel's, with its whole body:
+//! parameters, prompt and base64 attachments. Unlike [`crate::request_log`],
+//! nothing is cut short. [`last`] gives it back, to be written as a shell
+//! script around `curl` ([`CapturedRequest::curl_script`]) or as the JSON
+//! body alone, so a runtime issue can be reported with the exact call, or the
+//! call scripted.
+
+use chrono::{DateTime, Local};
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+static LAST: Mutex<BTreeMap<String, CapturedRequest>> = Mutex::new(BTreeMap::new());
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CapturedRequest {
+    /// Name the request was recorded under
+    pub name: String,
+    pub method: String,
+    pub url: String,
+    pub body: Vec<u8>,
+    pub sent: DateTime<Local>,
+}
+
+/// Keep a request as the last one sent under `name`
+pub fn record(name: &str, method: &str, url: &str, body: Option<&[u8]>) {
+    let request = CapturedRequest {
+        name: name.to_string(),
+        method: method.to_string(),
+        url: url.to_string(),
+        body: body.map(<[u8]>::to_vec).unwrap_or_default(),
+        sent: Local::now(),
+    };
+    LAST.lock().unwrap().insert(name.to_string(), request);
+}
+
+/// The last request sent under `name` in this session
+pub fn last(name: &str) -> Option<CapturedRequest> {
+    LAST.lock().unwrap().get(name).cloned()
+}
+
+impl CapturedRequest {
+    /// The body, indented when it is JSON
+    pub fn pretty_body(&self) -> String {
+        serde_json::from_slice::<serde_json::Value>(&self.body)
+            .ok()
+            .and_then(|value| serde_json::to_string_pretty(&value).ok())
+            .unwrap_or_else(|| String::from_utf8_lossy(&self.body).into_owned())
+    }
+
+    /// A file name for the request, without an extension
+    pub fn file_stem(&self) -> String {
+        format!("{}-request-{}", self.name, self.sent.format("%Y%m%d-%H%M%S"))
+    }
+
+    /// A `sh` script sending the same request with `curl`. Streamed answers
+    /// are printed as they arrive and audio is saved next to the script;
+    /// files the runtime reads from disk are listed, since they only exist
+    /// on this computer.
+    pub fn curl_script(&self) -> String {
+        let body: Option<serde_json::Value> = serde_json::from_slice(&self.body).ok();
+        let mut script = format!(
+            "#!/bin/sh\n# {} {}\n# Sent by OminiX Studio on {}\n",
+            self.method,
+            self.url,
+            self.sent.format("%Y-%m-%d %H:%M:%S")
+        );
+        let files = body.as_ref()
+            .and_then(|b| b.as_object())
+            .into_iter()
+            .flatten()
+            .filter(|(key, _)| key.as_str() == "file" || key.ends_with("_path"))
+            .filter_map(|(_, value)| value.as_str());
+        for file in files {
+            script.push_str(&format!("# Reads {} on this computer\n", file));
+        }
+        script.push_str(&format!("curl -sS -X {} {}", self.method, quote(&self.url)));
+        if body.as_ref().is_some_and(|b| b["stream"] == serde_json::Value::Bool(true)) {
+            script.push_str(" \\\n  -N");
+        }
+        if self.url.ends_with("/audio/speech") {
+            script.push_str(" \\\n  -o speech.wav");
+        }
+        if self.body.is_empty() {
+            script.push('\n');
+            return script;
+        }
+        script.push_str(" \\\n  -H 'Content-Type: application/json' \\\n  --data-binary @- <<'JSON'\n");
+        script.push_str(&self.pretty_body());
+        script.push_str("\nJSON\n");
+        script
+    }
+}
+
+/// `text` in single quotes for `sh`
+fn quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', "'\\''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_curl_script() {
+        record(
+            "test-tts",
+            "POST",
+            "http://localhost:8080/v1/audio/speech",
+            Some(br#"{"model":"qwen3-tts","input":"It's ready.","voice":"vivian"}"#),
+        );
+        let request = last("test-tts").unwrap();
+        assert!(last("test-unknown").is_none());
+        let script = request.curl_script();
+        assert!(script.starts_with("#!/bin/sh\n# POST http://localhost:8080/v1/audio/speech\n"));
+        assert!(script.contains("curl -sS -X POST 'http://localhost:8080/v1/audio/speech' \\\n  -o speech.wav \\\n"));
+        assert!(script.contains("<<'JSON'\n{\n") && script.contains("\n  \"input\": \"It's ready.\","));
+        assert!(script.ends_with("}\nJSON\n"));
+
+        record("test-asr", "POST", "http://localhost:8080/v1/audio/transcriptions", Some(br#"{"file":"/tmp/a.wav","stream":true}"#));
+        let script = last("test-asr").unwrap().curl_script();
+        assert!(script.contains("# Reads /tmp/a.wav on this computer\n"));
+        assert!(script.contains(" \\\n  -N"));
+        assert_eq!(quote("it's"), "'it'\\''s'");
+    }
+}
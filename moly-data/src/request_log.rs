@@ -0,0 +1,500 @@
+//! Recent requests to the local runtime and cloud providers
+//!
+//! Every call made through [`crate::OminiXApiClient`], every model load and
+//! unload, and every chat request sent through [`LoggingClient`] is kept in
+//! memory (the last [`MAX_ENTRIES`]) with its endpoint, status, latency and
+//! the start of both bodies. API keys and tokens are redacted before a body
+//! is stored ([`redact`]). Settings shows the log, filtered by
+//! [`matches`], and exports it as HAR or JSON Lines.
+//!
+//! Provider clients do their HTTP inside moly-kit, so chat requests are
+//! [`RequestRecord::reconstructed`]: the request body is rebuilt from the
+//! conversation and the response body is the answer, not the raw traffic,
+//! and the HTTP status is not known. The log view and both exports say so.
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use futures::StreamExt;
+use moly_kit::aitk::protocol::*;
+use moly_kit::aitk::utils::asynchronous::{BoxPlatformSendFuture, BoxPlatformSendStream};
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Instant;
+
+/// Requests kept; older ones are dropped
+pub const MAX_ENTRIES: usize = 500;
+
+/// Bytes of each body kept
+const MAX_BODY_BYTES: usize = 4000;
+
+const REDACTED: &str = "[REDACTED]";
+
+/// Source of requests to the ominix-api runtime
+pub const LOCAL_RUNTIME: &str = "Local runtime";
+
+static LOG: Mutex<VecDeque<RequestRecord>> = Mutex::new(VecDeque::new());
+
+/// Bumped on every change, so views can tell when to refresh
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Clone, Debug, Serialize)]
+pub struct RequestRecord {
+    pub started: DateTime<Utc>,
+    /// [`LOCAL_RUNTIME`] or the provider's name
+    pub source: String,
+    pub method: String,
+    pub url: String,
+    /// `None` when no response arrived, or when it isn't known for a
+    /// reconstructed record
+    pub status: Option<u16>,
+    pub latency_ms: u64,
+    pub request_body: String,
+    pub response_body: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// The bodies were rebuilt from the chat rather than captured, and the
+    /// status wasn't seen
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub reconstructed: bool,
+}
+
+impl RequestRecord {
+    /// One line for the log view, e.g. `12:03:41 POST 200 1.2 s Local runtime /v1/chat/completions`
+    pub fn summary(&self) -> String {
+        let status = self.status_text();
+        format!(
+            "{} {} {} {:.1} s {} {}",
+            self.started.with_timezone(&chrono::Local).format("%H:%M:%S"),
+            self.method,
+            status,
+            self.latency_ms as f64 / 1000.0,
+            self.source,
+            self.url
+        )
+    }
+
+    /// The status code; `OK` for a reconstructed request that got an answer
+    fn status_text(&self) -> String {
+        match self.status {
+            Some(status) => status.to_string(),
+            None if self.reconstructed && self.error.is_none() => "OK".to_string(),
+            None => "ERR".to_string(),
+        }
+    }
+
+    fn is_success(&self) -> bool {
+        match self.status {
+            Some(status) => (200..300).contains(&status),
+            None => self.reconstructed && self.error.is_none(),
+        }
+    }
+}
+
+/// A request that was sent and is waiting for its response
+pub struct PendingRequest {
+    started: DateTime<Utc>,
+    clock: Instant,
+    source: String,
+    method: String,
+    url: String,
+    request_body: String,
+    reconstructed: bool,
+}
+
+/// Note that a request is being sent; finish it with [`PendingRequest::finish`]
+/// or [`PendingRequest::fail`]
+pub fn start(source: &str, method: &str, url: &str, body: Option<&[u8]>) -> PendingRequest {
+    PendingRequest {
+        started: Utc::now(),
+        clock: Instant::now(),
+        source: source.to_string(),
+        method: method.to_string(),
+        url: redact_url(url),
+        request_body: body.map(body_text).unwrap_or_default(),
+        reconstructed: false,
+    }
+}
+
+/// Like [`start`], for a request sent by a client whose traffic can't be
+/// seen. `body` is a rebuilt stand-in; finish it with
+/// [`PendingRequest::answered`] or [`PendingRequest::fail`].
+pub fn start_reconstructed(source: &str, method: &str, url: &str, body: &[u8]) -> PendingRequest {
+    PendingRequest { reconstructed: true, ..start(source, method, url, Some(body)) }
+}
+
+impl PendingRequest {
+    pub fn finish(self, status: u16, body: &[u8]) {
+        let response_body = body_text(body);
+        self.record(Some(status), response_body, None);
+    }
+
+    /// An answer arrived, with a status that isn't known
+    pub fn answered(self, body: &[u8]) {
+        let response_body = body_text(body);
+        self.record(None, response_body, None);
+    }
+
+    /// No response arrived, or the answer was an error
+    pub fn fail(self, status: Option<u16>, error: &str) {
+        self.record(status, String::new(), Some(redact(error)));
+    }
+
+    fn record(self, status: Option<u16>, response_body: String, error: Option<String>) {
+        let record = RequestRecord {
+            started: self.started,
+            latency_ms: self.clock.elapsed().as_millis() as u64,
+            source: self.source,
+            method: self.method,
+            url: self.url,
+            status,
+            request_body: self.request_body,
+            response_body,
+            error,
+            reconstructed: self.reconstructed,
+        };
+        let mut log = LOG.lock().unwrap();
+        if log.len() == MAX_ENTRIES {
+            log.pop_front();
+        }
+        log.push_back(record);
+        GENERATION.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// All kept requests, oldest first
+pub fn entries() -> Vec<RequestRecord> {
+    LOG.lock().unwrap().iter().cloned().collect()
+}
+
+pub fn generation() -> u64 {
+    GENERATION.load(Ordering::Relaxed)
+}
+
+pub fn clear() {
+    LOG.lock().unwrap().clear();
+    GENERATION.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Whether a request matches every word of `query`, looked up in its method,
+/// status, source, URL and error. `error` matches failed requests.
+pub fn matches(record: &RequestRecord, query: &str) -> bool {
+    let haystack = format!(
+        "{} {} {} {} {} {}",
+        record.method,
+        record.status_text(),
+        record.source,
+        record.url,
+        record.error.as_deref().unwrap_or_default(),
+        if record.is_success() { "" } else { "error" }
+    )
+    .to_lowercase();
+    query.to_lowercase().split_whitespace().all(|word| haystack.contains(word))
+}
+
+/// The requests as JSON Lines, one object per request
+pub fn to_jsonl(records: &[RequestRecord]) -> String {
+    records.iter().filter_map(|r| serde_json::to_string(r).ok()).map(|line| line + "\n").collect()
+}
+
+/// The requests as an HTTP Archive (HAR 1.2), which browsers' network tools
+/// can open. Headers aren't recorded, so they are left empty. Reconstructed
+/// requests have status 0, and their comment says the bodies were rebuilt.
+pub fn to_har(records: &[RequestRecord]) -> String {
+    let entries: Vec<serde_json::Value> = records
+        .iter()
+        .map(|r| {
+            let mut entry = serde_json::json!({
+                "startedDateTime": r.started.to_rfc3339_opts(SecondsFormat::Millis, true),
+                "time": r.latency_ms,
+                "request": {
+                    "method": r.method,
+                    "url": r.url,
+                    "httpVersion": "HTTP/1.1",
+                    "headers": [],
+                    "queryString": [],
+                    "cookies": [],
+                    "headersSize": -1,
+                    "bodySize": r.request_body.len(),
+                    "postData": { "mimeType": "application/json", "text": r.request_body },
+                },
+                "response": {
+                    "status": r.status.unwrap_or(0),
+                    "statusText": "",
+                    "httpVersion": "HTTP/1.1",
+                    "headers": [],
+                    "cookies": [],
+                    "content": { "size": r.response_body.len(), "mimeType": "application/json", "text": r.response_body },
+                    "redirectURL": "",
+                    "headersSize": -1,
+                    "bodySize": -1,
+                },
+                "cache": {},
+                "timings": { "send": 0, "wait": r.latency_ms, "receive": 0 },
+                "comment": if r.reconstructed {
+                    format!("{}: bodies rebuilt from the chat, not captured; status unknown", r.source)
+                } else {
+                    r.source.clone()
+                },
+            });
+            if r.reconstructed {
+                entry["_reconstructed"] = true.into();
+            }
+            if let Some(error) = &r.error {
+                entry["response"]["_error"] = error.clone().into();
+            }
+            entry
+        })
+        .collect();
+    let har = serde_json::json!({
+        "log": {
+            "version": "1.2",
+            "creator": { "name": "OminiX Studio", "version": env!("CARGO_PKG_VERSION") },
+            "entries": entries,
+        }
+    });
+    serde_json::to_string_pretty(&har).unwrap_or_default()
+}
+
+/// A body for the log: the start of it, redacted, or its size when it isn't text
+fn body_text(body: &[u8]) -> String {
+    let kept = &body[..body.len().min(MAX_BODY_BYTES)];
+    let text = match std::str::from_utf8(kept) {
+        Ok(text) => text,
+        // Cut in the middle of a character
+        Err(e) if e.error_len().is_none() => std::str::from_utf8(&kept[..e.valid_up_to()]).unwrap_or_default(),
+        Err(_) => return format!("<{} bytes of binary data>", body.len()),
+    };
+    if text.contains('\0') {
+        return format!("<{} bytes of binary data>", body.len());
+    }
+    let mut text = redact(text);
+    if body.len() > kept.len() {
+        text.push_str(&format!("… ({} more bytes)", body.len() - kept.len()));
+    }
+    text
+}
+
+fn is_secret_key(key: &str) -> bool {
+    let key = key.to_ascii_lowercase().replace('-', "_");
+    matches!(key.as_str(), "key" | "apikey" | "token" | "password" | "authorization")
+        || key.ends_with("api_key")
+        || key.ends_with("_token")
+        || key.ends_with("secret")
+}
+
+/// Hide API keys and tokens: string values of keys like `api_key`, bearer
+/// tokens and `sk-…` keys
+pub fn redact(text: &str) -> String {
+    let text = redact_json_values(text);
+    let text = redact_after(&text, "Bearer ", 1);
+    redact_after(&text, "sk-", 8)
+}
+
+/// Index of the closing quote of a JSON string whose opening quote was just before `s`
+fn string_end(s: &str) -> Option<usize> {
+    let mut escaped = false;
+    for (i, c) in s.char_indices() {
+        match c {
+            '\\' if !escaped => escaped = true,
+            '"' if !escaped => return Some(i),
+            _ => escaped = false,
+        }
+    }
+    None
+}
+
+/// `"api_key": "value"` → `"api_key": "[REDACTED]"`
+fn redact_json_values(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(open) = rest.find('"') {
+        out.push_str(&rest[..=open]);
+        rest = &rest[open + 1..];
+        let Some(close) = string_end(rest) else { break };
+        let key = &rest[..close];
+        out.push_str(&rest[..=close]);
+        rest = &rest[close + 1..];
+
+        let Some(value) = rest.trim_start().strip_prefix(':').map(str::trim_start) else { continue };
+        if !value.starts_with('"') || !is_secret_key(key) {
+            continue;
+        }
+        let value_open = rest.len() - value.len();
+        out.push_str(&rest[..=value_open]);
+        rest = &rest[value_open + 1..];
+        out.push_str(REDACTED);
+        let Some(value_close) = string_end(rest) else {
+            rest = "";
+            break;
+        };
+        out.push('"');
+        rest = &rest[value_close + 1..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Replace the token after each `marker` that starts a word, when it is at
+/// least `min_len` characters long
+fn redact_after(text: &str, marker: &str, min_len: usize) -> String {
+    let is_token_char = |c: char| c.is_ascii_alphanumeric() || "-_.~+/=".contains(c);
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(at) = rest.find(marker) {
+        let starts_word = rest[..at].chars().last().map_or(true, |c| !c.is_ascii_alphanumeric());
+        let after = &rest[at + marker.len()..];
+        let len = after.find(|c: char| !is_token_char(c)).unwrap_or(after.len());
+        out.push_str(&rest[..at + marker.len()]);
+        if starts_word && len >= min_len {
+            out.push_str(REDACTED);
+            rest = &after[len..];
+        } else {
+            rest = after;
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Hide secret query parameters, e.g. `?key=…`
+fn redact_url(url: &str) -> String {
+    let Some((base, query)) = url.split_once('?') else { return url.to_string() };
+    let params: Vec<String> = query
+        .split('&')
+        .map(|param| match param.split_once('=') {
+            Some((key, _)) if is_secret_key(key) => format!("{}={}", key, REDACTED),
+            _ => param.to_string(),
+        })
+        .collect();
+    format!("{}?{}", base, params.join("&"))
+}
+
+/// Records the chat requests of a provider client
+pub struct LoggingClient {
+    inner: Box<dyn BotClient>,
+    source: String,
+    url: String,
+}
+
+impl LoggingClient {
+    /// `url` is the endpoint the client posts chat requests to
+    pub fn new(inner: Box<dyn BotClient>, source: &str, url: &str) -> Self {
+        Self { inner, source: source.to_string(), url: url.to_string() }
+    }
+}
+
+impl Clone for LoggingClient {
+    fn clone(&self) -> Self {
+        Self { inner: self.inner.clone_box(), source: self.source.clone(), url: self.url.clone() }
+    }
+}
+
+impl BotClient for LoggingClient {
+    fn bots(&mut self) -> BoxPlatformSendFuture<'static, ClientResult<Vec<Bot>>> {
+        self.inner.bots()
+    }
+
+    fn send(
+        &mut self,
+        bot_id: &BotId,
+        messages: &[Message],
+        tools: &[Tool],
+    ) -> BoxPlatformSendStream<'static, ClientResult<MessageContent>> {
+        let conversation: Vec<serde_json::Value> = messages
+            .iter()
+            .map(|m| {
+                let role = match &m.from {
+                    EntityId::User => "user",
+                    EntityId::System => "system",
+                    EntityId::Bot(_) => "assistant",
+                    _ => "tool",
+                };
+                serde_json::json!({ "role": role, "content": m.content.text })
+            })
+            .collect();
+        let tool_names: Vec<String> = tools.iter().map(|t| t.name.to_string()).collect();
+        let body = serde_json::json!({ "model": bot_id.id(), "messages": conversation, "tools": tool_names });
+        let pending = start_reconstructed(&self.source, "POST", &self.url, body.to_string().as_bytes());
+
+        let mut inner = self.inner.send(bot_id, messages, tools);
+        Box::pin(async_stream::stream! {
+            let mut answer = String::new();
+            let mut errors: Vec<String> = Vec::new();
+            while let Some(result) = inner.next().await {
+                if let Some(content) = result.value() {
+                    answer = content.text.clone();
+                }
+                errors.extend(result.errors().iter().map(|e| e.to_string()));
+                yield result;
+            }
+            if errors.is_empty() {
+                pending.answered(answer.as_bytes());
+            } else {
+                pending.fail(None, &errors.join("; "));
+            }
+        })
+    }
+
+    fn clone_box(&self) -> Box<dyn BotClient> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact_secrets() {
+        assert_eq!(
+            redact(r#"{"api_key": "abc\"def", "max_tokens": 5, "model": "sk-not-a-key"}"#),
+            r#"{"api_key": "[REDACTED]", "max_tokens": 5, "model": "sk-[REDACTED]"}"#
+        );
+        assert_eq!(redact("Authorization: Bearer eyJhbGciOi"), "Authorization: Bearer [REDACTED]");
+        assert_eq!(redact("task-12345678 sk-12"), "task-12345678 sk-12");
+        assert_eq!(redact_url("https://x.ai/v1/models?key=secret&alt=sse"), "https://x.ai/v1/models?key=[REDACTED]&alt=sse");
+        assert_eq!(body_text(&[0, 159, 146, 150]), "<4 bytes of binary data>");
+    }
+
+    #[test]
+    fn test_filter_and_export() {
+        let pending = start(LOCAL_RUNTIME, "POST", "http://localhost:8080/v1/completions", Some(b"{}"));
+        let record = RequestRecord {
+            started: pending.started,
+            source: pending.source,
+            method: pending.method,
+            url: pending.url,
+            status: Some(500),
+            latency_ms: 1200,
+            request_body: pending.request_body,
+            response_body: "oops".to_string(),
+            error: None,
+            reconstructed: false,
+        };
+        assert!(matches(&record, "post 500"));
+        assert!(matches(&record, "error completions"));
+        assert!(!matches(&record, "openai"));
+
+        let har: serde_json::Value = serde_json::from_str(&to_har(&[record.clone()])).unwrap();
+        assert_eq!(har["log"]["entries"][0]["response"]["status"], 500);
+        assert_eq!(to_jsonl(&[record.clone(), record]).lines().count(), 2);
+    }
+
+    #[test]
+    fn test_reconstructed_records() {
+        start_reconstructed("OpenAI", "POST", "https://api.openai.com/v1/chat/completions", b"{}").answered(b"Hello");
+        start_reconstructed("OpenAI", "POST", "https://api.openai.com/v1/chat/completions", b"{}").fail(None, "rate limited");
+        let records: Vec<RequestRecord> = entries().into_iter()
+            .filter(|r| r.source == "OpenAI")
+            .collect();
+        assert_eq!(records.len(), 2);
+        assert!(records[0].summary().contains(" OK ") && records[0].status.is_none());
+        assert!(matches(&records[1], "error") && !matches(&records[0], "error"));
+
+        let har: serde_json::Value = serde_json::from_str(&to_har(&records)).unwrap();
+        assert_eq!(har["log"]["entries"][0]["response"]["status"], 0);
+        assert_eq!(har["log"]["entries"][0]["_reconstructed"], true);
+        assert!(to_jsonl(&records).contains("\"reconstructed\":true"));
+    }
+}
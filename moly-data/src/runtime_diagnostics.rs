@@ -0,0 +1,376 @@
+//! What is listening on the runtime's port
+//!
+//! Every local request goes to [`runtime_url`]. When another program holds
+//! that port, loads and chats fail with errors that don't say why, and
+//! [`crate::ensure_server_running`] can mistake the other server for the
+//! runtime. [`diagnose`] connects to the port, checks that `/v1/models`
+//! answers the way ominix-api does (entries with a load `status`) or that
+//! `/v1/version` reports a runtime version, looks up the process holding the
+//! port and suggests fixes, which the Hub's Diagnostics panel offers.
+
+use crate::model_registry::RuntimeVersion;
+use crate::model_runtime_client::runtime_url;
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::process::Command;
+use std::time::Duration;
+
+/// Seconds to wait for the listener to connect or answer
+const TIMEOUT_SECS: u64 = 2;
+
+/// Ports after the runtime's tried when looking for a free one
+const PORT_SEARCH: u16 = 20;
+
+/// Load states ominix-api reports for each model at `/v1/models`
+const MODEL_STATUSES: [&str; 4] = ["loaded", "loading", "unloaded", "error"];
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PortState {
+    /// Nothing accepts connections
+    Free,
+    /// ominix-api answered
+    Runtime { version: Option<String>, models: usize },
+    /// Another HTTP server answered
+    OtherServer { detail: String },
+    /// Connections are accepted, but not answered over HTTP
+    NotHttp { detail: String },
+}
+
+/// A process listening on a port
+#[derive(Clone, Debug, PartialEq)]
+pub struct Listener {
+    pub pid: u32,
+    pub name: String,
+}
+
+/// A way out of a port conflict
+#[derive(Clone, Debug, PartialEq)]
+pub enum Fix {
+    /// Nothing is listening; launch ominix-api
+    StartRuntime,
+    /// Stop the program holding the port
+    StopProcess(Listener),
+    /// Run ominix-api on another port and point the app at it
+    ChangePort(u16),
+}
+
+impl Fix {
+    /// Text of the fix's button
+    pub fn label(&self) -> String {
+        match self {
+            Fix::StartRuntime => "Start runtime".to_string(),
+            Fix::StopProcess(listener) => format!("Stop {}", listener.name),
+            Fix::ChangePort(_) => "Copy setting".to_string(),
+        }
+    }
+
+    pub fn description(&self) -> String {
+        match self {
+            Fix::StartRuntime => "Launch ominix-api on this port.".to_string(),
+            Fix::StopProcess(listener) => {
+                format!("Stop {} (pid {}) to free the port, then start the runtime. Unsaved work in it is lost.", listener.name, listener.pid)
+            }
+            Fix::ChangePort(port) => format!(
+                "Keep the other program and run ominix-api on port {} instead, starting the app with {}.",
+                port,
+                port_setting(*port)
+            ),
+        }
+    }
+}
+
+/// Environment setting that points the app at a runtime on `port`
+pub fn port_setting(port: u16) -> String {
+    format!("OMINIX_API_URL=http://localhost:{}", port)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Diagnosis {
+    pub url: String,
+    pub port: u16,
+    pub state: PortState,
+    /// The process holding the port, when it is on this machine and could
+    /// be looked up
+    pub listener: Option<Listener>,
+    /// A free port near [`Self::port`], for [`Fix::ChangePort`]
+    pub free_port: Option<u16>,
+}
+
+impl Diagnosis {
+    /// One line on what was found
+    pub fn summary(&self) -> String {
+        let holder = match &self.listener {
+            Some(listener) => format!("{} (pid {})", listener.name, listener.pid),
+            None => "Another program".to_string(),
+        };
+        match &self.state {
+            PortState::Free => format!("Nothing is listening on port {}, so the runtime isn't running.", self.port),
+            PortState::Runtime { version, models } => format!(
+                "ominix-api {}is running on port {} with {} model{} listed.",
+                version.as_deref().map(|v| format!("{} ", v)).unwrap_or_default(),
+                self.port,
+                models,
+                if *models == 1 { "" } else { "s" }
+            ),
+            PortState::OtherServer { detail } => {
+                format!("{} is using port {}, and it isn't ominix-api: {}", holder, self.port, detail)
+            }
+            PortState::NotHttp { detail } => {
+                format!("{} is using port {} and doesn't answer HTTP: {}", holder, self.port, detail)
+            }
+        }
+    }
+
+    /// Suggested fixes, most direct first; none when the runtime is fine
+    pub fn fixes(&self) -> Vec<Fix> {
+        let mut fixes = Vec::new();
+        match &self.state {
+            PortState::Runtime { .. } => {}
+            PortState::Free => fixes.push(Fix::StartRuntime),
+            PortState::OtherServer { .. } | PortState::NotHttp { .. } => {
+                if let Some(listener) = &self.listener {
+                    fixes.push(Fix::StopProcess(listener.clone()));
+                }
+                if let Some(port) = self.free_port {
+                    fixes.push(Fix::ChangePort(port));
+                }
+            }
+        }
+        fixes
+    }
+}
+
+/// Port of an http(s) URL, explicit or by scheme
+pub fn port_of(url: &str) -> Option<u16> {
+    let (scheme, rest) = url.split_once("://")?;
+    let authority = rest.split('/').next().unwrap_or(rest);
+    let host_port = authority.rsplit('@').next().unwrap_or(authority);
+    // Skip an IPv6 host's colons
+    let after_host = host_port.rsplit_once(']').map_or(host_port, |(_, after)| after);
+    match after_host.rsplit_once(':') {
+        Some((_, port)) => port.parse().ok(),
+        None if scheme == "https" => Some(443),
+        None => Some(80),
+    }
+}
+
+fn host_of(url: &str) -> Option<&str> {
+    let rest = url.split_once("://")?.1;
+    let authority = rest.split('/').next().unwrap_or(rest);
+    let host_port = authority.rsplit('@').next().unwrap_or(authority);
+    match host_port.strip_prefix('[') {
+        Some(v6) => v6.split(']').next(),
+        None => host_port.split(':').next(),
+    }
+}
+
+/// Number of models when `body` is a `/v1/models` answer shaped like
+/// ominix-api's, or why it isn't. An empty list fits any OpenAI-compatible
+/// server, so it needs [`version_of`] to confirm.
+pub fn models_signature(body: &str) -> Result<usize, String> {
+    let value: serde_json::Value =
+        serde_json::from_str(body).map_err(|_| "/v1/models didn't answer with JSON".to_string())?;
+    let Some(data) = value.get("data").and_then(|d| d.as_array()) else {
+        return Err("/v1/models has no model list".to_string());
+    };
+    for entry in data {
+        if entry.get("id").and_then(|i| i.as_str()).is_none() {
+            return Err("/v1/models lists entries without ids".to_string());
+        }
+        let status = entry.get("status").and_then(|s| s.as_str());
+        if !status.is_some_and(|s| MODEL_STATUSES.contains(&s)) {
+            return Err("/v1/models lists models without load states, like an OpenAI-compatible server".to_string());
+        }
+    }
+    Ok(data.len())
+}
+
+/// Version in a `/v1/version` answer
+pub fn version_of(body: &str) -> Option<String> {
+    let value: serde_json::Value = serde_json::from_str(body).ok()?;
+    let version = RuntimeVersion::parse(value.get("version")?.as_str()?)?;
+    Some(version.to_string())
+}
+
+/// Check the runtime's port, see the module docs. Blocks for a few seconds
+/// at most; call it off the UI thread.
+pub fn diagnose() -> Diagnosis {
+    diagnose_url(&runtime_url())
+}
+
+pub fn diagnose_url(url: &str) -> Diagnosis {
+    let url = url.trim_end_matches('/').to_string();
+    let port = port_of(&url).unwrap_or(80);
+    let host = host_of(&url).unwrap_or("localhost");
+    let local = matches!(host, "localhost" | "127.0.0.1" | "::1");
+
+    let state = probe(&url, host, port);
+    let taken = matches!(state, PortState::OtherServer { .. } | PortState::NotHttp { .. });
+    Diagnosis {
+        listener: if taken && local { find_listener(port) } else { None },
+        free_port: if taken && local { free_port_after(port) } else { None },
+        url,
+        port,
+        state,
+    }
+}
+
+fn probe(url: &str, host: &str, port: u16) -> PortState {
+    let timeout = Duration::from_secs(TIMEOUT_SECS);
+    let addrs: Vec<SocketAddr> = (host, port).to_socket_addrs().map(|a| a.collect()).unwrap_or_default();
+    if !addrs.iter().any(|addr| TcpStream::connect_timeout(addr, timeout).is_ok()) {
+        return PortState::Free;
+    }
+
+    let client = match crate::network::blocking_client().timeout(timeout).build() {
+        Ok(client) => client,
+        Err(e) => return PortState::NotHttp { detail: e.to_string() },
+    };
+    let get = |path: &str| -> Result<(u16, String), String> {
+        let response = client.get(format!("{}{}", url, path)).send().map_err(|e| e.to_string())?;
+        let status = response.status().as_u16();
+        Ok((status, response.text().unwrap_or_default()))
+    };
+    let models = match get("/v1/models") {
+        Ok(answer) => answer,
+        Err(e) => return PortState::NotHttp { detail: e },
+    };
+    let version = match get("/v1/version") {
+        Ok((200, body)) => version_of(&body),
+        _ => None,
+    };
+    match models {
+        (200, body) => match models_signature(&body) {
+            Ok(count) if count > 0 || version.is_some() => PortState::Runtime { version, models: count },
+            Ok(_) => PortState::OtherServer { detail: "/v1/models lists no models and /v1/version reports no runtime version".to_string() },
+            Err(detail) => PortState::OtherServer { detail },
+        },
+        (status, _) => PortState::OtherServer { detail: format!("/v1/models answered HTTP {}", status) },
+    }
+}
+
+/// The first port after `port` that nothing listens on
+pub fn free_port_after(port: u16) -> Option<u16> {
+    (1..=PORT_SEARCH)
+        .filter_map(|offset| port.checked_add(offset))
+        .find(|&candidate| TcpListener::bind(("127.0.0.1", candidate)).is_ok())
+}
+
+// ─── Listening process ────────────────────────────────────────────────────────
+
+/// The process listening on a local TCP port, with the tools each OS has
+pub fn find_listener(port: u16) -> Option<Listener> {
+    let output = |program: &str, args: &[&str]| -> Option<String> {
+        let output = Command::new(program).args(args).output().ok()?;
+        Some(String::from_utf8_lossy(&output.stdout).into_owned())
+    };
+    if cfg!(windows) {
+        let pid = parse_netstat(&output("netstat", &["-ano", "-p", "TCP"])?, port)?;
+        let filter = format!("PID eq {}", pid);
+        let tasks = output("tasklist", &["/FI", &filter, "/FO", "CSV", "/NH"]).unwrap_or_default();
+        let name = tasks.split('"').nth(1).filter(|n| !n.is_empty()).unwrap_or("Unknown program").to_string();
+        return Some(Listener { pid, name });
+    }
+    let tcp = format!("-iTCP:{}", port);
+    if let Some(listener) = output("lsof", &["-nP", &tcp, "-sTCP:LISTEN", "-Fpc"]).as_deref().and_then(parse_lsof) {
+        return Some(listener);
+    }
+    let filter = format!("sport = :{}", port);
+    output("ss", &["-ltnpH", &filter]).as_deref().and_then(parse_ss)
+}
+
+/// First process in `lsof -F pc` output (`p1234` and `cnode` lines)
+pub fn parse_lsof(output: &str) -> Option<Listener> {
+    let pid = output.lines().find_map(|l| l.strip_prefix('p')?.trim().parse().ok())?;
+    let name = output.lines().find_map(|l| l.strip_prefix('c')).unwrap_or("Unknown program");
+    Some(Listener { pid, name: name.trim().to_string() })
+}
+
+/// First process in `ss -p` output: `users:(("node",pid=1234,fd=20))`
+pub fn parse_ss(output: &str) -> Option<Listener> {
+    let users = output.split("users:((").nth(1)?;
+    let name = users.split('"').nth(1)?.to_string();
+    let pid = users.split("pid=").nth(1)?.split(|c: char| !c.is_ascii_digit()).next()?.parse().ok()?;
+    Some(Listener { pid, name })
+}
+
+/// Pid listening on `port` in `netstat -ano` output
+pub fn parse_netstat(output: &str, port: u16) -> Option<u32> {
+    let suffix = format!(":{}", port);
+    output.lines().find_map(|line| {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        match fields.as_slice() {
+            ["TCP", local, _, "LISTENING", pid] if local.ends_with(&suffix) => pid.parse().ok(),
+            _ => None,
+        }
+    })
+}
+
+/// Ask a process to exit, then force it if it hasn't after a moment
+pub fn stop_process(pid: u32) -> Result<(), String> {
+    let status = if cfg!(windows) {
+        Command::new("taskkill").args(["/PID", &pid.to_string(), "/F"]).status()
+    } else {
+        Command::new("kill").arg(pid.to_string()).status()
+    };
+    match status {
+        Ok(status) if status.success() => {}
+        Ok(_) => return Err(format!("Could not stop process {}; it may belong to another user", pid)),
+        Err(e) => return Err(format!("Could not stop process {}: {}", pid, e)),
+    }
+    #[cfg(unix)]
+    {
+        std::thread::sleep(Duration::from_secs(1));
+        // Still there after SIGTERM
+        if unsafe { libc::kill(pid as i32, 0) } == 0 {
+            unsafe { libc::kill(pid as i32, libc::SIGKILL) };
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock_runtime::MockRuntime;
+
+    #[test]
+    fn test_signatures_and_listeners() {
+        assert_eq!(port_of("http://localhost:8080"), Some(8080));
+        assert_eq!(port_of("http://[::1]:9000/v1"), Some(9000));
+        assert_eq!(port_of("https://runtime.example"), Some(443));
+
+        assert_eq!(models_signature(r#"{"data":[{"id":"qwen3-8b","status":"loaded","memory_gb":4.6}]}"#), Ok(1));
+        assert_eq!(models_signature(r#"{"object":"list","data":[]}"#), Ok(0));
+        assert!(models_signature(r#"{"object":"list","data":[{"id":"llama3","object":"model"}]}"#).is_err());
+        assert!(models_signature("<html>Jenkins</html>").is_err());
+        assert_eq!(version_of(r#"{"version":"v0.9.1"}"#).as_deref(), Some("0.9.1"));
+
+        let listener = Listener { pid: 4211, name: "node".to_string() };
+        assert_eq!(parse_lsof("p4211\ncnode\nf23\n"), Some(listener.clone()));
+        let ss = "LISTEN 0 511 0.0.0.0:8080 0.0.0.0:* users:((\"node\",pid=4211,fd=23))\n";
+        assert_eq!(parse_ss(ss), Some(listener));
+        let netstat = "  TCP    0.0.0.0:80     0.0.0.0:0    LISTENING    4\n  TCP    0.0.0.0:8080   0.0.0.0:0    LISTENING    5120\n";
+        assert_eq!(parse_netstat(netstat, 8080), Some(5120));
+        assert_eq!(parse_netstat(netstat, 8081), None);
+    }
+
+    #[test]
+    fn test_diagnose() {
+        let mock = MockRuntime::start();
+        let diagnosis = diagnose_url(mock.base_url());
+        assert_eq!(diagnosis.state, PortState::Runtime { version: Some("0.9.0".to_string()), models: 0 });
+        assert!(diagnosis.fixes().is_empty());
+
+        // Accepts connections and closes them without answering
+        let silent = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = silent.local_addr().unwrap().port();
+        std::thread::spawn(move || {
+            for stream in silent.incoming() {
+                drop(stream);
+            }
+        });
+        let diagnosis = diagnose_url(&format!("http://127.0.0.1:{}", port));
+        assert!(matches!(diagnosis.state, PortState::NotHttp { .. }));
+        assert!(diagnosis.fixes().iter().any(|fix| matches!(fix, Fix::ChangePort(p) if *p > port)));
+    }
+}
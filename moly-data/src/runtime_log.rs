@@ -0,0 +1,244 @@
+//! The ominix-api server's log
+//!
+//! When the app starts the runtime itself ([`crate::ensure_server_running`]),
+//! the server's output is appended to a log file instead of being dropped:
+//! `~/.OminiX/logs/ominix-api.log`, or the file set in Settings when the
+//! runtime is started some other way and logs elsewhere. The Model Hub tails
+//! it with a [`LogTail`] and shows the lines [`filter`] keeps, so load errors
+//! like missing weights or unsupported ops can be read without a terminal.
+
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::PathBuf;
+use std::sync::RwLock;
+
+pub const DEFAULT_LOG_PATH: &str = "~/.OminiX/logs/ominix-api.log";
+
+/// Lines kept by a [`LogTail`]; older ones are dropped
+pub const MAX_LINES: usize = 2000;
+
+/// Bytes read from the end of an existing log when a tail is opened
+const INITIAL_BYTES: u64 = 256 * 1024;
+
+/// Characters kept of a single line
+const MAX_LINE_CHARS: usize = 2000;
+
+static LOG_PATH: RwLock<String> = RwLock::new(String::new());
+
+/// Mirror [`crate::Preferences::runtime_log_path`]
+pub fn set_path(path: &str) {
+    if let Ok(mut current) = LOG_PATH.write() {
+        *current = path.trim().to_string();
+    }
+}
+
+/// The log file the runtime writes to and the Hub reads
+pub fn path() -> PathBuf {
+    let configured = LOG_PATH.read().map(|p| p.clone()).unwrap_or_default();
+    let path = if configured.is_empty() { DEFAULT_LOG_PATH } else { configured.as_str() };
+    PathBuf::from(crate::local_models::expand_tilde(path))
+}
+
+/// Open the log for appending the runtime's output, creating its folder
+pub fn open_for_append() -> Result<File, String> {
+    let path = path();
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+    }
+    std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+/// Severity of a log line, least severe first
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl LogLevel {
+    /// The level a line is tagged with, looking at its first few words the
+    /// way `env_logger` and `tracing` write them (`[... ERROR ...]`,
+    /// `2025-01-01T00:00:00Z  WARN ...`). Panics count as errors.
+    pub fn parse(line: &str) -> Option<LogLevel> {
+        if line.contains("panicked at") {
+            return Some(LogLevel::Error);
+        }
+        line.split_whitespace().take(4).find_map(|word| {
+            let word = word.trim_matches(|c: char| !c.is_ascii_alphabetic());
+            match word.to_ascii_uppercase().as_str() {
+                "TRACE" => Some(LogLevel::Trace),
+                "DEBUG" => Some(LogLevel::Debug),
+                "INFO" => Some(LogLevel::Info),
+                "WARN" | "WARNING" => Some(LogLevel::Warn),
+                "ERROR" | "FATAL" => Some(LogLevel::Error),
+                _ => None,
+            }
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LogLine {
+    /// The line's own level, or that of the line it continues (backtraces,
+    /// wrapped messages); `None` before any tagged line
+    pub level: Option<LogLevel>,
+    pub text: String,
+}
+
+/// The end of a log file, read a little more on every [`LogTail::poll`]
+pub struct LogTail {
+    path: PathBuf,
+    offset: u64,
+    opened: bool,
+    /// Bytes after the last newline, kept until the line is finished
+    partial: Vec<u8>,
+    lines: VecDeque<LogLine>,
+}
+
+impl LogTail {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path, offset: 0, opened: false, partial: Vec::new(), lines: VecDeque::new() }
+    }
+
+    pub fn path(&self) -> &PathBuf {
+        &self.path
+    }
+
+    pub fn lines(&self) -> &VecDeque<LogLine> {
+        &self.lines
+    }
+
+    /// Forget the lines read so far; new output still shows
+    pub fn clear(&mut self) {
+        self.lines.clear();
+    }
+
+    /// Read what was appended since the last call. A file that got shorter
+    /// was truncated or rotated and is read again from the start. Returns
+    /// whether lines were added.
+    pub fn poll(&mut self) -> Result<bool, String> {
+        let mut file = File::open(&self.path).map_err(|e| format!("{}: {}", self.path.display(), e))?;
+        let len = file.metadata().map_err(|e| e.to_string())?.len();
+        let mut skip_first = false;
+        if !self.opened {
+            self.opened = true;
+            self.offset = len.saturating_sub(INITIAL_BYTES);
+            skip_first = self.offset > 0;
+        } else if len < self.offset {
+            self.offset = 0;
+            self.partial.clear();
+            self.push(LogLine { level: None, text: "--- log restarted ---".to_string() });
+        }
+        if len == self.offset {
+            return Ok(false);
+        }
+        file.seek(SeekFrom::Start(self.offset)).map_err(|e| e.to_string())?;
+        let mut bytes = Vec::new();
+        file.take(len - self.offset).read_to_end(&mut bytes).map_err(|e| e.to_string())?;
+        self.offset += bytes.len() as u64;
+
+        self.partial.extend_from_slice(&bytes);
+        let Some(end) = self.partial.iter().rposition(|b| *b == b'\n') else {
+            return Ok(false);
+        };
+        let complete: Vec<u8> = self.partial.drain(..=end).collect();
+        let text = String::from_utf8_lossy(&complete);
+        let mut added = false;
+        for line in text.lines().skip(skip_first as usize) {
+            let line = strip_ansi(line);
+            if line.trim().is_empty() {
+                continue;
+            }
+            let level = LogLevel::parse(&line).or_else(|| self.lines.back().and_then(|l| l.level));
+            self.push(LogLine { level, text: line.chars().take(MAX_LINE_CHARS).collect() });
+            added = true;
+        }
+        Ok(added)
+    }
+
+    fn push(&mut self, line: LogLine) {
+        if self.lines.len() == MAX_LINES {
+            self.lines.pop_front();
+        }
+        self.lines.push_back(line);
+    }
+}
+
+/// Lines at `min_level` or above (untagged lines always pass) that contain
+/// every word of `query`, ignoring case
+pub fn filter<'a>(lines: impl IntoIterator<Item = &'a LogLine>, min_level: LogLevel, query: &str) -> Vec<&'a LogLine> {
+    let words: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+    lines.into_iter()
+        .filter(|line| line.level.map_or(true, |level| level >= min_level))
+        .filter(|line| {
+            let text = line.text.to_lowercase();
+            words.iter().all(|w| text.contains(w.as_str()))
+        })
+        .collect()
+}
+
+/// Drop terminal color codes, which `tracing` writes even to files
+fn strip_ansi(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        if c == '\u{1b}' {
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn test_tail_and_filter() {
+        let path = std::env::temp_dir().join(format!("ominix-runtime-log-{}.log", std::process::id()));
+        std::fs::write(&path, "").unwrap();
+        let mut tail = LogTail::new(path.clone());
+        assert_eq!(tail.poll(), Ok(false));
+
+        let mut file = std::fs::OpenOptions::new().append(true).open(&path).unwrap();
+        write!(file, "2025-01-01T00:00:00Z  INFO ominix_api: listening on 8080\n\
+            \u{1b}[31mERROR\u{1b}[0m ominix_api::llm: missing weights model.safetensors\n  \
+            at loader.rs:42\n[2025 WARN ominix_api] unsupported op").unwrap();
+        assert_eq!(tail.poll(), Ok(true));
+        assert_eq!(tail.lines().len(), 3);
+        assert_eq!(tail.lines()[1].text, "ERROR ominix_api::llm: missing weights model.safetensors");
+        assert_eq!(tail.lines()[2].level, Some(LogLevel::Error));
+
+        // The unfinished line shows once it ends
+        writeln!(file, " rms_norm").unwrap();
+        assert_eq!(tail.poll(), Ok(true));
+        assert_eq!(tail.lines()[3].level, Some(LogLevel::Warn));
+
+        let errors = filter(tail.lines(), LogLevel::Warn, "");
+        assert_eq!(errors.len(), 3);
+        let found = filter(tail.lines(), LogLevel::Trace, "Unsupported RMS");
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].text, "[2025 WARN ominix_api] unsupported op rms_norm");
+
+        // Truncated: read again from the start
+        std::fs::write(&path, "INFO restarted\n").unwrap();
+        assert_eq!(tail.poll(), Ok(true));
+        assert_eq!(tail.lines().back().unwrap().text, "INFO restarted");
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,123 @@
+//! Per-model options for loading a model
+//!
+//! Context length, GPU layers and thread count go with the load request
+//! ([`crate::ModelRuntimeClient::load_model_with`]). They are set in the
+//! advanced section of the Hub's panel header and kept per registry id
+//! ([`ModelRuntimeOptions`]), so a model loads the same way every time.
+//! Options that aren't set leave the runtime's defaults.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::ops::RangeInclusive;
+use std::path::PathBuf;
+
+const OPTIONS_FILENAME: &str = "runtime_options.json";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuntimeOptions {
+    /// Maximum context in tokens, which also sizes the KV cache
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context_length: Option<u32>,
+    /// Layers offloaded to the GPU; 0 runs on the CPU only
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gpu_layers: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub threads: Option<u32>,
+}
+
+impl RuntimeOptions {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Options from the Hub's fields; empty fields are left unset
+    pub fn parse(context_length: &str, gpu_layers: &str, threads: &str) -> Result<Self, String> {
+        Ok(Self {
+            context_length: parse_field(context_length, "Context length", 256..=1_048_576)?,
+            gpu_layers: parse_field(gpu_layers, "GPU layers", 0..=999)?,
+            threads: parse_field(threads, "Threads", 1..=256)?,
+        })
+    }
+
+    /// The options as fields of the load request body
+    pub fn to_json(&self) -> serde_json::Map<String, serde_json::Value> {
+        match serde_json::to_value(self) {
+            Ok(serde_json::Value::Object(map)) => map,
+            _ => serde_json::Map::new(),
+        }
+    }
+}
+
+fn parse_field(text: &str, name: &str, range: RangeInclusive<u32>) -> Result<Option<u32>, String> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Ok(None);
+    }
+    match text.parse::<u32>() {
+        Ok(value) if range.contains(&value) => Ok(Some(value)),
+        _ => Err(format!("{} must be a whole number from {} to {}", name, range.start(), range.end())),
+    }
+}
+
+/// Runtime options set per model in the Hub, by registry id
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ModelRuntimeOptions {
+    #[serde(default)]
+    pub models: BTreeMap<String, RuntimeOptions>,
+}
+
+impl ModelRuntimeOptions {
+    pub fn path() -> PathBuf {
+        crate::profiles::data_dir().join(OPTIONS_FILENAME)
+    }
+
+    pub fn load() -> Self {
+        std::fs::read_to_string(Self::path())
+            .ok()
+            .and_then(|contents| match serde_json::from_str(&contents) {
+                Ok(options) => Some(options),
+                Err(e) => {
+                    log::error!("Failed to parse runtime options: {:?}", e);
+                    None
+                }
+            })
+            .unwrap_or_default()
+    }
+
+    /// The options to load a model with; all unset when none were saved
+    pub fn get(&self, model_id: &str) -> RuntimeOptions {
+        self.models.get(model_id).copied().unwrap_or_default()
+    }
+
+    /// Save a model's options; default options remove its entry
+    pub fn set(&mut self, model_id: &str, options: RuntimeOptions) -> Result<(), String> {
+        if options.is_default() {
+            self.models.remove(model_id);
+        } else {
+            self.models.insert(model_id.to_string(), options);
+        }
+        let path = Self::path();
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+        }
+        let json = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        std::fs::write(&path, json).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_options() {
+        let options = RuntimeOptions::parse("8192", "", " 8 ").unwrap();
+        assert_eq!(options, RuntimeOptions { context_length: Some(8192), gpu_layers: None, threads: Some(8) });
+        assert_eq!(serde_json::Value::Object(options.to_json()), serde_json::json!({"context_length": 8192, "threads": 8}));
+
+        assert!(RuntimeOptions::parse("", "", "").unwrap().is_default());
+        assert!(RuntimeOptions::parse("100", "", "").is_err());
+        assert!(RuntimeOptions::parse("", "-1", "").is_err());
+        assert!(RuntimeOptions::parse("", "", "four").is_err());
+    }
+}
@@ -0,0 +1,89 @@
+//! Secrets saved in preferences on this machine
+//!
+//! Sealed with ChaCha20-Poly1305 under a random key made on first use and
+//! kept in `~/.moly/secret.key`, readable only by the user. Preferences,
+//! their backups and settings exports then hold the sealed form; the key
+//! file stays behind, so a sealed secret copied to another machine can't be
+//! opened there and has to be entered again.
+
+use ring::aead::{self, LessSafeKey, UnboundKey};
+use ring::rand::{SecureRandom, SystemRandom};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+use crate::settings_sync::{self, EncryptedSecret};
+
+const KEY_LEN: usize = 32;
+
+fn key_path() -> PathBuf {
+    // Shared by all profiles
+    crate::profiles::profile_dir("").join("secret.key")
+}
+
+/// The key, read or made once per run
+fn key() -> Result<[u8; KEY_LEN], String> {
+    static KEY: OnceLock<Result<[u8; KEY_LEN], String>> = OnceLock::new();
+    KEY.get_or_init(read_or_create_key).clone()
+}
+
+fn read_or_create_key() -> Result<[u8; KEY_LEN], String> {
+    let path = key_path();
+    if let Ok(bytes) = std::fs::read(&path) {
+        return bytes.try_into().map_err(|_| format!("{} is damaged", path.display()));
+    }
+    let mut bytes = [0u8; KEY_LEN];
+    SystemRandom::new().fill(&mut bytes).map_err(|_| "Couldn't make a key for secrets".to_string())?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+    }
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options.open(&path)
+        .and_then(|mut file| file.write_all(&bytes))
+        .map_err(|e| format!("{}: {}", path.display(), e))?;
+    log::info!("Made a key for saved secrets at {}", path.display());
+    Ok(bytes)
+}
+
+fn sealing_key(bytes: &[u8; KEY_LEN]) -> Result<LessSafeKey, String> {
+    let key = UnboundKey::new(&aead::CHACHA20_POLY1305, bytes).map_err(|_| "Invalid key for secrets".to_string())?;
+    Ok(LessSafeKey::new(key))
+}
+
+/// Seal a secret to be saved
+pub fn seal(secret: &str) -> Result<EncryptedSecret, String> {
+    seal_with(&key()?, secret)
+}
+
+/// The secret a [`seal`] call saved
+pub fn open(sealed: &EncryptedSecret) -> Result<String, String> {
+    open_with(&key()?, sealed)
+}
+
+fn seal_with(key: &[u8; KEY_LEN], secret: &str) -> Result<EncryptedSecret, String> {
+    settings_sync::encrypt(&sealing_key(key)?, &SystemRandom::new(), secret)
+}
+
+fn open_with(key: &[u8; KEY_LEN], sealed: &EncryptedSecret) -> Result<String, String> {
+    settings_sync::decrypt(&sealing_key(key)?, sealed)
+        .map_err(|_| "It was sealed on another machine or is damaged".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seal_and_open() {
+        let key = [7u8; KEY_LEN];
+        let sealed = seal_with(&key, "p@ss word").unwrap();
+        assert_eq!(open_with(&key, &sealed).unwrap(), "p@ss word");
+        assert!(open_with(&[8u8; KEY_LEN], &sealed).is_err());
+    }
+}
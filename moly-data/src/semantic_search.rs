@@ -0,0 +1,349 @@
+//! Finding past chats by meaning
+//!
+//! Keyword search only finds chats that use the words typed. When semantic
+//! search is turned on in Settings, messages are also embedded with an
+//! embedding model served by the local runtime (`/v1/embeddings`), and
+//! [`search_chats`] ranks chats by their message closest to the query, so
+//! "that conversation about MLX quantization tradeoffs" is found without
+//! the exact words. Vectors are kept in `semantic_index.json` in the
+//! profile's data directory; only new and edited messages are embedded on
+//! each search.
+//!
+//! With a reranker model set as well, the closest chats by embedding are
+//! only candidates: the reranker (`/v1/rerank`) reads the query with each
+//! candidate message and puts them in its order ([`rerank_hits`]).
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+
+use crate::chats::{ChatData, ChatId};
+use crate::ominix_api_client::{EmbeddingRequest, OminiXApiClient, RerankRequest};
+
+const INDEX_FILENAME: &str = "semantic_index.json";
+
+/// Characters of a message that are embedded; the start of a message says
+/// what it is about
+const MAX_MESSAGE_CHARS: usize = 2000;
+
+/// Messages embedded per request
+const BATCH_SIZE: usize = 32;
+
+/// Closeness below which a message is not related to the query
+const MIN_SCORE: f32 = 0.35;
+
+/// Chats handed to the reranker for each chat asked for
+const RERANK_CANDIDATES: usize = 3;
+
+/// Semantic search settings, stored in preferences
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SemanticSearchSettings {
+    pub enabled: bool,
+    /// Embedding model loaded in the local runtime
+    pub model: String,
+    /// Reranker model loaded in the local runtime; empty to rank by
+    /// embedding alone
+    pub reranker: String,
+}
+
+impl SemanticSearchSettings {
+    pub fn is_ready(&self) -> bool {
+        self.enabled && !self.model.trim().is_empty()
+    }
+}
+
+/// A message's text, taken from the chats on the UI thread
+#[derive(Clone, Debug)]
+pub struct MessageText {
+    pub chat_id: ChatId,
+    pub message_index: usize,
+    pub text: String,
+}
+
+/// The texts of `chats` worth searching: finished, non-empty messages
+pub fn message_texts<'a>(chats: impl IntoIterator<Item = &'a ChatData>) -> Vec<MessageText> {
+    chats.into_iter()
+        .flat_map(|chat| chat.messages.iter().enumerate().map(move |(i, m)| (chat.id, i, m)))
+        .filter(|(_, _, m)| !m.metadata.is_writing && !m.content.text.trim().is_empty())
+        .map(|(chat_id, message_index, m)| MessageText {
+            chat_id,
+            message_index,
+            text: m.content.text.trim().chars().take(MAX_MESSAGE_CHARS).collect(),
+        })
+        .collect()
+}
+
+/// A chat related to the query, through its closest message
+#[derive(Clone, Debug, PartialEq)]
+pub struct Hit {
+    pub chat_id: ChatId,
+    pub message_index: usize,
+    /// Cosine similarity of the message and the query, or the reranker's
+    /// relevance score when one ranked the hits
+    pub score: f32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct IndexedMessage {
+    chat_id: ChatId,
+    message_index: usize,
+    /// Hash of the embedded text, to notice edits
+    hash: u64,
+    vector: Vec<f32>,
+}
+
+/// Embedded messages, for one embedding model
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct SemanticIndex {
+    #[serde(default)]
+    model: String,
+    #[serde(default)]
+    messages: Vec<IndexedMessage>,
+}
+
+impl SemanticIndex {
+    pub fn path() -> PathBuf {
+        crate::profiles::data_dir().join(INDEX_FILENAME)
+    }
+
+    pub fn load() -> Self {
+        std::fs::read_to_string(Self::path())
+            .ok()
+            .and_then(|contents| match serde_json::from_str(&contents) {
+                Ok(index) => Some(index),
+                Err(e) => {
+                    log::error!("Failed to parse semantic index: {:?}", e);
+                    None
+                }
+            })
+            .unwrap_or_default()
+    }
+
+    fn save(&self) -> Result<(), String> {
+        let path = Self::path();
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+        }
+        let json = serde_json::to_string(self).map_err(|e| e.to_string())?;
+        crate::chats::write_atomic(&path, json.as_bytes())
+    }
+
+    /// Number of embedded messages
+    pub fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+
+    /// Embed new and edited messages of `texts` with `embed`, and forget
+    /// messages that are gone. Vectors of another model are all replaced.
+    /// Returns how many messages were embedded.
+    pub fn refresh(
+        &mut self,
+        model: &str,
+        texts: &[MessageText],
+        mut embed: impl FnMut(&[String]) -> Result<Vec<Vec<f32>>, String>,
+    ) -> Result<usize, String> {
+        if self.model != model {
+            self.model = model.to_string();
+            self.messages.clear();
+        }
+        let current: HashMap<(ChatId, usize), u64> = texts.iter()
+            .map(|t| ((t.chat_id, t.message_index), hash(&t.text)))
+            .collect();
+        self.messages.retain(|m| current.get(&(m.chat_id, m.message_index)) == Some(&m.hash));
+        let indexed: HashSet<(ChatId, usize)> = self.messages.iter().map(|m| (m.chat_id, m.message_index)).collect();
+        let missing: Vec<&MessageText> = texts.iter()
+            .filter(|t| !indexed.contains(&(t.chat_id, t.message_index)))
+            .collect();
+
+        for batch in missing.chunks(BATCH_SIZE) {
+            let inputs: Vec<String> = batch.iter().map(|t| t.text.clone()).collect();
+            let vectors = embed(&inputs)?;
+            if vectors.len() != batch.len() {
+                return Err("The embedding model returned the wrong number of vectors".to_string());
+            }
+            self.messages.extend(batch.iter().zip(vectors).map(|(t, vector)| IndexedMessage {
+                chat_id: t.chat_id,
+                message_index: t.message_index,
+                hash: hash(&t.text),
+                vector,
+            }));
+        }
+        Ok(missing.len())
+    }
+
+    /// Chats whose closest message is related to `query`, closest first
+    pub fn search(&self, query: &[f32], limit: usize) -> Vec<Hit> {
+        let mut best: HashMap<ChatId, Hit> = HashMap::new();
+        for message in &self.messages {
+            let score = cosine(query, &message.vector);
+            if score < MIN_SCORE {
+                continue;
+            }
+            let hit = Hit { chat_id: message.chat_id, message_index: message.message_index, score };
+            best.entry(message.chat_id)
+                .and_modify(|b| if score > b.score { *b = hit.clone() })
+                .or_insert(hit);
+        }
+        let mut hits: Vec<Hit> = best.into_values().collect();
+        hits.sort_by(|a, b| b.score.total_cmp(&a.score));
+        hits.truncate(limit);
+        hits
+    }
+}
+
+/// Bring the index up to date with `texts` and rank chats for `query`,
+/// using the local runtime's embedding model, and its reranker when one is
+/// set. Blocks; call it off the UI thread.
+pub fn search_chats(settings: &SemanticSearchSettings, texts: &[MessageText], query: &str, limit: usize) -> Result<Vec<Hit>, String> {
+    let model = settings.model.trim();
+    let client = OminiXApiClient::localhost(120);
+    let embed = |input: &[String]| {
+        client.embeddings(&EmbeddingRequest { model: model.to_string(), input: input.to_vec() })
+            .map_err(|e| format!("Embedding with {} failed: {}", model, e))
+    };
+
+    let mut index = SemanticIndex::load();
+    let before = index.len();
+    let added = index.refresh(model, texts, &embed)?;
+    if added > 0 || index.len() != before {
+        if let Err(e) = index.save() {
+            log::warn!("Semantic index not saved: {}", e);
+        }
+    }
+    let query_text = query.trim();
+    let query = embed(&[query_text.to_string()])?
+        .into_iter()
+        .next()
+        .ok_or("The embedding model returned no vector")?;
+    let reranker = settings.reranker.trim();
+    if reranker.is_empty() {
+        return Ok(index.search(&query, limit));
+    }
+    let candidates = index.search(&query, limit * RERANK_CANDIDATES);
+    let rerank = |documents: &[String]| {
+        let request = RerankRequest {
+            model: reranker.to_string(),
+            query: query_text.to_string(),
+            documents: documents.to_vec(),
+            top_n: None,
+        };
+        client.rerank(&request)
+            .map(|results| results.into_iter().map(|r| (r.index, r.relevance_score)).collect())
+            .map_err(|e| format!("Reranking with {} failed: {}", reranker, e))
+    };
+    match rerank_hits(&candidates, texts, limit, rerank) {
+        Ok(hits) => Ok(hits),
+        // Embedding order is still a useful answer
+        Err(e) => {
+            log::warn!("{}", e);
+            Ok(candidates.into_iter().take(limit).collect())
+        }
+    }
+}
+
+/// The `limit` best of `hits` in the order `rerank` puts their messages.
+/// `rerank` gets the messages' texts and returns (position, score) pairs,
+/// most relevant first; hits it leaves out are dropped.
+pub fn rerank_hits(
+    hits: &[Hit],
+    texts: &[MessageText],
+    limit: usize,
+    rerank: impl FnOnce(&[String]) -> Result<Vec<(usize, f32)>, String>,
+) -> Result<Vec<Hit>, String> {
+    let (hits, documents): (Vec<&Hit>, Vec<String>) = hits.iter()
+        .filter_map(|hit| {
+            texts.iter()
+                .find(|t| t.chat_id == hit.chat_id && t.message_index == hit.message_index)
+                .map(|t| (hit, t.text.clone()))
+        })
+        .unzip();
+    if documents.is_empty() {
+        return Ok(Vec::new());
+    }
+    let ranked = rerank(&documents)?;
+    Ok(ranked.into_iter()
+        .filter_map(|(i, score)| hits.get(i).map(|hit| Hit { score, ..(*hit).clone() }))
+        .take(limit)
+        .collect())
+}
+
+fn cosine(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() || a.is_empty() {
+        return 0.0;
+    }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norms = a.iter().map(|x| x * x).sum::<f32>().sqrt() * b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norms == 0.0 { 0.0 } else { dot / norms }
+}
+
+/// FNV-1a, which stays the same across builds unlike `DefaultHasher`
+fn hash(text: &str) -> u64 {
+    text.bytes().fold(0xcbf29ce484222325, |h, b| (h ^ b as u64).wrapping_mul(0x100000001b3))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(chat_id: ChatId, message_index: usize, text: &str) -> MessageText {
+        MessageText { chat_id, message_index, text: text.to_string() }
+    }
+
+    /// Toy embedding: how often each of three topics is mentioned
+    fn embed(inputs: &[String]) -> Result<Vec<Vec<f32>>, String> {
+        Ok(inputs.iter().map(|t| {
+            ["quantiz", "recipe", "guitar"].iter().map(|w| t.matches(w).count() as f32).collect()
+        }).collect())
+    }
+
+    #[test]
+    fn test_refresh_and_search() {
+        let mut index = SemanticIndex::default();
+        let texts = vec![
+            text(1, 0, "4-bit quantization on MLX"),
+            text(1, 1, "quantized weights lose a little quality"),
+            text(2, 0, "a recipe for bread"),
+            text(3, 0, "guitar chords"),
+        ];
+        assert_eq!(index.refresh("embed-small", &texts, embed), Ok(4));
+        assert_eq!(index.refresh("embed-small", &texts, embed), Ok(0));
+
+        let hits = index.search(&[1.0, 0.1, 0.0], 10);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].chat_id, 1);
+        assert!(hits[0].score > 0.9);
+
+        // An edited message is embedded again, a deleted chat is forgotten
+        let texts = vec![text(1, 0, "4-bit quantization on MLX"), text(2, 0, "a guitar recipe")];
+        assert_eq!(index.refresh("embed-small", &texts, embed), Ok(1));
+        assert_eq!(index.len(), 2);
+        assert_eq!(index.search(&[0.0, 0.0, 1.0], 10)[0].chat_id, 2);
+
+        // Another model starts over
+        assert_eq!(index.refresh("embed-large", &texts, embed), Ok(2));
+    }
+
+    #[test]
+    fn test_rerank_hits() {
+        let texts = vec![text(1, 0, "quantization"), text(2, 3, "bread"), text(3, 0, "guitar")];
+        let hits = vec![
+            Hit { chat_id: 1, message_index: 0, score: 0.9 },
+            Hit { chat_id: 2, message_index: 3, score: 0.8 },
+            Hit { chat_id: 3, message_index: 0, score: 0.7 },
+        ];
+        let reranked = rerank_hits(&hits, &texts, 2, |documents| {
+            assert_eq!(documents, ["quantization", "bread", "guitar"]);
+            Ok(vec![(2, 0.95), (0, 0.5), (1, 0.1)])
+        }).unwrap();
+        assert_eq!(reranked, [
+            Hit { chat_id: 3, message_index: 0, score: 0.95 },
+            Hit { chat_id: 1, message_index: 0, score: 0.5 },
+        ]);
+        assert!(rerank_hits(&hits, &texts, 2, |_| Err("down".to_string())).is_err());
+    }
+}
@@ -0,0 +1,144 @@
+//! Reusable starting points for new sessions
+//!
+//! "Save as template" on a session in the chat history keeps its system
+//! prompt, tools, model kind and first messages under a name
+//! ([`SessionTemplate::from_chat`]). Templates are offered when a new session
+//! is empty, and [`crate::Chats::apply_template`] copies one into it. They are
+//! kept in `session_templates.json` in the profile's data directory.
+
+use chrono::{DateTime, Utc};
+use moly_kit::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use crate::chats::ChatData;
+use crate::model_registry::RegistryCategory;
+use crate::toolsets::ToolSelection;
+
+const TEMPLATES_FILENAME: &str = "session_templates.json";
+
+/// Messages of the session kept in a template, e.g. an instruction and the
+/// model's acknowledgement
+pub const MAX_TEMPLATE_MESSAGES: usize = 4;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SessionTemplate {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system_prompt: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tools: Option<ToolSelection>,
+    #[serde(default)]
+    pub model_category: Option<RegistryCategory>,
+    #[serde(default)]
+    pub messages: Vec<Message>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl SessionTemplate {
+    /// A template of `chat`'s setup and first [`MAX_TEMPLATE_MESSAGES`] messages
+    pub fn from_chat(name: &str, chat: &ChatData) -> Result<Self, String> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err("Enter a name for the template.".to_string());
+        }
+        let messages: Vec<Message> = chat.messages.iter()
+            .filter(|m| !m.metadata.is_writing && !m.content.text.is_empty())
+            .take(MAX_TEMPLATE_MESSAGES)
+            .cloned()
+            .collect();
+        if messages.is_empty() && chat.system_prompt.is_none() {
+            return Err("The session has no system prompt or messages to keep.".to_string());
+        }
+        Ok(Self {
+            name: name.to_string(),
+            system_prompt: chat.system_prompt.clone(),
+            tools: chat.tools.clone(),
+            model_category: chat.model_category,
+            messages,
+            created_at: Utc::now(),
+        })
+    }
+
+    /// One line for the template picker
+    pub fn describe(&self) -> String {
+        match (self.system_prompt.is_some(), self.messages.len()) {
+            (true, 0) => format!("{} — system prompt", self.name),
+            (true, n) => format!("{} — system prompt, {} message(s)", self.name, n),
+            (false, n) => format!("{} — {} message(s)", self.name, n),
+        }
+    }
+}
+
+/// Saved session templates, newest first
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct SessionTemplates {
+    #[serde(default)]
+    pub templates: Vec<SessionTemplate>,
+}
+
+impl SessionTemplates {
+    pub fn path() -> PathBuf {
+        crate::profiles::data_dir().join(TEMPLATES_FILENAME)
+    }
+
+    pub fn load() -> Self {
+        std::fs::read_to_string(Self::path())
+            .ok()
+            .and_then(|contents| match serde_json::from_str(&contents) {
+                Ok(templates) => Some(templates),
+                Err(e) => {
+                    log::error!("Failed to parse session templates: {:?}", e);
+                    None
+                }
+            })
+            .unwrap_or_default()
+    }
+
+    fn save(&self) -> Result<(), String> {
+        let path = Self::path();
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+        }
+        let json = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        crate::chats::write_atomic(&path, json.as_bytes())
+    }
+
+    /// Save a template, replacing one with the same name
+    pub fn add(&mut self, template: SessionTemplate) -> Result<(), String> {
+        self.templates.retain(|t| !t.name.eq_ignore_ascii_case(&template.name));
+        self.templates.insert(0, template);
+        self.save()
+    }
+
+    pub fn remove(&mut self, name: &str) -> Result<(), String> {
+        self.templates.retain(|t| t.name != name);
+        self.save()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_template_from_chat() {
+        let mut chat = ChatData::with_title("Tutor".to_string());
+        assert!(SessionTemplate::from_chat("Empty", &chat).is_err());
+
+        chat.system_prompt = Some("You are a patient maths tutor.".to_string());
+        for i in 0..6 {
+            chat.messages.push(Message {
+                from: if i % 2 == 0 { EntityId::User } else { EntityId::Bot(BotId::new("qwen3-8b")) },
+                content: MessageContent { text: format!("message {}", i), ..Default::default() },
+                ..Default::default()
+            });
+        }
+        let template = SessionTemplate::from_chat(" Maths ", &chat).unwrap();
+        assert_eq!(template.name, "Maths");
+        assert_eq!(template.messages.len(), MAX_TEMPLATE_MESSAGES);
+        assert_eq!(template.messages[0].content.text, "message 0");
+        assert_eq!(template.describe(), "Maths — system prompt, 4 message(s)");
+        assert!(SessionTemplate::from_chat("  ", &chat).is_err());
+    }
+}
@@ -0,0 +1,320 @@
+//! Settings export/import for replicating a setup across machines
+//!
+//! An export is a single JSON file holding the user's preferences and
+//! provider configurations. API keys are either left out or, when the user
+//! supplies a passphrase, encrypted with ChaCha20-Poly1305 under a key
+//! derived from it (PBKDF2-HMAC-SHA256). Cached model catalogs are never
+//! exported; they are refetched on the next connection test.
+//!
+//! Importing is two steps: [`plan_import`] decrypts the file and sorts its
+//! providers into new ones and ones that conflict with the local setup, then
+//! [`apply_import`] merges them using the chosen [`ConflictResolution`].
+
+use base64::Engine as _;
+use ring::aead::{self, Aad, LessSafeKey, Nonce, UnboundKey};
+use ring::pbkdf2;
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::{Deserialize, Serialize};
+use std::num::NonZeroU32;
+use std::path::Path;
+
+use crate::preferences::Preferences;
+use crate::providers::{ProviderId, ProviderPreferences};
+
+/// Bump when the file layout changes incompatibly
+pub const SETTINGS_EXPORT_VERSION: u32 = 1;
+
+const PBKDF2_ITERATIONS: u32 = 100_000;
+
+/// How API keys are stored in an export
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum SecretsMode {
+    /// Keys were left out
+    Omitted,
+    /// Keys are encrypted with the export passphrase
+    Encrypted,
+}
+
+/// An encrypted API key or other secret (base64 nonce and ciphertext+tag)
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EncryptedSecret {
+    pub nonce: String,
+    pub data: String,
+}
+
+/// A provider as written to the export file (its `api_key` is always empty)
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ExportedProvider {
+    #[serde(flatten)]
+    pub provider: ProviderPreferences,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encrypted_api_key: Option<EncryptedSecret>,
+}
+
+/// Contents of a settings export file
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SettingsExport {
+    pub version: u32,
+    /// RFC 3339 timestamp
+    pub exported_at: String,
+    pub secrets: SecretsMode,
+    /// Base64 PBKDF2 salt, present when secrets are encrypted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub salt: Option<String>,
+    pub sidebar_expanded: bool,
+    #[serde(default)]
+    pub current_chat_model: Option<String>,
+    pub providers: Vec<ExportedProvider>,
+}
+
+impl SettingsExport {
+    /// Build an export from the current preferences. With a non-empty
+    /// passphrase, API keys are encrypted; otherwise they are omitted.
+    pub fn from_preferences(prefs: &Preferences, passphrase: Option<&str>) -> Result<Self, String> {
+        let passphrase = passphrase.filter(|p| !p.is_empty());
+        let rng = SystemRandom::new();
+        let (salt, key) = match passphrase {
+            Some(passphrase) => {
+                let mut salt = [0u8; 16];
+                rng.fill(&mut salt).map_err(|_| "Failed to generate salt".to_string())?;
+                (Some(salt), Some(derive_key(passphrase, &salt)?))
+            }
+            None => (None, None),
+        };
+
+        let mut providers = Vec::with_capacity(prefs.providers_preferences.len());
+        for provider in &prefs.providers_preferences {
+            let encrypted_api_key = match (&key, provider.api_key.as_deref().filter(|k| !k.is_empty())) {
+                (Some(key), Some(api_key)) => Some(encrypt(key, &rng, api_key)?),
+                _ => None,
+            };
+            let mut provider = provider.clone();
+            provider.api_key = None;
+            provider.catalog.clear();
+            provider.catalog_updated_at = None;
+            providers.push(ExportedProvider { provider, encrypted_api_key });
+        }
+
+        Ok(Self {
+            version: SETTINGS_EXPORT_VERSION,
+            exported_at: chrono::Utc::now().to_rfc3339(),
+            secrets: if key.is_some() { SecretsMode::Encrypted } else { SecretsMode::Omitted },
+            salt: salt.map(|s| base64::engine::general_purpose::STANDARD.encode(s)),
+            sidebar_expanded: prefs.sidebar_expanded,
+            current_chat_model: prefs.current_chat_model.clone(),
+            providers,
+        })
+    }
+
+    pub fn write_to(&self, path: &Path) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        std::fs::write(path, json).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    pub fn read_from(path: &Path) -> Result<Self, String> {
+        let json = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        let export: Self = serde_json::from_str(&json)
+            .map_err(|e| format!("Not a settings export: {}", e))?;
+        if export.version > SETTINGS_EXPORT_VERSION {
+            return Err(format!(
+                "This export was made by a newer version (format {}, supported {})",
+                export.version, SETTINGS_EXPORT_VERSION
+            ));
+        }
+        Ok(export)
+    }
+}
+
+/// How to treat providers that exist both locally and in the import
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ConflictResolution {
+    /// Leave the local provider untouched
+    KeepLocal,
+    /// Take the imported configuration (a local API key is kept when the
+    /// import has none)
+    UseImported,
+}
+
+/// A provider whose imported configuration differs from the local one
+#[derive(Clone, Debug)]
+pub struct ProviderConflict {
+    pub provider_id: ProviderId,
+    pub name: String,
+    /// Which settings differ, e.g. "URL", "API key"
+    pub differences: Vec<&'static str>,
+}
+
+/// Result of [`plan_import`], ready for [`apply_import`]
+#[derive(Clone, Debug)]
+pub struct ImportPlan {
+    /// Imported providers with their API keys decrypted
+    providers: Vec<ProviderPreferences>,
+    sidebar_expanded: bool,
+    current_chat_model: Option<String>,
+    /// Providers not present locally; always added
+    pub new_providers: Vec<String>,
+    /// Providers that differ from the local configuration
+    pub conflicts: Vec<ProviderConflict>,
+    /// Whether the file carried API keys
+    pub has_secrets: bool,
+}
+
+/// Decrypt an export and compare it with the local preferences.
+pub fn plan_import(prefs: &Preferences, export: &SettingsExport, passphrase: Option<&str>) -> Result<ImportPlan, String> {
+    let key = match export.secrets {
+        SecretsMode::Omitted => None,
+        SecretsMode::Encrypted => {
+            let passphrase = passphrase.filter(|p| !p.is_empty())
+                .ok_or_else(|| "This export contains encrypted API keys; enter its passphrase".to_string())?;
+            let salt = export.salt.as_deref()
+                .and_then(|s| base64::engine::general_purpose::STANDARD.decode(s).ok())
+                .ok_or_else(|| "Export is missing its encryption salt".to_string())?;
+            Some(derive_key(passphrase, &salt)?)
+        }
+    };
+
+    let mut providers = Vec::with_capacity(export.providers.len());
+    let mut new_providers = Vec::new();
+    let mut conflicts = Vec::new();
+    for exported in &export.providers {
+        let mut provider = exported.provider.clone();
+        provider.api_key = match (&key, &exported.encrypted_api_key) {
+            (Some(key), Some(secret)) => Some(decrypt(key, secret)?),
+            _ => None,
+        };
+
+        match prefs.get_provider(&provider.id) {
+            None => new_providers.push(provider.name.clone()),
+            Some(local) => {
+                let differences = provider_differences(local, &provider);
+                if !differences.is_empty() {
+                    conflicts.push(ProviderConflict {
+                        provider_id: provider.id.clone(),
+                        name: local.name.clone(),
+                        differences,
+                    });
+                }
+            }
+        }
+        providers.push(provider);
+    }
+
+    Ok(ImportPlan {
+        providers,
+        sidebar_expanded: export.sidebar_expanded,
+        current_chat_model: export.current_chat_model.clone(),
+        new_providers,
+        conflicts,
+        has_secrets: key.is_some(),
+    })
+}
+
+/// Merge a planned import into the preferences and save them.
+pub fn apply_import(prefs: &mut Preferences, plan: ImportPlan, resolution: ConflictResolution) {
+    for imported in plan.providers {
+        let conflicting = plan.conflicts.iter().any(|c| c.provider_id == imported.id);
+        match prefs.get_provider_mut(&imported.id) {
+            None => prefs.providers_preferences.push(imported),
+            Some(local) if conflicting && resolution == ConflictResolution::UseImported => {
+                let api_key = imported.api_key.clone().or_else(|| local.api_key.take());
+                let catalog = std::mem::take(&mut local.catalog);
+                let catalog_updated_at = local.catalog_updated_at.take();
+                *local = ProviderPreferences { api_key, catalog, catalog_updated_at, ..imported };
+            }
+            Some(_) => {}
+        }
+    }
+    prefs.sidebar_expanded = plan.sidebar_expanded;
+    if prefs.current_chat_model.is_none() {
+        prefs.current_chat_model = plan.current_chat_model;
+    }
+    prefs.merge_with_supported_providers();
+    prefs.save();
+}
+
+fn provider_differences(local: &ProviderPreferences, imported: &ProviderPreferences) -> Vec<&'static str> {
+    let mut differences = Vec::new();
+    if local.url != imported.url { differences.push("URL"); }
+    if imported.api_key.is_some() && local.api_key != imported.api_key { differences.push("API key"); }
+    if local.enabled != imported.enabled { differences.push("enabled"); }
+    if local.models != imported.models { differences.push("models"); }
+    if local.favorite_models != imported.favorite_models { differences.push("favorites"); }
+    if local.system_prompt != imported.system_prompt { differences.push("system prompt"); }
+    if local.chat_defaults != imported.chat_defaults { differences.push("chat defaults"); }
+    if local.tools_enabled != imported.tools_enabled || local.a2ui_enabled != imported.a2ui_enabled {
+        differences.push("tools");
+    }
+    differences
+}
+
+// ─── Encryption ──────────────────────────────────────────────────────────────
+
+fn derive_key(passphrase: &str, salt: &[u8]) -> Result<LessSafeKey, String> {
+    let mut key_bytes = [0u8; 32];
+    let iterations = NonZeroU32::new(PBKDF2_ITERATIONS).expect("non-zero iterations");
+    pbkdf2::derive(pbkdf2::PBKDF2_HMAC_SHA256, iterations, salt, passphrase.as_bytes(), &mut key_bytes);
+    let key = UnboundKey::new(&aead::CHACHA20_POLY1305, &key_bytes)
+        .map_err(|_| "Failed to derive encryption key".to_string())?;
+    Ok(LessSafeKey::new(key))
+}
+
+pub(crate) fn encrypt(key: &LessSafeKey, rng: &SystemRandom, plaintext: &str) -> Result<EncryptedSecret, String> {
+    let mut nonce = [0u8; aead::NONCE_LEN];
+    rng.fill(&mut nonce).map_err(|_| "Failed to generate nonce".to_string())?;
+    let mut data = plaintext.as_bytes().to_vec();
+    key.seal_in_place_append_tag(Nonce::assume_unique_for_key(nonce), Aad::empty(), &mut data)
+        .map_err(|_| "Failed to encrypt API key".to_string())?;
+    let b64 = &base64::engine::general_purpose::STANDARD;
+    Ok(EncryptedSecret { nonce: b64.encode(nonce), data: b64.encode(data) })
+}
+
+pub(crate) fn decrypt(key: &LessSafeKey, secret: &EncryptedSecret) -> Result<String, String> {
+    let b64 = &base64::engine::general_purpose::STANDARD;
+    let invalid = || "Wrong passphrase or corrupted export".to_string();
+    let nonce: [u8; aead::NONCE_LEN] = b64.decode(&secret.nonce).ok()
+        .and_then(|n| n.try_into().ok())
+        .ok_or_else(invalid)?;
+    let mut data = b64.decode(&secret.data).map_err(|_| invalid())?;
+    let plaintext = key.open_in_place(Nonce::assume_unique_for_key(nonce), Aad::empty(), &mut data)
+        .map_err(|_| invalid())?;
+    String::from_utf8(plaintext.to_vec()).map_err(|_| invalid())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn prefs_with_key(key: &str) -> Preferences {
+        let mut prefs = Preferences::default();
+        prefs.providers_preferences[0].api_key = Some(key.to_string());
+        prefs
+    }
+
+    #[test]
+    fn test_encrypted_round_trip() {
+        let prefs = prefs_with_key("sk-secret");
+        let export = SettingsExport::from_preferences(&prefs, Some("hunter2")).unwrap();
+        assert_eq!(export.secrets, SecretsMode::Encrypted);
+        assert!(!serde_json::to_string(&export).unwrap().contains("sk-secret"));
+
+        assert!(plan_import(&Preferences::default(), &export, Some("wrong")).is_err());
+        let plan = plan_import(&Preferences::default(), &export, Some("hunter2")).unwrap();
+        assert!(plan.has_secrets);
+        assert_eq!(plan.conflicts.len(), 1);
+        assert_eq!(plan.conflicts[0].differences, vec!["API key"]);
+    }
+
+    #[test]
+    fn test_omitted_secrets_keep_local_key() {
+        let mut exported = prefs_with_key("sk-remote");
+        exported.providers_preferences[0].url = "https://example.com/v1".to_string();
+        let export = SettingsExport::from_preferences(&exported, None).unwrap();
+        assert_eq!(export.secrets, SecretsMode::Omitted);
+
+        let local = prefs_with_key("sk-local");
+        let plan = plan_import(&local, &export, None).unwrap();
+        assert_eq!(plan.conflicts[0].differences, vec!["URL"]);
+        let provider = plan.providers.iter().find(|p| p.id == plan.conflicts[0].provider_id).unwrap();
+        assert_eq!(provider.api_key, None);
+    }
+}
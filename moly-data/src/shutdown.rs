@@ -0,0 +1,86 @@
+//! Work that quitting would cut short
+//!
+//! Threads that download, train, generate or run a batch hold a [`Tracked`]
+//! guard from [`track`] for as long as they run. When the main window is
+//! closed while [`in_flight`] lists anything, the shell asks whether to wait
+//! for it or to stop it ([`cancel_all`]) before it saves drafts and window
+//! state, unloads models if Settings says so, and quits. Downloads stopped
+//! this way remove their `.part` file and keep the files already complete.
+
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// How long stopped work gets to wind down before the app quits anyway
+pub const CANCEL_GRACE: Duration = Duration::from_secs(10);
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+static WORK: Mutex<Vec<Work>> = Mutex::new(Vec::new());
+
+struct Work {
+    id: u64,
+    label: String,
+    cancel: Option<Arc<AtomicBool>>,
+}
+
+/// Lists its work in [`in_flight`] until dropped
+#[must_use = "the work is only listed while the guard is alive"]
+pub struct Tracked {
+    id: u64,
+}
+
+impl Drop for Tracked {
+    fn drop(&mut self) {
+        WORK.lock().unwrap().retain(|w| w.id != self.id);
+    }
+}
+
+/// List work as in flight, e.g. `Downloading Qwen3 4B`. `cancel` is the flag
+/// the work checks to stop early; without one it can only be waited for.
+pub fn track(label: impl Into<String>, cancel: Option<Arc<AtomicBool>>) -> Tracked {
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    WORK.lock().unwrap().push(Work { id, label: label.into(), cancel });
+    Tracked { id }
+}
+
+/// What is running, oldest first
+pub fn in_flight() -> Vec<String> {
+    WORK.lock().unwrap().iter().map(|w| w.label.clone()).collect()
+}
+
+/// Ask all work to stop. Returns what can't be stopped and will run to its end.
+pub fn cancel_all() -> Vec<String> {
+    let work = WORK.lock().unwrap();
+    for cancel in work.iter().filter_map(|w| w.cancel.as_ref()) {
+        cancel.store(true, Ordering::SeqCst);
+    }
+    work.iter().filter(|w| w.cancel.is_none()).map(|w| w.label.clone()).collect()
+}
+
+/// The close dialog's list, one line per item
+pub fn describe(work: &[String]) -> String {
+    work.iter().map(|label| format!("• {}", label)).collect::<Vec<_>>().join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_track_and_cancel() {
+        let flag = Arc::new(AtomicBool::new(false));
+        let download = track("test: Downloading Qwen3 4B", Some(flag.clone()));
+        let image = track("test: Generating an image", None);
+        let ours = |labels: Vec<String>| labels.into_iter().filter(|l| l.starts_with("test: ")).collect::<Vec<_>>();
+        assert_eq!(ours(in_flight()), ["test: Downloading Qwen3 4B", "test: Generating an image"]);
+
+        assert_eq!(ours(cancel_all()), ["test: Generating an image"]);
+        assert!(flag.load(Ordering::SeqCst));
+
+        drop(download);
+        assert_eq!(ours(in_flight()), ["test: Generating an image"]);
+        drop(image);
+        assert!(ours(in_flight()).is_empty());
+        assert_eq!(describe(&["A".to_string(), "B".to_string()]), "• A\n• B");
+    }
+}
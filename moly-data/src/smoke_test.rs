@@ -0,0 +1,120 @@
+//! Checking that a freshly loaded model answers
+//!
+//! A load can succeed while the model still fails on its first request, e.g.
+//! with missing weights or an unsupported architecture. [`run`] sends the
+//! smallest request of the model's kind — a one-token answer, half a second
+//! of silence to transcribe, one spoken word, a 64x64 image or two passages
+//! to rank — so the Hub can show the model as verified, or why it isn't.
+
+use crate::audio::build_wav;
+use crate::chat_template::ChatTemplate;
+use crate::model_registry::{RegistryCategory, RegistryModel};
+use crate::ominix_api_client::{
+    ChatCompletionRequest, ChatMessage, CompletionRequest, ImageGenerationRequest, MusicGenerationRequest, OminiXApiClient,
+    RerankRequest, SpeechRequest, TranscriptionRequest,
+};
+
+/// Seconds each check may take; a model that is slower than this on the
+/// smallest request is not usable anyway
+const TIMEOUT_SECS: u64 = 120;
+
+/// Voice for the TTS check, known to every bundled TTS model
+const VOICE: &str = "vivian";
+
+/// Whether [`run`] can check models of this kind; video generation is too
+/// slow even at the smallest size
+pub fn supported(category: RegistryCategory) -> bool {
+    category != RegistryCategory::VideoGen
+}
+
+/// Send `model` a tiny request, formatted with `template` for models that
+/// take formatted prompts. Blocks; call it off the UI thread.
+pub fn run(model: &RegistryModel, template: Option<ChatTemplate>) -> Result<(), String> {
+    let client = OminiXApiClient::localhost(TIMEOUT_SECS);
+    let api_id = model.runtime.api_model_id.clone();
+    match model.category {
+        RegistryCategory::Llm | RegistryCategory::Vlm => {
+            let messages = vec![ChatMessage::user("Reply with OK.")];
+            match template {
+                Some(template) => {
+                    let turns: Vec<(String, String)> = messages.iter().map(|m| (m.role.clone(), m.text())).collect();
+                    client.completion(&CompletionRequest {
+                        model: api_id,
+                        prompt: template.render(&turns),
+                        stop: template.stop().iter().map(|s| s.to_string()).collect(),
+                        max_tokens: Some(1),
+                    })?;
+                }
+                None => {
+                    client.chat_completion(&ChatCompletionRequest { model: api_id, messages, max_tokens: Some(1) })?;
+                }
+            }
+        }
+        RegistryCategory::Asr => {
+            let path = crate::artifacts::unique_path("smoke-test", "wav");
+            std::fs::write(&path, silence_wav(0.5)).map_err(|e| format!("{}: {}", path.display(), e))?;
+            let result = client.transcribe(&TranscriptionRequest::text(api_id, path.to_string_lossy()));
+            crate::artifacts::release(&path);
+            result?;
+        }
+        RegistryCategory::Tts => {
+            let audio = client.speech(&SpeechRequest::wav(api_id, VOICE, "Hello."))?;
+            if audio.is_empty() {
+                return Err("The model returned no audio.".to_string());
+            }
+        }
+        RegistryCategory::ImageGen => {
+            let request = ImageGenerationRequest { size: "64x64".to_string(), ..ImageGenerationRequest::new(api_id, "a red circle") };
+            client.generate_image(&request)?.first_bytes()?;
+        }
+        RegistryCategory::VideoGen => return Err("Video models are not checked.".to_string()),
+        RegistryCategory::MusicGen => {
+            let request = MusicGenerationRequest { duration: Some(1.0), ..MusicGenerationRequest::wav(api_id, "a short drum beat") };
+            if client.generate_music(&request, |_| {})?.is_empty() {
+                return Err("The model returned no audio.".to_string());
+            }
+        }
+        RegistryCategory::Reranker => {
+            let request = RerankRequest {
+                model: api_id,
+                query: "weather".to_string(),
+                documents: vec!["It is sunny today.".to_string(), "The cat sleeps.".to_string()],
+                top_n: None,
+            };
+            if client.rerank(&request)?.len() != 2 {
+                return Err("The model did not score every passage.".to_string());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// 16 kHz mono 16-bit PCM silence
+fn silence_wav(secs: f32) -> Vec<u8> {
+    const RATE: u32 = 16_000;
+    let mut fmt = [0u8; 16];
+    fmt[0..2].copy_from_slice(&1u16.to_le_bytes()); // PCM
+    fmt[2..4].copy_from_slice(&1u16.to_le_bytes()); // mono
+    fmt[4..8].copy_from_slice(&RATE.to_le_bytes());
+    fmt[8..12].copy_from_slice(&(RATE * 2).to_le_bytes());
+    fmt[12..14].copy_from_slice(&2u16.to_le_bytes());
+    fmt[14..16].copy_from_slice(&16u16.to_le_bytes());
+    let samples = (RATE as f32 * secs) as usize;
+    build_wav(&fmt, &vec![0u8; samples * 2])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::Wav;
+
+    #[test]
+    fn test_silence_is_half_a_second() {
+        let bytes = silence_wav(0.5);
+        let wav = Wav::parse(&bytes).unwrap();
+        assert_eq!(wav.duration_secs(), 0.5);
+        assert!(wav.data.iter().all(|b| *b == 0));
+        assert!(!supported(RegistryCategory::VideoGen));
+        assert!(supported(RegistryCategory::Asr));
+    }
+}
@@ -1,12 +1,15 @@
 use makepad_widgets::*;
 use moly_kit::prelude::*;
+use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 
-use crate::chats::Chats;
+use crate::chats::{ChatId, Chats};
 use crate::moly_client::MolyClient;
 use crate::preferences::Preferences;
+use crate::providers::{ProviderConnectionStatus, ProviderId};
 use crate::providers_manager::ProvidersManager;
 use crate::model_registry::RegistryCategory;
+use crate::model_state;
 
 /// Actions that can be dispatched to modify the Store
 #[derive(Clone, Debug, DefaultNone)]
@@ -21,14 +24,134 @@ pub enum StoreAction {
     SetLocalModel(Option<String>),
     /// Open a new chat session pre-loaded with a specific model
     OpenChatWithModel { model_id: String, category: RegistryCategory },
-    /// Notify the shell that a model was loaded from the hub
-    HubModelLoaded { model_id: String, model_name: String, category: RegistryCategory },
-    /// Notify the shell that a model was unloaded from the hub
-    HubModelUnloaded { model_id: String },
+    /// Load a downloaded registry model (by registry id), as the shell's
+    /// model selector does; the shell reports progress with
+    /// [`StoreEvent::ModelLoadStateChanged`]
+    LoadLocalModel { model_id: String },
+    /// Open a transcription session with a specific ASR model
+    OpenTranscriber { model_id: String },
+    /// Open a speech synthesis session with a specific TTS model
+    OpenSpeaker { model_id: String },
+    /// Show a chat, scrolled to a message if one is given; the shell opens it
+    OpenChat { chat_id: ChatId, message_index: Option<usize> },
+    /// Save new low power settings; the shell follows up with
+    /// [`StoreEvent::PowerModeChanged`] when the mode turns on or off
+    SetPowerSettings(crate::power::PowerSettings),
+    /// Save Voice Studio's synthesis presets
+    SetVoicePresets(Vec<crate::voice_presets::VoicePreset>),
     /// No action
     None,
 }
 
+/// Load state of a model in the local runtime, as carried by
+/// [`StoreEvent::ModelLoadStateChanged`]
+#[derive(Clone, Debug, PartialEq)]
+pub enum ModelLoadPhase {
+    Loading,
+    Loaded,
+    Unloaded,
+    Failed(String),
+}
+
+/// Notifications broadcast to every app through the Makepad action queue
+///
+/// Where [`StoreAction`] asks for something to change, a `StoreEvent` reports
+/// that something already did. Producers emit from the UI thread (typically
+/// while draining a worker channel) and anyone interested reacts in its
+/// action handler, so apps no longer poll each other or keep private copies
+/// of the same state.
+///
+/// # Usage
+///
+/// Publishing:
+/// ```rust,ignore
+/// cx.action(StoreEvent::DownloadCompleted { model_id: id.clone() });
+/// ```
+///
+/// Subscribing, in a widget's `handle_event` or the App's `handle_actions`:
+/// ```rust,ignore
+/// if let Event::Actions(actions) = event {
+///     for event in StoreEvent::iter(actions) {
+///         match event {
+///             StoreEvent::DownloadCompleted { model_id } => self.refresh(cx, &model_id),
+///             _ => {}
+///         }
+///     }
+/// }
+/// ```
+///
+/// The shell forwards every event to [`Store::handle_event`] so that state
+/// shared through the Store stays in sync as well.
+#[derive(Clone, Debug, DefaultNone)]
+pub enum StoreEvent {
+    /// A model started loading, finished loading, failed, or was unloaded
+    /// (`model_id` is the registry id)
+    ModelLoadStateChanged {
+        model_id: String,
+        model_name: String,
+        category: RegistryCategory,
+        phase: ModelLoadPhase,
+    },
+    /// All files of a registry model finished downloading
+    DownloadCompleted { model_id: String },
+    /// A registry model download stopped with an error
+    DownloadFailed { model_id: String, error: String },
+    /// A download status in [`crate::model_state`] changed (`key` is the
+    /// expanded storage path, or the file id for Moly server downloads)
+    ModelStateChanged { key: String },
+    /// A provider connection test produced a new status
+    ProviderStatusChanged { provider_id: ProviderId, status: ProviderConnectionStatus },
+    /// The UI switched between the light and dark palettes
+    ThemeChanged { dark_mode: bool },
+    /// The shell switched to another profile, or imported a workspace, and
+    /// reloaded the Store; drop anything cached from the previous data
+    ProfileChanged { profile_id: String },
+    /// `store.chats` was reloaded from disk after restoring a backup; views
+    /// showing chat history should refresh
+    ChatsRestored,
+    /// A workspace archive was unpacked over the profile's files; the shell
+    /// reloads the Store and follows with [`StoreEvent::ProfileChanged`]
+    WorkspaceImported,
+    /// Low power mode turned on or off, by hand or by the power source;
+    /// background checks should use [`crate::power::interval`]
+    PowerModeChanged { active: bool },
+    /// No event
+    None,
+}
+
+impl StoreEvent {
+    /// All store events in an action batch, in emission order
+    pub fn iter(actions: &Actions) -> impl Iterator<Item = StoreEvent> + '_ {
+        actions.iter()
+            .map(|action| action.cast::<StoreEvent>())
+            .filter(|event| !matches!(event, StoreEvent::None))
+    }
+
+    /// Load state changes only, as `(model_id, category, phase)`
+    pub fn model_load_changes(actions: &Actions) -> impl Iterator<Item = (String, RegistryCategory, ModelLoadPhase)> + '_ {
+        Self::iter(actions).filter_map(|event| match event {
+            StoreEvent::ModelLoadStateChanged { model_id, category, phase, .. } => Some((model_id, category, phase)),
+            _ => None,
+        })
+    }
+
+    /// Keys of [`crate::model_state`] entries that changed in this batch
+    pub fn model_state_changes(actions: &Actions) -> impl Iterator<Item = String> + '_ {
+        Self::iter(actions).filter_map(|event| match event {
+            StoreEvent::ModelStateChanged { key } => Some(key),
+            _ => None,
+        })
+    }
+
+    /// Registry ids of downloads that completed in this batch
+    pub fn completed_downloads(actions: &Actions) -> impl Iterator<Item = String> + '_ {
+        Self::iter(actions).filter_map(|event| match event {
+            StoreEvent::DownloadCompleted { model_id } => Some(model_id),
+            _ => None,
+        })
+    }
+}
+
 /// Central state container for the application
 ///
 /// The Store holds all shared application state and is passed down
@@ -83,6 +206,13 @@ pub struct Store {
     /// Pending model to open in a new chat session.
     /// Set by StoreAction::OpenChatWithModel; cleared once consumed by ChatApp.
     pub pending_chat_model: Option<(String, RegistryCategory)>,
+
+    /// Last known connection status per provider, kept current from
+    /// StoreEvent::ProviderStatusChanged.
+    pub provider_statuses: HashMap<ProviderId, ProviderConnectionStatus>,
+
+    /// Whether the dark palette is active, kept current from StoreEvent::ThemeChanged.
+    pub dark_mode: bool,
 }
 
 impl Default for Store {
@@ -100,6 +230,8 @@ impl Default for Store {
             active_local_model_category: None,
             active_local_model_supports_images: false,
             pending_chat_model: None,
+            provider_statuses: HashMap::new(),
+            dark_mode: false,
         }
     }
 }
@@ -108,6 +240,22 @@ impl Store {
     /// Create a new Store by loading preferences from disk
     pub fn load() -> Self {
         let preferences = Preferences::load();
+        crate::model_trash::set_hard_delete(preferences.hard_delete_models);
+        crate::privacy_filter::set_enabled(preferences.privacy_filter);
+        crate::download_settings::set_current(&preferences.downloads);
+        crate::network::set_current(&preferences.network);
+        crate::power::set_current(&preferences.power);
+        crate::asr_cleanup::set_current(&preferences.asr);
+        crate::transcribe_watch::set_current(&preferences.watch_folder);
+        crate::tts_voices::set_current(&preferences.tts_voices);
+        crate::tts_output::set_current(&preferences.tts_output);
+        crate::voice_presets::set_current(&preferences.voice_presets);
+        crate::fs_tool::set_allowed_dirs(&preferences.fs_tool_dirs);
+        crate::code_tool::set_current(&preferences.code_tool);
+        crate::mcp_client::set_servers(&preferences.mcp_servers);
+        crate::telemetry::set_current(&preferences.telemetry);
+        crate::artifacts::set_retention_hours(preferences.generated_files_hours);
+        crate::runtime_log::set_path(&preferences.runtime_log_path);
 
         // Create a ChatController with basic async spawner
         let chat_controller = ChatController::new_arc();
@@ -138,6 +286,8 @@ impl Store {
             active_local_model_category: None,
             active_local_model_supports_images: false,
             pending_chat_model: None,
+            provider_statuses: HashMap::new(),
+            dark_mode: false,
         }
     }
 
@@ -148,7 +298,7 @@ impl Store {
         // Re-inject local model if active (configure_providers clears all clients)
         if let Some(ref model_id) = self.active_local_model.clone() {
             let is_vlm = self.active_local_model_category == Some(RegistryCategory::Vlm);
-            self.providers_manager.inject_local_model(model_id, is_vlm);
+            self.providers_manager.inject_local_model(model_id, is_vlm, crate::chat_template::template_for_id(model_id));
         }
     }
 
@@ -156,7 +306,7 @@ impl Store {
     pub fn set_active_local_model(&mut self, model_id: Option<String>) {
         let is_vlm = self.active_local_model_category == Some(RegistryCategory::Vlm);
         match &model_id {
-            Some(id) => self.providers_manager.inject_local_model(id, is_vlm),
+            Some(id) => self.providers_manager.inject_local_model(id, is_vlm, crate::chat_template::template_for_id(id)),
             None     => self.providers_manager.remove_local_model(),
         }
         if model_id.is_none() {
@@ -236,6 +386,7 @@ impl Store {
                 self.set_sidebar_expanded(*expanded);
             }
             StoreAction::Navigate(view) => {
+                crate::telemetry::record_feature(&format!("view.{}", view));
                 self.set_current_view(view);
             }
             StoreAction::SetLocalModel(model_id) => {
@@ -245,10 +396,121 @@ impl Store {
                 self.set_active_local_model(Some(model_id.clone()));
                 self.set_pending_chat_model(model_id.clone(), *category);
             }
-            StoreAction::HubModelLoaded { .. } | StoreAction::HubModelUnloaded { .. } => {
-                // Handled by the shell (app.rs), not the Store
+            StoreAction::OpenTranscriber { model_id } => {
+                self.set_active_local_model(Some(model_id.clone()));
+                self.set_pending_chat_model(model_id.clone(), RegistryCategory::Asr);
+            }
+            StoreAction::OpenSpeaker { model_id } => {
+                self.set_active_local_model(Some(model_id.clone()));
+                self.set_pending_chat_model(model_id.clone(), RegistryCategory::Tts);
+            }
+            StoreAction::OpenChat { chat_id, .. } => {
+                self.chats.set_current_chat(Some(*chat_id));
+            }
+            // Loading runs in the shell
+            StoreAction::LoadLocalModel { .. } => {}
+            StoreAction::SetPowerSettings(settings) => {
+                crate::power::set_current(settings);
+                self.preferences.power = settings.clone();
+                self.preferences.save();
+            }
+            StoreAction::SetVoicePresets(presets) => {
+                crate::voice_presets::set_current(presets);
+                self.preferences.voice_presets = presets.clone();
+                self.preferences.save();
             }
             StoreAction::None => {}
         }
     }
+
+    /// Mirror a StoreEvent into shared state
+    pub fn handle_event(&mut self, event: &StoreEvent) {
+        match event {
+            StoreEvent::ProviderStatusChanged { provider_id, status } => {
+                self.provider_statuses.insert(provider_id.clone(), status.clone());
+            }
+            StoreEvent::ThemeChanged { dark_mode } => {
+                self.dark_mode = *dark_mode;
+            }
+            StoreEvent::ModelLoadStateChanged { model_id, phase, .. } => {
+                match phase {
+                    ModelLoadPhase::Loaded => crate::telemetry::record_feature("model.load"),
+                    ModelLoadPhase::Failed(e) => crate::telemetry::record_error(&crate::MolyError::from_message(e)),
+                    _ => {}
+                }
+                model_state::record_load_phase(model_id, phase.clone());
+            }
+            StoreEvent::DownloadCompleted { .. } => crate::telemetry::record_feature("model.download"),
+            StoreEvent::DownloadFailed { error, .. } => {
+                crate::telemetry::record_error(&crate::MolyError::from_message(error));
+            }
+            // Download state lives in model_state
+            StoreEvent::ModelStateChanged { .. }
+            | StoreEvent::ProfileChanged { .. }
+            | StoreEvent::ChatsRestored
+            | StoreEvent::WorkspaceImported
+            | StoreEvent::PowerModeChanged { .. }
+            | StoreEvent::None => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_helpers_filter_the_batch() {
+        let actions: Actions = vec![
+            Box::new(StoreEvent::DownloadCompleted { model_id: "qwen3-4b".into() }),
+            Box::new(StoreAction::Navigate("hub".into())),
+            Box::new(StoreEvent::ModelStateChanged { key: "/models/qwen3-4b".into() }),
+            Box::new(StoreEvent::ModelLoadStateChanged {
+                model_id: "qwen3-4b".into(),
+                model_name: "Qwen3 4B".into(),
+                category: RegistryCategory::Llm,
+                phase: ModelLoadPhase::Loaded,
+            }),
+        ];
+        assert_eq!(StoreEvent::iter(&actions).count(), 3);
+        assert_eq!(StoreEvent::completed_downloads(&actions).collect::<Vec<_>>(), ["qwen3-4b"]);
+        assert_eq!(StoreEvent::model_state_changes(&actions).collect::<Vec<_>>(), ["/models/qwen3-4b"]);
+        let loads: Vec<_> = StoreEvent::model_load_changes(&actions).collect();
+        assert_eq!(loads, [("qwen3-4b".to_string(), RegistryCategory::Llm, ModelLoadPhase::Loaded)]);
+    }
+
+    #[test]
+    fn test_handle_event_mirrors_state() {
+        let mut store = Store::default();
+        store.handle_event(&StoreEvent::ThemeChanged { dark_mode: true });
+        store.handle_event(&StoreEvent::ProviderStatusChanged {
+            provider_id: "openai".into(),
+            status: ProviderConnectionStatus::Connected,
+        });
+        store.handle_event(&StoreEvent::ModelLoadStateChanged {
+            model_id: "test-store-event-model".into(),
+            model_name: "Test".into(),
+            category: RegistryCategory::Llm,
+            phase: ModelLoadPhase::Unloaded,
+        });
+        assert!(store.dark_mode);
+        assert_eq!(store.provider_statuses.get("openai"), Some(&ProviderConnectionStatus::Connected));
+        assert_eq!(model_state::load_phase("test-store-event-model"), Some(ModelLoadPhase::Unloaded));
+    }
+
+    /// The Hub's Open Transcriber and Open Speaker buttons route the model
+    /// into a new session named after it
+    #[test]
+    fn test_open_transcriber_and_speaker() {
+        let mut store = Store::default();
+        store.handle_action(&StoreAction::OpenTranscriber { model_id: "whisper-large-v3".into() });
+        assert_eq!(store.get_active_local_model(), Some("whisper-large-v3"));
+        let (model_id, category) = store.take_pending_chat_model().unwrap();
+        assert_eq!(category, RegistryCategory::Asr);
+        assert_eq!(crate::ChatData::workspace_title(&model_id, category).unwrap(), "Transcription · whisper-large-v3");
+
+        store.handle_action(&StoreAction::OpenSpeaker { model_id: "qwen3-tts".into() });
+        assert_eq!(store.take_pending_chat_model(), Some(("qwen3-tts".to_string(), RegistryCategory::Tts)));
+        assert_eq!(crate::ChatData::workspace_title("qwen3-4b", RegistryCategory::Llm), None);
+    }
 }
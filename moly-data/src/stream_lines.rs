@@ -0,0 +1,46 @@
+//! Lines of a streamed response body
+//!
+//! Streamed chat answers (Ollama's NDJSON, OpenAI's server-sent events) come
+//! one record per line, but network chunks end anywhere, also inside a
+//! multibyte character. [`LineBuffer`] keeps the raw bytes and decodes only
+//! whole lines, so a character split across chunks arrives intact.
+
+/// Bytes received but not yet taken as lines
+#[derive(Debug, Default)]
+pub struct LineBuffer {
+    bytes: Vec<u8>,
+}
+
+impl LineBuffer {
+    pub fn push(&mut self, chunk: &[u8]) {
+        self.bytes.extend_from_slice(chunk);
+    }
+
+    /// The next whole line, without its line break
+    pub fn next_line(&mut self) -> Option<String> {
+        let pos = self.bytes.iter().position(|&b| b == b'\n')?;
+        let line: Vec<u8> = self.bytes.drain(..=pos).collect();
+        let line = line.strip_suffix(b"\n").unwrap_or(&line);
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        Some(String::from_utf8_lossy(line).into_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_character_split_across_chunks() {
+        let body = "{\"message\":{\"content\":\"你好\"}}\r\n{\"done\":true}\n".as_bytes();
+        // Cut inside the three bytes of 你
+        let cut = body.iter().position(|&b| b >= 0x80).unwrap() + 1;
+        let mut buffer = LineBuffer::default();
+        buffer.push(&body[..cut]);
+        assert_eq!(buffer.next_line(), None);
+        buffer.push(&body[cut..]);
+        assert_eq!(buffer.next_line().as_deref(), Some("{\"message\":{\"content\":\"你好\"}}"));
+        assert_eq!(buffer.next_line().as_deref(), Some("{\"done\":true}"));
+        assert_eq!(buffer.next_line(), None);
+    }
+}
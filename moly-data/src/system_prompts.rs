@@ -0,0 +1,187 @@
+//! Default system prompts by kind of model
+//!
+//! Settings keep one prompt for chat assistants, one for vision models that
+//! describe images and one for coding models ([`SystemPromptPresets`]). The
+//! Hub's "Open in Chat" copies the matching one into the new chat, where it
+//! can be changed for that chat only; chats with a provider start with the
+//! provider's prompt instead ([`crate::chat_defaults`]). The current chat's
+//! prompt is mirrored with [`set_active`] and sent ahead of the conversation
+//! by [`SystemPromptClient`] and the chat's VLM requests.
+
+use moly_kit::aitk::protocol::*;
+use moly_kit::aitk::utils::asynchronous::{BoxPlatformSendFuture, BoxPlatformSendStream};
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+
+use crate::model_registry::{ModelRegistry, RegistryCategory};
+
+/// System prompt of the chat that is open, if it has one
+static ACTIVE: Mutex<Option<String>> = Mutex::new(None);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PresetKind {
+    Assistant,
+    Describer,
+    Coding,
+}
+
+impl PresetKind {
+    /// The preset for a model; `None` for models that don't chat, like TTS
+    /// or image generation
+    pub fn for_model(model_id: &str, category: RegistryCategory, tags: &[String]) -> Option<Self> {
+        let is_code = |s: &str| {
+            let s = s.to_lowercase();
+            s.contains("code") || s == "coding"
+        };
+        match category {
+            RegistryCategory::Vlm => Some(Self::Describer),
+            RegistryCategory::Llm if is_code(model_id) || tags.iter().any(|t| is_code(t)) => Some(Self::Coding),
+            RegistryCategory::Llm => Some(Self::Assistant),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SystemPromptPresets {
+    #[serde(default = "default_assistant")]
+    pub assistant: String,
+    #[serde(default = "default_describer")]
+    pub describer: String,
+    #[serde(default = "default_coding")]
+    pub coding: String,
+}
+
+fn default_assistant() -> String {
+    "You are a helpful assistant. Answer clearly and concisely.".to_string()
+}
+
+fn default_describer() -> String {
+    "You describe images accurately and in detail: the subject, setting, text and anything notable. \
+     Don't guess about what you can't see."
+        .to_string()
+}
+
+fn default_coding() -> String {
+    "You are an expert programmer. Give correct, idiomatic code with brief explanations, \
+     and say which language each code block is in."
+        .to_string()
+}
+
+impl Default for SystemPromptPresets {
+    fn default() -> Self {
+        Self {
+            assistant: default_assistant(),
+            describer: default_describer(),
+            coding: default_coding(),
+        }
+    }
+}
+
+impl SystemPromptPresets {
+    pub fn get(&self, kind: PresetKind) -> &str {
+        match kind {
+            PresetKind::Assistant => &self.assistant,
+            PresetKind::Describer => &self.describer,
+            PresetKind::Coding => &self.coding,
+        }
+    }
+
+    pub fn set(&mut self, kind: PresetKind, prompt: &str) {
+        let prompt = prompt.trim().to_string();
+        match kind {
+            PresetKind::Assistant => self.assistant = prompt,
+            PresetKind::Describer => self.describer = prompt,
+            PresetKind::Coding => self.coding = prompt,
+        }
+    }
+
+    /// The prompt for a model opened from the Hub, by registry or API id;
+    /// `None` when its preset is empty
+    pub fn for_model(&self, model_id: &str, category: RegistryCategory) -> Option<String> {
+        let tags = ModelRegistry::load()
+            .models
+            .into_iter()
+            .find(|m| m.id == model_id || m.runtime.api_model_id == model_id)
+            .map(|m| m.tags)
+            .unwrap_or_default();
+        let kind = PresetKind::for_model(model_id, category, &tags)?;
+        Some(self.get(kind).trim().to_string()).filter(|p| !p.is_empty())
+    }
+}
+
+/// Mirror the open chat's [`crate::ChatData::system_prompt`]
+pub fn set_active(prompt: Option<&str>) {
+    *ACTIVE.lock().unwrap() = prompt.map(str::trim).filter(|p| !p.is_empty()).map(str::to_string);
+}
+
+pub fn active() -> Option<String> {
+    ACTIVE.lock().unwrap().clone()
+}
+
+/// Sends the [`active`] system prompt ahead of conversations that don't
+/// start with one
+pub struct SystemPromptClient {
+    inner: Box<dyn BotClient>,
+}
+
+impl SystemPromptClient {
+    pub fn new(inner: Box<dyn BotClient>) -> Self {
+        Self { inner }
+    }
+}
+
+impl Clone for SystemPromptClient {
+    fn clone(&self) -> Self {
+        Self { inner: self.inner.clone_box() }
+    }
+}
+
+impl BotClient for SystemPromptClient {
+    fn bots(&mut self) -> BoxPlatformSendFuture<'static, ClientResult<Vec<Bot>>> {
+        self.inner.bots()
+    }
+
+    fn send(
+        &mut self,
+        bot_id: &BotId,
+        messages: &[Message],
+        tools: &[Tool],
+    ) -> BoxPlatformSendStream<'static, ClientResult<MessageContent>> {
+        let prompt = active().filter(|_| !messages.iter().any(|m| matches!(m.from, EntityId::System)));
+        let Some(prompt) = prompt else {
+            return self.inner.send(bot_id, messages, tools);
+        };
+        let mut with_prompt = Vec::with_capacity(messages.len() + 1);
+        with_prompt.push(Message {
+            from: EntityId::System,
+            content: MessageContent { text: prompt, ..Default::default() },
+            ..Default::default()
+        });
+        with_prompt.extend_from_slice(messages);
+        self.inner.send(bot_id, &with_prompt, tools)
+    }
+
+    fn clone_box(&self) -> Box<dyn BotClient> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_preset_for_model() {
+        let none: Vec<String> = vec![];
+        assert_eq!(PresetKind::for_model("qwen3-8b", RegistryCategory::Llm, &none), Some(PresetKind::Assistant));
+        assert_eq!(PresetKind::for_model("qwen2.5-coder-7b", RegistryCategory::Llm, &none), Some(PresetKind::Coding));
+        assert_eq!(PresetKind::for_model("glm-4", RegistryCategory::Llm, &["coding".to_string()]), Some(PresetKind::Coding));
+        assert_eq!(PresetKind::for_model("qwen2-vl-2b", RegistryCategory::Vlm, &none), Some(PresetKind::Describer));
+        assert_eq!(PresetKind::for_model("kokoro", RegistryCategory::Tts, &none), None);
+
+        let presets: SystemPromptPresets = serde_json::from_str(r#"{"coding":"Write Rust."}"#).unwrap();
+        assert_eq!(presets.get(PresetKind::Coding), "Write Rust.");
+        assert_eq!(presets.assistant, default_assistant());
+    }
+}
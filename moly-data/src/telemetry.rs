@@ -0,0 +1,256 @@
+//! Opt-in usage statistics, kept on this computer
+//!
+//! With statistics on, the app counts how often features are used and which
+//! kinds of errors happen ([`crate::MolyError::category`], never the message
+//! itself). The counts are stored per profile in `telemetry.json`, where
+//! Settings shows them and can export or clear them. Nothing is recorded
+//! while statistics are off, and nothing leaves the computer unless an
+//! endpoint is set up, e.g. by a team collecting statistics on its own
+//! server; then the counts are posted to it at most once a day.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, RwLock};
+
+use crate::error::MolyError;
+
+const TELEMETRY_FILENAME: &str = "telemetry.json";
+
+/// Hours between uploads to the configured endpoint
+const UPLOAD_INTERVAL_HOURS: i64 = 24;
+
+/// Statistics settings, stored in preferences
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TelemetrySettings {
+    pub enabled: bool,
+    /// Where to post the counts; empty keeps them local
+    pub endpoint: String,
+}
+
+impl TelemetrySettings {
+    /// Check the endpoint, if one is set
+    pub fn validate(&self) -> Result<(), String> {
+        let endpoint = self.endpoint.trim();
+        if !endpoint.is_empty() && !endpoint.starts_with("http://") && !endpoint.starts_with("https://") {
+            return Err("The endpoint must be an http:// or https:// URL".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Counts since they were last cleared
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct UsageReport {
+    pub since: DateTime<Utc>,
+    #[serde(default)]
+    pub features: BTreeMap<String, u64>,
+    #[serde(default)]
+    pub errors: BTreeMap<String, u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_uploaded: Option<DateTime<Utc>>,
+}
+
+impl Default for UsageReport {
+    fn default() -> Self {
+        Self { since: Utc::now(), features: BTreeMap::new(), errors: BTreeMap::new(), last_uploaded: None }
+    }
+}
+
+impl UsageReport {
+    pub fn is_empty(&self) -> bool {
+        self.features.is_empty() && self.errors.is_empty()
+    }
+
+    /// Most used features first, as lines for display
+    pub fn summary(&self, max_lines: usize) -> Vec<String> {
+        let mut features: Vec<(&String, &u64)> = self.features.iter().collect();
+        features.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+        let mut lines: Vec<String> = features.iter().take(max_lines)
+            .map(|(name, count)| format!("{} — {}", name, count))
+            .collect();
+        if features.len() > max_lines {
+            lines.push(format!("and {} more", features.len() - max_lines));
+        }
+        if !self.errors.is_empty() {
+            let errors: Vec<String> = self.errors.iter().map(|(name, count)| format!("{} {}", count, name)).collect();
+            lines.push(format!("Errors: {}", errors.join(", ")));
+        }
+        lines
+    }
+
+    /// What an upload contains: the counts and the app version, nothing that
+    /// identifies the user or the computer
+    pub fn to_payload(&self) -> serde_json::Value {
+        serde_json::json!({
+            "app_version": env!("CARGO_PKG_VERSION"),
+            "os": std::env::consts::OS,
+            "since": self.since,
+            "features": self.features,
+            "errors": self.errors,
+        })
+    }
+
+    fn upload_due(&self, now: DateTime<Utc>) -> bool {
+        !self.is_empty()
+            && self.last_uploaded.map_or(true, |last| now - last >= Duration::hours(UPLOAD_INTERVAL_HOURS))
+    }
+}
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static ENDPOINT: RwLock<String> = RwLock::new(String::new());
+
+/// The report of the active profile, with the file it belongs to
+static REPORT: Mutex<Option<(PathBuf, UsageReport)>> = Mutex::new(None);
+
+/// Mirror [`crate::Preferences::telemetry`], and upload in the background
+/// if an endpoint is set and an upload is due
+pub fn set_current(settings: &TelemetrySettings) {
+    ENABLED.store(settings.enabled, Ordering::SeqCst);
+    *ENDPOINT.write().unwrap() = settings.endpoint.trim().to_string();
+    #[cfg(not(target_arch = "wasm32"))]
+    if settings.enabled && !settings.endpoint.trim().is_empty() {
+        std::thread::spawn(upload_if_due);
+    }
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::SeqCst)
+}
+
+pub fn report_path() -> PathBuf {
+    crate::profiles::data_dir().join(TELEMETRY_FILENAME)
+}
+
+/// Count a use of `feature`, e.g. `chat.send` or `view.ModelHub`
+pub fn record_feature(feature: &str) {
+    update(|report| *report.features.entry(feature.to_string()).or_default() += 1);
+}
+
+/// Count an error by its category; cancelling isn't an error
+pub fn record_error(error: &MolyError) {
+    if *error == MolyError::Cancelled {
+        return;
+    }
+    update(|report| *report.errors.entry(error.category().to_string()).or_default() += 1);
+}
+
+/// The counts so far
+pub fn report() -> UsageReport {
+    with_report(|report| report.clone())
+}
+
+/// Start counting from zero
+pub fn clear() -> Result<(), String> {
+    with_report(|report| {
+        *report = UsageReport { last_uploaded: report.last_uploaded, ..Default::default() };
+        save(report)
+    })
+}
+
+/// The counts as pretty JSON, for exporting
+pub fn export_json() -> Result<String, String> {
+    serde_json::to_string_pretty(&report()).map_err(|e| format!("Failed to serialize statistics: {}", e))
+}
+
+/// Post the counts to `endpoint` as JSON
+#[cfg(not(target_arch = "wasm32"))]
+pub fn upload(endpoint: &str, report: &UsageReport) -> Result<(), String> {
+    let client = crate::network::blocking_client()
+        .timeout(std::time::Duration::from_secs(15))
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+    let response = client.post(endpoint).json(&report.to_payload()).send()
+        .map_err(|e| format!("Failed to send statistics: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("Failed to send statistics: HTTP {}", response.status().as_u16()));
+    }
+    Ok(())
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn upload_if_due() {
+    let endpoint = ENDPOINT.read().unwrap().clone();
+    let now = Utc::now();
+    let report = report();
+    if endpoint.is_empty() || !is_enabled() || !report.upload_due(now) {
+        return;
+    }
+    match upload(&endpoint, &report) {
+        Ok(()) => {
+            log::info!("Sent usage statistics to {}", endpoint);
+            let _ = with_report(|report| {
+                report.last_uploaded = Some(now);
+                save(report)
+            });
+        }
+        Err(e) => log::warn!("{}", e),
+    }
+}
+
+fn update(change: impl FnOnce(&mut UsageReport)) {
+    if !is_enabled() {
+        return;
+    }
+    if let Err(e) = with_report(|report| {
+        change(report);
+        save(report)
+    }) {
+        log::warn!("Failed to save usage statistics: {}", e);
+    }
+}
+
+/// Run `f` on the active profile's report, loading it if the profile changed
+fn with_report<T>(f: impl FnOnce(&mut UsageReport) -> T) -> T {
+    let path = report_path();
+    let mut current = REPORT.lock().unwrap();
+    if current.as_ref().map_or(true, |(loaded, _)| *loaded != path) {
+        let report = std::fs::read_to_string(&path).ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default();
+        *current = Some((path, report));
+    }
+    f(&mut current.as_mut().unwrap().1)
+}
+
+fn save(report: &UsageReport) -> Result<(), String> {
+    let path = report_path();
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+    }
+    let json = serde_json::to_string_pretty(report).map_err(|e| e.to_string())?;
+    std::fs::write(&path, json).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_summary_and_payload() {
+        let mut report = UsageReport::default();
+        report.features.insert("chat.send".to_string(), 12);
+        report.features.insert("view.ModelHub".to_string(), 3);
+        report.features.insert("voice.train".to_string(), 3);
+        report.errors.insert(MolyError::RuntimeOffline.category().to_string(), 2);
+
+        assert_eq!(report.summary(2), vec![
+            "chat.send — 12".to_string(),
+            "view.ModelHub — 3".to_string(),
+            "and 1 more".to_string(),
+            "Errors: 2 runtime_offline".to_string(),
+        ]);
+        let payload = report.to_payload();
+        assert_eq!(payload["features"]["chat.send"], 12);
+        assert_eq!(payload["errors"]["runtime_offline"], 2);
+
+        let now = Utc::now();
+        assert!(report.upload_due(now));
+        report.last_uploaded = Some(now - Duration::hours(2));
+        assert!(!report.upload_due(now));
+        assert!(TelemetrySettings { enabled: true, endpoint: "stats.example.com".into() }.validate().is_err());
+    }
+}
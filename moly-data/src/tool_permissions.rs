@@ -0,0 +1,69 @@
+//! Approval of tool actions
+//!
+//! Built-in tools run on background threads. Before an action with side
+//! effects — writing a file, running code — the tool calls [`ask`], which
+//! blocks until the user answers in the chat app or the request times out
+//! (denied). The chat app, woken by a UI signal, polls [`next_request`] and
+//! replies with [`answer`].
+
+use std::sync::mpsc::{self, Sender};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// How long a tool waits for an answer before treating it as denied
+pub const APPROVAL_TIMEOUT: Duration = Duration::from_secs(300);
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PermissionRequest {
+    pub id: u64,
+    /// Name of the tool asking, e.g. "Files"
+    pub tool: String,
+    /// The question, e.g. "Write notes.md?"
+    pub title: String,
+    pub detail: String,
+}
+
+struct Pending {
+    request: PermissionRequest,
+    reply: Sender<bool>,
+    /// Handed to the UI by [`next_request`]
+    shown: bool,
+}
+
+static PENDING: Mutex<Vec<Pending>> = Mutex::new(Vec::new());
+static NEXT_ID: Mutex<u64> = Mutex::new(1);
+
+/// Ask the user to allow an action and wait for the answer. Returns false
+/// if denied or not answered within [`APPROVAL_TIMEOUT`].
+pub fn ask(tool: &str, title: impl Into<String>, detail: impl Into<String>) -> bool {
+    let id = {
+        let mut next = NEXT_ID.lock().unwrap();
+        *next += 1;
+        *next - 1
+    };
+    let (reply, answer) = mpsc::channel();
+    let request = PermissionRequest { id, tool: tool.to_string(), title: title.into(), detail: detail.into() };
+    log::info!("{} asks: {}", request.tool, request.title);
+    PENDING.lock().unwrap().push(Pending { request, reply, shown: false });
+    makepad_widgets::SignalToUI::set_ui_signal();
+
+    let allowed = answer.recv_timeout(APPROVAL_TIMEOUT).unwrap_or(false);
+    PENDING.lock().unwrap().retain(|p| p.request.id != id);
+    allowed
+}
+
+/// The oldest request not yet shown, if any
+pub fn next_request() -> Option<PermissionRequest> {
+    let mut pending = PENDING.lock().unwrap();
+    let next = pending.iter_mut().find(|p| !p.shown)?;
+    next.shown = true;
+    Some(next.request.clone())
+}
+
+/// Reply to a request from [`next_request`]
+pub fn answer(id: u64, allowed: bool) {
+    let mut pending = PENDING.lock().unwrap();
+    if let Some(index) = pending.iter().position(|p| p.request.id == id) {
+        let _ = pending.remove(index).reply.send(allowed);
+    }
+}
@@ -0,0 +1,217 @@
+//! Per-chat tool selection
+//!
+//! Every tool offered to a model costs prompt context, so each chat picks
+//! which tools it exposes: the built-in ones and the MCP servers set up in
+//! the MCP app. A chat without a selection of its own gets every available
+//! tool except the opt-in ones. Selections can be saved as named [`Toolset`]s in
+//! preferences and applied to other chats.
+//!
+//! The chat app mirrors the selection of the chat on screen here with
+//! [`set_active`]; [`crate::chat_tools`] offers only those tools with each
+//! request.
+
+use serde::{Deserialize, Deserializer, Serialize};
+use std::sync::{OnceLock, RwLock};
+
+use crate::chats::ChatId;
+use crate::mcp_servers::McpServerConfig;
+
+/// Tools built into the app
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BuiltinTool {
+    /// Interactive UI generation
+    A2ui,
+    /// Files in the folders shared in the MCP app
+    Files,
+    /// Running Python/JavaScript snippets
+    CodeExecution,
+}
+
+impl BuiltinTool {
+    pub const ALL: &'static [BuiltinTool] = &[
+        BuiltinTool::A2ui,
+        BuiltinTool::Files,
+        BuiltinTool::CodeExecution,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            BuiltinTool::A2ui => "Interactive UI (A2UI)",
+            BuiltinTool::Files => "Files in shared folders",
+            BuiltinTool::CodeExecution => "Run code",
+        }
+    }
+
+    /// Whether chats without a selection of their own get the tool. Code
+    /// execution is opt-in per chat.
+    pub fn on_by_default(&self) -> bool {
+        !matches!(self, BuiltinTool::CodeExecution)
+    }
+}
+
+/// One entry of the tool picker
+#[derive(Clone, Debug, PartialEq)]
+pub enum ToolChoice {
+    Builtin(BuiltinTool),
+    /// An MCP server, by name
+    Server(String),
+}
+
+impl ToolChoice {
+    pub fn label(&self) -> String {
+        match self {
+            ToolChoice::Builtin(tool) => tool.label().to_string(),
+            ToolChoice::Server(name) => format!("{} (MCP)", name),
+        }
+    }
+}
+
+/// The tools a chat exposes to the model
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ToolSelection {
+    #[serde(default, deserialize_with = "known_builtins")]
+    pub builtins: Vec<BuiltinTool>,
+    /// MCP server names
+    #[serde(default)]
+    pub mcp_servers: Vec<String>,
+}
+
+impl ToolSelection {
+    /// The built-in tools on by default and every enabled MCP server
+    pub fn all(servers: &[McpServerConfig]) -> Self {
+        Self {
+            builtins: BuiltinTool::ALL.iter().copied().filter(BuiltinTool::on_by_default).collect(),
+            mcp_servers: servers.iter().filter(|s| s.enabled).map(|s| s.name.clone()).collect(),
+        }
+    }
+
+    pub fn has_builtin(&self, tool: BuiltinTool) -> bool {
+        self.builtins.contains(&tool)
+    }
+
+    pub fn has_server(&self, name: &str) -> bool {
+        self.mcp_servers.iter().any(|s| s == name)
+    }
+
+    pub fn contains(&self, choice: &ToolChoice) -> bool {
+        match choice {
+            ToolChoice::Builtin(tool) => self.has_builtin(*tool),
+            ToolChoice::Server(name) => self.has_server(name),
+        }
+    }
+
+    /// The checked entries of a picker
+    pub fn from_choices<'a>(choices: impl IntoIterator<Item = &'a ToolChoice>) -> Self {
+        let mut selection = Self::default();
+        for choice in choices {
+            match choice {
+                ToolChoice::Builtin(tool) => selection.builtins.push(*tool),
+                ToolChoice::Server(name) => selection.mcp_servers.push(name.clone()),
+            }
+        }
+        selection
+    }
+
+    /// Turn a built-in tool on or off
+    pub fn set_builtin(&mut self, tool: BuiltinTool, on: bool) {
+        self.builtins.retain(|t| *t != tool);
+        if on {
+            self.builtins.push(tool);
+        }
+    }
+
+    /// Only the MCP servers that still exist and are enabled
+    pub fn available(&self, servers: &[McpServerConfig]) -> Self {
+        Self {
+            builtins: self.builtins.clone(),
+            mcp_servers: self.mcp_servers.iter()
+                .filter(|name| servers.iter().any(|s| s.enabled && &s.name == *name))
+                .cloned()
+                .collect(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.builtins.len() + self.mcp_servers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Built-in tools saved by a version that had tools since removed, such as
+/// web search, are dropped rather than failing the whole chat
+fn known_builtins<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<BuiltinTool>, D::Error> {
+    let names = Vec::<serde_json::Value>::deserialize(deserializer)?;
+    Ok(names.into_iter().filter_map(|name| serde_json::from_value(name).ok()).collect())
+}
+
+/// The entries of the tool picker: the built-in tools, then the enabled MCP
+/// servers
+pub fn tool_choices(servers: &[McpServerConfig]) -> Vec<ToolChoice> {
+    BuiltinTool::ALL.iter().map(|t| ToolChoice::Builtin(*t))
+        .chain(servers.iter().filter(|s| s.enabled).map(|s| ToolChoice::Server(s.name.clone())))
+        .collect()
+}
+
+/// A named selection, stored in preferences
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Toolset {
+    pub name: String,
+    pub tools: ToolSelection,
+}
+
+// ─── Active selection ─────────────────────────────────────────────────────────
+
+fn active_lock() -> &'static RwLock<Option<(Option<ChatId>, ToolSelection)>> {
+    static ACTIVE: OnceLock<RwLock<Option<(Option<ChatId>, ToolSelection)>>> = OnceLock::new();
+    ACTIVE.get_or_init(|| RwLock::new(None))
+}
+
+/// The tools of the chat on screen; `None` before a chat is shown
+pub fn active() -> Option<ToolSelection> {
+    active_lock().read().unwrap().as_ref().map(|(_, selection)| selection.clone())
+}
+
+/// The chat on screen, which the tool calls of its requests are made for
+pub fn active_chat() -> Option<ChatId> {
+    active_lock().read().unwrap().as_ref().and_then(|(chat_id, _)| *chat_id)
+}
+
+/// Mirror the chat on screen and its tools
+pub fn set_active(chat_id: Option<ChatId>, selection: &ToolSelection) {
+    *active_lock().write().unwrap() = Some((chat_id, selection.clone()));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_selection_from_choices() {
+        let mut disabled = McpServerConfig::new("git", "uvx", vec![]);
+        disabled.enabled = false;
+        let servers = vec![McpServerConfig::new("files", "npx", vec![]), disabled];
+
+        let choices = tool_choices(&servers);
+        assert_eq!(choices.len(), BuiltinTool::ALL.len() + 1);
+        assert_eq!(ToolSelection::all(&servers).mcp_servers, vec!["files"]);
+        assert!(!ToolSelection::all(&servers).has_builtin(BuiltinTool::CodeExecution));
+
+        let selection = ToolSelection::from_choices([&choices[0], &choices[BuiltinTool::ALL.len()]]);
+        assert!(selection.has_builtin(BuiltinTool::A2ui) && selection.has_server("files"));
+        assert_eq!(selection.len(), 2);
+
+        let stale = ToolSelection { builtins: vec![], mcp_servers: vec!["git".into(), "gone".into()] };
+        assert!(stale.available(&servers).is_empty());
+    }
+
+    #[test]
+    fn test_removed_builtins_are_dropped() {
+        let saved: ToolSelection = serde_json::from_str(r#"{"builtins":["a2ui","web_search","files"],"mcp_servers":["git"]}"#).unwrap();
+        assert_eq!(saved.builtins, vec![BuiltinTool::A2ui, BuiltinTool::Files]);
+        assert_eq!(saved.mcp_servers, vec!["git"]);
+    }
+}
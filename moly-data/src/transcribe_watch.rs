@@ -0,0 +1,437 @@
+//! Transcribing audio dropped into a folder
+//!
+//! The ASR panel of the Model Hub can watch a folder
+//! ([`WatchFolderSettings`], stored in preferences). A thread looks at it
+//! every few seconds: audio files without a transcript next to them are
+//! queued once their size stops changing, so files still being copied in
+//! are left alone, then transcribed one at a time with the ASR model the
+//! runtime has loaded. The transcript is written alongside the recording
+//! (`talk.m4a` → `talk.txt`, and `talk.srt` when the model gives timings),
+//! and a desktop notification says it's ready. Files that already have a
+//! transcript are skipped, so watching again after a restart doesn't redo
+//! any work.
+//!
+//! The watcher follows the setting ([`set_current`]) from startup on,
+//! whether or not the panel is open; the panel shows its status list
+//! ([`items`]) and refreshes when [`generation`] changes.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::artifacts;
+use crate::asr_cleanup;
+use crate::model_registry::{ModelRegistry, RegistryCategory};
+use crate::model_state;
+use crate::ominix_api_client::{OminiXApiClient, TranscriptionRequest};
+use crate::platform::{self, AudioFormat};
+use crate::shutdown;
+use crate::store::ModelLoadPhase;
+use crate::transcript::Transcript;
+
+/// How often the folder is looked at
+const POLL_INTERVAL: Duration = Duration::from_secs(3);
+
+/// Finished files kept in the status list
+const MAX_FINISHED: usize = 50;
+
+pub const AUDIO_EXTENSIONS: &[&str] = &["wav", "mp3", "m4a", "flac", "ogg", "aac"];
+
+/// Watched folder, stored in preferences
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WatchFolderSettings {
+    pub enabled: bool,
+    pub folder: String,
+}
+
+impl WatchFolderSettings {
+    /// The folder to watch, when watching is on
+    pub fn active_folder(&self) -> Option<PathBuf> {
+        (self.enabled && !self.folder.trim().is_empty()).then(|| PathBuf::from(self.folder.trim()))
+    }
+}
+
+pub fn is_audio(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| AUDIO_EXTENSIONS.contains(&e.to_ascii_lowercase().as_str()))
+}
+
+/// Where the transcript of `audio` goes: next to it, with `extension`
+pub fn transcript_path(audio: &Path, extension: &str) -> PathBuf {
+    audio.with_extension(extension)
+}
+
+/// Output file names, without extension, for a batch of `files`
+/// transcribed into one folder: the file's stem, or its whole name when
+/// another file of the batch has the same stem (`talk.mp3` and `talk.wav`
+/// → `talk.mp3` and `talk.wav`), so neither overwrites the other
+pub fn batch_stems(files: &[PathBuf]) -> Vec<String> {
+    let stem = |path: &PathBuf| path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+    files
+        .iter()
+        .map(|path| {
+            let shared = files.iter().filter(|other| stem(other).eq_ignore_ascii_case(&stem(path))).count() > 1;
+            match path.file_name() {
+                Some(name) if shared => name.to_string_lossy().to_string(),
+                _ => stem(path),
+            }
+        })
+        .collect()
+}
+
+/// Audio files directly inside `dir` that have no `.txt` transcript yet,
+/// with their sizes, sorted by name
+pub fn untranscribed(dir: &Path) -> Vec<(PathBuf, u64)> {
+    let mut files: Vec<(PathBuf, u64)> = std::fs::read_dir(dir)
+        .map(|entries| entries.flatten().collect::<Vec<_>>())
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|entry| is_audio(&entry.path()) && !transcript_path(&entry.path(), "txt").exists())
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok()?;
+            metadata.is_file().then(|| (entry.path(), metadata.len()))
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+/// Files whose size is the same, and not zero, as the last time they were
+/// seen: they're done being copied. Sizes are remembered for the next look.
+fn settled(sizes: &mut HashMap<PathBuf, u64>, files: &[(PathBuf, u64)]) -> Vec<PathBuf> {
+    let ready = files.iter()
+        .filter(|(path, size)| *size > 0 && sizes.get(path) == Some(size))
+        .map(|(path, _)| path.clone())
+        .collect();
+    *sizes = files.iter().cloned().collect();
+    ready
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum WatchStatus {
+    /// Still being copied in
+    Arriving,
+    Queued,
+    Transcribing,
+    Done,
+    Failed(String),
+}
+
+impl WatchStatus {
+    pub fn label(&self) -> String {
+        match self {
+            WatchStatus::Arriving => "Arriving".to_string(),
+            WatchStatus::Queued => "Queued".to_string(),
+            WatchStatus::Transcribing => "Transcribing…".to_string(),
+            WatchStatus::Done => "Done".to_string(),
+            WatchStatus::Failed(e) => format!("Failed: {}", e),
+        }
+    }
+
+    fn is_finished(&self) -> bool {
+        matches!(self, WatchStatus::Done | WatchStatus::Failed(_))
+    }
+}
+
+/// A file seen in the watched folder
+#[derive(Clone, Debug, PartialEq)]
+pub struct WatchItem {
+    pub path: PathBuf,
+    pub status: WatchStatus,
+}
+
+/// The status list, as the watcher's thread keeps it
+#[derive(Default)]
+struct WatchState {
+    /// Newest first
+    items: Vec<WatchItem>,
+    /// Files are queued but no ASR model is loaded
+    needs_model: bool,
+}
+
+impl WatchState {
+    /// Follow a look at the folder: add new files, drop unfinished ones that
+    /// went away, and queue the ones that `ready` says have arrived
+    fn update(&mut self, files: &[(PathBuf, u64)], ready: &[PathBuf]) {
+        self.items.retain(|item| item.status.is_finished() || files.iter().any(|(path, _)| *path == item.path));
+        for (path, _) in files {
+            match self.items.iter_mut().find(|item| item.path == *path) {
+                Some(item) if item.status == WatchStatus::Arriving && ready.contains(path) => {
+                    item.status = WatchStatus::Queued;
+                }
+                Some(_) => {}
+                None => {
+                    let status = if ready.contains(path) { WatchStatus::Queued } else { WatchStatus::Arriving };
+                    self.items.insert(0, WatchItem { path: path.clone(), status });
+                }
+            }
+        }
+        while self.items.iter().filter(|item| item.status.is_finished()).count() > MAX_FINISHED {
+            let oldest = self.items.iter().rposition(|item| item.status.is_finished()).unwrap();
+            self.items.remove(oldest);
+        }
+    }
+
+    /// The file to transcribe next: the one that arrived first
+    fn next_queued(&self) -> Option<PathBuf> {
+        self.items.iter().rev().find(|item| item.status == WatchStatus::Queued).map(|item| item.path.clone())
+    }
+
+    fn set_status(&mut self, path: &Path, status: WatchStatus) {
+        if let Some(item) = self.items.iter_mut().find(|item| item.path == path) {
+            item.status = status;
+        }
+    }
+
+    /// e.g. `3 transcribed, 1 waiting`
+    fn summary(&self) -> String {
+        let count = |f: fn(&WatchStatus) -> bool| self.items.iter().filter(|item| f(&item.status)).count();
+        let done = count(|s| *s == WatchStatus::Done);
+        let failed = count(|s| matches!(s, WatchStatus::Failed(_)));
+        let waiting = count(|s| !s.is_finished());
+        let mut parts = vec![format!("{} transcribed", done)];
+        if waiting > 0 {
+            parts.push(format!("{} waiting", waiting));
+        }
+        if failed > 0 {
+            parts.push(format!("{} failed", failed));
+        }
+        let mut summary = parts.join(", ");
+        if self.needs_model && waiting > 0 {
+            summary.push_str(" — load an ASR model to transcribe them");
+        }
+        summary
+    }
+}
+
+/// API id of a speech recognition model the runtime has loaded
+pub fn loaded_asr_model(registry: &ModelRegistry) -> Option<String> {
+    registry.by_category(RegistryCategory::Asr)
+        .find(|m| model_state::load_phase(&m.id) == Some(ModelLoadPhase::Loaded))
+        .map(|m| m.runtime.api_model_id.clone())
+}
+
+/// Transcribe `audio` with `model_id`, using the vocabulary set in Settings,
+/// and write the transcript next to it. The `.txt` is written last: it marks
+/// the file as done.
+fn transcribe_file(client: &OminiXApiClient, model_id: &str, audio: &Path) -> Result<(), String> {
+    let is_wav = audio.extension().is_some_and(|e| e.eq_ignore_ascii_case("wav"));
+    let wav = if is_wav {
+        audio.to_path_buf()
+    } else {
+        let tmp = artifacts::unique_path("watch-asr", "wav");
+        platform::convert_audio(audio, &tmp, AudioFormat::Wav16kMono)
+            .map_err(|e| format!("Format conversion failed: {}", e))?;
+        tmp
+    };
+    let settings = asr_cleanup::current();
+    let vocabulary = settings.active_vocabulary();
+    let request = TranscriptionRequest {
+        prompt: asr_cleanup::vocabulary_prompt(vocabulary),
+        ..TranscriptionRequest::with_segments(model_id, wav.to_string_lossy())
+    };
+    let result = client.transcribe(&request).map_err(String::from);
+    if !is_wav {
+        artifacts::release(&wav);
+    }
+    let mut transcript: Transcript = result?;
+    transcript.text = asr_cleanup::apply_vocabulary(&transcript.text, vocabulary);
+    for segment in &mut transcript.segments {
+        segment.text = asr_cleanup::apply_vocabulary(&segment.text, vocabulary);
+    }
+    if transcript.has_timestamps() {
+        std::fs::write(transcript_path(audio, "srt"), transcript.to_srt()).map_err(|e| e.to_string())?;
+    }
+    std::fs::write(transcript_path(audio, "txt"), transcript.text.trim()).map_err(|e| e.to_string())
+}
+
+/// Watches a folder on its own thread and transcribes what arrives in it.
+/// The thread stops when the watcher is dropped, after the file it's on.
+struct FolderWatcher {
+    folder: PathBuf,
+    state: Arc<Mutex<WatchState>>,
+    stop: Arc<AtomicBool>,
+}
+
+impl FolderWatcher {
+    fn start(folder: PathBuf) -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+        let state = Arc::new(Mutex::new(WatchState::default()));
+        let (stopped, shared, dir) = (stop.clone(), state.clone(), folder.clone());
+        std::thread::spawn(move || {
+            let client = OminiXApiClient::localhost(1800);
+            let registry = ModelRegistry::load();
+            let mut sizes = HashMap::new();
+            while !stopped.load(Ordering::Relaxed) {
+                let files = untranscribed(&dir);
+                let ready = settled(&mut sizes, &files);
+                let model = loaded_asr_model(&registry);
+                let next = {
+                    let mut state = shared.lock().unwrap();
+                    let before = (state.items.clone(), state.needs_model);
+                    state.update(&files, &ready);
+                    let next = state.next_queued();
+                    state.needs_model = next.is_some() && model.is_none();
+                    if (state.items.clone(), state.needs_model) != before {
+                        changed();
+                    }
+                    next.zip(model)
+                };
+
+                if let Some((audio, model_id)) = next {
+                    let name = file_name(&audio);
+                    shared.lock().unwrap().set_status(&audio, WatchStatus::Transcribing);
+                    changed();
+                    let result = {
+                        let work = format!("Transcribing {} from the watched folder", name);
+                        let _work = shutdown::track(work, Some(stopped.clone()));
+                        transcribe_file(&client, &model_id, &audio)
+                    };
+                    let status = match result {
+                        Ok(()) => {
+                            log::info!("Transcribed {} from the watched folder", audio.display());
+                            notify("Transcript ready", &format!("{} → {}", name, file_name(&transcript_path(&audio, "txt"))));
+                            WatchStatus::Done
+                        }
+                        Err(e) => {
+                            log::warn!("Transcribing {} from the watched folder failed: {}", audio.display(), e);
+                            notify("Transcription failed", &format!("{}: {}", name, e));
+                            WatchStatus::Failed(e)
+                        }
+                    };
+                    shared.lock().unwrap().set_status(&audio, status);
+                    changed();
+                    // Go straight on with the queue
+                    continue;
+                }
+
+                // Wake up now and then to notice the watcher was dropped
+                for _ in 0..POLL_INTERVAL.as_secs() {
+                    if stopped.load(Ordering::Relaxed) { return; }
+                    std::thread::sleep(Duration::from_secs(1));
+                }
+            }
+        });
+        log::info!("Watching {} for audio to transcribe", folder.display());
+        Self { folder, state, stop }
+    }
+}
+
+impl Drop for FolderWatcher {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+static WATCHER: Mutex<Option<FolderWatcher>> = Mutex::new(None);
+
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+fn changed() {
+    GENERATION.fetch_add(1, Ordering::SeqCst);
+    makepad_widgets::SignalToUI::set_ui_signal();
+}
+
+/// Follow [`crate::Preferences::watch_folder`]: start watching its folder,
+/// switch to another one, or stop
+pub fn set_current(settings: &WatchFolderSettings) {
+    let folder = settings.active_folder();
+    let mut watcher = WATCHER.lock().unwrap();
+    if watcher.as_ref().map(|w| &w.folder) == folder.as_ref() {
+        return;
+    }
+    *watcher = folder.map(FolderWatcher::start);
+    changed();
+}
+
+/// The folder being watched
+pub fn folder() -> Option<PathBuf> {
+    WATCHER.lock().unwrap().as_ref().map(|w| w.folder.clone())
+}
+
+/// Files seen in the watched folder, newest first
+pub fn items() -> Vec<WatchItem> {
+    let watcher = WATCHER.lock().unwrap();
+    watcher.as_ref().map(|w| w.state.lock().unwrap().items.clone()).unwrap_or_default()
+}
+
+/// e.g. `3 transcribed, 1 waiting`; empty when no folder is watched
+pub fn summary() -> String {
+    let watcher = WATCHER.lock().unwrap();
+    watcher.as_ref().map(|w| w.state.lock().unwrap().summary()).unwrap_or_default()
+}
+
+/// Changes whenever the watched folder or its status list does; compare
+/// with a value seen earlier to know when to refresh
+pub fn generation() -> u64 {
+    GENERATION.load(Ordering::SeqCst)
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default()
+}
+
+fn notify(title: &str, body: &str) {
+    if let Err(e) = platform::notify(title, body) {
+        log::debug!("No desktop notification: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_watch_state() {
+        let (a, b) = (PathBuf::from("/in/a.m4a"), PathBuf::from("/in/b.wav"));
+        let mut sizes = HashMap::new();
+        let mut state = WatchState::default();
+
+        let files = [(a.clone(), 100)];
+        let ready = settled(&mut sizes, &files);
+        assert!(ready.is_empty());
+        state.update(&files, &ready);
+        assert_eq!(state.items[0].status, WatchStatus::Arriving);
+
+        let files = [(a.clone(), 100), (b.clone(), 10)];
+        let ready = settled(&mut sizes, &files);
+        assert_eq!(ready, [a.clone()]);
+        state.update(&files, &ready);
+        assert_eq!(state.items.iter().map(|i| i.path.clone()).collect::<Vec<_>>(), [b.clone(), a.clone()]);
+        assert_eq!(state.next_queued(), Some(a.clone()));
+
+        state.set_status(&a, WatchStatus::Done);
+        state.needs_model = true;
+        assert_eq!(state.summary(), "1 transcribed, 1 waiting — load an ASR model to transcribe them");
+
+        // b was deleted before it was transcribed; a's transcript now hides it
+        state.update(&[], &[]);
+        assert_eq!(state.items, [WatchItem { path: a, status: WatchStatus::Done }]);
+        assert_eq!(state.next_queued(), None);
+    }
+
+    #[test]
+    fn test_settings_and_paths() {
+        assert_eq!(WatchFolderSettings { enabled: true, folder: " ".to_string() }.active_folder(), None);
+        assert_eq!(
+            WatchFolderSettings { enabled: true, folder: "/in".to_string() }.active_folder(),
+            Some(PathBuf::from("/in"))
+        );
+        assert_eq!(WatchFolderSettings { enabled: false, folder: "/in".to_string() }.active_folder(), None);
+        assert!(is_audio(Path::new("/in/talk.M4A")));
+        assert!(!is_audio(Path::new("/in/talk.txt")));
+        assert_eq!(transcript_path(Path::new("/in/talk.m4a"), "txt"), PathBuf::from("/in/talk.txt"));
+    }
+
+    #[test]
+    fn test_batch_stems() {
+        let files: Vec<PathBuf> = ["/in/a.mp3", "/in/A.wav", "/in/b.m4a", "/in/notes.v2.flac"].iter().map(PathBuf::from).collect();
+        assert_eq!(batch_stems(&files), ["a.mp3", "A.wav", "b", "notes.v2"]);
+        assert!(batch_stems(&[]).is_empty());
+    }
+}
@@ -0,0 +1,145 @@
+//! Timed transcripts from ASR.
+//!
+//! Parses the `verbose_json` transcription response (OpenAI Whisper format)
+//! into segments and renders them as SRT or WebVTT subtitle files.
+
+use serde::{Deserialize, Serialize};
+
+/// One timed span of a transcript. Times are in seconds.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct TranscriptSegment {
+    pub start: f64,
+    pub end: f64,
+    pub text: String,
+}
+
+/// A transcript with optional segment timings.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Transcript {
+    pub text: String,
+    #[serde(default)]
+    pub segments: Vec<TranscriptSegment>,
+}
+
+impl Transcript {
+    /// Build from a transcription response. Accepts both plain `{"text"}` and
+    /// `verbose_json` (`{"text", "segments": [{"start", "end", "text"}]}`).
+    pub fn from_response(value: &serde_json::Value) -> Result<Self, String> {
+        let text = value.get("text").and_then(|t| t.as_str())
+            .ok_or_else(|| format!("No 'text' field in response: {}", value))?
+            .trim()
+            .to_string();
+
+        let segments = value.get("segments").and_then(|s| s.as_array())
+            .map(|arr| arr.iter().filter_map(|seg| {
+                Some(TranscriptSegment {
+                    start: seg.get("start")?.as_f64()?,
+                    end: seg.get("end")?.as_f64()?,
+                    text: seg.get("text")?.as_str()?.trim().to_string(),
+                })
+            })
+            .filter(|seg| !seg.text.is_empty())
+            .collect())
+            .unwrap_or_default();
+
+        Ok(Self { text, segments })
+    }
+
+    pub fn has_timestamps(&self) -> bool {
+        !self.segments.is_empty()
+    }
+
+    /// Render as SubRip (.srt)
+    pub fn to_srt(&self) -> String {
+        let mut out = String::new();
+        for (i, seg) in self.segments.iter().enumerate() {
+            out.push_str(&format!(
+                "{}\n{} --> {}\n{}\n\n",
+                i + 1,
+                format_timestamp(seg.start, ','),
+                format_timestamp(seg.end, ','),
+                seg.text
+            ));
+        }
+        out
+    }
+
+    /// Render as WebVTT (.vtt)
+    pub fn to_vtt(&self) -> String {
+        let mut out = String::from("WEBVTT\n\n");
+        for seg in &self.segments {
+            out.push_str(&format!(
+                "{} --> {}\n{}\n\n",
+                format_timestamp(seg.start, '.'),
+                format_timestamp(seg.end, '.'),
+                seg.text
+            ));
+        }
+        out
+    }
+}
+
+/// Format seconds as `HH:MM:SS<sep>mmm` (SRT uses ',', VTT uses '.').
+pub fn format_timestamp(seconds: f64, millis_sep: char) -> String {
+    let total_ms = (seconds.max(0.0) * 1000.0).round() as u64;
+    let ms = total_ms % 1000;
+    let s = (total_ms / 1000) % 60;
+    let m = (total_ms / 60_000) % 60;
+    let h = total_ms / 3_600_000;
+    format!("{:02}:{:02}:{:02}{}{:03}", h, m, s, millis_sep, ms)
+}
+
+/// Short `M:SS.s` label for segment lists.
+pub fn format_short_timestamp(seconds: f64) -> String {
+    // Round before splitting, so 59.96 s is 1:00.0 rather than 0:60.0
+    let tenths = (seconds.max(0.0) * 10.0).round() as u64;
+    let (m, s) = (tenths / 600, tenths % 600);
+    format!("{}:{:02}.{}", m, s / 10, s % 10)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Transcript {
+        let value = serde_json::json!({
+            "text": "Hello there. General Kenobi.",
+            "segments": [
+                {"id": 0, "start": 0.0, "end": 1.5, "text": " Hello there."},
+                {"id": 1, "start": 1.5, "end": 3725.25, "text": " General Kenobi."}
+            ]
+        });
+        Transcript::from_response(&value).unwrap()
+    }
+
+    #[test]
+    fn test_srt_export() {
+        assert_eq!(
+            sample().to_srt(),
+            "1\n00:00:00,000 --> 00:00:01,500\nHello there.\n\n\
+             2\n00:00:01,500 --> 01:02:05,250\nGeneral Kenobi.\n\n"
+        );
+    }
+
+    #[test]
+    fn test_vtt_export() {
+        let vtt = sample().to_vtt();
+        assert!(vtt.starts_with("WEBVTT\n\n00:00:00.000 --> 00:00:01.500\nHello there.\n"));
+    }
+
+    #[test]
+    fn test_short_timestamp() {
+        assert_eq!(format_short_timestamp(5.25), "0:05.3");
+        assert_eq!(format_short_timestamp(59.96), "1:00.0");
+        assert_eq!(format_short_timestamp(119.94), "1:59.9");
+        assert_eq!(format_short_timestamp(3725.25), "62:05.3");
+        assert_eq!(format_short_timestamp(-1.0), "0:00.0");
+    }
+
+    #[test]
+    fn test_plain_text_response_has_no_timestamps() {
+        let t = Transcript::from_response(&serde_json::json!({"text": "hi"})).unwrap();
+        assert!(!t.has_timestamps());
+        assert_eq!(t.text, "hi");
+    }
+}
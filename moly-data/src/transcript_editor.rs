@@ -0,0 +1,193 @@
+//! Correcting a timed transcript
+//!
+//! The Model Hub's ASR panel opens a [`TranscriptEditor`] on a transcript
+//! with segments: each segment's text can be rewritten while the audio plays
+//! from it, and edits can be reverted one by one. Corrections are saved next
+//! to the audio file ([`corrections_path`]) so they can be picked up again,
+//! exported as text or subtitles, or cut into a voice training dataset
+//! ([`TranscriptEditor::voice_dataset`]) whose clip transcripts then match
+//! what is actually said.
+
+use std::path::{Path, PathBuf};
+
+use crate::audio::Wav;
+use crate::transcript::{Transcript, TranscriptSegment};
+use crate::voice_dataset::{VoiceDataset, VoiceSample};
+
+#[derive(Clone, Debug, Default)]
+pub struct TranscriptEditor {
+    original: Vec<TranscriptSegment>,
+    segments: Vec<TranscriptSegment>,
+    /// Segment being edited
+    current: usize,
+}
+
+impl TranscriptEditor {
+    pub fn new(transcript: &Transcript) -> Self {
+        Self { original: transcript.segments.clone(), segments: transcript.segments.clone(), current: 0 }
+    }
+
+    pub fn segments(&self) -> &[TranscriptSegment] {
+        &self.segments
+    }
+
+    pub fn current(&self) -> usize {
+        self.current
+    }
+
+    pub fn current_segment(&self) -> Option<&TranscriptSegment> {
+        self.segments.get(self.current)
+    }
+
+    pub fn select(&mut self, index: usize) {
+        if index < self.segments.len() {
+            self.current = index;
+        }
+    }
+
+    /// Move to the next or previous segment, stopping at the ends
+    pub fn step(&mut self, forward: bool) {
+        let index = if forward { self.current + 1 } else { self.current.saturating_sub(1) };
+        self.select(index);
+    }
+
+    /// Replace a segment's text; its times stay
+    pub fn set_text(&mut self, index: usize, text: &str) {
+        if let Some(segment) = self.segments.get_mut(index) {
+            segment.text = text.to_string();
+        }
+    }
+
+    pub fn revert(&mut self, index: usize) {
+        if let (Some(segment), Some(original)) = (self.segments.get_mut(index), self.original.get(index)) {
+            segment.text = original.text.clone();
+        }
+    }
+
+    pub fn is_edited(&self, index: usize) -> bool {
+        self.segments.get(index).map(|s| &s.text) != self.original.get(index).map(|s| &s.text)
+    }
+
+    pub fn edited_count(&self) -> usize {
+        (0..self.segments.len()).filter(|&i| self.is_edited(i)).count()
+    }
+
+    /// The corrected transcript; segments left empty are dropped
+    pub fn transcript(&self) -> Transcript {
+        let segments: Vec<TranscriptSegment> = self
+            .segments
+            .iter()
+            .map(|s| TranscriptSegment { text: s.text.trim().to_string(), ..s.clone() })
+            .filter(|s| !s.text.is_empty())
+            .collect();
+        let text = segments.iter().map(|s| s.text.as_str()).collect::<Vec<_>>().join(" ");
+        Transcript { text, segments }
+    }
+
+    /// One segment per line, as plain text
+    pub fn to_text(&self) -> String {
+        self.transcript().segments.iter().map(|s| format!("{}\n", s.text)).collect()
+    }
+
+    /// Save the corrected transcript as JSON
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(&self.transcript()).map_err(|e| e.to_string())?;
+        std::fs::write(path, json).map_err(|e| format!("Cannot write {}: {}", path.display(), e))?;
+        log::info!("Saved {} corrected segments to {}", self.edited_count(), path.display());
+        Ok(())
+    }
+
+    /// A voice training dataset named `name`: one clip per segment, cut out
+    /// of the PCM WAV `wav`, with the corrected text as its transcript. The
+    /// clips are written to `clips_dir`; [`VoiceDataset::save`] copies them
+    /// into the dataset's folder.
+    pub fn voice_dataset(&self, name: &str, wav: &Path, clips_dir: &Path) -> Result<VoiceDataset, String> {
+        let bytes = std::fs::read(wav).map_err(|e| format!("Cannot read {}: {}", wav.display(), e))?;
+        let audio = Wav::parse(&bytes)?;
+        std::fs::create_dir_all(clips_dir).map_err(|e| format!("Cannot create {}: {}", clips_dir.display(), e))?;
+        let mut samples = Vec::new();
+        for (index, segment) in self.transcript().segments.iter().enumerate() {
+            let clip = clips_dir.join(format!("segment-{:03}.wav", index + 1));
+            std::fs::write(&clip, audio.slice(segment.start, segment.end)?)
+                .map_err(|e| format!("Cannot write {}: {}", clip.display(), e))?;
+            samples.push(VoiceSample {
+                audio_path: clip.to_string_lossy().to_string(),
+                transcript: segment.text.clone(),
+                duration_secs: Some(segment.end - segment.start),
+            });
+        }
+        Ok(VoiceDataset { name: name.trim().to_string(), samples })
+    }
+}
+
+/// Where corrections to the transcript of `audio` are kept:
+/// `talk.wav` → `talk.transcript.json`
+pub fn corrections_path(audio: &Path) -> PathBuf {
+    audio.with_extension("transcript.json")
+}
+
+/// Corrections saved earlier for `audio`, if any
+pub fn load_corrections(audio: &Path) -> Option<Transcript> {
+    let json = std::fs::read_to_string(corrections_path(audio)).ok()?;
+    serde_json::from_str(&json).map_err(|e| log::warn!("Ignoring corrections for {}: {}", audio.display(), e)).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transcript() -> Transcript {
+        let segment = |start: f64, end: f64, text: &str| TranscriptSegment { start, end, text: text.to_string() };
+        Transcript {
+            text: "Helo world. This is a test. Um.".to_string(),
+            segments: vec![segment(0.0, 1.0, "Helo world."), segment(1.0, 2.5, "This is a test."), segment(2.5, 3.0, "Um.")],
+        }
+    }
+
+    #[test]
+    fn test_edit_and_revert() {
+        let mut editor = TranscriptEditor::new(&transcript());
+        editor.step(true);
+        editor.step(true);
+        editor.step(true);
+        assert_eq!(editor.current(), 2);
+        editor.set_text(0, "Hello world.");
+        editor.set_text(2, "  ");
+        editor.set_text(1, "This is a tests.");
+        editor.revert(1);
+        assert_eq!(editor.edited_count(), 2);
+        assert!(editor.is_edited(0) && !editor.is_edited(1));
+
+        let corrected = editor.transcript();
+        assert_eq!(corrected.text, "Hello world. This is a test.");
+        assert_eq!(corrected.segments.len(), 2);
+        assert_eq!(editor.to_text(), "Hello world.\nThis is a test.\n");
+        assert_eq!(corrections_path(Path::new("/notes/talk.wav")), Path::new("/notes/talk.transcript.json"));
+    }
+
+    #[test]
+    fn test_save_and_dataset() {
+        let dir = std::env::temp_dir().join(format!("moly-transcript-editor-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        // 3 s of 8 kHz mono 16-bit silence
+        let mut fmt = [0u8; 16];
+        fmt[..4].copy_from_slice(&[1, 0, 1, 0]);
+        fmt[4..8].copy_from_slice(&8000u32.to_le_bytes());
+        fmt[8..12].copy_from_slice(&16000u32.to_le_bytes());
+        fmt[12..16].copy_from_slice(&[2, 0, 16, 0]);
+        let wav = dir.join("talk.wav");
+        std::fs::write(&wav, crate::audio::build_wav(&fmt, &vec![0u8; 48000])).unwrap();
+
+        let mut editor = TranscriptEditor::new(&transcript());
+        editor.set_text(0, "Hello world.");
+        editor.save(&corrections_path(&wav)).unwrap();
+        assert_eq!(load_corrections(&wav).unwrap().segments[0].text, "Hello world.");
+
+        let dataset = editor.voice_dataset(" Narrator ", &wav, &dir.join("clips")).unwrap();
+        assert_eq!(dataset.name, "Narrator");
+        assert_eq!(dataset.samples.len(), 3);
+        assert_eq!(dataset.samples[1].transcript, "This is a test.");
+        assert_eq!(crate::voice_dataset::audio_duration_secs(Path::new(&dataset.samples[1].audio_path)), Some(1.5));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,115 @@
+//! Download speed and ETA
+//!
+//! The download threads feed [`TransferRate`] the running byte count as data
+//! arrives. Speed is averaged over the last few seconds rather than since the
+//! start, so it follows the connection when it speeds up or stalls, and the
+//! ETA follows the speed.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// How far back the rolling speed looks
+pub const RATE_WINDOW: Duration = Duration::from_secs(5);
+
+/// Samples closer together than this are merged, bounding the sample count
+const MIN_SAMPLE_GAP: Duration = Duration::from_millis(100);
+
+/// Rolling transfer speed over [`RATE_WINDOW`]
+#[derive(Clone, Debug, Default)]
+pub struct TransferRate {
+    /// (time, total bytes transferred so far), oldest first
+    samples: VecDeque<(Instant, u64)>,
+}
+
+impl TransferRate {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the running byte count at `now`
+    pub fn record(&mut self, now: Instant, total_bytes: u64) {
+        match self.samples.back_mut() {
+            Some(last) if now.duration_since(last.0) < MIN_SAMPLE_GAP => last.1 = total_bytes,
+            _ => self.samples.push_back((now, total_bytes)),
+        }
+        // Keep one sample at or beyond the window edge as the baseline
+        while self.samples.len() > 2 && now.duration_since(self.samples[1].0) >= RATE_WINDOW {
+            self.samples.pop_front();
+        }
+    }
+
+    /// Bytes per second over the window, 0 until two samples are apart
+    pub fn bytes_per_sec(&self) -> u64 {
+        let (Some(first), Some(last)) = (self.samples.front(), self.samples.back()) else { return 0 };
+        let elapsed = last.0.duration_since(first.0).as_secs_f64();
+        if elapsed <= 0.0 { return 0; }
+        (last.1.saturating_sub(first.1) as f64 / elapsed) as u64
+    }
+
+    /// Seconds until `remaining_bytes` arrive at the current speed
+    pub fn eta_seconds(&self, remaining_bytes: u64) -> Option<u64> {
+        match self.bytes_per_sec() {
+            0 => None,
+            speed => Some(remaining_bytes.div_ceil(speed)),
+        }
+    }
+
+    /// Forget all samples, e.g. when a download restarts
+    pub fn reset(&mut self) {
+        self.samples.clear();
+    }
+}
+
+/// "45.2 MB/s"
+pub fn format_speed(bytes_per_sec: u64) -> String {
+    const KB: f64 = 1024.0;
+    const MB: f64 = KB * 1024.0;
+    const GB: f64 = MB * 1024.0;
+    let b = bytes_per_sec as f64;
+    if b >= GB {
+        format!("{:.1} GB/s", b / GB)
+    } else if b >= MB {
+        format!("{:.1} MB/s", b / MB)
+    } else if b >= KB {
+        format!("{:.0} KB/s", b / KB)
+    } else {
+        format!("{} B/s", bytes_per_sec)
+    }
+}
+
+/// "3m 12s", "1h 05m", "42s"
+pub fn format_eta(seconds: u64) -> String {
+    let (h, m, s) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    if h > 0 {
+        format!("{}h {:02}m", h, m)
+    } else if m > 0 {
+        format!("{}m {}s", m, s)
+    } else {
+        format!("{}s", s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rolling_rate() {
+        let start = Instant::now();
+        let mut rate = TransferRate::new();
+        rate.record(start, 0);
+        assert_eq!(rate.bytes_per_sec(), 0);
+        assert_eq!(rate.eta_seconds(100), None);
+
+        // 1 MB/s for 10 s, then 4 MB/s: the window only sees the new speed
+        for s in 1..=10 { rate.record(start + Duration::from_secs(s), s * 1_048_576); }
+        assert_eq!(rate.bytes_per_sec(), 1_048_576);
+        for s in 11..=20 { rate.record(start + Duration::from_secs(s), (10 + (s - 10) * 4) * 1_048_576); }
+        assert_eq!(rate.bytes_per_sec(), 4 * 1_048_576);
+        assert_eq!(rate.eta_seconds(10 * 1_048_576), Some(3));
+
+        assert_eq!(format_speed(47_395_635), "45.2 MB/s");
+        assert_eq!(format_eta(192), "3m 12s");
+        assert_eq!(format_eta(3900), "1h 05m");
+    }
+}
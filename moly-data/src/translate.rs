@@ -0,0 +1,187 @@
+//! Translating text with a local LLM
+//!
+//! The chat's Translate tool sends a message, or the text in the prompt, to
+//! whichever LLM the runtime has loaded. The source language is guessed from
+//! the text ([`detect`]): by script for Chinese, Japanese and Korean, by
+//! common words for the European languages. The suggested target is English
+//! for anything else and Chinese for English ([`default_target`]), which
+//! covers the zh/en pair most users switch between.
+
+use serde::{Deserialize, Serialize};
+
+use crate::chat_template::{self, ChatTemplateOverrides};
+use crate::model_registry::{ModelRegistry, RegistryCategory, RegistryModel};
+use crate::model_state;
+use crate::ominix_api_client::{ChatCompletionRequest, ChatMessage, OminiXApiClient};
+use crate::store::ModelLoadPhase;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Language {
+    English,
+    Chinese,
+    Japanese,
+    Korean,
+    French,
+    German,
+    Spanish,
+}
+
+impl Language {
+    pub const ALL: [Language; 7] = [
+        Language::English,
+        Language::Chinese,
+        Language::Japanese,
+        Language::Korean,
+        Language::French,
+        Language::German,
+        Language::Spanish,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::Chinese => "Chinese",
+            Language::Japanese => "Japanese",
+            Language::Korean => "Korean",
+            Language::French => "French",
+            Language::German => "German",
+            Language::Spanish => "Spanish",
+        }
+    }
+
+    /// Words frequent enough in the language to tell it apart from the
+    /// others written in Latin script
+    fn common_words(self) -> &'static [&'static str] {
+        match self {
+            Language::English => &["the", "and", "is", "of", "to", "you", "that", "with"],
+            Language::French => &["le", "la", "les", "et", "est", "des", "une", "vous", "pas"],
+            Language::German => &["der", "die", "das", "und", "ist", "nicht", "ich", "mit", "ein"],
+            Language::Spanish => &["el", "los", "las", "es", "que", "por", "una", "con", "para"],
+            Language::Chinese | Language::Japanese | Language::Korean => &[],
+        }
+    }
+}
+
+/// The language `text` is most likely written in; English when unsure
+pub fn detect(text: &str) -> Language {
+    let (mut han, mut kana, mut hangul, mut letters) = (0usize, 0usize, 0usize, 0usize);
+    for c in text.chars() {
+        match c as u32 {
+            0x3040..=0x30FF => kana += 1,
+            0xAC00..=0xD7AF | 0x1100..=0x11FF => hangul += 1,
+            0x4E00..=0x9FFF | 0x3400..=0x4DBF => han += 1,
+            _ if c.is_alphabetic() => letters += 1,
+            _ => {}
+        }
+    }
+    // Japanese mixes kana into its kanji; a CJK character carries about as
+    // much as a short word, so weigh them against the Latin letters
+    let cjk = han + kana + hangul;
+    if cjk * 3 >= letters && cjk > 0 {
+        return if kana > 0 {
+            Language::Japanese
+        } else if hangul > han {
+            Language::Korean
+        } else {
+            Language::Chinese
+        };
+    }
+    let words: Vec<String> = text
+        .split(|c: char| !c.is_alphabetic())
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+    [Language::English, Language::French, Language::German, Language::Spanish]
+        .into_iter()
+        .map(|language| {
+            let hits = words.iter().filter(|w| language.common_words().contains(&w.as_str())).count();
+            (language, hits)
+        })
+        .fold((Language::English, 0), |best, (language, hits)| if hits > best.1 { (language, hits) } else { best })
+        .0
+}
+
+/// The language to offer first for text in `source`
+pub fn default_target(source: Language) -> Language {
+    if source == Language::English { Language::Chinese } else { Language::English }
+}
+
+/// System prompt asking for a translation into `target`
+pub fn instruction(target: Language) -> String {
+    format!(
+        "You are a translator. Translate the user's text into {}. Keep its meaning, tone and formatting, \
+        including Markdown, and leave code, names and URLs as they are. \
+        Reply with the translation only, without notes or explanations.",
+        target.name()
+    )
+}
+
+/// Chat request asking `model` to translate `text` into `target`
+pub fn request(model: impl Into<String>, text: &str, target: Language) -> ChatCompletionRequest {
+    ChatCompletionRequest {
+        model: model.into(),
+        messages: vec![ChatMessage::system(instruction(target)), ChatMessage::user(text.trim())],
+        max_tokens: None,
+    }
+}
+
+/// The translation from a model's answer: without a `<think>` block or a
+/// `Translation:` label
+pub fn clean_answer(answer: &str) -> Result<String, String> {
+    let mut text = answer;
+    if let Some(end) = text.find("</think>") {
+        text = &text[end + "</think>".len()..];
+    }
+    let mut text = text.trim();
+    let label = "Translation:";
+    if text.len() >= label.len() && text.is_char_boundary(label.len()) && text[..label.len()].eq_ignore_ascii_case(label) {
+        text = text[label.len()..].trim_start();
+    }
+    if text.is_empty() {
+        return Err("The model returned an empty translation.".to_string());
+    }
+    Ok(text.to_string())
+}
+
+/// An LLM (or, failing that, a VLM) the runtime has loaded
+pub fn loaded_llm(registry: &ModelRegistry) -> Option<RegistryModel> {
+    [RegistryCategory::Llm, RegistryCategory::Vlm].into_iter().find_map(|category| {
+        registry.by_category(category)
+            .find(|m| model_state::load_phase(&m.id) == Some(ModelLoadPhase::Loaded))
+            .cloned()
+    })
+}
+
+/// Translate `text` into `target` with `model`. Blocks; call it off the UI
+/// thread.
+pub fn translate(model: &RegistryModel, text: &str, target: Language) -> Result<String, String> {
+    let template = chat_template::template_for(model, &ChatTemplateOverrides::load());
+    let request = request(model.runtime.api_model_id.clone(), text, target);
+    chat_template::complete(&OminiXApiClient::localhost(120), &request.model, template, request.messages)
+        .map_err(|e| format!("Translating with {} failed: {}", model.name, e))
+        .and_then(|answer| clean_answer(&answer))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_and_clean() {
+        assert_eq!(detect("今天的天气怎么样？我想去公园。"), Language::Chinese);
+        assert_eq!(detect("用 MLX 加载模型"), Language::Chinese);
+        assert_eq!(detect("モデルを読み込んでください"), Language::Japanese);
+        assert_eq!(detect("모델을 불러오세요"), Language::Korean);
+        assert_eq!(detect("The model is loaded and ready to use."), Language::English);
+        assert_eq!(detect("Le modèle est chargé et prêt pour les tests."), Language::French);
+        assert_eq!(detect("Das Modell ist nicht geladen und ich warte."), Language::German);
+        assert_eq!(detect("12345"), Language::English);
+        assert_eq!(default_target(Language::Chinese), Language::English);
+        assert_eq!(default_target(Language::English), Language::Chinese);
+
+        assert_eq!(clean_answer("<think>Simple.</think>\nTranslation: 模型已加载。").unwrap(), "模型已加载。");
+        assert_eq!(clean_answer("\"Quoted\" stays").unwrap(), "\"Quoted\" stays");
+        assert!(clean_answer("<think>hmm</think>  ").is_err());
+        assert_eq!(request("qwen3-4b", " hi ", Language::Chinese).messages.len(), 2);
+    }
+}
@@ -0,0 +1,169 @@
+//! Long-form TTS.
+//!
+//! `/v1/audio/speech` times out on long inputs, so long text is split into
+//! sentence-aligned chunks, synthesized one request at a time, and the WAV
+//! results are joined. The audiobook builder reuses this per chapter.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::audio::concat_wavs;
+use crate::ominix_api_client::{OminiXApiClient, SpeechRequest};
+
+/// Default chunk size. Keeps a single request well under the server timeout.
+pub const DEFAULT_TTS_CHUNK_CHARS: usize = 400;
+
+const SENTENCE_ENDS: &[char] = &['.', '!', '?', '。', '！', '？', '；', ';'];
+const CLAUSE_BREAKS: &[char] = &[',', '，', '、', ':', '：'];
+
+/// Split text into chunks of at most `max_chars` characters, breaking at
+/// sentence ends where possible, then clause breaks, then whitespace.
+pub fn split_into_chunks(text: &str, max_chars: usize) -> Vec<String> {
+    let max_chars = max_chars.max(1);
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+
+    for sentence in split_keep_delims(text, SENTENCE_ENDS) {
+        // Paragraph breaks always end a chunk
+        for (i, para_piece) in sentence.split("\n\n").enumerate() {
+            if i > 0 && !current.trim().is_empty() {
+                chunks.push(current.trim().to_string());
+                current.clear();
+            }
+            for piece in split_oversized(para_piece, max_chars) {
+                if char_len(&current) + char_len(&piece) > max_chars && !current.trim().is_empty() {
+                    chunks.push(current.trim().to_string());
+                    current.clear();
+                }
+                current.push_str(&piece);
+            }
+        }
+    }
+    if !current.trim().is_empty() {
+        chunks.push(current.trim().to_string());
+    }
+    chunks
+}
+
+/// Split text into sentences for reading one at a time. Line breaks end a
+/// sentence too, and sentences over `max_chars` are cut like chunks are.
+pub fn split_sentences(text: &str, max_chars: usize) -> Vec<String> {
+    let max_chars = max_chars.max(1);
+    text.lines()
+        .flat_map(|line| split_keep_delims(line, SENTENCE_ENDS))
+        .flat_map(|sentence| split_oversized(sentence, max_chars))
+        .map(|sentence| sentence.trim().to_string())
+        .filter(|sentence| sentence.chars().any(char::is_alphanumeric))
+        .collect()
+}
+
+fn char_len(s: &str) -> usize {
+    s.chars().count()
+}
+
+/// Split after each delimiter, keeping the delimiter on the left piece.
+fn split_keep_delims<'a>(text: &'a str, delims: &[char]) -> Vec<&'a str> {
+    let mut out = Vec::new();
+    let mut start = 0;
+    for (i, c) in text.char_indices() {
+        if delims.contains(&c) {
+            let end = i + c.len_utf8();
+            out.push(&text[start..end]);
+            start = end;
+        }
+    }
+    if start < text.len() {
+        out.push(&text[start..]);
+    }
+    out
+}
+
+/// Break a single sentence that exceeds `max_chars` at clause breaks or spaces.
+fn split_oversized(sentence: &str, max_chars: usize) -> Vec<String> {
+    if char_len(sentence) <= max_chars {
+        return vec![sentence.to_string()];
+    }
+    let mut out = Vec::new();
+    for clause in split_keep_delims(sentence, CLAUSE_BREAKS) {
+        if char_len(clause) <= max_chars {
+            out.push(clause.to_string());
+            continue;
+        }
+        // Fall back to word boundaries, then hard character cuts (CJK has no spaces)
+        let mut piece = String::new();
+        for word in clause.split_inclusive(' ') {
+            if char_len(&piece) + char_len(word) > max_chars && !piece.is_empty() {
+                out.push(std::mem::take(&mut piece));
+            }
+            if char_len(word) > max_chars {
+                let chars: Vec<char> = word.chars().collect();
+                for hard in chars.chunks(max_chars) {
+                    out.push(hard.iter().collect());
+                }
+            } else {
+                piece.push_str(word);
+            }
+        }
+        if !piece.is_empty() {
+            out.push(piece);
+        }
+    }
+    out
+}
+
+/// Synthesize `chunks` one by one and join the audio (blocking — call from a thread).
+///
+/// `on_chunk(done, total)` is called after each chunk. Returns `Ok(None)` if
+/// cancelled between chunks.
+pub fn synthesize_chunks(
+    client: &OminiXApiClient,
+    model_id: &str,
+    voice: &str,
+    chunks: &[String],
+    cancel: &AtomicBool,
+    mut on_chunk: impl FnMut(usize, usize),
+) -> Result<Option<Vec<u8>>, String> {
+    let mut parts = Vec::with_capacity(chunks.len());
+    for (i, chunk) in chunks.iter().enumerate() {
+        if cancel.load(Ordering::SeqCst) {
+            return Ok(None);
+        }
+        parts.push(synthesize_one(client, model_id, voice, chunk)
+            .map_err(|e| format!("Chunk {}/{}: {}", i + 1, chunks.len(), e))?);
+        on_chunk(i + 1, chunks.len());
+    }
+    concat_wavs(&parts).map(Some)
+}
+
+/// One `/v1/audio/speech` request returning WAV bytes.
+pub fn synthesize_one(
+    client: &OminiXApiClient,
+    model_id: &str,
+    voice: &str,
+    text: &str,
+) -> Result<Vec<u8>, String> {
+    client.speech(&SpeechRequest::wav(model_id, voice, text)).map_err(String::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chunks_respect_sentences_and_limit() {
+        let text = "First sentence. Second one is here! Third? Fourth sentence goes on.";
+        let chunks = split_into_chunks(text, 40);
+        assert_eq!(chunks, vec![
+            "First sentence. Second one is here!",
+            "Third? Fourth sentence goes on.",
+        ]);
+        assert!(chunks.iter().all(|c| c.chars().count() <= 40));
+    }
+
+    #[test]
+    fn test_oversized_cjk_sentence_is_cut() {
+        let text = "这是一个没有标点的很长很长的中文句子需要被切开";
+        let chunks = split_into_chunks(text, 10);
+        assert!(chunks.len() > 1);
+        assert_eq!(chunks.concat(), text);
+    }
+}
@@ -0,0 +1,109 @@
+//! The format the chat's text to speech produces
+//!
+//! Settings picks the [`OutputFormat`] and sample rate speech is generated
+//! in, and the folder Save As opens in ([`TtsOutputSettings`]). The runtime
+//! encodes the audio itself: the chat passes both through
+//! [`SpeechRequest`]'s `response_format` and `sample_rate`. A reply spoken
+//! with several voices is fetched as WAV at that rate so the parts can be
+//! joined, then converted.
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::RwLock;
+
+use crate::ominix_api_client::SpeechRequest;
+use crate::voice_presets::OutputFormat;
+
+/// Sample rates offered besides the model's own
+pub const SAMPLE_RATES: [u32; 6] = [8000, 16000, 22050, 24000, 44100, 48000];
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TtsOutputSettings {
+    pub format: OutputFormat,
+    /// Hz; 0 keeps the model's own rate
+    pub sample_rate: u32,
+    /// Folder Save As opens in; empty for the system's choice
+    pub directory: String,
+}
+
+impl TtsOutputSettings {
+    /// A request for `text` in the chosen format and rate
+    pub fn request(&self, model: &str, voice: &str, text: &str) -> SpeechRequest {
+        SpeechRequest {
+            sample_rate: Some(self.sample_rate).filter(|r| *r != 0),
+            ..SpeechRequest::new(model, voice, text, self.format.extension())
+        }
+    }
+
+    /// A request for WAV at the chosen rate, for parts joined afterwards
+    pub fn wav_request(&self, model: &str, voice: &str, text: &str) -> SpeechRequest {
+        SpeechRequest { sample_rate: Some(self.sample_rate).filter(|r| *r != 0), ..SpeechRequest::wav(model, voice, text) }
+    }
+
+    pub fn output_dir(&self) -> Option<PathBuf> {
+        let dir = self.directory.trim();
+        (!dir.is_empty()).then(|| PathBuf::from(dir))
+    }
+
+    /// "MP3, 24 kHz"
+    pub fn summary(&self) -> String {
+        match self.sample_rate {
+            0 => self.format.label().to_string(),
+            rate => format!("{}, {} kHz", self.format.label(), rate as f32 / 1000.0),
+        }
+    }
+}
+
+/// Read Settings' sample rate; empty keeps the model's own
+pub fn parse_sample_rate(text: &str) -> Result<u32, String> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Ok(0);
+    }
+    let rate: u32 = text.trim_end_matches("Hz").trim().parse().map_err(|_| format!("\"{}\" isn't a sample rate", text))?;
+    if !SAMPLE_RATES.contains(&rate) {
+        let rates: Vec<String> = SAMPLE_RATES.iter().map(u32::to_string).collect();
+        return Err(format!("Use one of {} Hz, or leave it empty for the model's own", rates.join(", ")));
+    }
+    Ok(rate)
+}
+
+static CURRENT: RwLock<Option<TtsOutputSettings>> = RwLock::new(None);
+
+/// Mirror [`crate::Preferences::tts_output`]
+pub fn set_current(settings: &TtsOutputSettings) {
+    *CURRENT.write().unwrap() = Some(settings.clone());
+}
+
+pub fn current() -> TtsOutputSettings {
+    CURRENT.read().unwrap().clone().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_requests() {
+        let settings = TtsOutputSettings { format: OutputFormat::Mp3, sample_rate: 24000, directory: " ".to_string() };
+        let request = serde_json::to_value(settings.request("qwen3-tts", "vivian", "Hello.")).unwrap();
+        assert_eq!(request["response_format"], "mp3");
+        assert_eq!(request["sample_rate"], 24000);
+        assert_eq!(serde_json::to_value(settings.wav_request("qwen3-tts", "vivian", "Hello.")).unwrap()["response_format"], "wav");
+        assert_eq!(settings.summary(), "MP3, 24 kHz");
+        assert_eq!(settings.output_dir(), None);
+
+        let request = serde_json::to_value(TtsOutputSettings::default().request("qwen3-tts", "vivian", "Hello.")).unwrap();
+        assert_eq!(request["response_format"], "wav");
+        assert!(request.get("sample_rate").is_none());
+    }
+
+    #[test]
+    fn test_parse_sample_rate() {
+        assert_eq!(parse_sample_rate(""), Ok(0));
+        assert_eq!(parse_sample_rate("44100 Hz"), Ok(44100));
+        assert!(parse_sample_rate("44.1k").is_err());
+        assert!(parse_sample_rate("12345").unwrap_err().contains("48000"));
+    }
+}
@@ -0,0 +1,148 @@
+//! Picking the TTS voice by language
+//!
+//! A voice speaks its own language well and others with an accent, so
+//! Settings maps each language to a preferred voice ([`VoiceMap`]). With the
+//! mapping turned on, the chat's TTS mode and Read Aloud guess the language
+//! of each sentence ([`crate::translate::detect`]) and speak it with that
+//! language's voice ([`VoiceMap::runs`]): a reply mixing Chinese and English
+//! switches voice sentence by sentence. Languages without a voice use the
+//! one picked in the chat.
+
+use serde::{Deserialize, Serialize};
+use std::sync::RwLock;
+
+use crate::translate::{self, Language};
+use crate::tts_longform::{split_sentences, DEFAULT_TTS_CHUNK_CHARS};
+
+/// The voice preferred for a language
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LanguageVoice {
+    pub language: Language,
+    pub voice: String,
+}
+
+/// Voices by language, stored in preferences
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VoiceMap {
+    /// Pick the voice by language
+    pub auto: bool,
+    pub voices: Vec<LanguageVoice>,
+}
+
+impl Default for VoiceMap {
+    fn default() -> Self {
+        Self {
+            auto: false,
+            voices: vec![
+                LanguageVoice { language: Language::Chinese, voice: "chinese_woman".to_string() },
+                LanguageVoice { language: Language::English, voice: "vivian".to_string() },
+            ],
+        }
+    }
+}
+
+impl VoiceMap {
+    pub fn voice_for(&self, language: Language) -> Option<&str> {
+        self.voices.iter().find(|v| v.language == language).map(|v| v.voice.as_str())
+    }
+
+    /// The voice for `text`; `fallback` when the mapping is off or has no
+    /// voice for its language
+    pub fn pick(&self, text: &str, fallback: &str) -> String {
+        if !self.auto {
+            return fallback.to_string();
+        }
+        self.voice_for(translate::detect(text)).unwrap_or(fallback).to_string()
+    }
+
+    /// `text` as `(voice, text)` runs to synthesize in order: consecutive
+    /// sentences with the same voice go together. Sentences without letters,
+    /// e.g. a lone number, keep the voice before them.
+    pub fn runs(&self, text: &str, fallback: &str) -> Vec<(String, String)> {
+        let whole = || vec![(fallback.to_string(), text.to_string())];
+        if !self.auto {
+            return whole();
+        }
+        let mut runs: Vec<(String, String)> = Vec::new();
+        for sentence in split_sentences(text, DEFAULT_TTS_CHUNK_CHARS) {
+            let voice = match runs.last() {
+                Some((voice, _)) if !sentence.chars().any(char::is_alphabetic) => voice.clone(),
+                _ => self.pick(&sentence, fallback),
+            };
+            match runs.last_mut() {
+                Some((last, text)) if *last == voice => {
+                    text.push(' ');
+                    text.push_str(&sentence);
+                }
+                _ => runs.push((voice, sentence)),
+            }
+        }
+        if runs.is_empty() { whole() } else { runs }
+    }
+}
+
+/// Read Settings' `Language: voice` list, e.g. `Chinese: chinese_woman, English: vivian`
+pub fn parse(text: &str) -> Result<Vec<LanguageVoice>, String> {
+    let mut voices: Vec<LanguageVoice> = Vec::new();
+    for entry in text.split([',', '\n']).map(str::trim).filter(|e| !e.is_empty()) {
+        let (name, voice) = entry.split_once(':')
+            .ok_or_else(|| format!("Expected Language: voice, got \"{}\"", entry))?;
+        let language = Language::ALL.into_iter()
+            .find(|l| l.name().eq_ignore_ascii_case(name.trim()))
+            .ok_or_else(|| format!("Unknown language \"{}\"", name.trim()))?;
+        let voice = voice.trim();
+        if voice.is_empty() {
+            return Err(format!("No voice given for {}", language.name()));
+        }
+        voices.retain(|v| v.language != language);
+        voices.push(LanguageVoice { language, voice: voice.to_string() });
+    }
+    Ok(voices)
+}
+
+/// The list as [`parse`] reads it
+pub fn format(voices: &[LanguageVoice]) -> String {
+    voices.iter().map(|v| format!("{}: {}", v.language.name(), v.voice)).collect::<Vec<_>>().join(", ")
+}
+
+static CURRENT: RwLock<Option<VoiceMap>> = RwLock::new(None);
+
+/// Mirror [`crate::Preferences::tts_voices`]
+pub fn set_current(map: &VoiceMap) {
+    *CURRENT.write().unwrap() = Some(map.clone());
+}
+
+pub fn current() -> VoiceMap {
+    CURRENT.read().unwrap().clone().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_runs() {
+        let map = VoiceMap { auto: true, ..VoiceMap::default() };
+        let text = "今天我们来试一下。The model loads in two seconds. It is fast! 2. 速度很快。";
+        assert_eq!(map.runs(text, "ryan"), [
+            ("chinese_woman".to_string(), "今天我们来试一下。".to_string()),
+            ("vivian".to_string(), "The model loads in two seconds. It is fast! 2.".to_string()),
+            ("chinese_woman".to_string(), "速度很快。".to_string()),
+        ]);
+        assert_eq!(map.pick("Le modèle est chargé et prêt pour les tests.", "ryan"), "ryan");
+
+        let off = VoiceMap::default();
+        assert_eq!(off.runs(text, "ryan"), [("ryan".to_string(), text.to_string())]);
+    }
+
+    #[test]
+    fn test_parse() {
+        let voices = parse("chinese: uncle_fu,\nEnglish : aiden, Chinese: chinese_man").unwrap();
+        assert_eq!(format(&voices), "English: aiden, Chinese: chinese_man");
+        assert!(parse("").unwrap().is_empty());
+        assert_eq!(parse("Klingon: worf").unwrap_err(), "Unknown language \"Klingon\"");
+        assert_eq!(parse("English vivian").unwrap_err(), "Expected Language: voice, got \"English vivian\"");
+        assert_eq!(parse("English:").unwrap_err(), "No voice given for English");
+    }
+}
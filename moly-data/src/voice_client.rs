@@ -0,0 +1,218 @@
+//! Voice Studio operations on top of [`OminiXApiClient`].
+//!
+//! Both the Voice app and the Model Hub's Voice Studio panel talk to the
+//! server through here: listing voices, running a queued training job to
+//! completion, and synthesizing with a cloned voice. Call these from a
+//! background thread. The `_with` variants take the client to use, e.g. one
+//! pointed at [`crate::mock_runtime`] in tests.
+
+use base64::Engine as _;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::ominix_api_client::{
+    OminiXApiClient, OminiXApiError, SpeechRequest, VoiceTrainRequest, VoiceTrainSample, VoiceTrainStatus,
+};
+use crate::voice_dataset::VoiceSample;
+use crate::voice_presets::VoicePreset;
+use crate::voice_training::{voice_preview_path, VoiceTrainingJob};
+
+/// Model id the server uses for cloned-voice synthesis
+pub const VOICE_CLONE_MODEL: &str = "gpt-so-vits";
+/// Where the last synthesis result is written for playback
+pub fn voice_output_path() -> PathBuf {
+    crate::artifacts::session_file("voice-out.wav")
+}
+
+/// A voice known to the server.
+#[derive(Clone, Debug)]
+pub struct VoiceInfo {
+    pub name: String,
+    pub is_ready: bool,
+    /// Cached audition sample, if one has been generated
+    pub preview: Option<PathBuf>,
+}
+
+/// Progress reported while a training job runs.
+pub enum VoiceTrainingUpdate {
+    /// Upload accepted; the server task id to persist for resume
+    Submitted(String),
+    Progress { stage: String, progress: f32 },
+    Done,
+    Error(String),
+}
+
+/// `GET /v1/voices`, with each voice's cached preview if present.
+pub fn list_voices() -> Result<Vec<VoiceInfo>, String> {
+    list_voices_with(&OminiXApiClient::localhost(10))
+}
+
+pub fn list_voices_with(client: &OminiXApiClient) -> Result<Vec<VoiceInfo>, String> {
+    let voices = client.list_voices()?;
+    Ok(voices.into_iter().map(|v| {
+        let preview = Some(voice_preview_path(&v.name)).filter(|p| p.exists());
+        VoiceInfo { name: v.name, is_ready: v.is_ready, preview }
+    }).collect())
+}
+
+/// Submit a job (unless it already has a task id) and poll its status until
+/// it finishes. Returns silently when cancelled.
+pub fn run_training_job(job: VoiceTrainingJob, cancel: &AtomicBool, on_update: impl FnMut(VoiceTrainingUpdate)) {
+    run_training_job_with(&OminiXApiClient::localhost(600), job, cancel, on_update)
+}
+
+pub fn run_training_job_with(
+    client: &OminiXApiClient,
+    job: VoiceTrainingJob,
+    cancel: &AtomicBool,
+    mut on_update: impl FnMut(VoiceTrainingUpdate),
+) {
+    let task_id = match job.task_id.clone() {
+        Some(id) => id,
+        None => match submit_training(client, &job) {
+            Ok(id) => id,
+            Err(e) => { on_update(VoiceTrainingUpdate::Error(e)); return; }
+        },
+    };
+    on_update(VoiceTrainingUpdate::Submitted(task_id.clone()));
+
+    loop {
+        if cancel.load(Ordering::SeqCst) { return; }
+        std::thread::sleep(std::time::Duration::from_millis(800));
+        if cancel.load(Ordering::SeqCst) { return; }
+        let status = match client.train_status(&task_id) {
+            Ok(status) => status,
+            Err(OminiXApiError::Http { status: 404, .. }) => {
+                on_update(VoiceTrainingUpdate::Error("Training task no longer exists on the server".to_string()));
+                return;
+            }
+            // Transient; keep polling
+            Err(_) => continue,
+        };
+        match parse_training_status(status) {
+            update @ (VoiceTrainingUpdate::Done | VoiceTrainingUpdate::Error(_)) => {
+                on_update(update);
+                return;
+            }
+            update => on_update(update),
+        }
+    }
+}
+
+/// `POST /v1/voices/train` with every clip of the job. Returns the task id.
+fn submit_training(client: &OminiXApiClient, job: &VoiceTrainingJob) -> Result<String, String> {
+    // Jobs queued before datasets existed carry a single clip
+    let samples = if job.samples.is_empty() {
+        vec![VoiceSample {
+            audio_path: job.audio_path.clone(),
+            transcript: job.transcript.clone(),
+            duration_secs: None,
+        }]
+    } else {
+        job.samples.clone()
+    };
+    let mut encoded = Vec::with_capacity(samples.len());
+    for sample in &samples {
+        let bytes = std::fs::read(&sample.audio_path)
+            .map_err(|e| format!("{}: {}", sample.audio_path, e))?;
+        encoded.push(VoiceTrainSample {
+            audio_data: base64::engine::general_purpose::STANDARD.encode(&bytes),
+            transcript: sample.transcript.clone(),
+        });
+    }
+    let request = VoiceTrainRequest {
+        name: job.name.clone(),
+        audio_data: encoded[0].audio_data.clone(),
+        transcript: encoded[0].transcript.clone(),
+        samples: encoded,
+        quality: job.quality.clone(),
+        language: job.language.clone(),
+        denoise: job.denoise,
+    };
+    client.train_voice(&request).map_err(|e| format!("Training request failed: {}", e))
+}
+
+fn parse_training_status(status: VoiceTrainStatus) -> VoiceTrainingUpdate {
+    if let Some(e) = status.error_message() {
+        return VoiceTrainingUpdate::Error(e.to_string());
+    }
+    if status.is_done() {
+        return VoiceTrainingUpdate::Done;
+    }
+    VoiceTrainingUpdate::Progress { stage: status.stage, progress: status.progress }
+}
+
+/// Ask the server to stop a training task (best effort).
+pub fn cancel_training(task_id: &str) {
+    if let Err(e) = OminiXApiClient::localhost(10).cancel_training(task_id) {
+        log::warn!("Cancelling voice training {} failed: {}", task_id, e);
+    }
+}
+
+/// `POST /v1/audio/speech` with a cloned voice, returning WAV bytes.
+pub fn synthesize(voice: &str, text: &str, speed: f32) -> Result<Vec<u8>, String> {
+    let request = SpeechRequest { speed: Some(speed), ..SpeechRequest::wav(VOICE_CLONE_MODEL, voice, text) };
+    OminiXApiClient::localhost(120).speech(&request)
+        .map_err(|e| format!("Synthesis failed: {}", e))
+}
+
+/// Synthesize `text` with a preset's voice, speed and language
+pub fn synthesize_preset(preset: &VoicePreset, text: &str) -> Result<Vec<u8>, String> {
+    OminiXApiClient::localhost(120).speech(&preset.request(VOICE_CLONE_MODEL, text))
+        .map_err(|e| format!("Synthesis failed: {}", e))
+}
+
+/// Synthesize the audition sentence for a voice and cache it on disk.
+pub fn generate_preview(voice: &str, sentence: &str) -> Result<PathBuf, String> {
+    let bytes = synthesize(voice, sentence, 1.0)?;
+    let path = voice_preview_path(voice);
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    std::fs::write(&path, &bytes).map_err(|e| e.to_string())?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_training_status_shapes() {
+        let parse = |s: &str| parse_training_status(serde_json::from_str(s).unwrap());
+        assert!(matches!(parse(r#"{"state":"done"}"#), VoiceTrainingUpdate::Done));
+        assert!(matches!(parse(r#"{"done":true,"progress":0.9}"#), VoiceTrainingUpdate::Done));
+        assert!(matches!(parse(r#"{"state":"running","error":"oom"}"#), VoiceTrainingUpdate::Error(e) if e == "oom"));
+        assert!(matches!(
+            parse(r#"{"state":"running","stage":"features","progress":0.4}"#),
+            VoiceTrainingUpdate::Progress { stage, .. } if stage == "features"
+        ));
+    }
+
+    #[test]
+    fn test_training_job_against_mock_runtime() {
+        let mock = crate::mock_runtime::MockRuntime::start();
+        let client = OminiXApiClient::new(mock.base_url(), 5);
+        let clip = std::env::temp_dir().join(format!("moly-voice-clip-{}.wav", std::process::id()));
+        std::fs::write(&clip, b"RIFF").unwrap();
+        let job = VoiceTrainingJob {
+            id: "job-1".into(),
+            name: "narrator".into(),
+            audio_path: clip.display().to_string(),
+            transcript: "Hello there".into(),
+            quality: "standard".into(),
+            language: "en".into(),
+            ..Default::default()
+        };
+
+        let mut updates = Vec::new();
+        run_training_job_with(&client, job, &AtomicBool::new(false), |update| updates.push(update));
+        assert!(matches!(&updates[0], VoiceTrainingUpdate::Submitted(id) if id == "task-1"));
+        assert!(matches!(updates.last(), Some(VoiceTrainingUpdate::Done)));
+
+        let voices = list_voices_with(&client).unwrap();
+        assert_eq!(voices.len(), 1);
+        assert!(voices[0].name == "narrator" && voices[0].is_ready);
+        std::fs::remove_file(&clip).unwrap();
+    }
+}
@@ -0,0 +1,171 @@
+//! Voice training datasets.
+//!
+//! A dataset is a named set of audio clips with per-clip transcripts, kept in
+//! `voice_datasets/<name>/` in the active profile's data directory (clips
+//! copied in, plus `dataset.json`) so it can be reopened, extended and
+//! resubmitted after the source files move.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::audio::Wav;
+
+const DATASET_FILENAME: &str = "dataset.json";
+
+/// Minimum total audio for a usable clone
+pub const MIN_DATASET_SECS: f64 = 10.0;
+/// Upper bound accepted by the training endpoint
+pub const MAX_DATASET_SECS: f64 = 600.0;
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct VoiceSample {
+    pub audio_path: String,
+    #[serde(default)]
+    pub transcript: String,
+    /// Unknown if the file could not be measured
+    #[serde(default)]
+    pub duration_secs: Option<f64>,
+}
+
+impl VoiceSample {
+    pub fn new(audio_path: &str) -> Self {
+        Self {
+            audio_path: audio_path.to_string(),
+            transcript: String::new(),
+            duration_secs: audio_duration_secs(Path::new(audio_path)),
+        }
+    }
+
+    pub fn file_name(&self) -> String {
+        Path::new(&self.audio_path)
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| self.audio_path.clone())
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct VoiceDataset {
+    pub name: String,
+    #[serde(default)]
+    pub samples: Vec<VoiceSample>,
+}
+
+impl VoiceDataset {
+    pub fn datasets_dir() -> PathBuf {
+        crate::profiles::data_dir().join("voice_datasets")
+    }
+
+    fn dir_for(name: &str) -> PathBuf {
+        let safe: String = name.trim().chars()
+            .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' || c == ' ' { c } else { '_' })
+            .collect();
+        Self::datasets_dir().join(safe)
+    }
+
+    /// Load a dataset from its folder
+    pub fn load(dir: &Path) -> Result<Self, String> {
+        let path = dir.join(DATASET_FILENAME);
+        let json = std::fs::read_to_string(&path)
+            .map_err(|e| format!("Cannot read {}: {}", path.display(), e))?;
+        serde_json::from_str(&json).map_err(|e| format!("Invalid dataset: {}", e))
+    }
+
+    /// Save under [`Self::datasets_dir`]`/<name>/`, copying in any clips that
+    /// live elsewhere. Sample paths are rewritten to the copies.
+    pub fn save(&mut self) -> Result<PathBuf, String> {
+        if self.name.trim().is_empty() {
+            return Err("Dataset needs a name".to_string());
+        }
+        let dir = Self::dir_for(&self.name);
+        std::fs::create_dir_all(&dir).map_err(|e| format!("Cannot create {}: {}", dir.display(), e))?;
+
+        for (i, sample) in self.samples.iter_mut().enumerate() {
+            let src = PathBuf::from(&sample.audio_path);
+            if src.parent() == Some(dir.as_path()) {
+                continue;
+            }
+            let dest = dir.join(format!("{:02}-{}", i + 1, sample.file_name()));
+            std::fs::copy(&src, &dest).map_err(|e| format!("Cannot copy {}: {}", src.display(), e))?;
+            sample.audio_path = dest.to_string_lossy().to_string();
+        }
+
+        let json = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        std::fs::write(dir.join(DATASET_FILENAME), json).map_err(|e| e.to_string())?;
+        log::info!("Saved voice dataset {} ({} samples)", self.name, self.samples.len());
+        Ok(dir)
+    }
+
+    pub fn total_duration_secs(&self) -> f64 {
+        self.samples.iter().filter_map(|s| s.duration_secs).sum()
+    }
+
+    /// One-line summary, e.g. "3 samples · 42.5s".
+    pub fn summary(&self) -> String {
+        let unknown = self.samples.iter().filter(|s| s.duration_secs.is_none()).count();
+        let mut text = format!(
+            "{} sample{} · {:.1}s",
+            self.samples.len(),
+            if self.samples.len() == 1 { "" } else { "s" },
+            self.total_duration_secs()
+        );
+        if unknown > 0 {
+            text.push_str(&format!(" ({} unmeasured)", unknown));
+        }
+        text
+    }
+
+    /// Check the dataset is ready to train on.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.samples.is_empty() {
+            return Err("Add at least one audio sample.".to_string());
+        }
+        if let Some(missing) = self.samples.iter().find(|s| !Path::new(&s.audio_path).exists()) {
+            return Err(format!("Sample not found: {}", missing.audio_path));
+        }
+        let total = self.total_duration_secs();
+        if total < MIN_DATASET_SECS {
+            return Err(format!("Need at least {:.0}s of audio (have {:.1}s).", MIN_DATASET_SECS, total));
+        }
+        if total > MAX_DATASET_SECS {
+            return Err(format!("At most {:.0}s of audio is supported (have {:.1}s).", MAX_DATASET_SECS, total));
+        }
+        Ok(())
+    }
+}
+
+/// Duration of an audio file: parsed directly for WAV, via `afinfo` otherwise.
+pub fn audio_duration_secs(path: &Path) -> Option<f64> {
+    let is_wav = path.extension().and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("wav"));
+    if is_wav {
+        let bytes = std::fs::read(path).ok()?;
+        return Wav::parse(&bytes).ok().map(|w| w.duration_secs());
+    }
+    let output = std::process::Command::new("afinfo").arg(path).output().ok()?;
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .find_map(|l| l.trim().strip_prefix("estimated duration:"))
+        .and_then(|rest| rest.trim().split_whitespace().next()?.parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_total_duration() {
+        let sample = |secs| VoiceSample {
+            // Any file that exists, wherever the tests run from
+            audio_path: concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.toml").to_string(),
+            transcript: String::new(),
+            duration_secs: Some(secs),
+        };
+        let mut dataset = VoiceDataset { name: "test".into(), samples: vec![sample(4.0)] };
+        assert!(dataset.validate().unwrap_err().contains("at least"));
+
+        dataset.samples.push(sample(8.0));
+        assert!(dataset.validate().is_ok());
+        assert_eq!(dataset.summary(), "2 samples · 12.0s");
+    }
+}
@@ -0,0 +1,198 @@
+//! Named voice synthesis presets
+//!
+//! A [`VoicePreset`] bundles what Voice Studio synthesizes with: a cloned
+//! voice, the speed, a language hint and the format results are saved in.
+//! Voice Studio saves and applies them with one click, and Read Aloud can
+//! speak a conversation with one instead of the chat's voice. They are kept
+//! in [`crate::Preferences::voice_presets`]; Voice Studio changes them with
+//! [`crate::StoreAction::SetVoicePresets`] and everything else reads the
+//! mirror in [`current`].
+//!
+//! Synthesis always asks the server for WAV, which every platform can play;
+//! the format only applies when a result is saved ([`OutputFormat::save`]).
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::RwLock;
+
+use crate::ominix_api_client::SpeechRequest;
+use crate::platform::{self, AudioFormat};
+
+/// Speeds the server accepts
+pub const SPEED_RANGE: std::ops::RangeInclusive<f32> = 0.5..=2.0;
+
+/// Languages a preset can ask for, as Voice Studio's training form names
+/// them; "auto" lets the model detect it
+pub const LANGUAGES: [&str; 3] = ["auto", "zh", "en"];
+
+/// The file format synthesized speech is saved in
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    #[default]
+    Wav,
+    Mp3,
+    /// AAC in an MPEG-4 container
+    M4a,
+    Flac,
+}
+
+impl OutputFormat {
+    pub const ALL: [OutputFormat; 4] = [OutputFormat::Wav, OutputFormat::Mp3, OutputFormat::M4a, OutputFormat::Flac];
+
+    /// Also the `response_format` the runtime knows it by
+    pub fn extension(&self) -> &'static str {
+        match self {
+            OutputFormat::Wav => "wav",
+            OutputFormat::Mp3 => "mp3",
+            OutputFormat::M4a => "m4a",
+            OutputFormat::Flac => "flac",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            OutputFormat::Wav => "WAV",
+            OutputFormat::Mp3 => "MP3",
+            OutputFormat::M4a => "AAC",
+            OutputFormat::Flac => "FLAC",
+        }
+    }
+
+    /// A format by extension or label, e.g. "mp3" or "AAC"
+    pub fn parse(text: &str) -> Result<OutputFormat, String> {
+        let text = text.trim().trim_start_matches('.');
+        Self::ALL
+            .into_iter()
+            .find(|f| f.extension().eq_ignore_ascii_case(text) || f.label().eq_ignore_ascii_case(text))
+            .ok_or_else(|| format!("Unknown format \"{}\"; use WAV, MP3, AAC or FLAC", text))
+    }
+
+    /// Save the WAV file `wav` to `path` in this format. Blocks while
+    /// converting; call it off the UI thread.
+    pub fn save(&self, wav: &Path, path: &Path) -> Result<(), String> {
+        match self {
+            OutputFormat::Wav => std::fs::copy(wav, path).map(|_| ()).map_err(|e| e.to_string()),
+            OutputFormat::Mp3 => platform::convert_audio(wav, path, AudioFormat::Mp3),
+            OutputFormat::M4a => platform::convert_audio(wav, path, AudioFormat::Aac),
+            OutputFormat::Flac => platform::convert_audio(wav, path, AudioFormat::Flac),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct VoicePreset {
+    pub name: String,
+    /// Name of a voice trained in Voice Studio
+    pub voice: String,
+    pub speed: f32,
+    /// One of [`LANGUAGES`]
+    pub language: String,
+    #[serde(default)]
+    pub format: OutputFormat,
+}
+
+impl VoicePreset {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("Enter a name for the preset".to_string());
+        }
+        if self.voice.is_empty() {
+            return Err("Select a voice for the preset".to_string());
+        }
+        if !SPEED_RANGE.contains(&self.speed) {
+            return Err(format!("Speed must be between {} and {}", SPEED_RANGE.start(), SPEED_RANGE.end()));
+        }
+        if !LANGUAGES.contains(&self.language.as_str()) {
+            return Err(format!("Unknown language \"{}\"", self.language));
+        }
+        Ok(())
+    }
+
+    /// The language to send the server, `None` to let the model detect it
+    pub fn language_hint(&self) -> Option<String> {
+        Some(self.language.clone()).filter(|l| l != "auto")
+    }
+
+    /// "maya · 1.25× · EN · AAC"
+    pub fn summary(&self) -> String {
+        format!("{} · {}× · {} · {}", self.voice, self.speed, self.language.to_uppercase(), self.format.label())
+    }
+
+    /// A request speaking `text` with `model` as this preset says
+    pub fn request(&self, model: &str, text: &str) -> SpeechRequest {
+        SpeechRequest {
+            speed: Some(self.speed).filter(|s| *s != 1.0),
+            language: self.language_hint(),
+            ..SpeechRequest::wav(model, &self.voice, text)
+        }
+    }
+}
+
+/// Add `preset` to `presets`, replacing one of the same name
+pub fn save(presets: &mut Vec<VoicePreset>, preset: VoicePreset) -> Result<(), String> {
+    preset.validate()?;
+    let preset = VoicePreset { name: preset.name.trim().to_string(), ..preset };
+    match presets.iter_mut().find(|p| p.name.eq_ignore_ascii_case(&preset.name)) {
+        Some(existing) => *existing = preset,
+        None => presets.push(preset),
+    }
+    Ok(())
+}
+
+pub fn find<'a>(presets: &'a [VoicePreset], name: &str) -> Option<&'a VoicePreset> {
+    presets.iter().find(|p| p.name.eq_ignore_ascii_case(name.trim()))
+}
+
+static CURRENT: RwLock<Vec<VoicePreset>> = RwLock::new(Vec::new());
+
+/// Mirror [`crate::Preferences::voice_presets`]
+pub fn set_current(presets: &[VoicePreset]) {
+    *CURRENT.write().unwrap() = presets.to_vec();
+}
+
+pub fn current() -> Vec<VoicePreset> {
+    CURRENT.read().unwrap().clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn preset(name: &str, speed: f32) -> VoicePreset {
+        VoicePreset { name: name.to_string(), voice: "maya".to_string(), speed, language: "en".to_string(), format: OutputFormat::M4a }
+    }
+
+    #[test]
+    fn test_save_and_validate() {
+        let mut presets = Vec::new();
+        save(&mut presets, preset(" Narration ", 1.25)).unwrap();
+        save(&mut presets, preset("Quick", 1.5)).unwrap();
+        save(&mut presets, preset("narration", 0.75)).unwrap();
+        assert_eq!(presets.len(), 2);
+        assert_eq!((presets[0].name.as_str(), presets[0].speed), ("narration", 0.75));
+        assert_eq!(find(&presets, "QUICK").unwrap().summary(), "maya · 1.5× · EN · AAC");
+
+        assert!(save(&mut presets, preset("", 1.0)).is_err());
+        assert!(save(&mut presets, preset("Slow", 0.2)).is_err());
+        assert!(save(&mut presets, VoicePreset { language: "fr".to_string(), ..preset("French", 1.0) }).is_err());
+        assert_eq!(presets.len(), 2);
+    }
+
+    #[test]
+    fn test_request() {
+        let request = serde_json::to_value(preset("Narration", 1.25).request("gpt-so-vits", "Hello.")).unwrap();
+        assert_eq!(request["voice"], "maya");
+        assert_eq!(request["speed"], 1.25);
+        assert_eq!(request["language"], "en");
+        assert_eq!(request["response_format"], "wav");
+
+        let auto = VoicePreset { language: "auto".to_string(), ..preset("Auto", 1.0) };
+        let request = serde_json::to_value(auto.request("gpt-so-vits", "Hello.")).unwrap();
+        assert!(request.get("language").is_none() && request.get("speed").is_none());
+
+        // Presets saved before formats existed are WAV
+        let old: VoicePreset = serde_json::from_str(r#"{"name":"Old","voice":"maya","speed":1.0,"language":"zh"}"#).unwrap();
+        assert_eq!(old.format, OutputFormat::Wav);
+    }
+}
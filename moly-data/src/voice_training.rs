@@ -0,0 +1,465 @@
+//! Voice Studio training queue.
+//!
+//! ominix-api trains one voice per `/v1/voices/train` task. The queue holds
+//! jobs the user has submitted, runs them one at a time, and is persisted to
+//! `voice_training.json` in the active profile's data directory so that
+//! after a restart jobs that were already running on the server are resumed
+//! by re-querying their task id.
+//!
+//! There is one queue per process: the shell calls [`start`] once, and a
+//! thread works through the jobs whether or not a Voice Studio is open.
+//! After a profile switch [`reload`] picks up the new profile's queue.
+//! Voice Studios add and cancel jobs with [`enqueue`], [`cancel_active`] and
+//! [`dismiss`], and show [`jobs`] again when [`generation`] changes.
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+
+use crate::shutdown;
+use crate::voice_client::{self, VoiceTrainingUpdate};
+use crate::voice_dataset::VoiceSample;
+
+const VOICE_TRAINING_FILENAME: &str = "voice_training.json";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VoiceJobState {
+    #[default]
+    Queued,
+    /// Uploading audio; no task id yet
+    Submitting,
+    /// Running on the server under `task_id`
+    Training,
+    Failed,
+}
+
+/// A voice waiting for, or undergoing, training.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct VoiceTrainingJob {
+    /// Local id, stable across restarts
+    pub id: String,
+    pub name: String,
+    pub audio_path: String,
+    #[serde(default)]
+    pub transcript: String,
+    /// All clips of the dataset; `audio_path`/`transcript` mirror the first
+    #[serde(default)]
+    pub samples: Vec<VoiceSample>,
+    pub quality: String,
+    pub language: String,
+    pub denoise: bool,
+    #[serde(default)]
+    pub task_id: Option<String>,
+    #[serde(default)]
+    pub state: VoiceJobState,
+    #[serde(default)]
+    pub stage: String,
+    #[serde(default)]
+    pub progress: f32,
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+impl VoiceTrainingJob {
+    /// Short status for the voice list, e.g. "Training 42% · extracting features".
+    pub fn status_label(&self) -> String {
+        match self.state {
+            VoiceJobState::Queued => "Queued".to_string(),
+            VoiceJobState::Submitting => "Uploading…".to_string(),
+            VoiceJobState::Training if self.stage.is_empty() => {
+                format!("Training {:.0}%", self.progress * 100.0)
+            }
+            VoiceJobState::Training => {
+                format!("Training {:.0}% · {}", self.progress * 100.0, self.stage)
+            }
+            VoiceJobState::Failed => match &self.error {
+                Some(e) => format!("Failed: {}", e),
+                None => "Failed".to_string(),
+            },
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct VoiceTrainingQueue {
+    #[serde(default)]
+    pub jobs: Vec<VoiceTrainingJob>,
+}
+
+impl VoiceTrainingQueue {
+    /// Load the queue. A job interrupted while uploading has no server task,
+    /// so it goes back to `Queued`; `Training` jobs keep their task id.
+    pub fn load() -> Self {
+        let mut queue = std::fs::read_to_string(Self::queue_path())
+            .ok()
+            .and_then(|s| match serde_json::from_str::<Self>(&s) {
+                Ok(q) => Some(q),
+                Err(e) => {
+                    log::error!("Failed to parse voice training queue: {:?}", e);
+                    None
+                }
+            })
+            .unwrap_or_default();
+        for job in &mut queue.jobs {
+            if job.state == VoiceJobState::Submitting
+                || (job.state == VoiceJobState::Training && job.task_id.is_none())
+            {
+                job.state = VoiceJobState::Queued;
+            }
+        }
+        queue
+    }
+
+    pub fn save(&self) {
+        let path = Self::queue_path();
+        if let Some(parent) = path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        match serde_json::to_string_pretty(self) {
+            Ok(json) => {
+                if let Err(e) = std::fs::write(&path, json) {
+                    log::error!("Failed to write voice training queue: {:?}", e);
+                }
+            }
+            Err(e) => log::error!("Failed to serialize voice training queue: {:?}", e),
+        }
+    }
+
+    pub(crate) fn queue_path() -> PathBuf {
+        crate::profiles::data_dir().join(VOICE_TRAINING_FILENAME)
+    }
+
+    /// Append a job and save. Returns its 1-based queue position.
+    pub fn enqueue(&mut self, mut job: VoiceTrainingJob) -> usize {
+        if job.id.is_empty() {
+            job.id = format!("job-{}", chrono::Utc::now().timestamp_millis());
+        }
+        job.state = VoiceJobState::Queued;
+        self.jobs.push(job);
+        self.save();
+        self.jobs.iter().filter(|j| j.state != VoiceJobState::Failed).count()
+    }
+
+    /// The job currently uploading or training, if any
+    pub fn active(&self) -> Option<&VoiceTrainingJob> {
+        self.jobs.iter()
+            .find(|j| matches!(j.state, VoiceJobState::Submitting | VoiceJobState::Training))
+    }
+
+    /// Next job to start when nothing is active
+    pub fn next_queued(&self) -> Option<&VoiceTrainingJob> {
+        if self.active().is_some() {
+            return None;
+        }
+        self.jobs.iter().find(|j| j.state == VoiceJobState::Queued)
+    }
+
+    pub fn get_mut(&mut self, id: &str) -> Option<&mut VoiceTrainingJob> {
+        self.jobs.iter_mut().find(|j| j.id == id)
+    }
+
+    /// Remove a job (finished, cancelled or dismissed) and save
+    pub fn remove(&mut self, id: &str) -> Option<VoiceTrainingJob> {
+        let idx = self.jobs.iter().position(|j| j.id == id)?;
+        let job = self.jobs.remove(idx);
+        self.save();
+        Some(job)
+    }
+
+    /// Record what the server reported for job `id`. A finished job leaves
+    /// the queue and is returned. Doesn't save.
+    fn apply(&mut self, id: &str, update: &VoiceTrainingUpdate) -> Option<VoiceTrainingJob> {
+        let idx = self.jobs.iter().position(|j| j.id == id)?;
+        let job = &mut self.jobs[idx];
+        match update {
+            VoiceTrainingUpdate::Submitted(task_id) => {
+                job.task_id = Some(task_id.clone());
+                job.state = VoiceJobState::Training;
+            }
+            VoiceTrainingUpdate::Progress { stage, progress } => {
+                job.stage = stage.clone();
+                job.progress = *progress;
+            }
+            VoiceTrainingUpdate::Done => return Some(self.jobs.remove(idx)),
+            VoiceTrainingUpdate::Error(e) => {
+                job.state = VoiceJobState::Failed;
+                job.error = Some(e.clone());
+            }
+        }
+        None
+    }
+
+    /// "maya: Training 42% — 2 more queued"; empty when nothing runs
+    pub fn status_text(&self) -> String {
+        let waiting = self.jobs.iter().filter(|j| j.state == VoiceJobState::Queued).count();
+        match self.active() {
+            Some(job) if waiting > 0 => format!("{}: {} — {} more queued", job.name, job.status_label(), waiting),
+            Some(job) => format!("{}: {}", job.name, job.status_label()),
+            None => String::new(),
+        }
+    }
+}
+
+// ─── The process's queue ──────────────────────────────────────────────────────
+
+struct Trainer {
+    queue: VoiceTrainingQueue,
+    /// Stops watching the active job
+    cancel: Option<Arc<AtomicBool>>,
+    /// Jobs that finished, well or not, since startup
+    finished: u64,
+    /// How the last of them ended: the voice's name, or why it failed
+    outcome: Option<Result<String, String>>,
+}
+
+static TRAINER: Mutex<Trainer> = Mutex::new(Trainer {
+    queue: VoiceTrainingQueue { jobs: Vec::new() },
+    cancel: None,
+    finished: 0,
+    outcome: None,
+});
+
+/// Wakes the trainer thread when a job is added
+static WAKE: Condvar = Condvar::new();
+
+static STARTED: AtomicBool = AtomicBool::new(false);
+
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+fn changed() {
+    GENERATION.fetch_add(1, Ordering::SeqCst);
+    makepad_widgets::SignalToUI::set_ui_signal();
+}
+
+/// Load the saved queue and start working through it. Only the first call
+/// does anything.
+pub fn start() {
+    if STARTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    TRAINER.lock().unwrap().queue = VoiceTrainingQueue::load();
+    std::thread::spawn(run);
+    changed();
+}
+
+/// The trainer thread: one job at a time, waiting for more when the queue
+/// is empty. A job that was training when the app last quit is resumed by
+/// polling its task id again.
+fn run() {
+    loop {
+        let (job, cancel) = {
+            let mut trainer = TRAINER.lock().unwrap();
+            let job = loop {
+                match trainer.queue.active().or_else(|| trainer.queue.next_queued()).cloned() {
+                    Some(job) => break job,
+                    None => trainer = WAKE.wait(trainer).unwrap(),
+                }
+            };
+            if job.task_id.is_none() {
+                if let Some(j) = trainer.queue.get_mut(&job.id) {
+                    j.state = VoiceJobState::Submitting;
+                    j.stage.clear();
+                    j.progress = 0.0;
+                    j.error = None;
+                }
+                trainer.queue.save();
+            }
+            let cancel = Arc::new(AtomicBool::new(false));
+            trainer.cancel = Some(cancel.clone());
+            (job, cancel)
+        };
+        changed();
+
+        let (id, name) = (job.id.clone(), job.name.trim().to_string());
+        let mut trained = None;
+        {
+            // Stopping only stops watching; the job is resumed at next start
+            let _work = shutdown::track(format!("Training the voice “{}”", name), Some(cancel.clone()));
+            voice_client::run_training_job(job, &cancel, |update| {
+                let mut trainer = TRAINER.lock().unwrap();
+                let finished = trainer.queue.apply(&id, &update);
+                trainer.queue.save();
+                match &update {
+                    VoiceTrainingUpdate::Done => trained = finished,
+                    VoiceTrainingUpdate::Error(e) => {
+                        log::warn!("Training the voice {} failed: {}", name, e);
+                        trainer.finished += 1;
+                        trainer.outcome = Some(Err(e.clone()));
+                    }
+                    _ => {}
+                }
+                drop(trainer);
+                changed();
+            });
+        }
+        TRAINER.lock().unwrap().cancel = None;
+
+        // Cache the new voice's audition sample before Voice Studios list it
+        if let Some(job) = trained {
+            let sentence = preview_sentence(&job.language, &job.transcript);
+            if let Err(e) = voice_client::generate_preview(&job.name, sentence) {
+                log::warn!("Preview for {} failed: {}", job.name, e);
+            }
+            let mut trainer = TRAINER.lock().unwrap();
+            trainer.finished += 1;
+            trainer.outcome = Some(Ok(name.clone()));
+            drop(trainer);
+            changed();
+        }
+        // Cancelled but still queued: the app is quitting
+        if cancel.load(Ordering::SeqCst) && TRAINER.lock().unwrap().queue.jobs.iter().any(|j| j.id == id) {
+            return;
+        }
+    }
+}
+
+/// Switch to the active profile's queue. A job training for the previous
+/// profile keeps its task id and is resumed when that profile is active
+/// again.
+pub fn reload() {
+    if !STARTED.load(Ordering::SeqCst) {
+        return;
+    }
+    let mut trainer = TRAINER.lock().unwrap();
+    if let Some(cancel) = trainer.cancel.take() {
+        cancel.store(true, Ordering::SeqCst);
+    }
+    trainer.queue = VoiceTrainingQueue::load();
+    drop(trainer);
+    WAKE.notify_all();
+    changed();
+}
+
+/// Add a job to the queue. Returns its 1-based queue position.
+pub fn enqueue(job: VoiceTrainingJob) -> usize {
+    let position = TRAINER.lock().unwrap().queue.enqueue(job);
+    WAKE.notify_all();
+    changed();
+    position
+}
+
+/// Stop the job uploading or training, here and on the server; the next
+/// queued job starts
+pub fn cancel_active() {
+    let mut trainer = TRAINER.lock().unwrap();
+    if let Some(cancel) = trainer.cancel.take() {
+        cancel.store(true, Ordering::SeqCst);
+    }
+    let Some(id) = trainer.queue.active().map(|j| j.id.clone()) else { return };
+    if let Some(task_id) = trainer.queue.remove(&id).and_then(|job| job.task_id) {
+        std::thread::spawn(move || voice_client::cancel_training(&task_id));
+    }
+    drop(trainer);
+    changed();
+}
+
+/// Take a job that isn't running out of the queue, e.g. a failed one to
+/// fix and submit again
+pub fn dismiss(id: &str) -> Option<VoiceTrainingJob> {
+    let mut trainer = TRAINER.lock().unwrap();
+    if trainer.queue.active().is_some_and(|j| j.id == id) {
+        return None;
+    }
+    let job = trainer.queue.remove(id);
+    drop(trainer);
+    changed();
+    job
+}
+
+/// The queued, running and failed jobs
+pub fn jobs() -> Vec<VoiceTrainingJob> {
+    TRAINER.lock().unwrap().queue.jobs.clone()
+}
+
+/// See [`VoiceTrainingQueue::status_text`]
+pub fn status_text() -> String {
+    TRAINER.lock().unwrap().queue.status_text()
+}
+
+pub fn is_training() -> bool {
+    TRAINER.lock().unwrap().queue.active().is_some()
+}
+
+/// How many jobs have finished since startup, and how the last one ended
+pub fn outcome() -> (u64, Option<Result<String, String>>) {
+    let trainer = TRAINER.lock().unwrap();
+    (trainer.finished, trainer.outcome.clone())
+}
+
+/// Changes whenever the queue does; compare with a value seen earlier to
+/// know when to refresh
+pub fn generation() -> u64 {
+    GENERATION.load(Ordering::SeqCst)
+}
+
+// ─── Preview samples ──────────────────────────────────────────────────────────
+
+const PREVIEW_SENTENCE_EN: &str = "Hello! This is a short preview of my voice. How does it sound?";
+const PREVIEW_SENTENCE_ZH: &str = "你好！这是我的声音的简短试听。听起来怎么样？";
+
+/// Standard audition sentence in the voice's language. With `auto`, a
+/// transcript containing CJK characters selects Chinese.
+pub fn preview_sentence(language: &str, transcript: &str) -> &'static str {
+    let is_zh = match language {
+        "zh" | "chinese" => true,
+        "auto" | "" => transcript.chars().any(|c| ('\u{4e00}'..='\u{9fff}').contains(&c)),
+        _ => false,
+    };
+    if is_zh { PREVIEW_SENTENCE_ZH } else { PREVIEW_SENTENCE_EN }
+}
+
+/// Where a voice's preview sample is cached: `voice_previews/<name>.wav` in
+/// the active profile's data directory
+pub fn voice_preview_path(voice_name: &str) -> PathBuf {
+    let safe: String = voice_name.chars()
+        .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+    crate::profiles::data_dir().join("voice_previews").join(format!("{}.wav", safe))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_queue_runs_one_job_at_a_time() {
+        let mut queue = VoiceTrainingQueue::default();
+        queue.jobs.push(VoiceTrainingJob { id: "a".into(), ..Default::default() });
+        queue.jobs.push(VoiceTrainingJob { id: "b".into(), ..Default::default() });
+
+        assert_eq!(queue.next_queued().map(|j| j.id.as_str()), Some("a"));
+        queue.get_mut("a").unwrap().state = VoiceJobState::Training;
+        assert!(queue.next_queued().is_none());
+        assert_eq!(queue.active().map(|j| j.id.as_str()), Some("a"));
+    }
+
+    /// The queue and previews belong to the active profile
+    #[test]
+    fn test_paths_follow_profile() {
+        let dir = crate::profiles::data_dir();
+        assert_eq!(VoiceTrainingQueue::queue_path(), dir.join(VOICE_TRAINING_FILENAME));
+        assert_eq!(voice_preview_path("Maya (v2)"), dir.join("voice_previews").join("Maya__v2_.wav"));
+        assert_eq!(crate::voice_dataset::VoiceDataset::datasets_dir(), dir.join("voice_datasets"));
+    }
+
+    #[test]
+    fn test_apply_updates() {
+        let mut queue = VoiceTrainingQueue::default();
+        for (id, name) in [("a", "maya"), ("b", "leo")] {
+            queue.jobs.push(VoiceTrainingJob { id: id.into(), name: name.into(), ..Default::default() });
+        }
+        assert!(queue.apply("a", &VoiceTrainingUpdate::Submitted("task-1".into())).is_none());
+        queue.apply("a", &VoiceTrainingUpdate::Progress { stage: "extracting features".into(), progress: 0.42 });
+        assert_eq!(queue.jobs[0].task_id.as_deref(), Some("task-1"));
+        assert_eq!(queue.status_text(), "maya: Training 42% · extracting features — 1 more queued");
+
+        let done = queue.apply("a", &VoiceTrainingUpdate::Done).unwrap();
+        assert_eq!(done.name, "maya");
+        assert_eq!(queue.status_text(), "");
+        queue.apply("b", &VoiceTrainingUpdate::Error("out of memory".into()));
+        assert_eq!(queue.jobs[0].status_label(), "Failed: out of memory");
+        assert!(queue.next_queued().is_none());
+    }
+}
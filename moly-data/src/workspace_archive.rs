@@ -0,0 +1,484 @@
+//! Whole-workspace export and import
+//!
+//! "Export Workspace" in Settings writes a single archive with everything
+//! needed to move to another machine or keep a full backup: the profile's
+//! data directory (preferences, chats, session templates, runtime options,
+//! image history…), voice datasets and the training queue, the user's model
+//! registry extensions, and optionally the downloaded model weights. A
+//! `workspace.json` manifest comes first, so an import can show what the
+//! archive holds ([`read_manifest`]) before [`import`] unpacks the chosen
+//! sections. Files from the archive replace local files of the same name;
+//! other local files are kept.
+//!
+//! The archive is a plain tar file, readable with `tar -xf`. Long names use
+//! the GNU long name entry and sizes over 8 GiB the GNU base-256 size.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::{Component, Path, PathBuf};
+
+use crate::model_registry::ModelRegistry;
+use crate::voice_dataset::VoiceDataset;
+use crate::voice_training::VoiceTrainingQueue;
+
+/// Bump when the archive layout changes incompatibly
+pub const WORKSPACE_ARCHIVE_VERSION: u32 = 1;
+
+pub const MANIFEST_NAME: &str = "workspace.json";
+
+/// Entries of the profile directory that are not exported: other profiles
+/// (the default profile's directory holds them), chat snapshots, voice data
+/// exported in its own section, and the rebuildable search index
+const PROFILE_EXCLUDED: &[&str] = &[
+    "profiles",
+    "profiles.json",
+    "backups",
+    "voice_datasets",
+    "voice_previews",
+    "voice_training.json",
+    "semantic_index.json",
+];
+
+const BLOCK: usize = 512;
+
+/// Progress is reported about this often while copying large files
+const PROGRESS_BYTES: u64 = 8 * 1024 * 1024;
+
+/// Parts of a workspace that can be exported and imported separately
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Section {
+    /// Preferences, chats and everything else in the profile's directory
+    Profile,
+    /// Voice datasets and the training queue
+    Voices,
+    /// Models added to the registry on this machine
+    Registry,
+    /// Downloaded model weights
+    Models,
+}
+
+impl Section {
+    pub const ALL: [Section; 4] = [Section::Profile, Section::Voices, Section::Registry, Section::Models];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Profile => "Chats, preferences and prompts",
+            Self::Voices => "Voice datasets",
+            Self::Registry => "Model registry additions",
+            Self::Models => "Model weights",
+        }
+    }
+
+    /// Archive paths of the section and the local files they stand for
+    fn roots(&self) -> Vec<(&'static str, PathBuf)> {
+        match self {
+            Self::Profile => vec![("profile", crate::profiles::data_dir())],
+            Self::Voices => vec![
+                ("voices/datasets", VoiceDataset::datasets_dir()),
+                ("voices/voice_training.json", VoiceTrainingQueue::queue_path()),
+            ],
+            Self::Registry => ModelRegistry::override_path()
+                .map(|path| vec![("registry/models_registry.json", path)])
+                .unwrap_or_default(),
+            Self::Models => vec![("models", PathBuf::from(crate::download_settings::current().models_dir()))],
+        }
+    }
+}
+
+/// What one section of an archive holds
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SectionInfo {
+    pub section: Section,
+    pub files: usize,
+    pub bytes: u64,
+}
+
+/// `workspace.json`, the first entry of an archive
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Manifest {
+    pub version: u32,
+    pub exported_at: DateTime<Utc>,
+    /// Profile the workspace was exported from
+    pub profile: String,
+    pub sections: Vec<SectionInfo>,
+}
+
+impl Manifest {
+    pub fn section(&self, section: Section) -> Option<&SectionInfo> {
+        self.sections.iter().find(|s| s.section == section)
+    }
+
+    pub fn total_bytes(&self) -> u64 {
+        self.sections.iter().map(|s| s.bytes).sum()
+    }
+}
+
+/// A local file and its path in the archive
+struct Entry {
+    local: PathBuf,
+    archive: String,
+    size: u64,
+}
+
+/// Files of a section, in a stable order
+fn entries(section: Section) -> Vec<Entry> {
+    let mut entries = Vec::new();
+    for (archive, local) in section.roots() {
+        collect(&local, archive, section == Section::Profile, &mut entries);
+    }
+    entries
+}
+
+fn collect(local: &Path, archive: &str, top_of_profile: bool, entries: &mut Vec<Entry>) {
+    let Ok(meta) = std::fs::metadata(local) else { return };
+    if meta.is_file() {
+        entries.push(Entry { local: local.to_path_buf(), archive: archive.to_string(), size: meta.len() });
+        return;
+    }
+    let Ok(dir) = std::fs::read_dir(local) else { return };
+    let mut children: Vec<_> = dir.flatten().map(|e| e.file_name().to_string_lossy().to_string()).collect();
+    children.sort();
+    for name in children {
+        // Temporary files of atomic writes
+        if name.ends_with(".tmp") || (top_of_profile && PROFILE_EXCLUDED.contains(&name.as_str())) {
+            continue;
+        }
+        collect(&local.join(&name), &format!("{}/{}", archive, name), false, entries);
+    }
+}
+
+/// Files and bytes a section would add to an export
+pub fn section_size(section: Section) -> (usize, u64) {
+    let entries = entries(section);
+    (entries.len(), entries.iter().map(|e| e.size).sum())
+}
+
+/// Write the chosen sections to an archive at `path`. `progress` gets the
+/// bytes written so far and the total. Blocks; call it off the UI thread.
+pub fn export(path: &Path, sections: &[Section], mut progress: impl FnMut(u64, u64)) -> Result<Manifest, String> {
+    let mut manifest = Manifest {
+        version: WORKSPACE_ARCHIVE_VERSION,
+        exported_at: Utc::now(),
+        profile: crate::profiles::active_profile_id(),
+        sections: Vec::new(),
+    };
+    let mut files = Vec::new();
+    for &section in Section::ALL.iter().filter(|s| sections.contains(s)) {
+        let entries = entries(section);
+        manifest.sections.push(SectionInfo {
+            section,
+            files: entries.len(),
+            bytes: entries.iter().map(|e| e.size).sum(),
+        });
+        files.extend(entries);
+    }
+    let total = manifest.total_bytes();
+
+    let tmp = path.with_extension("tmp");
+    let result = (|| {
+        let file = File::create(&tmp).map_err(|e| format!("{}: {}", tmp.display(), e))?;
+        let mut out = BufWriter::new(file);
+        let json = serde_json::to_vec_pretty(&manifest).map_err(|e| e.to_string())?;
+        write_entry(&mut out, MANIFEST_NAME, json.len() as u64, &mut json.as_slice(), &mut |_| {})?;
+        let mut done = 0;
+        for entry in &files {
+            let mut input = File::open(&entry.local).map_err(|e| format!("{}: {}", entry.local.display(), e))?;
+            let mut reported = done;
+            write_entry(&mut out, &entry.archive, entry.size, &mut input, &mut |written| {
+                if done + written >= reported + PROGRESS_BYTES {
+                    reported = done + written;
+                    progress(reported, total);
+                }
+            })?;
+            done += entry.size;
+            progress(done, total);
+        }
+        // End of archive: two zero blocks
+        out.write_all(&[0u8; BLOCK * 2]).map_err(|e| e.to_string())?;
+        out.flush().map_err(|e| e.to_string())
+    })();
+    match result {
+        Ok(()) => std::fs::rename(&tmp, path).map_err(|e| format!("{}: {}", path.display(), e))?,
+        Err(e) => {
+            let _ = std::fs::remove_file(&tmp);
+            return Err(e);
+        }
+    }
+    log::info!("Exported workspace ({} files, {} bytes) to {:?}", files.len(), total, path);
+    Ok(manifest)
+}
+
+/// The manifest of an archive, without reading the rest
+pub fn read_manifest(path: &Path) -> Result<Manifest, String> {
+    let file = File::open(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let mut reader = TarReader::new(BufReader::new(file));
+    let (name, size) = reader.next_entry()?.ok_or("The archive is empty")?;
+    if name != MANIFEST_NAME {
+        return Err("This is not an OminiX Studio workspace archive".to_string());
+    }
+    let mut json = Vec::new();
+    reader.read_data(size, &mut json)?;
+    let manifest: Manifest = serde_json::from_slice(&json).map_err(|e| format!("Invalid workspace manifest: {}", e))?;
+    if manifest.version > WORKSPACE_ARCHIVE_VERSION {
+        return Err(format!(
+            "The archive was made by a newer version of OminiX Studio (format {})",
+            manifest.version
+        ));
+    }
+    Ok(manifest)
+}
+
+/// Unpack the chosen sections of an archive over the local files. Returns
+/// the number of files written. Blocks; call it off the UI thread.
+pub fn import(path: &Path, sections: &[Section], mut progress: impl FnMut(u64, u64)) -> Result<usize, String> {
+    let manifest = read_manifest(path)?;
+    let total: u64 = manifest.sections.iter().filter(|s| sections.contains(&s.section)).map(|s| s.bytes).sum();
+    let roots: Vec<(&'static str, PathBuf)> = sections.iter().flat_map(|s| s.roots()).collect();
+
+    let file = File::open(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let mut reader = TarReader::new(BufReader::new(file));
+    let (mut written, mut done) = (0, 0);
+    while let Some((name, size)) = reader.next_entry()? {
+        let Some(dest) = destination(&roots, &name)? else {
+            reader.skip_data(size)?;
+            continue;
+        };
+        if let Some(dir) = dest.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+        }
+        let tmp = dest.with_extension("import-tmp");
+        let mut out = BufWriter::new(File::create(&tmp).map_err(|e| format!("{}: {}", tmp.display(), e))?);
+        reader.read_data(size, &mut out)?;
+        out.flush().map_err(|e| e.to_string())?;
+        drop(out);
+        std::fs::rename(&tmp, &dest).map_err(|e| format!("{}: {}", dest.display(), e))?;
+        written += 1;
+        done += size;
+        progress(done, total);
+    }
+    if sections.contains(&Section::Voices) {
+        relink_voice_datasets();
+    }
+    log::info!("Imported {} files from workspace archive {:?}", written, path);
+    Ok(written)
+}
+
+/// Where an archive entry goes, or `None` if its section wasn't chosen
+fn destination(roots: &[(&'static str, PathBuf)], name: &str) -> Result<Option<PathBuf>, String> {
+    let rest = Path::new(name);
+    if rest.components().any(|c| !matches!(c, Component::Normal(_))) {
+        return Err(format!("Unsafe path in archive: {}", name));
+    }
+    for (archive, local) in roots {
+        if name == *archive {
+            return Ok(Some(local.clone()));
+        }
+        if let Some(rest) = name.strip_prefix(archive).and_then(|r| r.strip_prefix('/')) {
+            return Ok(Some(local.join(rest)));
+        }
+    }
+    Ok(None)
+}
+
+/// Point imported voice samples at their clips on this machine
+fn relink_voice_datasets() {
+    let Ok(dirs) = std::fs::read_dir(VoiceDataset::datasets_dir()) else { return };
+    for dir in dirs.flatten().map(|e| e.path()).filter(|p| p.is_dir()) {
+        let Ok(mut dataset) = VoiceDataset::load(&dir) else { continue };
+        for sample in &mut dataset.samples {
+            let local = dir.join(sample.file_name());
+            if local.exists() {
+                sample.audio_path = local.to_string_lossy().to_string();
+            }
+        }
+        if let Err(e) = dataset.save() {
+            log::warn!("Voice dataset {} not relinked: {}", dataset.name, e);
+        }
+    }
+}
+
+// ─── Tar ──────────────────────────────────────────────────────────────────────
+
+fn write_entry(
+    out: &mut impl Write,
+    name: &str,
+    size: u64,
+    input: &mut impl Read,
+    progress: &mut impl FnMut(u64),
+) -> Result<(), String> {
+    if name.len() > 100 {
+        // GNU long name: the name is the data of a preceding `L` entry
+        let mut long = name.as_bytes().to_vec();
+        long.push(0);
+        out.write_all(&header("././@LongLink", long.len() as u64, b'L')).map_err(|e| e.to_string())?;
+        out.write_all(&long).map_err(|e| e.to_string())?;
+        out.write_all(&vec![0u8; padding(long.len() as u64)]).map_err(|e| e.to_string())?;
+    }
+    out.write_all(&header(name, size, b'0')).map_err(|e| e.to_string())?;
+    let mut buf = vec![0u8; 64 * 1024];
+    let mut copied = 0u64;
+    while copied < size {
+        let want = buf.len().min((size - copied) as usize);
+        let n = input.read(&mut buf[..want]).map_err(|e| e.to_string())?;
+        if n == 0 {
+            return Err(format!("{} changed while it was exported", name));
+        }
+        out.write_all(&buf[..n]).map_err(|e| e.to_string())?;
+        copied += n as u64;
+        progress(copied);
+    }
+    out.write_all(&vec![0u8; padding(size)]).map_err(|e| e.to_string())
+}
+
+fn header(name: &str, size: u64, kind: u8) -> [u8; BLOCK] {
+    let mut h = [0u8; BLOCK];
+    let name = name.as_bytes();
+    let len = name.len().min(100);
+    h[..len].copy_from_slice(&name[..len]);
+    h[100..107].copy_from_slice(b"0000644");
+    h[108..115].copy_from_slice(b"0000000");
+    h[116..123].copy_from_slice(b"0000000");
+    if size < 0o77777777777 {
+        h[124..135].copy_from_slice(format!("{:011o}", size).as_bytes());
+    } else {
+        h[124] = 0x80;
+        h[128..136].copy_from_slice(&size.to_be_bytes());
+    }
+    h[136..147].copy_from_slice(format!("{:011o}", Utc::now().timestamp().max(0)).as_bytes());
+    h[156] = kind;
+    h[257..263].copy_from_slice(b"ustar\0");
+    h[263..265].copy_from_slice(b"00");
+    h[148..156].copy_from_slice(b"        ");
+    let sum: u32 = h.iter().map(|&b| b as u32).sum();
+    h[148..155].copy_from_slice(format!("{:06o}\0", sum).as_bytes());
+    h
+}
+
+fn padding(size: u64) -> usize {
+    (BLOCK - (size % BLOCK as u64) as usize) % BLOCK
+}
+
+struct TarReader<R: Read> {
+    input: R,
+}
+
+impl<R: Read> TarReader<R> {
+    fn new(input: R) -> Self {
+        Self { input }
+    }
+
+    /// Name and size of the next file; other entry types are skipped
+    fn next_entry(&mut self) -> Result<Option<(String, u64)>, String> {
+        let mut long_name = None;
+        loop {
+            let mut h = [0u8; BLOCK];
+            if let Err(e) = self.input.read_exact(&mut h) {
+                return if e.kind() == std::io::ErrorKind::UnexpectedEof { Ok(None) } else { Err(e.to_string()) };
+            }
+            if h.iter().all(|&b| b == 0) {
+                return Ok(None);
+            }
+            let size = parse_size(&h[124..136])?;
+            match h[156] {
+                b'L' => {
+                    let mut name = Vec::new();
+                    self.read_data(size, &mut name)?;
+                    long_name = Some(String::from_utf8_lossy(&name).trim_end_matches('\0').to_string());
+                }
+                b'0' | 0 => {
+                    let name = long_name.take().unwrap_or_else(|| {
+                        let name = field(&h[..100]);
+                        let prefix = field(&h[345..500]);
+                        if &h[257..262] == b"ustar" && !prefix.is_empty() { format!("{}/{}", prefix, name) } else { name }
+                    });
+                    return Ok(Some((name, size)));
+                }
+                _ => self.skip_data(size)?,
+            }
+        }
+    }
+
+    /// Copy an entry's data, then skip its padding
+    fn read_data(&mut self, size: u64, out: &mut impl Write) -> Result<(), String> {
+        let copied = std::io::copy(&mut (&mut self.input).take(size), out).map_err(|e| e.to_string())?;
+        if copied < size {
+            return Err("The archive is truncated".to_string());
+        }
+        self.skip(padding(size) as u64)
+    }
+
+    fn skip_data(&mut self, size: u64) -> Result<(), String> {
+        self.skip(size + padding(size) as u64)
+    }
+
+    fn skip(&mut self, bytes: u64) -> Result<(), String> {
+        let skipped = std::io::copy(&mut (&mut self.input).take(bytes), &mut std::io::sink()).map_err(|e| e.to_string())?;
+        if skipped < bytes { Err("The archive is truncated".to_string()) } else { Ok(()) }
+    }
+}
+
+fn field(bytes: &[u8]) -> String {
+    let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+    String::from_utf8_lossy(&bytes[..end]).to_string()
+}
+
+fn parse_size(bytes: &[u8]) -> Result<u64, String> {
+    if bytes[0] & 0x80 != 0 {
+        return Ok(bytes[4..].iter().fold(0u64, |n, &b| (n << 8) | b as u64));
+    }
+    let text = field(bytes);
+    let text = text.trim();
+    if text.is_empty() {
+        return Ok(0);
+    }
+    u64::from_str_radix(text, 8).map_err(|_| "The archive is damaged".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tar_round_trip() {
+        let long = format!("profile/chats/{}.json", "x".repeat(120));
+        let files: Vec<(&str, Vec<u8>)> = vec![
+            ("profile/preferences.json", b"{\"sidebar_expanded\":true}".to_vec()),
+            (&long, vec![7u8; 1000]),
+            ("models/empty.bin", Vec::new()),
+        ];
+        let mut archive = Vec::new();
+        for (name, data) in &files {
+            write_entry(&mut archive, name, data.len() as u64, &mut data.as_slice(), &mut |_| {}).unwrap();
+        }
+        archive.extend_from_slice(&[0u8; BLOCK * 2]);
+        assert_eq!(archive.len() % BLOCK, 0);
+
+        let mut reader = TarReader::new(archive.as_slice());
+        for (name, data) in &files {
+            let (read_name, size) = reader.next_entry().unwrap().unwrap();
+            assert_eq!(&read_name, name);
+            let mut read = Vec::new();
+            reader.read_data(size, &mut read).unwrap();
+            assert_eq!(&read, data);
+        }
+        assert_eq!(reader.next_entry().unwrap(), None);
+
+        let mut big = header("models/big.safetensors", 9 << 30, b'0');
+        assert_eq!(parse_size(&big[124..136]), Ok(9 << 30));
+        big[124..136].copy_from_slice(b"00000001750\0");
+        assert_eq!(parse_size(&big[124..136]), Ok(1000));
+    }
+
+    #[test]
+    fn test_destination_rejects_unsafe_paths() {
+        let roots = vec![("profile", PathBuf::from("/data")), ("registry/models_registry.json", PathBuf::from("/r.json"))];
+        assert_eq!(destination(&roots, "profile/chats/1.json"), Ok(Some(PathBuf::from("/data/chats/1.json"))));
+        assert_eq!(destination(&roots, "registry/models_registry.json"), Ok(Some(PathBuf::from("/r.json"))));
+        assert_eq!(destination(&roots, "models/a.bin"), Ok(None));
+        assert_eq!(destination(&roots, "profiles/x"), Ok(None));
+        assert!(destination(&roots, "profile/../../etc/passwd").is_err());
+        assert!(destination(&roots, "/etc/passwd").is_err());
+    }
+}
@@ -1,13 +1,21 @@
 use makepad_widgets::*;
 
-use moly_data::{ChatId, Store, StoreAction, ModelRegistry, RegistryCategory, ModelRuntimeClient, ensure_server_running};
-use std::sync::mpsc;
-use std::path::Path;
+use moly_data::{ChatId, Store, WindowState, StoreAction, StoreEvent, ModelLoadPhase, ModelRegistry, RegistryCategory, RegistryModel, ModelRuntimeClient, Profiles, ensure_server_running};
+use moly_data::{api_proxy, artifacts, bookmarks, chat_backup, chat_navigation, lan_share, mcp_client, model_integrity, model_state, model_trash, platform, preload, profiles, shutdown, translate, DownloadStatus, ModelWatcher};
+use moly_data::load_scheduler::{self, LoadEvent, LoadScheduler};
+use moly_data::runtime_options::ModelRuntimeOptions;
+use moly_data::clipboard_watch::{ClipboardWatcher, QuickAction};
+use moly_data::quick_ask::{self, AskEvent, Summon, SummonListener};
+use moly_data::power::{self, BatteryWatcher};
+use moly_data::semantic_search::{self, Hit};
+use moly_data::session_templates::{SessionTemplate, SessionTemplates};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
 use moly_kit::a2ui::{A2uiSurface, A2uiSurfaceAction};
 use moly_kit::widgets::chat::ChatAction;
 use moly_kit::widgets::prompt_input::PromptInputAction;
 use moly_kit::widgets::take_pending_a2ui_json;
-use moly_widgets::{MolyApp, MolyAppData};
+use moly_widgets::{DialogRequest, DialogResult, MolyApp, MolyAppData, MolyDialogWidgetRefExt, MolyTheme, UiStateCache};
 
 live_design! {
     use link::theme::*;
@@ -15,6 +23,7 @@ live_design! {
     use link::widgets::*;
     use moly_widgets::theme::*;
     use moly_widgets::components::*;
+    use moly_widgets::dialog::design::*;
     use moly_kit::a2ui::surface::*;
 
     // Import app widgets from external app crates
@@ -27,6 +36,7 @@ live_design! {
     ICON_HAMBURGER = dep("crate://self/resources/icons/hamburger.png")
     ICON_MOON = dep("crate://self/resources/icons/moon.svg")
     ICON_CHAT = dep("crate://self/resources/icons/chat.png")
+    ICON_BOOKMARK = dep("crate://self/resources/icons/bookmark.png")
     ICON_SETTINGS = dep("crate://self/resources/icons/settings.png")
     ICON_HUB = dep("crate://self/resources/icons/hub.svg")
     ICON_LLM = dep("crate://self/resources/icons/llm.png")
@@ -35,8 +45,13 @@ live_design! {
     ICON_TTS = dep("crate://self/resources/icons/tts.png")
     ICON_IMAGE = dep("crate://self/resources/icons/image.png")
     ICON_VIDEO = dep("crate://self/resources/icons/video.png")
+    ICON_MUSIC = dep("crate://self/resources/icons/music.png")
+    ICON_RERANK = dep("crate://self/resources/icons/rerank.png")
     ICON_NEW_CHAT = dep("crate://self/resources/icons/new-chat.svg")
     ICON_TRASH = dep("crate://self/resources/icons/trash.svg")
+    ICON_COPY = dep("crate://self/resources/icons/copy.svg")
+    ICON_TEMPLATE = dep("crate://self/resources/icons/template.svg")
+    ICON_RENAME = dep("crate://self/resources/icons/rename.svg")
 
     // Logo (light and dark variants)
     IMG_LOGO = dep("crate://self/resources/ominix-studio-logo.png")
@@ -70,6 +85,24 @@ live_design! {
                 width: Fill
                 draw_text: { color: (TEXT_PRIMARY), text_style: <FONT_SEMIBOLD>{ font_size: 11.0 }, wrap: Ellipsis }
             }
+            rename_btn = <View> {
+                width: 28, height: 28
+                align: {x: 0.5, y: 0.5}
+                cursor: Hand
+                <Icon> { draw_icon: { svg_file: (ICON_RENAME), color: (TEXT_MUTED) }, icon_walk: {width: 16, height: 16} }
+            }
+            duplicate_btn = <View> {
+                width: 28, height: 28
+                align: {x: 0.5, y: 0.5}
+                cursor: Hand
+                <Icon> { draw_icon: { svg_file: (ICON_COPY), color: (TEXT_MUTED) }, icon_walk: {width: 16, height: 16} }
+            }
+            template_btn = <View> {
+                width: 28, height: 28
+                align: {x: 0.5, y: 0.5}
+                cursor: Hand
+                <Icon> { draw_icon: { svg_file: (ICON_TEMPLATE), color: (TEXT_MUTED) }, icon_walk: {width: 17, height: 17} }
+            }
             delete_btn = <View> {
                 width: 28, height: 28
                 align: {x: 0.5, y: 0.5}
@@ -110,6 +143,46 @@ live_design! {
         tile_3 = <ChatTile> {}
     }
 
+    // Bookmarked message on the Bookmarks page
+    BookmarkCard = <RoundedView> {
+        width: Fill, height: Fit
+        show_bg: true
+        draw_bg: {
+            border_radius: 12.0
+            color: (PANEL_BG)
+        }
+        flow: Down
+        padding: {top: 12, left: 16, right: 12, bottom: 14}
+        spacing: 6
+        cursor: Hand
+        visible: false
+        header = <View> {
+            width: Fill, height: Fit
+            flow: Right
+            align: {y: 0.5}
+            spacing: 8
+            chat_title = <Label> {
+                width: Fill
+                draw_text: { color: (TEXT_MUTED), text_style: <FONT_MEDIUM>{ font_size: 10.0 }, wrap: Ellipsis }
+            }
+            date_label = <Label> { draw_text: { color: (TEXT_MUTED), text_style: { font_size: 10.0 } } }
+            remove_btn = <View> {
+                width: 28, height: 28
+                align: {x: 0.5, y: 0.5}
+                cursor: Hand
+                <Icon> { draw_icon: { svg_file: (ICON_TRASH), color: (TEXT_MUTED) }, icon_walk: {width: 18, height: 18} }
+            }
+        }
+        excerpt = <Label> {
+            width: Fill
+            draw_text: { color: (TEXT_PRIMARY), text_style: <FONT_REGULAR>{ font_size: 12.0 }, wrap: Word }
+        }
+        note = <Label> {
+            width: Fill
+            draw_text: { color: (ACCENT_INDIGO), text_style: <FONT_MEDIUM>{ font_size: 11.0 }, wrap: Word }
+        }
+    }
+
     // Sidebar button using Button directly (like mofa-studio SidebarMenuButton)
     // Button natively supports icon + text with draw_icon and draw_text
     // Note: Button's draw_bg/draw_text/draw_icon don't support custom instance variables,
@@ -301,7 +374,7 @@ live_design! {
         // Size on the right
         slot_meta = <Label> {
             draw_text: {
-                color: #9ca3af
+                color: (TEXT_MUTED)
                 text_style: { font_size: 11.0 }
             }
         }
@@ -336,7 +409,184 @@ live_design! {
         }
     }
 
+    ProfileDropdownSlot = <View> {
+        width: Fill, height: 40
+        cursor: Hand
+        visible: false
+        flow: Right
+        align: {y: 0.5}
+        padding: {left: 16, right: 16}
+        spacing: 8
+        show_bg: true
+        draw_bg: {
+            instance active: 0.0
+            fn pixel(self) -> vec4 {
+                return mix(#ffffff, #eff6ff, self.active);
+            }
+        }
+
+        profile_slot_name = <Label> {
+            width: Fill
+            draw_text: {
+                color: #1f2937
+                text_style: <FONT_MEDIUM>{ font_size: 13.0 }
+                wrap: Ellipsis
+            }
+        }
+
+        // "Default" tag — hidden unless this profile opens at startup
+        profile_slot_default_tag = <RoundedView> {
+            width: Fit, height: 20
+            visible: false
+            padding: {left: 6, right: 6}
+            align: {x: 0.5, y: 0.5}
+            show_bg: true
+            draw_bg: {
+                color: #f3f4f6
+                border_radius: 4.0
+            }
+            <Label> {
+                text: "Default"
+                draw_text: {
+                    color: #4b5563
+                    text_style: <FONT_MEDIUM>{ font_size: 10.0 }
+                }
+            }
+        }
+    }
+
+    ProfileActionButton = <View> {
+        width: Fit, height: 32
+        cursor: Hand
+        padding: {left: 12, right: 12}
+        align: {x: 0.5, y: 0.5}
+        show_bg: true
+        draw_bg: {
+            instance hover: 0.0
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                sdf.box(1.0, 1.0, self.rect_size.x - 2.0, self.rect_size.y - 2.0, 6.0);
+                sdf.fill(mix(#f3f4f6, #e5e7eb, self.hover));
+                return sdf.result;
+            }
+        }
+        animator: {
+            hover = {
+                default: off
+                off = { from: {all: Forward{duration: 0.1}}, apply: {draw_bg: {hover: 0.0}} }
+                on  = { from: {all: Forward{duration: 0.1}}, apply: {draw_bg: {hover: 1.0}} }
+            }
+        }
+    }
+
+    // A chat opened in its own window ("Open in new window")
+    DetachedChatWindow = <Window> {
+        window: { title: "OminiX Studio — Chat", inner_size: vec2(900, 760) }
+        pass: {
+            clear_color: #f5f7fa
+        }
+
+        body = <View> {
+            width: Fill, height: Fill
+            flow: Down
+            show_bg: true
+            draw_bg: {
+                color: #f5f7fa
+            }
+
+            detached_header = <View> {
+                width: Fill, height: 48
+                flow: Right
+                align: {y: 0.5}
+                padding: {left: 20, right: 12}
+                show_bg: true
+                draw_bg: {
+                    color: #ffffff
+                }
+
+                detached_title = <Label> {
+                    text: ""
+                    draw_text: {
+                        color: #1f2937
+                        text_style: <FONT_SEMIBOLD>{ font_size: 12.0 }
+                    }
+                }
+
+                <View> { width: Fill }
+
+                reattach_btn = <ProfileActionButton> {
+                    <Label> {
+                        text: "Move to main window"
+                        draw_text: {
+                            color: #374151
+                            text_style: <FONT_MEDIUM>{ font_size: 11.0 }
+                        }
+                    }
+                }
+            }
+
+            chat_app = <ChatApp> {
+                width: Fill, height: Fill
+            }
+        }
+    }
+
+    // Quick Ask, opened by its system-wide shortcut
+    QuickAskWindow = <Window> {
+        window: { title: "Quick Ask", inner_size: vec2(600, 360) }
+        pass: {
+            clear_color: #f5f7fa
+        }
+
+        body = <View> {
+            width: Fill, height: Fill
+            flow: Down
+            padding: 16
+            spacing: 10
+            show_bg: true
+            draw_bg: {
+                color: #f5f7fa
+            }
+
+            quick_ask_input = <TextInput> {
+                width: Fill, height: Fit
+                empty_text: "Ask the loaded model… (Enter to send)"
+            }
+
+            quick_ask_scroll = <ScrollYView> {
+                width: Fill, height: Fill
+                quick_ask_answer = <Label> {
+                    width: Fill
+                    text: ""
+                    draw_text: { color: #1f2937, text_style: { font_size: 12.0 }, wrap: Word }
+                }
+            }
+
+            quick_ask_footer = <View> {
+                width: Fill, height: Fit
+                flow: Right
+                align: {y: 0.5}
+                spacing: 8
+
+                quick_ask_status = <Label> {
+                    text: ""
+                    draw_text: { color: #6b7280, text_style: { font_size: 11.0 } }
+                }
+                <View> { width: Fill, height: 1 }
+                quick_ask_open_btn = <ProfileActionButton> {
+                    <Label> { text: "Open in Chat", draw_text: { color: #374151, text_style: <FONT_MEDIUM>{ font_size: 12.0 } } }
+                }
+                quick_ask_close_btn = <ProfileActionButton> {
+                    <Label> { text: "Close", draw_text: { color: #374151, text_style: <FONT_MEDIUM>{ font_size: 12.0 } } }
+                }
+            }
+        }
+    }
+
     App = {{App}} {
+        detached_chat_window: <DetachedChatWindow> {}
+        quick_ask_window: <QuickAskWindow> {}
+
         ui: <Window> {
             window: { title: "OminiX Studio", inner_size: vec2(1400, 900) }
             pass: {
@@ -448,20 +698,24 @@ live_design! {
                                 fn pixel(self) -> vec4 {
                                     let sdf = Sdf2d::viewport(self.pos * self.rect_size);
                                     sdf.box(0.0, 0.0, self.rect_size.x, self.rect_size.y, 4.0);
-                                    // LLM indigo-50, VLM violet-50, ASR green-50, TTS amber-50, Image pink-50, Video sky-50
+                                    // LLM indigo-50, VLM violet-50, ASR green-50, TTS amber-50, Image pink-50, Video sky-50, Music orange-50, Rerank teal-50
                                     let c0 = #dbeafe;
                                     let c1 = #ede9fe;
                                     let c2 = #d1fae5;
                                     let c3 = #fef3c7;
                                     let c4 = #fce7f3;
                                     let c5 = #d4f1f9;
+                                    let c6 = #ffedd5;
+                                    let c7 = #ccfbf1;
                                     let w0 = 1.0 - step(0.5, self.cat);
                                     let w1 = step(0.5, self.cat) * (1.0 - step(1.5, self.cat));
                                     let w2 = step(1.5, self.cat) * (1.0 - step(2.5, self.cat));
                                     let w3 = step(2.5, self.cat) * (1.0 - step(3.5, self.cat));
                                     let w4 = step(3.5, self.cat) * (1.0 - step(4.5, self.cat));
-                                    let w5 = step(4.5, self.cat);
-                                    sdf.fill(c0 * w0 + c1 * w1 + c2 * w2 + c3 * w3 + c4 * w4 + c5 * w5);
+                                    let w5 = step(4.5, self.cat) * (1.0 - step(5.5, self.cat));
+                                    let w6 = step(5.5, self.cat) * (1.0 - step(6.5, self.cat));
+                                    let w7 = step(6.5, self.cat);
+                                    sdf.fill(c0 * w0 + c1 * w1 + c2 * w2 + c3 * w3 + c4 * w4 + c5 * w5 + c6 * w6 + c7 * w7);
                                     return sdf.result;
                                 }
                             }
@@ -476,13 +730,17 @@ live_design! {
                                         let c3 = #92400f;
                                         let c4 = #9d174d;
                                         let c5 = #0c4a6e;
+                                        let c6 = #9a3412;
+                                        let c7 = #115e59;
                                         let w0 = 1.0 - step(0.5, self.cat);
                                         let w1 = step(0.5, self.cat) * (1.0 - step(1.5, self.cat));
                                         let w2 = step(1.5, self.cat) * (1.0 - step(2.5, self.cat));
                                         let w3 = step(2.5, self.cat) * (1.0 - step(3.5, self.cat));
                                         let w4 = step(3.5, self.cat) * (1.0 - step(4.5, self.cat));
-                                        let w5 = step(4.5, self.cat);
-                                        return c0 * w0 + c1 * w1 + c2 * w2 + c3 * w3 + c4 * w4 + c5 * w5;
+                                        let w5 = step(4.5, self.cat) * (1.0 - step(5.5, self.cat));
+                                        let w6 = step(5.5, self.cat) * (1.0 - step(6.5, self.cat));
+                                        let w7 = step(6.5, self.cat);
+                                        return c0 * w0 + c1 * w1 + c2 * w2 + c3 * w3 + c4 * w4 + c5 * w5 + c6 * w6 + c7 * w7;
                                     }
                                     text_style: <FONT_SEMIBOLD>{ font_size: 9.5 }
                                 }
@@ -540,8 +798,69 @@ live_design! {
                         }
                     }
 
+                    // Open the current chat in its own window (chat view only)
+                    detach_chat_btn = <View> {
+                        width: 32, height: 32
+                        cursor: Hand
+                        visible: false
+                        align: {x: 0.5, y: 0.5}
+                        margin: {right: 8}
+                        show_bg: true
+                        draw_bg: {
+                            instance hover: 0.0
+                            fn pixel(self) -> vec4 {
+                                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                                sdf.box(1.0, 1.0, self.rect_size.x - 2.0, self.rect_size.y - 2.0, 6.0);
+                                sdf.fill(mix(#f9fafb, #e5e7eb, self.hover));
+                                return sdf.result;
+                            }
+                        }
+                        animator: {
+                            hover = {
+                                default: off
+                                off = { from: {all: Forward{duration: 0.1}}, apply: {draw_bg: {hover: 0.0}} }
+                                on  = { from: {all: Forward{duration: 0.1}}, apply: {draw_bg: {hover: 1.0}} }
+                            }
+                        }
+                        <Label> {
+                            text: "⧉"
+                            draw_text: {
+                                color: #6b7280
+                                text_style: { font_size: 15.0 }
+                            }
+                        }
+                    }
+
                     <View> { width: Fill } // Right spacer
 
+                    // ── Startup preload progress (visible while preloading) ──
+                    preload_status = <View> {
+                        width: Fit, height: Fit
+                        visible: false
+                        flow: Right
+                        align: {y: 0.5}
+                        spacing: 6
+                        margin: {right: 12}
+                        preload_label = <Label> {
+                            text: ""
+                            draw_text: {
+                                color: #6366f1
+                                text_style: <FONT_MEDIUM>{ font_size: 10.0 }
+                            }
+                        }
+                        preload_cancel_btn = <ProfileActionButton> {
+                            height: 26
+                            padding: {left: 10, right: 10}
+                            <Label> {
+                                text: "Cancel"
+                                draw_text: {
+                                    color: #374151
+                                    text_style: <FONT_MEDIUM>{ font_size: 10.0 }
+                                }
+                            }
+                        }
+                    }
+
                     // ── RAM usage ring gauge ────────────────────────────
                     ram_gauge = <View> {
                         width: 26, height: 26
@@ -590,6 +909,48 @@ live_design! {
                             text_style: { font_size: 8.5 }
                         }
                     }
+
+                    // ── Active profile pill ─────────────────────────────
+                    profile_btn = <View> {
+                        width: Fit, height: 32
+                        cursor: Hand
+                        align: {x: 0.5, y: 0.5}
+                        padding: {left: 12, right: 10}
+                        flow: Right
+                        spacing: 6
+                        show_bg: true
+                        draw_bg: {
+                            instance hover: 0.0
+                            fn pixel(self) -> vec4 {
+                                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                                sdf.box(1.0, 1.0, self.rect_size.x - 2.0, self.rect_size.y - 2.0, 8.0);
+                                sdf.fill(mix(#f3f4f6, #e5e7eb, self.hover));
+                                return sdf.result;
+                            }
+                        }
+                        animator: {
+                            hover = {
+                                default: off
+                                off = { from: {all: Forward{duration: 0.15}}, apply: {draw_bg: {hover: 0.0}} }
+                                on  = { from: {all: Forward{duration: 0.15}}, apply: {draw_bg: {hover: 1.0}} }
+                            }
+                        }
+
+                        profile_label = <Label> {
+                            text: "Default"
+                            draw_text: {
+                                color: #374151
+                                text_style: <FONT_MEDIUM>{ font_size: 12.0 }
+                            }
+                        }
+                        <Label> {
+                            text: "▾"
+                            draw_text: {
+                                color: #6b7280
+                                text_style: { font_size: 10.0 }
+                            }
+                        }
+                    }
                 }
 
                 // Content area
@@ -694,6 +1055,11 @@ live_design! {
                                 }
                             }
 
+                            bookmarks_btn = <SidebarButton> {
+                                sidebar_label = { text: "Bookmarks" }
+                                sidebar_icon = { source: (ICON_BOOKMARK) }
+                            }
+
                             // MODELS section
                             models_section_label = <View> {
                                 width: Fill, height: Fit
@@ -710,6 +1076,8 @@ live_design! {
                             tts_btn   = <SidebarButton> { sidebar_label = { text: "TTS" }   sidebar_icon = { source: (ICON_TTS) } }
                             image_btn = <SidebarButton> { sidebar_label = { text: "Image" } sidebar_icon = { source: (ICON_IMAGE) } }
                             video_btn = <SidebarButton> { sidebar_label = { text: "Video" } sidebar_icon = { source: (ICON_VIDEO) } }
+                            music_btn = <SidebarButton> { sidebar_label = { text: "Music" } sidebar_icon = { source: (ICON_MUSIC) } }
+                            rerank_btn = <SidebarButton> { sidebar_label = { text: "Rerank" } sidebar_icon = { source: (ICON_RERANK) } }
 
                             settings_btn = <SidebarButton> {
                                 sidebar_label = { text: "Settings" }
@@ -797,6 +1165,8 @@ live_design! {
                             // Search bar container
                             <View> {
                                 width: Fill, height: Fit
+                                flow: Down
+                                spacing: 8
                                 align: {x: 0.5}
                                 margin: {bottom: 40}
 
@@ -845,6 +1215,15 @@ live_design! {
                                         }
                                     }
                                 }
+
+                                // Progress of the search by meaning
+                                semantic_status = <Label> {
+                                    text: ""
+                                    draw_text: {
+                                        color: #6b7280
+                                        text_style: { font_size: 11.0 }
+                                    }
+                                }
                             }
 
                             // Empty state (shown when no chats)
@@ -878,25 +1257,132 @@ live_design! {
                             }
                         }
 
-                        // Chat with canvas panel (horizontal layout)
-                        chat_with_canvas = <View> {
+                        // Bookmarks page: bookmarked messages across all sessions
+                        bookmarks_page = <View> {
                             width: Fill, height: Fill
-                            flow: Right
-                            visible: true
+                            flow: Down
+                            visible: false
+                            show_bg: true
+                            draw_bg: {
+                                color: #f5f7fa
+                            }
+                            padding: {top: 40, left: 48, right: 48, bottom: 32}
 
-                            // Left: Chat app (fills remaining space)
-                            chat_app = <ChatApp> {
-                                width: Fill, height: Fill
+                            <View> {
+                                width: Fill, height: Fit
+                                margin: {bottom: 24}
+                                align: {x: 0.5}
+                                <Label> {
+                                    text: "Bookmarks"
+                                    draw_text: {
+                                        color: #1f2937
+                                        text_style: <FONT_SEMIBOLD>{ font_size: 28.0 }
+                                    }
+                                }
                             }
 
-                            // Middle: Splitter (resizable divider)
-                            canvas_splitter = <View> {
-                                width: 0, height: Fill  // 0 when collapsed, 16 when expanded
-                                cursor: ColResize
-                                show_bg: true
-                                draw_bg: {
-                                    fn pixel(self) -> vec4 {
-                                        let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                            <View> {
+                                width: Fill, height: Fit
+                                align: {x: 0.5}
+                                margin: {bottom: 24}
+
+                                <RoundedView> {
+                                    width: 500, height: 44
+                                    show_bg: true
+                                    draw_bg: {
+                                        border_radius: 12.0
+                                        color: #e5e7eb
+                                    }
+                                    padding: {left: 20, right: 20}
+                                    align: {y: 0.5}
+
+                                    bookmark_search_input = <TextInput> {
+                                        width: Fill, height: 32
+                                        empty_text: "Search notes and messages..."
+                                        draw_text: {
+                                            color: #1f2937
+                                            color_focus: #1f2937
+                                            color_empty: #6b7280
+                                            color_empty_focus: #6b7280
+                                            text_style: { font_size: 14.0 }
+                                        }
+                                        draw_selection: {
+                                            color: #bfdbfe
+                                            color_focus: #bfdbfe
+                                        }
+                                        draw_cursor: {
+                                            color: #1f2937
+                                        }
+                                        draw_bg: {
+                                            fn pixel(self) -> vec4 {
+                                                return vec4(0.0, 0.0, 0.0, 0.0);
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+
+                            bookmarks_empty = <View> {
+                                width: Fill, height: Fill
+                                align: {x: 0.5, y: 0.3}
+                                bookmarks_empty_label = <Label> {
+                                    text: "No bookmarks yet. Use Bookmark… in a session to keep an answer here."
+                                    draw_text: {
+                                        color: #6b7280
+                                        text_style: { font_size: 16.0 }
+                                    }
+                                }
+                            }
+
+                            bookmarks_scroll = <ScrollYView> {
+                                width: Fill, height: Fill
+                                visible: false
+
+                                bookmarks_list = <View> {
+                                    width: Fill, height: Fit
+                                    flow: Down
+                                    spacing: 12
+
+                                    bookmark_0 = <BookmarkCard> {}
+                                    bookmark_1 = <BookmarkCard> {}
+                                    bookmark_2 = <BookmarkCard> {}
+                                    bookmark_3 = <BookmarkCard> {}
+                                    bookmark_4 = <BookmarkCard> {}
+                                    bookmark_5 = <BookmarkCard> {}
+                                    bookmark_6 = <BookmarkCard> {}
+                                    bookmark_7 = <BookmarkCard> {}
+                                    bookmark_8 = <BookmarkCard> {}
+                                    bookmark_9 = <BookmarkCard> {}
+                                    bookmark_10 = <BookmarkCard> {}
+                                    bookmark_11 = <BookmarkCard> {}
+
+                                    bookmarks_more = <Label> {
+                                        margin: {top: 4}
+                                        draw_text: { color: (TEXT_MUTED), text_style: { font_size: 11.0 } }
+                                    }
+                                }
+                            }
+                        }
+
+                        // Chat with canvas panel (horizontal layout)
+                        chat_with_canvas = <View> {
+                            width: Fill, height: Fill
+                            flow: Right
+                            visible: true
+
+                            // Left: Chat app (fills remaining space)
+                            chat_app = <ChatApp> {
+                                width: Fill, height: Fill
+                            }
+
+                            // Middle: Splitter (resizable divider)
+                            canvas_splitter = <View> {
+                                width: 0, height: Fill  // 0 when collapsed, 16 when expanded
+                                cursor: ColResize
+                                show_bg: true
+                                draw_bg: {
+                                    fn pixel(self) -> vec4 {
+                                        let sdf = Sdf2d::viewport(self.pos * self.rect_size);
                                         // Draw thin line in center
                                         sdf.rect(7.0, 16.0, 2.0, self.rect_size.y - 32.0);
                                         sdf.fill(#d1d5db);
@@ -922,7 +1408,7 @@ live_design! {
                                     <Label> {
                                         text: "›"
                                         draw_text: {
-                                            color: #9ca3af
+                                            color: (TEXT_MUTED)
                                             text_style: { font_size: 18.0 }
                                         }
                                     }
@@ -978,7 +1464,7 @@ live_design! {
                                 <Label> {
                                     text: "‹"
                                     draw_text: {
-                                        color: #9ca3af
+                                        color: (TEXT_MUTED)
                                         text_style: { font_size: 18.0 }
                                     }
                                 }
@@ -1015,6 +1501,14 @@ live_design! {
                             hub_category: 6.0
                             visible: false
                         }
+                        music_hub_app = <ModelHubApp> {
+                            hub_category: 7.0
+                            visible: false
+                        }
+                        rerank_hub_app = <ModelHubApp> {
+                            hub_category: 8.0
+                            visible: false
+                        }
 
                         // MCP app (desktop only)
                         mcp_app = <McpApp> {
@@ -1107,13 +1601,23 @@ live_design! {
                             about_video_body = <Label> { width: Fill, height: Fit, margin: {bottom: 10}
                                 draw_text: { color: #374151, text_style: { font_size: 11.5 }, wrap: Word } }
 
+                            about_music_header = <Label> { width: Fill, height: Fit, margin: {bottom: 2}
+                                draw_text: { color: #9a3412, text_style: <FONT_SEMIBOLD>{ font_size: 12.0 } } }
+                            about_music_body = <Label> { width: Fill, height: Fit, margin: {bottom: 10}
+                                draw_text: { color: #374151, text_style: { font_size: 11.5 }, wrap: Word } }
+
+                            about_rerank_header = <Label> { width: Fill, height: Fit, margin: {bottom: 2}
+                                draw_text: { color: #115e59, text_style: <FONT_SEMIBOLD>{ font_size: 12.0 } } }
+                            about_rerank_body = <Label> { width: Fill, height: Fit, margin: {bottom: 10}
+                                draw_text: { color: #374151, text_style: { font_size: 11.5 }, wrap: Word } }
+
                             // Footer
                             <Label> {
                                 width: Fill, height: Fit
                                 margin: {top: 6}
                                 text: "Models are downloaded from Hugging Face on first use and cached locally. All inference runs on-device via GGUF quantized weights on Apple Silicon."
                                 draw_text: {
-                                    color: #9ca3af
+                                    color: (TEXT_MUTED)
                                     text_style: { font_size: 10.5 }
                                     wrap: Word
                                 }
@@ -1172,7 +1676,7 @@ live_design! {
                                 dropdown_status_label = <Label> {
                                     text: ""
                                     draw_text: {
-                                        color: #9ca3af
+                                        color: (TEXT_MUTED)
                                         text_style: { font_size: 11.0 }
                                     }
                                 }
@@ -1238,8 +1742,8 @@ live_design! {
                                     }
                                 }
 
-                                <Label> {
-                                    text: "This will permanently remove the model files from disk."
+                                confirm_detail = <Label> {
+                                    text: "The model files will be moved to the Trash."
                                     draw_text: {
                                         color: #6b7280
                                         text_style: { font_size: 12.0 }
@@ -1319,13 +1823,187 @@ live_design! {
                     }
 
                 }
+                // ── Profile switcher overlay ────────────────────────────────
+                profile_dropdown = <View> {
+                    abs_pos: vec2(0.0, 0.0)
+                    width: Fill, height: Fill
+                    flow: Overlay
+                    visible: false
+
+                    // Full-screen dismiss area (behind the panel)
+                    profile_dismiss_area = <View> {
+                        width: Fill, height: Fill
+                        cursor: Arrow
+                    }
+
+                    profile_wrapper = <View> {
+                        width: Fill, height: Fit
+                        flow: Right
+                        align: {x: 1.0}
+                        margin: {top: 72, right: 20}
+
+                        profile_panel = <RoundedView> {
+                            width: 320, height: Fit
+                            show_bg: true
+                            draw_bg: {
+                                color: #ffffff
+                                border_radius: 12.0
+                                border_color: #d1d5db
+                                border_size: 1.0
+                            }
+                            flow: Down
+
+                            <View> {
+                                width: Fill, height: 44
+                                align: {y: 0.5}
+                                padding: {left: 16, right: 16}
+                                <Label> {
+                                    text: "Profiles"
+                                    draw_text: {
+                                        color: #1f2937
+                                        text_style: <FONT_SEMIBOLD>{ font_size: 14.0 }
+                                    }
+                                }
+                            }
+
+                            <View> { width: Fill, height: 1, show_bg: true, draw_bg: { color: #e5e7eb } }
+
+                            profile_slot_0 = <ProfileDropdownSlot> {}
+                            profile_slot_1 = <ProfileDropdownSlot> {}
+                            profile_slot_2 = <ProfileDropdownSlot> {}
+                            profile_slot_3 = <ProfileDropdownSlot> {}
+                            profile_slot_4 = <ProfileDropdownSlot> {}
+                            profile_slot_5 = <ProfileDropdownSlot> {}
+                            profile_slot_6 = <ProfileDropdownSlot> {}
+                            profile_slot_7 = <ProfileDropdownSlot> {}
+
+                            <View> { width: Fill, height: 1, show_bg: true, draw_bg: { color: #e5e7eb } }
+
+                            // New profile row
+                            <View> {
+                                width: Fill, height: Fit
+                                flow: Right
+                                align: {y: 0.5}
+                                padding: {left: 12, right: 12, top: 10, bottom: 6}
+                                spacing: 8
+
+                                new_profile_input = <TextInput> {
+                                    width: Fill, height: 32
+                                    empty_text: "New profile name"
+                                }
+                                create_profile_btn = <ProfileActionButton> {
+                                    <Label> {
+                                        text: "Create"
+                                        draw_text: { color: #374151, text_style: <FONT_MEDIUM>{ font_size: 12.0 } }
+                                    }
+                                }
+                            }
+                            profile_error_label = <Label> {
+                                width: Fill
+                                margin: {left: 16, right: 16}
+                                text: ""
+                                draw_text: { color: #dc2626, text_style: { font_size: 11.0 }, wrap: Word }
+                            }
+
+                            // Startup options for the active profile
+                            <View> {
+                                width: Fill, height: Fit
+                                flow: Right
+                                align: {y: 0.5}
+                                padding: {left: 12, right: 12, top: 6, bottom: 12}
+                                spacing: 8
+
+                                set_default_profile_btn = <ProfileActionButton> {
+                                    set_default_label = <Label> {
+                                        text: "Open at startup"
+                                        draw_text: { color: #374151, text_style: <FONT_MEDIUM>{ font_size: 12.0 } }
+                                    }
+                                }
+                                ask_at_startup_btn = <ProfileActionButton> {
+                                    ask_at_startup_label = <Label> {
+                                        text: "Ask at startup: Off"
+                                        draw_text: { color: #374151, text_style: <FONT_MEDIUM>{ font_size: 12.0 } }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+
+                // ── Clipboard quick actions popover ─────────────────────────
+                clipboard_popover = <View> {
+                    abs_pos: vec2(0.0, 0.0)
+                    width: Fill, height: Fill
+                    align: {x: 1.0, y: 1.0}
+                    padding: {right: 24, bottom: 24}
+                    visible: false
+
+                    clipboard_card = <RoundedView> {
+                        width: 360, height: Fit
+                        flow: Down
+                        padding: 14
+                        spacing: 8
+                        show_bg: true
+                        draw_bg: {
+                            color: #ffffff
+                            border_radius: 12.0
+                            border_color: #d1d5db
+                            border_size: 1.0
+                        }
+
+                        <Label> {
+                            text: "Copied text"
+                            draw_text: { color: #1f2937, text_style: <FONT_SEMIBOLD>{ font_size: 12.0 } }
+                        }
+                        clipboard_excerpt = <Label> {
+                            width: Fill
+                            text: ""
+                            draw_text: { color: #6b7280, text_style: { font_size: 11.0 }, wrap: Word }
+                        }
+                        <View> {
+                            width: Fill, height: Fit
+                            flow: Right
+                            spacing: 6
+                            align: {y: 0.5}
+
+                            clipboard_summarize_btn = <ProfileActionButton> {
+                                <Label> { text: "Summarize", draw_text: { color: #374151, text_style: <FONT_MEDIUM>{ font_size: 12.0 } } }
+                            }
+                            clipboard_translate_btn = <ProfileActionButton> {
+                                <Label> { text: "Translate", draw_text: { color: #374151, text_style: <FONT_MEDIUM>{ font_size: 12.0 } } }
+                            }
+                            clipboard_ask_btn = <ProfileActionButton> {
+                                <Label> { text: "Ask about this", draw_text: { color: #374151, text_style: <FONT_MEDIUM>{ font_size: 12.0 } } }
+                            }
+                            <View> { width: Fill, height: 1 }
+                            clipboard_dismiss_btn = <ProfileActionButton> {
+                                <Label> { text: "Dismiss", draw_text: { color: #6b7280, text_style: <FONT_MEDIUM>{ font_size: 12.0 } } }
+                            }
+                        }
+                    }
+                }
+
+                // ── Confirmation and input dialogs (above everything) ───────
+                dialog = <MolyDialog> {}
             }
         }
     }
 }
 
+/// Shortest query searched by meaning; shorter ones match too loosely
+const MIN_SEMANTIC_QUERY_CHARS: usize = 4;
+
+/// Chats related by meaning added to the history tiles
+const SEMANTIC_RESULTS: usize = 8;
+
 // ── Model selector types ──────────────────────────────────────────────────────
 
+/// Profiles listed in the header's profile dropdown
+const PROFILE_SLOTS: usize = 8;
+
+/// How long the clipboard popover stays up when left alone
+const CLIPBOARD_POPOVER_SECS: u64 = 12;
+
 #[derive(Clone, Copy, PartialEq, Default, Debug)]
 enum ShellModelLoadState {
     #[default]
@@ -1335,6 +2013,12 @@ enum ShellModelLoadState {
     Error,
 }
 
+/// Progress of the startup preload, sent by its thread
+enum PreloadProgress {
+    Event(LoadEvent),
+    Done { cancelled: bool },
+}
+
 #[derive(Clone, Debug)]
 struct DownloadedModelEntry {
     registry_id:      String,
@@ -1345,6 +2029,7 @@ struct DownloadedModelEntry {
     size_display:     String,
     local_path:       String,
     supports_images:  bool,
+    memory_gb:        f32,
 }
 
 fn category_to_model_type(cat: RegistryCategory) -> &'static str {
@@ -1355,9 +2040,22 @@ fn category_to_model_type(cat: RegistryCategory) -> &'static str {
         RegistryCategory::Tts      => "tts",
         RegistryCategory::ImageGen => "image",
         RegistryCategory::VideoGen => "video",
+        RegistryCategory::MusicGen => "music",
+        RegistryCategory::Reranker => "rerank",
     }
 }
 
+/// Tell the other apps about a load state change of a model the shell does
+/// not show in its selector yet
+fn publish_model_phase(cx: &mut Cx, model: &RegistryModel, phase: ModelLoadPhase) {
+    cx.action(StoreEvent::ModelLoadStateChanged {
+        model_id: model.id.clone(),
+        model_name: model.name.clone(),
+        category: model.category,
+        phase,
+    });
+}
+
 fn registry_category_as_f64(cat: RegistryCategory) -> f64 {
     match cat {
         RegistryCategory::Llm      => 0.0,
@@ -1366,53 +2064,11 @@ fn registry_category_as_f64(cat: RegistryCategory) -> f64 {
         RegistryCategory::Tts      => 3.0,
         RegistryCategory::ImageGen => 4.0,
         RegistryCategory::VideoGen => 5.0,
+        RegistryCategory::MusicGen => 6.0,
+        RegistryCategory::Reranker => 7.0,
     }
 }
 
-/// Check if a registry model's files are present on disk.
-///
-/// Multiple GGUF quant variants (e.g. q4km vs q8) share the same HuggingFace
-/// repo directory.  To avoid false positives we compare each `.gguf` file's
-/// actual size against the model's expected `size_bytes`.
-fn shell_is_model_downloaded(model: &moly_data::RegistryModel) -> bool {
-    let expanded = model.storage.expanded_path();
-    let path = Path::new(&expanded);
-    if !path.exists() { return false; }
-    if model.storage.size_bytes > 100 * 1024 * 1024 {
-        return has_weight_files_shell(path, model.storage.size_bytes);
-    }
-    std::fs::read_dir(path)
-        .map(|e| e.filter_map(|x| x.ok())
-             .filter(|x| !x.file_name().to_string_lossy().starts_with('.')).count())
-        .unwrap_or(0) > 0
-}
-
-fn has_weight_files_shell(dir: &Path, expected_size: u64) -> bool {
-    if let Ok(entries) = std::fs::read_dir(dir) {
-        for e in entries.flatten() {
-            let n = e.file_name();
-            let name = n.to_string_lossy();
-            if name.ends_with(".gguf") {
-                // GGUF: single file per quant variant — verify size matches
-                // (follows symlinks, which HF cache uses in snapshots/)
-                if expected_size > 0 {
-                    let actual = std::fs::metadata(e.path()).map(|m| m.len()).unwrap_or(0);
-                    let tolerance = expected_size / 20; // 5%
-                    if (actual as i64 - expected_size as i64).unsigned_abs() <= tolerance {
-                        return true;
-                    }
-                } else {
-                    return true;
-                }
-            } else if name.ends_with(".safetensors") || name.ends_with(".bin") {
-                return true;
-            }
-            if e.path().is_dir() && has_weight_files_shell(&e.path(), expected_size) { return true; }
-        }
-    }
-    false
-}
-
 // ─────────────────────────────────────────────────────────────────────────────
 
 #[derive(Clone, Copy, Debug, PartialEq, Default)]
@@ -1422,6 +2078,8 @@ enum NavigationTarget {
     ChatHistory,
     /// Active chat - shows the chat interface
     ActiveChat,
+    /// Bookmarked messages across chats
+    Bookmarks,
     Settings,
     LlmHub,
     VlmHub,
@@ -1429,13 +2087,49 @@ enum NavigationTarget {
     TtsHub,
     ImageHub,
     VideoHub,
+    MusicHub,
+    RerankHub,
     About,
 }
 
+/// The Quick Ask window and the question it is answering
+struct QuickAsk {
+    window: WidgetRef,
+    /// Platform id of the window, learned from its first geometry event
+    window_id: Option<WindowId>,
+    /// The loaded LLM when the window opened
+    model: Option<RegistryModel>,
+    question: String,
+    answer: String,
+    rx: Option<mpsc::Receiver<AskEvent>>,
+}
+
+/// Quitting once the work in flight is done
+struct PendingQuit {
+    /// When stopped work has to be done by; `None` waits for it to finish
+    deadline: Option<std::time::Instant>,
+    /// Reports when the runtime has unloaded its models
+    unload_rx: Option<mpsc::Receiver<()>>,
+}
+
+/// A chat opened in its own window
+struct DetachedChat {
+    chat_id: ChatId,
+    window: WidgetRef,
+    /// Platform id of the window, learned from its first geometry event
+    window_id: Option<WindowId>,
+}
+
 #[derive(Live)]
 pub struct App {
     #[live]
     ui: WidgetRef,
+    /// Template for chats opened in their own window
+    #[live]
+    detached_chat_window: Option<LivePtr>,
+    /// Template for the Quick Ask window
+    #[live]
+    quick_ask_window: Option<LivePtr>,
     #[rust]
     store: Store,
     #[rust]
@@ -1453,6 +2147,15 @@ pub struct App {
     /// Current search query for filtering chat history
     #[rust]
     search_query: String,
+    /// Starts the search by meaning once typing pauses
+    #[rust]
+    semantic_timer: Timer,
+    /// Result of the search by meaning, with the query it was for
+    #[rust]
+    semantic_rx: Option<mpsc::Receiver<(String, Result<Vec<Hit>, String>)>>,
+    /// Chats related to `search_query` by meaning, closest first
+    #[rust]
+    semantic_hits: Vec<Hit>,
     /// Whether the canvas panel is collapsed
     #[rust]
     canvas_panel_collapsed: bool,
@@ -1477,6 +2180,21 @@ pub struct App {
     /// Chat IDs shown in the sidebar history sublist (up to 6)
     #[rust]
     sidebar_chat_ids: Vec<moly_data::ChatId>,
+    /// Chat waiting for the delete confirmation dialog
+    #[rust]
+    pending_delete_chat: Option<ChatId>,
+    /// Chat waiting for a name in the save-as-template dialog
+    #[rust]
+    pending_template_chat: Option<ChatId>,
+    /// Chat waiting for its new title in the rename dialog
+    #[rust]
+    pending_rename_chat: Option<ChatId>,
+    /// Bookmarks shown on the Bookmarks page as (chat, message index)
+    #[rust]
+    displayed_bookmarks: Vec<(ChatId, usize)>,
+    /// Search query of the Bookmarks page, lowercase
+    #[rust]
+    bookmark_query: String,
 
     // ── Model-selector state ────────────────────────────────────────────────
     /// Whether the model-selector dropdown is currently open
@@ -1507,7 +2225,86 @@ pub struct App {
     #[rust]
     delete_confirm_index: Option<usize>,
 
+    // ── Startup preload state ───────────────────────────────────────────────
+    /// Progress of the models loaded at startup, while they load
+    #[rust]
+    preload_rx: Option<mpsc::Receiver<PreloadProgress>>,
+    /// Tells the preload thread to start no more loads
+    #[rust]
+    preload_cancel: Arc<AtomicBool>,
+    /// Models the preload is loading
+    #[rust]
+    preload_loading: Vec<RegistryModel>,
+
+    // ── Profile state ───────────────────────────────────────────────────────
+    /// Profile list and startup options
+    #[rust]
+    profiles: Profiles,
+    /// Whether the profile dropdown is currently open
+    #[rust]
+    profile_menu_open: bool,
+    /// Open the profile picker once the UI is up ("Ask at startup")
+    #[rust]
+    profile_picker_pending: bool,
+
+    /// Keeps model download state in sync with the model directories
+    #[rust]
+    model_watcher: Option<ModelWatcher>,
+
+    // ── Clipboard quick actions ─────────────────────────────────────────────
+    /// Running while clipboard quick actions are turned on
+    #[rust]
+    clipboard_watcher: Option<ClipboardWatcher>,
+    /// Text offered in the popover, and when it was shown
+    #[rust]
+    clipboard_offer: Option<(String, std::time::Instant)>,
+
+    // ── Quick Ask ───────────────────────────────────────────────────────────
+    /// Hears the Quick Ask shortcut when another process runs it
+    #[rust]
+    quick_ask_listener: Option<SummonListener>,
+    #[rust]
+    quick_ask: Option<QuickAsk>,
+
+    // ── Chat backup state ───────────────────────────────────────────────────
+    /// Checks whether an automatic chat snapshot is due
+    #[rust]
+    backup_timer: Timer,
+    /// Snapshot being written in the background
+    #[rust]
+    backup_thread: Option<std::thread::JoinHandle<()>>,
+
+    // ── Window state ────────────────────────────────────────────────────────
+    /// Main window geometry, saved on the next timer tick when it changed
+    #[rust]
+    window_state: Option<WindowState>,
+    #[rust]
+    window_state_dirty: bool,
+    /// Chats torn off into their own windows
+    #[rust]
+    detached_chats: Vec<DetachedChat>,
+
+    // ── Quit state ──────────────────────────────────────────────────────────
+    /// Set while waiting for work, or for models to unload, before quitting
+    #[rust]
+    pending_quit: Option<PendingQuit>,
+    /// Set when the app is about to quit, so closing goes through
+    #[rust]
+    quitting: bool,
+
+    // ── Low power mode ──────────────────────────────────────────────────────
+    /// Follows the power source for the battery side of low power mode
+    #[rust]
+    battery_watcher: Option<BatteryWatcher>,
+    /// Whether timers currently run at low power intervals
+    #[rust]
+    low_power: bool,
+    /// Model to load once the low power warning is confirmed
+    #[rust]
+    pending_power_load: Option<DownloadedModelEntry>,
+
     // ── RAM gauge state ─────────────────────────────────────────────────────
+    /// Drives the RAM gauge and other background checks; slower in low power mode
     #[rust]
     ram_timer: Timer,
     #[rust]
@@ -1516,11 +2313,33 @@ pub struct App {
     ram_used_gb: f64,
     #[rust]
     ram_total_gb: f64,
+
+    /// Last values written to header widgets that refresh on a timer
+    #[rust]
+    ui_cache: UiStateCache,
 }
 
 impl LiveHook for App {
     fn after_new_from_doc(&mut self, _cx: &mut Cx) {
         if !self.initialized {
+            // Pick the profile before loading the Store: `--profile <name>`
+            // wins over the saved default
+            self.profiles = Profiles::load();
+            let requested = std::env::args().skip_while(|a| a != "--profile").nth(1);
+            let profile_id = match requested.as_deref().and_then(|p| self.profiles.find(p)) {
+                Some(profile) => profile.id.clone(),
+                None => {
+                    if let Some(p) = &requested {
+                        ::log::warn!("Unknown profile {:?}, using the default", p);
+                    }
+                    self.profiles.startup_profile().to_string()
+                }
+            };
+            profiles::set_active_profile(&profile_id);
+            self.profile_picker_pending = requested.is_none()
+                && self.profiles.ask_at_startup
+                && self.profiles.profiles.len() > 1;
+
             // Load Store from disk (this is called after Makepad creates the struct)
             self.store = Store::load();
 
@@ -1528,12 +2347,15 @@ impl LiveHook for App {
             self.current_view = match self.store.current_view() {
                 "Settings"  => NavigationTarget::Settings,
                 "ActiveChat" => NavigationTarget::ActiveChat,
+                "Bookmarks" => NavigationTarget::Bookmarks,
                 "LlmHub"   => NavigationTarget::LlmHub,
                 "VlmHub"   => NavigationTarget::VlmHub,
                 "AsrHub"   => NavigationTarget::AsrHub,
                 "TtsHub"   => NavigationTarget::TtsHub,
                 "ImageHub" => NavigationTarget::ImageHub,
                 "VideoHub" => NavigationTarget::VideoHub,
+                "MusicHub" => NavigationTarget::MusicHub,
+                "RerankHub" => NavigationTarget::RerankHub,
                 _ => NavigationTarget::ChatHistory,
             };
 
@@ -1577,28 +2399,132 @@ impl MatchEvent for App {
         self.canvas_panel_width = 500.0;
         self.canvas_panel_collapsed = true;
 
-        // Start RAM usage polling (every 1 second)
-        self.ram_timer = cx.start_interval(1.0);
+        self.restore_window_state(cx);
+
+        // Start RAM usage polling (every 1 second, less often in low power mode)
+        self.low_power = power::is_active();
+        self.ram_timer = cx.start_interval(power::interval(1.0));
         self.poll_ram_usage(cx);
+        self.battery_watcher = Some(BatteryWatcher::start());
 
-        ::log::info!("App initialized with Store and MolyAppData");
-    }
+        // Scan the registry models once, then follow their directories
+        let registry = ModelRegistry::load();
+        let mut watcher = ModelWatcher::new();
+        watcher.watch_registry(&registry);
+        self.model_watcher = Some(watcher);
 
-    fn handle_actions(&mut self, cx: &mut Cx, actions: &Actions) {
-        // ── Model selector pill click ───────────────────────────────────────
-        if self.ui.view(ids!(body.body_layout.header.model_selector_btn)).finger_down(&actions).is_some() {
-            if self.selector_open {
-                self.close_selector(cx);
-            } else {
-                self.open_selector(cx);
-            }
-        }
+        self.start_preload(cx, &registry);
+
+        // Voice Studio's training queue runs whether or not a studio is open
+        moly_data::voice_training::start();
+
+        self.apply_lan_sharing();
+        self.apply_api_proxy();
+
+        match SummonListener::start() {
+            Ok(listener) => self.quick_ask_listener = Some(listener),
+            Err(e) => ::log::warn!("{}", e),
+        }
+        if std::env::args().any(|a| a == quick_ask::FLAG) {
+            self.open_quick_ask(cx);
+        }
+        if let Some(link) = chat_navigation::link_arg() {
+            self.open_link(cx, &link);
+        }
+        // Let message links open the app
+        if let Ok(exe) = std::env::current_exe() {
+            std::thread::spawn(move || {
+                if let Err(e) = platform::register_url_scheme(chat_navigation::SCHEME, &[exe.display().to_string()]) {
+                    ::log::debug!("Message links won't open the app: {}", e);
+                }
+            });
+        }
+
+        // Finish removals that were still inside their undo window at last quit
+        let model_dirs: std::collections::BTreeSet<std::path::PathBuf> = registry.models.iter()
+            .filter_map(|m| std::path::Path::new(&m.storage.expanded_path()).parent().map(|p| p.to_path_buf()))
+            .collect();
+        let hard_delete = self.store.preferences.hard_delete_models;
+        // Also remove partial downloads from earlier sessions, if enabled in Settings
+        let cleanup_registry = self.store.preferences.downloads.cleanup_partial_on_start.then(|| registry.clone());
+        std::thread::spawn(move || {
+            for dir in model_dirs {
+                model_trash::sweep_staged(&dir, hard_delete);
+            }
+            for orphan in cleanup_registry.map(|r| model_integrity::find_orphans(&r)).unwrap_or_default() {
+                if let Err(e) = model_integrity::delete_orphan(&orphan, hard_delete) {
+                    ::log::warn!("Startup cleanup failed: {}", e);
+                }
+            }
+            // Generated files of earlier sessions past their retention
+            let removed = artifacts::sweep_old();
+            if removed > 0 {
+                ::log::info!("Removed generated files of {} earlier session(s)", removed);
+            }
+        });
+
+        // Snapshot chat history now if one is due, then re-check every 10 minutes
+        self.backup_timer = cx.start_interval(600.0);
+        self.backup_chats_if_due();
+
+        self.update_profile_label(cx);
+        if self.profile_picker_pending {
+            self.profile_picker_pending = false;
+            self.open_profile_menu(cx);
+        }
+
+        ::log::info!("App initialized with Store and MolyAppData");
+    }
+
+    fn handle_actions(&mut self, cx: &mut Cx, actions: &Actions) {
+        // ── Profile switcher ────────────────────────────────────────────────
+        if self.ui.view(ids!(body.body_layout.header.profile_btn)).finger_down(&actions).is_some() {
+            if self.profile_menu_open {
+                self.close_profile_menu(cx);
+            } else {
+                self.open_profile_menu(cx);
+            }
+        }
+        if self.profile_menu_open {
+            self.handle_profile_menu_actions(cx, actions);
+        }
+        if self.clipboard_offer.is_some() {
+            self.handle_clipboard_actions(cx, actions);
+        }
+        if self.quick_ask.is_some() {
+            self.handle_quick_ask_actions(cx, actions);
+        }
+
+        // ── Model selector pill click ───────────────────────────────────────
+        if self.ui.view(ids!(body.body_layout.header.model_selector_btn)).finger_down(&actions).is_some() {
+            if self.selector_open {
+                self.close_selector(cx);
+            } else {
+                self.open_selector(cx);
+            }
+        }
+
+        // ── Startup preload cancel ─────────────────────────────────────────
+        if self.ui.view(ids!(body.body_layout.header.preload_status.preload_cancel_btn)).finger_down(&actions).is_some() {
+            self.cancel_preload(cx);
+        }
 
         // ── Eject / unload button click ────────────────────────────────────
         if self.ui.view(ids!(body.body_layout.header.eject_btn)).finger_down(&actions).is_some() {
             self.start_unload_model(cx);
         }
 
+        // ── Detached chat windows ───────────────────────────────────────────
+        if self.ui.view(ids!(body.body_layout.header.detach_chat_btn)).finger_down(&actions).is_some() {
+            self.open_chat_in_window(cx);
+        }
+        let reattach = self.detached_chats.iter()
+            .find(|d| d.window.view(ids!(body.detached_header.reattach_btn)).finger_down(&actions).is_some())
+            .map(|d| d.chat_id);
+        if let Some(chat_id) = reattach {
+            self.reattach_chat(cx, chat_id);
+        }
+
         // ── Dropdown: click-outside dismiss area ───────────────────────────
         if self.selector_open {
             if self.ui.view(ids!(body.model_selector_dropdown.dismiss_area)).finger_down(&actions).is_some() {
@@ -1609,7 +2535,7 @@ impl MatchEvent for App {
             if self.ui.view(ids!(body.model_selector_dropdown.dropdown_wrapper.dropdown_panel.dropdown_header.open_finder_btn)).finger_down(&actions).is_some() {
                 let home = std::env::var("HOME").unwrap_or_default();
                 let models_dir = format!("{}/.OminiX/models", home);
-                let _ = std::process::Command::new("open").arg(&models_dir).spawn();
+                let _ = platform::open_path(&models_dir);
             }
 
             // ── Delete confirmation buttons ─────────────────────────────────
@@ -1756,6 +2682,10 @@ impl MatchEvent for App {
             self.navigate_to(cx, NavigationTarget::ChatHistory);
         }
 
+        if self.ui.view(ids!(body.body_layout.content.sidebar.sidebar_scroll.bookmarks_btn)).finger_down(&actions).is_some() {
+            self.navigate_to(cx, NavigationTarget::Bookmarks);
+        }
+
         // Handle Show More button click
         if self.ui.view(ids!(body.body_layout.content.sidebar.sidebar_scroll.chat_section.chat_history_visible.show_more_btn)).finger_down(&actions).is_some() {
             self.chat_history_expanded = !self.chat_history_expanded;
@@ -1779,6 +2709,12 @@ impl MatchEvent for App {
         if self.ui.view(ids!(body.body_layout.content.sidebar.sidebar_scroll.video_btn)).finger_down(&actions).is_some() {
             self.navigate_to(cx, NavigationTarget::VideoHub);
         }
+        if self.ui.view(ids!(body.body_layout.content.sidebar.sidebar_scroll.music_btn)).finger_down(&actions).is_some() {
+            self.navigate_to(cx, NavigationTarget::MusicHub);
+        }
+        if self.ui.view(ids!(body.body_layout.content.sidebar.sidebar_scroll.rerank_btn)).finger_down(&actions).is_some() {
+            self.navigate_to(cx, NavigationTarget::RerankHub);
+        }
         if self.ui.view(ids!(body.body_layout.content.sidebar.sidebar_scroll.settings_btn)).finger_down(&actions).is_some() {
             ::log::info!(">>> Settings button clicked! <<<");
             self.navigate_to(cx, NavigationTarget::Settings);
@@ -1811,6 +2747,8 @@ impl MatchEvent for App {
 
             if let Some(idx) = sidebar_clicked {
                 let chat_id = self.sidebar_chat_ids[idx];
+                // A chat is only open in one window at a time
+                self.close_detached_chat(chat_id);
                 self.store.chats.set_current_chat(Some(chat_id));
                 if let Some(mut chat_app) = self.ui.widget(ids!(body.body_layout.content.main_content.chat_with_canvas.chat_app))
                     .borrow_mut::<moly_chat::screen::ChatApp>()
@@ -1825,13 +2763,20 @@ impl MatchEvent for App {
 
         // Handle chat tile clicks
         self.handle_chat_tile_clicks(cx, actions);
+        self.handle_bookmark_clicks(cx, actions);
+        self.handle_dialog_results(cx, actions);
 
         // Handle search input changes
         let search_input = self.ui.text_input(ids!(body.body_layout.content.main_content.chat_history_page.search_container.search_input));
         if search_input.changed(&actions).is_some() {
             self.search_query = search_input.text();
+            self.schedule_semantic_search(cx);
             self.update_chat_tiles(cx);
         }
+        if let Some(query) = self.ui.text_input(ids!(body.body_layout.content.main_content.bookmarks_page.bookmark_search_input)).changed(&actions) {
+            self.bookmark_query = query.trim().to_lowercase();
+            self.update_bookmarks(cx);
+        }
 
         // Handle canvas reopen strip (shown when canvas is collapsed)
         if self.ui.view(ids!(body.body_layout.content.main_content.chat_with_canvas.canvas_reopen_btn)).finger_down(&actions).is_some() {
@@ -1862,6 +2807,7 @@ impl MatchEvent for App {
                 let target = match view.as_str() {
                     "ActiveChat"  => Some(NavigationTarget::ActiveChat),
                     "ChatHistory" => Some(NavigationTarget::ChatHistory),
+                    "Bookmarks"   => Some(NavigationTarget::Bookmarks),
                     "Settings"    => Some(NavigationTarget::Settings),
                     "LlmHub"   => Some(NavigationTarget::LlmHub),
                     "VlmHub"   => Some(NavigationTarget::VlmHub),
@@ -1869,6 +2815,8 @@ impl MatchEvent for App {
                     "TtsHub"   => Some(NavigationTarget::TtsHub),
                     "ImageHub" => Some(NavigationTarget::ImageHub),
                     "VideoHub" => Some(NavigationTarget::VideoHub),
+                    "MusicHub" => Some(NavigationTarget::MusicHub),
+                    "RerankHub" => Some(NavigationTarget::RerankHub),
                     _ => None,
                 };
                 if let Some(t) = target {
@@ -1876,35 +2824,31 @@ impl MatchEvent for App {
                     self.navigate_to(cx, t);
                 }
             }
-            // Handle hub load/unload notifications — sync the top model selector bar
-            if let StoreAction::HubModelLoaded { model_id, model_name, category } = action.cast() {
-                ::log::info!("HubModelLoaded: {} ({})", model_name, model_id);
-                self.loaded_model_id      = model_id;
-                self.loaded_model_name    = model_name;
-                self.loaded_model_category = Some(category);
-                self.shell_load_state     = ShellModelLoadState::Loaded;
-                self.load_rx              = None; // clear any shell-level load
-                self.update_selector_bar(cx);
-                self.refresh_downloaded_models();
-            }
-            if let StoreAction::HubModelUnloaded { model_id } = action.cast() {
-                // Only clear if the unloaded model matches what the shell shows
-                if self.loaded_model_id == model_id {
-                    ::log::info!("HubModelUnloaded: {}", model_id);
-                    self.loaded_model_id       = String::new();
-                    self.loaded_model_name     = String::new();
-                    self.loaded_model_category = None;
-                    self.shell_load_state      = ShellModelLoadState::Unloaded;
-                    self.update_selector_bar(cx);
-                    self.refresh_downloaded_models();
-                }
+            // Handle "Open in Chat", "Open Transcriber" and "Open Speaker" from Model Hub —
+            // create a new session with the selected model; its category picks the chat mode
+            let open_with = match action.cast() {
+                StoreAction::OpenChatWithModel { model_id, category } => Some((model_id, category)),
+                StoreAction::OpenTranscriber { model_id } => Some((model_id, RegistryCategory::Asr)),
+                StoreAction::OpenSpeaker { model_id } => Some((model_id, RegistryCategory::Tts)),
+                _ => None,
+            };
+            if let StoreAction::OpenChat { chat_id, message_index } = action.cast() {
+                self.open_chat(cx, chat_id, message_index);
+            }
+            if let StoreAction::LoadLocalModel { model_id } = action.cast() {
+                self.load_model_by_id(cx, &model_id);
+            }
+            if let StoreAction::SetPowerSettings(settings) = action.cast() {
+                self.store.handle_action(&StoreAction::SetPowerSettings(settings));
+                self.apply_power_mode(cx);
             }
-            // Handle "Open in Chat" from Model Hub — create new chat with the selected model
-            if let StoreAction::OpenChatWithModel { model_id, category } = action.cast() {
-                ::log::info!(">>> OpenChatWithModel: {} ({:?}) <<<", model_id, category);
+            if let Some((model_id, category)) = open_with {
+                ::log::info!(">>> Open session with model: {} ({:?}) <<<", model_id, category);
                 // Set category BEFORE injecting model (capabilities depend on category)
                 self.store.set_active_local_model_category(Some(category));
                 self.store.set_active_local_model(Some(model_id.clone()));
+                // The new chat takes the system prompt preset for this kind of model
+                self.store.set_pending_chat_model(model_id.clone(), category);
                 // Request a new chat session
                 if let Some(mut chat_app) = self.ui.widget(ids!(body.body_layout.content.main_content.chat_with_canvas.chat_app))
                     .borrow_mut::<moly_chat::screen::ChatApp>()
@@ -1916,6 +2860,46 @@ impl MatchEvent for App {
             }
         }
 
+        // Store events: mirror into the Store, then sync the top model selector bar
+        for event in StoreEvent::iter(actions) {
+            self.store.handle_event(&event);
+            match event {
+                StoreEvent::ModelLoadStateChanged { model_id, model_name, category, phase } => {
+                    self.handle_model_load_event(cx, model_id, model_name, category, phase);
+                }
+                StoreEvent::DownloadCompleted { model_id } => {
+                    ::log::info!("Download completed: {}", model_id);
+                    self.refresh_downloaded_models();
+                    if self.selector_open { self.update_dropdown_slots(cx); }
+                }
+                StoreEvent::ModelStateChanged { .. } => {
+                    if self.selector_open {
+                        self.refresh_downloaded_models();
+                        self.update_dropdown_slots(cx);
+                    }
+                }
+                StoreEvent::ThemeChanged { dark_mode } => {
+                    for detached in &self.detached_chats {
+                        apply_detached_theme(cx, &detached.window, dark_mode);
+                    }
+                    if let Some(quick) = &self.quick_ask {
+                        apply_quick_ask_theme(cx, &quick.window, dark_mode);
+                    }
+                }
+                StoreEvent::WorkspaceImported => self.reload_store(cx),
+                StoreEvent::ChatsRestored => {
+                    if let Some(mut chat_app) = self.ui
+                        .widget(ids!(body.body_layout.content.main_content.chat_with_canvas.chat_app))
+                        .borrow_mut::<moly_chat::screen::ChatApp>()
+                    {
+                        chat_app.reload_chats();
+                    }
+                    self.update_sidebar_chats(cx);
+                }
+                _ => {}
+            }
+        }
+
         // Refresh sidebar when ChatApp creates a new chat (deferred from request_new_chat)
         for action in actions {
             if let moly_chat::screen::ChatHistoryAction::ChatCreated = action.cast() {
@@ -2028,14 +3012,55 @@ impl AppMain for App {
             }
         }
 
-        // Poll RAM usage on timer + refresh sidebar chat titles
+        match event {
+            Event::WindowGeomChange(e) => self.handle_window_geom_change(e),
+            Event::WindowCloseRequested(e) => self.handle_close_requested(cx, e),
+            Event::WindowClosed(e) => {
+                self.detached_chats.retain(|d| d.window_id != Some(e.window_id));
+                if self.quick_ask.as_ref().is_some_and(|q| q.window_id == Some(e.window_id)) {
+                    self.quick_ask = None;
+                }
+            }
+            _ => {}
+        }
+
+        // Poll RAM usage on timer + refresh sidebar chat titles and model directories
         if self.ram_timer.is_event(event).is_some() {
+            self.save_window_state();
             self.poll_ram_usage(cx);
             self.update_sidebar_chats(cx);
+            if let Some(watcher) = &mut self.model_watcher {
+                watcher.poll(cx);
+            }
+            self.poll_clipboard(cx);
+            self.poll_quit(cx);
+        }
+
+        if self.backup_timer.is_event(event).is_some() {
+            self.backup_chats_if_due();
+        }
+
+        if self.semantic_timer.is_event(event).is_some() {
+            self.start_semantic_search(cx);
         }
+        self.poll_semantic_search(cx);
 
         // Poll model load thread for completion
         self.poll_load_result(cx);
+        self.poll_preload(cx);
+
+        let summons = self.quick_ask_listener.as_ref().map(|l| l.poll()).unwrap_or_default();
+        for summon in summons {
+            match summon {
+                Summon::QuickAsk => self.open_quick_ask(cx),
+                Summon::Open(link) => self.open_link(cx, &link),
+            }
+        }
+        if let Some(on_battery) = self.battery_watcher.as_ref().and_then(|w| w.poll()) {
+            power::set_on_battery(on_battery);
+            self.apply_power_mode(cx);
+        }
+        self.poll_quick_ask(cx);
 
         // Pass Store to child widgets via Scope
         // TODO: Migrate apps to use MolyAppData instead of Store directly
@@ -2045,6 +3070,12 @@ impl AppMain for App {
         // processed by match_event's handle_actions
         let scope = &mut Scope::with_data(&mut self.store);
         self.ui.handle_event(cx, event, scope);
+        for detached in &self.detached_chats {
+            detached.window.handle_event(cx, event, scope);
+        }
+        if let Some(quick) = &self.quick_ask {
+            quick.window.handle_event(cx, event, scope);
+        }
 
 
         // Process actions after they've been generated
@@ -2055,11 +3086,11 @@ impl AppMain for App {
 impl App {
     // ── Model selector methods ────────────────────────────────────────────────
 
-    /// Scan the registry for downloaded models and cache the list.
+    /// Cache the registry models that the shared model state lists as downloaded.
     fn refresh_downloaded_models(&mut self) {
         let registry = ModelRegistry::load();
         self.downloaded_models = registry.models.iter()
-            .filter(|m| shell_is_model_downloaded(m))
+            .filter(|m| model_state::registry_download_status(m) == DownloadStatus::Downloaded)
             .map(|m| DownloadedModelEntry {
                 registry_id:     m.id.clone(),
                 name:            m.name.clone(),
@@ -2069,6 +3100,7 @@ impl App {
                 size_display:    m.storage.size_display.clone(),
                 local_path:      m.storage.expanded_path(),
                 supports_images: m.runtime.supports_images,
+                memory_gb:       m.runtime.memory_gb,
             })
             .collect();
         ::log::info!("Model selector: {} downloaded models", self.downloaded_models.len());
@@ -2076,7 +3108,8 @@ impl App {
 
     /// Open the dropdown and populate slots.
     fn open_selector(&mut self, cx: &mut Cx) {
-        if self.shell_load_state == ShellModelLoadState::Loading { return; }
+        // Loading by hand while the preload runs would race it for the runtime
+        if self.shell_load_state == ShellModelLoadState::Loading || self.preload_rx.is_some() { return; }
         self.refresh_downloaded_models();
         self.selector_open = true;
         self.delete_confirm_index = None;
@@ -2111,6 +3144,8 @@ impl App {
             CatInfo { cat: RegistryCategory::Tts,      header_id: &[live_id!(about_tts_header)],   body_id: &[live_id!(about_tts_body)],   title: "TTS \u{2014} Text to Speech" },
             CatInfo { cat: RegistryCategory::ImageGen, header_id: &[live_id!(about_image_header)], body_id: &[live_id!(about_image_body)], title: "Image Generation" },
             CatInfo { cat: RegistryCategory::VideoGen, header_id: &[live_id!(about_video_header)], body_id: &[live_id!(about_video_body)], title: "Video Generation" },
+            CatInfo { cat: RegistryCategory::MusicGen, header_id: &[live_id!(about_music_header)], body_id: &[live_id!(about_music_body)], title: "Music Generation" },
+            CatInfo { cat: RegistryCategory::Reranker, header_id: &[live_id!(about_rerank_header)], body_id: &[live_id!(about_rerank_body)], title: "Rerankers" },
         ];
 
         let page = self.ui.view(ids!(body.body_layout.content.main_content.about_page));
@@ -2192,6 +3227,8 @@ impl App {
                 Some(RegistryCategory::Tts)      => "TTS",
                 Some(RegistryCategory::ImageGen) => "Image",
                 Some(RegistryCategory::VideoGen) => "Video",
+                Some(RegistryCategory::MusicGen) => "Music",
+                Some(RegistryCategory::Reranker) => "Rerank",
                 None => "LLM",
             };
             tag.apply_over(cx, live! { draw_bg: { cat: (cat_val) } });
@@ -2225,6 +3262,8 @@ impl App {
             RegistryCategory::Tts      => (vec4(0.984, 0.929, 0.835, 1.0), vec4(0.573, 0.357, 0.082, 1.0)), // amber
             RegistryCategory::ImageGen => (vec4(0.953, 0.878, 0.957, 1.0), vec4(0.502, 0.145, 0.502, 1.0)), // magenta
             RegistryCategory::VideoGen => (vec4(0.835, 0.918, 0.996, 1.0), vec4(0.114, 0.318, 0.573, 1.0)), // blue
+            RegistryCategory::MusicGen => (vec4(1.000, 0.929, 0.835, 1.0), vec4(0.604, 0.204, 0.071, 1.0)), // orange
+            RegistryCategory::Reranker => (vec4(0.800, 0.984, 0.945, 1.0), vec4(0.067, 0.369, 0.349, 1.0)), // teal
         };
         tag.apply_over(cx, live! { draw_bg: { color: (bg) } });
         label.apply_over(cx, live! { draw_text: { color: (fg) } });
@@ -2279,6 +3318,13 @@ impl App {
         let name = self.downloaded_models[index].name.clone();
         self.ui.label(ids!(body.model_selector_dropdown.dropdown_wrapper.dropdown_panel.delete_confirm_panel.confirm_msg))
             .set_text(cx, &format!("Delete {}?", name));
+        let detail = if self.store.preferences.hard_delete_models {
+            "This will permanently remove the model files from disk."
+        } else {
+            "The model files will be moved to the Trash."
+        };
+        self.ui.label(ids!(body.model_selector_dropdown.dropdown_wrapper.dropdown_panel.delete_confirm_panel.confirm_detail))
+            .set_text(cx, detail);
         self.ui.view(ids!(body.model_selector_dropdown.dropdown_wrapper.dropdown_panel.model_scroll)).set_visible(cx, false);
         self.ui.view(ids!(body.model_selector_dropdown.dropdown_wrapper.dropdown_panel.empty_state)).set_visible(cx, false);
         self.ui.view(ids!(body.model_selector_dropdown.dropdown_wrapper.dropdown_panel.delete_confirm_panel)).set_visible(cx, true);
@@ -2307,16 +3353,18 @@ impl App {
 
         // Optimistically remove from the list
         self.downloaded_models.remove(idx);
+        model_state::set_download_status(cx, &entry.local_path, DownloadStatus::NotDownloaded);
 
-        // Delete files in background
+        // Trash (or delete) files in background
         let path = entry.local_path.clone();
+        let hard_delete = self.store.preferences.hard_delete_models;
         std::thread::spawn(move || {
             let p = std::path::Path::new(&path);
             if p.exists() {
-                if let Err(e) = std::fs::remove_dir_all(p) {
-                    ::log::error!("Failed to delete model at {}: {}", path, e);
+                if let Err(e) = model_trash::remove_now(p, hard_delete) {
+                    ::log::error!("Failed to remove model at {}: {}", path, e);
                 } else {
-                    ::log::info!("Deleted model files at {}", path);
+                    ::log::info!("Removed model files at {}", path);
                 }
             }
         });
@@ -2327,8 +3375,78 @@ impl App {
         self.ui.redraw(cx);
     }
 
+    /// Follow a load state change reported through the store event bus.
+    /// Events the shell published itself arrive here too and are no-ops.
+    fn handle_model_load_event(
+        &mut self,
+        cx: &mut Cx,
+        model_id: String,
+        model_name: String,
+        category: RegistryCategory,
+        phase: ModelLoadPhase,
+    ) {
+        match phase {
+            ModelLoadPhase::Loaded => {
+                if self.shell_load_state == ShellModelLoadState::Loaded && self.loaded_model_id == model_id { return; }
+                ::log::info!("Model loaded elsewhere: {} ({})", model_name, model_id);
+                self.loaded_model_id       = model_id;
+                self.loaded_model_name     = model_name;
+                self.loaded_model_category = Some(category);
+                self.shell_load_state      = ShellModelLoadState::Loaded;
+                self.load_rx               = None; // clear any shell-level load
+            }
+            ModelLoadPhase::Unloaded | ModelLoadPhase::Failed(_) => {
+                // Only clear if the model matches what the shell shows
+                if self.loaded_model_id != model_id { return; }
+                ::log::info!("Model unloaded elsewhere: {}", model_id);
+                self.loaded_model_id       = String::new();
+                self.loaded_model_name     = String::new();
+                self.loaded_model_category = None;
+                self.shell_load_state      = ShellModelLoadState::Unloaded;
+            }
+            ModelLoadPhase::Loading => return,
+        }
+        self.update_selector_bar(cx);
+        self.refresh_downloaded_models();
+    }
+
+    /// Broadcast the shell's own load state changes to the other apps.
+    fn publish_load_phase(&self, cx: &mut Cx, phase: ModelLoadPhase) {
+        let Some(category) = self.loaded_model_category else { return };
+        cx.action(StoreEvent::ModelLoadStateChanged {
+            model_id: self.loaded_model_id.clone(),
+            model_name: self.loaded_model_name.clone(),
+            category,
+            phase,
+        });
+    }
+
     /// Start loading a model in a background thread.
     fn start_load_model(&mut self, cx: &mut Cx, entry: DownloadedModelEntry) {
+        if let Some(warning) = power::load_warning(&entry.name, entry.memory_gb) {
+            self.pending_power_load = Some(entry);
+            self.ui.moly_dialog(ids!(body.dialog)).open(cx,
+                DialogRequest::confirm(live_id!(low_power_load), "Load in low power mode?", warning)
+                    .confirm_label("Load Anyway"));
+            return;
+        }
+        self.load_model(cx, entry);
+    }
+
+    /// Load a downloaded model picked outside the selector, e.g. in the
+    /// chat's model picker
+    fn load_model_by_id(&mut self, cx: &mut Cx, model_id: &str) {
+        // Same guard as opening the selector: don't race a load or the preload
+        if self.shell_load_state == ShellModelLoadState::Loading || self.preload_rx.is_some() { return; }
+        self.refresh_downloaded_models();
+        match self.downloaded_models.iter().find(|m| m.registry_id == model_id).cloned() {
+            Some(entry) => self.start_load_model(cx, entry),
+            None => ::log::warn!("Not loading {}: it isn't downloaded", model_id),
+        }
+    }
+
+    /// Load a model picked in the selector, without asking first
+    fn load_model(&mut self, cx: &mut Cx, entry: DownloadedModelEntry) {
         let (tx, rx) = mpsc::channel::<Result<(), String>>();
         self.load_rx = Some(rx);
         self.shell_load_state    = ShellModelLoadState::Loading;
@@ -2337,15 +3455,20 @@ impl App {
         self.loaded_model_category = Some(entry.category);
         self.loaded_model_supports_images = entry.supports_images;
 
+        let registry_id   = entry.registry_id.clone();
         let api_model_id  = entry.api_model_id.clone();
         let model_type    = entry.model_type_str.to_string();
+        let options       = ModelRuntimeOptions::load().get(&entry.registry_id);
 
         std::thread::spawn(move || {
+            let client = ModelRuntimeClient::localhost();
             let result = ensure_server_running()
-                .and_then(|()| ModelRuntimeClient::localhost().load_model(&api_model_id, &model_type));
+                .and_then(|()| ModelRegistry::load().get(&registry_id).map_or(Ok(()), |m| client.check_compatible(m)))
+                .and_then(|()| client.load_model_with(&api_model_id, &model_type, &options));
             let _ = tx.send(result);
         });
 
+        self.publish_load_phase(cx, ModelLoadPhase::Loading);
         self.update_selector_bar(cx);
         self.ui.redraw(cx);
     }
@@ -2359,6 +3482,8 @@ impl App {
             Some(RegistryCategory::Tts)      => "tts",
             Some(RegistryCategory::ImageGen) => "image",
             Some(RegistryCategory::VideoGen) => "video",
+            Some(RegistryCategory::MusicGen) => "music",
+            Some(RegistryCategory::Reranker) => "rerank",
             None                             => "all",
         }.to_string();
 
@@ -2366,6 +3491,8 @@ impl App {
             ModelRuntimeClient::localhost().unload_model(&model_type).ok();
         });
 
+        self.publish_load_phase(cx, ModelLoadPhase::Unloaded);
+
         // Optimistic UI reset
         self.shell_load_state    = ShellModelLoadState::Unloaded;
         self.loaded_model_id     = String::new();
@@ -2386,6 +3513,7 @@ impl App {
         match result {
             Ok(()) => {
                 self.shell_load_state = ShellModelLoadState::Loaded;
+                self.publish_load_phase(cx, ModelLoadPhase::Loaded);
 
                 // Set category + capabilities BEFORE injecting model
                 self.store.set_active_local_model_category(self.loaded_model_category);
@@ -2406,6 +3534,7 @@ impl App {
                 self.update_sidebar_chats(cx);
             }
             Err(e) => {
+                self.publish_load_phase(cx, ModelLoadPhase::Failed(e.clone()));
                 self.shell_load_state    = ShellModelLoadState::Error;
                 self.loaded_model_id     = String::new();
                 self.loaded_model_name   = String::new();
@@ -2418,6 +3547,123 @@ impl App {
         self.ui.redraw(cx);
     }
 
+    // ── Startup preload ──────────────────────────────────────────────────────
+
+    /// Load the models marked "Preload on startup" in the Hub in a
+    /// background thread, side by side as far as memory allows.
+    fn start_preload(&mut self, cx: &mut Cx, registry: &ModelRegistry) {
+        let models = preload::plan(&self.store.preferences.preload_models, registry, |m| {
+            model_state::registry_download_status(m) == DownloadStatus::Downloaded
+        });
+        if models.is_empty() { return; }
+        ::log::info!("Preloading {} model(s) at startup", models.len());
+
+        let (tx, rx) = mpsc::channel::<PreloadProgress>();
+        self.preload_rx = Some(rx);
+        let cancel = Arc::new(AtomicBool::new(false));
+        self.preload_cancel = cancel.clone();
+
+        let options = ModelRuntimeOptions::load();
+        let registry = registry.clone();
+        std::thread::spawn(move || {
+            // Started once up front, so parallel loads don't each launch one
+            let server = ensure_server_running();
+            // Models the runtime already holds count against the memory
+            let running = server.as_ref().ok()
+                .and_then(|_| ModelRuntimeClient::localhost().list_models().ok())
+                .unwrap_or_default();
+            let loaded_gb = load_scheduler::runtime_loaded_gb(&running, &registry, &models);
+            let scheduler = LoadScheduler::new(models, platform::total_memory(), loaded_gb);
+            let load_cancel = cancel.clone();
+            let load = move |model: &RegistryModel| {
+                server.clone()?;
+                let model_type = category_to_model_type(model.category);
+                let client = ModelRuntimeClient::localhost();
+                let result = client.check_compatible(model)
+                    .and_then(|()| client.load_model_with(&model.runtime.api_model_id, model_type, &options.get(&model.id)));
+                // Cancelled while loading: don't keep a model the user stopped
+                if result.is_ok() && load_cancel.load(Ordering::Relaxed) {
+                    client.unload_model(model_type).ok();
+                    return Err("Preload cancelled".to_string());
+                }
+                result
+            };
+            load_scheduler::run(scheduler, load, &cancel, |event| {
+                let _ = tx.send(PreloadProgress::Event(event));
+            });
+            let _ = tx.send(PreloadProgress::Done { cancelled: cancel.load(Ordering::Relaxed) });
+        });
+
+        self.ui.label(ids!(body.body_layout.header.preload_status.preload_label)).set_text(cx, "Preloading models...");
+        self.ui.view(ids!(body.body_layout.header.preload_status.preload_cancel_btn)).set_visible(cx, true);
+        self.ui.view(ids!(body.body_layout.header.preload_status)).set_visible(cx, true);
+    }
+
+    /// Stop the preload; models still loading are unloaded again when they
+    /// finish.
+    fn cancel_preload(&mut self, cx: &mut Cx) {
+        if self.preload_rx.is_none() { return; }
+        ::log::info!("Startup preload cancelled");
+        self.preload_cancel.store(true, Ordering::Relaxed);
+        self.ui.label(ids!(body.body_layout.header.preload_status.preload_label)).set_text(cx, "Cancelling preload...");
+        self.ui.view(ids!(body.body_layout.header.preload_status.preload_cancel_btn)).set_visible(cx, false);
+        self.ui.redraw(cx);
+    }
+
+    /// Follow the preload thread: show which model loads and tell the other
+    /// apps, as if each model had been loaded from the Hub.
+    fn poll_preload(&mut self, cx: &mut Cx) {
+        let Some(rx) = &self.preload_rx else { return };
+        let updates: Vec<PreloadProgress> = rx.try_iter().collect();
+        if updates.is_empty() { return; }
+
+        for update in updates {
+            match update {
+                PreloadProgress::Event(LoadEvent::Started(model)) => {
+                    publish_model_phase(cx, &model, ModelLoadPhase::Loading);
+                    self.preload_loading.push(model);
+                }
+                PreloadProgress::Event(LoadEvent::Progress(progress)) => {
+                    if !self.preload_cancel.load(Ordering::Relaxed) {
+                        let text = format!("Preloading: {}", progress.summary());
+                        self.ui.label(ids!(body.body_layout.header.preload_status.preload_label)).set_text(cx, &text);
+                    }
+                }
+                PreloadProgress::Event(LoadEvent::Finished(model, result)) => {
+                    self.preload_loading.retain(|m| m.id != model.id);
+                    if let Err(e) = result {
+                        if self.preload_cancel.load(Ordering::Relaxed) {
+                            publish_model_phase(cx, &model, ModelLoadPhase::Unloaded);
+                        } else {
+                            ::log::error!("Preloading {} failed: {}", model.id, e);
+                            publish_model_phase(cx, &model, ModelLoadPhase::Failed(e));
+                        }
+                        continue;
+                    }
+                    ::log::info!("Preloaded {}", model.id);
+                    // Chats use the preloaded chat model right away
+                    if matches!(model.category, RegistryCategory::Llm | RegistryCategory::Vlm) {
+                        self.loaded_model_supports_images = model.runtime.supports_images;
+                        self.store.set_active_local_model_category(Some(model.category));
+                        self.store.set_active_local_model_supports_images(model.runtime.supports_images);
+                        self.store.set_active_local_model(Some(model.id.clone()));
+                    }
+                    publish_model_phase(cx, &model, ModelLoadPhase::Loaded);
+                }
+                PreloadProgress::Done { cancelled } => {
+                    for model in std::mem::take(&mut self.preload_loading) {
+                        publish_model_phase(cx, &model, ModelLoadPhase::Unloaded);
+                    }
+                    ::log::info!("Startup preload {}", if cancelled { "cancelled" } else { "finished" });
+                    self.preload_rx = None;
+                    self.ui.view(ids!(body.body_layout.header.preload_status)).set_visible(cx, false);
+                    break;
+                }
+            }
+        }
+        self.ui.redraw(cx);
+    }
+
     fn navigate_to(&mut self, cx: &mut Cx, target: NavigationTarget) {
         ::log::info!("navigate_to: current={:?}, target={:?}", self.current_view, target);
         self.current_view = target;
@@ -2426,6 +3672,7 @@ impl App {
         let view_name = match target {
             NavigationTarget::ChatHistory => "ChatHistory",
             NavigationTarget::ActiveChat  => "ActiveChat",
+            NavigationTarget::Bookmarks   => "Bookmarks",
             NavigationTarget::Settings    => "Settings",
             NavigationTarget::LlmHub      => "LlmHub",
             NavigationTarget::VlmHub      => "VlmHub",
@@ -2433,6 +3680,8 @@ impl App {
             NavigationTarget::TtsHub      => "TtsHub",
             NavigationTarget::ImageHub    => "ImageHub",
             NavigationTarget::VideoHub    => "VideoHub",
+            NavigationTarget::MusicHub    => "MusicHub",
+            NavigationTarget::RerankHub   => "RerankHub",
             NavigationTarget::About       => "About",
         };
         self.store.set_current_view(view_name);
@@ -2449,15 +3698,20 @@ impl App {
 
         self.ui.widget(ids!(body.body_layout.content.main_content.chat_history_page)).set_visible(cx, show_chat_history);
         self.ui.widget(ids!(body.body_layout.content.main_content.chat_with_canvas)).set_visible(cx, show_active_chat);
+        self.ui.widget(ids!(body.body_layout.content.main_content.bookmarks_page)).set_visible(cx, target == NavigationTarget::Bookmarks);
         self.ui.widget(ids!(body.body_layout.content.main_content.llm_hub_app)).set_visible(cx, target == NavigationTarget::LlmHub);
         self.ui.widget(ids!(body.body_layout.content.main_content.vlm_hub_app)).set_visible(cx, target == NavigationTarget::VlmHub);
         self.ui.widget(ids!(body.body_layout.content.main_content.asr_hub_app)).set_visible(cx, target == NavigationTarget::AsrHub);
         self.ui.widget(ids!(body.body_layout.content.main_content.tts_hub_app)).set_visible(cx, target == NavigationTarget::TtsHub);
         self.ui.widget(ids!(body.body_layout.content.main_content.image_hub_app)).set_visible(cx, target == NavigationTarget::ImageHub);
         self.ui.widget(ids!(body.body_layout.content.main_content.video_hub_app)).set_visible(cx, target == NavigationTarget::VideoHub);
+        self.ui.widget(ids!(body.body_layout.content.main_content.music_hub_app)).set_visible(cx, target == NavigationTarget::MusicHub);
+        self.ui.widget(ids!(body.body_layout.content.main_content.rerank_hub_app)).set_visible(cx, target == NavigationTarget::RerankHub);
         self.ui.widget(ids!(body.body_layout.content.main_content.settings_app)).set_visible(cx, target == NavigationTarget::Settings);
         self.ui.widget(ids!(body.body_layout.content.main_content.about_page)).set_visible(cx, target == NavigationTarget::About);
 
+        self.ui.view(ids!(body.body_layout.header.detach_chat_btn)).set_visible(cx, show_active_chat);
+
         // Notify ChatApp when it becomes visible (to refresh model list)
         if show_active_chat {
             if let Some(mut chat_app) = self.ui.widget(ids!(body.body_layout.content.main_content.chat_with_canvas.chat_app)).borrow_mut::<moly_chat::screen::ChatApp>() {
@@ -2469,6 +3723,9 @@ impl App {
         if show_chat_history {
             self.update_chat_tiles(cx);
         }
+        if target == NavigationTarget::Bookmarks {
+            self.update_bookmarks(cx);
+        }
 
         // Update button selection state (SidebarButton is a Button with draw_bg.selected)
         // Chat button is selected for both ChatHistory and ActiveChat
@@ -2476,6 +3733,9 @@ impl App {
         self.ui.view(ids!(body.body_layout.content.sidebar.sidebar_scroll.chat_section.chat_history_btn)).apply_over(cx, live! {
             draw_bg: { selected: (if chat_selected { 1.0 } else { 0.0 }) }
         });
+        self.ui.view(ids!(body.body_layout.content.sidebar.sidebar_scroll.bookmarks_btn)).apply_over(cx, live! {
+            draw_bg: { selected: (if target == NavigationTarget::Bookmarks { 1.0 } else { 0.0 }) }
+        });
         self.ui.view(ids!(body.body_layout.content.sidebar.sidebar_scroll.llm_btn)).apply_over(cx, live! {
             draw_bg: { selected: (if target == NavigationTarget::LlmHub { 1.0 } else { 0.0 }) }
         });
@@ -2494,6 +3754,12 @@ impl App {
         self.ui.view(ids!(body.body_layout.content.sidebar.sidebar_scroll.video_btn)).apply_over(cx, live! {
             draw_bg: { selected: (if target == NavigationTarget::VideoHub { 1.0 } else { 0.0 }) }
         });
+        self.ui.view(ids!(body.body_layout.content.sidebar.sidebar_scroll.music_btn)).apply_over(cx, live! {
+            draw_bg: { selected: (if target == NavigationTarget::MusicHub { 1.0 } else { 0.0 }) }
+        });
+        self.ui.view(ids!(body.body_layout.content.sidebar.sidebar_scroll.rerank_btn)).apply_over(cx, live! {
+            draw_bg: { selected: (if target == NavigationTarget::RerankHub { 1.0 } else { 0.0 }) }
+        });
         self.ui.view(ids!(body.body_layout.content.sidebar.sidebar_scroll.settings_btn)).apply_over(cx, live! {
             draw_bg: { selected: (if target == NavigationTarget::Settings { 1.0 } else { 0.0 }) }
         });
@@ -2528,11 +3794,14 @@ impl App {
         let n = chats.len();
         self.sidebar_chat_ids = chats.iter().map(|c| c.id).collect();
 
+        // Runs every second: only write (and redraw) items whose title or
+        // visibility changed
+        let mut changed = false;
         macro_rules! update_item {
             ($index:expr, $section:ident, $item:ident) => {
                 let vis = $index < n;
-                self.ui.view(ids!(body.body_layout.content.sidebar.sidebar_scroll.chat_section.$section.$item))
-                    .set_visible(cx, vis);
+                let item = self.ui.widget(ids!(body.body_layout.content.sidebar.sidebar_scroll.chat_section.$section.$item));
+                changed |= self.ui_cache.set_visible(cx, &item, vis);
                 if vis {
                     // Sanitize: collapse newlines, truncate to single display line
                     let raw = &chats[$index].title;
@@ -2543,8 +3812,8 @@ impl App {
                     } else {
                         single
                     };
-                    self.ui.label(ids!(body.body_layout.content.sidebar.sidebar_scroll.chat_section.$section.$item.title))
-                        .set_text(cx, &display);
+                    let title = self.ui.label(ids!(body.body_layout.content.sidebar.sidebar_scroll.chat_section.$section.$item.title));
+                    changed |= self.ui_cache.set_text(cx, &title, &display);
                 }
             };
         }
@@ -2557,10 +3826,12 @@ impl App {
         update_item!(5, chat_history_more, chat_item_5);
 
         // Only show "Show More" when there are more than 3 chats
-        self.ui.view(ids!(body.body_layout.content.sidebar.sidebar_scroll.chat_section.chat_history_visible.show_more_btn))
-            .set_visible(cx, n > 3);
+        let show_more = self.ui.widget(ids!(body.body_layout.content.sidebar.sidebar_scroll.chat_section.chat_history_visible.show_more_btn));
+        changed |= self.ui_cache.set_visible(cx, &show_more, n > 3);
 
-        self.ui.redraw(cx);
+        if changed {
+            self.ui.view(ids!(body.body_layout.content.sidebar)).redraw(cx);
+        }
     }
 
     /// Update chat history visibility based on expanded state
@@ -2636,7 +3907,7 @@ impl App {
             &json_str[..preview_end]
         );
         // Dump full JSON to temp file for debugging
-        let _ = std::fs::write("/tmp/a2ui_last_json.txt", &json_str);
+        let _ = std::fs::write(artifacts::session_file("a2ui_last_json.txt"), &json_str);
 
         // Test: can serde parse it as generic JSON?
         match serde_json::from_str::<serde_json::Value>(&json_str) {
@@ -2718,7 +3989,7 @@ impl App {
         // Only show chats that have messages (filter out empty chats)
         // Also filter by search query if present
         let search_lower = self.search_query.to_lowercase();
-        let chats: Vec<_> = self.store.chats.get_sorted_chats()
+        let mut chats: Vec<_> = self.store.chats.get_sorted_chats()
             .into_iter()
             .filter(|c| !c.messages.is_empty())
             .filter(|c| {
@@ -2733,6 +4004,15 @@ impl App {
                 c.messages.iter().any(|m| m.content.text.to_lowercase().contains(&search_lower))
             })
             .collect();
+        // Then chats related by meaning that share no words with the query
+        for hit in &self.semantic_hits {
+            if chats.iter().any(|c| c.id == hit.chat_id) {
+                continue;
+            }
+            if let Some(chat) = self.store.chats.get_chat_by_id(hit.chat_id).filter(|c| !c.messages.is_empty()) {
+                chats.push(chat);
+            }
+        }
         let chat_count = chats.len().min(12); // Max 12 tiles
 
         // Update displayed_chat_ids
@@ -2798,18 +4078,136 @@ impl App {
         self.ui.redraw(cx);
     }
 
-    /// Handle chat tile clicks and delete button clicks
+    // ── Search by meaning ──────────────────────────────────────────────────
+
+    /// Search chat history by meaning once typing pauses, when it's set up
+    fn schedule_semantic_search(&mut self, cx: &mut Cx) {
+        cx.stop_timer(self.semantic_timer);
+        self.semantic_hits.clear();
+        let ready = self.store.preferences.semantic_search.is_ready();
+        let status = if ready && self.search_query.trim().chars().count() >= MIN_SEMANTIC_QUERY_CHARS {
+            self.semantic_timer = cx.start_timeout(0.6);
+            "Searching by meaning…"
+        } else {
+            ""
+        };
+        self.ui.label(ids!(body.body_layout.content.main_content.chat_history_page.semantic_status)).set_text(cx, status);
+    }
+
+    /// Embed new messages and the query in the background
+    fn start_semantic_search(&mut self, _cx: &mut Cx) {
+        let settings = self.store.preferences.semantic_search.clone();
+        let texts = semantic_search::message_texts(self.store.chats.saved_chats.iter());
+        let query = self.search_query.clone();
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let result = semantic_search::search_chats(&settings, &texts, &query, SEMANTIC_RESULTS);
+            let _ = tx.send((query, result));
+        });
+        self.semantic_rx = Some(rx);
+    }
+
+    fn poll_semantic_search(&mut self, cx: &mut Cx) {
+        let Some((query, result)) = self.semantic_rx.as_ref().and_then(|rx| rx.try_recv().ok()) else { return };
+        self.semantic_rx = None;
+        if query != self.search_query {
+            return;
+        }
+        let status = match result {
+            Ok(hits) => {
+                let added = hits.iter().filter(|h| !self.displayed_chat_ids.contains(&h.chat_id)).count();
+                self.semantic_hits = hits;
+                if added == 0 {
+                    String::new()
+                } else {
+                    format!("{} more related chat(s) found by meaning", added)
+                }
+            }
+            Err(e) => {
+                ::log::warn!("Search by meaning failed: {}", e);
+                format!("Search by meaning is unavailable: {}", e)
+            }
+        };
+        self.ui.label(ids!(body.body_layout.content.main_content.chat_history_page.semantic_status)).set_text(cx, &status);
+        self.update_chat_tiles(cx);
+    }
+
+    /// Act on answers from the shell's dialog
+    fn handle_dialog_results(&mut self, cx: &mut Cx, actions: &Actions) {
+        let Some((id, result)) = self.ui.moly_dialog(ids!(body.dialog)).closed(actions) else { return };
+        if id == live_id!(delete_chat) {
+            let Some(chat_id) = self.pending_delete_chat.take() else { return };
+            if result != DialogResult::Confirmed { return; }
+            self.close_detached_chat(chat_id);
+            self.store.chats.delete_chat(chat_id);
+            self.update_chat_tiles(cx);
+            self.update_sidebar_chats(cx);
+        } else if id == live_id!(rename_chat) {
+            let Some(chat_id) = self.pending_rename_chat.take() else { return };
+            let DialogResult::Text(title) = result else { return };
+            if title.is_empty() { return; }
+            self.store.chats.update_chat_title(chat_id, title);
+            self.update_chat_tiles(cx);
+            self.update_sidebar_chats(cx);
+        } else if id == live_id!(save_template) {
+            let Some(chat_id) = self.pending_template_chat.take() else { return };
+            let DialogResult::Text(name) = result else { return };
+            let Some(chat) = self.store.chats.get_chat_by_id(chat_id) else { return };
+            let saved = SessionTemplate::from_chat(&name, chat)
+                .and_then(|template| SessionTemplates::load().add(template));
+            if let Err(e) = saved {
+                self.ui.moly_dialog(ids!(body.dialog)).open(cx,
+                    DialogRequest::confirm(live_id!(template_failed), "Template not saved", e));
+            }
+        } else if id == live_id!(low_power_load) {
+            let Some(entry) = self.pending_power_load.take() else { return };
+            if result == DialogResult::Confirmed {
+                self.load_model(cx, entry);
+            }
+        } else if id == live_id!(quit) {
+            match result {
+                DialogResult::Selected(0) => self.start_quit(cx, false),
+                DialogResult::Selected(_) => self.start_quit(cx, true),
+                _ => {}
+            }
+        } else if id == live_id!(quit_waiting) {
+            match result {
+                DialogResult::Confirmed => self.finish_quit(cx),
+                // Keep working; stopped work stays stopped
+                _ => self.pending_quit = None,
+            }
+        }
+    }
+
+    /// Handle chat tile clicks and their rename, delete, duplicate and
+    /// template buttons
     fn handle_chat_tile_clicks(&mut self, cx: &mut Cx, actions: &Actions) {
         let mut tile_clicked: Option<usize> = None;
+        let mut rename_clicked: Option<usize> = None;
         let mut delete_clicked: Option<usize> = None;
+        let mut duplicate_clicked: Option<usize> = None;
+        let mut template_clicked: Option<usize> = None;
 
         macro_rules! check_tile {
             ($index:expr, $row:ident, $tile:ident) => {
-                if $index < self.displayed_chat_ids.len() && delete_clicked.is_none() && tile_clicked.is_none() {
+                if $index < self.displayed_chat_ids.len() && delete_clicked.is_none() && tile_clicked.is_none()
+                    && duplicate_clicked.is_none() && template_clicked.is_none() && rename_clicked.is_none() {
                     if self.ui.view(ids!(body.body_layout.content.main_content.chat_history_page.chat_tiles_scroll.chat_tiles_container.$row.$tile.header.delete_btn))
                         .finger_down(actions).is_some() {
                         delete_clicked = Some($index);
                     }
+                    else if self.ui.view(ids!(body.body_layout.content.main_content.chat_history_page.chat_tiles_scroll.chat_tiles_container.$row.$tile.header.rename_btn))
+                        .finger_down(actions).is_some() {
+                        rename_clicked = Some($index);
+                    }
+                    else if self.ui.view(ids!(body.body_layout.content.main_content.chat_history_page.chat_tiles_scroll.chat_tiles_container.$row.$tile.header.duplicate_btn))
+                        .finger_down(actions).is_some() {
+                        duplicate_clicked = Some($index);
+                    }
+                    else if self.ui.view(ids!(body.body_layout.content.main_content.chat_history_page.chat_tiles_scroll.chat_tiles_container.$row.$tile.header.template_btn))
+                        .finger_down(actions).is_some() {
+                        template_clicked = Some($index);
+                    }
                     else if self.ui.view(ids!(body.body_layout.content.main_content.chat_history_page.chat_tiles_scroll.chat_tiles_container.$row.$tile))
                         .finger_down(actions).is_some() {
                         tile_clicked = Some($index);
@@ -2835,9 +4233,54 @@ impl App {
         if let Some(idx) = delete_clicked {
             let chat_id = self.displayed_chat_ids[idx];
             ::log::info!("Delete button clicked for chat at index {}, id={}", idx, chat_id);
-            self.store.chats.delete_chat(chat_id);
-            self.update_chat_tiles(cx);
-            self.update_sidebar_chats(cx);
+            let title = self.store.chats.get_chat_by_id(chat_id)
+                .map(|c| c.title.clone())
+                .unwrap_or_default();
+            self.pending_delete_chat = Some(chat_id);
+            self.ui.moly_dialog(ids!(body.dialog)).open(cx,
+                DialogRequest::confirm(live_id!(delete_chat), "Delete chat?",
+                    format!("\"{}\" and all its messages will be deleted. This can't be undone.", title))
+                    .confirm_label("Delete")
+                    .danger());
+            return;
+        }
+
+        // Ask for the new title; applied in handle_dialog_results
+        if let Some(idx) = rename_clicked {
+            let chat_id = self.displayed_chat_ids[idx];
+            let title = self.store.chats.get_chat_by_id(chat_id)
+                .map(|c| c.title.clone())
+                .unwrap_or_default();
+            self.pending_rename_chat = Some(chat_id);
+            self.ui.moly_dialog(ids!(body.dialog)).open(cx,
+                DialogRequest::text_input(live_id!(rename_chat), "Rename chat", "", title)
+                    .placeholder("Chat title")
+                    .confirm_label("Rename"));
+            return;
+        }
+
+        // Copy the chat; the copy is listed first
+        if let Some(idx) = duplicate_clicked {
+            let chat_id = self.displayed_chat_ids[idx];
+            if self.store.chats.duplicate_chat(chat_id).is_some() {
+                self.update_chat_tiles(cx);
+                self.update_sidebar_chats(cx);
+            }
+            return;
+        }
+
+        // Ask for the template's name; saved in handle_dialog_results
+        if let Some(idx) = template_clicked {
+            let chat_id = self.displayed_chat_ids[idx];
+            let title = self.store.chats.get_chat_by_id(chat_id)
+                .map(|c| c.title.clone())
+                .unwrap_or_default();
+            self.pending_template_chat = Some(chat_id);
+            self.ui.moly_dialog(ids!(body.dialog)).open(cx,
+                DialogRequest::text_input(live_id!(save_template), "Save as template",
+                    format!("New sessions can start from the system prompt, tools and first {} messages of this chat.",
+                        moly_data::session_templates::MAX_TEMPLATE_MESSAGES), title)
+                    .confirm_label("Save"));
             return;
         }
 
@@ -2846,7 +4289,8 @@ impl App {
             let chat_id = self.displayed_chat_ids[idx];
             ::log::info!("Chat tile clicked at index {}, id={}", idx, chat_id);
 
-            // Set current chat in store
+            // Set current chat in store, taking it back from its own window
+            self.close_detached_chat(chat_id);
             self.store.chats.set_current_chat(Some(chat_id));
 
             // Load chat in ChatApp
@@ -2863,6 +4307,748 @@ impl App {
         }
     }
 
+    // ── Bookmarks ───────────────────────────────────────────────────────────
+
+    /// Fill the Bookmarks page cards (max 12) from the Store
+    fn update_bookmarks(&mut self, cx: &mut Cx) {
+        let entries: Vec<_> = bookmarks::collect(&self.store.chats.saved_chats, 400)
+            .into_iter()
+            .filter(|entry| entry.matches(&self.bookmark_query))
+            .collect();
+        let shown = entries.len().min(12);
+        self.displayed_bookmarks = entries.iter().take(12).map(|e| (e.chat_id, e.message_index)).collect();
+
+        let total = self.store.chats.saved_chats.iter().map(|c| c.bookmarks.len()).sum::<usize>();
+        let empty_text = if total == 0 {
+            "No bookmarks yet. Use Bookmark… in a session to keep an answer here."
+        } else {
+            "No bookmarks match your search."
+        };
+        self.ui.label(ids!(body.body_layout.content.main_content.bookmarks_page.bookmarks_empty.bookmarks_empty_label))
+            .set_text(cx, empty_text);
+        self.ui.view(ids!(body.body_layout.content.main_content.bookmarks_page.bookmarks_empty)).set_visible(cx, shown == 0);
+        self.ui.view(ids!(body.body_layout.content.main_content.bookmarks_page.bookmarks_scroll)).set_visible(cx, shown > 0);
+
+        macro_rules! update_card {
+            ($index:expr, $card:ident) => {
+                let visible = $index < shown;
+                self.ui.view(ids!(body.body_layout.content.main_content.bookmarks_page.bookmarks_scroll.bookmarks_list.$card))
+                    .set_visible(cx, visible);
+                if visible {
+                    let entry = &entries[$index];
+                    self.ui.label(ids!(body.body_layout.content.main_content.bookmarks_page.bookmarks_scroll.bookmarks_list.$card.header.chat_title))
+                        .set_text(cx, &entry.chat_title);
+                    self.ui.label(ids!(body.body_layout.content.main_content.bookmarks_page.bookmarks_scroll.bookmarks_list.$card.header.date_label))
+                        .set_text(cx, &entry.created_at.format("%b %d, %Y").to_string());
+                    self.ui.label(ids!(body.body_layout.content.main_content.bookmarks_page.bookmarks_scroll.bookmarks_list.$card.excerpt))
+                        .set_text(cx, &entry.excerpt);
+                    let note = self.ui.label(ids!(body.body_layout.content.main_content.bookmarks_page.bookmarks_scroll.bookmarks_list.$card.note));
+                    note.set_visible(cx, !entry.note.is_empty());
+                    note.set_text(cx, &entry.note);
+                }
+            };
+        }
+
+        update_card!(0, bookmark_0);
+        update_card!(1, bookmark_1);
+        update_card!(2, bookmark_2);
+        update_card!(3, bookmark_3);
+        update_card!(4, bookmark_4);
+        update_card!(5, bookmark_5);
+        update_card!(6, bookmark_6);
+        update_card!(7, bookmark_7);
+        update_card!(8, bookmark_8);
+        update_card!(9, bookmark_9);
+        update_card!(10, bookmark_10);
+        update_card!(11, bookmark_11);
+
+        let more = entries.len() - shown;
+        let more_text = if more > 0 { format!("{} more — search to narrow down", more) } else { String::new() };
+        self.ui.label(ids!(body.body_layout.content.main_content.bookmarks_page.bookmarks_scroll.bookmarks_list.bookmarks_more))
+            .set_text(cx, &more_text);
+
+        self.ui.redraw(cx);
+    }
+
+    /// Open a bookmarked message in its chat, or remove the bookmark
+    fn handle_bookmark_clicks(&mut self, cx: &mut Cx, actions: &Actions) {
+        let mut card_clicked: Option<usize> = None;
+        let mut remove_clicked: Option<usize> = None;
+
+        macro_rules! check_card {
+            ($index:expr, $card:ident) => {
+                if $index < self.displayed_bookmarks.len() && card_clicked.is_none() && remove_clicked.is_none() {
+                    if self.ui.view(ids!(body.body_layout.content.main_content.bookmarks_page.bookmarks_scroll.bookmarks_list.$card.header.remove_btn))
+                        .finger_down(actions).is_some() {
+                        remove_clicked = Some($index);
+                    }
+                    else if self.ui.view(ids!(body.body_layout.content.main_content.bookmarks_page.bookmarks_scroll.bookmarks_list.$card))
+                        .finger_down(actions).is_some() {
+                        card_clicked = Some($index);
+                    }
+                }
+            };
+        }
+
+        check_card!(0, bookmark_0);
+        check_card!(1, bookmark_1);
+        check_card!(2, bookmark_2);
+        check_card!(3, bookmark_3);
+        check_card!(4, bookmark_4);
+        check_card!(5, bookmark_5);
+        check_card!(6, bookmark_6);
+        check_card!(7, bookmark_7);
+        check_card!(8, bookmark_8);
+        check_card!(9, bookmark_9);
+        check_card!(10, bookmark_10);
+        check_card!(11, bookmark_11);
+
+        if let Some(idx) = remove_clicked {
+            let (chat_id, message_index) = self.displayed_bookmarks[idx];
+            self.store.chats.remove_bookmark(chat_id, message_index);
+            self.update_bookmarks(cx);
+            return;
+        }
+
+        if let Some(idx) = card_clicked {
+            let (chat_id, message_index) = self.displayed_bookmarks[idx];
+            ::log::info!("Opening bookmark: chat {} message {}", chat_id, message_index);
+            self.close_detached_chat(chat_id);
+            self.store.chats.set_current_chat(Some(chat_id));
+            if let Some(mut chat_app) = self.ui.widget(ids!(body.body_layout.content.main_content.chat_with_canvas.chat_app))
+                .borrow_mut::<moly_chat::screen::ChatApp>()
+            {
+                chat_app.load_chat_at(chat_id, message_index);
+            }
+            self.navigate_to(cx, NavigationTarget::ActiveChat);
+        }
+    }
+
+    /// Show the chat a message link points to, scrolled to the message
+    fn open_link(&mut self, cx: &mut Cx, link: &str) {
+        let Some(target) = chat_navigation::parse_link(link) else {
+            ::log::warn!("Not a message link: {}", link);
+            return;
+        };
+        if self.store.chats.get_chat_by_id(target.chat_id).is_none() {
+            self.ui.moly_dialog(ids!(body.dialog)).open(cx,
+                DialogRequest::confirm(live_id!(link_missing), "Chat not found",
+                    "The chat this link points to was deleted, or belongs to another profile."));
+            return;
+        }
+        ::log::info!("Opening link: chat {} message {:?}", target.chat_id, target.message_index);
+        self.open_chat(cx, target.chat_id, target.message_index);
+    }
+
+    /// Show a chat in the main window, scrolled to a message if one is given
+    fn open_chat(&mut self, cx: &mut Cx, chat_id: ChatId, message_index: Option<usize>) {
+        self.close_detached_chat(chat_id);
+        self.store.handle_action(&StoreAction::OpenChat { chat_id, message_index });
+        if let Some(mut chat_app) = self.ui.widget(ids!(body.body_layout.content.main_content.chat_with_canvas.chat_app))
+            .borrow_mut::<moly_chat::screen::ChatApp>()
+        {
+            match message_index {
+                Some(index) => chat_app.load_chat_at(chat_id, index),
+                None => chat_app.load_chat(chat_id),
+            }
+        }
+        self.navigate_to(cx, NavigationTarget::ActiveChat);
+        self.update_sidebar_chats(cx);
+    }
+
+    // ── Profiles ────────────────────────────────────────────────────────────
+
+    fn profile_slot(&self, index: usize) -> ViewRef {
+        match index {
+            0 => self.ui.view(ids!(body.profile_dropdown.profile_wrapper.profile_panel.profile_slot_0)),
+            1 => self.ui.view(ids!(body.profile_dropdown.profile_wrapper.profile_panel.profile_slot_1)),
+            2 => self.ui.view(ids!(body.profile_dropdown.profile_wrapper.profile_panel.profile_slot_2)),
+            3 => self.ui.view(ids!(body.profile_dropdown.profile_wrapper.profile_panel.profile_slot_3)),
+            4 => self.ui.view(ids!(body.profile_dropdown.profile_wrapper.profile_panel.profile_slot_4)),
+            5 => self.ui.view(ids!(body.profile_dropdown.profile_wrapper.profile_panel.profile_slot_5)),
+            6 => self.ui.view(ids!(body.profile_dropdown.profile_wrapper.profile_panel.profile_slot_6)),
+            _ => self.ui.view(ids!(body.profile_dropdown.profile_wrapper.profile_panel.profile_slot_7)),
+        }
+    }
+
+    fn open_profile_menu(&mut self, cx: &mut Cx) {
+        if self.selector_open { self.close_selector(cx); }
+        self.profile_menu_open = true;
+        self.ui.label(ids!(body.profile_dropdown.profile_wrapper.profile_panel.profile_error_label)).set_text(cx, "");
+        self.update_profile_menu(cx);
+        self.ui.view(ids!(body.profile_dropdown)).set_visible(cx, true);
+        self.ui.redraw(cx);
+    }
+
+    fn close_profile_menu(&mut self, cx: &mut Cx) {
+        self.profile_menu_open = false;
+        self.ui.view(ids!(body.profile_dropdown)).set_visible(cx, false);
+        self.ui.redraw(cx);
+    }
+
+    /// Fill the profile slots and startup option labels.
+    fn update_profile_menu(&mut self, cx: &mut Cx) {
+        let active = profiles::active_profile_id();
+        for i in 0..PROFILE_SLOTS {
+            let slot = self.profile_slot(i);
+            let Some(profile) = self.profiles.profiles.get(i) else {
+                slot.set_visible(cx, false);
+                continue;
+            };
+            slot.set_visible(cx, true);
+            slot.label(ids!(profile_slot_name)).set_text(cx, &profile.name);
+            slot.view(ids!(profile_slot_default_tag)).set_visible(cx, self.profiles.is_default(&profile.id));
+            let is_active = if profile.id == active { 1.0 } else { 0.0 };
+            slot.apply_over(cx, live! { draw_bg: { active: (is_active) } });
+        }
+
+        let is_default = self.profiles.is_default(&active);
+        self.ui.label(ids!(body.profile_dropdown.profile_wrapper.profile_panel.set_default_profile_btn.set_default_label))
+            .set_text(cx, if is_default { "Opens at startup" } else { "Open at startup" });
+        let ask = if self.profiles.ask_at_startup { "Ask at startup: On" } else { "Ask at startup: Off" };
+        self.ui.label(ids!(body.profile_dropdown.profile_wrapper.profile_panel.ask_at_startup_btn.ask_at_startup_label))
+            .set_text(cx, ask);
+    }
+
+    fn handle_profile_menu_actions(&mut self, cx: &mut Cx, actions: &Actions) {
+        if self.ui.view(ids!(body.profile_dropdown.profile_dismiss_area)).finger_down(actions).is_some() {
+            self.close_profile_menu(cx);
+            return;
+        }
+
+        for i in 0..self.profiles.profiles.len().min(PROFILE_SLOTS) {
+            if self.profile_slot(i).finger_down(actions).is_some() {
+                let id = self.profiles.profiles[i].id.clone();
+                self.close_profile_menu(cx);
+                self.switch_profile(cx, &id);
+                return;
+            }
+        }
+
+        let input = self.ui.text_input(ids!(body.profile_dropdown.profile_wrapper.profile_panel.new_profile_input));
+        let submitted = input.returned(actions).is_some()
+            || self.ui.view(ids!(body.profile_dropdown.profile_wrapper.profile_panel.create_profile_btn)).finger_down(actions).is_some();
+        if submitted {
+            self.create_profile(cx, &input.text());
+        }
+
+        if self.ui.view(ids!(body.profile_dropdown.profile_wrapper.profile_panel.set_default_profile_btn)).finger_down(actions).is_some() {
+            self.profiles.set_default(&profiles::active_profile_id());
+            self.profiles.save();
+            self.update_profile_menu(cx);
+            self.ui.redraw(cx);
+        }
+        if self.ui.view(ids!(body.profile_dropdown.profile_wrapper.profile_panel.ask_at_startup_btn)).finger_down(actions).is_some() {
+            self.profiles.ask_at_startup = !self.profiles.ask_at_startup;
+            self.profiles.save();
+            self.update_profile_menu(cx);
+            self.ui.redraw(cx);
+        }
+    }
+
+    /// Create a profile from the menu's name field and switch to it.
+    fn create_profile(&mut self, cx: &mut Cx, name: &str) {
+        let error_label = self.ui.label(ids!(body.profile_dropdown.profile_wrapper.profile_panel.profile_error_label));
+        if self.profiles.profiles.len() >= PROFILE_SLOTS {
+            error_label.set_text(cx, &format!("At most {} profiles are supported", PROFILE_SLOTS));
+            return;
+        }
+        match self.profiles.create(name) {
+            Ok(profile) => {
+                self.profiles.save();
+                self.ui.text_input(ids!(body.profile_dropdown.profile_wrapper.profile_panel.new_profile_input)).set_text(cx, "");
+                self.close_profile_menu(cx);
+                self.switch_profile(cx, &profile.id);
+            }
+            Err(e) => {
+                error_label.set_text(cx, &e);
+                self.ui.redraw(cx);
+            }
+        }
+    }
+
+    /// Start or stop serving models on the LAN to match the preferences
+    fn apply_lan_sharing(&self) {
+        if !self.store.preferences.lan_sharing {
+            lan_share::stop_sharing();
+        } else if let Err(e) = lan_share::start_sharing() {
+            ::log::error!("Failed to start LAN model sharing: {}", e);
+        }
+    }
+
+    /// Start or stop the OpenAI-compatible endpoint to match the preferences
+    fn apply_api_proxy(&self) {
+        if let Err(e) = api_proxy::apply(&self.store.preferences.api_proxy) {
+            ::log::error!("Failed to start the OpenAI-compatible endpoint: {}", e);
+        }
+    }
+
+    /// Reload the Store from another profile's data directory. A model loaded
+    /// in the runtime stays loaded and keeps serving the new profile's chats.
+    fn switch_profile(&mut self, cx: &mut Cx, profile_id: &str) {
+        if profiles::active_profile_id() == profile_id { return; }
+        ::log::info!("Switching to profile {}", profile_id);
+
+        profiles::set_active_profile(profile_id);
+        self.reload_store(cx);
+        self.navigate_to(cx, NavigationTarget::ChatHistory);
+    }
+
+    /// Load the active profile's data again and reset everything showing it
+    fn reload_store(&mut self, cx: &mut Cx) {
+        let profile_id = profiles::active_profile_id();
+        self.store = Store::load();
+        moly_data::voice_training::reload();
+        self.apply_lan_sharing();
+        self.apply_api_proxy();
+        if self.shell_load_state == ShellModelLoadState::Loaded {
+            self.store.set_active_local_model_category(self.loaded_model_category);
+            self.store.set_active_local_model_supports_images(self.loaded_model_supports_images);
+            self.store.set_active_local_model(Some(self.loaded_model_id.clone()));
+        }
+        self.app_data.sync_from_preferences(
+            self.store.is_sidebar_expanded(),
+            self.store.current_view(),
+            self.store.preferences.get_current_chat_model(),
+        );
+
+        if let Some(mut chat_app) = self.ui
+            .widget(ids!(body.body_layout.content.main_content.chat_with_canvas.chat_app))
+            .borrow_mut::<moly_chat::screen::ChatApp>()
+        {
+            chat_app.reset_for_profile();
+        }
+        cx.action(StoreEvent::ProfileChanged { profile_id });
+
+        self.update_profile_label(cx);
+        self.update_sidebar(cx);
+        self.update_sidebar_chats(cx);
+    }
+
+    fn update_profile_label(&mut self, cx: &mut Cx) {
+        let active = profiles::active_profile_id();
+        let name = self.profiles.get(&active).map(|p| p.name.clone()).unwrap_or(active);
+        self.ui.label(ids!(body.body_layout.header.profile_btn.profile_label)).set_text(cx, &name);
+    }
+
+    // ── Chat backups ────────────────────────────────────────────────────────
+
+    /// Write a chat snapshot on a background thread when automatic backups
+    /// are enabled and the interval has passed since the newest one.
+    fn backup_chats_if_due(&mut self) {
+        if self.backup_thread.as_ref().map_or(false, |t| !t.is_finished()) { return; }
+        let settings = self.store.preferences.chat_backup.clone();
+        let latest = chat_backup::list_backups().first().map(|b| b.created_at);
+        if !chat_backup::is_backup_due(&settings, latest, chrono::Utc::now()) { return; }
+        if self.store.chats.saved_chats.is_empty() { return; }
+
+        let chats = self.store.chats.saved_chats.clone();
+        self.backup_thread = Some(std::thread::spawn(move || {
+            if let Err(e) = chat_backup::create_backup(&chats, settings.retention) {
+                ::log::error!("Automatic chat backup failed: {}", e);
+            }
+        }));
+    }
+
+    // ── Window state ────────────────────────────────────────────────────────
+
+    /// Put the main window back where it was at the end of the last session
+    fn restore_window_state(&mut self, cx: &mut Cx) {
+        let Some(state) = self.store.preferences.window.filter(|s| s.is_usable()) else { return };
+        ::log::info!("Restoring window geometry {:?}", state);
+        self.window_state = Some(state);
+        let window = self.ui.as_window();
+        window.resize(cx, dvec2(state.width, state.height));
+        window.reposition(cx, dvec2(state.x, state.y));
+        if state.maximized {
+            window.maximize(cx);
+        }
+    }
+
+    /// Track main window geometry. While the window fills the screen the
+    /// last normal size is kept, so un-maximizing after a restart works.
+    fn handle_window_geom_change(&mut self, e: &WindowGeomChangeEvent) {
+        if e.window_id != CxWindowPool::id_zero() {
+            // The first geometry event of a new window tells us its id
+            let known = self.detached_chats.iter().any(|d| d.window_id == Some(e.window_id))
+                || self.quick_ask.as_ref().is_some_and(|q| q.window_id == Some(e.window_id));
+            if !known {
+                if let Some(detached) = self.detached_chats.iter_mut().find(|d| d.window_id.is_none()) {
+                    detached.window_id = Some(e.window_id);
+                } else if let Some(quick) = self.quick_ask.as_mut().filter(|q| q.window_id.is_none()) {
+                    quick.window_id = Some(e.window_id);
+                }
+            }
+            return;
+        }
+        let geom = &e.new_geom;
+        let mut state = self.window_state.unwrap_or(WindowState {
+            width: geom.inner_size.x,
+            height: geom.inner_size.y,
+            x: geom.position.x,
+            y: geom.position.y,
+            maximized: false,
+        });
+        state.maximized = geom.is_fullscreen;
+        if !geom.is_fullscreen {
+            state.width = geom.inner_size.x;
+            state.height = geom.inner_size.y;
+            state.x = geom.position.x;
+            state.y = geom.position.y;
+        }
+        if self.window_state != Some(state) {
+            self.window_state = Some(state);
+            self.window_state_dirty = true;
+        }
+    }
+
+    /// Persist the window geometry if it changed since the last tick
+    fn save_window_state(&mut self) {
+        if !self.window_state_dirty { return; }
+        self.window_state_dirty = false;
+        if let Some(state) = self.window_state.filter(|s| s.is_usable()) {
+            self.store.preferences.set_window_state(state);
+        }
+    }
+
+    // ── Quitting ────────────────────────────────────────────────────────────
+
+    /// The chat of the main window and those of detached windows
+    fn chat_apps(&self) -> Vec<WidgetRef> {
+        let main_chat = self.ui.widget(ids!(body.body_layout.content.main_content.chat_with_canvas.chat_app));
+        std::iter::once(main_chat).chain(self.detached_chats.iter().map(|d| d.window.widget(ids!(body.chat_app)))).collect()
+    }
+
+    /// What closing the main window would cut short
+    fn work_in_flight(&self) -> Vec<String> {
+        let mut work = shutdown::in_flight();
+        for chat in self.chat_apps() {
+            let Some(chat_app) = chat.borrow::<moly_chat::screen::ChatApp>() else { continue };
+            if chat_app.is_answering() {
+                let title = chat_app.current_chat_id()
+                    .and_then(|id| self.store.chats.get_chat_by_id(id))
+                    .map(|c| c.title.clone())
+                    .unwrap_or_default();
+                work.push(format!("Answering in the chat “{}”", title));
+            }
+        }
+        work
+    }
+
+    /// Closing the main window: with work in flight, ask whether to wait for
+    /// it or stop it; models are unloaded first when Settings says so.
+    /// Other windows close as usual.
+    fn handle_close_requested(&mut self, cx: &mut Cx, e: &WindowCloseRequestedEvent) {
+        let other_window = self.detached_chats.iter().any(|d| d.window_id == Some(e.window_id))
+            || self.quick_ask.as_ref().is_some_and(|q| q.window_id == Some(e.window_id));
+        if other_window || self.quitting { return; }
+        e.accept_close.set(false);
+        if self.pending_quit.is_some() {
+            // Closing again while waiting quits right away
+            self.finish_quit(cx);
+            return;
+        }
+        let work = self.work_in_flight();
+        if work.is_empty() {
+            self.finish_quit(cx);
+            return;
+        }
+        self.ui.moly_dialog(ids!(body.dialog)).open(cx,
+            DialogRequest::select(live_id!(quit), "Quit while work is running?",
+                format!("{}\n\nStopped downloads keep the files already complete.", shutdown::describe(&work)),
+                vec!["Wait for it to finish".to_string(), "Stop it".to_string()], 0)
+                .confirm_label("Quit"));
+    }
+
+    /// Quit once the work in flight is done. `stop` asks it to stop and gives
+    /// it [`shutdown::CANCEL_GRACE`] to do so.
+    fn start_quit(&mut self, cx: &mut Cx, stop: bool) {
+        let mut deadline = None;
+        if stop {
+            let unstoppable = shutdown::cancel_all();
+            if !unstoppable.is_empty() {
+                ::log::info!("Quitting after work that can't be stopped: {}", unstoppable.join(", "));
+            }
+            for chat in self.chat_apps() {
+                if let Some(mut chat_app) = chat.borrow_mut::<moly_chat::screen::ChatApp>() {
+                    chat_app.stop_answering();
+                }
+            }
+            deadline = Some(std::time::Instant::now() + shutdown::CANCEL_GRACE);
+        }
+        self.pending_quit = Some(PendingQuit { deadline, unload_rx: None });
+        let waiting = if stop { "Waiting for work to stop." } else { "Waiting for work to finish." };
+        self.ui.moly_dialog(ids!(body.dialog)).open(cx,
+            DialogRequest::confirm(live_id!(quit_waiting), "Quitting…",
+                format!("{} The app quits as soon as it's done.", waiting))
+                .confirm_label("Quit Now")
+                .danger());
+        self.poll_quit(cx);
+    }
+
+    /// Checked every second while quitting is pending
+    fn poll_quit(&mut self, cx: &mut Cx) {
+        let Some(pending) = &self.pending_quit else { return };
+        let timed_out = pending.deadline.is_some_and(|d| std::time::Instant::now() >= d);
+        if let Some(rx) = &pending.unload_rx {
+            if timed_out || !matches!(rx.try_recv(), Err(mpsc::TryRecvError::Empty)) {
+                self.quit(cx);
+            }
+            return;
+        }
+        if timed_out || self.work_in_flight().is_empty() {
+            self.finish_quit(cx);
+        }
+    }
+
+    /// Save drafts and window state, unload models if Settings says so, and quit
+    fn finish_quit(&mut self, cx: &mut Cx) {
+        if self.pending_quit.as_ref().is_some_and(|p| p.unload_rx.is_some()) {
+            // Already unloading; closing again doesn't wait for it
+            self.quit(cx);
+            return;
+        }
+        for chat in self.chat_apps() {
+            if let Some(mut chat_app) = chat.borrow_mut::<moly_chat::screen::ChatApp>() {
+                chat_app.flush_draft(&mut self.store);
+            }
+        }
+        self.save_window_state();
+
+        if !self.store.preferences.unload_models_on_quit {
+            self.quit(cx);
+            return;
+        }
+        ::log::info!("Unloading models before quitting");
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let client = ModelRuntimeClient::localhost();
+            if client.is_alive() {
+                if let Err(e) = client.unload_model("all") {
+                    ::log::warn!("Unloading models before quitting failed: {}", e);
+                }
+            }
+            let _ = tx.send(());
+        });
+        let deadline = Some(std::time::Instant::now() + shutdown::CANCEL_GRACE);
+        self.pending_quit = Some(PendingQuit { deadline, unload_rx: Some(rx) });
+    }
+
+    fn quit(&mut self, cx: &mut Cx) {
+        self.pending_quit = None;
+        self.quitting = true;
+        mcp_client::stop_all();
+        cx.quit();
+    }
+
+    // ── Low power mode ──────────────────────────────────────────────────────
+
+    /// Follow a change of the low power settings or power source: slow down
+    /// or speed up the background timers and tell the apps
+    fn apply_power_mode(&mut self, cx: &mut Cx) {
+        let active = power::is_active();
+        if active == self.low_power { return; }
+        self.low_power = active;
+        ::log::info!("{}", power::status());
+        cx.stop_timer(self.ram_timer);
+        self.ram_timer = cx.start_interval(power::interval(1.0));
+        cx.action(StoreEvent::PowerModeChanged { active });
+    }
+
+    // ── Detached chats ──────────────────────────────────────────────────────
+
+    /// Move the chat shown in the main window into its own window; the main
+    /// window continues with a new chat.
+    fn open_chat_in_window(&mut self, cx: &mut Cx) {
+        let chat_app = self.ui.widget(ids!(body.body_layout.content.main_content.chat_with_canvas.chat_app));
+        let Some(chat_id) = chat_app.borrow::<moly_chat::screen::ChatApp>().and_then(|c| c.current_chat_id()) else { return };
+        if self.detached_chats.iter().any(|d| d.chat_id == chat_id) { return; }
+
+        let window = WidgetRef::new_from_ptr(cx, self.detached_chat_window);
+        if let Some(mut detached_app) = window.widget(ids!(body.chat_app)).borrow_mut::<moly_chat::screen::ChatApp>() {
+            detached_app.pin_chat(chat_id);
+        }
+        let title = self.store.chats.get_chat_by_id(chat_id).map(|c| c.title.clone()).unwrap_or_default();
+        window.label(ids!(body.detached_header.detached_title)).set_text(cx, &title);
+        apply_detached_theme(cx, &window, self.store.dark_mode);
+        ::log::info!("Opened chat {} in its own window", chat_id);
+        self.detached_chats.push(DetachedChat { chat_id, window, window_id: None });
+
+        if let Some(mut chat_app) = chat_app.borrow_mut::<moly_chat::screen::ChatApp>() {
+            chat_app.request_new_chat();
+        }
+        self.ui.redraw(cx);
+    }
+
+    /// Close the chat's own window and show it in the main window again
+    fn reattach_chat(&mut self, cx: &mut Cx, chat_id: ChatId) {
+        self.close_detached_chat(chat_id);
+        self.store.chats.set_current_chat(Some(chat_id));
+        if let Some(mut chat_app) = self.ui.widget(ids!(body.body_layout.content.main_content.chat_with_canvas.chat_app))
+            .borrow_mut::<moly_chat::screen::ChatApp>()
+        {
+            chat_app.load_chat(chat_id);
+        }
+        self.navigate_to(cx, NavigationTarget::ActiveChat);
+        self.update_sidebar_chats(cx);
+    }
+
+    /// Close the window showing `chat_id`, if there is one
+    fn close_detached_chat(&mut self, chat_id: ChatId) {
+        self.detached_chats.retain(|d| d.chat_id != chat_id);
+    }
+
+    // ── Clipboard quick actions ─────────────────────────────────────────────
+
+    /// Start or stop watching as the setting changes, and offer new copies
+    fn poll_clipboard(&mut self, cx: &mut Cx) {
+        let settings = self.store.preferences.clipboard_watch.clone();
+        if !settings.enabled {
+            if self.clipboard_watcher.take().is_some() {
+                self.hide_clipboard_offer(cx);
+            }
+            return;
+        }
+        let watcher = self.clipboard_watcher.get_or_insert_with(ClipboardWatcher::start);
+        match watcher.poll() {
+            Some(text) if settings.offers(&text) => {
+                let excerpt = bookmarks::excerpt(&text, 160);
+                self.ui.label(ids!(body.clipboard_popover.clipboard_card.clipboard_excerpt)).set_text(cx, &excerpt);
+                self.ui.view(ids!(body.clipboard_popover)).set_visible(cx, true);
+                self.clipboard_offer = Some((text, std::time::Instant::now()));
+                self.ui.redraw(cx);
+            }
+            _ => {
+                let expired = self.clipboard_offer.as_ref()
+                    .is_some_and(|(_, shown)| shown.elapsed().as_secs() >= CLIPBOARD_POPOVER_SECS);
+                if expired {
+                    self.hide_clipboard_offer(cx);
+                }
+            }
+        }
+    }
+
+    fn handle_clipboard_actions(&mut self, cx: &mut Cx, actions: &Actions) {
+        let buttons = [
+            (ids!(body.clipboard_popover.clipboard_card.clipboard_summarize_btn), Some(QuickAction::Summarize)),
+            (ids!(body.clipboard_popover.clipboard_card.clipboard_translate_btn), Some(QuickAction::Translate)),
+            (ids!(body.clipboard_popover.clipboard_card.clipboard_ask_btn), Some(QuickAction::Ask)),
+            (ids!(body.clipboard_popover.clipboard_card.clipboard_dismiss_btn), None),
+        ];
+        let Some((_, action)) = buttons.into_iter().find(|(button, _)| self.ui.view(*button).finger_down(actions).is_some()) else {
+            return;
+        };
+        let text = self.clipboard_offer.as_ref().map(|(text, _)| text.clone()).unwrap_or_default();
+        self.hide_clipboard_offer(cx);
+        let Some(action) = action else { return };
+        if let Some(mut chat_app) = self.ui.widget(ids!(body.body_layout.content.main_content.chat_with_canvas.chat_app))
+            .borrow_mut::<moly_chat::screen::ChatApp>()
+        {
+            chat_app.request_new_chat_with_prompt(action.prompt(&text));
+        }
+        self.navigate_to(cx, NavigationTarget::ActiveChat);
+        self.update_sidebar_chats(cx);
+    }
+
+    fn hide_clipboard_offer(&mut self, cx: &mut Cx) {
+        self.clipboard_offer = None;
+        self.ui.view(ids!(body.clipboard_popover)).set_visible(cx, false);
+        self.ui.redraw(cx);
+    }
+
+    // ── Quick Ask ───────────────────────────────────────────────────────────
+
+    /// Show the Quick Ask window, or bring its prompt back into focus
+    fn open_quick_ask(&mut self, cx: &mut Cx) {
+        if let Some(quick) = &self.quick_ask {
+            quick.window.text_input(ids!(body.quick_ask_input)).set_key_focus(cx);
+            return;
+        }
+        let window = WidgetRef::new_from_ptr(cx, self.quick_ask_window);
+        apply_quick_ask_theme(cx, &window, self.store.dark_mode);
+        let model = translate::loaded_llm(&ModelRegistry::load());
+        let status = match &model {
+            Some(model) => format!("Answered by {}", model.name),
+            None => "Load an LLM in the Model Hub to ask it here".to_string(),
+        };
+        window.label(ids!(body.quick_ask_footer.quick_ask_status)).set_text(cx, &status);
+        window.text_input(ids!(body.quick_ask_input)).set_key_focus(cx);
+        ::log::info!("Opened Quick Ask");
+        self.quick_ask = Some(QuickAsk { window, window_id: None, model, question: String::new(), answer: String::new(), rx: None });
+    }
+
+    fn handle_quick_ask_actions(&mut self, cx: &mut Cx, actions: &Actions) {
+        let Some(quick) = &mut self.quick_ask else { return };
+        let input = quick.window.text_input(ids!(body.quick_ask_input));
+        if input.returned(actions).is_some() {
+            let question = input.text();
+            if let Some(model) = quick.model.as_ref().filter(|_| !question.trim().is_empty()) {
+                quick.rx = Some(quick_ask::ask(model, &question));
+                quick.question = question;
+                quick.answer.clear();
+                quick.window.label(ids!(body.quick_ask_scroll.quick_ask_answer)).set_text(cx, "");
+                quick.window.label(ids!(body.quick_ask_footer.quick_ask_status)).set_text(cx, "Thinking…");
+                quick.window.redraw(cx);
+            }
+        }
+        if quick.window.view(ids!(body.quick_ask_footer.quick_ask_open_btn)).finger_down(actions).is_some() {
+            self.open_quick_ask_in_chat(cx);
+        } else if quick.window.view(ids!(body.quick_ask_footer.quick_ask_close_btn)).finger_down(actions).is_some() {
+            self.quick_ask = None;
+        }
+    }
+
+    /// Show what arrived of the answer
+    fn poll_quick_ask(&mut self, cx: &mut Cx) {
+        let Some(quick) = &mut self.quick_ask else { return };
+        let Some(rx) = &quick.rx else { return };
+        let events: Vec<AskEvent> = rx.try_iter().collect();
+        if events.is_empty() {
+            return;
+        }
+        let mut status = None;
+        for event in events {
+            match event {
+                AskEvent::Text(text) => quick.answer.push_str(&text),
+                AskEvent::Done => status = Some(String::new()),
+                AskEvent::Failed(e) => status = Some(e),
+            }
+        }
+        if let Some(status) = status {
+            quick.rx = None;
+            quick.window.label(ids!(body.quick_ask_footer.quick_ask_status)).set_text(cx, &status);
+        }
+        let answer = quick_ask::visible_answer(&quick.answer);
+        quick.window.label(ids!(body.quick_ask_scroll.quick_ask_answer)).set_text(cx, answer);
+        quick.window.redraw(cx);
+    }
+
+    /// Move the exchange into a new chat in the main window and close Quick
+    /// Ask. Before anything was asked, the typed text becomes the chat's prompt.
+    fn open_quick_ask_in_chat(&mut self, cx: &mut Cx) {
+        let Some(quick) = self.quick_ask.take() else { return };
+        let chat_app = self.ui.widget(ids!(body.body_layout.content.main_content.chat_with_canvas.chat_app));
+        match &quick.model {
+            Some(model) if !quick.question.trim().is_empty() => {
+                let model_id = &model.runtime.api_model_id;
+                let chat_id = self.store.chats.create_chat(Some(moly_kit::aitk::protocol::BotId::new(model_id)));
+                let answer = quick_ask::visible_answer(&quick.answer);
+                self.store.chats.update_chat_messages(chat_id, quick_ask::as_messages(model_id, &quick.question, answer));
+                if let Some(mut chat_app) = chat_app.borrow_mut::<moly_chat::screen::ChatApp>() {
+                    chat_app.load_chat(chat_id);
+                }
+            }
+            _ => {
+                let prompt = quick.window.text_input(ids!(body.quick_ask_input)).text();
+                if let Some(mut chat_app) = chat_app.borrow_mut::<moly_chat::screen::ChatApp>() {
+                    chat_app.request_new_chat_with_prompt(prompt);
+                }
+            }
+        }
+        self.navigate_to(cx, NavigationTarget::ActiveChat);
+        self.update_sidebar_chats(cx);
+    }
+
     // ── RAM gauge ───────────────────────────────────────────────────────────
 
     fn poll_ram_usage(&mut self, cx: &mut Cx) {
@@ -2871,14 +5057,45 @@ impl App {
         self.ram_total_gb = total;
         self.ram_usage = if total > 0.0 { (used / total).min(1.0) } else { 0.0 };
 
-        self.ui.view(ids!(body.body_layout.header.ram_gauge))
-            .apply_over(cx, live! { draw_bg: { usage: (self.ram_usage) } });
-        self.ui.label(ids!(body.body_layout.header.ram_label))
-            .set_text(cx, &format!("RAM\n{:.0}/{:.0}GB", self.ram_used_gb, self.ram_total_gb));
-        self.ui.redraw(cx);
+        // Only touch the gauge when the rounded reading moved; this runs
+        // every second on every screen
+        let usage = (self.ram_usage * 100.0).round() / 100.0;
+        let gauge = self.ui.view(ids!(body.body_layout.header.ram_gauge));
+        if self.ui_cache.changed_f64(gauge.widget_uid(), live_id!(usage), usage) {
+            gauge.apply_over(cx, live! { draw_bg: { usage: (usage) } });
+            gauge.redraw(cx);
+        }
+        let label = self.ui.label(ids!(body.body_layout.header.ram_label));
+        let text = format!("RAM\n{:.0}/{:.0}GB", self.ram_used_gb, self.ram_total_gb);
+        if self.ui_cache.set_text(cx, &label, &text) {
+            label.redraw(cx);
+        }
     }
 }
 
+/// Light or dark palette for the Quick Ask window
+fn apply_quick_ask_theme(cx: &mut Cx, window: &WidgetRef, dark_mode: bool) {
+    let theme = MolyTheme::for_mode(dark_mode);
+    let (background, text, muted) = (theme.background, theme.text_primary, theme.text_muted);
+    window.apply_over(cx, live! { pass: { clear_color: (background) } });
+    window.view(ids!(body)).apply_over(cx, live! { draw_bg: { color: (background) } });
+    window.label(ids!(body.quick_ask_scroll.quick_ask_answer)).apply_over(cx, live! { draw_text: { color: (text) } });
+    window.label(ids!(body.quick_ask_footer.quick_ask_status)).apply_over(cx, live! { draw_text: { color: (muted) } });
+    window.redraw(cx);
+}
+
+/// Light or dark palette for a detached chat window. Each window applies
+/// it to its own pass and views when the theme changes.
+fn apply_detached_theme(cx: &mut Cx, window: &WidgetRef, dark_mode: bool) {
+    let theme = MolyTheme::for_mode(dark_mode);
+    let (background, header, text) = (theme.background, theme.panel, theme.text_primary);
+    window.apply_over(cx, live! { pass: { clear_color: (background) } });
+    window.view(ids!(body)).apply_over(cx, live! { draw_bg: { color: (background) } });
+    window.view(ids!(body.detached_header)).apply_over(cx, live! { draw_bg: { color: (header) } });
+    window.label(ids!(body.detached_header.detached_title)).apply_over(cx, live! { draw_text: { color: (text) } });
+    window.redraw(cx);
+}
+
 fn get_system_ram() -> (f64, f64) {
     use std::process::Command;
 
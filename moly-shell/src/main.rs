@@ -49,6 +49,20 @@ fn main() {
 
     // Initialize the logger
     env_logger::init();
+
+    // A Quick Ask shortcut goes to the instance already running, if any.
+    // Returning before the exit hooks below leaves its runtime alone.
+    if std::env::args().any(|a| a == moly_data::quick_ask::FLAG) && moly_data::quick_ask::summon_running() {
+        log::info!("Quick Ask handed to the running instance");
+        return;
+    }
+    // So does a message link
+    if let Some(link) = moly_data::chat_navigation::link_arg() {
+        if moly_data::quick_ask::open_in_running(&link) {
+            log::info!("Link handed to the running instance");
+            return;
+        }
+    }
     log::info!("Starting Moly");
 
     // Install panic hook that appends ALL panics to /tmp/studio_panic.log
@@ -78,7 +92,8 @@ fn main() {
         }
     }
 
-    // Register atexit handler to kill ominix-api on exit.
+    // Register atexit handler to kill ominix-api and remove this session's
+    // generated files on exit.
     // On macOS, [NSApplication terminate:] calls exit() directly, so app_main()
     // never returns — atexit is the only reliable cleanup hook.
     #[cfg(unix)]
@@ -92,15 +107,18 @@ fn main() {
 
     // Belt-and-suspenders: also call here in case app_main() does return.
     moly_data::kill_server_process();
+    moly_data::artifacts::cleanup_session();
 }
 
 #[cfg(unix)]
 extern "C" fn cleanup_on_exit() {
     moly_data::kill_server_process();
+    moly_data::artifacts::cleanup_session();
 }
 
 #[cfg(unix)]
 extern "C" fn sigint_handler(_sig: libc::c_int) {
     moly_data::kill_server_process();
+    moly_data::artifacts::cleanup_session();
     std::process::exit(0);
 }
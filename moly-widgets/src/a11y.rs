@@ -0,0 +1,220 @@
+//! # Accessibility
+//!
+//! Shared keyboard navigation and contrast helpers for the app screens.
+//!
+//! - [`FocusChain`] gives a screen Tab / Shift+Tab traversal over its
+//!   interactive widgets in a fixed order. Enter or Space on a focused button
+//!   clicks it, so existing `clicked(&actions)` handlers work unchanged.
+//! - Each [`FocusTarget`] carries a role and an accessible label. Makepad has
+//!   no platform accessibility tree yet, so the label of the focused widget is
+//!   exposed through [`FocusChain::focused_description`] for screens to show.
+//! - [`contrast_ratio`] and [`meets_wcag_aa`] check text colors; the
+//!   [`crate::MolyTheme`] palettes are tested against them.
+//!
+//! ```rust,ignore
+//! self.focus = FocusChain::new(vec![
+//!     FocusTarget::new(ids!(search_input), Role::TextInput, "Search models"),
+//!     FocusTarget::new(ids!(download_btn), Role::Button, "Download"),
+//! ]);
+//!
+//! let actions = cx.capture_actions(|cx| {
+//!     self.view.handle_event(cx, event, scope);
+//!     self.focus.handle_event(cx, event, scope, &self.view);
+//! });
+//! ```
+
+use makepad_widgets::*;
+
+/// What kind of control a focus target is
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Role {
+    /// Activated with Enter or Space
+    Button,
+    /// Takes keyboard focus for typing
+    TextInput,
+    /// On/off switch; focus only, toggled by its own key handling
+    Toggle,
+    /// A list or grid the user can scroll through
+    List,
+}
+
+impl Role {
+    pub fn name(self) -> &'static str {
+        match self {
+            Role::Button => "button",
+            Role::TextInput => "text field",
+            Role::Toggle => "switch",
+            Role::List => "list",
+        }
+    }
+}
+
+/// One stop in a [`FocusChain`]
+#[derive(Clone, Debug)]
+pub struct FocusTarget {
+    /// Widget path below the root passed to [`FocusChain::handle_event`]
+    pub path: Vec<LiveId>,
+    pub role: Role,
+    /// Accessible name, e.g. "Save provider"
+    pub label: String,
+}
+
+impl FocusTarget {
+    pub fn new(path: &[LiveId], role: Role, label: impl Into<String>) -> Self {
+        Self { path: path.to_vec(), role, label: label.into() }
+    }
+}
+
+/// Where focus target paths are looked up: a screen's own `View`, or a
+/// `WidgetRef` such as an open modal
+pub trait FocusRoot {
+    fn find(&self, path: &[LiveId]) -> WidgetRef;
+}
+
+impl FocusRoot for View {
+    fn find(&self, path: &[LiveId]) -> WidgetRef {
+        self.widget(path)
+    }
+}
+
+impl FocusRoot for WidgetRef {
+    fn find(&self, path: &[LiveId]) -> WidgetRef {
+        self.widget(path)
+    }
+}
+
+/// Tab order over a screen's interactive widgets.
+///
+/// Targets that aren't on screen (hidden, or not found under the root) are
+/// skipped, so one chain can list controls of several panels or modals.
+#[derive(Clone, Debug, Default)]
+pub struct FocusChain {
+    targets: Vec<FocusTarget>,
+    focused: Option<usize>,
+}
+
+impl FocusChain {
+    pub fn new(targets: Vec<FocusTarget>) -> Self {
+        Self { targets, focused: None }
+    }
+
+    /// Move focus on Tab / Shift+Tab and click the focused button on Enter
+    /// or Space. Call inside the screen's `capture_actions` so the click is
+    /// seen by the screen's normal action handlers.
+    pub fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope, root: &dyn FocusRoot) {
+        match event {
+            Event::KeyDown(ke) if ke.key_code == KeyCode::Tab => {
+                self.move_focus(cx, root, !ke.modifiers.shift);
+            }
+            Event::KeyDown(ke) if matches!(ke.key_code, KeyCode::ReturnKey | KeyCode::Space) => {
+                let Some(target) = self.focused.and_then(|i| self.targets.get(i)) else { return };
+                if target.role != Role::Button { return; }
+                let widget = root.find(&target.path);
+                if widget.is_empty() || !widget.area().is_valid(cx) { return; }
+                cx.widget_action(widget.widget_uid(), &scope.path, ButtonAction::Clicked(KeyModifiers::default()));
+            }
+            // A click elsewhere ends keyboard navigation
+            Event::MouseDown(_) => self.clear(cx, root),
+            _ => {}
+        }
+    }
+
+    /// The focused target as "label, role", for status lines and tooltips
+    pub fn focused_description(&self) -> Option<String> {
+        let target = self.targets.get(self.focused?)?;
+        Some(format!("{}, {}", target.label, target.role.name()))
+    }
+
+    /// Drop focus, e.g. when the screen is hidden
+    pub fn clear(&mut self, cx: &mut Cx, root: &dyn FocusRoot) {
+        if let Some(target) = self.focused.take().and_then(|i| self.targets.get(i)) {
+            set_focus_highlight(cx, &root.find(&target.path), false);
+        }
+    }
+
+    fn move_focus(&mut self, cx: &mut Cx, root: &dyn FocusRoot, forward: bool) {
+        let len = self.targets.len();
+        if len == 0 { return; }
+        let previous = self.focused;
+        let start = match (previous, forward) {
+            (Some(i), true) => i + 1,
+            (Some(i), false) => i + len - 1,
+            (None, true) => 0,
+            (None, false) => len - 1,
+        };
+        for step in 0..len {
+            let index = if forward { (start + step) % len } else { (start + len - step) % len };
+            let widget = root.find(&self.targets[index].path);
+            if widget.is_empty() || !widget.area().is_valid(cx) { continue; }
+
+            if let Some(prev) = previous.and_then(|i| self.targets.get(i)) {
+                set_focus_highlight(cx, &root.find(&prev.path), false);
+            }
+            self.focused = Some(index);
+            match self.targets[index].role {
+                Role::TextInput => widget.as_text_input().set_key_focus(cx),
+                _ => {
+                    cx.set_key_focus(widget.area());
+                    set_focus_highlight(cx, &widget, true);
+                }
+            }
+            widget.redraw(cx);
+            return;
+        }
+    }
+}
+
+/// Drive the `focus` instance that the button and toggle shaders use for
+/// their focus ring
+fn set_focus_highlight(cx: &mut Cx, widget: &WidgetRef, on: bool) {
+    let value = if on { 1.0 } else { 0.0 };
+    widget.apply_over(cx, live! { draw_bg: { focus: (value) } });
+    widget.redraw(cx);
+}
+
+// ─── Contrast ───────────────────────────────────────────────────────────────
+
+/// WCAG relative luminance of an sRGB color (alpha ignored)
+pub fn relative_luminance(color: Vec4) -> f32 {
+    let linear = |c: f32| if c <= 0.03928 { c / 12.92 } else { ((c + 0.055) / 1.055).powf(2.4) };
+    0.2126 * linear(color.x) + 0.7152 * linear(color.y) + 0.0722 * linear(color.z)
+}
+
+/// WCAG contrast ratio between two colors, from 1.0 to 21.0
+pub fn contrast_ratio(a: Vec4, b: Vec4) -> f32 {
+    let (la, lb) = (relative_luminance(a), relative_luminance(b));
+    (la.max(lb) + 0.05) / (la.min(lb) + 0.05)
+}
+
+/// Whether text in `fg` on `bg` meets WCAG AA: 4.5:1, or 3:1 for large text
+/// (18pt, or 14pt bold)
+pub fn meets_wcag_aa(fg: Vec4, bg: Vec4, large_text: bool) -> bool {
+    contrast_ratio(fg, bg) >= if large_text { 3.0 } else { 4.5 }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MolyTheme;
+
+    #[test]
+    fn test_contrast_ratio() {
+        let black = vec4(0.0, 0.0, 0.0, 1.0);
+        let white = vec4(1.0, 1.0, 1.0, 1.0);
+        assert!((contrast_ratio(black, white) - 21.0).abs() < 0.01);
+        assert!((contrast_ratio(white, white) - 1.0).abs() < 0.01);
+        // gray-400 on white, the old muted text color, fails
+        assert!(!meets_wcag_aa(vec4(0.612, 0.639, 0.686, 1.0), white, false));
+    }
+
+    #[test]
+    fn test_theme_palettes_meet_aa() {
+        for theme in [MolyTheme::light(), MolyTheme::dark()] {
+            for (name, fg, bg) in theme.text_pairs() {
+                assert!(meets_wcag_aa(fg, bg, false),
+                    "{} fails AA in {} theme: {:.2}", name,
+                    if theme.dark_mode { "dark" } else { "light" }, contrast_ratio(fg, bg));
+            }
+        }
+    }
+}
@@ -0,0 +1,202 @@
+use makepad_widgets::*;
+
+use super::MolyDialog;
+
+live_design! {
+    use link::theme::*;
+    use link::shaders::*;
+    use link::widgets::*;
+    use crate::theme::*;
+
+    DialogButton = <Button> {
+        width: Fit, height: 34
+        padding: {left: 16, right: 16}
+        animator: {
+            hover = {
+                default: off,
+                off = { from: {all: Forward {duration: 0.1}} apply: { draw_bg: {hover: 0.0} } }
+                on  = { from: {all: Forward {duration: 0.1}} apply: { draw_bg: {hover: 1.0} } }
+            }
+            pressed = {
+                default: off,
+                off = { from: {all: Forward {duration: 0.07}} apply: { draw_bg: {pressed: 0.0} } }
+                on  = { from: {all: Forward {duration: 0.07}} apply: { draw_bg: {pressed: 1.0} } }
+            }
+        }
+        draw_bg: {
+            instance hover: 0.0
+            instance pressed: 0.0
+            instance focus: 0.0
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                sdf.box(1.0, 1.0, self.rect_size.x - 2.0, self.rect_size.y - 2.0, 6.0);
+                let color = mix(#f3f4f6, #e5e7eb, self.hover);
+                sdf.fill(mix(color, color * 0.95, self.pressed));
+                sdf.stroke(mix(#d1d5db, #3b82f6, self.focus), 1.0);
+                return sdf.result;
+            }
+        }
+        draw_text: {
+            fn get_color(self) -> vec4 { return #374151; }
+            text_style: <FONT_MEDIUM>{ font_size: 11.0 }
+        }
+    }
+
+    // `danger` turns the confirm button red for destructive actions
+    DialogConfirmButton = <DialogButton> {
+        draw_bg: {
+            instance danger: 0.0
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                sdf.box(1.0, 1.0, self.rect_size.x - 2.0, self.rect_size.y - 2.0, 6.0);
+                let primary = mix(#2563fa, #1d4fd8, self.hover);
+                let danger  = mix(#b91c1c, #991b1b, self.hover);
+                let color = mix(primary, danger, self.danger);
+                sdf.fill(mix(color, color * 0.9, self.pressed));
+                sdf.stroke(mix(color, #1f2937, self.focus), 1.0);
+                return sdf.result;
+            }
+        }
+        draw_text: {
+            fn get_color(self) -> vec4 { return #ffffff; }
+        }
+    }
+
+    // One choice of a select dialog; `selected` highlights the current one, or
+    // the checked ones of a multi-select dialog
+    DialogOption = <Button> {
+        width: Fill, height: 32
+        padding: {left: 12, right: 12}
+        align: {x: 0.0, y: 0.5}
+        visible: false
+        animator: {
+            hover = {
+                default: off,
+                off = { from: {all: Forward {duration: 0.1}} apply: { draw_bg: {hover: 0.0} } }
+                on  = { from: {all: Forward {duration: 0.1}} apply: { draw_bg: {hover: 1.0} } }
+            }
+        }
+        draw_bg: {
+            instance hover: 0.0
+            instance selected: 0.0
+            instance focus: 0.0
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                sdf.box(0.0, 0.0, self.rect_size.x, self.rect_size.y, 5.0);
+                let normal = mix(#ffffff, #f1f5f9, self.hover);
+                sdf.fill(mix(normal, #dbeafe, self.selected));
+                sdf.stroke(mix(#00000000, #3b82f6, self.focus), 1.0);
+                return sdf.result;
+            }
+        }
+        draw_text: {
+            fn get_color(self) -> vec4 { return #1f2937; }
+            text_style: <FONT_REGULAR>{ font_size: 11.0 }
+        }
+    }
+
+    pub MolyDialog = {{MolyDialog}} {
+        width: Fill, height: Fill
+        flow: Overlay
+        visible: false
+
+        // Clicking outside the card cancels
+        backdrop = <View> {
+            width: Fill, height: Fill
+            cursor: Arrow
+            show_bg: true
+            draw_bg: {
+                fn pixel(self) -> vec4 {
+                    return vec4(0.0, 0.0, 0.0, 0.45);
+                }
+            }
+        }
+
+        <View> {
+            width: Fill, height: Fill
+            align: {x: 0.5, y: 0.5}
+
+            card = <RoundedView> {
+                width: 420, height: Fit
+                flow: Down
+                padding: 24
+                spacing: 14
+                show_bg: true
+                draw_bg: {
+                    color: (PANEL_BG)
+                    border_radius: 10.0
+                    border_color: (BORDER)
+                    border_size: 1.0
+                }
+
+                title = <Label> {
+                    width: Fill, height: Fit
+                    draw_text: {
+                        color: (TEXT_PRIMARY)
+                        text_style: <FONT_SEMIBOLD>{ font_size: 15.0 }
+                        wrap: Word
+                    }
+                }
+
+                message = <Label> {
+                    width: Fill, height: Fit
+                    draw_text: {
+                        color: (TEXT_SECONDARY)
+                        text_style: <FONT_REGULAR>{ font_size: 11.0 }
+                        wrap: Word
+                    }
+                }
+
+                text_input = <TextInput> {
+                    width: Fill, height: 36
+                    visible: false
+                    cursor: Text
+                    draw_bg: {
+                        fn pixel(self) -> vec4 {
+                            let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                            sdf.box(1.0, 1.0, self.rect_size.x - 2.0, self.rect_size.y - 2.0, 5.0);
+                            sdf.fill(#f8fafc);
+                            sdf.stroke(#d1d5db, 1.0);
+                            return sdf.result;
+                        }
+                    }
+                    draw_text: {
+                        color: (TEXT_PRIMARY)
+                        color_empty: (TEXT_MUTED)
+                        text_style: { font_size: 12.0 }
+                    }
+                }
+
+                options = <View> {
+                    width: Fill, height: Fit
+                    flow: Down
+                    spacing: 2
+                    visible: false
+                    option_0 = <DialogOption> {}
+                    option_1 = <DialogOption> {}
+                    option_2 = <DialogOption> {}
+                    option_3 = <DialogOption> {}
+                    option_4 = <DialogOption> {}
+                    option_5 = <DialogOption> {}
+                    option_6 = <DialogOption> {}
+                    option_7 = <DialogOption> {}
+                    option_8 = <DialogOption> {}
+                    option_9 = <DialogOption> {}
+                    option_10 = <DialogOption> {}
+                    option_11 = <DialogOption> {}
+                }
+
+                actions = <View> {
+                    width: Fill, height: Fit
+                    flow: Right
+                    spacing: 10
+                    margin: {top: 6}
+                    align: {x: 1.0}
+
+                    cancel_btn = <DialogButton> { text: "Cancel" }
+                    confirm_btn = <DialogConfirmButton> { text: "OK" }
+                }
+            }
+        }
+    }
+}
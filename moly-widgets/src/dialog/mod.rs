@@ -0,0 +1,429 @@
+//! # MolyDialog - Confirm, text input, select and multi-select dialogs
+//!
+//! A modal overlay that apps place once, as the last child of a
+//! `flow: Overlay` view, and open from Rust with a [`DialogRequest`]. The
+//! answer arrives later as a [`DialogAction::Closed`] carrying the request's
+//! id, so the invoking app keeps handling it in its normal action loop:
+//!
+//! ```rust,ignore
+//! self.view.moly_dialog(ids!(dialog)).open(cx,
+//!     DialogRequest::confirm(live_id!(delete_chat), "Delete chat?", "This can't be undone.")
+//!         .confirm_label("Delete")
+//!         .danger());
+//!
+//! // later, in handle_actions
+//! if let Some((live_id!(delete_chat), DialogResult::Confirmed)) = self.view.moly_dialog(ids!(dialog)).closed(actions) {
+//!     ...
+//! }
+//! ```
+//!
+//! Requests made while a dialog is showing are queued and shown in turn.
+//! Escape and clicks outside the card cancel; Tab moves between the controls.
+
+pub mod design;
+
+use crate::a11y::{FocusChain, FocusTarget, Role};
+use makepad_widgets::*;
+use std::collections::VecDeque;
+
+/// Choices a select or multi-select dialog can show
+pub const MAX_SELECT_OPTIONS: usize = 12;
+
+/// Widget ids of the option slots in `design.rs`
+const OPTION_IDS: [LiveId; MAX_SELECT_OPTIONS] = [
+    live_id!(option_0), live_id!(option_1), live_id!(option_2), live_id!(option_3),
+    live_id!(option_4), live_id!(option_5), live_id!(option_6), live_id!(option_7),
+    live_id!(option_8), live_id!(option_9), live_id!(option_10), live_id!(option_11),
+];
+
+fn option_path(index: usize) -> [LiveId; 2] {
+    [live_id!(options), OPTION_IDS[index]]
+}
+
+/// What the dialog asks for
+#[derive(Clone, Debug, PartialEq)]
+pub enum DialogKind {
+    /// Yes or no
+    Confirm,
+    /// A line of text, e.g. a new chat title
+    TextInput { initial: String, placeholder: String },
+    /// One of up to [`MAX_SELECT_OPTIONS`] options
+    Select { options: Vec<String>, selected: usize },
+    /// Any number of up to [`MAX_SELECT_OPTIONS`] options, toggled by
+    /// clicking them
+    MultiSelect { options: Vec<String>, checked: Vec<bool> },
+}
+
+impl DialogKind {
+    /// Select option `index`, or toggle it in a multi-select dialog
+    fn click_option(&mut self, index: usize) {
+        match self {
+            DialogKind::Select { options, selected } if index < options.len() => *selected = index,
+            DialogKind::MultiSelect { checked, .. } => {
+                if let Some(on) = checked.get_mut(index) {
+                    *on = !*on;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// The result of accepting, with `text` typed into a text input dialog
+    fn accepted(&self, text: &str) -> DialogResult {
+        match self {
+            DialogKind::Confirm => DialogResult::Confirmed,
+            DialogKind::TextInput { .. } => DialogResult::Text(text.trim().to_string()),
+            DialogKind::Select { selected, .. } => DialogResult::Selected(*selected),
+            DialogKind::MultiSelect { checked, .. } => {
+                DialogResult::MultiSelected(checked.iter().enumerate().filter(|(_, on)| **on).map(|(i, _)| i).collect())
+            }
+        }
+    }
+}
+
+/// A question for the user
+#[derive(Clone, Debug)]
+pub struct DialogRequest {
+    /// Chosen by the caller to tell its dialogs apart when the result arrives
+    pub id: LiveId,
+    pub title: String,
+    pub message: String,
+    pub confirm_label: String,
+    /// Red confirm button, for destructive actions
+    pub danger: bool,
+    pub kind: DialogKind,
+}
+
+impl DialogRequest {
+    pub fn confirm(id: LiveId, title: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            id,
+            title: title.into(),
+            message: message.into(),
+            confirm_label: "OK".to_string(),
+            danger: false,
+            kind: DialogKind::Confirm,
+        }
+    }
+
+    pub fn text_input(id: LiveId, title: impl Into<String>, message: impl Into<String>, initial: impl Into<String>) -> Self {
+        Self {
+            kind: DialogKind::TextInput { initial: initial.into(), placeholder: String::new() },
+            confirm_label: "Save".to_string(),
+            ..Self::confirm(id, title, message)
+        }
+    }
+
+    pub fn select(id: LiveId, title: impl Into<String>, message: impl Into<String>, options: Vec<String>, selected: usize) -> Self {
+        if options.len() > MAX_SELECT_OPTIONS {
+            ::log::warn!("Dialog {:?} has {} options; only {} are shown", id, options.len(), MAX_SELECT_OPTIONS);
+        }
+        Self {
+            kind: DialogKind::Select { options, selected },
+            ..Self::confirm(id, title, message)
+        }
+    }
+
+    /// `checked` holds the initial state of each option
+    pub fn multi_select(id: LiveId, title: impl Into<String>, message: impl Into<String>, options: Vec<String>, mut checked: Vec<bool>) -> Self {
+        if options.len() > MAX_SELECT_OPTIONS {
+            ::log::warn!("Dialog {:?} has {} options; only {} are shown", id, options.len(), MAX_SELECT_OPTIONS);
+        }
+        checked.resize(options.len(), false);
+        Self {
+            kind: DialogKind::MultiSelect { options, checked },
+            ..Self::confirm(id, title, message)
+        }
+    }
+
+    pub fn confirm_label(mut self, label: impl Into<String>) -> Self {
+        self.confirm_label = label.into();
+        self
+    }
+
+    /// Hint shown in an empty text input
+    pub fn placeholder(mut self, text: impl Into<String>) -> Self {
+        if let DialogKind::TextInput { placeholder, .. } = &mut self.kind {
+            *placeholder = text.into();
+        }
+        self
+    }
+
+    pub fn danger(mut self) -> Self {
+        self.danger = true;
+        self
+    }
+}
+
+/// How the user answered
+#[derive(Clone, Debug, PartialEq)]
+pub enum DialogResult {
+    /// A confirm dialog was accepted
+    Confirmed,
+    /// A text input dialog was accepted with this text (trimmed)
+    Text(String),
+    /// A select dialog was accepted with this option index
+    Selected(usize),
+    /// A multi-select dialog was accepted with these option indices checked
+    MultiSelected(Vec<usize>),
+    Cancelled,
+}
+
+/// The request being shown and the ones waiting their turn
+#[derive(Default)]
+struct DialogQueue {
+    current: Option<DialogRequest>,
+    waiting: VecDeque<DialogRequest>,
+}
+
+impl DialogQueue {
+    /// Add a request; true when it is the one to show now
+    fn push(&mut self, request: DialogRequest) -> bool {
+        if self.current.is_some() {
+            self.waiting.push_back(request);
+            false
+        } else {
+            self.current = Some(request);
+            true
+        }
+    }
+
+    /// Finish the current request, making the next waiting one current
+    fn advance(&mut self) -> Option<DialogRequest> {
+        let finished = self.current.take()?;
+        self.current = self.waiting.pop_front();
+        Some(finished)
+    }
+
+    fn kind(&self) -> Option<&DialogKind> {
+        self.current.as_ref().map(|r| &r.kind)
+    }
+}
+
+#[derive(Clone, Debug, DefaultNone)]
+pub enum DialogAction {
+    None,
+    /// (request id, result)
+    Closed(LiveId, DialogResult),
+}
+
+#[derive(Live, LiveHook, Widget)]
+pub struct MolyDialog {
+    #[deref]
+    view: View,
+
+    #[rust] dialogs: DialogQueue,
+    #[rust] focus: FocusChain,
+}
+
+impl Widget for MolyDialog {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        if self.dialogs.current.is_none() { return; }
+
+        let actions = cx.capture_actions(|cx| {
+            self.view.handle_event(cx, event, scope);
+            self.focus.handle_event(cx, event, scope, &self.view);
+        });
+
+        let result = if self.view.button(ids!(confirm_btn)).clicked(&actions) {
+            Some(self.accepted_result())
+        } else if self.view.button(ids!(cancel_btn)).clicked(&actions)
+            || self.view.view(ids!(backdrop)).finger_down(&actions).is_some()
+        {
+            Some(DialogResult::Cancelled)
+        } else if self.view.text_input(ids!(text_input)).returned(&actions).is_some() {
+            Some(self.accepted_result())
+        } else {
+            match event {
+                Event::KeyDown(ke) if ke.key_code == KeyCode::Escape => Some(DialogResult::Cancelled),
+                // Enter accepts unless Tab focus is on a button, which Enter clicks
+                Event::KeyDown(ke) if ke.key_code == KeyCode::ReturnKey
+                    && self.focus.focused_description().is_none()
+                    && !matches!(self.dialogs.kind(), Some(DialogKind::TextInput { .. })) =>
+                {
+                    Some(self.accepted_result())
+                }
+                _ => None,
+            }
+        };
+
+        if let Some(result) = result {
+            self.close(cx, scope, result);
+            return;
+        }
+
+        for i in 0..MAX_SELECT_OPTIONS {
+            if self.view.button(&option_path(i)).clicked(&actions) {
+                if let Some(request) = self.dialogs.current.as_mut() {
+                    request.kind.click_option(i);
+                }
+                self.update_options(cx);
+            }
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        self.view.draw_walk(cx, scope, walk)
+    }
+}
+
+impl MolyDialog {
+    /// Show a request now, or after the dialogs already waiting
+    pub fn open(&mut self, cx: &mut Cx, request: DialogRequest) {
+        if self.dialogs.push(request) {
+            self.show(cx);
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.dialogs.current.is_some()
+    }
+
+    /// Fill the card from the current request
+    fn show(&mut self, cx: &mut Cx) {
+        let Some(request) = self.dialogs.current.clone() else { return };
+        self.view.label(ids!(title)).set_text(cx, &request.title);
+        let message = self.view.label(ids!(message));
+        message.set_text(cx, &request.message);
+        message.set_visible(cx, !request.message.is_empty());
+
+        let confirm = self.view.button(ids!(confirm_btn));
+        confirm.set_text(cx, &request.confirm_label);
+        let danger = if request.danger { 1.0 } else { 0.0 };
+        confirm.apply_over(cx, live! { draw_bg: { danger: (danger) } });
+
+        let mut targets = Vec::new();
+        let input = self.view.text_input(ids!(text_input));
+        match &request.kind {
+            DialogKind::TextInput { initial, placeholder } => {
+                input.set_text(cx, initial);
+                input.apply_over(cx, live! { empty_text: (placeholder.as_str()) });
+                input.set_visible(cx, true);
+                input.set_key_focus(cx);
+                targets.push(FocusTarget::new(ids!(text_input), Role::TextInput, request.title.clone()));
+            }
+            _ => input.set_visible(cx, false),
+        }
+        let options = self.view.view(ids!(options));
+        match &request.kind {
+            DialogKind::Select { options: labels, .. } | DialogKind::MultiSelect { options: labels, .. } => {
+                options.set_visible(cx, true);
+                for i in 0..MAX_SELECT_OPTIONS {
+                    let id = option_path(i);
+                    let button = self.view.button(&id);
+                    button.set_visible(cx, i < labels.len());
+                    if let Some(label) = labels.get(i) {
+                        button.set_text(cx, label);
+                        targets.push(FocusTarget::new(&id, Role::Button, label.clone()));
+                    }
+                }
+            }
+            _ => options.set_visible(cx, false),
+        }
+        targets.push(FocusTarget::new(ids!(cancel_btn), Role::Button, "Cancel"));
+        targets.push(FocusTarget::new(ids!(confirm_btn), Role::Button, request.confirm_label.clone()));
+
+        self.focus = FocusChain::new(targets);
+        self.update_options(cx);
+        self.view.set_visible(cx, true);
+        self.view.redraw(cx);
+    }
+
+    /// Highlight the selected option of a select dialog, or the checked
+    /// ones of a multi-select dialog
+    fn update_options(&mut self, cx: &mut Cx) {
+        let checked: Vec<bool> = match self.dialogs.kind() {
+            Some(DialogKind::Select { selected, .. }) => (0..MAX_SELECT_OPTIONS).map(|i| i == *selected).collect(),
+            Some(DialogKind::MultiSelect { checked, .. }) => checked.clone(),
+            _ => return,
+        };
+        for i in 0..MAX_SELECT_OPTIONS {
+            let on = if checked.get(i).copied().unwrap_or(false) { 1.0 } else { 0.0 };
+            self.view.button(&option_path(i))
+                .apply_over(cx, live! { draw_bg: { selected: (on) } });
+        }
+        self.view.view(ids!(options)).redraw(cx);
+    }
+
+    fn accepted_result(&self) -> DialogResult {
+        let text = self.view.text_input(ids!(text_input)).text();
+        self.dialogs.kind().map_or(DialogResult::Confirmed, |kind| kind.accepted(&text))
+    }
+
+    /// Deliver the result to the invoking app and show the next request
+    fn close(&mut self, cx: &mut Cx, scope: &mut Scope, result: DialogResult) {
+        let Some(request) = self.dialogs.advance() else { return };
+        self.focus.clear(cx, &self.view);
+        cx.widget_action(self.widget_uid(), &scope.path, DialogAction::Closed(request.id, result));
+        if self.dialogs.current.is_some() {
+            self.show(cx);
+        } else {
+            self.view.set_visible(cx, false);
+            self.view.redraw(cx);
+        }
+    }
+
+    /// The result of a dialog closed during this event, with its request id
+    pub fn closed(&self, actions: &Actions) -> Option<(LiveId, DialogResult)> {
+        match actions.find_widget_action(self.widget_uid())?.cast() {
+            DialogAction::Closed(id, result) => Some((id, result)),
+            DialogAction::None => None,
+        }
+    }
+}
+
+impl MolyDialogRef {
+    pub fn open(&self, cx: &mut Cx, request: DialogRequest) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.open(cx, request);
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.borrow().map_or(false, |inner| inner.is_open())
+    }
+
+    pub fn closed(&self, actions: &Actions) -> Option<(LiveId, DialogResult)> {
+        self.borrow().and_then(|inner| inner.closed(actions))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_requests_are_shown_in_turn() {
+        let mut dialogs = DialogQueue::default();
+        assert!(dialogs.push(DialogRequest::confirm(live_id!(first), "First", "")));
+        assert!(!dialogs.push(DialogRequest::confirm(live_id!(second), "Second", "")));
+        assert!(!dialogs.push(DialogRequest::confirm(live_id!(third), "Third", "")));
+
+        assert_eq!(dialogs.advance().map(|r| r.id), Some(live_id!(first)));
+        assert_eq!(dialogs.current.as_ref().map(|r| r.id), Some(live_id!(second)));
+        assert_eq!(dialogs.advance().map(|r| r.id), Some(live_id!(second)));
+        assert_eq!(dialogs.advance().map(|r| r.id), Some(live_id!(third)));
+        assert!(dialogs.advance().is_none());
+        assert!(dialogs.push(DialogRequest::confirm(live_id!(fourth), "Fourth", "")));
+    }
+
+    #[test]
+    fn test_accepted_results() {
+        let options = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        assert_eq!(DialogKind::Confirm.accepted("ignored"), DialogResult::Confirmed);
+        let rename = DialogRequest::text_input(live_id!(rename_chat), "Rename", "", "Old");
+        assert_eq!(rename.kind.accepted("  New title "), DialogResult::Text("New title".to_string()));
+
+        let mut select = DialogRequest::select(live_id!(pick), "Pick", "", options.clone(), 0).kind;
+        select.click_option(2);
+        select.click_option(7);
+        assert_eq!(select.accepted(""), DialogResult::Selected(2));
+
+        let mut multi = DialogRequest::multi_select(live_id!(tools), "Tools", "", options, vec![true]).kind;
+        assert_eq!(multi, DialogKind::MultiSelect { options: vec!["a".into(), "b".into(), "c".into()], checked: vec![true, false, false] });
+        multi.click_option(0);
+        multi.click_option(2);
+        multi.click_option(7);
+        assert_eq!(multi.accepted(""), DialogResult::MultiSelected(vec![2]));
+    }
+}
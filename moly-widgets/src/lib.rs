@@ -4,11 +4,19 @@ pub mod app_trait;
 pub mod moly_theme;
 pub mod app_data;
 pub mod page_router;
+pub mod voice_studio;
+pub mod a11y;
+pub mod ui_state;
+pub mod dialog;
 
 pub use app_trait::{MolyApp, AppInfo, AppRegistry, TimerControl};
 pub use moly_theme::MolyTheme;
 pub use app_data::{MolyAppData, AppAction};
 pub use page_router::PageRouter;
+pub use voice_studio::{VoiceStudio, VoiceStudioRef};
+pub use a11y::{FocusChain, FocusRoot, FocusTarget};
+pub use ui_state::UiStateCache;
+pub use dialog::{DialogRequest, DialogResult, MolyDialog, MolyDialogRef, MolyDialogWidgetRefExt};
 
 use makepad_widgets::Cx;
 
@@ -22,4 +30,7 @@ pub fn live_design(cx: &mut Cx) {
     theme::live_design(cx);
     // Shared components depend on theme
     components::live_design(cx);
+    // Voice Studio places a dialog
+    dialog::design::live_design(cx);
+    voice_studio::design::live_design(cx);
 }
@@ -1,7 +1,80 @@
 //! # MolyTheme - Runtime Theme
 //!
-//! Placeholder for future theme support.
+//! Light and dark palettes for colors applied from Rust (`apply_over`),
+//! e.g. when a window switches theme at runtime. The light palette mirrors
+//! the semantic colors in [`crate::theme`]; every text color meets WCAG AA
+//! contrast against both backgrounds (see [`crate::a11y::contrast_ratio`]).
 
-/// Runtime theme state
-#[derive(Clone, Debug, Default)]
-pub struct MolyTheme;
+use makepad_widgets::{vec4, Vec4};
+
+/// Colors of one theme
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MolyTheme {
+    pub dark_mode: bool,
+    /// Window / page background
+    pub background: Vec4,
+    /// Card, header and panel background
+    pub panel: Vec4,
+    pub text_primary: Vec4,
+    pub text_secondary: Vec4,
+    /// Hints and placeholders
+    pub text_muted: Vec4,
+    /// Links and primary actions
+    pub accent: Vec4,
+}
+
+impl Default for MolyTheme {
+    fn default() -> Self {
+        Self::light()
+    }
+}
+
+impl MolyTheme {
+    pub fn light() -> Self {
+        Self {
+            dark_mode: false,
+            background: hex(0xf5f7fa),
+            panel: hex(0xffffff),
+            text_primary: hex(0x1f2937),
+            text_secondary: hex(0x4b5563),
+            text_muted: hex(0x646c79),
+            accent: hex(0x2565fb),
+        }
+    }
+
+    pub fn dark() -> Self {
+        Self {
+            dark_mode: true,
+            background: hex(0x0f172a),
+            panel: hex(0x1e293b),
+            text_primary: hex(0xe5e7eb),
+            text_secondary: hex(0xcbd5e1),
+            text_muted: hex(0x94a3b8),
+            accent: hex(0x60a5fa),
+        }
+    }
+
+    /// The palette for `store.dark_mode`
+    pub fn for_mode(dark_mode: bool) -> Self {
+        if dark_mode { Self::dark() } else { Self::light() }
+    }
+
+    /// Every text color paired with every background it is drawn on
+    pub fn text_pairs(&self) -> Vec<(&'static str, Vec4, Vec4)> {
+        let texts = [
+            ("text_primary", self.text_primary),
+            ("text_secondary", self.text_secondary),
+            ("text_muted", self.text_muted),
+            ("accent", self.accent),
+        ];
+        texts.iter()
+            .flat_map(|&(name, fg)| [(name, fg, self.background), (name, fg, self.panel)])
+            .collect()
+    }
+}
+
+/// Opaque color from `0xRRGGBB`
+fn hex(rgb: u32) -> Vec4 {
+    let channel = |shift: u32| ((rgb >> shift) & 0xff) as f32 / 255.0;
+    vec4(channel(16), channel(8), channel(0), 1.0)
+}
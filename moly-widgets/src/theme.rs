@@ -24,8 +24,8 @@ live_design! {
     pub ACCENT_YELLOW = #f59f0b    // Warning
     pub ACCENT_INDIGO = #6366f1    // Secondary accent
     pub TEXT_PRIMARY = #1f2937     // Main text (gray-800)
-    pub TEXT_SECONDARY = #6b7280   // Secondary text (gray-500)
-    pub TEXT_MUTED = #9ca3af       // Muted/disabled text (gray-400)
+    pub TEXT_SECONDARY = #4b5563   // Secondary text (gray-600)
+    pub TEXT_MUTED = #646c79       // Hints/placeholders (gray-500, darkened to 4.5:1 on panels)
     pub DIVIDER = #e2e8f0          // Divider lines (slate-200)
     pub BORDER = #e5e7eb           // Border color (gray-200)
     pub HOVER_BG = #f1f5f9         // Hover background (slate-100)
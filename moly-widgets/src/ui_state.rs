@@ -0,0 +1,77 @@
+//! # UiStateCache - Skip Unchanged Widget Updates
+//!
+//! Refresh functions like a hub panel header set a dozen labels, visibility
+//! flags and shader instances each time anything about the model changes,
+//! and download progress refreshes them every frame. Routing those writes
+//! through a [`UiStateCache`] remembers the last value sent to each widget
+//! property, so only properties whose value actually changed get a
+//! `set_text` / `set_visible` / `apply_over` call and a redraw.
+//!
+//! A cache only knows about writes that went through it: if some other code
+//! path changes the same widget directly, call [`UiStateCache::invalidate`].
+
+use makepad_widgets::*;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+/// Last value written to each (widget, property)
+#[derive(Clone, Debug, Default)]
+pub struct UiStateCache {
+    values: HashMap<(WidgetUid, LiveId), u64>,
+}
+
+impl UiStateCache {
+    /// Record `value` for the property and report whether it differs from
+    /// the last recorded one
+    pub fn changed<T: Hash + ?Sized>(&mut self, widget: WidgetUid, property: LiveId, value: &T) -> bool {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        let hash = hasher.finish();
+        self.values.insert((widget, property), hash) != Some(hash)
+    }
+
+    /// Set a label's text if it changed. Returns whether it was written.
+    pub fn set_text(&mut self, cx: &mut Cx, label: &LabelRef, text: &str) -> bool {
+        if !self.changed(label.widget_uid(), live_id!(text), text) { return false; }
+        label.set_text(cx, text);
+        true
+    }
+
+    /// Show or hide a widget if that changed. Returns whether it was written.
+    pub fn set_visible(&mut self, cx: &mut Cx, widget: &WidgetRef, visible: bool) -> bool {
+        if !self.changed(widget.widget_uid(), live_id!(visible), &visible) { return false; }
+        widget.set_visible(cx, visible);
+        true
+    }
+
+    /// [`changed`](Self::changed) for floats, e.g. shader instances set with
+    /// `apply_over`
+    pub fn changed_f64(&mut self, widget: WidgetUid, property: LiveId, value: f64) -> bool {
+        self.changed(widget, property, &value.to_bits())
+    }
+
+    /// Forget everything, so the next writes all go through
+    pub fn invalidate(&mut self) {
+        self.values.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_changed() {
+        let mut cache = UiStateCache::default();
+        let uid = WidgetUid(1);
+        assert!(cache.changed(uid, live_id!(text), "Downloading"));
+        assert!(!cache.changed(uid, live_id!(text), "Downloading"));
+        assert!(cache.changed(uid, live_id!(text), "Downloaded"));
+        // Properties and widgets are tracked separately
+        assert!(cache.changed(uid, live_id!(visible), &true));
+        assert!(cache.changed(WidgetUid(2), live_id!(text), "Downloaded"));
+        cache.invalidate();
+        assert!(cache.changed(uid, live_id!(text), "Downloaded"));
+    }
+}
@@ -0,0 +1,470 @@
+use makepad_widgets::*;
+
+use super::VoiceStudio;
+
+live_design! {
+    use link::theme::*;
+    use link::shaders::*;
+    use link::widgets::*;
+    use crate::theme::*;
+    use crate::dialog::design::*;
+
+    VoiceActionButton = <Button> {
+        width: Fit, height: 32
+        padding: {left: 14, right: 14}
+        margin: {right: 8}
+        animator: {
+            hover = {
+                default: off,
+                off = { from: {all: Forward {duration: 0.1}} apply: { draw_bg: {hover: 0.0} } }
+                on  = { from: {all: Forward {duration: 0.1}} apply: { draw_bg: {hover: 1.0} } }
+            }
+            pressed = {
+                default: off,
+                off = { from: {all: Forward {duration: 0.07}} apply: { draw_bg: {pressed: 0.0} } }
+                on  = { from: {all: Forward {duration: 0.07}} apply: { draw_bg: {pressed: 1.0} } }
+            }
+        }
+        draw_bg: {
+            instance hover: 0.0
+            instance pressed: 0.0
+            instance danger: 0.0   // 0=primary blue, 1=danger red
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                sdf.box(0.0, 0.0, self.rect_size.x, self.rect_size.y, 5.0);
+                let primary = mix(#3b82f6, #2563fa, self.hover);
+                let danger  = mix(#b91c1c, #991b1b, self.hover); // no e after digit
+                let color = mix(primary, danger, self.danger);
+                sdf.fill(mix(color, color * 0.9, self.pressed));
+                return sdf.result;
+            }
+        }
+        draw_text: {
+            fn get_color(self) -> vec4 { return #ffffff; }
+            text_style: <FONT_MEDIUM>{ font_size: 11.0 }
+        }
+    }
+
+    // Quality / language / denoise choice; `selected` highlights the active one
+    VoiceOptionButton = <Button> {
+        width: Fit, height: 28
+        padding: {left: 10, right: 10, top: 4, bottom: 4}
+        margin: {right: 6}
+        animator: {
+            hover = {
+                default: off,
+                off = { from: {all: Forward {duration: 0.15}} apply: { draw_bg: {hover: 0.0} } }
+                on  = { from: {all: Forward {duration: 0.15}} apply: { draw_bg: {hover: 1.0} } }
+            }
+        }
+        draw_bg: {
+            instance hover: 0.0
+            instance selected: 0.0
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                sdf.box(0.0, 0.0, self.rect_size.x, self.rect_size.y, 4.0);
+                let normal = mix(#f3f4f6, #e5e7eb, self.hover);
+                sdf.fill(mix(normal, #dbeafe, self.selected));
+                return sdf.result;
+            }
+        }
+        draw_text: {
+            fn get_color(self) -> vec4 { return #374151; }
+            text_style: <FONT_MEDIUM>{ font_size: 11.0 }
+        }
+    }
+
+    VoiceInputLabel = <Label> {
+        width: Fill, height: Fit
+        margin: {bottom: 4, top: 12}
+        draw_text: {
+            fn get_color(self) -> vec4 {
+                return #6b7280;
+            }
+            text_style: <FONT_SEMIBOLD>{ font_size: 10.0 }
+        }
+    }
+
+    VoicePanelInput = <TextInput> {
+        width: Fill, height: 36
+        margin: {bottom: 4}
+        cursor: Text
+        draw_bg: {
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                sdf.box(0.0, 0.0, self.rect_size.x, self.rect_size.y, 5.0);
+                sdf.fill(#f1f5f9);
+                return sdf.result;
+            }
+        }
+        draw_text: {
+            fn get_color(self) -> vec4 { return #374151; }
+            color: #374151
+            color_empty: #9ca3af
+            text_style: { font_size: 12.0 }
+        }
+        draw_cursor: {
+            uniform border_radius: 0.5
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                sdf.box(0.0, 0.0, self.rect_size.x, self.rect_size.y, self.border_radius);
+                sdf.fill(mix(#00000000, #1f2937, (1.0 - self.blink) * self.focus));
+                return sdf.result;
+            }
+        }
+    }
+
+    VoicePanelStatus = <Label> {
+        width: Fill, height: Fit
+        margin: {top: 6}
+        draw_text: {
+            fn get_color(self) -> vec4 {
+                return #6b7280;
+            }
+            text_style: { font_size: 11.0 }
+            wrap: Word
+        }
+    }
+
+    VoiceSectionTitle = <Label> {
+        width: Fill
+        margin: {bottom: 12}
+        draw_text: {
+            fn get_color(self) -> vec4 {
+                return #6b7280;
+            }
+            text_style: <FONT_SEMIBOLD>{ font_size: 10.5 }
+        }
+    }
+
+    VoiceDivider = <View> {
+        width: Fill, height: 1
+        show_bg: true
+        draw_bg: {
+            fn pixel(self) -> vec4 { return #f1f5f9; }
+        }
+    }
+
+    VoiceListItem = <View> {
+        width: Fill, height: 40
+        padding: {left: 12, right: 12, top: 8, bottom: 8}
+        margin: {left: 4, right: 4}
+        cursor: Hand
+        event_order: Down
+        flow: Right
+        align: {y: 0.5}
+        show_bg: true
+        draw_bg: {
+            instance selected: 0.0
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                sdf.box(1.0, 1.0, self.rect_size.x - 2.0, self.rect_size.y - 2.0, 6.0);
+                let normal   = #ffffff;
+                let sel_col  = #eaecf0;
+                sdf.fill(mix(normal, sel_col, self.selected));
+                return sdf.result;
+            }
+        }
+        voice_status_dot = <View> {
+            width: 8, height: 8
+            margin: {right: 8}
+            draw_bg: {
+                instance ready: 0.0
+                fn pixel(self) -> vec4 {
+                    let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                    sdf.circle(4.0, 4.0, 4.0);
+                    sdf.fill(mix(#d1d5db, #22c55a, self.ready));
+                    return sdf.result;
+                }
+            }
+        }
+        voice_item_name = <Label> {
+            width: Fill
+            draw_text: {
+                fn get_color(self) -> vec4 { return #1f2937; }
+                text_style: <FONT_REGULAR>{ font_size: 11.5 }
+                wrap: Ellipsis
+            }
+        }
+        // Training progress for queued jobs; empty for trained voices
+        voice_item_status = <Label> {
+            width: Fit
+            draw_text: {
+                fn get_color(self) -> vec4 { return #6b7280; }
+                text_style: <FONT_REGULAR>{ font_size: 10.0 }
+            }
+        }
+        voice_preview_btn = <VoiceActionButton> {
+            text: "▶"
+            visible: false
+            height: 24
+            padding: {left: 8, right: 8}
+            margin: {left: 6, right: 0}
+        }
+    }
+
+    // Shown in the voice list until the server reports any voices
+    VoiceEmptyItem = <View> {
+        width: Fill, height: Fit
+        padding: {left: 12, right: 12, top: 20, bottom: 20}
+        align: {x: 0.5}
+        <Label> {
+            text: "No voices yet.\nClick + New to train one."
+            draw_text: {
+                fn get_color(self) -> vec4 { return #9ca3af; }
+                text_style: { font_size: 11.0 }
+                wrap: Word
+            }
+        }
+    }
+
+    // One clip in the voice training dataset
+    VoiceSampleItem = <View> {
+        width: Fill, height: Fit
+        padding: {top: 4, bottom: 4}
+        flow: Down
+        <View> {
+            width: Fill, height: 24
+            flow: Right
+            align: {y: 0.5}
+            spacing: 8
+            sample_name = <Label> {
+                width: Fill
+                draw_text: {
+                    fn get_color(self) -> vec4 { return #1f2937; }
+                    text_style: <FONT_REGULAR>{ font_size: 11.0 }
+                    wrap: Ellipsis
+                }
+            }
+            sample_duration = <Label> {
+                width: Fit
+                draw_text: {
+                    fn get_color(self) -> vec4 { return #6b7280; }
+                    text_style: <FONT_REGULAR>{ font_size: 10.5 }
+                }
+            }
+            sample_remove_btn = <VoiceActionButton> {
+                text: "Remove"
+                height: 24
+                padding: {left: 8, right: 8}
+                margin: {right: 0}
+                draw_bg: { danger: 1.0 }
+            }
+        }
+        sample_transcript = <VoicePanelInput> {
+            height: 32
+            empty_text: "Transcript of this clip (optional)"
+        }
+    }
+
+    // Voice cloning UI shared by the Voice app and the Model Hub
+    pub VoiceStudio = {{VoiceStudio}} {
+        width: Fill, height: Fill
+        flow: Overlay
+
+        content = <View> {
+            width: Fill, height: Fill
+            flow: Right
+
+            // Left sub-panel: voice list + actions
+            <View> {
+                width: 240, height: Fill
+                flow: Down
+                show_bg: true
+                draw_bg: {
+                    fn pixel(self) -> vec4 { return #ffffff; }
+                }
+
+                // Header + New button
+                <View> {
+                    width: Fill, height: 48
+                    padding: {left: 16, right: 8}
+                    align: {y: 0.5}
+                    flow: Right
+                    voice_list_title = <Label> {
+                        width: Fill
+                        text: "Voices"
+                        draw_text: {
+                            fn get_color(self) -> vec4 {
+                                return #1f2937;
+                            }
+                            text_style: <FONT_SEMIBOLD>{ font_size: 13.0 }
+                        }
+                    }
+                    voice_new_btn = <VoiceActionButton> {
+                        text: "+ New"
+                        padding: {left: 8, right: 8}
+                        height: 28
+                    }
+                }
+
+                <VoiceDivider> {}
+
+                voice_list = <PortalList> {
+                    width: Fill, height: Fill
+                    flow: Down
+                    VoiceListItem = <VoiceListItem> {}
+                    VoiceEmptyItem = <VoiceEmptyItem> {}
+                }
+            }
+
+            // Vertical divider
+            <View> {
+                width: 1, height: Fill
+                show_bg: true
+                draw_bg: {
+                    fn pixel(self) -> vec4 { return #f1f5f9; }
+                }
+            }
+
+            // Right sub-panel: training form + synthesis
+            <ScrollYView> {
+                width: Fill, height: Fill
+                flow: Down
+                padding: {left: 28, right: 28, top: 20, bottom: 32}
+
+                <VoiceSectionTitle> { text: "VOICE TRAINING" }
+
+                <VoiceInputLabel> { text: "VOICE NAME" }
+                voice_name_input = <VoicePanelInput> {
+                    height: 36
+                    empty_text: "My Voice"
+                }
+
+                <VoiceInputLabel> { text: "TRAINING SAMPLES" }
+                <View> {
+                    width: Fill, height: Fit
+                    flow: Right
+                    align: {y: 0.5}
+                    margin: {bottom: 4}
+                    voice_add_samples_btn  = <VoiceActionButton> { text: "Add Samples..." }
+                    voice_dataset_open_btn = <VoiceActionButton> { text: "Open Dataset..." }
+                    voice_dataset_save_btn = <VoiceActionButton> { text: "Save Dataset" margin: {right: 0} }
+                }
+                voice_sample_list = <PortalList> {
+                    width: Fill, height: 180
+                    flow: Down
+                    VoiceSampleItem = <VoiceSampleItem> {}
+                }
+                voice_dataset_summary = <VoicePanelStatus> {
+                    margin: {bottom: 8}
+                    text: "Add 10s–600s of clean speech, ideally several clips."
+                }
+
+                <VoiceInputLabel> { text: "QUALITY" }
+                <View> {
+                    width: Fill, height: Fit
+                    flow: Right
+                    voice_quality_fast     = <VoiceOptionButton> { text: "Fast" }
+                    voice_quality_standard = <VoiceOptionButton> { text: "Standard" }
+                    voice_quality_high     = <VoiceOptionButton> { text: "High" }
+                }
+
+                <VoiceInputLabel> { text: "LANGUAGE" }
+                <View> {
+                    width: Fill, height: Fit
+                    flow: Right
+                    margin: {bottom: 12}
+                    voice_lang_auto   = <VoiceOptionButton> { text: "Auto" }
+                    voice_lang_zh     = <VoiceOptionButton> { text: "ZH" }
+                    voice_lang_en     = <VoiceOptionButton> { text: "EN" }
+                    voice_denoise_btn = <VoiceOptionButton> { text: "✓ Denoise" margin: {left: 16} }
+                }
+
+                <View> {
+                    width: Fill, height: Fit
+                    flow: Right
+                    margin: {bottom: 8}
+                    voice_train_btn        = <VoiceActionButton> { text: "Add to Queue", margin: {right: 8} }
+                    voice_cancel_train_btn = <VoiceActionButton> {
+                        text: "Cancel"
+                        visible: false
+                        draw_bg: { danger: 1.0 }
+                    }
+                }
+
+                voice_train_status = <VoicePanelStatus> {}
+
+                <VoiceDivider> { margin: {top: 20, bottom: 20} }
+
+                <VoiceSectionTitle> { text: "VOICE SYNTHESIS" }
+
+                // Saved presets; clicking one applies it
+                <VoiceInputLabel> { text: "PRESETS" }
+                <View> {
+                    width: Fill, height: Fit
+                    flow: RightWrap
+                    voice_preset_0 = <VoiceOptionButton> { visible: false }
+                    voice_preset_1 = <VoiceOptionButton> { visible: false }
+                    voice_preset_2 = <VoiceOptionButton> { visible: false }
+                    voice_preset_3 = <VoiceOptionButton> { visible: false }
+                    voice_preset_4 = <VoiceOptionButton> { visible: false }
+                    voice_preset_5 = <VoiceOptionButton> { visible: false }
+                    voice_presets_empty = <VoicePanelStatus> {
+                        width: Fit
+                        text: "Save the voice, speed, language and format below as a preset."
+                    }
+                }
+
+                <VoiceInputLabel> { text: "TEXT TO SYNTHESIZE" }
+                voice_synth_text = <VoicePanelInput> {
+                    height: 72
+                    empty_text: "Enter text to synthesize..."
+                }
+
+                <VoiceInputLabel> { text: "SPEED (0.5 – 2.0)" }
+                voice_speed_input = <VoicePanelInput> {
+                    height: 36
+                    empty_text: "1.0"
+                }
+
+                <VoiceInputLabel> { text: "LANGUAGE" }
+                <View> {
+                    width: Fill, height: Fit
+                    flow: Right
+                    voice_synth_lang_auto = <VoiceOptionButton> { text: "Auto" }
+                    voice_synth_lang_zh   = <VoiceOptionButton> { text: "ZH" }
+                    voice_synth_lang_en   = <VoiceOptionButton> { text: "EN" }
+                }
+
+                <VoiceInputLabel> { text: "SAVE AS" }
+                <View> {
+                    width: Fill, height: Fit
+                    flow: Right
+                    voice_format_wav  = <VoiceOptionButton> { text: "WAV" }
+                    voice_format_mp3  = <VoiceOptionButton> { text: "MP3" }
+                    voice_format_m4a  = <VoiceOptionButton> { text: "AAC" }
+                    voice_format_flac = <VoiceOptionButton> { text: "FLAC" }
+                }
+
+                <View> {
+                    width: Fill, height: Fit
+                    flow: Right
+                    align: {y: 0.5}
+                    margin: {top: 10}
+                    voice_preset_name = <VoicePanelInput> {
+                        width: Fill
+                        margin: {right: 8, bottom: 0}
+                        empty_text: "Preset name"
+                    }
+                    voice_preset_save_btn   = <VoiceActionButton> { text: "Save Preset", margin: {right: 8} }
+                    voice_preset_delete_btn = <VoiceActionButton> { text: "Delete", draw_bg: { danger: 1.0 } }
+                }
+
+                <View> {
+                    width: Fill, height: Fit
+                    flow: Right
+                    margin: {top: 10, bottom: 8}
+                    voice_generate_btn = <VoiceActionButton> { text: "Synthesize", margin: {right: 8} }
+                    voice_play_btn     = <VoiceActionButton> { text: "▶  Play", visible: false, margin: {right: 8} }
+                    voice_save_btn     = <VoiceActionButton> { text: "Save…", visible: false }
+                }
+
+                voice_synth_status = <VoicePanelStatus> {}
+            }
+        }
+
+        // Confirms deleting a preset
+        dialog = <MolyDialog> {}
+    }
+}
@@ -0,0 +1,775 @@
+//! # VoiceStudio - Voice cloning and synthesis
+//!
+//! Train custom voices from a dataset of clips, audition them, and synthesize
+//! speech with them. Used by both the Voice app and the Model Hub's Voice
+//! Studio panel; all server calls go through `moly_data::voice_client`.
+//!
+//! Training jobs go to the process's queue in `moly_data::voice_training`,
+//! which the shell starts once. Every Voice Studio shows the same jobs and
+//! refreshes them when the queue changes.
+//!
+//! The synthesis settings can be saved as named [`VoicePreset`]s, applied
+//! with one click and stored in preferences through the [`Store`] in scope.
+//! Deleting one asks first, in the studio's own [`crate::MolyDialog`].
+
+pub mod design;
+
+use makepad_widgets::*;
+use moly_data::voice_client::{self, VoiceInfo};
+use moly_data::voice_dataset::{VoiceDataset, VoiceSample, MAX_DATASET_SECS, MIN_DATASET_SECS};
+use moly_data::voice_presets::{self, OutputFormat, VoicePreset};
+use moly_data::voice_training::{self, preview_sentence, VoiceJobState, VoiceTrainingJob};
+use moly_data::platform::{self, FileDialog};
+use moly_data::{MolyError, Store, StoreAction, StoreEvent};
+use crate::dialog::{DialogRequest, DialogResult, MolyDialogWidgetRefExt};
+use std::path::PathBuf;
+use std::sync::mpsc;
+
+#[derive(Default)]
+enum SynthesisState {
+    #[default]
+    Idle,
+    Generating,
+    Done,
+}
+
+#[derive(Live, LiveHook, Widget)]
+pub struct VoiceStudio {
+    #[deref]
+    view: View,
+
+    #[rust] initialized:        bool,
+    #[rust] voices:             Vec<VoiceInfo>,
+    #[rust] selected_voice_idx: Option<usize>,
+    /// The training queue as of `queue_generation`
+    #[rust] jobs:               Vec<VoiceTrainingJob>,
+    #[rust] queue_generation:   Option<u64>,
+    /// Finished jobs already announced
+    #[rust] outcomes_seen:      u64,
+    /// Samples for the voice being set up in the training form
+    #[rust] dataset:            VoiceDataset,
+    #[rust] synthesis_state:    SynthesisState,
+    #[rust] quality:            String,
+    #[rust] language:           String,
+    #[rust] denoise:            bool,
+    /// Language hint for synthesis, one of [`voice_presets::LANGUAGES`]
+    #[rust] synth_language:     String,
+    #[rust] format:             OutputFormat,
+    #[rust] presets:            Vec<VoicePreset>,
+    /// Name of the preset last applied or saved, highlighted in the list
+    #[rust] applied_preset:     Option<String>,
+    /// Preset waiting for the delete confirmation dialog
+    #[rust] pending_delete:     Option<String>,
+
+    #[rust] voices_rx:    Option<mpsc::Receiver<Result<Vec<VoiceInfo>, String>>>,
+    #[rust] synthesis_rx: Option<mpsc::Receiver<Result<f32, String>>>,
+    /// Saving the last result in the chosen format
+    #[rust] save_rx:      Option<mpsc::Receiver<Result<PathBuf, String>>>,
+    /// (voice name, preview path, play when ready)
+    #[rust] preview_rx:   Option<mpsc::Receiver<(String, Result<PathBuf, String>, bool)>>,
+}
+
+impl Widget for VoiceStudio {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        if !self.initialized {
+            self.initialized = true;
+            self.quality  = "standard".to_string();
+            self.language = "auto".to_string();
+            self.denoise  = true;
+            self.synth_language = "auto".to_string();
+            self.presets = voice_presets::current();
+            self.outcomes_seen = voice_training::outcome().0;
+            self.fetch_voices();
+            self.update_dataset_summary(cx);
+            cx.new_next_frame();
+        }
+
+        // Another profile has its own presets and cached previews
+        if let Event::Actions(actions) = event {
+            if StoreEvent::iter(actions).any(|e| matches!(e, StoreEvent::ProfileChanged { .. })) {
+                self.presets = voice_presets::current();
+                self.applied_preset = None;
+                self.fetch_voices();
+                self.view.redraw(cx);
+            }
+        }
+
+        let actions = cx.capture_actions(|cx| {
+            self.view.handle_event(cx, event, scope);
+        });
+        if !actions.is_empty() {
+            self.handle_actions(cx, &actions, scope);
+        }
+        self.poll_channels(cx);
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        self.update_option_buttons(cx);
+
+        let voice_list = self.view.portal_list(ids!(voice_list));
+        let voice_list_uid = voice_list.widget_uid();
+        let sample_list = self.view.portal_list(ids!(voice_sample_list));
+        let sample_list_uid = sample_list.widget_uid();
+
+        while let Some(widget) = self.view.draw_walk(cx, scope, walk).step() {
+            if widget.widget_uid() == voice_list_uid {
+                self.draw_voice_list(cx, scope, widget);
+            } else if widget.widget_uid() == sample_list_uid {
+                self.draw_sample_list(cx, scope, widget);
+            }
+        }
+        DrawStep::done()
+    }
+}
+
+impl VoiceStudio {
+    // ── Drawing ──
+
+    fn update_option_buttons(&mut self, cx: &mut Cx2d) {
+        let sel = |on: bool| if on { 1.0_f64 } else { 0.0_f64 };
+        for (id, value) in [
+            (ids!(voice_quality_fast), "fast"),
+            (ids!(voice_quality_standard), "standard"),
+            (ids!(voice_quality_high), "high"),
+        ] {
+            let on = sel(self.quality == value);
+            self.view.button(id).apply_over(cx, live! { draw_bg: { selected: (on) } });
+        }
+        for (id, value) in [
+            (ids!(voice_lang_auto), "auto"),
+            (ids!(voice_lang_zh), "zh"),
+            (ids!(voice_lang_en), "en"),
+        ] {
+            let on = sel(self.language == value);
+            self.view.button(id).apply_over(cx, live! { draw_bg: { selected: (on) } });
+        }
+        let denoise = self.view.button(ids!(voice_denoise_btn));
+        denoise.apply_over(cx, live! { draw_bg: { selected: (sel(self.denoise)) } });
+        denoise.set_text(cx, if self.denoise { "✓ Denoise" } else { "Denoise" });
+
+        for (id, value) in [
+            (ids!(voice_synth_lang_auto), "auto"),
+            (ids!(voice_synth_lang_zh), "zh"),
+            (ids!(voice_synth_lang_en), "en"),
+        ] {
+            let on = sel(self.synth_language == value);
+            self.view.button(id).apply_over(cx, live! { draw_bg: { selected: (on) } });
+        }
+        for (id, format) in [
+            (ids!(voice_format_wav), OutputFormat::Wav),
+            (ids!(voice_format_mp3), OutputFormat::Mp3),
+            (ids!(voice_format_m4a), OutputFormat::M4a),
+            (ids!(voice_format_flac), OutputFormat::Flac),
+        ] {
+            let on = sel(self.format == format);
+            self.view.button(id).apply_over(cx, live! { draw_bg: { selected: (on) } });
+        }
+        for (index, id) in [
+            ids!(voice_preset_0), ids!(voice_preset_1), ids!(voice_preset_2),
+            ids!(voice_preset_3), ids!(voice_preset_4), ids!(voice_preset_5),
+        ].into_iter().enumerate() {
+            let button = self.view.button(id);
+            let Some(preset) = self.presets.get(index) else {
+                button.set_visible(cx, false);
+                continue;
+            };
+            let on = sel(self.applied_preset.as_deref() == Some(preset.name.as_str()));
+            button.set_visible(cx, true);
+            button.set_text(cx, &preset.name);
+            button.apply_over(cx, live! { draw_bg: { selected: (on) } });
+        }
+        self.view.label(ids!(voice_presets_empty)).set_visible(cx, self.presets.is_empty());
+
+        let generating = matches!(self.synthesis_state, SynthesisState::Generating);
+        let done = matches!(self.synthesis_state, SynthesisState::Done);
+        self.view.button(ids!(voice_generate_btn)).set_enabled(cx, !generating);
+        self.view.button(ids!(voice_play_btn)).set_visible(cx, done);
+        self.view.button(ids!(voice_save_btn)).set_visible(cx, done);
+    }
+
+    fn draw_voice_list(&mut self, cx: &mut Cx2d, scope: &mut Scope, widget: WidgetRef) {
+        let binding = widget.as_portal_list();
+        let Some(mut list) = binding.borrow_mut() else { return };
+        // Training jobs first, then the server's voices
+        let job_count = self.jobs.len();
+        let total = job_count + self.voices.len();
+        if total == 0 {
+            list.set_item_range(cx, 0, 1);
+            while let Some(item_id) = list.next_visible_item(cx) {
+                if item_id == 0 {
+                    list.item(cx, 0, live_id!(VoiceEmptyItem)).draw_all(cx, scope);
+                }
+            }
+            return;
+        }
+        list.set_item_range(cx, 0, total);
+
+        while let Some(item_id) = list.next_visible_item(cx) {
+            let (name, status, ready, sel) = if let Some(job) = self.jobs.get(item_id) {
+                (job.name.clone(), job.status_label(), false, false)
+            } else if let Some(voice) = self.voices.get(item_id - job_count) {
+                let sel = self.selected_voice_idx == Some(item_id - job_count);
+                (voice.name.clone(), String::new(), voice.is_ready, sel)
+            } else {
+                continue;
+            };
+            let previewing = self.preview_rx.is_some();
+
+            let item = list.item(cx, item_id, live_id!(VoiceListItem));
+            item.label(ids!(voice_item_name)).set_text(cx, &name);
+            item.label(ids!(voice_item_status)).set_text(cx, &status);
+            // Ready voices can be auditioned; a missing sample is generated on first play
+            item.button(ids!(voice_preview_btn)).set_visible(cx, ready);
+            item.button(ids!(voice_preview_btn)).set_enabled(cx, !previewing);
+            item.apply_over(cx, live! { draw_bg: { selected: (if sel { 1.0_f64 } else { 0.0_f64 }) } });
+            item.view(ids!(voice_status_dot)).apply_over(cx, live! {
+                draw_bg: { ready: (if ready { 1.0_f64 } else { 0.0_f64 }) }
+            });
+            item.draw_all(cx, scope);
+        }
+    }
+
+    fn draw_sample_list(&mut self, cx: &mut Cx2d, scope: &mut Scope, widget: WidgetRef) {
+        let binding = widget.as_portal_list();
+        let Some(mut list) = binding.borrow_mut() else { return };
+        let samples = &self.dataset.samples;
+        list.set_item_range(cx, 0, samples.len());
+
+        while let Some(item_id) = list.next_visible_item(cx) {
+            if let Some(sample) = samples.get(item_id) {
+                let duration = sample.duration_secs
+                    .map(|d| format!("{:.1}s", d))
+                    .unwrap_or_else(|| "?".to_string());
+                let item = list.item(cx, item_id, live_id!(VoiceSampleItem));
+                item.label(ids!(sample_name)).set_text(cx, &sample.file_name());
+                item.label(ids!(sample_duration)).set_text(cx, &duration);
+                let input = item.text_input(ids!(sample_transcript));
+                if input.text() != sample.transcript {
+                    input.set_text(cx, &sample.transcript);
+                }
+                item.draw_all(cx, scope);
+            }
+        }
+    }
+
+    // ── Actions ──
+
+    fn handle_actions(&mut self, cx: &mut Cx, actions: &Actions, scope: &mut Scope) {
+        // + New clears the form
+        if self.view.button(ids!(voice_new_btn)).clicked(actions) {
+            self.selected_voice_idx = None;
+            self.view.text_input(ids!(voice_name_input)).set_text(cx, "");
+            self.view.text_input(ids!(voice_name_input)).set_key_focus(cx);
+            self.dataset = VoiceDataset::default();
+            self.update_dataset_summary(cx);
+        }
+
+        // Voice list clicks: job rows come first; a failed job is moved back
+        // into the form so it can be fixed and resubmitted
+        let voice_list = self.view.portal_list(ids!(voice_list));
+        let job_count = self.jobs.len();
+        for (item_id, item) in voice_list.items_with_actions(actions) {
+            if item.button(ids!(voice_preview_btn)).clicked(actions) && item_id >= job_count {
+                if let Some(voice) = self.voices.get(item_id - job_count) {
+                    match &voice.preview {
+                        Some(path) => { let _ = platform::play_audio(path); }
+                        None => {
+                            let name = voice.name.clone();
+                            self.view.label(ids!(voice_synth_status))
+                                .set_text(cx, &format!("Generating preview for {}...", name));
+                            self.generate_preview(name, preview_sentence("auto", ""), true);
+                        }
+                    }
+                }
+                continue;
+            }
+            if let Some(fd) = item.as_view().finger_down(actions) {
+                if fd.tap_count != 1 { continue; }
+                if item_id >= job_count {
+                    self.selected_voice_idx = Some(item_id - job_count);
+                } else if self.jobs[item_id].state == VoiceJobState::Failed {
+                    if let Some(job) = voice_training::dismiss(&self.jobs[item_id].id) {
+                        self.view.text_input(ids!(voice_name_input)).set_text(cx, &job.name);
+                        self.dataset = VoiceDataset { name: job.name.clone(), samples: job.samples };
+                        self.quality = job.quality;
+                        self.language = job.language;
+                        self.denoise = job.denoise;
+                        self.update_dataset_summary(cx);
+                    }
+                }
+                self.view.redraw(cx);
+            }
+        }
+
+        // Training options
+        for (id, value) in [
+            (ids!(voice_quality_fast), "fast"),
+            (ids!(voice_quality_standard), "standard"),
+            (ids!(voice_quality_high), "high"),
+        ] {
+            if self.view.button(id).clicked(actions) {
+                self.quality = value.to_string();
+                self.view.redraw(cx);
+            }
+        }
+        for (id, value) in [
+            (ids!(voice_lang_auto), "auto"),
+            (ids!(voice_lang_zh), "zh"),
+            (ids!(voice_lang_en), "en"),
+        ] {
+            if self.view.button(id).clicked(actions) {
+                self.language = value.to_string();
+                self.view.redraw(cx);
+            }
+        }
+        if self.view.button(ids!(voice_denoise_btn)).clicked(actions) {
+            self.denoise = !self.denoise;
+            self.view.redraw(cx);
+        }
+
+        self.handle_dataset_actions(cx, actions);
+
+        // Train: save the dataset, then queue it
+        if self.view.button(ids!(voice_train_btn)).clicked(actions) {
+            let name = self.view.text_input(ids!(voice_name_input)).text();
+            if name.trim().is_empty() {
+                self.view.label(ids!(voice_train_status)).set_text(cx, "Please enter a voice name.");
+            } else if let Err(e) = self.dataset.validate() {
+                self.view.label(ids!(voice_train_status)).set_text(cx, &e);
+            } else {
+                self.dataset.name = name.trim().to_string();
+                match self.dataset.save() {
+                    Ok(_) => self.enqueue_training(cx, name),
+                    Err(e) => {
+                        self.view.label(ids!(voice_train_status)).set_text(cx, &format!("Could not save dataset: {}", e));
+                    }
+                }
+            }
+        }
+
+        // Cancel the active training job; the next queued job starts
+        if self.view.button(ids!(voice_cancel_train_btn)).clicked(actions) {
+            voice_training::cancel_active();
+        }
+
+        self.handle_preset_actions(cx, actions, scope);
+
+        if self.view.button(ids!(voice_generate_btn)).clicked(actions) {
+            let text = self.view.text_input(ids!(voice_synth_text)).text();
+            if text.trim().is_empty() {
+                self.view.label(ids!(voice_synth_status)).set_text(cx, "Please enter text to synthesize.");
+            } else if let Some(idx) = self.selected_voice_idx {
+                match self.voices.get(idx) {
+                    Some(_) => {
+                        let settings = self.synthesis_settings(String::new());
+                        self.start_synthesis(cx, text, settings);
+                    }
+                    None => {
+                        self.view.label(ids!(voice_synth_status)).set_text(cx, "Selected voice is no longer available.");
+                    }
+                }
+            } else {
+                self.view.label(ids!(voice_synth_status)).set_text(cx, "Please select a voice from the list.");
+            }
+        }
+
+        if self.view.button(ids!(voice_play_btn)).clicked(actions) {
+            let _ = platform::play_audio(voice_client::voice_output_path());
+        }
+        if self.view.button(ids!(voice_save_btn)).clicked(actions) {
+            self.save_output(cx);
+        }
+    }
+
+    /// Synthesis language and format choices, and saving, applying and
+    /// deleting presets
+    fn handle_preset_actions(&mut self, cx: &mut Cx, actions: &Actions, scope: &mut Scope) {
+        for (id, value) in [
+            (ids!(voice_synth_lang_auto), "auto"),
+            (ids!(voice_synth_lang_zh), "zh"),
+            (ids!(voice_synth_lang_en), "en"),
+        ] {
+            if self.view.button(id).clicked(actions) {
+                self.synth_language = value.to_string();
+                self.view.redraw(cx);
+            }
+        }
+        for (id, format) in [
+            (ids!(voice_format_wav), OutputFormat::Wav),
+            (ids!(voice_format_mp3), OutputFormat::Mp3),
+            (ids!(voice_format_m4a), OutputFormat::M4a),
+            (ids!(voice_format_flac), OutputFormat::Flac),
+        ] {
+            if self.view.button(id).clicked(actions) {
+                self.format = format;
+                self.view.redraw(cx);
+            }
+        }
+        for (index, id) in [
+            ids!(voice_preset_0), ids!(voice_preset_1), ids!(voice_preset_2),
+            ids!(voice_preset_3), ids!(voice_preset_4), ids!(voice_preset_5),
+        ].into_iter().enumerate() {
+            if self.view.button(id).clicked(actions) {
+                if let Some(preset) = self.presets.get(index).cloned() {
+                    self.apply_preset(cx, &preset);
+                }
+            }
+        }
+
+        if self.view.button(ids!(voice_preset_save_btn)).clicked(actions) {
+            let name = self.view.text_input(ids!(voice_preset_name)).text();
+            let preset = self.synthesis_settings(name);
+            let status = match voice_presets::save(&mut self.presets, preset) {
+                Ok(()) => {
+                    let name = self.view.text_input(ids!(voice_preset_name)).text().trim().to_string();
+                    self.store_presets(scope);
+                    let status = format!("Saved preset \"{}\"", name);
+                    self.applied_preset = Some(name);
+                    status
+                }
+                Err(e) => e,
+            };
+            self.view.label(ids!(voice_synth_status)).set_text(cx, &status);
+            self.view.redraw(cx);
+        }
+
+        // Ask first; deleted below once confirmed
+        if self.view.button(ids!(voice_preset_delete_btn)).clicked(actions) {
+            let name = self.view.text_input(ids!(voice_preset_name)).text();
+            match voice_presets::find(&self.presets, &name).map(|p| p.name.clone()) {
+                Some(name) => {
+                    self.view.moly_dialog(ids!(dialog)).open(cx,
+                        DialogRequest::confirm(live_id!(delete_voice), "Delete preset?",
+                            format!("The voice preset \"{}\" will be deleted.", name))
+                            .confirm_label("Delete")
+                            .danger());
+                    self.pending_delete = Some(name);
+                }
+                None => {
+                    self.view.label(ids!(voice_synth_status)).set_text(cx, "Enter the name of a saved preset to delete.");
+                    self.view.redraw(cx);
+                }
+            }
+        }
+
+        if let Some((live_id!(delete_voice), result)) = self.view.moly_dialog(ids!(dialog)).closed(actions) {
+            let Some(name) = self.pending_delete.take() else { return };
+            if result != DialogResult::Confirmed { return; }
+            self.presets.retain(|p| p.name != name);
+            self.store_presets(scope);
+            self.view.text_input(ids!(voice_preset_name)).set_text(cx, "");
+            self.applied_preset = None;
+            self.view.label(ids!(voice_synth_status)).set_text(cx, &format!("Deleted preset \"{}\"", name));
+            self.view.redraw(cx);
+        }
+    }
+
+    fn store_presets(&self, scope: &mut Scope) {
+        match scope.data.get_mut::<Store>() {
+            Some(store) => store.handle_action(&StoreAction::SetVoicePresets(self.presets.clone())),
+            None => ::log::warn!("Voice presets not saved: no store in scope"),
+        }
+    }
+
+    /// The synthesis form as a preset named `name`
+    fn synthesis_settings(&self, name: String) -> VoicePreset {
+        VoicePreset {
+            name,
+            voice: self.selected_voice_idx.and_then(|i| self.voices.get(i)).map(|v| v.name.clone()).unwrap_or_default(),
+            speed: self.view.text_input(ids!(voice_speed_input)).text().parse().unwrap_or(1.0),
+            language: self.synth_language.clone(),
+            format: self.format,
+        }
+    }
+
+    fn apply_preset(&mut self, cx: &mut Cx, preset: &VoicePreset) {
+        let status = match self.voices.iter().position(|v| v.name == preset.voice) {
+            Some(idx) => {
+                self.selected_voice_idx = Some(idx);
+                format!("Using preset \"{}\": {}", preset.name, preset.summary())
+            }
+            None => format!("Voice {} isn't available on the server; pick another before synthesizing.", preset.voice),
+        };
+        self.view.text_input(ids!(voice_speed_input)).set_text(cx, &preset.speed.to_string());
+        self.view.text_input(ids!(voice_preset_name)).set_text(cx, &preset.name);
+        self.synth_language = preset.language.clone();
+        self.format = preset.format;
+        self.applied_preset = Some(preset.name.clone());
+        self.view.label(ids!(voice_synth_status)).set_text(cx, &status);
+        self.view.redraw(cx);
+    }
+
+    /// Ask where to save the last result and save it in the chosen format
+    fn save_output(&mut self, cx: &mut Cx) {
+        if self.save_rx.is_some() { return; }
+        let format = self.format;
+        let Some(path) = FileDialog::new()
+            .set_file_name(format!("speech.{}", format.extension()))
+            .add_filter(format.label(), &[format.extension()])
+            .save_file()
+        else { return };
+        let (tx, rx) = mpsc::channel();
+        self.save_rx = Some(rx);
+        self.view.label(ids!(voice_synth_status)).set_text(cx, "Saving...");
+        std::thread::spawn(move || {
+            let result = format.save(&voice_client::voice_output_path(), &path).map(|_| path);
+            let _ = tx.send(result);
+        });
+        cx.new_next_frame();
+    }
+
+    fn handle_dataset_actions(&mut self, cx: &mut Cx, actions: &Actions) {
+        if self.view.button(ids!(voice_add_samples_btn)).clicked(actions) {
+            if let Some(paths) = FileDialog::new()
+                .add_filter("Audio", &["wav", "mp3", "m4a", "flac", "ogg", "aac"])
+                .pick_files()
+            {
+                for path in paths {
+                    self.dataset.samples.push(VoiceSample::new(&path.to_string_lossy()));
+                }
+                self.update_dataset_summary(cx);
+            }
+        }
+
+        if self.view.button(ids!(voice_dataset_open_btn)).clicked(actions) {
+            if let Some(dir) = FileDialog::new().set_directory(VoiceDataset::datasets_dir()).pick_folder() {
+                match VoiceDataset::load(&dir) {
+                    Ok(dataset) => {
+                        self.view.text_input(ids!(voice_name_input)).set_text(cx, &dataset.name);
+                        self.dataset = dataset;
+                        self.update_dataset_summary(cx);
+                    }
+                    Err(e) => {
+                        self.view.label(ids!(voice_dataset_summary)).set_text(cx, &e);
+                    }
+                }
+            }
+        }
+
+        if self.view.button(ids!(voice_dataset_save_btn)).clicked(actions) {
+            let name = self.view.text_input(ids!(voice_name_input)).text();
+            self.dataset.name = name.trim().to_string();
+            match self.dataset.save() {
+                Ok(dir) => {
+                    self.update_dataset_summary(cx);
+                    self.view.label(ids!(voice_dataset_summary))
+                        .set_text(cx, &format!("Saved to {}", dir.display()));
+                }
+                Err(e) => {
+                    self.view.label(ids!(voice_dataset_summary)).set_text(cx, &e);
+                }
+            }
+        }
+
+        // Per-sample transcript edits and removal
+        let sample_list = self.view.portal_list(ids!(voice_sample_list));
+        let mut remove = None;
+        for (item_id, item) in sample_list.items_with_actions(actions) {
+            if item.button(ids!(sample_remove_btn)).clicked(actions) {
+                remove = Some(item_id);
+            }
+            if let Some(text) = item.text_input(ids!(sample_transcript)).changed(actions) {
+                if let Some(sample) = self.dataset.samples.get_mut(item_id) {
+                    sample.transcript = text;
+                }
+            }
+        }
+        if let Some(idx) = remove {
+            if idx < self.dataset.samples.len() {
+                self.dataset.samples.remove(idx);
+                self.update_dataset_summary(cx);
+            }
+        }
+    }
+
+    fn update_dataset_summary(&mut self, cx: &mut Cx) {
+        let d = &self.dataset;
+        let text = if d.samples.is_empty() {
+            format!("Add {:.0}s–{:.0}s of clean speech, ideally several clips.", MIN_DATASET_SECS, MAX_DATASET_SECS)
+        } else {
+            match d.validate() {
+                Ok(()) => d.summary(),
+                Err(e) => format!("{} — {}", d.summary(), e),
+            }
+        };
+        self.view.label(ids!(voice_dataset_summary)).set_text(cx, &text);
+        self.view.redraw(cx);
+    }
+
+    // ── Training queue ──
+
+    fn enqueue_training(&mut self, cx: &mut Cx, name: String) {
+        let dataset = std::mem::take(&mut self.dataset);
+        let first = dataset.samples.first().cloned().unwrap_or_default();
+        let position = voice_training::enqueue(VoiceTrainingJob {
+            name: name.trim().to_string(),
+            audio_path: first.audio_path,
+            transcript: first.transcript,
+            samples: dataset.samples,
+            quality: self.quality.clone(),
+            language: self.language.clone(),
+            denoise: self.denoise,
+            ..Default::default()
+        });
+        self.view.text_input(ids!(voice_name_input)).set_text(cx, "");
+        self.update_dataset_summary(cx);
+        self.sync_queue(cx);
+        if position > 1 {
+            self.view.label(ids!(voice_train_status))
+                .set_text(cx, &format!("Queued “{}” (position {})", name.trim(), position));
+        }
+        cx.new_next_frame();
+    }
+
+    /// Show the queue as it is now, and announce jobs that finished since
+    /// the last look
+    fn sync_queue(&mut self, cx: &mut Cx) {
+        self.queue_generation = Some(voice_training::generation());
+        self.jobs = voice_training::jobs();
+        self.view.widget(ids!(voice_cancel_train_btn)).set_visible(cx, voice_training::is_training());
+        self.view.label(ids!(voice_train_status)).set_text(cx, &voice_training::status_text());
+
+        let (finished, outcome) = voice_training::outcome();
+        if finished != self.outcomes_seen {
+            self.outcomes_seen = finished;
+            let text = match outcome {
+                Some(Ok(name)) => {
+                    self.fetch_voices();
+                    format!("Training complete: {}", name)
+                }
+                Some(Err(e)) => format!("Training failed: {}", MolyError::from_message(&e).user_message()),
+                None => String::new(),
+            };
+            self.view.label(ids!(voice_train_status)).set_text(cx, &text);
+        }
+        self.view.redraw(cx);
+    }
+
+    // ── Background requests ──
+
+    fn fetch_voices(&mut self) {
+        let (tx, rx) = mpsc::channel();
+        self.voices_rx = Some(rx);
+        std::thread::spawn(move || { let _ = tx.send(voice_client::list_voices()); });
+    }
+
+    /// Synthesize the audition sentence for a voice and cache it on disk.
+    fn generate_preview(&mut self, voice_name: String, sentence: &'static str, play: bool) {
+        if self.preview_rx.is_some() { return; }
+        let (tx, rx) = mpsc::channel();
+        self.preview_rx = Some(rx);
+        std::thread::spawn(move || {
+            let result = voice_client::generate_preview(&voice_name, sentence);
+            let _ = tx.send((voice_name, result, play));
+        });
+    }
+
+    fn start_synthesis(&mut self, cx: &mut Cx, text: String, settings: VoicePreset) {
+        let (tx, rx) = mpsc::channel();
+        self.synthesis_rx = Some(rx);
+        self.synthesis_state = SynthesisState::Generating;
+        self.view.label(ids!(voice_synth_status)).set_text(cx, "Generating...");
+
+        std::thread::spawn(move || {
+            let t0 = std::time::Instant::now();
+            let result = voice_client::synthesize_preset(&settings, &text).and_then(|wav| {
+                std::fs::write(voice_client::voice_output_path(), &wav).map_err(|e| format!("Failed to write WAV: {}", e))?;
+                Ok(t0.elapsed().as_secs_f32())
+            });
+            let _ = tx.send(result);
+        });
+        cx.new_next_frame();
+        self.view.redraw(cx);
+    }
+
+    fn poll_channels(&mut self, cx: &mut Cx) {
+        let mut need_next_frame = false;
+
+        if let Some(rx) = &self.voices_rx {
+            match rx.try_recv() {
+                Ok(result) => {
+                    match result {
+                        Ok(voices) => { self.voices = voices; }
+                        Err(e) => { ::log::warn!("Voice list fetch failed: {}", e); }
+                    }
+                    self.voices_rx = None;
+                    self.view.redraw(cx);
+                }
+                Err(mpsc::TryRecvError::Empty) => { need_next_frame = true; }
+                Err(mpsc::TryRecvError::Disconnected) => { self.voices_rx = None; }
+            }
+        }
+
+        // The shared training queue moved on
+        if self.queue_generation != Some(voice_training::generation()) {
+            self.sync_queue(cx);
+        }
+
+        // Preview samples
+        if let Some(rx) = &self.preview_rx {
+            match rx.try_recv() {
+                Ok((name, result, play)) => {
+                    self.preview_rx = None;
+                    match result {
+                        Ok(path) => {
+                            if play {
+                                let _ = platform::play_audio(&path);
+                                self.view.label(ids!(voice_synth_status)).set_text(cx, "");
+                            }
+                            if let Some(v) = self.voices.iter_mut().find(|v| v.name == name) {
+                                v.preview = Some(path);
+                            }
+                        }
+                        Err(e) => {
+                            ::log::warn!("Preview for voice {} failed: {}", name, e);
+                            self.view.label(ids!(voice_synth_status))
+                                .set_text(cx, &format!("Preview failed: {}", MolyError::from_message(&e).user_message()));
+                        }
+                    }
+                    self.view.redraw(cx);
+                }
+                Err(mpsc::TryRecvError::Empty) => { need_next_frame = true; }
+                Err(mpsc::TryRecvError::Disconnected) => { self.preview_rx = None; }
+            }
+        }
+
+        if let Some(rx) = &self.synthesis_rx {
+            match rx.try_recv() {
+                Ok(result) => {
+                    let msg = match result {
+                        Ok(elapsed) => {
+                            self.synthesis_state = SynthesisState::Done;
+                            format!("Ready — generated in {:.1}s", elapsed)
+                        }
+                        Err(e) => {
+                            self.synthesis_state = SynthesisState::Idle;
+                            format!("Synthesis failed: {}", MolyError::from_message(&e).user_message())
+                        }
+                    };
+                    self.synthesis_rx = None;
+                    self.view.label(ids!(voice_synth_status)).set_text(cx, &msg);
+                    self.view.redraw(cx);
+                }
+                Err(mpsc::TryRecvError::Empty) => { need_next_frame = true; }
+                Err(mpsc::TryRecvError::Disconnected) => { self.synthesis_rx = None; }
+            }
+        }
+
+        if let Some(rx) = &self.save_rx {
+            match rx.try_recv() {
+                Ok(result) => {
+                    let msg = match result {
+                        Ok(path) => format!("Saved to {}", path.display()),
+                        Err(e) => format!("Saving failed: {}", e),
+                    };
+                    self.save_rx = None;
+                    self.view.label(ids!(voice_synth_status)).set_text(cx, &msg);
+                }
+                Err(mpsc::TryRecvError::Empty) => { need_next_frame = true; }
+                Err(mpsc::TryRecvError::Disconnected) => { self.save_rx = None; }
+            }
+        }
+
+        if need_next_frame { cx.new_next_frame(); }
+    }
+}